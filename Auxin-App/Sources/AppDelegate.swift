@@ -16,6 +16,10 @@ class AppDelegate: NSObject, NSApplicationDelegate {
         return true
     }
 
+    func application(_ application: NSApplication, open urls: [URL]) {
+        urls.forEach(URLSchemeHandler.handle)
+    }
+
 
     @objc func showAbout() {
         let alert = NSAlert()
@@ -10,6 +10,8 @@ struct AuxinApp: App {
                 .frame(minWidth: 800, minHeight: 600)
         }
         .windowStyle(.automatic)
+        // auxin:// URLs go to AppDelegate instead of opening a new window
+        .handlesExternalEvents(matching: [])
         .commands {
             CommandGroup(replacing: .appInfo) {
                 Button("About Auxin") {
@@ -0,0 +1,61 @@
+import AppKit
+
+/// Handles `auxin://` URLs opened by Shortcuts, Raycast or Alfred
+///
+/// Parsing and the operations themselves live in `auxin handle-url`; the app
+/// only forwards the URL. The CLI reports the result as a notification, so
+/// no window is opened.
+///
+/// Any web page can open an `auxin://` link, so every URL but `status` and
+/// commit links (`repo/...`) is confirmed here first; the CLI refuses them
+/// without `--yes`.
+enum URLSchemeHandler {
+    static let scheme = "auxin"
+
+    private static let cliPath = "/usr/local/bin/auxin"
+    private static let queue = DispatchQueue(label: "com.auxin.url-scheme", qos: .userInitiated)
+
+    static func handle(_ url: URL) {
+        guard url.scheme == scheme else { return }
+
+        var arguments = ["handle-url", url.absoluteString, "--notify"]
+        if url.host != "status" && url.host != "repo" {
+            guard confirm(url) else { return }
+            arguments.append("--yes")
+        }
+
+        queue.async {
+            let task = Process()
+            task.executableURL = URL(fileURLWithPath: cliPath)
+            task.arguments = arguments
+            task.standardInput = FileHandle.nullDevice
+            task.standardOutput = FileHandle.nullDevice
+            task.standardError = FileHandle.nullDevice
+
+            do {
+                try task.run()
+                task.waitUntilExit()
+
+                // The CLI has already notified the user; refresh in case a commit or lock changed
+                DispatchQueue.main.async {
+                    NotificationCenter.default.post(name: .refreshProjects, object: nil)
+                }
+            } catch {
+                print("Failed to handle \(url.absoluteString): \(error.localizedDescription)")
+            }
+        }
+    }
+
+    /// Ask before a link changes a project; URLs arrive on the main thread
+    private static func confirm(_ url: URL) -> Bool {
+        let alert = NSAlert()
+        alert.messageText = "Run this auxin:// link?"
+        alert.informativeText = "A link asked Auxin to change a project:\n\n\(url.absoluteString)"
+        alert.alertStyle = .warning
+        alert.addButton(withTitle: "Run")
+        alert.addButton(withTitle: "Cancel")
+
+        NSApp.activate(ignoringOtherApps: true)
+        return alert.runModal() == .alertFirstButtonReturn
+    }
+}
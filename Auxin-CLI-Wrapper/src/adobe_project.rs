@@ -0,0 +1,268 @@
+//! Premiere Pro and After Effects projects
+//!
+//! Both apps keep a project in a single file next to its media: `.prproj`
+//! for Premiere Pro (gzipped XML) and `.aep` for After Effects (a RIFX
+//! binary). Media caches, previews and auto-saves are written alongside
+//! and are left out by the ignore templates in
+//! [`ignore_template`](crate::ignore_template).
+
+use crate::vlog;
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use flate2::read::GzDecoder;
+use regex::Regex;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Premiere Pro's time base: ticks per second
+const PREMIERE_TICKS_PER_SECOND: f64 = 254_016_000_000.0;
+
+/// A Premiere Pro `.prproj` file
+#[derive(Debug, Clone)]
+pub struct PremiereProject {
+    /// Canonical absolute path to the .prproj file
+    pub file_path: PathBuf,
+    /// Folder the project and its media live in
+    pub project_dir: PathBuf,
+}
+
+/// What's read from a `.prproj`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PremiereProjectInfo {
+    pub sequence_count: usize,
+    /// Width and height of the first sequence
+    pub resolution: Option<(u32, u32)>,
+    /// Frames per second of the first sequence
+    pub frame_rate: Option<f64>,
+}
+
+impl PremiereProject {
+    /// Check that `path` is an existing `.prproj` file
+    pub fn detect(path: impl AsRef<Path>) -> Result<Self> {
+        let (file_path, project_dir) = detect_file(path.as_ref(), "prproj", "Premiere Pro")?;
+        Ok(Self {
+            file_path,
+            project_dir,
+        })
+    }
+
+    /// File name without the extension
+    pub fn name(&self) -> String {
+        file_stem(&self.file_path)
+    }
+
+    /// Sequences, resolution and frame rate, from the project's XML
+    pub fn read_info(&self) -> Result<PremiereProjectInfo> {
+        let bytes = std::fs::read(&self.file_path)
+            .with_context(|| format!("Failed to read {}", self.file_path.display()))?;
+        // Saved projects are gzipped; older or hand-exported ones may not be
+        let xml = if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut xml = String::new();
+            GzDecoder::new(bytes.as_slice())
+                .read_to_string(&mut xml)
+                .context("Failed to decompress Premiere Pro project")?;
+            xml
+        } else {
+            String::from_utf8(bytes).context("Premiere Pro project is not XML")?
+        };
+        if !xml.contains("<PremiereData") {
+            bail!("Not a Premiere Pro project: {}", self.file_path.display());
+        }
+        Ok(PremiereProjectInfo::parse(&xml))
+    }
+
+    /// Patterns for files Premiere Pro writes next to the project
+    pub fn ignored_patterns() -> Vec<&'static str> {
+        vec![
+            "Adobe Premiere Pro Auto-Save/",
+            "Adobe Premiere Pro Preview Files/",
+            "Adobe Premiere Pro Video Previews/",
+            "Adobe Premiere Pro Audio Previews/",
+            "Media Cache/",
+            "Media Cache Files/",
+            "*.cfa",
+            "*.pek",
+            "*.prlock",
+        ]
+    }
+}
+
+impl PremiereProjectInfo {
+    /// Read the project's XML
+    ///
+    /// Sequences are the `<Sequence>` elements that define an object
+    /// (`ObjectUID`) rather than refer to one. Resolution and frame rate
+    /// come from the first `<VideoSettings>`; `<FrameRate>` there is in
+    /// ticks per frame.
+    pub fn parse(xml: &str) -> Self {
+        let sequences = Regex::new(r"<Sequence\s[^>]*\bObjectUID=").unwrap();
+        let video_settings = Regex::new(r"(?s)<VideoSettings\b.*?</VideoSettings>").unwrap();
+        let frame_rect =
+            Regex::new(r"<FrameRect>\s*-?\d+,\s*-?\d+,\s*(\d+),\s*(\d+)\s*</FrameRect>").unwrap();
+        let frame_rate = Regex::new(r"<FrameRate>\s*(\d+)\s*</FrameRate>").unwrap();
+
+        let mut info = Self {
+            sequence_count: sequences.find_iter(xml).count(),
+            ..Self::default()
+        };
+        for settings in video_settings.find_iter(xml).map(|m| m.as_str()) {
+            if info.resolution.is_none() {
+                info.resolution = frame_rect
+                    .captures(settings)
+                    .and_then(|c| Some((c[1].parse().ok()?, c[2].parse().ok()?)))
+                    .filter(|&(width, height)| width > 0 && height > 0);
+            }
+            if info.frame_rate.is_none() {
+                info.frame_rate = frame_rate
+                    .captures(settings)
+                    .and_then(|c| c[1].parse::<f64>().ok())
+                    .filter(|&ticks| ticks > 0.0)
+                    .map(|ticks| PREMIERE_TICKS_PER_SECOND / ticks);
+            }
+        }
+        vlog!(
+            "Premiere Pro project: {} sequence(s), {:?}, {:?} fps",
+            info.sequence_count,
+            info.resolution,
+            info.frame_rate
+        );
+        info
+    }
+}
+
+/// An After Effects `.aep` file
+#[derive(Debug, Clone)]
+pub struct AfterEffectsProject {
+    /// Canonical absolute path to the .aep file
+    pub file_path: PathBuf,
+    /// Folder the project and its footage live in
+    pub project_dir: PathBuf,
+}
+
+impl AfterEffectsProject {
+    /// Check that `path` is an existing `.aep` file
+    pub fn detect(path: impl AsRef<Path>) -> Result<Self> {
+        let (file_path, project_dir) = detect_file(path.as_ref(), "aep", "After Effects")?;
+        Ok(Self {
+            file_path,
+            project_dir,
+        })
+    }
+
+    /// File name without the extension
+    pub fn name(&self) -> String {
+        file_stem(&self.file_path)
+    }
+
+    /// Number of compositions
+    ///
+    /// The format is undocumented; each composition's settings are in a
+    /// `cdta` chunk, so those are counted.
+    pub fn composition_count(&self) -> Result<usize> {
+        let bytes = std::fs::read(&self.file_path)
+            .with_context(|| format!("Failed to read {}", self.file_path.display()))?;
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFX" || &bytes[8..12] != b"Egg!" {
+            bail!("Not an After Effects project: {}", self.file_path.display());
+        }
+        Ok(bytes.windows(4).filter(|w| w == b"cdta").count())
+    }
+
+    /// Patterns for files After Effects writes next to the project
+    pub fn ignored_patterns() -> Vec<&'static str> {
+        vec![
+            "Adobe After Effects Auto-Save/",
+            "Disk Cache/",
+            "Media Cache/",
+            "Media Cache Files/",
+            "*.aep Logs/",
+            "*.cfa",
+            "*.pek",
+        ]
+    }
+}
+
+/// Canonical path and folder of the existing `extension` file at `path`
+fn detect_file(path: &Path, extension: &str, app: &str) -> Result<(PathBuf, PathBuf)> {
+    vlog!("Detecting {} project at {}", app, path.display());
+    if !path.is_file() {
+        bail!("Not a file: {}", path.display());
+    }
+    let file_path = std::fs::canonicalize(path).context("Failed to canonicalize path")?;
+    let matches = file_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension));
+    if !matches {
+        bail!(
+            "Path is not a {} project (.{}): {}",
+            app,
+            extension,
+            file_path.display()
+        );
+    }
+    let project_dir = file_path
+        .parent()
+        .ok_or_else(|| anyhow!("Cannot determine parent directory"))?
+        .to_path_buf();
+    Ok((file_path, project_dir))
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const PRPROJ_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<PremiereData Version="3">
+  <Sequence ObjectUID="a1" ClassID="6a15d903" Version="11"><Name>Main edit</Name></Sequence>
+  <Sequence ObjectUID="b2" ClassID="6a15d903" Version="11"><Name>Trailer</Name></Sequence>
+  <Sequence ObjectURef="a1"/>
+  <AudioSettings><FrameRate>5292000</FrameRate></AudioSettings>
+  <VideoSettings Version="9">
+    <FrameRate>8475667200</FrameRate>
+    <FrameRect>0,0,3840,2160</FrameRect>
+  </VideoSettings>
+</PremiereData>"#;
+
+    #[test]
+    fn test_premiere_read_info() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Film.prproj");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(PRPROJ_XML.as_bytes()).unwrap();
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let project = PremiereProject::detect(&path).unwrap();
+        assert_eq!(project.name(), "Film");
+        let info = project.read_info().unwrap();
+        assert_eq!(info.sequence_count, 2);
+        assert_eq!(info.resolution, Some((3840, 2160)));
+        assert!((info.frame_rate.unwrap() - 29.97).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_after_effects_composition_count() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Titles.aep");
+        let mut bytes = b"RIFX\0\0\0\x40Egg!".to_vec();
+        bytes.extend_from_slice(b"LIST\0\0\0\x08Itemcdta\0\0\0\0LIST\0\0\0\x08Itemcdta");
+        std::fs::write(&path, bytes).unwrap();
+
+        let project = AfterEffectsProject::detect(&path).unwrap();
+        assert_eq!(project.composition_count().unwrap(), 2);
+
+        std::fs::write(&path, b"not a project").unwrap();
+        assert!(project.composition_count().is_err());
+        assert!(AfterEffectsProject::detect(dir.path().join("missing.aep")).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Command aliases and macros from `[aliases]` in config.toml
+//!
+//! ```toml
+//! [aliases]
+//! save = "commit -m 'WIP' --tags wip"
+//! ship = ["commit -m 'Final mix' --tags final", "push"]
+//! ```
+//!
+//! Aliases are expanded before the command line is parsed, so `auxin save`
+//! runs `auxin commit -m 'WIP' --tags wip`. Arguments after the alias are
+//! appended (`auxin save --bpm 120`). An alias may name another alias.
+//! Built-in commands always win over an alias with the same name.
+//!
+//! A list is a macro: each command runs in turn as its own `auxin`
+//! process, stopping at the first one that fails. Arguments after a macro
+//! are appended to its last command.
+
+use anyhow::{bail, Context, Result};
+use auxin_config::Alias;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Aliases naming aliases deeper than this are treated as a loop
+const MAX_DEPTH: usize = 16;
+
+/// The command line after alias expansion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expansion {
+    /// A single command line, including the program name
+    Command(Vec<String>),
+    /// Argument lists (without the program name) to run one after another
+    Macro(Vec<Vec<String>>),
+}
+
+/// Expand an alias in `args` (as from `std::env::args`)
+///
+/// The first argument that isn't a flag is the command name; it is replaced
+/// when it names an alias and `is_builtin` says it isn't a real command.
+pub fn expand(
+    args: Vec<String>,
+    aliases: &BTreeMap<String, Alias>,
+    is_builtin: impl Fn(&str) -> bool,
+) -> Result<Expansion> {
+    let Some(position) = args.iter().skip(1).position(|a| !a.starts_with('-')) else {
+        return Ok(Expansion::Command(args));
+    };
+    let position = position + 1;
+
+    let mut name = args[position].clone();
+    let mut seen = Vec::new();
+    let mut expanded: Vec<String> = Vec::new();
+    loop {
+        if is_builtin(&name) {
+            break;
+        }
+        let Some(alias) = aliases.get(&name) else {
+            if seen.is_empty() {
+                return Ok(Expansion::Command(args));
+            }
+            break;
+        };
+        if seen.contains(&name) || seen.len() >= MAX_DEPTH {
+            bail!("Alias '{}' refers to itself", seen[0]);
+        }
+        seen.push(name.clone());
+
+        match alias {
+            Alias::Command(line) => {
+                let mut words =
+                    split_args(line).with_context(|| format!("Invalid alias '{}'", name))?;
+                if words.is_empty() {
+                    bail!("Alias '{}' is empty", name);
+                }
+                // `expanded` holds arguments from outer aliases, which follow
+                words.append(&mut expanded);
+                name = words.remove(0);
+                expanded = words;
+            }
+            Alias::Macro(lines) => {
+                if lines.is_empty() {
+                    bail!("Alias '{}' is empty", name);
+                }
+                let mut steps = lines
+                    .iter()
+                    .map(|line| split_args(line))
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| format!("Invalid alias '{}'", name))?;
+                let global = &args[1..position];
+                for step in &mut steps {
+                    step.splice(0..0, global.iter().cloned());
+                }
+                if let Some(last) = steps.last_mut() {
+                    last.extend(expanded);
+                    last.extend(args[position + 1..].iter().cloned());
+                }
+                return Ok(Expansion::Macro(steps));
+            }
+        }
+    }
+
+    let mut result = args[..position].to_vec();
+    result.push(name);
+    result.extend(expanded);
+    result.extend(args[position + 1..].iter().cloned());
+    Ok(Expansion::Command(result))
+}
+
+/// Run a macro's commands in order with `auxin_exe`, stopping at the
+/// first failure; returns the exit code to finish with
+pub fn run_macro(auxin_exe: &Path, steps: &[Vec<String>]) -> Result<i32> {
+    for step in steps {
+        crate::progress::info(&format!("auxin {}", step.join(" ")));
+        let status = Command::new(auxin_exe)
+            .args(step)
+            .status()
+            .with_context(|| format!("Failed to run auxin {}", step.join(" ")))?;
+        if !status.success() {
+            return Ok(status.code().unwrap_or(1));
+        }
+    }
+    Ok(0)
+}
+
+/// Split a command line into words, honouring quotes and backslashes
+pub fn split_args(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => match chars.next() {
+                Some(escaped) => word.push(escaped),
+                None => bail!("Trailing backslash in '{}'", line),
+            }
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        bail!("Unclosed quote in '{}'", line);
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        split_args(line).unwrap()
+    }
+
+    fn aliases() -> BTreeMap<String, Alias> {
+        BTreeMap::from([
+            (
+                "save".to_string(),
+                Alias::Command("commit -m 'WIP' --tags wip".to_string()),
+            ),
+            (
+                "s".to_string(),
+                Alias::Command("save --bpm 120".to_string()),
+            ),
+            (
+                "ship".to_string(),
+                Alias::Macro(vec!["save".to_string(), "push origin".to_string()]),
+            ),
+            ("log".to_string(), Alias::Command("status".to_string())),
+            ("loop".to_string(), Alias::Command("loop -v".to_string())),
+        ])
+    }
+
+    fn builtin(name: &str) -> bool {
+        ["commit", "push", "log", "status"].contains(&name)
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            args(r#"commit -m 'WIP mix' --tags "a b" x\ y"#),
+            vec!["commit", "-m", "WIP mix", "--tags", "a b", "x y"]
+        );
+        assert_eq!(args(r#"-m "" "say \"hi\"""#), vec!["-m", "", "say \"hi\""]);
+        assert!(split_args("commit -m 'open").is_err());
+    }
+
+    #[test]
+    fn test_expand() {
+        let expand = |line: &str| expand(args(line), &aliases(), builtin).unwrap();
+
+        assert_eq!(
+            expand("auxin -v save --bpm 90"),
+            Expansion::Command(args("auxin -v commit -m WIP --tags wip --bpm 90"))
+        );
+        assert_eq!(
+            expand("auxin s -k Am"),
+            Expansion::Command(args("auxin commit -m WIP --tags wip --bpm 120 -k Am"))
+        );
+        assert_eq!(expand("auxin log"), Expansion::Command(args("auxin log")));
+        assert_eq!(expand("auxin nope"), Expansion::Command(args("auxin nope")));
+        assert_eq!(
+            expand("auxin -v ship --force"),
+            Expansion::Macro(vec![args("-v save"), args("-v push origin --force")])
+        );
+        assert!(super::expand(args("auxin loop"), &aliases(), builtin).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! Anomaly detection for daemon auto-commits
+//!
+//! An auto-commit faithfully versions whatever is on disk, including a
+//! project that was just truncated by a crash, emptied by a sync client or
+//! half-deleted by a stray `rm`. Before each auto-commit the daemon runs
+//! `auxin anomaly check`, which compares the project with a baseline taken
+//! at the last commit (`.auxin/anomaly_baseline.json`) and flags:
+//!
+//! - the project shrinking by `[daemon] anomaly_size_drop_percent` (90%)
+//! - a Logic Pro `ProjectData` file becoming empty or disappearing
+//! - `[daemon] anomaly_deleted_files` (100) or more files deleted
+//!
+//! A flagged change pauses auto-commits for the project
+//! (`.auxin/auto_commit_paused.json`), records a safety snapshot pointing at
+//! the last good commit and notifies the user. Auto-commits stay paused until
+//! `auxin anomaly resume`, which also accepts the current state as the new
+//! baseline.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Exit code of `auxin anomaly check` while auto-commits are paused
+///
+/// Distinct from clap's usage error (2), so an older CLI that doesn't know
+/// the command never pauses the daemon.
+pub const PAUSED_EXIT_CODE: i32 = 3;
+
+/// Logic Pro's main project file, one per alternative
+const PROJECT_DATA: &str = "ProjectData";
+
+/// When a change counts as suspicious
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+    pub size_drop_percent: f64,
+    pub deleted_files: usize,
+}
+
+impl Thresholds {
+    pub fn from_config(daemon: &auxin_config::Daemon) -> Self {
+        Self {
+            size_drop_percent: daemon.anomaly_size_drop_percent,
+            deleted_files: daemon.anomaly_deleted_files,
+        }
+    }
+}
+
+/// Sizes of a project's files, by path relative to the project
+pub type Footprint = BTreeMap<String, u64>;
+
+/// Walk a project, skipping `.oxen` and `.auxin`
+pub fn scan(project: &Path) -> Result<Footprint> {
+    fn walk(root: &Path, dir: &Path, files: &mut Footprint) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if dir == root && (name == ".oxen" || name == ".auxin") {
+                    continue;
+                }
+                walk(root, &path, files)?;
+            } else if file_type.is_file() {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                files.insert(relative.to_string_lossy().replace('\\', "/"), size);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Footprint::new();
+    walk(project, project, &mut files)?;
+    Ok(files)
+}
+
+/// The project as it was at the last commit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_id: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+    #[serde(default)]
+    pub files: Footprint,
+}
+
+impl Baseline {
+    /// Location of a project's baseline
+    pub fn path(project: &Path) -> PathBuf {
+        project.join(".auxin").join("anomaly_baseline.json")
+    }
+
+    /// Read a project's baseline, if one was recorded
+    pub fn load(project: &Path) -> Result<Option<Self>> {
+        read_json(&Self::path(project))
+    }
+
+    /// Record the project's current state after a commit
+    pub fn record(project: &Path, commit_id: Option<&str>) -> Result<Self> {
+        let baseline = Self {
+            commit_id: commit_id.map(str::to_string),
+            recorded_at: Utc::now(),
+            files: scan(project)?,
+        };
+        write_json(&Self::path(project), &baseline)?;
+        Ok(baseline)
+    }
+}
+
+/// A change that looks like data loss
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Anomaly {
+    SizeDrop { before_bytes: u64, after_bytes: u64 },
+    EmptyProjectData { path: String },
+    MassDeletion { count: usize },
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anomaly::SizeDrop {
+                before_bytes,
+                after_bytes,
+            } => write!(
+                f,
+                "Project shrank {:.0}% since the last commit ({} → {})",
+                percent_drop(*before_bytes, *after_bytes),
+                crate::describe::format_size(*before_bytes),
+                crate::describe::format_size(*after_bytes)
+            ),
+            Anomaly::EmptyProjectData { path } => write!(f, "{} is empty or missing", path),
+            Anomaly::MassDeletion { count } => {
+                write!(f, "{} files deleted since the last commit", count)
+            },
+        }
+    }
+}
+
+fn percent_drop(before: u64, after: u64) -> f64 {
+    if before == 0 || after >= before {
+        0.0
+    } else {
+        (before - after) as f64 / before as f64 * 100.0
+    }
+}
+
+/// Compare the project now with its baseline
+pub fn detect(baseline: &Footprint, current: &Footprint, thresholds: &Thresholds) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+
+    let before_bytes: u64 = baseline.values().sum();
+    let after_bytes: u64 = current.values().sum();
+    if before_bytes > 0 && percent_drop(before_bytes, after_bytes) >= thresholds.size_drop_percent
+    {
+        anomalies.push(Anomaly::SizeDrop {
+            before_bytes,
+            after_bytes,
+        });
+    }
+
+    for (path, size) in baseline {
+        let is_project_data = path.rsplit('/').next() == Some(PROJECT_DATA);
+        if is_project_data && *size > 0 && current.get(path).copied().unwrap_or(0) == 0 {
+            anomalies.push(Anomaly::EmptyProjectData { path: path.clone() });
+        }
+    }
+
+    let deleted = baseline.keys().filter(|path| !current.contains_key(*path)).count();
+    if thresholds.deleted_files > 0 && deleted >= thresholds.deleted_files {
+        anomalies.push(Anomaly::MassDeletion { count: deleted });
+    }
+
+    anomalies
+}
+
+/// Check a project against its baseline; nothing is flagged without one
+pub fn check(project: &Path, thresholds: &Thresholds) -> Result<Vec<Anomaly>> {
+    let Some(baseline) = Baseline::load(project)? else {
+        return Ok(Vec::new());
+    };
+    Ok(detect(&baseline.files, &scan(project)?, thresholds))
+}
+
+/// Auto-commits paused after an anomaly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PauseState {
+    pub paused_at: DateTime<Utc>,
+    pub anomalies: Vec<Anomaly>,
+    /// Commit the baseline was taken at: the last known good state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_good_commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+}
+
+impl PauseState {
+    /// Location of a project's pause marker
+    pub fn path(project: &Path) -> PathBuf {
+        project.join(".auxin").join("auto_commit_paused.json")
+    }
+
+    /// The project's pause, if auto-commits are paused
+    pub fn load(project: &Path) -> Result<Option<Self>> {
+        read_json(&Self::path(project))
+    }
+
+    pub fn save(&self, project: &Path) -> Result<()> {
+        write_json(&Self::path(project), self)
+    }
+
+    /// Lift the pause; returns whether auto-commits were paused
+    pub fn clear(project: &Path) -> Result<bool> {
+        let path = Self::path(project);
+        if !path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        Ok(true)
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .map(Some)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const THRESHOLDS: Thresholds = Thresholds {
+        size_drop_percent: 90.0,
+        deleted_files: 3,
+    };
+
+    #[test]
+    fn test_detect() {
+        let baseline: Footprint = [
+            ("Alternatives/000/ProjectData".to_string(), 4_000_000),
+            ("Media/Audio Files/Kick.wav".to_string(), 5_000_000),
+            ("Media/Audio Files/Snare.wav".to_string(), 1_000_000),
+            ("Media/Audio Files/Vox.wav".to_string(), 10_000),
+        ]
+        .into();
+
+        // Ordinary edits pass
+        let mut edited = baseline.clone();
+        edited.insert("Alternatives/000/ProjectData".to_string(), 4_100_000);
+        edited.remove("Media/Audio Files/Vox.wav");
+        assert!(detect(&baseline, &edited, &THRESHOLDS).is_empty());
+
+        let mut wiped = baseline.clone();
+        wiped.insert("Alternatives/000/ProjectData".to_string(), 0);
+        wiped.retain(|path, _| !path.ends_with(".wav"));
+        assert_eq!(
+            detect(&baseline, &wiped, &THRESHOLDS),
+            vec![
+                Anomaly::SizeDrop {
+                    before_bytes: 10_010_000,
+                    after_bytes: 0
+                },
+                Anomaly::EmptyProjectData {
+                    path: "Alternatives/000/ProjectData".to_string()
+                },
+                Anomaly::MassDeletion { count: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_and_pause() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        fs::create_dir_all(project.join("Alternatives/000")).unwrap();
+        fs::write(project.join("Alternatives/000/ProjectData"), vec![1u8; 2048]).unwrap();
+
+        // No baseline yet: nothing to compare with
+        assert!(check(project, &THRESHOLDS).unwrap().is_empty());
+
+        let baseline = Baseline::record(project, Some("abc123")).unwrap();
+        assert_eq!(baseline.files.len(), 1);
+        fs::write(project.join("Alternatives/000/ProjectData"), b"").unwrap();
+        let anomalies = check(project, &THRESHOLDS).unwrap();
+        assert_eq!(anomalies.len(), 2);
+
+        let pause = PauseState {
+            paused_at: Utc::now(),
+            anomalies,
+            last_good_commit: baseline.commit_id,
+            snapshot_id: None,
+        };
+        pause.save(project).unwrap();
+        assert_eq!(PauseState::load(project).unwrap(), Some(pause));
+        assert!(PauseState::clear(project).unwrap());
+        assert!(PauseState::load(project).unwrap().is_none());
+    }
+}
@@ -0,0 +1,153 @@
+//! Read-only mode for delivered projects: `auxin archive`
+//!
+//! Marking a project archived writes `.auxin/archive.json`:
+//!
+//! ```json
+//! {
+//!   "archived_at": "2026-10-17T09:30:00Z",
+//!   "archived_by": "me@studio-mac",
+//!   "reason": "Delivered to the label"
+//! }
+//! ```
+//!
+//! While the marker is there, commands that would change the project, its
+//! history or its lock (commit, restore, pull, lock acquire, ...) refuse to
+//! run unless given `--allow-archived`, and the daemon stops watching the
+//! project. Commands that only read (status, log, show, diff, export) work
+//! as before. The marker belongs to this copy of the project; it isn't
+//! committed, so archiving never adds history of its own.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Why and when a project was archived
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveMark {
+    pub archived_at: DateTime<Utc>,
+    pub archived_by: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ArchiveMark {
+    /// Location of the marker in a project
+    pub fn path(repo: &Path) -> PathBuf {
+        repo.join(".auxin").join("archive.json")
+    }
+
+    /// The project's marker, if it's archived
+    pub fn load(repo: &Path) -> Result<Option<Self>> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mark = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(mark))
+    }
+
+    fn save(&self, repo: &Path) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// `archived 2026-10-17 by me@studio-mac (Delivered to the label)`
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "archived {} by {}",
+            self.archived_at.format("%Y-%m-%d"),
+            self.archived_by
+        );
+        if let Some(reason) = &self.reason {
+            summary.push_str(&format!(" ({})", reason));
+        }
+        summary
+    }
+}
+
+/// Put the project at `repo` in read-only mode
+pub fn mark(repo: &Path, reason: Option<String>) -> Result<ArchiveMark> {
+    if let Some(existing) = ArchiveMark::load(repo)? {
+        bail!("The project is already {}", existing.summary());
+    }
+    let mark = ArchiveMark {
+        archived_at: Utc::now(),
+        archived_by: crate::lock_integration::get_user_identifier(),
+        reason,
+    };
+    mark.save(repo)?;
+    Ok(mark)
+}
+
+/// Take the project out of read-only mode; `false` if it wasn't archived
+pub fn unmark(repo: &Path) -> Result<bool> {
+    let path = ArchiveMark::path(repo);
+    if !path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(true)
+}
+
+/// Refuse `command` when the project containing `dir` is archived
+pub fn ensure_writable(dir: &Path, command: &str) -> Result<()> {
+    let Some(repo) = crate::describe::find_repo_root(dir) else {
+        return Ok(());
+    };
+    let Some(mark) = ArchiveMark::load(&repo)? else {
+        return Ok(());
+    };
+    bail!(
+        "This project is read-only: {}.\n\
+         'auxin {}' would change it. Run it with --allow-archived to go ahead anyway, \
+         or 'auxin archive unmark' to make the project writable again",
+        mark.summary(),
+        command
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_mark_and_unmark() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path();
+        fs::create_dir(repo.join(".oxen")).unwrap();
+        let nested = repo.join("Audio Files");
+        fs::create_dir(&nested).unwrap();
+
+        assert!(ArchiveMark::load(repo).unwrap().is_none());
+        assert!(ensure_writable(&nested, "commit").is_ok());
+
+        let mark = mark(repo, Some("Delivered".to_string())).unwrap();
+        assert_eq!(ArchiveMark::load(repo).unwrap(), Some(mark.clone()));
+        assert!(mark.summary().ends_with("(Delivered)"));
+        assert!(super::mark(repo, None).is_err());
+
+        let refused = ensure_writable(&nested, "commit").unwrap_err().to_string();
+        assert!(refused.contains("'auxin commit' would change it"));
+        assert!(refused.contains("--allow-archived"));
+
+        assert!(unmark(repo).unwrap());
+        assert!(!unmark(repo).unwrap());
+        assert!(ensure_writable(&nested, "commit").is_ok());
+    }
+
+    #[test]
+    fn test_outside_a_repository_is_writable() {
+        let dir = TempDir::new().unwrap();
+        assert!(ensure_writable(dir.path(), "commit").is_ok());
+    }
+}
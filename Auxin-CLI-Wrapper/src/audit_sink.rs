@@ -0,0 +1,505 @@
+//! Mirroring the operation history to external audit systems
+//!
+//! Studios with compliance requirements collect events in a SIEM. With
+//! `[audit] sinks` set, every entry [`OperationHistoryManager`] records is
+//! also sent, as it happens, to each configured sink:
+//!
+//! - `syslog`: the system logger, or a remote collector over UDP
+//! - `http`: a JSON POST to an ingestion endpoint
+//! - `file`: one JSON object per line, for a log shipper to pick up
+//!
+//! Delivery is at-least-once. An entry a sink doesn't accept is spooled to
+//! `~/.auxin/audit-spool/<sink>.jsonl` and sent, in order, ahead of the
+//! next entry; while a sink is failing, new entries go straight to the
+//! spool for a minute at a time so commands don't wait on it.
+//! `auxin history audit` shows what's waiting and delivers it.
+//!
+//! [`OperationHistoryManager`]: crate::OperationHistoryManager
+
+use crate::operation_history::{OperationHistoryEntry, OperationResult};
+use anyhow::{bail, Context, Result};
+use auxin_config::{Audit, AuditSinkKind};
+use colored::Colorize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a failing sink is left alone before it's tried again
+const RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Timeout for one HTTP delivery
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Somewhere audit entries are delivered
+pub trait AuditSink {
+    /// Name used for the sink's spool, e.g. `syslog`
+    fn name(&self) -> &str;
+
+    /// Deliver one entry; an error leaves it spooled for later
+    fn send(&self, entry: &OperationHistoryEntry) -> Result<()>;
+}
+
+/// RFC 3164 messages to the system logger or a UDP collector
+pub struct SyslogSink {
+    /// Empty for the local logger
+    address: String,
+    hostname: String,
+}
+
+impl SyslogSink {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            hostname: hostname::get()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| "localhost".to_string()),
+        }
+    }
+
+    /// `<14>Oct 17 09:30:00 studio-mac auxin[4242]: {...}`, facility user
+    fn format(&self, entry: &OperationHistoryEntry) -> Result<String> {
+        let severity = match entry.result {
+            OperationResult::Success => 6,
+            OperationResult::Failure(_) | OperationResult::Partial(_) => 4,
+        };
+        Ok(format!(
+            "<{}>{} {} auxin[{}]: {}",
+            8 + severity,
+            entry.timestamp.format("%b %e %H:%M:%S"),
+            self.hostname,
+            std::process::id(),
+            serde_json::to_string(entry)?
+        ))
+    }
+
+    #[cfg(unix)]
+    fn send_local(&self, message: &str) -> Result<()> {
+        use std::os::unix::net::UnixDatagram;
+
+        let socket = UnixDatagram::unbound()?;
+        let path = ["/var/run/syslog", "/dev/log"]
+            .into_iter()
+            .find(|path| Path::new(path).exists())
+            .context("No system logger socket found")?;
+        socket
+            .send_to(message.as_bytes(), path)
+            .with_context(|| format!("Failed to write to {}", path))?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn send_local(&self, _message: &str) -> Result<()> {
+        bail!("No local syslog on this platform; set [audit] syslog_address to host:port")
+    }
+}
+
+impl AuditSink for SyslogSink {
+    fn name(&self) -> &str {
+        "syslog"
+    }
+
+    fn send(&self, entry: &OperationHistoryEntry) -> Result<()> {
+        let message = self.format(entry)?;
+        if self.address.is_empty() {
+            return self.send_local(&message);
+        }
+        #[cfg(unix)]
+        if Path::new(&self.address).is_absolute() {
+            let socket = std::os::unix::net::UnixDatagram::unbound()?;
+            socket
+                .send_to(message.as_bytes(), &self.address)
+                .with_context(|| format!("Failed to write to {}", self.address))?;
+            return Ok(());
+        }
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0")?;
+        socket
+            .send_to(message.as_bytes(), &self.address)
+            .with_context(|| format!("Failed to send to {}", self.address))?;
+        Ok(())
+    }
+}
+
+/// A JSON POST per entry
+pub struct HttpSink {
+    url: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl HttpSink {
+    pub fn new(url: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            token,
+            agent: ureq::AgentBuilder::new().timeout(HTTP_TIMEOUT).build(),
+        }
+    }
+}
+
+impl AuditSink for HttpSink {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn send(&self, entry: &OperationHistoryEntry) -> Result<()> {
+        let mut request = self.agent.post(&self.url);
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+        request
+            .send_json(entry)
+            .with_context(|| format!("Failed to deliver to {}", self.url))?;
+        Ok(())
+    }
+}
+
+/// JSON lines appended to a file
+pub struct FileSink {
+    path: PathBuf,
+}
+
+impl FileSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl AuditSink for FileSink {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    fn send(&self, entry: &OperationHistoryEntry) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)
+            .with_context(|| format!("Failed to write to {}", self.path.display()))
+    }
+}
+
+/// What happened to one sink's entries
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delivery {
+    pub sink: String,
+    pub delivered: usize,
+    /// Entries still spooled
+    pub pending: usize,
+    /// Oldest entries dropped because the spool was full
+    pub dropped: usize,
+    pub error: Option<String>,
+}
+
+/// The configured sinks and their spools
+pub struct AuditLog {
+    sinks: Vec<Box<dyn AuditSink>>,
+    spool_dir: PathBuf,
+    buffer_limit: usize,
+}
+
+impl AuditLog {
+    /// The sinks in `[audit]`; `None` when there aren't any
+    pub fn from_config(config: &Audit) -> Result<Option<Self>> {
+        if config.sinks.is_empty() {
+            return Ok(None);
+        }
+        let mut sinks: Vec<Box<dyn AuditSink>> = Vec::new();
+        for kind in &config.sinks {
+            sinks.push(match kind {
+                AuditSinkKind::Syslog => Box::new(SyslogSink::new(&config.syslog_address)),
+                AuditSinkKind::Http => {
+                    if config.http_url.is_empty() {
+                        bail!("[audit] sinks includes \"http\" but http_url isn't set");
+                    }
+                    let token = Some(config.http_token.clone()).filter(|t| !t.is_empty());
+                    Box::new(HttpSink::new(&config.http_url, token))
+                }
+                AuditSinkKind::File => {
+                    if config.file.is_empty() {
+                        bail!("[audit] sinks includes \"file\" but file isn't set");
+                    }
+                    Box::new(FileSink::new(&config.file))
+                }
+            });
+        }
+        Ok(Some(Self::with_sinks(
+            sinks,
+            Self::default_spool_dir(),
+            config.buffer_limit,
+        )))
+    }
+
+    pub fn with_sinks(
+        sinks: Vec<Box<dyn AuditSink>>,
+        spool_dir: PathBuf,
+        buffer_limit: usize,
+    ) -> Self {
+        Self {
+            sinks,
+            spool_dir,
+            buffer_limit,
+        }
+    }
+
+    /// `~/.auxin/audit-spool`
+    fn default_spool_dir() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home).join(".auxin").join("audit-spool")
+    }
+
+    /// Send `entry` to every sink, after anything spooled for it
+    pub fn record(&self, entry: &OperationHistoryEntry) -> Vec<Delivery> {
+        self.sinks
+            .iter()
+            .map(|sink| self.deliver(sink.as_ref(), Some(entry), false))
+            .collect()
+    }
+
+    /// Send everything spooled, whether or not the sinks failed recently
+    pub fn flush(&self) -> Vec<Delivery> {
+        self.sinks
+            .iter()
+            .map(|sink| self.deliver(sink.as_ref(), None, true))
+            .collect()
+    }
+
+    /// Entries spooled for each sink
+    pub fn pending(&self) -> Vec<(String, usize)> {
+        self.sinks
+            .iter()
+            .map(|sink| {
+                let count = self.read_spool(sink.name()).map(|e| e.len()).unwrap_or(0);
+                (sink.name().to_string(), count)
+            })
+            .collect()
+    }
+
+    fn spool_path(&self, sink: &str) -> PathBuf {
+        self.spool_dir.join(format!("{}.jsonl", sink))
+    }
+
+    /// Marker whose age says when the sink last failed
+    fn failed_path(&self, sink: &str) -> PathBuf {
+        self.spool_dir.join(format!("{}.failed", sink))
+    }
+
+    fn recently_failed(&self, sink: &str) -> bool {
+        fs::metadata(self.failed_path(sink))
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < RETRY_AFTER)
+    }
+
+    fn read_spool(&self, sink: &str) -> Result<Vec<OperationHistoryEntry>> {
+        let path = self.spool_path(sink);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    fn write_spool(&self, sink: &str, entries: &[OperationHistoryEntry]) -> Result<()> {
+        let path = self.spool_path(sink);
+        if entries.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+        let mut contents = String::new();
+        for entry in entries {
+            contents.push_str(&serde_json::to_string(entry)?);
+            contents.push('\n');
+        }
+        fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Spool lock, so concurrent auxin processes don't lose each other's
+    /// entries
+    fn lock(&self, sink: &str) -> Result<File> {
+        fs::create_dir_all(&self.spool_dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.spool_dir.join(format!("{}.lock", sink)))?;
+        file.lock()?;
+        Ok(file)
+    }
+
+    fn deliver(
+        &self,
+        sink: &dyn AuditSink,
+        entry: Option<&OperationHistoryEntry>,
+        force: bool,
+    ) -> Delivery {
+        let mut delivery = Delivery {
+            sink: sink.name().to_string(),
+            delivered: 0,
+            pending: 0,
+            dropped: 0,
+            error: None,
+        };
+        if let Err(e) = self.try_deliver(sink, entry, force, &mut delivery) {
+            delivery.error = Some(format!("{:#}", e));
+        }
+        delivery
+    }
+
+    fn try_deliver(
+        &self,
+        sink: &dyn AuditSink,
+        entry: Option<&OperationHistoryEntry>,
+        force: bool,
+        delivery: &mut Delivery,
+    ) -> Result<()> {
+        let name = sink.name();
+        let _lock = self.lock(name)?;
+        let mut pending = self.read_spool(name)?;
+        pending.extend(entry.cloned());
+
+        if force || !self.recently_failed(name) {
+            for entry in &pending {
+                if let Err(e) = sink.send(entry) {
+                    fs::write(self.failed_path(name), "")?;
+                    delivery.error = Some(format!("{:#}", e));
+                    break;
+                }
+                delivery.delivered += 1;
+            }
+            if delivery.error.is_none() {
+                let _ = fs::remove_file(self.failed_path(name));
+            }
+        } else {
+            delivery.error = Some("failed recently; spooled".to_string());
+        }
+
+        let mut remaining = pending.split_off(delivery.delivered);
+        if remaining.len() > self.buffer_limit {
+            delivery.dropped = remaining.len() - self.buffer_limit;
+            remaining.drain(..delivery.dropped);
+            crate::warn!(
+                "Audit spool for {} is full; dropped the {} oldest entries",
+                name,
+                delivery.dropped
+            );
+        }
+        delivery.pending = remaining.len();
+        self.write_spool(name, &remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation_history::HistoryOperation;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use tempfile::TempDir;
+
+    /// Accepts entries while `up` is set
+    struct FlakySink {
+        up: Rc<Cell<bool>>,
+        received: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl AuditSink for FlakySink {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn send(&self, entry: &OperationHistoryEntry) -> Result<()> {
+            if !self.up.get() {
+                bail!("connection refused");
+            }
+            self.received.borrow_mut().push(entry.id.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_spools_while_sink_is_down_and_delivers_in_order() {
+        let dir = TempDir::new().unwrap();
+        let up = Rc::new(Cell::new(false));
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = FlakySink {
+            up: up.clone(),
+            received: received.clone(),
+        };
+        let audit = AuditLog::with_sinks(vec![Box::new(sink)], dir.path().to_path_buf(), 2);
+
+        let entries: Vec<_> = (0..3)
+            .map(|_| OperationHistoryEntry::new(HistoryOperation::Commit))
+            .collect();
+
+        let first = audit.record(&entries[0]);
+        assert_eq!(first[0].pending, 1);
+        assert!(first[0].error.as_deref().unwrap().contains("connection refused"));
+
+        // Within the retry window nothing is attempted, even once it's up
+        up.set(true);
+        audit.record(&entries[1]);
+        assert!(received.borrow().is_empty());
+        assert_eq!(audit.pending(), vec![("flaky".to_string(), 2)]);
+
+        // The spool holds at most two entries
+        let third = audit.record(&entries[2]);
+        assert_eq!(third[0].dropped, 1);
+
+        let flushed = audit.flush();
+        assert_eq!(flushed[0].delivered, 2);
+        assert_eq!(flushed[0].pending, 0);
+        assert_eq!(
+            *received.borrow(),
+            vec![entries[1].id.clone(), entries[2].id.clone()]
+        );
+        assert_eq!(audit.pending(), vec![("flaky".to_string(), 0)]);
+    }
+
+    #[test]
+    fn test_file_and_syslog_sinks() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("audit").join("auxin.jsonl");
+        let entry = OperationHistoryEntry::new(HistoryOperation::LockAcquire)
+            .with_result(OperationResult::Failure("locked".to_string()));
+
+        let file = FileSink::new(&path);
+        file.send(&entry).unwrap();
+        file.send(&entry).unwrap();
+        let lines: Vec<String> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: OperationHistoryEntry = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(parsed, entry);
+
+        let message = SyslogSink::new("").format(&entry).unwrap();
+        assert!(message.starts_with("<12>"));
+        assert!(message.contains(" auxin["));
+        assert!(message.ends_with(&serde_json::to_string(&entry).unwrap()));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert!(AuditLog::from_config(&Audit::default()).unwrap().is_none());
+        let missing_url = Audit {
+            sinks: vec![AuditSinkKind::Http],
+            ..Default::default()
+        };
+        assert!(AuditLog::from_config(&missing_url).is_err());
+    }
+}
@@ -319,6 +319,11 @@ impl AuthManager {
         Ok(Some(Credentials::with_hub_url(username, "", hub_url)))
     }
 
+    /// Hub username saved by `auxin auth login`, without asking the Oxen CLI
+    pub fn stored_username(&self) -> Option<String> {
+        self.get_from_config_file().ok().flatten().map(|creds| creds.username)
+    }
+
     /// Check if user is authenticated
     pub fn is_authenticated(&self) -> bool {
         self.get_credentials()
@@ -0,0 +1,260 @@
+//! Author identity mapping
+//!
+//! Commit authors are recorded as whatever identity the machine had at the
+//! time (`alice@old-laptop`, `Alice <alice@gmail.com>`, ...). A mailmap-style
+//! file at `.auxin/mailmap` folds these into the names the team actually
+//! uses. It is read by team statistics, the activity feed and `auxin migrate`.
+//!
+//! Each line names the canonical author followed by one or more aliases in
+//! angle brackets:
+//!
+//! ```text
+//! # canonical name   aliases
+//! alice             <alice@old-laptop> <alice.smith@gmail.com>
+//! Bob Jones         <bob@studio-mac>
+//! ```
+//!
+//! A `#` at the start of a line or after whitespace or `>` starts a comment;
+//! one inside a name or an alias is kept. Aliases match case-insensitively,
+//! either the whole author string or the email part of a `Name <email>`
+//! author.
+
+use crate::identity::Identity;
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maps author aliases to canonical names
+#[derive(Debug, Clone, Default)]
+pub struct AuthorMap {
+    /// Lowercased alias -> canonical name
+    aliases: HashMap<String, String>,
+}
+
+impl AuthorMap {
+    /// Location of the mapping file in a repository
+    pub fn path(repo_root: &Path) -> PathBuf {
+        repo_root.join(".auxin").join("mailmap")
+    }
+
+    /// Load the repository's mapping file; empty if there is none
+    pub fn load(repo_root: &Path) -> Result<Self> {
+        let path = Self::path(repo_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::from_file(&path)
+    }
+
+    /// `load`, but an invalid file is reported and ignored, for views that
+    /// are still useful without the mapping
+    pub fn load_or_warn(repo_root: &Path) -> Self {
+        Self::load(repo_root).unwrap_or_else(|e| {
+            crate::warn!("Ignoring the author map: {:#}", e);
+            Self::default()
+        })
+    }
+
+    /// Load a mapping file from an explicit path
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read author map {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid author map {}", path.display()))
+    }
+
+    /// Parse mapping file contents
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut aliases = HashMap::new();
+
+        for (number, line) in content.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let Some((canonical, mut rest)) = line.split_once('<') else {
+                bail!("line {}: expected 'Name <alias>'", number + 1);
+            };
+            let canonical = canonical.trim();
+            if canonical.is_empty() {
+                bail!("line {}: missing canonical name before '<'", number + 1);
+            }
+
+            loop {
+                let Some((alias, after)) = rest.split_once('>') else {
+                    bail!("line {}: unclosed '<'", number + 1);
+                };
+                let alias = alias.trim();
+                if alias.is_empty() {
+                    bail!("line {}: empty alias", number + 1);
+                }
+                aliases.insert(alias.to_lowercase(), canonical.to_string());
+
+                let after = after.trim_start();
+                if after.is_empty() {
+                    break;
+                }
+                match after.strip_prefix('<') {
+                    Some(next) => rest = next,
+                    None => bail!("line {}: unexpected text '{}'", number + 1, after),
+                }
+            }
+        }
+
+        Ok(Self { aliases })
+    }
+
+    /// Canonical name for an author, or the author unchanged if unmapped
+    pub fn resolve(&self, author: &str) -> String {
+        let author = author.trim();
+        let email = author
+            .rsplit_once('<')
+            .and_then(|(_, rest)| rest.strip_suffix('>'))
+            .map(str::trim);
+
+        std::iter::once(author)
+            .chain(email)
+            .find_map(|key| self.aliases.get(&key.to_lowercase()))
+            .cloned()
+            .unwrap_or_else(|| author.to_string())
+    }
+
+    /// Also fold `identity`'s other names into its current one, unless the
+    /// file maps them already, so your own work under an older name is
+    /// counted as one person
+    pub fn with_identity(mut self, identity: &Identity) -> Self {
+        let canonical = self.resolve(&identity.user());
+        for name in std::iter::once(identity.user()).chain(identity.other_names()) {
+            if name != canonical {
+                self.aliases.entry(name.to_lowercase()).or_insert_with(|| canonical.clone());
+            }
+        }
+        self
+    }
+
+    /// Every canonical name and alias, aliases lowercased
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .aliases
+            .iter()
+            .flat_map(|(alias, canonical)| [alias.clone(), canonical.clone()])
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Number of aliases
+    pub fn len(&self) -> usize {
+        self.aliases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+}
+
+/// `line` without its comment: from a `#` at the start or after whitespace
+/// or `>`, outside `<...>`
+fn strip_comment(line: &str) -> &str {
+    let mut in_alias = false;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        if c == '#' && !in_alias && (previous.is_whitespace() || previous == '>') {
+            return &line[..index];
+        }
+        in_alias = match c {
+            '<' => true,
+            '>' => false,
+            _ => in_alias,
+        };
+        previous = c;
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MAP: &str = "\
+# Studio team
+alice      <alice@old-laptop> <Alice.Smith@gmail.com>
+Bob Jones  <bob@studio-mac>   # moved to the B room
+";
+
+    #[test]
+    fn test_resolve() {
+        let map = AuthorMap::parse(MAP).unwrap();
+        assert_eq!(map.len(), 3);
+
+        assert_eq!(map.resolve("alice@old-laptop"), "alice");
+        assert_eq!(map.resolve("ALICE@OLD-LAPTOP"), "alice");
+        assert_eq!(map.resolve("Alice S <alice.smith@gmail.com>"), "alice");
+        assert_eq!(map.resolve(" bob@studio-mac "), "Bob Jones");
+        assert_eq!(map.resolve("carol@mixroom"), "carol@mixroom");
+    }
+
+    #[test]
+    fn test_hash_inside_names_is_kept() {
+        let map = AuthorMap::parse(
+            "DJ#1 <dj #1@booth> # the opener\nC# Minor <csharp@studio>#keys\n  # indented",
+        )
+        .unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.resolve("dj #1@booth"), "DJ#1");
+        assert_eq!(map.resolve("csharp@studio"), "C# Minor");
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        for (content, expected) in [
+            ("alice", "line 1: expected"),
+            ("\n<alice@laptop>", "line 2: missing canonical name"),
+            ("alice <alice@laptop", "line 1: unclosed"),
+            ("alice <>", "line 1: empty alias"),
+            ("alice <a@x> and <b@y>", "line 1: unexpected text"),
+        ] {
+            let error = AuthorMap::parse(content).unwrap_err().to_string();
+            assert!(error.starts_with(expected), "{:?}: {}", content, error);
+        }
+    }
+
+    #[test]
+    fn test_with_identity_links_own_names() {
+        let config = auxin_config::Identity {
+            name: String::new(),
+            aliases: vec!["bob@studio-mac".to_string()],
+        };
+        let identity = Identity::resolve(
+            &config,
+            "sam".to_string(),
+            "mixroom".to_string(),
+            Some("samantha".to_string()),
+        );
+        let map = AuthorMap::parse(MAP).unwrap().with_identity(&identity);
+
+        assert_eq!(map.resolve("sam@mixroom"), "samantha@mixroom");
+        assert_eq!(map.resolve("Samantha"), "samantha@mixroom");
+        // The mailmap wins over the identity's aliases
+        assert_eq!(map.resolve("bob@studio-mac"), "Bob Jones");
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let temp = TempDir::new().unwrap();
+        assert!(AuthorMap::load(temp.path()).unwrap().is_empty());
+
+        fs::create_dir_all(temp.path().join(".auxin")).unwrap();
+        fs::write(AuthorMap::path(temp.path()), MAP).unwrap();
+        assert_eq!(AuthorMap::load(temp.path()).unwrap().resolve("bob@studio-mac"), "Bob Jones");
+
+        // Views that can do without it carry on without it
+        fs::write(AuthorMap::path(temp.path()), "alice <alice@laptop").unwrap();
+        assert!(AuthorMap::load(temp.path()).is_err());
+        assert!(AuthorMap::load_or_warn(temp.path()).is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+//! Descriptive titles for daemon auto-commits
+//!
+//! Instead of "Auto-save at 14:02", the daemon asks `auxin auto-title` to
+//! describe what changed, e.g. "Modified: Lead Vox comp, added Drum Bus
+//! Channel EQ".
+//!
+//! For Logic Pro projects the parsed project is compared with a snapshot
+//! taken at the last commit (`.auxin/auto_title_snapshot.json`), using the
+//! same diff as `auxin metadata-diff`. Other projects, and Logic projects
+//! without a snapshot, fall back to the names of changed files. Turned off
+//! with `[daemon] auto_titles = false`.
+
+use crate::logic_parser::{LogicParser, LogicProjectData};
+use crate::metadata_diff::{
+    AutomationChange, ChannelStripDiff, GlobalChange, MetadataDiff, PluginChainChange, RegionDiff,
+    TrackChange,
+};
+use crate::MetadataDiffer;
+use anyhow::{Context, Result};
+use auxin_oxen::StatusInfo;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Items listed per category before "+N more"
+const MAX_ITEMS: usize = 3;
+
+/// What changed, grouped for a commit title
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSummary {
+    pub modified: Vec<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl ChangeSummary {
+    /// Summarise a Logic Pro project diff
+    pub fn from_metadata_diff(diff: &MetadataDiff) -> Self {
+        let mut summary = Self::default();
+
+        for change in &diff.global_changes {
+            let item = match change {
+                GlobalChange::TempoChange { from, to } => format!("tempo {} → {} BPM", from, to),
+                GlobalChange::KeySignatureChange { from, to } => format!("key {} → {}", from, to),
+                GlobalChange::SampleRateChange { .. } => "sample rate".to_string(),
+                GlobalChange::TimeSignatureChange { to, .. } => {
+                    format!("time signature {}/{}", to.0, to.1)
+                },
+                GlobalChange::BitDepthChange { .. } => "bit depth".to_string(),
+            };
+            push_unique(&mut summary.modified, item);
+        }
+
+        for change in &diff.track_changes {
+            match change {
+                TrackChange::Added { track } => {
+                    push_unique(&mut summary.added, format!("{} track", track.name))
+                },
+                TrackChange::Removed { track_name, .. } => {
+                    push_unique(&mut summary.removed, format!("{} track", track_name))
+                },
+                TrackChange::Renamed {
+                    old_name, new_name, ..
+                } => push_unique(
+                    &mut summary.modified,
+                    format!("renamed {} to {}", old_name, new_name),
+                ),
+                TrackChange::RegionChanged {
+                    track_name,
+                    region_diff,
+                } => summary.add_region_change(track_name, region_diff),
+                TrackChange::ChannelStripChanged {
+                    track_name,
+                    changes,
+                    ..
+                } => summary.add_channel_strip_changes(track_name, changes),
+                TrackChange::Reordered { track_name, .. }
+                | TrackChange::TypeChanged { track_name, .. }
+                | TrackChange::MuteChanged { track_name, .. }
+                | TrackChange::SoloChanged { track_name, .. }
+                | TrackChange::ColorChanged { track_name, .. } => {
+                    push_unique(&mut summary.modified, track_name.clone())
+                },
+            }
+        }
+
+        for change in &diff.plugin_changes {
+            push_unique(
+                &mut summary.modified,
+                format!("{} {}", change.track_name, change.plugin_name),
+            );
+        }
+
+        for change in &diff.automation_changes {
+            let (track, parameter) = match change {
+                AutomationChange::Added {
+                    track_name,
+                    parameter,
+                    ..
+                }
+                | AutomationChange::Removed {
+                    track_name,
+                    parameter,
+                }
+                | AutomationChange::Modified {
+                    track_name,
+                    parameter,
+                    ..
+                } => (track_name, parameter),
+            };
+            push_unique(
+                &mut summary.modified,
+                format!("{} {} automation", track, parameter.to_lowercase()),
+            );
+        }
+
+        summary
+    }
+
+    /// Summarise changed files, for projects without a structured diff
+    pub fn from_status(project: &Path, status: &StatusInfo) -> Self {
+        let name = |path: &PathBuf| {
+            let relative = path.strip_prefix(project).unwrap_or(path);
+            relative
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| relative.display().to_string())
+        };
+
+        let mut summary = Self::default();
+        for path in status.modified.iter().chain(&status.staged) {
+            if project.join(path).exists() {
+                push_unique(&mut summary.modified, name(path));
+            } else {
+                push_unique(&mut summary.removed, name(path));
+            }
+        }
+        for path in &status.untracked {
+            push_unique(&mut summary.added, name(path));
+        }
+        // A staged new file shows up as both staged and untracked
+        summary.modified.retain(|m| !summary.added.contains(m));
+        summary
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modified.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Title such as "Modified: Lead Vox comp, added Drum Bus Channel EQ"
+    pub fn title(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            ("modified", &self.modified),
+            ("added", &self.added),
+            ("removed", &self.removed),
+        ]
+        .into_iter()
+        .filter(|(_, items)| !items.is_empty())
+        .enumerate()
+        .map(|(i, (verb, items))| {
+            let list = list_items(items);
+            if i == 0 {
+                format!("{}{}: {}", verb[..1].to_uppercase(), &verb[1..], list)
+            } else {
+                format!("{} {}", verb, list)
+            }
+        })
+        .collect();
+
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    fn add_region_change(&mut self, track: &str, diff: &RegionDiff) {
+        match diff {
+            RegionDiff::Added { region } => {
+                push_unique(&mut self.added, format!("{} {}", track, region.name))
+            },
+            RegionDiff::Removed { region_name } => {
+                push_unique(&mut self.removed, format!("{} {}", track, region_name))
+            },
+            RegionDiff::Moved { region_name, .. }
+            | RegionDiff::Resized { region_name, .. }
+            | RegionDiff::MuteToggled { region_name, .. }
+            | RegionDiff::LoopToggled { region_name, .. }
+            | RegionDiff::FadeChanged { region_name, .. } => {
+                push_unique(&mut self.modified, format!("{} {}", track, region_name))
+            },
+        }
+    }
+
+    fn add_channel_strip_changes(&mut self, track: &str, changes: &ChannelStripDiff) {
+        for change in &changes.plugin_chain_changes {
+            match change {
+                PluginChainChange::PluginAdded { plugin } => {
+                    push_unique(&mut self.added, format!("{} {}", track, plugin.name))
+                },
+                PluginChainChange::PluginRemoved { plugin_name, .. } => {
+                    push_unique(&mut self.removed, format!("{} {}", track, plugin_name))
+                },
+                PluginChainChange::PluginReordered { plugin_name, .. }
+                | PluginChainChange::PluginBypassed { plugin_name, .. } => {
+                    push_unique(&mut self.modified, format!("{} {}", track, plugin_name))
+                },
+            }
+        }
+
+        let modules = [
+            (!changes.eq_changes.is_empty(), "EQ"),
+            (!changes.compressor_changes.is_empty(), "compressor"),
+            (!changes.reverb_changes.is_empty(), "reverb"),
+            (
+                changes.volume_delta.is_some() || changes.pan_delta.is_some(),
+                "mix",
+            ),
+        ];
+        for (changed, module) in modules {
+            if changed {
+                push_unique(&mut self.modified, format!("{} {}", track, module));
+            }
+        }
+    }
+}
+
+/// Location of the Logic Pro snapshot taken at the last commit
+pub fn snapshot_path(project: &Path) -> PathBuf {
+    project.join(".auxin").join("auto_title_snapshot.json")
+}
+
+/// Record the project's current state for the next auto-title
+///
+/// Returns `false` (and writes nothing) for projects that aren't Logic Pro
+/// projects or can't be parsed.
+pub fn save_snapshot(project: &Path) -> Result<bool> {
+    if !LogicParser::is_valid_project(project) {
+        return Ok(false);
+    }
+    let Ok(data) = LogicParser::parse(project) else {
+        return Ok(false);
+    };
+
+    let path = snapshot_path(project);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string(&data)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Title describing the project's uncommitted changes, if any
+pub fn auto_title(project: &Path, status: &StatusInfo) -> Option<String> {
+    let summary = logic_summary(project)
+        .filter(|summary| !summary.is_empty())
+        .unwrap_or_else(|| ChangeSummary::from_status(project, status));
+    summary.title()
+}
+
+fn logic_summary(project: &Path) -> Option<ChangeSummary> {
+    let snapshot = fs::read_to_string(snapshot_path(project)).ok()?;
+    let previous: LogicProjectData = serde_json::from_str(&snapshot).ok()?;
+    let current = LogicParser::parse(project).ok()?;
+    Some(ChangeSummary::from_metadata_diff(&MetadataDiffer::compare(
+        &previous, &current,
+    )))
+}
+
+fn push_unique(items: &mut Vec<String>, item: String) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+fn list_items(items: &[String]) -> String {
+    let mut listed = items[..items.len().min(MAX_ITEMS)].join(", ");
+    if items.len() > MAX_ITEMS {
+        listed.push_str(&format!(" +{} more", items.len() - MAX_ITEMS));
+    }
+    listed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_title_from_metadata_diff() {
+        let mut diff = MetadataDiff::new();
+        diff.track_changes.push(TrackChange::RegionChanged {
+            track_name: "Lead Vox".to_string(),
+            region_diff: RegionDiff::Resized {
+                region_name: "comp".to_string(),
+                old_duration: 8.0,
+                new_duration: 12.0,
+            },
+        });
+        let mut strip = ChannelStripDiff::new();
+        strip.volume_delta = Some(-1.5);
+        diff.track_changes.push(TrackChange::ChannelStripChanged {
+            track_name: "Bass".to_string(),
+            track_id: "t2".to_string(),
+            changes: strip,
+        });
+        diff.track_changes.push(TrackChange::Removed {
+            track_name: "Old Pad".to_string(),
+            track_id: "t3".to_string(),
+        });
+        diff.global_changes.push(GlobalChange::TempoChange {
+            from: 120.0,
+            to: 124.0,
+        });
+
+        let summary = ChangeSummary::from_metadata_diff(&diff);
+        assert_eq!(
+            summary.title().unwrap(),
+            "Modified: tempo 120 → 124 BPM, Lead Vox comp, Bass mix, removed Old Pad track"
+        );
+        assert_eq!(
+            ChangeSummary::from_metadata_diff(&MetadataDiff::new()).title(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_title_from_status() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        for file in ["model.skp", "a.png", "b.png", "c.png", "d.png"] {
+            fs::write(project.join(file), b"x").unwrap();
+        }
+        let status = StatusInfo {
+            modified: vec!["model.skp".into(), "gone.skp".into()],
+            untracked: ["a.png", "b.png", "c.png", "d.png"]
+                .iter()
+                .map(PathBuf::from)
+                .collect(),
+            staged: vec!["a.png".into()],
+        };
+
+        let summary = ChangeSummary::from_status(project, &status);
+        assert_eq!(
+            summary.title().unwrap(),
+            "Modified: model.skp, added a.png, b.png, c.png +1 more, removed gone.skp"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_skips_other_projects() {
+        let temp = TempDir::new().unwrap();
+        assert!(!save_snapshot(temp.path()).unwrap());
+        assert!(!snapshot_path(temp.path()).exists());
+    }
+}
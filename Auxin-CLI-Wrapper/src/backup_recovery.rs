@@ -2,6 +2,7 @@ use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -31,6 +32,10 @@ pub struct Snapshot {
 
     /// Metadata about the snapshot
     pub metadata: std::collections::HashMap<String, String>,
+
+    /// Unknown fields of the snapshot record
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -44,6 +49,9 @@ pub enum SnapshotType {
     AutoBeforeLockBreak,
     AutoBeforeRollback,
 
+    /// Automatic snapshot when auto-commits were paused after a suspicious change
+    AnomalyDetected,
+
     /// Scheduled automatic snapshot
     Scheduled,
 }
@@ -58,6 +66,7 @@ impl Snapshot {
             commit_id: None,
             description: String::new(),
             metadata: std::collections::HashMap::new(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -268,9 +277,12 @@ impl BackupRecoveryManager {
         Ok(instructions)
     }
 
-    /// Display snapshots in a formatted way
-    pub fn display_snapshots(&self, limit: Option<usize>) -> Result<()> {
-        let snapshots = self.list_snapshots()?;
+    /// Display snapshots in a formatted way, only for `repo` if given
+    pub fn display_snapshots(&self, limit: Option<usize>, repo: Option<&Path>) -> Result<()> {
+        let snapshots = match repo {
+            Some(repo) => self.list_snapshots_for_repo(repo)?,
+            None => self.list_snapshots()?,
+        };
 
         if snapshots.is_empty() {
             println!("{}", "No snapshots found".bright_black());
@@ -295,6 +307,7 @@ impl BackupRecoveryManager {
                 SnapshotType::AutoBeforePull => "⬇",
                 SnapshotType::AutoBeforeLockBreak => "🔨",
                 SnapshotType::AutoBeforeRollback => "↩",
+                SnapshotType::AnomalyDetected => "⚠",
                 SnapshotType::Scheduled => "⏰",
             };
 
@@ -304,7 +317,7 @@ impl BackupRecoveryManager {
             println!(
                 "│ {} {} {}",
                 icon,
-                snapshot.id[..8].bright_cyan(),
+                short_id(&snapshot.id).bright_cyan(),
                 age.bright_black()
             );
 
@@ -313,7 +326,7 @@ impl BackupRecoveryManager {
             }
 
             if let Some(commit_id) = &snapshot.commit_id {
-                println!("│   Commit: {}", commit_id[..8].bright_yellow());
+                println!("│   Commit: {}", short_id(commit_id).bright_yellow());
             }
 
             println!(
@@ -331,7 +344,7 @@ impl BackupRecoveryManager {
         if let Some(limit) = limit {
             if snapshots.len() > limit {
                 println!(
-                    "\n{} more snapshots available. Use 'auxin snapshots list --all' to see all.\n",
+                    "\n{} more snapshots available. Use 'auxin snapshot list --all' to see all.\n",
                     snapshots.len() - limit
                 );
             }
@@ -357,6 +370,11 @@ impl BackupRecoveryManager {
     }
 }
 
+/// First 8 characters of an ID, for listings
+fn short_id(id: &str) -> &str {
+    id.char_indices().nth(8).map_or(id, |(end, _)| &id[..end])
+}
+
 impl Default for BackupRecoveryManager {
     fn default() -> Self {
         Self::new()
@@ -599,4 +617,11 @@ mod tests {
         assert!(!lock_steps.is_empty());
         assert!(lock_steps.iter().any(|s| s.contains("lock")));
     }
+
+    #[test]
+    fn test_short_id() {
+        assert_eq!(short_id("0123456789abcdef"), "01234567");
+        assert_eq!(short_id("abc123"), "abc123");
+        assert_eq!(short_id(""), "");
+    }
 }
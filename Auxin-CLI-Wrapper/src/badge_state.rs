@@ -0,0 +1,258 @@
+//! Per-file status snapshot for Finder / Explorer badges
+//!
+//! File manager extensions (a Finder Sync extension on macOS, an icon overlay
+//! handler on Windows) can't afford to run `oxen status` for every icon they
+//! draw. `auxin badges` writes the project's status to
+//! `.auxin/badges.json` instead, and the daemon refreshes it after each
+//! status check and commit. Extensions only read the file.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "project": "/Users/me/Music/MySong.logicx",
+//!   "updated_at": "2026-10-16T14:02:11Z",
+//!   "state": "modified",
+//!   "lock": { "state": "mine", "holder": "me@studio-mac" },
+//!   "files": {
+//!     "Alternatives/000/ProjectData": "modified",
+//!     "Bounces/mix v2.wav": "untracked"
+//!   }
+//! }
+//! ```
+//!
+//! Paths are relative to the project and use `/`. Files that aren't listed
+//! are clean. A folder takes the state of the files below it (see
+//! [`BadgeSnapshot::state_of`]). The file is replaced atomically, so readers
+//! never see a partial write. `version` only changes when existing fields
+//! change meaning; new fields may be added at any time.
+
+use crate::quick_actions::LockState;
+use anyhow::{Context, Result};
+use auxin_oxen::StatusInfo;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Format version of `badges.json`
+pub const BADGE_STATE_VERSION: u32 = 1;
+
+/// Status of a file, folder or the whole project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileState {
+    Clean,
+    Staged,
+    Untracked,
+    Modified,
+}
+
+/// Who holds the project lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockBadge {
+    Unlocked,
+    Mine,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockSnapshot {
+    pub state: LockBadge,
+    pub holder: Option<String>,
+}
+
+impl From<&LockState> for LockSnapshot {
+    fn from(lock: &LockState) -> Self {
+        match lock {
+            LockState::Unlocked => Self {
+                state: LockBadge::Unlocked,
+                holder: None,
+            },
+            LockState::Mine => Self {
+                state: LockBadge::Mine,
+                holder: Some(crate::lock_integration::get_user_identifier()),
+            },
+            LockState::Other(holder) => Self {
+                state: LockBadge::Other,
+                holder: Some(holder.clone()),
+            },
+        }
+    }
+}
+
+/// Contents of `.auxin/badges.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BadgeSnapshot {
+    pub version: u32,
+    pub project: PathBuf,
+    pub updated_at: DateTime<Utc>,
+    /// Overall project state
+    pub state: FileState,
+    pub lock: LockSnapshot,
+    /// Files that aren't clean
+    pub files: BTreeMap<String, FileState>,
+}
+
+impl BadgeSnapshot {
+    pub fn new(project: &Path, status: &StatusInfo, lock: LockSnapshot) -> Self {
+        let mut files = BTreeMap::new();
+        // Later lists win: a partially staged file still shows as modified
+        let lists = [
+            (&status.staged, FileState::Staged),
+            (&status.untracked, FileState::Untracked),
+            (&status.modified, FileState::Modified),
+        ];
+        for (paths, state) in lists {
+            for path in paths {
+                files.insert(relative_key(project, path), state);
+            }
+        }
+
+        let state = files.values().copied().max().unwrap_or(FileState::Clean);
+        Self {
+            version: BADGE_STATE_VERSION,
+            project: project.to_path_buf(),
+            updated_at: Utc::now(),
+            state,
+            lock,
+            files,
+        }
+    }
+
+    /// Location of the snapshot for a project
+    pub fn path(project: &Path) -> PathBuf {
+        project.join(".auxin").join("badges.json")
+    }
+
+    /// Read a project's snapshot, if one has been written
+    pub fn load(project: &Path) -> Result<Option<Self>> {
+        let path = Self::path(project);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let snapshot = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(snapshot))
+    }
+
+    /// Replace the project's snapshot atomically
+    pub fn write(&self) -> Result<PathBuf> {
+        let path = Self::path(&self.project);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// State of a file or folder, given relative to the project
+    ///
+    /// A folder is as dirty as the dirtiest file below it; unlisted files are
+    /// clean. An empty path is the project itself.
+    pub fn state_of(&self, relative: &str) -> FileState {
+        let relative = relative.trim_matches('/');
+        if relative.is_empty() {
+            return self.state;
+        }
+        if let Some(state) = self.files.get(relative) {
+            return *state;
+        }
+
+        let prefix = format!("{}/", relative);
+        self.files
+            .range(prefix.clone()..)
+            .take_while(|(path, _)| path.starts_with(&prefix))
+            .map(|(_, state)| *state)
+            .max()
+            .unwrap_or(FileState::Clean)
+    }
+}
+
+/// Status paths as `/`-separated keys relative to the project
+fn relative_key(project: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(project).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn status() -> StatusInfo {
+        StatusInfo {
+            modified: vec![
+                PathBuf::from("Alternatives/000/ProjectData"),
+                PathBuf::from("notes.txt"),
+            ],
+            untracked: vec![PathBuf::from("Bounces/mix v2.wav")],
+            staged: vec![PathBuf::from("notes.txt"), PathBuf::from("Resources/cover.png")],
+        }
+    }
+
+    fn unlocked() -> LockSnapshot {
+        LockSnapshot::from(&LockState::Unlocked)
+    }
+
+    #[test]
+    fn test_snapshot_states() {
+        let snapshot = BadgeSnapshot::new(Path::new("/music/Song"), &status(), unlocked());
+
+        assert_eq!(snapshot.state, FileState::Modified);
+        assert_eq!(snapshot.state_of(""), FileState::Modified);
+        assert_eq!(snapshot.state_of("notes.txt"), FileState::Modified);
+        assert_eq!(snapshot.state_of("Resources/cover.png"), FileState::Staged);
+        assert_eq!(snapshot.state_of("Bounces/"), FileState::Untracked);
+        assert_eq!(snapshot.state_of("Alternatives"), FileState::Modified);
+        assert_eq!(snapshot.state_of("Alternatives/001"), FileState::Clean);
+        assert_eq!(snapshot.state_of("Alt"), FileState::Clean);
+
+        let clean = BadgeSnapshot::new(
+            Path::new("/music/Song"),
+            &StatusInfo {
+                modified: vec![],
+                untracked: vec![],
+                staged: vec![],
+            },
+            LockSnapshot::from(&LockState::Other("alice@studio".to_string())),
+        );
+        assert_eq!(clean.state, FileState::Clean);
+        assert_eq!(clean.lock.state, LockBadge::Other);
+        assert_eq!(clean.lock.holder.as_deref(), Some("alice@studio"));
+    }
+
+    #[test]
+    fn test_write_and_load() {
+        let temp = TempDir::new().unwrap();
+        assert!(BadgeSnapshot::load(temp.path()).unwrap().is_none());
+
+        let mut status = status();
+        status.modified[0] = temp.path().join("Alternatives/000/ProjectData");
+        let snapshot = BadgeSnapshot::new(temp.path(), &status, unlocked());
+        let path = snapshot.write().unwrap();
+
+        assert_eq!(path, temp.path().join(".auxin/badges.json"));
+        let loaded = BadgeSnapshot::load(temp.path()).unwrap().unwrap();
+        assert_eq!(loaded, snapshot);
+        assert!(loaded.files.contains_key("Alternatives/000/ProjectData"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["lock"]["state"], "unlocked");
+        assert_eq!(json["files"]["Bounces/mix v2.wav"], "untracked");
+    }
+}
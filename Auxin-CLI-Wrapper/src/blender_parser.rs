@@ -0,0 +1,640 @@
+//! Reading scene details from a `.blend` file
+//!
+//! A `.blend` is a short header (version, pointer size, byte order) and a
+//! list of file blocks, each holding the data of one or more structs. The
+//! `DNA1` block describes the layout of every struct, so fields are found
+//! by name rather than at offsets that change between Blender versions.
+//! Compressed files are gzip (before Blender 3.0) or Zstandard.
+
+use crate::blender_metadata::BlenderMetadata;
+use crate::vlog;
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// `Object.type` values
+const OB_MESH: i64 = 1;
+const OB_LAMP: i64 = 10;
+const OB_CAMERA: i64 = 11;
+
+/// What's read from a `.blend`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlendFileInfo {
+    /// Version of Blender that saved the file, e.g. "4.2"
+    pub version: String,
+    pub scene_count: u32,
+    pub object_count: u32,
+    pub mesh_count: u32,
+    pub light_count: u32,
+    pub camera_count: u32,
+    pub material_count: u32,
+    /// The scene open when the file was saved
+    pub active_scene: Option<String>,
+    /// Render settings of the active scene
+    pub render_engine: Option<String>,
+    pub resolution: Option<(u32, u32)>,
+    pub frame_range: Option<(u32, u32)>,
+    pub fps: Option<u32>,
+    pub file_size_bytes: u64,
+}
+
+impl BlendFileInfo {
+    /// Commit metadata for `message` with everything read from the file
+    pub fn metadata(&self, message: impl Into<String>) -> BlenderMetadata {
+        let mut metadata = BlenderMetadata::new(message)
+            .with_blender_version(&self.version)
+            .with_scene_count(self.scene_count)
+            .with_object_count(self.object_count)
+            .with_mesh_count(self.mesh_count)
+            .with_light_count(self.light_count)
+            .with_camera_count(self.camera_count)
+            .with_material_count(self.material_count)
+            .with_file_size(self.file_size_bytes);
+        if let Some(scene) = &self.active_scene {
+            metadata = metadata.with_active_scene(scene);
+        }
+        if let Some(engine) = &self.render_engine {
+            metadata = metadata.with_render_engine(engine);
+        }
+        if let Some((width, height)) = self.resolution {
+            metadata = metadata.with_resolution(width, height);
+        }
+        if let Some((start, end)) = self.frame_range {
+            metadata = metadata.with_frame_range(start, end);
+        }
+        if let Some(fps) = self.fps {
+            metadata = metadata.with_fps(fps);
+        }
+        metadata
+    }
+}
+
+/// Reads Blender files
+pub struct BlenderParser;
+
+impl BlenderParser {
+    /// Read the `.blend` at `path`
+    pub fn parse(path: &Path) -> Result<BlendFileInfo> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut info = Self::parse_bytes(&bytes)
+            .with_context(|| format!("Failed to read Blender file {}", path.display()))?;
+        info.file_size_bytes = bytes.len() as u64;
+        vlog!(
+            "Blender file {}: Blender {}, {} scene(s), {} object(s)",
+            path.display(),
+            info.version,
+            info.scene_count,
+            info.object_count
+        );
+        Ok(info)
+    }
+
+    /// Read a `.blend` already in memory, compressed or not
+    pub fn parse_bytes(bytes: &[u8]) -> Result<BlendFileInfo> {
+        let decompressed;
+        let bytes = if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut data = Vec::new();
+            GzDecoder::new(bytes)
+                .read_to_end(&mut data)
+                .context("Failed to decompress Blender file")?;
+            decompressed = data;
+            &decompressed
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            decompressed =
+                zstd::stream::decode_all(bytes).context("Failed to decompress Blender file")?;
+            &decompressed
+        } else {
+            bytes
+        };
+
+        let file = BlendFile::read(bytes)?;
+        let dna = file
+            .blocks
+            .iter()
+            .find(|block| &block.code == b"DNA1")
+            .ok_or_else(|| anyhow!("Blender file has no DNA block"))
+            .and_then(|block| Dna::parse(block.data, &file.format))?;
+
+        let mut info = BlendFileInfo {
+            version: file.version.clone(),
+            file_size_bytes: bytes.len() as u64,
+            ..Default::default()
+        };
+        let object_type = dna.field("Object", "type");
+        for block in &file.blocks {
+            match &block.code {
+                b"SC\0\0" => info.scene_count += block.count,
+                b"MA\0\0" => info.material_count += block.count,
+                b"OB\0\0" => {
+                    info.object_count += block.count;
+                    let kind = object_type.and_then(|f| f.read_int(block.data, &file.format));
+                    match kind {
+                        Some(OB_MESH) => info.mesh_count += 1,
+                        Some(OB_LAMP) => info.light_count += 1,
+                        Some(OB_CAMERA) => info.camera_count += 1,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(scene) = active_scene(&file, &dna) {
+            read_scene(scene, &file.format, &dna, &mut info);
+        }
+        Ok(info)
+    }
+}
+
+/// Pointer size and byte order, from the file header
+struct Format {
+    pointer_size: usize,
+    little_endian: bool,
+}
+
+impl Format {
+    fn uint(&self, bytes: &[u8]) -> u64 {
+        let mut buf = [0u8; 8];
+        if self.little_endian {
+            buf[..bytes.len()].copy_from_slice(bytes);
+            u64::from_le_bytes(buf)
+        } else {
+            buf[8 - bytes.len()..].copy_from_slice(bytes);
+            u64::from_be_bytes(buf)
+        }
+    }
+
+    fn read_uint(&self, data: &[u8], pos: usize, size: usize) -> Option<u64> {
+        data.get(pos..pos + size).map(|bytes| self.uint(bytes))
+    }
+}
+
+struct Block<'a> {
+    code: [u8; 4],
+    /// Where the data was in memory when saved; pointers refer to these
+    address: u64,
+    count: u32,
+    data: &'a [u8],
+}
+
+struct BlendFile<'a> {
+    version: String,
+    format: Format,
+    blocks: Vec<Block<'a>>,
+}
+
+impl<'a> BlendFile<'a> {
+    /// The header and file blocks, up to `ENDB`
+    ///
+    /// Until Blender 5.0 the header is `BLENDER`, `_` or `-` for 4- or
+    /// 8-byte pointers, `v` or `V` for byte order and a three-digit
+    /// version; block headers are code, length, address, struct index and
+    /// count. Newer files have `BLENDER17-01v` and a four-digit version,
+    /// and block headers with 64-bit lengths and counts.
+    fn read(bytes: &'a [u8]) -> Result<Self> {
+        if !bytes.starts_with(b"BLENDER") || bytes.len() < 12 {
+            bail!("Not a Blender file");
+        }
+        let digits = |range: std::ops::Range<usize>| -> Result<u32> {
+            std::str::from_utf8(bytes.get(range).unwrap_or_default())
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| anyhow!("Unrecognized Blender file header"))
+        };
+
+        let large = bytes[7].is_ascii_digit();
+        let (version, format, header_len) = if large {
+            if bytes.get(9..13) != Some(b"-01v") {
+                bail!("Unsupported Blender file format");
+            }
+            let version = digits(13..17)?;
+            let format = Format {
+                pointer_size: 8,
+                little_endian: true,
+            };
+            (version, format, digits(7..9)? as usize)
+        } else {
+            let format = Format {
+                pointer_size: if bytes[7] == b'-' { 8 } else { 4 },
+                little_endian: bytes[8] == b'v',
+            };
+            (digits(9..12)?, format, 12)
+        };
+
+        let mut blocks = Vec::new();
+        let mut pos = header_len;
+        loop {
+            let (block, next) = if large {
+                read_large_block(bytes, pos, &format)
+            } else {
+                read_block(bytes, pos, &format)
+            }
+            .ok_or_else(|| anyhow!("Blender file is truncated"))?;
+            if &block.code == b"ENDB" {
+                break;
+            }
+            blocks.push(block);
+            pos = next;
+        }
+
+        Ok(Self {
+            version: format!("{}.{}", version / 100, version % 100),
+            format,
+            blocks,
+        })
+    }
+}
+
+/// Code, length, address, struct index, count
+fn read_block<'a>(bytes: &'a [u8], pos: usize, format: &Format) -> Option<(Block<'a>, usize)> {
+    let ptr = format.pointer_size;
+    let code: [u8; 4] = bytes.get(pos..pos + 4)?.try_into().ok()?;
+    let len = format.read_uint(bytes, pos + 4, 4)? as usize;
+    let address = format.read_uint(bytes, pos + 8, ptr)?;
+    let count = format.read_uint(bytes, pos + 12 + ptr, 4)? as u32;
+    let start = pos + 16 + ptr;
+    let data = if &code == b"ENDB" {
+        &[]
+    } else {
+        bytes.get(start..start + len)?
+    };
+    let block = Block {
+        code,
+        address,
+        count,
+        data,
+    };
+    Some((block, start + len))
+}
+
+/// Code, struct index, address, then 64-bit length and count
+fn read_large_block<'a>(
+    bytes: &'a [u8],
+    pos: usize,
+    format: &Format,
+) -> Option<(Block<'a>, usize)> {
+    let code: [u8; 4] = bytes.get(pos..pos + 4)?.try_into().ok()?;
+    let address = format.read_uint(bytes, pos + 8, 8)?;
+    let len = format.read_uint(bytes, pos + 16, 8)? as usize;
+    let count = format.read_uint(bytes, pos + 24, 8)? as u32;
+    let start = pos + 32;
+    let data = if &code == b"ENDB" {
+        &[]
+    } else {
+        bytes.get(start..start + len)?
+    };
+    let block = Block {
+        code,
+        address,
+        count,
+        data,
+    };
+    Some((block, start + len))
+}
+
+/// Where a struct field is and how big it is
+#[derive(Clone, Copy)]
+struct Field {
+    offset: usize,
+    size: usize,
+}
+
+impl Field {
+    fn read_int(&self, data: &[u8], format: &Format) -> Option<i64> {
+        let value = format.read_uint(data, self.offset, self.size)?;
+        Some(match self.size {
+            1 => value as i8 as i64,
+            2 => value as i16 as i64,
+            4 => value as i32 as i64,
+            _ => value as i64,
+        })
+    }
+
+    fn read_float(&self, data: &[u8], format: &Format) -> Option<f32> {
+        if self.size != 4 {
+            return None;
+        }
+        Some(f32::from_bits(
+            format.read_uint(data, self.offset, 4)? as u32
+        ))
+    }
+
+    fn read_string(&self, data: &[u8]) -> Option<String> {
+        let bytes = data.get(self.offset..self.offset + self.size)?;
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        let text = String::from_utf8_lossy(&bytes[..end]).into_owned();
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+/// Struct layouts from the `DNA1` block, by struct then field name
+struct Dna {
+    structs: HashMap<String, HashMap<String, Field>>,
+}
+
+impl Dna {
+    /// `SDNA`, then `NAME`, `TYPE`, `TLEN` and `STRC` sections, each
+    /// 4-byte aligned: field names, type names, type sizes, and per struct
+    /// its type and (type, name) pairs for each field
+    fn parse(data: &[u8], format: &Format) -> Result<Self> {
+        let invalid = || anyhow!("Blender file has an invalid DNA block");
+        let mut pos = 0;
+        let expect = |pos: &mut usize, tag: &[u8]| -> Result<()> {
+            *pos = (*pos + 3) & !3;
+            if data.get(*pos..*pos + 4) != Some(tag) {
+                return Err(invalid());
+            }
+            *pos += 4;
+            Ok(())
+        };
+        let read_u32 = |pos: &mut usize| -> Result<usize> {
+            let value = format.read_uint(data, *pos, 4).ok_or_else(invalid)?;
+            *pos += 4;
+            Ok(value as usize)
+        };
+        let read_strings = |pos: &mut usize, count: usize| -> Result<Vec<String>> {
+            let mut strings = Vec::with_capacity(count);
+            for _ in 0..count {
+                let rest = data.get(*pos..).ok_or_else(invalid)?;
+                let end = rest.iter().position(|&b| b == 0).ok_or_else(invalid)?;
+                strings.push(String::from_utf8_lossy(&rest[..end]).into_owned());
+                *pos += end + 1;
+            }
+            Ok(strings)
+        };
+
+        expect(&mut pos, b"SDNA")?;
+        expect(&mut pos, b"NAME")?;
+        let count = read_u32(&mut pos)?;
+        let names = read_strings(&mut pos, count)?;
+        expect(&mut pos, b"TYPE")?;
+        let count = read_u32(&mut pos)?;
+        let types = read_strings(&mut pos, count)?;
+        expect(&mut pos, b"TLEN")?;
+        let mut sizes = Vec::with_capacity(types.len());
+        for _ in 0..types.len() {
+            sizes.push(format.read_uint(data, pos, 2).ok_or_else(invalid)? as usize);
+            pos += 2;
+        }
+        expect(&mut pos, b"STRC")?;
+        let count = read_u32(&mut pos)?;
+
+        let mut structs = HashMap::new();
+        let read_u16 = |pos: &mut usize| -> Result<usize> {
+            let value = format.read_uint(data, *pos, 2).ok_or_else(invalid)?;
+            *pos += 2;
+            Ok(value as usize)
+        };
+        for _ in 0..count {
+            let struct_type = read_u16(&mut pos)?;
+            let field_count = read_u16(&mut pos)?;
+            let mut fields = HashMap::new();
+            let mut offset = 0;
+            for _ in 0..field_count {
+                let field_type = read_u16(&mut pos)?;
+                let name = names.get(read_u16(&mut pos)?).ok_or_else(invalid)?;
+                let pointer = name.starts_with('*') || name.starts_with("(*");
+                let element = if pointer {
+                    format.pointer_size
+                } else {
+                    *sizes.get(field_type).ok_or_else(invalid)?
+                };
+                let size = element * array_length(name);
+                fields.insert(bare_name(name), Field { offset, size });
+                offset += size;
+            }
+            let struct_name = types.get(struct_type).ok_or_else(invalid)?;
+            structs.insert(struct_name.clone(), fields);
+        }
+        Ok(Self { structs })
+    }
+
+    fn field(&self, struct_name: &str, field: &str) -> Option<Field> {
+        self.structs.get(struct_name)?.get(field).copied()
+    }
+
+    /// A field of a struct nested in another, e.g. `Scene.r.xsch`
+    fn nested(&self, outer: (&str, &str), inner: (&str, &str)) -> Option<Field> {
+        let outer = self.field(outer.0, outer.1)?;
+        let inner = self.field(inner.0, inner.1)?;
+        Some(Field {
+            offset: outer.offset + inner.offset,
+            size: inner.size,
+        })
+    }
+}
+
+/// "*next" -> "next", "name[66]" -> "name", "(*func)()" -> "func"
+fn bare_name(name: &str) -> String {
+    name.trim_start_matches(['*', '('])
+        .split(['[', ')'])
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('*')
+        .to_string()
+}
+
+/// Elements in a field: the product of its array dimensions
+fn array_length(name: &str) -> usize {
+    name.split('[')
+        .skip(1)
+        .filter_map(|dim| dim.split(']').next()?.parse::<usize>().ok())
+        .product()
+}
+
+/// The scene `FileGlobal.curscene` points at, or the first scene
+fn active_scene<'a>(file: &'a BlendFile, dna: &Dna) -> Option<&'a Block<'a>> {
+    let scenes = file.blocks.iter().filter(|block| &block.code == b"SC\0\0");
+    let current = file
+        .blocks
+        .iter()
+        .find(|block| &block.code == b"GLOB")
+        .zip(dna.field("FileGlobal", "curscene"))
+        .and_then(|(global, field)| field.read_int(global.data, &file.format));
+    let mut scenes = scenes.peekable();
+    let first = scenes.peek().copied();
+    scenes
+        .find(|scene| Some(scene.address as i64) == current)
+        .or(first)
+}
+
+/// Name and render settings of `scene`
+fn read_scene(scene: &Block, format: &Format, dna: &Dna, info: &mut BlendFileInfo) {
+    let id_name = dna.nested(("Scene", "id"), ("ID", "name"));
+    // ID names start with a two-letter type code, "SC" for scenes
+    info.active_scene = id_name
+        .and_then(|f| f.read_string(scene.data))
+        .map(|name| name.chars().skip(2).collect());
+
+    let render = |field: &str| dna.nested(("Scene", "r"), ("RenderData", field));
+    let int = |field: &str| {
+        render(field)
+            .and_then(|f| f.read_int(scene.data, format))
+            .and_then(|value| u32::try_from(value).ok())
+    };
+    info.render_engine = render("engine").and_then(|f| f.read_string(scene.data));
+    info.resolution = int("xsch").zip(int("ysch"));
+    info.frame_range = int("sfra").zip(int("efra"));
+    let base = render("frs_sec_base")
+        .and_then(|f| f.read_float(scene.data, format))
+        .filter(|base| *base > 0.0)
+        .unwrap_or(1.0);
+    info.fps = int("frs_sec").map(|fps| (fps as f32 / base).round() as u32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    /// A minimal little-endian, 8-byte-pointer file
+    fn blend_file() -> Vec<u8> {
+        fn block(code: &[u8; 4], address: u64, data: &[u8]) -> Vec<u8> {
+            let mut bytes = code.to_vec();
+            bytes.extend((data.len() as u32).to_le_bytes());
+            bytes.extend(address.to_le_bytes());
+            bytes.extend(0u32.to_le_bytes());
+            bytes.extend(1u32.to_le_bytes());
+            bytes.extend(data);
+            bytes
+        }
+        fn strings(items: &[&str]) -> Vec<u8> {
+            let mut bytes = (items.len() as u32).to_le_bytes().to_vec();
+            for item in items {
+                bytes.extend(item.as_bytes());
+                bytes.push(0);
+            }
+            while bytes.len() % 4 != 0 {
+                bytes.push(0);
+            }
+            bytes
+        }
+
+        // Types: 0 char, 1 short, 2 int, 3 float, 4 ID, 5 RenderData,
+        // 6 Scene, 7 Object, 8 FileGlobal
+        let mut dna = b"SDNANAME".to_vec();
+        dna.extend(strings(&[
+            "*next",
+            "name[8]",
+            "xsch",
+            "ysch",
+            "sfra",
+            "efra",
+            "frs_sec",
+            "frs_sec_base",
+            "engine[8]",
+            "id",
+            "r",
+            "type",
+            "*curscene",
+        ]));
+        dna.extend(b"TYPE");
+        dna.extend(strings(&[
+            "char",
+            "short",
+            "int",
+            "float",
+            "ID",
+            "RenderData",
+            "Scene",
+            "Object",
+            "FileGlobal",
+        ]));
+        dna.extend(b"TLEN");
+        for size in [1u16, 2, 4, 4, 16, 32, 48, 18, 8] {
+            dna.extend(size.to_le_bytes());
+        }
+        dna.extend([0, 0]);
+        dna.extend(b"STRC");
+        let structs: [(u16, &[(u16, u16)]); 5] = [
+            (4, &[(4, 0), (0, 1)]),
+            (5, &[(2, 2), (2, 3), (2, 4), (2, 5), (1, 6), (3, 7), (0, 8)]),
+            (6, &[(4, 9), (5, 10)]),
+            (7, &[(4, 9), (1, 11)]),
+            (8, &[(6, 12)]),
+        ];
+        dna.extend((structs.len() as u32).to_le_bytes());
+        for (struct_type, fields) in structs {
+            dna.extend(struct_type.to_le_bytes());
+            dna.extend((fields.len() as u16).to_le_bytes());
+            for (field_type, name) in fields {
+                dna.extend(field_type.to_le_bytes());
+                dna.extend(name.to_le_bytes());
+            }
+        }
+
+        let scene = |name: &[u8; 8], engine: &[u8; 8]| {
+            let mut data = vec![0u8; 8];
+            data.extend(name);
+            for value in [1920i32, 1080, 1, 250] {
+                data.extend(value.to_le_bytes());
+            }
+            data.extend(30000i16.to_le_bytes());
+            data.extend(1001f32.to_le_bytes());
+            data.extend(engine);
+            data
+        };
+        let object = |kind: i16| {
+            let mut data = vec![0u8; 16];
+            data.extend(kind.to_le_bytes());
+            data
+        };
+
+        let mut bytes = b"BLENDER-v402".to_vec();
+        bytes.extend(block(b"GLOB", 0x10, &0x200u64.to_le_bytes()));
+        bytes.extend(block(
+            b"SC\0\0",
+            0x100,
+            &scene(b"SCDraft\0", b"EEVEE\0\0\0"),
+        ));
+        bytes.extend(block(b"SC\0\0", 0x200, &scene(b"SCFinal\0", b"CYCLES\0\0")));
+        bytes.extend(block(b"OB\0\0", 0x300, &object(1)));
+        bytes.extend(block(b"OB\0\0", 0x400, &object(1)));
+        bytes.extend(block(b"OB\0\0", 0x500, &object(11)));
+        bytes.extend(block(b"MA\0\0", 0x600, &[0; 16]));
+        bytes.extend(block(b"DNA1", 0x700, &dna));
+        bytes.extend(block(b"ENDB", 0, &[]));
+        bytes
+    }
+
+    #[test]
+    fn test_parse_blend_file() {
+        let info = BlenderParser::parse_bytes(&blend_file()).unwrap();
+        assert_eq!(info.version, "4.2");
+        assert_eq!(info.scene_count, 2);
+        assert_eq!(info.object_count, 3);
+        assert_eq!(info.mesh_count, 2);
+        assert_eq!(info.camera_count, 1);
+        assert_eq!(info.material_count, 1);
+        assert_eq!(info.active_scene.as_deref(), Some("Final"));
+        assert_eq!(info.render_engine.as_deref(), Some("CYCLES"));
+        assert_eq!(info.resolution, Some((1920, 1080)));
+        assert_eq!(info.frame_range, Some((1, 250)));
+        assert_eq!(info.fps, Some(30));
+
+        let message = info.metadata("Lighting pass").format_commit_message();
+        assert!(message.contains("Active Scene: Final"));
+        assert!(message.contains("Blender Version: 4.2"));
+    }
+
+    #[test]
+    fn test_parse_compressed_and_invalid() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&blend_file()).unwrap();
+        let gzipped = BlenderParser::parse_bytes(&encoder.finish().unwrap()).unwrap();
+        let zstd = zstd::stream::encode_all(blend_file().as_slice(), 0).unwrap();
+        let zstd = BlenderParser::parse_bytes(&zstd).unwrap();
+        assert_eq!(gzipped.object_count, 3);
+        assert_eq!(zstd.object_count, 3);
+
+        assert!(BlenderParser::parse_bytes(b"not a blend file").is_err());
+        let truncated = &blend_file()[..40];
+        assert!(BlenderParser::parse_bytes(truncated).is_err());
+    }
+}
@@ -10,6 +10,7 @@ use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -94,6 +95,8 @@ impl AudioFormat {
 }
 
 /// Metadata about a bounce file
+///
+/// Stored as JSON beside the bounce.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BounceMetadata {
     /// Commit ID this bounce is associated with
@@ -131,6 +134,10 @@ pub struct BounceMetadata {
 
     /// Audio fingerprint hash (for comparison)
     pub fingerprint: Option<String>,
+
+    /// Unknown fields of the bounce's metadata file
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl BounceMetadata {
@@ -154,6 +161,7 @@ impl BounceMetadata {
             added_by: get_current_user(),
             description: None,
             fingerprint: None,
+            other: BTreeMap::new(),
         }
     }
 
@@ -0,0 +1,153 @@
+//! Branch management: `auxin branch`
+//!
+//! Checks around the Oxen branch commands, so the draft workflow can be
+//! driven without the raw `oxen` CLI:
+//!
+//! - Names are checked before they reach `oxen`, so `-D` can't be taken for
+//!   an option and `..` or spaces don't produce an unusable branch
+//! - Switching refuses while files are modified or staged, since the
+//!   checkout would replace them
+//! - The current branch can't be deleted; `main` and the draft branch only
+//!   with `--force`
+
+use crate::draft_manager::DraftManager;
+use anyhow::{bail, Result};
+use auxin_oxen::{BranchInfo, OxenSubprocess};
+use std::path::Path;
+
+/// Characters Oxen and Git don't accept in branch names
+const FORBIDDEN: &[char] = &[' ', '~', '^', ':', '?', '*', '[', '\\'];
+
+/// Check that `name` can be used as a branch name
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("Branch name can't be empty");
+    }
+    if name.starts_with('-') || name.starts_with('/') || name.ends_with('/') {
+        bail!(
+            "Branch name '{}' can't start with '-' or start or end with '/'",
+            name
+        );
+    }
+    if name.ends_with(".lock") || name.ends_with('.') || name.contains("..") || name.contains("//")
+    {
+        bail!(
+            "Branch name '{}' can't contain '..' or '//' or end with '.' or '.lock'",
+            name
+        );
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| FORBIDDEN.contains(c) || c.is_control())
+    {
+        bail!("Branch name '{}' can't contain {:?}", name, c);
+    }
+    Ok(())
+}
+
+/// Branches of the repository, the current one marked
+pub fn list(oxen: &OxenSubprocess, repo: &Path) -> Result<Vec<BranchInfo>> {
+    oxen.list_branches(repo)
+}
+
+/// Create `name` at the current commit and switch to it
+pub fn create(oxen: &OxenSubprocess, repo: &Path, name: &str) -> Result<()> {
+    validate_name(name)?;
+    if exists(oxen, repo, name)? {
+        bail!(
+            "Branch '{}' already exists; switch to it with 'auxin branch switch {}'",
+            name,
+            name
+        );
+    }
+    oxen.create_branch(repo, name)
+}
+
+/// Switch to `name`; `false` if it's already the current branch
+///
+/// Refuses when files are modified or staged, unless `force`.
+pub fn switch(oxen: &OxenSubprocess, repo: &Path, name: &str, force: bool) -> Result<bool> {
+    validate_name(name)?;
+    if !exists(oxen, repo, name)? {
+        bail!(
+            "No branch '{}'; create it with 'auxin branch create {}'",
+            name,
+            name
+        );
+    }
+    if oxen.current_branch(repo)? == name {
+        return Ok(false);
+    }
+    if !force {
+        let status = oxen.status(repo)?;
+        let changed = status.modified.len() + status.staged.len();
+        if changed > 0 {
+            bail!(
+                "{} file(s) have uncommitted changes that switching to '{}' would replace.\n\
+                 Commit them first, or pass --force to discard them",
+                changed,
+                name
+            );
+        }
+    }
+    oxen.checkout(repo, name)?;
+    Ok(true)
+}
+
+/// Delete `name`
+pub fn delete(oxen: &OxenSubprocess, repo: &Path, name: &str, force: bool) -> Result<()> {
+    validate_name(name)?;
+    if !exists(oxen, repo, name)? {
+        bail!("No branch '{}'", name);
+    }
+    check_delete(name, &oxen.current_branch(repo)?, force)?;
+    oxen.delete_branch(repo, name)
+}
+
+/// Whether `name` may be deleted while `current` is checked out
+fn check_delete(name: &str, current: &str, force: bool) -> Result<()> {
+    if name == current {
+        bail!(
+            "'{}' is the current branch; switch to another branch first",
+            name
+        );
+    }
+    if !force && (name == DraftManager::MAIN_BRANCH || name == DraftManager::DEFAULT_DRAFT_BRANCH) {
+        bail!(
+            "'{}' is used by the draft workflow; pass --force to delete it anyway",
+            name
+        );
+    }
+    Ok(())
+}
+
+fn exists(oxen: &OxenSubprocess, repo: &Path, name: &str) -> Result<bool> {
+    Ok(list(oxen, repo)?.iter().any(|branch| branch.name == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        for name in ["mix-v2", "feature/vocals", "client_notes.2026"] {
+            assert!(validate_name(name).is_ok(), "{}", name);
+        }
+        for name in [
+            "", "-D", "mix v2", "a..b", "mix.lock", "mix.", "/mix", "mix/", "a//b", "mix:1",
+            "mix\n",
+        ] {
+            assert!(validate_name(name).is_err(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_check_delete() {
+        assert!(check_delete("mix-v2", "main", false).is_ok());
+        assert!(check_delete("mix-v2", "mix-v2", true).is_err());
+        assert!(check_delete("draft", "main", false).is_err());
+        assert!(check_delete("draft", "main", true).is_ok());
+        assert!(check_delete("main", "draft", false).is_err());
+    }
+}
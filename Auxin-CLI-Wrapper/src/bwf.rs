@@ -0,0 +1,435 @@
+//! Broadcast WAV metadata for exported bounces
+//!
+//! A WAV sent to a client or mastering engineer outlives the repository it
+//! came from. `auxin bounce export` writes a BWF `bext` chunk (EBU Tech
+//! 3285) and an `iXML` chunk into WAV exports so the file can be traced
+//! back to its commit with `auxin bounce identify`, or read in any DAW or
+//! BWF-aware tool:
+//!
+//! - `bext`: description ("My Song @ 8f3e2a1b (v1.1)"), originator
+//!   "Auxin", the commit ID as originator reference, and the bounce date
+//! - `iXML`: `PROJECT`, `NOTE` (commit message) and `USER` lines
+//!   `AUXIN_COMMIT=`, `BPM=`, `KEY=`, `VERSION=`
+//!
+//! Existing `bext`/`iXML` chunks are replaced; all other chunks are kept
+//! as they are. RF64 files (over 4 GB) aren't supported.
+
+use crate::metadata_edits::MetadataEdits;
+use crate::versioning::VersionLog;
+use crate::CommitInfo;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Fixed part of a version 1 `bext` chunk, before the coding history
+const BEXT_FIXED_LEN: usize = 602;
+
+/// What is embedded in an exported bounce
+#[derive(Debug, Clone, PartialEq)]
+pub struct BwfMetadata {
+    pub commit_id: String,
+    pub project: String,
+    /// Commit message
+    pub message: Option<String>,
+    pub bpm: Option<f32>,
+    pub key: Option<String>,
+    /// Version label, e.g. "v1.1"
+    pub version: Option<String>,
+    /// When the bounce was made
+    pub created_at: DateTime<Utc>,
+}
+
+impl BwfMetadata {
+    /// Metadata for a bounce of `commit`, including metadata edits and the
+    /// commit's version label
+    pub fn for_commit(repo: &Path, commit: &CommitInfo, created_at: DateTime<Utc>) -> Self {
+        let metadata = MetadataEdits::load(repo)
+            .unwrap_or_default()
+            .effective(commit);
+        let version = VersionLog::load(repo)
+            .ok()
+            .and_then(|log| log.for_commit(&commit.id).map(|v| v.label.clone()));
+        let message = metadata.message.trim();
+
+        Self {
+            commit_id: commit.id.clone(),
+            project: repo
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            message: (!message.is_empty()).then(|| message.to_string()),
+            bpm: metadata.bpm,
+            key: metadata.key_signature,
+            version,
+            created_at,
+        }
+    }
+
+    /// The `bext` description: project, short commit ID and version
+    fn description(&self) -> String {
+        let short = &self.commit_id[..8.min(self.commit_id.len())];
+        match &self.version {
+            Some(version) => format!("{} @ {} ({})", self.project, short, version),
+            None => format!("{} @ {}", self.project, short),
+        }
+    }
+
+    /// Body of the `bext` chunk
+    fn bext(&self) -> Vec<u8> {
+        let mut chunk = Vec::with_capacity(BEXT_FIXED_LEN);
+        put_ascii(&mut chunk, &self.description(), 256);
+        put_ascii(&mut chunk, "Auxin", 32);
+        put_ascii(&mut chunk, &self.commit_id, 32);
+        put_ascii(
+            &mut chunk,
+            &self.created_at.format("%Y-%m-%d").to_string(),
+            10,
+        );
+        put_ascii(
+            &mut chunk,
+            &self.created_at.format("%H:%M:%S").to_string(),
+            8,
+        );
+        chunk.extend_from_slice(&0u64.to_le_bytes()); // TimeReference
+        chunk.extend_from_slice(&1u16.to_le_bytes()); // Version
+        chunk.resize(BEXT_FIXED_LEN, 0); // UMID and reserved
+        chunk
+    }
+
+    /// Body of the `iXML` chunk
+    fn ixml(&self) -> String {
+        let mut user = vec![format!("AUXIN_COMMIT={}", self.commit_id)];
+        if let Some(bpm) = self.bpm {
+            user.push(format!("BPM={}", bpm));
+        }
+        if let Some(key) = &self.key {
+            user.push(format!("KEY={}", key));
+        }
+        if let Some(version) = &self.version {
+            user.push(format!("VERSION={}", version));
+        }
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<BWFXML>\n");
+        xml.push_str("  <IXML_VERSION>2.10</IXML_VERSION>\n");
+        xml.push_str(&format!(
+            "  <PROJECT>{}</PROJECT>\n",
+            xml_escape(&self.project)
+        ));
+        if let Some(message) = &self.message {
+            xml.push_str(&format!("  <NOTE>{}</NOTE>\n", xml_escape(message)));
+        }
+        xml.push_str(&format!(
+            "  <USER>{}</USER>\n",
+            xml_escape(&user.join("\n"))
+        ));
+        xml.push_str("</BWFXML>\n");
+        xml
+    }
+
+    /// Parse an `iXML` chunk written by [`embed`]; `None` if it has no commit
+    fn from_ixml(xml: &str, created_at: DateTime<Utc>) -> Option<Self> {
+        let user = xml_element(xml, "USER")?;
+        let field = |name: &str| {
+            user.lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+                .map(|value| value.trim().to_string())
+        };
+
+        Some(Self {
+            commit_id: field("AUXIN_COMMIT")?,
+            project: xml_element(xml, "PROJECT").unwrap_or_default(),
+            message: xml_element(xml, "NOTE"),
+            bpm: field("BPM").and_then(|bpm| bpm.parse().ok()),
+            key: field("KEY"),
+            version: field("VERSION"),
+            created_at,
+        })
+    }
+}
+
+/// A chunk of the source file: its ID and where its data is
+struct Chunk {
+    id: [u8; 4],
+    offset: u64,
+    size: u32,
+}
+
+/// Write `metadata` into the WAV file at `path`, replacing any `bext` and
+/// `iXML` chunks
+pub fn embed(path: &Path, metadata: &BwfMetadata) -> Result<()> {
+    let mut source = BufReader::new(
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?,
+    );
+    let chunks = read_chunks(&mut source)?;
+    if !chunks.iter().any(|c| &c.id == b"fmt ") {
+        bail!("{} has no fmt chunk", path.display());
+    }
+
+    let bext = metadata.bext();
+    let ixml = metadata.ixml().into_bytes();
+    let tmp = path.with_extension("wav.tmp");
+    let mut out = BufWriter::new(
+        File::create(&tmp).with_context(|| format!("Failed to write {}", tmp.display()))?,
+    );
+    out.write_all(b"RIFF\0\0\0\0WAVE")?;
+    let mut riff_size: u64 = 4;
+
+    for chunk in chunks
+        .iter()
+        .filter(|c| &c.id != b"bext" && &c.id != b"iXML")
+    {
+        out.write_all(&chunk.id)?;
+        out.write_all(&chunk.size.to_le_bytes())?;
+        source.seek(SeekFrom::Start(chunk.offset))?;
+        let copied = std::io::copy(&mut (&mut source).take(chunk.size as u64), &mut out)?;
+        if copied < chunk.size as u64 {
+            drop(out);
+            let _ = fs::remove_file(&tmp);
+            bail!("{} is truncated", path.display());
+        }
+        if chunk.size % 2 == 1 {
+            out.write_all(&[0])?;
+        }
+        riff_size += 8 + padded(chunk.size as u64);
+
+        if &chunk.id == b"fmt " {
+            riff_size += write_chunk(&mut out, b"bext", &bext)?;
+            riff_size += write_chunk(&mut out, b"iXML", &ixml)?;
+        }
+    }
+
+    if riff_size > u32::MAX as u64 {
+        drop(out);
+        let _ = fs::remove_file(&tmp);
+        bail!(
+            "{} would be over 4 GB; RF64 isn't supported",
+            path.display()
+        );
+    }
+    out.seek(SeekFrom::Start(4))?;
+    out.write_all(&(riff_size as u32).to_le_bytes())?;
+    out.flush()?;
+    drop(out);
+
+    fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// The metadata embedded in a WAV file, if it was exported by auxin
+pub fn read(path: &Path) -> Result<Option<BwfMetadata>> {
+    let mut source = BufReader::new(
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?,
+    );
+    let chunks = read_chunks(&mut source)?;
+    let mut body = |id: &[u8; 4]| -> Result<Option<Vec<u8>>> {
+        let Some(chunk) = chunks.iter().find(|c| &c.id == id) else {
+            return Ok(None);
+        };
+        // The size is the file's word; a bogus one mustn't allocate more
+        // than the file holds
+        let mut data = Vec::new();
+        source.seek(SeekFrom::Start(chunk.offset))?;
+        (&mut source)
+            .take(chunk.size as u64)
+            .read_to_end(&mut data)?;
+        if data.len() < chunk.size as usize {
+            bail!("{} is truncated", path.display());
+        }
+        Ok(Some(data))
+    };
+
+    let created_at = body(b"bext")?
+        .filter(|bext| bext.len() >= 338)
+        .and_then(|bext| {
+            let date = ascii_field(&bext[320..330]);
+            let time = ascii_field(&bext[330..338]).replace('-', ":");
+            chrono::NaiveDateTime::parse_from_str(
+                &format!("{} {}", date, time),
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .ok()
+        })
+        .map(|t| t.and_utc())
+        .unwrap_or_default();
+    let Some(ixml) = body(b"iXML")? else {
+        return Ok(None);
+    };
+    Ok(BwfMetadata::from_ixml(
+        &String::from_utf8_lossy(&ixml),
+        created_at,
+    ))
+}
+
+/// The chunks of a RIFF/WAVE file
+fn read_chunks<R: Read + Seek>(source: &mut R) -> Result<Vec<Chunk>> {
+    let mut header = [0u8; 12];
+    source.read_exact(&mut header).context("Not a WAV file")?;
+    match (&header[0..4], &header[8..12]) {
+        (b"RIFF", b"WAVE") => {},
+        (b"RF64", _) => bail!("RF64 files aren't supported"),
+        _ => bail!("Not a WAV file"),
+    }
+
+    let len = source.seek(SeekFrom::End(0))?;
+    let mut chunks = Vec::new();
+    let mut position = 12;
+    while position + 8 <= len {
+        source.seek(SeekFrom::Start(position))?;
+        let mut chunk_header = [0u8; 8];
+        source.read_exact(&mut chunk_header)?;
+        let id = [
+            chunk_header[0],
+            chunk_header[1],
+            chunk_header[2],
+            chunk_header[3],
+        ];
+        let size = u32::from_le_bytes([
+            chunk_header[4],
+            chunk_header[5],
+            chunk_header[6],
+            chunk_header[7],
+        ]);
+        chunks.push(Chunk {
+            id,
+            offset: position + 8,
+            size,
+        });
+        position += 8 + padded(size as u64);
+    }
+    Ok(chunks)
+}
+
+/// Write a whole chunk; returns the bytes written
+fn write_chunk<W: Write>(out: &mut W, id: &[u8; 4], body: &[u8]) -> Result<u64> {
+    out.write_all(id)?;
+    out.write_all(&(body.len() as u32).to_le_bytes())?;
+    out.write_all(body)?;
+    if body.len() % 2 == 1 {
+        out.write_all(&[0])?;
+    }
+    Ok(8 + padded(body.len() as u64))
+}
+
+/// Chunk data is padded to an even length
+fn padded(size: u64) -> u64 {
+    size + size % 2
+}
+
+/// Append `value` as a fixed-width, NUL-padded ASCII field
+fn put_ascii(buf: &mut Vec<u8>, value: &str, width: usize) {
+    let mut field: Vec<u8> = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() {
+                c as u8
+            } else {
+                b'?'
+            }
+        })
+        .take(width)
+        .collect();
+    field.resize(width, 0);
+    buf.extend_from_slice(&field);
+}
+
+fn ascii_field(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Text of the first `<name>` element, unescaped
+fn xml_element(xml: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = start + xml[start..].find(&format!("</{}>", name))?;
+    Some(
+        xml[start..end]
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn wav(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut body = b"WAVE".to_vec();
+        for (id, data) in chunks {
+            write_chunk(&mut body, id, data).unwrap();
+        }
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        file.extend_from_slice(&body);
+        file
+    }
+
+    #[test]
+    fn test_embed_and_read() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Mix.wav");
+        let fmt = [1u8; 16];
+        let data = [7u8; 11];
+        fs::write(
+            &path,
+            wav(&[(b"fmt ", &fmt), (b"bext", &[0; 602]), (b"data", &data)]),
+        )
+        .unwrap();
+
+        let metadata = BwfMetadata {
+            commit_id: "8f3e2a1b9c".to_string(),
+            project: "My Song".to_string(),
+            message: Some("Vocals <comped> & tuned".to_string()),
+            bpm: Some(120.5),
+            key: Some("A Minor".to_string()),
+            version: Some("v1.1".to_string()),
+            created_at: "2026-10-16T14:02:11Z".parse().unwrap(),
+        };
+        embed(&path, &metadata).unwrap();
+        embed(&path, &metadata).unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        let mut source = std::io::Cursor::new(&bytes);
+        let chunks = read_chunks(&mut source).unwrap();
+        let ids: Vec<&[u8; 4]> = chunks.iter().map(|c| &c.id).collect();
+        assert_eq!(ids, vec![b"fmt ", b"bext", b"iXML", b"data"]);
+        assert_eq!(
+            u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize,
+            bytes.len() - 8
+        );
+        let data_chunk = chunks.last().unwrap();
+        assert_eq!(
+            &bytes[data_chunk.offset as usize..][..data_chunk.size as usize],
+            &data
+        );
+        let bext = &bytes[chunks[1].offset as usize..];
+        assert_eq!(ascii_field(&bext[..256]), "My Song @ 8f3e2a1b (v1.1)");
+
+        assert_eq!(read(&path).unwrap(), Some(metadata));
+
+        let plain = temp.path().join("Plain.wav");
+        fs::write(&plain, wav(&[(b"fmt ", &fmt), (b"data", &data)])).unwrap();
+        assert_eq!(read(&plain).unwrap(), None);
+
+        // A bext chunk claiming 4 GB in a small file is an error, not an
+        // allocation of 4 GB
+        let mut bytes = wav(&[(b"fmt ", &fmt), (b"bext", &[0; 8])]);
+        let size_at = bytes.len() - 12;
+        bytes[size_at..size_at + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        let bogus = temp.path().join("Bogus.wav");
+        fs::write(&bogus, bytes).unwrap();
+        let error = read(&bogus).unwrap_err();
+        assert!(error.to_string().contains("truncated"), "{}", error);
+    }
+}
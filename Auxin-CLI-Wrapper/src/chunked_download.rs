@@ -0,0 +1,614 @@
+//! Chunked download manager for pulls
+//!
+//! The counterpart of [`crate::chunked_upload`] for `auxin pull`:
+//! - Progress callbacks while the pull runs
+//! - Bandwidth estimation
+//! - Resume after interruption, with retries on network failures
+//!
+//! # Architecture
+//!
+//! There is no transfer of its own here: resuming means running `oxen pull`
+//! again (with retries under [`AdaptiveRetryPolicy`]). Oxen stores pulled
+//! content by hash under `.oxen/` and skips what already arrived, so the new
+//! pull carries on where the last one stopped.
+//!
+//! The manager reads the progress bar oxen prints (see [`ProgressEvent`]),
+//! passes each update to the callback, and keeps a session in
+//! `~/.auxin/downloads/`:
+//! - Saved at most every second while oxen reports, so a pull that is
+//!   killed still leaves its progress
+//! - Bytes received add up across attempts
+//! - Removed once the pull completes
+//!
+//! Backends that don't report progress (FFI) still pull and resume, but
+//! count nothing received.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use auxin::chunked_download::{ChunkedDownloadManager, DownloadConfig};
+//! use std::path::Path;
+//!
+//! let manager = ChunkedDownloadManager::new(DownloadConfig::default())?;
+//!
+//! // Start or resume a pull
+//! let result = manager.download_with_progress(
+//!     Path::new("/path/to/repo"),
+//!     "origin",
+//!     "main",
+//!     |progress| {
+//!         println!("Received {} at {}", progress.bytes_string(), progress.bandwidth_string());
+//!     },
+//! )?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::chunked_upload::UploadProgress;
+use crate::network_resilience::{AdaptiveRetryPolicy, RetryPolicy};
+use crate::oxen_backend::{
+    create_backend_with_network_timeout, BackendType, OxenBackend, ProgressEvent,
+};
+use auxin_config::{Config, NetworkClass};
+
+/// Default state directory for download tracking
+const DEFAULT_STATE_DIR: &str = ".auxin/downloads";
+
+/// How often the session is saved while a pull runs
+const SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configuration for chunked downloads
+#[derive(Debug, Clone)]
+pub struct DownloadConfig {
+    /// Directory to store download state
+    pub state_dir: PathBuf,
+    /// Maximum retries of the pull
+    pub max_retries: u32,
+    /// Delay before the first retry, doubling up to `max_backoff_ms`
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Time limit for the pull itself
+    pub network_timeout_secs: u64,
+    /// Enable verbose logging
+    pub verbose: bool,
+    /// Backend type (Subprocess or FFI)
+    pub backend_type: BackendType,
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        Self {
+            state_dir: home.join(DEFAULT_STATE_DIR),
+            max_retries: 3,
+            initial_backoff_ms: 2000,
+            max_backoff_ms: 16000,
+            network_timeout_secs: 120,
+            verbose: false,
+            backend_type: BackendType::default(),
+        }
+    }
+}
+
+impl DownloadConfig {
+    /// Default config with the `[network.pull]` retry settings applied
+    pub fn from_config(config: &Config) -> Self {
+        let retry = config.retry_settings(NetworkClass::Pull);
+        Self {
+            max_retries: retry.max_retries,
+            initial_backoff_ms: retry.initial_backoff_ms,
+            max_backoff_ms: retry.max_backoff_ms,
+            network_timeout_secs: retry.timeout_s,
+            ..Default::default()
+        }
+    }
+
+    /// Create config with FFI backend (faster, requires ffi feature)
+    pub fn with_ffi_backend() -> Self {
+        Self {
+            backend_type: BackendType::FFI,
+            ..Default::default()
+        }
+    }
+}
+
+/// Status of a download
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DownloadStatus {
+    /// Download pending
+    Pending,
+    /// Download in progress
+    InProgress,
+    /// Download completed successfully
+    Completed,
+    /// Download failed
+    Failed,
+}
+
+/// Overall download session state
+///
+/// Saved as JSON so an interrupted pull can resume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadSession {
+    /// Unique session ID
+    pub id: String,
+    /// Repository path
+    pub repo_path: String,
+    /// Remote name
+    pub remote: String,
+    /// Branch name
+    pub branch: String,
+    /// Bytes received so far, over every attempt
+    pub bytes_downloaded: u64,
+    /// Number of times the pull was started
+    pub attempts: u32,
+    /// Session started at
+    pub started_at: DateTime<Utc>,
+    /// Last activity timestamp
+    pub last_activity: DateTime<Utc>,
+    /// Overall status
+    pub status: DownloadStatus,
+    /// Last error message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Bandwidth samples (bytes per second)
+    pub bandwidth_samples: Vec<f64>,
+
+    /// Unknown fields, written back when the session is saved
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl DownloadSession {
+    /// Create a new download session
+    pub fn new(repo_path: &Path, remote: &str, branch: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            repo_path: repo_path.to_string_lossy().to_string(),
+            remote: remote.to_string(),
+            branch: branch.to_string(),
+            bytes_downloaded: 0,
+            attempts: 0,
+            started_at: Utc::now(),
+            last_activity: Utc::now(),
+            status: DownloadStatus::Pending,
+            last_error: None,
+            bandwidth_samples: Vec::new(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    /// Get average bandwidth in bytes per second
+    pub fn average_bandwidth(&self) -> Option<f64> {
+        if self.bandwidth_samples.is_empty() {
+            None
+        } else {
+            let sum: f64 = self.bandwidth_samples.iter().sum();
+            Some(sum / self.bandwidth_samples.len() as f64)
+        }
+    }
+
+    /// Add a bandwidth sample
+    pub fn add_bandwidth_sample(&mut self, bytes_per_second: f64) {
+        // Keep last 10 samples for moving average
+        if self.bandwidth_samples.len() >= 10 {
+            self.bandwidth_samples.remove(0);
+        }
+        self.bandwidth_samples.push(bytes_per_second);
+    }
+}
+
+/// Progress information for callbacks
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    /// Session ID
+    pub session_id: String,
+    /// Bytes received so far, including earlier attempts
+    pub bytes_downloaded: u64,
+    /// Bytes received by this run
+    pub bytes_this_run: u64,
+    /// Bandwidth of this run in bytes per second
+    pub bandwidth_bps: Option<f64>,
+    /// Time since this run started
+    pub elapsed: Duration,
+    /// Whether an interrupted pull is being resumed
+    pub resumed: bool,
+}
+
+impl DownloadProgress {
+    /// Format bandwidth as human-readable string
+    pub fn bandwidth_string(&self) -> String {
+        match self.bandwidth_bps {
+            Some(bps) => format!("{}/s", UploadProgress::bytes_string(bps as u64)),
+            None => "calculating...".to_string(),
+        }
+    }
+
+    /// Format bytes received as human-readable string
+    pub fn bytes_string(&self) -> String {
+        UploadProgress::bytes_string(self.bytes_downloaded)
+    }
+}
+
+/// Result of a download
+#[derive(Debug, Clone)]
+pub struct DownloadResult {
+    /// Bytes received, including earlier attempts
+    pub bytes_downloaded: u64,
+    /// Duration of this run
+    pub duration: Duration,
+    /// Average bandwidth (bytes per second)
+    pub average_bandwidth: Option<f64>,
+    /// Whether an interrupted pull was resumed
+    pub resumed: bool,
+}
+
+/// Summary information about a resumable session
+#[derive(Debug, Clone)]
+pub struct DownloadSessionInfo {
+    /// Session ID
+    pub id: String,
+    /// Remote name
+    pub remote: String,
+    /// Branch name
+    pub branch: String,
+    /// Bytes received
+    pub bytes_downloaded: u64,
+    /// Number of times the pull was started
+    pub attempts: u32,
+    /// When the download started
+    pub started_at: DateTime<Utc>,
+    /// Last activity
+    pub last_activity: DateTime<Utc>,
+}
+
+/// Manager for chunked downloads with progress tracking
+pub struct ChunkedDownloadManager {
+    /// Configuration
+    config: DownloadConfig,
+    /// Oxen backend (Subprocess or FFI)
+    backend: Box<dyn OxenBackend>,
+}
+
+impl ChunkedDownloadManager {
+    /// Create a new chunked download manager
+    pub fn new(config: DownloadConfig) -> Result<Self> {
+        if !config.state_dir.exists() {
+            fs::create_dir_all(&config.state_dir)
+                .context("Failed to create download state directory")?;
+        }
+
+        let backend =
+            create_backend_with_network_timeout(config.backend_type, config.network_timeout_secs)
+                .context("Failed to create Oxen backend")?;
+
+        Ok(Self { config, backend })
+    }
+
+    /// Create with default configuration (Subprocess backend)
+    pub fn with_defaults() -> Result<Self> {
+        Self::new(DownloadConfig::default())
+    }
+
+    /// Create with FFI backend for better performance
+    #[cfg(feature = "ffi")]
+    pub fn with_ffi() -> Result<Self> {
+        Self::new(DownloadConfig::with_ffi_backend())
+    }
+
+    /// Pull `remote`/`branch` into `repo_path`, resuming an interrupted pull
+    ///
+    /// `progress_callback` is called when the pull starts, for each update
+    /// oxen prints while it runs and once it has finished. On failure the
+    /// session is kept, so running it again pulls only what is missing.
+    pub fn download_with_progress<F>(
+        &self,
+        repo_path: &Path,
+        remote: &str,
+        branch: &str,
+        progress_callback: F,
+    ) -> Result<DownloadResult>
+    where
+        F: Fn(DownloadProgress) + Sync,
+    {
+        crate::info!(
+            "Starting download from {}/{} using {} backend",
+            remote,
+            branch,
+            self.backend.name()
+        );
+
+        let policy = RetryPolicy::new(
+            self.config.max_retries,
+            self.config.initial_backoff_ms,
+            self.config.max_backoff_ms,
+        )
+        .set_verbose(self.config.verbose);
+        self.transfer(repo_path, remote, branch, progress_callback, |sender| {
+            AdaptiveRetryPolicy::with_policy(policy).execute(|| {
+                self.backend
+                    .pull_with_progress(repo_path, Some(remote), Some(branch), sender)
+            })
+        })
+    }
+
+    /// Run `pull`, tracking the progress it sends down the channel
+    fn transfer<F, P>(
+        &self,
+        repo_path: &Path,
+        remote: &str,
+        branch: &str,
+        progress_callback: F,
+        pull: P,
+    ) -> Result<DownloadResult>
+    where
+        F: Fn(DownloadProgress) + Sync,
+        P: FnOnce(&mpsc::Sender<ProgressEvent>) -> Result<()>,
+    {
+        let mut session = self.load_or_create_session(repo_path, remote, branch);
+        let resumed = session.attempts > 0;
+        session.attempts += 1;
+        session.status = DownloadStatus::InProgress;
+        session.last_activity = Utc::now();
+        self.save_session(repo_path, &session)?;
+
+        let prior_bytes = session.bytes_downloaded;
+        let session_id = session.id.clone();
+        let start = Instant::now();
+        let progress_at = |received: u64, rate: Option<f64>| {
+            let elapsed = start.elapsed();
+            DownloadProgress {
+                session_id: session_id.clone(),
+                bytes_downloaded: prior_bytes + received,
+                bytes_this_run: received,
+                bandwidth_bps: rate.or_else(|| {
+                    (elapsed.as_secs_f64() > 0.0).then(|| received as f64 / elapsed.as_secs_f64())
+                }),
+                elapsed,
+                resumed,
+            }
+        };
+        progress_callback(progress_at(0, None));
+
+        // Report on another thread while the pull blocks this one
+        let session_file = self.session_file_path(repo_path);
+        let mut snapshot = session.clone();
+        let (progress_at, callback) = (&progress_at, &progress_callback);
+        let (result, latest) = std::thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel::<ProgressEvent>();
+            let reporter = scope.spawn(move || {
+                let mut latest = ProgressEvent::default();
+                let mut saved_at: Option<Instant> = None;
+                for event in receiver {
+                    latest.merge(&event);
+                    let received = latest.bytes.unwrap_or(0);
+                    if saved_at.is_none_or(|at| at.elapsed() >= SESSION_SAVE_INTERVAL) {
+                        snapshot.bytes_downloaded = prior_bytes + received;
+                        snapshot.last_activity = Utc::now();
+                        let _ = write_session(&session_file, &snapshot);
+                        saved_at = Some(Instant::now());
+                    }
+                    callback(progress_at(received, latest.bytes_per_second));
+                }
+                latest
+            });
+
+            let result = pull(&sender);
+            // Ends the loop above
+            drop(sender);
+            (result, reporter.join().unwrap_or_default())
+        });
+
+        // Oxen's last redraw may stop short of the total it finished with
+        let received = match result {
+            Ok(()) => latest.total_bytes.or(latest.bytes),
+            Err(_) => latest.bytes,
+        }
+        .unwrap_or(0);
+        let duration = start.elapsed();
+        session.bytes_downloaded = prior_bytes + received;
+        session.last_activity = Utc::now();
+        if received > 0 && duration.as_secs_f64() > 0.0 {
+            session.add_bandwidth_sample(received as f64 / duration.as_secs_f64());
+        }
+
+        match result {
+            Ok(()) => {
+                progress_callback(progress_at(received, None));
+                self.clear_session(repo_path)?;
+                crate::info!(
+                    "Download completed: {}",
+                    UploadProgress::bytes_string(session.bytes_downloaded)
+                );
+                Ok(DownloadResult {
+                    bytes_downloaded: session.bytes_downloaded,
+                    duration,
+                    average_bandwidth: session.average_bandwidth(),
+                    resumed,
+                })
+            },
+            Err(e) => {
+                session.status = DownloadStatus::Failed;
+                session.last_error = Some(e.to_string());
+                self.save_session(repo_path, &session)?;
+                Err(e)
+            },
+        }
+    }
+
+    /// Check if there's a resumable session
+    pub fn has_resumable_session(&self, repo_path: &Path) -> bool {
+        self.get_resumable_session_info(repo_path).is_some()
+    }
+
+    /// Get information about a resumable session
+    pub fn get_resumable_session_info(&self, repo_path: &Path) -> Option<DownloadSessionInfo> {
+        let session = self.read_session(repo_path)?;
+        if session.status == DownloadStatus::Completed {
+            return None;
+        }
+        Some(DownloadSessionInfo {
+            id: session.id,
+            remote: session.remote,
+            branch: session.branch,
+            bytes_downloaded: session.bytes_downloaded,
+            attempts: session.attempts,
+            started_at: session.started_at,
+            last_activity: session.last_activity,
+        })
+    }
+
+    /// Clear a resumable session
+    pub fn clear_session(&self, repo_path: &Path) -> Result<()> {
+        let session_file = self.session_file_path(repo_path);
+        if session_file.exists() {
+            fs::remove_file(&session_file).context("Failed to remove download session file")?;
+        }
+        Ok(())
+    }
+
+    /// The saved session, if it's for the same remote and branch
+    fn load_or_create_session(
+        &self,
+        repo_path: &Path,
+        remote: &str,
+        branch: &str,
+    ) -> DownloadSession {
+        match self.read_session(repo_path) {
+            Some(session)
+                if session.remote == remote
+                    && session.branch == branch
+                    && session.status != DownloadStatus::Completed =>
+            {
+                crate::info!("Resuming download session: {}", &session.id[..8]);
+                session
+            },
+            _ => DownloadSession::new(repo_path, remote, branch),
+        }
+    }
+
+    fn read_session(&self, repo_path: &Path) -> Option<DownloadSession> {
+        let content = fs::read_to_string(self.session_file_path(repo_path)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Get path to session file
+    fn session_file_path(&self, repo_path: &Path) -> PathBuf {
+        // Use repository path hash as session filename
+        let repo_hash = format!("{:x}", md5::compute(repo_path.to_string_lossy().as_bytes()));
+        self.config.state_dir.join(format!("{}.json", repo_hash))
+    }
+
+    /// Save a session to disk
+    fn save_session(&self, repo_path: &Path, session: &DownloadSession) -> Result<()> {
+        write_session(&self.session_file_path(repo_path), session)
+    }
+}
+
+fn write_session(path: &Path, session: &DownloadSession) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(session).context("Failed to serialize download session")?;
+    fs::write(path, json).context("Failed to write download session file")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    fn test_manager(state_dir: &Path) -> ChunkedDownloadManager {
+        ChunkedDownloadManager::new(DownloadConfig {
+            state_dir: state_dir.to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    /// What oxen prints as it receives `bytes` of `total`
+    fn receive(progress: &mpsc::Sender<ProgressEvent>, bytes: u64, total: u64) {
+        let line = format!("{} B / {} B", bytes, total);
+        progress.send(ProgressEvent::parse(&line).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_interrupted_download_resumes() {
+        let state = TempDir::new().unwrap();
+        let repo = TempDir::new().unwrap();
+        let manager = test_manager(state.path());
+
+        let failed = manager.transfer(
+            repo.path(),
+            "origin",
+            "main",
+            |_| {},
+            |progress| {
+                receive(progress, 1000, 3000);
+                Err(anyhow!("connection reset"))
+            },
+        );
+        assert!(failed.is_err());
+        let info = manager.get_resumable_session_info(repo.path()).unwrap();
+        assert_eq!(info.bytes_downloaded, 1000);
+        assert_eq!(info.attempts, 1);
+
+        let updates = Mutex::new(Vec::new());
+        let result = manager
+            .transfer(
+                repo.path(),
+                "origin",
+                "main",
+                |progress| updates.lock().unwrap().push(progress),
+                |progress| {
+                    receive(progress, 1500, 2000);
+                    Ok(())
+                },
+            )
+            .unwrap();
+        assert!(result.resumed);
+        assert_eq!(result.bytes_downloaded, 3000);
+        assert!(result.average_bandwidth.is_some());
+        assert!(!manager.has_resumable_session(repo.path()));
+
+        let updates = updates.into_inner().unwrap();
+        assert_eq!(updates.first().unwrap().bytes_downloaded, 1000);
+        assert!(updates.iter().any(|p| p.bytes_downloaded == 2500));
+        assert_eq!(updates.last().unwrap().bytes_this_run, 2000);
+        assert!(updates.iter().all(|p| p.session_id == info.id && p.resumed));
+    }
+
+    #[test]
+    fn test_other_branch_starts_a_new_session() {
+        let state = TempDir::new().unwrap();
+        let repo = TempDir::new().unwrap();
+        let manager = test_manager(state.path());
+
+        let _ = manager.transfer(
+            repo.path(),
+            "origin",
+            "main",
+            |_| {},
+            |_| Err(anyhow!("timeout")),
+        );
+        let result = manager
+            .transfer(repo.path(), "origin", "mix-v2", |_| {}, |_| Ok(()))
+            .unwrap();
+        assert!(!result.resumed);
+        assert_eq!(result.bytes_downloaded, 0);
+    }
+}
@@ -38,12 +38,18 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use crate::oxen_backend::{create_backend, BackendType, OxenBackend};
+use crate::network_resilience::{AdaptiveRetryPolicy, RetryPolicy};
+use crate::oxen_backend::{
+    create_backend_with_network_timeout, BackendType, OxenBackend, ProgressEvent,
+};
+use auxin_config::{Config, NetworkClass};
 
 // Suppress unused import warning for Colorize (used by macros)
 #[allow(unused_imports)]
@@ -58,6 +64,10 @@ const DEFAULT_CHUNK_SIZE: u64 = 100 * 1024 * 1024;
 /// Minimum file size to enable chunked tracking (50 MB)
 const MIN_CHUNKED_SIZE: u64 = 50 * 1024 * 1024;
 
+/// How recently an in-progress session must have been saved for its push
+/// to count as still running
+pub const ACTIVE_WINDOW_SECS: i64 = 120;
+
 /// Configuration for chunked uploads
 #[derive(Debug, Clone)]
 pub struct UploadConfig {
@@ -69,6 +79,11 @@ pub struct UploadConfig {
     pub state_dir: PathBuf,
     /// Maximum retries per chunk
     pub max_retries: u32,
+    /// Delay before the first retry, doubling up to `max_backoff_ms`
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Time limit for the push itself
+    pub network_timeout_secs: u64,
     /// Enable verbose logging
     pub verbose: bool,
     /// Backend type (Subprocess or FFI)
@@ -83,6 +98,9 @@ impl Default for UploadConfig {
             min_chunked_size: MIN_CHUNKED_SIZE,
             state_dir: home.join(DEFAULT_STATE_DIR),
             max_retries: 3,
+            initial_backoff_ms: 2000,
+            max_backoff_ms: 16000,
+            network_timeout_secs: 120,
             verbose: false,
             backend_type: BackendType::default(),
         }
@@ -90,6 +108,18 @@ impl Default for UploadConfig {
 }
 
 impl UploadConfig {
+    /// Default config with the `[network.push]` retry settings applied
+    pub fn from_config(config: &Config) -> Self {
+        let retry = config.retry_settings(NetworkClass::Push);
+        Self {
+            max_retries: retry.max_retries,
+            initial_backoff_ms: retry.initial_backoff_ms,
+            max_backoff_ms: retry.max_backoff_ms,
+            network_timeout_secs: retry.timeout_s,
+            ..Default::default()
+        }
+    }
+
     /// Create config with FFI backend (faster, requires ffi feature)
     pub fn with_ffi_backend() -> Self {
         Self {
@@ -134,6 +164,9 @@ pub enum UploadStatus {
 }
 
 /// Overall upload session state
+///
+/// Saved as JSON so an interrupted upload can resume, possibly under another
+/// auxin version.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadSession {
     /// Unique session ID
@@ -158,6 +191,10 @@ pub struct UploadSession {
     pub status: UploadStatus,
     /// Bandwidth samples (bytes per second)
     pub bandwidth_samples: Vec<f64>,
+
+    /// Unknown fields of the saved session
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl UploadSession {
@@ -175,6 +212,7 @@ impl UploadSession {
             last_activity: Utc::now(),
             status: UploadStatus::Pending,
             bandwidth_samples: Vec::new(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -207,6 +245,13 @@ impl UploadSession {
         Some((remaining_bytes as f64 / bandwidth) as u64)
     }
 
+    /// Whether a push is still working on this session: in progress, with
+    /// activity in the last [`ACTIVE_WINDOW_SECS`]
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.status == UploadStatus::InProgress
+            && (now - self.last_activity).num_seconds() < ACTIVE_WINDOW_SECS
+    }
+
     /// Add a bandwidth sample
     pub fn add_bandwidth_sample(&mut self, bytes_per_second: f64) {
         // Keep last 10 samples for moving average
@@ -251,6 +296,29 @@ impl UploadProgress {
         }
     }
 
+    /// This progress brought up to date with what oxen last reported
+    fn with_event(&self, event: &ProgressEvent) -> UploadProgress {
+        let bytes_uploaded = event.bytes.unwrap_or(self.bytes_uploaded);
+        let total_bytes = event.total_bytes.unwrap_or(self.total_bytes);
+        let percentage = event.percent_done().unwrap_or(if total_bytes > 0 {
+            bytes_uploaded as f64 / total_bytes as f64 * 100.0
+        } else {
+            self.percentage
+        });
+
+        UploadProgress {
+            session_id: self.session_id.clone(),
+            percentage: percentage.min(100.0),
+            bytes_uploaded,
+            total_bytes,
+            current_file: self.current_file.clone(),
+            bandwidth_bps: event.bytes_per_second.or(self.bandwidth_bps),
+            eta_seconds: event.eta.map(|eta| eta.as_secs()).or(self.eta_seconds),
+            files_completed: event.files.map_or(self.files_completed, |n| n as usize),
+            total_files: event.total_files.map_or(self.total_files, |n| n as usize),
+        }
+    }
+
     /// Format ETA as human-readable string
     pub fn eta_string(&self) -> String {
         match self.eta_seconds {
@@ -304,7 +372,8 @@ impl ChunkedUploadManager {
 
         // Create the appropriate backend
         let backend =
-            create_backend(config.backend_type).context("Failed to create Oxen backend")?;
+            create_backend_with_network_timeout(config.backend_type, config.network_timeout_secs)
+                .context("Failed to create Oxen backend")?;
 
         Ok(Self {
             config,
@@ -402,6 +471,9 @@ impl ChunkedUploadManager {
     }
 
     /// Upload with progress callback
+    ///
+    /// The callback is called before and after the push and, while it runs,
+    /// from another thread for each progress line oxen prints.
     pub fn upload_with_progress<F>(
         &mut self,
         repo_path: &Path,
@@ -410,7 +482,7 @@ impl ChunkedUploadManager {
         progress_callback: F,
     ) -> Result<UploadResult>
     where
-        F: Fn(UploadProgress),
+        F: Fn(UploadProgress) + Sync,
     {
         // Get or create session
         self.get_or_create_session(repo_path, remote, branch)?;
@@ -458,7 +530,32 @@ impl ChunkedUploadManager {
             self.backend.name()
         );
 
-        let push_result = self.backend.push(repo_path, Some(remote), Some(branch));
+        let policy = RetryPolicy::new(
+            self.config.max_retries,
+            self.config.initial_backoff_ms,
+            self.config.max_backoff_ms,
+        )
+        .set_verbose(self.config.verbose);
+        let backend = &self.backend;
+        let before_push = self.current_progress();
+        let callback = &progress_callback;
+        let push_result = std::thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel::<ProgressEvent>();
+            scope.spawn(move || {
+                let mut latest = ProgressEvent::default();
+                for event in receiver {
+                    latest.merge(&event);
+                    if let Some(progress) = &before_push {
+                        callback(progress.with_event(&latest));
+                    }
+                }
+            });
+
+            // The sender is dropped on return, ending the loop above
+            AdaptiveRetryPolicy::with_policy(policy).execute(|| {
+                backend.push_with_progress(repo_path, Some(remote), Some(branch), &sender)
+            })
+        });
 
         // Calculate bandwidth
         let elapsed = start_time.elapsed();
@@ -637,7 +734,14 @@ impl ChunkedUploadManager {
     where
         F: Fn(UploadProgress),
     {
-        if let Some(session) = &self.current_session {
+        if let Some(progress) = self.current_progress() {
+            callback(progress);
+        }
+    }
+
+    /// Progress of the current session, from what's been recorded so far
+    fn current_progress(&self) -> Option<UploadProgress> {
+        self.current_session.as_ref().map(|session| {
             let files_completed = session
                 .files
                 .iter()
@@ -650,7 +754,7 @@ impl ChunkedUploadManager {
                 .find(|f| f.status == UploadStatus::InProgress)
                 .map(|f| f.path.clone());
 
-            let progress = UploadProgress {
+            UploadProgress {
                 session_id: session.id.clone(),
                 percentage: session.percentage(),
                 bytes_uploaded: session.bytes_uploaded,
@@ -660,10 +764,8 @@ impl ChunkedUploadManager {
                 eta_seconds: session.estimated_remaining_seconds(),
                 files_completed,
                 total_files: session.files.len(),
-            };
-
-            callback(progress);
-        }
+            }
+        })
     }
 
     /// Get path to session file
@@ -721,6 +823,57 @@ pub struct UploadSessionInfo {
     pub last_activity: DateTime<Utc>,
 }
 
+/// Unfinished upload sessions for every repository, oldest activity first
+///
+/// A session is written when an upload starts and removed when it finishes,
+/// so one left behind in `state_dir` is an interrupted push that
+/// `auxin push` resumes.
+pub fn resumable_sessions(state_dir: &Path) -> Result<Vec<UploadSession>> {
+    if !state_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut sessions = Vec::new();
+    for entry in fs::read_dir(state_dir).context("Failed to read upload state directory")? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let session = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<UploadSession>(&content).ok());
+        if let Some(session) = session.filter(|s| s.status != UploadStatus::Completed) {
+            sessions.push(session);
+        }
+    }
+    sessions.sort_by_key(|s| s.last_activity);
+    Ok(sessions)
+}
+
+/// Delete the saved session with ID `id`, so the next `auxin push` of its
+/// repository starts over instead of resuming
+///
+/// Returns `false` when no such session is saved.
+pub fn discard_session(state_dir: &Path, id: &str) -> Result<bool> {
+    if !state_dir.exists() {
+        return Ok(false);
+    }
+    for entry in fs::read_dir(state_dir).context("Failed to read upload state directory")? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let matches = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<UploadSession>(&content).ok())
+            .is_some_and(|session| session.id == id);
+        if matches {
+            fs::remove_file(&path).context("Failed to remove upload session file")?;
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -811,6 +964,31 @@ mod tests {
         assert_eq!(UploadProgress::bytes_string(500_000_000), "500.0 MB");
     }
 
+    #[test]
+    fn test_upload_progress_with_event() {
+        let progress = UploadProgress {
+            session_id: "test".to_string(),
+            percentage: 0.0,
+            bytes_uploaded: 0,
+            total_bytes: 1_000_000,
+            current_file: None,
+            bandwidth_bps: None,
+            eta_seconds: None,
+            files_completed: 0,
+            total_files: 4,
+        };
+
+        let event = ProgressEvent::parse("250 KB/1 MB 50 KB/s eta 15s").unwrap();
+        let updated = progress.with_event(&event);
+        assert_eq!(updated.session_id, "test");
+        assert_eq!(updated.bytes_uploaded, 250_000);
+        assert_eq!(updated.percentage, 25.0);
+        assert_eq!(updated.bandwidth_string(), "50.0 KB/s");
+        assert_eq!(updated.eta_seconds, Some(15));
+        // Oxen didn't report files, so the session's counts stand
+        assert_eq!(updated.total_files, 4);
+    }
+
     #[test]
     fn test_chunked_upload_manager_creation() {
         let temp_dir = TempDir::new().unwrap();
@@ -854,6 +1032,23 @@ mod tests {
         assert!(!manager.has_resumable_session(repo_path));
     }
 
+    #[test]
+    fn test_discard_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut session = UploadSession::new(Path::new("/test/repo"), "origin", "main");
+        session.status = UploadStatus::InProgress;
+        let file = temp_dir.path().join("abc.json");
+        fs::write(&file, serde_json::to_string(&session).unwrap()).unwrap();
+
+        assert!(session.is_active(Utc::now()));
+        assert!(!session.is_active(Utc::now() + chrono::Duration::minutes(5)));
+
+        assert!(!discard_session(temp_dir.path(), "other").unwrap());
+        assert!(discard_session(temp_dir.path(), &session.id).unwrap());
+        assert!(!file.exists());
+        assert!(resumable_sessions(temp_dir.path()).unwrap().is_empty());
+    }
+
     #[test]
     fn test_file_upload_state() {
         let state = FileUploadState {
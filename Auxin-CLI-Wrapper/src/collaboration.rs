@@ -31,7 +31,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::{CommitInfo, OxenSubprocess};
+use crate::identity::Identity;
+use crate::{AuthorMap, CommitInfo, OxenSubprocess};
 
 /// A project activity entry (commit, lock, comment, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,10 +116,11 @@ impl ActivityFeed {
             .log(repo_path, Some(limit))
             .context("Failed to fetch commit log")?;
 
+        let authors = AuthorMap::load_or_warn(repo_path).with_identity(Identity::current());
         let mut activities = Vec::new();
 
         for commit in commits {
-            let activity = self.commit_to_activity(&commit)?;
+            let activity = self.commit_to_activity(&commit, &authors)?;
             activities.push(activity);
         }
 
@@ -142,14 +144,15 @@ impl ActivityFeed {
     }
 
     /// Convert commit to activity
-    fn commit_to_activity(&self, commit: &CommitInfo) -> Result<Activity> {
+    fn commit_to_activity(&self, commit: &CommitInfo, authors: &AuthorMap) -> Result<Activity> {
         // Parse commit message to extract metadata
         let (message, metadata) = self.parse_commit_message(&commit.message);
 
         // Try to extract author from commit message
         // In real implementation, would use git log --format to get author
-        let author =
-            extract_author_from_message(&commit.message).unwrap_or_else(|| "unknown".to_string());
+        let author = extract_author_from_message(&commit.message)
+            .map(|author| authors.resolve(&author))
+            .unwrap_or_else(|| "unknown".to_string());
 
         Ok(Activity {
             id: commit.id.clone(),
@@ -202,17 +205,23 @@ impl TeamManager {
     }
 
     /// Discover team members from commit history
+    ///
+    /// Authors are folded together according to the repository's
+    /// [`AuthorMap`], so one person committing from several machines is
+    /// counted once.
     pub fn discover_team_members(&self, repo_path: &Path) -> Result<Vec<TeamMember>> {
         // Get commit history
         let commits = self
             .oxen
             .log(repo_path, Some(100))
             .context("Failed to fetch commit log")?;
+        let authors = AuthorMap::load_or_warn(repo_path).with_identity(Identity::current());
 
         let mut members_map: HashMap<String, TeamMember> = HashMap::new();
 
         for commit in commits {
             if let Some(author) = extract_author_from_message(&commit.message) {
+                let author = authors.resolve(&author);
                 members_map
                     .entry(author.clone())
                     .and_modify(|m| m.commit_count += 1)
@@ -242,7 +251,7 @@ impl Default for TeamManager {
 /// A team member working on the project
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamMember {
-    /// Member name (username@hostname)
+    /// Member name (username@hostname, or its canonical name from the author map)
     pub name: String,
 
     /// Number of commits
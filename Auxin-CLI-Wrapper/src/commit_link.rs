@@ -0,0 +1,226 @@
+//! Shareable commit references: `auxin link`
+//!
+//! A commit is referenced as `auxin://repo/<namespace>/<name>/commit/<id>`,
+//! or as a web URL on the configured auxin-server
+//! (`<server>/<namespace>/<name>?commit=<id>`) that opens the commit in the
+//! web UI. `show`, `restore` and `compare` accept either form wherever they
+//! take a commit ID, so a link pasted from chat works as-is.
+//!
+//! Repositories are identified as `<default_namespace>/<directory name>`,
+//! the same as `auxin server backfill-metadata`.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use url::Url;
+
+/// URI scheme of canonical references
+pub const SCHEME: &str = "auxin";
+
+/// Clipboard tools tried in order: macOS, Wayland, X11
+const CLIPBOARD_TOOLS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// A commit in a specific repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitLink {
+    pub namespace: String,
+    pub name: String,
+    pub commit: String,
+}
+
+impl CommitLink {
+    /// Link to `commit` in the repository at `repo_root`
+    pub fn for_repo(config: &auxin_config::Config, repo_root: &Path, commit: &str) -> Result<Self> {
+        Ok(Self {
+            namespace: config.cli.default_namespace.clone(),
+            name: repo_name(repo_root)?,
+            commit: commit.to_string(),
+        })
+    }
+
+    /// `auxin://repo/<namespace>/<name>/commit/<id>`
+    pub fn uri(&self) -> String {
+        let mut url = Url::parse(&format!("{}://repo", SCHEME)).expect("valid base URI");
+        url.path_segments_mut().expect("URI has a host").extend([
+            &self.namespace,
+            &self.name,
+            "commit",
+            &self.commit,
+        ]);
+        url.to_string()
+    }
+
+    /// The commit in the web UI of `server_url`
+    pub fn web_url(&self, server_url: &str) -> Result<String> {
+        let mut url = Url::parse(server_url)
+            .with_context(|| format!("Invalid server URL: {}", server_url))?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid server URL: {}", server_url))?
+            .pop_if_empty()
+            .extend([&self.namespace, &self.name]);
+        url.query_pairs_mut().append_pair("commit", &self.commit);
+        Ok(url.to_string())
+    }
+
+    /// Parse either link form; `None` for anything else, such as a plain
+    /// commit ID
+    pub fn parse(link: &str) -> Option<Self> {
+        let url = Url::parse(link.trim()).ok()?;
+        let segments: Vec<String> = url.path_segments()?.map(percent_decode).collect();
+
+        let (namespace, name, commit) = match url.scheme() {
+            SCHEME if url.host_str() == Some("repo") => match segments.as_slice() {
+                [namespace, name, kind, commit] if kind == "commit" => {
+                    (namespace.clone(), name.clone(), commit.clone())
+                },
+                _ => return None,
+            },
+            "http" | "https" => {
+                let commit = url
+                    .query_pairs()
+                    .find(|(key, _)| key == "commit")
+                    .map(|(_, value)| value.to_string())?;
+                match segments.as_slice() {
+                    [.., namespace, name] => (namespace.clone(), name.clone(), commit),
+                    _ => return None,
+                }
+            },
+            _ => return None,
+        };
+
+        let valid = |s: &str| !s.is_empty();
+        (valid(&namespace) && valid(&name) && valid(&commit)).then_some(Self {
+            namespace,
+            name,
+            commit,
+        })
+    }
+}
+
+impl fmt::Display for CommitLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.uri())
+    }
+}
+
+/// The commit ID in a `show`/`restore`/`compare` argument, which may be a
+/// plain ID or a link; warns when the link is for another repository
+pub fn resolve_commit_arg(arg: &str) -> String {
+    let Some(link) = CommitLink::parse(arg) else {
+        return arg.to_string();
+    };
+
+    let config = auxin_config::Config::load().unwrap_or_default();
+    let here = std::env::current_dir()
+        .ok()
+        .and_then(|dir| CommitLink::for_repo(&config, &dir, &link.commit).ok());
+    if let Some(here) = here {
+        if here.namespace != link.namespace || here.name != link.name {
+            crate::warn!(
+                "Link is for {}/{}, but this project is {}/{}",
+                link.namespace,
+                link.name,
+                here.namespace,
+                here.name
+            );
+        }
+    }
+    link.commit
+}
+
+/// Put `text` on the system clipboard; returns the tool used
+pub fn copy_to_clipboard(text: &str) -> Result<&'static str> {
+    for (tool, args) in CLIPBOARD_TOOLS {
+        let Ok(mut child) = Command::new(tool)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(tool);
+        }
+    }
+    bail!("No clipboard tool found (tried pbcopy, wl-copy, xclip, xsel)")
+}
+
+fn repo_name(repo_root: &Path) -> Result<String> {
+    repo_root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .context("Failed to determine repository name")
+}
+
+/// Decode `%XX` escapes in a URL path segment
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            },
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_round_trip() {
+        let link = CommitLink {
+            namespace: "studio".to_string(),
+            name: "My Song.logicx".to_string(),
+            commit: "3f9a1c2e7b".to_string(),
+        };
+
+        let uri = link.uri();
+        assert_eq!(
+            uri,
+            "auxin://repo/studio/My%20Song.logicx/commit/3f9a1c2e7b"
+        );
+        assert_eq!(CommitLink::parse(&uri), Some(link.clone()));
+
+        let web = link.web_url("http://192.168.1.20:3000/").unwrap();
+        assert_eq!(
+            web,
+            "http://192.168.1.20:3000/studio/My%20Song.logicx?commit=3f9a1c2e7b"
+        );
+        assert_eq!(CommitLink::parse(&web), Some(link.clone()));
+
+        let prefixed = link.web_url("https://example.com/auxin").unwrap();
+        assert_eq!(CommitLink::parse(&prefixed), Some(link));
+
+        assert_eq!(CommitLink::parse("3f9a1c2e"), None);
+        assert_eq!(CommitLink::parse("auxin://repo/studio/song"), None);
+        assert_eq!(CommitLink::parse("http://server:3000/studio/song"), None);
+        assert_eq!(resolve_commit_arg("3f9a1c2e"), "3f9a1c2e");
+    }
+}
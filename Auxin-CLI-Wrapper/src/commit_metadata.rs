@@ -1,4 +1,8 @@
+use crate::music_theory::Key;
+use anyhow::{bail, Result};
+use auxin_config::{MetadataField, MetadataFieldType};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Structured metadata for Logic Pro project commits.
 ///
@@ -19,6 +23,7 @@ use serde::{Deserialize, Serialize};
 /// Sample Rate: <rate> Hz
 /// Key: <key_signature>
 /// Tags: <tag1>, <tag2>, ...
+/// <custom field>: <value>
 /// ```
 ///
 /// # Examples
@@ -45,7 +50,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// # Serialization
 ///
-/// Supports JSON serialization via Serde for storage and IPC.
+/// Supports JSON serialization via Serde for storage and IPC. The JSON form
+/// is stable: it round-trips losslessly, and fields written by newer versions
+/// are kept in `other` rather than dropped (see `tests/compat.rs`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitMetadata {
     /// User-provided commit message (primary description)
@@ -74,6 +81,14 @@ pub struct CommitMetadata {
     /// Path to bounce audio file relative to .auxin/bounces/
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bounce_path: Option<String>,
+
+    /// Values of custom fields defined in `[metadata_fields]`, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+
+    /// Fields added by newer versions of auxin
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl CommitMetadata {
@@ -111,6 +126,8 @@ impl CommitMetadata {
             timestamp: None,
             thumbnail_path: None,
             bounce_path: None,
+            custom: BTreeMap::new(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -160,11 +177,13 @@ impl CommitMetadata {
 
     /// Sets the musical key signature.
     ///
-    /// Builder pattern method that consumes and returns self.
+    /// Builder pattern method that consumes and returns self. Recognized keys
+    /// are stored in canonical form (see [`crate::music_theory`]); anything
+    /// else is stored as given.
     ///
     /// # Arguments
     ///
-    /// * `key` - Musical key (e.g., "C Major", "A Minor", "F# Major")
+    /// * `key` - Musical key (e.g., "C Major", "A Minor", "F#m", "Bb")
     ///
     /// # Examples
     ///
@@ -172,11 +191,11 @@ impl CommitMetadata {
     /// use auxin::CommitMetadata;
     ///
     /// let commit = CommitMetadata::new("Melody draft")
-    ///     .with_key_signature("D Minor");
+    ///     .with_key_signature("d min");
     /// assert_eq!(commit.key_signature, Some("D Minor".to_string()));
     /// ```
     pub fn with_key_signature(mut self, key: impl Into<String>) -> Self {
-        self.key_signature = Some(key.into());
+        self.key_signature = Some(Key::canonicalize(&key.into()));
         self
     }
 
@@ -231,6 +250,92 @@ impl CommitMetadata {
         self
     }
 
+    /// Sets a custom field
+    ///
+    /// Builder pattern method that consumes and returns self. Check the
+    /// fields against the project's definitions with `validate_fields`.
+    pub fn with_field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.custom.insert(name.into(), value.into());
+        self
+    }
+
+    /// Checks custom fields against the project's `[metadata_fields]`
+    ///
+    /// Every field must be defined and have a value of its type, and every
+    /// required field must be set. Names match case-insensitively and are
+    /// returned spelled as defined. All problems are reported together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use auxin::CommitMetadata;
+    /// use auxin_config::{MetadataField, MetadataFieldType};
+    ///
+    /// let fields = vec![MetadataField {
+    ///     name: "revision".to_string(),
+    ///     field_type: MetadataFieldType::Integer,
+    ///     required: true,
+    /// }];
+    ///
+    /// let commit = CommitMetadata::new("Client notes").with_field("Revision", "3");
+    /// assert_eq!(commit.validate_fields(&fields).unwrap().custom["revision"], "3");
+    ///
+    /// assert!(CommitMetadata::new("No revision").validate_fields(&fields).is_err());
+    /// ```
+    pub fn validate_fields(mut self, fields: &[MetadataField]) -> Result<Self> {
+        let mut problems = Vec::new();
+        let mut custom = BTreeMap::new();
+        for (name, value) in std::mem::take(&mut self.custom) {
+            let Some(field) = fields.iter().find(|f| f.name.eq_ignore_ascii_case(&name)) else {
+                let defined: Vec<&str> = fields.iter().map(|f| f.name.as_str()).collect();
+                problems.push(if defined.is_empty() {
+                    format!("Unknown metadata field '{}' (none are defined)", name)
+                } else {
+                    format!(
+                        "Unknown metadata field '{}' (expected {})",
+                        name,
+                        defined.join(", ")
+                    )
+                });
+                continue;
+            };
+            let value = value.trim().to_string();
+            let valid = match field.field_type {
+                MetadataFieldType::Text => !value.is_empty() && !value.contains('\n'),
+                MetadataFieldType::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+                MetadataFieldType::Integer => value.parse::<i64>().is_ok(),
+                MetadataFieldType::Boolean => matches!(
+                    value.to_lowercase().as_str(),
+                    "true" | "false" | "yes" | "no"
+                ),
+            };
+            if !valid {
+                problems.push(format!(
+                    "'{}' must be {}, got '{}'",
+                    field.name,
+                    match field.field_type {
+                        MetadataFieldType::Text => "a single line of text",
+                        MetadataFieldType::Number => "a number",
+                        MetadataFieldType::Integer => "a whole number",
+                        MetadataFieldType::Boolean => "true or false",
+                    },
+                    value
+                ));
+            }
+            custom.insert(field.name.clone(), value);
+        }
+        for field in fields.iter().filter(|f| f.required) {
+            if !custom.contains_key(&field.name) {
+                problems.push(format!("Missing required metadata field '{}'", field.name));
+            }
+        }
+        if !problems.is_empty() {
+            bail!("{}", problems.join("\n"));
+        }
+        self.custom = custom;
+        Ok(self)
+    }
+
     /// Formats the metadata as a structured commit message for version control.
     ///
     /// Generates a multi-line string with the message followed by metadata fields.
@@ -255,7 +360,8 @@ impl CommitMetadata {
     ///
     /// # Field Order
     ///
-    /// Metadata always appears in this order: BPM, Sample Rate, Key, Tags
+    /// Metadata always appears in this order: BPM, Sample Rate, Key, Tags,
+    /// then custom fields by name
     ///
     /// # Examples
     ///
@@ -310,6 +416,10 @@ impl CommitMetadata {
             metadata_lines.push(format!("Tags: {}", self.tags.join(", ")));
         }
 
+        for (name, value) in &self.custom {
+            metadata_lines.push(format!("{}: {}", name, value));
+        }
+
         if !metadata_lines.is_empty() {
             msg.push_str("\n\n");
             msg.push_str(&metadata_lines.join("\n"));
@@ -330,6 +440,7 @@ impl CommitMetadata {
     /// - Lines starting with `Sample Rate:` are parsed as Hz (u32, "Hz" suffix optional)
     /// - Lines starting with `Key:` are parsed as key signature (string)
     /// - Lines starting with `Tags:` are parsed as comma-separated list
+    /// - Other `Name: value` lines in the metadata section are custom fields
     /// - All other lines (before metadata section) are treated as the message
     /// - Parsing is lenient: invalid values result in None, not errors
     ///
@@ -425,7 +536,13 @@ impl CommitMetadata {
                         .filter(|s| !s.is_empty())
                         .collect();
                 }
-            } else if !in_metadata && !line.trim().is_empty() {
+            } else if in_metadata {
+                if let Some((name, value)) = line.split_once(": ") {
+                    metadata
+                        .custom
+                        .insert(name.trim().to_string(), value.trim().to_string());
+                }
+            } else if !line.trim().is_empty() {
                 if !main_message.is_empty() {
                     main_message.push('\n');
                 }
@@ -981,4 +1098,59 @@ mod tests {
         assert_eq!(metadata.key_signature, Some("C Major".to_string()));
         assert_eq!(metadata.tags.len(), 1);
     }
+
+    fn field(name: &str, field_type: MetadataFieldType, required: bool) -> MetadataField {
+        MetadataField {
+            name: name.to_string(),
+            field_type,
+            required,
+        }
+    }
+
+    #[test]
+    fn test_custom_fields_round_trip() {
+        let original = CommitMetadata::new("Client revisions")
+            .with_bpm(96.0)
+            .with_field("client", "Acme Records")
+            .with_field("mix engineer", "Sam");
+        let formatted = original.format_commit_message();
+        assert!(formatted.ends_with("BPM: 96\nclient: Acme Records\nmix engineer: Sam"));
+
+        let parsed = CommitMetadata::parse_commit_message(&formatted);
+        assert_eq!(parsed.message, "Client revisions");
+        assert_eq!(parsed.custom, original.custom);
+    }
+
+    #[test]
+    fn test_validate_fields() {
+        let fields = vec![
+            field("client", MetadataFieldType::Text, true),
+            field("revision", MetadataFieldType::Integer, false),
+            field("approved", MetadataFieldType::Boolean, false),
+        ];
+
+        let valid = CommitMetadata::new("Mix")
+            .with_field("Client", " Acme ")
+            .with_field("approved", "yes")
+            .validate_fields(&fields)
+            .unwrap();
+        assert_eq!(valid.custom.get("client").map(String::as_str), Some("Acme"));
+
+        let error = CommitMetadata::new("Mix")
+            .with_field("revision", "two")
+            .with_field("clinet", "Acme")
+            .validate_fields(&fields)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("'revision' must be a whole number, got 'two'"));
+        assert!(error.contains("Unknown metadata field 'clinet'"));
+        assert!(error.contains("Missing required metadata field 'client'"));
+
+        // Without definitions only field-less commits pass
+        assert!(CommitMetadata::new("Mix").validate_fields(&[]).is_ok());
+        assert!(CommitMetadata::new("Mix")
+            .with_field("client", "Acme")
+            .validate_fields(&[])
+            .is_err());
+    }
 }
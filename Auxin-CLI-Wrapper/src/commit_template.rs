@@ -0,0 +1,128 @@
+//! Commit templates: `auxin commit --like <commit>`
+//!
+//! During a mix session most commits share BPM, key, sample rate and tags
+//! with the one before. `--like` copies those fields from an earlier commit
+//! (including any `auxin metadata edit` changes to it) as defaults; flags
+//! given on the command line still win.
+
+use crate::metadata_edits::MetadataEdits;
+use crate::sketchup_metadata::SketchUpMetadata;
+use crate::CommitInfo;
+use anyhow::{anyhow, Result};
+
+/// Tags that describe one particular commit and aren't carried over
+const NON_INHERITED_TAGS: [&str; 1] = ["milestone"];
+
+/// Metadata defaults copied from a previous commit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommitTemplate {
+    pub bpm: Option<f32>,
+    pub sample_rate: Option<u32>,
+    pub key: Option<String>,
+    /// Comma-separated, as passed to `--tags`
+    pub tags: Option<String>,
+    pub units: Option<String>,
+}
+
+impl CommitTemplate {
+    /// Template from `commit`, with metadata edits applied
+    pub fn from_commit(commit: &CommitInfo, edits: &MetadataEdits) -> Self {
+        let metadata = edits.effective(commit);
+        let tags: Vec<&str> = metadata
+            .tags
+            .iter()
+            .map(String::as_str)
+            .filter(|t| !NON_INHERITED_TAGS.iter().any(|n| t.eq_ignore_ascii_case(n)))
+            .collect();
+
+        Self {
+            bpm: metadata.bpm,
+            sample_rate: metadata.sample_rate,
+            key: metadata.key_signature,
+            tags: (!tags.is_empty()).then(|| tags.join(", ")),
+            units: SketchUpMetadata::parse_commit_message(&commit.message).units,
+        }
+    }
+
+    /// Find the commit `reference` names in `history` (newest first):
+    /// `HEAD` for the latest commit, otherwise an ID or ID prefix
+    pub fn find<'a>(history: &'a [CommitInfo], reference: &str) -> Result<&'a CommitInfo> {
+        let found = if reference.eq_ignore_ascii_case("HEAD") {
+            history.first()
+        } else {
+            history.iter().find(|c| c.id.starts_with(reference))
+        };
+        found.ok_or_else(|| anyhow!("Commit '{}' not found", reference))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// One-line description of the copied fields, e.g. `120 BPM, D Minor`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(bpm) = self.bpm {
+            parts.push(format!("{} BPM", bpm));
+        }
+        if let Some(rate) = self.sample_rate {
+            parts.push(format!("{} Hz", rate));
+        }
+        if let Some(key) = &self.key {
+            parts.push(key.clone());
+        }
+        if let Some(units) = &self.units {
+            parts.push(units.clone());
+        }
+        if let Some(tags) = &self.tags {
+            parts.push(format!("tags: {}", tags));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata_edits::MetadataChange;
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_template_from_commit() {
+        let previous = commit(
+            "abc123",
+            "Mix v3\n\nBPM: 120\nSample Rate: 48000 Hz\nKey: D Major\nTags: mix, milestone",
+        );
+        let mut edits = MetadataEdits::default();
+        let template = CommitTemplate::from_commit(&previous, &edits);
+        assert_eq!(template.bpm, Some(120.0));
+        assert_eq!(template.sample_rate, Some(48000));
+        assert_eq!(template.key.as_deref(), Some("D Major"));
+        assert_eq!(template.tags.as_deref(), Some("mix"));
+        assert_eq!(template.summary(), "120 BPM, 48000 Hz, D Major, tags: mix");
+
+        // Corrected metadata is what gets copied
+        edits.edit(&previous, &[MetadataChange::SetKey("D Minor".to_string())], "me");
+        let template = CommitTemplate::from_commit(&previous, &edits);
+        assert_eq!(template.key.as_deref(), Some("D Minor"));
+
+        let plain = CommitTemplate::from_commit(&commit("def", "Plain"), &edits);
+        assert!(plain.is_empty());
+    }
+
+    #[test]
+    fn test_find_commit() {
+        let history = vec![commit("bbb222", "Newest"), commit("aaa111", "Oldest")];
+        assert_eq!(CommitTemplate::find(&history, "HEAD").unwrap().id, "bbb222");
+        assert_eq!(CommitTemplate::find(&history, "aaa").unwrap().id, "aaa111");
+        assert!(CommitTemplate::find(&history, "ccc").is_err());
+        assert!(CommitTemplate::find(&[], "HEAD").is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Single-value edits to config files that keep everything else as written
+//!
+//! `Config::save_to_file` writes out the whole merged configuration, which
+//! is fine for `auxin server set` but would copy user-level settings and
+//! secrets into a project file, and drops comments. The edits here change
+//! one `key` in one `[table]` and leave every other line alone.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// The user config file, `~/.auxin/config.toml`
+pub fn user_config_path() -> Result<PathBuf> {
+    Ok(dirs::home_dir()
+        .ok_or_else(|| anyhow!("Could not find home directory"))?
+        .join(".auxin/config.toml"))
+}
+
+/// `content` with `key` in `[table]` set to the string `value`, or removed
+/// when `value` is `None`
+///
+/// A missing key goes right under the table's header, and a missing table
+/// at the end. `table` is dotted, e.g. `servers.studio-a`; quoted headers
+/// such as `[servers."studio-a"]` match too.
+pub fn set_value(content: &str, table: &str, key: &str, value: Option<&str>) -> Result<String> {
+    set_toml(content, table, key, value.map(|v| toml::Value::String(v.to_string())))
+}
+
+/// [`set_value`] for any TOML value, such as a number
+pub fn set_toml(
+    content: &str,
+    table: &str,
+    key: &str,
+    value: Option<toml::Value>,
+) -> Result<String> {
+    let line_for = |value: &toml::Value| format!("{} = {}", key, value);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    let header = lines.iter().position(|line| header_of(line).as_deref() == Some(table));
+    match header {
+        Some(header) => {
+            let end = lines[header + 1..]
+                .iter()
+                .position(|line| header_of(line).is_some())
+                .map_or(lines.len(), |i| header + 1 + i);
+            let existing = (header + 1..end).find(|&i| key_of(&lines[i]) == Some(key));
+            match (existing, &value) {
+                (Some(i), Some(value)) => {
+                    let indent = &lines[i][..lines[i].len() - lines[i].trim_start().len()];
+                    lines[i] = format!("{}{}", indent, line_for(value));
+                }
+                (Some(i), None) => {
+                    lines.remove(i);
+                }
+                (None, Some(value)) => lines.insert(header + 1, line_for(value)),
+                (None, None) => {}
+            }
+        }
+        None => {
+            if let Some(value) = &value {
+                if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push(format!("[{}]", table));
+                lines.push(line_for(value));
+            }
+        }
+    }
+
+    let mut edited = lines.join("\n");
+    if !edited.is_empty() {
+        edited.push('\n');
+    }
+    // Keys set some other way, like an inline table, can't be edited here
+    let parsed: toml::Value = toml::from_str(&edited)
+        .map_err(|e| anyhow!("Can't set {}.{} in this file: {}", table, key, e))?;
+    let mut slot = Some(&parsed);
+    for part in table.split('.').chain([key]) {
+        slot = slot.and_then(|v| v.get(part));
+    }
+    if slot != value.as_ref() {
+        return Err(anyhow!("Can't set {}.{} in this file", table, key));
+    }
+    Ok(edited)
+}
+
+/// Set `key` in `[table]` of the config file at `path`, creating it if needed
+pub fn update_file(path: &Path, table: &str, key: &str, value: Option<&str>) -> Result<()> {
+    update_file_toml(path, table, key, value.map(|v| toml::Value::String(v.to_string())))
+}
+
+/// [`update_file`] for any TOML value
+pub fn update_file_toml(
+    path: &Path,
+    table: &str,
+    key: &str,
+    value: Option<toml::Value>,
+) -> Result<()> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let edited = set_toml(&content, table, key, value)
+        .with_context(|| format!("Failed to update {}", path.display()))?;
+    write_file(path, &edited)
+}
+
+/// Replace the file at `path` with `content`, written whole and renamed so
+/// nothing reads it half written
+pub fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, content).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to update {}", path.display()))
+}
+
+/// The table a `[header]` line opens, without quotes
+fn header_of(line: &str) -> Option<String> {
+    let line = line.trim();
+    let name = line.strip_prefix('[')?.split(']').next()?;
+    Some(name.split('.').map(|part| part.trim().trim_matches('"')).collect::<Vec<_>>().join("."))
+}
+
+/// The key a `key = value` line sets
+fn key_of(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+    let (key, _) = line.split_once('=')?;
+    Some(key.trim().trim_matches('"'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_value_keeps_other_lines() {
+        let content = "# Studio settings\n[cli]\nurl = \"http://a\"  # office\ntoken = \"abc\"\n\n\
+                       [servers.\"studio-a\"]\nurl = \"https://a.example\"\n";
+
+        let edited = set_value(content, "cli", "token", Some("keychain")).unwrap();
+        assert_eq!(edited, content.replace("\"abc\"", "\"keychain\""));
+
+        let edited = set_value(content, "servers.studio-a", "token", Some("keychain")).unwrap();
+        assert!(edited.ends_with(
+            "[servers.\"studio-a\"]\ntoken = \"keychain\"\nurl = \"https://a.example\"\n"
+        ));
+
+        let edited = set_value(content, "cli", "token", None).unwrap();
+        assert!(!edited.contains("token"));
+        assert!(edited.contains("# office"));
+
+        let edited = set_value("", "cli", "server", Some("studio-a")).unwrap();
+        assert_eq!(edited, "[cli]\nserver = \"studio-a\"\n");
+
+        let edited = set_toml("[ui.console]\n", "ui.console", "side_percent", Some(40.into()));
+        assert_eq!(edited.unwrap(), "[ui.console]\nside_percent = 40\n");
+
+        // An inline table can't be edited line by line
+        assert!(set_value("cli = { token = \"abc\" }\n", "cli", "token", Some("x")).is_err());
+    }
+}
@@ -1,7 +1,13 @@
+use crate::operation_history::{
+    HistoryOperation, OperationHistoryEntry, OperationHistoryManager, OperationResult,
+};
 use crate::OxenSubprocess;
+use auxin_oxen::MergeSide;
 use crate::remote_lock::RemoteLockManager;
-use anyhow::Result;
-use std::path::Path;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
 
 /// Represents the result of a conflict check
 #[derive(Debug, Clone, PartialEq)]
@@ -45,7 +51,6 @@ pub enum ConflictRecommendation {
 
 /// Manager for detecting and preventing conflicts
 pub struct ConflictDetector {
-    #[allow(dead_code)]
     oxen: OxenSubprocess,
     lock_manager: RemoteLockManager,
 }
@@ -95,6 +100,47 @@ impl ConflictDetector {
         })
     }
 
+    /// Files a pull from `remote`/`branch` would overwrite
+    ///
+    /// A file conflicts when it has uncommitted changes and its committed
+    /// version differs on the remote branch. Fetches first; files whose
+    /// remote version can't be read are reported as conflicts.
+    pub fn conflicting_files(
+        &self,
+        repo_path: &Path,
+        remote: &str,
+        branch: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let status = self.oxen.status(repo_path)?;
+        let mut changed: Vec<PathBuf> = status.modified.into_iter().chain(status.staged).collect();
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        self.oxen.fetch(repo_path, Some(remote))?;
+        let remote_ref = format!("{}/{}", remote, branch);
+        Ok(changed
+            .into_iter()
+            .filter(|path| {
+                let remote_info = self.oxen.file_info(repo_path, path, Some(&remote_ref));
+                let local_info = self.oxen.file_info(repo_path, path, None);
+                match (remote_info, local_info) {
+                    (Ok(remote), Ok(local)) => remote.hash != local.hash,
+                    // Not on the remote: the pull leaves it alone
+                    (Err(_), Ok(_)) => false,
+                    _ => true,
+                }
+            })
+            .collect())
+    }
+
+    /// Files left with merge conflicts by an earlier pull or merge
+    pub fn merge_conflicts(&self, repo_path: &Path) -> Result<Vec<PathBuf>> {
+        self.oxen.merge_conflicts(repo_path)
+    }
+
     /// Check for conflicts before pushing
     pub fn check_before_push(
         &self,
@@ -156,6 +202,304 @@ impl Default for ConflictDetector {
     }
 }
 
+/// What to do with a file changed both locally and on the remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictChoice {
+    /// Keep the local changes on top of the pulled version
+    KeepLocal,
+    /// Use the remote version; the local copy is only kept as a backup
+    TakeRemote,
+    /// Take the remote version and keep the local one next to it
+    KeepBoth,
+    /// Cancel the pull
+    Abort,
+}
+
+impl ConflictChoice {
+    pub const ALL: [Self; 4] = [Self::KeepLocal, Self::TakeRemote, Self::KeepBoth, Self::Abort];
+
+    /// Key that picks this choice at the prompt
+    pub fn key(&self) -> char {
+        match self {
+            Self::KeepLocal => 'l',
+            Self::TakeRemote => 'r',
+            Self::KeepBoth => 'b',
+            Self::Abort => 'a',
+        }
+    }
+
+    pub fn from_key(key: char) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|choice| choice.key() == key.to_ascii_lowercase())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::KeepLocal => "keep local",
+            Self::TakeRemote => "take remote",
+            Self::KeepBoth => "keep both (local as alternative)",
+            Self::Abort => "abort",
+        }
+    }
+
+    /// Name used on the command line and in history
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::KeepLocal => "local",
+            Self::TakeRemote => "remote",
+            Self::KeepBoth => "both",
+            Self::Abort => "abort",
+        }
+    }
+}
+
+impl std::str::FromStr for ConflictChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|choice| choice.name() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown choice '{}' (local, remote, both, abort)", s))
+    }
+}
+
+/// Carries out conflict choices around a pull, or on merge conflicts a
+/// pull left behind
+///
+/// Every conflicting file is copied to `.auxin/conflicts/<time>/` and
+/// restored to its committed version so the pull applies cleanly; the
+/// choices are then applied to the pulled files. The backups are kept.
+pub struct ConflictResolver {
+    repo_path: PathBuf,
+    backup_dir: PathBuf,
+    oxen: OxenSubprocess,
+}
+
+impl ConflictResolver {
+    pub fn new(repo_path: &Path) -> Self {
+        let stamp = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            backup_dir: repo_path.join(".auxin").join("conflicts").join(stamp),
+            oxen: OxenSubprocess::new(),
+        }
+    }
+
+    /// Where local copies are backed up before the pull
+    pub fn backup_dir(&self) -> &Path {
+        &self.backup_dir
+    }
+
+    /// Back up the conflicting files, pull, then apply `choices`
+    ///
+    /// Returns where each "keep both" file's local version was put. If the
+    /// pull fails, the local copies are put back.
+    pub fn pull_with(&self, choices: &[(PathBuf, ConflictChoice)]) -> Result<Vec<PathBuf>> {
+        if choices.iter().any(|(_, choice)| *choice == ConflictChoice::Abort) {
+            bail!("Pull aborted");
+        }
+
+        for (path, _) in choices {
+            copy_file(&self.repo_path.join(path), &self.backup_dir.join(path))?;
+        }
+        let paths: Vec<&Path> = choices.iter().map(|(path, _)| path.as_path()).collect();
+        let pulled = self
+            .oxen
+            .restore_files(&self.repo_path, &paths)
+            .and_then(|()| self.oxen.pull(&self.repo_path));
+        if let Err(e) = pulled {
+            for (path, _) in choices {
+                copy_file(&self.backup_dir.join(path), &self.repo_path.join(path))?;
+            }
+            return Err(e.context("Pull failed; local changes were put back"));
+        }
+
+        let mut kept = Vec::new();
+        for (path, choice) in choices {
+            let backup = self.backup_dir.join(path);
+            match choice {
+                ConflictChoice::KeepLocal => copy_file(&backup, &self.repo_path.join(path))?,
+                ConflictChoice::KeepBoth => {
+                    kept.push(keep_local_copy(&self.repo_path, path, &backup)?)
+                }
+                ConflictChoice::TakeRemote | ConflictChoice::Abort => {}
+            }
+        }
+        Ok(kept)
+    }
+
+    /// Settle merge conflicts a pull or merge left in the working copy
+    ///
+    /// "Keep local" takes our side of the merge and "take remote" theirs;
+    /// "keep both" takes theirs and puts ours next to it, as
+    /// [`keep_local_copy`] does. Each file is backed up first, then staged
+    /// along with any copy. Returns where each "keep both" file's local
+    /// version was put.
+    pub fn resolve_merge(&self, choices: &[(PathBuf, ConflictChoice)]) -> Result<Vec<PathBuf>> {
+        if choices.iter().any(|(_, choice)| *choice == ConflictChoice::Abort) {
+            bail!("Resolve aborted");
+        }
+
+        let mut kept = Vec::new();
+        for (path, choice) in choices {
+            let file = self.repo_path.join(path);
+            if file.exists() {
+                copy_file(&file, &self.backup_dir.join(path))?;
+            }
+            let mut staged = vec![path.clone()];
+            match choice {
+                ConflictChoice::KeepLocal => {
+                    self.oxen.checkout_conflict(&self.repo_path, path, MergeSide::Ours)?
+                },
+                ConflictChoice::TakeRemote => {
+                    self.oxen.checkout_conflict(&self.repo_path, path, MergeSide::Theirs)?
+                },
+                ConflictChoice::KeepBoth => {
+                    let ours = self.backup_dir.join("ours").join(path);
+                    self.oxen.checkout_conflict(&self.repo_path, path, MergeSide::Ours)?;
+                    copy_file(&file, &ours)?;
+                    self.oxen.checkout_conflict(&self.repo_path, path, MergeSide::Theirs)?;
+                    let copy = keep_local_copy(&self.repo_path, path, &ours)?;
+                    staged.push(staged_path(&copy));
+                    kept.push(copy);
+                },
+                ConflictChoice::Abort => {},
+            }
+            let staged: Vec<&Path> = staged.iter().map(PathBuf::as_path).collect();
+            self.oxen.add(&self.repo_path, &staged)?;
+        }
+        Ok(kept)
+    }
+}
+
+/// `path` relative to `repo`, without `.` or `..` components
+///
+/// Lets "./Audio Files/Vox.wav" or an absolute path inside the repo match
+/// the path status reports.
+pub fn repo_relative_path(repo: &Path, path: &Path) -> PathBuf {
+    let mut relative = PathBuf::new();
+    for component in path.strip_prefix(repo).unwrap_or(path).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::ParentDir => {
+                relative.pop();
+            },
+            _ => {},
+        }
+    }
+    relative
+}
+
+/// Index of the `Alternatives` component of a path inside a Logic Pro
+/// alternative (`.../Alternatives/NNN/...`)
+fn alternatives_index(components: &[Component]) -> Option<usize> {
+    components.windows(3).position(|window| {
+        window[0].as_os_str() == "Alternatives"
+            && window[1].as_os_str().to_string_lossy().parse::<u32>().is_ok()
+    })
+}
+
+/// What to stage for a copy made by [`keep_local_copy`]: the whole new
+/// alternative for a file inside one, or the copy itself
+fn staged_path(copy: &Path) -> PathBuf {
+    let components: Vec<_> = copy.components().collect();
+    match alternatives_index(&components) {
+        Some(index) => components[..index + 2].iter().collect(),
+        None => copy.to_path_buf(),
+    }
+}
+
+/// Put the local version of `relative` next to the pulled one
+///
+/// A file inside a Logic Pro alternative (`Alternatives/NNN/...`, at any
+/// depth, e.g. under `Project.logicx/`) gets a new
+/// alternative: a copy of the pulled one with the local file in place, so
+/// both versions can be opened from Logic's Alternatives menu. Other files
+/// are copied to "<name> (local).<ext>". Returns the new path, relative to
+/// the project.
+pub fn keep_local_copy(project: &Path, relative: &Path, backup: &Path) -> Result<PathBuf> {
+    let components: Vec<_> = relative.components().collect();
+
+    let target = if let Some(index) = alternatives_index(&components) {
+        let alternatives: PathBuf = components[..=index].iter().collect();
+        let next = (0..1000)
+            .map(|n| format!("{:03}", n))
+            .find(|name| !project.join(&alternatives).join(name).exists())
+            .context("No free Logic Pro alternative slot")?;
+        copy_dir(
+            &project.join(&alternatives).join(components[index + 1].as_os_str()),
+            &project.join(&alternatives).join(&next),
+        )?;
+        let rest: PathBuf = components[index + 2..].iter().collect();
+        alternatives.join(next).join(rest)
+    } else {
+        let stem = relative
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = relative
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let mut n = 1;
+        loop {
+            let suffix = if n == 1 {
+                " (local)".to_string()
+            } else {
+                format!(" (local {})", n)
+            };
+            let candidate = relative.with_file_name(format!("{}{}{}", stem, suffix, extension));
+            if !project.join(&candidate).exists() {
+                break candidate;
+            }
+            n += 1;
+        }
+    };
+
+    copy_file(backup, &project.join(&target))?;
+    Ok(target)
+}
+
+/// Record the choices made for a pull or `auxin resolve` in the operation
+/// history
+pub fn record_resolution(
+    repo_path: &Path,
+    choices: &[(PathBuf, ConflictChoice)],
+    result: OperationResult,
+) -> Result<()> {
+    let mut entry = OperationHistoryEntry::new(HistoryOperation::ConflictResolve)
+        .with_repo_path(repo_path)
+        .with_result(result);
+    for (path, choice) in choices {
+        entry = entry.with_metadata(path.display().to_string(), choice.name());
+    }
+    OperationHistoryManager::new().record(entry)
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(from, to).with_context(|| format!("Failed to copy {}", from.display()))?;
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 struct LockStatus {
     is_locked: bool,
@@ -225,6 +569,84 @@ mod tests {
         assert_eq!(result.recommendation, ConflictRecommendation::AcquireLock);
     }
 
+    #[test]
+    fn test_conflict_choice_keys() {
+        for choice in ConflictChoice::ALL {
+            assert_eq!(ConflictChoice::from_key(choice.key()), Some(choice));
+            assert_eq!(choice.name().parse::<ConflictChoice>().unwrap(), choice);
+        }
+        assert_eq!(ConflictChoice::from_key('L'), Some(ConflictChoice::KeepLocal));
+        assert_eq!(ConflictChoice::from_key('x'), None);
+    }
+
+    #[test]
+    fn test_keep_local_copy() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = temp.path();
+        let backup = project.join("backup");
+        fs::write(&backup, "local").unwrap();
+
+        fs::create_dir_all(project.join("Alternatives/000/Media")).unwrap();
+        fs::write(project.join("Alternatives/000/ProjectData"), "remote").unwrap();
+        fs::write(project.join("Alternatives/000/Media/a.wav"), "audio").unwrap();
+        let kept =
+            keep_local_copy(project, Path::new("Alternatives/000/ProjectData"), &backup).unwrap();
+        assert_eq!(kept, Path::new("Alternatives/001/ProjectData"));
+        assert_eq!(fs::read_to_string(project.join(&kept)).unwrap(), "local");
+        assert_eq!(staged_path(&kept), Path::new("Alternatives/001"));
+        assert!(project.join("Alternatives/001/Media/a.wav").exists());
+        assert_eq!(
+            fs::read_to_string(project.join("Alternatives/000/ProjectData")).unwrap(),
+            "remote"
+        );
+
+        fs::write(project.join("notes.txt"), "remote").unwrap();
+        let kept = keep_local_copy(project, Path::new("notes.txt"), &backup).unwrap();
+        assert_eq!(kept, Path::new("notes (local).txt"));
+        assert_eq!(staged_path(&kept), kept);
+        let kept = keep_local_copy(project, Path::new("notes.txt"), &backup).unwrap();
+        assert_eq!(kept, Path::new("notes (local 2).txt"));
+    }
+
+    #[test]
+    fn test_keep_local_copy_inside_project_bundle() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = temp.path();
+        let backup = project.join("backup");
+        fs::write(&backup, "local").unwrap();
+
+        let bundle = project.join("Project.logicx/Alternatives/000");
+        fs::create_dir_all(bundle.join("Media")).unwrap();
+        fs::write(bundle.join("ProjectData"), "remote").unwrap();
+        fs::write(bundle.join("Media/a.wav"), "audio").unwrap();
+        let relative = Path::new("Project.logicx/Alternatives/000/ProjectData");
+        let kept = keep_local_copy(project, relative, &backup).unwrap();
+
+        assert_eq!(kept, Path::new("Project.logicx/Alternatives/001/ProjectData"));
+        assert_eq!(fs::read_to_string(project.join(&kept)).unwrap(), "local");
+        assert_eq!(
+            staged_path(&kept),
+            Path::new("Project.logicx/Alternatives/001")
+        );
+        assert!(project.join("Project.logicx/Alternatives/001/Media/a.wav").exists());
+        assert!(!bundle.join("ProjectData (local)").exists());
+        assert_eq!(fs::read_to_string(bundle.join("ProjectData")).unwrap(), "remote");
+    }
+
+    #[test]
+    fn test_repo_relative_path() {
+        let repo = Path::new("/music/song");
+        let expected = Path::new("Audio Files/Vox.wav");
+        for path in [
+            "Audio Files/Vox.wav",
+            "./Audio Files/Vox.wav",
+            "/music/song/Audio Files/Vox.wav",
+            "Audio Files/../Audio Files/Vox.wav",
+        ] {
+            assert_eq!(repo_relative_path(repo, Path::new(path)), expected);
+        }
+    }
+
     #[test]
     fn test_conflict_recommendation_variants() {
         assert_eq!(ConflictRecommendation::Safe, ConflictRecommendation::Safe);
@@ -0,0 +1,184 @@
+//! Project metadata panel for the console
+//!
+//! Summarizes the project's latest milestone (BPM and key for Logic Pro,
+//! units and layers for SketchUp), how BPM, track counts and layer counts
+//! moved over the last [`TREND_COMMITS`] commits, and how many comments are
+//! still unresolved. Metadata comes from commit messages with `auxin
+//! metadata edit` changes applied and from the recorded track counts, the
+//! same sources `auxin compare --last` uses.
+//!
+//! A comment counts as unresolved until a milestone lands after the commit
+//! it's on; comments on the latest milestone itself are still open.
+
+use crate::collaboration::CommentManager;
+use crate::evolution::TrackCounts;
+use crate::metadata_edits::MetadataEdits;
+use crate::session_sheet::is_milestone;
+use crate::{CommitInfo, SketchUpMetadata};
+use serde::Serialize;
+use std::path::Path;
+
+/// Commits the trend sparklines cover
+pub const TREND_COMMITS: usize = 20;
+
+/// What the metadata panel shows
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProjectDashboard {
+    /// Newest commit when the panel was built
+    pub head: Option<String>,
+    /// Latest commit tagged `milestone`
+    pub milestone: Option<MilestoneSummary>,
+    /// BPM per commit, oldest first; commits without one are skipped
+    pub bpm_trend: Vec<u64>,
+    /// Logic Pro track count per commit, oldest first
+    pub track_trend: Vec<u64>,
+    /// SketchUp layer count per commit, oldest first
+    pub layer_trend: Vec<u64>,
+    /// Comments on the latest milestone and the commits after it
+    pub unresolved_comments: usize,
+}
+
+/// The metadata of one milestone commit
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MilestoneSummary {
+    pub commit_id: String,
+    /// First line of the commit message
+    pub message: String,
+    pub bpm: Option<f32>,
+    pub key: Option<String>,
+    pub units: Option<String>,
+    pub layers: Option<u32>,
+    pub tracks: Option<usize>,
+}
+
+impl ProjectDashboard {
+    /// Build the panel for `repo` from its history, newest first
+    pub fn build(repo: &Path, history: &[CommitInfo]) -> Self {
+        let edits = MetadataEdits::load(repo).unwrap_or_default();
+        let counts = TrackCounts::load(repo).unwrap_or_default();
+
+        let milestone_at = history.iter().position(|c| is_milestone(&edits.effective(c)));
+        let milestone = milestone_at.map(|i| {
+            let commit = &history[i];
+            let metadata = edits.effective(commit);
+            let sketchup = SketchUpMetadata::parse_commit_message(&commit.message);
+            MilestoneSummary {
+                commit_id: commit.id.clone(),
+                message: metadata.message.lines().next().unwrap_or("").to_string(),
+                bpm: metadata.bpm,
+                key: metadata.key_signature,
+                units: sketchup.units,
+                layers: sketchup.layer_count,
+                tracks: counts.get(&commit.id),
+            }
+        });
+
+        let recent: Vec<&CommitInfo> = history.iter().take(TREND_COMMITS).rev().collect();
+        let bpm_trend = recent
+            .iter()
+            .filter_map(|c| edits.effective(c).bpm)
+            .map(|bpm| bpm.round() as u64)
+            .collect();
+        let track_trend = recent
+            .iter()
+            .filter_map(|c| counts.get(&c.id))
+            .map(|count| count as u64)
+            .collect();
+        let layer_trend = recent
+            .iter()
+            .filter_map(|c| SketchUpMetadata::parse_commit_message(&c.message).layer_count)
+            .map(u64::from)
+            .collect();
+
+        // Everything from the latest milestone on, or the whole history
+        let open = &history[..milestone_at.map_or(history.len(), |i| i + 1)];
+        let unresolved_comments = CommentManager::new()
+            .get_all_comments(repo)
+            .unwrap_or_default()
+            .iter()
+            .filter(|comment| {
+                open.iter().any(|c| {
+                    c.id.starts_with(&comment.commit_id) || comment.commit_id.starts_with(&c.id)
+                })
+            })
+            .count();
+
+        Self {
+            head: history.first().map(|c| c.id.clone()),
+            milestone,
+            bpm_trend,
+            track_trend,
+            layer_trend,
+            unresolved_comments,
+        }
+    }
+
+    /// The trends worth drawing, by label; a trend needs two points
+    pub fn trends(&self) -> Vec<(&'static str, &[u64])> {
+        [
+            ("BPM", &self.bpm_trend),
+            ("Tracks", &self.track_trend),
+            ("Layers", &self.layer_trend),
+        ]
+        .into_iter()
+        .filter(|(_, values)| values.len() >= 2)
+        .map(|(label, values)| (label, values.as_slice()))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_summarizes_latest_milestone() {
+        let dir = TempDir::new().unwrap();
+        let comments = CommentManager::new();
+        comments.add_comment(dir.path(), "ccc333", "sam", "Louder kick").unwrap();
+        comments.add_comment(dir.path(), "bbb222", "sam", "Love it").unwrap();
+        comments.add_comment(dir.path(), "aaa111", "sam", "Old note").unwrap();
+
+        // Newest first
+        let history = vec![
+            commit("ccc333", "Tweak hats\n\nBPM: 124"),
+            commit("bbb222", "Mix v2\n\nBPM: 122\nKey: A Minor\nTags: milestone"),
+            commit("aaa111", "Sketch\n\nBPM: 120\nTags: milestone"),
+        ];
+        let dashboard = ProjectDashboard::build(dir.path(), &history);
+
+        let milestone = dashboard.milestone.as_ref().unwrap();
+        assert_eq!(milestone.commit_id, "bbb222");
+        assert_eq!(milestone.message, "Mix v2");
+        assert_eq!(milestone.bpm, Some(122.0));
+        assert_eq!(milestone.key.as_deref(), Some("A Minor"));
+        assert_eq!(dashboard.head.as_deref(), Some("ccc333"));
+        assert_eq!(dashboard.bpm_trend, vec![120, 122, 124]);
+        // The comment on the earlier milestone was resolved by Mix v2
+        assert_eq!(dashboard.unresolved_comments, 2);
+        assert_eq!(dashboard.trends(), vec![("BPM", &[120, 122, 124][..])]);
+    }
+
+    #[test]
+    fn test_build_without_milestone() {
+        let dir = TempDir::new().unwrap();
+        CommentManager::new().add_comment(dir.path(), "aaa111", "sam", "Note").unwrap();
+
+        let history = vec![commit("aaa111", "Model\n\nUnits: Meters\nLayers: 12")];
+        let dashboard = ProjectDashboard::build(dir.path(), &history);
+
+        assert!(dashboard.milestone.is_none());
+        assert_eq!(dashboard.layer_trend, vec![12]);
+        assert!(dashboard.trends().is_empty());
+        assert_eq!(dashboard.unresolved_comments, 1);
+    }
+}
@@ -0,0 +1,408 @@
+//! Pane layout and mouse input for the console
+//!
+//! The normal view follows a `[ui.console]` preset: status and project
+//! panes beside the activity log, above it, or hidden. Dragging the divider
+//! (or pressing `<` / `>`) resizes them, `p` cycles presets, and both are
+//! saved to `~/.auxin/config.toml` so the next console opens the same way.
+//!
+//! With the mouse, a click focuses a pane or presses a footer shortcut, and
+//! the wheel scrolls the activity log or moves through the open list.
+
+use auxin_config::{ConsoleLayout, ConsolePreset};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::Span,
+};
+
+use super::{Console, ConsoleMode, LogLevel};
+
+/// Narrowest and widest the status and project panes can be, in percent
+pub const MIN_SIDE_PERCENT: u16 = 15;
+pub const MAX_SIDE_PERCENT: u16 = 70;
+
+/// Percent `<` and `>` resize by
+const RESIZE_STEP: u16 = 5;
+
+/// A pane of the normal view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pane {
+    Status,
+    Project,
+    #[default]
+    Log,
+}
+
+/// Where the normal view's panes go; hidden panes have no area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Panes {
+    pub status: Option<Rect>,
+    pub project: Option<Rect>,
+    pub log: Rect,
+}
+
+impl Panes {
+    /// The pane at a screen position
+    fn at(&self, column: u16, row: u16) -> Option<Pane> {
+        let inside = |area: Rect| {
+            column >= area.x
+                && column < area.x + area.width
+                && row >= area.y
+                && row < area.y + area.height
+        };
+        [
+            (self.status, Pane::Status),
+            (self.project, Pane::Project),
+            (Some(self.log), Pane::Log),
+        ]
+        .into_iter()
+        .find(|(area, _)| area.is_some_and(inside))
+        .map(|(_, pane)| pane)
+    }
+}
+
+/// Header, body and footer of the screen
+pub fn screen_chunks(area: Rect) -> (Rect, Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Body
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+    (chunks[0], chunks[1], chunks[2])
+}
+
+/// Split the body of the normal view as `layout` says
+pub fn normal_panes(layout: &ConsoleLayout, body: Rect) -> Panes {
+    let side = layout.side_percent.clamp(MIN_SIDE_PERCENT, MAX_SIDE_PERCENT);
+    let (direction, side_direction, side_constraints) = match layout.preset {
+        ConsolePreset::LogOnly => {
+            return Panes {
+                status: None,
+                project: None,
+                log: body,
+            }
+        }
+        // Status above the project pane, left of the log
+        ConsolePreset::SideBySide => (
+            Direction::Horizontal,
+            Direction::Vertical,
+            [Constraint::Length(8), Constraint::Min(0)],
+        ),
+        // Status beside the project pane, above the log
+        ConsolePreset::Stacked => (
+            Direction::Vertical,
+            Direction::Horizontal,
+            [Constraint::Percentage(40), Constraint::Percentage(60)],
+        ),
+    };
+
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([Constraint::Percentage(side), Constraint::Percentage(100 - side)])
+        .split(body);
+    let side_chunks = Layout::default()
+        .direction(side_direction)
+        .constraints(side_constraints)
+        .split(chunks[0]);
+    Panes {
+        status: Some(side_chunks[0]),
+        project: Some(side_chunks[1]),
+        log: chunks[1],
+    }
+}
+
+impl Console {
+    /// Panes of the normal view within `body`
+    pub(super) fn panes(&self, body: Rect) -> Panes {
+        normal_panes(&self.layout, body)
+    }
+
+    /// Focus the next visible pane
+    pub(super) fn focus_next_pane(&mut self) {
+        let order = match self.layout.preset {
+            ConsolePreset::LogOnly => &[Pane::Log][..],
+            _ => &[Pane::Status, Pane::Project, Pane::Log][..],
+        };
+        let at = order.iter().position(|p| *p == self.focus).unwrap_or(order.len() - 1);
+        self.focus = order[(at + 1) % order.len()];
+    }
+
+    /// Scroll the activity log by `lines`, positive toward older entries
+    pub(super) fn scroll_log(&mut self, lines: isize) {
+        let max = self.activity_log.len().saturating_sub(1);
+        self.log_scroll = self.log_scroll.saturating_add_signed(lines).min(max);
+    }
+
+    /// Switch to the next layout preset and save it
+    pub(super) fn cycle_layout_preset(&mut self) {
+        self.layout.preset = self.layout.preset.next();
+        if self.layout.preset == ConsolePreset::LogOnly {
+            self.focus = Pane::Log;
+        }
+        self.log(
+            LogLevel::Info,
+            format!("Layout: {}", self.layout.preset.name()),
+        );
+        self.save_layout();
+    }
+
+    /// Grow (positive) or shrink the status and project panes and save
+    pub(super) fn resize_side(&mut self, steps: i16) {
+        let percent = self.layout.side_percent as i16 + steps * RESIZE_STEP as i16;
+        self.layout.side_percent =
+            (percent.max(0) as u16).clamp(MIN_SIDE_PERCENT, MAX_SIDE_PERCENT);
+        self.save_layout();
+    }
+
+    /// Write the layout to `[ui.console]` in the user config
+    fn save_layout(&mut self) {
+        use crate::config_edit;
+
+        let result = config_edit::user_config_path().and_then(|path| {
+            config_edit::update_file(
+                &path,
+                "ui.console",
+                "preset",
+                Some(self.layout.preset.name()),
+            )?;
+            config_edit::update_file_toml(
+                &path,
+                "ui.console",
+                "side_percent",
+                Some(i64::from(self.layout.side_percent).into()),
+            )
+        });
+        if let Err(e) = result {
+            self.log(LogLevel::Warning, format!("Layout not saved: {}", e));
+        }
+    }
+
+    /// Handle a mouse event on a screen of size `area`
+    pub(super) fn handle_mouse_event(
+        &mut self,
+        mouse: MouseEvent,
+        area: Rect,
+    ) -> anyhow::Result<()> {
+        let (_, body, footer) = screen_chunks(area);
+        let (column, row) = (mouse.column, mouse.row);
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if row >= footer.y => {
+                if let Some(code) = self.footer_button_at(footer, column, row) {
+                    self.handle_key_event(code, KeyModifiers::empty())?;
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) => match self.mode {
+                ConsoleMode::Normal => {
+                    let panes = self.panes(body);
+                    if self.on_divider(&panes, column, row) {
+                        self.dragging_divider = true;
+                    } else if let Some(pane) = panes.at(column, row) {
+                        self.focus = pane;
+                    }
+                }
+                ConsoleMode::Help => self.handle_key_event(KeyCode::Esc, KeyModifiers::empty())?,
+                _ => self.select_list_row(body, row),
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging_divider => {
+                let percent = match self.layout.preset {
+                    ConsolePreset::Stacked => {
+                        row.saturating_sub(body.y) as u32 * 100 / body.height.max(1) as u32
+                    }
+                    _ => column.saturating_sub(body.x) as u32 * 100 / body.width.max(1) as u32,
+                };
+                self.layout.side_percent =
+                    (percent as u16).clamp(MIN_SIDE_PERCENT, MAX_SIDE_PERCENT);
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.dragging_divider => {
+                self.dragging_divider = false;
+                self.save_layout();
+            }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let down = mouse.kind == MouseEventKind::ScrollDown;
+                match self.mode {
+                    ConsoleMode::Normal => {
+                        if self.panes(body).at(column, row) == Some(Pane::Log) {
+                            self.scroll_log(if down { 3 } else { -3 });
+                        }
+                    }
+                    ConsoleMode::RestoreBrowser
+                    | ConsoleMode::Compare
+                    | ConsoleMode::Search
+                    | ConsoleMode::Hooks
+                    | ConsoleMode::Transfers => {
+                        let code = if down { KeyCode::Down } else { KeyCode::Up };
+                        self.handle_key_event(code, KeyModifiers::empty())?;
+                    }
+                    ConsoleMode::CommitDialog | ConsoleMode::Help => {}
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Whether a position is on the border between the side panes and the
+    /// activity log
+    fn on_divider(&self, panes: &Panes, column: u16, row: u16) -> bool {
+        let Some(side) = panes.status else {
+            return false;
+        };
+        let log = panes.log;
+        match self.layout.preset {
+            ConsolePreset::SideBySide => {
+                (column == log.x || column + 1 == log.x) && row >= side.y && row < log.y + log.height
+            }
+            ConsolePreset::Stacked => (row == log.y || row + 1 == log.y) && column >= log.x,
+            ConsolePreset::LogOnly => false,
+        }
+    }
+
+    /// Select the clicked row of the list filling the body, in the modes
+    /// that show one
+    fn select_list_row(&mut self, body: Rect, row: u16) {
+        // Rows start below the list's top border
+        let Some(index) = row.checked_sub(body.y + 1).map(usize::from) else {
+            return;
+        };
+        let (selected, len) = match self.mode {
+            ConsoleMode::RestoreBrowser => (
+                &mut self.restore_browser.selected_index,
+                self.restore_browser.commits.len(),
+            ),
+            ConsoleMode::Hooks => (&mut self.hooks_state.selected_index, self.hooks_state.hooks.len()),
+            ConsoleMode::Transfers => (
+                &mut self.transfers_state.selected_index,
+                self.transfers_state.items.len(),
+            ),
+            _ => return,
+        };
+        if index < len {
+            *selected = index;
+        }
+    }
+
+    /// The key of the footer shortcut at a position, as
+    /// [`Console::render_footer`] lays them out
+    pub(super) fn footer_button_at(&self, footer: Rect, column: u16, row: u16) -> Option<KeyCode> {
+        // The shortcuts sit centered on the line inside the border
+        if row != footer.y + 1 {
+            return None;
+        }
+        let shortcuts = self.footer_shortcuts();
+        let last = shortcuts.len().saturating_sub(1);
+        let widths: Vec<u16> = shortcuts
+            .iter()
+            .enumerate()
+            .map(|(i, (key, action, _))| {
+                let gap = if i == last { "" } else { "  " };
+                (Span::raw(*key).width() + Span::raw(format!(":{}{}", action, gap)).width()) as u16
+            })
+            .collect();
+        let inner_width = footer.width.saturating_sub(2);
+        let total: u16 = widths.iter().sum();
+        let mut x = footer.x + 1 + (inner_width / 2).saturating_sub(total / 2);
+
+        for ((key, _, _), width) in shortcuts.iter().zip(widths) {
+            if column >= x && column < x + width {
+                return shortcut_key(key);
+            }
+            x += width;
+        }
+        None
+    }
+}
+
+/// The key a footer shortcut label stands for; labels like `↑↓` that
+/// aren't a single key aren't buttons
+fn shortcut_key(label: &str) -> Option<KeyCode> {
+    let mut chars = label.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii() => return Some(KeyCode::Char(c)),
+        _ => {}
+    }
+    match label {
+        "Enter" => Some(KeyCode::Enter),
+        "Esc" | "Any Key" => Some(KeyCode::Esc),
+        "Tab" => Some(KeyCode::Tab),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    #[test]
+    fn test_normal_panes_follow_preset() {
+        let body = Rect::new(0, 3, 100, 40);
+        let mut layout = ConsoleLayout::default();
+
+        let panes = normal_panes(&layout, body);
+        assert_eq!(panes.log.x, 30);
+        assert_eq!(panes.status.unwrap().height, 8);
+
+        layout.preset = ConsolePreset::Stacked;
+        layout.side_percent = 50;
+        let panes = normal_panes(&layout, body);
+        assert_eq!((panes.log.y, panes.log.height), (23, 20));
+
+        layout.preset = ConsolePreset::LogOnly;
+        assert_eq!(normal_panes(&layout, body).log, body);
+        assert!(normal_panes(&layout, body).status.is_none());
+    }
+
+    #[test]
+    fn test_click_focuses_pane_and_wheel_scrolls_log() {
+        let mut console = Console::new(PathBuf::from("/test/project.logicx"));
+        let area = Rect::new(0, 0, 100, 40);
+        for i in 0..10 {
+            console.log(LogLevel::Info, format!("entry {}", i));
+        }
+
+        console.handle_mouse_event(click(5, 5), area).unwrap();
+        assert_eq!(console.focus, Pane::Status);
+
+        let scroll = MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            ..click(60, 10)
+        };
+        console.handle_mouse_event(scroll, area).unwrap();
+        assert_eq!(console.log_scroll, 3);
+        // Scrolling over the status pane leaves the log alone
+        console.handle_mouse_event(MouseEvent { column: 5, ..scroll }, area).unwrap();
+        assert_eq!(console.log_scroll, 3);
+    }
+
+    #[test]
+    fn test_footer_buttons() {
+        let mut console = Console::new(PathBuf::from("/test/project.logicx"));
+        let area = Rect::new(0, 0, 100, 40);
+        let (_, _, footer) = screen_chunks(area);
+
+        // "q:Quit" is the first button of the centered line
+        let shortcuts_width = 77;
+        let first = 1 + (98 / 2 - shortcuts_width / 2);
+        assert_eq!(console.footer_button_at(footer, first, footer.y + 1), Some(KeyCode::Char('q')));
+        assert_eq!(console.footer_button_at(footer, first, footer.y), None);
+
+        console.handle_mouse_event(click(first + 2, footer.y + 1), area).unwrap();
+        assert!(console.should_quit);
+
+        assert_eq!(shortcut_key("↑↓"), None);
+        assert_eq!(shortcut_key("Esc"), Some(KeyCode::Esc));
+    }
+}
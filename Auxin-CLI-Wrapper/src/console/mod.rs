@@ -4,7 +4,11 @@
 /// - Live daemon status
 /// - Activity log with real-time updates
 /// - Repository status display
+/// - Project metadata panel (latest milestone, trends, open comments)
+/// - Queue and upload manager: sync now, cancel uploads, discard dead letters
+/// - Mouse support and resizable panes, with layout presets kept in config
 /// - Keyboard shortcuts for common operations
+/// - A one-shot text or JSON snapshot for sessions without a terminal
 use anyhow::{Context, Result};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
@@ -16,7 +20,7 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline, Wrap},
     Frame, Terminal,
 };
 use std::io;
@@ -25,12 +29,28 @@ use std::time::{Duration, SystemTime};
 
 use crate::{CommitMetadata, OxenRepository};
 
+mod dashboard;
+mod layout;
+mod snapshot;
+mod transfers;
+
+pub use dashboard::{MilestoneSummary, ProjectDashboard};
+pub use layout::{normal_panes, Pane, Panes};
+pub use snapshot::{ConsoleSnapshot, TransferRow};
+pub use transfers::TransferItem;
+
 /// Maximum number of activity log entries to retain
 const MAX_LOG_ENTRIES: usize = 100;
 
 /// Polling interval for daemon status updates (milliseconds)
 const POLL_INTERVAL_MS: u64 = 2000;
 
+/// Refresh interval for the metadata panel (milliseconds)
+const DASHBOARD_INTERVAL_MS: u64 = 30_000;
+
+/// How far back the metadata panel looks for the latest milestone
+const DASHBOARD_HISTORY: usize = 200;
+
 /// Console application mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConsoleMode {
@@ -46,6 +66,8 @@ pub enum ConsoleMode {
     Search,
     /// Hooks management
     Hooks,
+    /// Offline queue and upload sessions
+    Transfers,
     /// Help screen
     Help,
 }
@@ -60,6 +82,8 @@ pub struct Console {
     pub daemon_status: DaemonStatus,
     /// Repository status (staged, modified, etc.)
     pub repo_status: Option<RepositoryStatus>,
+    /// Project metadata panel contents
+    pub dashboard: Option<ProjectDashboard>,
     /// Whether the console should exit
     pub should_quit: bool,
     /// Current UI mode
@@ -74,8 +98,22 @@ pub struct Console {
     search_state: SearchState,
     /// Hooks mode state
     hooks_state: HooksState,
+    /// Queue and upload manager state
+    transfers_state: TransfersState,
+    /// Set when something wrote to the terminal behind the UI's back
+    needs_clear: bool,
+    /// Pane arrangement, from `[ui.console]`
+    layout: auxin_config::ConsoleLayout,
+    /// Pane that arrow keys act on in the normal view
+    focus: Pane,
+    /// Activity log entries scrolled past (newest first)
+    log_scroll: usize,
+    /// Whether the pane divider is being dragged
+    dragging_divider: bool,
     /// Last daemon poll time
     last_poll: SystemTime,
+    /// Last metadata panel refresh
+    last_dashboard_refresh: SystemTime,
 }
 
 /// State for commit dialog
@@ -132,6 +170,13 @@ struct HooksState {
     selected_index: usize,
 }
 
+/// State for the queue and upload manager
+#[derive(Debug, Clone, Default)]
+struct TransfersState {
+    items: Vec<TransferItem>,
+    selected_index: usize,
+}
+
 /// Single entry in the activity log
 #[derive(Debug, Clone)]
 pub struct LogEntry {
@@ -161,7 +206,7 @@ pub enum DaemonStatus {
 }
 
 /// Repository status snapshot
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct RepositoryStatus {
     /// Number of staged files
     pub staged: usize,
@@ -179,6 +224,7 @@ impl Console {
             activity_log: Vec::new(),
             daemon_status: DaemonStatus::Unknown,
             repo_status: None,
+            dashboard: None,
             should_quit: false,
             mode: ConsoleMode::Normal,
             commit_dialog: CommitDialogState::default(),
@@ -186,7 +232,14 @@ impl Console {
             compare_state: CompareState::default(),
             search_state: SearchState::default(),
             hooks_state: HooksState::default(),
+            transfers_state: TransfersState::default(),
+            needs_clear: false,
+            layout: auxin_config::ConsoleLayout::default(),
+            focus: Pane::default(),
+            log_scroll: 0,
+            dragging_divider: false,
             last_poll: SystemTime::now(),
+            last_dashboard_refresh: SystemTime::now(),
         }
     }
 
@@ -201,6 +254,11 @@ impl Console {
             },
         );
 
+        // Keep a scrolled log showing the same entries
+        if self.log_scroll > 0 {
+            self.log_scroll += 1;
+        }
+
         // Prune old entries
         if self.activity_log.len() > MAX_LOG_ENTRIES {
             self.activity_log.truncate(MAX_LOG_ENTRIES);
@@ -231,6 +289,10 @@ impl Console {
 
     /// Run the console application
     pub async fn run(&mut self) -> Result<()> {
+        self.layout = auxin_config::Config::load()
+            .map(|config| config.ui.console)
+            .unwrap_or_default();
+
         // Setup terminal
         enable_raw_mode().context("Failed to enable raw mode")?;
         let mut stdout = io::stdout();
@@ -244,6 +306,7 @@ impl Console {
             LogLevel::Info,
             format!("Monitoring project: {}", self.project_path.display()),
         );
+        self.refresh_dashboard();
 
         // Main event loop
         let res = self.event_loop(&mut terminal).await;
@@ -267,18 +330,36 @@ impl Console {
         terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     ) -> Result<()> {
         loop {
+            // Queue syncs log straight to stderr; repaint over them
+            if self.needs_clear {
+                terminal.clear()?;
+                self.needs_clear = false;
+            }
             terminal.draw(|f| self.ui(f))?;
 
             // Poll for events with timeout
             if event::poll(Duration::from_millis(250))? {
-                if let Event::Key(key) = event::read()? {
-                    self.handle_key_event(key.code, key.modifiers)?;
+                match event::read()? {
+                    Event::Key(key) => self.handle_key_event(key.code, key.modifiers)?,
+                    Event::Mouse(mouse) => {
+                        let area = terminal.size()?;
+                        self.handle_mouse_event(mouse, area)?;
+                    }
+                    _ => {}
                 }
             }
 
             // Poll daemon for updates periodically
             self.poll_daemon_updates()?;
 
+            // Pick up new commits, edits and comments in the metadata panel
+            let since_refresh = SystemTime::now()
+                .duration_since(self.last_dashboard_refresh)
+                .unwrap_or(Duration::from_secs(0));
+            if since_refresh.as_millis() >= DASHBOARD_INTERVAL_MS as u128 {
+                self.refresh_dashboard();
+            }
+
             if self.should_quit {
                 break;
             }
@@ -344,6 +425,7 @@ impl Console {
             ConsoleMode::Compare => self.handle_compare_mode_key(code, modifiers),
             ConsoleMode::Search => self.handle_search_mode_key(code, modifiers),
             ConsoleMode::Hooks => self.handle_hooks_mode_key(code, modifiers),
+            ConsoleMode::Transfers => self.handle_transfers_mode_key(code, modifiers),
             ConsoleMode::Help => self.handle_help_mode_key(code, modifiers),
         }
     }
@@ -359,6 +441,7 @@ impl Console {
             (KeyCode::Char('r'), _) => {
                 self.log(LogLevel::Info, "Refreshing status...");
                 self.refresh_repo_status();
+                self.refresh_dashboard();
             }
             // Clear log on 'c'
             (KeyCode::Char('c'), _) => {
@@ -398,10 +481,37 @@ impl Console {
                 self.log(LogLevel::Info, "Opened hooks manager");
                 self.load_hooks();
             }
+            // Open queue and upload manager on 'u'
+            (KeyCode::Char('u'), _) => {
+                self.mode = ConsoleMode::Transfers;
+                self.transfers_state = TransfersState::default();
+                self.log(LogLevel::Info, "Opened queue and upload manager");
+                self.load_transfers();
+            }
             // Show help on '?' or 'h'
             (KeyCode::Char('?'), _) | (KeyCode::Char('h'), _) => {
                 self.mode = ConsoleMode::Help;
             }
+            // Cycle layout presets on 'p'
+            (KeyCode::Char('p'), _) => {
+                self.cycle_layout_preset();
+            }
+            // Resize the status and project panes on '<' and '>'
+            (KeyCode::Char('<'), _) => {
+                self.resize_side(-1);
+            }
+            (KeyCode::Char('>'), _) => {
+                self.resize_side(1);
+            }
+            // Move focus between panes on Tab
+            (KeyCode::Tab, _) => {
+                self.focus_next_pane();
+            }
+            // Scroll the activity log when it has focus
+            (KeyCode::Down, _) if self.focus == Pane::Log => self.scroll_log(1),
+            (KeyCode::Up, _) if self.focus == Pane::Log => self.scroll_log(-1),
+            (KeyCode::PageDown, _) if self.focus == Pane::Log => self.scroll_log(10),
+            (KeyCode::PageUp, _) if self.focus == Pane::Log => self.scroll_log(-10),
             _ => {}
         }
         Ok(())
@@ -622,6 +732,45 @@ impl Console {
         Ok(())
     }
 
+    /// Handle keyboard in queue and upload manager
+    fn handle_transfers_mode_key(&mut self, code: KeyCode, _modifiers: KeyModifiers) -> Result<()> {
+        match code {
+            // Close on Esc
+            KeyCode::Esc => {
+                self.mode = ConsoleMode::Normal;
+                self.log(LogLevel::Info, "Closed queue and upload manager");
+            }
+            // Navigate up
+            KeyCode::Up if self.transfers_state.selected_index > 0 => {
+                self.transfers_state.selected_index -= 1;
+            }
+            // Navigate down
+            KeyCode::Down
+                if self.transfers_state.selected_index + 1 < self.transfers_state.items.len() =>
+            {
+                self.transfers_state.selected_index += 1;
+            }
+            // Sync the queue now on 's'
+            KeyCode::Char('s') => {
+                self.sync_queue_now();
+            }
+            // Cancel the selected upload on 'x'
+            KeyCode::Char('x') => {
+                self.cancel_selected_upload();
+            }
+            // Discard the selected dead letter on 'd'
+            KeyCode::Char('d') => {
+                self.discard_selected_dead_letter();
+            }
+            // Refresh on 'r'
+            KeyCode::Char('r') => {
+                self.load_transfers();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     /// Get mutable reference to active field in commit dialog
     fn get_active_field_mut(&mut self) -> &mut String {
         match self.commit_dialog.active_field {
@@ -673,6 +822,148 @@ impl Console {
         }
     }
 
+    /// Load offline queue entries and upload sessions
+    fn load_transfers(&mut self) {
+        let queue = match crate::OfflineQueue::new() {
+            Ok(queue) => queue,
+            Err(e) => {
+                self.log(LogLevel::Error, format!("Failed to load queue: {}", e));
+                self.transfers_state.items.clear();
+                return;
+            }
+        };
+        let state_dir = crate::UploadConfig::default().state_dir;
+        let uploads = crate::chunked_upload::resumable_sessions(&state_dir).unwrap_or_default();
+
+        self.transfers_state.items = TransferItem::collect(&queue, uploads);
+        self.transfers_state.selected_index = self
+            .transfers_state
+            .selected_index
+            .min(self.transfers_state.items.len().saturating_sub(1));
+    }
+
+    /// Sync every pending queue entry, as `auxin queue sync` does
+    fn sync_queue_now(&mut self) {
+        let mut queue = match crate::OfflineQueue::new() {
+            Ok(queue) => queue,
+            Err(e) => {
+                self.log(LogLevel::Error, format!("Failed to load queue: {}", e));
+                return;
+            }
+        };
+        let pending = queue.pending().len();
+        if pending == 0 {
+            self.log(LogLevel::Info, "No pending operations to sync");
+            return;
+        }
+
+        self.log(LogLevel::Info, format!("Syncing {} pending operation(s)...", pending));
+        let result = tokio::task::block_in_place(|| queue.sync_all());
+        self.needs_clear = true;
+        match result {
+            Ok(report) => {
+                if report.is_complete_success() {
+                    self.log(
+                        LogLevel::Success,
+                        format!("Synced {} operation(s)", report.success_count()),
+                    );
+                } else {
+                    self.log(
+                        LogLevel::Warning,
+                        format!(
+                            "Synced {}, {} failed ({} dead-lettered)",
+                            report.success_count(),
+                            report.failure_count(),
+                            report.dead_lettered.len()
+                        ),
+                    );
+                }
+            }
+            Err(e) => self.log(LogLevel::Error, format!("Sync failed: {}", e)),
+        }
+        self.load_transfers();
+    }
+
+    /// Discard the selected upload session so the next push starts over
+    fn cancel_selected_upload(&mut self) {
+        let Some(TransferItem::Upload(session)) =
+            self.transfers_state.items.get(self.transfers_state.selected_index)
+        else {
+            self.log(LogLevel::Warning, "Select an upload to cancel");
+            return;
+        };
+        if session.is_active(chrono::Utc::now()) {
+            self.log(
+                LogLevel::Warning,
+                "That upload is still running; stop its push first, then cancel it here",
+            );
+            return;
+        }
+
+        let id = session.id.clone();
+        let state_dir = crate::UploadConfig::default().state_dir;
+        match crate::chunked_upload::discard_session(&state_dir, &id) {
+            Ok(true) => {
+                self.log(LogLevel::Success, "Upload cancelled; the next push starts over")
+            }
+            Ok(false) => self.log(LogLevel::Info, "Upload already finished or cancelled"),
+            Err(e) => self.log(LogLevel::Error, format!("Failed to cancel upload: {}", e)),
+        }
+        self.load_transfers();
+    }
+
+    /// Delete the selected dead-lettered queue entry for good
+    fn discard_selected_dead_letter(&mut self) {
+        let Some(TransferItem::DeadLetter(entry)) =
+            self.transfers_state.items.get(self.transfers_state.selected_index)
+        else {
+            self.log(LogLevel::Warning, "Select a dead letter to discard");
+            return;
+        };
+
+        let (id, description) = (entry.id.clone(), entry.operation.description());
+        match crate::OfflineQueue::new().and_then(|mut queue| queue.discard_dead_letter(&id)) {
+            Ok(()) => self.log(LogLevel::Success, format!("Discarded: {}", description)),
+            Err(e) => self.log(LogLevel::Error, format!("Failed to discard: {}", e)),
+        }
+        self.load_transfers();
+    }
+
+    /// Rebuild the metadata panel from the latest history
+    ///
+    /// Failures are logged once per change in outcome, not on every refresh.
+    fn refresh_dashboard(&mut self) {
+        self.last_dashboard_refresh = SystemTime::now();
+        if !self.project_path.join(".oxen").exists() {
+            return;
+        }
+
+        let project_path = self.project_path.clone();
+        match tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let repo = OxenRepository::new(&project_path);
+                repo.get_history(Some(DASHBOARD_HISTORY)).await
+            })
+        }) {
+            Ok(history) => {
+                let dashboard = ProjectDashboard::build(&self.project_path, &history);
+                let head_changed = self
+                    .dashboard
+                    .as_ref()
+                    .is_some_and(|old| old.head != dashboard.head);
+                if head_changed {
+                    self.log(LogLevel::Info, "New commit; metadata panel updated");
+                }
+                self.dashboard = Some(dashboard);
+            }
+            Err(e) => {
+                if self.dashboard.take().is_some() {
+                    self.log(LogLevel::Error, format!("Failed to load metadata: {}", e));
+                }
+            }
+        }
+    }
+
     /// Load commit history
     fn load_commits(&mut self) {
         // Check if repository exists
@@ -783,8 +1074,9 @@ impl Console {
                     &commit_id
                 };
                 self.log(LogLevel::Success, format!("Commit created: {}", short_id));
-                // Refresh status after commit
+                // Refresh status and metadata after commit
                 self.refresh_repo_status();
+                self.refresh_dashboard();
             }
             Err(e) => {
                 self.log(LogLevel::Error, format!("Failed to create commit: {}", e));
@@ -920,6 +1212,7 @@ impl Console {
 
     /// Execute search query
     fn execute_search(&mut self) {
+        use crate::metadata_store::MetadataStore;
         use crate::search::SearchEngine;
 
         if self.search_state.query.is_empty() {
@@ -950,7 +1243,7 @@ impl Console {
             Ok(commits) => {
                 // Parse and execute search
                 let query = SearchEngine::parse_query(&self.search_state.query);
-                let engine = SearchEngine::new();
+                let engine = SearchEngine::with_store(MetadataStore::for_repo(&project_path));
                 let results = engine.search(&commits, &query);
 
                 self.search_state.results = results
@@ -1008,11 +1301,7 @@ impl Console {
                 self.hooks_state.hooks = hooks
                     .iter()
                     .map(|(hook_type, name)| {
-                        let type_str = match hook_type {
-                            crate::hooks::HookType::PreCommit => "pre-commit",
-                            crate::hooks::HookType::PostCommit => "post-commit",
-                        };
-                        (type_str.to_string(), name.clone())
+                        (hook_type.dir_name().to_string(), name.clone())
                     })
                     .collect();
 
@@ -1038,13 +1327,9 @@ impl Console {
 
         let (hook_type_str, hook_name) = &self.hooks_state.hooks[self.hooks_state.selected_index];
 
-        let hook_type = match hook_type_str.as_str() {
-            "pre-commit" => HookType::PreCommit,
-            "post-commit" => HookType::PostCommit,
-            _ => {
-                self.log(LogLevel::Error, "Invalid hook type");
-                return;
-            }
+        let Some(hook_type) = HookType::from_dir_name(hook_type_str) else {
+            self.log(LogLevel::Error, "Invalid hook type");
+            return;
         };
 
         let manager = HookManager::new(&self.project_path);
@@ -1069,17 +1354,9 @@ impl Console {
 
     /// Render the UI
     fn ui(&self, f: &mut Frame) {
-        let size = f.size();
-
         // Create main layout: header, body, footer
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Min(0),    // Body
-                Constraint::Length(3), // Footer
-            ])
-            .split(size);
+        let (header, body, footer) = layout::screen_chunks(f.size());
+        let chunks = [header, body, footer];
 
         // Render header
         self.render_header(f, chunks[0]);
@@ -1087,20 +1364,19 @@ impl Console {
         // Render body based on mode
         match self.mode {
             ConsoleMode::Normal => {
-                // Split body into status panel and activity log
-                let body_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([
-                        Constraint::Percentage(30), // Status panel
-                        Constraint::Percentage(70), // Activity log
-                    ])
-                    .split(chunks[1]);
-
-                // Render status panel
-                self.render_status_panel(f, body_chunks[0]);
+                // Split body into panes as the layout preset says
+                let panes = self.panes(chunks[1]);
+
+                // Render status and metadata panels, unless hidden
+                if let Some(area) = panes.status {
+                    self.render_status_panel(f, area);
+                }
+                if let Some(area) = panes.project {
+                    self.render_metadata_panel(f, area);
+                }
 
                 // Render activity log
-                self.render_activity_log(f, body_chunks[1]);
+                self.render_activity_log(f, panes.log);
             }
             ConsoleMode::CommitDialog => {
                 // Render activity log as background
@@ -1124,6 +1400,10 @@ impl Console {
                 // Render hooks mode
                 self.render_hooks_mode(f, chunks[1]);
             }
+            ConsoleMode::Transfers => {
+                // Render queue and upload manager
+                self.render_transfers_mode(f, chunks[1]);
+            }
             ConsoleMode::Help => {
                 // Render help screen
                 self.render_help_screen(f, chunks[1]);
@@ -1207,7 +1487,7 @@ impl Console {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::White))
+                    .border_style(self.pane_border(Pane::Status))
                     .title("Status"),
             )
             .wrap(Wrap { trim: true });
@@ -1215,11 +1495,114 @@ impl Console {
         f.render_widget(status_panel, area);
     }
 
+    /// Render the project metadata panel
+    fn render_metadata_panel(&self, f: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.pane_border(Pane::Project))
+            .title("Project");
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let Some(ref dashboard) = self.dashboard else {
+            let empty = Paragraph::new(Span::styled(
+                "Metadata: Not loaded",
+                Style::default().fg(Color::DarkGray),
+            ));
+            f.render_widget(empty, inner);
+            return;
+        };
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = vec![];
+        match dashboard.milestone {
+            Some(ref milestone) => {
+                let short_id = milestone.commit_id.get(..7).unwrap_or(&milestone.commit_id);
+                lines.push(Line::from(vec![
+                    Span::styled("Milestone ", bold),
+                    Span::styled(short_id.to_string(), Style::default().fg(Color::Yellow)),
+                ]));
+                lines.push(Line::from(format!("  {}", milestone.message)));
+                let fields = [
+                    ("BPM", milestone.bpm.map(|bpm| bpm.to_string())),
+                    ("Key", milestone.key.clone()),
+                    ("Tracks", milestone.tracks.map(|n| n.to_string())),
+                    ("Units", milestone.units.clone()),
+                    ("Layers", milestone.layers.map(|n| n.to_string())),
+                ];
+                for (label, value) in fields {
+                    if let Some(value) = value {
+                        lines.push(Line::from(vec![
+                            Span::raw(format!("  {}: ", label)),
+                            Span::styled(value, Style::default().fg(Color::Cyan)),
+                        ]));
+                    }
+                }
+            }
+            None => lines.push(Line::from(Span::styled(
+                "No milestone yet",
+                Style::default().fg(Color::DarkGray),
+            ))),
+        }
+        let comments_style = if dashboard.unresolved_comments > 0 {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        lines.push(Line::from(vec![
+            Span::raw("Unresolved comments: "),
+            Span::styled(dashboard.unresolved_comments.to_string(), comments_style),
+        ]));
+
+        // Text on top, then a label and sparkline per trend
+        let trends = dashboard.trends();
+        let mut constraints = vec![Constraint::Length(lines.len() as u16 + 1)];
+        constraints.extend(trends.iter().map(|_| Constraint::Length(2)));
+        constraints.push(Constraint::Min(0));
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), rows[0]);
+        for (i, (label, values)) in trends.into_iter().enumerate() {
+            let row = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Length(1)])
+                .split(rows[i + 1]);
+            let (min, max) = (
+                values.iter().min().copied().unwrap_or(0),
+                values.iter().max().copied().unwrap_or(0),
+            );
+            let caption = format!("{} (last {}): {}–{}", label, values.len(), min, max);
+            f.render_widget(
+                Paragraph::new(Span::styled(caption, Style::default().fg(Color::DarkGray))),
+                row[0],
+            );
+            // Drawn relative to the lowest value so small changes show
+            let relative: Vec<u64> = values.iter().map(|v| v - min + 1).collect();
+            let sparkline = Sparkline::default()
+                .data(&relative)
+                .style(Style::default().fg(Color::Cyan));
+            f.render_widget(sparkline, row[1]);
+        }
+    }
+
+    /// Border style of a pane, highlighted when it has focus
+    fn pane_border(&self, pane: Pane) -> Style {
+        if self.mode == ConsoleMode::Normal && self.focus == pane {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        }
+    }
+
     /// Render activity log
     fn render_activity_log(&self, f: &mut Frame, area: Rect) {
         let items: Vec<ListItem> = self
             .activity_log
             .iter()
+            .skip(self.log_scroll)
             .map(|entry| {
                 let (prefix, style) = match entry.level {
                     LogLevel::Info => ("ℹ", Style::default().fg(Color::Cyan)),
@@ -1243,86 +1626,92 @@ impl Console {
             })
             .collect();
 
+        let title = if self.log_scroll > 0 {
+            format!("Activity Log ({} newer above)", self.log_scroll)
+        } else {
+            "Activity Log".to_string()
+        };
         let activity_log = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::White))
-                .title("Activity Log"),
+                .border_style(self.pane_border(Pane::Log))
+                .title(title),
         );
 
         f.render_widget(activity_log, area);
     }
 
-    /// Render footer with keyboard shortcuts
-    fn render_footer(&self, f: &mut Frame, area: Rect) {
-        let shortcuts = match self.mode {
+    /// Footer shortcuts for the current mode: key, action and key color
+    ///
+    /// Each one is also a button; see [`Console::footer_button_at`].
+    fn footer_shortcuts(&self) -> Vec<(&'static str, &'static str, Color)> {
+        match self.mode {
             ConsoleMode::Normal => vec![
-                Span::styled("q", Style::default().fg(Color::Cyan)),
-                Span::raw(":Quit  "),
-                Span::styled("i", Style::default().fg(Color::Cyan)),
-                Span::raw(":Commit  "),
-                Span::styled("l", Style::default().fg(Color::Cyan)),
-                Span::raw(":Log  "),
-                Span::styled("d", Style::default().fg(Color::Cyan)),
-                Span::raw(":Diff  "),
-                Span::styled("s", Style::default().fg(Color::Cyan)),
-                Span::raw(":Search  "),
-                Span::styled("k", Style::default().fg(Color::Cyan)),
-                Span::raw(":Hooks  "),
-                Span::styled("?", Style::default().fg(Color::Cyan)),
-                Span::raw(":Help"),
+                ("q", "Quit", Color::Cyan),
+                ("i", "Commit", Color::Cyan),
+                ("l", "Log", Color::Cyan),
+                ("d", "Diff", Color::Cyan),
+                ("s", "Search", Color::Cyan),
+                ("k", "Hooks", Color::Cyan),
+                ("u", "Uploads", Color::Cyan),
+                ("p", "Layout", Color::Cyan),
+                ("?", "Help", Color::Cyan),
             ],
             ConsoleMode::CommitDialog => vec![
-                Span::styled("Tab", Style::default().fg(Color::Cyan)),
-                Span::raw(":Next Field  "),
-                Span::styled("Enter", Style::default().fg(Color::Green)),
-                Span::raw(":Submit  "),
-                Span::styled("Esc", Style::default().fg(Color::Red)),
-                Span::raw(":Cancel"),
+                ("Tab", "Next Field", Color::Cyan),
+                ("Enter", "Submit", Color::Green),
+                ("Esc", "Cancel", Color::Red),
             ],
             ConsoleMode::RestoreBrowser => vec![
-                Span::styled("↑↓", Style::default().fg(Color::Cyan)),
-                Span::raw(":Navigate  "),
-                Span::styled("Enter", Style::default().fg(Color::Green)),
-                Span::raw(":Restore  "),
-                Span::styled("Esc", Style::default().fg(Color::Red)),
-                Span::raw(":Cancel"),
+                ("↑↓", "Navigate", Color::Cyan),
+                ("Enter", "Restore", Color::Green),
+                ("Esc", "Cancel", Color::Red),
             ],
             ConsoleMode::Compare => vec![
-                Span::styled("Tab", Style::default().fg(Color::Cyan)),
-                Span::raw(":Switch  "),
-                Span::styled("↑↓", Style::default().fg(Color::Cyan)),
-                Span::raw(":Navigate  "),
-                Span::styled("Enter", Style::default().fg(Color::Green)),
-                Span::raw(":Compare  "),
-                Span::styled("Esc", Style::default().fg(Color::Red)),
-                Span::raw(":Cancel"),
+                ("Tab", "Switch", Color::Cyan),
+                ("↑↓", "Navigate", Color::Cyan),
+                ("Enter", "Compare", Color::Green),
+                ("Esc", "Cancel", Color::Red),
             ],
             ConsoleMode::Search => vec![
-                Span::styled("Type", Style::default().fg(Color::Cyan)),
-                Span::raw(":Query  "),
-                Span::styled("Enter", Style::default().fg(Color::Green)),
-                Span::raw(":Search  "),
-                Span::styled("↑↓", Style::default().fg(Color::Cyan)),
-                Span::raw(":Navigate  "),
-                Span::styled("Esc", Style::default().fg(Color::Red)),
-                Span::raw(":Cancel"),
+                ("Type", "Query", Color::Cyan),
+                ("Enter", "Search", Color::Green),
+                ("↑↓", "Navigate", Color::Cyan),
+                ("Esc", "Cancel", Color::Red),
             ],
             ConsoleMode::Hooks => vec![
-                Span::styled("↑↓", Style::default().fg(Color::Cyan)),
-                Span::raw(":Navigate  "),
-                Span::styled("d", Style::default().fg(Color::Red)),
-                Span::raw(":Delete  "),
-                Span::styled("r", Style::default().fg(Color::Cyan)),
-                Span::raw(":Refresh  "),
-                Span::styled("Esc", Style::default().fg(Color::Red)),
-                Span::raw(":Cancel"),
+                ("↑↓", "Navigate", Color::Cyan),
+                ("d", "Delete", Color::Red),
+                ("r", "Refresh", Color::Cyan),
+                ("Esc", "Cancel", Color::Red),
             ],
-            ConsoleMode::Help => vec![
-                Span::styled("Any Key", Style::default().fg(Color::Cyan)),
-                Span::raw(":Return to Console"),
+            ConsoleMode::Transfers => vec![
+                ("↑↓", "Navigate", Color::Cyan),
+                ("s", "Sync Now", Color::Green),
+                ("x", "Cancel Upload", Color::Red),
+                ("d", "Discard", Color::Red),
+                ("r", "Refresh", Color::Cyan),
+                ("Esc", "Close", Color::Red),
             ],
-        };
+            ConsoleMode::Help => vec![("Any Key", "Return to Console", Color::Cyan)],
+        }
+    }
+
+    /// Render footer with keyboard shortcuts
+    fn render_footer(&self, f: &mut Frame, area: Rect) {
+        let shortcuts = self.footer_shortcuts();
+        let last = shortcuts.len().saturating_sub(1);
+        let shortcuts: Vec<Span> = shortcuts
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, (key, action, color))| {
+                let gap = if i == last { "" } else { "  " };
+                [
+                    Span::styled(key, Style::default().fg(color)),
+                    Span::raw(format!(":{}{}", action, gap)),
+                ]
+            })
+            .collect();
 
         let footer = Paragraph::new(Line::from(shortcuts))
             .block(
@@ -1484,10 +1873,20 @@ impl Console {
             Line::from("  d         - Open compare mode (semantic diff)"),
             Line::from("  s         - Open search mode"),
             Line::from("  k         - Open hooks manager"),
-            Line::from("  r         - Refresh repository status"),
+            Line::from("  u         - Open queue and upload manager"),
+            Line::from("  r         - Refresh repository status and metadata"),
             Line::from("  c         - Clear activity log"),
+            Line::from("  Tab       - Move focus between panes"),
+            Line::from("  ↑ / ↓     - Scroll the activity log (PgUp/PgDn by page)"),
+            Line::from("  p         - Switch layout (side by side, stacked, log only)"),
+            Line::from("  < / >     - Shrink or grow the status panes"),
             Line::from("  ?  or h   - Show this help"),
             Line::from(""),
+            Line::from("Mouse:"),
+            Line::from("  Click a pane to focus it, or a footer shortcut to run it"),
+            Line::from("  Drag the border beside the activity log to resize"),
+            Line::from("  Scroll the activity log and lists with the wheel"),
+            Line::from(""),
             Line::from("Commit Dialog:"),
             Line::from("  Tab       - Move to next field"),
             Line::from("  Shift+Tab - Move to previous field"),
@@ -1517,6 +1916,14 @@ impl Console {
             Line::from("  r         - Refresh hook list"),
             Line::from("  Esc       - Close hooks mode"),
             Line::from(""),
+            Line::from("Queue and Upload Manager:"),
+            Line::from("  ↑ / ↓     - Navigate uploads and queue entries"),
+            Line::from("  s         - Sync the offline queue now"),
+            Line::from("  x         - Cancel selected interrupted upload"),
+            Line::from("  d         - Discard selected dead letter"),
+            Line::from("  r         - Refresh"),
+            Line::from("  Esc       - Close manager"),
+            Line::from(""),
             Line::from(Span::styled(
                 "Press any key to return to console",
                 Style::default().fg(Color::Green),
@@ -1800,6 +2207,83 @@ impl Console {
             f.render_widget(list, area);
         }
     }
+
+    /// Render queue and upload manager
+    fn render_transfers_mode(&self, f: &mut Frame, area: Rect) {
+        if self.transfers_state.items.is_empty() {
+            let empty_text = vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Nothing waiting to sync",
+                    Style::default().fg(Color::Green),
+                )),
+                Line::from(""),
+                Line::from("Queued operations and interrupted uploads show up here."),
+            ];
+
+            let empty_paragraph = Paragraph::new(empty_text)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::White))
+                        .title("Queue & Uploads"),
+                )
+                .alignment(Alignment::Center);
+
+            f.render_widget(empty_paragraph, area);
+            return;
+        }
+
+        let now = chrono::Utc::now();
+        let items: Vec<ListItem> = self
+            .transfers_state
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let style = if i == self.transfers_state.selected_index {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+
+                let kind_color = match item {
+                    TransferItem::Upload(_) => Color::Cyan,
+                    TransferItem::Queued(_) => Color::Green,
+                    TransferItem::DeadLetter(_) => Color::Red,
+                };
+
+                let content = Line::from(vec![
+                    Span::styled(
+                        format!("{:<12}", item.kind(now)),
+                        Style::default().fg(kind_color),
+                    ),
+                    Span::styled(item.summary(), style),
+                ]);
+
+                ListItem::new(content)
+            })
+            .collect();
+
+        let count = |matches: fn(&TransferItem) -> bool| {
+            self.transfers_state.items.iter().filter(|item| matches(item)).count()
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::White))
+                .title(format!(
+                    "Queue & Uploads ({} uploads, {} queued, {} dead letters)",
+                    count(|item| matches!(item, TransferItem::Upload(_))),
+                    count(|item| matches!(item, TransferItem::Queued(_))),
+                    count(|item| matches!(item, TransferItem::DeadLetter(_))),
+                )),
+        );
+
+        f.render_widget(list, area);
+    }
 }
 
 /// Format a SystemTime as HH:MM:SS
@@ -2280,6 +2764,46 @@ mod tests {
         assert_eq!(console.mode, ConsoleMode::Normal);
     }
 
+    #[test]
+    fn test_transfers_mode_navigation_and_actions() {
+        use crate::offline_queue::{QueueEntry, QueuedOperation};
+
+        let mut console = Console::new(PathBuf::from("/test/project.logicx"));
+        console.mode = ConsoleMode::Transfers;
+        let entry = |branch: &str| {
+            QueueEntry::new(QueuedOperation::PushCommits {
+                repo_path: "/test/project.logicx".to_string(),
+                branch: branch.to_string(),
+            })
+        };
+        console.transfers_state.items =
+            vec![TransferItem::Queued(entry("main")), TransferItem::Queued(entry("mix"))];
+
+        console
+            .handle_transfers_mode_key(KeyCode::Down, KeyModifiers::empty())
+            .unwrap();
+        console
+            .handle_transfers_mode_key(KeyCode::Down, KeyModifiers::empty())
+            .unwrap();
+        assert_eq!(console.transfers_state.selected_index, 1);
+
+        // Actions that don't apply to a queued entry leave it alone
+        console
+            .handle_transfers_mode_key(KeyCode::Char('x'), KeyModifiers::empty())
+            .unwrap();
+        assert_eq!(console.activity_log[0].message, "Select an upload to cancel");
+        console
+            .handle_transfers_mode_key(KeyCode::Char('d'), KeyModifiers::empty())
+            .unwrap();
+        assert_eq!(console.activity_log[0].message, "Select a dead letter to discard");
+        assert_eq!(console.transfers_state.items.len(), 2);
+
+        console
+            .handle_transfers_mode_key(KeyCode::Esc, KeyModifiers::empty())
+            .unwrap();
+        assert_eq!(console.mode, ConsoleMode::Normal);
+    }
+
     // Mode Transition Tests
 
     #[test]
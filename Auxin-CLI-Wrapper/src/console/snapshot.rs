@@ -0,0 +1,290 @@
+//! One-shot console output for CI and remote sessions
+//!
+//! `auxin console --once` gathers what the console shows (daemon status,
+//! repository status, the project panel, recent activity, the project lock,
+//! and the queue and uploads) a single time and prints it as plain text, or
+//! with `--json` as one JSON document. Parts that can't be gathered are left
+//! empty and listed under `errors`, so a snapshot is printed either way.
+
+use super::{MilestoneSummary, ProjectDashboard, RepositoryStatus, TransferItem, DASHBOARD_HISTORY};
+use crate::badge_state::{LockBadge, LockSnapshot};
+use crate::collaboration::{Activity, ActivityFeed};
+use crate::daemon_client::{DaemonClient, DaemonStatus};
+use crate::{OfflineQueue, OxenRepository, UploadConfig};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Activity entries a snapshot includes
+pub const SNAPSHOT_ACTIVITY: usize = 10;
+
+/// Everything the console shows, gathered once
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsoleSnapshot {
+    pub project: PathBuf,
+    pub taken_at: DateTime<Utc>,
+    pub daemon: DaemonStatus,
+    /// Working tree counts; `None` outside an Oxen repository
+    pub repo: Option<RepositoryStatus>,
+    /// The project panel: latest milestone, trends, unresolved comments
+    pub dashboard: Option<ProjectDashboard>,
+    /// Recent activity, newest first
+    pub activity: Vec<Activity>,
+    pub lock: LockSnapshot,
+    /// Uploads, queued operations and dead letters, as the `u` view lists them
+    pub transfers: Vec<TransferRow>,
+    /// What couldn't be gathered
+    pub errors: Vec<String>,
+}
+
+/// One row of the queue and upload list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TransferRow {
+    pub kind: String,
+    pub summary: String,
+}
+
+impl ConsoleSnapshot {
+    /// Gather a snapshot for the project at `project`
+    pub async fn gather(project: &Path) -> Self {
+        let mut errors = Vec::new();
+        let daemon = DaemonClient::new().status().unwrap_or_else(|e| {
+            errors.push(format!("daemon: {}", e));
+            DaemonStatus::default()
+        });
+
+        let (mut repo, mut dashboard, mut activity) = (None, None, Vec::new());
+        if project.join(".oxen").exists() {
+            let oxen = OxenRepository::new(project);
+            match oxen.status().await {
+                Ok(status) => {
+                    repo = Some(RepositoryStatus {
+                        staged: status.staged.len(),
+                        modified: status.modified.len(),
+                        untracked: status.untracked.len(),
+                    })
+                }
+                Err(e) => errors.push(format!("repository: {}", e)),
+            }
+            match oxen.get_history(Some(DASHBOARD_HISTORY)).await {
+                Ok(history) => dashboard = Some(ProjectDashboard::build(project, &history)),
+                Err(e) => errors.push(format!("project panel: {}", e)),
+            }
+            match ActivityFeed::new().get_recent_activity(project, SNAPSHOT_ACTIVITY) {
+                Ok(recent) => activity = recent,
+                Err(e) => errors.push(format!("activity: {}", e)),
+            }
+        } else {
+            errors.push("repository: not an Oxen repository".to_string());
+        }
+
+        let lock = LockSnapshot::from(&crate::quick_actions::lock_state(project));
+
+        let transfers = match OfflineQueue::new() {
+            Ok(queue) => {
+                let uploads =
+                    crate::chunked_upload::resumable_sessions(&UploadConfig::default().state_dir)
+                        .unwrap_or_default();
+                TransferRow::from_items(&TransferItem::collect(&queue, uploads), Utc::now())
+            }
+            Err(e) => {
+                errors.push(format!("queue: {}", e));
+                Vec::new()
+            }
+        };
+
+        Self {
+            project: project.to_path_buf(),
+            taken_at: Utc::now(),
+            daemon,
+            repo,
+            dashboard,
+            activity,
+            lock,
+            transfers,
+            errors,
+        }
+    }
+
+    /// Plain-text rendering, one section per console panel
+    pub fn to_text(&self) -> String {
+        let daemon = match (self.daemon.is_running, self.daemon.pid) {
+            (true, Some(pid)) => format!("running (pid {})", pid),
+            (true, None) => "running".to_string(),
+            (false, _) => "stopped".to_string(),
+        };
+        let repo = match &self.repo {
+            Some(repo) => format!(
+                "{} staged, {} modified, {} untracked",
+                repo.staged, repo.modified, repo.untracked
+            ),
+            None => "unknown".to_string(),
+        };
+        let lock = match (self.lock.state, &self.lock.holder) {
+            (LockBadge::Unlocked, _) => "unlocked".to_string(),
+            (LockBadge::Mine, _) => "held by you".to_string(),
+            (LockBadge::Other, Some(holder)) => format!("held by {}", holder),
+            (LockBadge::Other, None) => "held by someone else".to_string(),
+        };
+
+        let mut lines = vec![
+            format!("Project:    {}", self.project.display()),
+            format!("Taken:      {}", self.taken_at.format("%Y-%m-%d %H:%M:%S UTC")),
+            format!("Daemon:     {}", daemon),
+            format!("Repository: {}", repo),
+            format!("Lock:       {}", lock),
+        ];
+
+        if let Some(dashboard) = &self.dashboard {
+            lines.push(match &dashboard.milestone {
+                Some(milestone) => format!("Milestone:  {}", milestone_summary(milestone)),
+                None => "Milestone:  none".to_string(),
+            });
+            lines.push(format!("Unresolved comments: {}", dashboard.unresolved_comments));
+        }
+
+        lines.push(String::new());
+        lines.push("Recent activity:".to_string());
+        if self.activity.is_empty() {
+            lines.push("  (none)".to_string());
+        }
+        for activity in &self.activity {
+            lines.push(format!(
+                "  {}  {:<14} {:<16} {}",
+                activity.timestamp.format("%Y-%m-%d %H:%M"),
+                activity.activity_type.label(),
+                activity.author,
+                activity.message.lines().next().unwrap_or("")
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Queue and uploads:".to_string());
+        if self.transfers.is_empty() {
+            lines.push("  (none)".to_string());
+        }
+        for row in &self.transfers {
+            lines.push(format!("  {:<12} {}", row.kind, row.summary));
+        }
+
+        if !self.errors.is_empty() {
+            lines.push(String::new());
+            lines.push("Not available:".to_string());
+            for error in &self.errors {
+                lines.push(format!("  {}", error));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+/// `abc1234 Mix v2 (BPM 122, key A Minor, 24 tracks)`
+fn milestone_summary(milestone: &MilestoneSummary) -> String {
+    let short = &milestone.commit_id[..milestone.commit_id.len().min(7)];
+    let mut details = Vec::new();
+    if let Some(bpm) = milestone.bpm {
+        details.push(format!("BPM {}", bpm));
+    }
+    if let Some(key) = &milestone.key {
+        details.push(format!("key {}", key));
+    }
+    if let Some(tracks) = milestone.tracks {
+        details.push(format!("{} tracks", tracks));
+    }
+    if let Some(units) = &milestone.units {
+        details.push(format!("units {}", units));
+    }
+    if let Some(layers) = milestone.layers {
+        details.push(format!("{} layers", layers));
+    }
+
+    if details.is_empty() {
+        format!("{} {}", short, milestone.message)
+    } else {
+        format!("{} {} ({})", short, milestone.message, details.join(", "))
+    }
+}
+
+impl TransferRow {
+    fn from_items(items: &[TransferItem], now: DateTime<Utc>) -> Vec<Self> {
+        items
+            .iter()
+            .map(|item| TransferRow {
+                kind: item.kind(now).to_string(),
+                summary: item.summary(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collaboration::ActivityType;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_snapshot_text_and_json() {
+        let snapshot = ConsoleSnapshot {
+            project: PathBuf::from("/music/Song.logicx"),
+            taken_at: Utc::now(),
+            daemon: DaemonStatus {
+                is_running: true,
+                pid: Some(4242),
+                ..Default::default()
+            },
+            repo: Some(RepositoryStatus {
+                staged: 2,
+                modified: 3,
+                untracked: 1,
+            }),
+            dashboard: Some(ProjectDashboard {
+                milestone: Some(MilestoneSummary {
+                    commit_id: "abc1234def".to_string(),
+                    message: "Mix v2".to_string(),
+                    bpm: Some(122.0),
+                    key: Some("A Minor".to_string()),
+                    tracks: Some(24),
+                    ..Default::default()
+                }),
+                unresolved_comments: 2,
+                ..Default::default()
+            }),
+            activity: vec![Activity {
+                id: "abc1234".to_string(),
+                activity_type: ActivityType::Commit,
+                author: "sam".to_string(),
+                timestamp: Utc::now(),
+                message: "Mix v2\n\nBPM: 122".to_string(),
+                metadata: HashMap::new(),
+            }],
+            lock: LockSnapshot {
+                state: LockBadge::Other,
+                holder: Some("alex".to_string()),
+            },
+            transfers: vec![TransferRow {
+                kind: "queued".to_string(),
+                summary: "Push main to remote".to_string(),
+            }],
+            errors: Vec::new(),
+        };
+
+        let text = snapshot.to_text();
+        assert!(text.contains("Daemon:     running (pid 4242)"));
+        assert!(text.contains("Repository: 2 staged, 3 modified, 1 untracked"));
+        assert!(text.contains("Lock:       held by alex"));
+        assert!(text.contains("Milestone:  abc1234 Mix v2 (BPM 122, key A Minor, 24 tracks)"));
+        assert!(text.contains("Unresolved comments: 2"));
+        assert!(text.contains("sam              Mix v2\n"));
+        assert!(text.contains("  queued       Push main to remote"));
+        assert!(!text.contains("Not available"));
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["daemon"]["pid"], 4242);
+        assert_eq!(json["repo"]["modified"], 3);
+        assert_eq!(json["lock"]["state"], "other");
+        assert_eq!(json["transfers"][0]["kind"], "queued");
+        assert_eq!(json["dashboard"]["unresolved_comments"], 2);
+        assert_eq!(json["activity"][0]["author"], "sam");
+    }
+}
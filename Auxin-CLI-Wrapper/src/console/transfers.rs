@@ -0,0 +1,138 @@
+//! Queue and upload panel for the console
+//!
+//! Lists the work `auxin sync-status` reports as waiting: offline queue
+//! entries still to sync, entries dead-lettered after failing too often, and
+//! push upload sessions, running or interrupted. The console acts on the
+//! selected item: sync the queue now, cancel an interrupted upload so the
+//! next push starts over, or discard a dead letter.
+
+use crate::chunked_upload::{UploadSession, UploadStatus};
+use crate::offline_queue::{OfflineQueue, QueueEntry};
+use chrono::{DateTime, Utc};
+
+/// One row of the panel
+#[derive(Debug, Clone)]
+pub enum TransferItem {
+    /// Waiting in the offline queue
+    Queued(QueueEntry),
+    /// Set aside after too many failed attempts
+    DeadLetter(QueueEntry),
+    /// A push's upload session, running or interrupted
+    Upload(UploadSession),
+}
+
+impl TransferItem {
+    /// Rows for the queue and upload sessions: uploads, then queued work in
+    /// sync order, then dead letters
+    pub fn collect(queue: &OfflineQueue, uploads: Vec<UploadSession>) -> Vec<TransferItem> {
+        let mut queued: Vec<QueueEntry> = queue.pending().into_iter().cloned().collect();
+        queued.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.queued_at.cmp(&b.queued_at)));
+
+        let mut items: Vec<TransferItem> = uploads.into_iter().map(TransferItem::Upload).collect();
+        items.extend(queued.into_iter().map(TransferItem::Queued));
+        items.extend(
+            queue
+                .dead_letters()
+                .into_iter()
+                .cloned()
+                .map(TransferItem::DeadLetter),
+        );
+        items
+    }
+
+    /// Short label for the row's kind
+    pub fn kind(&self, now: DateTime<Utc>) -> &'static str {
+        match self {
+            TransferItem::Queued(_) => "queued",
+            TransferItem::DeadLetter(_) => "dead letter",
+            TransferItem::Upload(session) if session.is_active(now) => "uploading",
+            TransferItem::Upload(session) if session.status == UploadStatus::Aborted => "aborted",
+            TransferItem::Upload(_) => "interrupted",
+        }
+    }
+
+    /// What the row is about
+    pub fn summary(&self) -> String {
+        match self {
+            TransferItem::Queued(entry) => {
+                let mut summary = entry.operation.description();
+                if entry.attempts > 0 {
+                    summary.push_str(&format!(" ({} failed attempts)", entry.attempts));
+                }
+                summary
+            }
+            TransferItem::DeadLetter(entry) => {
+                let error = entry
+                    .dead_letter
+                    .as_ref()
+                    .and_then(|dead| dead.error_chain.first())
+                    .or(entry.last_error.as_ref());
+                match error {
+                    Some(error) => format!("{}: {}", entry.operation.description(), error),
+                    None => entry.operation.description(),
+                }
+            }
+            TransferItem::Upload(session) => {
+                let project = std::path::Path::new(&session.repo_path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| session.repo_path.clone());
+                format!(
+                    "{} → {}:{} {:.0}% of {}",
+                    project,
+                    session.remote,
+                    session.branch,
+                    session.percentage(),
+                    crate::UploadProgress::bytes_string(session.total_bytes)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::offline_queue::QueuedOperation;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collect_orders_uploads_then_queue() {
+        let dir = TempDir::new().unwrap();
+        let mut queue = OfflineQueue::with_dir(dir.path().to_path_buf()).unwrap();
+        queue
+            .enqueue(QueuedOperation::SyncComments {
+                repo_path: "/music/Song.logicx".to_string(),
+            })
+            .unwrap();
+        queue
+            .enqueue_with_priority(
+                QueuedOperation::PushCommits {
+                    repo_path: "/music/Song.logicx".to_string(),
+                    branch: "main".to_string(),
+                },
+                10,
+            )
+            .unwrap();
+
+        let mut session = UploadSession::new(Path::new("/music/Song.logicx"), "origin", "main");
+        session.status = UploadStatus::InProgress;
+        session.total_bytes = 2_000_000;
+        session.bytes_uploaded = 500_000;
+        session.last_activity = Utc::now() - chrono::Duration::hours(1);
+
+        let items = TransferItem::collect(&queue, vec![session]);
+        let now = Utc::now();
+        let rows: Vec<(&str, String)> =
+            items.iter().map(|item| (item.kind(now), item.summary())).collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("interrupted", "Song.logicx → origin:main 25% of 2.0 MB".to_string()),
+                ("queued", "Push main to remote".to_string()),
+                ("queued", "Sync comments for /music/Song.logicx".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! Secrets kept in the system keychain instead of config files
+//!
+//! [`CredentialStore`] hides where a secret lives. [`SystemKeychain`] uses
+//! the macOS Keychain (Security framework), the Secret Service on Linux
+//! (`secret-tool`: GNOME Keyring, KWallet) and the Windows Credential
+//! Locker (PowerShell).
+//!
+//! The server token is stored under the server's URL, and `[cli] token`
+//! holds the reference `"keychain"` in its place, as does a
+//! `[servers.<name>] token`. [`migrate_config_tokens`] moves plaintext
+//! tokens out of the user and project config files, and
+//! [`server_token`] reads the token back wherever the CLI talks to the
+//! server.
+
+use anyhow::{anyhow, Context, Result};
+use auxin_config::Config;
+use colored::Colorize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+#[cfg(target_os = "macos")]
+use security_framework::passwords;
+
+use crate::config_edit;
+
+/// What `[cli] token` holds when the token is in the keychain
+pub const KEYCHAIN_REF: &str = "keychain";
+
+/// Keychain service the server tokens are stored under
+const SERVICE: &str = "auxin-server-token";
+
+/// Somewhere secrets can be kept, by account
+pub trait CredentialStore {
+    /// The secret stored for `account`, if any
+    fn get(&self, account: &str) -> Result<Option<String>>;
+    /// Store `secret` for `account`, replacing any previous one
+    fn set(&self, account: &str, secret: &str) -> Result<()>;
+    /// Forget the secret for `account`; forgetting a missing one succeeds
+    fn delete(&self, account: &str) -> Result<()>;
+}
+
+/// The platform's keychain
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemKeychain;
+
+#[cfg(target_os = "macos")]
+impl CredentialStore for SystemKeychain {
+    // The Security framework directly: `security add-generic-password`
+    // only takes the secret as an argument, where any process can see it
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        match passwords::get_generic_password(SERVICE, account) {
+            Ok(secret) => Ok(Some(String::from_utf8_lossy(&secret).into_owned())),
+            Err(e) if e.code() == ITEM_NOT_FOUND => Ok(None),
+            Err(e) => Err(anyhow!("Failed to read the keychain: {}", e)),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<()> {
+        passwords::set_generic_password(SERVICE, account, secret.as_bytes())
+            .map_err(|e| anyhow!("Failed to write to the keychain: {}", e))
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        match passwords::delete_generic_password(SERVICE, account) {
+            Err(e) if e.code() != ITEM_NOT_FOUND => Err(anyhow!("Failed to delete: {}", e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `errSecItemNotFound`
+#[cfg(target_os = "macos")]
+const ITEM_NOT_FOUND: i32 = -25300;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl CredentialStore for SystemKeychain {
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        let output = run(
+            "secret-tool",
+            &["lookup", "service", SERVICE, "account", account],
+            None,
+        )?;
+        // A missing item exits 1 without output
+        match (output.status.success(), output.stdout.is_empty()) {
+            (true, false) => Ok(Some(trim_secret(&output.stdout))),
+            (_, true) if output.stderr.is_empty() => Ok(None),
+            _ => Err(failure("read the Secret Service keyring", &output)),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<()> {
+        let label = format!("auxin server token ({})", account);
+        let output = run(
+            "secret-tool",
+            &["store", "--label", &label, "service", SERVICE, "account", account],
+            Some(secret),
+        )?;
+        check(output, "write to the Secret Service keyring")
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        let output = run(
+            "secret-tool",
+            &["clear", "service", SERVICE, "account", account],
+            None,
+        )?;
+        // Clearing a missing item exits 1 without output
+        if output.status.success() || output.stderr.is_empty() {
+            Ok(())
+        } else {
+            Err(failure("delete from the Secret Service keyring", &output))
+        }
+    }
+}
+
+#[cfg(windows)]
+impl CredentialStore for SystemKeychain {
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        let script = format!(
+            "{} try {{ $c = $vault.Retrieve('{}', '{}'); $c.RetrievePassword(); $c.Password }} \
+             catch {{ exit 44 }}",
+            VAULT,
+            SERVICE,
+            powershell_quote(account)
+        );
+        let output = run("powershell", &["-NoProfile", "-Command", &script], None)?;
+        match output.status.code() {
+            Some(0) => Ok(Some(trim_secret(&output.stdout))),
+            Some(44) => Ok(None),
+            _ => Err(failure("read the Credential Locker", &output)),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<()> {
+        // The token arrives on stdin so it never shows in a process list
+        let script = format!(
+            "{} $s = [Console]::In.ReadToEnd(); \
+             $vault.Add((New-Object Windows.Security.Credentials.PasswordCredential \
+             -ArgumentList '{}', '{}', $s))",
+            VAULT,
+            SERVICE,
+            powershell_quote(account)
+        );
+        let output = run("powershell", &["-NoProfile", "-Command", &script], Some(secret))?;
+        check(output, "write to the Credential Locker")
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        let script = format!(
+            "{} try {{ $vault.Remove($vault.Retrieve('{}', '{}')) }} catch {{ }}",
+            VAULT,
+            SERVICE,
+            powershell_quote(account)
+        );
+        let output = run("powershell", &["-NoProfile", "-Command", &script], None)?;
+        check(output, "delete from the Credential Locker")
+    }
+}
+
+#[cfg(windows)]
+const VAULT: &str = "[void][Windows.Security.Credentials.PasswordVault,\
+    Windows.Security.Credentials,ContentType=WindowsRuntime]; \
+    $vault = New-Object Windows.Security.Credentials.PasswordVault;";
+
+#[cfg(windows)]
+fn powershell_quote(s: &str) -> String {
+    s.replace('\'', "''")
+}
+
+fn run(program: &str, args: &[&str], stdin: Option<&str>) -> Result<Output> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {} (is a system keychain available?)", program))?;
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes())?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+fn check(output: Output, action: &str) -> Result<()> {
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(failure(action, &output))
+    }
+}
+
+fn failure(action: &str, output: &Output) -> anyhow::Error {
+    anyhow!(
+        "Failed to {}: {}",
+        action,
+        String::from_utf8_lossy(&output.stderr).trim()
+    )
+}
+
+fn trim_secret(stdout: &[u8]) -> String {
+    String::from_utf8_lossy(stdout).trim_end_matches(['\n', '\r']).to_string()
+}
+
+/// Whether `token`, as written in a config file, is a secret that belongs
+/// in the keychain rather than a reference to one
+pub fn is_plaintext(token: &str) -> bool {
+    let token = token.trim();
+    !token.is_empty() && token != KEYCHAIN_REF && !token.contains("${")
+}
+
+/// The token for the server in `[cli]`, reading it from the keychain when
+/// the config refers there
+pub fn server_token(config: &Config) -> Option<String> {
+    server_token_from(&SystemKeychain, config)
+}
+
+pub fn server_token_from(store: &dyn CredentialStore, config: &Config) -> Option<String> {
+    let token = config.cli.token.trim();
+    if token.is_empty() {
+        return None;
+    }
+    if token != KEYCHAIN_REF {
+        return Some(token.to_string());
+    }
+    match store.get(&config.cli.url) {
+        Ok(Some(token)) => Some(token),
+        Ok(None) => {
+            crate::warn!(
+                "No server token in the keychain for {}; set one with 'auxin server set token'",
+                config.cli.url
+            );
+            None
+        }
+        Err(e) => {
+            crate::warn!("Couldn't read the server token from the keychain: {}", e);
+            None
+        }
+    }
+}
+
+/// Store `token` for `url` and check it reads back
+pub fn store_server_token(store: &dyn CredentialStore, url: &str, token: &str) -> Result<()> {
+    store.set(url, token)?;
+    match store.get(url)? {
+        Some(stored) if stored == token => Ok(()),
+        _ => Err(anyhow!("The keychain didn't keep the token for {}", url)),
+    }
+}
+
+/// The config files a plaintext token may sit in: the user's, then the
+/// project's
+pub fn config_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = dirs::home_dir()
+        .map(|home| home.join(".auxin/config.toml"))
+        .into_iter()
+        .collect();
+    files.push(PathBuf::from(".auxin/config.toml"));
+    files
+}
+
+/// Move plaintext tokens in `files` into `store`, leaving
+/// `token = "keychain"` behind
+///
+/// Covers `[cli] token` and each `[servers.<name>] token`. A server's token
+/// is stored under its own `url`; the `[cli]` one under the `[cli] url` in
+/// effect for its file: its own, else that of a file before it, else the
+/// default. A file is only rewritten once its tokens read back from the
+/// store. Returns the files that were scrubbed.
+pub fn migrate_config_tokens(
+    store: &dyn CredentialStore,
+    files: &[PathBuf],
+) -> Result<Vec<PathBuf>> {
+    let mut cli_url = Config::default().cli.url;
+    let mut scrubbed = Vec::new();
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(file) else {
+            continue;
+        };
+        let value: toml::Value = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
+        let str_at = |table: &toml::Value, key: &str| table.get(key)?.as_str().map(str::to_string);
+
+        let mut tokens = Vec::new();
+        if let Some(cli) = value.get("cli") {
+            if let Some(url) = str_at(cli, "url") {
+                cli_url = url;
+            }
+            tokens.push(("cli".to_string(), cli_url.clone(), str_at(cli, "token")));
+        }
+        for (name, server) in value.get("servers").and_then(|s| s.as_table()).into_iter().flatten() {
+            if let Some(url) = str_at(server, "url") {
+                tokens.push((format!("servers.{}", name), url, str_at(server, "token")));
+            }
+        }
+
+        let mut edited = content.clone();
+        for (table, url, token) in tokens {
+            let Some(token) = token.filter(|token| is_plaintext(token)) else {
+                continue;
+            };
+            match config_edit::set_value(&edited, &table, "token", Some(KEYCHAIN_REF)) {
+                Ok(next) => {
+                    store_server_token(store, &url, &token)?;
+                    edited = next;
+                }
+                Err(e) => crate::vlog!("Leaving the token in {}: {}", file.display(), e),
+            }
+        }
+        if edited != content {
+            config_edit::write_file(file, &edited)?;
+            scrubbed.push(file.clone());
+        }
+    }
+    Ok(scrubbed)
+}
+
+/// Refer `[servers.<name>]` in the user config to the keychain for its token
+pub fn use_keychain_for_server(name: &str) -> Result<PathBuf> {
+    let path = config_edit::user_config_path()?;
+    config_edit::update_file(&path, &format!("servers.{}", name), "token", Some(KEYCHAIN_REF))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct MemoryStore(RefCell<HashMap<String, String>>);
+
+    impl CredentialStore for MemoryStore {
+        fn get(&self, account: &str) -> Result<Option<String>> {
+            Ok(self.0.borrow().get(account).cloned())
+        }
+
+        fn set(&self, account: &str, secret: &str) -> Result<()> {
+            self.0.borrow_mut().insert(account.to_string(), secret.to_string());
+            Ok(())
+        }
+
+        fn delete(&self, account: &str) -> Result<()> {
+            self.0.borrow_mut().remove(account);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_migration_moves_tokens_into_the_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let user = temp_dir.path().join("user.toml");
+        let project = temp_dir.path().join("project.toml");
+        std::fs::write(
+            &user,
+            "# my server\n[cli]\nurl = \"https://auxin.studio\"\ntoken = \"abc123\"\n\n\
+             [lock]\ntoken = \"not-this-one\"\n\n\
+             [servers.studio-b]\nurl = \"https://b.example\"\ntoken = \"b-token\"\n",
+        )
+        .unwrap();
+        std::fs::write(&project, "[cli]\ntoken = \"${AUXIN_TOKEN}\"\n").unwrap();
+        let store = MemoryStore::default();
+
+        let files = vec![user.clone(), project.clone()];
+        assert_eq!(migrate_config_tokens(&store, &files).unwrap(), vec![user.clone()]);
+        assert_eq!(
+            std::fs::read_to_string(&user).unwrap(),
+            "# my server\n[cli]\nurl = \"https://auxin.studio\"\ntoken = \"keychain\"\n\n\
+             [lock]\ntoken = \"not-this-one\"\n\n\
+             [servers.studio-b]\nurl = \"https://b.example\"\ntoken = \"keychain\"\n"
+        );
+        // Each server's token is kept under its own URL
+        assert_eq!(store.get("https://b.example").unwrap().as_deref(), Some("b-token"));
+        // Environment references stay; a second run has nothing to do
+        assert!(std::fs::read_to_string(&project).unwrap().contains("${AUXIN_TOKEN}"));
+        assert!(migrate_config_tokens(&store, &files).unwrap().is_empty());
+
+        let mut config = Config::default();
+        config.cli.url = "https://auxin.studio".to_string();
+        config.cli.token = KEYCHAIN_REF.to_string();
+        assert_eq!(server_token_from(&store, &config).as_deref(), Some("abc123"));
+        config.cli.url = "https://elsewhere".to_string();
+        assert_eq!(server_token_from(&store, &config), None);
+    }
+}
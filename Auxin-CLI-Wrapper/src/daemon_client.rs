@@ -1,16 +1,28 @@
 /// Daemon client for communicating with Auxin LaunchAgent
 ///
 /// Provides CLI commands to control and query the background daemon service.
-/// Uses launchctl for daemon lifecycle management and status checks.
+/// Uses launchctl for daemon lifecycle management and status checks, and the
+/// daemon's health socket to tell a hung daemon from a working one.
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 /// LaunchAgent service identifier
 const LAUNCH_AGENT_LABEL: &str = "com.auxin.agent";
 
+/// CLI <-> daemon protocol version this CLI was built for
+///
+/// Must match `DaemonVersion.protocolVersion` in the LaunchAgent.
+pub const DAEMON_PROTOCOL_VERSION: u32 = 6;
+
+/// First protocol version whose daemon answers health checks
+pub const HEALTH_PROTOCOL_VERSION: u32 = 6;
+
 /// Daemon status information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DaemonStatus {
     /// Whether the daemon process is running
     pub is_running: bool,
@@ -20,6 +32,9 @@ pub struct DaemonStatus {
     pub project_count: Option<usize>,
     /// Daemon version (if available)
     pub version: Option<String>,
+    /// Daemon protocol version (if available)
+    #[serde(default)]
+    pub protocol_version: Option<u32>,
     /// Uptime in seconds (if available)
     pub uptime: Option<f64>,
 }
@@ -43,13 +58,7 @@ impl DaemonClient {
 
         if !output.status.success() {
             // Service not loaded
-            return Ok(DaemonStatus {
-                is_running: false,
-                pid: None,
-                project_count: None,
-                version: None,
-                uptime: None,
-            });
+            return Ok(DaemonStatus::default());
         }
 
         // Parse launchctl output
@@ -59,12 +68,18 @@ impl DaemonClient {
         // Format: "PID" = <number>;
         let pid = extract_pid_from_launchctl_output(&stdout);
 
+        // Only trust the version file if it was written by this process
+        let info = pid
+            .and_then(|_| read_daemon_info(&daemon_info_path()))
+            .filter(|info| info.pid.is_none() || info.pid == pid);
+
         Ok(DaemonStatus {
             is_running: pid.is_some(),
             pid,
             project_count: None, // Would need XPC/socket connection to get this
-            version: None,       // Would need XPC/socket connection to get this
-            uptime: None,        // Would need XPC/socket connection to get this
+            version: info.as_ref().map(|i| i.version.clone()),
+            protocol_version: info.map(|i| i.protocol_version),
+            uptime: None, // Would need XPC/socket connection to get this
         })
     }
 
@@ -155,6 +170,23 @@ impl DaemonClient {
         std::path::Path::new(&plist_path).exists()
     }
 
+    /// Ask the running daemon for its health, waiting at most `timeout`
+    pub fn health(&self, timeout: Duration) -> Result<DaemonHealth> {
+        read_health(&health_socket_path(), timeout)
+    }
+
+    /// Whether the daemon is running and answering, for the watchdog
+    pub fn liveness(&self, timeout: Duration) -> Result<Liveness> {
+        let status = self.status()?;
+        if !status.is_running {
+            return Ok(Liveness::NotRunning);
+        }
+        if status.protocol_version.is_none_or(|v| v < HEALTH_PROTOCOL_VERSION) {
+            return Ok(Liveness::Unsupported);
+        }
+        Ok(Liveness::assess(self.health(timeout)))
+    }
+
     /// Get the path to the daemon's log file
     pub fn log_path(&self) -> Result<String> {
         let home = std::env::var("HOME").context("HOME environment variable not set")?;
@@ -190,6 +222,178 @@ impl Default for DaemonClient {
     }
 }
 
+/// Version file written by a running daemon (`~/.auxin/daemon.json`)
+#[derive(Debug, Clone, Deserialize)]
+struct DaemonInfo {
+    version: String,
+    protocol_version: u32,
+    pid: Option<u32>,
+}
+
+fn daemon_info_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".auxin").join("daemon.json")
+}
+
+fn read_daemon_info(path: &Path) -> Option<DaemonInfo> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Reply to the daemon's health check (`~/.auxin/daemon.sock`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonHealth {
+    pub pid: Option<u32>,
+    pub version: Option<String>,
+    pub protocol_version: Option<u32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub generated_at: Option<DateTime<Utc>>,
+    /// One entry per monitored project
+    #[serde(default)]
+    pub projects: Vec<WatcherHealth>,
+}
+
+/// State of one project's file system watcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatcherHealth {
+    pub path: String,
+    /// Whether the watcher is still receiving events
+    pub watching: bool,
+    /// Last relevant change the watcher saw
+    pub last_event_at: Option<DateTime<Utc>>,
+}
+
+impl DaemonHealth {
+    /// Projects whose watcher has stopped
+    pub fn stopped_watchers(&self) -> Vec<&WatcherHealth> {
+        self.projects.iter().filter(|p| !p.watching).collect()
+    }
+
+    /// How long the daemon has been running
+    pub fn uptime(&self) -> Option<chrono::Duration> {
+        Some(self.generated_at? - self.started_at?)
+    }
+}
+
+/// Location of the daemon's health socket
+pub fn health_socket_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".auxin").join("daemon.sock")
+}
+
+/// Read one health reply from the socket at `path`
+#[cfg(unix)]
+pub fn read_health(path: &Path, timeout: Duration) -> Result<DaemonHealth> {
+    use std::io::Read;
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(path)
+        .with_context(|| format!("Failed to connect to {}", path.display()))?;
+    stream.set_read_timeout(Some(timeout))?;
+    let mut reply = String::new();
+    stream
+        .read_to_string(&mut reply)
+        .context("Daemon didn't answer the health check")?;
+    serde_json::from_str(&reply).context("Failed to parse the daemon's health reply")
+}
+
+#[cfg(not(unix))]
+pub fn read_health(_path: &Path, _timeout: Duration) -> Result<DaemonHealth> {
+    anyhow::bail!("Daemon health checks need a Unix socket")
+}
+
+/// What the watchdog makes of the daemon
+#[derive(Debug, Clone, PartialEq)]
+pub enum Liveness {
+    /// Running, answering, and every watcher is up
+    Healthy(DaemonHealth),
+
+    /// Running but not answering, or a watcher stopped; restart it
+    Unhealthy(String),
+
+    /// Not running; left alone, since it may have been stopped on purpose
+    NotRunning,
+
+    /// Running a protocol without health checks, so it can't be judged
+    Unsupported,
+}
+
+impl Liveness {
+    /// Judge a running daemon by its health reply
+    pub fn assess(health: Result<DaemonHealth>) -> Self {
+        let health = match health {
+            Ok(health) => health,
+            Err(e) => return Self::Unhealthy(format!("not responding: {:#}", e)),
+        };
+        let stopped: Vec<&str> = health
+            .stopped_watchers()
+            .iter()
+            .map(|p| p.path.as_str())
+            .collect();
+        if stopped.is_empty() {
+            Self::Healthy(health)
+        } else {
+            Self::Unhealthy(format!("watcher stopped for {}", stopped.join(", ")))
+        }
+    }
+}
+
+/// How the running daemon's protocol compares with this CLI's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaemonCompatibility {
+    /// Daemon and CLI speak the same protocol
+    Compatible,
+
+    /// Daemon is not running, so there's nothing to compare
+    NotRunning,
+
+    /// Daemon is running but predates version reporting
+    Unknown,
+
+    /// Daemon speaks an older protocol than this CLI
+    DaemonOlder { daemon_version: u32 },
+
+    /// Daemon speaks a newer protocol than this CLI
+    DaemonNewer { daemon_version: u32 },
+}
+
+impl DaemonCompatibility {
+    pub fn check(status: &DaemonStatus) -> Self {
+        if !status.is_running {
+            return Self::NotRunning;
+        }
+
+        match status.protocol_version {
+            None => Self::Unknown,
+            Some(v) if v < DAEMON_PROTOCOL_VERSION => Self::DaemonOlder { daemon_version: v },
+            Some(v) if v > DAEMON_PROTOCOL_VERSION => Self::DaemonNewer { daemon_version: v },
+            Some(_) => Self::Compatible,
+        }
+    }
+
+    /// Warning to show the user, including which side to upgrade
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            Self::Compatible | Self::NotRunning => None,
+            Self::Unknown => Some(
+                "Daemon doesn't report its version and may be outdated; \
+                 reinstall Auxin.app to upgrade it"
+                    .to_string(),
+            ),
+            Self::DaemonOlder { daemon_version } => Some(format!(
+                "Daemon speaks protocol v{} but this CLI expects v{}; \
+                 reinstall Auxin.app to upgrade the daemon",
+                daemon_version, DAEMON_PROTOCOL_VERSION
+            )),
+            Self::DaemonNewer { daemon_version } => Some(format!(
+                "Daemon speaks protocol v{} but this CLI expects v{}; \
+                 upgrade the auxin CLI to match",
+                daemon_version, DAEMON_PROTOCOL_VERSION
+            )),
+        }
+    }
+}
+
 /// Extract PID from launchctl list output
 fn extract_pid_from_launchctl_output(output: &str) -> Option<u32> {
     // Look for line like: "PID" = <number>;
@@ -242,6 +446,89 @@ mod tests {
         assert_eq!(pid, None);
     }
 
+    #[test]
+    fn test_read_daemon_info() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("daemon.json");
+        assert!(read_daemon_info(&path).is_none());
+
+        std::fs::write(
+            &path,
+            r#"{"version": "2.0.0", "protocol_version": 1, "pid": 42, "started_at": "x"}"#,
+        )
+        .unwrap();
+        let info = read_daemon_info(&path).unwrap();
+        assert_eq!(info.version, "2.0.0");
+        assert_eq!(info.protocol_version, 1);
+        assert_eq!(info.pid, Some(42));
+    }
+
+    #[test]
+    fn test_daemon_compatibility() {
+        let running = |protocol_version| DaemonStatus {
+            is_running: true,
+            protocol_version,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            DaemonCompatibility::check(&DaemonStatus::default()),
+            DaemonCompatibility::NotRunning
+        );
+        assert_eq!(
+            DaemonCompatibility::check(&running(Some(DAEMON_PROTOCOL_VERSION))),
+            DaemonCompatibility::Compatible
+        );
+        assert_eq!(DaemonCompatibility::check(&running(None)), DaemonCompatibility::Unknown);
+
+        let newer = DaemonCompatibility::check(&running(Some(DAEMON_PROTOCOL_VERSION + 1)));
+        assert!(newer.warning().unwrap().contains("upgrade the auxin CLI"));
+
+        let older = DaemonCompatibility::DaemonOlder { daemon_version: 0 };
+        assert!(older.warning().unwrap().contains("upgrade the daemon"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_health_and_assess() {
+        use std::io::Write;
+        use std::os::unix::net::UnixListener;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("daemon.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream
+                .write_all(
+                    br#"{"pid": 42, "version": "2.0.0", "protocol_version": 6,
+                        "started_at": "2024-05-01T10:00:00Z",
+                        "generated_at": "2024-05-01T12:30:00Z",
+                        "projects": [
+                            {"path": "/music/A.logicx", "watching": true,
+                             "last_event_at": "2024-05-01T12:00:00Z"},
+                            {"path": "/music/B.logicx", "watching": false}
+                        ]}"#,
+                )
+                .unwrap();
+        });
+
+        let health = read_health(&path, Duration::from_secs(5)).unwrap();
+        server.join().unwrap();
+        assert_eq!(health.pid, Some(42));
+        assert_eq!(health.uptime(), Some(chrono::Duration::minutes(150)));
+        assert!(health.projects[0].last_event_at.is_some());
+        assert_eq!(
+            Liveness::assess(Ok(health)),
+            Liveness::Unhealthy("watcher stopped for /music/B.logicx".to_string())
+        );
+
+        // Nobody listening any more
+        std::fs::remove_file(&path).unwrap();
+        let unanswered = Liveness::assess(read_health(&path, Duration::from_millis(100)));
+        assert!(matches!(unanswered, Liveness::Unhealthy(reason) if reason.starts_with("not responding")));
+    }
+
     #[test]
     fn test_daemon_client_creation() {
         let _client = DaemonClient::new();
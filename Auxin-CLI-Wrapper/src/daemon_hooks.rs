@@ -0,0 +1,403 @@
+//! Daemon event hooks
+//!
+//! Repository hooks (`.oxen/hooks`) run around commits made from the CLI.
+//! Daemon event hooks react to things the background daemon does on its own,
+//! such as creating an auto-commit or failing to push, so a studio can wire
+//! in its own integrations: OSC messages to a console, lighting cues, chat
+//! notifications and so on.
+//!
+//! Hooks are registered per user in `~/.auxin/daemon_hooks.json`. The daemon
+//! runs them by invoking `auxin daemon hooks fire`, which goes through the
+//! same script runner as repository hooks (stdin closed, working directory
+//! pinned, killed after a timeout).
+//!
+//! Every script receives `AUXIN_EVENT` and, when the event concerns a
+//! project, `AUXIN_PROJECT_PATH`. Event details are passed as
+//! `AUXIN_<KEY>`:
+//!
+//! - `auto-commit-created`: `AUXIN_COMMIT_ID`, `AUXIN_MESSAGE`
+//! - `lock-expiring`: `AUXIN_LOCKED_BY`, `AUXIN_EXPIRES_AT`
+//! - `push-failed`: `AUXIN_ERROR`
+//! - `auto-commit-paused`: `AUXIN_REASON`
+
+use crate::hooks::{run_script, ScriptOutcome};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Default time a daemon hook may run before it is killed
+pub const DEFAULT_DAEMON_HOOK_TIMEOUT_SECS: u64 = 30;
+
+/// Something the daemon did that hooks can react to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DaemonEvent {
+    /// The daemon committed a project after it went quiet
+    AutoCommitCreated,
+    /// A lock held on this machine is about to expire
+    LockExpiring,
+    /// Syncing queued operations to the server failed
+    PushFailed,
+    /// Auto-commits were paused after a change that looks like data loss
+    AutoCommitPaused,
+}
+
+impl DaemonEvent {
+    pub const ALL: [DaemonEvent; 4] = [
+        DaemonEvent::AutoCommitCreated,
+        DaemonEvent::LockExpiring,
+        DaemonEvent::PushFailed,
+        DaemonEvent::AutoCommitPaused,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DaemonEvent::AutoCommitCreated => "auto-commit-created",
+            DaemonEvent::LockExpiring => "lock-expiring",
+            DaemonEvent::PushFailed => "push-failed",
+            DaemonEvent::AutoCommitPaused => "auto-commit-paused",
+        }
+    }
+}
+
+impl fmt::Display for DaemonEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DaemonEvent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|e| e.as_str()).collect();
+                anyhow!("Unknown daemon event '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+/// A script registered to run on a daemon event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonHook {
+    /// Unique name used to list and remove the hook
+    pub name: String,
+    pub event: DaemonEvent,
+    /// Absolute path to the executable script
+    pub script: PathBuf,
+    /// Only run for this project; all projects if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<PathBuf>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_DAEMON_HOOK_TIMEOUT_SECS
+}
+
+impl DaemonHook {
+    /// Whether this hook should run for an event on a project
+    pub fn applies_to(&self, event: DaemonEvent, project: Option<&Path>) -> bool {
+        if self.event != event {
+            return false;
+        }
+        match (&self.project, project) {
+            (None, _) => true,
+            (Some(filter), Some(project)) => same_path(filter, project),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Outcome of running one hook
+#[derive(Debug)]
+pub struct HookRun {
+    pub name: String,
+    pub result: Result<ScriptOutcome>,
+}
+
+impl HookRun {
+    pub fn succeeded(&self) -> bool {
+        matches!(&self.result, Ok(outcome) if outcome.success)
+    }
+}
+
+/// Registered daemon hooks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DaemonHookRegistry {
+    #[serde(default)]
+    pub hooks: Vec<DaemonHook>,
+}
+
+impl DaemonHookRegistry {
+    /// Location of the registry file
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home).join(".auxin").join("daemon_hooks.json")
+    }
+
+    /// Load the registry; empty if the file does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read daemon hooks {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid daemon hooks file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write daemon hooks {}", path.display()))
+    }
+
+    /// Register a hook
+    pub fn add(&mut self, hook: DaemonHook) -> Result<()> {
+        if self.hooks.iter().any(|h| h.name == hook.name) {
+            bail!("A daemon hook named '{}' already exists", hook.name);
+        }
+        if !hook.script.is_file() {
+            bail!("Hook script not found: {}", hook.script.display());
+        }
+        if hook.timeout_secs == 0 {
+            bail!("Hook timeout must be at least one second");
+        }
+        self.hooks.push(hook);
+        Ok(())
+    }
+
+    /// Remove a hook by name; false if there was none
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.hooks.len();
+        self.hooks.retain(|h| h.name != name);
+        self.hooks.len() != before
+    }
+
+    /// Hooks that should run for an event, in registration order
+    pub fn matching(&self, event: DaemonEvent, project: Option<&Path>) -> Vec<&DaemonHook> {
+        self.hooks
+            .iter()
+            .filter(|h| h.applies_to(event, project))
+            .collect()
+    }
+
+    /// Run every hook registered for an event
+    ///
+    /// `details` are `(key, value)` pairs exported as `AUXIN_<KEY>`. Hooks
+    /// run one after another; a failing hook doesn't stop the rest.
+    pub fn fire(
+        &self,
+        event: DaemonEvent,
+        project: Option<&Path>,
+        details: &[(String, String)],
+    ) -> Vec<HookRun> {
+        let env = hook_env(event, project, details);
+
+        self.matching(event, project)
+            .into_iter()
+            .map(|hook| {
+                let cwd = project
+                    .filter(|p| p.is_dir())
+                    .or_else(|| hook.script.parent())
+                    .unwrap_or_else(|| Path::new("/"));
+                let timeout = Duration::from_secs(hook.timeout_secs);
+                HookRun {
+                    name: hook.name.clone(),
+                    result: run_script(&hook.script, cwd, &env, timeout),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Environment passed to daemon hook scripts
+fn hook_env(
+    event: DaemonEvent,
+    project: Option<&Path>,
+    details: &[(String, String)],
+) -> Vec<(String, String)> {
+    let mut env = vec![("AUXIN_EVENT".to_string(), event.to_string())];
+    if let Some(project) = project {
+        env.push((
+            "AUXIN_PROJECT_PATH".to_string(),
+            project.to_string_lossy().to_string(),
+        ));
+    }
+    for (key, value) in details {
+        let key = key.to_uppercase().replace('-', "_");
+        env.push((format!("AUXIN_{}", key), value.clone()));
+    }
+    env
+}
+
+/// Parse a `KEY=VALUE` event detail
+pub fn parse_detail(s: &str) -> Result<(String, String)> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => bail!("Expected KEY=VALUE, got '{}'", s),
+    }
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    if a == b {
+        return true;
+    }
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn hook(name: &str, event: DaemonEvent, project: Option<&str>) -> DaemonHook {
+        DaemonHook {
+            name: name.to_string(),
+            event,
+            script: PathBuf::from("/bin/true"),
+            project: project.map(PathBuf::from),
+            timeout_secs: DEFAULT_DAEMON_HOOK_TIMEOUT_SECS,
+        }
+    }
+
+    #[test]
+    fn test_event_names_round_trip() {
+        for event in DaemonEvent::ALL {
+            assert_eq!(event.as_str().parse::<DaemonEvent>().unwrap(), event);
+            let json = serde_json::to_string(&event).unwrap();
+            assert_eq!(json, format!("\"{}\"", event));
+        }
+        assert!("lock-expired".parse::<DaemonEvent>().is_err());
+    }
+
+    #[test]
+    fn test_matching_filters_event_and_project() {
+        let registry = DaemonHookRegistry {
+            hooks: vec![
+                hook("lights", DaemonEvent::AutoCommitCreated, None),
+                hook("osc", DaemonEvent::AutoCommitCreated, Some("/music/Song.logicx")),
+                hook("pager", DaemonEvent::PushFailed, None),
+            ],
+        };
+
+        let names = |event, project: Option<&str>| -> Vec<String> {
+            registry
+                .matching(event, project.map(Path::new))
+                .into_iter()
+                .map(|h| h.name.clone())
+                .collect()
+        };
+
+        let event = DaemonEvent::AutoCommitCreated;
+        assert_eq!(names(event, Some("/music/Song.logicx")), ["lights", "osc"]);
+        assert_eq!(names(event, Some("/music/Song.logicx/")), ["lights", "osc"]);
+        assert_eq!(names(event, Some("/music/Other.logicx")), ["lights"]);
+        assert_eq!(names(DaemonEvent::PushFailed, None), ["pager"]);
+        assert!(names(DaemonEvent::LockExpiring, None).is_empty());
+    }
+
+    #[test]
+    fn test_add_remove_and_persist() {
+        let temp = TempDir::new().unwrap();
+        let script = temp.path().join("cue.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        let path = temp.path().join("daemon_hooks.json");
+
+        let mut registry = DaemonHookRegistry::load(&path).unwrap();
+        assert!(registry.hooks.is_empty());
+
+        let mut cue = hook("cue", DaemonEvent::LockExpiring, None);
+        cue.script = script;
+        registry.add(cue.clone()).unwrap();
+        assert!(registry.add(cue).is_err());
+        assert!(registry
+            .add(hook("missing", DaemonEvent::LockExpiring, None).with_script("/no/such"))
+            .is_err());
+        registry.save(&path).unwrap();
+
+        let mut loaded = DaemonHookRegistry::load(&path).unwrap();
+        assert_eq!(loaded.hooks, registry.hooks);
+        assert!(loaded.remove("cue"));
+        assert!(!loaded.remove("cue"));
+    }
+
+    #[test]
+    fn test_hook_env() {
+        let details = vec![
+            ("commit-id".to_string(), "abc123".to_string()),
+            parse_detail("message=Auto-save: 2 files").unwrap(),
+        ];
+        let env = hook_env(
+            DaemonEvent::AutoCommitCreated,
+            Some(Path::new("/music/Song.logicx")),
+            &details,
+        );
+
+        assert_eq!(
+            env,
+            [
+                ("AUXIN_EVENT", "auto-commit-created"),
+                ("AUXIN_PROJECT_PATH", "/music/Song.logicx"),
+                ("AUXIN_COMMIT_ID", "abc123"),
+                ("AUXIN_MESSAGE", "Auto-save: 2 files"),
+            ]
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+        );
+        assert!(parse_detail("no-equals").is_err());
+        assert!(parse_detail("=value").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fire_runs_matching_hooks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let script = temp.path().join("record.sh");
+        let out = temp.path().join("out.txt");
+        fs::write(
+            &script,
+            format!("#!/bin/sh\necho \"$AUXIN_EVENT $AUXIN_ERROR\" >> {}\n", out.display()),
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let registry = DaemonHookRegistry {
+            hooks: vec![
+                hook("record", DaemonEvent::PushFailed, None).with_script(&script),
+                hook("other", DaemonEvent::LockExpiring, None).with_script(&script),
+            ],
+        };
+        let details = [("error".to_string(), "offline".to_string())];
+        let runs = registry.fire(DaemonEvent::PushFailed, None, &details);
+
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].succeeded());
+        assert_eq!(fs::read_to_string(&out).unwrap(), "push-failed offline\n");
+    }
+
+    impl DaemonHook {
+        fn with_script(mut self, script: impl Into<PathBuf>) -> Self {
+            self.script = script.into();
+            self
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Reading and filtering the daemon log for `auxin daemon logs`
+//!
+//! Commands the daemon runs append JSON [`Record`] lines (time, level,
+//! project, message) to the log. The daemon's own output is plain text;
+//! those lines are kept with a level read from `[WARN]`-style tags or the
+//! ⚠️ / ✗ markers, but have no time or project, so `--since` and `--project`
+//! leave them out.
+
+use crate::logger::{Level, Record};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use regex::Regex;
+use std::path::Path;
+
+/// One line of the daemon log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    pub ts: Option<DateTime<Utc>>,
+    pub level: Level,
+    pub project: Option<String>,
+    pub message: String,
+}
+
+impl LogLine {
+    /// Parse a structured record, or fall back to a plain text line
+    pub fn parse(line: &str) -> Self {
+        if let Ok(record) = serde_json::from_str::<Record>(line) {
+            return Self {
+                ts: Some(record.ts),
+                level: record.level,
+                project: record.project,
+                message: record.message,
+            };
+        }
+
+        let trimmed = line.trim_start();
+        let level = if trimmed.starts_with("[ERROR]") || trimmed.starts_with('✗') {
+            Level::Error
+        } else if trimmed.starts_with("[WARN]") || trimmed.starts_with('⚠') {
+            Level::Warn
+        } else if trimmed.starts_with("[DEBUG]") {
+            Level::Debug
+        } else {
+            Level::Info
+        };
+        Self {
+            ts: None,
+            level,
+            project: None,
+            message: line.to_string(),
+        }
+    }
+}
+
+/// Which lines `auxin daemon logs` shows
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Lowest level to show
+    pub level: Option<Level>,
+    pub since: Option<DateTime<Utc>>,
+    /// Project path, or its last components (e.g. `Song.logicx`)
+    pub project: Option<String>,
+    pub grep: Option<Regex>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, line: &LogLine) -> bool {
+        if self.level.is_some_and(|level| line.level < level) {
+            return false;
+        }
+        if let Some(since) = self.since {
+            if line.ts.is_none_or(|ts| ts < since) {
+                return false;
+            }
+        }
+        if let Some(project) = &self.project {
+            let Some(line_project) = &line.project else {
+                return false;
+            };
+            if !Path::new(line_project).ends_with(project) {
+                return false;
+            }
+        }
+        if let Some(grep) = &self.grep {
+            if !grep.is_match(&line.message) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Parse `--since`: an age such as `30m`, `2h` or `7d`, a date
+/// (`YYYY-MM-DD`, UTC midnight) or an RFC 3339 time
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Some(unit) = value.chars().last().filter(|c| c.is_ascii_alphabetic()) {
+        if let Ok(amount) = value[..value.len() - 1].parse::<i64>() {
+            let age = match unit {
+                's' => Duration::seconds(amount),
+                'm' => Duration::minutes(amount),
+                'h' => Duration::hours(amount),
+                'd' => Duration::days(amount),
+                'w' => Duration::weeks(amount),
+                _ => anyhow::bail!("Unknown unit in '{}' (use s, m, h, d or w)", value),
+            };
+            return Ok(now - age);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .with_context(|| format!("Invalid time '{}' (try 2h, 3d or 2024-05-01)", value))
+}
+
+/// The last `limit` lines of the log at `path` that pass `filter`, oldest first
+pub fn read(path: &Path, filter: &LogFilter, limit: usize) -> Result<Vec<LogLine>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let matching: Vec<LogLine> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(LogLine::parse)
+        .filter(|line| filter.matches(line))
+        .collect();
+    let skip = matching.len().saturating_sub(limit);
+    Ok(matching.into_iter().skip(skip).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_filters_structured_and_text_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("daemon.log");
+        std::fs::write(
+            &path,
+            concat!(
+                "🚀 Starting Oxen VCS Daemon...\n",
+                "⚠️  Failed to check status: timeout\n",
+                r#"{"ts":"2024-05-01T10:00:00Z","level":"info","project":"/music/Song.logicx","message":"Committed draft"}"#,
+                "\n",
+                r#"{"ts":"2024-05-02T10:00:00Z","level":"error","project":"/music/Other.logicx","message":"Push failed"}"#,
+                "\n",
+                r#"{"ts":"2024-05-03T10:00:00Z","level":"warn","project":"/music/Song.logicx","message":"Push retried"}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let all = read(&path, &LogFilter::default(), 50).unwrap();
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[1].level, Level::Warn);
+        assert_eq!(all[1].ts, None);
+
+        let warnings = LogFilter {
+            level: Some(Level::Warn),
+            ..Default::default()
+        };
+        let messages: Vec<String> = read(&path, &warnings, 2)
+            .unwrap()
+            .into_iter()
+            .map(|line| line.message)
+            .collect();
+        assert_eq!(messages, vec!["Push failed", "Push retried"]);
+
+        let song_since = LogFilter {
+            since: Some(parse_since("2024-05-02", Utc::now()).unwrap()),
+            project: Some("Song.logicx".to_string()),
+            grep: Some(Regex::new("(?i)push").unwrap()),
+            ..Default::default()
+        };
+        let lines = read(&path, &song_since, 50).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].message, "Push retried");
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = Utc::now();
+        assert_eq!(parse_since("2h", now).unwrap(), now - Duration::hours(2));
+        assert_eq!(parse_since("7d", now).unwrap(), now - Duration::days(7));
+        assert_eq!(
+            parse_since("2024-05-01T12:00:00+02:00", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2024-05-01T10:00:00+00:00"
+        );
+        assert!(parse_since("2x", now).is_err());
+        assert!(parse_since("yesterday", now).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Client delivery tracking: `auxin deliver`
+//!
+//! Answers "which version did we actually send?". Each delivery records
+//! the commit, who it went to, in what format, the commit's bounce and
+//! version label at the time, and who sent it, in `.auxin/deliveries.json`:
+//!
+//! ```json
+//! {
+//!   "deliveries": [
+//!     {
+//!       "commit_id": "8f3e2a1b...",
+//!       "recipient": "Client X",
+//!       "format": "24bit WAV",
+//!       "bounce": { "filename": "Mix v3.wav", "size_bytes": 52428800 },
+//!       "version": "v1.1",
+//!       "user": "me@studio-mac",
+//!       "delivered_at": "2026-10-16T14:02:11Z"
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::bounce::BounceManager;
+use crate::versioning::VersionLog;
+use crate::CommitInfo;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The bounce that was sent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveredBounce {
+    pub filename: String,
+    pub size_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+}
+
+/// One delivery of a commit to a client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Delivery {
+    pub commit_id: String,
+    /// Who received it, e.g. "Client X"
+    pub recipient: String,
+    /// What was sent, e.g. "24bit WAV"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// The commit's bounce, if it had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounce: Option<DeliveredBounce>,
+    /// The commit's version label, if it had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub user: String,
+    pub delivered_at: DateTime<Utc>,
+}
+
+/// Which deliveries `auxin deliver list` shows
+#[derive(Debug, Clone, Default)]
+pub struct DeliveryFilter {
+    /// Recipient name contains this, ignoring case
+    pub recipient: Option<String>,
+    /// Commit ID starts with this
+    pub commit: Option<String>,
+}
+
+impl DeliveryFilter {
+    pub fn matches(&self, delivery: &Delivery) -> bool {
+        let recipient = self.recipient.as_ref().is_none_or(|r| {
+            delivery
+                .recipient
+                .to_lowercase()
+                .contains(&r.to_lowercase())
+        });
+        let commit = self
+            .commit
+            .as_ref()
+            .is_none_or(|c| delivery.commit_id.starts_with(c.as_str()));
+        recipient && commit
+    }
+}
+
+/// Contents of `.auxin/deliveries.json`, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryLog {
+    #[serde(default)]
+    pub deliveries: Vec<Delivery>,
+}
+
+impl DeliveryLog {
+    /// Location of the delivery log for a repo
+    pub fn path(repo: &Path) -> PathBuf {
+        repo.join(".auxin").join("deliveries.json")
+    }
+
+    /// Read a repo's deliveries; a missing file means none
+    pub fn load(repo: &Path) -> Result<Self> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace the repo's delivery log atomically
+    pub fn save(&self, repo: &Path) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Deliveries of a commit, oldest first
+    pub fn for_commit<'a>(&'a self, commit_id: &'a str) -> impl Iterator<Item = &'a Delivery> {
+        self.deliveries
+            .iter()
+            .filter(move |d| d.commit_id == commit_id)
+    }
+
+    /// Matching deliveries, newest first
+    pub fn list(&self, filter: &DeliveryFilter) -> Vec<&Delivery> {
+        self.deliveries
+            .iter()
+            .rev()
+            .filter(|d| filter.matches(d))
+            .collect()
+    }
+}
+
+/// Record that `commit` was sent to `recipient`, noting its bounce and
+/// version label
+pub fn record(
+    repo: &Path,
+    commit: &CommitInfo,
+    recipient: &str,
+    format: Option<String>,
+    note: Option<String>,
+) -> Result<Delivery> {
+    let bounce = BounceManager::new(repo)
+        .get_bounce(&commit.id)
+        .ok()
+        .flatten()
+        .map(|b| DeliveredBounce {
+            filename: b.original_filename,
+            size_bytes: b.size_bytes,
+            duration_secs: b.duration_secs,
+        });
+    let version = VersionLog::load(repo)
+        .ok()
+        .and_then(|log| log.for_commit(&commit.id).map(|v| v.label.clone()));
+
+    let delivery = Delivery {
+        commit_id: commit.id.clone(),
+        recipient: recipient.trim().to_string(),
+        format,
+        bounce,
+        version,
+        note,
+        user: crate::lock_integration::get_user_identifier(),
+        delivered_at: Utc::now(),
+    };
+
+    let mut log = DeliveryLog::load(repo)?;
+    log.deliveries.push(delivery.clone());
+    log.save(repo)?;
+    Ok(delivery)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_list() {
+        let temp = TempDir::new().unwrap();
+        let commit = |id: &str| CommitInfo {
+            id: id.to_string(),
+            message: "Mix".to_string(),
+            ..Default::default()
+        };
+
+        let mut versions = VersionLog::default();
+        versions
+            .bump(
+                &commit("aaa111"),
+                crate::versioning::Bump::Minor,
+                "v{major}.{minor}",
+                None,
+                "me",
+            )
+            .unwrap();
+        versions.save(temp.path()).unwrap();
+
+        let format = Some("24bit WAV".to_string());
+        let first = record(temp.path(), &commit("aaa111"), " Client X ", format, None).unwrap();
+        assert_eq!(first.recipient, "Client X");
+        assert_eq!(first.version.as_deref(), Some("v1.0"));
+        assert!(first.bounce.is_none());
+        record(temp.path(), &commit("bbb222"), "Label Y", None, None).unwrap();
+        record(temp.path(), &commit("bbb222"), "Client X", None, None).unwrap();
+
+        let log = DeliveryLog::load(temp.path()).unwrap();
+        let to_client = DeliveryFilter {
+            recipient: Some("client x".to_string()),
+            commit: None,
+        };
+        let listed = log.list(&to_client);
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].commit_id, "bbb222");
+        assert_eq!(log.for_commit("bbb222").count(), 2);
+
+        let by_commit = DeliveryFilter {
+            recipient: None,
+            commit: Some("aaa".to_string()),
+        };
+        assert_eq!(log.list(&by_commit), vec![&first]);
+    }
+}
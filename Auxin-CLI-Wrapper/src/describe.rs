@@ -0,0 +1,516 @@
+//! `auxin describe`: everything auxin knows about one file
+//!
+//! Answers "why isn't this file being versioned?" and "when did this last
+//! change?" in one place. The report covers:
+//! - tracking state, and which `.oxenignore` rule ignores the file
+//! - the last commit that changed it and how its size evolved, from
+//!   `oxen info` at each recent commit
+//! - the project lock, which covers every file in the project
+//! - offline-queue operations still waiting to sync this project
+
+use crate::offline_queue::{OfflineQueue, QueuedOperation};
+use crate::quick_actions::{self, LockState};
+use crate::rename_tracking::RenameLog;
+use anyhow::{bail, Context, Result};
+use auxin_oxen::{CommitInfo, FileInfo, OxenSubprocess};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Commits checked for size history by default
+pub const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// One pattern from `.oxenignore`
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    /// Pattern as written
+    pub pattern: String,
+    /// 1-based line number
+    pub line: usize,
+    negated: bool,
+    dir_only: bool,
+    regex: Regex,
+}
+
+/// Patterns from `.oxenignore`, using gitignore syntax
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// Read `.oxenignore` in `repo`; missing file means no rules
+    pub fn load(repo: &Path) -> Result<Self> {
+        let path = repo.join(".oxenignore");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::parse(&contents))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let rules = contents
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| parse_rule(line, i + 1))
+            .collect();
+        Self { rules }
+    }
+
+    /// Number of rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rule that ignores `relative` (a `/`-separated path inside the repo)
+    ///
+    /// A file inside an ignored folder is ignored by the folder's rule, and
+    /// as in git a later `!pattern` can't re-include it.
+    pub fn ignored_by(&self, relative: &str, is_dir: bool) -> Option<&IgnoreRule> {
+        let parts: Vec<&str> = relative.split('/').filter(|p| !p.is_empty()).collect();
+        for end in 1..parts.len() {
+            if let Some(rule) = self.last_match(&parts[..end].join("/"), true) {
+                return Some(rule);
+            }
+        }
+        self.last_match(&parts.join("/"), is_dir)
+    }
+
+    fn last_match(&self, path: &str, is_dir: bool) -> Option<&IgnoreRule> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.regex.is_match(path))
+            .filter(|rule| !rule.negated)
+    }
+}
+
+fn parse_rule(line: &str, number: usize) -> Option<IgnoreRule> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    let (negated, body) = match trimmed.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    let (dir_only, body) = match body.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, body),
+    };
+    // A slash anywhere but the end anchors the pattern to the repo root
+    let anchored = body.contains('/');
+    let body = body.trim_start_matches('/');
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let chars: Vec<char> = body.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    regex.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            },
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    regex.push('$');
+
+    Some(IgnoreRule {
+        pattern: trimmed.to_string(),
+        line: number,
+        negated,
+        dir_only,
+        regex: Regex::new(&regex).ok()?,
+    })
+}
+
+/// Whether and how a file is versioned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackingState {
+    /// Tracked with no uncommitted changes
+    Clean,
+    Modified,
+    Staged,
+    /// Not ignored, but never added
+    Untracked,
+    Ignored {
+        pattern: String,
+        line: usize,
+    },
+    /// Not on disk
+    Missing,
+}
+
+/// The file as it was at one commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileVersion {
+    pub commit: CommitInfo,
+    /// Path at that commit, following recorded moves
+    pub path: String,
+    /// `None` when the file wasn't there
+    pub info: Option<FileInfo>,
+}
+
+/// A commit where the file's content or location changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeChange {
+    pub commit: CommitInfo,
+    /// `None` when the commit removed the file
+    pub size: Option<u64>,
+    /// Previous path when the commit moved the file
+    pub renamed_from: Option<String>,
+}
+
+/// Everything `auxin describe` reports about a file
+#[derive(Debug, Clone)]
+pub struct FileDescription {
+    pub repo: PathBuf,
+    /// Path relative to the repo, `/`-separated
+    pub relative: String,
+    /// Size on disk
+    pub size: Option<u64>,
+    pub state: TrackingState,
+    /// Content changes and moves, newest first
+    pub size_history: Vec<SizeChange>,
+    /// How many commits were checked for `size_history`
+    pub commits_checked: usize,
+    pub lock: LockState,
+    /// Offline-queue operations waiting to sync this project
+    pub pending: Vec<String>,
+}
+
+impl FileDescription {
+    /// Last commit that changed the file, among the commits checked
+    pub fn last_change(&self) -> Option<&SizeChange> {
+        self.size_history.first()
+    }
+}
+
+/// Describe `path`, checking up to `history_limit` commits for size history
+pub fn describe(path: &Path, history_limit: usize) -> Result<FileDescription> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    let Some(repo) = find_repo_root(&absolute) else {
+        bail!("{} is not inside an auxin repository", path.display());
+    };
+    let relative = relative_path(&repo, &absolute);
+    let metadata = fs::metadata(&absolute).ok();
+    let is_dir = metadata.as_ref().is_some_and(|m| m.is_dir());
+
+    let oxen = OxenSubprocess::new();
+    let state = match IgnoreRules::load(&repo)?.ignored_by(&relative, is_dir) {
+        Some(rule) => TrackingState::Ignored {
+            pattern: rule.pattern.clone(),
+            line: rule.line,
+        },
+        None if metadata.is_none() => TrackingState::Missing,
+        None => {
+            let status = oxen.status(&repo)?;
+            let listed =
+                |paths: &[PathBuf]| paths.iter().any(|p| relative_path(&repo, p) == relative);
+            if listed(&status.modified) {
+                TrackingState::Modified
+            } else if listed(&status.staged) {
+                TrackingState::Staged
+            } else if listed(&status.untracked) {
+                TrackingState::Untracked
+            } else {
+                TrackingState::Clean
+            }
+        },
+    };
+
+    let (size_history, commits_checked) = if is_dir {
+        (Vec::new(), 0)
+    } else {
+        let renames = RenameLog::load(&repo).unwrap_or_default();
+        let mut path = relative.clone();
+        let versions: Vec<FileVersion> = oxen
+            .log(&repo, Some(history_limit))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|commit| {
+                path = renames.path_at(&path, &commit.id);
+                let info = oxen
+                    .file_info(&repo, Path::new(&path), Some(&commit.id))
+                    .ok();
+                FileVersion {
+                    commit,
+                    path: path.clone(),
+                    info,
+                }
+            })
+            .collect();
+        (size_history(&versions), versions.len())
+    };
+
+    Ok(FileDescription {
+        size: metadata.filter(|m| m.is_file()).map(|m| m.len()),
+        state,
+        size_history,
+        commits_checked,
+        lock: quick_actions::lock_state(&repo),
+        pending: pending_operations(&repo),
+        repo,
+        relative,
+    })
+}
+
+/// Commits where the file's content or path changed, newest first
+///
+/// `versions` is the file at each commit, newest first. The oldest commit
+/// counts as a change when the file exists in it, since whatever came before
+/// wasn't checked. A recorded move shows up as a rename of the same file
+/// rather than as a removal and an addition.
+pub fn size_history(versions: &[FileVersion]) -> Vec<SizeChange> {
+    let mut changes = Vec::new();
+    let mut previous: Option<&FileVersion> = None;
+
+    for (i, version) in versions.iter().enumerate().rev() {
+        let before = previous.and_then(|p| p.info.as_ref());
+        let renamed_from = previous
+            .filter(|p| before.is_some() && version.info.is_some() && p.path != version.path)
+            .map(|p| p.path.clone());
+        let changed = match (before, &version.info) {
+            (None, Some(_)) | (Some(_), None) => true,
+            (Some(before), Some(after)) => before.hash != after.hash || renamed_from.is_some(),
+            (None, None) => false,
+        };
+        let oldest = i == versions.len() - 1;
+        if changed && (version.info.is_some() || !oldest) {
+            changes.push(SizeChange {
+                commit: version.commit.clone(),
+                size: version.info.as_ref().map(|info| info.size),
+                renamed_from,
+            });
+        }
+        previous = Some(version);
+    }
+
+    changes.reverse();
+    changes
+}
+
+/// Human-readable file size
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1_000_000_000 {
+        format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
+    } else if bytes >= 1_000_000 {
+        format!("{:.2} MB", bytes as f64 / 1_000_000.0)
+    } else if bytes >= 1_000 {
+        format!("{:.1} KB", bytes as f64 / 1_000.0)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
+fn pending_operations(repo: &Path) -> Vec<String> {
+    let Ok(queue) = OfflineQueue::new() else {
+        return Vec::new();
+    };
+    queue
+        .pending()
+        .into_iter()
+        .filter(|entry| {
+            let path = match &entry.operation {
+                QueuedOperation::AcquireLock { project_path, .. }
+                | QueuedOperation::ReleaseLock { project_path, .. }
+                | QueuedOperation::RenewLock { project_path, .. } => project_path,
+                QueuedOperation::PushCommits { repo_path, .. }
+                | QueuedOperation::PullCommits { repo_path, .. }
+                | QueuedOperation::SyncComments { repo_path }
+                | QueuedOperation::StoreMetadata { repo_path, .. } => repo_path,
+            };
+            same_path(Path::new(path), repo)
+        })
+        .map(|entry| entry.operation.description())
+        .collect()
+}
+
+/// Nearest ancestor of `path` (or `path` itself) containing `.oxen`
+pub fn find_repo_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join(".oxen").is_dir())
+        .map(Path::to_path_buf)
+}
+
+pub(crate) fn relative_path(repo: &Path, path: &Path) -> String {
+    path.strip_prefix(repo)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IGNORE: &str = "\
+# Volatile files
+Bounces/
+*.nosync
+Freeze Files/
+/render.tmp
+**/Autosave/*.bak
+*.wav
+!keep.wav
+";
+
+    fn ignored(rules: &IgnoreRules, path: &str, is_dir: bool) -> Option<(String, usize)> {
+        rules
+            .ignored_by(path, is_dir)
+            .map(|rule| (rule.pattern.clone(), rule.line))
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = IgnoreRules::parse(IGNORE);
+
+        assert_eq!(
+            ignored(&rules, "Bounces/mix.aif", false),
+            Some(("Bounces/".into(), 2))
+        );
+        assert_eq!(
+            ignored(&rules, "Song/Bounces", true),
+            Some(("Bounces/".into(), 2))
+        );
+        assert_eq!(ignored(&rules, "Bounces", false), None);
+        assert_eq!(
+            ignored(&rules, "Audio/take.nosync", false),
+            Some(("*.nosync".into(), 3))
+        );
+        assert_eq!(
+            ignored(&rules, "render.tmp", false),
+            Some(("/render.tmp".into(), 5))
+        );
+        assert_eq!(ignored(&rules, "sub/render.tmp", false), None);
+        assert_eq!(
+            ignored(&rules, "a/b/Autosave/x.bak", false),
+            Some(("**/Autosave/*.bak".into(), 6))
+        );
+        assert_eq!(
+            ignored(&rules, "Audio Files/vox.wav", false),
+            Some(("*.wav".into(), 7))
+        );
+        assert_eq!(ignored(&rules, "Audio Files/keep.wav", false), None);
+        // A negation can't re-include a file inside an ignored folder
+        assert_eq!(
+            ignored(&rules, "Bounces/keep.wav", false),
+            Some(("Bounces/".into(), 2))
+        );
+        assert_eq!(ignored(&rules, "Alternatives/000/ProjectData", false), None);
+    }
+
+    fn commit(id: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: format!("Commit {}", id),
+            ..Default::default()
+        }
+    }
+
+    fn info(hash: &str, size: u64) -> Option<FileInfo> {
+        Some(FileInfo {
+            hash: hash.to_string(),
+            size,
+        })
+    }
+
+    fn version(id: &str, path: &str, info: Option<FileInfo>) -> FileVersion {
+        FileVersion {
+            commit: commit(id),
+            path: path.to_string(),
+            info,
+        }
+    }
+
+    #[test]
+    fn test_size_history() {
+        // Newest first: added in c1, unchanged in c2, grown in c3, removed in c4
+        let versions = vec![
+            version("c4", "a.wav", None),
+            version("c3", "a.wav", info("h2", 200)),
+            version("c2", "a.wav", info("h1", 100)),
+            version("c1", "a.wav", info("h1", 100)),
+            version("c0", "a.wav", None),
+        ];
+        let history = size_history(&versions);
+
+        let summary: Vec<(&str, Option<u64>)> = history
+            .iter()
+            .map(|change| (change.commit.id.as_str(), change.size))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("c4", None), ("c3", Some(200)), ("c1", Some(100))]
+        );
+
+        assert!(size_history(&[version("c0", "a.wav", None)]).is_empty());
+        assert_eq!(
+            size_history(&[version("c0", "a.wav", info("h", 5))]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_size_history_follows_renames() {
+        // Moved from Samples/ in c2 without changing content
+        let versions = vec![
+            version("c2", "Audio Files/kick.wav", info("h1", 100)),
+            version("c1", "Samples/kick.wav", info("h1", 100)),
+        ];
+        let history = size_history(&versions);
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].commit.id, "c2");
+        assert_eq!(history[0].renamed_from.as_deref(), Some("Samples/kick.wav"));
+        assert_eq!(history[1].renamed_from, None);
+    }
+
+    #[test]
+    fn test_find_repo_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("Song");
+        fs::create_dir_all(repo.join(".oxen")).unwrap();
+        fs::create_dir_all(repo.join("Audio Files")).unwrap();
+
+        assert_eq!(
+            find_repo_root(&repo.join("Audio Files/vox.wav")),
+            Some(repo.clone())
+        );
+        assert_eq!(find_repo_root(temp.path()), None);
+        assert_eq!(
+            relative_path(&repo, &repo.join("Audio Files/vox.wav")),
+            "Audio Files/vox.wav"
+        );
+    }
+}
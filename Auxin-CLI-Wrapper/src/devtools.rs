@@ -0,0 +1,496 @@
+//! Synthetic projects for performance work, demos and load tests:
+//! `auxin devtools gen-project`
+//!
+//! Real sessions are confidential and can't be shared for benchmarking, so
+//! this fabricates a project of a chosen type and size with a plausible
+//! tree and history:
+//!
+//! - Logic Pro: `Name.logicx` with `Alternatives/000/ProjectData`,
+//!   `MetaData.plist`, `Resources/ProjectInformation.plist` and WAV takes
+//!   under `Media/Audio Files`
+//! - SketchUp: a `.skp` model with textures and exported PDFs
+//! - Blender: a `.blend` file with textures and renders
+//! - other: a folder of binary assets
+//!
+//! Media files get real headers (RIFF/WAVE, PNG, PDF, OpenEXR) and random
+//! bodies, so they neither compress nor deduplicate, like recorded audio.
+//! With `--commits N` the media is spread across N commits, each also
+//! rewriting the main project file, and commit messages carry BPM, key and
+//! tags the way `auxin commit` writes them. The same `--seed` always gives
+//! the same project.
+
+use crate::CommitMetadata;
+use anyhow::{bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Random bytes are written this many at a time
+const CHUNK: usize = 1024 * 1024;
+
+/// Project types the generator can fabricate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntheticType {
+    LogicPro,
+    SketchUp,
+    Blender,
+    Other,
+}
+
+impl SyntheticType {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "logicpro" | "logic" => Some(Self::LogicPro),
+            "sketchup" | "skp" => Some(Self::SketchUp),
+            "blender" | "blend" => Some(Self::Blender),
+            "other" | "generic" => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    /// Folder the project is generated in, e.g. `Song.logicx`
+    pub fn folder_name(self, name: &str) -> String {
+        match self {
+            Self::LogicPro => format!("{}.logicx", name),
+            _ => name.to_string(),
+        }
+    }
+}
+
+/// Parse a size such as `20GB`, `512MB`, `1.5TB` or `4096` (bytes)
+///
+/// Units are decimal (`GB` is 10^9 bytes); `KiB`, `MiB`, `GiB` and `TiB`
+/// are binary.
+pub fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .with_context(|| format!("Invalid size '{}' (try 500MB or 20GB)", value))?;
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "t" | "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => bail!("Unknown unit in '{}' (use KB, MB, GB or TB)", value),
+    };
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// What to generate
+#[derive(Debug, Clone)]
+pub struct GenSpec {
+    pub kind: SyntheticType,
+    pub name: String,
+    /// Approximate size of the final working tree
+    pub size: u64,
+    /// Commits to create; 0 writes the tree without a repository
+    pub commits: usize,
+    pub seed: u64,
+}
+
+/// What was generated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenReport {
+    pub path: PathBuf,
+    pub files: usize,
+    pub bytes: u64,
+    pub commits: usize,
+}
+
+/// splitmix64: fast, and reproducible from a seed
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `low..=high`
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next() as usize % items.len()]
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            let bytes = self.next().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+/// A media file to write: path in the project, header, total size
+struct MediaFile {
+    path: String,
+    header: Vec<u8>,
+    size: u64,
+}
+
+const TRACKS: &[&str] = &[
+    "Kick",
+    "Snare",
+    "Overheads",
+    "Room",
+    "Bass DI",
+    "Bass Amp",
+    "Rhythm Gtr",
+    "Lead Gtr",
+    "Keys",
+    "Pad",
+    "Lead Vox",
+    "Harmony Vox",
+    "Backing Vox",
+    "Strings",
+    "Brass",
+    "Percussion",
+];
+const MESSAGES: &[&str] = &[
+    "Comp lead vocal",
+    "Tighten drum edits",
+    "Rough mix",
+    "Mix pass",
+    "Add harmony takes",
+    "Re-amp guitars",
+    "Automation on the bridge",
+    "Print stems",
+    "Client notes",
+    "Arrangement tweaks",
+];
+const KEYS: &[&str] = &[
+    "C Major", "A Minor", "G Major", "E Minor", "D Major", "F# Minor",
+];
+const TAGS: &[&str] = &[
+    "tracking",
+    "editing",
+    "mixing",
+    "mastering",
+    "wip",
+    "milestone",
+];
+
+/// Generate a project under `parent`
+///
+/// `progress` is called with a description of each step.
+pub fn generate(
+    spec: &GenSpec,
+    parent: &Path,
+    mut progress: impl FnMut(&str),
+) -> Result<GenReport> {
+    let root = parent.join(spec.kind.folder_name(&spec.name));
+    if root.exists() {
+        bail!("{} already exists", root.display());
+    }
+    fs::create_dir_all(&root).with_context(|| format!("Failed to create {}", root.display()))?;
+
+    let mut rng = Rng(spec.seed);
+    let main_size = (spec.size / 50).clamp(64 * 1024, 50_000_000);
+    let media = plan_media(spec, spec.size.saturating_sub(main_size), &mut rng);
+
+    let oxen = OxenSubprocess::new();
+    if spec.commits > 0 {
+        oxen.init(&root)
+            .context("Failed to initialize the repository")?;
+    }
+
+    let rounds = spec.commits.max(1);
+    let mut written = 0;
+    let mut bpm = rng.range(70, 160) as f32;
+    let key = *rng.pick(KEYS);
+    for round in 0..rounds {
+        if round == 0 {
+            write_skeleton(spec, &root, bpm, key)?;
+        }
+        if rng.range(0, 9) == 0 {
+            bpm = rng.range(70, 160) as f32;
+        }
+        write_main_file(spec, &root, main_size, bpm, &mut rng)?;
+
+        // Media is spread evenly, so the tree reaches its size at the end
+        let until = media.len() * (round + 1) / rounds;
+        for file in &media[written..until] {
+            write_media(&root.join(&file.path), &file.header, file.size, &mut rng)?;
+        }
+        written = until;
+
+        if spec.commits > 0 {
+            let message = CommitMetadata::new(format!("{} {}", rng.pick(MESSAGES), round + 1))
+                .with_bpm(bpm)
+                .with_sample_rate(48000)
+                .with_key_signature(key)
+                .with_tag(*rng.pick(TAGS))
+                .format_commit_message();
+            oxen.add_all(&root)?;
+            oxen.commit(&root, &message)
+                .with_context(|| format!("Failed to create commit {}", round + 1))?;
+            progress(&format!("Commit {}/{}", round + 1, spec.commits));
+        }
+    }
+
+    let (files, bytes) = tree_size(&root)?;
+    Ok(GenReport {
+        path: root,
+        files,
+        bytes,
+        commits: spec.commits,
+    })
+}
+
+/// Media files adding up to `budget` bytes
+fn plan_media(spec: &GenSpec, budget: u64, rng: &mut Rng) -> Vec<MediaFile> {
+    let mut files = Vec::new();
+    let mut remaining = budget;
+    let mut index = 0;
+    while remaining > 0 {
+        index += 1;
+        let (path, header, low, high) = match spec.kind {
+            SyntheticType::LogicPro => {
+                let track = rng.pick(TRACKS);
+                let take = index / TRACKS.len() + 1;
+                (
+                    format!("Media/Audio Files/{} #{:02}.wav", track, take),
+                    Vec::new(),
+                    2_000_000,
+                    120_000_000,
+                )
+            },
+            SyntheticType::SketchUp if index % 4 == 0 => (
+                format!("Exports/Sheet {:03}.pdf", index),
+                b"%PDF-1.7\n".to_vec(),
+                200_000,
+                20_000_000,
+            ),
+            SyntheticType::SketchUp => (
+                format!("Textures/texture_{:04}.png", index),
+                b"\x89PNG\r\n\x1a\n".to_vec(),
+                100_000,
+                8_000_000,
+            ),
+            SyntheticType::Blender if index % 3 == 0 => (
+                format!("renders/frame_{:04}.exr", index),
+                vec![0x76, 0x2f, 0x31, 0x01],
+                5_000_000,
+                60_000_000,
+            ),
+            SyntheticType::Blender => (
+                format!("textures/tex_{:04}.png", index),
+                b"\x89PNG\r\n\x1a\n".to_vec(),
+                500_000,
+                16_000_000,
+            ),
+            SyntheticType::Other => (
+                format!("assets/asset_{:05}.bin", index),
+                Vec::new(),
+                1_000_000,
+                50_000_000,
+            ),
+        };
+        let size = rng.range(low, high).min(remaining).max(64);
+        let header = if spec.kind == SyntheticType::LogicPro {
+            wav_header(size)
+        } else {
+            header
+        };
+        remaining = remaining.saturating_sub(size);
+        files.push(MediaFile { path, header, size });
+    }
+    files
+}
+
+/// 48 kHz, 24-bit stereo WAV header for a file of `size` bytes
+fn wav_header(size: u64) -> Vec<u8> {
+    let data_len = size.saturating_sub(44).min(u32::MAX as u64 - 36) as u32;
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(data_len + 36).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    header.extend_from_slice(&2u16.to_le_bytes()); // channels
+    header.extend_from_slice(&48_000u32.to_le_bytes());
+    header.extend_from_slice(&(48_000u32 * 6).to_le_bytes()); // byte rate
+    header.extend_from_slice(&6u16.to_le_bytes()); // block align
+    header.extend_from_slice(&24u16.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    header
+}
+
+/// Write `header` followed by random bytes, `size` in all
+fn write_media(path: &Path, header: &[u8], size: u64, rng: &mut Rng) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    let header = &header[..header.len().min(size as usize)];
+    out.write_all(header)?;
+
+    let mut buf = vec![0u8; CHUNK];
+    let mut left = size - header.len() as u64;
+    while left > 0 {
+        let n = left.min(CHUNK as u64) as usize;
+        rng.fill(&mut buf[..n]);
+        out.write_all(&buf[..n])?;
+        left -= n as u64;
+    }
+    out.flush()
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Files written once: plists for Logic Pro, nothing for the other types
+fn write_skeleton(spec: &GenSpec, root: &Path, bpm: f32, key: &str) -> Result<()> {
+    if spec.kind != SyntheticType::LogicPro {
+        return Ok(());
+    }
+    let plist = |body: String| {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+             \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+             <plist version=\"1.0\">\n<dict>\n{}</dict>\n</plist>\n",
+            body
+        )
+    };
+    let alternative = root.join("Alternatives/000");
+    fs::create_dir_all(&alternative)?;
+    fs::write(
+        alternative.join("MetaData.plist"),
+        plist(format!(
+            "\t<key>BeatsPerMinute</key>\n\t<real>{}</real>\n\
+             \t<key>SampleRate</key>\n\t<integer>48000</integer>\n\
+             \t<key>SongKey</key>\n\t<string>{}</string>\n\
+             \t<key>NumberOfTracks</key>\n\t<integer>{}</integer>\n",
+            bpm,
+            key,
+            TRACKS.len()
+        )),
+    )?;
+    fs::create_dir_all(root.join("Resources"))?;
+    fs::write(
+        root.join("Resources/ProjectInformation.plist"),
+        plist(format!(
+            "\t<key>LastSavedFrom</key>\n\t<string>Logic Pro 11.0.0</string>\n\
+             \t<key>Name</key>\n\t<string>{}</string>\n",
+            spec.name
+        )),
+    )?;
+    Ok(())
+}
+
+/// The file every commit changes: ProjectData, the .skp or the .blend
+fn write_main_file(spec: &GenSpec, root: &Path, size: u64, bpm: f32, rng: &mut Rng) -> Result<()> {
+    let (path, mut header) = match spec.kind {
+        SyntheticType::LogicPro => (
+            root.join("Alternatives/000/ProjectData"),
+            b"#LogicProject".to_vec(),
+        ),
+        SyntheticType::SketchUp => (
+            root.join(format!("{}.skp", spec.name)),
+            b"\xff\xfe\xffSketchUp Model".to_vec(),
+        ),
+        SyntheticType::Blender => (
+            root.join(format!("{}.blend", spec.name)),
+            b"BLENDER-v400".to_vec(),
+        ),
+        SyntheticType::Other => (root.join("project.dat"), Vec::new()),
+    };
+    // The tempo as a little-endian float, where the ProjectData parser looks
+    header.extend_from_slice(&bpm.to_le_bytes());
+    write_media(&path, &header, size, rng)
+}
+
+/// Files and bytes under `root`, leaving out `.oxen`
+fn tree_size(root: &Path) -> Result<(usize, u64)> {
+    let mut files = 0;
+    let mut bytes = 0;
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        if entry.file_name() == ".oxen" {
+            continue;
+        }
+        let meta = entry.metadata()?;
+        if meta.is_dir() {
+            let (f, b) = tree_size(&entry.path())?;
+            files += f;
+            bytes += b;
+        } else {
+            files += 1;
+            bytes += meta.len();
+        }
+    }
+    Ok((files, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("20GB").unwrap(), 20_000_000_000);
+        assert_eq!(parse_size("1.5 MB").unwrap(), 1_500_000);
+        assert_eq!(parse_size("2GiB").unwrap(), 2 << 30);
+        assert_eq!(parse_size("4096").unwrap(), 4096);
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("3 parsecs").is_err());
+    }
+
+    #[test]
+    fn test_generates_logic_project_tree() {
+        let dir = TempDir::new().unwrap();
+        let spec = GenSpec {
+            kind: SyntheticType::LogicPro,
+            name: "Synthetic".to_string(),
+            size: 20_000_000,
+            commits: 0,
+            seed: 7,
+        };
+        let report = generate(&spec, dir.path(), |_| {}).unwrap();
+
+        assert_eq!(report.path, dir.path().join("Synthetic.logicx"));
+        assert!(report.files > 3);
+        assert!(
+            report.bytes.abs_diff(spec.size) < 1_000_000,
+            "{}",
+            report.bytes
+        );
+        assert!(crate::logic_parser::binary_parser::find_project_data(&report.path).is_some());
+
+        let audio = fs::read_dir(report.path.join("Media/Audio Files"))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let bytes = fs::read(&audio).unwrap();
+        assert_eq!(&bytes[..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+        let data_len = u32::from_le_bytes(bytes[40..44].try_into().unwrap());
+        assert_eq!(data_len as usize, bytes.len() - 44);
+
+        // Same seed, same project
+        let again = TempDir::new().unwrap();
+        let repeat = generate(&spec, again.path(), |_| {}).unwrap();
+        assert_eq!((repeat.files, repeat.bytes), (report.files, report.bytes));
+        assert!(generate(&spec, dir.path(), |_| {}).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! Environment checks: `auxin doctor`
+//!
+//! [`run`] performs the checks in turn and hands each to a callback as it
+//! finishes, so the text output appears while the network probes are
+//! still running. The collected [`DoctorReport`] is what `--json` prints.
+
+use crate::daemon_client::{DaemonClient, DaemonCompatibility};
+use crate::dual_stack::AddressReport;
+use crate::{OfflineQueue, OxenSubprocess};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// How long to wait for each address of the server and Oxen Hub
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckState {
+    Ok,
+    Warning,
+    Error,
+    /// Couldn't be determined, e.g. the daemon isn't running
+    Unknown,
+}
+
+/// One check, e.g. that the Oxen CLI is installed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoctorCheck {
+    /// Stable identifier, e.g. "oxen" or "offline_queue"
+    pub name: String,
+    /// What's shown after "Checking", e.g. "Oxen CLI"
+    pub label: String,
+    pub state: CheckState,
+    pub summary: String,
+    /// One line per item looked at, e.g. each remote
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    /// What to run to fix it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// `false` if this check means auxin won't work as expected; network
+    /// and authentication checks are informational and always pass
+    pub passed: bool,
+}
+
+impl DoctorCheck {
+    fn new(name: &str, label: &str, state: CheckState, summary: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            label: label.to_string(),
+            state,
+            summary: summary.into(),
+            details: Vec::new(),
+            hint: None,
+            passed: matches!(state, CheckState::Ok | CheckState::Unknown),
+        }
+    }
+
+    fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Count as passed whatever the state
+    fn informational(mut self) -> Self {
+        self.passed = true;
+        self
+    }
+}
+
+/// Result of `auxin doctor`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    /// Every check passed
+    pub all_good: bool,
+}
+
+/// Check the environment for the repository at `repo`, calling `on_check`
+/// as each check finishes
+pub fn run(repo: &Path, mut on_check: impl FnMut(&DoctorCheck)) -> DoctorReport {
+    let mut checks = Vec::new();
+    let mut add = |check: DoctorCheck| {
+        on_check(&check);
+        checks.push(check);
+    };
+
+    let oxen = OxenSubprocess::new();
+    add(match oxen.version() {
+        Ok(version) => DoctorCheck::new("oxen", "Oxen CLI", CheckState::Ok, version.trim()),
+        Err(_) => DoctorCheck::new("oxen", "Oxen CLI", CheckState::Error, "Not found")
+            .with_hint("Install with: pip install oxen-ai"),
+    });
+
+    if repo.join(".oxen").exists() {
+        add(DoctorCheck::new(
+            "repository",
+            "repository",
+            CheckState::Ok,
+            "Oxen repository found",
+        ));
+        add(check_remotes(&oxen, repo));
+        add(match oxen.current_branch(repo) {
+            Ok(branch) => DoctorCheck::new("branch", "branch", CheckState::Ok, branch),
+            Err(e) => DoctorCheck::new("branch", "branch", CheckState::Error, e.to_string()),
+        });
+        add(check_features(repo));
+    } else {
+        add(DoctorCheck::new(
+            "repository",
+            "repository",
+            CheckState::Warning,
+            "Not in an Oxen repository",
+        )
+        .with_hint("Initialize with: auxin init <path>")
+        .informational());
+    }
+
+    add(check_daemon());
+    add(check_queue());
+
+    let config = auxin_config::Config::load().unwrap_or_default();
+    let hosts = [
+        (
+            "server",
+            format!("{}/", config.cli.url.trim_end_matches('/')),
+        ),
+        ("hub", "https://hub.oxen.ai".to_string()),
+    ];
+    for (host, url) in hosts {
+        add(check_addresses(host, &url));
+    }
+
+    // Auth can't be checked without connecting; just point at the login
+    add(DoctorCheck::new(
+        "authentication",
+        "authentication",
+        CheckState::Unknown,
+        "Run 'auxin auth login' to authenticate",
+    ));
+
+    let all_good = checks.iter().all(|check| check.passed);
+    DoctorReport { checks, all_good }
+}
+
+fn check_remotes(oxen: &OxenSubprocess, repo: &Path) -> DoctorCheck {
+    match oxen.remote_list(repo) {
+        Ok(remotes) if !remotes.is_empty() => DoctorCheck::new(
+            "remotes",
+            "remotes",
+            CheckState::Ok,
+            format!("{} configured", remotes.len()),
+        )
+        .with_details(
+            remotes
+                .iter()
+                .map(|(name, url)| format!("{} → {}", name, url))
+                .collect(),
+        ),
+        Ok(_) => DoctorCheck::new(
+            "remotes",
+            "remotes",
+            CheckState::Warning,
+            "No remotes configured",
+        )
+        .with_hint("Add with: auxin remote add origin <URL>"),
+        Err(e) => DoctorCheck::new("remotes", "remotes", CheckState::Error, e.to_string()),
+    }
+}
+
+/// Whether this build knows the repository's features
+fn check_features(repo: &Path) -> DoctorCheck {
+    match auxin_config::RepoFeatures::load(repo) {
+        Ok(Some(features)) => match features.incompatibility() {
+            Some(problem) => DoctorCheck::new(
+                "features",
+                "features",
+                CheckState::Error,
+                format!("Project {}", problem),
+            )
+            .with_hint("Upgrade auxin: auxin upgrade-check"),
+            None => DoctorCheck::new("features", "features", CheckState::Ok, ""),
+        },
+        Ok(None) => DoctorCheck::new(
+            "features",
+            "features",
+            CheckState::Ok,
+            "None recorded; all on",
+        ),
+        Err(e) => DoctorCheck::new("features", "features", CheckState::Error, e.to_string()),
+    }
+}
+
+/// Whether the daemon's version matches this CLI
+fn check_daemon() -> DoctorCheck {
+    let status = DaemonClient::new().status().unwrap_or_default();
+    let warning = DaemonCompatibility::check(&status).warning();
+    if !status.is_running {
+        DoctorCheck::new("daemon", "daemon", CheckState::Unknown, "Not running")
+    } else if let Some(warning) = warning {
+        DoctorCheck::new("daemon", "daemon", CheckState::Warning, "Version mismatch")
+            .with_hint(warning)
+    } else {
+        let version = status.version.as_deref().unwrap_or("Running");
+        DoctorCheck::new("daemon", "daemon", CheckState::Ok, version)
+    }
+}
+
+/// Whether queued operations gave up
+fn check_queue() -> DoctorCheck {
+    match OfflineQueue::new() {
+        Ok(queue) => {
+            let stats = queue.stats();
+            if stats.dead_letter > 0 {
+                DoctorCheck::new(
+                    "offline_queue",
+                    "offline queue",
+                    CheckState::Warning,
+                    format!(
+                        "{} operation(s) gave up after repeated failures",
+                        stats.dead_letter
+                    ),
+                )
+                .with_hint("Review with: auxin queue deadletter list")
+            } else {
+                DoctorCheck::new(
+                    "offline_queue",
+                    "offline queue",
+                    CheckState::Ok,
+                    format!("{} pending", stats.pending),
+                )
+            }
+        }
+        Err(e) => DoctorCheck::new(
+            "offline_queue",
+            "offline queue",
+            CheckState::Error,
+            e.to_string(),
+        ),
+    }
+}
+
+/// Which address families reach `url`
+fn check_addresses(host: &str, url: &str) -> DoctorCheck {
+    let name = format!("{}_addresses", host);
+    let label = format!("{} addresses", host);
+    let check = match AddressReport::probe(url, PROBE_TIMEOUT) {
+        Ok(report) if report.results.is_empty() => DoctorCheck::new(
+            &name,
+            &label,
+            CheckState::Error,
+            format!("{} has no addresses", url),
+        ),
+        Ok(report) => {
+            let reachable = report.results.iter().any(|(_, time)| time.is_some());
+            let state = if reachable {
+                CheckState::Ok
+            } else {
+                CheckState::Error
+            };
+            let details = report
+                .results
+                .iter()
+                .map(|(addr, time)| match time {
+                    Some(time) => format!("{} {}ms", addr.ip(), time.as_millis()),
+                    None => format!("{} unreachable", addr.ip()),
+                })
+                .collect();
+            DoctorCheck::new(
+                &name,
+                &label,
+                state,
+                format!("{} ({})", report.netloc, report),
+            )
+            .with_details(details)
+        }
+        Err(e) => DoctorCheck::new(&name, &label, CheckState::Error, format!("{}: {}", url, e)),
+    };
+    check.informational()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_passed_by_state() {
+        assert!(DoctorCheck::new("oxen", "Oxen CLI", CheckState::Ok, "0.19.0").passed);
+        assert!(DoctorCheck::new("daemon", "daemon", CheckState::Unknown, "").passed);
+        assert!(!DoctorCheck::new("remotes", "remotes", CheckState::Warning, "").passed);
+        assert!(
+            DoctorCheck::new("hub_addresses", "hub addresses", CheckState::Error, "")
+                .informational()
+                .passed
+        );
+    }
+
+    #[test]
+    fn test_report_json_shape() {
+        let check = DoctorCheck::new("remotes", "remotes", CheckState::Warning, "None")
+            .with_hint("Add with: auxin remote add origin <URL>");
+        let report = DoctorReport {
+            all_good: check.passed,
+            checks: vec![check],
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["all_good"], false);
+        assert_eq!(json["checks"][0]["state"], "warning");
+        assert!(json["checks"][0].get("details").is_none());
+    }
+}
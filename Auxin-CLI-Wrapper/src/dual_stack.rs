@@ -0,0 +1,246 @@
+//! IPv4/IPv6 dual-stack connection setup
+//!
+//! Server URLs may name an IPv6 literal (`http://[fd00::10]:3000`) or a host
+//! with both A and AAAA records. The standard resolver returns addresses in
+//! the system's order and ureq tries them one at a time, each with half of
+//! the remaining connect timeout, so on a network where one family is
+//! black-holed every new connection stalls before falling back.
+//!
+//! [`HappyEyeballsResolver`] follows RFC 8305 instead: addresses are
+//! interleaved by family, connection attempts start [`ATTEMPT_DELAY`] apart
+//! and the address that connects first is tried first. The winner is
+//! remembered for [`CACHE_TTL`] so later connections skip the race.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay before the next address is tried while earlier attempts are pending
+pub const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// How long the winning address for a host is reused
+pub const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Winning address per `host:port`
+fn winners() -> &'static Mutex<HashMap<String, (SocketAddr, Instant)>> {
+    static WINNERS: OnceLock<Mutex<HashMap<String, (SocketAddr, Instant)>>> = OnceLock::new();
+    WINNERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A ureq resolver racing IPv6 and IPv4 connections
+#[derive(Debug, Clone, Copy)]
+pub struct HappyEyeballsResolver {
+    connect_timeout: Duration,
+}
+
+impl HappyEyeballsResolver {
+    pub fn new(connect_timeout: Duration) -> Self {
+        Self { connect_timeout }
+    }
+}
+
+impl ureq::Resolver for HappyEyeballsResolver {
+    fn resolve(&self, netloc: &str) -> io::Result<Vec<SocketAddr>> {
+        let mut addrs = interleave(netloc.to_socket_addrs()?.collect());
+        if addrs.len() < 2 {
+            return Ok(addrs);
+        }
+
+        let cached = winners()
+            .lock()
+            .ok()
+            .and_then(|winners| winners.get(netloc).copied())
+            .filter(|(addr, at)| at.elapsed() < CACHE_TTL && addrs.contains(addr))
+            .map(|(addr, _)| addr);
+        let winner = cached.or_else(|| race(&addrs, self.connect_timeout));
+
+        if let Some(winner) = winner {
+            if let Ok(mut winners) = winners().lock() {
+                winners.insert(netloc.to_string(), (winner, Instant::now()));
+            }
+            addrs.retain(|addr| *addr != winner);
+            addrs.insert(0, winner);
+        }
+        Ok(addrs)
+    }
+}
+
+/// Alternate address families, starting with the family listed first
+pub fn interleave(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let first_is_v6 = addrs.first().is_some_and(SocketAddr::is_ipv6);
+    let (mut preferred, mut other): (VecDeque<_>, VecDeque<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_is_v6);
+
+    let mut ordered = Vec::with_capacity(preferred.len() + other.len());
+    while !preferred.is_empty() || !other.is_empty() {
+        ordered.extend(preferred.pop_front());
+        ordered.extend(other.pop_front());
+    }
+    ordered
+}
+
+/// Connect to `addrs` in order, starting the next attempt after
+/// [`ATTEMPT_DELAY`] or as soon as one fails; returns the first address to
+/// accept a connection
+pub fn race(addrs: &[SocketAddr], timeout: Duration) -> Option<SocketAddr> {
+    let (sender, results) = mpsc::channel();
+    let mut pending = 0;
+
+    for &addr in addrs {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            let connected = TcpStream::connect_timeout(&addr, timeout).is_ok();
+            let _ = sender.send((addr, connected));
+        });
+        pending += 1;
+
+        match results.recv_timeout(ATTEMPT_DELAY) {
+            Ok((addr, true)) => return Some(addr),
+            Ok((_, false)) => pending -= 1,
+            Err(_) => {},
+        }
+    }
+    drop(sender);
+
+    let deadline = Instant::now() + timeout;
+    while pending > 0 {
+        match results.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok((addr, true)) => return Some(addr),
+            Ok((_, false)) => pending -= 1,
+            Err(_) => return None,
+        }
+    }
+    None
+}
+
+/// How each address of a host answered, for `auxin doctor`
+#[derive(Debug, Clone)]
+pub struct AddressReport {
+    pub netloc: String,
+    /// Each resolved address with its connect time, `None` if unreachable
+    pub results: Vec<(SocketAddr, Option<Duration>)>,
+}
+
+impl AddressReport {
+    /// Resolve the host of `url` and connect to every address concurrently
+    pub fn probe(url: &str, timeout: Duration) -> io::Result<Self> {
+        let netloc = netloc(url)?;
+        let addrs: Vec<SocketAddr> = netloc.to_socket_addrs()?.collect();
+
+        let results = thread::scope(|scope| {
+            let attempts: Vec<_> = addrs
+                .iter()
+                .map(|addr| {
+                    scope.spawn(move || {
+                        let started = Instant::now();
+                        TcpStream::connect_timeout(addr, timeout)
+                            .ok()
+                            .map(|_| started.elapsed())
+                    })
+                })
+                .collect();
+            addrs
+                .iter()
+                .zip(attempts)
+                .map(|(addr, attempt)| (*addr, attempt.join().ok().flatten()))
+                .collect()
+        });
+
+        Ok(Self { netloc, results })
+    }
+
+    /// Whether any address of the given family accepted a connection
+    pub fn reachable(&self, ipv6: bool) -> Option<bool> {
+        let family: Vec<_> = self
+            .results
+            .iter()
+            .filter(|(addr, _)| addr.is_ipv6() == ipv6)
+            .collect();
+        (!family.is_empty()).then(|| family.iter().any(|(_, time)| time.is_some()))
+    }
+}
+
+impl fmt::Display for AddressReport {
+    /// e.g. `IPv6 ok (12ms), IPv4 unreachable`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let families = [("IPv6", true), ("IPv4", false)]
+            .into_iter()
+            .map(|(name, ipv6)| {
+                let fastest = self
+                    .results
+                    .iter()
+                    .filter(|(addr, _)| addr.is_ipv6() == ipv6)
+                    .filter_map(|(_, time)| *time)
+                    .min();
+                match (self.reachable(ipv6), fastest) {
+                    (None, _) => format!("{} no address", name),
+                    (_, Some(time)) => format!("{} ok ({}ms)", name, time.as_millis()),
+                    (_, None) => format!("{} unreachable", name),
+                }
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", families.join(", "))
+    }
+}
+
+/// `host:port` of `url`, keeping the brackets of IPv6 literals
+pub fn netloc(url: &str) -> io::Result<String> {
+    let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidInput, reason.to_string());
+    let url = url::Url::parse(url).map_err(|e| invalid(&e.to_string()))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| invalid("URL has no port"))?;
+    match url.host() {
+        Some(url::Host::Ipv6(ip)) => Ok(format!("[{}]:{}", ip, port)),
+        Some(host) => Ok(format!("{}:{}", host, port)),
+        None => Err(invalid("URL has no host")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use ureq::Resolver;
+
+    #[test]
+    fn test_interleave() {
+        let v4 = |n: u8| SocketAddr::from(([10, 0, 0, n], 80));
+        let v6 = |n: u16| SocketAddr::from(([0xfd00, 0, 0, 0, 0, 0, 0, n], 80));
+
+        assert_eq!(
+            interleave(vec![v6(1), v6(2), v6(3), v4(1)]),
+            vec![v6(1), v4(1), v6(2), v6(3)]
+        );
+        assert_eq!(
+            interleave(vec![v4(1), v4(2), v6(1), v6(2)]),
+            vec![v4(1), v6(1), v4(2), v6(2)]
+        );
+        assert_eq!(netloc("http://[fd00::10]:3000/api").unwrap(), "[fd00::10]:3000");
+        assert_eq!(netloc("https://hub.oxen.ai").unwrap(), "hub.oxen.ai:443");
+    }
+
+    #[test]
+    fn test_race_prefers_reachable_address() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open = listener.local_addr().unwrap();
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+        assert_eq!(race(&[closed, open], Duration::from_secs(2)), Some(open));
+        assert_eq!(race(&[closed], Duration::from_secs(2)), None);
+
+        let resolver = HappyEyeballsResolver::new(Duration::from_secs(2));
+        assert_eq!(resolver.resolve(&open.to_string()).unwrap(), vec![open]);
+
+        let report = AddressReport::probe(&format!("http://{}", open), Duration::from_secs(2));
+        let report = report.unwrap();
+        assert_eq!(report.reachable(false), Some(true));
+        assert_eq!(report.reachable(true), None);
+        assert!(report.to_string().starts_with("IPv6 no address, IPv4 ok"));
+    }
+}
@@ -0,0 +1,300 @@
+//! How a project evolved across commits: `auxin compare --range` / `--last`
+//!
+//! Builds one row per commit, oldest first, with the commit's BPM and key
+//! (metadata edits applied), its Logic Pro track count and the integrated
+//! loudness of its bounce. Each row notes what changed since the row before,
+//! so a tempo change three commits back stands out in the table.
+//!
+//! Track counts come from `.auxin/track_counts.json`, recorded by `auxin
+//! commit` for Logic Pro projects; commits made before it was recorded show
+//! no count. Loudness is measured with ffmpeg, as `auxin bounce check` does.
+
+use crate::bounce::BounceManager;
+use crate::logic_parser::LogicParser;
+use crate::loudness;
+use crate::metadata_edits::MetadataEdits;
+use crate::CommitInfo;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Which commits to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitSelection {
+    /// From the first commit to the second, inclusive
+    Range { from: String, to: String },
+    /// The most recent commits
+    Last(usize),
+}
+
+impl CommitSelection {
+    /// Parse `abc123..def456`
+    pub fn parse_range(range: &str) -> Result<Self> {
+        let (from, to) = range
+            .split_once("..")
+            .ok_or_else(|| anyhow!("Expected a range like abc123..def456, got '{}'", range))?;
+        let (from, to) = (from.trim(), to.trim());
+        if from.is_empty() || to.is_empty() {
+            bail!("Expected a range like abc123..def456, got '{}'", range);
+        }
+        Ok(CommitSelection::Range {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Pick the selected commits out of history (newest first), returning
+    /// them oldest first
+    pub fn select(&self, history: &[CommitInfo]) -> Result<Vec<CommitInfo>> {
+        let mut selected = match self {
+            CommitSelection::Last(count) => history.iter().take(*count).cloned().collect(),
+            CommitSelection::Range { from, to } => {
+                let position = |id: &str| {
+                    history
+                        .iter()
+                        .position(|c| c.id.starts_with(id))
+                        .ok_or_else(|| anyhow!("Commit not found: {}", id))
+                };
+                let (from, to) = (position(from)?, position(to)?);
+                // Either order works; history runs newest first
+                let (newest, oldest) = (from.min(to), from.max(to));
+                history[newest..=oldest].to_vec()
+            },
+        };
+        selected.reverse();
+        Ok(selected)
+    }
+}
+
+/// Track counts recorded at commit time, by commit ID
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrackCounts {
+    #[serde(default)]
+    pub counts: BTreeMap<String, usize>,
+}
+
+impl TrackCounts {
+    /// Location of the counts for a repo
+    pub fn path(repo: &Path) -> PathBuf {
+        repo.join(".auxin").join("track_counts.json")
+    }
+
+    /// Read a repo's counts; a missing file means none
+    pub fn load(repo: &Path) -> Result<Self> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace the repo's counts atomically
+    pub fn save(&self, repo: &Path) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Count for a commit, by full ID or prefix
+    pub fn get(&self, commit_id: &str) -> Option<usize> {
+        self.counts
+            .iter()
+            .find(|(id, _)| id.starts_with(commit_id) || commit_id.starts_with(id.as_str()))
+            .map(|(_, count)| *count)
+    }
+}
+
+/// Record the project's current track count for a new commit
+///
+/// Returns `false` (and writes nothing) for projects that aren't Logic Pro
+/// projects or can't be parsed.
+pub fn record_track_count(project: &Path, commit_id: &str) -> Result<bool> {
+    if !LogicParser::is_valid_project(project) {
+        return Ok(false);
+    }
+    let Ok(data) = LogicParser::parse(project) else {
+        return Ok(false);
+    };
+
+    let mut counts = TrackCounts::load(project)?;
+    counts.counts.insert(commit_id.to_string(), data.tracks.len());
+    counts.save(project)?;
+    Ok(true)
+}
+
+/// One commit in the evolution table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionRow {
+    pub commit_id: String,
+    /// First line of the commit message
+    pub message: String,
+    pub bpm: Option<f32>,
+    pub key: Option<String>,
+    pub track_count: Option<usize>,
+    pub bounce_lufs: Option<f64>,
+    /// What differs from the previous row
+    pub bpm_changed: bool,
+    pub key_changed: bool,
+    pub tracks_changed: bool,
+}
+
+/// Build the table for `commits`, oldest first
+///
+/// `measure_loudness` controls whether bounces are measured, which runs
+/// ffmpeg once per bounce.
+pub fn build(repo: &Path, commits: &[CommitInfo], measure_loudness: bool) -> Vec<EvolutionRow> {
+    let edits = MetadataEdits::load(repo).unwrap_or_default();
+    let counts = TrackCounts::load(repo).unwrap_or_default();
+    let bounces = BounceManager::new(repo);
+
+    let mut rows: Vec<EvolutionRow> = commits
+        .iter()
+        .map(|commit| {
+            let metadata = edits.effective(commit);
+            let bounce_lufs = if measure_loudness {
+                bounces
+                    .get_bounce_path(&commit.id)
+                    .ok()
+                    .flatten()
+                    .and_then(|path| loudness::measure(&path).ok())
+                    .map(|m| m.integrated_lufs)
+                    .filter(|lufs| lufs.is_finite())
+            } else {
+                None
+            };
+            EvolutionRow {
+                commit_id: commit.id.clone(),
+                message: metadata.message.lines().next().unwrap_or("").to_string(),
+                bpm: metadata.bpm,
+                key: metadata.key_signature,
+                track_count: counts.get(&commit.id),
+                bounce_lufs,
+                bpm_changed: false,
+                key_changed: false,
+                tracks_changed: false,
+            }
+        })
+        .collect();
+    mark_changes(&mut rows);
+    rows
+}
+
+/// Flag values that differ from the previous row; unknown values on either
+/// side aren't changes
+pub fn mark_changes(rows: &mut [EvolutionRow]) {
+    fn changed<T: PartialEq>(before: &Option<T>, after: &Option<T>) -> bool {
+        matches!((before, after), (Some(a), Some(b)) if a != b)
+    }
+
+    for i in 1..rows.len() {
+        let (before, after) = rows.split_at_mut(i);
+        let (prev, row) = (&before[i - 1], &mut after[0]);
+        row.bpm_changed = changed(&prev.bpm, &row.bpm);
+        row.key_changed = changed(&prev.key, &row.key);
+        row.tracks_changed = changed(&prev.track_count, &row.track_count);
+    }
+}
+
+/// The table as CSV, one row per commit
+pub fn to_csv(rows: &[EvolutionRow]) -> String {
+    let mut csv = String::from("commit,message,bpm,key,tracks,bounce_lufs\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            row.commit_id,
+            csv_field(&row.message),
+            row.bpm.map(|b| b.to_string()).unwrap_or_default(),
+            csv_field(row.key.as_deref().unwrap_or("")),
+            row.track_count.map(|t| t.to_string()).unwrap_or_default(),
+            row.bounce_lufs.map(|l| format!("{:.1}", l)).unwrap_or_default(),
+        ));
+    }
+    csv
+}
+
+/// Quote a field containing commas, quotes or newlines
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn row(id: &str, bpm: Option<f32>, key: Option<&str>, tracks: Option<usize>) -> EvolutionRow {
+        EvolutionRow {
+            commit_id: id.to_string(),
+            message: "Mix, v2".to_string(),
+            bpm,
+            key: key.map(str::to_string),
+            track_count: tracks,
+            bounce_lufs: None,
+            bpm_changed: false,
+            key_changed: false,
+            tracks_changed: false,
+        }
+    }
+
+    #[test]
+    fn test_select_and_mark_changes() {
+        let history = vec![
+            commit("dddd", "Master"),
+            commit("cccc", "Mix"),
+            commit("bbbb", "Vocals"),
+            commit("aaaa", "Drums"),
+        ];
+
+        let range = CommitSelection::parse_range("bb..dd").unwrap();
+        let ids: Vec<_> = range
+            .select(&history)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, ["bbbb", "cccc", "dddd"]);
+
+        let last = CommitSelection::Last(2).select(&history).unwrap();
+        assert_eq!(last[0].id, "cccc");
+        assert!(CommitSelection::parse_range("abc").is_err());
+        assert!(CommitSelection::parse_range("abc..").is_err());
+        assert!(CommitSelection::parse_range("ee..dd")
+            .unwrap()
+            .select(&history)
+            .is_err());
+
+        let mut rows = vec![
+            row("aaaa", Some(120.0), Some("C Major"), None),
+            row("bbbb", Some(120.0), Some("A Minor"), Some(12)),
+            row("cccc", Some(124.0), None, Some(14)),
+        ];
+        mark_changes(&mut rows);
+        assert!(!rows[1].bpm_changed && rows[1].key_changed && !rows[1].tracks_changed);
+        assert!(rows[2].bpm_changed && !rows[2].key_changed && rows[2].tracks_changed);
+
+        let csv = to_csv(&rows);
+        assert!(csv.starts_with("commit,message,bpm,key,tracks,bounce_lufs\n"));
+        assert!(csv.contains("bbbb,\"Mix, v2\",120,A Minor,12,\n"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Forking a project for a remix or alternate version: `auxin fork`
+//!
+//! A fork is an independent repository with the source's full history. The
+//! bulk of a project's `.oxen` folder is the object store in
+//! `.oxen/versions`, whose files are content-addressed and never rewritten,
+//! so the fork hardlinks them instead of copying: tens of GB of history
+//! cost no extra disk. Everything else (the rest of `.oxen` and the working
+//! files, which the DAW edits in place) is copied. On a different volume,
+//! where hardlinks aren't possible, the object store is copied too.
+//!
+//! The fork drops the source's remotes so it can't push into the original,
+//! and records where it came from in `.auxin/fork.json`:
+//!
+//! ```json
+//! {
+//!   "source_path": "/Users/me/Music/Album/Track 3.logicx",
+//!   "source_commit": "8f3e2a1b...",
+//!   "source_branch": "main",
+//!   "source_remote": "https://hub.oxen.ai/me/track-3",
+//!   "user": "me@studio-mac",
+//!   "forked_at": "2026-10-16T14:02:11Z"
+//! }
+//! ```
+
+use anyhow::{bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a fork came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkProvenance {
+    pub source_path: PathBuf,
+    /// Commit the source was at when forked
+    pub source_commit: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_branch: Option<String>,
+    /// The source's `origin`, if it had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_remote: Option<String>,
+    pub user: String,
+    pub forked_at: DateTime<Utc>,
+}
+
+impl ForkProvenance {
+    /// Location of the provenance file in a fork
+    pub fn path(repo: &Path) -> PathBuf {
+        repo.join(".auxin").join("fork.json")
+    }
+
+    /// The project's provenance, if it's a fork
+    pub fn load(repo: &Path) -> Result<Option<Self>> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, repo: &Path) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Files and bytes a fork shared or copied
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyStats {
+    pub linked_files: usize,
+    pub linked_bytes: u64,
+    pub copied_files: usize,
+    pub copied_bytes: u64,
+}
+
+/// Copy `from` into `to`, hardlinking files when `link` is set and falling
+/// back to copying when a link can't be made
+fn copy_tree(from: &Path, to: &Path, link: bool, stats: &mut CopyStats) -> Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+    let entries =
+        fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))?;
+    for entry in entries.flatten() {
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_tree(&source, &target, link, stats)?;
+        } else if file_type.is_file() {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if link && fs::hard_link(&source, &target).is_ok() {
+                stats.linked_files += 1;
+                stats.linked_bytes += size;
+            } else {
+                fs::copy(&source, &target)
+                    .with_context(|| format!("Failed to copy {}", source.display()))?;
+                stats.copied_files += 1;
+                stats.copied_bytes += size;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy a repository, sharing its object store; `.auxin` is left behind
+pub fn copy_repo(source: &Path, destination: &Path) -> Result<CopyStats> {
+    let mut stats = CopyStats::default();
+    fs::create_dir_all(destination)?;
+    for entry in fs::read_dir(source)?.flatten() {
+        let name = entry.file_name();
+        let target = destination.join(&name);
+        if name == ".auxin" {
+            continue;
+        } else if name == ".oxen" {
+            for inner in fs::read_dir(entry.path())?.flatten() {
+                let inner_target = target.join(inner.file_name());
+                let link = inner.file_name() == "versions";
+                if inner.file_type()?.is_dir() {
+                    copy_tree(&inner.path(), &inner_target, link, &mut stats)?;
+                } else {
+                    fs::create_dir_all(&target)?;
+                    stats.copied_bytes += fs::copy(inner.path(), &inner_target)?;
+                    stats.copied_files += 1;
+                }
+            }
+        } else if entry.file_type()?.is_dir() {
+            copy_tree(&entry.path(), &target, false, &mut stats)?;
+        } else {
+            stats.copied_bytes += fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+            stats.copied_files += 1;
+        }
+    }
+    Ok(stats)
+}
+
+/// `path` made absolute with its symlinks resolved, for a path that may
+/// not exist yet: the part that exists is canonicalized
+fn resolve(path: &Path) -> Result<PathBuf> {
+    let path = std::path::absolute(path)?;
+    let mut existing = path.as_path();
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            break;
+        };
+        missing.push(name);
+        existing = parent;
+    }
+    let mut resolved = existing.canonicalize()?;
+    resolved.extend(missing.into_iter().rev());
+    Ok(resolved)
+}
+
+/// Fork `source` into `destination`
+pub fn fork(
+    source: &Path,
+    destination: &Path,
+    oxen: &OxenSubprocess,
+) -> Result<(ForkProvenance, CopyStats)> {
+    if !source.join(".oxen").is_dir() {
+        bail!("{} isn't an auxin repository", source.display());
+    }
+    if destination.exists() {
+        bail!("{} already exists", destination.display());
+    }
+    // Copying into itself would never finish
+    if resolve(destination)?.starts_with(source.canonicalize()?) {
+        bail!("Can't fork {} into a folder inside it", source.display());
+    }
+    let source_commit = oxen
+        .log(source, Some(1))?
+        .into_iter()
+        .next()
+        .map(|commit| commit.id)
+        .context("The project has no commits to fork from")?;
+    let remotes = oxen.remote_list(source).unwrap_or_default();
+
+    let stats = copy_repo(source, destination).inspect_err(|_| {
+        let _ = fs::remove_dir_all(destination);
+    })?;
+    for (name, _) in &remotes {
+        // A fork that can still push to the original isn't kept
+        oxen.remote_remove(destination, name).inspect_err(|_| {
+            let _ = fs::remove_dir_all(destination);
+        })?;
+    }
+
+    let provenance = ForkProvenance {
+        source_path: source.canonicalize().unwrap_or_else(|_| source.to_path_buf()),
+        source_commit,
+        source_branch: oxen.current_branch(source).ok(),
+        source_remote: remotes
+            .into_iter()
+            .find(|(name, _)| name == "origin")
+            .map(|(_, url)| url),
+        user: crate::lock_integration::get_user_identifier(),
+        forked_at: Utc::now(),
+    };
+    provenance.save(destination)?;
+    Ok((provenance, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_copy_repo_links_object_store() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("Track.logicx");
+        fs::create_dir_all(source.join(".oxen/versions/ab/cdef")).unwrap();
+        fs::create_dir_all(source.join(".oxen/refs/heads")).unwrap();
+        fs::create_dir_all(source.join(".auxin")).unwrap();
+        fs::create_dir_all(source.join("Alternatives/000")).unwrap();
+        fs::write(source.join(".oxen/versions/ab/cdef/data"), b"audio").unwrap();
+        fs::write(source.join(".oxen/refs/heads/main"), b"abc").unwrap();
+        fs::write(source.join(".oxen/HEAD"), b"main").unwrap();
+        fs::write(source.join(".auxin/operation.lock"), b"").unwrap();
+        fs::write(source.join("Alternatives/000/ProjectData"), b"project").unwrap();
+
+        let fork = temp.path().join("Track (Remix).logicx");
+        let stats = copy_repo(&source, &fork).unwrap();
+        assert_eq!(stats.linked_files, 1);
+        assert_eq!(stats.linked_bytes, 5);
+        assert_eq!(stats.copied_files, 3);
+        assert!(!fork.join(".auxin").exists());
+
+        // Working files are independent copies
+        fs::write(fork.join("Alternatives/000/ProjectData"), b"remix").unwrap();
+        assert_eq!(
+            fs::read(source.join("Alternatives/000/ProjectData")).unwrap(),
+            b"project"
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let linked = fs::metadata(fork.join(".oxen/versions/ab/cdef/data")).unwrap();
+            assert_eq!(linked.nlink(), 2);
+        }
+    }
+
+    #[test]
+    fn test_fork_refuses_a_destination_inside_the_source() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("Track.logicx");
+        fs::create_dir_all(source.join(".oxen")).unwrap();
+
+        let mut destinations = vec![
+            source.join("Remix"),
+            temp.path().join("./Track.logicx/./Remix"),
+        ];
+        #[cfg(unix)]
+        {
+            // Through a symlink to the source
+            std::os::unix::fs::symlink(&source, temp.path().join("link")).unwrap();
+            destinations.push(temp.path().join("link/Remix"));
+        }
+        for destination in destinations {
+            let error = fork(&source, &destination, &OxenSubprocess::new()).unwrap_err();
+            assert!(error.to_string().contains("inside it"), "{}", error);
+            assert!(!destination.exists());
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Scrubbed history export for client handover: `auxin export history`
+//!
+//! Handing a project's version history to a client or another studio means
+//! handing over the commits, not the studio's internal chatter. The export
+//! replays every commit of a branch, oldest first, into a fresh repository
+//! at the output folder:
+//!
+//! - commit messages lose pen-names: names and aliases from `.auxin/mailmap`,
+//!   comment authors, the local user and `@mentions`
+//! - with `todos` redacted, `TODO`/`FIXME` lines and unchecked `[ ]` items
+//!   are dropped from messages
+//! - comments are carried over to the new commits, scrubbed the same way,
+//!   unless `comments` is redacted
+//! - nothing from `.auxin` (metadata edits, deliveries, bounces, locks) is
+//!   copied
+//!
+//! The source project isn't touched: commits are checked out in a scratch
+//! copy under the system temp folder, removed when the export finishes.
+
+use crate::author_map::AuthorMap;
+use crate::collaboration::{Comment, CommentManager};
+use anyhow::{bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// What replaces a redacted name
+pub const REDACTED: &str = "[redacted]";
+
+/// What to leave out besides pen-names, which are always scrubbed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Redactions {
+    pub comments: bool,
+    pub todos: bool,
+}
+
+impl Redactions {
+    /// Parse a comma-separated list such as `comments,todos`
+    pub fn parse(list: &str) -> Result<Self> {
+        let mut redactions = Self::default();
+        for kind in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match kind.to_lowercase().as_str() {
+                "comments" => redactions.comments = true,
+                "todos" => redactions.todos = true,
+                "all" => {
+                    redactions.comments = true;
+                    redactions.todos = true;
+                },
+                other => bail!("Unknown redaction '{}' (expected comments, todos or all)", other),
+            }
+        }
+        Ok(redactions)
+    }
+}
+
+/// Removes pen-names and, optionally, todo lines from text
+pub struct Scrubber {
+    names: Vec<Regex>,
+    mention: Regex,
+    todo: Option<Regex>,
+}
+
+impl Scrubber {
+    /// `names` are matched case-insensitively as whole words
+    pub fn new(names: &[String], redactions: Redactions) -> Self {
+        let mut names: Vec<&String> = names.iter().filter(|n| !n.trim().is_empty()).collect();
+        names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+        names.dedup();
+
+        let names = names
+            .into_iter()
+            .filter_map(|name| {
+                let name = name.trim();
+                let boundary = |c: Option<char>| match c {
+                    Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+                    _ => "",
+                };
+                Regex::new(&format!(
+                    "(?i){}{}{}",
+                    boundary(name.chars().next()),
+                    regex::escape(name),
+                    boundary(name.chars().last())
+                ))
+                .ok()
+            })
+            .collect();
+
+        Self {
+            names,
+            mention: Regex::new(r"(^|\s)@[\w.-]+").expect("valid mention pattern"),
+            todo: redactions.todos.then(|| {
+                Regex::new(r"(?i)^\s*(?:[-*]\s*)?(?:\[ \]|(?:todo|fixme)\b)")
+                    .expect("valid todo pattern")
+            }),
+        }
+    }
+
+    /// Scrub a commit message or comment; empty if nothing is left
+    pub fn scrub(&self, text: &str) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        for line in text.lines() {
+            if self.todo.as_ref().is_some_and(|todo| todo.is_match(line)) {
+                continue;
+            }
+            let mut line = self
+                .mention
+                .replace_all(line, format!("${{1}}{}", REDACTED))
+                .to_string();
+            for name in &self.names {
+                line = name.replace_all(&line, REDACTED).to_string();
+            }
+            // Collapse the blank lines a dropped block leaves behind
+            if line.trim().is_empty() && lines.last().is_none_or(|l| l.trim().is_empty()) {
+                continue;
+            }
+            lines.push(line);
+        }
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+}
+
+/// Names to scrub from a project's history
+pub fn pen_names(repo: &Path) -> Result<Vec<String>> {
+    let mut names = AuthorMap::load(repo)?.names();
+    for comment in CommentManager::new().get_all_comments(repo).unwrap_or_default() {
+        names.push(comment.author);
+    }
+    let identity = crate::identity::Identity::current();
+    names.extend([identity.name.clone(), identity.login.clone(), identity.user()]);
+    names.extend(identity.other_names());
+    Ok(names)
+}
+
+/// What an export produced
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportReport {
+    /// Commits replayed into the export
+    pub commits: usize,
+    /// Commits that changed no files and were folded into the previous one
+    pub folded: usize,
+    /// Comments carried over
+    pub comments: usize,
+}
+
+/// Copy `from` into `to`, leaving out the named top-level entries
+fn copy_tree(from: &Path, to: &Path, skip: &[&str]) -> Result<()> {
+    fs::create_dir_all(to)?;
+    let entries =
+        fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))?;
+    for entry in entries.flatten() {
+        if skip.iter().any(|name| entry.file_name() == *name) {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_tree(&entry.path(), &target, &[])?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Make the working files of `to` match those of `from`
+fn mirror(from: &Path, to: &Path) -> Result<()> {
+    for entry in fs::read_dir(to)?.flatten() {
+        if entry.file_name() == ".oxen" {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    copy_tree(from, to, &[".oxen", ".auxin"])
+}
+
+/// Drop uncommitted work from the scratch copy so only committed files are
+/// exported
+fn discard_local_changes(scratch: &Path, oxen: &OxenSubprocess) -> Result<()> {
+    if !oxen.status(scratch)?.staged.is_empty() {
+        oxen.reset(scratch, None)?;
+    }
+    let status = oxen.status(scratch)?;
+    for path in &status.untracked {
+        let path = scratch.join(path);
+        if path.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else if path.exists() {
+            fs::remove_file(&path)?;
+        }
+    }
+    if !status.modified.is_empty() {
+        let modified: Vec<&Path> = status.modified.iter().map(|p| p.as_path()).collect();
+        oxen.restore_files(scratch, &modified)?;
+    }
+    Ok(())
+}
+
+/// Replay `branch` of `repo` into a new repository at `output`
+///
+/// `progress` is called with the number of commits replayed and the total.
+pub fn export_history(
+    repo: &Path,
+    branch: &str,
+    output: &Path,
+    redactions: Redactions,
+    oxen: &OxenSubprocess,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<ExportReport> {
+    if output.exists() && fs::read_dir(output)?.next().is_some() {
+        bail!("{} already exists and isn't empty", output.display());
+    }
+    let scrubber = Scrubber::new(&pen_names(repo)?, redactions);
+
+    let scratch = std::env::temp_dir().join(format!("auxin-export-{}", std::process::id()));
+    let result = (|| {
+        copy_tree(repo, &scratch, &[".auxin"]).context("Failed to make a scratch copy")?;
+        discard_local_changes(&scratch, oxen)?;
+        oxen.checkout(&scratch, branch)
+            .with_context(|| format!("Failed to check out {}", branch))?;
+        let mut commits = oxen.log(&scratch, None)?;
+        commits.reverse();
+
+        fs::create_dir_all(output)?;
+        oxen.init(output)?;
+
+        let mut report = ExportReport::default();
+        let mut new_ids: HashMap<String, String> = HashMap::new();
+        let mut last_id: Option<String> = None;
+        for (index, commit) in commits.iter().enumerate() {
+            progress(index, commits.len());
+            oxen.checkout(&scratch, &commit.id)?;
+            mirror(&scratch, output)?;
+            oxen.add_all(output)?;
+
+            let status = oxen.status(output)?;
+            let changed = !(status.staged.is_empty() && status.modified.is_empty());
+            if changed || last_id.is_none() {
+                let mut message = scrubber.scrub(&commit.message);
+                if message.trim().is_empty() {
+                    message = format!("Version {}", report.commits + 1);
+                }
+                let new = oxen.commit(output, &message)?;
+                last_id = Some(new.id);
+                report.commits += 1;
+            } else {
+                report.folded += 1;
+            }
+            if let Some(id) = &last_id {
+                new_ids.insert(commit.id.clone(), id.clone());
+            }
+        }
+        progress(commits.len(), commits.len());
+
+        if !redactions.comments {
+            report.comments = copy_comments(repo, output, &new_ids, &scrubber)?;
+        }
+        Ok(report)
+    })();
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+/// Carry comments over to the exported commits; returns how many
+fn copy_comments(
+    repo: &Path,
+    output: &Path,
+    new_ids: &HashMap<String, String>,
+    scrubber: &Scrubber,
+) -> Result<usize> {
+    let mut by_commit: HashMap<&str, Vec<Comment>> = HashMap::new();
+    for comment in CommentManager::new().get_all_comments(repo)? {
+        let Some(new_id) = new_ids.get(&comment.commit_id) else {
+            continue;
+        };
+        let text = scrubber.scrub(&comment.text);
+        if text.trim().is_empty() {
+            continue;
+        }
+        by_commit.entry(new_id).or_default().push(Comment {
+            commit_id: new_id.clone(),
+            author: REDACTED.to_string(),
+            text,
+            ..comment
+        });
+    }
+
+    let dir = output.join(".oxen").join("comments");
+    fs::create_dir_all(&dir)?;
+    let mut count = 0;
+    for (commit_id, comments) in by_commit {
+        count += comments.len();
+        fs::write(
+            dir.join(format!("{}.json", commit_id)),
+            serde_json::to_string_pretty(&comments)?,
+        )?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_message() {
+        assert_eq!(
+            Redactions::parse("comments, todos").unwrap(),
+            Redactions {
+                comments: true,
+                todos: true
+            }
+        );
+        assert!(Redactions::parse("secrets").is_err());
+
+        let names = vec!["Alice".to_string(), "bob@studio-mac".to_string()];
+        let message = "Tighter kick, thanks alice and @sam\n\n\
+                       TODO: ask bob@studio-mac about the bridge\n\
+                       - [ ] redo vocal comp\n\
+                       - [x] fix click\n\n\
+                       BPM: 120";
+
+        let scrubber = Scrubber::new(&names, Redactions::parse("todos").unwrap());
+        assert_eq!(
+            scrubber.scrub(message),
+            "Tighter kick, thanks [redacted] and [redacted]\n\n- [x] fix click\n\nBPM: 120"
+        );
+
+        // Names are whole words; todos stay unless redacted
+        let scrubber = Scrubber::new(&names, Redactions::default());
+        assert_eq!(scrubber.scrub("Malice in the mix"), "Malice in the mix");
+        assert!(scrubber.scrub(message).contains("TODO: ask [redacted] about the bridge"));
+    }
+}
@@ -43,24 +43,47 @@ use crate::CommitMetadata;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use wait_timeout::ChildExt;
 
 /// Type of hook
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HookType {
     PreCommit,
     PostCommit,
+    /// Runs before `auxin deliver` records a delivery
+    PreDeliver,
 }
 
 impl HookType {
+    pub const ALL: [HookType; 3] = [
+        HookType::PreCommit,
+        HookType::PostCommit,
+        HookType::PreDeliver,
+    ];
+
     /// Get the directory name for this hook type
     pub fn dir_name(&self) -> &'static str {
         match self {
             HookType::PreCommit => "pre-commit",
             HookType::PostCommit => "post-commit",
+            HookType::PreDeliver => "pre-deliver",
         }
     }
+
+    /// Parse a directory name such as "pre-commit"
+    pub fn from_dir_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.dir_name() == name)
+    }
+
+    /// Whether a failing hook of this type stops the operation
+    pub fn can_abort(&self) -> bool {
+        matches!(self, HookType::PreCommit | HookType::PreDeliver)
+    }
 }
 
 /// Built-in hook that can be enabled/disabled
@@ -108,8 +131,9 @@ impl HookManager {
         fs::create_dir_all(&hooks_dir).context("Failed to create hooks directory")?;
 
         // Create subdirectories for each hook type
-        fs::create_dir_all(self.hook_type_dir(HookType::PreCommit))?;
-        fs::create_dir_all(self.hook_type_dir(HookType::PostCommit))?;
+        for hook_type in HookType::ALL {
+            fs::create_dir_all(self.hook_type_dir(hook_type))?;
+        }
 
         // Create README
         let readme_path = hooks_dir.join("README.md");
@@ -123,6 +147,17 @@ impl HookManager {
 
     /// Run all enabled hooks of a specific type
     pub fn run_hooks(&self, hook_type: HookType, metadata: &CommitMetadata) -> Result<bool> {
+        self.run_hooks_with_env(hook_type, metadata, &[])
+    }
+
+    /// Run all enabled hooks of a specific type with extra environment
+    /// variables, e.g. `AUXIN_RECIPIENT` for pre-deliver hooks
+    pub fn run_hooks_with_env(
+        &self,
+        hook_type: HookType,
+        metadata: &CommitMetadata,
+        extra_env: &[(String, String)],
+    ) -> Result<bool> {
         let hooks_dir = self.hook_type_dir(hook_type);
 
         if !hooks_dir.exists() {
@@ -151,12 +186,12 @@ impl HookManager {
 
             println!("Running {} hook: {}", hook_type.dir_name(), hook_name);
 
-            let success = self.run_hook(&hook_path, metadata)?;
+            let success = self.run_hook(&hook_path, metadata, extra_env)?;
 
             if !success {
                 eprintln!("Hook failed: {}", hook_name);
-                if matches!(hook_type, HookType::PreCommit) {
-                    // Pre-commit hooks can abort the commit
+                if hook_type.can_abort() {
+                    // Pre-commit and pre-deliver hooks can abort the operation
                     return Ok(false);
                 }
                 // Post-commit hooks don't abort, just warn
@@ -167,43 +202,62 @@ impl HookManager {
     }
 
     /// Run a single hook script
-    fn run_hook(&self, hook_path: &Path, metadata: &CommitMetadata) -> Result<bool> {
+    fn run_hook(
+        &self,
+        hook_path: &Path,
+        metadata: &CommitMetadata,
+        extra_env: &[(String, String)],
+    ) -> Result<bool> {
         // Prepare environment variables for the hook
-        let output = Command::new(hook_path)
-            .env("AUXIN_MESSAGE", &metadata.message)
-            .env(
-                "AUXIN_BPM",
+        let mut env = vec![
+            ("AUXIN_MESSAGE".to_string(), metadata.message.clone()),
+            (
+                "AUXIN_BPM".to_string(),
                 metadata.bpm.map(|b| b.to_string()).unwrap_or_default(),
-            )
-            .env(
-                "AUXIN_SAMPLE_RATE",
+            ),
+            (
+                "AUXIN_SAMPLE_RATE".to_string(),
                 metadata
                     .sample_rate
                     .map(|s| s.to_string())
                     .unwrap_or_default(),
-            )
-            .env("AUXIN_KEY", metadata.key_signature.as_deref().unwrap_or(""))
-            .env("AUXIN_TAGS", metadata.tags.join(","))
-            .env("AUXIN_REPO_PATH", &self.repo_path)
-            .output()
-            .with_context(|| format!("Failed to execute hook: {:?}", hook_path))?;
+            ),
+            (
+                "AUXIN_KEY".to_string(),
+                metadata.key_signature.clone().unwrap_or_default(),
+            ),
+            ("AUXIN_TAGS".to_string(), metadata.tags.join(",")),
+            (
+                "AUXIN_REPO_PATH".to_string(),
+                self.repo_path.to_string_lossy().to_string(),
+            ),
+        ];
+        env.extend_from_slice(extra_env);
+        let outcome = run_script(hook_path, &self.repo_path, &env, DEFAULT_HOOK_TIMEOUT)?;
 
         // Print hook output
-        if !output.stdout.is_empty() {
-            println!("{}", String::from_utf8_lossy(&output.stdout));
+        if !outcome.stdout.is_empty() {
+            println!("{}", outcome.stdout);
+        }
+        if !outcome.stderr.is_empty() {
+            eprintln!("{}", outcome.stderr);
         }
-        if !output.stderr.is_empty() {
-            eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        if outcome.timed_out {
+            eprintln!(
+                "Hook timed out after {}s: {:?}",
+                DEFAULT_HOOK_TIMEOUT.as_secs(),
+                hook_path
+            );
         }
 
-        Ok(output.status.success())
+        Ok(outcome.success)
     }
 
     /// List all hooks
     pub fn list_hooks(&self) -> Result<Vec<(HookType, String)>> {
         let mut hooks = Vec::new();
 
-        for hook_type in [HookType::PreCommit, HookType::PostCommit] {
+        for hook_type in HookType::ALL {
             let hooks_dir = self.hook_type_dir(hook_type);
 
             if !hooks_dir.exists() {
@@ -251,6 +305,7 @@ impl HookManager {
             (HookType::PreCommit, "check-file-sizes") => HOOK_CHECK_FILE_SIZES,
             (HookType::PostCommit, "notify") => HOOK_NOTIFY,
             (HookType::PostCommit, "backup") => HOOK_BACKUP,
+            (HookType::PreDeliver, "check-loudness") => HOOK_CHECK_LOUDNESS,
             _ => return Err(anyhow::anyhow!("Unknown built-in hook: {}", name)),
         };
 
@@ -314,10 +369,87 @@ impl HookManager {
                 enabled: false,
                 hook_type: HookType::PostCommit,
             },
+            BuiltInHook {
+                name: "check-loudness".to_string(),
+                description: "Check the bounce's loudness before delivering".to_string(),
+                enabled: false,
+                hook_type: HookType::PreDeliver,
+            },
         ]
     }
 }
 
+/// How long a hook script may run before it is killed
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Result of running a hook script
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOutcome {
+    /// Script exited with status 0
+    pub success: bool,
+    /// Script was killed for exceeding its timeout
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Run a hook script
+///
+/// The script runs in `cwd` with stdin closed and `env` added to its
+/// environment. It is killed once `timeout` elapses; output written before
+/// then is discarded. Repository hooks and daemon event hooks both run
+/// through here so they behave the same way.
+pub fn run_script(
+    script: &Path,
+    cwd: &Path,
+    env: &[(String, String)],
+    timeout: Duration,
+) -> Result<ScriptOutcome> {
+    let mut child = Command::new(script)
+        .current_dir(cwd)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute hook: {:?}", script))?;
+
+    // Drain pipes on separate threads so a chatty script can't block on a full pipe
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    match child
+        .wait_timeout(timeout)
+        .with_context(|| format!("Error waiting for hook: {:?}", script))?
+    {
+        Some(status) => Ok(ScriptOutcome {
+            success: status.success(),
+            timed_out: false,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        }),
+        None => {
+            let _ = child.kill();
+            let _ = child.wait();
+            // Don't join the readers: a backgrounded grandchild may still hold the pipes
+            Ok(ScriptOutcome {
+                timed_out: true,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        String::from_utf8_lossy(&buffer).trim_end().to_string()
+    })
+}
+
 // Built-in hook templates
 
 const HOOKS_README: &str = r#"# Auxin Hooks
@@ -332,6 +464,9 @@ Run before creating a commit. Can abort the commit if validation fails.
 ### Post-Commit Hooks (`post-commit/`)
 Run after a successful commit. Cannot abort the commit.
 
+### Pre-Deliver Hooks (`pre-deliver/`)
+Run before `auxin deliver` records a delivery. Can abort the delivery.
+
 ## Creating Custom Hooks
 
 1. Create a script in the appropriate directory
@@ -347,6 +482,12 @@ Run after a successful commit. Cannot abort the commit.
 - `AUXIN_TAGS` - Comma-separated tags
 - `AUXIN_REPO_PATH` - Path to the repository
 
+Pre-deliver hooks also get:
+
+- `AUXIN_COMMIT` - Commit being delivered
+- `AUXIN_RECIPIENT` - Who it is going to
+- `AUXIN_FORMAT` - Delivery format (if given)
+
 See the documentation for examples and more information.
 "#;
 
@@ -441,6 +582,24 @@ ls -t ${PROJECT_NAME}_*.tar.gz | tail -n +11 | xargs -r rm
 exit 0
 "#;
 
+const HOOK_CHECK_LOUDNESS: &str = r#"#!/bin/bash
+# Pre-deliver hook: Check loudness against a delivery target
+
+# Set AUXIN_LOUDNESS_TARGET to spotify, apple-music, youtube or
+# broadcast-ebu-r128, or edit the default below
+TARGET="${AUXIN_LOUDNESS_TARGET:-spotify}"
+
+cd "$AUXIN_REPO_PATH" || exit 1
+
+if ! auxin bounce check "$AUXIN_COMMIT" --target "$TARGET"; then
+    echo "ERROR: Bounce doesn't meet the $TARGET loudness target."
+    echo "Deliver anyway with: auxin deliver --no-verify ..."
+    exit 1
+fi
+
+exit 0
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -464,6 +623,7 @@ mod tests {
         assert!(manager.hooks_dir().exists());
         assert!(manager.hook_type_dir(HookType::PreCommit).exists());
         assert!(manager.hook_type_dir(HookType::PostCommit).exists());
+        assert!(manager.hook_type_dir(HookType::PreDeliver).exists());
     }
 
     #[test]
@@ -479,6 +639,33 @@ mod tests {
     fn test_hook_type_dir_name() {
         assert_eq!(HookType::PreCommit.dir_name(), "pre-commit");
         assert_eq!(HookType::PostCommit.dir_name(), "post-commit");
+        assert_eq!(
+            HookType::from_dir_name("pre-deliver"),
+            Some(HookType::PreDeliver)
+        );
+        assert_eq!(HookType::from_dir_name("pre-push"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pre_deliver_hook_aborts() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        let manager = HookManager::new(dir.path());
+        manager.init().unwrap();
+
+        let hook = manager.hook_type_dir(HookType::PreDeliver).join("gate");
+        fs::write(&hook, "#!/bin/sh\n[ \"$AUXIN_RECIPIENT\" = \"Client X\" ]\n").unwrap();
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let metadata = CommitMetadata::new("Mix");
+        let env = |to: &str| [("AUXIN_RECIPIENT".to_string(), to.to_string())];
+        assert!(manager
+            .run_hooks_with_env(HookType::PreDeliver, &metadata, &env("Client X"))
+            .unwrap());
+        assert!(!manager
+            .run_hooks_with_env(HookType::PreDeliver, &metadata, &env("Label Y"))
+            .unwrap());
     }
 
     #[test]
@@ -530,4 +717,46 @@ mod tests {
             .join("validate-metadata");
         assert!(!hook_path.exists());
     }
+
+    #[cfg(unix)]
+    fn write_script(dir: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join("hook.sh");
+        fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_script_passes_env_and_cwd() {
+        let dir = tempdir().unwrap();
+        let script = write_script(
+            dir.path(),
+            "echo \"$AUXIN_EVENT $(pwd)\"; echo oops >&2; exit 3",
+        );
+
+        let env = [("AUXIN_EVENT".to_string(), "push-failed".to_string())];
+        let outcome = run_script(&script, dir.path(), &env, Duration::from_secs(10)).unwrap();
+
+        assert!(!outcome.success);
+        assert!(!outcome.timed_out);
+        let cwd = dir.path().canonicalize().unwrap();
+        assert_eq!(outcome.stdout, format!("push-failed {}", cwd.display()));
+        assert_eq!(outcome.stderr, "oops");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_script_kills_on_timeout() {
+        let dir = tempdir().unwrap();
+        let script = write_script(dir.path(), "sleep 30");
+
+        let started = std::time::Instant::now();
+        let outcome = run_script(&script, dir.path(), &[], Duration::from_millis(200)).unwrap();
+
+        assert!(outcome.timed_out);
+        assert!(!outcome.success);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
 }
@@ -0,0 +1,146 @@
+//! Who the current user is, to locks, comments and activity
+//!
+//! The same person goes by several names: the login and hostname locks
+//! have always recorded (`sam@studio-mac`), the Oxen hub account, and the
+//! auxin server account. [`Identity`] picks the one name recorded
+//! everywhere, `name@machine`, taking the name from `[identity] name`, the
+//! hub username saved by `auxin auth login`, or the login, in that order.
+//! It keeps the others so a lock taken under an older name is still yours.
+//! `auxin auth whoami --link` and the daemon's presence pings tell the
+//! server which account the name belongs to, so its audit log and lock
+//! stats show the account.
+
+use crate::auth::AuthManager;
+use auxin_config::Config;
+use std::sync::OnceLock;
+
+static CURRENT: OnceLock<Identity> = OnceLock::new();
+
+/// The current user's names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    /// Team name, without any `@machine`
+    pub name: String,
+    /// Login name on this machine
+    pub login: String,
+    pub machine: String,
+    /// Oxen hub username, if logged in with `auxin auth login`
+    pub hub_user: Option<String>,
+    /// `[identity] aliases`
+    pub aliases: Vec<String>,
+}
+
+impl Identity {
+    /// The identity of whoever runs auxin, resolved once per process
+    pub fn current() -> &'static Identity {
+        CURRENT.get_or_init(|| {
+            Self::resolve(
+                &Config::load().unwrap_or_default().identity,
+                login(),
+                machine(),
+                AuthManager::new().stored_username(),
+            )
+        })
+    }
+
+    pub fn resolve(
+        config: &auxin_config::Identity,
+        login: String,
+        machine: String,
+        hub_user: Option<String>,
+    ) -> Self {
+        let configured = config.name.split('@').next().unwrap_or_default().trim();
+        let hub_user = hub_user.filter(|user| !user.trim().is_empty());
+        let name = match (configured, &hub_user) {
+            ("", Some(hub_user)) => hub_user.clone(),
+            ("", None) => login.clone(),
+            (configured, _) => configured.to_string(),
+        };
+
+        Self {
+            name,
+            login,
+            machine,
+            hub_user,
+            aliases: config.aliases.clone(),
+        }
+    }
+
+    /// What locks, comments and activity record: `name@machine`
+    pub fn user(&self) -> String {
+        format!("{}@{}", self.name, self.machine)
+    }
+
+    /// The other names this user went by, most recent first: the
+    /// `login@machine` locks used before, the hub username and the aliases
+    pub fn other_names(&self) -> Vec<String> {
+        let user = self.user();
+        let mut names = vec![format!("{}@{}", self.login, self.machine)];
+        names.extend(self.hub_user.clone());
+        names.extend(self.aliases.iter().cloned());
+        names.retain(|name| !name.eq_ignore_ascii_case(&user));
+        names.dedup();
+        names
+    }
+
+    /// Whether `user`, e.g. a lock holder or comment author, is this user
+    pub fn is_me(&self, user: &str) -> bool {
+        let user = user.trim();
+        user.eq_ignore_ascii_case(&self.user())
+            || self.other_names().iter().any(|name| name.eq_ignore_ascii_case(user))
+    }
+}
+
+/// Login name on this machine
+pub fn login() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// This machine's hostname
+pub fn machine() -> String {
+    hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_configured_then_hub_name() {
+        let mut config = auxin_config::Identity::default();
+        let resolve = |config: &auxin_config::Identity, hub: Option<&str>| {
+            Identity::resolve(
+                config,
+                "sam".to_string(),
+                "studio-mac".to_string(),
+                hub.map(str::to_string),
+            )
+        };
+
+        let identity = resolve(&config, None);
+        assert_eq!(identity.user(), "sam@studio-mac");
+        assert!(identity.other_names().is_empty());
+
+        let identity = resolve(&config, Some("samantha"));
+        assert_eq!(identity.user(), "samantha@studio-mac");
+        // Locks taken before logging in to the hub are still yours
+        assert!(identity.is_me("sam@studio-mac"));
+        assert!(identity.is_me("Samantha@Studio-Mac"));
+        assert!(!identity.is_me("alex@studio-mac"));
+
+        config.name = "Sam R@anywhere".to_string();
+        config.aliases = vec!["sam@old-laptop".to_string()];
+        let identity = resolve(&config, Some("samantha"));
+        assert_eq!(identity.user(), "Sam R@studio-mac");
+        assert_eq!(
+            identity.other_names(),
+            vec!["sam@studio-mac", "samantha", "sam@old-laptop"]
+        );
+        assert!(identity.is_me(" sam@old-laptop "));
+    }
+}
@@ -451,6 +451,162 @@ pub fn generate_blender_oxenignore() -> String {
     content
 }
 
+/// Generates the `.oxenignore` template for Premiere Pro projects.
+///
+/// Premiere Pro writes auto-saves, rendered previews and the media cache
+/// (conformed audio, peak files) next to the `.prproj`. All of it is
+/// regenerated on demand and can be far larger than the project itself.
+///
+/// # See Also
+///
+/// - `PremiereProject::ignored_patterns()` - Source of truth for patterns
+pub fn generate_premiere_oxenignore() -> String {
+    let mut content = String::new();
+    content.push_str("# Oxen VCS - Premiere Pro Ignore Rules\n");
+    content.push_str("# Auto-generated ignore file for Premiere Pro projects\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Auto-Save and Lock Files\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Premiere Pro's own backups, redundant with version control\n\n");
+    content.push_str("Adobe Premiere Pro Auto-Save/\n");
+    content.push_str("*.prlock\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Preview Renders\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Rendered timeline previews, regenerated when needed\n\n");
+    content.push_str("Adobe Premiere Pro Preview Files/\n");
+    content.push_str("Adobe Premiere Pro Video Previews/\n");
+    content.push_str("Adobe Premiere Pro Audio Previews/\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Media Cache\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Conformed audio and waveform peaks\n\n");
+    content.push_str("Media Cache/\n");
+    content.push_str("Media Cache Files/\n");
+    content.push_str("*.cfa\n");
+    content.push_str("*.pek\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# System Files\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# OS-specific metadata\n\n");
+    content.push_str(".DS_Store\n");
+    content.push_str("Thumbs.db\n");
+    content.push_str("desktop.ini\n");
+    content.push_str("*.smbdelete*\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Custom Ignore Patterns\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Add your custom patterns below\n\n");
+
+    content
+}
+
+/// Generates the `.oxenignore` template for After Effects projects.
+///
+/// Like Premiere Pro, After Effects keeps auto-saves and caches next to the
+/// `.aep`, plus a disk cache of rendered frames and a log folder per
+/// project.
+///
+/// # See Also
+///
+/// - `AfterEffectsProject::ignored_patterns()` - Source of truth for patterns
+pub fn generate_after_effects_oxenignore() -> String {
+    let mut content = String::new();
+    content.push_str("# Oxen VCS - After Effects Ignore Rules\n");
+    content.push_str("# Auto-generated ignore file for After Effects projects\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Auto-Save and Logs\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# After Effects' own backups and render logs\n\n");
+    content.push_str("Adobe After Effects Auto-Save/\n");
+    content.push_str("*.aep Logs/\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Caches\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Rendered frames, conformed audio and waveform peaks\n\n");
+    content.push_str("Disk Cache/\n");
+    content.push_str("Media Cache/\n");
+    content.push_str("Media Cache Files/\n");
+    content.push_str("*.cfa\n");
+    content.push_str("*.pek\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# System Files\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# OS-specific metadata\n\n");
+    content.push_str(".DS_Store\n");
+    content.push_str("Thumbs.db\n");
+    content.push_str("desktop.ini\n");
+    content.push_str("*.smbdelete*\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Custom Ignore Patterns\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Add your custom patterns below\n\n");
+
+    content
+}
+
+/// Generates the `.oxenignore` template for Unreal Engine projects.
+///
+/// The engine rebuilds `Intermediate/`, `Saved/`, `DerivedDataCache/` and
+/// `Binaries/` from the project's sources and assets; in a large game these
+/// run to tens of gigabytes. IDE project files are regenerated from the
+/// `.uproject` too.
+///
+/// # See Also
+///
+/// - `UnrealProject::ignored_patterns()` - Source of truth for patterns
+pub fn generate_unreal_oxenignore() -> String {
+    let mut content = String::new();
+    content.push_str("# Oxen VCS - Unreal Engine Ignore Rules\n");
+    content.push_str("# Auto-generated ignore file for Unreal Engine projects\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Engine-Generated Folders\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Build output, caches, logs and autosaves, rebuilt by the editor\n\n");
+    content.push_str("Binaries/\n");
+    content.push_str("Intermediate/\n");
+    content.push_str("Saved/\n");
+    content.push_str("DerivedDataCache/\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# IDE Project Files\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Regenerated from the .uproject\n\n");
+    content.push_str(".vs/\n");
+    content.push_str("*.sln\n");
+    content.push_str("*.suo\n");
+    content.push_str("*.VC.db\n");
+    content.push_str("*.opensdf\n");
+    content.push_str("*.sdf\n");
+    content.push_str("*.xcworkspace\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# System Files\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# OS-specific metadata\n\n");
+    content.push_str(".DS_Store\n");
+    content.push_str("Thumbs.db\n");
+    content.push_str("desktop.ini\n");
+    content.push_str("*.smbdelete*\n\n");
+
+    content.push_str("# ===================================\n");
+    content.push_str("# Custom Ignore Patterns\n");
+    content.push_str("# ===================================\n");
+    content.push_str("# Add your custom patterns below\n\n");
+
+    content
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -898,4 +1054,47 @@ mod tests {
             "Should not have duplicate patterns"
         );
     }
+
+    // ==================== Premiere Pro / After Effects Tests ====================
+
+    #[test]
+    fn test_adobe_oxenignore_consistency_with_projects() {
+        use crate::adobe_project::{AfterEffectsProject, PremiereProject};
+
+        let templates = [
+            (
+                generate_premiere_oxenignore(),
+                PremiereProject::ignored_patterns(),
+            ),
+            (
+                generate_after_effects_oxenignore(),
+                AfterEffectsProject::ignored_patterns(),
+            ),
+        ];
+        for (content, patterns) in templates {
+            assert!(content.contains("Custom Ignore Patterns"));
+            for pattern in patterns {
+                assert!(
+                    content.lines().any(|line| line == pattern),
+                    "Pattern '{}' not found in template",
+                    pattern
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_unreal_oxenignore_consistency_with_project() {
+        use crate::unreal_project::UnrealProject;
+
+        let content = generate_unreal_oxenignore();
+        for pattern in UnrealProject::ignored_patterns() {
+            assert!(
+                content.lines().any(|line| line == pattern),
+                "Pattern '{}' not found in template",
+                pattern
+            );
+        }
+        assert!(!content.contains("Content/"));
+    }
 }
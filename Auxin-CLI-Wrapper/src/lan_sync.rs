@@ -0,0 +1,679 @@
+//! Peer-to-peer project hand-off on the LAN: `auxin lan send` / `receive`
+//!
+//! For handing a session to the engineer across the room when there is no
+//! server or internet. The sender listens on a TCP port and shows a
+//! one-time pairing code; the receiver connects with the code and gets a
+//! copy of the project folder: working files, commit history (`.oxen`),
+//! metadata and bounces (`.auxin`).
+//!
+//! The pairing code never crosses the network. Both sides derive keys from
+//! it (PBKDF2 with a random salt), prove they know it with HMAC challenges,
+//! and everything after the handshake is encrypted and authenticated with
+//! ChaCha20-Poly1305. The sender gives up after [`MAX_ATTEMPTS`] failed
+//! handshakes. The project itself travels as a `tar` stream, in data frames
+//! followed by an end frame, or an error frame if the sender's `tar` fails,
+//! so the receiver never takes a cut-off archive for a whole one.
+
+use anyhow::{anyhow, bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305};
+use ring::hmac;
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Failed pairing attempts before the sender stops listening
+pub const MAX_ATTEMPTS: usize = 3;
+
+/// Version 2 added the frame kinds below
+const MAGIC: &[u8; 8] = b"AUXLAN2\n";
+/// No 0/O or 1/I, so codes survive being read aloud
+const CODE_ALPHABET: &[u8; 32] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+const CODE_LEN: usize = 12;
+const PBKDF2_ROUNDS: u32 = 100_000;
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Largest frame accepted: a chunk plus its authentication tag
+const MAX_FRAME: usize = CHUNK_SIZE + 64;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Paths left out of the archive
+const EXCLUDES: &[&str] = &["./.auxin/conflicts", "./.auxin/prompt_refresh", ".DS_Store"];
+
+const ACCEPT: &[u8] = b"accept";
+const DECLINE: &[u8] = b"decline";
+const DONE: &[u8] = b"done";
+
+/// First byte of each archive frame: a piece of the archive, its end, or
+/// the sender's error message
+const FRAME_DATA: u8 = b'd';
+const FRAME_END: u8 = b'e';
+const FRAME_ERROR: u8 = b'x';
+
+/// A one-time pairing code such as `K7QF-9XMA-2CDE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingCode(String);
+
+impl PairingCode {
+    pub fn generate() -> Result<Self> {
+        let bytes: [u8; CODE_LEN] = random()?;
+        // 256 is a multiple of 32, so every character is equally likely
+        let code = bytes
+            .iter()
+            .map(|b| CODE_ALPHABET[*b as usize % CODE_ALPHABET.len()] as char)
+            .collect();
+        Ok(Self(code))
+    }
+}
+
+impl FromStr for PairingCode {
+    type Err = anyhow::Error;
+
+    /// Case, dashes and spaces don't matter
+    fn from_str(s: &str) -> Result<Self> {
+        let code: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if code.len() != CODE_LEN || !code.bytes().all(|b| CODE_ALPHABET.contains(&b)) {
+            bail!(
+                "'{}' is not a pairing code (expected e.g. K7QF-9XMA-2CDE)",
+                s
+            );
+        }
+        Ok(Self(code))
+    }
+}
+
+impl fmt::Display for PairingCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups: Vec<&str> = [&self.0[..4], &self.0[4..8], &self.0[8..]].to_vec();
+        write!(f, "{}", groups.join("-"))
+    }
+}
+
+/// What the sender is handing over, shown before anything is written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Project folder name
+    pub project: String,
+    /// `user@host` of the sender
+    pub sender: String,
+    pub branch: Option<String>,
+    /// Latest commit, `<short id> <message>`
+    pub head: Option<String>,
+    /// Whether the working files include uncommitted changes
+    pub uncommitted: bool,
+    /// Size of the project folder
+    pub size_bytes: u64,
+    pub sent_at: DateTime<Utc>,
+}
+
+impl Manifest {
+    pub fn for_project(project: &Path) -> Result<Self> {
+        let project = project
+            .canonicalize()
+            .with_context(|| format!("Project not found: {}", project.display()))?;
+        let oxen = OxenSubprocess::new();
+        let head = oxen.log(&project, Some(1)).ok().and_then(|log| {
+            log.into_iter()
+                .next()
+                .map(|c| format!("{} {}", &c.id[..c.id.len().min(8)], c.message.trim()))
+        });
+        let uncommitted = oxen
+            .status(&project)
+            .is_ok_and(|s| !s.modified.is_empty() || !s.untracked.is_empty());
+
+        Ok(Self {
+            project: project
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "project".to_string()),
+            sender: crate::lock_integration::get_user_identifier(),
+            branch: oxen.current_branch(&project).ok(),
+            head,
+            uncommitted,
+            size_bytes: dir_size(&project),
+            sent_at: Utc::now(),
+        })
+    }
+}
+
+/// Keys derived from the pairing code for one session
+struct SessionKeys {
+    auth: hmac::Key,
+    /// Both handshake nonces, bound into every proof and key
+    nonces: Vec<u8>,
+    sender_to_receiver: LessSafeKey,
+    receiver_to_sender: LessSafeKey,
+}
+
+impl SessionKeys {
+    fn derive(code: &PairingCode, salt: &[u8], sender_nonce: &[u8], receiver_nonce: &[u8]) -> Self {
+        let mut master = [0u8; 32];
+        let rounds = NonZeroU32::new(PBKDF2_ROUNDS).expect("non-zero rounds");
+        let algorithm = pbkdf2::PBKDF2_HMAC_SHA256;
+        pbkdf2::derive(algorithm, rounds, salt, code.0.as_bytes(), &mut master);
+        let master = hmac::Key::new(hmac::HMAC_SHA256, &master);
+
+        let nonces = [sender_nonce, receiver_nonce].concat();
+        let subkey = |label: &[u8]| hmac::sign(&master, &[label, &nonces].concat());
+        let aead_key = |label: &[u8]| {
+            let key = UnboundKey::new(&CHACHA20_POLY1305, subkey(label).as_ref())
+                .expect("HMAC-SHA256 output is a valid ChaCha20 key");
+            LessSafeKey::new(key)
+        };
+
+        Self {
+            auth: hmac::Key::new(hmac::HMAC_SHA256, subkey(b"auth").as_ref()),
+            sender_to_receiver: aead_key(b"sender->receiver"),
+            receiver_to_sender: aead_key(b"receiver->sender"),
+            nonces,
+        }
+    }
+
+    fn proof(&self, role: &[u8]) -> hmac::Tag {
+        hmac::sign(&self.auth, &[role, &self.nonces].concat())
+    }
+
+    fn verify(&self, role: &[u8], proof: &[u8]) -> bool {
+        hmac::verify(&self.auth, &[role, &self.nonces].concat(), proof).is_ok()
+    }
+}
+
+/// Encrypted, authenticated frames over a TCP stream
+pub struct SecureChannel {
+    stream: TcpStream,
+    seal: LessSafeKey,
+    open: LessSafeKey,
+    sent: u64,
+    received: u64,
+}
+
+impl SecureChannel {
+    /// Sender side of the handshake
+    fn accept(mut stream: TcpStream, code: &PairingCode) -> Result<Self> {
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let salt: [u8; 16] = random()?;
+        let sender_nonce: [u8; 16] = random()?;
+        stream.write_all(&[&MAGIC[..], &salt, &sender_nonce].concat())?;
+
+        let mut reply = [0u8; 16 + 32];
+        stream
+            .read_exact(&mut reply)
+            .context("The receiver disconnected during pairing")?;
+        let (receiver_nonce, proof) = reply.split_at(16);
+        let keys = SessionKeys::derive(code, &salt, &sender_nonce, receiver_nonce);
+        if !keys.verify(b"receiver", proof) {
+            bail!("Wrong pairing code");
+        }
+        stream.write_all(keys.proof(b"sender").as_ref())?;
+        stream.set_read_timeout(None)?;
+
+        Ok(Self::new(
+            stream,
+            keys.sender_to_receiver,
+            keys.receiver_to_sender,
+        ))
+    }
+
+    /// Receiver side of the handshake
+    fn connect(mut stream: TcpStream, code: &PairingCode) -> Result<Self> {
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let mut hello = [0u8; 8 + 16 + 16];
+        stream
+            .read_exact(&mut hello)
+            .context("Not an auxin lan sender")?;
+        if &hello[..8] != MAGIC {
+            bail!("Not an auxin lan sender");
+        }
+        let (salt, sender_nonce) = hello[8..].split_at(16);
+        let receiver_nonce: [u8; 16] = random()?;
+        let keys = SessionKeys::derive(code, salt, sender_nonce, &receiver_nonce);
+        stream.write_all(&[&receiver_nonce[..], keys.proof(b"receiver").as_ref()].concat())?;
+
+        let mut proof = [0u8; 32];
+        stream
+            .read_exact(&mut proof)
+            .map_err(|_| anyhow!("Pairing failed; check the code shown on the sender"))?;
+        if !keys.verify(b"sender", &proof) {
+            bail!("The sender could not prove it knows the pairing code");
+        }
+        stream.set_read_timeout(None)?;
+
+        Ok(Self::new(
+            stream,
+            keys.receiver_to_sender,
+            keys.sender_to_receiver,
+        ))
+    }
+
+    fn new(stream: TcpStream, seal: LessSafeKey, open: LessSafeKey) -> Self {
+        Self {
+            stream,
+            seal,
+            open,
+            sent: 0,
+            received: 0,
+        }
+    }
+
+    pub fn send(&mut self, data: &[u8]) -> Result<()> {
+        let mut frame = data.to_vec();
+        self.seal
+            .seal_in_place_append_tag(frame_nonce(self.sent), Aad::empty(), &mut frame)
+            .map_err(|_| anyhow!("Failed to encrypt data"))?;
+        self.sent += 1;
+        self.stream.write_all(&(frame.len() as u32).to_be_bytes())?;
+        self.stream.write_all(&frame)?;
+        Ok(())
+    }
+
+    pub fn recv(&mut self) -> Result<Vec<u8>> {
+        let mut len = [0u8; 4];
+        self.stream
+            .read_exact(&mut len)
+            .context("Connection closed by the other machine")?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_FRAME {
+            bail!(
+                "Received a frame of {} bytes; the connection is corrupt",
+                len
+            );
+        }
+        let mut frame = vec![0u8; len];
+        self.stream
+            .read_exact(&mut frame)
+            .context("Connection closed by the other machine")?;
+        let plain_len = self
+            .open
+            .open_in_place(frame_nonce(self.received), Aad::empty(), &mut frame)
+            .map_err(|_| anyhow!("Received data failed authentication"))?
+            .len();
+        self.received += 1;
+        frame.truncate(plain_len);
+        Ok(frame)
+    }
+}
+
+/// A frame telling the receiver the transfer failed
+fn error_frame(message: &str) -> Vec<u8> {
+    [&[FRAME_ERROR][..], message.as_bytes()].concat()
+}
+
+/// Nonces are frame counters; each direction has its own key
+fn frame_nonce(counter: u64) -> Nonce {
+    let mut nonce = [0u8; aead::NONCE_LEN];
+    nonce[4..].copy_from_slice(&counter.to_be_bytes());
+    Nonce::assume_unique_for_key(nonce)
+}
+
+fn random<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| anyhow!("No secure random number source"))?;
+    Ok(bytes)
+}
+
+/// The sending side: listens until a receiver pairs, then streams the project
+pub struct LanSender {
+    project: PathBuf,
+    listener: TcpListener,
+    code: PairingCode,
+}
+
+impl LanSender {
+    /// Listen on `port` (0 picks a free port) with a fresh pairing code
+    pub fn bind(project: &Path, port: u16) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+            .with_context(|| format!("Failed to listen on port {}", port))?;
+        Ok(Self {
+            project: project.to_path_buf(),
+            listener,
+            code: PairingCode::generate()?,
+        })
+    }
+
+    pub fn code(&self) -> &PairingCode {
+        &self.code
+    }
+
+    pub fn port(&self) -> Result<u16> {
+        Ok(self.listener.local_addr()?.port())
+    }
+
+    /// Wait for a receiver with the right code and send it the project;
+    /// returns who received it and the bytes sent
+    pub fn serve(&self, mut on_progress: impl FnMut(u64)) -> Result<(SocketAddr, u64)> {
+        for _ in 0..MAX_ATTEMPTS {
+            let (stream, peer) = self.listener.accept()?;
+            match SecureChannel::accept(stream, &self.code) {
+                Ok(channel) => {
+                    let sent = self.send_project(channel, &mut on_progress)?;
+                    return Ok((peer, sent));
+                },
+                Err(e) => crate::warn!("Pairing attempt from {} failed: {}", peer.ip(), e),
+            }
+        }
+        bail!(
+            "{} failed pairing attempts; run 'auxin lan send' again for a new code",
+            MAX_ATTEMPTS
+        )
+    }
+
+    fn send_project(
+        &self,
+        mut channel: SecureChannel,
+        on_progress: &mut impl FnMut(u64),
+    ) -> Result<u64> {
+        let manifest = Manifest::for_project(&self.project)?;
+        channel.send(&serde_json::to_vec(&manifest)?)?;
+        if channel.recv()? != ACCEPT {
+            bail!("The receiver declined the transfer");
+        }
+
+        let mut tar = Command::new("tar");
+        for exclude in EXCLUDES {
+            tar.arg(format!("--exclude={}", exclude));
+        }
+        let mut child = tar
+            .args(["-cf", "-", "-C"])
+            .arg(&self.project)
+            .arg(".")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run 'tar'. Is it installed?")?;
+        let mut archive = child.stdout.take().expect("piped stdout");
+
+        let mut sent = 0;
+        let mut frame = vec![FRAME_DATA; CHUNK_SIZE + 1];
+        loop {
+            let read = match archive.read(&mut frame[1..]) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) => {
+                    let _ = child.kill();
+                    let _ = channel.send(&error_frame(&format!("reading the archive: {}", e)));
+                    return Err(e).context("Failed to read the archive");
+                },
+            };
+            channel.send(&frame[..=read])?;
+            sent += read as u64;
+            on_progress(sent);
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let error = format!(
+                "tar failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            let _ = channel.send(&error_frame(&error));
+            bail!(error);
+        }
+        channel.send(&[FRAME_END])?;
+
+        let reply = channel.recv()?;
+        if reply != DONE {
+            bail!("The receiver failed: {}", String::from_utf8_lossy(&reply));
+        }
+        Ok(sent)
+    }
+}
+
+/// The receiving side, paired and holding the sender's manifest
+pub struct LanReceiver {
+    channel: SecureChannel,
+    manifest: Manifest,
+}
+
+impl LanReceiver {
+    /// Connect to `address` (`host:port`) and pair with `code`
+    pub fn connect(address: &str, code: &PairingCode) -> Result<Self> {
+        let addrs: Vec<SocketAddr> = address
+            .to_socket_addrs()
+            .with_context(|| format!("Invalid address '{}' (expected host:port)", address))?
+            .collect();
+        let stream = addrs
+            .iter()
+            .find_map(|addr| TcpStream::connect_timeout(addr, CONNECT_TIMEOUT).ok())
+            .ok_or_else(|| anyhow!("Could not connect to {}", address))?;
+
+        let mut channel = SecureChannel::connect(stream, code)?;
+        let manifest = serde_json::from_slice(&channel.recv()?)
+            .context("The sender's manifest is not valid")?;
+        Ok(Self { channel, manifest })
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Tell the sender no
+    pub fn decline(mut self) -> Result<()> {
+        self.channel.send(DECLINE)
+    }
+
+    /// Receive the project into `dest`, which must not exist or be empty;
+    /// returns the bytes received
+    pub fn receive_into(mut self, dest: &Path, mut on_progress: impl FnMut(u64)) -> Result<u64> {
+        if !is_empty_dir(dest) {
+            bail!("{} already exists and is not empty", dest.display());
+        }
+        fs::create_dir_all(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+        self.channel.send(ACCEPT)?;
+
+        let mut child = Command::new("tar")
+            .args(["-xf", "-", "-C"])
+            .arg(dest)
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run 'tar'. Is it installed?")?;
+        let mut stdin = child.stdin.take().expect("piped stdin");
+
+        let mut received = 0;
+        loop {
+            let frame = self.channel.recv()?;
+            let chunk = match frame.split_first() {
+                Some((&FRAME_DATA, chunk)) => chunk,
+                Some((&FRAME_END, [])) => break,
+                Some((&FRAME_ERROR, error)) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    bail!(
+                        "The sender failed, the project in {} is incomplete: {}",
+                        dest.display(),
+                        String::from_utf8_lossy(error)
+                    );
+                },
+                _ => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    bail!("Received an unexpected frame; the connection is corrupt");
+                },
+            };
+            if let Err(e) = stdin.write_all(chunk) {
+                let _ = self
+                    .channel
+                    .send(format!("extracting failed: {}", e).as_bytes());
+                return Err(e).context("Failed to extract the project");
+            }
+            received += chunk.len() as u64;
+            on_progress(received);
+        }
+        drop(stdin);
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let _ = self
+                .channel
+                .send(format!("extracting failed: {}", error).as_bytes());
+            bail!("tar failed: {}", error);
+        }
+        self.channel.send(DONE)?;
+        Ok(received)
+    }
+}
+
+/// Move a non-empty `dest` aside to `<dest>.before-lan-<timestamp>`
+pub fn set_aside(dest: &Path) -> Result<Option<PathBuf>> {
+    if is_empty_dir(dest) {
+        return Ok(None);
+    }
+    let mut aside = dest.as_os_str().to_owned();
+    aside.push(format!(
+        ".before-lan-{}",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    let aside = PathBuf::from(aside);
+    fs::rename(dest, &aside).with_context(|| format!("Failed to move {} aside", dest.display()))?;
+    Ok(Some(aside))
+}
+
+/// This machine's LAN address, for the command shown to the receiver
+pub fn local_address() -> Option<IpAddr> {
+    // No packet is sent; connecting only picks the interface
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(224, 0, 0, 251), 5353)).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_unspecified()).then_some(ip)
+}
+
+fn is_empty_dir(path: &Path) -> bool {
+    match fs::read_dir(path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(_) => !path.exists(),
+    }
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| Some((entry.path(), entry.file_type().ok()?)))
+        .map(|(path, file_type)| {
+            if file_type.is_dir() {
+                dir_size(&path)
+            } else if file_type.is_file() {
+                fs::metadata(&path).map_or(0, |m| m.len())
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pairing_code() {
+        let code = PairingCode::generate().unwrap();
+        let shown = code.to_string();
+        assert_eq!(shown.len(), CODE_LEN + 2);
+        assert_eq!(shown.parse::<PairingCode>().unwrap(), code);
+        assert_eq!(
+            "k7qf 9xma-2cde".parse::<PairingCode>().unwrap().to_string(),
+            "K7QF-9XMA-2CDE"
+        );
+        assert!("K7QF-9XMA".parse::<PairingCode>().is_err());
+        assert!("K7QF-9XMA-2CD0".parse::<PairingCode>().is_err());
+    }
+
+    #[test]
+    fn test_send_and_receive() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Album.logicx");
+        fs::create_dir_all(project.join(".oxen")).unwrap();
+        fs::create_dir_all(project.join(".auxin/bounces")).unwrap();
+        fs::create_dir_all(project.join(".auxin/conflicts")).unwrap();
+        fs::write(project.join(".oxen/HEAD"), "main").unwrap();
+        fs::write(project.join(".auxin/bounces/mix.wav"), vec![7u8; 200_000]).unwrap();
+        fs::write(project.join(".auxin/conflicts/old"), "backup").unwrap();
+        fs::write(project.join("ProjectData"), "session").unwrap();
+
+        let sender = LanSender::bind(&project, 0).unwrap();
+        let address = format!("127.0.0.1:{}", sender.port().unwrap());
+        let code = sender.code().clone();
+        let serving = thread::spawn(move || sender.serve(|_| {}));
+
+        // A wrong code costs one attempt
+        let wrong = "2222-2222-2222".parse().unwrap();
+        assert!(LanReceiver::connect(&address, &wrong).is_err());
+
+        let receiver = LanReceiver::connect(&address, &code).unwrap();
+        assert_eq!(receiver.manifest().project, "Album.logicx");
+        assert!(receiver.manifest().size_bytes >= 200_000);
+
+        let dest = temp.path().join("received");
+        let received = receiver.receive_into(&dest, |_| {}).unwrap();
+        let (_, sent) = serving.join().unwrap().unwrap();
+        assert_eq!(sent, received);
+
+        assert_eq!(fs::read_to_string(dest.join(".oxen/HEAD")).unwrap(), "main");
+        assert_eq!(
+            fs::read(dest.join(".auxin/bounces/mix.wav")).unwrap().len(),
+            200_000
+        );
+        assert_eq!(
+            fs::read_to_string(dest.join("ProjectData")).unwrap(),
+            "session"
+        );
+        assert!(!dest.join(".auxin/conflicts").exists());
+
+        assert!(set_aside(&dest).unwrap().is_some());
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_sender_error_fails_the_receiver() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Album.logicx");
+        fs::create_dir_all(&project).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let code = PairingCode::generate().unwrap();
+
+        // A sender whose tar fails partway through the archive
+        let sender_code = code.clone();
+        let sending = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut channel = SecureChannel::accept(stream, &sender_code).unwrap();
+            let manifest = Manifest::for_project(&project).unwrap();
+            channel
+                .send(&serde_json::to_vec(&manifest).unwrap())
+                .unwrap();
+            assert_eq!(channel.recv().unwrap(), ACCEPT);
+            channel.send(&[FRAME_DATA, 0, 0, 0]).unwrap();
+            channel
+                .send(&error_frame("tar failed: disk read error"))
+                .unwrap();
+        });
+
+        let receiver = LanReceiver::connect(&address, &code).unwrap();
+        let dest = temp.path().join("received");
+        let err = receiver.receive_into(&dest, |_| {}).unwrap_err();
+        assert!(err.to_string().contains("disk read error"));
+        sending.join().unwrap();
+    }
+}
@@ -0,0 +1,414 @@
+//! First-run migration from the legacy OxVCS layout
+//!
+//! Before the rename, the CLI kept its settings in `~/.oxenvcs/` and each
+//! project's sidecar files in `<project>/.oxenvcs/`, with commit comments in
+//! `.oxenvcs/comments/`. `auxin migrate-legacy` (or the prompt shown the
+//! first time auxin finds a legacy directory) moves them to the current
+//! layout:
+//!
+//! | Legacy                            | Current                           |
+//! |-----------------------------------|-----------------------------------|
+//! | `~/.oxenvcs/config.toml`          | `~/.auxin/config.toml`            |
+//! | `~/.oxenvcs/queue/`               | `~/.auxin/queue/`                 |
+//! | other files in `~/.oxenvcs/`      | same place under `~/.auxin/`      |
+//! | `<project>/.oxenvcs/comments/`    | `<project>/.oxen/comments/`       |
+//! | other files in `<project>/.oxenvcs/` | same place under `<project>/.auxin/` |
+//!
+//! Paths and `OXENVCS_` variable names inside the config are rewritten, and
+//! legacy comment files (one comment object, `timestamp`/`user`/`message`
+//! fields) are converted to the current comment list format.
+//!
+//! Nothing in the new layout is overwritten: a file that already exists
+//! there is left alone and reported. Afterwards each legacy directory is
+//! renamed to `.oxenvcs.migrated`, so skipped files can still be inspected
+//! and the migration doesn't run twice.
+
+use crate::collaboration::Comment;
+use anyhow::{Context, Result};
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory name used by the legacy layout, in the home and project dirs
+pub const LEGACY_DIR: &str = ".oxenvcs";
+
+/// Suffix given to a legacy directory once it has been migrated
+pub const MIGRATED_SUFFIX: &str = ".migrated";
+
+/// Marker in `~/.auxin/` recording that the user declined the prompt
+const DECLINED_MARKER: &str = "legacy_migration_declined";
+
+/// What happens to one legacy file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationAction {
+    /// Config copied with legacy paths and variable names rewritten
+    RewriteConfig,
+    /// Comments converted and merged into the current comment file
+    ConvertComments,
+    /// File copied unchanged
+    Copy,
+    /// Destination already exists with different contents; left alone
+    Skip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStep {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub action: MigrationAction,
+}
+
+/// Legacy directories to migrate for a user and, optionally, a project
+#[derive(Debug, Clone)]
+pub struct LegacyMigration {
+    home: PathBuf,
+    project: Option<PathBuf>,
+}
+
+/// Outcome of [`LegacyMigration::run`]
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub migrated: Vec<MigrationStep>,
+    pub skipped: Vec<MigrationStep>,
+    /// Legacy files that couldn't be read or converted, with the reason
+    pub failed: Vec<(PathBuf, String)>,
+    /// Where each legacy directory was moved afterwards
+    pub archived: Vec<PathBuf>,
+}
+
+impl LegacyMigration {
+    pub fn new(home: impl Into<PathBuf>, project: Option<PathBuf>) -> Self {
+        Self {
+            home: home.into(),
+            project,
+        }
+    }
+
+    /// Migration for the current user's home directory and `project`
+    pub fn for_current_user(project: Option<PathBuf>) -> Result<Self> {
+        let home = dirs::home_dir().context("Could not determine home directory")?;
+        Ok(Self::new(home, project))
+    }
+
+    /// Legacy directories that exist and haven't been migrated
+    pub fn legacy_dirs(&self) -> Vec<PathBuf> {
+        self.roots().into_iter().map(|(legacy, _)| legacy).collect()
+    }
+
+    pub fn is_needed(&self) -> bool {
+        !self.roots().is_empty()
+    }
+
+    /// Whether auxin should offer to migrate on startup
+    pub fn should_prompt(&self) -> bool {
+        self.is_needed() && !self.home.join(".auxin").join(DECLINED_MARKER).exists()
+    }
+
+    /// Stop offering the migration on startup
+    pub fn decline_prompt(&self) -> Result<()> {
+        let dir = self.home.join(".auxin");
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(DECLINED_MARKER), "")?;
+        Ok(())
+    }
+
+    /// What `run` would do, without changing anything
+    pub fn plan(&self) -> Result<Vec<MigrationStep>> {
+        let mut steps = Vec::new();
+        for (legacy, target) in self.roots() {
+            for file in files_under(&legacy)? {
+                steps.push(self.step_for(&legacy, &target, &file)?);
+            }
+        }
+        Ok(steps)
+    }
+
+    /// Migrate every legacy directory, then archive it
+    pub fn run(&self) -> Result<MigrationReport> {
+        let mut report = MigrationReport::default();
+        for step in self.plan()? {
+            let result = match step.action {
+                MigrationAction::Skip => {
+                    report.skipped.push(step);
+                    continue;
+                },
+                MigrationAction::RewriteConfig => fs::read_to_string(&step.from)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|config| write_new(&step.to, &rewrite_config(&config))),
+                MigrationAction::ConvertComments => merge_comments(&step.from, &step.to),
+                MigrationAction::Copy => copy_new(&step.from, &step.to),
+            };
+            match result {
+                Ok(()) => report.migrated.push(step),
+                Err(e) => report.failed.push((step.from, e.to_string())),
+            }
+        }
+
+        for legacy in self.legacy_dirs() {
+            let archive = archive_path(&legacy);
+            fs::rename(&legacy, &archive)
+                .with_context(|| format!("Failed to rename {}", legacy.display()))?;
+            report.archived.push(archive);
+        }
+        Ok(report)
+    }
+
+    /// Existing legacy directories and where their contents go
+    fn roots(&self) -> Vec<(PathBuf, PathBuf)> {
+        let mut roots = vec![(self.home.join(LEGACY_DIR), self.home.join(".auxin"))];
+        if let Some(project) = &self.project {
+            if project != &self.home {
+                roots.push((project.join(LEGACY_DIR), project.join(".auxin")));
+            }
+        }
+        roots.retain(|(legacy, _)| legacy.is_dir());
+        roots
+    }
+
+    fn step_for(&self, legacy: &Path, target: &Path, file: &Path) -> Result<MigrationStep> {
+        let relative = file.strip_prefix(legacy).unwrap_or(file);
+        let is_home = legacy.parent() == Some(self.home.as_path());
+
+        let (to, action) = if is_home && relative == Path::new("config.toml") {
+            (target.join(relative), MigrationAction::RewriteConfig)
+        } else if let (false, Ok(comment_file)) = (is_home, relative.strip_prefix("comments")) {
+            let project = legacy.parent().unwrap_or(legacy);
+            let to = project.join(".oxen").join("comments").join(comment_file);
+            // Comment files are merged, so an existing one isn't a conflict
+            return Ok(MigrationStep {
+                from: file.to_path_buf(),
+                to,
+                action: MigrationAction::ConvertComments,
+            });
+        } else {
+            (target.join(relative), MigrationAction::Copy)
+        };
+
+        let action = if to.exists() && !same_contents(file, &to)? {
+            MigrationAction::Skip
+        } else {
+            action
+        };
+        Ok(MigrationStep {
+            from: file.to_path_buf(),
+            to,
+            action,
+        })
+    }
+}
+
+/// Rewrite legacy paths and environment variable names in a config file
+pub fn rewrite_config(config: &str) -> String {
+    config
+        .replace(&format!("{}/", LEGACY_DIR), ".auxin/")
+        .replace("OXENVCS_", "AUXIN_")
+}
+
+/// Parse a comment file in either the current format (a list of comments)
+/// or the legacy one (a single comment, `timestamp`/`user`/`message` fields)
+pub fn parse_comments(contents: &str, commit_id: &str) -> Result<Vec<Comment>> {
+    let value: Value = serde_json::from_str(contents)?;
+    let entries = match value {
+        Value::Array(entries) => entries,
+        single => vec![single],
+    };
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut entry)| {
+            if let Value::Object(fields) = &mut entry {
+                for (legacy, current) in [("user", "author"), ("message", "text")] {
+                    if let Some(v) = fields.remove(legacy) {
+                        fields.entry(current).or_insert(v);
+                    }
+                }
+                if let Some(seconds) = fields.remove("timestamp").and_then(|t| t.as_i64()) {
+                    if let Some(time) = Utc.timestamp_opt(seconds, 0).single() {
+                        fields
+                            .entry("created_at")
+                            .or_insert(Value::String(time.to_rfc3339()));
+                    }
+                }
+                fields
+                    .entry("commit_id")
+                    .or_insert(Value::String(commit_id.to_string()));
+                fields
+                    .entry("id")
+                    .or_insert(Value::String(format!("{}-{}", commit_id, index)));
+            }
+            serde_json::from_value(entry).context("Unrecognized comment format")
+        })
+        .collect()
+}
+
+fn merge_comments(from: &Path, to: &Path) -> Result<()> {
+    let commit_id = from
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let legacy = parse_comments(&fs::read_to_string(from)?, &commit_id)?;
+
+    let mut comments = if to.exists() {
+        parse_comments(&fs::read_to_string(to)?, &commit_id)?
+    } else {
+        Vec::new()
+    };
+    for comment in legacy {
+        if !comments.iter().any(|c| c.id == comment.id) {
+            comments.push(comment);
+        }
+    }
+    comments.sort_by_key(|c| c.created_at);
+
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(to, serde_json::to_string_pretty(&comments)?)?;
+    Ok(())
+}
+
+fn write_new(to: &Path, contents: &str) -> Result<()> {
+    if to.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(to, contents)?;
+    Ok(())
+}
+
+fn copy_new(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(from, to)?;
+    Ok(())
+}
+
+fn same_contents(a: &Path, b: &Path) -> Result<bool> {
+    Ok(b.is_file() && fs::read(a)? == fs::read(b)?)
+}
+
+/// `.oxenvcs.migrated`, or `.oxenvcs.migrated.N` if that's taken
+fn archive_path(legacy: &Path) -> PathBuf {
+    let base = format!("{}{}", legacy.display(), MIGRATED_SUFFIX);
+    let mut archive = PathBuf::from(&base);
+    let mut n = 1;
+    while archive.exists() {
+        archive = PathBuf::from(format!("{}.{}", base, n));
+        n += 1;
+    }
+    archive
+}
+
+fn files_under(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_under(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_legacy_and_current_comments() {
+        let legacy = r#"{"user": "alice", "message": "Love the chorus", "timestamp": 1700000000}"#;
+        let comments = parse_comments(legacy, "abc123").unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author, "alice");
+        assert_eq!(comments[0].text, "Love the chorus");
+        assert_eq!(comments[0].commit_id, "abc123");
+        assert_eq!(comments[0].created_at.timestamp(), 1_700_000_000);
+
+        let current = serde_json::to_string(&comments).unwrap();
+        let reparsed = parse_comments(&current, "abc123").unwrap();
+        assert_eq!(reparsed[0].id, comments[0].id);
+        assert_eq!(reparsed[0].created_at, comments[0].created_at);
+        assert!(parse_comments(r#"{"user": "bob"}"#, "abc123").is_err());
+    }
+
+    #[test]
+    fn test_migrate_home_and_project() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        let project = temp.path().join("Song.logicx");
+
+        let legacy_home = home.join(LEGACY_DIR);
+        fs::create_dir_all(legacy_home.join("queue")).unwrap();
+        fs::write(
+            legacy_home.join("config.toml"),
+            "[queue]\nqueue_dir = \"~/.oxenvcs/queue\"\n",
+        )
+        .unwrap();
+        fs::write(legacy_home.join("queue").join("op1.json"), "{}").unwrap();
+
+        let legacy_project = project.join(LEGACY_DIR);
+        fs::create_dir_all(legacy_project.join("comments")).unwrap();
+        fs::create_dir_all(legacy_project.join("bounces")).unwrap();
+        fs::write(
+            legacy_project.join("comments").join("abc123.json"),
+            r#"{"user": "alice", "message": "Nice", "timestamp": 1700000000}"#,
+        )
+        .unwrap();
+        fs::write(legacy_project.join("bounces").join("abc123.wav"), "old").unwrap();
+        fs::create_dir_all(project.join(".auxin").join("bounces")).unwrap();
+        fs::write(project.join(".auxin/bounces/abc123.wav"), "new").unwrap();
+
+        let migration = LegacyMigration::new(&home, Some(project.clone()));
+        assert!(migration.is_needed());
+        assert!(migration.should_prompt());
+
+        let report = migration.run().unwrap();
+        assert_eq!(report.migrated.len(), 3);
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.failed.is_empty());
+        assert_eq!(report.archived.len(), 2);
+
+        let config = fs::read_to_string(home.join(".auxin/config.toml")).unwrap();
+        assert!(config.contains("~/.auxin/queue"));
+        assert!(home.join(".auxin/queue/op1.json").exists());
+        let comments = fs::read_to_string(project.join(".oxen/comments/abc123.json")).unwrap();
+        assert_eq!(
+            parse_comments(&comments, "abc123").unwrap()[0].author,
+            "alice"
+        );
+        // The existing bounce wins; the legacy one stays in the archive
+        assert_eq!(
+            fs::read_to_string(project.join(".auxin/bounces/abc123.wav")).unwrap(),
+            "new"
+        );
+        assert!(project
+            .join(".oxenvcs.migrated/bounces/abc123.wav")
+            .exists());
+
+        assert!(!migration.is_needed());
+        assert!(migration.run().unwrap().archived.is_empty());
+    }
+
+    #[test]
+    fn test_decline_prompt() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(LEGACY_DIR)).unwrap();
+        let migration = LegacyMigration::new(temp.path(), None);
+        assert!(migration.should_prompt());
+        migration.decline_prompt().unwrap();
+        assert!(!migration.should_prompt());
+        assert!(migration.is_needed());
+    }
+}
@@ -9,46 +9,118 @@ pub use auxin_oxen::{error, info, success, vlog, warn};
 pub use auxin_oxen::logger;
 pub use auxin_oxen as oxen_subprocess_crate;
 
+pub mod adobe_project;
+pub mod aliases;
+pub mod anomaly;
+pub mod archive;
 pub mod auth;
+pub mod audit_sink;
+pub mod auto_title;
+pub mod author_map;
 pub mod backup_recovery;
+pub mod badge_state;
 pub mod blender_metadata;
+pub mod blender_parser;
 pub mod blender_project;
 pub mod bounce;
+pub mod branch;
+pub mod bwf;
+pub mod chunked_download;
 pub mod chunked_upload;
 pub mod collaboration;
+pub mod commit_link;
 pub mod commit_metadata;
+pub mod commit_template;
+pub mod config_edit;
 pub mod conflict_detection;
 pub mod console;
+pub mod crash_report;
+pub mod credential_store;
 pub mod daemon_client;
+pub mod daemon_hooks;
+pub mod daemon_log;
+pub mod delivery;
+pub mod describe;
+pub mod devtools;
+pub mod doctor;
 pub mod draft_manager;
+pub mod dual_stack;
+pub mod evolution;
+pub mod fork;
+pub mod history_export;
 pub mod hooks;
+pub mod identity;
 pub mod ignore_template;
+pub mod lan_sync;
+pub mod legacy_migration;
 pub mod lock_integration;
 pub mod logic_parser;
 pub mod logic_project;
+pub mod loudness;
+pub mod meta_branch;
+pub mod metadata_backfill;
 pub mod metadata_diff;
+pub mod metadata_edits;
+pub mod metadata_store;
+pub mod music_theory;
 pub mod network_resilience;
 pub mod offline_queue;
 pub mod operation_history;
+pub mod operation_lock;
+pub mod osc_trigger;
 pub mod oxen_backend;
 pub mod oxen_ops;
 pub mod progress;
+pub mod project_handler;
+pub mod prompt;
+pub mod quarantine;
+pub mod quick_actions;
 pub mod remote_lock;
+pub mod rename_tracking;
+pub mod reports;
+pub mod repo_migration;
+pub mod restore_verify;
 pub mod search;
+pub mod search_actions;
 pub mod server_client;
+pub mod server_discovery;
+pub mod server_init;
+pub mod session_sheet;
 pub mod sketchup_metadata;
+pub mod sketchup_parser;
 pub mod sketchup_project;
+pub mod spotlight;
+pub mod stage_policy;
+pub mod staging_preview;
+pub mod state_migration;
+pub mod stems;
+pub mod sync_status;
 pub mod thumbnail;
+pub mod tree_manifest;
+pub mod unreal_project;
+pub mod upgrade_check;
+pub mod url_scheme;
+pub mod versioning;
+pub mod watermark;
 pub mod workflow_automation;
+pub mod workspace;
 pub mod write_ahead_log;
+pub mod wrapup;
 
+pub use adobe_project::{AfterEffectsProject, PremiereProject};
 pub use auth::{AuthManager, Credentials};
+pub use author_map::AuthorMap;
 pub use backup_recovery::{BackupRecoveryManager, RecoveryHelper, Snapshot, SnapshotType};
 pub use blender_metadata::BlenderMetadata;
+pub use blender_parser::{BlendFileInfo, BlenderParser};
 pub use blender_project::BlenderProject;
 pub use bounce::{
     AudioFormat, BounceComparison, BounceFilter, BounceManager, BounceMetadata, NullTestResult,
 };
+pub use chunked_download::{
+    ChunkedDownloadManager, DownloadConfig, DownloadProgress, DownloadResult, DownloadSession,
+    DownloadSessionInfo, DownloadStatus,
+};
 pub use chunked_upload::{
     ChunkedUploadManager, UploadConfig, UploadProgress, UploadResult, UploadSession,
     UploadSessionInfo, UploadStatus,
@@ -57,23 +129,32 @@ pub use collaboration::{
     Activity, ActivityFeed, ActivityType, Comment, CommentManager, TeamManager, TeamMember,
 };
 pub use commit_metadata::CommitMetadata;
-pub use conflict_detection::{ConflictCheckResult, ConflictDetector, ConflictRecommendation};
+pub use conflict_detection::{
+    ConflictCheckResult, ConflictChoice, ConflictDetector, ConflictRecommendation,
+    ConflictResolver,
+};
 pub use console::{Console, ConsoleMode, DaemonStatus, LogEntry, LogLevel, RepositoryStatus};
+pub use crash_report::CrashReporter;
 pub use draft_manager::{DraftManager, DraftStats};
 pub use ignore_template::{
-    generate_blender_oxenignore, generate_oxenignore, generate_sketchup_oxenignore,
+    generate_after_effects_oxenignore, generate_blender_oxenignore, generate_oxenignore,
+    generate_premiere_oxenignore, generate_sketchup_oxenignore,
 };
 pub use logic_parser::{LogicParser, LogicProjectData};
 pub use logic_project::LogicProject;
+pub use metadata_backfill::{BackfillPlan, BackfillState};
 pub use metadata_diff::{MetadataDiff, MetadataDiffer, ReportGenerator};
+pub use metadata_store::{MetadataStore, StoredMetadata};
 pub use network_resilience::{
     check_network_availability, check_network_health, estimate_transfer_time, is_transient_error,
-    AdaptiveRetryPolicy, CircuitBreaker, CircuitBreakerStats, CircuitState, ConnectivityState,
-    ErrorKind, NetworkHealth, NetworkHealthMonitor, NetworkQuality, NetworkResilienceManager,
-    OperationData, OperationType, QueuedOperation, RetryPolicy, RetryableError,
+    AdaptiveRetryPolicy, CircuitBreaker, CircuitBreakerStats, CircuitState, ConnectivityProbe,
+    ConnectivityState, ErrorKind, NetworkHealth, NetworkHealthMonitor, NetworkQuality,
+    NetworkResilienceManager, OperationData, OperationType, QueuedOperation, RetryPolicy,
+    RetryableError,
 };
 pub use offline_queue::{
-    OfflineQueue, QueueEntry, QueueStats, QueuedOperation as OfflineQueuedOperation, SyncReport,
+    OfflineQueue, QosClass, QueueEntry, QueueStats, QueuedOperation as OfflineQueuedOperation,
+    SyncPolicy, SyncReport,
 };
 pub use operation_history::{
     HistoryOperation, OperationHistoryEntry, OperationHistoryManager, OperationResult,
@@ -83,18 +164,23 @@ pub use oxen_backend::{
     create_backend, create_default_backend, BackendType, OxenBackend, SubprocessBackend,
 };
 pub use oxen_ops::OxenRepository;
+pub use project_handler::{ProjectHandler, ProjectRegistry};
 // Re-export types from auxin-oxen crate
 pub use auxin_oxen::{
-    BranchInfo, CommitInfo, CommitInfo as SubprocessCommitInfo, OxenConfig, OxenError,
-    OxenSubprocess, StatusInfo,
+    BranchInfo, CommitInfo, CommitInfo as SubprocessCommitInfo, FileChange, FileChangeKind,
+    OxenConfig, OxenError, OxenSubprocess, ProgressEvent, StatusInfo,
 };
 pub use remote_lock::{RemoteLock, RemoteLockManager};
+pub use repo_migration::{MigrationPlan, MigrationSource, MigrationTarget, RepoMigrator};
 pub use server_client::{
-    AuxinServerClient, LockHolder, LockInfo, LogicProMetadata as ServerMetadata, ServerConfig,
+    ApiCompatibility, AuxinServerClient, LockHolder, LockInfo, LogicProMetadata as ServerMetadata,
+    MetadataBatchEntry, MetadataBatchReport, ServerConfig,
 };
 pub use sketchup_metadata::SketchUpMetadata;
+pub use sketchup_parser::{SketchUpModelInfo, SketchUpParser};
 pub use sketchup_project::SketchUpProject;
 pub use thumbnail::{ThumbnailDiff, ThumbnailManager, ThumbnailMetadata};
+pub use unreal_project::UnrealProject;
 pub use workflow_automation::{WorkflowAutomation, WorkflowConfig};
 pub use write_ahead_log::{
     RecoveryReport, WalEntry, WalOperation, WalRecoveryManager, WalStats, WalStatus, WriteAheadLog,
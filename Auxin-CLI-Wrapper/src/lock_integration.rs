@@ -3,23 +3,18 @@
 /// This module provides helper functions to integrate RemoteLockManager
 /// with the CLI commands, handling user feedback and error presentation.
 use crate::progress;
+use crate::quick_actions::{self, LockState};
 use crate::remote_lock::RemoteLockManager;
+use crate::server_client::{self, AuxinServerClient, ServerConfig};
 use anyhow::Result;
+use auxin_config::{Config, LockRelease};
+use auxin_oxen::OxenSubprocess;
 use colored::Colorize;
 use std::path::Path;
 
 /// Get user identifier for lock operations
 pub fn get_user_identifier() -> String {
-    let username = std::env::var("USER")
-        .or_else(|_| std::env::var("USERNAME"))
-        .unwrap_or_else(|_| "unknown".to_string());
-
-    let hostname = hostname::get()
-        .ok()
-        .and_then(|h| h.into_string().ok())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    format!("{}@{}", username, hostname)
+    crate::identity::Identity::current().user()
 }
 
 /// Handle lock acquisition with UI feedback
@@ -104,6 +99,58 @@ pub fn handle_lock_release(repo_path: &Path) -> Result<()> {
     }
 }
 
+/// Release the current user's lock after a successful commit or push, when
+/// `[lock] release_on` names `after`
+///
+/// Uncommitted changes keep the lock, since whoever takes it next wouldn't
+/// have them. Returns whether the lock was released.
+pub fn auto_release(repo_path: &Path, after: LockRelease) -> Result<bool> {
+    let config = Config::load().unwrap_or_default();
+    if after == LockRelease::Never
+        || config.lock.release_on != after
+        || quick_actions::lock_state(repo_path) != LockState::Mine
+    {
+        return Ok(false);
+    }
+
+    let status = OxenSubprocess::new().status(repo_path)?;
+    if !status.modified.is_empty() || !status.staged.is_empty() {
+        progress::warning("Keeping the lock: there are uncommitted changes");
+        return Ok(false);
+    }
+
+    // Server locks when enabled and reachable, like `auxin lock release`
+    if config.cli.use_server_locks {
+        if let Ok(client) = AuxinServerClient::new(ServerConfig::from_config(&config)) {
+            let namespace = &config.cli.default_namespace;
+            let repo_name = repo_path
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            let Some(lock) = client.get_lock_status(namespace, &repo_name)?.lock else {
+                return Ok(false);
+            };
+            client.release_lock(
+                namespace,
+                &repo_name,
+                &lock,
+                &server_client::get_user_identifier(),
+                &server_client::get_machine_id(),
+            )?;
+            return Ok(true);
+        }
+    }
+
+    let manager = RemoteLockManager::new();
+    match manager.get_lock(repo_path)? {
+        Some(lock) => {
+            manager.release_lock(repo_path, &lock.lock_id)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 /// Handle lock status check with UI feedback
 pub fn handle_lock_status(repo_path: &Path) -> Result<()> {
     let manager = RemoteLockManager::new();
@@ -256,6 +303,18 @@ mod tests {
         assert_eq!(truncate("exact", 5), "exact");
     }
 
+    #[test]
+    fn test_auto_release_setting() {
+        let config: Config = toml::from_str("[lock]\nrelease_on = \"push\"\n").unwrap();
+        assert_eq!(config.lock.release_on, LockRelease::Push);
+        assert_eq!(Config::default().lock.release_on, LockRelease::Never);
+        assert!(toml::from_str::<Config>("[lock]\nrelease_on = \"sometimes\"\n").is_err());
+
+        // Nothing to do for a setting that never releases
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(!auto_release(temp_dir.path(), LockRelease::Never).unwrap());
+    }
+
     // Note: The following tests require a real Oxen repository for full integration testing.
     // For unit testing without Oxen, we test the logic flow and error handling.
 
@@ -4,8 +4,9 @@
 // This requires reverse engineering of the proprietary format.
 
 use super::project_data::*;
+use crate::rename_tracking::RenameLog;
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Parse a Logic Pro .logicx project file
 pub fn parse_logic_project(path: &Path) -> Result<LogicProjectData> {
@@ -25,18 +26,25 @@ pub fn parse_logic_project(path: &Path) -> Result<LogicProjectData> {
     }
 
     // 3. Locate ProjectData binary
-    let project_data_path = alternatives_dir.join("001").join("ProjectData");
+    let project_data_path =
+        find_project_data(path).context("ProjectData not found in package")?;
 
-    if !project_data_path.exists() {
-        // Try alternative location
-        let alt_path = alternatives_dir.join("000").join("ProjectData");
-        if alt_path.exists() {
-            return parse_project_data_file(&alt_path, path);
-        }
-        anyhow::bail!("ProjectData not found in package");
+    parse_project_data_file(&project_data_path, path)
+}
+
+/// Locate the ProjectData binary: `Alternatives/001`, then `Alternatives/000`,
+/// then wherever `auxin mv` recorded either of them being moved to
+pub fn find_project_data(path: &Path) -> Option<PathBuf> {
+    let standard = ["Alternatives/001/ProjectData", "Alternatives/000/ProjectData"];
+    if let Some(found) = standard.iter().map(|p| path.join(p)).find(|p| p.exists()) {
+        return Some(found);
     }
 
-    parse_project_data_file(&project_data_path, path)
+    let renames = RenameLog::load(path).ok()?;
+    standard
+        .iter()
+        .map(|p| path.join(renames.current_path(p)))
+        .find(|p| p.exists())
 }
 
 fn parse_project_data_file(
@@ -27,11 +27,8 @@ impl LogicParser {
         }
 
         // Check for required structure
-        let alternatives = project_path.join("Alternatives");
-        let project_data = alternatives.join("001").join("ProjectData");
-
-        alternatives.exists()
-            && (project_data.exists() || alternatives.join("000").join("ProjectData").exists())
+        project_path.join("Alternatives").exists()
+            && binary_parser::find_project_data(project_path).is_some()
     }
 
     /// Get Logic Pro version without full parsing
@@ -91,4 +88,23 @@ mod tests {
         let invalid_path = temp_dir.path().join("NotAProject.logicx");
         assert!(!LogicParser::is_valid_project(&invalid_path));
     }
+
+    #[test]
+    fn test_moved_alternative_is_found() {
+        use crate::rename_tracking::RenameLog;
+
+        let (_temp, project_path) = create_mock_project();
+        let alternatives = project_path.join("Alternatives");
+        fs::rename(alternatives.join("001"), alternatives.join("002")).unwrap();
+        assert!(!LogicParser::is_valid_project(&project_path));
+
+        let mut renames = RenameLog::default();
+        renames.record("Alternatives/001", "Alternatives/002", Some("c1"));
+        renames.save(&project_path).unwrap();
+        assert!(LogicParser::is_valid_project(&project_path));
+        assert_eq!(
+            binary_parser::find_project_data(&project_path),
+            Some(alternatives.join("002/ProjectData"))
+        );
+    }
 }
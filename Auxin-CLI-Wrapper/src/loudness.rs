@@ -0,0 +1,235 @@
+//! Loudness compliance of bounces: `auxin bounce check`
+//!
+//! Measures a bounce's integrated loudness (LUFS), true peak (dBTP) and
+//! loudness range with ffmpeg's EBU R128 `loudnorm` analysis and checks
+//! them against a delivery target:
+//!
+//! | Target               | Integrated        | True peak  |
+//! |----------------------|-------------------|------------|
+//! | `spotify`            | -14 LUFS ± 1 LU   | ≤ -1 dBTP  |
+//! | `apple-music`        | -16 LUFS ± 1 LU   | ≤ -1 dBTP  |
+//! | `youtube`            | -14 LUFS ± 1 LU   | ≤ -1 dBTP  |
+//! | `broadcast-ebu-r128` | -23 LUFS ± 0.5 LU | ≤ -1 dBTP  |
+//!
+//! A failed check suggests the gain that would meet the target, held back
+//! where the true peak leaves no headroom. The `check-loudness` pre-deliver
+//! hook runs the check before `auxin deliver` records a delivery.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+/// A delivery target's loudness requirements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoudnessTarget {
+    Spotify,
+    AppleMusic,
+    Youtube,
+    BroadcastEbuR128,
+}
+
+impl LoudnessTarget {
+    pub const ALL: [LoudnessTarget; 4] = [
+        LoudnessTarget::Spotify,
+        LoudnessTarget::AppleMusic,
+        LoudnessTarget::Youtube,
+        LoudnessTarget::BroadcastEbuR128,
+    ];
+
+    /// Name used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoudnessTarget::Spotify => "spotify",
+            LoudnessTarget::AppleMusic => "apple-music",
+            LoudnessTarget::Youtube => "youtube",
+            LoudnessTarget::BroadcastEbuR128 => "broadcast-ebu-r128",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    /// Target integrated loudness in LUFS
+    pub fn integrated_lufs(&self) -> f64 {
+        match self {
+            LoudnessTarget::Spotify | LoudnessTarget::Youtube => -14.0,
+            LoudnessTarget::AppleMusic => -16.0,
+            LoudnessTarget::BroadcastEbuR128 => -23.0,
+        }
+    }
+
+    /// How far integrated loudness may be from the target, in LU
+    pub fn tolerance_lu(&self) -> f64 {
+        match self {
+            LoudnessTarget::BroadcastEbuR128 => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Highest allowed true peak in dBTP
+    pub fn max_true_peak_dbtp(&self) -> f64 {
+        -1.0
+    }
+}
+
+impl fmt::Display for LoudnessTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Measured loudness of an audio file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessMeasurement {
+    /// Integrated loudness in LUFS; `-inf` for silence
+    pub integrated_lufs: f64,
+    pub true_peak_dbtp: f64,
+    pub loudness_range_lu: f64,
+}
+
+/// Result of checking a measurement against a target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessReport {
+    pub target: LoudnessTarget,
+    pub measurement: LoudnessMeasurement,
+    pub integrated_ok: bool,
+    pub true_peak_ok: bool,
+    /// Gain that would bring the bounce into spec, in dB
+    pub suggested_gain_db: f64,
+    /// The suggested gain stops short of the loudness target because the
+    /// true peak would go over; reaching it needs limiting
+    pub needs_limiting: bool,
+}
+
+impl LoudnessReport {
+    pub fn check(target: LoudnessTarget, measurement: LoudnessMeasurement) -> Self {
+        let to_target = target.integrated_lufs() - measurement.integrated_lufs;
+        let headroom = target.max_true_peak_dbtp() - measurement.true_peak_dbtp;
+        let integrated_ok = to_target.abs() <= target.tolerance_lu();
+        let true_peak_ok = headroom >= 0.0;
+
+        let (suggested_gain_db, needs_limiting) = if !measurement.integrated_lufs.is_finite() {
+            (0.0, false)
+        } else if integrated_ok {
+            (headroom.min(0.0), false)
+        } else if to_target > headroom {
+            (headroom, true)
+        } else {
+            (to_target, false)
+        };
+
+        Self {
+            target,
+            measurement,
+            integrated_ok,
+            true_peak_ok,
+            suggested_gain_db: round_tenth(suggested_gain_db),
+            needs_limiting,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.integrated_ok && self.true_peak_ok
+    }
+}
+
+/// Measure a file's loudness with ffmpeg
+pub fn measure(path: &Path) -> Result<LoudnessMeasurement> {
+    let output = Command::new("ffmpeg")
+        .args(["-hide_banner", "-nostats", "-i"])
+        .arg(path)
+        .args(["-af", "loudnorm=print_format=json", "-f", "null", "-"])
+        .output()
+        .context("Failed to run ffmpeg. Install it with: brew install ffmpeg")?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        bail!("ffmpeg failed: {}", stderr.trim());
+    }
+    parse_loudnorm(&stderr)
+}
+
+/// Pull the measurement out of loudnorm's JSON summary, which ffmpeg
+/// prints last on stderr
+fn parse_loudnorm(stderr: &str) -> Result<LoudnessMeasurement> {
+    #[derive(Deserialize)]
+    struct Summary {
+        input_i: String,
+        input_tp: String,
+        input_lra: String,
+    }
+
+    let start = stderr
+        .rfind('{')
+        .ok_or_else(|| anyhow!("ffmpeg printed no loudness summary"))?;
+    let end = start
+        + stderr[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("ffmpeg printed no loudness summary"))?;
+    let summary: Summary = serde_json::from_str(&stderr[start..=end])
+        .context("Failed to parse ffmpeg loudness summary")?;
+    let value = |v: &str| -> Result<f64> {
+        v.trim()
+            .parse()
+            .with_context(|| format!("Unexpected loudness value: {}", v))
+    };
+
+    Ok(LoudnessMeasurement {
+        integrated_lufs: value(&summary.input_i)?,
+        true_peak_dbtp: value(&summary.input_tp)?,
+        loudness_range_lu: value(&summary.input_lra)?,
+    })
+}
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_check() {
+        let stderr = "[Parsed_loudnorm_0 @ 0x7f8] \n{\n\t\"input_i\" : \"-9.82\",\n\
+                      \t\"input_tp\" : \"0.35\",\n\t\"input_lra\" : \"4.10\",\n\
+                      \t\"input_thresh\" : \"-19.95\"\n}\n";
+        let loud = parse_loudnorm(stderr).unwrap();
+        assert_eq!(loud.integrated_lufs, -9.82);
+        assert_eq!(loud.true_peak_dbtp, 0.35);
+
+        let spotify = LoudnessReport::check(LoudnessTarget::Spotify, loud);
+        assert!(!spotify.passed());
+        assert_eq!(spotify.suggested_gain_db, -4.2);
+        assert!(!spotify.needs_limiting);
+
+        // Too quiet for Spotify, but turning it up would clip
+        let quiet = LoudnessMeasurement {
+            integrated_lufs: -20.0,
+            true_peak_dbtp: -3.0,
+            loudness_range_lu: 8.0,
+        };
+        let report = LoudnessReport::check(LoudnessTarget::Spotify, quiet);
+        assert_eq!(report.suggested_gain_db, 2.0);
+        assert!(report.needs_limiting);
+
+        let broadcast = LoudnessMeasurement {
+            integrated_lufs: -23.3,
+            true_peak_dbtp: -2.0,
+            loudness_range_lu: 8.0,
+        };
+        let report = LoudnessReport::check(LoudnessTarget::BroadcastEbuR128, broadcast);
+        assert!(report.passed());
+        assert_eq!(report.suggested_gain_db, 0.0);
+
+        assert_eq!(
+            LoudnessTarget::parse("Broadcast-EBU-R128"),
+            Some(LoudnessTarget::BroadcastEbuR128)
+        );
+        assert_eq!(LoudnessTarget::parse("tidal"), None);
+    }
+}
@@ -1,34 +1,19 @@
 use anyhow::Context;
 use auxin::{
-    lock_integration, logger, progress, server_client, success, vlog, warn, AuxinServerClient,
-    BlenderProject, BounceManager, CommitMetadata, LogicProject, OxenRepository,
-    OxenSubprocess, ServerConfig, SketchUpMetadata, SketchUpProject, ThumbnailManager,
+    aliases, legacy_migration, lock_integration, logger, metadata_backfill, progress,
+    repo_migration, server_client, state_migration, success, vlog, warn, AuthorMap,
+    AuxinServerClient, BackfillState, BlenderParser, BounceManager, CommitMetadata, CrashReporter,
+    LogicProject, MetadataStore, MigrationSource, MigrationTarget, OxenRepository, OxenSubprocess,
+    ProjectRegistry, RepoMigrator, ServerConfig, SketchUpMetadata, SketchUpParser, StoredMetadata,
+    ThumbnailManager,
 };
-use auxin_config::Config;
-use clap::{Parser, Subcommand};
+use auxin::sketchup_metadata::{ModelUnits, UnitSystem};
+use auxin_config::{Config, LockRelease};
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::Colorize;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, PartialEq)]
-enum ProjectType {
-    LogicPro,
-    SketchUp,
-    Blender,
-    Auto,
-}
-
-impl ProjectType {
-    fn parse(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "logicpro" | "logic" => Some(ProjectType::LogicPro),
-            "sketchup" | "skp" => Some(ProjectType::SketchUp),
-            "blender" | "blend" => Some(ProjectType::Blender),
-            "auto" => Some(ProjectType::Auto),
-            _ => None,
-        }
-    }
-}
-
 #[derive(Parser)]
 #[command(name = "auxin")]
 #[command(version)]
@@ -63,6 +48,15 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Run a command that would change an archived (read-only) project
+    #[arg(long, global = true)]
+    allow_archived: bool,
+
+    /// Print results as JSON (status, log, show, lock status, team, activity,
+    /// queue status, doctor and the commands with their own --json)
+    #[arg(long, global = true)]
+    json: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -83,7 +77,8 @@ DESCRIPTION:
     The lock includes:
       • Lock holder identification (username@hostname)
       • Timeout (default: 4 hours, prevents abandoned locks)
-      • Automatic release when lock holder commits
+      • Automatic release after the holder commits or pushes, if
+        [lock] release_on is set to \"commit\" or \"push\"
 
 OPTIONS:
     --timeout <HOURS>    Lock expiration time in hours (default: 4)
@@ -162,6 +157,81 @@ EXAMPLES:
         #[arg(long, help = "Confirm you want to force break the lock")]
         force: bool,
     },
+
+    /// Take over a lock whose holder's machine went offline
+    #[command(long_about = "Take over a lock whose holder's machine went offline
+
+USAGE:
+    auxin lock reclaim [--timeout <HOURS>]
+
+DESCRIPTION:
+    If a machine crashes or loses power while holding a lock, the lock stays
+    held until it expires, often hours later. The background daemon pings
+    the server every few minutes, so the server can tell when the holder's
+    machine has gone quiet. 'auxin lock status' flags such a lock as
+    probably orphaned.
+
+    Reclaiming releases the orphaned lock and acquires it for you in one
+    step. Unlike 'auxin lock break', it needs no admin rights and no --force,
+    and the server refuses it while the holder's daemon or lock heartbeat is
+    still active. The takeover is recorded in the project's activity.
+
+    Requires server locks (use_server_locks) and a daemon on the holder's
+    machine; without one, ask the holder or an admin to release the lock.
+
+OPTIONS:
+    --timeout <HOURS>    Lock expiration time in hours (default: 4)
+
+EXAMPLES:
+    # See whether the lock looks orphaned
+    auxin lock status
+
+    # Take it over
+    auxin lock reclaim")]
+    Reclaim {
+        #[arg(long, default_value = "4", help = "Lock timeout in hours")]
+        timeout: u64,
+    },
+
+    /// Show how long locks are held and who waits for them
+    #[command(long_about = "Show how long locks are held and who waits for them
+
+USAGE:
+    auxin lock stats [--days <DAYS>] [--all [--namespace <NAMESPACE>]]
+
+DESCRIPTION:
+    Reports lock usage from the server's activity log, to help a team decide
+    on lock timeouts or whether to split a project:
+      • Hold times: mean, median (p50), p90, p99 and longest
+      • Contention: how often someone asked for a lock that was held
+      • Expiries: locks that ran out instead of being released
+      • Per user: holds, how often they were blocked and how often they
+        blocked someone else
+
+    Without --all the stats cover the current project; with --all they cover
+    every project on the server you can read, most contended first.
+
+OPTIONS:
+    --days <DAYS>              Days of history to include (default: 30)
+    --all                      Every project you can read
+    --namespace <NAMESPACE>    With --all, only projects in this namespace
+
+EXAMPLES:
+    # This project over the last 30 days
+    auxin lock stats
+
+    # The most contended projects in a namespace this quarter
+    auxin lock stats --all --namespace studio --days 90")]
+    Stats {
+        #[arg(long, help = "Every project on the server you can read")]
+        all: bool,
+
+        #[arg(long, default_value_t = 30, help = "Days of history to include")]
+        days: i64,
+
+        #[arg(long, requires = "all", help = "With --all, only this namespace")]
+        namespace: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -247,24 +317,222 @@ EXAMPLES:
     #[command(long_about = "Show daemon logs
 
 USAGE:
-    auxin daemon logs [--lines <N>]
+    auxin daemon logs [--lines <N>] [--level <LEVEL>] [--since <TIME>]
+                      [--project <PATH>] [--grep <PATTERN>]
 
 DESCRIPTION:
     Displays recent entries from the daemon log file.
     Useful for debugging and monitoring daemon activity.
 
+    Commands the daemon runs log structured entries with a time, level
+    and project; the filters below apply to those. The daemon's own
+    plain-text lines have no time or project, so --since and --project
+    leave them out.
+
 OPTIONS:
-    --lines <N>    Number of recent log lines to show (default: 50)
+    --lines <N>          Number of matching entries to show (default: 50)
+    --level <LEVEL>      Lowest level to show: debug, info, warn, error
+    --since <TIME>       Entries newer than an age (30m, 2h, 7d), a date
+                         (2024-05-01) or an RFC 3339 time
+    --project <PATH>     Entries for one project (path or folder name)
+    --grep <PATTERN>     Entries whose message matches a regular expression
 
 EXAMPLES:
     # Show last 50 log lines
     auxin daemon logs
 
     # Show last 100 log lines
-    auxin daemon logs --lines 100")]
+    auxin daemon logs --lines 100
+
+    # Warnings and errors from the last day for one project
+    auxin daemon logs --level warn --since 1d --project MySong.logicx
+
+    # Failed pushes
+    auxin daemon logs --grep 'push.*failed'")]
     Logs {
         #[arg(long, default_value = "50", help = "Number of log lines to show")]
         lines: usize,
+
+        #[arg(long, value_name = "LEVEL", help = "Lowest level to show (debug, info, warn, error)")]
+        level: Option<String>,
+
+        #[arg(long, value_name = "TIME", help = "Only entries since an age (2h, 7d) or date")]
+        since: Option<String>,
+
+        #[arg(long, value_name = "PATH", help = "Only entries for this project")]
+        project: Option<String>,
+
+        #[arg(long, value_name = "PATTERN", help = "Only entries matching this regular expression")]
+        grep: Option<String>,
+    },
+
+    /// Check that the daemon is responding
+    #[command(long_about = "Check that the daemon is responding
+
+USAGE:
+    auxin daemon health [--json]
+
+DESCRIPTION:
+    Asks the running daemon for its health over ~/.auxin/daemon.sock and
+    shows, for every monitored project, whether its file system watcher
+    is running and when it last saw a change.
+
+    Exits with status 1 when the daemon doesn't answer within
+    [daemon] health_timeout_secs (default 5) or a watcher has stopped.
+
+EXAMPLES:
+    auxin daemon health
+    auxin daemon health --json")]
+    Health {
+        #[arg(long, help = "Print the daemon's reply as JSON")]
+        json: bool,
+    },
+
+    /// Restart the daemon when it stops responding
+    #[command(long_about = "Restart the daemon when it stops responding
+
+USAGE:
+    auxin daemon watchdog [--interval <SECS>] [--once]
+
+DESCRIPTION:
+    Checks the daemon's health every [daemon] watchdog_interval_secs
+    (default 60). A daemon that is running but doesn't answer, or whose
+    file system watchers have stopped, is restarted, and the restart is
+    recorded in the operation history ('auxin history').
+
+    A stopped daemon is left alone, since it may have been stopped on
+    purpose; launchd restarts one that crashes. Daemons too old to answer
+    health checks are skipped.
+
+OPTIONS:
+    --interval <SECS>    Seconds between checks
+    --once               Check once and exit, for cron or a LaunchAgent
+
+EXAMPLES:
+    # Keep watching in a terminal or a login item
+    auxin daemon watchdog
+
+    # Check every 5 minutes from cron
+    */5 * * * * auxin daemon watchdog --once")]
+    Watchdog {
+        #[arg(long, value_name = "SECS", help = "Seconds between checks")]
+        interval: Option<u64>,
+
+        #[arg(long, help = "Check once and exit")]
+        once: bool,
+    },
+
+    /// Tell the server this machine's daemon is running (used by the daemon)
+    #[command(long_about = "Tell the server this machine's daemon is running (used by the daemon)
+
+USAGE:
+    auxin daemon ping
+
+DESCRIPTION:
+    The daemon runs this every few minutes while server locks are enabled.
+    The server records when each machine was last seen, so a lock held by a
+    machine that crashed or lost power can be flagged as probably orphaned
+    and taken over with 'auxin lock reclaim'.
+
+    Does nothing unless server locks (use_server_locks) are enabled.
+
+EXAMPLES:
+    # Check that pings reach the server
+    auxin daemon ping")]
+    Ping,
+
+    /// Manage scripts that run on daemon events
+    #[command(long_about = "Manage scripts that run on daemon events
+
+USAGE:
+    auxin daemon hooks add <EVENT> <SCRIPT> [--name <NAME>] [--project <PATH>]
+    auxin daemon hooks list
+    auxin daemon hooks remove <NAME>
+    auxin daemon hooks fire <EVENT> [--project <PATH>] [--set KEY=VALUE]...
+
+DESCRIPTION:
+    Daemon hooks run your own scripts when the background daemon does
+    something on its own. Unlike repository hooks ('auxin hooks'), they are
+    registered once per user in ~/.auxin/daemon_hooks.json and can apply to
+    every project or just one.
+
+    Events:
+      • auto-commit-created  The daemon auto-committed a project
+      • lock-expiring        A lock held on this machine expires soon
+      • push-failed          Syncing queued operations to the server failed
+      • auto-commit-paused   Auto-commits stopped after a suspicious change
+
+    Scripts run with stdin closed, inside the project directory, and are
+    killed after their timeout. They receive AUXIN_EVENT, AUXIN_PROJECT_PATH
+    and the event details (AUXIN_COMMIT_ID, AUXIN_MESSAGE, AUXIN_LOCKED_BY,
+    AUXIN_EXPIRES_AT, AUXIN_ERROR, AUXIN_REASON).
+
+    'fire' is what the daemon runs; use it to try out a hook by hand.
+
+EXAMPLES:
+    # Flash the studio lights after every auto-commit
+    auxin daemon hooks add auto-commit-created ~/scripts/lights.sh
+
+    # Send an OSC cue for one project only
+    auxin daemon hooks add lock-expiring ~/scripts/osc-cue.py --project Song.logicx
+
+    # Try a hook out
+    auxin daemon hooks fire push-failed --set error=\"server unreachable\"")]
+    #[command(subcommand)]
+    Hooks(DaemonHookCommands),
+}
+
+#[derive(Subcommand)]
+enum DaemonHookCommands {
+    /// Register a script for a daemon event
+    Add {
+        #[arg(
+            value_name = "EVENT",
+            help = "auto-commit-created, lock-expiring, push-failed or auto-commit-paused"
+        )]
+        event: String,
+
+        #[arg(value_name = "SCRIPT", help = "Executable script to run")]
+        script: PathBuf,
+
+        #[arg(long, help = "Hook name (default: script file name)")]
+        name: Option<String>,
+
+        #[arg(long, value_name = "PATH", help = "Only run for this project")]
+        project: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            default_value_t = auxin::daemon_hooks::DEFAULT_DAEMON_HOOK_TIMEOUT_SECS,
+            help = "Kill the script after this many seconds"
+        )]
+        timeout: u64,
+    },
+
+    /// List registered daemon hooks
+    List,
+
+    /// Remove a daemon hook
+    Remove {
+        #[arg(value_name = "NAME", help = "Name of hook to remove")]
+        name: String,
+    },
+
+    /// Run the hooks for an event (used by the daemon)
+    Fire {
+        #[arg(value_name = "EVENT", help = "Event that occurred")]
+        event: String,
+
+        #[arg(long, value_name = "PATH", help = "Project the event concerns")]
+        project: Option<PathBuf>,
+
+        #[arg(
+            long = "set",
+            value_name = "KEY=VALUE",
+            help = "Event detail passed as AUXIN_<KEY>"
+        )]
+        details: Vec<String>,
     },
 }
 
@@ -340,6 +608,35 @@ EXAMPLES:
     # Test authentication
     auxin auth test")]
     Test,
+
+    /// Show the name locks, comments and activity record for you
+    #[command(long_about = "Show the name locks, comments and activity record for you
+
+USAGE:
+    auxin auth whoami [--link]
+
+DESCRIPTION:
+    Locks, comments and activity record you as name@machine. The name is
+    [identity] name from ~/.auxin/config.toml, else your Oxen Hub username
+    from 'auxin auth login', else your login name.
+
+    Names you used before (your login name, [identity] aliases) still count
+    as you, so locks taken under them are yours to release.
+
+    --link tells the auxin server which account the name belongs to, so its
+    audit log and lock stats show your account. The daemon's presence pings
+    link the name too.
+
+EXAMPLES:
+    # Show your identity
+    auxin auth whoami
+
+    # Link it to your server account
+    auxin auth whoami --link")]
+    Whoami {
+        #[arg(long, help = "Link the name to your auxin server account")]
+        link: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -354,6 +651,7 @@ DESCRIPTION:
     Displays the current server configuration including:
       • Server URL
       • Connection status (healthy/unreachable)
+      • Negotiated API version
       • Whether server locks are enabled
       • Whether server metadata storage is enabled
       • Default namespace
@@ -390,6 +688,7 @@ DESCRIPTION:
     Updates the server configuration in .auxin/config.toml.
     Available keys:
       • url          - Server URL (e.g., http://localhost:3000)
+      • token        - Server token, kept in the system keychain for the URL
       • namespace    - Default namespace for repositories
       • timeout      - Request timeout in seconds
       • locks        - Enable/disable server locks (true/false)
@@ -399,6 +698,9 @@ EXAMPLES:
     # Set server URL
     auxin server set url http://192.168.1.100:3000
 
+    # Store the server token in the keychain
+    auxin server set token <TOKEN>
+
     # Set default namespace
     auxin server set namespace myteam
 
@@ -414,155 +716,481 @@ EXAMPLES:
         #[arg(value_name = "VALUE", help = "Value to set")]
         value: String,
     },
-}
 
-#[derive(Subcommand)]
-enum BounceCommands {
-    /// Add a bounce file for a commit
-    #[command(long_about = "Add a bounce file for a commit
+    /// Choose which of your named servers this project uses
+    #[command(long_about = "Choose which of your named servers this project uses
 
 USAGE:
-    auxin bounce add <FILE> [--commit <ID>] [--description <TEXT>]
+    auxin server use [NAME]
+    auxin server use --clear
 
 DESCRIPTION:
-    Attaches an audio bounce file to a commit as an audio 'screenshot' of the
-    project state. Bounces are used for:
-      • Quick A/B comparison between versions
-      • Audio fingerprinting and semantic analysis
-      • Historical record of project evolution
+    For working with several studios, each running its own auxin-server.
+    Name each server in ~/.auxin/config.toml:
 
-    Supported formats: WAV, AIFF, MP3, FLAC, M4A
+      [servers.studio-a]
+      url = \"https://auxin.studio-a.com\"
+      token = \"keychain\"
 
-    If no commit ID is specified, the bounce is attached to the most recent commit.
+    then choose one per project. The choice is saved as [cli] server in
+    the project's .auxin/config.toml, and that server's url and token
+    replace [cli] url and token. Each server's token is kept in the system
+    keychain under its URL; store one with 'auxin server set token' after
+    choosing the server.
 
-EXAMPLES:
-    # Add bounce to latest commit
-    auxin bounce add Bounces/MyMix.wav
+    Without a name, lists the named servers.
 
-    # Add bounce to specific commit
-    auxin bounce add Bounces/MyMix.wav --commit abc123
+EXAMPLES:
+    # List named servers
+    auxin server use
 
-    # Add bounce with description
-    auxin bounce add Bounces/MyMix.wav --description 'Final mix before mastering'")]
-    Add {
-        #[arg(value_name = "FILE", help = "Path to the audio bounce file")]
-        file: PathBuf,
+    # Use studio-a for this project
+    auxin server use studio-a
 
-        #[arg(
-            long,
-            value_name = "ID",
-            help = "Commit ID to attach bounce to (default: latest)"
-        )]
-        commit: Option<String>,
+    # Go back to [cli] url and token
+    auxin server use --clear")]
+    Use {
+        #[arg(value_name = "NAME", help = "Server from [servers.<name>]")]
+        name: Option<String>,
 
-        #[arg(long, short, value_name = "TEXT", help = "Description of the bounce")]
-        description: Option<String>,
+        #[arg(long, conflicts_with = "name", help = "Stop using a named server")]
+        clear: bool,
     },
 
-    /// List all bounces in the repository
-    #[command(long_about = "List all bounces in the repository
+    /// Generate a production config and deployment files for auxin-server
+    #[command(long_about = "Generate a production config and deployment files for auxin-server
 
 USAGE:
-    auxin bounce list
+    auxin server init-config --preset <docker|systemd|fly> [--redis] [--postgres]
+    auxin server init-config --preset <PRESET> --output <DIR>
 
 DESCRIPTION:
-    Shows all audio bounces stored in the repository, including:
-      • Commit ID
-      • Original filename
-      • Format and duration
-      • File size
-      • When it was added
-      • Description (if provided)
+    Writes the [server] section for a production deployment:
+      • A random 64-byte auth_token_secret, instead of the
+        dev_secret_change_in_production placeholder
+      • A storage path (sync_dir) suited to the preset
+      • Redis and Postgres URLs, turned on with --redis and --postgres,
+        with a random database password
+
+    The config is validated, then printed along with the deployment
+    files for the preset, or written to --output:
+      • docker   config.toml and docker-compose.yml, with Redis and
+                 Postgres services when asked
+      • systemd  config.toml and auxin-server.service, listening on
+                 127.0.0.1 behind a reverse proxy
+      • fly      config.toml and fly.toml; the config is deployed as
+                 the AUXIN_CONFIG secret, never baked into the image
+
+    Every run generates a new secret. Changing it logs everyone out.
 
 EXAMPLES:
-    # List all bounces
-    auxin bounce list")]
-    List,
+    # Docker Compose with Redis locks
+    auxin server init-config --preset docker --redis --output deploy/
 
-    /// Play a bounce audio file
-    #[command(long_about = "Play a bounce audio file
+    # Print a systemd setup
+    auxin server init-config --preset systemd
+
+    # Fly.io app named studio-auxin
+    auxin server init-config --preset fly --app-name studio-auxin --output fly/")]
+    InitConfig {
+        #[arg(long, value_name = "PRESET", help = "Deployment: docker, systemd or fly")]
+        preset: String,
+
+        #[arg(long, help = "Use Redis for repository locks")]
+        redis: bool,
+
+        #[arg(long, help = "Use Postgres for the web UI")]
+        postgres: bool,
+
+        #[arg(long, value_name = "NAME", default_value = "auxin-server", help = "Fly app name")]
+        app_name: String,
+
+        #[arg(
+            short,
+            long,
+            value_name = "DIR",
+            help = "Write the files here instead of printing them"
+        )]
+        output: Option<PathBuf>,
+    },
+
+    /// Upload metadata for commits made before server metadata existed
+    #[command(long_about = "Upload metadata for commits made before server metadata existed
 
 USAGE:
-    auxin bounce play <COMMIT_ID>
+    auxin server backfill-metadata [--dry-run] [--restart]
 
 DESCRIPTION:
-    Plays the bounce audio file associated with a commit using the system
-    audio player (afplay on macOS). This allows quick preview of how the
-    project sounded at any point in history.
+    Walks the local commit history, parses metadata embedded in commit
+    messages (BPM, sample rate, key, tags), and uploads it to the server
+    for every commit the server has no metadata for.
+
+    Uploads are sent in rate-limited batches. Progress is saved to
+    .auxin/backfill_state.json after each batch, so an interrupted run
+    picks up where it left off when started again.
+
+    The repository is addressed as <namespace>/<directory name>, using the
+    configured default namespace.
 
 EXAMPLES:
-    # Play bounce for a commit
-    auxin bounce play abc123")]
-    Play {
-        #[arg(value_name = "COMMIT_ID", help = "Commit ID of the bounce to play")]
-        commit_id: String,
+    # Preview what would be uploaded
+    auxin server backfill-metadata --dry-run
+
+    # Upload missing metadata
+    auxin server backfill-metadata
+
+    # Ignore saved progress and start over
+    auxin server backfill-metadata --restart")]
+    BackfillMetadata {
+        #[arg(long, help = "Show what would be uploaded without uploading")]
+        dry_run: bool,
+
+        #[arg(long, help = "Discard saved progress and start from scratch")]
+        restart: bool,
     },
 
-    /// Show bounce metadata
-    #[command(long_about = "Show bounce metadata
+    /// Find auxin-server instances on the local network
+    #[command(long_about = "Find auxin-server instances on the local network
 
 USAGE:
-    auxin bounce info <COMMIT_ID>
+    auxin server discover [--timeout <SECS>] [--use <N>]
 
 DESCRIPTION:
-    Displays detailed metadata about a bounce file:
-      • Original filename
-      • Audio format
-      • Duration
-      • Sample rate, bit depth, channels
-      • File size
-      • When added and by whom
-      • Description
+    Browses for servers advertising _auxin._tcp over multicast DNS
+    (Bonjour) and lists them with their URL and version. In a terminal,
+    offers to save the chosen server's URL to the project config
+    (.auxin/config.toml), the same as 'auxin server set url'.
+
+    Servers advertise themselves unless [server] advertise = false, or when
+    they only listen on localhost.
 
 EXAMPLES:
-    # Show bounce info
-    auxin bounce info abc123")]
-    Info {
-        #[arg(value_name = "COMMIT_ID", help = "Commit ID of the bounce")]
-        commit_id: String,
+    # List servers on the LAN
+    auxin server discover
+
+    # Use the first server found without asking
+    auxin server discover --use 1")]
+    Discover {
+        #[arg(long, default_value = "3", help = "Seconds to wait for replies")]
+        timeout: u64,
+
+        #[arg(
+            long = "use",
+            value_name = "N",
+            help = "Save the URL of server N to the project config"
+        )]
+        use_server: Option<usize>,
     },
+}
 
-    /// Delete a bounce
-    #[command(long_about = "Delete a bounce
+#[derive(Subcommand)]
+enum LanCommands {
+    /// Offer a project to another workstation on the LAN
+    #[command(long_about = "Offer a project to another workstation on the LAN
 
 USAGE:
-    auxin bounce delete <COMMIT_ID>
+    auxin lan send [PATH] [--port <PORT>]
 
 DESCRIPTION:
-    Removes the bounce audio file and metadata for a commit.
-    This action cannot be undone.
+    Hands a project to another machine directly, without a server or
+    internet connection. Prints a one-time pairing code and the command
+    to run on the receiving machine, then waits for it to connect.
+
+    The receiver gets the working files, commit history and .auxin
+    metadata, including bounces. The pairing code is never sent over the
+    network; it is used to authenticate both machines and encrypt the
+    transfer. After 3 failed pairing attempts the sender stops listening.
 
 EXAMPLES:
-    # Delete bounce
-    auxin bounce delete abc123")]
-    Delete {
-        #[arg(value_name = "COMMIT_ID", help = "Commit ID of the bounce to delete")]
-        commit_id: String,
+    # Offer the current project
+    auxin lan send
+
+    # Offer another project on a fixed port (for firewalls)
+    auxin lan send ~/Music/Album.logicx --port 7401")]
+    Send {
+        #[arg(value_name = "PATH", default_value = ".", help = "Project to send")]
+        path: PathBuf,
+
+        #[arg(long, default_value = "0", help = "Port to listen on (0 picks a free port)")]
+        port: u16,
     },
 
-    /// Search and filter bounces
-    #[command(long_about = "Search and filter bounces
+    /// Receive a project offered with 'auxin lan send'
+    #[command(long_about = "Receive a project offered with 'auxin lan send'
 
 USAGE:
-    auxin bounce search [OPTIONS]
+    auxin lan receive <ADDRESS> [--code <CODE>] [--dest <DIR>] [--replace] [--yes]
 
 DESCRIPTION:
-    Search through bounces with various filters:
-      • Format (wav, mp3, flac, etc.)
-      • Filename pattern (regex)
-      • Duration range
-      • File size range
-      • Date range
-      • User who added
+    Connects to the sending machine, checks the pairing code and shows
+    what is being sent (project, sender, branch, latest commit, size)
+    before anything is written. The project is saved to a new folder named
+    after it, or to --dest.
+
+    If the destination already has files, --replace moves them aside to
+    <DIR>.before-lan-<timestamp> first; nothing is deleted.
 
 EXAMPLES:
-    # Find all WAV bounces
-    auxin bounce search --format wav
+    # Receive using the command shown by the sender
+    auxin lan receive 192.168.1.20:7401 --code K7QF-9XMA-2CDE
 
-    # Find bounces longer than 3 minutes
-    auxin bounce search --min-duration 180
+    # Replace an older copy of the project
+    auxin lan receive 192.168.1.20:7401 --dest Album.logicx --replace")]
+    Receive {
+        #[arg(
+            value_name = "ADDRESS",
+            help = "Sender address shown by 'auxin lan send' (host:port)"
+        )]
+        address: String,
 
-    # Find bounces with 'mix' in the filename
+        #[arg(long, help = "Pairing code shown by the sender (prompted if omitted)")]
+        code: Option<String>,
+
+        #[arg(long, value_name = "DIR", help = "Folder to receive into")]
+        dest: Option<PathBuf>,
+
+        #[arg(long, help = "Move an existing, non-empty destination aside")]
+        replace: bool,
+
+        #[arg(short, long, help = "Accept without confirming")]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DeliverCommands {
+    /// List recorded deliveries
+    #[command(long_about = "List recorded deliveries
+
+USAGE:
+    auxin deliver list [--to <RECIPIENT>] [--commit <ID>]
+
+DESCRIPTION:
+    Lists deliveries newest first, with the commit, version label,
+    format, bounce file and who sent it.
+
+EXAMPLES:
+    # Everything sent to a client
+    auxin deliver list --to \"Client X\"
+
+    # Who has received a commit
+    auxin deliver list --commit abc123f")]
+    List {
+        #[arg(long, value_name = "RECIPIENT", help = "Only deliveries to this recipient")]
+        to: Option<String>,
+
+        #[arg(long, value_name = "ID", help = "Only deliveries of this commit")]
+        commit: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum VersionCommands {
+    /// Give a commit the next version label
+    #[command(long_about = "Give a commit the next version label
+
+USAGE:
+    auxin version bump [--major | --minor] [-m <NOTE>] [--commit <ID>]
+
+DESCRIPTION:
+    Labels a commit (the latest by default) with the next human version,
+    such as v1.1, and marks it as a milestone. The first version is v1.0;
+    after that --minor (the default) gives v1.1, v1.2, ... and --major
+    gives v2.0.
+
+    Labels follow [versioning] format in the config, e.g. \"Mix {n}\" for
+    Mix 1, Mix 2, ... Versions are stored in .auxin/versions.json and
+    shown in 'auxin log', 'auxin show' and session sheets. The latest
+    commit also gets an oxen tag with the label.
+
+    With [versioning] auto = true, every commit tagged 'milestone' gets
+    the next minor version automatically.
+
+EXAMPLES:
+    # Label the latest commit after the client's notes
+    auxin version bump --minor -m \"Client revision\"
+
+    # Start a new major version
+    auxin version bump --major -m \"Re-recorded drums\"
+
+    # Label an earlier commit
+    auxin version bump --commit abc123f")]
+    Bump {
+        #[arg(long, conflicts_with = "minor", help = "Increment the major version")]
+        major: bool,
+
+        #[arg(long, help = "Increment the minor version (default)")]
+        minor: bool,
+
+        #[arg(short, long, value_name = "NOTE", help = "What changed in this version")]
+        message: Option<String>,
+
+        #[arg(long, value_name = "ID", help = "Commit to label (default: latest)")]
+        commit: Option<String>,
+    },
+
+    /// List version labels
+    #[command(long_about = "List version labels
+
+USAGE:
+    auxin version list
+
+DESCRIPTION:
+    Lists every version label with its commit, note, author and date,
+    newest first.
+
+EXAMPLES:
+    auxin version list")]
+    List,
+}
+
+#[derive(Subcommand)]
+enum BounceCommands {
+    /// Add a bounce file for a commit
+    #[command(long_about = "Add a bounce file for a commit
+
+USAGE:
+    auxin bounce add <FILE> [--commit <ID>] [--description <TEXT>]
+
+DESCRIPTION:
+    Attaches an audio bounce file to a commit as an audio 'screenshot' of the
+    project state. Bounces are used for:
+      • Quick A/B comparison between versions
+      • Audio fingerprinting and semantic analysis
+      • Historical record of project evolution
+
+    Supported formats: WAV, AIFF, MP3, FLAC, M4A
+
+    If no commit ID is specified, the bounce is attached to the most recent commit.
+
+EXAMPLES:
+    # Add bounce to latest commit
+    auxin bounce add Bounces/MyMix.wav
+
+    # Add bounce to specific commit
+    auxin bounce add Bounces/MyMix.wav --commit abc123
+
+    # Add bounce with description
+    auxin bounce add Bounces/MyMix.wav --description 'Final mix before mastering'")]
+    Add {
+        #[arg(value_name = "FILE", help = "Path to the audio bounce file")]
+        file: PathBuf,
+
+        #[arg(
+            long,
+            value_name = "ID",
+            help = "Commit ID to attach bounce to (default: latest)"
+        )]
+        commit: Option<String>,
+
+        #[arg(long, short, value_name = "TEXT", help = "Description of the bounce")]
+        description: Option<String>,
+    },
+
+    /// List all bounces in the repository
+    #[command(long_about = "List all bounces in the repository
+
+USAGE:
+    auxin bounce list
+
+DESCRIPTION:
+    Shows all audio bounces stored in the repository, including:
+      • Commit ID
+      • Original filename
+      • Format and duration
+      • File size
+      • When it was added
+      • Description (if provided)
+
+EXAMPLES:
+    # List all bounces
+    auxin bounce list")]
+    List,
+
+    /// Play a bounce audio file
+    #[command(long_about = "Play a bounce audio file
+
+USAGE:
+    auxin bounce play <COMMIT_ID>
+
+DESCRIPTION:
+    Plays the bounce audio file associated with a commit using the system
+    audio player (afplay on macOS). This allows quick preview of how the
+    project sounded at any point in history.
+
+EXAMPLES:
+    # Play bounce for a commit
+    auxin bounce play abc123")]
+    Play {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID of the bounce to play")]
+        commit_id: String,
+    },
+
+    /// Show bounce metadata
+    #[command(long_about = "Show bounce metadata
+
+USAGE:
+    auxin bounce info <COMMIT_ID>
+
+DESCRIPTION:
+    Displays detailed metadata about a bounce file:
+      • Original filename
+      • Audio format
+      • Duration
+      • Sample rate, bit depth, channels
+      • File size
+      • When added and by whom
+      • Description
+
+EXAMPLES:
+    # Show bounce info
+    auxin bounce info abc123")]
+    Info {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID of the bounce")]
+        commit_id: String,
+    },
+
+    /// Delete a bounce
+    #[command(long_about = "Delete a bounce
+
+USAGE:
+    auxin bounce delete <COMMIT_ID>
+
+DESCRIPTION:
+    Removes the bounce audio file and metadata for a commit.
+    This action cannot be undone.
+
+EXAMPLES:
+    # Delete bounce
+    auxin bounce delete abc123")]
+    Delete {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID of the bounce to delete")]
+        commit_id: String,
+    },
+
+    /// Search and filter bounces
+    #[command(long_about = "Search and filter bounces
+
+USAGE:
+    auxin bounce search [OPTIONS]
+
+DESCRIPTION:
+    Search through bounces with various filters:
+      • Format (wav, mp3, flac, etc.)
+      • Filename pattern (regex)
+      • Duration range
+      • File size range
+      • Date range
+      • User who added
+
+EXAMPLES:
+    # Find all WAV bounces
+    auxin bounce search --format wav
+
+    # Find bounces longer than 3 minutes
+    auxin bounce search --min-duration 180
+
+    # Find bounces with 'mix' in the filename
     auxin bounce search --pattern 'mix'
 
     # Find bounces from last week
@@ -725,4957 +1353,12702 @@ EXAMPLES:
         #[arg(long, help = "Force deletion without confirmation")]
         force: bool,
     },
-}
 
-#[derive(Subcommand)]
-enum HooksCommands {
-    /// Initialize hooks directory
-    Init,
+    /// Export a bounce, optionally as a watermarked preview
+    #[command(long_about = "Export a bounce, optionally as a watermarked preview
 
-    /// List all installed hooks
-    List,
+USAGE:
+    auxin bounce export <COMMIT_ID> [--watermark] [--no-bwf] [-o <FILE>]
 
-    /// List available built-in hooks
-    Builtins,
+DESCRIPTION:
+    Writes a copy of a commit's bounce. With --watermark, the copy is a
+    client preview with an audible watermark mixed in at regular
+    intervals, so a leaked preview can't stand in for the final mix. The
+    stored bounce is never changed.
 
-    /// Install a built-in hook
-    Install {
-        #[arg(value_name = "HOOK_NAME", help = "Name of built-in hook to install")]
-        name: String,
+    The watermark is set in the [watermark] config section: a sine tone
+    (the default) or an audio file such as a voice tag, how often it
+    repeats and how loud it is. Rendering needs ffmpeg.
+
+    Without --output, the copy is written to the current directory;
+    watermarked previews are named '<bounce> (preview).mp3'. The output
+    format follows the file extension.
+
+    WAV exports carry Broadcast WAV (bext) and iXML metadata: the commit
+    ID, project name, commit message, BPM, key and version label. The file
+    stays traceable after it leaves the repository; see
+    'auxin bounce identify'. Use --no-bwf to leave it out.
+
+EXAMPLES:
+    # Watermarked MP3 preview for the client
+    auxin bounce export abc123f --watermark
+
+    # Preview as WAV, with a voice tag for this export only
+    auxin bounce export abc123f --watermark --sound ~/Tags/preview.wav -o preview.wav
+
+    # Plain copy of the bounce
+    auxin bounce export abc123f -o ~/Desktop/mix.wav")]
+    Export {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID or link of the bounce")]
+        commit_id: String,
+
+        #[arg(long, help = "Mix in an audible watermark")]
+        watermark: bool,
 
         #[arg(
             long,
-            value_name = "TYPE",
-            default_value = "pre-commit",
-            help = "Hook type (pre-commit or post-commit)"
+            value_name = "FILE",
+            requires = "watermark",
+            help = "Audio file to use as the watermark instead of [watermark] sound"
         )]
-        hook_type: String,
+        sound: Option<PathBuf>,
+
+        #[arg(short, long, value_name = "FILE", help = "Where to write the copy")]
+        output: Option<PathBuf>,
+
+        #[arg(long, help = "Don't embed commit metadata in WAV exports")]
+        no_bwf: bool,
     },
 
-    /// Remove an installed hook
-    Remove {
-        #[arg(value_name = "HOOK_NAME", help = "Name of hook to remove")]
-        name: String,
+    /// Check a bounce's loudness against a delivery target
+    #[command(long_about = "Check a bounce's loudness against a delivery target
+
+USAGE:
+    auxin bounce check <COMMIT_ID> --target <TARGET> [--json]
+
+DESCRIPTION:
+    Measures the bounce's integrated loudness, true peak and loudness
+    range (EBU R128, via ffmpeg) and checks them against a target:
+
+      spotify              -14 LUFS ± 1 LU,   true peak ≤ -1 dBTP
+      apple-music          -16 LUFS ± 1 LU,   true peak ≤ -1 dBTP
+      youtube              -14 LUFS ± 1 LU,   true peak ≤ -1 dBTP
+      broadcast-ebu-r128   -23 LUFS ± 0.5 LU, true peak ≤ -1 dBTP
+
+    Reports pass or fail with the gain that would bring the bounce into
+    spec. Exits with status 1 on failure, so it can gate scripts; the
+    built-in check-loudness pre-deliver hook runs it before
+    'auxin deliver'.
+
+EXAMPLES:
+    auxin bounce check abc123f --target spotify
+    auxin bounce check abc123f --target broadcast-ebu-r128
+
+    # Run before every delivery
+    auxin hooks install check-loudness")]
+    Check {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID or link of the bounce")]
+        commit_id: String,
 
         #[arg(
             long,
-            value_name = "TYPE",
-            default_value = "pre-commit",
-            help = "Hook type (pre-commit or post-commit)"
+            value_name = "TARGET",
+            value_parser = ["spotify", "apple-music", "youtube", "broadcast-ebu-r128"],
+            help = "Delivery target"
         )]
-        hook_type: String,
+        target: String,
+
+        #[arg(long, help = "Print the report as JSON")]
+        json: bool,
+    },
+
+    /// Show which commit an exported WAV came from
+    #[command(long_about = "Show which commit an exported WAV came from
+
+USAGE:
+    auxin bounce identify <FILE>
+
+DESCRIPTION:
+    Reads the Broadcast WAV (bext) and iXML metadata written by
+    'auxin bounce export' and shows the commit, project, BPM, key and
+    version the file was exported from. Works on files outside the
+    repository, e.g. a mix sent back by a client.
+
+EXAMPLES:
+    auxin bounce identify ~/Downloads/Mix_v3_final_FINAL.wav")]
+    Identify {
+        #[arg(value_name = "FILE", help = "WAV file to inspect")]
+        file: PathBuf,
     },
 }
 
 #[derive(Subcommand)]
-enum Commands {
-    /// Initialize a new Oxen repository for a project
-    #[command(long_about = "Initialize a new Oxen repository for a project
+enum StemsCommands {
+    /// Add stem files to a commit
+    #[command(long_about = "Add stem files to a commit
 
 USAGE:
-    auxin init [--type <TYPE>] <PATH>
+    auxin stems add <COMMIT> <FILES>... [--description <TEXT>] [--push]
 
 DESCRIPTION:
-    Creates a new Oxen repository at the specified path with project-specific
-    configuration. The project type can be auto-detected or explicitly specified.
+    Attaches a set of audio files (drums, bass, vox, ...) to a commit, the
+    usual deliverable for a mixing handoff. Each stem is named after its
+    file; adding a file with the same name as an existing stem replaces it.
 
-    Supported project types:
-      • auto       - Auto-detect based on file extension (default)
-      • logicpro   - Logic Pro projects (.logicx)
-      • sketchup   - SketchUp models (.skp)
-
-    For Logic Pro projects:
-      • Detects and validates .logicx structure
-      • Creates .oxenignore with Logic-specific patterns
-      • Tracks projectData, Alternatives, Resources
-      • Sets up draft branch workflow
-
-    For SketchUp projects:
-      • Detects and validates .skp file
-      • Creates .oxenignore with SketchUp-specific patterns
-      • Tracks .skp file and asset directories (textures/, components/)
-      • Sets up draft branch workflow
+    Stems are stored in .auxin/stems/<commit>/ with a manifest of their
+    names, sizes and SHA-256 checksums.
 
-    The PATH can be:
-      • Current directory: .
-      • Relative path: MyProject.logicx or MyModel.skp
-      • Absolute path: /Users/you/Projects/MyModel.skp
+    Supported formats: WAV, AIFF, MP3, FLAC, M4A
 
 EXAMPLES:
-    # Auto-detect project type
-    auxin init MyProject.logicx
-
-    # Explicitly specify Logic Pro
-    auxin init --type logicpro MyProject.logicx
+    # Attach the stems bounced from Logic
+    auxin stems add abc123f Bounces/Stems/*.wav
 
-    # Initialize SketchUp project
-    auxin init --type sketchup MyModel.skp
+    # Replace one stem and push the set to the server
+    auxin stems add abc123f Bounces/Stems/Vox.wav --push")]
+    Add {
+        #[arg(value_name = "COMMIT", help = "Commit ID or link to attach the stems to")]
+        commit: String,
 
-    # Auto-detect in current directory
-    auxin init .")]
-    Init {
-        #[arg(value_name = "PATH", help = "Path to the project file or directory")]
-        path: PathBuf,
+        #[arg(value_name = "FILES", required = true, help = "Stem audio files")]
+        files: Vec<PathBuf>,
 
-        #[arg(
-            long,
-            value_name = "TYPE",
-            help = "Project type: auto, logicpro, sketchup (default: auto)"
-        )]
-        r#type: Option<String>,
+        #[arg(long, short, value_name = "TEXT", help = "Description of the stem set")]
+        description: Option<String>,
 
-        /// Legacy flag for backward compatibility
-        #[arg(long, hide = true)]
-        logic: bool,
+        #[arg(long, help = "Upload the stem set to auxin-server afterwards")]
+        push: bool,
     },
 
-    /// Clone an existing Auxin project from a remote repository
-    #[command(long_about = "Clone an existing Auxin project from a remote repository
+    /// List stem sets, or the stems of one commit
+    #[command(long_about = "List stem sets, or the stems of one commit
 
 USAGE:
-    auxin clone <REMOTE_URL> <DESTINATION>
+    auxin stems list [COMMIT]
 
 DESCRIPTION:
-    Clones an Auxin project from a remote repository to your local machine.
-    Similar to 'git clone', this downloads the entire project history and
-    creates a working copy at the specified destination.
-
-    Supported remote types:
-      • Oxen Hub URLs (https://hub.oxen.ai/user/project)
-      • Local file paths (file:///path/to/repo)
-      • Auxin server URLs (http://server:3000/namespace/repo)
-
-    The clone operation will:
-      • Download all project files and history
-      • Set up the local repository structure
-      • Configure the remote connection
-      • Check out the default branch (usually 'main')
-
-    For Logic Pro projects:
-      • Clones the .logicx bundle with all project data
-      • Includes all alternatives, resources, and metadata
-      • Preserves commit history with BPM, sample rate, etc.
-
-    For SketchUp projects:
-      • Clones the .skp file and associated assets
-      • Includes textures, components, and metadata
-      • Preserves commit history with units, layers, etc.
+    Without a commit, lists every commit with stems. With one, lists its
+    stems and checks each file against its checksum.
 
 EXAMPLES:
-    # Clone from Oxen Hub
-    auxin clone https://hub.oxen.ai/username/my-project MyProject.logicx
-
-    # Clone from local file path
-    auxin clone file:///Volumes/Shared/MyProject.logicx LocalCopy.logicx
-
-    # Clone a SketchUp project
-    auxin clone https://hub.oxen.ai/architect/building MyBuilding.skp
-
-    # Clone to current directory (uses repo name)
-    auxin clone https://hub.oxen.ai/user/project .")]
-    Clone {
-        #[arg(
-            value_name = "REMOTE_URL",
-            help = "URL of the remote repository to clone"
-        )]
-        remote_url: String,
-
-        #[arg(
-            value_name = "DESTINATION",
-            help = "Local path where the project will be cloned"
-        )]
-        destination: PathBuf,
+    auxin stems list
+    auxin stems list abc123f")]
+    List {
+        #[arg(value_name = "COMMIT", help = "Commit ID or link")]
+        commit: Option<String>,
     },
 
-    /// Stage changes to be committed
-    #[command(long_about = "Stage changes to be committed
+    /// Export a commit's stems to a folder
+    #[command(long_about = "Export a commit's stems to a folder
 
 USAGE:
-    auxin add --all
-    auxin add <PATHS>...
+    auxin stems export <COMMIT> [-o <DIR>]
 
 DESCRIPTION:
-    Stages changes in the working directory for the next commit. Similar to 'git add'.
-    Use --all to stage all changes, or specify individual files/directories.
+    Copies every stem and the manifest into a folder, ready to hand off.
+    Each stem is checked against its checksum first; nothing is exported
+    if any stem is missing or modified.
 
 EXAMPLES:
-    # Stage all changes in the repository
-    auxin add --all
-
-    # Stage specific files
-    auxin add projectData Alternatives/Take001
-
-    # Stage a directory
-    auxin add Resources/")]
-    Add {
-        #[arg(value_name = "PATHS", help = "Files or directories to stage")]
-        paths: Vec<PathBuf>,
+    auxin stems export abc123f
+    auxin stems export abc123f -o ~/Desktop/Handoff")]
+    Export {
+        #[arg(value_name = "COMMIT", help = "Commit ID or link")]
+        commit: String,
 
-        #[arg(long, short, help = "Stage all changes in the repository")]
-        all: bool,
+        #[arg(
+            short,
+            long,
+            value_name = "DIR",
+            help = "Folder to export to (default: <project>-stems-<commit>)"
+        )]
+        output: Option<PathBuf>,
     },
 
-    /// Create a commit with optional project metadata
-    #[command(long_about = "Create a commit with optional project metadata
+    /// Upload a commit's stems to auxin-server
+    #[command(long_about = "Upload a commit's stems to auxin-server
 
 USAGE:
-    auxin commit -m <MESSAGE> [OPTIONS]
+    auxin stems push <COMMIT>
 
 DESCRIPTION:
-    Creates a new commit with the currently staged changes. You can attach
-    project-specific metadata to help track the evolution of your project.
-
-    Logic Pro metadata:
-      • BPM (tempo)
-      • Sample rate (Hz)
-      • Key signature
-      • Tags for categorization
-
-    SketchUp metadata:
-      • Units (Inches, Feet, Meters, etc.)
-      • Layer count
-      • Component count
-      • Group count
-      • File size (bytes)
-      • Tags for categorization
-
-EXAMPLES (Logic Pro):
-    # Simple commit
-    auxin commit -m \"Initial project setup\"
-
-    # Commit with audio metadata
-    auxin commit -m \"Added bass line\" --bpm 120 --key \"A Minor\"
+    Uploads the stem set and its manifest to the configured server, where
+    collaborators can download them. Requires the Producer or Admin role.
 
-    # Full metadata commit
-    auxin commit -m \"Verse 2 complete\" \\
-        --bpm 128 \\
-        --sample-rate 48000 \\
-        --key \"C Major\" \\
-        --tags \"verse,arrangement\"
+EXAMPLES:
+    auxin stems push abc123f")]
+    Push {
+        #[arg(value_name = "COMMIT", help = "Commit ID or link")]
+        commit: String,
+    },
+}
 
-EXAMPLES (SketchUp):
-    # Simple commit
-    auxin commit -m \"Initial model geometry\"
+#[derive(Subcommand)]
+enum HooksCommands {
+    /// Initialize hooks directory
+    Init,
 
-    # Commit with SketchUp metadata
-    auxin commit -m \"Added materials and textures\" \\
-        --units Inches \\
-        --layers 10 \\
-        --components 150
+    /// List all installed hooks
+    List,
 
-    # Full metadata commit
-    auxin commit -m \"Presentation ready\" \\
-        --units Feet \\
-        --layers 15 \\
-        --components 234 \\
-        --groups 12 \\
-        --tags \"presentation,milestone\"")]
-    Commit {
-        #[arg(short, long, help = "Commit message describing the changes")]
-        message: String,
+    /// List available built-in hooks
+    Builtins,
 
-        // Logic Pro metadata
-        #[arg(long, help = "[Logic Pro] Beats per minute (tempo) of the project")]
-        bpm: Option<f32>,
+    /// Install a built-in hook
+    Install {
+        #[arg(value_name = "HOOK_NAME", help = "Name of built-in hook to install")]
+        name: String,
 
         #[arg(
             long,
-            help = "[Logic Pro] Sample rate in Hz (e.g., 44100, 48000, 96000)"
+            value_name = "TYPE",
+            help = "Hook type (pre-commit, post-commit or pre-deliver; default: the hook's own)"
         )]
-        sample_rate: Option<u32>,
+        hook_type: Option<String>,
+    },
+
+    /// Remove an installed hook
+    Remove {
+        #[arg(value_name = "HOOK_NAME", help = "Name of hook to remove")]
+        name: String,
 
         #[arg(
             long,
-            help = "[Logic Pro] Key signature (e.g., 'C Major', 'A Minor', 'F# Minor')"
+            value_name = "TYPE",
+            default_value = "pre-commit",
+            help = "Hook type (pre-commit, post-commit or pre-deliver)"
         )]
-        key: Option<String>,
+        hook_type: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum OscCommands {
+    /// Listen for OSC messages and run the mapped actions
+    Listen {
+        #[arg(long, value_name = "ADDR", help = "Address to listen on (default from config)")]
+        bind: Option<String>,
 
-        // SketchUp metadata
         #[arg(
             long,
-            help = "[SketchUp] Model units (e.g., Inches, Feet, Meters, Millimeters)"
+            value_name = "PATH",
+            help = "Project to act on (repeatable; default: current directory)"
         )]
-        units: Option<String>,
+        project: Vec<PathBuf>,
+    },
 
-        #[arg(long, help = "[SketchUp] Number of layers/tags in the model")]
-        layers: Option<u32>,
+    /// Send an OSC message (for testing mappings)
+    Send {
+        #[arg(value_name = "ADDRESS", help = "OSC address, e.g. /auxin/milestone")]
+        address: String,
 
-        #[arg(long, help = "[SketchUp] Number of component instances")]
-        components: Option<u32>,
+        #[arg(value_name = "ARGS", help = "Arguments; numbers are sent as int or float")]
+        args: Vec<String>,
 
-        #[arg(long, help = "[SketchUp] Number of groups")]
-        groups: Option<u32>,
+        #[arg(long, value_name = "ADDR", help = "Listener address (default from config)")]
+        to: Option<String>,
+    },
+}
 
-        #[arg(long, help = "[SketchUp] Model file size in bytes")]
-        file_size: Option<u64>,
+#[derive(Subcommand)]
+enum MetadataCommands {
+    /// Change the metadata of one commit
+    Edit {
+        #[arg(value_name = "COMMIT", help = "Commit ID or prefix")]
+        commit: String,
+
+        #[command(flatten)]
+        changes: MetadataChangeArgs,
+    },
 
-        // Common metadata
+    /// Change the metadata of every commit matching a filter
+    BulkEdit {
         #[arg(
-            long,
-            help = "Tags for categorization (comma-separated, e.g., 'mixing,draft' or 'presentation,milestone')"
+            long = "filter",
+            value_name = "FIELD:VALUE",
+            required = true,
+            help = "tag:NAME, key:NAME, bpm:N or message:TEXT (repeatable)"
         )]
-        tags: Option<String>,
+        filters: Vec<String>,
 
-        // Audio bounce
-        #[arg(
-            long,
-            value_name = "FILE",
-            help = "Audio bounce file to attach (WAV, AIFF, MP3, FLAC, M4A)"
-        )]
-        bounce: Option<PathBuf>,
+        #[command(flatten)]
+        changes: MetadataChangeArgs,
+
+        /// List the commits that would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
-    /// Show commit history
-    #[command(long_about = "Show commit history
+    /// Show the audit trail of metadata edits
+    History {
+        #[arg(value_name = "COMMIT", help = "Only edits to this commit (ID or prefix)")]
+        commit: Option<String>,
+    },
 
-USAGE:
-    auxin log [OPTIONS]
+    /// Upload edits that haven't reached the server yet
+    Sync,
 
-DESCRIPTION:
-    Displays the commit history for the repository, showing commit IDs, authors,
-    timestamps, and messages. Audio metadata (BPM, key, etc.) is displayed if
-    present in the commit message.
+    /// Store metadata for commits that only have it in their message
+    Migrate,
+}
 
-    Filter commits by metadata, tags, or date range to find specific versions.
+#[derive(clap::Args)]
+struct MetadataChangeArgs {
+    #[arg(
+        long = "set",
+        value_name = "FIELD=VALUE",
+        help = "bpm, sample_rate, key or tags (repeatable)"
+    )]
+    set: Vec<String>,
 
-EXAMPLES:
-    # Show all commits
-    auxin log
+    #[arg(long = "add-tag", value_name = "TAG", help = "Add a tag (repeatable)")]
+    add_tags: Vec<String>,
 
-    # Show only the last 5 commits
-    auxin log --limit 5
+    #[arg(long = "remove-tag", value_name = "TAG", help = "Remove a tag (repeatable)")]
+    remove_tags: Vec<String>,
+}
 
-    # Show commits with specific BPM
-    auxin log --bpm 128
+impl MetadataChangeArgs {
+    fn changes(&self) -> anyhow::Result<Vec<auxin::metadata_edits::MetadataChange>> {
+        use auxin::metadata_edits::MetadataChange;
+
+        let mut changes = self
+            .set
+            .iter()
+            .map(|s| MetadataChange::parse_set(s))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        changes.extend(self.add_tags.iter().cloned().map(MetadataChange::AddTag));
+        changes.extend(self.remove_tags.iter().cloned().map(MetadataChange::RemoveTag));
+        if changes.is_empty() {
+            anyhow::bail!("Nothing to change: use --set, --add-tag or --remove-tag");
+        }
+        Ok(changes)
+    }
+}
 
-    # Show commits with specific tag
-    auxin log --tag mixing
+/// Upload unsynced metadata edits if server metadata is enabled; returns
+/// how many commits were stored, or `None` when the server isn't in use
+fn sync_metadata_edits(
+    repo_root: &std::path::Path,
+    edits: &mut auxin::metadata_edits::MetadataEdits,
+    commits: &[auxin::CommitInfo],
+) -> anyhow::Result<Option<usize>> {
+    let config = auxin_config::load_config().unwrap_or_default();
+    if !config.cli.use_server_metadata {
+        return Ok(None);
+    }
+    let entries = edits.sync_entries(commits);
+    if entries.is_empty() {
+        return Ok(Some(0));
+    }
 
-    # Show commits since a date
-    auxin log --since \"2025-01-01\"
+    let server_config = ServerConfig::from_config(&config);
+    let client = AuxinServerClient::new(server_config)?;
+    let repo_name = repo_root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .context("Failed to determine repository name")?;
+    let report =
+        client.store_metadata_batch(&config.cli.default_namespace, &repo_name, &entries)?;
+
+    edits.mark_synced(&report.stored);
+    edits.save(repo_root)?;
+    for failure in &report.failed {
+        warn!("Server rejected metadata for {}: {}", failure.commit_id, failure.error);
+    }
+    Ok(Some(report.stored.len()))
+}
 
-    # Combine filters
-    auxin log --bpm 120 --tag vocals --limit 10
+#[derive(Subcommand)]
+enum AnomalyCommands {
+    /// Check the project before an auto-commit; exits 3 while paused
+    Check {
+        #[arg(value_name = "PATH", help = "Path to the project (default: current directory)")]
+        path: Option<PathBuf>,
+    },
 
-    # Output as JSON
-    auxin log --format json --limit 5")]
-    Log {
-        #[arg(short, long, help = "Maximum number of commits to display")]
-        limit: Option<usize>,
+    /// Show whether auto-commits are paused and why
+    Status {
+        #[arg(value_name = "PATH", help = "Path to the project (default: current directory)")]
+        path: Option<PathBuf>,
+    },
 
-        #[arg(long, help = "Filter by BPM (e.g., 120, 128)")]
-        bpm: Option<f32>,
+    /// Resume auto-commits, accepting the project as it is now
+    Resume {
+        #[arg(value_name = "PATH", help = "Path to the project (default: current directory)")]
+        path: Option<PathBuf>,
+    },
+}
 
-        #[arg(long, help = "Filter by tag (e.g., 'mixing', 'vocals')")]
-        tag: Option<String>,
+#[derive(Subcommand)]
+enum PolicyCommands {
+    /// Explain the staging rules in effect for a project
+    Show {
+        #[arg(value_name = "PATH", help = "Path to the project (default: current directory)")]
+        path: Option<PathBuf>,
+    },
+}
 
-        #[arg(long, help = "Filter by key signature (e.g., 'C Major')")]
-        key: Option<String>,
+#[derive(Subcommand)]
+enum QuarantineCommands {
+    /// List the files held back from pulls
+    List,
 
-        #[arg(long, help = "Show commits since date (YYYY-MM-DD)")]
-        since: Option<String>,
+    /// Go through held files one by one: approve, reject or skip
+    Review,
 
-        #[arg(long, help = "Output format (text or json)", value_parser = ["text", "json"], default_value = "text")]
-        format: String,
+    /// Let files through, restoring their permissions
+    Approve {
+        #[arg(value_name = "PATH", required_unless_present = "all", help = "Quarantined files")]
+        paths: Vec<String>,
+
+        #[arg(long, help = "Approve every quarantined file")]
+        all: bool,
     },
 
-    /// Restore project to a previous commit
-    #[command(long_about = "Restore project to a previous commit
+    /// Delete quarantined files
+    Reject {
+        #[arg(value_name = "PATH", required = true, help = "Quarantined files")]
+        paths: Vec<String>,
+    },
+}
 
-USAGE:
-    auxin restore <COMMIT_ID>
+#[derive(Subcommand)]
+enum ArchiveCommands {
+    /// Make the project read-only
+    Mark {
+        #[arg(long, help = "Why the project was archived, e.g. who it was delivered to")]
+        reason: Option<String>,
+    },
 
-DESCRIPTION:
-    Restores the project to the state at the specified commit. This checks out
-    the files from that commit, allowing you to return to a previous version.
+    /// Make the project writable again
+    Unmark,
 
-    WARNING: Make sure to commit any current changes before restoring, or they
-    will be lost.
+    /// Show whether the project is archived
+    Status,
+}
 
-    You can find commit IDs using the 'log' command.
+#[derive(Subcommand)]
+enum BranchCommands {
+    /// List branches, marking the current one
+    List,
 
-EXAMPLES:
-    # Find commit IDs
-    auxin log --limit 5
+    /// Create a branch at the current commit and switch to it
+    Create {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
 
-    # Restore to a specific commit
-    auxin restore abc123def
+    /// Switch to another branch
+    Switch {
+        #[arg(value_name = "NAME")]
+        name: String,
 
-    # Restore to a commit (full hash)
-    auxin restore abc123def456789012345678901234567890")]
-    Restore {
-        #[arg(
-            value_name = "COMMIT_ID",
-            help = "Commit ID to restore to (from 'log' command)"
-        )]
-        commit_id: String,
+        #[arg(long, help = "Switch even if it discards uncommitted changes")]
+        force: bool,
     },
 
-    /// Show repository status
-    #[command(long_about = "Show repository status
-
-USAGE:
-    auxin status
+    /// Delete a branch
+    Delete {
+        #[arg(value_name = "NAME")]
+        name: String,
 
-DESCRIPTION:
-    Displays the current state of the working directory and staging area:
-      • Staged files (ready to commit)
-      • Modified files (changed but not staged)
-      • Untracked files (new files not yet added)
+        #[arg(long, help = "Allow deleting main or the draft branch")]
+        force: bool,
+    },
+}
 
-    This is similar to 'git status' and helps you see what changes are pending.
+#[derive(Subcommand)]
+enum WorkspaceCommands {
+    /// List the registered repositories
+    List,
 
-EXAMPLES:
-    # Check repository status
-    auxin status")]
-    Status,
+    /// Register repositories
+    Add {
+        #[arg(value_name = "PATH", help = "Repositories to add (default: current directory)")]
+        paths: Vec<PathBuf>,
+    },
 
-    /// Show detailed information about a commit
-    #[command(long_about = "Show detailed information about a commit
+    /// Forget repositories
+    Remove {
+        #[arg(value_name = "PATH", required = true, help = "Repositories to remove")]
+        paths: Vec<PathBuf>,
+    },
 
-USAGE:
-    auxin show <COMMIT_ID>
+    /// Run maintenance tasks on every registered repository
+    Maintain {
+        #[arg(long, help = "Remove stale Spotlight sidecars and old Oxen temp files")]
+        gc: bool,
 
-DESCRIPTION:
-    Displays comprehensive information about a specific commit, including:
-      • Full commit message
-      • Audio metadata (BPM, sample rate, key signature, tags)
-      • Author and timestamp
-      • Files changed
-      • Commit statistics
+        #[arg(long, help = "Remove unused draft branches and flag oversized ones")]
+        prune_drafts: bool,
 
-EXAMPLES:
-    # Show details of a recent commit
-    auxin show abc123f
+        #[arg(long, help = "Check that each repository can be read")]
+        verify: bool,
 
-    # Show details with full hash
-    auxin show abc123def456789012345678901234567890")]
-    Show {
-        #[arg(value_name = "COMMIT_ID", help = "Commit ID to show details for")]
-        commit_id: String,
+        #[arg(long, help = "Print the report as JSON")]
+        json: bool,
     },
+}
 
-    /// Show changes between commits or working directory
-    #[command(long_about = "Show changes between commits or working directory
+#[derive(Subcommand)]
+enum DevtoolsCommands {
+    /// Generate a synthetic project with history
+    GenProject {
+        #[arg(long = "type", value_name = "TYPE", default_value = "logicpro", help = "logicpro, sketchup, blender or other")]
+        kind: String,
 
-USAGE:
-    auxin diff [COMMIT_ID]
+        #[arg(long, default_value = "1GB", help = "Approximate size of the working tree (e.g. 500MB, 20GB)")]
+        size: String,
 
-DESCRIPTION:
-    Shows file-level changes in the repository:
-      • Without arguments: shows changes in working directory vs last commit
-      • With commit ID: shows changes between that commit and working directory
-      • With two IDs: shows changes between two commits
+        #[arg(long, default_value = "50", help = "Commits to create (0 writes the files only)")]
+        commits: usize,
 
-    Displays:
-      • Modified files with size changes
-      • Added files
-      • Deleted files
-      • Total size impact
+        #[arg(long, default_value = "Synthetic Project", help = "Project name")]
+        name: String,
 
-EXAMPLES:
-    # Show uncommitted changes
-    auxin diff
+        #[arg(long, default_value = "1", help = "Random seed; the same seed gives the same project")]
+        seed: u64,
 
-    # Show changes since specific commit
-    auxin diff abc123f
+        #[arg(short, long, value_name = "DIR", help = "Directory to create the project in (default: current directory)")]
+        output: Option<PathBuf>,
+    },
+}
 
-    # Compare two commits (future enhancement)
-    # auxin diff abc123f def456a")]
-    Diff {
-        #[arg(
-            value_name = "COMMIT_ID",
-            help = "Commit ID to compare against (optional)"
-        )]
-        commit_id: Option<String>,
+#[derive(Subcommand)]
+enum FeaturesCommands {
+    /// List the features and whether they're on
+    List,
+
+    /// Print 'on' or 'off' for one feature
+    Get {
+        #[arg(value_name = "FEATURE")]
+        name: String,
+    },
+
+    /// Turn a feature on or off
+    Set {
+        #[arg(value_name = "FEATURE")]
+        name: String,
+
+        #[arg(value_name = "on|off")]
+        value: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum MetaCommands {
+    /// Merge with the meta branch, commit and push
+    Sync {
+        #[arg(value_name = "PATH", help = "Path to the project (default: current directory)")]
+        path: Option<PathBuf>,
     },
+}
 
-    /// Compare metadata between two commits
-    #[command(long_about = "Compare metadata between two commits
+#[derive(Subcommand)]
+enum ExportCommands {
+    /// Export a branch's history with internal notes scrubbed
+    #[command(long_about = "Export a branch's history with internal notes scrubbed
 
 USAGE:
-    auxin compare <COMMIT_A> <COMMIT_B>
-    auxin compare <COMMIT_A> <COMMIT_B> --format json
+    auxin export history --output <DIR> [--branch <BRANCH>] [--redact <KINDS>]
 
 DESCRIPTION:
-    Performs semantic diff between two commits, showing changes in:
-      • Commit message
-      • BPM (tempo)
-      • Sample rate
-      • Key signature
-      • Tags
+    Replays every commit of a branch, oldest first, into a new repository
+    at <DIR>, ready to hand to a client or another studio. The project
+    itself isn't touched.
 
-    This helps understand what changed in the project's audio characteristics
-    between versions, beyond just file changes.
+    Pen-names are always scrubbed from commit messages and comments:
+    names and aliases in .auxin/mailmap, comment authors, your own user
+    name and @mentions become [redacted]. --redact removes more:
 
-OPTIONS:
-    --format <FORMAT>    Output format: text (default), colored, json, compact
-    --plain              Disable colored output
+      comments   Leave commit comments out (otherwise they're copied,
+                 scrubbed, with the author redacted)
+      todos      Drop TODO/FIXME lines and unchecked [ ] items from
+                 messages and comments
+      all        Both
+
+    Nothing from .auxin (metadata edits, deliveries, bounces) is copied.
+    Commits that changed no files are folded into the one before.
 
 EXAMPLES:
-    # Compare two commits with colored output
-    auxin compare abc123f def456a
+    # Client handover without comments or todos
+    auxin export history --redact comments,todos --branch main --output handover/
 
-    # Compare with plain text (no colors)
-    auxin compare abc123f def456a --plain
+    # Keep the (scrubbed) comments
+    auxin export history --output handover/")]
+    History {
+        #[arg(short, long, value_name = "DIR", help = "Folder for the new repository")]
+        output: PathBuf,
 
-    # Compare with JSON output
-    auxin compare abc123f def456a --format json
+        #[arg(long, value_name = "BRANCH", default_value = "main", help = "Branch to export")]
+        branch: String,
 
-    # Compare with compact one-line summary
-    auxin compare abc123f def456a --format compact")]
-    Compare {
-        #[arg(value_name = "COMMIT_A", help = "First commit ID (older)")]
-        commit_a: String,
+        #[arg(long, value_name = "KINDS", help = "What else to remove: comments, todos or all")]
+        redact: Option<String>,
+    },
+}
 
-        #[arg(value_name = "COMMIT_B", help = "Second commit ID (newer)")]
-        commit_b: String,
+#[derive(Subcommand)]
+enum SpotlightCommands {
+    /// Write sidecars for the project's whole history and drop stale ones
+    Sync {
+        #[arg(value_name = "PATH", help = "Path to the project (default: current directory)")]
+        path: Option<PathBuf>,
+    },
+
+    /// Remove sidecars for deleted projects and commits
+    Gc,
+
+    /// Remove all of the project's sidecars
+    Clear {
+        #[arg(value_name = "PATH", help = "Path to the project (default: current directory)")]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AdminCommands {
+    /// List held locks across all repositories, longest idle first
+    Locks {
+        #[arg(long, help = "Only show stale locks")]
+        stale: bool,
 
         #[arg(
             long,
-            value_name = "FORMAT",
-            default_value = "colored",
-            help = "Output format (text, colored, json, compact)"
+            value_name = "MINUTES",
+            default_value_t = 30,
+            help = "Minutes without a heartbeat before a lock counts as stale"
         )]
-        format: String,
-
-        #[arg(long, help = "Disable colored output")]
-        plain: bool,
+        stale_minutes: i64,
     },
 
-    /// Search commit history with advanced filtering
-    #[command(long_about = "Search commit history with advanced filtering
+    /// Break a repository's lock
+    BreakLock {
+        #[arg(value_name = "REPO", help = "Repository as namespace/name")]
+        repo: String,
 
-USAGE:
-    auxin search <QUERY>
-    auxin search bpm:120-140 key:minor tag:mixing
+        #[arg(long, help = "Break the lock even if its holder is still active")]
+        force: bool,
 
-DESCRIPTION:
-    Smart search across commit history with metadata-based filtering.
-    Supports natural language-style queries with multiple criteria:
+        #[arg(
+            long,
+            value_name = "MINUTES",
+            default_value_t = 30,
+            help = "Minutes without a heartbeat before a lock counts as stale"
+        )]
+        stale_minutes: i64,
+    },
 
-    Query Syntax:
-      • bpm:120-140      - BPM range
-      • bpm:>120         - BPM greater than
-      • bpm:<140         - BPM less than
-      • sr:48000         - Exact sample rate
-      • key:minor        - Key signature (fuzzy match)
-      • tag:mixing       - Has tag (single)
-      • tag:mix,vocal    - Has any of these tags (OR logic)
-      • msg:final        - Message contains text
-      • limit:10         - Limit results
+    /// Show audit events from every repository, newest first
+    Audit {
+        #[arg(short = 'n', long, default_value_t = 50, help = "Number of events to show")]
+        limit: usize,
 
-    Multiple criteria can be combined (AND logic):
-      bpm:120-140 key:minor tag:mixing
+        #[arg(long, help = "Only events in this namespace")]
+        namespace: Option<String>,
 
-OPTIONS:
-    --format <FORMAT>    Output format: list (default), compact, json
-    --ranked             Sort by relevance score
+        #[arg(long, help = "Only events by this user")]
+        user: Option<String>,
 
-EXAMPLES:
-    # Find all commits between 120-140 BPM
-    auxin search \"bpm:120-140\"
+        #[arg(
+            long = "type",
+            value_name = "TYPE",
+            help = "Only events of this type, e.g. lock_released"
+        )]
+        activity_type: Option<String>,
+    },
 
-    # Find commits in minor keys with mixing tag
-    auxin search \"key:minor tag:mixing\"
+    /// List users with live sessions
+    Sessions,
 
-    # Find high BPM commits (>140)
-    auxin search \"bpm:>140\"
+    /// Revoke every token of a user, or a single token
+    Revoke {
+        #[arg(long, value_name = "USERNAME", required_unless_present = "revoked")]
+        user: Option<String>,
 
-    # Find commits with 'final' in message
-    auxin search \"msg:final\"
+        #[arg(value_name = "TOKEN", conflicts_with = "user", help = "A single token to revoke")]
+        revoked: Option<String>,
+    },
 
-    # Combined search with limit
-    auxin search \"bpm:120-140 key:minor tag:vocals limit:5\"
+    /// Show disk usage per namespace
+    Storage,
 
-    # Get compact one-line summaries
-    auxin search \"bpm:>128\" --format compact
+    /// Remove expired sessions and locks, stale lock guards and temp files
+    Gc {
+        #[arg(long, help = "Show what would be removed without removing it")]
+        dry_run: bool,
+    },
+}
 
-    # Ranked by relevance
-    auxin search \"bpm:120-140 tag:mixing\" --ranked")]
-    Search {
-        #[arg(value_name = "QUERY", help = "Search query string")]
-        query: String,
+#[derive(Subcommand)]
+enum Commands {
+    /// Initialize a new Oxen repository for a project
+    #[command(long_about = "Initialize a new Oxen repository for a project
 
-        #[arg(
-            long,
-            value_name = "FORMAT",
-            default_value = "list",
-            help = "Output format (list, compact, json)"
-        )]
-        format: String,
+USAGE:
+    auxin init [--type <TYPE>] <PATH>
 
-        #[arg(long, help = "Sort results by relevance score")]
-        ranked: bool,
-    },
+DESCRIPTION:
+    Creates a new Oxen repository at the specified path with project-specific
+    configuration. The project type can be auto-detected or explicitly specified.
 
-    /// Manage project locks for team collaboration
-    #[command(subcommand)]
-    Lock(LockCommands),
+    Supported project types:
+      • auto       - Auto-detect based on file extension (default)
+      • logicpro   - Logic Pro projects (.logicx)
+      • sketchup   - SketchUp models (.skp)
+      • blender    - Blender files (.blend)
+      • premiere   - Premiere Pro projects (.prproj)
+      • aftereffects - After Effects projects (.aep)
+      • unreal     - Unreal Engine projects (folder with a .uproject)
 
-    /// Authenticate with Oxen Hub for remote collaboration
-    #[command(subcommand)]
-    Auth(AuthCommands),
+    For Logic Pro projects:
+      • Detects and validates .logicx structure
+      • Creates .oxenignore with Logic-specific patterns
+      • Tracks projectData, Alternatives, Resources
+      • Sets up draft branch workflow
 
-    /// Manage auxin-server connection and configuration
-    #[command(subcommand)]
-    Server(ServerCommands),
+    For SketchUp projects:
+      • Detects and validates .skp file
+      • Creates .oxenignore with SketchUp-specific patterns
+      • Tracks .skp file and asset directories (textures/, components/)
+      • Sets up draft branch workflow
 
-    /// Manage audio bounce files for commits
-    #[command(subcommand)]
-    Bounce(BounceCommands),
+    Blender files are handled the same way, with Blender-specific ignore
+    patterns. Where the type has them, details read from the project
+    (tempo, key, file size) are shown once it's initialized.
 
-    /// Compare metadata between two Logic Pro project versions
-    #[command(name = "metadata-diff")]
-    #[command(long_about = "Compare metadata between two Logic Pro project versions
+    For Premiere Pro and After Effects projects:
+      • Creates .oxenignore leaving out the media cache, preview renders
+        and auto-saves
+      • Shows the sequence count, resolution and frame rate (Premiere Pro)
+        or composition count (After Effects)
 
-USAGE:
-    auxin metadata-diff <PROJECT_A> <PROJECT_B>
-    auxin metadata-diff <PROJECT_A> <PROJECT_B> --output json
+    For Unreal Engine projects:
+      • Versions the whole project folder (Content/, Config/, Source/)
+      • Creates .oxenignore leaving out Binaries/, Intermediate/, Saved/
+        and DerivedDataCache/
+      • Records the engine version in the initial commit and every
+        commit after it
 
-DESCRIPTION:
-    Analyzes and compares the metadata of two Logic Pro project versions,
-    generating a detailed report of what changed. Detects:
-      • Global changes (tempo, sample rate, key signature)
-      • Track additions, removals, and modifications
-      • EQ changes (frequency, gain, Q factor)
-      • Compressor changes (threshold, ratio, attack, release)
-      • Volume and pan adjustments
-      • Automation curve changes
-      • Plugin parameter changes
+    The PATH can be:
+      • Current directory: .
+      • Relative path: MyProject.logicx or MyModel.skp
+      • Absolute path: /Users/you/Projects/MyModel.skp
 
-    The output is a human-readable report showing exactly what changed
-    between versions, making it easy to understand project evolution.
+    The repository's features are recorded in .auxin/features.toml and
+    committed with it, so every clone behaves the same. All are on unless
+    turned off with --without (see 'auxin features').
 
 EXAMPLES:
-    # Compare two project versions
-    auxin metadata-diff Project_v1.logicx Project_v2.logicx
+    # Auto-detect project type
+    auxin init MyProject.logicx
 
-    # Output as JSON for programmatic use
-    auxin metadata-diff Project_v1.logicx Project_v2.logicx --output json
+    # No draft branch and no server sync
+    auxin init MyProject.logicx --without draft_workflow --without server_sync
 
-    # Compare with colored output
-    auxin metadata-diff Project_v1.logicx Project_v2.logicx --color
+    # Explicitly specify Logic Pro
+    auxin init --type logicpro MyProject.logicx
 
-    # Verbose mode with technical details
-    auxin metadata-diff Project_v1.logicx Project_v2.logicx --verbose")]
-    MetadataDiff {
-        #[arg(value_name = "PROJECT_A", help = "First Logic Pro project (.logicx)")]
-        project_a: PathBuf,
+    # Initialize SketchUp project
+    auxin init --type sketchup MyModel.skp
 
-        #[arg(value_name = "PROJECT_B", help = "Second Logic Pro project (.logicx)")]
-        project_b: PathBuf,
+    # Initialize Premiere Pro project
+    auxin init --type premiere Edit.prproj
+
+    # Initialize Unreal Engine project
+    auxin init --type unreal ShooterGame/
+
+    # Auto-detect in current directory
+    auxin init .")]
+    Init {
+        #[arg(value_name = "PATH", help = "Path to the project file or directory")]
+        path: PathBuf,
 
         #[arg(
             long,
-            value_name = "FORMAT",
-            default_value = "text",
-            help = "Output format (text or json)"
+            value_name = "TYPE",
+            help = "Project type: auto, logicpro, sketchup, blender, premiere, aftereffects, \
+                    unreal (default: auto)"
         )]
-        output: String,
+        r#type: Option<String>,
 
-        #[arg(long, help = "Use colored output (default: auto-detect)")]
-        color: bool,
+        /// Legacy flag for backward compatibility
+        #[arg(long, hide = true)]
+        logic: bool,
 
-        #[arg(long, short, help = "Include technical details in output")]
-        verbose: bool,
+        #[arg(long, value_name = "FEATURE", help = "Turn a repository feature off")]
+        without: Vec<String>,
     },
 
-    /// Control the background daemon service
-    #[command(subcommand)]
-    Daemon(DaemonCommands),
-
-    /// Manage workflow automation hooks
-    #[command(long_about = "Manage workflow automation hooks
+    /// Clone an existing Auxin project from a remote repository
+    #[command(long_about = "Clone an existing Auxin project from a remote repository
 
 USAGE:
-    auxin hooks init
-    auxin hooks list
-    auxin hooks install <HOOK_NAME>
+    auxin clone <REMOTE_URL> <DESTINATION>
 
 DESCRIPTION:
-    Workflow automation hooks allow you to run custom scripts before and after
-    commits. Use hooks for:
+    Clones an Auxin project from a remote repository to your local machine.
+    Similar to 'git clone', this downloads the entire project history and
+    creates a working copy at the specified destination.
 
-    Pre-commit hooks (run before creating commit):
-      • Validate metadata completeness
-      • Check file sizes
-      • Run linting or formatting
-      • Verify project structure
+    Supported remote types:
+      • Oxen Hub URLs (https://hub.oxen.ai/user/project)
+      • Local file paths (file:///path/to/repo)
+      • Auxin server URLs (http://server:3000/namespace/repo)
 
-    Post-commit hooks (run after successful commit):
-      • Send notifications
-      • Create backups
-      • Trigger CI/CD pipelines
-      • Update tracking systems
+    The clone operation will:
+      • Download all project files and history
+      • Set up the local repository structure
+      • Configure the remote connection
+      • Check out the default branch (usually 'main')
 
-EXAMPLES:
-    # Initialize hooks directory
-    auxin hooks init
+    For Logic Pro projects:
+      • Clones the .logicx bundle with all project data
+      • Includes all alternatives, resources, and metadata
+      • Preserves commit history with BPM, sample rate, etc.
 
-    # List all installed hooks
-    auxin hooks list
+    For SketchUp projects:
+      • Clones the .skp file and associated assets
+      • Includes textures, components, and metadata
+      • Preserves commit history with units, layers, etc.
 
-    # List available built-in hooks
-    auxin hooks builtins
+EXAMPLES:
+    # Clone from Oxen Hub
+    auxin clone https://hub.oxen.ai/username/my-project MyProject.logicx
 
-    # Install a built-in hook
-    auxin hooks install validate-metadata
+    # Clone from local file path
+    auxin clone file:///Volumes/Shared/MyProject.logicx LocalCopy.logicx
 
-    # Install a post-commit hook
-    auxin hooks install backup --hook-type post-commit
+    # Clone a SketchUp project
+    auxin clone https://hub.oxen.ai/architect/building MyBuilding.skp
 
-    # Remove a hook
-    auxin hooks remove validate-metadata")]
-    #[command(subcommand)]
-    Hooks(HooksCommands),
+    # Clone to current directory (uses repo name)
+    auxin clone https://hub.oxen.ai/user/project .")]
+    Clone {
+        #[arg(
+            value_name = "REMOTE_URL",
+            help = "URL of the remote repository to clone"
+        )]
+        remote_url: String,
 
-    /// Launch interactive console for real-time monitoring
-    #[command(long_about = "Launch interactive console for real-time monitoring
+        #[arg(
+            value_name = "DESTINATION",
+            help = "Local path where the project will be cloned"
+        )]
+        destination: PathBuf,
+    },
+
+    /// Fork a project into an independent repo for a remix or alt version
+    #[command(long_about = "Fork a project into an independent repo for a remix or alt version
 
 USAGE:
-    auxin console [PATH]
+    auxin fork <PATH> <NEW_PATH>
 
 DESCRIPTION:
-    Launches a full-screen interactive TUI (Terminal User Interface) that provides
-    real-time monitoring and control of your Logic Pro project version control.
-
-    Features:
-      • Live daemon status display
-      • Real-time activity log with auto-updates
-      • Repository status (staged, modified, untracked files)
-      • Keyboard shortcuts for common operations
-      • Color-coded output for clarity
+    Creates a new repository at <NEW_PATH> with the full history of the
+    project at <PATH>, for a remix, alternate version or spin-off that
+    should evolve on its own.
 
-    The console runs until you press 'q' to quit.
+    The object store (.oxen/versions) is hardlinked rather than copied,
+    so the history costs no extra disk space. Its files are never
+    rewritten, which makes sharing them safe. Working files are copied,
+    since Logic and other apps edit them in place. On a different volume
+    everything is copied.
 
-KEYBOARD SHORTCUTS:
-    q       - Quit console
-    r       - Refresh status
-    c       - Clear activity log
-    ? or h  - Show help
+    The fork has no remotes, so it can't push into the original. Where it
+    came from (source path, commit, branch and remote) is recorded in
+    .auxin/fork.json. Uncommitted changes in the source are copied as
+    they are.
 
 EXAMPLES:
-    # Launch console in current directory
-    auxin console
+    auxin fork \"Track 3.logicx\" \"Track 3 (Club Remix).logicx\"
+    auxin fork ~/Music/Album/Single.logicx ~/Music/Remixes/Single.logicx")]
+    Fork {
+        #[arg(value_name = "PATH", help = "Project to fork")]
+        source: PathBuf,
 
-    # Launch console for specific project
-    auxin console ~/Music/MyProject.logicx")]
-    Console {
-        #[arg(
-            value_name = "PATH",
-            help = "Path to Logic Pro project (default: current directory)"
-        )]
-        path: Option<PathBuf>,
+        #[arg(value_name = "NEW_PATH", help = "Where to create the fork")]
+        destination: PathBuf,
     },
 
-    /// Show recent project activity timeline
-    #[command(long_about = "Show recent project activity timeline
+    /// Stage changes to be committed
+    #[command(long_about = "Stage changes to be committed
 
 USAGE:
-    auxin activity [--limit <N>]
+    auxin add --all
+    auxin add <PATHS>...
+    auxin add --all --dry-run
 
 DESCRIPTION:
-    Displays a timeline of recent project activity including commits,
-    lock operations, and comments. Helps teams stay synchronized on
-    project progress.
-
-    Activity types shown:
-      • Commits with metadata (BPM, sample rate, key)
-      • Lock acquisitions/releases
-      • Comments on commits
-      • Branch creations
+    Stages changes in the working directory for the next commit. Similar to 'git add'.
+    Use --all to stage all changes, or specify individual files/directories.
 
-OPTIONS:
-    --limit <N>    Number of recent activities to show (default: 10)
+    --dry-run stages nothing. It lists the changed files that would be
+    staged, and every file or folder left out by .oxenignore together with
+    the rule (pattern and line) that excludes it. Use it when samples or
+    other assets silently aren't being versioned.
 
 EXAMPLES:
-    # Show last 10 activities
-    auxin activity
+    # Stage all changes in the repository
+    auxin add --all
 
-    # Show last 20 activities
-    auxin activity --limit 20")]
-    Activity {
-        #[arg(long, default_value = "10", help = "Number of activities to show")]
-        limit: usize,
-    },
+    # Stage specific files
+    auxin add projectData Alternatives/Take001
 
-    /// Show team members and their contributions
-    #[command(long_about = "Show team members and their contributions
+    # Stage a directory
+    auxin add Resources/
 
-USAGE:
-    auxin team
-
-DESCRIPTION:
-    Discovers team members from commit history and displays their
-    contribution statistics. Helps identify who's working on the project.
-
-    Information shown:
-      • Member name (username@hostname)
-      • Number of commits
-      • Last activity timestamp
-      • Contribution percentage
-
-EXAMPLES:
-    # Show team members
-    auxin team")]
-    Team,
+    # See what would be staged, and why a folder isn't
+    auxin add --all --dry-run
+    auxin add Samples/ --dry-run")]
+    Add {
+        #[arg(value_name = "PATHS", help = "Files or directories to stage")]
+        paths: Vec<PathBuf>,
 
-    /// Manage offline operation queue
-    #[command(subcommand)]
-    Queue(QueueCommands),
+        #[arg(long, short, help = "Stage all changes in the repository")]
+        all: bool,
 
-    /// Manage comments on commits
-    #[command(subcommand)]
-    Comment(CommentCommands),
+        #[arg(long, short = 'n', help = "Show what would be staged and what is ignored")]
+        dry_run: bool,
+    },
 
-    /// Generate shell completion scripts
-    #[command(long_about = "Generate shell completion scripts
+    /// Create a commit with optional project metadata
+    #[command(long_about = "Create a commit with optional project metadata
 
 USAGE:
-    auxin completions <SHELL>
+    auxin commit -m <MESSAGE> [OPTIONS]
 
 DESCRIPTION:
-    Generates shell completion scripts for the specified shell.
-    This enables tab completion for commands, subcommands, and flags.
+    Creates a new commit with the currently staged changes. You can attach
+    project-specific metadata to help track the evolution of your project.
 
-SUPPORTED SHELLS:
-    • bash
-    • zsh
-    • fish
-    • powershell
+    Logic Pro metadata:
+      • BPM (tempo)
+      • Sample rate (Hz)
+      • Key signature
+      • Tags for categorization
 
-INSTALLATION:
-    # Bash (Linux)
-    auxin completions bash > /etc/bash_completion.d/auxin
+    SketchUp metadata:
+      • Units (Inches, Feet, Meters, etc.)
+      • Layer count
+      • Component count
+      • Group count
+      • File size (bytes)
+      • Tags for categorization
 
-    # Bash (macOS with Homebrew)
-    auxin completions bash > /usr/local/etc/bash_completion.d/auxin
+    Blender metadata (read from the .blend with --auto-metadata):
+      • Blender version
+      • Scene, object, mesh, light, camera and material counts
+      • Active scene, render engine, resolution, frame range and FPS
+      • File size
 
-    # Zsh
-    auxin completions zsh > /usr/local/share/zsh/site-functions/_auxin
+    With --auto-metadata the model in the current directory is read: the
+    layer, component and group counts and the file size of a .skp, or the
+    Blender metadata of a .blend (the most recently saved file, if there
+    are several). For SketchUp, values given as options win.
 
-    # Fish
-    auxin completions fish > ~/.config/fish/completions/auxin.fish
+    Custom fields such as client or revision are defined per project type
+    in .auxin/config.toml and set with --field:
 
-    # PowerShell
-    auxin completions powershell > auxin.ps1
+      [[metadata_fields.default]]
+      name = \"client\"
+      required = true
 
-EXAMPLES:
-    # Generate bash completions
-    auxin completions bash
+      [[metadata_fields.logic]]
+      name = \"revision\"
+      type = \"integer\"       # text (default), number, integer or boolean
 
-    # Install for current user (bash)
-    auxin completions bash > ~/.local/share/bash-completion/completions/auxin")]
-    Completions {
-        #[arg(
-            value_name = "SHELL",
-            help = "Shell to generate completions for (bash, zsh, fish, powershell)"
-        )]
-        shell: String,
-    },
+    The commit is refused if a field isn't defined, its value isn't of its
+    type, or a required field is missing.
 
-    /// View operation history and audit trail
-    #[command(subcommand)]
-    History(HistoryCommands),
+EXAMPLES (Logic Pro):
+    # Simple commit
+    auxin commit -m \"Initial project setup\"
 
-    /// Workflow automation and smart suggestions
-    #[command(subcommand)]
-    Workflow(WorkflowCommands),
+    # Commit with audio metadata
+    auxin commit -m \"Added bass line\" --bpm 120 --key \"A Minor\"
 
-    /// Backup and snapshot management
-    #[command(subcommand)]
-    Snapshot(SnapshotCommands),
+    # Full metadata commit
+    auxin commit -m \"Verse 2 complete\" \\
+        --bpm 128 \\
+        --sample-rate 48000 \\
+        --key \"C Major\" \\
+        --tags \"verse,arrangement\"
 
-    /// Recovery guides for common scenarios
-    #[command(subcommand)]
-    Recovery(RecoveryCommands),
+    # Same BPM, key, sample rate and tags as the last commit
+    auxin commit -m \"Mix v4\" --like HEAD
 
-    /// Push commits to remote with progress tracking
-    #[command(long_about = "Push commits to remote with progress tracking
+    # Like an older commit, but at a new tempo
+    auxin commit -m \"Double-time version\" --like abc123 --bpm 240
 
-USAGE:
-    auxin push [OPTIONS]
+    # With custom fields
+    auxin commit -m \"Client mix\" --field client=\"Acme Records\" --field revision=3
 
-DESCRIPTION:
-    Pushes commits to the remote repository with enhanced progress tracking
-    and resume capability. For large files, uploads are chunked to allow
-    resuming interrupted transfers.
+EXAMPLES (SketchUp):
+    # Simple commit
+    auxin commit -m \"Initial model geometry\"
 
-    Features:
-      • Real-time progress display with speed/ETA
-      • Automatic resume of interrupted uploads
-      • Bandwidth estimation
-      • Retry on network failures
+    # Commit with SketchUp metadata
+    auxin commit -m \"Added materials and textures\" \\
+        --units Inches \\
+        --layers 10 \\
+        --components 150
 
-    If no remote or branch is specified, uses 'origin' and the current branch.
+    # Full metadata commit
+    auxin commit -m \"Presentation ready\" \\
+        --units Feet \\
+        --layers 15 \\
+        --components 234 \\
+        --groups 12 \\
+        --tags \"presentation,milestone\"
 
-EXAMPLES:
-    # Push to default remote (origin) and current branch
-    auxin push
+    # Counts and file size read from the model, units given
+    auxin commit -m \"Landscaping pass\" --auto-metadata --units Feet
 
-    # Push to specific remote and branch
-    auxin push --remote origin --branch main
+EXAMPLES (Blender):
+    # Version, scenes, objects and render settings read from the .blend
+    auxin commit -m \"Lighting pass\" --auto-metadata --tags lighting")]
+    Commit {
+        #[arg(short, long, help = "Commit message describing the changes")]
+        message: String,
 
-    # Push with verbose output
-    auxin push --verbose
+        // Logic Pro metadata
+        #[arg(
+            long,
+            value_parser = auxin::music_theory::parse_bpm,
+            help = "[Logic Pro] Beats per minute (tempo) of the project"
+        )]
+        bpm: Option<f32>,
 
-    # Force push (use with caution)
-    auxin push --force")]
-    Push {
-        #[arg(long, short, help = "Remote name (default: origin)")]
-        remote: Option<String>,
+        #[arg(
+            long,
+            help = "[Logic Pro] Sample rate in Hz (e.g., 44100, 48000, 96000)"
+        )]
+        sample_rate: Option<u32>,
 
-        #[arg(long, short, help = "Branch name (default: current branch)")]
-        branch: Option<String>,
+        #[arg(
+            long,
+            value_parser = auxin::music_theory::parse_key,
+            help = "[Logic Pro] Key signature (e.g., 'C Major', 'Am', 'F# Minor')"
+        )]
+        key: Option<String>,
 
-        #[arg(long, help = "Force push (overwrites remote history)")]
-        force: bool,
+        // SketchUp metadata
+        #[arg(
+            long,
+            value_parser = auxin::sketchup_metadata::parse_units,
+            help = "[SketchUp] Model units (e.g., Inches, Feet, Meters, Millimeters, mm)"
+        )]
+        units: Option<String>,
 
-        #[arg(long, short, help = "Show detailed progress")]
-        verbose: bool,
-    },
+        #[arg(long, help = "[SketchUp] Number of layers/tags in the model")]
+        layers: Option<u32>,
 
-    /// Check system environment and dependencies
-    #[command(long_about = "Check system environment and dependencies
+        #[arg(long, help = "[SketchUp] Number of component instances")]
+        components: Option<u32>,
 
-USAGE:
-    auxin doctor
+        #[arg(long, help = "[SketchUp] Number of groups")]
+        groups: Option<u32>,
 
-DESCRIPTION:
-    Validates your environment to ensure Auxin can work correctly.
-    Checks for:
-      • Oxen CLI installation and version
-      • Authentication status with Oxen Hub
-      • Remote repository configuration
-      • Project initialization status
-      • Daemon status
+        #[arg(long, help = "[SketchUp] Model file size in bytes")]
+        file_size: Option<u64>,
 
-    This is the first command to run if you're having issues.
+        #[arg(
+            long,
+            help = "[SketchUp, Blender] Read metadata from the .skp or .blend file"
+        )]
+        auto_metadata: bool,
 
-EXAMPLES:
-    # Check everything
-    auxin doctor
+        // Common metadata
+        #[arg(
+            long,
+            help = "Tags for categorization (comma-separated, e.g., 'mixing,draft' or 'presentation,milestone')"
+        )]
+        tags: Option<String>,
 
-    # Check in current project directory
-    cd MyProject.logicx && auxin doctor")]
-    Doctor,
+        // Audio bounce
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Audio bounce file to attach (WAV, AIFF, MP3, FLAC, M4A)"
+        )]
+        bounce: Option<PathBuf>,
 
-    /// Manage remote repositories
-    #[command(subcommand)]
-    Remote(RemoteCommands),
-}
+        #[arg(
+            long,
+            value_name = "COMMIT",
+            help = "Copy BPM, sample rate, key, tags and units from a commit (ID or HEAD)"
+        )]
+        like: Option<String>,
 
-#[derive(Subcommand)]
-enum CommentCommands {
-    /// Add a comment to a commit
-    #[command(long_about = "Add a comment to a commit
+        #[arg(long, help = "Keep the lock even if [lock] release_on = \"commit\"")]
+        keep_lock: bool,
+
+        #[arg(
+            long = "field",
+            value_name = "NAME=VALUE",
+            help = "Custom metadata field from [metadata_fields] (repeatable)"
+        )]
+        fields: Vec<String>,
+    },
+
+    /// Show commit history
+    #[command(long_about = "Show commit history
 
 USAGE:
-    auxin comment add <COMMIT_ID> <TEXT>
+    auxin log [OPTIONS]
 
 DESCRIPTION:
-    Adds a comment to a specific commit. Comments are stored in the
-    repository and synced across team members.
+    Displays the commit history for the repository, showing commit IDs, authors,
+    timestamps, and messages. Audio metadata (BPM, key, etc.) is displayed if
+    present in the commit message.
 
-    Use cases:
-      • Code review feedback
-      • Mix notes for specific versions
-      • Questions about changes
-      • Track decisions
+    Filter commits by metadata, tags, or date range to find specific versions.
 
 EXAMPLES:
-    # Add comment to latest commit
-    auxin comment add HEAD \"Vocals need more reverb\"
+    # Show all commits
+    auxin log
 
-    # Add comment to specific commit
-    auxin comment add abc123 \"Great mix on this version!\"")]
-    Add {
-        #[arg(value_name = "COMMIT_ID", help = "Commit ID or HEAD")]
-        commit_id: String,
+    # Show only the last 5 commits
+    auxin log --limit 5
 
-        #[arg(value_name = "TEXT", help = "Comment text")]
-        text: String,
+    # Show commits with specific BPM
+    auxin log --bpm 128
+
+    # Show commits with specific tag
+    auxin log --tag mixing
+
+    # Show commits since a date
+    auxin log --since \"2025-01-01\"
+
+    # Show the last week's commits, or those in a range of days
+    auxin log --since 7d
+    auxin log --since 2025-01-01 --until 2025-01-31
+
+    # Combine filters
+    auxin log --bpm 120 --tag vocals --limit 10
+
+    # Output as JSON
+    auxin log --format json --limit 5")]
+    Log {
+        #[arg(short, long, help = "Maximum number of commits to display")]
+        limit: Option<usize>,
+
+        #[arg(long, help = "Filter by BPM (e.g., 120, 128)")]
+        bpm: Option<f32>,
+
+        #[arg(long, help = "Filter by tag (e.g., 'mixing', 'vocals')")]
+        tag: Option<String>,
+
+        #[arg(long, help = "Filter by key signature (e.g., 'C Major')")]
+        key: Option<String>,
+
+        #[arg(long, help = "Show commits since a date (YYYY-MM-DD) or age (e.g. 7d, 2h)")]
+        since: Option<String>,
+
+        #[arg(long, help = "Show commits up to a date (YYYY-MM-DD, inclusive) or age")]
+        until: Option<String>,
+
+        #[arg(long, help = "Output format (text or json)", value_parser = ["text", "json"], default_value = "text")]
+        format: String,
     },
 
-    /// List comments on a commit
-    #[command(long_about = "List comments on a commit
+    /// Restore project to a previous commit
+    #[command(long_about = "Restore project to a previous commit
 
 USAGE:
-    auxin comment list [COMMIT_ID]
+    auxin restore <COMMIT_ID>
 
 DESCRIPTION:
-    Shows all comments on a specific commit. If no commit ID is provided,
-    shows comments on the latest commit (HEAD).
+    Restores the project to the state at the specified commit. This checks out
+    the files from that commit, allowing you to return to a previous version.
+
+    WARNING: Make sure to commit any current changes before restoring, or they
+    will be lost.
+
+    Restored files are then checked against the commit's hashes; any that
+    don't match are restored again. Use --no-verify to skip the check.
+
+    You can find commit IDs using the 'log' command. Afterwards,
+    'auxin what-changed --since-last-restore' lists the files it changed.
 
 EXAMPLES:
-    # Show comments on latest commit
-    auxin comment list
+    # Find commit IDs
+    auxin log --limit 5
 
-    # Show comments on specific commit
-    auxin comment list abc123")]
-    List {
-        #[arg(value_name = "COMMIT_ID", help = "Commit ID or HEAD (optional)")]
-        commit_id: Option<String>,
+    # Restore to a specific commit
+    auxin restore abc123def
+
+    # Restore to a commit (full hash)
+    auxin restore abc123def456789012345678901234567890
+
+    # Restore to a commit someone shared with 'auxin link'
+    auxin restore auxin://repo/studio/Album.logicx/commit/abc123def")]
+    Restore {
+        #[arg(
+            value_name = "COMMIT_ID",
+            help = "Commit ID to restore to (from 'log' command) or commit link"
+        )]
+        commit_id: String,
+
+        #[arg(long, help = "Skip checking restored files against the commit's hashes")]
+        no_verify: bool,
     },
-}
 
-#[derive(Subcommand)]
-enum QueueCommands {
-    /// Show pending operations in the queue
-    #[command(long_about = "Show pending operations in the queue
+    /// Show what the last restore or pull changed
+    #[command(long_about = "Show what the last restore or pull changed
 
 USAGE:
-    auxin queue status
+    auxin what-changed --since-last-restore
 
 DESCRIPTION:
-    Displays all pending operations that are queued due to network unavailability.
-    Shows operation type, priority, queued time, and number of retry attempts.
+    Right before 'auxin restore' and 'auxin pull' change the working copy,
+    auxin records every file's size and modification time, plus a checksum
+    of files up to 1 MB. This compares that record with the project as it
+    is now and lists the files added, removed and modified since.
+
+    Large files (usually audio) aren't checksummed: one with the same size
+    but a new timestamp is listed as possibly modified.
+
+    Edits made after the operation show up too, so run it soon afterwards.
 
 EXAMPLES:
-    # Show all pending operations
-    auxin queue status")]
-    Status,
+    # The mix sounds different after pulling: what did the pull touch?
+    auxin pull
+    auxin what-changed --since-last-restore")]
+    WhatChanged {
+        #[arg(
+            long,
+            required = true,
+            help = "Compare with the working copy before the last restore or pull"
+        )]
+        since_last_restore: bool,
+    },
 
-    /// Manually sync all pending operations
-    #[command(long_about = "Manually sync all pending operations
+    /// Show repository status
+    #[command(long_about = "Show repository status
 
 USAGE:
-    auxin queue sync
+    auxin status
 
 DESCRIPTION:
-    Attempts to execute all pending operations in the queue. Operations are
-    executed in priority order (highest first), then by age (oldest first).
+    Displays the current state of the working directory and staging area:
+      • Staged files (ready to commit)
+      • Modified files (changed but not staged)
+      • Untracked files (new files not yet added)
 
-    Only executes if network connectivity is available. Failed operations
-    remain in the queue for retry.
+    This is similar to 'git status' and helps you see what changes are pending.
 
 EXAMPLES:
-    # Sync all pending operations
-    auxin queue sync")]
-    Sync,
+    # Check repository status
+    auxin status")]
+    Status,
 
-    /// Clear completed operations from the queue
-    #[command(long_about = "Clear completed operations from the queue
+    /// Show detailed information about a commit
+    #[command(long_about = "Show detailed information about a commit
 
 USAGE:
-    auxin queue clear [--all]
+    auxin show <COMMIT_ID>
 
 DESCRIPTION:
-    Removes completed operations from the queue to free up disk space.
-    Use --all to remove ALL operations (including pending).
+    Displays comprehensive information about a specific commit, including:
+      • Full commit message
+      • Audio metadata (BPM, sample rate, key signature, tags)
+      • Author and timestamp
+      • Files changed
+      • Commit statistics
 
 EXAMPLES:
-    # Clear only completed operations
-    auxin queue clear
+    # Show details of a recent commit
+    auxin show abc123f
 
-    # Clear all operations (pending and completed)
-    auxin queue clear --all")]
-    Clear {
-        #[arg(long, help = "Clear all operations including pending")]
-        all: bool,
+    # Show details with full hash
+    auxin show abc123def456789012345678901234567890
+
+    # Show a commit from a link pasted in chat
+    auxin show 'http://192.168.1.20:3000/studio/Album.logicx?commit=abc123f'")]
+    Show {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID or commit link to show details for")]
+        commit_id: String,
     },
 
-    /// Remove a specific operation from the queue
-    #[command(long_about = "Remove a specific operation from the queue
+    /// Print and copy a shareable link to a commit
+    #[command(long_about = "Print and copy a shareable link to a commit
 
 USAGE:
-    auxin queue remove <ENTRY_ID>
+    auxin link [COMMIT_ID] [--no-copy]
 
 DESCRIPTION:
-    Removes a specific operation from the queue by its entry ID.
-    Use 'queue status' to see entry IDs.
+    Prints a canonical reference to a commit, for pasting into chat:
+      auxin://repo/<namespace>/<name>/commit/<id>
+    and a link that opens the commit in the auxin-server web UI. The
+    canonical reference is copied to the clipboard (pbcopy on macOS;
+    wl-copy, xclip or xsel elsewhere).
+
+    'show', 'restore' and 'compare' accept either form in place of a
+    commit ID. The repository is <default_namespace>/<folder name>.
 
 EXAMPLES:
-    # Remove a specific operation
-    auxin queue remove 01234567-89ab-cdef-0123-456789abcdef")]
-    Remove {
-        #[arg(value_name = "ENTRY_ID", help = "Queue entry ID to remove")]
-        entry_id: String,
+    # Link to the latest commit
+    auxin link
+
+    # Link to a specific commit without touching the clipboard
+    auxin link abc123f --no-copy")]
+    Link {
+        #[arg(value_name = "COMMIT_ID", help = "Commit to link to (default: latest)")]
+        commit_id: Option<String>,
+
+        #[arg(long, help = "Print only; don't copy to the clipboard")]
+        no_copy: bool,
     },
-}
 
-#[derive(Subcommand)]
-enum HistoryCommands {
-    /// View recent operation history
-    #[command(long_about = "View recent operation history
+    /// Show changes between commits or working directory
+    #[command(long_about = "Show changes between commits or working directory
 
 USAGE:
-    auxin history view [--limit <N>]
-    auxin history view --repo <PATH>
+    auxin diff [COMMIT_ID]
+    auxin diff <COMMIT_A> <COMMIT_B>
 
 DESCRIPTION:
-    Displays a timeline of all operations performed, including:
-      • Lock operations (acquire, release, renew, break)
-      • Commits and pushes
-      • Authentication events
-      • Comments and activity views
-      • Success/failure status for each operation
+    Shows file-level changes in the repository:
+      • Without arguments: shows changes in working directory vs last commit
+      • With commit ID: shows changes between that commit and working directory
+      • With two IDs: shows changes from the first commit to the second
 
-    Operations are stored persistently and survive restarts.
+    Displays:
+      • Modified files with size changes
+      • Added files
+      • Deleted files
+      • Total size impact
 
-OPTIONS:
-    --limit <N>     Number of recent operations to show (default: 20)
-    --repo <PATH>   Filter by repository path
+    Between two commits, sizes are read from the repository; --json prints
+    the changes as a list.
 
 EXAMPLES:
-    # Show last 20 operations
-    auxin history view
+    # Show uncommitted changes
+    auxin diff
 
-    # Show last 50 operations
-    auxin history view --limit 50
+    # Show changes since specific commit
+    auxin diff abc123f
 
-    # Show operations for specific repository
-    auxin history view --repo /path/to/project.logicx")]
-    View {
-        #[arg(long, default_value = "20", help = "Number of operations to show")]
-        limit: usize,
+    # Compare two commits
+    auxin diff abc123f def456a")]
+    Diff {
+        #[arg(
+            value_name = "COMMIT_ID",
+            help = "Commit ID to compare against (optional)"
+        )]
+        commit_id: Option<String>,
 
-        #[arg(long, value_name = "PATH", help = "Filter by repository path")]
-        repo: Option<PathBuf>,
+        #[arg(
+            value_name = "COMMIT_B",
+            requires = "commit_id",
+            help = "Second commit; compares COMMIT_ID to it instead of the working directory"
+        )]
+        other: Option<String>,
     },
 
-    /// Export history to CSV file
-    #[command(long_about = "Export history to CSV file
+    /// Compare metadata between two commits, or across a range of commits
+    #[command(long_about = "Compare metadata between two commits, or across a range of commits
 
 USAGE:
-    auxin history export <OUTPUT_FILE>
+    auxin compare <COMMIT_A> <COMMIT_B>
+    auxin compare <COMMIT_A> <COMMIT_B> --format json
+    auxin compare --range <FROM>..<TO>
+    auxin compare --last <N> --format csv
 
 DESCRIPTION:
-    Exports complete operation history to a CSV file for analysis,
-    compliance, or reporting. CSV includes:
-      • Timestamp
-      • Operation type
-      • User and machine
-      • Result (success/failure)
-      • Repository path
+    Performs semantic diff between two commits, showing changes in:
+      • Commit message
+      • BPM (tempo)
+      • Sample rate
+      • Key signature
+      • Tags
 
-EXAMPLES:
-    # Export to CSV
-    auxin history export operations.csv
+    This helps understand what changed in the project's audio characteristics
+    between versions, beyond just file changes.
 
-    # Export and open in Excel
-    auxin history export report.csv && open report.csv")]
-    Export {
-        #[arg(value_name = "OUTPUT_FILE", help = "CSV file to write")]
-        output: PathBuf,
-    },
+    With --range or --last, shows how the project evolved instead: one row
+    per commit, oldest first, with BPM, key, Logic Pro track count and the
+    integrated loudness (LUFS) of the commit's bounce. Values that changed
+    since the previous commit are highlighted. Track counts are recorded by
+    'auxin commit'; loudness needs ffmpeg.
 
-    /// Show operation statistics
-    #[command(long_about = "Show operation statistics
+OPTIONS:
+    --format <FORMAT>    Output format: text (default), colored, json, compact
+                         (csv with --range/--last; compact is two-commit only)
+    --plain              Disable colored output
+    --range <FROM..TO>   Commits from FROM to TO, inclusive
+    --last <N>           The N most recent commits
+    --no-loudness        Skip measuring bounces (faster)
 
-USAGE:
-    auxin history stats
+EXAMPLES:
+    # Compare two commits with colored output
+    auxin compare abc123f def456a
 
-DESCRIPTION:
-    Displays statistics about all operations:
-      • Total operations
-      • Success rate
-      • Lock operation count
-      • Network operation count
-      • Failure breakdown
+    # Compare with plain text (no colors)
+    auxin compare abc123f def456a --plain
 
-EXAMPLES:
-    # View statistics
-    auxin history stats")]
-    Stats,
-}
+    # Compare with JSON output
+    auxin compare abc123f def456a --format json
 
-#[derive(Subcommand)]
-enum WorkflowCommands {
-    /// Get smart suggestions based on repository state
-    #[command(long_about = "Get smart suggestions based on repository state
+    # Compare with compact one-line summary
+    auxin compare abc123f def456a --format compact
 
-USAGE:
-    auxin workflow suggest [PATH]
+    # Commit links from 'auxin link' work too
+    auxin compare abc123f auxin://repo/studio/Album.logicx/commit/def456a
 
-DESCRIPTION:
-    Analyzes current repository state and provides context-aware
-    suggestions for next actions. Checks:
-      • Lock status and expiration
-      • Recent operation failures
-      • Uncommitted changes
-      • Recommended workflows
+    # How tempo, key, tracks and loudness moved over the last 5 commits
+    auxin compare --last 5
 
-EXAMPLES:
-    # Get suggestions for current directory
-    auxin workflow suggest
+    # The same for a range, as CSV for a spreadsheet
+    auxin compare --range abc123f..def456a --format csv > evolution.csv")]
+    Compare {
+        #[arg(
+            value_name = "COMMIT_A",
+            required_unless_present_any = ["range", "last"],
+            conflicts_with_all = ["range", "last"],
+            help = "First commit ID or link (older)"
+        )]
+        commit_a: Option<String>,
 
-    # Get suggestions for specific project
-    auxin workflow suggest /path/to/project.logicx")]
-    Suggest {
         #[arg(
-            value_name = "PATH",
-            help = "Repository path (default: current directory)"
+            value_name = "COMMIT_B",
+            required_unless_present_any = ["range", "last"],
+            help = "Second commit ID or link (newer)"
         )]
-        path: Option<PathBuf>,
+        commit_b: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            default_value = "colored",
+            help = "Output format (text, colored, json, compact, csv)"
+        )]
+        format: String,
+
+        #[arg(long, help = "Disable colored output")]
+        plain: bool,
+
+        #[arg(
+            long,
+            value_name = "FROM..TO",
+            conflicts_with = "last",
+            help = "Show the evolution across commits FROM to TO"
+        )]
+        range: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(usize),
+            help = "Show the evolution across the N most recent commits"
+        )]
+        last: Option<usize>,
+
+        #[arg(long, help = "Don't measure bounce loudness (with --range/--last)")]
+        no_loudness: bool,
     },
 
-    /// Run lock renewal daemon (keeps lock alive)
-    #[command(long_about = "Run lock renewal daemon (keeps lock alive)
+    /// Search commit history with advanced filtering
+    #[command(long_about = "Search commit history with advanced filtering
 
 USAGE:
-    auxin workflow lock-daemon <PATH>
+    auxin search <QUERY>
+    auxin search bpm:120-140 key:minor tag:mixing
 
 DESCRIPTION:
-    Starts a background daemon that automatically renews your lock
-    before it expires. Useful for long editing sessions (>4 hours).
+    Smart search across commit history with metadata-based filtering.
+    Supports natural language-style queries with multiple criteria:
 
-    The daemon:
-      • Checks every 15 minutes
-      • Renews when <60 minutes remaining
-      • Records all renewals in history
-      • Stops when lock is released
+    Query Syntax:
+      • bpm:120-140      - BPM range
+      • bpm:>120         - BPM greater than
+      • bpm:<140         - BPM less than
+      • sr:48000         - Exact sample rate
+      • key:minor        - Key signature (fuzzy match)
+      • tag:mixing       - Has tag (single)
+      • tag:mix,vocal    - Has any of these tags (OR logic)
+      • msg:final        - Message contains text
+      • limit:10         - Limit results
+
+    Multiple criteria can be combined (AND logic):
+      bpm:120-140 key:minor tag:mixing
 
 OPTIONS:
-    Daemon runs in foreground. Use '&' to run in background:
-    auxin workflow lock-daemon . &
+    --format <FORMAT>    Output format: list (default), compact, json
+    --ranked             Sort by relevance score
+    --interactive        Pick a result and act on it: show, compare with
+                         HEAD, play its bounce, open it in a sandbox copy
+                         of the project, or restore it
 
 EXAMPLES:
-    # Start lock renewal daemon
-    auxin workflow lock-daemon .
+    # Find all commits between 120-140 BPM
+    auxin search \"bpm:120-140\"
 
-    # Run in background
-    auxin workflow lock-daemon . &")]
-    LockDaemon {
-        #[arg(value_name = "PATH", help = "Repository path")]
-        path: PathBuf,
-    },
+    # Find commits in minor keys with mixing tag
+    auxin search \"key:minor tag:mixing\"
 
-    /// Show current workflow configuration
-    #[command(long_about = "Show current workflow configuration
+    # Find high BPM commits (>140)
+    auxin search \"bpm:>140\"
 
-USAGE:
-    auxin workflow config
+    # Find commits with 'final' in message
+    auxin search \"msg:final\"
 
-DESCRIPTION:
-    Displays current workflow automation settings:
-      • Auto-lock renewal (enabled/disabled)
-      • Lock check interval
-      • Lock renew threshold
-      • Auto-pull on startup
-      • Auto-push after commit
-      • Confirmation prompts
-      • Dry-run mode
+    # Combined search with limit
+    auxin search \"bpm:120-140 key:minor tag:vocals limit:5\"
 
-    Configuration file: ~/.auxin/workflow_config.json
+    # Get compact one-line summaries
+    auxin search \"bpm:>128\" --format compact
 
-EXAMPLES:
-    # View configuration
-    auxin workflow config")]
-    Config,
-}
+    # Ranked by relevance
+    auxin search \"bpm:120-140 tag:mixing\" --ranked
 
-#[derive(Subcommand)]
-enum SnapshotCommands {
-    /// Create a manual backup snapshot
-    #[command(long_about = "Create a manual backup snapshot
+    # Browse results and act on them
+    auxin search \"key:minor tag:mix\" --interactive")]
+    Search {
+        #[arg(value_name = "QUERY", help = "Search query string")]
+        query: String,
 
-USAGE:
-    auxin snapshot create <PATH> [DESCRIPTION]
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            default_value = "list",
+            help = "Output format (list, compact, json)"
+        )]
+        format: String,
 
-DESCRIPTION:
-    Creates a manual backup snapshot of the repository state.
-    Snapshots include:
-      • Current commit ID
-      • Timestamp
-      • Repository path
-      • User description
+        #[arg(long, help = "Sort results by relevance score")]
+        ranked: bool,
 
-    Automatic snapshots are also created before:
-      • Push operations
-      • Pull operations
-      • Lock break operations
-      • Rollback operations
+        #[arg(short, long, help = "Select a result and act on it")]
+        interactive: bool,
+    },
 
-EXAMPLES:
-    # Create snapshot with description
-    auxin snapshot create . \"Before major refactor\"
+    /// Manage project locks for team collaboration
+    #[command(subcommand)]
+    Lock(LockCommands),
 
-    # Create snapshot without description
-    auxin snapshot create /path/to/project.logicx")]
-    Create {
-        #[arg(value_name = "PATH", help = "Repository path")]
-        path: PathBuf,
+    /// Authenticate with Oxen Hub for remote collaboration
+    #[command(subcommand)]
+    Auth(AuthCommands),
 
-        #[arg(value_name = "DESCRIPTION", help = "Optional description")]
-        description: Option<String>,
-    },
+    /// Manage auxin-server connection and configuration
+    #[command(subcommand)]
+    Server(ServerCommands),
 
-    /// List all backup snapshots
-    #[command(long_about = "List all backup snapshots
+    /// Hand a project to another workstation on the LAN
+    #[command(subcommand)]
+    Lan(LanCommands),
+
+    /// Record that a commit was delivered to a client
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    #[command(long_about = "Record that a commit was delivered to a client
 
 USAGE:
-    auxin snapshot list [OPTIONS]
+    auxin deliver <COMMIT> --to <RECIPIENT> [--format <FORMAT>] [-m <NOTE>]
+    auxin deliver list [--to <RECIPIENT>] [--commit <ID>]
 
 DESCRIPTION:
-    Lists all backup snapshots with:
-      • Snapshot ID
-      • Type (manual, auto-before-push, etc.)
-      • Timestamp
-      • Description
-      • Associated commit ID
-      • Repository path
+    Records who a commit was sent to, when, in what format and by whom,
+    along with the commit's bounce file and version label, so there's
+    never any doubt about which version a client has. Deliveries are
+    stored in .auxin/deliveries.json and shown by 'auxin show'.
 
-OPTIONS:
-    --all           Show all snapshots (default: last 20)
-    --repo <PATH>   Filter by repository
+    Pre-deliver hooks (.oxen/hooks/pre-deliver/) run first and can stop
+    the delivery, e.g. the built-in check-loudness hook. Skip them with
+    --no-verify.
 
 EXAMPLES:
-    # List recent snapshots
-    auxin snapshot list
-
-    # List all snapshots
-    auxin snapshot list --all
+    # Record a delivery
+    auxin deliver abc123f --to \"Client X\" --format \"24bit WAV\"
 
-    # List snapshots for specific repository
-    auxin snapshot list --repo /path/to/project.logicx")]
-    List {
-        #[arg(long, help = "Show all snapshots")]
-        all: bool,
+    # With a note
+    auxin deliver abc123f --to \"Label Y\" --format \"MP3 320\" -m \"Radio edit\"
 
-        #[arg(long, value_name = "PATH", help = "Filter by repository")]
-        repo: Option<PathBuf>,
-    },
+    # What did we send Client X?
+    auxin deliver list --to \"Client X\"")]
+    Deliver {
+        #[command(subcommand)]
+        command: Option<DeliverCommands>,
 
-    /// Get restore instructions for a snapshot
-    #[command(long_about = "Get restore instructions for a snapshot
+        #[arg(value_name = "COMMIT", required = true, help = "Commit ID or link that was sent")]
+        commit: Option<String>,
 
-USAGE:
-    auxin snapshot restore <SNAPSHOT_ID>
+        #[arg(long, value_name = "RECIPIENT", required = true, help = "Who received it")]
+        to: Option<String>,
 
-DESCRIPTION:
-    Displays step-by-step instructions for restoring from a snapshot.
-    Does NOT actually perform the restore (dry-run only).
+        #[arg(long, value_name = "FORMAT", help = "What was sent, e.g. '24bit WAV'")]
+        format: Option<String>,
 
-    Instructions include:
-      • Snapshot details
-      • Commit to restore
-      • Commands to run
-      • Warnings about uncommitted changes
+        #[arg(short, long, value_name = "NOTE", help = "Note about the delivery")]
+        message: Option<String>,
 
-EXAMPLES:
-    # Get restore instructions
-    auxin snapshot restore abc123def456")]
-    Restore {
-        #[arg(value_name = "SNAPSHOT_ID", help = "Snapshot ID to restore from")]
-        snapshot_id: String,
+        #[arg(long, help = "Skip pre-deliver hooks")]
+        no_verify: bool,
     },
 
-    /// Delete a snapshot
-    #[command(long_about = "Delete a snapshot
+    /// Label milestone commits with human versions (v1.0, v1.1, ...)
+    #[command(subcommand)]
+    Version(VersionCommands),
+
+    /// Manage audio bounce files for commits
+    #[command(subcommand)]
+    Bounce(BounceCommands),
+
+    /// Manage multi-file stem sets for commits
+    #[command(subcommand)]
+    Stems(StemsCommands),
+
+    /// Compare metadata between two Logic Pro project versions
+    #[command(name = "metadata-diff")]
+    #[command(long_about = "Compare metadata between two Logic Pro project versions
 
 USAGE:
-    auxin snapshot delete <SNAPSHOT_ID>
+    auxin metadata-diff <PROJECT_A> <PROJECT_B>
+    auxin metadata-diff <PROJECT_A> <PROJECT_B> --output json
 
 DESCRIPTION:
-    Permanently deletes a snapshot. Cannot be undone.
+    Analyzes and compares the metadata of two Logic Pro project versions,
+    generating a detailed report of what changed. Detects:
+      • Global changes (tempo, sample rate, key signature)
+      • Track additions, removals, and modifications
+      • EQ changes (frequency, gain, Q factor)
+      • Compressor changes (threshold, ratio, attack, release)
+      • Volume and pan adjustments
+      • Automation curve changes
+      • Plugin parameter changes
 
-    Note: Automatic cleanup keeps only the 50 most recent snapshots.
+    The output is a human-readable report showing exactly what changed
+    between versions, making it easy to understand project evolution.
 
 EXAMPLES:
-    # Delete snapshot
-    auxin snapshot delete abc123def456")]
-    Delete {
-        #[arg(value_name = "SNAPSHOT_ID", help = "Snapshot ID to delete")]
-        snapshot_id: String,
-    },
-}
+    # Compare two project versions
+    auxin metadata-diff Project_v1.logicx Project_v2.logicx
 
-#[derive(Subcommand)]
-enum RecoveryCommands {
-    /// Show recovery guide for failed push
-    #[command(long_about = "Show recovery guide for failed push
+    # Output as JSON for programmatic use
+    auxin metadata-diff Project_v1.logicx Project_v2.logicx --output json
+
+    # Compare with colored output
+    auxin metadata-diff Project_v1.logicx Project_v2.logicx --color
+
+    # Verbose mode with technical details
+    auxin metadata-diff Project_v1.logicx Project_v2.logicx --verbose")]
+    MetadataDiff {
+        #[arg(value_name = "PROJECT_A", help = "First Logic Pro project (.logicx)")]
+        project_a: PathBuf,
+
+        #[arg(value_name = "PROJECT_B", help = "Second Logic Pro project (.logicx)")]
+        project_b: PathBuf,
+
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            default_value = "text",
+            help = "Output format (text or json)"
+        )]
+        output: String,
+
+        #[arg(long, help = "Use colored output (default: auto-detect)")]
+        color: bool,
+
+        #[arg(long, short, help = "Include technical details in output")]
+        verbose: bool,
+    },
+
+    /// Edit the metadata of existing commits
+    #[command(long_about = "Edit the metadata of existing commits
 
 USAGE:
-    auxin recovery push
+    auxin metadata edit <COMMIT> [--set FIELD=VALUE]... [--add-tag TAG]...
+    auxin metadata bulk-edit --filter FIELD:VALUE [--set ...] [--add-tag ...]
+    auxin metadata history [COMMIT]
+    auxin metadata sync
+    auxin metadata migrate
 
 DESCRIPTION:
-    Displays step-by-step recovery guide for push failures.
+    Fixes BPM, key, sample rate or tags on commits that are already made.
+    History is never rewritten: edits are stored in .auxin/metadata_edits.json
+    and applied on top of the commit message by 'auxin log'. Every change is
+    kept in an audit trail ('metadata history').
 
-    Common push failure causes:
-      • Network connectivity issues
-      • Authentication problems
-      • Missing lock
-      • Diverged branches
+    Fields for --set: bpm, sample_rate, key, tags (comma-separated).
+    Filters for --filter: tag:NAME, key:NAME, bpm:N, message:TEXT; repeat
+    --filter to require all of them.
+
+    With server metadata enabled ('auxin config set metadata true') edits
+    are uploaded straight away; 'metadata sync' retries any that failed.
+
+    'auxin commit' also stores each commit's metadata as JSON in
+    .oxen/auxin/metadata/, which log, search and compare read instead of
+    the message. 'metadata migrate' fills that in for older commits and
+    ones pulled from elsewhere.
 
 EXAMPLES:
-    # Show push recovery guide
-    auxin recovery push")]
-    Push,
+    auxin metadata edit abc123 --set key=\"D Minor\"
+    auxin metadata bulk-edit --filter tag:live --add-tag mixed
+    auxin metadata bulk-edit --filter \"key:D Major\" --set \"key=D Minor\" --dry-run
+    auxin metadata history abc123
+    auxin metadata migrate")]
+    #[command(subcommand)]
+    Metadata(MetadataCommands),
 
-    /// Show recovery guide for failed pull
-    #[command(long_about = "Show recovery guide for failed pull
+    /// Control the background daemon service
+    #[command(subcommand)]
+    Daemon(DaemonCommands),
+
+    /// Manage workflow automation hooks
+    #[command(long_about = "Manage workflow automation hooks
 
 USAGE:
-    auxin recovery pull
+    auxin hooks init
+    auxin hooks list
+    auxin hooks install <HOOK_NAME>
 
 DESCRIPTION:
-    Displays step-by-step recovery guide for pull failures.
+    Workflow automation hooks allow you to run custom scripts before and after
+    commits. Use hooks for:
 
-    Common pull failure causes:
-      • Network connectivity issues
-      • Authentication problems
-      • Uncommitted local changes
-      • Merge conflicts
+    Pre-commit hooks (run before creating commit):
+      • Validate metadata completeness
+      • Check file sizes
+      • Run linting or formatting
+      • Verify project structure
+
+    Post-commit hooks (run after successful commit):
+      • Send notifications
+      • Create backups
+      • Trigger CI/CD pipelines
+      • Update tracking systems
 
 EXAMPLES:
-    # Show pull recovery guide
-    auxin recovery pull")]
-    Pull,
+    # Initialize hooks directory
+    auxin hooks init
 
-    /// Show recovery guide for lock conflicts
-    #[command(long_about = "Show recovery guide for lock conflicts
+    # List all installed hooks
+    auxin hooks list
+
+    # List available built-in hooks
+    auxin hooks builtins
+
+    # Install a built-in hook
+    auxin hooks install validate-metadata
+
+    # Install a post-commit hook
+    auxin hooks install backup --hook-type post-commit
+
+    # Remove a hook
+    auxin hooks remove validate-metadata")]
+    #[command(subcommand)]
+    Hooks(HooksCommands),
+
+    /// Trigger commits and locks from OSC messages
+    #[command(long_about = "Trigger commits and locks from OSC messages
 
 USAGE:
-    auxin recovery lock
+    auxin osc listen [--bind <ADDR>] [--project <PATH>]...
+    auxin osc send <ADDRESS> [ARGS]... [--to <ADDR>]
 
 DESCRIPTION:
-    Displays step-by-step recovery guide for lock conflicts.
+    Lets a control surface, TouchOSC layout, or your DAW's OSC output drive
+    auxin without leaving the session. Enable it in ~/.auxin/config.toml:
 
-    Common lock conflict scenarios:
-      • Lock held by another user
-      • Expired lock not auto-released
-      • Stale lock after crash
+        [integrations.osc]
+        enabled = true
+        bind = \"127.0.0.1:9050\"
+
+        [integrations.osc.addresses]
+        \"/auxin/milestone\" = \"milestone\"
+        \"/auxin/commit\" = \"commit\"
+        \"/auxin/lock/toggle\" = \"toggle-lock\"
+
+    Actions:
+      • milestone    Commit tagged 'milestone' with the project's BPM,
+                     sample rate and key
+      • commit       Plain checkpoint commit
+      • toggle-lock  Release your lock, or acquire one if you don't hold it
+
+    The first string argument of a message picks the project (folder name or
+    path), the second sets the commit message. The daemon runs the listener
+    for all monitored projects when the integration is enabled.
 
 EXAMPLES:
-    # Show lock recovery guide
-    auxin recovery lock")]
-    Lock,
-}
+    # Listen for the current project
+    auxin osc listen
 
-#[derive(Subcommand)]
-enum RemoteCommands {
-    /// Add a remote repository
-    #[command(long_about = "Add a remote repository
+    # Test the mapping from another terminal
+    auxin osc send /auxin/milestone MySong \"Final vocal comp\"")]
+    #[command(subcommand)]
+    Osc(OscCommands),
+
+    /// Commit everything with automatic message, metadata and bounce
+    #[command(long_about = "Commit everything with automatic message, metadata and bounce
 
 USAGE:
-    auxin remote add <NAME> <URL>
+    auxin quick-commit [PATH] [--notify]
 
 DESCRIPTION:
-    Adds a remote repository URL. This is typically your Oxen Hub repository
-    where you push and pull changes for collaboration.
+    Single-shot commit for Stream Deck buttons and keyboard macros. Stages
+    all changes and commits them as \"Quick save at <time>\" with the
+    project's current BPM, sample rate and key. The newest file in the
+    Bounces folder that isn't attached to a commit yet is attached as the
+    commit's bounce.
+
+    Prints nothing on success and exits non-zero on failure. With --notify
+    the result is shown as a desktop notification instead.
 
 EXAMPLES:
-    # Add Oxen Hub remote
-    auxin remote add origin https://hub.oxen.ai/username/myproject")]
-    Add {
-        #[arg(value_name = "NAME", help = "Name for the remote (typically 'origin')")]
-        name: String,
+    # Stream Deck \"System: Open\" action
+    auxin quick-commit ~/Music/MySong.logicx --notify")]
+    QuickCommit {
+        #[arg(
+            value_name = "PATH",
+            help = "Path to the project (default: current directory)"
+        )]
+        path: Option<PathBuf>,
 
-        #[arg(value_name = "URL", help = "URL of the remote repository")]
-        url: String,
+        /// Report the result as a desktop notification
+        #[arg(long)]
+        notify: bool,
     },
 
-    /// List configured remotes
-    #[command(long_about = "List configured remotes
+    /// Toggle the project lock
+    #[command(long_about = "Toggle the project lock
 
 USAGE:
-    auxin remote list
+    auxin quick-lock [PATH] [--notify]
 
 DESCRIPTION:
-    Shows all configured remote repositories for the current project.
+    Releases your lock on the project, or acquires one if nobody holds it.
+    Fails without changing anything when someone else holds the lock.
+
+    Prints nothing on success and exits non-zero on failure. With --notify
+    the result is shown as a desktop notification instead.
 
 EXAMPLES:
-    # List all remotes
-    auxin remote list")]
-    List,
+    auxin quick-lock ~/Music/MySong.logicx --notify")]
+    QuickLock {
+        #[arg(
+            value_name = "PATH",
+            help = "Path to the project (default: current directory)"
+        )]
+        path: Option<PathBuf>,
 
-    /// Remove a remote
-    #[command(long_about = "Remove a remote
+        /// Report the result as a desktop notification
+        #[arg(long)]
+        notify: bool,
+    },
+
+    /// Print a one-line project status
+    #[command(long_about = "Print a one-line project status
 
 USAGE:
-    auxin remote remove <NAME>
+    auxin quick-status [PATH] [--notify]
 
 DESCRIPTION:
-    Removes a remote repository configuration.
+    Summarises branch, uncommitted changes, lock holder and the last commit
+    on a single line:
+
+        MySong.logicx · main · 3 changes · locked by you · last: Vocal comp
+
+    With --notify the line is shown as a desktop notification and nothing
+    is printed.
 
 EXAMPLES:
-    # Remove a remote
-    auxin remote remove origin")]
-    Remove {
-        #[arg(value_name = "NAME", help = "Name of the remote to remove")]
-        name: String,
+    auxin quick-status ~/Music/MySong.logicx --notify")]
+    QuickStatus {
+        #[arg(
+            value_name = "PATH",
+            help = "Path to the project (default: current directory)"
+        )]
+        path: Option<PathBuf>,
+
+        /// Show the status as a desktop notification instead of printing it
+        #[arg(long)]
+        notify: bool,
     },
-}
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+    /// Run an operation from an auxin:// URL
+    #[command(long_about = "Run an operation from an auxin:// URL
 
-    // Enable verbose logging if requested
-    logger::set_verbose(cli.verbose);
+USAGE:
+    auxin handle-url <URL> [--notify]
 
-    match cli.command {
-        Commands::Init {
-            path,
-            r#type,
-            logic,
-        } => {
-            vlog!("Starting initialization for path: {}", path.display());
+DESCRIPTION:
+    Auxin.app registers the auxin:// URL scheme and passes every URL it
+    receives to this command, so Apple Shortcuts, Raycast and Alfred can
+    drive auxin by opening a link.
+
+    URLs:
+      • auxin://commit                Quick save (like quick-commit)
+      • auxin://commit?message=...    Stage everything and commit; also
+                                      takes bpm, sample_rate, key and tags
+      • auxin://milestone?message=... Commit tagged 'milestone'
+      • auxin://lock/acquire          Acquire the lock; takes timeout (hours)
+      • auxin://lock/release          Release your lock
+      • auxin://lock/toggle           Release or acquire (like quick-lock)
+      • auxin://status                One-line status (like quick-status)
+      • auxin://repo/<ns>/<name>/commit/<id>
+                                      A link from 'auxin link': the commit,
+                                      looked up in the project
+
+    Every URL takes project=<path>; without it the current directory is used.
+
+    Any web page can open an auxin:// link, so only auxin://status and
+    commit links run straight away. The others change the project and are
+    confirmed first: Auxin.app asks before passing --yes, and a terminal
+    prompts.
 
-            // Determine project type (handle backward compatibility with --logic flag)
-            let project_type = if logic {
-                vlog!("Using legacy --logic flag, treating as LogicPro");
-                ProjectType::LogicPro
-            } else if let Some(type_str) = r#type {
-                ProjectType::parse(&type_str).unwrap_or_else(|| {
-                    progress::error(&format!(
-                        "Unknown project type: {}. Supported types: auto, logicpro, sketchup",
-                        type_str
-                    ));
-                    std::process::exit(1);
-                })
-            } else {
-                // Auto-detect based on file extension
-                let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                match extension {
-                    "logicx" => {
-                        vlog!("Auto-detected Logic Pro project (.logicx)");
-                        ProjectType::LogicPro
-                    }
-                    "skp" => {
-                        vlog!("Auto-detected SketchUp project (.skp)");
-                        ProjectType::SketchUp
-                    }
-                    _ => {
-                        vlog!("No specific project type detected, using generic init");
-                        ProjectType::Auto
-                    }
-                }
-            };
+EXAMPLES:
+    auxin handle-url \"auxin://commit?message=Vocal%20comp&bpm=120&project=~/Music/MySong.logicx\"
+    auxin handle-url auxin://lock/toggle?project=~/Music/MySong.logicx --notify --yes")]
+    HandleUrl {
+        #[arg(value_name = "URL", help = "auxin:// URL to handle")]
+        url: String,
 
-            vlog!("Project type: {:?}", project_type);
+        /// Report the result as a desktop notification
+        #[arg(long)]
+        notify: bool,
 
-            match project_type {
-                ProjectType::LogicPro => {
-                    let pb = progress::spinner("Validating Logic Pro project structure...");
-                    vlog!("Initializing Logic Pro project repository...");
-                    let _repo = OxenRepository::init_for_logic_project(&path).await?;
+        /// Run an action that changes the project without confirming
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-                    progress::finish_success(&pb, "Logic Pro project repository initialized");
-                    println!();
-                    progress::success(&format!("Repository created at: {}", path.display()));
-                    progress::success("Initial commit created on main branch");
-                    progress::success("Draft branch created and checked out");
-                    println!();
-                    progress::info("You're all set! Start working in Logic Pro:");
-                    println!("  • Changes will be automatically tracked on the draft branch");
-                    println!(
-                        "  • Create milestone commits: auxin commit -m \"Your message\" --bpm 120"
-                    );
-                    println!("  • View history: auxin log");
-                    println!("  • Restore to any commit: auxin restore <commit-id>");
-                }
-                ProjectType::SketchUp => {
-                    let pb = progress::spinner("Validating SketchUp project structure...");
-                    vlog!("Detecting SketchUp project...");
+    /// Make commit history searchable from Spotlight
+    #[command(long_about = "Make commit history searchable from Spotlight
 
-                    // Validate SketchUp project
-                    let _skp_project = SketchUpProject::detect(&path)?;
+USAGE:
+    auxin spotlight sync [PATH]
+    auxin spotlight gc
+    auxin spotlight clear [PATH]
 
-                    vlog!("Initializing SketchUp project repository...");
-                    let _repo = OxenRepository::init(&path).await?;
+DESCRIPTION:
+    Writes a small .auxinmeta text file per commit with its message, BPM,
+    key, sample rate and tags, so macOS Spotlight can find \"project commits
+    in A minor at 140 BPM\". Sidecars are kept in
+    ~/Library/Caches/Metadata/Auxin, outside your project.
 
-                    progress::finish_success(&pb, "SketchUp project repository initialized");
-                    println!();
-                    progress::success(&format!("Repository created at: {}", path.display()));
-                    progress::success("Initial commit created on main branch");
-                    progress::success("Draft branch created and checked out");
-                    println!();
-                    progress::info("You're all set! Start working in SketchUp:");
-                    println!("  • Changes will be automatically tracked on the draft branch");
-                    println!("  • Create milestone commits: auxin commit -m \"Your message\" --units Inches --layers 10");
-                    println!("  • View history: auxin log");
-                    println!("  • Restore to any commit: auxin restore <commit-id>");
-                }
-                ProjectType::Blender => {
-                    let pb = progress::spinner("Validating Blender project structure...");
-                    vlog!("Detecting Blender project...");
+    Opt in with:
 
-                    // Validate Blender project
-                    let _blend_project = BlenderProject::detect(&path)?;
+        [integrations.spotlight]
+        enabled = true
 
-                    vlog!("Initializing Blender project repository...");
-                    let _repo = OxenRepository::init(&path).await?;
+    New commits then get a sidecar automatically. 'sync' covers existing
+    history, 'gc' removes sidecars for deleted projects and commits, and
+    'clear' removes a project's sidecars.
 
-                    progress::finish_success(&pb, "Blender project repository initialized");
-                    println!();
-                    progress::success(&format!("Repository created at: {}", path.display()));
-                    progress::success("Initial commit created on main branch");
-                    progress::success("Draft branch created and checked out");
-                    println!();
-                    progress::info("You're all set! Start working in Blender:");
-                    println!("  • Changes will be automatically tracked on the draft branch");
-                    println!("  • Create milestone commits: auxin commit -m \"Your message\"");
-                    println!("  • View history: auxin log");
-                    println!("  • Restore to any commit: auxin restore <commit-id>");
-                }
-                ProjectType::Auto => {
-                    let pb = progress::spinner(&format!(
-                        "Initializing Oxen repository at {}...",
-                        path.display()
-                    ));
-                    vlog!("Initializing generic Oxen repository...");
-                    let _repo = OxenRepository::init(&path).await?;
+EXAMPLES:
+    auxin spotlight sync ~/Music/MySong.logicx
+    auxin spotlight gc")]
+    #[command(subcommand)]
+    Spotlight(SpotlightCommands),
 
-                    progress::finish_success(
-                        &pb,
-                        &format!("Oxen repository initialized at: {}", path.display()),
-                    );
-                }
-            }
-            Ok(())
-        }
+    /// Render a Quick Look-able summary of a commit
+    #[command(long_about = "Render a Quick Look-able summary of a commit
 
-        Commands::Clone {
-            remote_url,
-            destination,
-        } => {
-            vlog!("Starting clone operation");
-            vlog!("Remote URL: {}", remote_url);
-            vlog!("Destination: {}", destination.display());
+USAGE:
+    auxin session-sheet [COMMIT] [--open]
 
-            // Check if oxen is available
-            let oxen = OxenSubprocess::new();
-            if !oxen.is_available() {
-                progress::error("oxen CLI not found. Please install: pip install oxen-ai");
-                std::process::exit(1);
-            }
+DESCRIPTION:
+    Writes a one-page HTML session sheet for a commit: message, BPM, key,
+    sample rate, bounce waveform, project thumbnail and comments. Sheets go
+    to 'Session Sheets/' in the project folder (next to the .logicx package
+    for Logic Pro), so collaborators can preview a version in Finder with
+    Quick Look without running auxin.
 
-            // Show progress
-            let pb = progress::spinner(&format!("Cloning from {}...", remote_url));
+    With [integrations.session_sheets] enabled = true, a sheet is rendered
+    automatically for every commit tagged 'milestone'; those sheets also
+    list the files the commit changed. The waveform needs ffmpeg.
 
-            // Perform the clone
-            match OxenRepository::clone(&remote_url, &destination).await {
-                Ok(_repo) => {
-                    progress::finish_success(&pb, "Repository cloned successfully");
-                    println!();
-                    progress::success(&format!("Project cloned to: {}", destination.display()));
-                    progress::success("All history and files downloaded");
-                    println!();
+EXAMPLES:
+    # Sheet for the latest commit
+    auxin session-sheet
 
-                    // Detect project type and give relevant next steps
-                    let extension = destination
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .unwrap_or("");
-                    match extension {
-                        "logicx" => {
-                            progress::info("Logic Pro project cloned successfully!");
-                            println!("  • Open in Logic Pro: {}", destination.display());
-                            println!(
-                                "  • View history: cd {} && auxin log",
-                                destination.display()
-                            );
-                            println!(
-                                "  • Create commits: auxin commit -m \"Your message\" --bpm 120"
-                            );
-                        }
-                        "skp" => {
-                            progress::info("SketchUp project cloned successfully!");
-                            println!("  • Open in SketchUp: {}", destination.display());
-                            println!(
-                                "  • View history: cd {} && auxin log",
-                                destination.display()
-                            );
-                            println!("  • Create commits: auxin commit -m \"Your message\" --units Inches");
-                        }
-                        "blend" => {
-                            progress::info("Blender project cloned successfully!");
-                            println!("  • Open in Blender: {}", destination.display());
-                            println!(
-                                "  • View history: cd {} && auxin log",
-                                destination.display()
-                            );
-                            println!("  • Create commits: auxin commit -m \"Your message\"");
-                        }
-                        _ => {
-                            progress::info("Project cloned successfully!");
-                            println!("  • Navigate to: cd {}", destination.display());
-                            println!("  • View history: auxin log");
-                            println!(
-                                "  • Make changes and commit: auxin commit -m \"Your message\""
-                            );
-                        }
-                    }
-                }
-                Err(e) => {
-                    progress::finish_error(&pb, "Clone failed");
-                    progress::error(&format!("Failed to clone repository: {}", e));
+    # Sheet for an older commit, then open it
+    auxin session-sheet abc123 --open")]
+    SessionSheet {
+        #[arg(value_name = "COMMIT", help = "Commit ID or prefix (default: latest commit)")]
+        commit: Option<String>,
 
-                    // Provide helpful error messages based on error type
-                    if e.to_string().contains("already exists") {
-                        println!();
-                        progress::info("The destination directory already exists.");
-                        println!("  • Choose a different destination, or");
-                        println!("  • Remove the existing directory and try again");
-                    } else if e.to_string().contains("Network")
-                        || e.to_string().contains("timed out")
-                    {
-                        println!();
-                        progress::info("Network error occurred.");
-                        println!("  • Check your internet connection");
-                        println!("  • Verify the remote URL is correct");
-                        println!("  • Try again in a few moments");
-                    } else if e.to_string().contains("not found") || e.to_string().contains("404") {
-                        println!();
-                        progress::info("Repository not found.");
-                        println!("  • Verify the remote URL is correct");
-                        println!("  • Check if you have access to the repository");
-                        println!("  • For private repos, make sure you're authenticated");
-                    }
+        /// Open the sheet after writing it
+        #[arg(long)]
+        open: bool,
+    },
 
-                    std::process::exit(1);
-                }
-            }
+    /// Explain how a file is versioned
+    #[command(long_about = "Explain how a file is versioned
 
-            Ok(())
-        }
+USAGE:
+    auxin describe <PATH> [--limit <N>]
 
-        Commands::Add { paths, all } => {
-            let repo = OxenRepository::new(".");
+DESCRIPTION:
+    Reports everything auxin knows about one file or folder:
 
-            if all {
-                let pb = progress::spinner("Staging all changes...");
-                repo.stage_all().await?;
-                progress::finish_success(&pb, "All changes staged");
-                println!();
-                progress::info("Next step: auxin commit -m \"Your message\"");
-            } else {
-                if paths.is_empty() {
-                    progress::error("Please provide paths to stage or use --all");
-                    std::process::exit(1);
-                }
-                let pb = progress::spinner(&format!("Staging {} file(s)...", paths.len()));
-                repo.stage_changes(paths).await?;
-                progress::finish_success(&pb, "Files staged");
-            }
+    - whether it is tracked, modified, staged, untracked or ignored, and
+      which .oxenignore pattern ignores it
+    - the last commit that changed it and how its size changed over the
+      most recent commits
+    - the project lock (locks cover the whole project, not single files)
+    - offline-queue operations still waiting to sync the project
 
-            Ok(())
-        }
+    Useful when a file isn't showing up in commits, or to find when a
+    sample or bounce last changed.
 
-        Commands::Commit {
-            message,
-            bpm,
-            sample_rate,
-            key,
-            units,
-            layers,
-            components,
-            groups,
-            file_size,
-            tags,
-            bounce,
-        } => {
-            let pb = progress::spinner("Preparing commit...");
-            let repo = OxenRepository::new(".");
+EXAMPLES:
+    auxin describe \"Audio Files/Lead Vocal.wav\"
+    auxin describe Bounces/mix.wav --limit 50")]
+    Describe {
+        #[arg(value_name = "PATH", help = "File or folder inside the project")]
+        path: PathBuf,
 
-            // Detect if we're using Logic Pro or SketchUp metadata
-            let has_logic_metadata = bpm.is_some() || sample_rate.is_some() || key.is_some();
-            let has_sketchup_metadata = units.is_some()
-                || layers.is_some()
-                || components.is_some()
-                || groups.is_some()
-                || file_size.is_some();
+        /// Number of recent commits to check for size history
+        #[arg(long, default_value_t = auxin::describe::DEFAULT_HISTORY_LIMIT)]
+        limit: usize,
+    },
 
-            let formatted_message = if has_sketchup_metadata {
-                // SketchUp project - use SketchUpMetadata
-                vlog!("Using SketchUp metadata");
-                let mut skp_metadata = SketchUpMetadata::new(message.clone());
+    /// Move or rename a file or folder, keeping its history
+    #[command(long_about = "Move or rename a file or folder, keeping its history
 
-                if let Some(ref units_val) = units {
-                    skp_metadata = skp_metadata.with_units(units_val.clone());
-                }
+USAGE:
+    auxin mv <FROM> <TO>
 
-                if let Some(layer_count) = layers {
-                    skp_metadata = skp_metadata.with_layer_count(layer_count);
-                }
+DESCRIPTION:
+    Moves the file or folder, stages both the removal and the addition, and
+    records the move in .auxin/renames.json so 'auxin describe' and
+    'auxin metadata-diff' follow it instead of seeing a deleted file and an
+    unrelated new one. Commit afterwards as usual.
 
-                if let Some(component_count) = components {
-                    skp_metadata = skp_metadata.with_component_count(component_count);
-                }
+    Refuses to overwrite an existing path, to move anything outside the
+    project, and to move files while someone else holds the project lock.
 
-                if let Some(group_count) = groups {
-                    skp_metadata = skp_metadata.with_group_count(group_count);
-                }
+EXAMPLES:
+    auxin mv \"Samples/kick.wav\" \"Audio Files/Kick 1.wav\"
+    auxin mv Alternatives/000 Alternatives/001")]
+    Mv {
+        #[arg(value_name = "FROM", help = "File or folder to move")]
+        from: PathBuf,
+
+        #[arg(value_name = "TO", help = "New path (must not exist)")]
+        to: PathBuf,
+    },
 
-                if let Some(size) = file_size {
-                    skp_metadata = skp_metadata.with_file_size(size);
-                }
+    /// Describe uncommitted changes as a one-line commit title
+    #[command(long_about = "Describe uncommitted changes as a one-line commit title
 
-                if let Some(ref tags_str) = tags {
-                    for tag in tags_str.split(',') {
-                        skp_metadata = skp_metadata.with_tag(tag.trim());
-                    }
-                }
+USAGE:
+    auxin auto-title [PATH]
 
-                skp_metadata.format_commit_message()
-            } else if has_logic_metadata {
-                // Logic Pro project - use CommitMetadata
-                vlog!("Using Logic Pro metadata");
-                let mut metadata = CommitMetadata::new(message.clone());
+DESCRIPTION:
+    Prints a title for the project's uncommitted changes, such as
+    \"Modified: Lead Vox comp, added Drum Bus Channel EQ\". The daemon uses it
+    as the message for auto-commits.
 
-                if let Some(bpm) = bpm {
-                    metadata = metadata.with_bpm(bpm);
-                }
+    Logic Pro projects are compared with the state recorded at the last
+    commit, so the title names tracks, regions and plugins. Other projects
+    list the changed files.
 
-                if let Some(sr) = sample_rate {
-                    metadata = metadata.with_sample_rate(sr);
-                }
+    Prints nothing when there is nothing to describe or when
+    [daemon] auto_titles is turned off in config.toml.
 
-                if let Some(ref key_val) = key {
-                    metadata = metadata.with_key_signature(key_val.clone());
-                }
+EXAMPLES:
+    auxin auto-title
+    auxin auto-title ~/Music/Song.logicx")]
+    AutoTitle {
+        #[arg(value_name = "PATH", help = "Project directory (default: current directory)")]
+        path: Option<PathBuf>,
+    },
 
-                if let Some(ref tags_str) = tags {
-                    for tag in tags_str.split(',') {
-                        metadata = metadata.with_tag(tag.trim());
-                    }
-                }
+    /// Pause auto-commits when a change looks like data loss
+    #[command(long_about = "Pause auto-commits when a change looks like data loss
 
-                metadata.format_commit_message()
-            } else {
-                // No metadata, just use message
-                vlog!("No metadata provided, using plain message");
-                if let Some(ref tags_str) = tags {
-                    let mut msg = message.clone();
-                    msg.push_str(&format!("\n\nTags: {}", tags_str));
-                    msg
-                } else {
-                    message.clone()
-                }
-            };
+USAGE:
+    auxin anomaly check [PATH]
+    auxin anomaly status [PATH]
+    auxin anomaly resume [PATH]
 
-            pb.set_message("Creating commit...");
-            let commit_metadata = CommitMetadata::new(formatted_message);
-            let commit_id = repo.create_commit(commit_metadata).await?;
+DESCRIPTION:
+    The daemon runs 'auxin anomaly check' before every auto-commit. It
+    compares the project with its state at the last commit and flags
+    changes that look like data loss rather than work:
 
-            progress::finish_success(&pb, &format!("Commit created: {}", commit_id));
+      • The project shrank by 90% or more
+      • A Logic Pro ProjectData file became empty or disappeared
+      • 100 or more files were deleted
 
-            // Store metadata on server if configured
-            let config = auxin_config::load_config().unwrap_or_default();
-            if config.cli.use_server_metadata {
-                let server_config = ServerConfig {
-                    url: config.cli.url.clone(),
-                    token: if config.cli.token.is_empty() { None } else { Some(config.cli.token.clone()) },
-                    timeout_secs: config.cli.timeout_secs as u64,
-                };
+    When one is found, auto-commits for the project are paused, a safety
+    snapshot pointing at the last good commit is recorded, and you get a
+    notification, so the broken state never gets versioned on top of good
+    history. Manual 'auxin commit' still works.
 
-                if let Ok(client) = AuxinServerClient::new(server_config) {
-                    let namespace = config.cli.default_namespace.clone();
-                    let current_dir = std::env::current_dir()?;
-                    let repo_name = current_dir
-                        .file_name()
-                        .map(|s| s.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "unknown".to_string());
+    Check what happened with 'status'. Restore the last good commit if
+    the change was an accident, then 'resume' to start auto-commits again;
+    resuming accepts the project as it is now.
 
-                    // Build server metadata
-                    let server_metadata = auxin::server_client::LogicProMetadata {
-                        bpm: bpm.map(|b| b as f64),
-                        sample_rate,
-                        key_signature: key.clone(),
-                        tags: tags
-                            .as_ref()
-                            .map(|t| t.split(',').map(|s| s.trim().to_string()).collect()),
-                        custom: None,
-                    };
+    'check' exits with code 3 while auto-commits are paused. Thresholds
+    are set in the [daemon] config section.
 
-                    match client.store_metadata(
-                        &namespace,
-                        &repo_name,
-                        &commit_id,
-                        &server_metadata,
-                    ) {
-                        Ok(()) => {
-                            vlog!("Metadata stored on server for commit {}", commit_id);
-                        }
-                        Err(e) => {
-                            vlog!("Failed to store metadata on server: {}", e);
-                        }
-                    }
-                }
-            }
+EXAMPLES:
+    auxin anomaly status
+    auxin restore abc123f && auxin anomaly resume")]
+    #[command(subcommand)]
+    Anomaly(AnomalyCommands),
 
-            // Show commit details
-            println!();
-            progress::info("Commit Details:");
-            println!("  Message: {}", message);
+    /// Show the auto-stage policy for a project
+    #[command(long_about = "Show the auto-stage policy for a project
 
-            // Show Logic Pro metadata
-            if let Some(ref bpm_val) = bpm {
-                println!("  BPM: {}", bpm_val);
-            }
-            if let Some(ref sr_val) = sample_rate {
-                println!("  Sample Rate: {} Hz", sr_val);
-            }
-            if let Some(ref key_val) = key {
-                println!("  Key: {}", key_val);
-            }
+USAGE:
+    auxin policy show [PATH]
 
-            // Show SketchUp metadata
-            if let Some(ref units_val) = units {
-                println!("  Units: {}", units_val);
-            }
-            if let Some(layer_count) = layers {
-                println!("  Layers: {}", layer_count);
-            }
-            if let Some(component_count) = components {
-                println!("  Components: {}", component_count);
-            }
-            if let Some(group_count) = groups {
-                println!("  Groups: {}", group_count);
-            }
-            if let Some(size) = file_size {
-                let size_mb = size as f64 / (1024.0 * 1024.0);
-                println!("  File Size: {:.2} MB", size_mb);
-            }
+DESCRIPTION:
+    'auxin add --all', 'auxin quick-commit' and the daemon's auto-commits
+    stage changes according to a staging policy, set per project type in
+    the config file:
 
-            // Show common metadata
-            if let Some(ref tags_val) = tags {
-                println!("  Tags: {}", tags_val);
-            }
+        [staging.logic]
+        always = [\"Alternatives/\", \"**/ProjectData\"]
+        never = [\"Freeze Files/\", \"Bounces/\"]
 
-            // Extract thumbnail for Logic Pro projects
-            if has_logic_metadata {
-                let current_dir = std::env::current_dir()?;
-                let thumbnail_manager = ThumbnailManager::new(&current_dir);
+        [staging.default]
+        never = [\"*.bak\"]
 
-                // Try to detect Logic Pro project and extract thumbnail
-                if let Ok(logic_project) = LogicProject::detect(&current_dir) {
-                    let pb = progress::spinner("Extracting project thumbnail...");
-                    match thumbnail_manager.extract_logic_thumbnail(&commit_id, &logic_project.path)
-                    {
-                        Ok(thumb_metadata) => {
-                            progress::finish_success(&pb, "Thumbnail extracted");
-                            if let Some(width) = thumb_metadata.width {
-                                if let Some(height) = thumb_metadata.height {
-                                    println!(
-                                        "  Thumbnail: {}x{} ({})",
-                                        width, height, thumb_metadata.format
-                                    );
-                                } else {
-                                    println!("  Thumbnail: {}", thumb_metadata.format);
-                                }
-                            } else {
-                                println!("  Thumbnail: {}", thumb_metadata.format);
-                            }
-                        }
-                        Err(e) => {
-                            // Don't fail the commit if thumbnail extraction fails
-                            vlog!("Could not extract thumbnail: {}", e);
-                        }
-                    }
-                }
-            }
+    Types are logic, sketchup, blender and other; [staging.default] applies
+    to every project. Patterns use .oxenignore syntax. 'never' keeps
+    changed files out of automatic staging (add them by path if needed);
+    'always' stages files even when .oxenignore excludes them.
 
-            // Process bounce file if provided
-            if let Some(bounce_path) = bounce {
-                let current_dir = std::env::current_dir()?;
-                let bounce_manager = BounceManager::new(&current_dir);
+    'show' prints the detected project type, every rule in effect and the
+    section it comes from. 'auxin add --all --dry-run' shows which files
+    the rules affect right now.
 
-                let pb = progress::spinner("Adding bounce file...");
-                match bounce_manager.add_bounce(&commit_id, &bounce_path, None) {
-                    Ok(metadata) => {
-                        progress::finish_success(&pb, "Bounce added");
-                        println!(
-                            "  Bounce: {} ({}, {})",
-                            metadata.original_filename,
-                            metadata.format_duration(),
-                            metadata.format_size()
-                        );
-                    }
-                    Err(e) => {
-                        progress::finish_error(&pb, "Failed to add bounce");
-                        warn!("Bounce error: {}", e);
-                    }
-                }
-            }
+EXAMPLES:
+    auxin policy show
+    auxin policy show ~/Music/MySong.logicx")]
+    #[command(subcommand)]
+    Policy(PolicyCommands),
 
-            Ok(())
-        }
+    /// Review executables and scripts held back from pulls
+    #[command(long_about = "Review executables and scripts held back from pulls
 
-        Commands::Log {
-            limit,
-            bpm,
-            tag,
-            key,
-            since,
-            format,
-        } => {
-            let repo = OxenRepository::new(".");
+USAGE:
+    auxin quarantine list
+    auxin quarantine review
+    auxin quarantine approve <PATH>... | --all
+    auxin quarantine reject <PATH>...
 
-            let mut commits = repo.get_history(None).await?;
+DESCRIPTION:
+    For repos shared with outside collaborators. With quarantine turned on,
+    every executable or script that 'auxin pull' adds or changes is held
+    back: its execute permission is removed, macOS Gatekeeper is told it
+    came from elsewhere, and it's listed here until someone reviews it.
 
-            if commits.is_empty() {
-                // If JSON format, return empty array
-                if format == "json" {
-                    println!("[]");
-                    return Ok(());
-                }
+    Held back are scripts (.sh, .py, .command, .applescript, ...),
+    installers and binaries (.pkg, .dmg, .dylib, .exe, ...), anything
+    inside an app or plugin bundle (.app, .component, .vst3, ...) and any
+    file marked executable.
 
-                println!();
-                progress::info("No commits yet");
-                println!();
-                progress::info("Create your first commit:");
-                println!("  auxin add --all");
-                println!("  auxin commit -m \"Initial commit\"");
-                return Ok(());
-            }
+    Approving a file restores its permissions. Rejecting it deletes it; a
+    later commit records the deletion.
 
-            // Apply filters
-            let total_before_filter = commits.len();
-            let mut filters_applied = vec![];
+    Turn it on, optionally letting some extensions through:
 
-            if let Some(bpm_filter) = bpm {
-                commits.retain(|c| {
-                    c.message
-                        .lines()
-                        .any(|line| line.contains("BPM:") && line.contains(&bpm_filter.to_string()))
-                });
-                filters_applied.push(format!("BPM = {}", bpm_filter));
-            }
+        [quarantine]
+        enabled = true
+        auto_approve_extensions = [\"py\"]
 
-            if let Some(tag_filter) = &tag {
-                commits.retain(|c| {
-                    c.message.lines().any(|line| {
-                        line.contains("Tags:")
-                            && line.to_lowercase().contains(&tag_filter.to_lowercase())
-                    })
-                });
-                filters_applied.push(format!("tag = {}", tag_filter));
-            }
+EXAMPLES:
+    auxin quarantine review
+    auxin quarantine approve Scripts/bounce-stems.sh
+    auxin quarantine reject Tools/Installer.pkg")]
+    #[command(subcommand)]
+    Quarantine(QuarantineCommands),
 
-            if let Some(key_filter) = &key {
-                commits.retain(|c| {
-                    c.message.lines().any(|line| {
-                        line.contains("Key:")
-                            && line.to_lowercase().contains(&key_filter.to_lowercase())
-                    })
-                });
-                filters_applied.push(format!("key = {}", key_filter));
-            }
+    /// Version metadata, comments and bounce manifests on a branch
+    #[command(long_about = "Version metadata, comments and bounce manifests on a branch
 
-            if let Some(_since_filter) = &since {
-                // TODO: Implement date filtering when commit timestamps are available
-                progress::warning("Date filtering not yet implemented (commit timestamps needed)");
-            }
+USAGE:
+    auxin meta sync [PATH]
 
-            // Apply limit after filtering
-            if let Some(lim) = limit {
-                commits.truncate(lim);
-            }
+DESCRIPTION:
+    Metadata edits, comments and bounce manifests normally stay on this
+    machine. With the meta branch turned on, they're committed to a
+    dedicated branch of the project's remote after every commit, pull,
+    comment, bounce and metadata edit, so they travel with the repo:
+
+        [meta_branch]
+        enabled = true
+        branch = \"auxin-meta\"
+        remote = \"origin\"
+
+    The branch lives in its own small repo, .auxin/meta-repo, so nothing
+    in the project is checked out. Each sync pulls the branch first and
+    merges teammates' changes with yours: JSON objects key by key, lists
+    (comments, audit trails) as a union, your value winning where both
+    changed the same field. Deleting a comment locally doesn't remove it
+    from the branch.
+
+    'auxin meta sync' runs a sync now, e.g. after a failed push, and works
+    even when the meta branch is turned off.
 
-            // If JSON format requested, output JSON and return
-            if format == "json" {
-                let json = serde_json::to_string_pretty(&commits)?;
-                println!("{}", json);
-                return Ok(());
-            }
+EXAMPLES:
+    auxin meta sync
+    auxin meta sync ~/Music/MySong.logicx")]
+    #[command(subcommand)]
+    Meta(MetaCommands),
 
-            // Show results (text format)
-            println!();
-            println!("┌─ Commit History ────────────────────────────────────────┐");
-            if !filters_applied.is_empty() {
-                println!(
-                    "│ Filters: {}                                              │",
-                    filters_applied.join(", ")
-                );
-                println!(
-                    "│ Found {} of {} commit(s)                                 │",
-                    commits.len(),
-                    total_before_filter
-                );
-            } else if let Some(_lim) = limit {
-                println!(
-                    "│ Showing last {} of {} commit(s)                          │",
-                    commits.len(),
-                    total_before_filter
-                );
-            } else {
-                println!(
-                    "│ Showing all {} commit(s)                                 │",
-                    commits.len()
-                );
-            }
-            println!("└──────────────────────────────────────────────────────────┘");
-            println!();
+    /// Export project history for handing over to a client
+    #[command(subcommand)]
+    Export(ExportCommands),
 
-            if commits.is_empty() {
-                progress::info("No commits match the specified filters");
-                return Ok(());
-            }
+    /// Write the status snapshot used by Finder / Explorer badges
+    #[command(long_about = "Write the status snapshot used by Finder / Explorer badges
 
-            for (idx, commit) in commits.iter().enumerate() {
-                let short_id = &commit.id[..7.min(commit.id.len())];
+USAGE:
+    auxin badges [PATH] [--print]
 
-                // Visual timeline with bullets
-                println!(
-                    "{} {} - {}",
-                    "●".cyan(),
-                    short_id.bright_yellow(),
-                    "now".bright_black()
-                );
+DESCRIPTION:
+    Writes each changed file's state (modified, staged, untracked) and the
+    project's lock state to .auxin/badges.json. Finder Sync and Explorer
+    overlay extensions read that file instead of running status for every
+    icon. The daemon refreshes it after each status check and commit;
+    run this command to refresh it by hand.
 
-                // Commit message (indented)
-                let lines: Vec<&str> = commit.message.lines().collect();
-                if let Some(first_line) = lines.first() {
-                    println!("  │ {}", first_line.bright_white());
-                }
+    Files not listed in the snapshot are clean. The format is described in
+    the badge_state module docs.
 
-                // Additional metadata if present in message
-                for line in lines.iter().skip(1) {
-                    if !line.trim().is_empty() {
-                        if line.contains("BPM:")
-                            || line.contains("Sample Rate:")
-                            || line.contains("Key:")
-                            || line.contains("Tags:")
-                        {
-                            println!("  │ {}", line.trim().bright_black());
-                        } else {
-                            println!("  │ {}", line.trim());
-                        }
-                    }
-                }
+EXAMPLES:
+    auxin badges
+    auxin badges ~/Music/MySong.logicx --print")]
+    Badges {
+        #[arg(
+            value_name = "PATH",
+            help = "Path to the project (default: current directory)"
+        )]
+        path: Option<PathBuf>,
 
-                // Add spacing between commits (except last one)
-                if idx < commits.len() - 1 {
-                    println!("  │");
-                }
-            }
+        /// Also print the snapshot as JSON
+        #[arg(long)]
+        print: bool,
+    },
 
-            println!();
-            progress::info(&format!("Showing {} commit(s)", commits.len()));
+    /// Print a short repository status for shell prompts
+    #[command(long_about = "Print a short repository status for shell prompts
 
-            Ok(())
-        }
+USAGE:
+    auxin prompt [PATH] [--ascii] [--no-refresh]
+    auxin prompt --starship
 
-        Commands::Restore { commit_id } => {
-            let pb = progress::spinner(&format!(
-                "Restoring to commit {}...",
-                &commit_id[..7.min(commit_id.len())]
-            ));
-            let repo = OxenRepository::new(".");
+DESCRIPTION:
+    Prints the branch, a '*' when there are uncommitted changes, the lock
+    (🔒 held by you, ⛔ held by someone else) and the number of operations
+    waiting in the offline queue (⇡), e.g. 'main* 🔒 ⇡2'. Prints nothing
+    outside a repository.
 
-            pb.set_message("Checking out files...");
-            repo.restore(&commit_id).await?;
+    The output comes from cached files, so it is fast enough to run on every
+    prompt: changes and lock come from the badge snapshot (.auxin/badges.json).
+    When the snapshot is older than 30 seconds, 'auxin badges' is started in
+    the background and the next prompt shows the refreshed status.
 
-            progress::finish_success(
-                &pb,
-                &format!(
-                    "Restored to commit {}",
-                    &commit_id[..7.min(commit_id.len())]
-                ),
-            );
-            println!();
-            progress::warning("Your working directory has been updated to match this commit");
-            progress::info("To create a new commit from here, use:");
-            println!("  auxin add --all");
-            println!("  auxin commit -m \"Your message\"");
+    --starship prints a custom module for ~/.config/starship.toml.
 
-            Ok(())
-        }
+EXAMPLES:
+    # zsh (~/.zshrc)
+    setopt PROMPT_SUBST
+    RPROMPT='$(auxin prompt)'
 
-        Commands::Status => {
-            let repo = OxenRepository::new(".");
+    # fish (~/.config/fish/functions/fish_right_prompt.fish)
+    function fish_right_prompt; auxin prompt; end
 
-            let status = repo.status().await?;
+    # starship
+    auxin prompt --starship >> ~/.config/starship.toml")]
+    Prompt {
+        #[arg(value_name = "PATH", help = "Directory to report on (default: current directory)")]
+        path: Option<PathBuf>,
 
-            // Header
-            println!();
-            println!("┌─ Repository Status ─────────────────────────────────────┐");
-            println!("│                                                          │");
+        #[arg(long, help = "Use plain ASCII instead of symbols")]
+        ascii: bool,
 
-            let total_changes =
-                status.staged.len() + status.modified.len() + status.untracked.len();
+        #[arg(long, help = "Don't refresh a stale status in the background")]
+        no_refresh: bool,
 
-            if total_changes == 0 {
-                println!("│  ✓ Working directory clean                              │");
-            } else {
-                println!(
-                    "│  Changes: {} staged, {} modified, {} untracked",
-                    status.staged.len().to_string().green(),
-                    status.modified.len().to_string().yellow(),
-                    status.untracked.len().to_string().cyan(),
-                );
-            }
+        #[arg(long, help = "Print a starship custom module instead")]
+        starship: bool,
+    },
 
-            // Check for pending sync operations
-            {
-                use auxin::OfflineQueue;
-                if let Ok(queue) = OfflineQueue::new() {
-                    let pending_count = queue.pending().len();
-                    if pending_count > 0 {
-                        println!("│                                                          │");
-                        println!(
-                            "│  {} {} pending sync operation(s)                        │",
-                            "⟳".yellow(),
-                            pending_count.to_string().yellow()
-                        );
-                    }
-                }
-            }
+    /// Launch interactive console for real-time monitoring
+    #[command(long_about = "Launch interactive console for real-time monitoring
 
-            println!("│                                                          │");
-            println!("└──────────────────────────────────────────────────────────┘");
-            println!();
+USAGE:
+    auxin console [PATH] [--once [--json]]
 
-            if !status.staged.is_empty() {
-                println!("{} Staged files ({}):", "●".green(), status.staged.len());
-                for path in &status.staged {
-                    println!("  {} {}", "+".green(), path.display());
-                }
-                println!();
-            }
+DESCRIPTION:
+    Launches a full-screen interactive TUI (Terminal User Interface) that provides
+    real-time monitoring and control of your Logic Pro project version control.
 
-            if !status.modified.is_empty() {
-                println!(
-                    "{} Modified files ({}):",
-                    "◆".yellow(),
-                    status.modified.len()
-                );
-                for path in &status.modified {
-                    println!("  {} {}", "M".yellow(), path.display());
-                }
-                println!();
-            }
+    With --once, prints what the console would show (daemon status, repository
+    status, project panel, recent activity, lock state, queue and uploads) a
+    single time and exits; --json prints it as one JSON document. Use it in CI
+    and remote sessions. When stdout isn't a terminal, the console prints the
+    text snapshot instead of starting the TUI.
 
-            if !status.untracked.is_empty() {
-                println!(
-                    "{} Untracked files ({}):",
-                    "?".cyan(),
-                    status.untracked.len()
-                );
-                for path in &status.untracked {
-                    println!("  {} {}", "?".cyan(), path.display());
-                }
-                println!();
-            }
+    Features:
+      • Live daemon status display
+      • Real-time activity log with auto-updates
+      • Repository status (staged, modified, untracked files)
+      • Latest milestone metadata, trends and unresolved comments
+      • Offline queue and upload manager (press 'u')
+      • Keyboard shortcuts for common operations
+      • Color-coded output for clarity
 
-            // Next steps suggestion
-            if total_changes > 0 {
-                if status.staged.is_empty()
-                    && (!status.modified.is_empty() || !status.untracked.is_empty())
-                {
-                    progress::info("Next step: auxin add --all");
-                } else if !status.staged.is_empty() {
-                    progress::info("Next step: auxin commit -m \"Your message\"");
-                }
-            }
+    The console runs until you press 'q' to quit.
 
-            // Hint about pending sync operations
-            {
-                use auxin::OfflineQueue;
-                if let Ok(queue) = OfflineQueue::new() {
-                    let pending_count = queue.pending().len();
-                    if pending_count > 0 {
-                        progress::warning(&format!("{} operation(s) pending sync", pending_count));
-                        progress::info("Sync with: auxin queue sync");
-                    }
-                }
-            }
+KEYBOARD SHORTCUTS:
+    q       - Quit console
+    u       - Queue and upload manager
+    r       - Refresh status
+    c       - Clear activity log
+    ? or h  - Show help
 
-            Ok(())
-        }
+EXAMPLES:
+    # Launch console in current directory
+    auxin console
 
-        Commands::Show { commit_id } => {
-            let repo = OxenRepository::new(".");
+    # Launch console for specific project
+    auxin console ~/Music/MyProject.logicx
 
-            // Get all commits to find the one we want
-            let commits = repo.get_history(None).await?;
+    # Snapshot for a CI job
+    auxin console --once --json > console.json")]
+    Console {
+        #[arg(
+            value_name = "PATH",
+            help = "Path to Logic Pro project (default: current directory)"
+        )]
+        path: Option<PathBuf>,
 
-            let commit = commits
-                .iter()
-                .find(|c| c.id.starts_with(&commit_id) || c.id == commit_id);
+        #[arg(long, help = "Print a snapshot of the console once and exit")]
+        once: bool,
 
-            if let Some(commit) = commit {
-                println!();
-                println!("┌─ Commit Details ────────────────────────────────────────┐");
-                println!("│                                                          │");
-                println!(
-                    "│  Commit: {}                                      │",
-                    commit.id.bright_yellow()
-                );
-                println!("│                                                          │");
-                println!("└──────────────────────────────────────────────────────────┘");
-                println!();
+        #[arg(long, requires = "once", help = "Print the snapshot as JSON")]
+        json: bool,
+    },
 
-                // Parse commit message and metadata
-                let lines: Vec<&str> = commit.message.lines().collect();
+    /// Show recent project activity timeline
+    #[command(long_about = "Show recent project activity timeline
 
-                // First line is the commit message
-                if let Some(first_line) = lines.first() {
-                    println!("{}", "Message:".bright_white().bold());
-                    println!("  {}", first_line);
-                    println!();
-                }
+USAGE:
+    auxin activity [--limit <N>]
 
-                // Extract metadata
-                let mut metadata_found = false;
-                for line in lines.iter().skip(1) {
-                    let trimmed = line.trim();
-                    if !trimmed.is_empty() {
-                        if trimmed.starts_with("BPM:")
-                            || trimmed.starts_with("Sample Rate:")
-                            || trimmed.starts_with("Key:")
-                            || trimmed.starts_with("Tags:")
-                        {
-                            if !metadata_found {
-                                println!("{}", "Metadata:".bright_white().bold());
-                                metadata_found = true;
-                            }
-                            println!("  {}", trimmed.bright_black());
-                        } else {
-                            // Additional commit message content
-                            println!("  {}", trimmed);
-                        }
-                    }
-                }
+DESCRIPTION:
+    Displays a timeline of recent project activity including commits,
+    lock operations, and comments. Helps teams stay synchronized on
+    project progress.
 
-                println!();
-                progress::info(&format!(
-                    "Use 'auxin restore {}' to restore to this commit",
-                    &commit.id[..7.min(commit.id.len())]
-                ));
-            } else {
-                progress::error(&format!("Commit not found: {}", commit_id));
-                std::process::exit(1);
-            }
+    Activity types shown:
+      • Commits with metadata (BPM, sample rate, key)
+      • Lock acquisitions/releases
+      • Comments on commits
+      • Branch creations
 
-            Ok(())
-        }
+OPTIONS:
+    --limit <N>    Number of recent activities to show (default: 10)
 
-        Commands::Diff { commit_id } => {
-            let repo = OxenRepository::new(".");
+EXAMPLES:
+    # Show last 10 activities
+    auxin activity
 
-            println!();
-            if let Some(cid) = &commit_id {
-                println!(
-                    "┌─ Changes Since Commit {} ─────────────────────┐",
-                    &cid[..7.min(cid.len())].bright_yellow()
-                );
-            } else {
-                println!("┌─ Uncommitted Changes ───────────────────────────────────┐");
-            }
-            println!("│                                                          │");
-            println!("└──────────────────────────────────────────────────────────┘");
-            println!();
+    # Show last 20 activities
+    auxin activity --limit 20")]
+    Activity {
+        #[arg(long, default_value = "10", help = "Number of activities to show")]
+        limit: usize,
+    },
 
-            // Get current status
-            let status = repo.status().await?;
+    /// Show team members and their contributions
+    #[command(long_about = "Show team members and their contributions
 
-            let total_files = status.staged.len() + status.modified.len() + status.untracked.len();
+USAGE:
+    auxin team
 
-            if total_files == 0 {
-                progress::info("No changes in working directory");
-                return Ok(());
-            }
+DESCRIPTION:
+    Discovers team members from commit history and displays their
+    contribution statistics. Helps identify who's working on the project.
 
-            // Modified files
-            if !status.modified.is_empty() {
-                println!(
-                    "{} Modified files ({}):",
-                    "◆".yellow(),
-                    status.modified.len()
-                );
-                for path in &status.modified {
-                    // Try to get file size info
-                    if let Ok(metadata) = std::fs::metadata(path) {
-                        let size = metadata.len();
-                        println!(
-                            "  {} {} {}",
-                            "~".yellow(),
-                            path.display(),
-                            format!("({} bytes)", size).bright_black()
-                        );
-                    } else {
-                        println!("  {} {}", "~".yellow(), path.display());
-                    }
-                }
-                println!();
-            }
+    Information shown:
+      • Member name (username@hostname)
+      • Number of commits
+      • Last activity timestamp
+      • Contribution percentage
 
-            // Untracked (new) files
-            if !status.untracked.is_empty() {
-                println!("{} Added files ({}):", "◆".green(), status.untracked.len());
-                for path in &status.untracked {
-                    if let Ok(metadata) = std::fs::metadata(path) {
-                        let size = metadata.len();
-                        let size_mb = size as f64 / 1_048_576.0;
-                        if size_mb >= 1.0 {
-                            println!(
-                                "  {} {} {}",
-                                "+".green(),
-                                path.display(),
-                                format!("({:.1} MB)", size_mb).bright_black()
-                            );
-                        } else {
-                            println!(
-                                "  {} {} {}",
-                                "+".green(),
-                                path.display(),
-                                format!("({} bytes)", size).bright_black()
-                            );
-                        }
-                    } else {
-                        println!("  {} {}", "+".green(), path.display());
-                    }
-                }
-                println!();
-            }
+    Identities listed in .auxin/mailmap are combined, so someone who has
+    committed from several machines is counted once:
 
-            // Summary
-            let total_modified = status.modified.len();
-            let total_added = status.untracked.len();
+        # canonical name   aliases
+        alice             <alice@old-laptop> <alice@studio-mac>
 
-            progress::info(&format!(
-                "Total changes: {} modified, {} added",
-                total_modified.to_string().yellow(),
-                total_added.to_string().green()
-            ));
+EXAMPLES:
+    # Show team members
+    auxin team")]
+    Team,
 
-            if !status.staged.is_empty() {
-                println!();
-                progress::info("Some changes are already staged. Use 'auxin status' for details");
-            }
+    /// Manage offline operation queue
+    #[command(subcommand)]
+    Queue(QueueCommands),
 
-            Ok(())
-        }
+    /// Manage comments on commits
+    #[command(subcommand)]
+    Comment(CommentCommands),
 
-        Commands::Compare {
-            commit_a,
-            commit_b,
-            format,
-            plain,
-        } => {
-            use auxin::CommitMetadata;
+    /// Generate shell completion scripts
+    #[command(long_about = "Generate shell completion scripts
 
-            let repo = OxenRepository::new(".");
+USAGE:
+    auxin completions <SHELL>
 
-            vlog!("Fetching commit A: {}", commit_a);
-            vlog!("Fetching commit B: {}", commit_b);
+DESCRIPTION:
+    Generates shell completion scripts for the specified shell.
+    This enables tab completion for commands, subcommands, and flags.
 
-            // Get commit history to find the two commits
-            let commits = repo.get_history(None).await?;
+SUPPORTED SHELLS:
+    • bash
+    • zsh
+    • fish
+    • powershell
 
-            // Find commit A
-            let commit_a_info = commits
-                .iter()
-                .find(|c| c.id.starts_with(&commit_a))
-                .ok_or_else(|| anyhow::anyhow!("Commit not found: {}", commit_a))?;
+INSTALLATION:
+    # Bash (Linux)
+    auxin completions bash > /etc/bash_completion.d/auxin
 
-            // Find commit B
-            let commit_b_info = commits
-                .iter()
-                .find(|c| c.id.starts_with(&commit_b))
-                .ok_or_else(|| anyhow::anyhow!("Commit not found: {}", commit_b))?;
+    # Bash (macOS with Homebrew)
+    auxin completions bash > /usr/local/etc/bash_completion.d/auxin
 
-            vlog!("Found commit A: {}", commit_a_info.id);
-            vlog!("Found commit B: {}", commit_b_info.id);
+    # Zsh
+    auxin completions zsh > /usr/local/share/zsh/site-functions/_auxin
 
-            // Parse commit metadata
-            let metadata_a = CommitMetadata::parse_commit_message(&commit_a_info.message);
-            let metadata_b = CommitMetadata::parse_commit_message(&commit_b_info.message);
+    # Fish
+    auxin completions fish > ~/.config/fish/completions/auxin.fish
 
-            println!();
-            println!(
-                "┌─ Comparing {} → {} ─────────────┐",
-                &commit_a_info.id[..7].bright_cyan(),
-                &commit_b_info.id[..7].bright_cyan()
-            );
-            println!("│                                                          │");
-            println!("└──────────────────────────────────────────────────────────┘");
-            println!();
+    # PowerShell
+    auxin completions powershell > auxin.ps1
 
-            // Output based on format
-            match format.as_str() {
-                "json" => {
-                    // Create a simple JSON structure
-                    let json_output = serde_json::json!({
-                        "commit_a": {
-                            "id": &commit_a_info.id,
-                            "metadata": &metadata_a
-                        },
-                        "commit_b": {
-                            "id": &commit_b_info.id,
-                            "metadata": &metadata_b
-                        }
-                    });
-                    println!("{}", serde_json::to_string_pretty(&json_output)?);
-                }
-                "compact" => {
-                    let summary = metadata_a.compare_compact(&metadata_b);
-                    println!("{}", summary);
-                }
-                "text" => {
-                    println!("{}", metadata_a.compare_with_plain(&metadata_b));
-                }
-                _ => {
-                    if plain {
-                        println!("{}", metadata_a.compare_with_plain(&metadata_b));
-                    } else {
-                        println!("{}", metadata_a.compare_with(&metadata_b));
-                    }
-                }
-            }
+EXAMPLES:
+    # Generate bash completions
+    auxin completions bash
 
-            // Add thumbnail comparison
-            let current_dir = std::env::current_dir()?;
-            let thumbnail_mgr = ThumbnailManager::new(&current_dir);
+    # Install for current user (bash)
+    auxin completions bash > ~/.local/share/bash-completion/completions/auxin")]
+    Completions {
+        #[arg(
+            value_name = "SHELL",
+            help = "Shell to generate completions for (bash, zsh, fish, powershell)"
+        )]
+        shell: String,
+    },
 
-            if let Ok(Some(_)) = thumbnail_mgr.get_thumbnail(&commit_a_info.id) {
-                if let Ok(Some(_)) = thumbnail_mgr.get_thumbnail(&commit_b_info.id) {
-                    println!();
-                    println!("{}", "Visual Changes:".bright_cyan());
-                    match thumbnail_mgr.compare_thumbnails(&commit_a_info.id, &commit_b_info.id) {
-                        Ok(diff) => {
-                            println!("  Difference: {:.1}%", diff.difference_percent);
-                            if let Some(dim_diff) = &diff.dimension_diff {
-                                println!("  Dimensions: {}", dim_diff);
-                            }
-                            println!(
-                                "  Size change: {} bytes",
-                                if diff.size_diff_bytes > 0 { "+" } else { "" }.to_string()
-                                    + &diff.size_diff_bytes.to_string()
-                            );
-                        }
-                        Err(_) => {}
-                    }
-                }
-            }
+    /// View operation history and audit trail
+    #[command(subcommand)]
+    History(HistoryCommands),
 
-            // Add bounce comparison
-            let bounce_mgr = BounceManager::new(&current_dir);
+    /// Workflow automation and smart suggestions
+    #[command(subcommand)]
+    Workflow(WorkflowCommands),
 
-            if let Ok(Some(_)) = bounce_mgr.get_bounce(&commit_a_info.id) {
-                if let Ok(Some(_)) = bounce_mgr.get_bounce(&commit_b_info.id) {
-                    println!();
-                    println!("{}", "Audio Changes:".bright_cyan());
-                    match bounce_mgr.compare_bounces(&commit_a_info.id, &commit_b_info.id) {
-                        Ok(comparison) => {
-                            if let Some(duration_diff) = comparison.duration_diff() {
-                                println!(
-                                    "  Duration: {}{:.2}s",
-                                    if duration_diff >= 0.0 { "+" } else { "" },
-                                    duration_diff
-                                );
-                            }
-                            println!(
-                                "  Size: {} bytes",
-                                if comparison.size_diff() > 0 { "+" } else { "" }.to_string()
-                                    + &comparison.size_diff().to_string()
-                            );
-                            println!("  {} For detailed audio comparison, use: auxin bounce compare {} {} --null-test",
-                                "💡".bright_yellow(),
-                                &commit_a_info.id[..8],
-                                &commit_b_info.id[..8]);
-                        }
-                        Err(_) => {}
-                    }
-                }
-            }
+    /// Backup and snapshot management
+    #[command(subcommand)]
+    Snapshot(SnapshotCommands),
 
-            Ok(())
-        }
+    /// Recovery guides for common scenarios
+    #[command(subcommand)]
+    Recovery(RecoveryCommands),
 
-        Commands::Search {
-            query,
-            format,
-            ranked,
-        } => {
-            use auxin::search::SearchEngine;
+    /// Create, list, switch and delete branches
+    #[command(long_about = "Create, list, switch and delete branches
 
-            let repo = OxenRepository::new(".");
+USAGE:
+    auxin branch list
+    auxin branch create <NAME>
+    auxin branch switch <NAME> [--force]
+    auxin branch delete <NAME> [--force]
 
-            vlog!("Parsing search query: {}", query);
+DESCRIPTION:
+    Manages the project's branches without the raw oxen CLI, for example
+    to move between the draft branch the daemon auto-commits to and main.
 
-            // Parse the natural language query
-            let search_query = SearchEngine::parse_query(&query);
+    'create' starts a branch at the current commit and switches to it.
 
-            vlog!("Fetching commit history...");
-            let commits = repo.get_history(None).await?;
+    'switch' refuses while files are modified or staged, since the switch
+    would replace them; commit first, or pass --force to discard them. The
+    working copy is recorded before switching, so 'auxin undo' can bring
+    it back.
 
-            vlog!("Executing search...");
-            let engine = SearchEngine::new();
-            let mut results = engine.search(&commits, &search_query);
+    'delete' never deletes the current branch, and only deletes main or
+    the draft branch with --force. Commits only on the deleted branch are
+    no longer reachable from any branch.
 
-            // Sort by relevance if requested
-            if ranked {
-                results.sort_by(|a, b| {
-                    let score_b = engine.relevance_score(b, &search_query);
-                    let score_a = engine.relevance_score(a, &search_query);
-                    score_b
-                        .partial_cmp(&score_a)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                });
-            }
+EXAMPLES:
+    auxin branch list
+    auxin branch create mix-v2
+    auxin branch switch draft
+    auxin branch delete mix-v2")]
+    #[command(subcommand)]
+    Branch(BranchCommands),
 
-            println!();
-            println!(
-                "┌─ Search Results ({} matches) ─────────────────────┐",
-                results.len().to_string().bright_cyan()
-            );
-            println!("│ Query: {:<49} │", query.bright_yellow());
-            println!("│                                                          │");
-            println!("└──────────────────────────────────────────────────────────┘");
-            println!();
+    /// Pull from remote, resolving conflicts with local changes
+    #[command(long_about = "Pull from remote, resolving conflicts with local changes
 
-            if results.is_empty() {
-                progress::warning("No commits match your search criteria");
-                println!();
-                println!("Try:");
-                println!("  • Broadening your BPM range");
-                println!("  • Using partial key matches (e.g., 'minor' instead of 'A Minor')");
-                println!("  • Checking tag spellings");
-                return Ok(());
-            }
+USAGE:
+    auxin pull [OPTIONS]
 
-            // Output based on format
-            match format.as_str() {
-                "json" => {
-                    let json = serde_json::to_string_pretty(&results)?;
-                    println!("{}", json);
-                }
-                "compact" => {
-                    for (i, commit) in results.iter().enumerate() {
-                        let metadata = CommitMetadata::parse_commit_message(&commit.message);
-                        let short_id = if commit.id.len() >= 7 {
-                            &commit.id[..7]
-                        } else {
-                            &commit.id
-                        };
+DESCRIPTION:
+    Pulls the remote branch into the working copy. Files you have changed
+    that were also changed on the remote are listed first, and you choose
+    for each one with a single key:
 
-                        let mut parts = vec![format!("{}", short_id.bright_cyan())];
+      l  keep local   - your version stays, on top of the pulled commits
+      r  take remote  - the pulled version replaces yours
+      b  keep both    - the pulled version, plus yours as a Logic Pro
+                        alternative (or a '<name> (local)' copy)
+      a  abort        - cancel the pull; nothing is changed
 
-                        if let Some(bpm) = metadata.bpm {
-                            parts.push(format!("{}BPM", bpm));
-                        }
-                        if let Some(sr) = metadata.sample_rate {
-                            parts.push(format!("{}Hz", sr));
-                        }
-                        if let Some(ref key) = metadata.key_signature {
-                            parts.push(key.clone());
-                        }
+    Local versions are always backed up to .auxin/conflicts/<time>/ first,
+    and the choices are recorded in the operation history (auxin history).
 
-                        let first_line =
-                            metadata.message.lines().next().unwrap_or(&metadata.message);
-                        parts.push(first_line.to_string());
+    Without a terminal, pass --resolve to apply one choice to every file.
 
-                        if ranked {
-                            let score = engine.relevance_score(commit, &search_query);
-                            parts.push(format!("(score: {:.1})", score).dimmed().to_string());
-                        }
+    With [quarantine] enabled, executables and scripts the pull brings in
+    can't be run until reviewed with 'auxin quarantine review'.
 
-                        println!("{}. {}", i + 1, parts.join(" │ "));
-                    }
-                }
-                _ => {
-                    for (i, commit) in results.iter().enumerate() {
-                        let metadata = CommitMetadata::parse_commit_message(&commit.message);
-                        let short_id = if commit.id.len() >= 7 {
-                            &commit.id[..7]
-                        } else {
-                            &commit.id
-                        };
+    When nothing conflicts, progress shows the data received and the
+    download speed as oxen reports them. Network failures are retried
+    ([network.pull] in config.toml); if the pull is still interrupted,
+    running it again resumes: oxen keeps what was already received and
+    fetches only the rest.
 
-                        println!(
-                            "{}. {} {}",
-                            format!("{}", i + 1).dimmed(),
-                            "Commit".bright_black(),
-                            short_id.bright_cyan()
-                        );
+EXAMPLES:
+    # Pull from origin into the current branch
+    auxin pull
 
-                        let first_line =
-                            metadata.message.lines().next().unwrap_or(&metadata.message);
-                        println!("   Message: {}", first_line);
+    # Keep both versions of every conflicting file, without prompting
+    auxin pull --resolve both")]
+    Pull {
+        #[arg(long, short, help = "Remote name (default: origin)")]
+        remote: Option<String>,
 
-                        if let Some(bpm) = metadata.bpm {
-                            println!("   BPM: {}", format!("{}", bpm).yellow());
-                        }
-                        if let Some(sr) = metadata.sample_rate {
-                            println!("   Sample Rate: {} Hz", sr);
-                        }
-                        if let Some(ref key) = metadata.key_signature {
-                            println!("   Key: {}", key.green());
-                        }
-                        if !metadata.tags.is_empty() {
-                            println!("   Tags: {}", metadata.tags.join(", ").bright_black());
-                        }
+        #[arg(long, short, help = "Branch name (default: current branch)")]
+        branch: Option<String>,
 
-                        if ranked {
-                            let score = engine.relevance_score(commit, &search_query);
-                            println!("   Relevance: {:.1}", score);
-                        }
+        #[arg(
+            long,
+            value_name = "CHOICE",
+            help = "Resolve every conflict the same way: local, remote, both or abort"
+        )]
+        resolve: Option<auxin::ConflictChoice>,
+    },
 
-                        println!();
-                    }
-                }
-            }
+    /// Resolve merge conflicts left by a pull or merge
+    #[command(long_about = "Resolve merge conflicts left by a pull or merge
 
-            progress::success(&format!(
-                "Found {} matching commit{}",
-                results.len(),
-                if results.len() == 1 { "" } else { "s" }
-            ));
+USAGE:
+    auxin resolve [OPTIONS] [FILES]...
 
-            Ok(())
-        }
+DESCRIPTION:
+    When a pull merges commits that changed the same file on both sides,
+    Oxen leaves the file in conflict, and binary files such as ProjectData
+    or audio can't be merged line by line. This lists the conflicting files
+    and lets you choose for each one with a single key:
 
-        Commands::Lock(lock_cmd) => {
-            use auxin::lock_integration;
-            use auxin::network_resilience::{check_connectivity, ConnectivityState};
-            use auxin::{OfflineQueue, OfflineQueuedOperation};
-            use std::env;
+      l  keep local   - our version (--ours)
+      r  take remote  - the version merged in (--theirs)
+      b  keep both    - the version merged in, plus ours as a Logic Pro
+                        alternative or a '<name> (local)' copy (--keep-both)
+      a  abort        - stop; nothing more is changed
 
-            let current_dir = env::current_dir()?;
+    Each file is backed up to .auxin/conflicts/<time>/ first and staged once
+    resolved, and the choices are recorded in the operation history. Commit
+    when no conflicts are left.
 
-            // Auto-sync pending queue if online (for all lock commands)
-            if check_connectivity() == ConnectivityState::Online {
-                let mut queue = OfflineQueue::new()?;
-                let pending_count = queue.pending().len();
+    Without a terminal, pass --ours, --theirs or --keep-both to resolve
+    every file (or only FILES) the same way.
 
-                if pending_count > 0 {
-                    vlog!(
-                        "Auto-syncing {} pending operation(s) before lock operation...",
-                        pending_count
-                    );
-                    let report = queue.sync_all()?;
+EXAMPLES:
+    # Choose for each conflicting file
+    auxin resolve
 
-                    if !report.failed.is_empty() {
-                        warn!("{} queued operation(s) failed to sync", report.failed.len());
-                    }
-                }
-            }
+    # Keep the incoming version of one file
+    auxin resolve --theirs \"Audio Files/Vox.wav\"
 
-            match lock_cmd {
-                LockCommands::Acquire { timeout } => {
-                    // Load config to check if server locks are enabled
-                    let config = auxin_config::load_config().unwrap_or_default();
+    # Keep both versions of everything, then commit the merge
+    auxin resolve --keep-both
+    auxin commit -m \"Merge remote changes\"")]
+    Resolve {
+        #[arg(value_name = "FILES", help = "Conflicting files to resolve (default: all)")]
+        files: Vec<PathBuf>,
 
-                    if config.cli.use_server_locks {
-                        // Use server-based locking
-                        let server_config = ServerConfig {
-                            url: config.cli.url.clone(),
-                            token: if config.cli.token.is_empty() { None } else { Some(config.cli.token.clone()) },
-                            timeout_secs: config.cli.timeout_secs as u64,
-                        };
+        #[arg(long, group = "side", help = "Keep our version of every file")]
+        ours: bool,
 
-                        match AuxinServerClient::new(server_config) {
-                            Ok(client) => {
-                                let user = server_client::get_user_identifier();
-                                let machine_id = server_client::get_machine_id();
+        #[arg(long, group = "side", help = "Take the version merged in for every file")]
+        theirs: bool,
 
-                                // Get namespace/name from config or current directory
-                                let namespace = config.cli.default_namespace.clone();
-                                let repo_name = current_dir
-                                    .file_name()
-                                    .map(|s| s.to_string_lossy().to_string())
-                                    .unwrap_or_else(|| "unknown".to_string());
+        #[arg(
+            long,
+            group = "side",
+            help = "Take the version merged in and keep ours next to it"
+        )]
+        keep_both: bool,
+    },
 
-                                let pb = progress::spinner("Acquiring server lock...");
-                                match client.acquire_lock(
-                                    &namespace,
-                                    &repo_name,
-                                    &user,
-                                    &machine_id,
-                                    timeout as u32,
-                                ) {
-                                    Ok(lock) => {
-                                        progress::finish_success(&pb, "Lock acquired via server");
-                                        println!();
-                                        success!("Lock acquired successfully");
-                                        println!(
-                                            "  {} {}",
-                                            "Lock ID:".dimmed(),
-                                            lock.lock_id.cyan()
-                                        );
-                                        println!("  {} {}", "User:".dimmed(), lock.user.dimmed());
-                                        println!(
-                                            "  {} {}",
-                                            "Expires:".dimmed(),
-                                            lock.expires_at.dimmed()
-                                        );
-                                    }
-                                    Err(e) => {
-                                        progress::finish_error(&pb, "Failed to acquire lock");
-                                        anyhow::bail!("Server lock error: {}", e);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Failed to connect to server, falling back to local lock");
-                                vlog!("Server error: {}", e);
-                                lock_integration::handle_lock_acquire(&current_dir, timeout)?;
-                            }
-                        }
-                    } else {
-                        // Use local locking (original behavior)
-                        // Check connectivity
-                        match check_connectivity() {
-                            ConnectivityState::Offline => {
-                                // Queue the operation
-                                let mut queue = OfflineQueue::new()?;
-                                let user_id = lock_integration::get_user_identifier();
+    /// Push commits to remote with progress tracking
+    #[command(long_about = "Push commits to remote with progress tracking
 
-                                let entry_id = queue.enqueue_with_priority(
-                                    OfflineQueuedOperation::AcquireLock {
-                                        project_path: current_dir.to_string_lossy().to_string(),
-                                        user_id: user_id.clone(),
-                                        timeout_hours: timeout as u32,
-                                    },
-                                    100,
-                                )?; // High priority
+USAGE:
+    auxin push [OPTIONS]
 
-                                warn!("Network is offline - operation queued");
-                                println!();
-                                println!("  {} {}", "Queued:".bold(), "Acquire lock".yellow());
-                                println!("  {} {}", "User:".dimmed(), user_id.dimmed());
-                                println!("  {} {} hours", "Timeout:".dimmed(), timeout);
-                                println!("  {} {}", "Entry ID:".dimmed(), &entry_id[..8].dimmed());
-                                println!();
-                                progress::info("Lock will be acquired when network is available");
-                                progress::info("Use 'auxin queue sync' to retry manually");
-                            }
-                            _ => {
-                                // Execute normally (online or unknown)
-                                lock_integration::handle_lock_acquire(&current_dir, timeout)?;
-                            }
-                        }
-                    }
-                }
+DESCRIPTION:
+    Pushes commits to the remote repository with enhanced progress tracking
+    and resume capability. For large files, uploads are chunked to allow
+    resuming interrupted transfers.
 
-                LockCommands::Release => {
-                    // Load config to check if server locks are enabled
-                    let config = Config::load().unwrap_or_default();
+    Features:
+      • Real-time progress display with speed/ETA
+      • Automatic resume of interrupted uploads
+      • Bandwidth estimation
+      • Retry on network failures
 
-                    if config.cli.use_server_locks {
-                        // Use server-based locking
-                        let server_config = ServerConfig {
-                            url: config.cli.url.clone(),
-                            token: if config.cli.token.is_empty() { None } else { Some(config.cli.token.clone()) },
-                            timeout_secs: config.cli.timeout_secs as u64,
-                        };
+    If no remote or branch is specified, uses 'origin' and the current branch.
 
-                        match AuxinServerClient::new(server_config) {
-                            Ok(client) => {
-                                let user = server_client::get_user_identifier();
-                                let machine_id = server_client::get_machine_id();
+EXAMPLES:
+    # Push to default remote (origin) and current branch
+    auxin push
 
-                                // Get namespace/name from config or current directory
-                                let namespace = config.cli.default_namespace.clone();
-                                let repo_name = current_dir
-                                    .file_name()
-                                    .map(|s| s.to_string_lossy().to_string())
-                                    .unwrap_or_else(|| "unknown".to_string());
+    # Push to specific remote and branch
+    auxin push --remote origin --branch main
 
-                                // First get the current lock status to get lock_id
-                                let pb = progress::spinner("Releasing server lock...");
-                                match client.get_lock_status(&namespace, &repo_name) {
-                                    Ok(status) => {
-                                        if let Some(lock) = status.lock {
-                                            match client.release_lock(
-                                                &namespace,
-                                                &repo_name,
-                                                &lock.lock_id,
-                                                &user,
-                                                &machine_id,
-                                            ) {
-                                                Ok(()) => {
-                                                    progress::finish_success(
-                                                        &pb,
-                                                        "Lock released via server",
-                                                    );
-                                                    success!("Lock released successfully");
-                                                }
-                                                Err(e) => {
-                                                    progress::finish_error(
-                                                        &pb,
-                                                        "Failed to release lock",
-                                                    );
-                                                    anyhow::bail!("Server release error: {}", e);
-                                                }
-                                            }
-                                        } else {
-                                            progress::finish_error(&pb, "No lock to release");
-                                            warn!("No active lock found for this repository");
-                                        }
-                                    }
-                                    Err(e) => {
-                                        progress::finish_error(&pb, "Failed to get lock status");
-                                        anyhow::bail!("Server error: {}", e);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Failed to connect to server, falling back to local lock");
-                                vlog!("Server error: {}", e);
-                                lock_integration::handle_lock_release(&current_dir)?;
-                            }
-                        }
-                    } else {
-                        // Use local locking (original behavior)
-                        // Check connectivity
-                        match check_connectivity() {
-                            ConnectivityState::Offline => {
-                                // Queue the operation
-                                let mut queue = OfflineQueue::new()?;
+    # Push with verbose output
+    auxin push --verbose
 
-                                // We don't know the lock_id when offline, so use a placeholder
-                                // The execute_entry will look up the actual lock
-                                let entry_id = queue.enqueue_with_priority(
-                                    OfflineQueuedOperation::ReleaseLock {
-                                        project_path: current_dir.to_string_lossy().to_string(),
-                                        lock_id: "pending".to_string(), // Will be looked up during execution
-                                    },
-                                    100,
-                                )?; // High priority
+    # Force push (use with caution)
+    auxin push --force")]
+    Push {
+        #[arg(long, short, help = "Remote name (default: origin)")]
+        remote: Option<String>,
 
-                                warn!("Network is offline - operation queued");
-                                println!();
-                                println!("  {} {}", "Queued:".bold(), "Release lock".yellow());
-                                println!("  {} {}", "Entry ID:".dimmed(), &entry_id[..8].dimmed());
-                                println!();
-                                progress::info("Lock will be released when network is available");
-                                progress::info("Use 'auxin queue sync' to retry manually");
-                            }
-                            _ => {
-                                // Execute normally (online or unknown)
-                                lock_integration::handle_lock_release(&current_dir)?;
-                            }
-                        }
-                    }
-                }
+        #[arg(long, short, help = "Branch name (default: current branch)")]
+        branch: Option<String>,
 
-                LockCommands::Status => {
-                    // Load config to check if server locks are enabled
-                    let config = Config::load().unwrap_or_default();
+        #[arg(long, help = "Force push (overwrites remote history)")]
+        force: bool,
 
-                    if config.cli.use_server_locks {
-                        // Use server-based locking
-                        let server_config = ServerConfig {
-                            url: config.cli.url.clone(),
-                            token: if config.cli.token.is_empty() { None } else { Some(config.cli.token.clone()) },
-                            timeout_secs: config.cli.timeout_secs as u64,
-                        };
+        #[arg(long, short, help = "Show detailed progress")]
+        verbose: bool,
+
+        #[arg(long, help = "Keep the lock even if [lock] release_on = \"push\"")]
+        keep_lock: bool,
+    },
+
+    /// Check system environment and dependencies
+    #[command(long_about = "Check system environment and dependencies
+
+USAGE:
+    auxin doctor
+
+DESCRIPTION:
+    Validates your environment to ensure Auxin can work correctly.
+    Checks for:
+      • Oxen CLI installation and version
+      • Authentication status with Oxen Hub
+      • Remote repository configuration
+      • Project initialization status
+      • Daemon status
+
+    This is the first command to run if you're having issues.
+
+EXAMPLES:
+    # Check everything
+    auxin doctor
+
+    # Check in current project directory
+    cd MyProject.logicx && auxin doctor")]
+    Doctor,
+
+    /// Check the Oxen CLI, auxin, the daemon and the server for upgrades
+    #[command(long_about = "Check the Oxen CLI, auxin, the daemon and the server for upgrades
+
+USAGE:
+    auxin upgrade-check [--json]
+
+DESCRIPTION:
+    Compares the installed Oxen CLI, this auxin binary, the running daemon
+    and the configured server with the latest releases, and prints how to
+    upgrade each one that is behind.
+
+    The latest releases come from the manifest at [updates] manifest_url.
+    When it can't be reached, the last one fetched is used.
+
+    Commands run in a terminal also check once a day and mention outdated
+    components. Turn that off with [updates] check = false.
+
+EXAMPLES:
+    auxin upgrade-check
+    auxin upgrade-check --json")]
+    UpgradeCheck {
+        #[arg(long, help = "Print the report as JSON")]
+        json: bool,
+    },
+
+    /// Show or change the repository's features
+    #[command(long_about = "Show or change the repository's features
+
+USAGE:
+    auxin features <COMMAND>
+
+DESCRIPTION:
+    Repositories choose some auxin behaviors for everyone who works in
+    them. They're kept in .auxin/features.toml, written by 'auxin init'
+    and committed with the project:
+
+      • metadata_sidecars - Spotlight sidecars and server-side metadata
+      • draft_workflow    - the daemon auto-commits to the draft branch
+      • server_sync       - server locks, metadata uploads and meta
+                            branch sync
+
+    Turning a feature off here overrides your own configuration for this
+    project. Commit the file so collaborators get the change.
+
+    A project recorded by a newer auxin, with features this one doesn't
+    know, is refused until auxin is upgraded.
+
+EXAMPLES:
+    auxin features list
+    auxin features set server_sync off
+    auxin features get draft_workflow")]
+    #[command(subcommand)]
+    Features(FeaturesCommands),
+
+    /// Make a finished project read-only
+    #[command(long_about = "Make a finished project read-only
+
+USAGE:
+    auxin archive mark [--reason <TEXT>]
+    auxin archive unmark
+    auxin archive status
+
+DESCRIPTION:
+    Protects a delivered project from accidental edits. Marking it
+    archived records .auxin/archive.json; from then on:
+
+      • Commands that change the project or its history (add, commit,
+        restore, mv, pull, wrapup, snapshot restore, metadata edits,
+        version bump, features set, quick-commit) refuse to run
+      • Locks can't be acquired
+      • The daemon stops watching the project and makes no auto-commits
+
+    status, log, show, diff, compare, search, export and push keep working.
+    Pass --allow-archived to run a refused command anyway.
+
+    The marker belongs to this copy of the project and isn't committed.
+
+EXAMPLES:
+    auxin archive mark --reason \"Delivered to the label\"
+    auxin archive status
+    auxin commit -m \"Fix typo in notes\" --allow-archived
+    auxin archive unmark")]
+    #[command(subcommand)]
+    Archive(ArchiveCommands),
+
+    /// Look after every registered repository at once
+    #[command(long_about = "Look after every registered repository at once
+
+USAGE:
+    auxin workspace list
+    auxin workspace add [PATH]...
+    auxin workspace remove <PATH>...
+    auxin workspace maintain [--gc] [--prune-drafts] [--verify] [--json]
+
+DESCRIPTION:
+    Repositories created with 'auxin init' or 'auxin clone' are registered
+    in ~/.auxin/workspace.json; add others with 'auxin workspace add'.
+
+    'maintain' runs the selected tasks on every registered repository and
+    prints one consolidated report:
+
+      --gc            Remove Spotlight sidecars for commits no longer in
+                      the history, and Oxen temp files over a day old
+      --prune-drafts  Remove the draft branch of projects that turned the
+                      draft workflow off; flag draft branches over their
+                      commit limit
+      --verify        Check that status and history can be read and that
+                      this auxin understands the project's features
+
+    Each task reports ok, fixed, skipped or attention. The command exits
+    non-zero if any repository needs attention, so a cron job can alert
+    on it. Archived projects are skipped by --prune-drafts.
+
+EXAMPLES:
+    auxin workspace add ~/Music/Album/*.logicx
+    auxin workspace maintain --gc --prune-drafts --verify
+
+    # Nightly at 3am, in crontab
+    0 3 * * * auxin workspace maintain --gc --prune-drafts --verify --json >> ~/auxin-maintain.log")]
+    #[command(subcommand)]
+    Workspace(WorkspaceCommands),
+
+    /// Developer tools for testing and benchmarking auxin
+    #[command(hide = true, long_about = "Developer tools for testing and benchmarking auxin
+
+USAGE:
+    auxin devtools gen-project [--type <TYPE>] [--size <SIZE>] [--commits <N>]
+                               [--name <NAME>] [--seed <N>] [-o <DIR>]
+
+DESCRIPTION:
+    'gen-project' fabricates a project so performance work, demos and
+    server load tests don't need real (confidential) sessions. The tree
+    looks like the real thing: a Logic Pro project gets
+    Alternatives/000/ProjectData, MetaData.plist and WAV takes with valid
+    headers under Media/Audio Files; SketchUp and Blender projects get a
+    model file with textures, exports or renders.
+
+    File contents are random, so they neither compress nor deduplicate.
+    Media is spread across --commits commits, each of which also rewrites
+    the main project file, and the commit messages carry BPM, sample rate,
+    key and tags like 'auxin commit' writes them. The same --seed always
+    gives the same project.
+
+    Sizes are decimal (GB = 10^9 bytes); use GiB for binary units.
+
+EXAMPLES:
+    auxin devtools gen-project --type logicpro --size 20GB --commits 500
+    auxin devtools gen-project --type blender --size 2GB --commits 0 -o /tmp
+    auxin devtools gen-project --size 500MB --name \"Demo Song\" --seed 42")]
+    #[command(subcommand)]
+    Devtools(DevtoolsCommands),
+
+    /// Check that everything is saved and synced
+    #[command(long_about = "Check that everything is saved and synced
+
+USAGE:
+    auxin sync-status [--recover]
+
+DESCRIPTION:
+    Answers \"is everything actually saved and synced?\" in one place by
+    checking:
+      • Operations waiting in the offline queue, and ones that gave up
+      • Operations that were interrupted before finishing (write-ahead log)
+      • Interrupted uploads that 'auxin push' would resume
+      • The daemon, and whether auto-commits are paused for this project
+      • Whether the network and the auxin server can be reached
+
+    Ends with the commands to run next, most pressing first.
+
+    --recover first checks each interrupted operation and marks the ones
+    that actually finished, so only those needing attention are reported.
+
+EXAMPLES:
+    # Before closing the laptop
+    auxin sync-status
+
+    # Clear up operations interrupted by a crash
+    auxin sync-status --recover")]
+    SyncStatus {
+        #[arg(long, help = "Check interrupted operations and mark finished ones")]
+        recover: bool,
+    },
+
+    /// Check that it's safe to shut down, finishing up what isn't
+    #[command(long_about = "Check that it's safe to shut down, finishing up what isn't
+
+USAGE:
+    auxin wrapup [--check] [--yes] [-m <MESSAGE>]
+
+DESCRIPTION:
+    The end-of-session check. It's safe to shut down when:
+      • The project has no uncommitted changes
+      • The offline queue is empty, with nothing that gave up
+      • No operation or upload was interrupted
+      • You don't hold the project's lock, so collaborators can take it
+
+    For each problem it can fix, wrapup asks before committing, pushing,
+    syncing the offline queue or releasing the lock. A commit made here is
+    pushed too, and the lock is only released once the earlier steps
+    succeeded.
+
+    Ends with a verdict and exits with status 1 when it isn't safe yet.
+    Without a terminal, wrapup only reports unless --yes is given.
+
+EXAMPLES:
+    # Before closing the laptop
+    auxin wrapup
+
+    # Just report
+    auxin wrapup --check
+
+    # Finish everything without asking
+    auxin wrapup --yes -m \"End of mixing session\"")]
+    Wrapup {
+        #[arg(long, help = "Only report; don't offer to fix anything")]
+        check: bool,
+
+        #[arg(short, long, help = "Take every step without asking")]
+        yes: bool,
+
+        #[arg(short, long, help = "Message for the wrap-up commit")]
+        message: Option<String>,
+    },
+
+    /// Package diagnostics into a zip for a bug report
+    #[command(long_about = "Package diagnostics into a zip for a bug report
+
+USAGE:
+    auxin report-issue [--output <FILE>] [--all]
+
+DESCRIPTION:
+    Collects a diagnostics bundle and zips it together with the most recent
+    crash report from ~/.auxin/crash/ (written automatically if Auxin crashes).
+
+    The bundle contains:
+      • Crash details and backtrace (if any)
+      • Configuration, with tokens and passwords redacted
+      • Environment checks from 'auxin doctor'
+      • Recent write-ahead log entries
+      • The tail of the daemon log
+
+    Review the zip before attaching it to an issue.
+
+EXAMPLES:
+    # Create auxin-report-<timestamp>.zip in the current directory
+    auxin report-issue
+
+    # Include every saved crash report
+    auxin report-issue --all --output ~/Desktop/auxin-report.zip")]
+    ReportIssue {
+        #[arg(long, short, value_name = "FILE", help = "Where to write the zip")]
+        output: Option<PathBuf>,
+
+        #[arg(long, help = "Include all saved crash reports, not just the latest")]
+        all: bool,
+    },
+
+    /// Move this repository and its Auxin data to another server
+    #[command(long_about = "Move this repository and its Auxin data to another server
+
+USAGE:
+    auxin migrate --to <URL> [--token <TOKEN>] [--author-map <FILE>] [--repoint-origin] [--dry-run]
+
+DESCRIPTION:
+    Copies the repository to an Auxin server, for example when moving a studio
+    off hub.oxen.ai to a self-hosted server. Run it from the repository's
+    default branch.
+
+    What is copied:
+      • Every branch (pushed through a temporary 'auxin-migrate' remote)
+      • Commit metadata (BPM, key, tags) from the configured server, or
+        parsed from commit messages
+      • Bounces stored in .auxin/bounces
+      • Commit comments
+      • Lock history from the configured server
+
+    Comment authors and lock holders are renamed using the author map
+    (.auxin/mailmap, or --author-map), e.g. 'alice <alice@old-laptop>'.
+    Oxen can't push tags; they are listed so they can be recreated.
+
+    Afterwards the commit count and branches on the target are checked
+    against the local repository. Running the command again is safe: data
+    already on the target is left as it is.
+
+EXAMPLES:
+    # See what would be copied
+    auxin migrate --to https://vcs.studio.example/band/album --dry-run
+
+    # Migrate and make the new server 'origin'
+    auxin migrate --to https://vcs.studio.example/band/album --repoint-origin")]
+    Migrate {
+        #[arg(long, value_name = "URL", help = "Target repository (https://server/namespace/repo)")]
+        to: String,
+
+        #[arg(long, help = "Token for the target server (defaults to the configured token)")]
+        token: Option<String>,
+
+        #[arg(long, help = "Make the target 'origin' (the old one is kept as 'origin-previous')")]
+        repoint_origin: bool,
+
+        #[arg(long, help = "Don't read metadata or lock history from the configured server")]
+        no_source: bool,
+
+        #[arg(long, value_name = "FILE", help = "Author mapping file (defaults to .auxin/mailmap)")]
+        author_map: Option<PathBuf>,
+
+        #[arg(long, help = "Show what would be migrated without changing anything")]
+        dry_run: bool,
+    },
+
+    /// Upgrade files left by the old OxVCS layout (~/.oxenvcs)
+    #[command(long_about = "Upgrade files left by the old OxVCS layout (~/.oxenvcs)
+
+USAGE:
+    auxin migrate-legacy [PATH] [--dry-run]
+
+DESCRIPTION:
+    Moves settings and sidecar files from the layout used before the rename
+    to OxVCS to where auxin looks for them now:
+
+      ~/.oxenvcs/config.toml        →  ~/.auxin/config.toml
+      ~/.oxenvcs/queue/             →  ~/.auxin/queue/
+      <project>/.oxenvcs/comments/  →  <project>/.oxen/comments/
+      <project>/.oxenvcs/...        →  <project>/.auxin/...
+
+    Paths and OXENVCS_ variable names in the config are rewritten, and old
+    comment files are converted to the current format. Files that already
+    exist in the new layout are never overwritten; they are listed instead.
+    Each legacy folder is then renamed to .oxenvcs.migrated.
+
+    auxin offers to run this automatically the first time it finds a legacy
+    folder. Declining stops the prompt; the command can be run at any time.
+
+EXAMPLES:
+    # See what would move for this project and your home folder
+    auxin migrate-legacy --dry-run
+
+    # Migrate a specific project
+    auxin migrate-legacy ~/Music/Album.logicx")]
+    MigrateLegacy {
+        #[arg(help = "Project to migrate alongside ~/.oxenvcs (defaults to current directory)")]
+        path: Option<PathBuf>,
+
+        #[arg(long, help = "Show what would be migrated without changing anything")]
+        dry_run: bool,
+    },
+
+    /// Update auxin's own files to the current format
+    #[command(long_about = "Update auxin's own files to the current format
+
+USAGE:
+    auxin migrate-state [PATH] [--dry-run]
+
+DESCRIPTION:
+    auxin's state has versioned formats: the offline queue, write-ahead log
+    and snapshots in ~/.auxin, and bounce manifests and metadata edits in a
+    project's .auxin folder. Each folder's state_versions.json records
+    which format its files are in.
+
+    When a new auxin changes a format, the files are migrated step by step.
+    Files that are rewritten are first copied to state-backups/<time>/ in
+    the same folder. auxin does this by itself when it starts; this command
+    shows or retries it.
+
+    Files written by a newer auxin are left alone until auxin is upgraded.
+
+EXAMPLES:
+    # See what would change for ~/.auxin and this project
+    auxin migrate-state --dry-run
+
+    # Migrate a specific project
+    auxin migrate-state ~/Music/Album.logicx")]
+    MigrateState {
+        #[arg(help = "Project to migrate alongside ~/.auxin (defaults to current directory)")]
+        path: Option<PathBuf>,
+
+        #[arg(long, help = "Show what would be migrated without changing anything")]
+        dry_run: bool,
+    },
+
+    /// Manage remote repositories
+    #[command(subcommand)]
+    Remote(RemoteCommands),
+
+    /// Manage auxin-server remotely (needs an Admin token)
+    #[command(long_about = "Manage auxin-server remotely (needs an Admin token)
+
+USAGE:
+    auxin admin locks [--stale] [--stale-minutes <MINUTES>]
+    auxin admin break-lock <NAMESPACE/NAME> [--force]
+    auxin admin audit [-n <LIMIT>] [--namespace <NS>] [--user <USER>] [--type <TYPE>]
+    auxin admin sessions
+    auxin admin revoke --user <USERNAME> | <TOKEN>
+    auxin admin storage
+    auxin admin gc [--dry-run]
+
+DESCRIPTION:
+    Operations across every repository on the configured server. The
+    server only accepts them from an account with the Admin role; the
+    token comes from --token, or 'token' under [cli] in the config.
+
+    A lock is stale once its holder has sent no heartbeat for
+    --stale-minutes (default 30). break-lock only breaks stale locks
+    unless --force is given; the holder's next save fails, and the break
+    is recorded in the repository's activity log.
+
+    gc is server housekeeping: expired sessions and locks, abandoned
+    lock guards and leftover temp files. It doesn't touch repository
+    history.
+
+EXAMPLES:
+    # Who is holding what?
+    auxin admin locks
+
+    # Free a project someone left locked overnight
+    auxin admin break-lock studio/album-track-3
+
+    # Lock breaks in the last 20 events
+    auxin admin audit -n 20 --type lock_released
+
+    # Sign out a departed engineer everywhere
+    auxin admin revoke --user sam
+
+    # See what gc would clean up
+    auxin admin gc --dry-run")]
+    Admin {
+        #[command(subcommand)]
+        command: AdminCommands,
+
+        #[arg(
+            long,
+            global = true,
+            value_name = "TOKEN",
+            help = "Admin token (default: [cli] token)"
+        )]
+        token: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommentCommands {
+    /// Add a comment to a commit
+    #[command(long_about = "Add a comment to a commit
+
+USAGE:
+    auxin comment add <COMMIT_ID> <TEXT>
+
+DESCRIPTION:
+    Adds a comment to a specific commit. Comments are stored in the
+    repository and synced across team members.
+
+    Use cases:
+      • Code review feedback
+      • Mix notes for specific versions
+      • Questions about changes
+      • Track decisions
+
+EXAMPLES:
+    # Add comment to latest commit
+    auxin comment add HEAD \"Vocals need more reverb\"
+
+    # Add comment to specific commit
+    auxin comment add abc123 \"Great mix on this version!\"")]
+    Add {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID or HEAD")]
+        commit_id: String,
+
+        #[arg(value_name = "TEXT", help = "Comment text")]
+        text: String,
+    },
+
+    /// List comments on a commit
+    #[command(long_about = "List comments on a commit
+
+USAGE:
+    auxin comment list [COMMIT_ID]
+
+DESCRIPTION:
+    Shows all comments on a specific commit. If no commit ID is provided,
+    shows comments on the latest commit (HEAD).
+
+EXAMPLES:
+    # Show comments on latest commit
+    auxin comment list
+
+    # Show comments on specific commit
+    auxin comment list abc123")]
+    List {
+        #[arg(value_name = "COMMIT_ID", help = "Commit ID or HEAD (optional)")]
+        commit_id: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueCommands {
+    /// Show pending operations in the queue
+    #[command(long_about = "Show pending operations in the queue
+
+USAGE:
+    auxin queue status
+
+DESCRIPTION:
+    Displays all pending operations that are queued due to network unavailability.
+    Shows operation type, priority, queued time, and number of retry attempts.
+
+EXAMPLES:
+    # Show all pending operations
+    auxin queue status")]
+    Status,
+
+    /// Manually sync all pending operations
+    #[command(long_about = "Manually sync all pending operations
+
+USAGE:
+    auxin queue sync
+
+DESCRIPTION:
+    Attempts to execute all pending operations in the queue. Operations are
+    executed in priority order (highest first), then by age (oldest first).
+
+    Only executes if network connectivity is available. Failed operations
+    remain in the queue for retry.
+
+EXAMPLES:
+    # Sync all pending operations
+    auxin queue sync")]
+    Sync,
+
+    /// Clear completed operations from the queue
+    #[command(long_about = "Clear completed operations from the queue
+
+USAGE:
+    auxin queue clear [--all]
+
+DESCRIPTION:
+    Removes completed operations from the queue to free up disk space.
+    Use --all to remove ALL operations (including pending).
+
+EXAMPLES:
+    # Clear only completed operations
+    auxin queue clear
+
+    # Clear all operations (pending and completed)
+    auxin queue clear --all")]
+    Clear {
+        #[arg(long, help = "Clear all operations including pending")]
+        all: bool,
+    },
+
+    /// Remove a specific operation from the queue
+    #[command(long_about = "Remove a specific operation from the queue
+
+USAGE:
+    auxin queue remove <ENTRY_ID>
+
+DESCRIPTION:
+    Removes a specific operation from the queue by its entry ID.
+    Use 'queue status' to see entry IDs.
+
+EXAMPLES:
+    # Remove a specific operation
+    auxin queue remove 01234567-89ab-cdef-0123-456789abcdef")]
+    Remove {
+        #[arg(value_name = "ENTRY_ID", help = "Queue entry ID to remove")]
+        entry_id: String,
+    },
+
+    /// Change a queued operation's parameters before it syncs
+    #[command(long_about = "Change a queued operation's parameters before it syncs
+
+USAGE:
+    auxin queue edit <ENTRY_ID>
+    auxin queue edit <ENTRY_ID> --set <FIELD=VALUE>...
+
+DESCRIPTION:
+    Without --set, shows the operation's fields. With --set, changes them,
+    e.g. to point a queued push at the right repository or give a queued
+    lock a longer timeout.
+
+    Values are checked against the operation: only its own fields can be
+    set, numbers must be whole and in range, paths must exist (and are
+    stored absolute), and structured fields such as metadata take JSON. Operations that already
+    synced can't be edited. Entry IDs can be shortened to any unique prefix.
+
+EXAMPLES:
+    # See what can be changed
+    auxin queue edit 0123abcd
+
+    # Re-target a push
+    auxin queue edit 0123abcd --set repo_path=Music/Song.logicx
+
+    # Ask for an 8-hour lock instead
+    auxin queue edit 0123abcd --set timeout_hours=8")]
+    Edit {
+        #[arg(value_name = "ENTRY_ID", help = "Queue entry ID")]
+        entry_id: String,
+
+        #[arg(long = "set", value_name = "FIELD=VALUE", help = "Field to change")]
+        changes: Vec<String>,
+    },
+
+    /// Put an operation back in line with its attempts reset
+    #[command(long_about = "Put an operation back in line with its attempts reset
+
+USAGE:
+    auxin queue requeue <ENTRY_ID> [--priority <N>]
+
+DESCRIPTION:
+    Resets the operation's failed attempts, brings it back from the dead
+    letters if it was set aside, and optionally changes its priority
+    (higher syncs first within its class). It runs with the next sync.
+
+EXAMPLES:
+    # Retry an operation after fixing it with 'auxin queue edit'
+    auxin queue requeue 0123abcd
+
+    # Sync it ahead of the rest of its class
+    auxin queue requeue 0123abcd --priority 100")]
+    Requeue {
+        #[arg(value_name = "ENTRY_ID", help = "Queue entry ID")]
+        entry_id: String,
+
+        #[arg(long, value_name = "N", allow_hyphen_values = true, help = "New priority")]
+        priority: Option<i32>,
+    },
+
+    /// Review operations that failed too often to keep retrying
+    #[command(long_about = "Review operations that failed too often to keep retrying
+
+USAGE:
+    auxin queue deadletter list
+    auxin queue deadletter retry <ENTRY_ID>... | --all
+    auxin queue deadletter discard <ENTRY_ID>... | --all
+
+DESCRIPTION:
+    An operation that fails max_attempts times in a row (see [queue] in
+    ~/.auxin/config.toml, default 5) is set aside instead of being retried
+    on every sync. 'list' shows each one with the error that stopped it and
+    its causes.
+
+    'retry' puts operations back in the queue with their attempts reset,
+    for the next 'auxin queue sync'. 'discard' deletes them for good.
+    Entry IDs can be shortened to any unique prefix.
+
+EXAMPLES:
+    # See what gave up
+    auxin queue deadletter list
+
+    # Try one again after fixing the cause
+    auxin queue deadletter retry 0123abcd
+
+    # Drop everything that gave up
+    auxin queue deadletter discard --all")]
+    #[command(subcommand)]
+    Deadletter(DeadLetterCommands),
+}
+
+#[derive(Subcommand)]
+enum DeadLetterCommands {
+    /// List dead-lettered operations and their errors
+    List,
+
+    /// Put dead-lettered operations back in the queue
+    Retry {
+        #[arg(value_name = "ENTRY_ID", required_unless_present = "all", help = "Entry IDs")]
+        entry_ids: Vec<String>,
+
+        #[arg(long, help = "Retry every dead-lettered operation")]
+        all: bool,
+    },
+
+    /// Delete dead-lettered operations
+    Discard {
+        #[arg(value_name = "ENTRY_ID", required_unless_present = "all", help = "Entry IDs")]
+        entry_ids: Vec<String>,
+
+        #[arg(long, help = "Discard every dead-lettered operation")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommands {
+    /// View recent operation history
+    #[command(long_about = "View recent operation history
+
+USAGE:
+    auxin history view [--limit <N>]
+    auxin history view --repo <PATH>
+
+DESCRIPTION:
+    Displays a timeline of all operations performed, including:
+      • Lock operations (acquire, release, renew, break)
+      • Commits and pushes
+      • Authentication events
+      • Comments and activity views
+      • Success/failure status for each operation
+
+    Operations are stored persistently and survive restarts.
+
+OPTIONS:
+    --limit <N>     Number of recent operations to show (default: 20)
+    --repo <PATH>   Filter by repository path
+
+EXAMPLES:
+    # Show last 20 operations
+    auxin history view
+
+    # Show last 50 operations
+    auxin history view --limit 50
+
+    # Show operations for specific repository
+    auxin history view --repo /path/to/project.logicx")]
+    View {
+        #[arg(long, default_value = "20", help = "Number of operations to show")]
+        limit: usize,
+
+        #[arg(long, value_name = "PATH", help = "Filter by repository path")]
+        repo: Option<PathBuf>,
+    },
+
+    /// Export history to CSV file
+    #[command(long_about = "Export history to CSV file
+
+USAGE:
+    auxin history export <OUTPUT_FILE>
+
+DESCRIPTION:
+    Exports complete operation history to a CSV file for analysis,
+    compliance, or reporting. CSV includes:
+      • Timestamp
+      • Operation type
+      • User and machine
+      • Result (success/failure)
+      • Repository path
+
+EXAMPLES:
+    # Export to CSV
+    auxin history export operations.csv
+
+    # Export and open in Excel
+    auxin history export report.csv && open report.csv")]
+    Export {
+        #[arg(value_name = "OUTPUT_FILE", help = "CSV file to write")]
+        output: PathBuf,
+    },
+
+    /// Show operation statistics
+    #[command(long_about = "Show operation statistics
+
+USAGE:
+    auxin history stats
+
+DESCRIPTION:
+    Displays statistics about all operations:
+      • Total operations
+      • Success rate
+      • Lock operation count
+      • Network operation count
+      • Failure breakdown
+
+EXAMPLES:
+    # View statistics
+    auxin history stats")]
+    Stats,
+
+    /// Show and deliver entries waiting for audit sinks
+    #[command(long_about = "Show and deliver entries waiting for audit sinks
+
+USAGE:
+    auxin history audit [--flush]
+
+DESCRIPTION:
+    With [audit] sinks configured, every recorded operation is also sent
+    to syslog, an HTTP endpoint or a file as it happens. Entries a sink
+    doesn't accept are kept in ~/.auxin/audit-spool/ and sent, in order,
+    with the next entry once it's reachable again.
+
+    Lists each sink and how many entries are waiting for it. --flush
+    delivers them now instead of waiting for the next operation.
+
+EXAMPLES:
+    auxin history audit
+    auxin history audit --flush")]
+    Audit {
+        #[arg(long, help = "Deliver waiting entries now")]
+        flush: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkflowCommands {
+    /// Get smart suggestions based on repository state
+    #[command(long_about = "Get smart suggestions based on repository state
+
+USAGE:
+    auxin workflow suggest [PATH]
+
+DESCRIPTION:
+    Analyzes current repository state and provides context-aware
+    suggestions for next actions. Checks:
+      • Lock status and expiration
+      • Recent operation failures
+      • Uncommitted changes
+      • Recommended workflows
+
+EXAMPLES:
+    # Get suggestions for current directory
+    auxin workflow suggest
+
+    # Get suggestions for specific project
+    auxin workflow suggest /path/to/project.logicx")]
+    Suggest {
+        #[arg(
+            value_name = "PATH",
+            help = "Repository path (default: current directory)"
+        )]
+        path: Option<PathBuf>,
+    },
+
+    /// Run lock renewal daemon (keeps lock alive)
+    #[command(long_about = "Run lock renewal daemon (keeps lock alive)
+
+USAGE:
+    auxin workflow lock-daemon <PATH>
+
+DESCRIPTION:
+    Starts a background daemon that automatically renews your lock
+    before it expires. Useful for long editing sessions (>4 hours).
+
+    The daemon:
+      • Checks every 15 minutes
+      • Renews when <60 minutes remaining
+      • Records all renewals in history
+      • Stops when lock is released
+
+OPTIONS:
+    Daemon runs in foreground. Use '&' to run in background:
+    auxin workflow lock-daemon . &
+
+EXAMPLES:
+    # Start lock renewal daemon
+    auxin workflow lock-daemon .
+
+    # Run in background
+    auxin workflow lock-daemon . &")]
+    LockDaemon {
+        #[arg(value_name = "PATH", help = "Repository path")]
+        path: PathBuf,
+    },
+
+    /// Show current workflow configuration
+    #[command(long_about = "Show current workflow configuration
+
+USAGE:
+    auxin workflow config
+
+DESCRIPTION:
+    Displays current workflow automation settings:
+      • Auto-lock renewal (enabled/disabled)
+      • Lock check interval
+      • Lock renew threshold
+      • Auto-pull on startup
+      • Auto-push after commit
+      • Confirmation prompts
+      • Dry-run mode
+
+    Configuration file: ~/.auxin/workflow_config.json
+
+EXAMPLES:
+    # View configuration
+    auxin workflow config")]
+    Config,
+}
+
+#[derive(Subcommand)]
+enum SnapshotCommands {
+    /// Create a manual backup snapshot
+    #[command(long_about = "Create a manual backup snapshot
+
+USAGE:
+    auxin snapshot create <PATH> [DESCRIPTION]
+
+DESCRIPTION:
+    Creates a manual backup snapshot of the repository state.
+    Snapshots include:
+      • Current commit ID
+      • Timestamp
+      • Repository path
+      • User description
+
+    Automatic snapshots are also created before:
+      • Push operations
+      • Pull operations
+      • Lock break operations
+      • Rollback operations
+
+EXAMPLES:
+    # Create snapshot with description
+    auxin snapshot create . \"Before major refactor\"
+
+    # Create snapshot without description
+    auxin snapshot create /path/to/project.logicx")]
+    Create {
+        #[arg(value_name = "PATH", help = "Repository path")]
+        path: PathBuf,
+
+        #[arg(value_name = "DESCRIPTION", help = "Optional description")]
+        description: Option<String>,
+    },
+
+    /// List all backup snapshots
+    #[command(long_about = "List all backup snapshots
+
+USAGE:
+    auxin snapshot list [OPTIONS]
+
+DESCRIPTION:
+    Lists all backup snapshots with:
+      • Snapshot ID
+      • Type (manual, auto-before-push, etc.)
+      • Timestamp
+      • Description
+      • Associated commit ID
+      • Repository path
+
+OPTIONS:
+    --all           Show all snapshots (default: last 20)
+    --repo <PATH>   Filter by repository
+
+EXAMPLES:
+    # List recent snapshots
+    auxin snapshot list
+
+    # List all snapshots
+    auxin snapshot list --all
+
+    # List snapshots for specific repository
+    auxin snapshot list --repo /path/to/project.logicx")]
+    List {
+        #[arg(long, help = "Show all snapshots")]
+        all: bool,
+
+        #[arg(long, value_name = "PATH", help = "Filter by repository")]
+        repo: Option<PathBuf>,
+    },
+
+    /// Get restore instructions for a snapshot
+    #[command(long_about = "Get restore instructions for a snapshot
+
+USAGE:
+    auxin snapshot restore <SNAPSHOT_ID>
+
+DESCRIPTION:
+    Displays step-by-step instructions for restoring from a snapshot.
+    Does NOT actually perform the restore (dry-run only).
+
+    Instructions include:
+      • Snapshot details
+      • Commit to restore
+      • Commands to run
+      • Warnings about uncommitted changes
+
+EXAMPLES:
+    # Get restore instructions
+    auxin snapshot restore abc123def456")]
+    Restore {
+        #[arg(value_name = "SNAPSHOT_ID", help = "Snapshot ID to restore from")]
+        snapshot_id: String,
+    },
+
+    /// Delete a snapshot
+    #[command(long_about = "Delete a snapshot
+
+USAGE:
+    auxin snapshot delete <SNAPSHOT_ID>
+
+DESCRIPTION:
+    Permanently deletes a snapshot. Cannot be undone.
+
+    Note: Automatic cleanup keeps only the 50 most recent snapshots.
+
+EXAMPLES:
+    # Delete snapshot
+    auxin snapshot delete abc123def456")]
+    Delete {
+        #[arg(value_name = "SNAPSHOT_ID", help = "Snapshot ID to delete")]
+        snapshot_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum RecoveryCommands {
+    /// Show recovery guide for failed push
+    #[command(long_about = "Show recovery guide for failed push
+
+USAGE:
+    auxin recovery push
+
+DESCRIPTION:
+    Displays step-by-step recovery guide for push failures.
+
+    Common push failure causes:
+      • Network connectivity issues
+      • Authentication problems
+      • Missing lock
+      • Diverged branches
+
+EXAMPLES:
+    # Show push recovery guide
+    auxin recovery push")]
+    Push,
+
+    /// Show recovery guide for failed pull
+    #[command(long_about = "Show recovery guide for failed pull
+
+USAGE:
+    auxin recovery pull
+
+DESCRIPTION:
+    Displays step-by-step recovery guide for pull failures.
+
+    Common pull failure causes:
+      • Network connectivity issues
+      • Authentication problems
+      • Uncommitted local changes
+      • Merge conflicts
+
+EXAMPLES:
+    # Show pull recovery guide
+    auxin recovery pull")]
+    Pull,
+
+    /// Show recovery guide for lock conflicts
+    #[command(long_about = "Show recovery guide for lock conflicts
+
+USAGE:
+    auxin recovery lock
+
+DESCRIPTION:
+    Displays step-by-step recovery guide for lock conflicts.
+
+    Common lock conflict scenarios:
+      • Lock held by another user
+      • Expired lock not auto-released
+      • Stale lock after crash
+
+EXAMPLES:
+    # Show lock recovery guide
+    auxin recovery lock")]
+    Lock,
+}
+
+#[derive(Subcommand)]
+enum RemoteCommands {
+    /// Add a remote repository
+    #[command(long_about = "Add a remote repository
+
+USAGE:
+    auxin remote add <NAME> <URL>
+
+DESCRIPTION:
+    Adds a remote repository URL. This is typically your Oxen Hub repository
+    where you push and pull changes for collaboration.
+
+EXAMPLES:
+    # Add Oxen Hub remote
+    auxin remote add origin https://hub.oxen.ai/username/myproject")]
+    Add {
+        #[arg(value_name = "NAME", help = "Name for the remote (typically 'origin')")]
+        name: String,
+
+        #[arg(value_name = "URL", help = "URL of the remote repository")]
+        url: String,
+    },
+
+    /// List configured remotes
+    #[command(long_about = "List configured remotes
+
+USAGE:
+    auxin remote list
+
+DESCRIPTION:
+    Shows all configured remote repositories for the current project.
+
+EXAMPLES:
+    # List all remotes
+    auxin remote list")]
+    List,
+
+    /// Remove a remote
+    #[command(long_about = "Remove a remote
+
+USAGE:
+    auxin remote remove <NAME>
+
+DESCRIPTION:
+    Removes a remote repository configuration.
+
+EXAMPLES:
+    # Remove a remote
+    auxin remote remove origin")]
+    Remove {
+        #[arg(value_name = "NAME", help = "Name of the remote to remove")]
+        name: String,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    CrashReporter::install_panic_hook();
+
+    if let Err(e) = run().await {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(exit_code_for(&e));
+    }
+}
+
+/// Let the user pick search results and run actions on them until they quit
+fn browse_search_results(
+    engine: &auxin::search::SearchEngine,
+    results: &[auxin::CommitInfo],
+    history: &[auxin::CommitInfo],
+) -> anyhow::Result<()> {
+    use auxin::search_actions::SearchAction;
+    use dialoguer::{Confirm, Select};
+
+    let project = std::env::current_dir()?;
+    let auxin_exe = std::env::current_exe()?;
+    let head_id = history.first().map(|c| c.id.clone()).unwrap_or_default();
+    let items: Vec<String> = results
+        .iter()
+        .map(|commit| {
+            let metadata = engine.metadata(commit);
+            let mut details = Vec::new();
+            if let Some(bpm) = metadata.bpm {
+                details.push(format!("{} BPM", bpm));
+            }
+            if let Some(ref key) = metadata.key_signature {
+                details.push(key.clone());
+            }
+            format!(
+                "{}  {}  {}",
+                &commit.id[..7.min(commit.id.len())],
+                metadata.message.lines().next().unwrap_or_default(),
+                details.join(", ").dimmed()
+            )
+        })
+        .collect();
+
+    let mut selected = 0;
+    loop {
+        let Some(index) = Select::new()
+            .with_prompt("Select a commit (Esc to quit)")
+            .items(&items)
+            .default(selected)
+            .interact_opt()?
+        else {
+            return Ok(());
+        };
+        selected = index;
+        let commit = &results[index];
+
+        let actions = SearchAction::available(&project, commit);
+        let labels: Vec<&str> = actions.iter().map(|a| a.label()).collect();
+        let Some(choice) = Select::new()
+            .with_prompt(format!("Commit {}", &commit.id[..7.min(commit.id.len())]))
+            .items(&labels)
+            .default(0)
+            .interact_opt()?
+        else {
+            continue;
+        };
+        let action = actions[choice];
+
+        if action.is_destructive()
+            && !Confirm::new()
+                .with_prompt("Replace the working copy with this commit?")
+                .default(false)
+                .interact()?
+        {
+            continue;
+        }
+        if let Err(e) = action.run(&auxin_exe, &project, &commit.id, &head_id) {
+            warn!("{}", e);
+        }
+        if action == SearchAction::Restore {
+            return Ok(());
+        }
+        println!();
+    }
+}
+
+/// `~/.auxin` and, if there is one, the current project's `.auxin`
+fn state_dirs(project: &std::path::Path) -> Vec<state_migration::StateDir> {
+    let mut dirs = Vec::new();
+    if let Ok(user) = state_migration::StateDir::user() {
+        dirs.push(user);
+    }
+    dirs.push(state_migration::StateDir::project(project));
+    dirs.into_iter().filter(|state| state.dir.is_dir()).collect()
+}
+
+/// Bring auxin's files up to this build's formats
+fn migrate_state_on_start() {
+    let Ok(project) = std::env::current_dir() else {
+        return;
+    };
+    for state in state_dirs(&project) {
+        let plan = match state.plan() {
+            Ok(plan) => plan,
+            Err(e) => {
+                warn!("{:#}", e);
+                continue;
+            }
+        };
+        if !plan.newer.is_empty() {
+            warn!(
+                "Files in {} were written by a newer auxin; upgrade auxin \
+                 (see 'auxin upgrade-check')",
+                state.dir.display()
+            );
+            continue;
+        }
+        match state.migrate(&plan) {
+            Ok(Some(backup)) => progress::info(&format!(
+                "Updated the files in {} to the current format (backup in {})",
+                state.dir.display(),
+                backup.display()
+            )),
+            Ok(None) => {}
+            Err(e) => warn!("{:#}; run 'auxin migrate-state' to retry", e),
+        }
+    }
+}
+
+/// Refuse to work in a project recorded by a newer auxin
+fn check_repo_features() -> anyhow::Result<()> {
+    let Ok(dir) = std::env::current_dir() else {
+        return Ok(());
+    };
+    let Some(features) = auxin_config::RepoFeatures::load(&dir)? else {
+        return Ok(());
+    };
+    if let Some(problem) = features.incompatibility() {
+        anyhow::bail!(
+            "This project {}; upgrade auxin to work in it (see 'auxin upgrade-check')",
+            problem
+        );
+    }
+    Ok(())
+}
+
+/// Move plaintext server tokens out of the config files into the keychain
+///
+/// Runs with the `auth` commands rather than on every start, so reading
+/// the config files and the keychain only happens when asked about
+/// credentials.
+fn move_tokens_to_keychain() {
+    use auxin::credential_store::{self, SystemKeychain};
+
+    let files = credential_store::config_files();
+    match credential_store::migrate_config_tokens(&SystemKeychain, &files) {
+        Ok(scrubbed) => {
+            for file in scrubbed {
+                progress::info(&format!(
+                    "Moved the server token from {} into the system keychain",
+                    file.display()
+                ));
+            }
+        }
+        // Without a usable keychain the token stays where it is
+        Err(e) => vlog!("Server token left in the config file: {}", e),
+    }
+}
+
+/// Name of a command that would change the project, and the project it
+/// runs in when that isn't the current directory
+fn mutating_command(command: &Commands) -> Option<(&'static str, Option<&PathBuf>)> {
+    let name = match command {
+        Commands::Add { .. } => "add",
+        Commands::Commit { .. } => "commit",
+        Commands::Restore { .. } => "restore",
+        Commands::Mv { .. } => "mv",
+        Commands::Pull { .. } => "pull",
+        Commands::Resolve { .. } => "resolve",
+        Commands::Branch(BranchCommands::Create { .. }) => "branch create",
+        Commands::Branch(BranchCommands::Switch { .. }) => "branch switch",
+        Commands::Branch(BranchCommands::Delete { .. }) => "branch delete",
+        Commands::Wrapup { .. } => "wrapup",
+        Commands::Lock(LockCommands::Acquire { .. }) => "lock acquire",
+        Commands::Lock(LockCommands::Reclaim { .. }) => "lock reclaim",
+        Commands::Snapshot(SnapshotCommands::Restore { .. }) => "snapshot restore",
+        Commands::Metadata(MetadataCommands::Edit { .. }) => "metadata edit",
+        Commands::Metadata(MetadataCommands::BulkEdit { .. }) => "metadata bulk-edit",
+        Commands::Version(VersionCommands::Bump { .. }) => "version bump",
+        Commands::Features(FeaturesCommands::Set { .. }) => "features set",
+        Commands::QuickCommit { path, .. } => return Some(("quick-commit", path.as_ref())),
+        Commands::QuickLock { path, .. } => return Some(("quick-lock", path.as_ref())),
+        _ => return None,
+    };
+    Some((name, None))
+}
+
+/// Offer to migrate legacy `.oxenvcs` folders in the home directory or the
+/// current project, once; non-interactive runs only get a verbose hint
+fn offer_legacy_migration() {
+    use dialoguer::Confirm;
+
+    for (name, _) in std::env::vars().filter(|(name, _)| name.starts_with("OXENVCS_")) {
+        warn!(
+            "{} is no longer read; rename it to {}",
+            name,
+            name.replacen("OXENVCS_", "AUXIN_", 1)
+        );
+    }
+
+    let Ok(migration) =
+        legacy_migration::LegacyMigration::for_current_user(std::env::current_dir().ok())
+    else {
+        return;
+    };
+    if !migration.should_prompt() {
+        return;
+    }
+    if !atty::is(atty::Stream::Stdin) || !atty::is(atty::Stream::Stdout) {
+        vlog!("Legacy .oxenvcs folders found; run 'auxin migrate-legacy' to upgrade them");
+        return;
+    }
+
+    for dir in migration.legacy_dirs() {
+        progress::info(&format!("Found files from the old OxVCS layout: {}", dir.display()));
+    }
+    let accepted = Confirm::new()
+        .with_prompt("Move them to the current layout now?")
+        .default(true)
+        .interact()
+        .unwrap_or(false);
+    if !accepted {
+        if let Err(e) = migration.decline_prompt() {
+            vlog!("Could not record the declined migration: {}", e);
+        }
+        progress::info("Skipped. Run 'auxin migrate-legacy' whenever you're ready.");
+        println!();
+        return;
+    }
+
+    match migration.run() {
+        Ok(report) => print_legacy_migration_report(&report),
+        Err(e) => warn!("Legacy migration failed: {}", e),
+    }
+    println!();
+}
+
+/// Print `auxin lock stats`
+fn print_lock_stats(stats: &server_client::LockStats, days: i64, all: bool) {
+    let minutes = |m: f64| {
+        if m >= 60.0 {
+            format!("{:.1}h", m / 60.0)
+        } else {
+            format!("{:.0}m", m)
+        }
+    };
+
+    println!();
+    println!("{}", format!("Lock usage over the last {} days", days).bold());
+    println!();
+    if stats.holds.count == 0 && stats.contention_events == 0 {
+        progress::info("No locks were held or asked for");
+        return;
+    }
+
+    let holds = &stats.holds;
+    println!("  {:<20} {}", "Holds:".dimmed(), holds.count);
+    println!(
+        "  {:<20} mean {}  p50 {}  p90 {}  p99 {}  max {}",
+        "Hold time:".dimmed(),
+        minutes(holds.mean_minutes),
+        minutes(holds.p50_minutes),
+        minutes(holds.p90_minutes),
+        minutes(holds.p99_minutes),
+        minutes(holds.max_minutes)
+    );
+    println!("  {:<20} {}", "Contention events:".dimmed(), stats.contention_events);
+    let expired = stats.expired.to_string();
+    println!(
+        "  {:<20} {}",
+        "Expired:".dimmed(),
+        if stats.expired > 0 {
+            expired.yellow()
+        } else {
+            expired.normal()
+        }
+    );
+
+    if all && !stats.repos.is_empty() {
+        println!();
+        println!("{}", "Most contended projects".bold());
+        for repo in stats.repos.iter().take(10) {
+            println!(
+                "  {:<32} {:>4} contended  {:>4} holds  mean {}",
+                format!("{}/{}", repo.namespace, repo.name),
+                repo.contention_events,
+                repo.holds,
+                minutes(repo.mean_hold_minutes)
+            );
+        }
+    }
+
+    if !stats.users.is_empty() {
+        println!();
+        println!("{}", "By user".bold());
+        println!(
+            "  {:<20} {:>6} {:>8} {:>8} {:>8} {:>8} {:>9}",
+            "", "holds", "mean", "p90", "expired", "blocked", "blocking"
+        );
+        for user in &stats.users {
+            println!(
+                "  {:<20} {:>6} {:>8} {:>8} {:>8} {:>8} {:>9}",
+                user.user,
+                user.holds.count,
+                minutes(user.holds.mean_minutes),
+                minutes(user.holds.p90_minutes),
+                user.expired,
+                user.blocked,
+                user.blocking
+            );
+        }
+    }
+    println!();
+}
+
+/// Run an `auxin admin` subcommand against the server
+fn run_admin_command(client: &AuxinServerClient, command: AdminCommands) -> anyhow::Result<()> {
+    let short_time = |timestamp: &str| {
+        chrono::DateTime::parse_from_rfc3339(timestamp)
+            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| timestamp.to_string())
+    };
+
+    match command {
+        AdminCommands::Locks {
+            stale,
+            stale_minutes,
+        } => {
+            let locks = client.admin_locks(stale_minutes, stale)?;
+            if locks.is_empty() {
+                progress::info(if stale { "No stale locks" } else { "No locks are held" });
+                return Ok(());
+            }
+            println!();
+            for held in &locks {
+                let repo = format!("{}/{}", held.namespace, held.name);
+                let idle = format!("idle {}m", held.idle_minutes);
+                println!(
+                    "  {:<32} {:<24} {:>10}  expires {}",
+                    repo.bold(),
+                    held.lock.user,
+                    if held.stale {
+                        idle.yellow()
+                    } else {
+                        idle.normal()
+                    },
+                    short_time(&held.lock.expires_at)
+                );
+            }
+            println!();
+            let stale_count = locks.iter().filter(|l| l.stale).count();
+            if stale_count > 0 {
+                progress::info(&format!(
+                    "{} stale; break one with: auxin admin break-lock <namespace/name>",
+                    stale_count
+                ));
+            }
+        },
+
+        AdminCommands::BreakLock {
+            repo,
+            force,
+            stale_minutes,
+        } => {
+            let Some((namespace, name)) = repo.split_once('/') else {
+                anyhow::bail!("Expected the repository as namespace/name, got '{}'", repo);
+            };
+            let pb = progress::spinner(&format!("Breaking lock on {}...", repo));
+            match client.admin_break_lock(namespace, name, stale_minutes, force) {
+                Ok(lock) => progress::finish_success(
+                    &pb,
+                    &format!("Broke {}'s lock on {}", lock.user, repo),
+                ),
+                Err(e) => {
+                    progress::finish_error(&pb, "Failed to break lock");
+                    return Err(e);
+                },
+            }
+        },
+
+        AdminCommands::Audit {
+            limit,
+            namespace,
+            user,
+            activity_type,
+        } => {
+            let events = client.admin_audit(&server_client::AuditFilter {
+                limit,
+                namespace,
+                user,
+                activity_type,
+            })?;
+            if events.is_empty() {
+                progress::info("No matching events");
+                return Ok(());
+            }
+            println!();
+            for event in &events {
+                let activity = &event.activity;
+                println!(
+                    "  {}  {:<28} {:<16} {:<16} {}",
+                    short_time(&activity.timestamp).dimmed(),
+                    format!("{}/{}", event.namespace, event.name),
+                    activity.activity_type.cyan(),
+                    event.account.as_deref().unwrap_or(&activity.user),
+                    activity.message
+                );
+            }
+            println!();
+        },
+
+        AdminCommands::Sessions => {
+            let sessions = client.admin_sessions()?;
+            if sessions.is_empty() {
+                progress::info("No live sessions");
+                return Ok(());
+            }
+            println!();
+            for summary in &sessions {
+                println!(
+                    "  {:<24} {:>3} session(s)  until {}",
+                    summary.username.bold(),
+                    summary.sessions,
+                    short_time(&summary.expires_at)
+                );
+            }
+            println!();
+        },
+
+        AdminCommands::Revoke { user, revoked } => {
+            let count = client.admin_revoke_tokens(user.as_deref(), revoked.as_deref())?;
+            match (user, count) {
+                (_, 0) => progress::info("No matching tokens"),
+                (Some(user), n) => {
+                    progress::success(&format!("Revoked {} token(s) of {}", n, user))
+                },
+                (None, _) => progress::success("Token revoked"),
+            }
+        },
+
+        AdminCommands::Storage => {
+            let usage = client.admin_storage()?;
+            if usage.is_empty() {
+                progress::info("No repositories on the server");
+                return Ok(());
+            }
+            println!();
+            for namespace in &usage {
+                println!(
+                    "  {:<24} {:>10}  {} repo(s), {} file(s)",
+                    namespace.namespace.bold(),
+                    auxin::describe::format_size(namespace.bytes),
+                    namespace.repos,
+                    namespace.files
+                );
+            }
+            let total: u64 = usage.iter().map(|n| n.bytes).sum();
+            println!("  {:<24} {:>10}", "total", auxin::describe::format_size(total));
+            println!();
+        },
+
+        AdminCommands::Gc { dry_run } => {
+            let report = client.admin_gc(dry_run)?;
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            progress::success(&format!(
+                "{} {} expired session(s), {} expired lock(s), {} stale lock guard(s) and \
+                 {} temp file(s) ({})",
+                verb,
+                report.expired_sessions,
+                report.expired_locks,
+                report.stale_guards,
+                report.temp_files,
+                auxin::describe::format_size(report.bytes)
+            ));
+        },
+    }
+    Ok(())
+}
+
+fn print_legacy_migration_report(report: &legacy_migration::MigrationReport) {
+    progress::success(&format!("Migrated {} file(s)", report.migrated.len()));
+    for step in &report.skipped {
+        warn!(
+            "Kept existing {} (legacy copy left in the archive)",
+            step.to.display()
+        );
+    }
+    for (path, reason) in &report.failed {
+        warn!("Could not migrate {}: {}", path.display(), reason);
+    }
+    for archive in &report.archived {
+        progress::info(&format!("Legacy folder archived as {}", archive.display()));
+    }
+}
+
+/// Ask with one key per file how to resolve each conflict; stops at "abort"
+fn prompt_conflict_choices(
+    conflicts: Vec<PathBuf>,
+) -> anyhow::Result<Vec<(PathBuf, auxin::ConflictChoice)>> {
+    use auxin::ConflictChoice;
+    use std::io::Write;
+
+    let keys: Vec<String> = ConflictChoice::ALL
+        .iter()
+        .map(|choice| format!("[{}] {}", choice.key(), choice.label()))
+        .collect();
+    println!("  {}", keys.join("  ").dimmed());
+
+    let term = console::Term::stdout();
+    let mut choices = Vec::new();
+    for path in conflicts {
+        print!("  {} ", path.display().to_string().bold());
+        std::io::stdout().flush()?;
+        let choice = loop {
+            if let Some(choice) = ConflictChoice::from_key(term.read_char()?) {
+                break choice;
+            }
+        };
+        println!("→ {}", choice.label());
+        choices.push((path, choice));
+        if choice == ConflictChoice::Abort {
+            break;
+        }
+    }
+    Ok(choices)
+}
+
+/// List what a staging policy held back or forced in
+fn print_stage_plan(plan: &auxin::stage_policy::StagePlan) {
+    if !plan.held_back.is_empty() {
+        println!();
+        println!("Held back by the staging policy ({} file(s)):", plan.held_back.len());
+        for decision in &plan.held_back {
+            println!(
+                "  {} {}  {}",
+                "-".dimmed(),
+                decision.path,
+                format!("[{}] never: {}", decision.rule.section, decision.rule.pattern).dimmed()
+            );
+        }
+    }
+    if !plan.forced.is_empty() {
+        println!();
+        println!("Staged despite .oxenignore ({} file(s)):", plan.forced.len());
+        for decision in &plan.forced {
+            println!(
+                "  {} {}  {}",
+                "+".green(),
+                decision.path,
+                format!("[{}] always: {}", decision.rule.section, decision.rule.pattern).dimmed()
+            );
+        }
+    }
+}
+
+/// Hold back the executables and scripts a pull added or changed
+fn quarantine_pulled_files(
+    repo: &std::path::Path,
+    before: &auxin::quarantine::FileStates,
+    config: &auxin_config::Quarantine,
+) -> anyhow::Result<()> {
+    use auxin::quarantine::{self, Quarantine};
+
+    let arrived = quarantine::arrived(before, &quarantine::scan(repo)?);
+    let mut held = Quarantine::load(repo)?;
+    let newly_held = held.hold(repo, &arrived, &config.auto_approve_extensions)?;
+    if newly_held.is_empty() {
+        return Ok(());
+    }
+    held.save(repo)?;
+
+    warn!(
+        "Quarantined {} file(s) from the pull; they can't be run until reviewed:",
+        newly_held.len()
+    );
+    for file in &newly_held {
+        println!("  {} {}", file.path.bold(), format!("({})", file.reason).dimmed());
+    }
+    progress::info("Review them with: auxin quarantine review");
+    Ok(())
+}
+
+/// Record the working tree before an operation rewrites it, for
+/// `auxin what-changed`; a failure only costs that report, so it's a warning
+fn record_tree_manifest(repo: &std::path::Path, operation: &str, target: &str) {
+    if let Err(e) = auxin::tree_manifest::TreeManifest::record(repo, operation, target) {
+        warn!("Couldn't record the working copy before the {}: {}", operation, e);
+    }
+}
+
+/// Release the lock after a commit or push when `[lock] release_on` asks
+/// for it; a failure is only a warning
+fn release_lock_after(repo: &std::path::Path, after: LockRelease) {
+    match lock_integration::auto_release(repo, after) {
+        Ok(true) => progress::success("Lock released ([lock] release_on)"),
+        Ok(false) => {}
+        Err(e) => {
+            warn!("Could not release the lock: {}", e);
+            progress::info("Release it with: auxin lock release");
+        }
+    }
+}
+
+/// Sync metadata, comments and bounce manifests to the `[meta_branch]`
+/// branch after an operation that may have changed them; a failure is only
+/// a warning, retried by the next sync
+fn sync_meta_branch(repo: &std::path::Path) {
+    let config = Config::load().unwrap_or_default().meta_branch;
+    if !config.enabled {
+        return;
+    }
+    match auxin::meta_branch::sync(repo, &config, &OxenSubprocess::new()) {
+        Ok(report) => {
+            for path in &report.updated_locally {
+                progress::info(&format!("Merged teammates' changes into {}", path));
+            }
+            if let Some(e) = report.remote_error {
+                vlog!("Could not reach the {} branch on {}: {}", config.branch, config.remote, e);
+            }
+        },
+        Err(e) => {
+            warn!("Could not sync metadata to the {} branch: {}", config.branch, e);
+            progress::info("Run 'auxin meta sync' to retry");
+        },
+    }
+}
+
+/// How long ago a commit was made, e.g. "3h ago"; its date after a month
+fn commit_age(
+    timestamp: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let age = now.signed_duration_since(timestamp);
+    if age.num_days() >= 30 {
+        timestamp.format("%Y-%m-%d").to_string()
+    } else if age.num_days() > 0 {
+        format!("{}d ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h ago", age.num_hours())
+    } else if age.num_minutes() > 0 {
+        format!("{}m ago", age.num_minutes())
+    } else {
+        "just now".to_string()
+    }
+}
+
+/// Map server error codes to exit codes and print what to do next
+///
+/// Exit codes are listed in docs/user/error-codes.md.
+fn exit_code_for(e: &anyhow::Error) -> i32 {
+    let Some(request_error) = e
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<server_client::RequestError>())
+    else {
+        return 1;
+    };
+
+    if let Some(hint) = request_error.code().as_ref().and_then(|code| code.hint()) {
+        progress::info(hint);
+    }
+
+    request_error.exit_code()
+}
+
+/// Finish a `quick-*` command
+///
+/// Successes are silent unless `notify` is set. With `notify`, failures are
+/// reported only as a notification so a button press never opens a terminal.
+fn quick_report(notify: bool, title: &str, result: anyhow::Result<String>) -> anyhow::Result<()> {
+    use auxin::quick_actions;
+
+    match result {
+        Ok(summary) => {
+            if notify {
+                quick_actions::notify(title, &summary);
+            }
+            Ok(())
+        }
+        Err(e) if notify => {
+            quick_actions::notify(&format!("{} failed", title), &format!("{:#}", e));
+            std::process::exit(exit_code_for(&e));
+        }
+        Err(e) => Err(e),
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let loaded = Config::load();
+    let user_aliases = loaded.as_ref().map(|c| c.aliases.clone()).unwrap_or_default();
+    let is_builtin = |name: &str| Cli::command().find_subcommand(name).is_some();
+    let cli = match aliases::expand(std::env::args().collect(), &user_aliases, is_builtin)? {
+        aliases::Expansion::Command(args) => Cli::parse_from(args),
+        aliases::Expansion::Macro(steps) => {
+            let code = aliases::run_macro(&std::env::current_exe()?, &steps)?;
+            std::process::exit(code);
+        }
+    };
+
+    // Enable verbose logging if requested
+    logger::set_verbose(cli.verbose);
+    // Commands run by the daemon also log to the daemon log
+    logger::init_from_env();
+
+    if !matches!(
+        cli.command,
+        Commands::MigrateLegacy { .. } | Commands::Prompt { .. }
+    ) {
+        offer_legacy_migration();
+    }
+    if !matches!(
+        cli.command,
+        Commands::MigrateState { .. } | Commands::Prompt { .. }
+    ) {
+        migrate_state_on_start();
+    }
+    if matches!(cli.command, Commands::Auth(_)) {
+        move_tokens_to_keychain();
+    }
+    // A project naming a server that isn't configured must not quietly
+    // talk to the [cli] one; 'server status' and 'server use' can fix it
+    if let Err(e) = loaded {
+        let fixes_it = matches!(
+            cli.command,
+            Commands::Server(ServerCommands::Status | ServerCommands::Use { .. })
+        );
+        if e.is::<auxin_config::UnknownServer>() && !fixes_it {
+            return Err(e);
+        }
+    }
+
+    if !matches!(
+        cli.command,
+        Commands::Init { .. }
+            | Commands::Clone { .. }
+            | Commands::Features(_)
+            | Commands::Workspace(_)
+            | Commands::Devtools(_)
+            | Commands::UpgradeCheck { .. }
+            | Commands::Doctor
+            | Commands::Prompt { .. }
+    ) {
+        check_repo_features()?;
+    }
+    if !cli.allow_archived {
+        if let Some((name, path)) = mutating_command(&cli.command) {
+            let dir = match path {
+                Some(path) => path.clone(),
+                None => std::env::current_dir()?,
+            };
+            auxin::archive::ensure_writable(&dir, name)?;
+        }
+    }
+
+    let passive_upgrade_check = !matches!(
+        cli.command,
+        Commands::UpgradeCheck { .. } | Commands::Prompt { .. } | Commands::Console { .. }
+    ) && std::env::var_os("AUXIN_LOG_FILE").is_none()
+        && atty::is(atty::Stream::Stderr);
+
+    let result = match cli.command {
+        Commands::Init {
+            path,
+            r#type,
+            logic,
+            without,
+        } => {
+            vlog!("Starting initialization for path: {}", path.display());
+
+            let mut features = auxin_config::RepoFeatures::default();
+            for name in &without {
+                features.features.set(name, false)?;
+            }
+
+            // Determine project type (handle backward compatibility with --logic flag)
+            let registry = ProjectRegistry::builtin();
+            let type_name = if logic {
+                vlog!("Using legacy --logic flag, treating as LogicPro");
+                Some("logicpro".to_string())
+            } else {
+                r#type
+            };
+            let handler = match type_name.as_deref() {
+                None | Some("auto") => {
+                    // Auto-detect based on file extension
+                    let handler = registry.detect(&path);
+                    match handler {
+                        Some(handler) => {
+                            vlog!("Auto-detected {} project", handler.display_name())
+                        }
+                        None => vlog!("No specific project type detected, using generic init"),
+                    }
+                    handler
+                }
+                Some(name) => Some(registry.get(name).unwrap_or_else(|| {
+                    progress::error(&format!(
+                        "Unknown project type: {}. Supported types: auto, {}",
+                        name,
+                        registry.ids().join(", ")
+                    ));
+                    std::process::exit(1);
+                })),
+            };
+
+            let repo_path = match handler {
+                Some(handler) => {
+                    let name = handler.display_name();
+                    let pb =
+                        progress::spinner(&format!("Validating {} project structure...", name));
+                    vlog!("Initializing {} project repository...", name);
+                    let repo =
+                        OxenRepository::init_for_project_with(&path, handler, &features).await?;
+
+                    progress::finish_success(
+                        &pb,
+                        &format!("{} project repository initialized", name),
+                    );
+                    println!();
+                    progress::success(&format!("Repository created at: {}", repo.path.display()));
+                    progress::success("Initial commit created on main branch");
+                    if features.features.draft_workflow {
+                        progress::success("Draft branch created and checked out");
+                    }
+                    match handler.extract_metadata(&path) {
+                        Ok(details) if !details.is_empty() => {
+                            println!();
+                            progress::info("Project details:");
+                            for (label, value) in details {
+                                println!("  • {}: {}", label, value);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => vlog!("Could not read project details: {}", e),
+                    }
+                    println!();
+                    progress::info(&format!("You're all set! Start working in {}:", name));
+                    println!("  • Changes will be automatically tracked on the draft branch");
+                    println!("  • Create milestone commits: {}", handler.commit_example());
+                    println!("  • View history: auxin log");
+                    println!("  • Restore to any commit: auxin restore <commit-id>");
+                    repo.path
+                }
+                None => {
+                    let pb = progress::spinner(&format!(
+                        "Initializing Oxen repository at {}...",
+                        path.display()
+                    ));
+                    vlog!("Initializing generic Oxen repository...");
+                    let _repo = OxenRepository::init_with(&path, &features).await?;
+
+                    progress::finish_success(
+                        &pb,
+                        &format!("Oxen repository initialized at: {}", path.display()),
+                    );
+                    path
+                }
+            };
+            if let Err(e) = auxin::workspace::register(&repo_path) {
+                vlog!("Not added to the workspace: {}", e);
+            }
+            Ok(())
+        }
+
+        Commands::Clone {
+            remote_url,
+            destination,
+        } => {
+            vlog!("Starting clone operation");
+            vlog!("Remote URL: {}", remote_url);
+            vlog!("Destination: {}", destination.display());
+
+            // Check if oxen is available
+            let oxen = OxenSubprocess::new();
+            if !oxen.is_available() {
+                progress::error("oxen CLI not found. Please install: pip install oxen-ai");
+                std::process::exit(1);
+            }
+
+            // Show progress
+            let pb = progress::spinner(&format!("Cloning from {}...", remote_url));
+
+            // Perform the clone
+            match OxenRepository::clone(&remote_url, &destination).await {
+                Ok(_repo) => {
+                    progress::finish_success(&pb, "Repository cloned successfully");
+                    println!();
+                    progress::success(&format!("Project cloned to: {}", destination.display()));
+                    progress::success("All history and files downloaded");
+                    if let Err(e) = auxin::workspace::register(&destination) {
+                        vlog!("Not added to the workspace: {}", e);
+                    }
+                    match auxin_config::RepoFeatures::load(&destination) {
+                        Ok(Some(features)) => {
+                            if let Some(problem) = features.incompatibility() {
+                                warn!(
+                                    "This project {}; upgrade auxin before working in it \
+                                     (see 'auxin upgrade-check')",
+                                    problem
+                                );
+                            }
+                            for (name, _) in auxin_config::Features::KNOWN {
+                                if features.features.get(name) == Some(false) {
+                                    progress::info(&format!(
+                                        "Feature turned off for this project: {}",
+                                        name
+                                    ));
+                                }
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!("{}", e),
+                    }
+                    println!();
+
+                    // Detect project type and give relevant next steps
+                    match ProjectRegistry::builtin().detect(&destination) {
+                        Some(handler) => {
+                            let name = handler.display_name();
+                            progress::info(&format!("{} project cloned successfully!", name));
+                            println!("  • Open in {}: {}", name, destination.display());
+                            println!(
+                                "  • View history: cd {} && auxin log",
+                                destination.display()
+                            );
+                            println!("  • Create commits: {}", handler.commit_example());
+                        }
+                        None => {
+                            progress::info("Project cloned successfully!");
+                            println!("  • Navigate to: cd {}", destination.display());
+                            println!("  • View history: auxin log");
+                            println!(
+                                "  • Make changes and commit: auxin commit -m \"Your message\""
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    progress::finish_error(&pb, "Clone failed");
+                    progress::error(&format!("Failed to clone repository: {}", e));
+
+                    // Provide helpful error messages based on error type
+                    if e.to_string().contains("already exists") {
+                        println!();
+                        progress::info("The destination directory already exists.");
+                        println!("  • Choose a different destination, or");
+                        println!("  • Remove the existing directory and try again");
+                    } else if e.to_string().contains("Network")
+                        || e.to_string().contains("timed out")
+                    {
+                        println!();
+                        progress::info("Network error occurred.");
+                        println!("  • Check your internet connection");
+                        println!("  • Verify the remote URL is correct");
+                        println!("  • Try again in a few moments");
+                    } else if e.to_string().contains("not found") || e.to_string().contains("404") {
+                        println!();
+                        progress::info("Repository not found.");
+                        println!("  • Verify the remote URL is correct");
+                        println!("  • Check if you have access to the repository");
+                        println!("  • For private repos, make sure you're authenticated");
+                    }
+
+                    std::process::exit(1);
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Add {
+            paths,
+            all,
+            dry_run,
+        } => {
+            use auxin::stage_policy::StagePolicy;
+
+            if dry_run {
+                use auxin::describe::{find_repo_root, IgnoreRules};
+
+                if !all && paths.is_empty() {
+                    progress::error("Please provide paths to preview or use --all");
+                    std::process::exit(1);
+                }
+                let cwd = std::env::current_dir()?;
+                let Some(root) = find_repo_root(&cwd) else {
+                    anyhow::bail!("{} is not inside an auxin repository", cwd.display());
+                };
+                let status = OxenSubprocess::new().status(&root)?;
+                let paths: Vec<PathBuf> = if all {
+                    Vec::new()
+                } else {
+                    paths.iter().map(|path| cwd.join(path)).collect()
+                };
+                let mut preview = auxin::staging_preview::preview(
+                    &root,
+                    &paths,
+                    &status,
+                    &IgnoreRules::load(&root)?,
+                )?;
+
+                // --all follows the staging policy; explicit paths don't
+                let policy = StagePolicy::for_project(&root, &Config::load().unwrap_or_default());
+                let plan = (all && !policy.is_empty())
+                    .then(|| auxin::stage_policy::plan(&root, &preview, &policy));
+                if let Some(plan) = &plan {
+                    preview.to_stage = plan.stage.clone();
+                }
+
+                if preview.to_stage.is_empty() {
+                    progress::info("Nothing would be staged");
+                } else {
+                    println!("Would stage {} file(s):", preview.to_stage.len());
+                    for path in &preview.to_stage {
+                        println!("  {} {}", "+".green(), path);
+                    }
+                }
+                if let Some(plan) = &plan {
+                    print_stage_plan(plan);
+                }
+                if !preview.ignored.is_empty() {
+                    println!();
+                    println!(
+                        "Ignored by .oxenignore ({} file(s)):",
+                        preview.ignored_file_count()
+                    );
+                    for entry in &preview.ignored {
+                        let path = if entry.is_dir {
+                            format!("{}/ ({} files)", entry.path, entry.file_count)
+                        } else {
+                            entry.path.clone()
+                        };
+                        println!(
+                            "  {} {}  {}",
+                            "-".dimmed(),
+                            path,
+                            format!("line {}: {}", entry.line, entry.pattern).dimmed()
+                        );
+                    }
+                }
+                return Ok(());
+            }
+
+            auxin::operation_lock::OperationLock::ensure_free(&std::env::current_dir()?)?;
+            let repo = OxenRepository::new(".");
+
+            if all {
+                let pb = progress::spinner("Staging all changes...");
+                let cwd = std::env::current_dir()?;
+                let root = auxin::describe::find_repo_root(&cwd).unwrap_or(cwd);
+                let config = Config::load().unwrap_or_default();
+                if StagePolicy::for_project(&root, &config).is_empty() {
+                    repo.stage_all().await?;
+                    progress::finish_success(&pb, "All changes staged");
+                } else {
+                    let plan = auxin::stage_policy::stage_all(&root, &config)?.unwrap_or_default();
+                    progress::finish_success(
+                        &pb,
+                        &format!("{} file(s) staged by the staging policy", plan.stage.len()),
+                    );
+                    print_stage_plan(&plan);
+                }
+                println!();
+                progress::info("Next step: auxin commit -m \"Your message\"");
+            } else {
+                if paths.is_empty() {
+                    progress::error("Please provide paths to stage or use --all");
+                    std::process::exit(1);
+                }
+                let pb = progress::spinner(&format!("Staging {} file(s)...", paths.len()));
+                repo.stage_changes(paths).await?;
+                progress::finish_success(&pb, "Files staged");
+            }
+
+            Ok(())
+        }
+
+        Commands::Commit {
+            message,
+            bpm,
+            sample_rate,
+            key,
+            units,
+            layers,
+            components,
+            groups,
+            file_size,
+            auto_metadata,
+            tags,
+            bounce,
+            like,
+            keep_lock,
+            fields,
+        } => {
+            auxin::operation_lock::OperationLock::ensure_free(&std::env::current_dir()?)?;
+
+            // Custom fields, checked against [metadata_fields] for this kind of project
+            let custom_fields = {
+                let project_type = auxin::stage_policy::project_type(&std::env::current_dir()?);
+                let defined = Config::load().unwrap_or_default().metadata_fields_for(project_type);
+                let mut metadata = CommitMetadata::new(message.clone());
+                for field in &fields {
+                    let (name, value) = field
+                        .split_once('=')
+                        .ok_or_else(|| anyhow::anyhow!("Expected NAME=VALUE, got '{}'", field))?;
+                    metadata = metadata.with_field(name.trim(), value);
+                }
+                metadata.validate_fields(&defined)?.custom
+            };
+
+            // Metadata not given on the command line defaults to the --like commit's
+            let (bpm, sample_rate, key, tags, units) = match &like {
+                Some(reference) => {
+                    use auxin::commit_template::CommitTemplate;
+                    use auxin::metadata_edits::MetadataEdits;
+
+                    let current_dir = std::env::current_dir()?;
+                    let history = OxenSubprocess::new().log(&current_dir, None)?;
+                    let source = CommitTemplate::find(&history, reference)?;
+                    let edits = MetadataEdits::load(&current_dir).unwrap_or_default();
+                    let template = CommitTemplate::from_commit(source, &edits);
+                    if template.is_empty() {
+                        warn!("Commit {} has no metadata to copy", reference);
+                    } else {
+                        vlog!("Metadata from {}: {}", source.id, template.summary());
+                    }
+                    (
+                        bpm.or(template.bpm),
+                        sample_rate.or(template.sample_rate),
+                        key.or(template.key),
+                        tags.or(template.tags),
+                        units.or(template.units),
+                    )
+                }
+                None => (bpm, sample_rate, key, tags, units),
+            };
+
+            // --auto-metadata reads the model here: a .skp or, failing that, a .blend
+            let (skp_model, blend_file) = if auto_metadata {
+                use auxin::project_handler::newest_file_with_extension;
+
+                let dir = std::env::current_dir()?;
+                let skp_model = newest_file_with_extension(&dir, "skp")?;
+                let blend_file = match skp_model {
+                    Some(_) => None,
+                    None => newest_file_with_extension(&dir, "blend")?,
+                };
+                if skp_model.is_none() && blend_file.is_none() {
+                    anyhow::bail!(
+                        "No SketchUp (.skp) or Blender (.blend) file found in {}",
+                        dir.display()
+                    );
+                }
+                (skp_model, blend_file)
+            } else {
+                (None, None)
+            };
+
+            // SketchUp counts not given on the command line are read from the model
+            let (layers, components, groups, file_size) = if let Some(model) = &skp_model {
+                let info = SketchUpParser::parse(model)?;
+                vlog!("Metadata from {}", model.display());
+                (
+                    layers.or(Some(info.layer_count)),
+                    components.or(Some(info.component_count)),
+                    groups.or(Some(info.group_count)),
+                    file_size.or(Some(info.file_size_bytes)),
+                )
+            } else {
+                (layers, components, groups, file_size)
+            };
+
+            let pb = progress::spinner("Preparing commit...");
+            let repo = OxenRepository::new(".");
+
+            // Detect if we're using Logic Pro, SketchUp or Blender metadata
+            let has_logic_metadata = bpm.is_some() || sample_rate.is_some() || key.is_some();
+            let has_sketchup_metadata = units.is_some()
+                || layers.is_some()
+                || components.is_some()
+                || groups.is_some()
+                || file_size.is_some();
+
+            let formatted_message = if has_sketchup_metadata {
+                // SketchUp project - use SketchUpMetadata
+                vlog!("Using SketchUp metadata");
+                let mut skp_metadata = SketchUpMetadata::new(message.clone());
+
+                if let Some(ref units_val) = units {
+                    skp_metadata = skp_metadata.with_units(units_val.clone());
+                }
+
+                if let Some(layer_count) = layers {
+                    skp_metadata = skp_metadata.with_layer_count(layer_count);
+                }
+
+                if let Some(component_count) = components {
+                    skp_metadata = skp_metadata.with_component_count(component_count);
+                }
+
+                if let Some(group_count) = groups {
+                    skp_metadata = skp_metadata.with_group_count(group_count);
+                }
+
+                if let Some(size) = file_size {
+                    skp_metadata = skp_metadata.with_file_size(size);
+                }
+
+                if let Some(ref tags_str) = tags {
+                    for tag in tags_str.split(',') {
+                        skp_metadata = skp_metadata.with_tag(tag.trim());
+                    }
+                }
+
+                skp_metadata.format_commit_message()
+            } else if has_logic_metadata {
+                // Logic Pro project - use CommitMetadata
+                vlog!("Using Logic Pro metadata");
+                let mut metadata = CommitMetadata::new(message.clone());
+
+                if let Some(bpm) = bpm {
+                    metadata = metadata.with_bpm(bpm);
+                }
+
+                if let Some(sr) = sample_rate {
+                    metadata = metadata.with_sample_rate(sr);
+                }
+
+                if let Some(ref key_val) = key {
+                    metadata = metadata.with_key_signature(key_val.clone());
+                }
+
+                if let Some(ref tags_str) = tags {
+                    for tag in tags_str.split(',') {
+                        metadata = metadata.with_tag(tag.trim());
+                    }
+                }
+
+                metadata.format_commit_message()
+            } else if let Some(blend_file) = &blend_file {
+                vlog!("Using Blender metadata from {}", blend_file.display());
+                let info = BlenderParser::parse(blend_file)?;
+                let mut blend_metadata = info.metadata(message.clone());
+
+                if let Some(ref tags_str) = tags {
+                    for tag in tags_str.split(',') {
+                        blend_metadata = blend_metadata.with_tag(tag.trim());
+                    }
+                }
+
+                blend_metadata.format_commit_message()
+            } else {
+                // No metadata, just use message
+                vlog!("No metadata provided, using plain message");
+                if let Some(ref tags_str) = tags {
+                    let mut msg = message.clone();
+                    msg.push_str(&format!("\n\nTags: {}", tags_str));
+                    msg
+                } else {
+                    message.clone()
+                }
+            };
+
+            // Some projects record details with every commit, e.g. Unreal's engine version
+            let current_dir = std::env::current_dir()?;
+            let formatted_message = match ProjectRegistry::builtin().detect(&current_dir) {
+                Some(handler) => match handler.commit_metadata(&current_dir) {
+                    Ok(details) => auxin::project_handler::append_commit_metadata(
+                        &formatted_message,
+                        &details,
+                    ),
+                    Err(e) => {
+                        vlog!("Could not read {} project details: {}", handler.display_name(), e);
+                        formatted_message
+                    }
+                },
+                None => formatted_message,
+            };
+            let custom_fields: Vec<(&str, String)> =
+                custom_fields.iter().map(|(name, value)| (name.as_str(), value.clone())).collect();
+            let formatted_message =
+                auxin::project_handler::append_commit_metadata(&formatted_message, &custom_fields);
+
+            // A milestone's session sheet lists the staged files, so read them first
+            let sheets = Config::load().unwrap_or_default().integrations.session_sheets;
+            let sheet_files = if sheets.enabled
+                && auxin::session_sheet::is_milestone(&CommitMetadata::parse_commit_message(
+                    &formatted_message,
+                ))
+            {
+                let status = OxenSubprocess::new().status(&std::env::current_dir()?);
+                Some(status.map(|s| s.staged).unwrap_or_default())
+            } else {
+                None
+            };
+
+            pb.set_message("Creating commit...");
+            let commit_metadata = CommitMetadata::new(formatted_message.clone());
+            let commit_id = repo.create_commit(commit_metadata).await?;
+
+            progress::finish_success(&pb, &format!("Commit created: {}", commit_id));
+
+            // Structured copy of the metadata for log, search and compare
+            let store = MetadataStore::for_repo(&current_dir);
+            if let Err(e) = store.record(&commit_id, &formatted_message) {
+                vlog!("Could not store commit metadata: {}", e);
+            }
+
+            // Store metadata on server if configured
+            let config = auxin_config::load_config().unwrap_or_default();
+
+            // The next auto-commit title describes changes since this commit
+            if config.daemon.auto_titles {
+                if let Err(e) = auxin::auto_title::save_snapshot(&std::env::current_dir()?) {
+                    vlog!("Could not save auto-title snapshot: {}", e);
+                }
+            }
+
+            // Track count for `auxin compare --range/--last`
+            if let Err(e) =
+                auxin::evolution::record_track_count(&std::env::current_dir()?, &commit_id)
+            {
+                vlog!("Could not record track count: {}", e);
+            }
+
+            // What the next auto-commit is checked against
+            if config.daemon.anomaly_detection {
+                if let Err(e) =
+                    auxin::anomaly::Baseline::record(&std::env::current_dir()?, Some(&commit_id))
+                {
+                    vlog!("Could not record anomaly baseline: {}", e);
+                }
+            }
+            if config.cli.use_server_metadata {
+                let server_config = ServerConfig::from_config(&config);
+
+                if let Ok(client) = AuxinServerClient::new(server_config) {
+                    let namespace = config.cli.default_namespace.clone();
+                    let current_dir = std::env::current_dir()?;
+                    let repo_name = current_dir
+                        .file_name()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+
+                    // Build server metadata
+                    let server_metadata = auxin::server_client::LogicProMetadata {
+                        bpm: bpm.map(|b| b as f64),
+                        sample_rate,
+                        key_signature: key.clone(),
+                        tags: tags
+                            .as_ref()
+                            .map(|t| t.split(',').map(|s| s.trim().to_string()).collect()),
+                        custom: None,
+                    };
+
+                    match client.store_metadata(
+                        &namespace,
+                        &repo_name,
+                        &commit_id,
+                        &server_metadata,
+                    ) {
+                        Ok(()) => {
+                            vlog!("Metadata stored on server for commit {}", commit_id);
+                        }
+                        Err(e) => {
+                            vlog!("Failed to store metadata on server: {}", e);
+                            // Retried with the next queue sync
+                            let queued = auxin::OfflineQueue::new().and_then(|mut queue| {
+                                queue.enqueue(auxin::OfflineQueuedOperation::StoreMetadata {
+                                    repo_path: current_dir.to_string_lossy().to_string(),
+                                    namespace,
+                                    name: repo_name,
+                                    commit_id: commit_id.clone(),
+                                    metadata: server_metadata,
+                                })
+                            });
+                            if let Err(e) = queued {
+                                vlog!("Could not queue metadata for later: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Show commit details
+            println!();
+            progress::info("Commit Details:");
+            println!("  Message: {}", message);
+
+            // Show Logic Pro metadata
+            if let Some(ref bpm_val) = bpm {
+                println!("  BPM: {}", bpm_val);
+            }
+            if let Some(ref sr_val) = sample_rate {
+                println!("  Sample Rate: {} Hz", sr_val);
+            }
+            if let Some(ref key_val) = key {
+                println!("  Key: {}", key_val);
+            }
+
+            // Show SketchUp metadata
+            if let Some(ref units_val) = units {
+                println!("  Units: {}", units_val);
+            }
+            if let Some(layer_count) = layers {
+                println!("  Layers: {}", layer_count);
+            }
+            if let Some(component_count) = components {
+                println!("  Components: {}", component_count);
+            }
+            if let Some(group_count) = groups {
+                println!("  Groups: {}", group_count);
+            }
+            if let Some(size) = file_size {
+                let size_mb = size as f64 / (1024.0 * 1024.0);
+                println!("  File Size: {:.2} MB", size_mb);
+            }
+
+            // Show common metadata
+            if let Some(ref tags_val) = tags {
+                println!("  Tags: {}", tags_val);
+            }
+
+            // Extract thumbnail for Logic Pro projects
+            if has_logic_metadata {
+                let current_dir = std::env::current_dir()?;
+                let thumbnail_manager = ThumbnailManager::new(&current_dir);
+
+                // Try to detect Logic Pro project and extract thumbnail
+                if let Ok(logic_project) = LogicProject::detect(&current_dir) {
+                    let pb = progress::spinner("Extracting project thumbnail...");
+                    match thumbnail_manager.extract_logic_thumbnail(&commit_id, &logic_project.path)
+                    {
+                        Ok(thumb_metadata) => {
+                            progress::finish_success(&pb, "Thumbnail extracted");
+                            if let Some(width) = thumb_metadata.width {
+                                if let Some(height) = thumb_metadata.height {
+                                    println!(
+                                        "  Thumbnail: {}x{} ({})",
+                                        width, height, thumb_metadata.format
+                                    );
+                                } else {
+                                    println!("  Thumbnail: {}", thumb_metadata.format);
+                                }
+                            } else {
+                                println!("  Thumbnail: {}", thumb_metadata.format);
+                            }
+                        }
+                        Err(e) => {
+                            // Don't fail the commit if thumbnail extraction fails
+                            vlog!("Could not extract thumbnail: {}", e);
+                        }
+                    }
+                }
+            }
+
+            // Process bounce file if provided
+            if let Some(bounce_path) = bounce {
+                let current_dir = std::env::current_dir()?;
+                let bounce_manager = BounceManager::new(&current_dir);
+
+                let pb = progress::spinner("Adding bounce file...");
+                match bounce_manager.add_bounce(&commit_id, &bounce_path, None) {
+                    Ok(metadata) => {
+                        progress::finish_success(&pb, "Bounce added");
+                        println!(
+                            "  Bounce: {} ({}, {})",
+                            metadata.original_filename,
+                            metadata.format_duration(),
+                            metadata.format_size()
+                        );
+                    }
+                    Err(e) => {
+                        progress::finish_error(&pb, "Failed to add bounce");
+                        warn!("Bounce error: {}", e);
+                    }
+                }
+            }
+
+            // Make the commit findable from Spotlight
+            if config.integrations.spotlight.enabled {
+                let commit = auxin_oxen::CommitInfo {
+                    id: commit_id.clone(),
+                    message: formatted_message.clone(),
+                    ..Default::default()
+                };
+                let project = std::env::current_dir()?;
+                let index = auxin::spotlight::SpotlightIndex::for_project(&project);
+                if let Err(e) = index.write(&commit) {
+                    vlog!("Failed to write Spotlight sidecar: {}", e);
+                }
+            }
+
+            if config.versioning.auto
+                && auxin::session_sheet::is_milestone(&CommitMetadata::parse_commit_message(
+                    &formatted_message,
+                ))
+            {
+                let commit = auxin_oxen::CommitInfo {
+                    id: commit_id.clone(),
+                    message: formatted_message.clone(),
+                    ..Default::default()
+                };
+                let project = std::env::current_dir()?;
+                let format = &config.versioning.format;
+                let bump = auxin::versioning::Bump::Minor;
+                match auxin::versioning::record(&project, &commit, bump, format, None) {
+                    Ok(version) => println!("  Version: {}", version.label),
+                    Err(e) => warn!("Could not assign a version: {}", e),
+                }
+            }
+
+            if let Some(files) = sheet_files {
+                let project = std::env::current_dir()?;
+                let commit = auxin_oxen::CommitInfo {
+                    id: commit_id.clone(),
+                    message: formatted_message.clone(),
+                    ..Default::default()
+                };
+                let files = files.iter().map(|f| f.display().to_string()).collect();
+                let sheet = auxin::session_sheet::SessionSheet::collect(&project, &commit, files);
+                match sheet.write(&project) {
+                    Ok(path) => println!("  Session sheet: {}", path.display()),
+                    Err(e) => warn!("Could not write session sheet: {}", e),
+                }
+            }
+
+            sync_meta_branch(&std::env::current_dir()?);
+            if !keep_lock {
+                release_lock_after(&std::env::current_dir()?, LockRelease::Commit);
+            }
+            Ok(())
+        }
+
+        Commands::Log {
+            limit,
+            bpm,
+            tag,
+            key,
+            since,
+            until,
+            format,
+        } => {
+            let json = format == "json" || cli.json;
+            let now = chrono::Utc::now();
+            let since_time =
+                since.as_deref().map(|s| auxin::daemon_log::parse_since(s, now)).transpose()?;
+            let until_time = until
+                .as_deref()
+                .map(|u| {
+                    // A date means up to the end of that day
+                    let time = auxin::daemon_log::parse_since(u, now)?;
+                    let whole_day = chrono::NaiveDate::parse_from_str(u.trim(), "%Y-%m-%d").is_ok();
+                    anyhow::Ok(if whole_day {
+                        time + chrono::Duration::days(1) - chrono::Duration::nanoseconds(1)
+                    } else {
+                        time
+                    })
+                })
+                .transpose()?;
+            let repo = OxenRepository::new(".");
+
+            let repo_root = std::env::current_dir()?;
+            let edits =
+                auxin::metadata_edits::MetadataEdits::load(&repo_root).unwrap_or_default();
+            let store = MetadataStore::for_repo(&repo_root);
+            let mut history = repo.get_history(None).await?;
+            edits.apply_to_history(&mut history);
+
+            if history.is_empty() {
+                // If JSON format, return empty array
+                if json {
+                    println!("[]");
+                    return Ok(());
+                }
+
+                println!();
+                progress::info("No commits yet");
+                println!();
+                progress::info("Create your first commit:");
+                println!("  auxin add --all");
+                println!("  auxin commit -m \"Initial commit\"");
+                return Ok(());
+            }
+
+            // Each commit's metadata, from its sidecar, with edits applied
+            let mut commits: Vec<(auxin::CommitInfo, StoredMetadata)> = history
+                .into_iter()
+                .map(|commit| {
+                    let mut stored = store.get(&commit);
+                    stored.metadata = edits.apply(&commit.id, stored.metadata);
+                    (commit, stored)
+                })
+                .collect();
+
+            // Apply filters
+            let total_before_filter = commits.len();
+            let mut filters_applied = vec![];
+
+            if let Some(bpm_filter) = bpm {
+                commits.retain(|(_, stored)| {
+                    stored.metadata.bpm.is_some_and(|b| (b - bpm_filter).abs() < 0.01)
+                });
+                filters_applied.push(format!("BPM = {}", bpm_filter));
+            }
+
+            if let Some(tag_filter) = &tag {
+                let wanted = tag_filter.to_lowercase();
+                commits.retain(|(_, stored)| {
+                    stored.metadata.tags.iter().any(|t| t.to_lowercase().contains(&wanted))
+                });
+                filters_applied.push(format!("tag = {}", tag_filter));
+            }
+
+            if let Some(key_filter) = &key {
+                commits.retain(|(_, stored)| {
+                    stored.metadata.key_signature.as_deref().is_some_and(|stored| {
+                        auxin::music_theory::key_matches(stored, key_filter)
+                    })
+                });
+                filters_applied.push(format!("key = {}", key_filter));
+            }
+
+            // Commits without a date can't be placed in a range
+            if since_time.is_some() || until_time.is_some() {
+                commits.retain(|(commit, _)| {
+                    commit.timestamp.is_some_and(|t| {
+                        since_time.is_none_or(|s| t >= s) && until_time.is_none_or(|u| t <= u)
+                    })
+                });
+            }
+            if let Some(since) = &since {
+                filters_applied.push(format!("since {}", since));
+            }
+            if let Some(until) = &until {
+                filters_applied.push(format!("until {}", until));
+            }
+
+            // Apply limit after filtering
+            if let Some(lim) = limit {
+                commits.truncate(lim);
+            }
+
+            // If JSON format requested, output JSON and return
+            if json {
+                let commits: Vec<&auxin::CommitInfo> =
+                    commits.iter().map(|(commit, _)| commit).collect();
+                let json = serde_json::to_string_pretty(&commits)?;
+                println!("{}", json);
+                return Ok(());
+            }
+
+            // Show results (text format)
+            println!();
+            println!("┌─ Commit History ────────────────────────────────────────┐");
+            if !filters_applied.is_empty() {
+                println!(
+                    "│ Filters: {}                                              │",
+                    filters_applied.join(", ")
+                );
+                println!(
+                    "│ Found {} of {} commit(s)                                 │",
+                    commits.len(),
+                    total_before_filter
+                );
+            } else if let Some(_lim) = limit {
+                println!(
+                    "│ Showing last {} of {} commit(s)                          │",
+                    commits.len(),
+                    total_before_filter
+                );
+            } else {
+                println!(
+                    "│ Showing all {} commit(s)                                 │",
+                    commits.len()
+                );
+            }
+            println!("└──────────────────────────────────────────────────────────┘");
+            println!();
+
+            if commits.is_empty() {
+                progress::info("No commits match the specified filters");
+                return Ok(());
+            }
+
+            let unit_system =
+                UnitSystem::from_config(&Config::load().unwrap_or_default().ui.unit_system);
+            let versions =
+                auxin::versioning::VersionLog::load(&std::env::current_dir()?).unwrap_or_default();
+
+            for (idx, (commit, stored)) in commits.iter().enumerate() {
+                let short_id = &commit.id[..7.min(commit.id.len())];
+                let version = versions
+                    .for_commit(&commit.id)
+                    .map(|v| format!(" {}", format!("[{}]", v.label).bright_green().bold()))
+                    .unwrap_or_default();
+
+                let mut when = commit
+                    .timestamp
+                    .map(|t| commit_age(t, now))
+                    .unwrap_or_else(|| "unknown date".to_string());
+                if let Some(author) = &commit.author {
+                    // Without the email
+                    let name = author.split(" <").next().unwrap_or(author);
+                    when = format!("{} by {}", when, name);
+                }
+
+                // Visual timeline with bullets
+                println!(
+                    "{} {}{} - {}",
+                    "●".cyan(),
+                    short_id.bright_yellow(),
+                    version,
+                    when.bright_black()
+                );
+
+                // Commit message (indented)
+                let mut lines = stored.metadata.message.lines().filter(|l| !l.trim().is_empty());
+                if let Some(first_line) = lines.next() {
+                    println!("  │ {}", first_line.bright_white());
+                }
+                for line in lines {
+                    println!("  │ {}", line.trim());
+                }
+
+                // Metadata, dimmed
+                let block = CommitMetadata {
+                    message: String::new(),
+                    ..stored.metadata.clone()
+                }
+                .format_commit_message();
+                let mut metadata_lines: Vec<String> =
+                    block.lines().filter(|l| !l.is_empty()).map(String::from).collect();
+                for (label, value) in &stored.details {
+                    let units = (label == "Units")
+                        .then(|| value.parse::<ModelUnits>().ok())
+                        .flatten();
+                    match units {
+                        Some(units) => metadata_lines
+                            .push(format!("Units: {}", units.display_in(unit_system))),
+                        None => metadata_lines.push(format!("{}: {}", label, value)),
+                    }
+                }
+                for line in metadata_lines {
+                    println!("  │ {}", line.bright_black());
+                }
+
+                // Add spacing between commits (except last one)
+                if idx < commits.len() - 1 {
+                    println!("  │");
+                }
+            }
+
+            println!();
+            progress::info(&format!("Showing {} commit(s)", commits.len()));
+
+            Ok(())
+        }
+
+        Commands::Restore {
+            commit_id,
+            no_verify,
+        } => {
+            let commit_id = auxin::commit_link::resolve_commit_arg(&commit_id);
+            let cwd = std::env::current_dir()?;
+            let _lock = auxin::operation_lock::OperationLock::acquire(&cwd, "restore")?;
+            record_tree_manifest(&cwd, "restore", &commit_id);
+            let pb = progress::spinner(&format!(
+                "Restoring to commit {}...",
+                &commit_id[..7.min(commit_id.len())]
+            ));
+            let repo = OxenRepository::new(".");
+
+            pb.set_message("Checking out files...");
+            let full_hash = repo.restore(&commit_id).await?;
+
+            progress::finish_success(
+                &pb,
+                &format!(
+                    "Restored to commit {}",
+                    &commit_id[..7.min(commit_id.len())]
+                ),
+            );
+
+            if !no_verify {
+                use auxin::restore_verify::{self, OxenRestoreSource};
+
+                let files = restore_verify::files_to_check(&cwd)?;
+                let oxen = OxenSubprocess::new();
+                let source = OxenRestoreSource::new(&oxen, &cwd, &full_hash);
+                let pb = progress::progress_bar(files.len() as u64, "Verifying restored files");
+                let report = restore_verify::verify(&files, &source, |path| {
+                    pb.set_message(format!("Verifying {}", path));
+                    pb.inc(1);
+                });
+                pb.finish_and_clear();
+
+                for path in &report.repaired {
+                    progress::info(&format!("Restored {} again after a hash mismatch", path));
+                }
+                if !report.is_verified() {
+                    progress::error(&format!(
+                        "{} file(s) don't match commit {}:",
+                        report.failed.len(),
+                        &full_hash[..7.min(full_hash.len())]
+                    ));
+                    for (path, reason) in &report.failed {
+                        println!("  {} {}  {}", "✗".red(), path, reason.dimmed());
+                    }
+                    anyhow::bail!("Restore could not be verified; run it again or check the files");
+                }
+                progress::success(&format!(
+                    "Verified {} file(s) ({}) against the commit",
+                    report.verified,
+                    auxin::describe::format_size(report.verified_bytes)
+                ));
+            }
+            println!();
+            progress::warning("Your working directory has been updated to match this commit");
+            progress::info("To create a new commit from here, use:");
+            println!("  auxin add --all");
+            println!("  auxin commit -m \"Your message\"");
+
+            Ok(())
+        }
+
+        Commands::WhatChanged { .. } => {
+            use auxin::tree_manifest::{self, ChangeKind};
+
+            let cwd = std::env::current_dir()?;
+            let repo = auxin::describe::find_repo_root(&cwd).unwrap_or(cwd);
+            let Some((manifest, changes)) = tree_manifest::since_last_operation(&repo)? else {
+                progress::info("No restore or pull recorded for this project yet");
+                return Ok(());
+            };
+
+            println!(
+                "Since {} {} ({}):",
+                manifest.operation,
+                manifest.target,
+                manifest
+                    .taken_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+            );
+            if changes.is_empty() {
+                progress::success("Nothing changed");
+                return Ok(());
+            }
+
+            let size = |entry: &Option<tree_manifest::ManifestEntry>| {
+                entry
+                    .as_ref()
+                    .map(|e| auxin::describe::format_size(e.size))
+                    .unwrap_or_default()
+            };
+            let count = |kind: ChangeKind| changes.iter().filter(|c| c.kind == kind).count();
+            for change in &changes {
+                match change.kind {
+                    ChangeKind::Added => println!(
+                        "  {} {}  {}",
+                        "+".green(),
+                        change.path,
+                        size(&change.after).dimmed()
+                    ),
+                    ChangeKind::Removed => println!(
+                        "  {} {}  {}",
+                        "-".red(),
+                        change.path,
+                        size(&change.before).dimmed()
+                    ),
+                    ChangeKind::Modified => println!(
+                        "  {} {}  {}",
+                        "M".yellow(),
+                        change.path,
+                        format!("{} → {}", size(&change.before), size(&change.after)).dimmed()
+                    ),
+                    ChangeKind::PossiblyModified => println!(
+                        "  {} {}  {}",
+                        "~".yellow(),
+                        change.path,
+                        "same size, new timestamp".dimmed()
+                    ),
+                }
+            }
+            let summary: Vec<String> = [
+                (ChangeKind::Modified, "modified"),
+                (ChangeKind::PossiblyModified, "possibly modified"),
+                (ChangeKind::Added, "added"),
+                (ChangeKind::Removed, "removed"),
+            ]
+            .into_iter()
+            .filter(|(kind, _)| count(*kind) > 0)
+            .map(|(kind, label)| format!("{} {}", count(kind), label))
+            .collect();
+            println!();
+            progress::info(&summary.join(", "));
+            Ok(())
+        },
+
+        Commands::Status => {
+            let repo = OxenRepository::new(".");
+
+            let status = repo.status().await?;
+
+            if cli.json {
+                let pending_sync = auxin::OfflineQueue::new()
+                    .map(|queue| queue.pending().len())
+                    .unwrap_or(0);
+                let report = auxin::reports::StatusReport::new(status, pending_sync);
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+
+            // Header
+            println!();
+            println!("┌─ Repository Status ─────────────────────────────────────┐");
+            println!("│                                                          │");
+
+            let total_changes =
+                status.staged.len() + status.modified.len() + status.untracked.len();
+
+            if total_changes == 0 {
+                println!("│  ✓ Working directory clean                              │");
+            } else {
+                println!(
+                    "│  Changes: {} staged, {} modified, {} untracked",
+                    status.staged.len().to_string().green(),
+                    status.modified.len().to_string().yellow(),
+                    status.untracked.len().to_string().cyan(),
+                );
+            }
+
+            // Check for pending sync operations
+            {
+                use auxin::OfflineQueue;
+                if let Ok(queue) = OfflineQueue::new() {
+                    let pending_count = queue.pending().len();
+                    if pending_count > 0 {
+                        println!("│                                                          │");
+                        println!(
+                            "│  {} {} pending sync operation(s)                        │",
+                            "⟳".yellow(),
+                            pending_count.to_string().yellow()
+                        );
+                    }
+                }
+            }
+
+            println!("│                                                          │");
+            println!("└──────────────────────────────────────────────────────────┘");
+            println!();
+
+            if !status.staged.is_empty() {
+                println!("{} Staged files ({}):", "●".green(), status.staged.len());
+                for path in &status.staged {
+                    println!("  {} {}", "+".green(), path.display());
+                }
+                println!();
+            }
+
+            if !status.modified.is_empty() {
+                println!(
+                    "{} Modified files ({}):",
+                    "◆".yellow(),
+                    status.modified.len()
+                );
+                for path in &status.modified {
+                    println!("  {} {}", "M".yellow(), path.display());
+                }
+                println!();
+            }
+
+            if !status.untracked.is_empty() {
+                println!(
+                    "{} Untracked files ({}):",
+                    "?".cyan(),
+                    status.untracked.len()
+                );
+                for path in &status.untracked {
+                    println!("  {} {}", "?".cyan(), path.display());
+                }
+                println!();
+            }
+
+            // Next steps suggestion
+            if total_changes > 0 {
+                if status.staged.is_empty()
+                    && (!status.modified.is_empty() || !status.untracked.is_empty())
+                {
+                    progress::info("Next step: auxin add --all");
+                } else if !status.staged.is_empty() {
+                    progress::info("Next step: auxin commit -m \"Your message\"");
+                }
+            }
+
+            // Hint about pending sync operations
+            {
+                use auxin::OfflineQueue;
+                if let Ok(queue) = OfflineQueue::new() {
+                    let pending_count = queue.pending().len();
+                    if pending_count > 0 {
+                        progress::warning(&format!("{} operation(s) pending sync", pending_count));
+                        progress::info("Sync with: auxin queue sync");
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Show { commit_id } => {
+            let commit_id = auxin::commit_link::resolve_commit_arg(&commit_id);
+            let repo = OxenRepository::new(".");
+
+            // Get all commits to find the one we want
+            let commits = repo.get_history(None).await?;
+
+            let commit = commits
+                .iter()
+                .find(|c| c.id.starts_with(&commit_id) || c.id == commit_id);
+
+            if let Some(commit) = commit.filter(|_| cli.json) {
+                let cwd = std::env::current_dir()?;
+                let mut report = auxin::reports::CommitReport::new(&commit.id, &commit.message);
+                let versions = auxin::versioning::VersionLog::load(&cwd).unwrap_or_default();
+                report.version = versions.for_commit(&commit.id).cloned();
+                let deliveries = auxin::delivery::DeliveryLog::load(&cwd).unwrap_or_default();
+                report.deliveries = deliveries.for_commit(&commit.id).cloned().collect();
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else if let Some(commit) = commit {
+                println!();
+                println!("┌─ Commit Details ────────────────────────────────────────┐");
+                println!("│                                                          │");
+                println!(
+                    "│  Commit: {}                                      │",
+                    commit.id.bright_yellow()
+                );
+                println!("│                                                          │");
+                println!("└──────────────────────────────────────────────────────────┘");
+                println!();
+
+                let versions = auxin::versioning::VersionLog::load(&std::env::current_dir()?)
+                    .unwrap_or_default();
+                if let Some(version) = versions.for_commit(&commit.id) {
+                    println!("{}", "Version:".bright_white().bold());
+                    match &version.note {
+                        Some(note) => println!("  {} - {}", version.label.bright_green(), note),
+                        None => println!("  {}", version.label.bright_green()),
+                    }
+                    println!();
+                }
+
+                let deliveries = auxin::delivery::DeliveryLog::load(&std::env::current_dir()?)
+                    .unwrap_or_default();
+                let sent: Vec<_> = deliveries.for_commit(&commit.id).collect();
+                if !sent.is_empty() {
+                    println!("{}", "Delivered:".bright_white().bold());
+                    for delivery in sent {
+                        let format = delivery
+                            .format
+                            .as_ref()
+                            .map(|f| format!(" ({})", f))
+                            .unwrap_or_default();
+                        let when = delivery.delivered_at.with_timezone(&chrono::Local);
+                        println!(
+                            "  {}{} - {}",
+                            delivery.recipient,
+                            format,
+                            when.format("%Y-%m-%d %H:%M").to_string().bright_black()
+                        );
+                    }
+                    println!();
+                }
+
+                // Parse commit message and metadata
+                let lines: Vec<&str> = commit.message.lines().collect();
+
+                // First line is the commit message
+                if let Some(first_line) = lines.first() {
+                    println!("{}", "Message:".bright_white().bold());
+                    println!("  {}", first_line);
+                    println!();
+                }
+
+                // Extract metadata
+                let mut metadata_found = false;
+                for line in lines.iter().skip(1) {
+                    let trimmed = line.trim();
+                    if !trimmed.is_empty() {
+                        if trimmed.starts_with("BPM:")
+                            || trimmed.starts_with("Sample Rate:")
+                            || trimmed.starts_with("Key:")
+                            || trimmed.starts_with("Tags:")
+                        {
+                            if !metadata_found {
+                                println!("{}", "Metadata:".bright_white().bold());
+                                metadata_found = true;
+                            }
+                            println!("  {}", trimmed.bright_black());
+                        } else {
+                            // Additional commit message content
+                            println!("  {}", trimmed);
+                        }
+                    }
+                }
+
+                println!();
+                progress::info(&format!(
+                    "Use 'auxin restore {}' to restore to this commit",
+                    &commit.id[..7.min(commit.id.len())]
+                ));
+            } else {
+                progress::error(&format!("Commit not found: {}", commit_id));
+                std::process::exit(1);
+            }
+
+            Ok(())
+        }
+
+        Commands::Link { commit_id, no_copy } => {
+            use auxin::commit_link::{self, CommitLink};
+
+            let repo = OxenRepository::new(".");
+            let commits = repo.get_history(None).await?;
+            let commit = match &commit_id {
+                Some(id) => {
+                    let id = commit_link::resolve_commit_arg(id);
+                    commits.iter().find(|c| c.id.starts_with(&id))
+                }
+                None => commits.first(),
+            };
+            let Some(commit) = commit else {
+                match commit_id {
+                    Some(id) => progress::error(&format!("Commit not found: {}", id)),
+                    None => progress::error("No commits yet"),
+                }
+                std::process::exit(1);
+            };
+
+            let config = Config::load().unwrap_or_default();
+            let link = CommitLink::for_repo(&config, &std::env::current_dir()?, &commit.id)?;
+            println!("{}", link.uri());
+            match link.web_url(&config.cli.url) {
+                Ok(url) => println!("{}", url),
+                Err(e) => vlog!("No web link: {}", e),
+            }
+
+            if !no_copy {
+                match commit_link::copy_to_clipboard(&link.uri()) {
+                    Ok(_) => progress::success("Copied to clipboard"),
+                    Err(e) => vlog!("Not copied: {}", e),
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Diff { commit_id, other } => {
+            if let (Some(from), Some(to)) = (&commit_id, &other) {
+                use auxin::FileChangeKind;
+
+                let from = auxin::commit_link::resolve_commit_arg(from);
+                let to = auxin::commit_link::resolve_commit_arg(to);
+                let changes =
+                    OxenSubprocess::new().diff_commits(&std::env::current_dir()?, &from, &to)?;
+                if cli.json {
+                    println!("{}", serde_json::to_string_pretty(&changes)?);
+                    return Ok(());
+                }
+
+                println!();
+                println!(
+                    "┌─ Changes From {} To {} ───────────────────────┐",
+                    &from[..7.min(from.len())].bright_yellow(),
+                    &to[..7.min(to.len())].bright_yellow()
+                );
+                println!("└──────────────────────────────────────────────────────────┘");
+                println!();
+
+                if changes.is_empty() {
+                    progress::info("No file changes between these commits");
+                    return Ok(());
+                }
+
+                let size = |bytes: Option<u64>| bytes.map(auxin::describe::format_size);
+                let signed = |delta: i64| {
+                    let sign = if delta < 0 { "-" } else { "+" };
+                    format!("{}{}", sign, auxin::describe::format_size(delta.unsigned_abs()))
+                };
+                let sections = [
+                    (FileChangeKind::Modified, "Modified", "◆".yellow(), "~".yellow()),
+                    (FileChangeKind::Added, "Added", "◆".green(), "+".green()),
+                    (FileChangeKind::Removed, "Deleted", "◆".red(), "-".red()),
+                ];
+                for (kind, title, bullet, marker) in sections {
+                    let files: Vec<_> = changes.iter().filter(|c| c.kind == kind).collect();
+                    if files.is_empty() {
+                        continue;
+                    }
+                    println!("{} {} files ({}):", bullet, title, files.len());
+                    for change in files {
+                        let detail = match kind {
+                            FileChangeKind::Modified => {
+                                match (size(change.old_size), size(change.new_size)) {
+                                    (Some(old), Some(new)) => Some(format!(
+                                        "{} → {}, {}",
+                                        old,
+                                        new,
+                                        signed(change.size_delta().unwrap_or(0))
+                                    )),
+                                    _ => None,
+                                }
+                            }
+                            FileChangeKind::Added => size(change.new_size),
+                            FileChangeKind::Removed => size(change.old_size),
+                        };
+                        match detail {
+                            Some(detail) => println!(
+                                "  {} {} {}",
+                                marker,
+                                change.path.display(),
+                                format!("({})", detail).bright_black()
+                            ),
+                            None => println!("  {} {}", marker, change.path.display()),
+                        }
+                    }
+                    println!();
+                }
+
+                let count = |kind| changes.iter().filter(|c| c.kind == kind).count();
+                progress::info(&format!(
+                    "Total changes: {} modified, {} added, {} deleted",
+                    count(FileChangeKind::Modified).to_string().yellow(),
+                    count(FileChangeKind::Added).to_string().green(),
+                    count(FileChangeKind::Removed).to_string().red()
+                ));
+                let deltas: Option<i64> = changes.iter().map(|c| c.size_delta()).sum();
+                if let Some(total) = deltas {
+                    progress::info(&format!("Size change: {}", signed(total)));
+                }
+                return Ok(());
+            }
+
+            let repo = OxenRepository::new(".");
+
+            println!();
+            if let Some(cid) = &commit_id {
+                println!(
+                    "┌─ Changes Since Commit {} ─────────────────────┐",
+                    &cid[..7.min(cid.len())].bright_yellow()
+                );
+            } else {
+                println!("┌─ Uncommitted Changes ───────────────────────────────────┐");
+            }
+            println!("│                                                          │");
+            println!("└──────────────────────────────────────────────────────────┘");
+            println!();
+
+            // Get current status
+            let status = repo.status().await?;
+
+            let total_files = status.staged.len() + status.modified.len() + status.untracked.len();
+
+            if total_files == 0 {
+                progress::info("No changes in working directory");
+                return Ok(());
+            }
+
+            // Modified files
+            if !status.modified.is_empty() {
+                println!(
+                    "{} Modified files ({}):",
+                    "◆".yellow(),
+                    status.modified.len()
+                );
+                for path in &status.modified {
+                    // Try to get file size info
+                    if let Ok(metadata) = std::fs::metadata(path) {
+                        let size = metadata.len();
+                        println!(
+                            "  {} {} {}",
+                            "~".yellow(),
+                            path.display(),
+                            format!("({} bytes)", size).bright_black()
+                        );
+                    } else {
+                        println!("  {} {}", "~".yellow(), path.display());
+                    }
+                }
+                println!();
+            }
+
+            // Untracked (new) files
+            if !status.untracked.is_empty() {
+                println!("{} Added files ({}):", "◆".green(), status.untracked.len());
+                for path in &status.untracked {
+                    if let Ok(metadata) = std::fs::metadata(path) {
+                        let size = metadata.len();
+                        let size_mb = size as f64 / 1_048_576.0;
+                        if size_mb >= 1.0 {
+                            println!(
+                                "  {} {} {}",
+                                "+".green(),
+                                path.display(),
+                                format!("({:.1} MB)", size_mb).bright_black()
+                            );
+                        } else {
+                            println!(
+                                "  {} {} {}",
+                                "+".green(),
+                                path.display(),
+                                format!("({} bytes)", size).bright_black()
+                            );
+                        }
+                    } else {
+                        println!("  {} {}", "+".green(), path.display());
+                    }
+                }
+                println!();
+            }
+
+            // Summary
+            let total_modified = status.modified.len();
+            let total_added = status.untracked.len();
+
+            progress::info(&format!(
+                "Total changes: {} modified, {} added",
+                total_modified.to_string().yellow(),
+                total_added.to_string().green()
+            ));
+
+            if !status.staged.is_empty() {
+                println!();
+                progress::info("Some changes are already staged. Use 'auxin status' for details");
+            }
+
+            Ok(())
+        }
+
+        Commands::Compare {
+            commit_a,
+            commit_b,
+            format,
+            plain,
+            range,
+            last,
+            no_loudness,
+        } => {
+            use auxin::evolution::{self, CommitSelection};
+
+            if range.is_some() || last.is_some() {
+                let selection = match (range, last) {
+                    (Some(range), _) => CommitSelection::parse_range(&range)?,
+                    (None, last) => CommitSelection::Last(last.unwrap_or(0)),
+                };
+                let repo = OxenRepository::new(".");
+                let history = repo.get_history(None).await?;
+                let commits = match selection.select(&history) {
+                    Ok(commits) => commits,
+                    Err(e) => {
+                        progress::error(&e.to_string());
+                        std::process::exit(1);
+                    }
+                };
+                if commits.is_empty() {
+                    progress::info("No commits to compare");
+                    return Ok(());
+                }
+
+                let pb = (!no_loudness).then(|| progress::spinner("Measuring bounces..."));
+                let rows = evolution::build(&std::env::current_dir()?, &commits, !no_loudness);
+                if let Some(pb) = pb {
+                    pb.finish_and_clear();
+                }
+
+                match format.as_str() {
+                    "json" => println!("{}", serde_json::to_string_pretty(&rows)?),
+                    "csv" => print!("{}", evolution::to_csv(&rows)),
+                    _ => {
+                        let colored = format != "text" && !plain;
+                        let cell = |value: String, changed: bool| {
+                            let value = format!("{:<12}", value);
+                            if colored && changed {
+                                value.yellow().bold().to_string()
+                            } else {
+                                value
+                            }
+                        };
+                        println!();
+                        println!(
+                            "{:<9}{:<8}{:<12}{:<12}{:<12}Message",
+                            "Commit", "BPM", "Key", "Tracks", "LUFS"
+                        );
+                        for row in &rows {
+                            let bpm = format!(
+                                "{:<8}",
+                                row.bpm.map_or_else(|| "—".to_string(), |b| b.to_string())
+                            );
+                            let bpm = if colored && row.bpm_changed {
+                                bpm.yellow().bold().to_string()
+                            } else {
+                                bpm
+                            };
+                            let id = format!("{:<9}", &row.commit_id[..row.commit_id.len().min(7)]);
+                            println!(
+                                "{}{}{}{}{:<12}{}",
+                                if colored { id.bright_cyan().to_string() } else { id },
+                                bpm,
+                                cell(row.key.clone().unwrap_or("—".to_string()), row.key_changed),
+                                cell(
+                                    row.track_count.map_or("—".to_string(), |t| t.to_string()),
+                                    row.tracks_changed
+                                ),
+                                row.bounce_lufs.map_or("—".to_string(), |l| format!("{:.1}", l)),
+                                row.message
+                            );
+                        }
+                        println!();
+                    }
+                }
+                return Ok(());
+            }
+
+            let commit_a = auxin::commit_link::resolve_commit_arg(&commit_a.unwrap_or_default());
+            let commit_b = auxin::commit_link::resolve_commit_arg(&commit_b.unwrap_or_default());
+            let repo = OxenRepository::new(".");
+
+            vlog!("Fetching commit A: {}", commit_a);
+            vlog!("Fetching commit B: {}", commit_b);
+
+            // Get commit history to find the two commits
+            let commits = repo.get_history(None).await?;
+
+            // Find commit A
+            let commit_a_info = commits
+                .iter()
+                .find(|c| c.id.starts_with(&commit_a))
+                .ok_or_else(|| anyhow::anyhow!("Commit not found: {}", commit_a))?;
+
+            // Find commit B
+            let commit_b_info = commits
+                .iter()
+                .find(|c| c.id.starts_with(&commit_b))
+                .ok_or_else(|| anyhow::anyhow!("Commit not found: {}", commit_b))?;
+
+            vlog!("Found commit A: {}", commit_a_info.id);
+            vlog!("Found commit B: {}", commit_b_info.id);
+
+            // Commit metadata, from the sidecars
+            let store = MetadataStore::for_repo(&std::env::current_dir()?);
+            let stored_a = store.get(commit_a_info);
+            let stored_b = store.get(commit_b_info);
+            let (metadata_a, metadata_b) = (&stored_a.metadata, &stored_b.metadata);
+
+            println!();
+            println!(
+                "┌─ Comparing {} → {} ─────────────┐",
+                &commit_a_info.id[..7].bright_cyan(),
+                &commit_b_info.id[..7].bright_cyan()
+            );
+            println!("│                                                          │");
+            println!("└──────────────────────────────────────────────────────────┘");
+            println!();
+
+            // Output based on format
+            match format.as_str() {
+                "json" => {
+                    // Create a simple JSON structure
+                    let json_output = serde_json::json!({
+                        "commit_a": {
+                            "id": &commit_a_info.id,
+                            "metadata": metadata_a
+                        },
+                        "commit_b": {
+                            "id": &commit_b_info.id,
+                            "metadata": metadata_b
+                        }
+                    });
+                    println!("{}", serde_json::to_string_pretty(&json_output)?);
+                }
+                "compact" => {
+                    let summary = metadata_a.compare_compact(metadata_b);
+                    println!("{}", summary);
+                }
+                "text" => {
+                    println!("{}", metadata_a.compare_with_plain(metadata_b));
+                }
+                _ => {
+                    if plain {
+                        println!("{}", metadata_a.compare_with_plain(metadata_b));
+                    } else {
+                        println!("{}", metadata_a.compare_with(metadata_b));
+                    }
+                }
+            }
+
+            // SketchUp model units, shown in the preferred unit system
+            let units_of = |stored: &StoredMetadata| {
+                stored.detail("Units").and_then(|u| u.parse::<ModelUnits>().ok())
+            };
+            let units_a = units_of(&stored_a);
+            let units_b = units_of(&stored_b);
+            if format != "json" && (units_a.is_some() || units_b.is_some()) {
+                let system =
+                    UnitSystem::from_config(&Config::load().unwrap_or_default().ui.unit_system);
+                let show = |units: Option<ModelUnits>| {
+                    units.map_or_else(|| "not set".to_string(), |u| u.display_in(system))
+                };
+                if units_a == units_b {
+                    println!("Units: {}", show(units_a));
+                } else {
+                    println!("Units: {} → {}", show(units_a), show(units_b));
+                    if let (Some(a), Some(b)) = (units_a, units_b) {
+                        progress::warning(&format!("Model units changed: {}", a.conversion(b)));
+                    }
+                }
+            }
+
+            // Add thumbnail comparison
+            let current_dir = std::env::current_dir()?;
+            let thumbnail_mgr = ThumbnailManager::new(&current_dir);
+
+            if let Ok(Some(_)) = thumbnail_mgr.get_thumbnail(&commit_a_info.id) {
+                if let Ok(Some(_)) = thumbnail_mgr.get_thumbnail(&commit_b_info.id) {
+                    println!();
+                    println!("{}", "Visual Changes:".bright_cyan());
+                    match thumbnail_mgr.compare_thumbnails(&commit_a_info.id, &commit_b_info.id) {
+                        Ok(diff) => {
+                            println!("  Difference: {:.1}%", diff.difference_percent);
+                            if let Some(dim_diff) = &diff.dimension_diff {
+                                println!("  Dimensions: {}", dim_diff);
+                            }
+                            println!(
+                                "  Size change: {} bytes",
+                                if diff.size_diff_bytes > 0 { "+" } else { "" }.to_string()
+                                    + &diff.size_diff_bytes.to_string()
+                            );
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+
+            // Add bounce comparison
+            let bounce_mgr = BounceManager::new(&current_dir);
+
+            if let Ok(Some(_)) = bounce_mgr.get_bounce(&commit_a_info.id) {
+                if let Ok(Some(_)) = bounce_mgr.get_bounce(&commit_b_info.id) {
+                    println!();
+                    println!("{}", "Audio Changes:".bright_cyan());
+                    match bounce_mgr.compare_bounces(&commit_a_info.id, &commit_b_info.id) {
+                        Ok(comparison) => {
+                            if let Some(duration_diff) = comparison.duration_diff() {
+                                println!(
+                                    "  Duration: {}{:.2}s",
+                                    if duration_diff >= 0.0 { "+" } else { "" },
+                                    duration_diff
+                                );
+                            }
+                            println!(
+                                "  Size: {} bytes",
+                                if comparison.size_diff() > 0 { "+" } else { "" }.to_string()
+                                    + &comparison.size_diff().to_string()
+                            );
+                            println!("  {} For detailed audio comparison, use: auxin bounce compare {} {} --null-test",
+                                "💡".bright_yellow(),
+                                &commit_a_info.id[..8],
+                                &commit_b_info.id[..8]);
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Search {
+            query,
+            format,
+            ranked,
+            interactive,
+        } => {
+            use auxin::search::SearchEngine;
+
+            let repo = OxenRepository::new(".");
+
+            vlog!("Parsing search query: {}", query);
+
+            // Parse the natural language query
+            let search_query = SearchEngine::parse_query(&query);
+
+            vlog!("Fetching commit history...");
+            let commits = repo.get_history(None).await?;
+
+            vlog!("Executing search...");
+            let store = MetadataStore::for_repo(&std::env::current_dir()?);
+            let engine = SearchEngine::with_store(store);
+            let mut results = engine.search(&commits, &search_query);
+
+            // Sort by relevance if requested
+            if ranked {
+                results.sort_by(|a, b| {
+                    let score_b = engine.relevance_score(b, &search_query);
+                    let score_a = engine.relevance_score(a, &search_query);
+                    score_b
+                        .partial_cmp(&score_a)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            }
+
+            println!();
+            println!(
+                "┌─ Search Results ({} matches) ─────────────────────┐",
+                results.len().to_string().bright_cyan()
+            );
+            println!("│ Query: {:<49} │", query.bright_yellow());
+            println!("│                                                          │");
+            println!("└──────────────────────────────────────────────────────────┘");
+            println!();
+
+            if results.is_empty() {
+                progress::warning("No commits match your search criteria");
+                println!();
+                println!("Try:");
+                println!("  • Broadening your BPM range");
+                println!("  • Using partial key matches (e.g., 'minor' instead of 'A Minor')");
+                println!("  • Checking tag spellings");
+                return Ok(());
+            }
+
+            if interactive {
+                return browse_search_results(&engine, &results, &commits);
+            }
+
+            // Output based on format
+            match format.as_str() {
+                "json" => {
+                    let json = serde_json::to_string_pretty(&results)?;
+                    println!("{}", json);
+                }
+                "compact" => {
+                    for (i, commit) in results.iter().enumerate() {
+                        let metadata = engine.metadata(commit);
+                        let short_id = if commit.id.len() >= 7 {
+                            &commit.id[..7]
+                        } else {
+                            &commit.id
+                        };
+
+                        let mut parts = vec![format!("{}", short_id.bright_cyan())];
+
+                        if let Some(bpm) = metadata.bpm {
+                            parts.push(format!("{}BPM", bpm));
+                        }
+                        if let Some(sr) = metadata.sample_rate {
+                            parts.push(format!("{}Hz", sr));
+                        }
+                        if let Some(ref key) = metadata.key_signature {
+                            parts.push(key.clone());
+                        }
+
+                        let first_line =
+                            metadata.message.lines().next().unwrap_or(&metadata.message);
+                        parts.push(first_line.to_string());
+
+                        if ranked {
+                            let score = engine.relevance_score(commit, &search_query);
+                            parts.push(format!("(score: {:.1})", score).dimmed().to_string());
+                        }
+
+                        println!("{}. {}", i + 1, parts.join(" │ "));
+                    }
+                }
+                _ => {
+                    for (i, commit) in results.iter().enumerate() {
+                        let metadata = engine.metadata(commit);
+                        let short_id = if commit.id.len() >= 7 {
+                            &commit.id[..7]
+                        } else {
+                            &commit.id
+                        };
+
+                        println!(
+                            "{}. {} {}",
+                            format!("{}", i + 1).dimmed(),
+                            "Commit".bright_black(),
+                            short_id.bright_cyan()
+                        );
+
+                        let first_line =
+                            metadata.message.lines().next().unwrap_or(&metadata.message);
+                        println!("   Message: {}", first_line);
+
+                        if let Some(bpm) = metadata.bpm {
+                            println!("   BPM: {}", format!("{}", bpm).yellow());
+                        }
+                        if let Some(sr) = metadata.sample_rate {
+                            println!("   Sample Rate: {} Hz", sr);
+                        }
+                        if let Some(ref key) = metadata.key_signature {
+                            println!("   Key: {}", key.green());
+                        }
+                        if !metadata.tags.is_empty() {
+                            println!("   Tags: {}", metadata.tags.join(", ").bright_black());
+                        }
+
+                        if ranked {
+                            let score = engine.relevance_score(commit, &search_query);
+                            println!("   Relevance: {:.1}", score);
+                        }
+
+                        println!();
+                    }
+                }
+            }
+
+            progress::success(&format!(
+                "Found {} matching commit{}",
+                results.len(),
+                if results.len() == 1 { "" } else { "s" }
+            ));
+
+            Ok(())
+        }
+
+        Commands::Lock(lock_cmd) => {
+            use auxin::lock_integration;
+            use auxin::network_resilience::{check_connectivity, ConnectivityState};
+            use auxin::{OfflineQueue, OfflineQueuedOperation, QosClass};
+            use std::env;
+
+            let current_dir = env::current_dir()?;
+
+            // Auto-sync queued lock operations if online (for all lock commands).
+            // Only locks: a queued push would hold the command up for minutes
+            if check_connectivity() == ConnectivityState::Online {
+                let mut queue = OfflineQueue::new()?;
+                let pending_count = queue
+                    .pending()
+                    .iter()
+                    .filter(|entry| entry.operation.qos_class() == QosClass::Lock)
+                    .count();
+
+                if pending_count > 0 {
+                    vlog!(
+                        "Auto-syncing {} pending lock operation(s) before lock operation...",
+                        pending_count
+                    );
+                    let report = queue.sync_classes(&[QosClass::Lock])?;
+
+                    if !report.failed.is_empty() {
+                        warn!("{} queued operation(s) failed to sync", report.failed.len());
+                    }
+                }
+            }
+
+            match lock_cmd {
+                LockCommands::Acquire { timeout } => {
+                    // Load config to check if server locks are enabled
+                    let config = auxin_config::load_config().unwrap_or_default();
+
+                    if config.cli.use_server_locks {
+                        // Use server-based locking
+                        let server_config = ServerConfig::from_config(&config);
+
+                        match AuxinServerClient::new(server_config) {
+                            Ok(client) => {
+                                let user = server_client::get_user_identifier();
+                                let machine_id = server_client::get_machine_id();
+
+                                // Get namespace/name from config or current directory
+                                let namespace = config.cli.default_namespace.clone();
+                                let repo_name = current_dir
+                                    .file_name()
+                                    .map(|s| s.to_string_lossy().to_string())
+                                    .unwrap_or_else(|| "unknown".to_string());
+
+                                let pb = progress::spinner("Acquiring server lock...");
+                                match client.acquire_lock(
+                                    &namespace,
+                                    &repo_name,
+                                    &user,
+                                    &machine_id,
+                                    timeout as u32,
+                                ) {
+                                    Ok(lock) => {
+                                        progress::finish_success(&pb, "Lock acquired via server");
+                                        println!();
+                                        success!("Lock acquired successfully");
+                                        println!(
+                                            "  {} {}",
+                                            "Lock ID:".dimmed(),
+                                            lock.lock_id.cyan()
+                                        );
+                                        println!("  {} {}", "User:".dimmed(), lock.user.dimmed());
+                                        println!(
+                                            "  {} {}",
+                                            "Expires:".dimmed(),
+                                            lock.expires_at.dimmed()
+                                        );
+                                        if let Some(token) = lock.fencing_token {
+                                            println!("  {} {}", "Fencing token:".dimmed(), token);
+                                        }
+                                    }
+                                    Err(e) => {
+                                        progress::finish_error(&pb, "Failed to acquire lock");
+                                        return Err(e.context("Server lock error"));
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                warn!("Failed to connect to server, falling back to local lock");
+                                vlog!("Server error: {}", e);
+                                lock_integration::handle_lock_acquire(&current_dir, timeout)?;
+                            }
+                        }
+                    } else {
+                        // Use local locking (original behavior)
+                        // Check connectivity
+                        match check_connectivity() {
+                            state @ (ConnectivityState::Offline | ConnectivityState::Captive) => {
+                                // Queue the operation
+                                let mut queue = OfflineQueue::new()?;
+                                let user_id = lock_integration::get_user_identifier();
+
+                                let entry_id = queue.enqueue_with_priority(
+                                    OfflineQueuedOperation::AcquireLock {
+                                        project_path: current_dir.to_string_lossy().to_string(),
+                                        user_id: user_id.clone(),
+                                        timeout_hours: timeout as u32,
+                                    },
+                                    100,
+                                )?; // High priority
+
+                                warn!("{} - operation queued", state.describe());
+                                println!();
+                                println!("  {} {}", "Queued:".bold(), "Acquire lock".yellow());
+                                println!("  {} {}", "User:".dimmed(), user_id.dimmed());
+                                println!("  {} {} hours", "Timeout:".dimmed(), timeout);
+                                println!("  {} {}", "Entry ID:".dimmed(), &entry_id[..8].dimmed());
+                                println!();
+                                progress::info("Lock will be acquired when network is available");
+                                progress::info("Use 'auxin queue sync' to retry manually");
+                            }
+                            _ => {
+                                // Execute normally (online or unknown)
+                                lock_integration::handle_lock_acquire(&current_dir, timeout)?;
+                            }
+                        }
+                    }
+                }
+
+                LockCommands::Release => {
+                    // Load config to check if server locks are enabled
+                    let config = Config::load().unwrap_or_default();
+
+                    if config.cli.use_server_locks {
+                        // Use server-based locking
+                        let server_config = ServerConfig::from_config(&config);
+
+                        match AuxinServerClient::new(server_config) {
+                            Ok(client) => {
+                                let user = server_client::get_user_identifier();
+                                let machine_id = server_client::get_machine_id();
+
+                                // Get namespace/name from config or current directory
+                                let namespace = config.cli.default_namespace.clone();
+                                let repo_name = current_dir
+                                    .file_name()
+                                    .map(|s| s.to_string_lossy().to_string())
+                                    .unwrap_or_else(|| "unknown".to_string());
+
+                                // First get the current lock status to get lock_id
+                                let pb = progress::spinner("Releasing server lock...");
+                                match client.get_lock_status(&namespace, &repo_name) {
+                                    Ok(status) => {
+                                        if let Some(lock) = status.lock {
+                                            match client.release_lock(
+                                                &namespace,
+                                                &repo_name,
+                                                &lock,
+                                                &user,
+                                                &machine_id,
+                                            ) {
+                                                Ok(()) => {
+                                                    progress::finish_success(
+                                                        &pb,
+                                                        "Lock released via server",
+                                                    );
+                                                    success!("Lock released successfully");
+                                                }
+                                                Err(e) => {
+                                                    progress::finish_error(
+                                                        &pb,
+                                                        "Failed to release lock",
+                                                    );
+                                                    return Err(e.context("Server release error"));
+                                                }
+                                            }
+                                        } else {
+                                            progress::finish_error(&pb, "No lock to release");
+                                            warn!("No active lock found for this repository");
+                                        }
+                                    }
+                                    Err(e) => {
+                                        progress::finish_error(&pb, "Failed to get lock status");
+                                        return Err(e.context("Server error"));
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                warn!("Failed to connect to server, falling back to local lock");
+                                vlog!("Server error: {}", e);
+                                lock_integration::handle_lock_release(&current_dir)?;
+                            }
+                        }
+                    } else {
+                        // Use local locking (original behavior)
+                        // Check connectivity
+                        match check_connectivity() {
+                            state @ (ConnectivityState::Offline | ConnectivityState::Captive) => {
+                                // Queue the operation
+                                let mut queue = OfflineQueue::new()?;
+
+                                // We don't know the lock_id when offline, so use a placeholder
+                                // The execute_entry will look up the actual lock
+                                let entry_id = queue.enqueue_with_priority(
+                                    OfflineQueuedOperation::ReleaseLock {
+                                        project_path: current_dir.to_string_lossy().to_string(),
+                                        lock_id: "pending".to_string(), // Will be looked up during execution
+                                    },
+                                    100,
+                                )?; // High priority
+
+                                warn!("{} - operation queued", state.describe());
+                                println!();
+                                println!("  {} {}", "Queued:".bold(), "Release lock".yellow());
+                                println!("  {} {}", "Entry ID:".dimmed(), &entry_id[..8].dimmed());
+                                println!();
+                                progress::info("Lock will be released when network is available");
+                                progress::info("Use 'auxin queue sync' to retry manually");
+                            }
+                            _ => {
+                                // Execute normally (online or unknown)
+                                lock_integration::handle_lock_release(&current_dir)?;
+                            }
+                        }
+                    }
+                }
+
+                LockCommands::Status if cli.json => {
+                    use auxin::reports::LockStatusReport;
+
+                    let config = Config::load().unwrap_or_default();
+                    let client = if config.cli.use_server_locks {
+                        AuxinServerClient::new(ServerConfig::from_config(&config))
+                            .map_err(|e| vlog!("Server error: {}", e))
+                            .ok()
+                    } else {
+                        None
+                    };
+                    let report = match client {
+                        Some(client) => {
+                            let repo_name = current_dir
+                                .file_name()
+                                .map(|s| s.to_string_lossy().to_string())
+                                .unwrap_or_else(|| "unknown".to_string());
+                            let status = client
+                                .get_lock_status(&config.cli.default_namespace, &repo_name)
+                                .context("Server error")?;
+                            LockStatusReport::from_server(&status)
+                        }
+                        None => {
+                            let lock = auxin::RemoteLockManager::new().get_lock(&current_dir)?;
+                            LockStatusReport::from_local(lock.as_ref())
+                        }
+                    };
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                }
+
+                LockCommands::Status => {
+                    // Load config to check if server locks are enabled
+                    let config = Config::load().unwrap_or_default();
+
+                    if config.cli.use_server_locks {
+                        // Use server-based locking
+                        let server_config = ServerConfig::from_config(&config);
+
+                        match AuxinServerClient::new(server_config) {
+                            Ok(client) => {
+                                // Get namespace/name from config or current directory
+                                let namespace = config.cli.default_namespace.clone();
+                                let repo_name = current_dir
+                                    .file_name()
+                                    .map(|s| s.to_string_lossy().to_string())
+                                    .unwrap_or_else(|| "unknown".to_string());
+
+                                let pb = progress::spinner("Checking server lock status...");
+
+                                // ureq is a blocking client, safe to call directly
+                                match client.get_lock_status(&namespace, &repo_name) {
+                                    Ok(status) => {
+                                        progress::finish_success(&pb, "Lock status retrieved");
+                                        println!();
+                                        if status.locked {
+                                            if let Some(lock) = status.lock {
+                                                println!(
+                                                    "{}",
+                                                    "🔒 Repository is LOCKED".red().bold()
+                                                );
+                                                println!();
+                                                println!(
+                                                    "  {} {}",
+                                                    "Lock ID:".dimmed(),
+                                                    lock.lock_id.cyan()
+                                                );
+                                                println!(
+                                                    "  {} {}",
+                                                    "Held by:".dimmed(),
+                                                    lock.user.yellow()
+                                                );
+                                                println!(
+                                                    "  {} {}",
+                                                    "Machine:".dimmed(),
+                                                    lock.machine_id.dimmed()
+                                                );
+                                                println!(
+                                                    "  {} {}",
+                                                    "Acquired:".dimmed(),
+                                                    lock.acquired_at.dimmed()
+                                                );
+                                                println!(
+                                                    "  {} {}",
+                                                    "Expires:".dimmed(),
+                                                    lock.expires_at.yellow()
+                                                );
+                                                println!(
+                                                    "  {} {}",
+                                                    "Last seen:".dimmed(),
+                                                    lock.last_heartbeat.dimmed()
+                                                );
+                                                if let Some(seen) = &status.holder_last_seen {
+                                                    println!(
+                                                        "  {} {}",
+                                                        "Daemon seen:".dimmed(),
+                                                        seen.dimmed()
+                                                    );
+                                                }
+                                                if status.orphaned {
+                                                    println!();
+                                                    warn!(
+                                                        "The holder's machine has gone offline; \
+                                                         the lock is probably orphaned"
+                                                    );
+                                                    progress::info(
+                                                        "Take it over with: auxin lock reclaim",
+                                                    );
+                                                }
+                                            }
+                                        } else {
+                                            println!(
+                                                "{}",
+                                                "🔓 Repository is UNLOCKED".green().bold()
+                                            );
+                                            println!();
+                                            progress::info(
+                                                "You can acquire a lock with: auxin lock acquire",
+                                            );
+                                        }
+                                    }
+                                    Err(e) => {
+                                        progress::finish_error(&pb, "Failed to get lock status");
+                                        return Err(e.context("Server error"));
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                warn!("Failed to connect to server, falling back to local lock status");
+                                vlog!("Server error: {}", e);
+                                lock_integration::handle_lock_status(&current_dir)?;
+                            }
+                        }
+                    } else {
+                        // Status check always executes (even when offline, shows local state)
+                        lock_integration::handle_lock_status(&current_dir)?;
+                    }
+                }
+
+                LockCommands::Break { force } => {
+                    // Break force always executes (administrative override)
+                    lock_integration::handle_lock_break(&current_dir, force)?;
+                }
+
+                LockCommands::Reclaim { timeout } => {
+                    let config = Config::load().unwrap_or_default();
+                    if !config.cli.use_server_locks {
+                        anyhow::bail!(
+                            "Reclaiming needs server locks; enable use_server_locks or ask \
+                             the holder to release the lock"
+                        );
+                    }
+                    let client = AuxinServerClient::new(ServerConfig::from_config(&config))?;
+                    let user = server_client::get_user_identifier();
+                    let machine_id = server_client::get_machine_id();
+                    let namespace = config.cli.default_namespace.clone();
+                    let repo_name = current_dir
+                        .file_name()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+
+                    let pb = progress::spinner("Reclaiming orphaned lock...");
+                    match client.reclaim_lock(
+                        &namespace,
+                        &repo_name,
+                        &user,
+                        &machine_id,
+                        timeout as u32,
+                    ) {
+                        Ok(lock) => {
+                            progress::finish_success(&pb, "Lock reclaimed");
+                            println!();
+                            println!("  {} {}", "Lock ID:".dimmed(), lock.lock_id.cyan());
+                            println!("  {} {}", "Expires:".dimmed(), lock.expires_at.dimmed());
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Failed to reclaim lock");
+                            return Err(e);
+                        }
+                    }
+                }
+
+                LockCommands::Stats {
+                    all,
+                    days,
+                    namespace,
+                } => {
+                    let config = Config::load().unwrap_or_default();
+                    let client = AuxinServerClient::new(ServerConfig::from_config(&config))?;
+
+                    let pb = progress::spinner("Fetching lock stats...");
+                    let stats = if all {
+                        client.lock_stats(days, namespace.as_deref())
+                    } else {
+                        let repo_name = current_dir
+                            .file_name()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_else(|| "unknown".to_string());
+                        client.repo_lock_stats(&config.cli.default_namespace, &repo_name, days)
+                    };
+                    match stats {
+                        Ok(stats) => {
+                            progress::finish_success(&pb, "Lock stats retrieved");
+                            print_lock_stats(&stats, days, all);
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Failed to get lock stats");
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Auth(auth_cmd) => {
+            use auxin::AuthManager;
+
+            let auth = AuthManager::new();
+
+            match auth_cmd {
+                AuthCommands::Login => {
+                    use std::io::{self, Write};
+
+                    println!();
+                    println!("┌─ Oxen Hub Authentication ──────────────────────────────┐");
+                    println!("│                                                          │");
+                    println!("│  Login to Oxen Hub to enable remote collaboration       │");
+                    println!("│                                                          │");
+                    println!("│  Get your API key from: https://hub.oxen.ai             │");
+                    println!("│  Settings → API Keys → Create New Key                   │");
+                    println!("│                                                          │");
+                    println!("└──────────────────────────────────────────────────────────┘");
+                    println!();
+
+                    // Prompt for username
+                    print!("Username: ");
+                    io::stdout().flush()?;
+                    let mut username = String::new();
+                    io::stdin().read_line(&mut username)?;
+                    let username = username.trim();
+
+                    if username.is_empty() {
+                        progress::error("Username cannot be empty");
+                        std::process::exit(1);
+                    }
+
+                    // Prompt for API key (hidden input would be better, but keep it simple for now)
+                    print!("API Key: ");
+                    io::stdout().flush()?;
+                    let mut api_key = String::new();
+                    io::stdin().read_line(&mut api_key)?;
+                    let api_key = api_key.trim();
+
+                    if api_key.is_empty() {
+                        progress::error("API key cannot be empty");
+                        std::process::exit(1);
+                    }
+
+                    // Store credentials
+                    let pb = progress::spinner("Storing credentials...");
+
+                    match auth.store_credentials(username, api_key) {
+                        Ok(_) => {
+                            progress::finish_success(&pb, "Credentials stored");
+                            println!();
+                            progress::success(&format!("Authenticated as: {}", username));
+                            progress::info("You can now push/pull projects to Oxen Hub");
+                            println!();
+                            progress::info("Test authentication with: auxin auth test");
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Failed to store credentials");
+                            progress::error(&format!("Error: {}", e));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+
+                AuthCommands::Logout => {
+                    let pb = progress::spinner("Clearing credentials...");
+
+                    match auth.clear_credentials() {
+                        Ok(_) => {
+                            progress::finish_success(&pb, "Logged out");
+                            println!();
+                            progress::success("Credentials removed");
+                            progress::info("You are now logged out from Oxen Hub");
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Failed to clear credentials");
+                            progress::error(&format!("Error: {}", e));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+
+                AuthCommands::Status => {
+                    println!();
+                    println!("┌─ Authentication Status ─────────────────────────────────┐");
+                    println!("│                                                          │");
+
+                    match auth.get_credentials() {
+                        Ok(Some(creds)) => {
+                            println!(
+                                "│  Status: {} Authenticated                            │",
+                                "●".green()
+                            );
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!(
+                                "│  Username: {}                                    │",
+                                creds.username
+                            );
+                            println!("│  Hub URL:  {}                      │", creds.hub_url);
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!(
+                                "└──────────────────────────────────────────────────────────┘"
+                            );
+                            println!();
+                            progress::info("Run 'auxin auth test' to verify connection");
+                        }
+                        Ok(None) => {
+                            println!(
+                                "│  Status: {} Not authenticated                        │",
+                                "○".yellow()
+                            );
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!("│  You need to login to use remote features               │");
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!(
+                                "└──────────────────────────────────────────────────────────┘"
+                            );
+                            println!();
+                            progress::info("Login with: auxin auth login");
+                        }
+                        Err(e) => {
+                            println!(
+                                "│  Status: {} Error                                    │",
+                                "✗".red()
+                            );
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!(
+                                "│  Error reading credentials                               │"
+                            );
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!(
+                                "└──────────────────────────────────────────────────────────┘"
+                            );
+                            println!();
+                            progress::error(&format!("Error: {}", e));
+                        }
+                    }
+                }
+
+                AuthCommands::Test => {
+                    let pb = progress::spinner("Testing authentication...");
+
+                    match auth.test_authentication() {
+                        Ok(username) => {
+                            progress::finish_success(&pb, "Authentication verified");
+                            println!();
+                            progress::success(&format!(
+                                "Successfully authenticated as: {}",
+                                username
+                            ));
+                            progress::info("Your credentials are valid");
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Authentication failed");
+                            println!();
+                            progress::error("Unable to verify authentication");
+                            progress::info(&format!("Error: {}", e));
+                            println!();
+                            progress::info("Try logging in again: auxin auth login");
+                            std::process::exit(1);
+                        }
+                    }
+                }
+
+                AuthCommands::Whoami { link } => {
+                    use auxin::identity::Identity;
+
+                    let identity = Identity::current();
+                    println!();
+                    println!("  {} {}", "Recorded as:".bold(), identity.user().cyan());
+                    println!(
+                        "  {} {}",
+                        "Hub user:   ".bold(),
+                        identity.hub_user.as_deref().unwrap_or("not logged in")
+                    );
+                    println!("  {} {}", "Machine:    ".bold(), identity.machine);
+                    let others = identity.other_names();
+                    if !others.is_empty() {
+                        println!("  {} {}", "Also you:   ".bold(), others.join(", "));
+                    }
+                    println!();
+
+                    let config = Config::load().unwrap_or_default();
+                    let has_server = !config.cli.url.trim().is_empty();
+                    if link {
+                        let server_config = ServerConfig::from_config(&config);
+                        if !has_server || server_config.token.is_none() {
+                            anyhow::bail!("Linking needs a server url and token under [cli]");
+                        }
+                        let client = AuxinServerClient::new(server_config)?;
+                        let linked = client.link_identity(identity)?;
+                        progress::success(&format!(
+                            "Linked {} to server account {}",
+                            linked.identity, linked.account
+                        ));
+                        println!();
+                    } else if has_server {
+                        progress::info("Link it to your server account: auxin auth whoami --link");
+                        println!();
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Server(server_cmd) => {
+            let config = Config::load()
+                .or_else(|_| Config::load_unselected())
+                .unwrap_or_default();
+
+            match server_cmd {
+                ServerCommands::Status => {
+                    // Hide credentials, truncate URL if too long
+                    let url = auxin::crash_report::redact_text(&config.cli.url);
+                    let url_display = if url.len() > 43 {
+                        format!("{}...", &url[..40])
+                    } else {
+                        url
+                    };
+
+                    println!();
+                    println!("┌─ Server Configuration ──────────────────────────────────┐");
+                    println!("│                                                          │");
+                    if !config.cli.server.is_empty() {
+                        let server = match config.selected_server() {
+                            Some(_) => config.cli.server.clone(),
+                            None => format!("{} (no [servers] entry)", config.cli.server),
+                        };
+                        println!("│  Server:     {:<43} │", server);
+                    }
+                    println!("│  URL:        {:<43} │", url_display);
+                    println!(
+                        "│  Namespace:  {:<43} │",
+                        config.cli.default_namespace.as_str()
+                    );
+                    println!(
+                        "│  Timeout:    {} seconds{:<34} │",
+                        config.cli.timeout_secs, ""
+                    );
+                    println!(
+                        "│  Locks:      {:<43} │",
+                        if config.cli.use_server_locks {
+                            "enabled"
+                        } else {
+                            "disabled"
+                        }
+                    );
+                    println!(
+                        "│  Metadata:   {:<43} │",
+                        if config.cli.use_server_metadata {
+                            "enabled"
+                        } else {
+                            "disabled"
+                        }
+                    );
+                    println!("│                                                          │");
+
+                    // Check connection
+                    let server_config = ServerConfig::from_config(&config);
+
+                    let mut api_warning = None;
+                    if let Ok(client) = AuxinServerClient::new(server_config) {
+                        match client.health_check() {
+                            Ok(true) => {
+                                println!("│  Status:     {} Connected{:<33} │", "●".green(), "");
+
+                                let api_display = match client.check_compatibility() {
+                                    Ok(compat) => {
+                                        api_warning = compat.warning();
+                                        compat.api_prefix()
+                                    }
+                                    Err(_) => "unknown".to_string(),
+                                };
+                                println!("│  API:        {:<43} │", api_display);
+                            }
+                            Ok(false) | Err(_) => {
+                                println!("│  Status:     {} Unreachable{:<31} │", "●".red(), "");
+                            }
+                        }
+                    } else {
+                        println!("│  Status:     {} Unknown{:<35} │", "●".yellow(), "");
+                    }
+
+                    println!("│                                                          │");
+                    println!("└──────────────────────────────────────────────────────────┘");
+                    println!();
+
+                    if let Some(warning) = api_warning {
+                        progress::warning(&warning);
+                        println!();
+                    }
+                }
+
+                ServerCommands::Health => {
+                    let pb = progress::spinner("Testing server connection...");
+
+                    let server_config = ServerConfig::from_config(&config);
+
+                    match AuxinServerClient::new(server_config) {
+                        Ok(client) => match client.health_check() {
+                            Ok(true) => {
+                                progress::finish_success(&pb, "Server is healthy");
+                                println!();
+                                progress::success(&format!("Connected to {}", config.cli.url));
+                                if let Ok(Some(info)) = client.server_version() {
+                                    println!(
+                                        "  Server version {} (API v{}-v{})",
+                                        info.server_version, info.min_api_version, info.api_version
+                                    );
+                                }
+                                if let Some(warning) = client
+                                    .check_compatibility()
+                                    .ok()
+                                    .and_then(|compat| compat.warning())
+                                {
+                                    progress::warning(&warning);
+                                }
+                            }
+                            Ok(false) => {
+                                progress::finish_error(&pb, "Server health check failed");
+                                println!();
+                                progress::error(&format!(
+                                    "Server at {} is not responding",
+                                    config.cli.url
+                                ));
+                            }
+                            Err(e) => {
+                                progress::finish_error(&pb, "Connection failed");
+                                println!();
+                                progress::error(&format!("Failed to connect: {}", e));
+                            }
+                        },
+                        Err(e) => {
+                            progress::finish_error(&pb, "Client error");
+                            println!();
+                            progress::error(&format!("Failed to create client: {}", e));
+                        }
+                    }
+                }
+
+                ServerCommands::Use { name, clear } => match name {
+                    None if clear => {
+                        let Some(project_config_path) = Config::project_config_path() else {
+                            anyhow::bail!("Not in a project; run this from a project folder");
+                        };
+                        auxin::config_edit::update_file(&project_config_path, "cli", "server", None)?;
+                        progress::success("This project uses [cli] url and token again");
+                    }
+                    None if config.servers.is_empty() => {
+                        progress::info("No named servers in ~/.auxin/config.toml. Add one:");
+                        println!();
+                        println!("  [servers.studio-a]");
+                        println!("  url = \"https://auxin.studio-a.com\"");
+                        println!("  token = \"keychain\"");
+                        println!();
+                    }
+                    None => {
+                        println!();
+                        for (name, server) in &config.servers {
+                            let current = if *name == config.cli.server {
+                                " (this project)".green().to_string()
+                            } else {
+                                String::new()
+                            };
+                            println!(
+                                "  {}  {}{}",
+                                name.bold(),
+                                auxin::crash_report::redact_text(&server.url),
+                                current
+                            );
+                        }
+                        println!();
+                    }
+                    Some(name) => {
+                        let Some(server) = config.servers.get(&name) else {
+                            let known: Vec<&str> =
+                                config.servers.keys().map(String::as_str).collect();
+                            anyhow::bail!(
+                                "No [servers.{}] in ~/.auxin/config.toml{}",
+                                name,
+                                if known.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!("; named servers: {}", known.join(", "))
+                                }
+                            );
+                        };
+                        let Some(project_config_path) = Config::project_config_path() else {
+                            anyhow::bail!("Not in a project; run this from a project folder");
+                        };
+                        auxin::config_edit::update_file(
+                            &project_config_path,
+                            "cli",
+                            "server",
+                            Some(&name),
+                        )?;
+                        progress::success(&format!(
+                            "This project now uses {} ({})",
+                            name,
+                            auxin::crash_report::redact_text(&server.url)
+                        ));
+                        progress::info(&format!("Saved to {}", project_config_path.display()));
+                        if server.token.is_empty() {
+                            progress::info("It has no token; store one with: auxin server set token");
+                        }
+                    }
+                },
+
+                ServerCommands::InitConfig {
+                    preset,
+                    redis,
+                    postgres,
+                    app_name,
+                    output,
+                } => {
+                    let preset: auxin::server_init::Preset = preset.parse()?;
+                    let options = auxin::server_init::InitOptions {
+                        redis,
+                        postgres,
+                        app_name,
+                    };
+                    let setup = auxin::server_init::generate(preset, &options)?;
+                    progress::success(&format!(
+                        "Generated a {} configuration (validated, new auth secret)",
+                        setup.preset
+                    ));
+
+                    match output {
+                        Some(dir) => {
+                            std::fs::create_dir_all(&dir)?;
+                            for file in &setup.files {
+                                let path = dir.join(&file.name);
+                                if path.exists() {
+                                    anyhow::bail!(
+                                        "{} already exists; choose another --output",
+                                        path.display()
+                                    );
+                                }
+                            }
+                            for file in &setup.files {
+                                let path = dir.join(&file.name);
+                                std::fs::write(&path, &file.contents)?;
+                                #[cfg(unix)]
+                                if file.name == "config.toml" {
+                                    use std::os::unix::fs::PermissionsExt;
+                                    std::fs::set_permissions(
+                                        &path,
+                                        std::fs::Permissions::from_mode(0o600),
+                                    )?;
+                                }
+                                progress::info(&format!("Wrote {}", path.display()));
+                            }
+                        },
+                        None => {
+                            for file in &setup.files {
+                                println!();
+                                println!("{}", format!("# ---- {} ----", file.name).bold());
+                                print!("{}", file.contents);
+                            }
+                        },
+                    }
+
+                    println!();
+                    println!("{}", "Next steps:".bold());
+                    for step in &setup.next_steps {
+                        println!("  {}", step);
+                    }
+                    println!();
+                    progress::warning("config.toml holds the auth secret; keep it out of git");
+                }
+
+                ServerCommands::Set { key, value } => {
+                    let mut config = Config::load().unwrap_or_default();
+
+                    match key.as_str() {
+                        "url" => {
+                            config.cli.url = value.clone();
+                            progress::success(&format!("Set server URL to: {}", value));
+                            if config.selected_server().is_some() {
+                                progress::warning(&format!(
+                                    "This project uses server '{}', whose url wins; \
+                                     run 'auxin server use --clear' to use this one",
+                                    config.cli.server
+                                ));
+                            }
+                        }
+                        "token" => {
+                            use auxin::credential_store::{self, SystemKeychain, KEYCHAIN_REF};
+                            credential_store::store_server_token(
+                                &SystemKeychain,
+                                &config.cli.url,
+                                &value,
+                            )?;
+                            config.cli.token = KEYCHAIN_REF.to_string();
+                            progress::success(&format!(
+                                "Stored the token for {} in the keychain",
+                                config.cli.url
+                            ));
+                            // A named server's own token would win over [cli]
+                            if let Some(server) = config.selected_server() {
+                                if server.token != KEYCHAIN_REF {
+                                    let path = credential_store::use_keychain_for_server(
+                                        &config.cli.server,
+                                    )?;
+                                    progress::info(&format!(
+                                        "[servers.{}] in {} now reads it from the keychain",
+                                        config.cli.server,
+                                        path.display()
+                                    ));
+                                }
+                            }
+                        }
+                        "namespace" => {
+                            config.cli.default_namespace = value.clone();
+                            progress::success(&format!("Set default namespace to: {}", value));
+                        }
+                        "timeout" => match value.parse::<i64>() {
+                            Ok(timeout) => {
+                                config.cli.timeout_secs = timeout;
+                                progress::success(&format!("Set timeout to: {} seconds", timeout));
+                            }
+                            Err(_) => {
+                                progress::error("Invalid timeout value (must be a number)");
+                                std::process::exit(1);
+                            }
+                        },
+                        "locks" => match value.to_lowercase().as_str() {
+                            "true" | "on" | "yes" | "1" => {
+                                config.cli.use_server_locks = true;
+                                progress::success("Server locks enabled");
+                            }
+                            "false" | "off" | "no" | "0" => {
+                                config.cli.use_server_locks = false;
+                                progress::success("Server locks disabled");
+                            }
+                            _ => {
+                                progress::error("Invalid value for locks (use true/false)");
+                                std::process::exit(1);
+                            }
+                        },
+                        "metadata" => match value.to_lowercase().as_str() {
+                            "true" | "on" | "yes" | "1" => {
+                                config.cli.use_server_metadata = true;
+                                progress::success("Server metadata storage enabled");
+                            }
+                            "false" | "off" | "no" | "0" => {
+                                config.cli.use_server_metadata = false;
+                                progress::success("Server metadata storage disabled");
+                            }
+                            _ => {
+                                progress::error("Invalid value for metadata (use true/false)");
+                                std::process::exit(1);
+                            }
+                        },
+                        _ => {
+                            progress::error(&format!("Unknown configuration key: {}", key));
+                            progress::info(
+                                "Available keys: url, token, namespace, timeout, locks, metadata",
+                            );
+                            std::process::exit(1);
+                        }
+                    }
+
+                    // Save to project config
+                    if let Some(project_config_path) = Config::project_config_path() {
+                        match config.save_to_file(&project_config_path) {
+                            Ok(()) => {
+                                progress::info(&format!(
+                                    "Saved to {}",
+                                    project_config_path.display()
+                                ));
+                            }
+                            Err(e) => {
+                                progress::error(&format!("Failed to save config: {}", e));
+                            }
+                        }
+                    }
+                }
+
+                ServerCommands::Discover {
+                    timeout,
+                    use_server,
+                } => {
+                    use auxin::server_discovery;
+                    use dialoguer::Select;
+
+                    let pb = progress::spinner("Looking for servers on the local network...");
+                    let servers = server_discovery::discover(std::time::Duration::from_secs(timeout))?;
+                    if servers.is_empty() {
+                        progress::finish_error(&pb, "No servers found");
+                        println!();
+                        progress::info("Check the server is running and on the same network");
+                        progress::info("Servers bound to localhost are not advertised");
+                        return Ok(());
+                    }
+                    progress::finish_success(&pb, &format!("Found {} server(s)", servers.len()));
+                    println!();
+                    for (i, server) in servers.iter().enumerate() {
+                        let current = if server.url() == config.cli.url.trim_end_matches('/') {
+                            " (current)".green().to_string()
+                        } else {
+                            String::new()
+                        };
+                        println!("  {}. {}{}", i + 1, server.name.bold(), current);
+                        println!("     {} {}", "URL:".dimmed(), server.url());
+                        if let Some(version) = server.properties.get("version") {
+                            println!("     {} {}", "Version:".dimmed(), version);
+                        }
+                    }
+                    println!();
+
+                    let chosen = match use_server {
+                        Some(n) if (1..=servers.len()).contains(&n) => Some(n - 1),
+                        Some(n) => {
+                            progress::error(&format!(
+                                "No server {}; choose 1-{}",
+                                n,
+                                servers.len()
+                            ));
+                            std::process::exit(1);
+                        }
+                        None if atty::is(atty::Stream::Stdin)
+                            && atty::is(atty::Stream::Stdout) =>
+                        {
+                            let mut items: Vec<String> = servers
+                                .iter()
+                                .map(|s| format!("{} ({})", s.name, s.url()))
+                                .collect();
+                            items.push("Don't change the server URL".to_string());
+                            let choice = Select::new()
+                                .with_prompt("Use a server for this project?")
+                                .items(&items)
+                                .default(0)
+                                .interact()?;
+                            (choice < servers.len()).then_some(choice)
+                        }
+                        None => None,
+                    };
+
+                    let Some(chosen) = chosen else {
+                        progress::info("Set one with: auxin server set url <URL>");
+                        return Ok(());
+                    };
+                    let url = servers[chosen].url();
+                    let Some(project_config_path) = Config::project_config_path() else {
+                        progress::warning("Not in a project; run this from a project folder");
+                        progress::info(&format!("Or set it with: auxin server set url {}", url));
+                        return Ok(());
+                    };
+                    let mut config = config;
+                    config.cli.url = url.clone();
+                    config.save_to_file(&project_config_path)?;
+                    progress::success(&format!("Set server URL to: {}", url));
+                    progress::info(&format!("Saved to {}", project_config_path.display()));
+                }
+
+                ServerCommands::BackfillMetadata { dry_run, restart } => {
+                    let repo_root = std::env::current_dir()?;
+                    let namespace = config.cli.default_namespace.clone();
+                    let repo_name = repo_root
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .context("Failed to determine repository name")?;
+
+                    let server_config = ServerConfig::from_config(&config);
+                    let client = AuxinServerClient::new(server_config)?;
+
+                    let pb = progress::spinner("Reading commit history...");
+                    let repo = OxenRepository::new(".");
+                    let commits = repo.get_history(None).await?;
+                    let on_server: HashSet<String> =
+                        match client.list_metadata(&namespace, &repo_name) {
+                            Ok(ids) => ids.into_iter().collect(),
+                            Err(e) => {
+                                progress::finish_error(&pb, "Failed to query server");
+                                return Err(e);
+                            }
+                        };
+                    pb.finish_and_clear();
+
+                    if restart {
+                        BackfillState::clear(&repo_root)?;
+                    }
+                    let mut state =
+                        BackfillState::load(&repo_root, &config.cli.url, &namespace, &repo_name);
+                    let plan = metadata_backfill::plan_backfill(&commits, &on_server, &state);
+
+                    println!();
+                    println!(
+                        "Backfill for {}/{} ({} commits)",
+                        namespace,
+                        repo_name,
+                        commits.len()
+                    );
+                    println!("  Already on server:   {}", plan.already_on_server);
+                    if plan.already_uploaded > 0 {
+                        println!("  Uploaded previously: {}", plan.already_uploaded);
+                    }
+                    println!("  No metadata:         {}", plan.without_metadata);
+                    println!("  To upload:           {}", plan.entries.len());
+                    println!();
+
+                    if plan.entries.is_empty() {
+                        BackfillState::clear(&repo_root)?;
+                        progress::success("Server metadata is up to date");
+                        return Ok(());
+                    }
+
+                    if dry_run {
+                        for entry in &plan.entries {
+                            let short = &entry.commit_id[..entry.commit_id.len().min(8)];
+                            println!("  {} {}", "→".cyan(), short);
+                        }
+                        println!();
+                        progress::info("Dry run: nothing uploaded");
+                        return Ok(());
+                    }
+
+                    let pb =
+                        progress::progress_bar(plan.entries.len() as u64, "Uploading metadata");
+
+                    // Record each chunk as it lands, so an interrupted run resumes
+                    let uploaded = client.store_metadata_batch_with_progress(
+                        &namespace,
+                        &repo_name,
+                        &plan.entries,
+                        |chunk, report| {
+                            state.uploaded.extend(report.stored.iter().cloned());
+                            state.save(&repo_root)?;
+                            pb.inc(chunk.len() as u64);
+                            Ok(())
+                        },
+                    );
+                    let failed = match uploaded {
+                        Ok(report) => report.failed,
+                        Err(e) => {
+                            progress::finish_error(&pb, "Upload interrupted");
+                            progress::info("Run the command again to resume");
+                            return Err(e);
+                        }
+                    };
+
+                    if failed.is_empty() {
+                        BackfillState::clear(&repo_root)?;
+                        progress::finish_success(
+                            &pb,
+                            &format!("Uploaded metadata for {} commits", plan.entries.len()),
+                        );
+                    } else {
+                        progress::finish_error(
+                            &pb,
+                            &format!("{} of {} uploads failed", failed.len(), plan.entries.len()),
+                        );
+                        for failure in &failed {
+                            progress::warning(&format!(
+                                "{}: {}",
+                                failure.commit_id, failure.error
+                            ));
+                        }
+                        progress::info("Run the command again to retry failed commits");
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Lan(lan_cmd) => {
+            use auxin::describe;
+            use auxin::lan_sync::{self, LanReceiver, LanSender, PairingCode};
+            use dialoguer::{Confirm, Input};
+
+            match lan_cmd {
+                LanCommands::Send { path, port } => {
+                    if !path.join(".oxen").exists() {
+                        progress::error(&format!(
+                            "Not an Auxin project: {}",
+                            path.display()
+                        ));
+                        progress::info("Run 'auxin init' first, or pass the project path");
+                        std::process::exit(1);
+                    }
+                    let sender = LanSender::bind(&path, port)?;
+                    let host = lan_sync::local_address()
+                        .map(|ip| ip.to_string())
+                        .unwrap_or_else(|| "<this-machine-ip>".to_string());
+
+                    println!();
+                    let code = sender.code().to_string();
+                    println!("  {} {}", "Pairing code:".bold(), code.green().bold());
+                    println!();
+                    println!("  On the receiving machine, run:");
+                    println!(
+                        "    auxin lan receive {}:{} --code {}",
+                        host,
+                        sender.port()?,
+                        sender.code()
+                    );
+                    println!();
+
+                    let pb = progress::spinner("Waiting for the receiver (Ctrl+C to cancel)...");
+                    let result = sender.serve(|sent| {
+                        pb.set_message(format!("Sending... {}", describe::format_size(sent)))
+                    });
+                    match result {
+                        Ok((peer, sent)) => progress::finish_success(
+                            &pb,
+                            &format!("Sent {} to {}", describe::format_size(sent), peer.ip()),
+                        ),
+                        Err(e) => {
+                            progress::finish_error(&pb, "Transfer failed");
+                            return Err(e);
+                        }
+                    }
+                }
+
+                LanCommands::Receive {
+                    address,
+                    code,
+                    dest,
+                    replace,
+                    yes,
+                } => {
+                    let interactive =
+                        atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout);
+                    let code = match code {
+                        Some(code) => code,
+                        None if interactive => Input::new()
+                            .with_prompt("Pairing code shown on the sender")
+                            .interact_text()?,
+                        None => {
+                            progress::error("No pairing code; pass --code");
+                            std::process::exit(1);
+                        }
+                    };
+                    let code: PairingCode = code.parse()?;
+
+                    let pb = progress::spinner(&format!("Pairing with {}...", address));
+                    let receiver = match LanReceiver::connect(&address, &code) {
+                        Ok(receiver) => receiver,
+                        Err(e) => {
+                            progress::finish_error(&pb, "Pairing failed");
+                            return Err(e);
+                        }
+                    };
+                    progress::finish_success(&pb, "Paired");
+
+                    let manifest = receiver.manifest().clone();
+                    println!();
+                    println!("  {} {}", "Project:".bold(), manifest.project);
+                    println!("  {} {}", "From:".bold(), manifest.sender);
+                    if let Some(branch) = &manifest.branch {
+                        println!("  {} {}", "Branch:".bold(), branch);
+                    }
+                    if let Some(head) = &manifest.head {
+                        println!("  {} {}", "Latest:".bold(), head);
+                    }
+                    println!(
+                        "  {} {}",
+                        "Size:".bold(),
+                        describe::format_size(manifest.size_bytes)
+                    );
+                    if manifest.uncommitted {
+                        println!("  {}", "Includes uncommitted changes".yellow());
+                    }
+                    println!();
+
+                    let dest = dest.unwrap_or_else(|| PathBuf::from(&manifest.project));
+                    let accepted = yes
+                        || (interactive
+                            && Confirm::new()
+                                .with_prompt(format!("Receive into {}?", dest.display()))
+                                .default(true)
+                                .interact()?);
+                    if !accepted {
+                        receiver.decline()?;
+                        progress::info(if interactive {
+                            "Declined"
+                        } else {
+                            "Not a terminal; pass --yes to accept"
+                        });
+                        return Ok(());
+                    }
+
+                    if replace {
+                        if let Some(aside) = lan_sync::set_aside(&dest)? {
+                            progress::info(&format!("Moved existing files to {}", aside.display()));
+                        }
+                    } else if dest.read_dir().is_ok_and(|mut d| d.next().is_some()) {
+                        receiver.decline()?;
+                        progress::error(&format!("{} is not empty", dest.display()));
+                        progress::info("Pass --dest <DIR> or --replace to move it aside");
+                        std::process::exit(1);
+                    }
+
+                    let pb = progress::spinner("Receiving...");
+                    let result = receiver.receive_into(&dest, |received| {
+                        pb.set_message(format!(
+                            "Receiving... {} of {}",
+                            describe::format_size(received),
+                            describe::format_size(manifest.size_bytes)
+                        ))
+                    });
+                    match result {
+                        Ok(received) => progress::finish_success(
+                            &pb,
+                            &format!(
+                                "Received {} into {}",
+                                describe::format_size(received),
+                                dest.display()
+                            ),
+                        ),
+                        Err(e) => {
+                            progress::finish_error(&pb, "Transfer failed");
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Deliver {
+            command,
+            commit,
+            to,
+            format,
+            message,
+            no_verify,
+        } => {
+            use auxin::delivery::{self, DeliveryFilter, DeliveryLog};
+
+            let project = std::env::current_dir()?;
+            if let Some(DeliverCommands::List { to, commit }) = command {
+                let log = DeliveryLog::load(&project)?;
+                let filter = DeliveryFilter {
+                    recipient: to,
+                    commit: commit.map(|c| auxin::commit_link::resolve_commit_arg(&c)),
+                };
+                let deliveries = log.list(&filter);
+                if deliveries.is_empty() {
+                    progress::info("No deliveries recorded");
+                    return Ok(());
+                }
+
+                println!();
+                for delivery in &deliveries {
+                    let short_id = &delivery.commit_id[..7.min(delivery.commit_id.len())];
+                    let version = delivery
+                        .version
+                        .as_ref()
+                        .map(|v| format!(" [{}]", v).bright_green().to_string())
+                        .unwrap_or_default();
+                    println!(
+                        "{} {} {}{}",
+                        "●".cyan(),
+                        delivery.recipient.bold(),
+                        short_id.bright_yellow(),
+                        version
+                    );
+                    let when = delivery.delivered_at.with_timezone(&chrono::Local);
+                    println!(
+                        "  │ {}",
+                        format!("{} · {}", when.format("%Y-%m-%d %H:%M"), delivery.user)
+                            .bright_black()
+                    );
+                    if let Some(format) = &delivery.format {
+                        println!("  │ Format: {}", format);
+                    }
+                    if let Some(bounce) = &delivery.bounce {
+                        println!(
+                            "  │ Bounce: {} ({})",
+                            bounce.filename,
+                            auxin::describe::format_size(bounce.size_bytes)
+                        );
+                    }
+                    if let Some(note) = &delivery.note {
+                        println!("  │ {}", note);
+                    }
+                }
+                println!();
+                progress::info(&format!("{} delivery(ies)", deliveries.len()));
+                return Ok(());
+            }
+
+            // clap requires both when there's no subcommand
+            let (Some(commit_arg), Some(recipient)) = (commit, to) else {
+                unreachable!("COMMIT and --to are required");
+            };
+            let commit_id = auxin::commit_link::resolve_commit_arg(&commit_arg);
+            let commits = OxenRepository::new(".").get_history(None).await?;
+            let Some(commit) = commits.iter().find(|c| c.id.starts_with(&commit_id)) else {
+                progress::error(&format!("Commit not found: {}", commit_arg));
+                std::process::exit(1);
+            };
+
+            if !no_verify {
+                use auxin::hooks::{HookManager, HookType};
+
+                let env = [
+                    ("AUXIN_COMMIT".to_string(), commit.id.clone()),
+                    ("AUXIN_RECIPIENT".to_string(), recipient.clone()),
+                    ("AUXIN_FORMAT".to_string(), format.clone().unwrap_or_default()),
+                ];
+                let metadata = CommitMetadata::parse_commit_message(&commit.message);
+                if !HookManager::new(&project).run_hooks_with_env(
+                    HookType::PreDeliver,
+                    &metadata,
+                    &env,
+                )? {
+                    progress::error("Delivery stopped by a pre-deliver hook");
+                    progress::info("Skip hooks with: auxin deliver --no-verify ...");
+                    std::process::exit(1);
+                }
+            }
+
+            let delivery = delivery::record(&project, commit, &recipient, format, message)?;
+            progress::success(&format!(
+                "Recorded delivery of {} to {}",
+                &commit.id[..7.min(commit.id.len())],
+                delivery.recipient
+            ));
+            if let Some(version) = &delivery.version {
+                println!("  Version: {}", version);
+            }
+            match &delivery.bounce {
+                Some(bounce) => println!("  Bounce: {}", bounce.filename),
+                None => progress::warning("This commit has no bounce attached"),
+            }
+
+            Ok(())
+        }
+
+        Commands::Version(version_cmd) => {
+            use auxin::versioning::{self, Bump, VersionLog};
+
+            let project = std::env::current_dir()?;
+            match version_cmd {
+                VersionCommands::Bump {
+                    major,
+                    minor: _,
+                    message,
+                    commit,
+                } => {
+                    let commits = OxenRepository::new(".").get_history(None).await?;
+                    let target = match &commit {
+                        Some(id) => {
+                            let id = auxin::commit_link::resolve_commit_arg(id);
+                            commits.iter().find(|c| c.id.starts_with(&id))
+                        }
+                        None => commits.first(),
+                    };
+                    let Some(target) = target else {
+                        match commit {
+                            Some(id) => progress::error(&format!("Commit not found: {}", id)),
+                            None => progress::error("No commits yet"),
+                        }
+                        std::process::exit(1);
+                    };
+
+                    let bump = if major { Bump::Major } else { Bump::Minor };
+                    let format = Config::load().unwrap_or_default().versioning.format;
+                    let version = versioning::record(&project, target, bump, &format, message)?;
+                    progress::success(&format!(
+                        "{} is now {}",
+                        &target.id[..7.min(target.id.len())],
+                        version.label.bold()
+                    ));
+                }
+
+                VersionCommands::List => {
+                    let versions = VersionLog::load(&project)?;
+                    if versions.versions.is_empty() {
+                        progress::info("No versions yet");
+                        progress::info("Label the latest commit with: auxin version bump");
+                        return Ok(());
+                    }
+                    println!();
+                    for version in versions.versions.iter().rev() {
+                        println!(
+                            "{} {}  {}",
+                            "●".cyan(),
+                            version.label.bright_green().bold(),
+                            version.commit_id[..7.min(version.commit_id.len())].bright_yellow()
+                        );
+                        if let Some(note) = &version.note {
+                            println!("  │ {}", note);
+                        }
+                        let created = version.created_at.with_timezone(&chrono::Local);
+                        let byline = format!(
+                            "{} · {}",
+                            version.user,
+                            created.format("%Y-%m-%d %H:%M")
+                        );
+                        println!("  │ {}", byline.bright_black());
+                    }
+                    println!();
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Bounce(bounce_cmd) => {
+            // Find repository root
+            let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+
+            let manager = BounceManager::new(&current_dir);
+
+            match bounce_cmd {
+                BounceCommands::Add {
+                    file,
+                    commit,
+                    description,
+                } => {
+                    // Get commit ID - use latest if not specified
+                    let commit_id = match commit {
+                        Some(id) => id,
+                        None => {
+                            // Get latest commit from oxen log
+                            let subprocess = auxin::OxenSubprocess::new();
+                            let commits = subprocess
+                                .log(&current_dir, Some(1))
+                                .context("Failed to get latest commit")?;
+                            if commits.is_empty() {
+                                anyhow::bail!("No commits found. Create a commit first.");
+                            }
+                            commits[0].id.clone()
+                        }
+                    };
+
+                    let pb = progress::spinner(&format!(
+                        "Adding bounce for commit {}...",
+                        &commit_id[..8.min(commit_id.len())]
+                    ));
+
+                    match manager.add_bounce(&commit_id, &file, description.as_deref()) {
+                        Ok(metadata) => {
+                            progress::finish_success(&pb, "Bounce added");
+                            println!();
+                            println!(
+                                "  Commit:    {}",
+                                &metadata.commit_id[..8.min(metadata.commit_id.len())]
+                            );
+                            println!("  File:      {}", metadata.original_filename);
+                            println!("  Format:    {:?}", metadata.format);
+                            println!("  Size:      {}", metadata.format_size());
+                            println!("  Duration:  {}", metadata.format_duration());
+                            if let Some(desc) = &metadata.description {
+                                println!("  Note:      {}", desc);
+                            }
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Failed to add bounce");
+                            anyhow::bail!("{}", e);
+                        }
+                    }
+                    sync_meta_branch(&current_dir);
+                }
+
+                BounceCommands::List => {
+                    let bounces = manager.list_bounces().context("Failed to list bounces")?;
+
+                    if bounces.is_empty() {
+                        println!("No bounces found.");
+                        println!();
+                        println!("Add a bounce with: auxin bounce add <file>");
+                    } else {
+                        println!();
+                        println!("┌─ Audio Bounces ─────────────────────────────────────────┐");
+                        println!("│                                                          │");
+
+                        for bounce in &bounces {
+                            let commit_short = &bounce.commit_id[..8.min(bounce.commit_id.len())];
+                            let duration = bounce.format_duration();
+                            let size = bounce.format_size();
+
+                            println!(
+                                "│  {} {} │",
+                                commit_short.yellow(),
+                                " ".repeat(48 - commit_short.len())
+                            );
+                            println!(
+                                "│    File:     {:<41} │",
+                                if bounce.original_filename.len() > 41 {
+                                    format!("{}...", &bounce.original_filename[..38])
+                                } else {
+                                    bounce.original_filename.clone()
+                                }
+                            );
+                            println!("│    Format:   {:<41} │", format!("{:?}", bounce.format));
+                            println!("│    Duration: {:<41} │", duration);
+                            println!("│    Size:     {:<41} │", size);
+
+                            if let Some(desc) = &bounce.description {
+                                let desc_display = if desc.len() > 41 {
+                                    format!("{}...", &desc[..38])
+                                } else {
+                                    desc.clone()
+                                };
+                                println!("│    Note:     {:<41} │", desc_display);
+                            }
+                            println!(
+                                "│                                                          │"
+                            );
+                        }
+
+                        println!("└──────────────────────────────────────────────────────────┘");
+                        println!();
+                        println!("{} bounce(s) total", bounces.len());
+                    }
+                }
+
+                BounceCommands::Play { commit_id } => {
+                    let pb = progress::spinner(&format!(
+                        "Playing bounce for {}...",
+                        &commit_id[..8.min(commit_id.len())]
+                    ));
+
+                    match manager.play_bounce(&commit_id) {
+                        Ok(()) => {
+                            progress::finish_success(&pb, "Playback complete");
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Playback failed");
+                            anyhow::bail!("{}", e);
+                        }
+                    }
+                }
+
+                BounceCommands::Info { commit_id } => {
+                    match manager.get_bounce(&commit_id)? {
+                        Some(metadata) => {
+                            println!();
+                            println!("┌─ Bounce Info ───────────────────────────────────────────┐");
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!(
+                                "│  Commit:      {:<42} │",
+                                &metadata.commit_id[..8.min(metadata.commit_id.len())]
+                            );
+                            println!(
+                                "│  File:        {:<42} │",
+                                if metadata.original_filename.len() > 42 {
+                                    format!("{}...", &metadata.original_filename[..39])
+                                } else {
+                                    metadata.original_filename.clone()
+                                }
+                            );
+                            println!("│  Format:      {:<42} │", format!("{:?}", metadata.format));
+                            println!("│  Size:        {:<42} │", metadata.format_size());
+                            println!("│  Duration:    {:<42} │", metadata.format_duration());
+
+                            if let Some(sr) = metadata.sample_rate {
+                                println!("│  Sample Rate: {:<42} │", format!("{} Hz", sr));
+                            }
+                            if let Some(bd) = metadata.bit_depth {
+                                println!("│  Bit Depth:   {:<42} │", format!("{}-bit", bd));
+                            }
+                            if let Some(ch) = metadata.channels {
+                                let ch_str = if ch == 1 {
+                                    "Mono".to_string()
+                                } else if ch == 2 {
+                                    "Stereo".to_string()
+                                } else {
+                                    format!("{} channels", ch)
+                                };
+                                println!("│  Channels:    {:<42} │", ch_str);
+                            }
+
+                            println!(
+                                "│  Added:       {:<42} │",
+                                metadata.added_at.format("%Y-%m-%d %H:%M").to_string()
+                            );
+                            println!("│  By:          {:<42} │", metadata.added_by);
+
+                            if let Some(desc) = &metadata.description {
+                                println!(
+                                    "│                                                          │"
+                                );
+                                println!(
+                                    "│  Description:                                            │"
+                                );
+                                // Word wrap description
+                                for chunk in desc.as_bytes().chunks(54) {
+                                    let line = String::from_utf8_lossy(chunk);
+                                    println!("│    {:<54} │", line);
+                                }
+                            }
+
+                            println!(
+                                "│                                                          │"
+                            );
+                            println!(
+                                "└──────────────────────────────────────────────────────────┘"
+                            );
+                            println!();
+                        }
+                        None => {
+                            anyhow::bail!("No bounce found for commit {}", commit_id);
+                        }
+                    }
+                }
+
+                BounceCommands::Delete { commit_id } => {
+                    let pb = progress::spinner(&format!(
+                        "Deleting bounce for {}...",
+                        &commit_id[..8.min(commit_id.len())]
+                    ));
+
+                    match manager.delete_bounce(&commit_id) {
+                        Ok(()) => {
+                            progress::finish_success(&pb, "Bounce deleted");
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Delete failed");
+                            anyhow::bail!("{}", e);
+                        }
+                    }
+                }
+
+                BounceCommands::Search {
+                    format,
+                    pattern,
+                    min_duration,
+                    max_duration,
+                    min_size,
+                    max_size,
+                    after,
+                    before,
+                    user,
+                } => {
+                    use auxin::BounceFilter;
+                    use chrono::NaiveDate;
+
+                    let pb = progress::spinner("Searching bounces...");
+
+                    // Build filter
+                    let mut filter = BounceFilter::default();
+
+                    if let Some(fmt) = format {
+                        filter.format = auxin::AudioFormat::from_extension(&fmt);
+                        if filter.format.is_none() {
+                            progress::finish_error(&pb, "Invalid format");
+                            anyhow::bail!("Unknown audio format: {}", fmt);
+                        }
+                    }
+
+                    filter.filename_pattern = pattern;
+                    filter.min_duration = min_duration;
+                    filter.max_duration = max_duration;
+                    filter.min_size = min_size;
+                    filter.max_size = max_size;
+                    filter.added_by = user;
+
+                    // Parse date filters
+                    if let Some(after_str) = after {
+                        match NaiveDate::parse_from_str(&after_str, "%Y-%m-%d") {
+                            Ok(date) => {
+                                filter.after = Some(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+                            }
+                            Err(_) => {
+                                progress::finish_error(&pb, "Invalid date");
+                                anyhow::bail!("Invalid date format: {}. Use YYYY-MM-DD", after_str);
+                            }
+                        }
+                    }
+
+                    if let Some(before_str) = before {
+                        match NaiveDate::parse_from_str(&before_str, "%Y-%m-%d") {
+                            Ok(date) => {
+                                filter.before =
+                                    Some(date.and_hms_opt(23, 59, 59).unwrap().and_utc());
+                            }
+                            Err(_) => {
+                                progress::finish_error(&pb, "Invalid date");
+                                anyhow::bail!(
+                                    "Invalid date format: {}. Use YYYY-MM-DD",
+                                    before_str
+                                );
+                            }
+                        }
+                    }
+
+                    match manager.search_bounces(&filter) {
+                        Ok(bounces) => {
+                            progress::finish_success(
+                                &pb,
+                                &format!("Found {} bounces", bounces.len()),
+                            );
+
+                            if bounces.is_empty() {
+                                println!("No bounces match the filter criteria");
+                            } else {
+                                for bounce in bounces {
+                                    println!("\n{}", "─".repeat(50).dimmed());
+                                    println!(
+                                        "{}: {}",
+                                        "Commit".cyan(),
+                                        &bounce.commit_id[..8.min(bounce.commit_id.len())]
+                                    );
+                                    println!("{}: {}", "File".cyan(), bounce.original_filename);
+                                    println!(
+                                        "{}: {:?} | {} | {}",
+                                        "Info".cyan(),
+                                        bounce.format,
+                                        bounce.format_duration(),
+                                        bounce.format_size()
+                                    );
+                                    println!(
+                                        "{}: {}",
+                                        "Added".cyan(),
+                                        bounce.added_at.format("%Y-%m-%d %H:%M")
+                                    );
+                                    if let Some(desc) = &bounce.description {
+                                        println!("{}: {}", "Description".cyan(), desc);
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Search failed");
+                            anyhow::bail!("{}", e);
+                        }
+                    }
+                }
+
+                BounceCommands::Compare {
+                    commit_a,
+                    commit_b,
+                    null_test,
+                } => {
+                    let pb = progress::spinner(&format!(
+                        "Comparing {} and {}{}...",
+                        &commit_a[..8.min(commit_a.len())],
+                        &commit_b[..8.min(commit_b.len())],
+                        if null_test { " (with null test)" } else { "" }
+                    ));
+
+                    let result = if null_test {
+                        manager.compare_bounces_with_null_test(&commit_a, &commit_b)
+                    } else {
+                        manager.compare_bounces(&commit_a, &commit_b)
+                    };
+
+                    match result {
+                        Ok(comparison) => {
+                            progress::finish_success(&pb, "Comparison complete");
+                            println!("\n{}", comparison.format_report());
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Comparison failed");
+                            anyhow::bail!("{}", e);
+                        }
+                    }
+                }
+
+                BounceCommands::BatchAdd { files, commit } => {
+                    let pb = progress::spinner(&format!("Adding {} bounce files...", files.len()));
+
+                    // Get latest commit if no default commit specified
+                    let default_commit = if let Some(c) = commit {
+                        c
+                    } else {
+                        let subprocess = auxin::OxenSubprocess::new();
+                        match subprocess.log(&current_dir, Some(1)) {
+                            Ok(commits) if !commits.is_empty() => commits[0].id.clone(),
+                            _ => {
+                                progress::finish_error(&pb, "No commits found");
+                                anyhow::bail!("No commits found. Please specify --commit or create a commit first.");
+                            }
+                        }
+                    };
+
+                    let mut success_count = 0;
+                    let mut error_count = 0;
+
+                    for file in &files {
+                        // Try to extract commit ID from filename (pattern: commit_<id>_name.ext)
+                        let commit_id =
+                            if let Some(filename) = file.file_name().and_then(|n| n.to_str()) {
+                                if filename.starts_with("commit_") {
+                                    let parts: Vec<&str> = filename.splitn(3, '_').collect();
+                                    if parts.len() >= 2 {
+                                        parts[1].to_string()
+                                    } else {
+                                        default_commit.clone()
+                                    }
+                                } else {
+                                    default_commit.clone()
+                                }
+                            } else {
+                                default_commit.clone()
+                            };
+
+                        match manager.add_bounce(&commit_id, file, None) {
+                            Ok(_) => {
+                                success_count += 1;
+                            }
+                            Err(e) => {
+                                error_count += 1;
+                                eprintln!("{}: {}", file.display(), e);
+                            }
+                        }
+                    }
+
+                    if error_count == 0 {
+                        progress::finish_success(&pb, &format!("Added {} bounces", success_count));
+                    } else {
+                        progress::finish_error(
+                            &pb,
+                            &format!("{} added, {} failed", success_count, error_count),
+                        );
+                    }
+                }
+
+                BounceCommands::BulkDelete {
+                    format,
+                    before,
+                    user,
+                    force,
+                } => {
+                    use auxin::BounceFilter;
+                    use chrono::NaiveDate;
+
+                    // Require at least one filter
+                    if format.is_none() && before.is_none() && user.is_none() {
+                        anyhow::bail!(
+                            "At least one filter (--format, --before, or --user) must be specified"
+                        );
+                    }
+
+                    let pb = progress::spinner("Finding bounces to delete...");
+
+                    // Build filter
+                    let mut filter = BounceFilter::default();
+
+                    if let Some(fmt) = &format {
+                        filter.format = auxin::AudioFormat::from_extension(fmt);
+                    }
+
+                    if let Some(before_str) = &before {
+                        match NaiveDate::parse_from_str(before_str, "%Y-%m-%d") {
+                            Ok(date) => {
+                                filter.before =
+                                    Some(date.and_hms_opt(23, 59, 59).unwrap().and_utc());
+                            }
+                            Err(_) => {
+                                progress::finish_error(&pb, "Invalid date");
+                                anyhow::bail!(
+                                    "Invalid date format: {}. Use YYYY-MM-DD",
+                                    before_str
+                                );
+                            }
+                        }
+                    }
+
+                    filter.added_by = user.clone();
+
+                    // Find matching bounces
+                    let bounces = match manager.search_bounces(&filter) {
+                        Ok(b) => b,
+                        Err(e) => {
+                            progress::finish_error(&pb, "Search failed");
+                            anyhow::bail!("{}", e);
+                        }
+                    };
+
+                    if bounces.is_empty() {
+                        progress::finish_success(&pb, "No matching bounces found");
+                        return Ok(());
+                    }
+
+                    progress::finish_success(&pb, &format!("Found {} bounces", bounces.len()));
+
+                    // Confirm deletion
+                    if !force {
+                        println!("\n{} bounces will be deleted:", bounces.len());
+                        for bounce in &bounces {
+                            println!(
+                                "  • {} ({})",
+                                bounce.original_filename,
+                                &bounce.commit_id[..8.min(bounce.commit_id.len())]
+                            );
+                        }
+                        println!("\nUse --force to delete without confirmation.");
+                        return Ok(());
+                    }
+
+                    // Delete bounces
+                    let pb = progress::spinner(&format!("Deleting {} bounces...", bounces.len()));
+                    let mut deleted = 0;
+
+                    for bounce in &bounces {
+                        if let Err(e) = manager.delete_bounce(&bounce.commit_id) {
+                            eprintln!("Failed to delete {}: {}", bounce.commit_id, e);
+                        } else {
+                            deleted += 1;
+                        }
+                    }
+
+                    progress::finish_success(&pb, &format!("Deleted {} bounces", deleted));
+                }
+
+                BounceCommands::Export {
+                    commit_id,
+                    watermark,
+                    sound,
+                    output,
+                    no_bwf,
+                } => {
+                    use auxin::watermark::{self, Watermark, WatermarkSound};
+
+                    let commit_id = auxin::commit_link::resolve_commit_arg(&commit_id);
+                    let bounce = manager
+                        .list_bounces()?
+                        .into_iter()
+                        .find(|b| b.commit_id.starts_with(&commit_id));
+                    let path = bounce
+                        .as_ref()
+                        .and_then(|b| manager.get_bounce_path(&b.commit_id).ok().flatten());
+                    let (Some(bounce), Some(path)) = (bounce, path) else {
+                        progress::error(&format!("No bounce found for commit {}", commit_id));
+                        progress::info("Attach one with: auxin bounce add <FILE> --commit <ID>");
+                        std::process::exit(1);
+                    };
+
+                    let watermark = watermark.then(|| {
+                        let config = Config::load().unwrap_or_default();
+                        let mut watermark = Watermark::from_config(&config.watermark);
+                        if let Some(sound) = sound {
+                            watermark.sound = WatermarkSound::File(sound);
+                        }
+                        watermark
+                    });
+                    let output = output.unwrap_or_else(|| {
+                        watermark::default_output(&bounce.original_filename, watermark.is_some())
+                    });
+
+                    let pb = progress::spinner(if watermark.is_some() {
+                        "Rendering watermarked preview..."
+                    } else {
+                        "Exporting bounce..."
+                    });
+                    match watermark::export(&path, &output, watermark.as_ref()) {
+                        Ok(()) => {
+                            progress::finish_success(
+                                &pb,
+                                &format!("Exported to {}", output.display()),
+                            );
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Export failed");
+                            return Err(e);
+                        }
+                    }
+
+                    let is_wav = output
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+                    if is_wav && !no_bwf {
+                        let commits = OxenRepository::new(".").get_history(None).await?;
+                        let commit = commits
+                            .into_iter()
+                            .find(|c| c.id == bounce.commit_id)
+                            .unwrap_or_else(|| auxin::CommitInfo {
+                                id: bounce.commit_id.clone(),
+                                message: String::new(),
+                                ..Default::default()
+                            });
+                        let metadata = auxin::bwf::BwfMetadata::for_commit(
+                            &current_dir,
+                            &commit,
+                            bounce.added_at,
+                        );
+                        match auxin::bwf::embed(&output, &metadata) {
+                            Ok(()) => println!(
+                                "  Embedded BWF/iXML metadata for {}",
+                                &commit.id[..8.min(commit.id.len())]
+                            ),
+                            Err(e) => warn!("Could not embed BWF metadata: {}", e),
+                        }
+                    }
+                }
+
+                BounceCommands::Check {
+                    commit_id,
+                    target,
+                    json,
+                } => {
+                    let json = json || cli.json;
+                    use auxin::loudness::{self, LoudnessReport, LoudnessTarget};
+
+                    let target = LoudnessTarget::parse(&target).context("Unknown target")?;
+                    let commit_id = auxin::commit_link::resolve_commit_arg(&commit_id);
+                    let bounce = manager
+                        .list_bounces()?
+                        .into_iter()
+                        .find(|b| b.commit_id.starts_with(&commit_id));
+                    let Some(path) = bounce
+                        .as_ref()
+                        .and_then(|b| manager.get_bounce_path(&b.commit_id).ok().flatten())
+                    else {
+                        progress::error(&format!("No bounce found for commit {}", commit_id));
+                        std::process::exit(1);
+                    };
+
+                    let pb = progress::spinner("Measuring loudness...");
+                    let measurement = match loudness::measure(&path) {
+                        Ok(measurement) => {
+                            pb.finish_and_clear();
+                            measurement
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Loudness measurement failed");
+                            return Err(e);
+                        }
+                    };
+                    let report = LoudnessReport::check(target, measurement);
+
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else {
+                        let mark = |ok: bool| if ok { "✓".green() } else { "✗".red() };
+                        println!();
+                        println!("  Target:      {}", target.name().bold());
+                        println!(
+                            "  {} Integrated: {:.1} LUFS (target {:.0} ± {})",
+                            mark(report.integrated_ok),
+                            measurement.integrated_lufs,
+                            target.integrated_lufs(),
+                            target.tolerance_lu()
+                        );
+                        println!(
+                            "  {} True peak:  {:.1} dBTP (max {:.0})",
+                            mark(report.true_peak_ok),
+                            measurement.true_peak_dbtp,
+                            target.max_true_peak_dbtp()
+                        );
+                        println!("    Range:      {:.1} LU", measurement.loudness_range_lu);
+                        println!();
+                        if report.passed() {
+                            progress::success(&format!("Meets the {} target", target));
+                        } else {
+                            progress::error(&format!("Doesn't meet the {} target", target));
+                            if report.suggested_gain_db != 0.0 {
+                                progress::info(&format!(
+                                    "Suggested gain: {:+.1} dB",
+                                    report.suggested_gain_db
+                                ));
+                            }
+                            if report.needs_limiting {
+                                progress::info(
+                                    "Reaching the loudness target without clipping needs limiting",
+                                );
+                            }
+                        }
+                    }
+
+                    if !report.passed() {
+                        std::process::exit(1);
+                    }
+                }
+
+                BounceCommands::Identify { file } => {
+                    let Some(metadata) = auxin::bwf::read(&file)? else {
+                        progress::warning(&format!("{} has no auxin metadata", file.display()));
+                        return Ok(());
+                    };
+
+                    println!();
+                    println!("  Commit:   {}", metadata.commit_id.yellow());
+                    println!("  Project:  {}", metadata.project);
+                    if let Some(version) = &metadata.version {
+                        println!("  Version:  {}", version);
+                    }
+                    if let Some(message) = &metadata.message {
+                        println!("  Message:  {}", message.lines().next().unwrap_or_default());
+                    }
+                    if let Some(bpm) = metadata.bpm {
+                        println!("  BPM:      {}", bpm);
+                    }
+                    if let Some(key) = &metadata.key {
+                        println!("  Key:      {}", key);
+                    }
+                    println!("  Bounced:  {}", metadata.created_at.format("%Y-%m-%d %H:%M"));
+                    println!();
+                    progress::info(&format!("Open it with: auxin show {}", metadata.commit_id));
+                }
+            }
+
+            Ok(())
+        }
+
+        Commands::Stems(stems_cmd) => {
+            use auxin::stems::{StemSet, StemsManager};
+
+            let project = std::env::current_dir()?;
+            let manager = StemsManager::new(&project);
+            let short = |id: &str| id[..7.min(id.len())].to_string();
+            // A commit's stem set, by ID prefix or link
+            let find_set = |arg: &str| -> anyhow::Result<StemSet> {
+                let id = auxin::commit_link::resolve_commit_arg(arg);
+                let set = manager
+                    .list_stem_sets()?
+                    .into_iter()
+                    .find(|s| s.commit_id.starts_with(&id));
+                set.ok_or_else(|| anyhow::anyhow!("No stems found for commit {}", arg))
+            };
+            let push = |set: &StemSet| -> anyhow::Result<()> {
+                let config = auxin_config::load_config().unwrap_or_default();
+                let client = AuxinServerClient::new(ServerConfig::from_config(&config))?;
+                let repo_name = project
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .context("Failed to determine repository name")?;
+                let pb = progress::spinner(&format!(
+                    "Uploading {} stems ({})...",
+                    set.stems.len(),
+                    auxin::describe::format_size(set.total_size())
+                ));
+                let dir = manager.set_dir(&set.commit_id);
+                match client.upload_stems(&config.cli.default_namespace, &repo_name, set, &dir) {
+                    Ok(()) => {
+                        progress::finish_success(&pb, "Stems uploaded to server");
+                        Ok(())
+                    }
+                    Err(e) => {
+                        progress::finish_error(&pb, "Failed to upload stems");
+                        Err(e)
+                    }
+                }
+            };
+
+            match stems_cmd {
+                StemsCommands::Add {
+                    commit,
+                    files,
+                    description,
+                    push: push_after,
+                } => {
+                    let commit_id = auxin::commit_link::resolve_commit_arg(&commit);
+                    let commits = OxenRepository::new(".").get_history(None).await?;
+                    let Some(target) = commits.iter().find(|c| c.id.starts_with(&commit_id)) else {
+                        progress::error(&format!("Commit not found: {}", commit));
+                        std::process::exit(1);
+                    };
+
+                    let pb = progress::spinner(&format!(
+                        "Adding {} stems to {}...",
+                        files.len(),
+                        short(&target.id)
+                    ));
+                    let set = match manager.add_stems(&target.id, &files, description.as_deref()) {
+                        Ok(set) => {
+                            progress::finish_success(&pb, &format!("Added {} stems", files.len()));
+                            set
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Failed to add stems");
+                            return Err(e);
+                        }
+                    };
+                    println!(
+                        "  {} now has {} stems ({})",
+                        short(&set.commit_id),
+                        set.stems.len(),
+                        auxin::describe::format_size(set.total_size())
+                    );
+                    if push_after {
+                        push(&set)?;
+                    }
+                }
+
+                StemsCommands::List { commit: None } => {
+                    let sets = manager.list_stem_sets()?;
+                    if sets.is_empty() {
+                        println!("No stems found.");
+                        println!();
+                        println!("Add stems with: auxin stems add <COMMIT> <FILES>...");
+                        return Ok(());
+                    }
+                    println!();
+                    for set in &sets {
+                        let names: Vec<&str> = set.stems.iter().map(|s| s.name.as_str()).collect();
+                        println!(
+                            "{}  {} stems, {}  {}",
+                            short(&set.commit_id).yellow(),
+                            set.stems.len(),
+                            auxin::describe::format_size(set.total_size()),
+                            set.updated_at.format("%Y-%m-%d %H:%M").to_string().dimmed()
+                        );
+                        println!("         {}", names.join(", "));
+                        if let Some(description) = &set.description {
+                            println!("         {}", description.dimmed());
+                        }
+                    }
+                    println!();
+                    progress::info(&format!("{} commit(s) with stems", sets.len()));
+                }
+
+                StemsCommands::List {
+                    commit: Some(commit),
+                } => {
+                    let set = find_set(&commit)?;
+                    let problems = manager.verify(&set)?;
+                    println!();
+                    println!(
+                        "{} {}",
+                        "Stems for".bold(),
+                        short(&set.commit_id).yellow()
+                    );
+                    if let Some(description) = &set.description {
+                        println!("  {}", description);
+                    }
+                    println!();
+                    for stem in &set.stems {
+                        println!(
+                            "  {:<20} {:<24} {:>10}  {}",
+                            stem.name,
+                            stem.filename,
+                            auxin::describe::format_size(stem.size_bytes),
+                            &stem.sha256[..12.min(stem.sha256.len())].dimmed()
+                        );
+                    }
+                    println!();
+                    println!(
+                        "  Added by {} on {}",
+                        set.added_by,
+                        set.updated_at.format("%Y-%m-%d %H:%M")
+                    );
+                    if problems.is_empty() {
+                        progress::success("All stems match their checksums");
+                    } else {
+                        for problem in &problems {
+                            progress::warning(&problem.to_string());
+                        }
+                    }
+                }
+
+                StemsCommands::Export { commit, output } => {
+                    let set = find_set(&commit)?;
+                    let output = output.unwrap_or_else(|| {
+                        let name = project
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_else(|| "project".to_string());
+                        PathBuf::from(format!("{}-stems-{}", name, short(&set.commit_id)))
+                    });
+
+                    let pb = progress::spinner(&format!("Exporting {} stems...", set.stems.len()));
+                    match manager.export(&set, &output) {
+                        Ok(_) => progress::finish_success(
+                            &pb,
+                            &format!("Exported to {}", output.display()),
+                        ),
+                        Err(e) => {
+                            progress::finish_error(&pb, "Export failed");
+                            return Err(e);
+                        }
+                    }
+                }
+
+                StemsCommands::Push { commit } => {
+                    let set = find_set(&commit)?;
+                    push(&set)?;
+                }
+            }
+
+            Ok(())
+        }
 
-                        match AuxinServerClient::new(server_config) {
-                            Ok(client) => {
-                                // Get namespace/name from config or current directory
-                                let namespace = config.cli.default_namespace.clone();
-                                let repo_name = current_dir
-                                    .file_name()
-                                    .map(|s| s.to_string_lossy().to_string())
-                                    .unwrap_or_else(|| "unknown".to_string());
+        Commands::MetadataDiff {
+            project_a,
+            project_b,
+            output,
+            color,
+            verbose: verbose_flag,
+        } => {
+            use auxin::{LogicParser, MetadataDiffer};
 
-                                let pb = progress::spinner("Checking server lock status...");
+            vlog!("Parsing project A: {}", project_a.display());
 
-                                // ureq is a blocking client, safe to call directly
-                                match client.get_lock_status(&namespace, &repo_name) {
-                                    Ok(status) => {
-                                        progress::finish_success(&pb, "Lock status retrieved");
-                                        println!();
-                                        if status.locked {
-                                            if let Some(lock) = status.lock {
-                                                println!(
-                                                    "{}",
-                                                    "🔒 Repository is LOCKED".red().bold()
-                                                );
-                                                println!();
-                                                println!(
-                                                    "  {} {}",
-                                                    "Lock ID:".dimmed(),
-                                                    lock.lock_id.cyan()
-                                                );
-                                                println!(
-                                                    "  {} {}",
-                                                    "Held by:".dimmed(),
-                                                    lock.user.yellow()
-                                                );
-                                                println!(
-                                                    "  {} {}",
-                                                    "Machine:".dimmed(),
-                                                    lock.machine_id.dimmed()
-                                                );
-                                                println!(
-                                                    "  {} {}",
-                                                    "Acquired:".dimmed(),
-                                                    lock.acquired_at.dimmed()
-                                                );
-                                                println!(
-                                                    "  {} {}",
-                                                    "Expires:".dimmed(),
-                                                    lock.expires_at.yellow()
-                                                );
-                                                println!(
-                                                    "  {} {}",
-                                                    "Last seen:".dimmed(),
-                                                    lock.last_heartbeat.dimmed()
-                                                );
-                                            }
-                                        } else {
-                                            println!(
-                                                "{}",
-                                                "🔓 Repository is UNLOCKED".green().bold()
-                                            );
-                                            println!();
-                                            progress::info(
-                                                "You can acquire a lock with: auxin lock acquire",
-                                            );
-                                        }
-                                    }
-                                    Err(e) => {
-                                        progress::finish_error(&pb, "Failed to get lock status");
-                                        anyhow::bail!("Server error: {}", e);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Failed to connect to server, falling back to local lock status");
-                                vlog!("Server error: {}", e);
-                                lock_integration::handle_lock_status(&current_dir)?;
-                            }
+            // Validate paths
+            if !LogicParser::is_valid_project(&project_a) {
+                anyhow::bail!("Invalid Logic Pro project: {}", project_a.display());
+            }
+
+            if !LogicParser::is_valid_project(&project_b) {
+                anyhow::bail!("Invalid Logic Pro project: {}", project_b.display());
+            }
+
+            // Parse both projects
+            let data_a = LogicParser::parse(&project_a)
+                .map_err(|e| anyhow::anyhow!("Failed to parse project A: {}", e))?;
+
+            vlog!("Parsing project B: {}", project_b.display());
+            let data_b = LogicParser::parse(&project_b)
+                .map_err(|e| anyhow::anyhow!("Failed to parse project B: {}", e))?;
+
+            // Generate diff
+            vlog!("Computing metadata diff");
+            let diff = MetadataDiffer::compare(&data_a, &data_b);
+
+            // Output result
+            match output.as_str() {
+                "json" => {
+                    let json = MetadataDiffer::to_json(&diff)?;
+                    println!("{}", json);
+                }
+                _ => {
+                    // Determine color usage
+                    let use_color = if color {
+                        true
+                    } else {
+                        // Auto-detect TTY
+                        atty::is(atty::Stream::Stdout)
+                    };
+
+                    let report = MetadataDiffer::generate_report_with_options(
+                        &diff,
+                        use_color,
+                        verbose_flag || cli.verbose,
+                    );
+                    println!("{}", report);
+                }
+            }
+
+            success!("Metadata diff completed");
+            Ok(())
+        }
+
+        Commands::Metadata(metadata_cmd) => {
+            use auxin::metadata_edits::{MetadataEdits, MetadataFilter};
+
+            let repo_root = std::env::current_dir()?;
+            let commits = OxenSubprocess::new().log(&repo_root, None)?;
+            let mut edits = MetadataEdits::load(&repo_root)?;
+            let find_commit = |prefix: &str| {
+                commits
+                    .iter()
+                    .find(|c| c.id.starts_with(prefix))
+                    .ok_or_else(|| anyhow::anyhow!("Commit '{}' not found", prefix))
+            };
+            let user = auxin::lock_integration::get_user_identifier();
+
+            let edited = match metadata_cmd {
+                MetadataCommands::Edit { commit, changes } => {
+                    let commit = find_commit(&commit)?;
+                    let recorded = edits.edit(commit, &changes.changes()?, &user);
+                    if recorded.is_empty() {
+                        progress::info("Nothing changed");
+                        return Ok(());
+                    }
+                    for edit in &recorded {
+                        println!(
+                            "  {}: {} → {}",
+                            edit.field,
+                            edit.old.as_deref().unwrap_or("-"),
+                            edit.new.as_deref().unwrap_or("-")
+                        );
+                    }
+                    1
+                }
+                MetadataCommands::BulkEdit {
+                    filters,
+                    changes,
+                    dry_run,
+                } => {
+                    let filters = filters
+                        .iter()
+                        .map(|f| f.parse())
+                        .collect::<anyhow::Result<Vec<MetadataFilter>>>()?;
+                    let changes = changes.changes()?;
+                    let matching: Vec<_> = commits
+                        .iter()
+                        .filter(|c| {
+                            let metadata = edits.effective(c);
+                            filters.iter().all(|f| f.matches(&metadata))
+                        })
+                        .collect();
+
+                    let mut edited = 0;
+                    for commit in &matching {
+                        let short = &commit.id[..commit.id.len().min(8)];
+                        let recorded = edits.edit(commit, &changes, &user);
+                        if !recorded.is_empty() {
+                            let fields: Vec<&str> =
+                                recorded.iter().map(|e| e.field.as_str()).collect();
+                            println!("  {} {} ({})", "→".cyan(), short, fields.join(", "));
+                            edited += 1;
                         }
+                    }
+                    println!();
+                    if dry_run {
+                        progress::info(&format!(
+                            "Dry run: {} of {} matching commit(s) would change",
+                            edited,
+                            matching.len()
+                        ));
+                        return Ok(());
+                    }
+                    edited
+                }
+                MetadataCommands::History { commit } => {
+                    let commit_id = commit.map(|c| find_commit(&c).map(|c| c.id.clone()));
+                    let commit_id = commit_id.transpose()?;
+                    let entries: Vec<_> = edits
+                        .audit
+                        .iter()
+                        .filter(|e| commit_id.as_ref().is_none_or(|id| &e.commit_id == id))
+                        .collect();
+                    if entries.is_empty() {
+                        progress::info("No metadata edits");
+                    }
+                    for edit in entries {
+                        println!(
+                            "{}  {}  {}  {}: {} → {}",
+                            edit.edited_at.format("%Y-%m-%d %H:%M"),
+                            &edit.commit_id[..edit.commit_id.len().min(8)],
+                            edit.user,
+                            edit.field,
+                            edit.old.as_deref().unwrap_or("-"),
+                            edit.new.as_deref().unwrap_or("-")
+                        );
+                    }
+                    return Ok(());
+                }
+                MetadataCommands::Migrate => {
+                    let stored = MetadataStore::for_repo(&repo_root).migrate(&commits)?;
+                    if stored == 0 {
+                        progress::info("Every commit already has stored metadata");
                     } else {
-                        // Status check always executes (even when offline, shows local state)
-                        lock_integration::handle_lock_status(&current_dir)?;
+                        success!("Stored metadata for {} commit(s)", stored);
                     }
+                    return Ok(());
                 }
-
-                LockCommands::Break { force } => {
-                    // Break force always executes (administrative override)
-                    lock_integration::handle_lock_break(&current_dir, force)?;
+                MetadataCommands::Sync => {
+                    match sync_metadata_edits(&repo_root, &mut edits, &commits)? {
+                        Some(stored) => success!("Synced metadata for {} commit(s)", stored),
+                        None => progress::info(
+                            "Server metadata is disabled (auxin config set metadata true)",
+                        ),
+                    }
+                    return Ok(());
                 }
+            };
+
+            if edited == 0 {
+                progress::info("Nothing changed");
+                return Ok(());
             }
+            edits.save(&repo_root)?;
+            success!("Updated metadata for {} commit(s)", edited);
 
+            match sync_metadata_edits(&repo_root, &mut edits, &commits) {
+                Ok(Some(stored)) => vlog!("Synced metadata for {} commit(s)", stored),
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Could not sync metadata to the server: {}", e);
+                    progress::info("Run 'auxin metadata sync' to retry");
+                }
+            }
+            sync_meta_branch(&repo_root);
             Ok(())
         }
 
-        Commands::Auth(auth_cmd) => {
-            use auxin::AuthManager;
+        Commands::Daemon(daemon_cmd) => {
+            use auxin::daemon_client::{DaemonClient, DaemonCompatibility, Liveness};
+            use std::time::Duration;
 
-            let auth = AuthManager::new();
+            let client = DaemonClient::new();
+            let daemon_config = Config::load().unwrap_or_default().daemon;
+            let health_timeout = Duration::from_secs(daemon_config.health_timeout_secs);
 
-            match auth_cmd {
-                AuthCommands::Login => {
-                    use std::io::{self, Write};
+            match daemon_cmd {
+                DaemonCommands::Status => {
+                    let pb = progress::spinner("Checking daemon status...");
+                    let mut status = client.status()?;
+                    // Fill in what launchctl can't tell from the health check
+                    if status.is_running {
+                        if let Ok(health) = client.health(health_timeout) {
+                            status.project_count = Some(health.projects.len());
+                            status.uptime = health.uptime().map(|u| u.num_seconds() as f64);
+                        }
+                    }
+                    pb.finish_and_clear();
 
                     println!();
-                    println!("┌─ Oxen Hub Authentication ──────────────────────────────┐");
-                    println!("│                                                          │");
-                    println!("│  Login to Oxen Hub to enable remote collaboration       │");
-                    println!("│                                                          │");
-                    println!("│  Get your API key from: https://hub.oxen.ai             │");
-                    println!("│  Settings → API Keys → Create New Key                   │");
-                    println!("│                                                          │");
+                    println!("┌─ Daemon Status ─────────────────────────────────────────┐");
+
+                    if status.is_running {
+                        println!("│  Status: {} Running", "●".green());
+                        if let Some(pid) = status.pid {
+                            println!("│  PID: {}", pid.to_string().bright_yellow());
+                        }
+                    } else {
+                        println!("│  Status: {} Stopped", "●".red());
+                    }
+
+                    if let Some(count) = status.project_count {
+                        println!("│  Monitored Projects: {}", count);
+                    }
+                    if let Some(ref version) = status.version {
+                        match status.protocol_version {
+                            Some(protocol) => {
+                                println!("│  Version: {} (protocol v{})", version, protocol)
+                            }
+                            None => println!("│  Version: {}", version),
+                        }
+                    }
+                    if let Some(uptime) = status.uptime {
+                        println!("│  Uptime: {:.1} hours", uptime / 3600.0);
+                    }
+
                     println!("└──────────────────────────────────────────────────────────┘");
                     println!();
 
-                    // Prompt for username
-                    print!("Username: ");
-                    io::stdout().flush()?;
-                    let mut username = String::new();
-                    io::stdin().read_line(&mut username)?;
-                    let username = username.trim();
+                    if !status.is_running {
+                        progress::info("Start the daemon with: auxin daemon start");
+                    } else if let Some(warning) = DaemonCompatibility::check(&status).warning() {
+                        progress::warning(&warning);
+                    }
 
-                    if username.is_empty() {
-                        progress::error("Username cannot be empty");
-                        std::process::exit(1);
+                    Ok(())
+                }
+
+                DaemonCommands::Start => {
+                    // Check if already running
+                    let status = client.status()?;
+                    if status.is_running {
+                        progress::warning("Daemon is already running");
+                        if let Some(pid) = status.pid {
+                            println!("  PID: {}", pid);
+                        }
+                        return Ok(());
                     }
 
-                    // Prompt for API key (hidden input would be better, but keep it simple for now)
-                    print!("API Key: ");
-                    io::stdout().flush()?;
-                    let mut api_key = String::new();
-                    io::stdin().read_line(&mut api_key)?;
-                    let api_key = api_key.trim();
+                    // Check if installed
+                    if !client.is_installed() {
+                        progress::error("Daemon is not installed");
+                        progress::info("Please run the installer: ./install.sh");
+                        anyhow::bail!("Daemon not installed");
+                    }
 
-                    if api_key.is_empty() {
-                        progress::error("API key cannot be empty");
-                        std::process::exit(1);
+                    let pb = progress::spinner("Starting daemon...");
+                    client.start()?;
+                    progress::finish_success(&pb, "Daemon started");
+
+                    // Show updated status
+                    let status = client.status()?;
+                    if let Some(pid) = status.pid {
+                        println!();
+                        progress::info(&format!("Daemon running with PID: {}", pid));
                     }
 
-                    // Store credentials
-                    let pb = progress::spinner("Storing credentials...");
+                    Ok(())
+                }
 
-                    match auth.store_credentials(username, api_key) {
-                        Ok(_) => {
-                            progress::finish_success(&pb, "Credentials stored");
-                            println!();
-                            progress::success(&format!("Authenticated as: {}", username));
-                            progress::info("You can now push/pull projects to Oxen Hub");
-                            println!();
-                            progress::info("Test authentication with: auxin auth test");
-                        }
-                        Err(e) => {
-                            progress::finish_error(&pb, "Failed to store credentials");
-                            progress::error(&format!("Error: {}", e));
-                            std::process::exit(1);
-                        }
+                DaemonCommands::Stop => {
+                    // Check if running
+                    let status = client.status()?;
+                    if !status.is_running {
+                        progress::warning("Daemon is not running");
+                        return Ok(());
                     }
+
+                    let pb = progress::spinner("Stopping daemon...");
+                    client.stop()?;
+                    progress::finish_success(&pb, "Daemon stopped");
+
+                    Ok(())
                 }
 
-                AuthCommands::Logout => {
-                    let pb = progress::spinner("Clearing credentials...");
+                DaemonCommands::Restart => {
+                    let pb = progress::spinner("Restarting daemon...");
+                    client.restart()?;
+                    progress::finish_success(&pb, "Daemon restarted");
 
-                    match auth.clear_credentials() {
-                        Ok(_) => {
-                            progress::finish_success(&pb, "Logged out");
-                            println!();
-                            progress::success("Credentials removed");
-                            progress::info("You are now logged out from Oxen Hub");
-                        }
-                        Err(e) => {
-                            progress::finish_error(&pb, "Failed to clear credentials");
-                            progress::error(&format!("Error: {}", e));
-                            std::process::exit(1);
-                        }
+                    // Show updated status
+                    let status = client.status()?;
+                    if let Some(pid) = status.pid {
+                        println!();
+                        progress::info(&format!("Daemon running with PID: {}", pid));
                     }
+
+                    Ok(())
                 }
 
-                AuthCommands::Status => {
+                DaemonCommands::Logs {
+                    lines,
+                    level,
+                    since,
+                    project,
+                    grep,
+                } => {
+                    use auxin::daemon_log::{self, LogFilter};
+                    use auxin::logger::Level;
+
+                    let log_path = client.log_path()?;
+                    let filter = LogFilter {
+                        level: level.as_deref().map(str::parse).transpose()?,
+                        since: since
+                            .as_deref()
+                            .map(|since| daemon_log::parse_since(since, chrono::Utc::now()))
+                            .transpose()?,
+                        // A path to an existing project matches however it was logged
+                        project: project.map(|project| {
+                            std::fs::canonicalize(&project)
+                                .map(|path| path.to_string_lossy().to_string())
+                                .unwrap_or(project)
+                        }),
+                        grep: grep
+                            .as_deref()
+                            .map(regex::Regex::new)
+                            .transpose()
+                            .context("Invalid --grep pattern")?,
+                    };
+
+                    println!();
+                    println!("┌─ Daemon Logs ───────────────────────────────────────────┐");
+                    println!("│  Path: {}", log_path);
+                    println!("│  Lines: {}", lines);
+                    println!("└──────────────────────────────────────────────────────────┘");
                     println!();
-                    println!("┌─ Authentication Status ─────────────────────────────────┐");
-                    println!("│                                                          │");
 
-                    match auth.get_credentials() {
-                        Ok(Some(creds)) => {
-                            println!(
-                                "│  Status: {} Authenticated                            │",
-                                "●".green()
-                            );
-                            println!(
-                                "│                                                          │"
-                            );
-                            println!(
-                                "│  Username: {}                                    │",
-                                creds.username
-                            );
-                            println!("│  Hub URL:  {}                      │", creds.hub_url);
-                            println!(
-                                "│                                                          │"
-                            );
-                            println!(
-                                "└──────────────────────────────────────────────────────────┘"
-                            );
-                            println!();
-                            progress::info("Run 'auxin auth test' to verify connection");
-                        }
-                        Ok(None) => {
-                            println!(
-                                "│  Status: {} Not authenticated                        │",
-                                "○".yellow()
-                            );
-                            println!(
-                                "│                                                          │"
-                            );
-                            println!("│  You need to login to use remote features               │");
-                            println!(
-                                "│                                                          │"
-                            );
-                            println!(
-                                "└──────────────────────────────────────────────────────────┘"
-                            );
-                            println!();
-                            progress::info("Login with: auxin auth login");
-                        }
-                        Err(e) => {
-                            println!(
-                                "│  Status: {} Error                                    │",
-                                "✗".red()
-                            );
-                            println!(
-                                "│                                                          │"
-                            );
-                            println!(
-                                "│  Error reading credentials                               │"
-                            );
-                            println!(
-                                "│                                                          │"
-                            );
+                    if !std::path::Path::new(&log_path).exists() {
+                        progress::info(&format!("Log file not found: {}", log_path));
+                        return Ok(());
+                    }
+                    let log_lines =
+                        daemon_log::read(std::path::Path::new(&log_path), &filter, lines)?;
+
+                    if log_lines.is_empty() {
+                        progress::info("No log entries found");
+                    } else {
+                        for line in log_lines {
+                            let Some(ts) = line.ts else {
+                                println!("{}", line.message);
+                                continue;
+                            };
+                            let level = match line.level {
+                                Level::Debug => "DEBUG".bright_blue(),
+                                Level::Info => "INFO ".green(),
+                                Level::Warn => "WARN ".yellow(),
+                                Level::Error => "ERROR".red(),
+                            };
+                            let project = line
+                                .project
+                                .as_deref()
+                                .and_then(|p| std::path::Path::new(p).file_name())
+                                .map(|name| format!("[{}] ", name.to_string_lossy()))
+                                .unwrap_or_default();
+                            let time = ts.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S");
                             println!(
-                                "└──────────────────────────────────────────────────────────┘"
+                                "{} {} {}{}",
+                                time.to_string().bright_black(),
+                                level,
+                                project.cyan(),
+                                line.message
                             );
-                            println!();
-                            progress::error(&format!("Error: {}", e));
                         }
                     }
+
+                    Ok(())
                 }
 
-                AuthCommands::Test => {
-                    let pb = progress::spinner("Testing authentication...");
+                DaemonCommands::Health { json } => {
+                    let json = json || cli.json;
+                    let health = client.health(health_timeout);
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&health?)?);
+                        return Ok(());
+                    }
 
-                    match auth.test_authentication() {
-                        Ok(username) => {
-                            progress::finish_success(&pb, "Authentication verified");
-                            println!();
-                            progress::success(&format!(
-                                "Successfully authenticated as: {}",
-                                username
-                            ));
-                            progress::info("Your credentials are valid");
-                        }
+                    let health = match health {
+                        Ok(health) => health,
                         Err(e) => {
-                            progress::finish_error(&pb, "Authentication failed");
-                            println!();
-                            progress::error("Unable to verify authentication");
-                            progress::info(&format!("Error: {}", e));
-                            println!();
-                            progress::info("Try logging in again: auxin auth login");
+                            progress::error(&format!("Daemon is not responding: {:#}", e));
+                            progress::info("Check it's running with: auxin daemon status");
                             std::process::exit(1);
                         }
-                    }
-                }
-            }
-
-            Ok(())
-        }
-
-        Commands::Server(server_cmd) => {
-            let config = Config::load().unwrap_or_default();
-
-            match server_cmd {
-                ServerCommands::Status => {
-                    // Truncate URL if too long
-                    let url_display = if config.cli.url.len() > 43 {
-                        format!("{}...", &config.cli.url[..40])
-                    } else {
-                        config.cli.url.clone()
                     };
 
                     println!();
-                    println!("┌─ Server Configuration ──────────────────────────────────┐");
-                    println!("│                                                          │");
-                    println!("│  URL:        {:<43} │", url_display);
-                    println!(
-                        "│  Namespace:  {:<43} │",
-                        config.cli.default_namespace.as_str()
-                    );
-                    println!(
-                        "│  Timeout:    {} seconds{:<34} │",
-                        config.cli.timeout_secs, ""
-                    );
-                    println!(
-                        "│  Locks:      {:<43} │",
-                        if config.cli.use_server_locks {
-                            "enabled"
-                        } else {
-                            "disabled"
-                        }
-                    );
-                    println!(
-                        "│  Metadata:   {:<43} │",
-                        if config.cli.use_server_metadata {
-                            "enabled"
+                    println!("┌─ Daemon Health ─────────────────────────────────────────┐");
+                    if let Some(pid) = health.pid {
+                        println!("│  PID: {}", pid.to_string().bright_yellow());
+                    }
+                    if let Some(uptime) = health.uptime() {
+                        println!("│  Uptime: {:.1} hours", uptime.num_seconds() as f64 / 3600.0);
+                    }
+                    println!("│  Projects: {}", health.projects.len());
+                    for project in &health.projects {
+                        let state = if project.watching {
+                            "●".green()
                         } else {
-                            "disabled"
-                        }
-                    );
-                    println!("│                                                          │");
-
-                    // Check connection
-                    let server_config = ServerConfig {
-                        url: config.cli.url.clone(),
-                        token: if config.cli.token.is_empty() { None } else { Some(config.cli.token.clone()) },
-                        timeout_secs: config.cli.timeout_secs as u64,
-                    };
-
-                    if let Ok(client) = AuxinServerClient::new(server_config) {
-                        match client.health_check() {
-                            Ok(true) => {
-                                println!("│  Status:     {} Connected{:<33} │", "●".green(), "");
-                            }
-                            Ok(false) | Err(_) => {
-                                println!("│  Status:     {} Unreachable{:<31} │", "●".red(), "");
-                            }
-                        }
-                    } else {
-                        println!("│  Status:     {} Unknown{:<35} │", "●".yellow(), "");
+                            "●".red()
+                        };
+                        let last_event = project
+                            .last_event_at
+                            .map(|t| {
+                                t.with_timezone(&chrono::Local)
+                                    .format("last change %Y-%m-%d %H:%M")
+                                    .to_string()
+                            })
+                            .unwrap_or_else(|| "no changes yet".to_string());
+                        println!("│    {} {} ({})", state, project.path, last_event.bright_black());
                     }
-
-                    println!("│                                                          │");
                     println!("└──────────────────────────────────────────────────────────┘");
                     println!();
-                }
 
-                ServerCommands::Health => {
-                    let pb = progress::spinner("Testing server connection...");
+                    if let Liveness::Unhealthy(reason) = Liveness::assess(Ok(health)) {
+                        progress::warning(&format!("Daemon is unhealthy: {}", reason));
+                        progress::info("Restart it with: auxin daemon restart");
+                        std::process::exit(1);
+                    }
+                    Ok(())
+                }
 
-                    let server_config = ServerConfig {
-                        url: config.cli.url.clone(),
-                        token: if config.cli.token.is_empty() { None } else { Some(config.cli.token.clone()) },
-                        timeout_secs: config.cli.timeout_secs as u64,
+                DaemonCommands::Watchdog { interval, once } => {
+                    use auxin::operation_history::{
+                        HistoryOperation, OperationHistoryEntry, OperationHistoryManager,
+                        OperationResult,
                     };
 
-                    match AuxinServerClient::new(server_config) {
-                        Ok(client) => match client.health_check() {
-                            Ok(true) => {
-                                progress::finish_success(&pb, "Server is healthy");
-                                println!();
-                                progress::success(&format!("Connected to {}", config.cli.url));
-                            }
-                            Ok(false) => {
-                                progress::finish_error(&pb, "Server health check failed");
-                                println!();
-                                progress::error(&format!(
-                                    "Server at {} is not responding",
-                                    config.cli.url
-                                ));
+                    let interval = interval.unwrap_or(daemon_config.watchdog_interval_secs);
+                    let interval = Duration::from_secs(interval);
+                    if !once {
+                        progress::info(&format!(
+                            "Watching the daemon every {}s (Ctrl+C to stop)",
+                            interval.as_secs()
+                        ));
+                    }
+
+                    loop {
+                        match client.liveness(health_timeout) {
+                            Ok(Liveness::Unhealthy(reason)) => {
+                                warn!("Daemon is unhealthy ({}); restarting", reason);
+                                let restarted = client.restart();
+                                let result = match &restarted {
+                                    Ok(()) => OperationResult::Success,
+                                    Err(e) => OperationResult::Failure(format!("{:#}", e)),
+                                };
+                                let entry =
+                                    OperationHistoryEntry::new(HistoryOperation::DaemonRestart)
+                                        .with_result(result)
+                                        .with_metadata("reason", reason);
+                                if let Err(e) = OperationHistoryManager::new().record(entry) {
+                                    vlog!("Failed to record daemon restart: {}", e);
+                                }
+                                match restarted {
+                                    Ok(()) => success!("Daemon restarted"),
+                                    Err(e) => auxin::error!("Failed to restart daemon: {:#}", e),
+                                }
                             }
-                            Err(e) => {
-                                progress::finish_error(&pb, "Connection failed");
-                                println!();
-                                progress::error(&format!("Failed to connect: {}", e));
+                            Ok(Liveness::Healthy(_)) => vlog!("Daemon is healthy"),
+                            Ok(Liveness::NotRunning) => vlog!("Daemon is not running; leaving it"),
+                            Ok(Liveness::Unsupported) => {
+                                vlog!("Daemon predates health checks; skipping")
                             }
-                        },
-                        Err(e) => {
-                            progress::finish_error(&pb, "Client error");
-                            println!();
-                            progress::error(&format!("Failed to create client: {}", e));
+                            Err(e) => vlog!("Failed to check the daemon: {:#}", e),
+                        }
+
+                        if once {
+                            return Ok(());
                         }
+                        std::thread::sleep(interval);
                     }
                 }
 
-                ServerCommands::Set { key, value } => {
-                    let mut config = Config::load().unwrap_or_default();
+                DaemonCommands::Ping => {
+                    let config = Config::load().unwrap_or_default();
+                    if !config.cli.use_server_locks {
+                        vlog!("Server locks are off; not pinging");
+                        return Ok(());
+                    }
+                    let server = AuxinServerClient::new(ServerConfig::from_config(&config))?;
+                    let machine_id = server_client::get_machine_id();
+                    server.send_presence(&server_client::get_user_identifier(), &machine_id)?;
+                    vlog!("Reported {} as online", machine_id);
 
-                    match key.as_str() {
-                        "url" => {
-                            config.cli.url = value.clone();
-                            progress::success(&format!("Set server URL to: {}", value));
-                        }
-                        "namespace" => {
-                            config.cli.default_namespace = value.clone();
-                            progress::success(&format!("Set default namespace to: {}", value));
+                    Ok(())
+                }
+
+                DaemonCommands::Hooks(hooks_cmd) => {
+                    use auxin::daemon_hooks::{parse_detail, DaemonHook, DaemonHookRegistry};
+
+                    let registry_path = DaemonHookRegistry::default_path();
+                    let mut registry = DaemonHookRegistry::load(&registry_path)?;
+
+                    match hooks_cmd {
+                        DaemonHookCommands::Add {
+                            event,
+                            script,
+                            name,
+                            project,
+                            timeout,
+                        } => {
+                            let event = event.parse()?;
+                            let script = script.canonicalize().with_context(|| {
+                                format!("Hook script not found: {}", script.display())
+                            })?;
+                            let project = project
+                                .map(|p| {
+                                    p.canonicalize().with_context(|| {
+                                        format!("Project not found: {}", p.display())
+                                    })
+                                })
+                                .transpose()?;
+                            let name = name.unwrap_or_else(|| {
+                                script
+                                    .file_name()
+                                    .map(|n| n.to_string_lossy().to_string())
+                                    .unwrap_or_default()
+                            });
+
+                            registry.add(DaemonHook {
+                                name: name.clone(),
+                                event,
+                                script,
+                                project,
+                                timeout_secs: timeout,
+                            })?;
+                            registry.save(&registry_path)?;
+
+                            progress::success(&format!(
+                                "Added daemon hook '{}' ({})",
+                                name, event
+                            ));
+                            progress::info("The daemon picks up changes on the next event");
+                            Ok(())
                         }
-                        "timeout" => match value.parse::<i64>() {
-                            Ok(timeout) => {
-                                config.cli.timeout_secs = timeout;
-                                progress::success(&format!("Set timeout to: {} seconds", timeout));
-                            }
-                            Err(_) => {
-                                progress::error("Invalid timeout value (must be a number)");
-                                std::process::exit(1);
-                            }
-                        },
-                        "locks" => match value.to_lowercase().as_str() {
-                            "true" | "on" | "yes" | "1" => {
-                                config.cli.use_server_locks = true;
-                                progress::success("Server locks enabled");
-                            }
-                            "false" | "off" | "no" | "0" => {
-                                config.cli.use_server_locks = false;
-                                progress::success("Server locks disabled");
-                            }
-                            _ => {
-                                progress::error("Invalid value for locks (use true/false)");
-                                std::process::exit(1);
-                            }
-                        },
-                        "metadata" => match value.to_lowercase().as_str() {
-                            "true" | "on" | "yes" | "1" => {
-                                config.cli.use_server_metadata = true;
-                                progress::success("Server metadata storage enabled");
+
+                        DaemonHookCommands::List => {
+                            if registry.hooks.is_empty() {
+                                progress::info("No daemon hooks registered");
+                                progress::info(
+                                    "Add one with: auxin daemon hooks add <EVENT> <SCRIPT>",
+                                );
                             }
-                            "false" | "off" | "no" | "0" => {
-                                config.cli.use_server_metadata = false;
-                                progress::success("Server metadata storage disabled");
+
+                            for hook in &registry.hooks {
+                                println!(
+                                    "{}  {}  {}",
+                                    hook.name.bright_yellow(),
+                                    hook.event.to_string().cyan(),
+                                    hook.script.display()
+                                );
+                                let scope = hook
+                                    .project
+                                    .as_ref()
+                                    .map(|p| p.display().to_string())
+                                    .unwrap_or_else(|| "all projects".to_string());
+                                println!(
+                                    "    {}",
+                                    format!("{}, timeout {}s", scope, hook.timeout_secs).dimmed()
+                                );
                             }
-                            _ => {
-                                progress::error("Invalid value for metadata (use true/false)");
-                                std::process::exit(1);
+                            Ok(())
+                        }
+
+                        DaemonHookCommands::Remove { name } => {
+                            if !registry.remove(&name) {
+                                anyhow::bail!("Daemon hook not found: {}", name);
                             }
-                        },
-                        _ => {
-                            progress::error(&format!("Unknown configuration key: {}", key));
-                            progress::info(
-                                "Available keys: url, namespace, timeout, locks, metadata",
-                            );
-                            std::process::exit(1);
+                            registry.save(&registry_path)?;
+                            progress::success(&format!("Removed daemon hook '{}'", name));
+                            Ok(())
                         }
-                    }
 
-                    // Save to project config
-                    if let Some(project_config_path) = Config::project_config_path() {
-                        match config.save_to_file(&project_config_path) {
-                            Ok(()) => {
-                                progress::info(&format!(
-                                    "Saved to {}",
-                                    project_config_path.display()
-                                ));
+                        DaemonHookCommands::Fire {
+                            event,
+                            project,
+                            details,
+                        } => {
+                            let event = event.parse()?;
+                            let details = details
+                                .iter()
+                                .map(|d| parse_detail(d))
+                                .collect::<anyhow::Result<Vec<_>>>()?;
+
+                            let runs = registry.fire(event, project.as_deref(), &details);
+                            let mut failed = 0;
+                            for run in &runs {
+                                match &run.result {
+                                    Ok(outcome) => {
+                                        if !outcome.stdout.is_empty() {
+                                            println!("{}", outcome.stdout);
+                                        }
+                                        if !outcome.stderr.is_empty() {
+                                            eprintln!("{}", outcome.stderr);
+                                        }
+                                        if outcome.timed_out {
+                                            progress::warning(&format!(
+                                                "Hook '{}' timed out",
+                                                run.name
+                                            ));
+                                        } else if !outcome.success {
+                                            progress::warning(&format!(
+                                                "Hook '{}' failed",
+                                                run.name
+                                            ));
+                                        }
+                                    }
+                                    Err(e) => {
+                                        progress::warning(&format!("Hook '{}': {:#}", run.name, e))
+                                    }
+                                }
+                                if !run.succeeded() {
+                                    failed += 1;
+                                }
                             }
-                            Err(e) => {
-                                progress::error(&format!("Failed to save config: {}", e));
+
+                            if failed > 0 {
+                                anyhow::bail!(
+                                    "{} of {} daemon hook(s) failed",
+                                    failed,
+                                    runs.len()
+                                );
                             }
+                            vlog!("Ran {} daemon hook(s) for {}", runs.len(), event);
+                            Ok(())
                         }
                     }
                 }
             }
-
-            Ok(())
         }
 
-        Commands::Bounce(bounce_cmd) => {
-            // Find repository root
-            let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+        Commands::Osc(osc_cmd) => {
+            use auxin::osc_trigger::{OscArg, OscBindings, OscListener, OscMessage};
 
-            let manager = BounceManager::new(&current_dir);
+            let osc = Config::load().unwrap_or_default().integrations.osc;
 
-            match bounce_cmd {
-                BounceCommands::Add {
-                    file,
-                    commit,
-                    description,
-                } => {
-                    // Get commit ID - use latest if not specified
-                    let commit_id = match commit {
-                        Some(id) => id,
-                        None => {
-                            // Get latest commit from oxen log
-                            let subprocess = auxin::OxenSubprocess::new();
-                            let commits = subprocess
-                                .log(&current_dir, Some(1))
-                                .context("Failed to get latest commit")?;
-                            if commits.is_empty() {
-                                anyhow::bail!("No commits found. Create a commit first.");
-                            }
-                            commits[0].id.clone()
-                        }
+            match osc_cmd {
+                OscCommands::Listen { bind, project } => {
+                    if !osc.enabled {
+                        progress::info("OSC integration is disabled");
+                        progress::info("Enable it with [integrations.osc] enabled = true");
+                        return Ok(());
+                    }
+
+                    let bindings = OscBindings::from_config(&osc.addresses)?;
+                    let projects = if project.is_empty() {
+                        vec![std::env::current_dir()?]
+                    } else {
+                        project
                     };
+                    let bind = bind.unwrap_or(osc.bind);
+                    let listener = OscListener::bind(&bind, bindings.clone(), projects.clone())?;
 
-                    let pb = progress::spinner(&format!(
-                        "Adding bounce for commit {}...",
-                        &commit_id[..8.min(commit_id.len())]
-                    ));
+                    success!("Listening for OSC on {}", listener.local_addr()?);
+                    for (address, action) in bindings.iter() {
+                        println!("  {} {} {}", address.cyan(), "→".dimmed(), action);
+                    }
+                    for project in &projects {
+                        println!("  {} {}", "Project:".dimmed(), project.display());
+                    }
+                    println!();
 
-                    match manager.add_bounce(&commit_id, &file, description.as_deref()) {
-                        Ok(metadata) => {
-                            progress::finish_success(&pb, "Bounce added");
-                            println!();
-                            println!(
-                                "  Commit:    {}",
-                                &metadata.commit_id[..8.min(metadata.commit_id.len())]
-                            );
-                            println!("  File:      {}", metadata.original_filename);
-                            println!("  Format:    {:?}", metadata.format);
-                            println!("  Size:      {}", metadata.format_size());
-                            println!("  Duration:  {}", metadata.format_duration());
-                            if let Some(desc) = &metadata.description {
-                                println!("  Note:      {}", desc);
+                    listener.run(|result| match result {
+                        Ok(summary) => success!("{}", summary),
+                        Err(e) => warn!("{:#}", e),
+                    })
+                }
+
+                OscCommands::Send { address, args, to } => {
+                    let args = args
+                        .into_iter()
+                        .map(|arg| {
+                            if let Ok(v) = arg.parse() {
+                                OscArg::Int(v)
+                            } else if let Ok(v) = arg.parse() {
+                                OscArg::Float(v)
+                            } else {
+                                OscArg::String(arg)
                             }
-                        }
-                        Err(e) => {
-                            progress::finish_error(&pb, "Failed to add bounce");
-                            anyhow::bail!("{}", e);
-                        }
-                    }
+                        })
+                        .collect();
+                    let target = to.unwrap_or(osc.bind);
+
+                    let socket = std::net::UdpSocket::bind("0.0.0.0:0")?;
+                    socket
+                        .send_to(&OscMessage::new(address.as_str(), args).encode(), &target)
+                        .with_context(|| format!("Failed to send OSC message to {}", target))?;
+                    success!("Sent {} to {}", address, target);
+                    Ok(())
                 }
+            }
+        }
 
-                BounceCommands::List => {
-                    let bounces = manager.list_bounces().context("Failed to list bounces")?;
+        Commands::QuickCommit { path, notify } => {
+            use auxin::quick_actions;
 
-                    if bounces.is_empty() {
-                        println!("No bounces found.");
-                        println!();
-                        println!("Add a bounce with: auxin bounce add <file>");
-                    } else {
-                        println!();
-                        println!("┌─ Audio Bounces ─────────────────────────────────────────┐");
-                        println!("│                                                          │");
+            let project = path.map_or_else(std::env::current_dir, Ok)?;
+            let result = std::env::current_exe()
+                .map_err(anyhow::Error::from)
+                .and_then(|exe| quick_actions::quick_commit(&project, &exe));
+            quick_report(notify, "Quick commit", result.map(|commit| commit.summary()))
+        }
 
-                        for bounce in &bounces {
-                            let commit_short = &bounce.commit_id[..8.min(bounce.commit_id.len())];
-                            let duration = bounce.format_duration();
-                            let size = bounce.format_size();
+        Commands::QuickLock { path, notify } => {
+            use auxin::quick_actions::{self, LockState};
 
-                            println!(
-                                "│  {} {} │",
-                                commit_short.yellow(),
-                                " ".repeat(48 - commit_short.len())
-                            );
-                            println!(
-                                "│    File:     {:<41} │",
-                                if bounce.original_filename.len() > 41 {
-                                    format!("{}...", &bounce.original_filename[..38])
-                                } else {
-                                    bounce.original_filename.clone()
-                                }
-                            );
-                            println!("│    Format:   {:<41} │", format!("{:?}", bounce.format));
-                            println!("│    Duration: {:<41} │", duration);
-                            println!("│    Size:     {:<41} │", size);
+            let project = path.map_or_else(std::env::current_dir, Ok)?;
+            let result = std::env::current_exe()
+                .map_err(anyhow::Error::from)
+                .and_then(|exe| quick_actions::toggle_lock(&project, &exe))
+                .map(|state| match state {
+                    LockState::Mine => "Lock acquired".to_string(),
+                    _ => "Lock released".to_string(),
+                });
+            quick_report(notify, "Lock", result)
+        }
 
-                            if let Some(desc) = &bounce.description {
-                                let desc_display = if desc.len() > 41 {
-                                    format!("{}...", &desc[..38])
-                                } else {
-                                    desc.clone()
-                                };
-                                println!("│    Note:     {:<41} │", desc_display);
-                            }
-                            println!(
-                                "│                                                          │"
-                            );
-                        }
+        Commands::QuickStatus { path, notify } => {
+            use auxin::quick_actions;
 
-                        println!("└──────────────────────────────────────────────────────────┘");
-                        println!();
-                        println!("{} bounce(s) total", bounces.len());
-                    }
+            let project = path.map_or_else(std::env::current_dir, Ok)?;
+            let result = quick_actions::status_line(&project);
+            if notify {
+                quick_report(true, "Auxin status", result)
+            } else {
+                println!("{}", result?);
+                Ok(())
+            }
+        }
+
+        Commands::HandleUrl { url, notify, yes } => {
+            use auxin::url_scheme::UrlRequest;
+            use dialoguer::Confirm;
+
+            let request: UrlRequest = match url.parse() {
+                Ok(request) => request,
+                Err(e) => return quick_report(notify, "Auxin", Err(e)),
+            };
+            if let Some(prompt) = request.confirmation_prompt().filter(|_| !yes) {
+                let interactive = atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout);
+                if !interactive {
+                    let refused = anyhow::anyhow!(
+                        "{} changes the project; confirm it in Auxin.app or pass --yes",
+                        url
+                    );
+                    return quick_report(notify, request.title(), Err(refused));
+                }
+                if !Confirm::new().with_prompt(prompt).default(false).interact()? {
+                    progress::info("Cancelled");
+                    return Ok(());
+                }
+            }
+            let result = std::env::current_exe()
+                .map_err(anyhow::Error::from)
+                .and_then(|exe| request.run(&exe));
+            match result {
+                Ok(summary) if !notify => {
+                    println!("{}", summary);
+                    Ok(())
+                }
+                result => quick_report(notify, request.title(), result),
+            }
+        }
+
+        Commands::Badges { path, print } => {
+            use auxin::badge_state::{BadgeSnapshot, LockSnapshot};
+            use auxin::quick_actions;
+
+            let project = path.map_or_else(std::env::current_dir, Ok)?;
+            let project = project.canonicalize().unwrap_or(project);
+            let status = OxenSubprocess::new().status(&project)?;
+            let lock = LockSnapshot::from(&quick_actions::lock_state(&project));
+            let snapshot = BadgeSnapshot::new(&project, &status, lock);
+            snapshot.write()?;
+
+            if print {
+                println!("{}", serde_json::to_string_pretty(&snapshot)?);
+            }
+            Ok(())
+        }
+
+        Commands::Prompt {
+            path,
+            ascii,
+            no_refresh,
+            starship,
+        } => {
+            use auxin::prompt::{self, PromptStatus};
+
+            if starship {
+                print!("{}", prompt::starship_module());
+                return Ok(());
+            }
+
+            let dir = path.map_or_else(std::env::current_dir, Ok)?;
+            let Some(project) = prompt::find_repo_root(&dir) else {
+                return Ok(());
+            };
+            let status = PromptStatus::read(&project);
+            println!("{}", status.render(ascii));
+
+            if status.stale && !no_refresh {
+                if let Err(e) = std::env::current_exe()
+                    .map_err(anyhow::Error::from)
+                    .and_then(|exe| prompt::refresh_in_background(&exe, &project))
+                {
+                    vlog!("Could not refresh the prompt status: {}", e);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::SessionSheet { commit, open } => {
+            use auxin::session_sheet::SessionSheet;
+
+            let project = std::env::current_dir()?;
+            let history = OxenSubprocess::new().log(&project, None)?;
+            let commit = match commit {
+                Some(id) => history.into_iter().find(|c| c.id.starts_with(&id)),
+                None => history.into_iter().next(),
+            }
+            .ok_or_else(|| anyhow::anyhow!("Commit not found"))?;
+
+            let path = SessionSheet::collect(&project, &commit, Vec::new()).write(&project)?;
+            success!("Session sheet written to {}", path.display());
+
+            if open {
+                let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+                std::process::Command::new(opener)
+                    .arg(&path)
+                    .status()
+                    .with_context(|| format!("Failed to run {}", opener))?;
+            }
+            Ok(())
+        }
+
+        Commands::Describe { path, limit } => {
+            use auxin::describe::{self, TrackingState};
+            use auxin::quick_actions::LockState;
+
+            let info = describe::describe(&path, limit)?;
+            println!("{}", info.relative.bold());
+            println!("  Project:  {}", info.repo.display());
+            if let Some(size) = info.size {
+                println!("  Size:     {}", describe::format_size(size));
+            }
+
+            let state = match &info.state {
+                TrackingState::Clean => "tracked, no changes".green().to_string(),
+                TrackingState::Modified => "tracked, modified".yellow().to_string(),
+                TrackingState::Staged => "staged for the next commit".green().to_string(),
+                TrackingState::Untracked => "not tracked (run 'auxin add')".yellow().to_string(),
+                TrackingState::Ignored { pattern, line } => format!(
+                    "{} by '{}' (.oxenignore line {})",
+                    "ignored".red(),
+                    pattern,
+                    line
+                ),
+                TrackingState::Missing => "not on disk".red().to_string(),
+            };
+            println!("  Status:   {}", state);
+
+            match info.last_change() {
+                Some(change) => println!(
+                    "  Changed:  {} {}",
+                    &change.commit.id[..8.min(change.commit.id.len())],
+                    change.commit.message.lines().next().unwrap_or_default()
+                ),
+                None if info.commits_checked > 0 => println!(
+                    "  Changed:  not in the last {} commits",
+                    info.commits_checked
+                ),
+                None => {}
+            }
+            if info.size_history.len() > 1 {
+                println!("  History:");
+                for change in &info.size_history {
+                    let size = change
+                        .size
+                        .map_or_else(|| "removed".to_string(), describe::format_size);
+                    let renamed = change
+                        .renamed_from
+                        .as_ref()
+                        .map(|from| format!(" (moved from {})", from))
+                        .unwrap_or_default();
+                    println!(
+                        "    {}  {:>10}  {}{}",
+                        &change.commit.id[..8.min(change.commit.id.len())],
+                        size,
+                        change.commit.message.lines().next().unwrap_or_default(),
+                        renamed
+                    );
                 }
+            }
 
-                BounceCommands::Play { commit_id } => {
-                    let pb = progress::spinner(&format!(
-                        "Playing bounce for {}...",
-                        &commit_id[..8.min(commit_id.len())]
-                    ));
+            let lock = match &info.lock {
+                LockState::Unlocked => "project unlocked".to_string(),
+                LockState::Mine => "project locked by you".to_string(),
+                LockState::Other(holder) => format!("project locked by {}", holder),
+            };
+            println!("  Lock:     {}", lock);
 
-                    match manager.play_bounce(&commit_id) {
-                        Ok(()) => {
-                            progress::finish_success(&pb, "Playback complete");
-                        }
-                        Err(e) => {
-                            progress::finish_error(&pb, "Playback failed");
-                            anyhow::bail!("{}", e);
-                        }
-                    }
+            if info.pending.is_empty() {
+                println!("  Queue:    nothing pending");
+            } else {
+                println!("  Queue:");
+                for operation in &info.pending {
+                    println!("    {}", operation);
                 }
+            }
+            Ok(())
+        }
 
-                BounceCommands::Info { commit_id } => {
-                    match manager.get_bounce(&commit_id)? {
-                        Some(metadata) => {
-                            println!();
-                            println!("┌─ Bounce Info ───────────────────────────────────────────┐");
-                            println!(
-                                "│                                                          │"
-                            );
-                            println!(
-                                "│  Commit:      {:<42} │",
-                                &metadata.commit_id[..8.min(metadata.commit_id.len())]
-                            );
-                            println!(
-                                "│  File:        {:<42} │",
-                                if metadata.original_filename.len() > 42 {
-                                    format!("{}...", &metadata.original_filename[..39])
-                                } else {
-                                    metadata.original_filename.clone()
-                                }
-                            );
-                            println!("│  Format:      {:<42} │", format!("{:?}", metadata.format));
-                            println!("│  Size:        {:<42} │", metadata.format_size());
-                            println!("│  Duration:    {:<42} │", metadata.format_duration());
+        Commands::Mv { from, to } => {
+            use auxin::describe::find_repo_root;
+            use auxin::rename_tracking;
 
-                            if let Some(sr) = metadata.sample_rate {
-                                println!("│  Sample Rate: {:<42} │", format!("{} Hz", sr));
-                            }
-                            if let Some(bd) = metadata.bit_depth {
-                                println!("│  Bit Depth:   {:<42} │", format!("{}-bit", bd));
-                            }
-                            if let Some(ch) = metadata.channels {
-                                let ch_str = if ch == 1 {
-                                    "Mono".to_string()
-                                } else if ch == 2 {
-                                    "Stereo".to_string()
-                                } else {
-                                    format!("{} channels", ch)
-                                };
-                                println!("│  Channels:    {:<42} │", ch_str);
-                            }
+            let current_dir = std::env::current_dir()?;
+            let repo = find_repo_root(&current_dir.join(&from))
+                .ok_or_else(|| anyhow::anyhow!("Not inside an auxin repository"))?;
+            let record = rename_tracking::move_path(&repo, &from, &to)?;
+            success!("Moved {} → {}", record.from, record.to);
+            progress::info("Next step: auxin commit -m \"Your message\"");
+            Ok(())
+        }
 
-                            println!(
-                                "│  Added:       {:<42} │",
-                                metadata.added_at.format("%Y-%m-%d %H:%M").to_string()
-                            );
-                            println!("│  By:          {:<42} │", metadata.added_by);
+        Commands::Anomaly(anomaly_cmd) => {
+            use auxin::anomaly::{self, Baseline, PauseState, Thresholds};
+            use auxin::backup_recovery::{BackupRecoveryManager, Snapshot, SnapshotType};
 
-                            if let Some(desc) = &metadata.description {
-                                println!(
-                                    "│                                                          │"
-                                );
-                                println!(
-                                    "│  Description:                                            │"
-                                );
-                                // Word wrap description
-                                for chunk in desc.as_bytes().chunks(54) {
-                                    let line = String::from_utf8_lossy(chunk);
-                                    println!("│    {:<54} │", line);
-                                }
-                            }
+            let print_pause = |pause: &PauseState| {
+                progress::warning(&format!(
+                    "Auto-commits paused since {}",
+                    pause.paused_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                ));
+                for anomaly in &pause.anomalies {
+                    println!("  • {}", anomaly);
+                }
+                if let Some(commit) = &pause.last_good_commit {
+                    println!();
+                    println!("  Last good commit: {}", commit.bright_cyan());
+                    let short = &commit[..commit.len().min(7)];
+                    println!("  Restore it with:  auxin restore {}", short);
+                    println!("  Then resume with: auxin anomaly resume");
+                } else {
+                    println!();
+                    println!("  Resume with: auxin anomaly resume");
+                }
+            };
 
-                            println!(
-                                "│                                                          │"
-                            );
-                            println!(
-                                "└──────────────────────────────────────────────────────────┘"
-                            );
-                            println!();
-                        }
-                        None => {
-                            anyhow::bail!("No bounce found for commit {}", commit_id);
-                        }
+            match anomaly_cmd {
+                AnomalyCommands::Check { path } => {
+                    let project = path.map_or_else(std::env::current_dir, Ok)?;
+                    let daemon = Config::load().unwrap_or_default().daemon;
+                    if !daemon.anomaly_detection {
+                        vlog!("Anomaly detection is disabled in config");
+                        return Ok(());
                     }
-                }
 
-                BounceCommands::Delete { commit_id } => {
-                    let pb = progress::spinner(&format!(
-                        "Deleting bounce for {}...",
-                        &commit_id[..8.min(commit_id.len())]
-                    ));
+                    if let Some(pause) = PauseState::load(&project)? {
+                        print_pause(&pause);
+                        std::process::exit(anomaly::PAUSED_EXIT_CODE);
+                    }
 
-                    match manager.delete_bounce(&commit_id) {
-                        Ok(()) => {
-                            progress::finish_success(&pb, "Bounce deleted");
-                        }
+                    let anomalies = anomaly::check(&project, &Thresholds::from_config(&daemon))?;
+                    if anomalies.is_empty() {
+                        vlog!("No anomalies in {}", project.display());
+                        return Ok(());
+                    }
+
+                    let last_good_commit =
+                        Baseline::load(&project)?.and_then(|baseline| baseline.commit_id);
+                    let reasons: Vec<String> = anomalies.iter().map(|a| a.to_string()).collect();
+                    let mut snapshot = Snapshot::new(SnapshotType::AnomalyDetected, &project)
+                        .with_description(format!("Auto-commit paused: {}", reasons.join("; ")));
+                    if let Some(commit) = &last_good_commit {
+                        snapshot = snapshot.with_commit_id(commit);
+                    }
+                    let snapshot_id = match BackupRecoveryManager::new().create_snapshot(snapshot)
+                    {
+                        Ok(snapshot) => Some(snapshot.id),
                         Err(e) => {
-                            progress::finish_error(&pb, "Delete failed");
-                            anyhow::bail!("{}", e);
+                            warn!("Could not record safety snapshot: {}", e);
+                            None
                         }
+                    };
+
+                    let pause = PauseState {
+                        paused_at: chrono::Utc::now(),
+                        anomalies,
+                        last_good_commit,
+                        snapshot_id,
+                    };
+                    pause.save(&project)?;
+
+                    let name = project
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    auxin::quick_actions::notify(
+                        "Auxin auto-commits paused",
+                        &format!("{}: {}", name, reasons.join("; ")),
+                    );
+                    print_pause(&pause);
+                    std::process::exit(anomaly::PAUSED_EXIT_CODE);
+                }
+
+                AnomalyCommands::Status { path } => {
+                    let project = path.map_or_else(std::env::current_dir, Ok)?;
+                    match PauseState::load(&project)? {
+                        Some(pause) => print_pause(&pause),
+                        None => progress::success("Auto-commits are running"),
                     }
+                    Ok(())
                 }
 
-                BounceCommands::Search {
-                    format,
-                    pattern,
-                    min_duration,
-                    max_duration,
-                    min_size,
-                    max_size,
-                    after,
-                    before,
-                    user,
-                } => {
-                    use auxin::BounceFilter;
-                    use chrono::NaiveDate;
+                AnomalyCommands::Resume { path } => {
+                    let project = path.map_or_else(std::env::current_dir, Ok)?;
+                    let was_paused = PauseState::clear(&project)?;
+                    let head = OxenSubprocess::new()
+                        .log(&project, Some(1))
+                        .ok()
+                        .and_then(|commits| commits.into_iter().next())
+                        .map(|commit| commit.id);
+                    Baseline::record(&project, head.as_deref())?;
+                    if was_paused {
+                        progress::success("Auto-commits resumed");
+                    } else {
+                        progress::info("Auto-commits weren't paused");
+                    }
+                    Ok(())
+                }
+            }
+        }
 
-                    let pb = progress::spinner("Searching bounces...");
+        Commands::Policy(PolicyCommands::Show { path }) => {
+            use auxin::describe::IgnoreRules;
+            use auxin::stage_policy::StagePolicy;
 
-                    // Build filter
-                    let mut filter = BounceFilter::default();
+            let project = path.map_or_else(std::env::current_dir, Ok)?;
+            let root = auxin::describe::find_repo_root(&project).unwrap_or(project);
+            let policy = StagePolicy::for_project(&root, &Config::load().unwrap_or_default());
 
-                    if let Some(fmt) = format {
-                        filter.format = auxin::AudioFormat::from_extension(&fmt);
-                        if filter.format.is_none() {
-                            progress::finish_error(&pb, "Invalid format");
-                            anyhow::bail!("Unknown audio format: {}", fmt);
-                        }
+            println!("Project type: {}", policy.project_type.bold());
+            println!();
+            if policy.is_empty() {
+                println!("No staging policy: 'add --all' stages every change .oxenignore allows.");
+                println!(
+                    "{}",
+                    format!(
+                        "Add [staging.{}] or [staging.default] to the config file to set one.",
+                        policy.project_type
+                    )
+                    .dimmed()
+                );
+            } else {
+                let show = |label: &str, rules: &[auxin::stage_policy::PolicyRule], what: &str| {
+                    if rules.is_empty() {
+                        return;
+                    }
+                    println!("{} ({}):", label.bold(), what);
+                    for rule in rules {
+                        println!(
+                            "  {}  {}",
+                            rule.pattern,
+                            format!("[{}]", rule.section).dimmed()
+                        );
                     }
+                    println!();
+                };
+                show("Always stage", &policy.always, "even if .oxenignore excludes it");
+                show("Never stage", &policy.never, "add by path to include");
+            }
 
-                    filter.filename_pattern = pattern;
-                    filter.min_duration = min_duration;
-                    filter.max_duration = max_duration;
-                    filter.min_size = min_size;
-                    filter.max_size = max_size;
-                    filter.added_by = user;
+            let ignore = IgnoreRules::load(&root)?;
+            println!(
+                "{}",
+                format!(
+                    "Also applied: {} .oxenignore rule(s). See the effect with 'auxin add --all \
+                     --dry-run'.",
+                    ignore.len()
+                )
+                .dimmed()
+            );
+            Ok(())
+        }
 
-                    // Parse date filters
-                    if let Some(after_str) = after {
-                        match NaiveDate::parse_from_str(&after_str, "%Y-%m-%d") {
-                            Ok(date) => {
-                                filter.after = Some(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
-                            }
-                            Err(_) => {
-                                progress::finish_error(&pb, "Invalid date");
-                                anyhow::bail!("Invalid date format: {}. Use YYYY-MM-DD", after_str);
-                            }
-                        }
+        Commands::Export(ExportCommands::History {
+            output,
+            branch,
+            redact,
+        }) => {
+            let redactions =
+                auxin::history_export::Redactions::parse(redact.as_deref().unwrap_or(""))?;
+            let cwd = std::env::current_dir()?;
+            let repo = auxin::describe::find_repo_root(&cwd).unwrap_or(cwd);
+
+            let pb = progress::progress_bar(0, &format!("Exporting {}...", branch));
+            let result = auxin::history_export::export_history(
+                &repo,
+                &branch,
+                &output,
+                redactions,
+                &OxenSubprocess::new(),
+                |done, total| {
+                    pb.set_length(total as u64);
+                    pb.set_position(done as u64);
+                },
+            );
+            let report = match result {
+                Ok(report) => {
+                    progress::finish_success(
+                        &pb,
+                        &format!("Exported {} commit(s) to {}", report.commits, output.display()),
+                    );
+                    report
+                },
+                Err(e) => {
+                    progress::finish_error(&pb, "Export failed");
+                    return Err(e);
+                },
+            };
+
+            if report.folded > 0 {
+                progress::info(&format!(
+                    "{} commit(s) with no file changes folded into the one before",
+                    report.folded
+                ));
+            }
+            if redactions.comments {
+                progress::info("Comments left out");
+            } else {
+                progress::info(&format!("{} comment(s) carried over", report.comments));
+            }
+            progress::info("Pen-names scrubbed from messages and comments");
+            Ok(())
+        },
+
+        Commands::Fork {
+            source,
+            destination,
+        } => {
+            let oxen = OxenSubprocess::new();
+            if oxen
+                .status(&source)
+                .is_ok_and(|s| !(s.modified.is_empty() && s.staged.is_empty()))
+            {
+                warn!(
+                    "{} has uncommitted changes; they'll be copied as they are",
+                    source.display()
+                );
+            }
+
+            let pb = progress::spinner(&format!("Forking {}...", source.display()));
+            let (provenance, stats) = match auxin::fork::fork(&source, &destination, &oxen) {
+                Ok(result) => result,
+                Err(e) => {
+                    progress::finish_error(&pb, "Fork failed");
+                    return Err(e);
+                },
+            };
+            progress::finish_success(&pb, &format!("Forked to {}", destination.display()));
+
+            let short = &provenance.source_commit[..provenance.source_commit.len().min(8)];
+            progress::info(&format!(
+                "From commit {}{}",
+                short,
+                provenance
+                    .source_branch
+                    .as_ref()
+                    .map(|b| format!(" on {}", b))
+                    .unwrap_or_default()
+            ));
+            progress::info(&format!(
+                "Shared {} of history ({} file(s)), copied {}",
+                auxin::describe::format_size(stats.linked_bytes),
+                stats.linked_files,
+                auxin::describe::format_size(stats.copied_bytes)
+            ));
+            if provenance.source_remote.is_some() {
+                progress::info("Remotes weren't carried over; add one with 'auxin remote add'");
+            }
+            Ok(())
+        },
+
+        Commands::Meta(MetaCommands::Sync { path }) => {
+            let project = match path {
+                Some(path) => path,
+                None => std::env::current_dir()?,
+            };
+            let config = Config::load().unwrap_or_default().meta_branch;
+            let pb = progress::spinner(&format!("Syncing with {}...", config.branch));
+            let report = auxin::meta_branch::sync(&project, &config, &OxenSubprocess::new())?;
+            pb.finish_and_clear();
+
+            for path in &report.updated_locally {
+                progress::info(&format!("Merged teammates' changes into {}", path));
+            }
+            if report.committed {
+                progress::success(&format!("Committed metadata to {}", config.branch));
+            } else {
+                progress::info("No metadata changes to commit");
+            }
+            match (&report.remote_error, report.pushed) {
+                (_, true) => {
+                    progress::success(&format!("Pushed {}/{}", config.remote, config.branch))
+                },
+                (Some(e), false) => warn!("Could not push to {}: {}", config.remote, e),
+                (None, false) => progress::info(&format!(
+                    "No remote named {}; the branch is only local",
+                    config.remote
+                )),
+            }
+            Ok(())
+        },
+
+        Commands::Quarantine(quarantine_cmd) => {
+            use auxin::quarantine::{Quarantine, ReviewDecision};
+            use std::io::Write;
+
+            let repo = std::env::current_dir()?;
+            let mut quarantine = Quarantine::load(&repo)?;
+
+            let decide_all = |quarantine: &mut Quarantine,
+                              paths: Vec<String>,
+                              decision: ReviewDecision|
+             -> anyhow::Result<()> {
+                for path in paths {
+                    quarantine.decide(&repo, &path, decision)?;
+                    match decision {
+                        ReviewDecision::Approve => progress::success(&format!("Approved {}", path)),
+                        _ => progress::success(&format!("Deleted {}", path)),
+                    }
+                }
+                quarantine.save(&repo)
+            };
+
+            match quarantine_cmd {
+                QuarantineCommands::List => {
+                    if quarantine.files.is_empty() {
+                        progress::info("Nothing is quarantined");
+                        return Ok(());
+                    }
+                    println!("{} quarantined file(s):", quarantine.files.len());
+                    for file in &quarantine.files {
+                        println!(
+                            "  {} {} {}",
+                            file.path.bold(),
+                            format!("({})", file.reason).dimmed(),
+                            file.quarantined_at
+                                .with_timezone(&chrono::Local)
+                                .format("%Y-%m-%d %H:%M")
+                                .to_string()
+                                .dimmed()
+                        );
+                    }
+                    Ok(())
+                }
+
+                QuarantineCommands::Review => {
+                    if quarantine.files.is_empty() {
+                        progress::info("Nothing is quarantined");
+                        return Ok(());
+                    }
+                    if !atty::is(atty::Stream::Stdin) {
+                        anyhow::bail!(
+                            "Review needs a terminal; use 'auxin quarantine approve' or 'reject'"
+                        );
                     }
 
-                    if let Some(before_str) = before {
-                        match NaiveDate::parse_from_str(&before_str, "%Y-%m-%d") {
-                            Ok(date) => {
-                                filter.before =
-                                    Some(date.and_hms_opt(23, 59, 59).unwrap().and_utc());
+                    println!("  {}", "[a] approve  [r] reject (delete)  [s] skip".dimmed());
+                    let term = console::Term::stdout();
+                    let files = quarantine.files.clone();
+                    for file in files {
+                        print!(
+                            "  {} {} ",
+                            file.path.bold(),
+                            format!("({})", file.reason).dimmed()
+                        );
+                        std::io::stdout().flush()?;
+                        let decision = loop {
+                            match term.read_char()? {
+                                'a' => break ReviewDecision::Approve,
+                                'r' => break ReviewDecision::Reject,
+                                's' => break ReviewDecision::Skip,
+                                _ => {}
                             }
-                            Err(_) => {
-                                progress::finish_error(&pb, "Invalid date");
-                                anyhow::bail!(
-                                    "Invalid date format: {}. Use YYYY-MM-DD",
-                                    before_str
-                                );
+                        };
+                        println!(
+                            "→ {}",
+                            match decision {
+                                ReviewDecision::Approve => "approved",
+                                ReviewDecision::Reject => "deleted",
+                                ReviewDecision::Skip => "skipped",
                             }
-                        }
+                        );
+                        quarantine.decide(&repo, &file.path, decision)?;
+                        quarantine.save(&repo)?;
                     }
 
-                    match manager.search_bounces(&filter) {
-                        Ok(bounces) => {
-                            progress::finish_success(
-                                &pb,
-                                &format!("Found {} bounces", bounces.len()),
-                            );
-
-                            if bounces.is_empty() {
-                                println!("No bounces match the filter criteria");
-                            } else {
-                                for bounce in bounces {
-                                    println!("\n{}", "─".repeat(50).dimmed());
-                                    println!(
-                                        "{}: {}",
-                                        "Commit".cyan(),
-                                        &bounce.commit_id[..8.min(bounce.commit_id.len())]
-                                    );
-                                    println!("{}: {}", "File".cyan(), bounce.original_filename);
-                                    println!(
-                                        "{}: {:?} | {} | {}",
-                                        "Info".cyan(),
-                                        bounce.format,
-                                        bounce.format_duration(),
-                                        bounce.format_size()
-                                    );
-                                    println!(
-                                        "{}: {}",
-                                        "Added".cyan(),
-                                        bounce.added_at.format("%Y-%m-%d %H:%M")
-                                    );
-                                    if let Some(desc) = &bounce.description {
-                                        println!("{}: {}", "Description".cyan(), desc);
-                                    }
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            progress::finish_error(&pb, "Search failed");
-                            anyhow::bail!("{}", e);
-                        }
+                    if !quarantine.files.is_empty() {
+                        progress::info(&format!(
+                            "{} file(s) still quarantined",
+                            quarantine.files.len()
+                        ));
                     }
+                    Ok(())
                 }
 
-                BounceCommands::Compare {
-                    commit_a,
-                    commit_b,
-                    null_test,
-                } => {
-                    let pb = progress::spinner(&format!(
-                        "Comparing {} and {}{}...",
-                        &commit_a[..8.min(commit_a.len())],
-                        &commit_b[..8.min(commit_b.len())],
-                        if null_test { " (with null test)" } else { "" }
-                    ));
-
-                    let result = if null_test {
-                        manager.compare_bounces_with_null_test(&commit_a, &commit_b)
+                QuarantineCommands::Approve { paths, all } => {
+                    let paths = if all {
+                        quarantine.files.iter().map(|f| f.path.clone()).collect()
                     } else {
-                        manager.compare_bounces(&commit_a, &commit_b)
+                        paths
                     };
+                    decide_all(&mut quarantine, paths, ReviewDecision::Approve)
+                }
 
-                    match result {
-                        Ok(comparison) => {
-                            progress::finish_success(&pb, "Comparison complete");
-                            println!("\n{}", comparison.format_report());
-                        }
-                        Err(e) => {
-                            progress::finish_error(&pb, "Comparison failed");
-                            anyhow::bail!("{}", e);
-                        }
+                QuarantineCommands::Reject { paths } => {
+                    decide_all(&mut quarantine, paths, ReviewDecision::Reject)
+                }
+            }
+        }
+
+        Commands::AutoTitle { path } => {
+            if !Config::load().unwrap_or_default().daemon.auto_titles {
+                vlog!("Auto-titles are disabled in config");
+                return Ok(());
+            }
+            let project = match path {
+                Some(path) => path,
+                None => std::env::current_dir()?,
+            };
+            let status = OxenSubprocess::new().status(&project)?;
+            if let Some(title) = auxin::auto_title::auto_title(&project, &status) {
+                println!("{}", title);
+            }
+            Ok(())
+        }
+
+        Commands::Spotlight(spotlight_cmd) => {
+            use auxin::spotlight::{self, SpotlightIndex};
+
+            let oxen = OxenSubprocess::new();
+
+            match spotlight_cmd {
+                SpotlightCommands::Sync { path } => {
+                    let project = path.map_or_else(std::env::current_dir, Ok)?;
+                    let pb = progress::spinner("Reading history...");
+                    let commits = oxen.log(&project, None)?;
+                    let index = SpotlightIndex::for_project(&project);
+                    let stats = index.sync(&commits)?;
+                    progress::finish_success(
+                        &pb,
+                        &format!(
+                            "{} sidecars written, {} removed",
+                            stats.written, stats.removed
+                        ),
+                    );
+                    println!("  {} {}", "Folder:".dimmed(), index.dir().display());
+
+                    if !Config::load().unwrap_or_default().integrations.spotlight.enabled {
+                        println!();
+                        progress::info("New commits are not indexed automatically");
+                        progress::info("Enable it with [integrations.spotlight] enabled = true");
                     }
+                    Ok(())
                 }
 
-                BounceCommands::BatchAdd { files, commit } => {
-                    let pb = progress::spinner(&format!("Adding {} bounce files...", files.len()));
+                SpotlightCommands::Gc => {
+                    let stats = spotlight::gc(&SpotlightIndex::default_root(), |project| {
+                        oxen.log(project, None)
+                    })?;
+                    success!("Removed {} stale sidecars", stats.removed);
+                    Ok(())
+                }
 
-                    // Get latest commit if no default commit specified
-                    let default_commit = if let Some(c) = commit {
-                        c
+                SpotlightCommands::Clear { path } => {
+                    let project = path.map_or_else(std::env::current_dir, Ok)?;
+                    let removed = SpotlightIndex::for_project(&project).clear()?;
+                    success!("Removed {} sidecars", removed);
+                    Ok(())
+                }
+            }
+        }
+
+        Commands::Hooks(hooks_cmd) => {
+            use auxin::hooks::{HookManager, HookType};
+
+            let repo_path = std::env::current_dir()?;
+            let manager = HookManager::new(&repo_path);
+
+            match hooks_cmd {
+                HooksCommands::Init => {
+                    let pb = progress::spinner("Initializing hooks directory...");
+                    manager.init()?;
+                    progress::finish_success(&pb, "Hooks directory initialized");
+
+                    println!();
+                    println!("┌─ Hooks Initialized ─────────────────────────────────────┐");
+                    println!("│                                                          │");
+                    println!("│  Created:                                                │");
+                    println!("│    .oxen/hooks/pre-commit/                               │");
+                    println!("│    .oxen/hooks/post-commit/                              │");
+                    println!("│    .oxen/hooks/pre-deliver/                              │");
+                    println!("│    .oxen/hooks/README.md                                 │");
+                    println!("│                                                          │");
+                    println!("└──────────────────────────────────────────────────────────┘");
+                    println!();
+                    progress::info("See .oxen/hooks/README.md for documentation");
+                    progress::info("Install built-in hooks with: auxin hooks install <name>");
+
+                    Ok(())
+                }
+
+                HooksCommands::List => {
+                    let hooks = manager.list_hooks()?;
+
+                    println!();
+                    println!("┌─ Installed Hooks ───────────────────────────────────────┐");
+                    println!("│                                                          │");
+
+                    if hooks.is_empty() {
+                        println!("│  No hooks installed                                      │");
                     } else {
-                        let subprocess = auxin::OxenSubprocess::new();
-                        match subprocess.log(&current_dir, Some(1)) {
-                            Ok(commits) if !commits.is_empty() => commits[0].id.clone(),
-                            _ => {
-                                progress::finish_error(&pb, "No commits found");
-                                anyhow::bail!("No commits found. Please specify --commit or create a commit first.");
+                        let pre_commit: Vec<_> = hooks
+                            .iter()
+                            .filter(|(t, _)| matches!(t, HookType::PreCommit))
+                            .collect();
+                        let post_commit: Vec<_> = hooks
+                            .iter()
+                            .filter(|(t, _)| matches!(t, HookType::PostCommit))
+                            .collect();
+                        let pre_deliver: Vec<_> = hooks
+                            .iter()
+                            .filter(|(t, _)| matches!(t, HookType::PreDeliver))
+                            .collect();
+
+                        if !pre_commit.is_empty() {
+                            println!(
+                                "│  Pre-commit hooks:                                       │"
+                            );
+                            for (_, name) in pre_commit {
+                                println!("│    • {:<51} │", name);
                             }
+                            println!(
+                                "│                                                          │"
+                            );
                         }
-                    };
-
-                    let mut success_count = 0;
-                    let mut error_count = 0;
-
-                    for file in &files {
-                        // Try to extract commit ID from filename (pattern: commit_<id>_name.ext)
-                        let commit_id =
-                            if let Some(filename) = file.file_name().and_then(|n| n.to_str()) {
-                                if filename.starts_with("commit_") {
-                                    let parts: Vec<&str> = filename.splitn(3, '_').collect();
-                                    if parts.len() >= 2 {
-                                        parts[1].to_string()
-                                    } else {
-                                        default_commit.clone()
-                                    }
-                                } else {
-                                    default_commit.clone()
-                                }
-                            } else {
-                                default_commit.clone()
-                            };
 
-                        match manager.add_bounce(&commit_id, file, None) {
-                            Ok(_) => {
-                                success_count += 1;
+                        if !post_commit.is_empty() {
+                            println!(
+                                "│  Post-commit hooks:                                      │"
+                            );
+                            for (_, name) in post_commit {
+                                println!("│    • {:<51} │", name);
                             }
-                            Err(e) => {
-                                error_count += 1;
-                                eprintln!("{}: {}", file.display(), e);
+                            println!(
+                                "│                                                          │"
+                            );
+                        }
+
+                        if !pre_deliver.is_empty() {
+                            println!(
+                                "│  Pre-deliver hooks:                                      │"
+                            );
+                            for (_, name) in pre_deliver {
+                                println!("│    • {:<51} │", name);
                             }
                         }
                     }
 
-                    if error_count == 0 {
-                        progress::finish_success(&pb, &format!("Added {} bounces", success_count));
-                    } else {
-                        progress::finish_error(
-                            &pb,
-                            &format!("{} added, {} failed", success_count, error_count),
-                        );
-                    }
+                    println!("│                                                          │");
+                    println!("└──────────────────────────────────────────────────────────┘");
+                    println!();
+
+                    Ok(())
                 }
 
-                BounceCommands::BulkDelete {
-                    format,
-                    before,
-                    user,
-                    force,
-                } => {
-                    use auxin::BounceFilter;
-                    use chrono::NaiveDate;
+                HooksCommands::Builtins => {
+                    let builtins = HookManager::list_builtins();
 
-                    // Require at least one filter
-                    if format.is_none() && before.is_none() && user.is_none() {
-                        anyhow::bail!(
-                            "At least one filter (--format, --before, or --user) must be specified"
+                    println!();
+                    println!("┌─ Available Built-in Hooks ──────────────────────────────┐");
+                    println!("│                                                          │");
+
+                    for hook in builtins {
+                        let type_str = hook.hook_type.dir_name();
+                        println!(
+                            "│  {} ({})                                 ",
+                            hook.name.bright_yellow(),
+                            type_str.dimmed()
                         );
+                        println!("│    {:<55} │", hook.description);
+                        println!("│                                                          │");
                     }
 
-                    let pb = progress::spinner("Finding bounces to delete...");
-
-                    // Build filter
-                    let mut filter = BounceFilter::default();
+                    println!("└──────────────────────────────────────────────────────────┘");
+                    println!();
+                    progress::info("Install with: auxin hooks install <name>");
 
-                    if let Some(fmt) = &format {
-                        filter.format = auxin::AudioFormat::from_extension(fmt);
-                    }
+                    Ok(())
+                }
 
-                    if let Some(before_str) = &before {
-                        match NaiveDate::parse_from_str(before_str, "%Y-%m-%d") {
-                            Ok(date) => {
-                                filter.before =
-                                    Some(date.and_hms_opt(23, 59, 59).unwrap().and_utc());
-                            }
-                            Err(_) => {
-                                progress::finish_error(&pb, "Invalid date");
+                HooksCommands::Install { name, hook_type } => {
+                    // Parse hook type, defaulting to the built-in's own type
+                    let hook_type = match hook_type {
+                        Some(hook_type) => {
+                            let Some(parsed) = HookType::from_dir_name(&hook_type) else {
                                 anyhow::bail!(
-                                    "Invalid date format: {}. Use YYYY-MM-DD",
-                                    before_str
+                                    "Invalid hook type: {}. Use 'pre-commit', 'post-commit' or \
+                                     'pre-deliver'",
+                                    hook_type
                                 );
-                            }
+                            };
+                            parsed
                         }
-                    }
+                        None => HookManager::list_builtins()
+                            .into_iter()
+                            .find(|hook| hook.name == name)
+                            .map(|hook| hook.hook_type)
+                            .unwrap_or(HookType::PreCommit),
+                    };
 
-                    filter.added_by = user.clone();
+                    // Ensure hooks directory exists
+                    manager.init()?;
 
-                    // Find matching bounces
-                    let bounces = match manager.search_bounces(&filter) {
-                        Ok(b) => b,
-                        Err(e) => {
-                            progress::finish_error(&pb, "Search failed");
-                            anyhow::bail!("{}", e);
-                        }
-                    };
+                    let pb = progress::spinner(&format!("Installing {} hook...", name));
+                    manager.install_builtin(&name, hook_type)?;
+                    progress::finish_success(&pb, &format!("Installed {} hook", name));
 
-                    if bounces.is_empty() {
-                        progress::finish_success(&pb, "No matching bounces found");
-                        return Ok(());
-                    }
+                    println!();
+                    progress::success(&format!("Hook '{}' installed successfully", name));
+                    progress::info(&format!(
+                        "Edit at: .oxen/hooks/{}/{}",
+                        hook_type.dir_name(),
+                        name
+                    ));
 
-                    progress::finish_success(&pb, &format!("Found {} bounces", bounces.len()));
+                    Ok(())
+                }
 
-                    // Confirm deletion
-                    if !force {
-                        println!("\n{} bounces will be deleted:", bounces.len());
-                        for bounce in &bounces {
-                            println!(
-                                "  • {} ({})",
-                                bounce.original_filename,
-                                &bounce.commit_id[..8.min(bounce.commit_id.len())]
-                            );
-                        }
-                        println!("\nUse --force to delete without confirmation.");
-                        return Ok(());
-                    }
+                HooksCommands::Remove { name, hook_type } => {
+                    // Parse hook type
+                    let Some(hook_type) = HookType::from_dir_name(&hook_type) else {
+                        anyhow::bail!(
+                            "Invalid hook type: {}. Use 'pre-commit', 'post-commit' or \
+                             'pre-deliver'",
+                            hook_type
+                        );
+                    };
 
-                    // Delete bounces
-                    let pb = progress::spinner(&format!("Deleting {} bounces...", bounces.len()));
-                    let mut deleted = 0;
+                    let pb = progress::spinner(&format!("Removing {} hook...", name));
+                    manager.remove_hook(&name, hook_type)?;
+                    progress::finish_success(&pb, &format!("Removed {} hook", name));
 
-                    for bounce in &bounces {
-                        if let Err(e) = manager.delete_bounce(&bounce.commit_id) {
-                            eprintln!("Failed to delete {}: {}", bounce.commit_id, e);
-                        } else {
-                            deleted += 1;
-                        }
-                    }
+                    Ok(())
+                }
+            }
+        }
 
-                    progress::finish_success(&pb, &format!("Deleted {} bounces", deleted));
+        Commands::Console { path, once, json } => {
+            let json = json || cli.json;
+            use auxin::console::{Console, ConsoleSnapshot, DaemonStatus as ConsoleDaemonStatus};
+            use auxin::daemon_client::DaemonClient;
+            use std::io::IsTerminal;
+
+            // Determine project path
+            let project_path = match path {
+                Some(p) => p,
+                None => std::env::current_dir().context("Failed to get current directory")?,
+            };
+
+            // No terminal to draw on: print a snapshot instead
+            if once || !std::io::stdout().is_terminal() {
+                let snapshot = ConsoleSnapshot::gather(&project_path).await;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&snapshot)?);
+                } else {
+                    print!("{}", snapshot.to_text());
                 }
+                return Ok(());
             }
 
+            vlog!("Launching console for project: {}", project_path.display());
+
+            // Validate it's a Logic Pro project or Oxen repository
+            // TODO: Add validation once we have repository detection
+
+            // Create and run console
+            let mut console = Console::new(project_path);
+
+            // Check daemon status
+            let daemon_client = DaemonClient::new();
+            let status = daemon_client.status().unwrap_or_default();
+
+            // Set initial daemon status
+            let console_status = if status.is_running {
+                ConsoleDaemonStatus::Running
+            } else {
+                ConsoleDaemonStatus::Stopped
+            };
+            console.set_daemon_status(console_status);
+
+            // Run the console
+            console.run().await?;
+
+            success!("Console exited");
             Ok(())
         }
 
-        Commands::MetadataDiff {
-            project_a,
-            project_b,
-            output,
-            color,
-            verbose: verbose_flag,
-        } => {
-            use auxin::{LogicParser, MetadataDiffer};
+        Commands::Activity { limit } => {
+            use auxin::ActivityFeed;
+            use std::env;
 
-            vlog!("Parsing project A: {}", project_a.display());
+            let current_dir = env::current_dir()?;
+            let feed = ActivityFeed::new();
 
-            // Validate paths
-            if !LogicParser::is_valid_project(&project_a) {
-                anyhow::bail!("Invalid Logic Pro project: {}", project_a.display());
+            let pb = progress::spinner("Fetching project activity...");
+            let activities = feed.get_recent_activity(&current_dir, limit)?;
+            pb.finish_and_clear();
+
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&activities)?);
+                return Ok(());
             }
 
-            if !LogicParser::is_valid_project(&project_b) {
-                anyhow::bail!("Invalid Logic Pro project: {}", project_b.display());
+            if activities.is_empty() {
+                println!();
+                progress::info("No activity found");
+                println!("This project has no commit history yet.");
+                println!();
+                progress::info("Create your first commit:");
+                println!("  auxin add --all");
+                println!("  auxin commit -m \"Initial commit\" --bpm 120");
+                return Ok(());
             }
 
-            // Parse both projects
-            let data_a = LogicParser::parse(&project_a)
-                .map_err(|e| anyhow::anyhow!("Failed to parse project A: {}", e))?;
+            println!();
+            println!("┌─ Project Activity ──────────────────────────────────────┐");
+            println!("│                                                          │");
+            println!(
+                "│  Recent activity ({} commits)                            │",
+                activities.len()
+            );
+            println!("│                                                          │");
+            println!("└──────────────────────────────────────────────────────────┘");
+            println!();
 
-            vlog!("Parsing project B: {}", project_b.display());
-            let data_b = LogicParser::parse(&project_b)
-                .map_err(|e| anyhow::anyhow!("Failed to parse project B: {}", e))?;
+            for (i, activity) in activities.iter().enumerate() {
+                let icon = activity.activity_type.icon();
+                let author = if activity.author.len() > 20 {
+                    format!("{}...", &activity.author[..17])
+                } else {
+                    activity.author.clone()
+                };
 
-            // Generate diff
-            vlog!("Computing metadata diff");
-            let diff = MetadataDiffer::compare(&data_a, &data_b);
+                println!(
+                    "{} {} {} - {}",
+                    (i + 1).to_string().bright_black(),
+                    icon,
+                    author.cyan(),
+                    activity.message.white()
+                );
 
-            // Output result
-            match output.as_str() {
-                "json" => {
-                    let json = MetadataDiffer::to_json(&diff)?;
-                    println!("{}", json);
+                // Show metadata if present
+                if !activity.metadata.is_empty() {
+                    for (key, value) in &activity.metadata {
+                        println!(
+                            "    {} {}: {}",
+                            "│".bright_black(),
+                            key.bright_black(),
+                            value.bright_black()
+                        );
+                    }
                 }
-                _ => {
-                    // Determine color usage
-                    let use_color = if color {
-                        true
-                    } else {
-                        // Auto-detect TTY
-                        atty::is(atty::Stream::Stdout)
-                    };
+            }
+
+            println!();
+            progress::info(&format!(
+                "Showing {} most recent activities",
+                activities.len()
+            ));
+
+            Ok(())
+        }
+
+        Commands::Team => {
+            use auxin::TeamManager;
+            use std::env;
+
+            let current_dir = env::current_dir()?;
+            let team_mgr = TeamManager::new();
+
+            let pb = progress::spinner("Discovering team members...");
+            let members = team_mgr.discover_team_members(&current_dir)?;
+            pb.finish_and_clear();
+
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&members)?);
+                return Ok(());
+            }
+
+            if members.is_empty() {
+                println!();
+                progress::warning("No team members found");
+                println!("This project has no commit history with author information.");
+                return Ok(());
+            }
+
+            let total_commits: usize = members.iter().map(|m| m.commit_count).sum();
+
+            println!();
+            println!("┌─ Team Members ──────────────────────────────────────────┐");
+            println!("│                                                          │");
+            println!(
+                "│  {} members · {} total commits                           │",
+                members.len(),
+                total_commits
+            );
+            println!("│                                                          │");
+            println!("└──────────────────────────────────────────────────────────┘");
+            println!();
 
-                    let report = MetadataDiffer::generate_report_with_options(
-                        &diff,
-                        use_color,
-                        verbose_flag || cli.verbose,
-                    );
-                    println!("{}", report);
-                }
+            for (i, member) in members.iter().enumerate() {
+                let percentage =
+                    (member.commit_count as f64 / total_commits as f64 * 100.0) as usize;
+                let bar_length = (percentage / 5).min(20);
+                let bar = "█".repeat(bar_length);
+
+                println!(
+                    "{} {} {} commits ({}%)",
+                    (i + 1).to_string().bright_black(),
+                    member.name.cyan(),
+                    member.commit_count.to_string().green(),
+                    percentage
+                );
+                println!("   {} {}", bar.green(), " ".repeat(20 - bar_length));
             }
 
-            success!("Metadata diff completed");
+            println!();
+            progress::success(&format!("Found {} team members", members.len()));
+
             Ok(())
         }
 
-        Commands::Daemon(daemon_cmd) => {
-            use auxin::daemon_client::DaemonClient;
+        Commands::Queue(queue_cmd) => {
+            use auxin::OfflineQueue;
+            use colored::Colorize;
 
-            let client = DaemonClient::new();
+            let mut queue = OfflineQueue::new()?;
 
-            match daemon_cmd {
-                DaemonCommands::Status => {
-                    let pb = progress::spinner("Checking daemon status...");
-                    let status = client.status()?;
-                    pb.finish_and_clear();
+            match queue_cmd {
+                QueueCommands::Status if cli.json => {
+                    let report = auxin::reports::QueueStatusReport {
+                        stats: queue.stats(),
+                        pending: queue.pending().into_iter().cloned().collect(),
+                    };
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                    Ok(())
+                }
+
+                QueueCommands::Status => {
+                    let pending = queue.pending();
+                    let stats = queue.stats();
 
+                    println!("\n{}", "Offline Operation Queue".bold());
+                    println!("{}", "=".repeat(50));
                     println!();
-                    println!("┌─ Daemon Status ─────────────────────────────────────────┐");
 
-                    if status.is_running {
-                        println!("│  Status: {} Running", "●".green());
-                        if let Some(pid) = status.pid {
-                            println!("│  PID: {}", pid.to_string().bright_yellow());
+                    if pending.is_empty() {
+                        progress::success("No pending operations");
+                        if stats.dead_letter > 0 {
+                            warn!(
+                                "{} operation(s) gave up after repeated failures - see 'auxin \
+                                 queue deadletter list'",
+                                stats.dead_letter
+                            );
+                        } else {
+                            println!("\n  All operations have been synced!");
                         }
                     } else {
-                        println!("│  Status: {} Stopped", "●".red());
-                    }
+                        println!(
+                            "  {} {}",
+                            "Pending:".bold(),
+                            pending.len().to_string().yellow()
+                        );
+                        println!(
+                            "  {} {}",
+                            "Completed:".bold(),
+                            stats.completed.to_string().green()
+                        );
+                        println!("  {} {}", "Failed:".bold(), stats.failed.to_string().red());
+                        if stats.dead_letter > 0 {
+                            println!(
+                                "  {} {}",
+                                "Dead letter:".bold(),
+                                stats.dead_letter.to_string().red()
+                            );
+                        }
+                        println!();
 
-                    if let Some(count) = status.project_count {
-                        println!("│  Monitored Projects: {}", count);
-                    }
-                    if let Some(version) = status.version {
-                        println!("│  Version: {}", version);
-                    }
-                    if let Some(uptime) = status.uptime {
-                        println!("│  Uptime: {:.1} hours", uptime / 3600.0);
-                    }
+                        for (i, entry) in pending.iter().enumerate() {
+                            let age = chrono::Utc::now().signed_duration_since(entry.queued_at);
+                            let age_str = if age.num_hours() > 0 {
+                                format!("{}h ago", age.num_hours())
+                            } else if age.num_minutes() > 0 {
+                                format!("{}m ago", age.num_minutes())
+                            } else {
+                                format!("{}s ago", age.num_seconds())
+                            };
 
-                    println!("└──────────────────────────────────────────────────────────┘");
-                    println!();
+                            println!(
+                                "  {}. {} {}",
+                                (i + 1).to_string().cyan(),
+                                entry.operation.description(),
+                                format!("({})", age_str).dimmed()
+                            );
+                            println!(
+                                "     {} {} | {} {} | {} {} | {} {}",
+                                "ID:".dimmed(),
+                                &entry.id[..8].dimmed(),
+                                "Class:".dimmed(),
+                                entry.operation.qos_class().name().dimmed(),
+                                "Priority:".dimmed(),
+                                entry.priority.to_string().dimmed(),
+                                "Attempts:".dimmed(),
+                                entry.attempts.to_string().dimmed()
+                            );
+                            println!();
+                        }
 
-                    if !status.is_running {
-                        progress::info("Start the daemon with: auxin daemon start");
+                        println!(
+                            "  {}",
+                            "Use 'auxin queue sync' to sync pending operations".dimmed()
+                        );
                     }
 
                     Ok(())
                 }
 
-                DaemonCommands::Start => {
-                    // Check if already running
-                    let status = client.status()?;
-                    if status.is_running {
-                        progress::warning("Daemon is already running");
-                        if let Some(pid) = status.pid {
-                            println!("  PID: {}", pid);
+                QueueCommands::Sync => {
+                    use auxin::network_resilience::{check_connectivity, ConnectivityState};
+
+                    println!("\n{}", "Syncing Offline Queue".bold());
+                    println!("{}", "=".repeat(50));
+                    println!();
+
+                    // Check connectivity
+                    match check_connectivity() {
+                        ConnectivityState::Offline => {
+                            progress::error("Network is offline - cannot sync");
+                            println!(
+                                "\n  Operations will remain queued until network is available"
+                            );
+                            return Ok(());
+                        }
+                        ConnectivityState::Captive => {
+                            progress::error("Network requires sign-in - cannot sync");
+                            println!(
+                                "\n  Sign in to the Wi-Fi network in a browser, then sync again"
+                            );
+                            return Ok(());
+                        }
+                        ConnectivityState::Unknown => {
+                            warn!("Network state unknown, attempting sync anyway");
+                        }
+                        ConnectivityState::Online => {
+                            progress::success("Network is online");
                         }
-                        return Ok(());
                     }
 
-                    // Check if installed
-                    if !client.is_installed() {
-                        progress::error("Daemon is not installed");
-                        progress::info("Please run the installer: ./install.sh");
-                        anyhow::bail!("Daemon not installed");
+                    let pending_count = queue.pending().len();
+                    if pending_count == 0 {
+                        progress::success("No pending operations to sync");
+                        return Ok(());
                     }
 
-                    let pb = progress::spinner("Starting daemon...");
-                    client.start()?;
-                    progress::finish_success(&pb, "Daemon started");
+                    println!("  Syncing {} pending operation(s)...\n", pending_count);
 
-                    // Show updated status
-                    let status = client.status()?;
-                    if let Some(pid) = status.pid {
+                    let report = queue.sync_all()?;
+
+                    println!();
+                    println!("{}", "Sync Results".bold());
+                    println!("{}", "=".repeat(50));
+                    println!(
+                        "  {} {}",
+                        "Succeeded:".bold(),
+                        report.succeeded.len().to_string().green()
+                    );
+                    println!(
+                        "  {} {}",
+                        "Failed:".bold(),
+                        report.failed.len().to_string().red()
+                    );
+
+                    if !report.failed.is_empty() {
                         println!();
-                        progress::info(&format!("Daemon running with PID: {}", pid));
+                        println!("{}", "Failed Operations:".bold());
+                        for (id, error) in &report.failed {
+                            println!("  {} {} - {}", "✗".red(), &id[..8], error.dimmed());
+                        }
+                    }
+
+                    println!();
+
+                    if report.failed.is_empty() {
+                        progress::success("All operations synced successfully!");
+                    } else if report.dead_lettered.is_empty() {
+                        warn!("Some operations failed - they remain queued for retry");
+                    } else {
+                        warn!(
+                            "{} operation(s) failed too often and were set aside - see \
+                             'auxin queue deadletter list'",
+                            report.dead_lettered.len()
+                        );
                     }
 
                     Ok(())
                 }
 
-                DaemonCommands::Stop => {
-                    // Check if running
-                    let status = client.status()?;
-                    if !status.is_running {
-                        progress::warning("Daemon is not running");
-                        return Ok(());
+                QueueCommands::Clear { all } => {
+                    if all {
+                        // Clear everything
+                        let total = queue.pending().len() + queue.stats().completed;
+
+                        // Remove all entries
+                        let total = total + queue.stats().dead_letter;
+                        let entry_ids: Vec<String> = queue
+                            .pending()
+                            .iter()
+                            .chain(&queue.dead_letters())
+                            .map(|e| e.id.clone())
+                            .collect();
+
+                        for id in entry_ids {
+                            queue.remove(&id)?;
+                        }
+
+                        queue.clear_completed()?;
+
+                        progress::success(&format!("Cleared {} total operation(s)", total));
+                    } else {
+                        // Clear only completed
+                        let completed_count = queue.stats().completed;
+                        queue.clear_completed()?;
+                        progress::success(&format!(
+                            "Cleared {} completed operation(s)",
+                            completed_count
+                        ));
                     }
 
-                    let pb = progress::spinner("Stopping daemon...");
-                    client.stop()?;
-                    progress::finish_success(&pb, "Daemon stopped");
+                    Ok(())
+                }
 
+                QueueCommands::Remove { entry_id } => {
+                    queue.remove(&entry_id)?;
+                    progress::success(&format!("Removed operation {}", &entry_id[..8]));
                     Ok(())
                 }
 
-                DaemonCommands::Restart => {
-                    let pb = progress::spinner("Restarting daemon...");
-                    client.restart()?;
-                    progress::finish_success(&pb, "Daemon restarted");
+                QueueCommands::Edit { entry_id, changes } => {
+                    let id = queue.resolve_id(&entry_id)?;
+                    let changes = changes
+                        .iter()
+                        .map(|change| match change.split_once('=') {
+                            Some((field, value)) if !field.trim().is_empty() => {
+                                Ok((field.trim().to_string(), value.to_string()))
+                            }
+                            _ => Err(anyhow::anyhow!("Expected FIELD=VALUE, got '{}'", change)),
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
 
-                    // Show updated status
-                    let status = client.status()?;
-                    if let Some(pid) = status.pid {
-                        println!();
-                        progress::info(&format!("Daemon running with PID: {}", pid));
+                    let entry = if changes.is_empty() {
+                        queue.get(&id).ok_or_else(|| anyhow::anyhow!("No queue entry {}", id))?
+                    } else {
+                        queue.edit(&id, &changes)?
+                    };
+
+                    println!(
+                        "\n{} {}",
+                        entry.operation.description().bold(),
+                        format!("({})", &entry.id[..8]).dimmed()
+                    );
+                    for (field, value) in entry.operation.fields() {
+                        let value = match value {
+                            serde_json::Value::String(s) => s,
+                            other => other.to_string(),
+                        };
+                        println!("  {} {}", format!("{}:", field).dimmed(), value);
+                    }
+                    println!();
+                    if !changes.is_empty() {
+                        progress::success(&format!("Updated {} field(s)", changes.len()));
+                        if entry.dead_letter.is_some() {
+                            progress::info(&format!(
+                                "It's dead-lettered; run 'auxin queue requeue {}' to retry it",
+                                &entry.id[..8]
+                            ));
+                        }
                     }
+                    Ok(())
+                }
 
+                QueueCommands::Requeue { entry_id, priority } => {
+                    let id = queue.resolve_id(&entry_id)?;
+                    queue.requeue(&id, priority)?;
+                    progress::success(&format!("Requeued operation {}", &id[..8]));
+                    progress::info("It'll run with the next 'auxin queue sync'");
                     Ok(())
                 }
 
-                DaemonCommands::Logs { lines } => {
-                    let log_path = client.log_path()?;
+                QueueCommands::Deadletter(deadletter_cmd) => {
+                    let ids = |entry_ids: Vec<String>, all: bool| -> anyhow::Result<Vec<String>> {
+                        if all {
+                            return Ok(queue.dead_letters().iter().map(|e| e.id.clone()).collect());
+                        }
+                        entry_ids.iter().map(|id| queue.resolve_id(id)).collect()
+                    };
 
-                    println!();
-                    println!("┌─ Daemon Logs ───────────────────────────────────────────┐");
-                    println!("│  Path: {}", log_path);
-                    println!("│  Lines: {}", lines);
-                    println!("└──────────────────────────────────────────────────────────┘");
-                    println!();
+                    match deadletter_cmd {
+                        DeadLetterCommands::List => {
+                            let dead = queue.dead_letters();
+                            if dead.is_empty() {
+                                progress::success("No dead-lettered operations");
+                                return Ok(());
+                            }
 
-                    let log_lines = client.tail_logs(lines)?;
+                            println!("\n{}", "Dead-Lettered Operations".bold());
+                            println!("{}", "=".repeat(50));
+                            println!();
+                            for entry in dead {
+                                let Some(dead_letter) = &entry.dead_letter else {
+                                    continue;
+                                };
+                                println!(
+                                    "  {} {}",
+                                    "✗".red(),
+                                    entry.operation.description().bold()
+                                );
+                                println!(
+                                    "     {} {} | {} {} | {} {}",
+                                    "ID:".dimmed(),
+                                    &entry.id[..8].dimmed(),
+                                    "Attempts:".dimmed(),
+                                    entry.attempts.to_string().dimmed(),
+                                    "Gave up:".dimmed(),
+                                    dead_letter
+                                        .at
+                                        .with_timezone(&chrono::Local)
+                                        .format("%Y-%m-%d %H:%M")
+                                        .to_string()
+                                        .dimmed()
+                                );
+                                for (i, cause) in dead_letter.error_chain.iter().enumerate() {
+                                    if i == 0 {
+                                        println!("     {} {}", "Error:".red(), cause);
+                                    } else {
+                                        println!("     {} {}", "Caused by:".dimmed(), cause);
+                                    }
+                                }
+                                println!();
+                            }
+                            println!(
+                                "  {}",
+                                "Use 'auxin queue deadletter retry' or 'discard' to resolve them"
+                                    .dimmed()
+                            );
+                            Ok(())
+                        }
 
-                    if log_lines.is_empty() {
-                        progress::info("No log entries found");
-                    } else {
-                        for line in log_lines {
-                            println!("{}", line);
+                        DeadLetterCommands::Retry { entry_ids, all } => {
+                            let ids = ids(entry_ids, all)?;
+                            for id in &ids {
+                                queue.retry_dead_letter(id)?;
+                            }
+                            progress::success(&format!(
+                                "Requeued {} operation(s)",
+                                ids.len()
+                            ));
+                            progress::info("They'll run with the next 'auxin queue sync'");
+                            Ok(())
                         }
-                    }
 
-                    Ok(())
+                        DeadLetterCommands::Discard { entry_ids, all } => {
+                            let ids = ids(entry_ids, all)?;
+                            for id in &ids {
+                                queue.discard_dead_letter(id)?;
+                            }
+                            progress::success(&format!(
+                                "Discarded {} operation(s)",
+                                ids.len()
+                            ));
+                            Ok(())
+                        }
+                    }
                 }
             }
         }
 
-        Commands::Hooks(hooks_cmd) => {
-            use auxin::hooks::{HookManager, HookType};
+        Commands::Completions { shell } => {
+            use clap::CommandFactory;
+            use clap_complete::{generate, Shell};
+            use std::io;
 
-            let repo_path = std::env::current_dir()?;
-            let manager = HookManager::new(&repo_path);
+            let shell_type = match shell.to_lowercase().as_str() {
+                "bash" => Shell::Bash,
+                "zsh" => Shell::Zsh,
+                "fish" => Shell::Fish,
+                "powershell" => Shell::PowerShell,
+                _ => {
+                    progress::error(&format!("Unsupported shell: {}", shell));
+                    println!("\nSupported shells: bash, zsh, fish, powershell");
+                    std::process::exit(1);
+                }
+            };
 
-            match hooks_cmd {
-                HooksCommands::Init => {
-                    let pb = progress::spinner("Initializing hooks directory...");
-                    manager.init()?;
-                    progress::finish_success(&pb, "Hooks directory initialized");
+            let mut cmd = Cli::command();
+            let bin_name = "auxin";
 
-                    println!();
-                    println!("┌─ Hooks Initialized ─────────────────────────────────────┐");
-                    println!("│                                                          │");
-                    println!("│  Created:                                                │");
-                    println!("│    .oxen/hooks/pre-commit/                               │");
-                    println!("│    .oxen/hooks/post-commit/                              │");
-                    println!("│    .oxen/hooks/README.md                                 │");
-                    println!("│                                                          │");
-                    println!("└──────────────────────────────────────────────────────────┘");
-                    println!();
-                    progress::info("See .oxen/hooks/README.md for documentation");
-                    progress::info("Install built-in hooks with: auxin hooks install <name>");
+            generate(shell_type, &mut cmd, bin_name, &mut io::stdout());
 
-                    Ok(())
-                }
+            Ok(())
+        }
 
-                HooksCommands::List => {
-                    let hooks = manager.list_hooks()?;
+        Commands::Comment(comment_cmd) => {
+            use auxin::CommentManager;
+            use std::env;
 
-                    println!();
-                    println!("┌─ Installed Hooks ───────────────────────────────────────┐");
-                    println!("│                                                          │");
+            let current_dir = env::current_dir()?;
+            let comment_mgr = CommentManager::new();
 
-                    if hooks.is_empty() {
-                        println!("│  No hooks installed                                      │");
-                    } else {
-                        let pre_commit: Vec<_> = hooks
-                            .iter()
-                            .filter(|(t, _)| matches!(t, HookType::PreCommit))
-                            .collect();
-                        let post_commit: Vec<_> = hooks
-                            .iter()
-                            .filter(|(t, _)| matches!(t, HookType::PostCommit))
-                            .collect();
+            match comment_cmd {
+                CommentCommands::Add { commit_id, text } => {
+                    let user = lock_integration::get_user_identifier();
 
-                        if !pre_commit.is_empty() {
-                            println!(
-                                "│  Pre-commit hooks:                                       │"
-                            );
-                            for (_, name) in pre_commit {
-                                println!("│    • {:<51} │", name);
-                            }
-                            println!(
-                                "│                                                          │"
-                            );
-                        }
+                    let pb = progress::spinner("Adding comment...");
 
-                        if !post_commit.is_empty() {
-                            println!(
-                                "│  Post-commit hooks:                                      │"
-                            );
-                            for (_, name) in post_commit {
-                                println!("│    • {:<51} │", name);
-                            }
+                    match comment_mgr.add_comment(&current_dir, &commit_id, &user, &text) {
+                        Ok(_comment) => {
+                            progress::finish_success(&pb, "Comment added");
+                            println!();
+                            progress::success("Comment added successfully");
+                            println!();
+                            println!("  💬 {} said:", user.cyan());
+                            println!("     \"{}\"", text);
+                            println!();
+                            progress::info("Comment stored in .oxen/comments/");
+                            println!("  Commit and push to share with team:");
+                            println!("    oxen add .oxen/comments/");
+                            println!("    oxen commit -m \"Add comment\"");
+                            println!("    oxen push origin main");
+                        }
+                        Err(e) => {
+                            progress::finish_error(&pb, "Failed to add comment");
+                            println!();
+                            progress::error(&format!("{}", e));
+                            std::process::exit(1);
                         }
                     }
 
-                    println!("│                                                          │");
-                    println!("└──────────────────────────────────────────────────────────┘");
-                    println!();
-
+                    sync_meta_branch(&current_dir);
                     Ok(())
                 }
 
-                HooksCommands::Builtins => {
-                    let builtins = HookManager::list_builtins();
+                CommentCommands::List { commit_id } => {
+                    let commit = commit_id.as_deref().unwrap_or("HEAD");
+
+                    let pb = progress::spinner("Fetching comments...");
+                    let comments = comment_mgr.get_comments(&current_dir, commit)?;
+                    pb.finish_and_clear();
+
+                    if comments.is_empty() {
+                        println!();
+                        progress::info("No comments on this commit");
+                        println!();
+                        progress::info("Add a comment:");
+                        println!("  auxin comment add {} \"Your comment here\"", commit);
+                        return Ok(());
+                    }
 
                     println!();
-                    println!("┌─ Available Built-in Hooks ──────────────────────────────┐");
+                    println!(
+                        "┌─ Comments on {} ─────────────────────────────────┐",
+                        if commit.len() > 8 {
+                            &commit[..8]
+                        } else {
+                            commit
+                        }
+                    );
+                    println!("│                                                          │");
+                    println!(
+                        "│  {} comments                                              │",
+                        comments.len()
+                    );
                     println!("│                                                          │");
+                    println!("└──────────────────────────────────────────────────────────┘");
+                    println!();
 
-                    for hook in builtins {
-                        let type_str = match hook.hook_type {
-                            HookType::PreCommit => "pre-commit",
-                            HookType::PostCommit => "post-commit",
-                        };
+                    for (i, comment) in comments.iter().enumerate() {
                         println!(
-                            "│  {} ({})                                 ",
-                            hook.name.bright_yellow(),
-                            type_str.dimmed()
+                            "{} 💬 {} said:",
+                            (i + 1).to_string().bright_black(),
+                            comment.author.cyan()
                         );
-                        println!("│    {:<55} │", hook.description);
-                        println!("│                                                          │");
+                        println!("   \"{}\"", comment.text);
+                        println!(
+                            "   {}",
+                            comment
+                                .created_at
+                                .format("%Y-%m-%d %H:%M UTC")
+                                .to_string()
+                                .bright_black()
+                        );
+                        if i < comments.len() - 1 {
+                            println!();
+                        }
                     }
 
-                    println!("└──────────────────────────────────────────────────────────┘");
                     println!();
-                    progress::info("Install with: auxin hooks install <name>");
+                    progress::success(&format!("Showing {} comments", comments.len()));
 
                     Ok(())
                 }
+            }
+        }
 
-                HooksCommands::Install { name, hook_type } => {
-                    // Parse hook type
-                    let hook_type = match hook_type.as_str() {
-                        "pre-commit" => HookType::PreCommit,
-                        "post-commit" => HookType::PostCommit,
-                        _ => {
-                            anyhow::bail!(
-                                "Invalid hook type: {}. Use 'pre-commit' or 'post-commit'",
-                                hook_type
-                            );
+        Commands::Branch(command) => {
+            use auxin::branch;
+
+            let repo = std::env::current_dir()?;
+            let oxen = OxenSubprocess::new();
+            match command {
+                BranchCommands::List => {
+                    for info in branch::list(&oxen, &repo)? {
+                        if info.is_current {
+                            println!("* {}", info.name.green().bold());
+                        } else {
+                            println!("  {}", info.name);
                         }
-                    };
+                    }
+                }
+                BranchCommands::Create { name } => {
+                    branch::create(&oxen, &repo, &name)?;
+                    success!("Created branch '{}' and switched to it", name);
+                }
+                BranchCommands::Switch { name, force } => {
+                    let _lock = auxin::operation_lock::OperationLock::acquire(&repo, "branch switch")?;
+                    record_tree_manifest(&repo, "branch switch", &name);
+                    if branch::switch(&oxen, &repo, &name, force)? {
+                        success!("Switched to branch '{}'", name);
+                    } else {
+                        progress::info(&format!("Already on '{}'", name));
+                    }
+                }
+                BranchCommands::Delete { name, force } => {
+                    branch::delete(&oxen, &repo, &name, force)?;
+                    success!("Deleted branch '{}'", name);
+                }
+            }
+            Ok(())
+        }
 
-                    // Ensure hooks directory exists
-                    manager.init()?;
+        Commands::Pull {
+            remote,
+            branch,
+            resolve,
+        } => {
+            use auxin::conflict_detection::{record_resolution, ConflictResolver};
+            use auxin::operation_history::{
+                HistoryOperation, OperationHistoryEntry, OperationHistoryManager, OperationResult,
+            };
+            use auxin::{
+                ChunkedDownloadManager, ConflictChoice, ConflictDetector, ConflictRecommendation,
+                DownloadConfig, UploadProgress,
+            };
 
-                    let pb = progress::spinner(&format!("Installing {} hook...", name));
-                    manager.install_builtin(&name, hook_type)?;
-                    progress::finish_success(&pb, &format!("Installed {} hook", name));
+            let repo = std::env::current_dir()?;
+            let _lock = auxin::operation_lock::OperationLock::acquire(&repo, "pull")?;
+            let oxen = OxenSubprocess::new();
+            let remote = remote.unwrap_or_else(|| "origin".to_string());
+            let branch = match branch {
+                Some(branch) => branch,
+                None => oxen.current_branch(&repo).context("Failed to get current branch")?,
+            };
 
-                    println!();
-                    progress::success(&format!("Hook '{}' installed successfully", name));
-                    progress::info(&format!(
-                        "Edit at: .oxen/hooks/{}/{}",
-                        hook_type.dir_name(),
-                        name
-                    ));
+            // Note what's on disk so files the pull brings in can be quarantined
+            let quarantine_config = Config::load().unwrap_or_default().quarantine;
+            let before_pull = if quarantine_config.enabled {
+                Some(auxin::quarantine::scan(&repo)?)
+            } else {
+                None
+            };
 
-                    Ok(())
-                }
+            let detector = ConflictDetector::new();
+            let check = detector.check_before_pull(&repo, &branch)?;
+            if check.recommendation == ConflictRecommendation::AcquireLock {
+                warn!(
+                    "{} holds the lock; their latest changes may not be pushed yet",
+                    check.lock_owner.as_deref().unwrap_or("Someone else")
+                );
+            }
 
-                HooksCommands::Remove { name, hook_type } => {
-                    // Parse hook type
-                    let hook_type = match hook_type.as_str() {
-                        "pre-commit" => HookType::PreCommit,
-                        "post-commit" => HookType::PostCommit,
-                        _ => {
-                            anyhow::bail!(
-                                "Invalid hook type: {}. Use 'pre-commit' or 'post-commit'",
-                                hook_type
-                            );
-                        }
-                    };
+            let pb = progress::spinner(&format!("Checking {}/{} for conflicts...", remote, branch));
+            let conflicts = detector.conflicting_files(&repo, &remote, &branch)?;
+            pb.finish_and_clear();
 
-                    let pb = progress::spinner(&format!("Removing {} hook...", name));
-                    manager.remove_hook(&name, hook_type)?;
-                    progress::finish_success(&pb, &format!("Removed {} hook", name));
+            let target = format!("{}/{}", remote, branch);
+            if conflicts.is_empty() {
+                let mut download_config =
+                    DownloadConfig::from_config(&Config::load().unwrap_or_default());
+                download_config.verbose = cli.verbose;
+                let manager = ChunkedDownloadManager::new(download_config)
+                    .context("Failed to initialize download manager")?;
+                if let Some(session) = manager.get_resumable_session_info(&repo) {
+                    if session.remote == remote && session.branch == branch {
+                        progress::info(&format!(
+                            "Resuming interrupted pull ({} already received)",
+                            UploadProgress::bytes_string(session.bytes_downloaded)
+                        ));
+                    }
+                }
 
-                    Ok(())
+                record_tree_manifest(&repo, "pull", &target);
+                let pb = progress::spinner(&format!("Pulling {}...", target));
+                let result = manager.download_with_progress(&repo, &remote, &branch, |progress| {
+                    if progress.bytes_this_run > 0 {
+                        pb.set_message(format!(
+                            "Pulling {}... {} received, {}",
+                            target,
+                            progress.bytes_string(),
+                            progress.bandwidth_string()
+                        ));
+                    }
+                });
+                let entry = OperationHistoryEntry::new(HistoryOperation::Pull)
+                    .with_repo_path(&repo)
+                    .with_result(match &result {
+                        Ok(_) => OperationResult::Success,
+                        Err(e) => OperationResult::Failure(e.to_string()),
+                    });
+                let _ = OperationHistoryManager::new().record(entry);
+                let download = match result {
+                    Ok(download) => download,
+                    Err(e) => {
+                        pb.finish_and_clear();
+                        progress::info("Run 'auxin pull' again to resume; what was received is kept");
+                        return Err(e);
+                    },
+                };
+                let mut summary = format!("Pulled {}/{}", remote, branch);
+                if download.bytes_downloaded > 0 {
+                    summary.push_str(&format!(
+                        " ({}",
+                        UploadProgress::bytes_string(download.bytes_downloaded)
+                    ));
+                    if let Some(bps) = download.average_bandwidth {
+                        summary.push_str(&format!(
+                            " at {}/s",
+                            UploadProgress::bytes_string(bps as u64)
+                        ));
+                    }
+                    summary.push(')');
                 }
+                progress::finish_success(&pb, &summary);
+                if let Some(before) = &before_pull {
+                    quarantine_pulled_files(&repo, before, &quarantine_config)?;
+                }
+                sync_meta_branch(&repo);
+                return Ok(());
             }
-        }
-
-        Commands::Console { path } => {
-            use auxin::console::{Console, DaemonStatus as ConsoleDaemonStatus};
-            use auxin::daemon_client::DaemonClient;
 
-            // Determine project path
-            let project_path = match path {
-                Some(p) => p,
-                None => std::env::current_dir().context("Failed to get current directory")?,
+            println!(
+                "{} file(s) changed both here and on {}/{}:",
+                conflicts.len(),
+                remote,
+                branch
+            );
+            let choices: Vec<(PathBuf, ConflictChoice)> = match resolve {
+                Some(choice) => conflicts.into_iter().map(|path| (path, choice)).collect(),
+                None if atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout) => {
+                    prompt_conflict_choices(conflicts)?
+                },
+                None => anyhow::bail!(
+                    "Conflicting local changes. Run 'auxin pull' in a terminal to choose, \
+                     or pass --resolve local|remote|both"
+                ),
             };
 
-            vlog!("Launching console for project: {}", project_path.display());
+            if choices.iter().any(|(_, choice)| *choice == ConflictChoice::Abort) {
+                let _ = record_resolution(
+                    &repo,
+                    &choices,
+                    OperationResult::Failure("aborted".to_string()),
+                );
+                progress::info("Pull cancelled; nothing was changed");
+                return Ok(());
+            }
 
-            // Validate it's a Logic Pro project or Oxen repository
-            // TODO: Add validation once we have repository detection
+            let resolver = ConflictResolver::new(&repo);
+            record_tree_manifest(&repo, "pull", &target);
+            let pb = progress::spinner(&format!("Pulling {}...", target));
+            match resolver.pull_with(&choices) {
+                Ok(kept) => {
+                    let _ = record_resolution(&repo, &choices, OperationResult::Success);
+                    progress::finish_success(&pb, &format!("Pulled {}/{}", remote, branch));
+                    for path in kept {
+                        progress::info(&format!("Your version kept as {}", path.display()));
+                    }
+                    progress::info(&format!(
+                        "Local copies backed up in {}",
+                        resolver.backup_dir().display()
+                    ));
+                    if let Some(before) = &before_pull {
+                        quarantine_pulled_files(&repo, before, &quarantine_config)?;
+                    }
+                    sync_meta_branch(&repo);
+                    Ok(())
+                },
+                Err(e) => {
+                    pb.finish_and_clear();
+                    let _ = record_resolution(
+                        &repo,
+                        &choices,
+                        OperationResult::Failure(e.to_string()),
+                    );
+                    Err(e)
+                },
+            }
+        }
 
-            // Create and run console
-            let mut console = Console::new(project_path);
+        Commands::Resolve {
+            files,
+            ours,
+            theirs,
+            keep_both,
+        } => {
+            use auxin::conflict_detection::{
+                record_resolution, repo_relative_path, ConflictResolver,
+            };
+            use auxin::operation_history::OperationResult;
+            use auxin::{ConflictChoice, ConflictDetector};
 
-            // Check daemon status
-            let daemon_client = DaemonClient::new();
-            let status = daemon_client
-                .status()
-                .unwrap_or(auxin::daemon_client::DaemonStatus {
-                    is_running: false,
-                    pid: None,
-                    project_count: None,
-                    version: None,
-                    uptime: None,
-                });
+            let repo = std::env::current_dir()?;
+            let _lock = auxin::operation_lock::OperationLock::acquire(&repo, "resolve")?;
+            let mut conflicts: Vec<PathBuf> = ConflictDetector::new()
+                .merge_conflicts(&repo)?
+                .iter()
+                .map(|path| repo_relative_path(&repo, path))
+                .collect();
+            if !files.is_empty() {
+                let files: Vec<PathBuf> =
+                    files.iter().map(|file| repo_relative_path(&repo, file)).collect();
+                if let Some(file) = files.iter().find(|f| !conflicts.contains(f)) {
+                    anyhow::bail!("{} has no merge conflict", file.display());
+                }
+                conflicts.retain(|path| files.contains(path));
+            }
+            if conflicts.is_empty() {
+                progress::info("No merge conflicts to resolve");
+                return Ok(());
+            }
 
-            // Set initial daemon status
-            let console_status = if status.is_running {
-                ConsoleDaemonStatus::Running
+            let side = if ours {
+                Some(ConflictChoice::KeepLocal)
+            } else if theirs {
+                Some(ConflictChoice::TakeRemote)
+            } else if keep_both {
+                Some(ConflictChoice::KeepBoth)
             } else {
-                ConsoleDaemonStatus::Stopped
+                None
+            };
+            println!("{} file(s) with merge conflicts:", conflicts.len());
+            let choices: Vec<(PathBuf, ConflictChoice)> = match side {
+                Some(choice) => conflicts.into_iter().map(|path| (path, choice)).collect(),
+                None if atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout) => {
+                    prompt_conflict_choices(conflicts)?
+                },
+                None => anyhow::bail!(
+                    "Merge conflicts need a choice. Run 'auxin resolve' in a terminal, \
+                     or pass --ours, --theirs or --keep-both"
+                ),
             };
-            console.set_daemon_status(console_status);
 
-            // Run the console
-            console.run().await?;
+            if choices.iter().any(|(_, choice)| *choice == ConflictChoice::Abort) {
+                let _ = record_resolution(
+                    &repo,
+                    &choices,
+                    OperationResult::Failure("aborted".to_string()),
+                );
+                progress::info("Resolve cancelled; nothing was changed");
+                return Ok(());
+            }
 
-            success!("Console exited");
-            Ok(())
+            let resolver = ConflictResolver::new(&repo);
+            match resolver.resolve_merge(&choices) {
+                Ok(kept) => {
+                    let _ = record_resolution(&repo, &choices, OperationResult::Success);
+                    success!("Resolved {} file(s)", choices.len());
+                    for path in kept {
+                        progress::info(&format!("Your version kept as {}", path.display()));
+                    }
+                    progress::info(&format!(
+                        "Working copies backed up in {}",
+                        resolver.backup_dir().display()
+                    ));
+                    let remaining = ConflictDetector::new().merge_conflicts(&repo)?;
+                    if remaining.is_empty() {
+                        progress::info("No conflicts left; commit the merge with 'auxin commit'");
+                    } else {
+                        progress::info(&format!(
+                            "{} conflict(s) left; run 'auxin resolve' again",
+                            remaining.len()
+                        ));
+                    }
+                    Ok(())
+                },
+                Err(e) => {
+                    let _ = record_resolution(
+                        &repo,
+                        &choices,
+                        OperationResult::Failure(e.to_string()),
+                    );
+                    Err(e.context(format!(
+                        "Resolve failed; working copies are backed up in {}",
+                        resolver.backup_dir().display()
+                    )))
+                },
+            }
         }
 
-        Commands::Activity { limit } => {
-            use auxin::ActivityFeed;
-            use std::env;
+        Commands::Push {
+            remote,
+            branch,
+            force,
+            verbose,
+            keep_lock,
+        } => {
+            use auxin::{ChunkedUploadManager, UploadConfig, UploadProgress};
 
-            let current_dir = env::current_dir()?;
-            let feed = ActivityFeed::new();
+            let current_dir = std::env::current_dir()?;
+            vlog!("Push from directory: {}", current_dir.display());
 
-            let pb = progress::spinner("Fetching project activity...");
-            let activities = feed.get_recent_activity(&current_dir, limit)?;
-            pb.finish_and_clear();
+            // Initialize upload manager with config
+            let mut config = UploadConfig::from_config(&Config::load().unwrap_or_default());
+            config.verbose = verbose || cli.verbose;
 
-            if activities.is_empty() {
-                println!();
-                progress::info("No activity found");
-                println!("This project has no commit history yet.");
-                println!();
-                progress::info("Create your first commit:");
-                println!("  auxin add --all");
-                println!("  auxin commit -m \"Initial commit\" --bpm 120");
-                return Ok(());
-            }
+            let mut manager =
+                ChunkedUploadManager::new(config).context("Failed to initialize upload manager")?;
+
+            // Determine remote and branch
+            let remote_name = remote.unwrap_or_else(|| "origin".to_string());
+            let branch_name = if let Some(b) = branch {
+                b
+            } else {
+                // Get current branch
+                let subprocess = auxin::OxenSubprocess::new();
+                subprocess
+                    .current_branch(&current_dir)
+                    .context("Failed to get current branch")?
+            };
 
+            // Show push info
             println!();
-            println!("┌─ Project Activity ──────────────────────────────────────┐");
+            println!("┌─ Push to Remote ────────────────────────────────────────┐");
             println!("│                                                          │");
             println!(
-                "│  Recent activity ({} commits)                            │",
-                activities.len()
+                "│  {} {}:{}",
+                "→".cyan(),
+                remote_name.cyan().bold(),
+                branch_name.cyan().bold()
             );
             println!("│                                                          │");
             println!("└──────────────────────────────────────────────────────────┘");
             println!();
 
-            for (i, activity) in activities.iter().enumerate() {
-                let icon = activity.activity_type.icon();
-                let author = if activity.author.len() > 20 {
-                    format!("{}...", &activity.author[..17])
-                } else {
-                    activity.author.clone()
-                };
+            if force {
+                progress::warning("Force push enabled - this will overwrite remote history");
+            }
 
-                println!(
-                    "{} {} {} - {}",
-                    (i + 1).to_string().bright_black(),
-                    icon,
-                    author.cyan(),
-                    activity.message.white()
-                );
+            // Check for pending local changes
+            let subprocess = auxin::OxenSubprocess::new();
+            if let Ok(status) = subprocess.status(&current_dir) {
+                if !status.staged.is_empty() || !status.modified.is_empty() {
+                    progress::warning("You have uncommitted changes");
+                    progress::info("Consider committing before push: auxin commit -m \"message\"");
+                    println!();
+                }
+            }
 
-                // Show metadata if present
-                if !activity.metadata.is_empty() {
-                    for (key, value) in &activity.metadata {
-                        println!(
-                            "    {} {}: {}",
-                            "│".bright_black(),
-                            key.bright_black(),
-                            value.bright_black()
-                        );
+            // Check for existing upload session to resume
+            let is_resuming = manager.has_resumable_session(&current_dir);
+            if is_resuming {
+                if let Some(session_info) = manager.get_resumable_session_info(&current_dir) {
+                    progress::info(&format!(
+                        "Resuming interrupted upload ({:.1}% complete)",
+                        session_info.percentage
+                    ));
+                    println!();
+                }
+            }
+
+            // Execute push with progress tracking
+            let pb = progress::spinner("Starting push...");
+
+            let result = manager.upload_with_progress(
+                &current_dir,
+                &remote_name,
+                &branch_name,
+                |progress| {
+                    if progress.bytes_uploaded > 0 {
+                        pb.set_message(format!(
+                            "Pushing... {} of {} ({:.0}%), {}, ETA {}",
+                            UploadProgress::bytes_string(progress.bytes_uploaded),
+                            UploadProgress::bytes_string(progress.total_bytes),
+                            progress.percentage,
+                            progress.bandwidth_string(),
+                            progress.eta_string()
+                        ));
+                    }
+                },
+            );
+            pb.finish_and_clear();
+
+            match result {
+                Ok(result) => {
+                    println!();
+
+                    if is_resuming {
+                        progress::success("Upload resumed and completed successfully");
+                    } else {
+                        progress::success("Push completed successfully");
+                    }
+
+                    // Show statistics
+                    if result.bytes_uploaded > 0 {
+                        let size_mb = result.bytes_uploaded as f64 / (1024.0 * 1024.0);
+                        let duration_secs = result.duration.as_secs_f64();
+                        let speed_mbps = if duration_secs > 0.0 {
+                            size_mb / duration_secs
+                        } else {
+                            0.0
+                        };
+
+                        println!();
+                        println!("  {} Uploaded: {:.2} MB", "•".dimmed(), size_mb);
+                        println!("  {} Duration: {:.1}s", "•".dimmed(), duration_secs);
+                        println!("  {} Speed: {:.2} MB/s", "•".dimmed(), speed_mbps);
+
+                        if result.files_uploaded > 0 {
+                            println!("  {} Files: {}", "•".dimmed(), result.files_uploaded);
+                        }
+                    }
+
+                    println!();
+                    progress::info(&format!(
+                        "Branch '{}' pushed to '{}'",
+                        branch_name, remote_name
+                    ));
+
+                    if !keep_lock {
+                        release_lock_after(&current_dir, LockRelease::Push);
+                    }
+                    Ok(())
+                }
+                Err(e) => {
+                    println!();
+                    progress::error(&format!("Push failed: {}", e));
+
+                    // Provide recovery hints
+                    println!();
+                    progress::info("To retry, run the same command again");
+                    progress::info("Your progress has been saved and will resume automatically");
+
+                    // Check if it's a network error
+                    let error_str = e.to_string().to_lowercase();
+                    if error_str.contains("network")
+                        || error_str.contains("connection")
+                        || error_str.contains("timeout")
+                    {
+                        println!();
+                        progress::info("Network issue detected. Check your connection and retry.");
+                        progress::info("For recovery help: auxin recovery push");
+                    } else if error_str.contains("auth")
+                        || error_str.contains("permission")
+                        || error_str.contains("401")
+                        || error_str.contains("403")
+                    {
+                        println!();
+                        progress::info("Authentication issue. Try: auxin login");
                     }
+
+                    Err(e)
                 }
             }
-
-            println!();
-            progress::info(&format!(
-                "Showing {} most recent activities",
-                activities.len()
-            ));
-
-            Ok(())
         }
 
-        Commands::Team => {
-            use auxin::TeamManager;
-            use std::env;
-
-            let current_dir = env::current_dir()?;
-            let team_mgr = TeamManager::new();
-
-            let pb = progress::spinner("Discovering team members...");
-            let members = team_mgr.discover_team_members(&current_dir)?;
-            pb.finish_and_clear();
+        Commands::UpgradeCheck { json } => {
+            let json = json || cli.json;
+            use auxin::upgrade_check::{self, VersionState};
 
-            if members.is_empty() {
-                println!();
-                progress::warning("No team members found");
-                println!("This project has no commit history with author information.");
+            let config = Config::load().unwrap_or_default();
+            let report = upgrade_check::check(&config, std::time::Duration::from_secs(10));
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
                 return Ok(());
             }
 
-            let total_commits: usize = members.iter().map(|m| m.commit_count).sum();
-
-            println!();
-            println!("┌─ Team Members ──────────────────────────────────────────┐");
-            println!("│                                                          │");
-            println!(
-                "│  {} members · {} total commits                           │",
-                members.len(),
-                total_commits
-            );
-            println!("│                                                          │");
-            println!("└──────────────────────────────────────────────────────────┘");
-            println!();
-
-            for (i, member) in members.iter().enumerate() {
-                let percentage =
-                    (member.commit_count as f64 / total_commits as f64 * 100.0) as usize;
-                let bar_length = (percentage / 5).min(20);
-                let bar = "█".repeat(bar_length);
-
+            println!("\n{}", "Upgrade Check".cyan().bold());
+            println!("{}", "=".repeat(40).dimmed());
+            if let Some(error) = &report.manifest_error {
+                match report.checked_at {
+                    Some(checked_at) => warn!(
+                        "{}; using releases known on {}",
+                        error,
+                        checked_at.with_timezone(&chrono::Local).format("%Y-%m-%d")
+                    ),
+                    None => warn!("{}", error),
+                }
+            }
+            for status in &report.components {
+                let installed = status
+                    .installed
+                    .clone()
+                    .or_else(|| status.note.clone())
+                    .unwrap_or_else(|| "unknown".to_string());
+                let latest = status.latest.as_deref().unwrap_or("unknown");
+                let mark = match status.state {
+                    VersionState::UpToDate => "✓".green(),
+                    VersionState::Outdated => "↑".yellow(),
+                    VersionState::Unknown => "?".dimmed(),
+                };
                 println!(
-                    "{} {} {} commits ({}%)",
-                    (i + 1).to_string().bright_black(),
-                    member.name.cyan(),
-                    member.commit_count.to_string().green(),
-                    percentage
+                    "{} {:<14} {} (latest {})",
+                    mark,
+                    status.component.label(),
+                    installed,
+                    latest
                 );
-                println!("   {} {}", bar.green(), " ".repeat(20 - bar_length));
+                if let Some(upgrade) = &status.upgrade {
+                    println!("  {} {}", "→".yellow(), upgrade);
+                }
+            }
+            let any_current = report
+                .components
+                .iter()
+                .any(|status| status.state == VersionState::UpToDate);
+            if any_current && report.outdated().next().is_none() {
+                println!("\n{}", "Everything known is up to date.".green());
             }
-
-            println!();
-            progress::success(&format!("Found {} team members", members.len()));
-
             Ok(())
         }
 
-        Commands::Queue(queue_cmd) => {
-            use auxin::OfflineQueue;
-            use colored::Colorize;
-
-            let mut queue = OfflineQueue::new()?;
-
-            match queue_cmd {
-                QueueCommands::Status => {
-                    let pending = queue.pending();
-                    let stats = queue.stats();
-
-                    println!("\n{}", "Offline Operation Queue".bold());
-                    println!("{}", "=".repeat(50));
-                    println!();
-
-                    if pending.is_empty() {
-                        progress::success("No pending operations");
-                        println!("\n  All operations have been synced!");
-                    } else {
-                        println!(
-                            "  {} {}",
-                            "Pending:".bold(),
-                            pending.len().to_string().yellow()
-                        );
-                        println!(
-                            "  {} {}",
-                            "Completed:".bold(),
-                            stats.completed.to_string().green()
-                        );
-                        println!("  {} {}", "Failed:".bold(), stats.failed.to_string().red());
+        Commands::Features(command) => {
+            use auxin_config::{Features, RepoFeatures};
+
+            let repo_root = std::env::current_dir()?;
+            let recorded = RepoFeatures::load(&repo_root)?;
+            match command {
+                FeaturesCommands::List => {
+                    let features = recorded.clone().unwrap_or_default();
+                    for (name, description) in Features::KNOWN {
+                        let mark = if features.features.get(name) == Some(true) {
+                            "on ".green()
+                        } else {
+                            "off".yellow()
+                        };
+                        println!("{} {:<18} {}", mark, name, description.dimmed());
+                    }
+                    for name in features.features.unknown_enabled() {
+                        println!("{} {:<18} {}", "?  ".red(), name, "unknown to this auxin".dimmed());
+                    }
+                    if recorded.is_none() {
                         println!();
-
-                        for (i, entry) in pending.iter().enumerate() {
-                            let age = chrono::Utc::now().signed_duration_since(entry.queued_at);
-                            let age_str = if age.num_hours() > 0 {
-                                format!("{}h ago", age.num_hours())
-                            } else if age.num_minutes() > 0 {
-                                format!("{}m ago", age.num_minutes())
-                            } else {
-                                format!("{}s ago", age.num_seconds())
-                            };
-
-                            println!(
-                                "  {}. {} {}",
-                                (i + 1).to_string().cyan(),
-                                entry.operation.description(),
-                                format!("({})", age_str).dimmed()
-                            );
-                            println!(
-                                "     {} {} | {} {} | {} {}",
-                                "ID:".dimmed(),
-                                &entry.id[..8].dimmed(),
-                                "Priority:".dimmed(),
-                                entry.priority.to_string().dimmed(),
-                                "Attempts:".dimmed(),
-                                entry.attempts.to_string().dimmed()
-                            );
-                            println!();
-                        }
-
-                        println!(
-                            "  {}",
-                            "Use 'auxin queue sync' to sync pending operations".dimmed()
+                        progress::info("No .auxin/features.toml; every feature is on");
+                    }
+                }
+                FeaturesCommands::Get { name } => {
+                    let features = recorded.unwrap_or_default();
+                    match features.features.get(&name) {
+                        Some(true) => println!("on"),
+                        Some(false) => println!("off"),
+                        None => anyhow::bail!("Unknown feature '{}'", name),
+                    }
+                }
+                FeaturesCommands::Set { name, value } => {
+                    if !repo_root.join(".oxen").exists() {
+                        anyhow::bail!("Not in an Oxen repository");
+                    }
+                    let enabled = match value.to_lowercase().as_str() {
+                        "true" | "on" | "yes" | "1" => true,
+                        "false" | "off" | "no" | "0" => false,
+                        _ => anyhow::bail!("Invalid value '{}' (use on or off)", value),
+                    };
+                    let mut features = recorded.unwrap_or_default();
+                    if let Some(problem) = features.incompatibility() {
+                        anyhow::bail!(
+                            "This project {}; upgrade auxin to change its features",
+                            problem
                         );
                     }
-
-                    Ok(())
+                    features.features.set(&name, enabled)?;
+                    features.save(&repo_root)?;
+                    success!("{} turned {}", name, if enabled { "on" } else { "off" });
+                    progress::info("Commit .auxin/features.toml so collaborators get the change");
                 }
+            }
+            Ok(())
+        }
 
-                QueueCommands::Sync => {
-                    use auxin::network_resilience::{check_connectivity, ConnectivityState};
-
-                    println!("\n{}", "Syncing Offline Queue".bold());
-                    println!("{}", "=".repeat(50));
-                    println!();
+        Commands::Workspace(command) => {
+            use auxin::workspace::{self, Outcome, Task, Workspace};
 
-                    // Check connectivity
-                    match check_connectivity() {
-                        ConnectivityState::Offline => {
-                            progress::error("Network is offline - cannot sync");
-                            println!(
-                                "\n  Operations will remain queued until network is available"
-                            );
-                            return Ok(());
+            let workspace_path = Workspace::default_path();
+            let mut registered = Workspace::load(&workspace_path)?;
+            match command {
+                WorkspaceCommands::List => {
+                    if registered.repos.is_empty() {
+                        progress::info("No repositories registered; add one with 'auxin workspace add'");
+                    }
+                    for repo in &registered.repos {
+                        println!("{}", repo.display());
+                    }
+                }
+                WorkspaceCommands::Add { paths } => {
+                    let paths = if paths.is_empty() {
+                        vec![std::env::current_dir()?]
+                    } else {
+                        paths
+                    };
+                    for path in paths {
+                        if !path.join(".oxen").is_dir() {
+                            anyhow::bail!("Not an Oxen repository: {}", path.display());
                         }
-                        ConnectivityState::Unknown => {
-                            warn!("Network state unknown, attempting sync anyway");
+                        if registered.add(&path) {
+                            success!("Added {}", path.display());
+                        } else {
+                            progress::info(&format!("Already registered: {}", path.display()));
                         }
-                        ConnectivityState::Online => {
-                            progress::success("Network is online");
+                    }
+                    registered.save(&workspace_path)?;
+                }
+                WorkspaceCommands::Remove { paths } => {
+                    for path in paths {
+                        if registered.remove(&path) {
+                            success!("Removed {}", path.display());
+                        } else {
+                            warn!("Not registered: {}", path.display());
                         }
                     }
-
-                    let pending_count = queue.pending().len();
-                    if pending_count == 0 {
-                        progress::success("No pending operations to sync");
-                        return Ok(());
+                    registered.save(&workspace_path)?;
+                }
+                WorkspaceCommands::Maintain {
+                    gc,
+                    prune_drafts,
+                    verify,
+                    json,
+                } => {
+                    let json = json || cli.json;
+                    let tasks: Vec<Task> = [
+                        (gc, Task::Gc),
+                        (prune_drafts, Task::PruneDrafts),
+                        (verify, Task::Verify),
+                    ]
+                    .into_iter()
+                    .filter_map(|(selected, task)| selected.then_some(task))
+                    .collect();
+                    if tasks.is_empty() {
+                        anyhow::bail!("Choose at least one task: --gc, --prune-drafts or --verify");
                     }
 
-                    println!("  Syncing {} pending operation(s)...\n", pending_count);
-
-                    let report = queue.sync_all()?;
-
-                    println!();
-                    println!("{}", "Sync Results".bold());
-                    println!("{}", "=".repeat(50));
-                    println!(
-                        "  {} {}",
-                        "Succeeded:".bold(),
-                        report.succeeded.len().to_string().green()
-                    );
-                    println!(
-                        "  {} {}",
-                        "Failed:".bold(),
-                        report.failed.len().to_string().red()
-                    );
+                    let oxen = OxenSubprocess::new();
+                    let reports: Vec<_> = registered
+                        .repos
+                        .iter()
+                        .map(|repo| workspace::maintain(repo, &tasks, &oxen))
+                        .collect();
 
-                    if !report.failed.is_empty() {
-                        println!();
-                        println!("{}", "Failed Operations:".bold());
-                        for (id, error) in &report.failed {
-                            println!("  {} {} - {}", "✗".red(), &id[..8], error.dimmed());
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&reports)?);
+                    } else {
+                        if reports.is_empty() {
+                            progress::info("No repositories registered; add one with 'auxin workspace add'");
+                        }
+                        for report in &reports {
+                            println!("{}", report.repo.display().to_string().bold());
+                            for result in &report.results {
+                                let outcome = match result.outcome {
+                                    Outcome::Ok => "ok       ".green(),
+                                    Outcome::Fixed => "fixed    ".cyan(),
+                                    Outcome::Skipped => "skipped  ".dimmed(),
+                                    Outcome::Attention => "attention".red(),
+                                };
+                                println!(
+                                    "  {} {:<13} {}",
+                                    outcome,
+                                    result.task.name(),
+                                    result.detail
+                                );
+                            }
                         }
                     }
 
-                    println!();
-
-                    if report.failed.is_empty() {
-                        progress::success("All operations synced successfully!");
-                    } else {
-                        warn!("Some operations failed - they remain queued for retry");
+                    let attention = reports.iter().filter(|r| r.needs_attention()).count();
+                    if attention > 0 {
+                        anyhow::bail!(
+                            "{} of {} repositories need attention",
+                            attention,
+                            reports.len()
+                        );
+                    }
+                    if !json && !reports.is_empty() {
+                        println!();
+                        success!("{} repositories maintained", reports.len());
                     }
-
-                    Ok(())
                 }
+            }
+            Ok(())
+        }
 
-                QueueCommands::Clear { all } => {
-                    if all {
-                        // Clear everything
-                        let total = queue.pending().len() + queue.stats().completed;
-
-                        // Remove all entries
-                        let entry_ids: Vec<String> =
-                            queue.pending().iter().map(|e| e.id.clone()).collect();
-
-                        for id in entry_ids {
-                            queue.remove(&id)?;
-                        }
-
-                        queue.clear_completed()?;
+        Commands::Devtools(DevtoolsCommands::GenProject {
+            kind,
+            size,
+            commits,
+            name,
+            seed,
+            output,
+        }) => {
+            use auxin::devtools::{self, GenSpec, SyntheticType};
 
-                        progress::success(&format!("Cleared {} total operation(s)", total));
-                    } else {
-                        // Clear only completed
-                        let completed_count = queue.stats().completed;
-                        queue.clear_completed()?;
-                        progress::success(&format!(
-                            "Cleared {} completed operation(s)",
-                            completed_count
-                        ));
-                    }
+            let Some(kind) = SyntheticType::parse(&kind) else {
+                anyhow::bail!("Unknown project type '{}' (use logicpro, sketchup, blender or other)", kind);
+            };
+            let spec = GenSpec {
+                kind,
+                name,
+                size: devtools::parse_size(&size)?,
+                commits,
+                seed,
+            };
+            let parent = match output {
+                Some(dir) => dir,
+                None => std::env::current_dir()?,
+            };
 
+            let pb = progress::spinner(&format!(
+                "Generating {} of {}...",
+                auxin::describe::format_size(spec.size),
+                spec.kind.folder_name(&spec.name)
+            ));
+            let result = devtools::generate(&spec, &parent, |step| pb.set_message(step.to_string()));
+            match result {
+                Ok(report) => {
+                    progress::finish_success(
+                        &pb,
+                        &format!(
+                            "Generated {} ({} files, {}, {} commits)",
+                            report.path.display(),
+                            report.files,
+                            auxin::describe::format_size(report.bytes),
+                            report.commits
+                        ),
+                    );
                     Ok(())
                 }
-
-                QueueCommands::Remove { entry_id } => {
-                    queue.remove(&entry_id)?;
-                    progress::success(&format!("Removed operation {}", &entry_id[..8]));
-                    Ok(())
+                Err(e) => {
+                    progress::finish_error(&pb, "Generation failed");
+                    Err(e)
                 }
             }
         }
 
-        Commands::Completions { shell } => {
-            use clap::CommandFactory;
-            use clap_complete::{generate, Shell};
-            use std::io;
+        Commands::Archive(command) => {
+            use auxin::archive::{self, ArchiveMark};
 
-            let shell_type = match shell.to_lowercase().as_str() {
-                "bash" => Shell::Bash,
-                "zsh" => Shell::Zsh,
-                "fish" => Shell::Fish,
-                "powershell" => Shell::PowerShell,
-                _ => {
-                    progress::error(&format!("Unsupported shell: {}", shell));
-                    println!("\nSupported shells: bash, zsh, fish, powershell");
-                    std::process::exit(1);
-                }
+            let current_dir = std::env::current_dir()?;
+            let Some(repo_root) = auxin::describe::find_repo_root(&current_dir) else {
+                anyhow::bail!("Not in an Oxen repository");
             };
-
-            let mut cmd = Cli::command();
-            let bin_name = "auxin";
-
-            generate(shell_type, &mut cmd, bin_name, &mut io::stdout());
-
+            match command {
+                ArchiveCommands::Mark { reason } => {
+                    let mark = archive::mark(&repo_root, reason)?;
+                    success!("Project {}", mark.summary());
+                    println!("  Commands that would change it now refuse to run, locks can't be");
+                    println!("  acquired and the daemon stops watching it.");
+                    println!("  Undo with: auxin archive unmark");
+                }
+                ArchiveCommands::Unmark => {
+                    if archive::unmark(&repo_root)? {
+                        success!("Project is writable again");
+                        progress::info("Restart the daemon to watch it again: auxin daemon restart");
+                    } else {
+                        progress::info("Project isn't archived");
+                    }
+                }
+                ArchiveCommands::Status => match ArchiveMark::load(&repo_root)? {
+                    Some(mark) => println!("Read-only: {}", mark.summary()),
+                    None => println!("Not archived"),
+                },
+            }
             Ok(())
         }
 
-        Commands::Comment(comment_cmd) => {
-            use auxin::CommentManager;
-            use std::env;
-
-            let current_dir = env::current_dir()?;
-            let comment_mgr = CommentManager::new();
-
-            match comment_cmd {
-                CommentCommands::Add { commit_id, text } => {
-                    let user = lock_integration::get_user_identifier();
+        Commands::Doctor => {
+            use auxin::doctor::{self, CheckState};
 
-                    let pb = progress::spinner("Adding comment...");
+            let current_dir = std::env::current_dir()?;
+            if cli.json {
+                let report = doctor::run(&current_dir, |_| {});
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
 
-                    match comment_mgr.add_comment(&current_dir, &commit_id, &user, &text) {
-                        Ok(_comment) => {
-                            progress::finish_success(&pb, "Comment added");
-                            println!();
-                            progress::success("Comment added successfully");
-                            println!();
-                            println!("  💬 {} said:", user.cyan());
-                            println!("     \"{}\"", text);
-                            println!();
-                            progress::info("Comment stored in .oxen/comments/");
-                            println!("  Commit and push to share with team:");
-                            println!("    oxen add .oxen/comments/");
-                            println!("    oxen commit -m \"Add comment\"");
-                            println!("    oxen push origin main");
-                        }
-                        Err(e) => {
-                            progress::finish_error(&pb, "Failed to add comment");
-                            println!();
-                            progress::error(&format!("{}", e));
-                            std::process::exit(1);
-                        }
-                    }
+            println!("\n{}", "Auxin Doctor - Environment Check".cyan().bold());
+            println!("{}", "=".repeat(40).dimmed());
+            println!();
 
-                    Ok(())
+            let report = doctor::run(&current_dir, |check| {
+                let mark = match check.state {
+                    CheckState::Ok => "✓".green(),
+                    CheckState::Warning => "⚠".yellow(),
+                    CheckState::Error => "✗".red(),
+                    CheckState::Unknown => "?".yellow(),
+                };
+                println!(
+                    "{} {} {}",
+                    format!("Checking {}...", check.label).cyan(),
+                    mark,
+                    check.summary
+                );
+                for detail in &check.details {
+                    println!("  {} {}", "•".dimmed(), detail);
                 }
+                if let Some(hint) = &check.hint {
+                    println!("  {} {}", "→".yellow(), hint);
+                }
+            });
 
-                CommentCommands::List { commit_id } => {
-                    let commit = commit_id.as_deref().unwrap_or("HEAD");
-
-                    let pb = progress::spinner("Fetching comments...");
-                    let comments = comment_mgr.get_comments(&current_dir, commit)?;
-                    pb.finish_and_clear();
-
-                    if comments.is_empty() {
-                        println!();
-                        progress::info("No comments on this commit");
-                        println!();
-                        progress::info("Add a comment:");
-                        println!("  auxin comment add {} \"Your comment here\"", commit);
-                        return Ok(());
-                    }
-
-                    println!();
-                    println!(
-                        "┌─ Comments on {} ─────────────────────────────────┐",
-                        if commit.len() > 8 {
-                            &commit[..8]
-                        } else {
-                            commit
-                        }
-                    );
-                    println!("│                                                          │");
-                    println!(
-                        "│  {} comments                                              │",
-                        comments.len()
-                    );
-                    println!("│                                                          │");
-                    println!("└──────────────────────────────────────────────────────────┘");
-                    println!();
+            // Summary
+            println!("\n{}", "─".repeat(40).dimmed());
+            if report.all_good {
+                println!(
+                    "{}",
+                    "All checks passed! You're ready to use Auxin."
+                        .green()
+                        .bold()
+                );
+            } else {
+                println!("{}", "Some issues found. See suggestions above.".yellow());
+            }
+            println!();
 
-                    for (i, comment) in comments.iter().enumerate() {
-                        println!(
-                            "{} 💬 {} said:",
-                            (i + 1).to_string().bright_black(),
-                            comment.author.cyan()
-                        );
-                        println!("   \"{}\"", comment.text);
-                        println!(
-                            "   {}",
-                            comment
-                                .created_at
-                                .format("%Y-%m-%d %H:%M UTC")
-                                .to_string()
-                                .bright_black()
-                        );
-                        if i < comments.len() - 1 {
-                            println!();
-                        }
-                    }
+            Ok(())
+        }
 
-                    println!();
-                    progress::success(&format!("Showing {} comments", comments.len()));
+        Commands::SyncStatus { recover } => {
+            use auxin::describe::find_repo_root;
+            use auxin::network_resilience::ConnectivityState;
+            use auxin::sync_status::{ServerReach, SyncStatus};
+            use auxin::write_ahead_log::WalRecoveryManager;
 
-                    Ok(())
+            if recover {
+                let report = WalRecoveryManager::new().check_and_recover()?;
+                if report.entries_found == 0 {
+                    progress::success("No interrupted operations to recover");
+                } else {
+                    progress::info(&format!(
+                        "Checked {} interrupted operation(s): {} finished, {} failed, {} need \
+                         attention",
+                        report.entries_found, report.recovered, report.failed, report.skipped
+                    ));
                 }
             }
-        }
 
-        Commands::Push {
-            remote,
-            branch,
-            force,
-            verbose,
-        } => {
-            use auxin::{ChunkedUploadManager, UploadConfig};
+            let project = find_repo_root(&std::env::current_dir()?);
+            let pb = progress::spinner("Checking sync status...");
+            let status = SyncStatus::gather(project.as_deref());
+            pb.finish_and_clear();
 
-            let current_dir = std::env::current_dir()?;
-            vlog!("Push from directory: {}", current_dir.display());
+            println!("\n{}", "Sync Status".bold());
+            println!("{}", "=".repeat(50));
+            println!();
 
-            // Initialize upload manager with config
-            let mut config = UploadConfig::default();
-            config.verbose = verbose || cli.verbose;
+            let ok = |done: bool| if done { "✓".green() } else { "⚠".yellow() };
+            let mut queue_line = format!("{} pending", status.queued.len());
+            if let Some(oldest) = status.queued.iter().map(|e| e.queued_at).min() {
+                let age = chrono::Utc::now() - oldest;
+                queue_line.push_str(&format!(
+                    ", oldest {}h {}m",
+                    age.num_hours(),
+                    age.num_minutes() % 60
+                ));
+            }
+            if status.dead_letters > 0 {
+                queue_line.push_str(&format!(", {} gave up", status.dead_letters));
+            }
+            println!(
+                "  {} {} {}",
+                ok(status.queued.is_empty() && status.dead_letters == 0),
+                "Offline queue:".bold(),
+                queue_line
+            );
 
-            let mut manager =
-                ChunkedUploadManager::new(config).context("Failed to initialize upload manager")?;
+            println!(
+                "  {} {} {} interrupted",
+                ok(status.unfinished.is_empty()),
+                "Operations:".bold(),
+                status.unfinished.len()
+            );
+            for entry in &status.unfinished {
+                println!("      {} {}", "•".dimmed(), entry.description());
+            }
 
-            // Determine remote and branch
-            let remote_name = remote.unwrap_or_else(|| "origin".to_string());
-            let branch_name = if let Some(b) = branch {
-                b
-            } else {
-                // Get current branch
-                let subprocess = auxin::OxenSubprocess::new();
-                subprocess
-                    .current_branch(&current_dir)
-                    .context("Failed to get current branch")?
+            println!(
+                "  {} {} {} interrupted",
+                ok(status.uploads.is_empty()),
+                "Uploads:".bold(),
+                status.uploads.len()
+            );
+            for upload in &status.uploads {
+                println!(
+                    "      {} {} ({:.0}% of {})",
+                    "•".dimmed(),
+                    upload.repo_path,
+                    upload.percentage(),
+                    auxin::chunked_upload::UploadProgress::bytes_string(upload.total_bytes)
+                );
+            }
+
+            let daemon_line = match (&status.daemon.is_running, &status.paused) {
+                (false, _) => "not running".to_string(),
+                (true, Some(_)) => "running, auto-commits paused here".to_string(),
+                (true, None) => status
+                    .daemon
+                    .version
+                    .as_ref()
+                    .map_or("running".to_string(), |v| format!("running ({})", v)),
             };
+            println!(
+                "  {} {} {}",
+                ok(status.daemon.is_running
+                    && status.paused.is_none()
+                    && status.daemon_warning.is_none()),
+                "Daemon:".bold(),
+                daemon_line
+            );
 
-            // Show push info
-            println!();
-            println!("┌─ Push to Remote ────────────────────────────────────────┐");
-            println!("│                                                          │");
+            let (server_mark, server_line) = match status.server {
+                ServerReach::NotConfigured => ("-".dimmed(), "not configured".to_string()),
+                ServerReach::Reachable => {
+                    ("✓".green(), format!("{} reachable", status.server_url))
+                }
+                ServerReach::Unreachable => {
+                    ("✗".red(), format!("{} unreachable", status.server_url))
+                }
+            };
+            let network = match status.network {
+                ConnectivityState::Online => "online",
+                ConnectivityState::Captive => "needs sign-in",
+                ConnectivityState::Offline => "offline",
+                ConnectivityState::Unknown => "unknown",
+            };
             println!(
-                "│  {} {}:{}",
-                "→".cyan(),
-                remote_name.cyan().bold(),
-                branch_name.cyan().bold()
+                "  {} {} {} (network {})",
+                server_mark,
+                "Server:".bold(),
+                server_line,
+                network
             );
-            println!("│                                                          │");
-            println!("└──────────────────────────────────────────────────────────┘");
             println!();
 
-            if force {
-                progress::warning("Force push enabled - this will overwrite remote history");
+            if status.is_synced() {
+                progress::success("Everything is saved and synced");
+            } else {
+                progress::warning("Not everything is synced yet");
             }
 
-            // Check for pending local changes
-            let subprocess = auxin::OxenSubprocess::new();
-            if let Ok(status) = subprocess.status(&current_dir) {
-                if !status.staged.is_empty() || !status.modified.is_empty() {
-                    progress::warning("You have uncommitted changes");
-                    progress::info("Consider committing before push: auxin commit -m \"message\"");
-                    println!();
+            let suggestions = status.suggestions();
+            if !suggestions.is_empty() {
+                println!("\n{}", "Next steps:".bold());
+                for suggestion in suggestions {
+                    match suggestion.command {
+                        Some(command) => println!(
+                            "  {} {}  {}",
+                            "→".yellow(),
+                            command.cyan(),
+                            suggestion.reason.dimmed()
+                        ),
+                        None => println!("  {} {}", "→".yellow(), suggestion.reason),
+                    }
                 }
             }
+            println!();
 
-            // Check for existing upload session to resume
-            let is_resuming = manager.has_resumable_session(&current_dir);
-            if is_resuming {
-                if let Some(session_info) = manager.get_resumable_session_info(&current_dir) {
-                    progress::info(&format!(
-                        "Resuming interrupted upload ({:.1}% complete)",
-                        session_info.percentage
-                    ));
-                    println!();
+            Ok(())
+        }
+
+        Commands::Wrapup {
+            check,
+            yes,
+            message,
+        } => {
+            use auxin::describe::find_repo_root;
+            use auxin::offline_queue::OfflineQueue;
+            use auxin::quick_actions;
+            use auxin::wrapup::{self, WrapupCheck, WrapupStep};
+            use dialoguer::{Confirm, Input};
+
+            let project = find_repo_root(&std::env::current_dir()?);
+            let pb = progress::spinner("Checking what's left to do...");
+            let before = WrapupCheck::run(project.as_deref());
+            pb.finish_and_clear();
+
+            println!("\n{}", "Wrap Up".bold());
+            println!("{}", "=".repeat(50));
+            println!();
+            let issues = before.issues();
+            if issues.is_empty() {
+                println!("  {} Nothing left to do", "✓".green());
+            }
+            for issue in &issues {
+                println!("  {} {}", "⚠".yellow(), issue.describe());
+            }
+            println!();
+
+            let interactive = atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout);
+            let steps = if check || !(yes || interactive) {
+                Vec::new()
+            } else {
+                wrapup::plan(&issues, project.as_deref())
+            };
+
+            let exe = std::env::current_exe()?;
+            let mut failed = false;
+            let mut took_steps = false;
+            for step in steps {
+                if failed && step == WrapupStep::ReleaseLock {
+                    progress::warning("Keeping the lock until the steps above succeed");
+                    continue;
+                }
+                if !yes && !Confirm::new().with_prompt(step.prompt()).default(true).interact()? {
+                    continue;
+                }
+                took_steps = true;
+
+                // Every step but the queue sync comes from an issue in the project
+                let dir = project.as_deref().unwrap_or(std::path::Path::new("."));
+                let result = match step {
+                    WrapupStep::Commit => {
+                        let default = format!(
+                            "Wrap up session {}",
+                            chrono::Local::now().format("%Y-%m-%d %H:%M")
+                        );
+                        let message = match &message {
+                            Some(message) => message.clone(),
+                            None if yes => default,
+                            None => Input::new()
+                                .with_prompt("Commit message")
+                                .default(default)
+                                .interact_text()?,
+                        };
+                        wrapup::commit_all(dir, &exe, &message)
+                    }
+                    WrapupStep::Push => std::process::Command::new(&exe)
+                        .arg("push")
+                        .current_dir(dir)
+                        .status()
+                        .map_err(anyhow::Error::from)
+                        .and_then(|status| {
+                            if status.success() {
+                                Ok(())
+                            } else {
+                                Err(anyhow::anyhow!("auxin push exited with {}", status))
+                            }
+                        }),
+                    WrapupStep::SyncQueue => {
+                        OfflineQueue::new().and_then(|mut q| q.sync_all()).and_then(|report| {
+                            if report.failed.is_empty() {
+                                Ok(())
+                            } else {
+                                Err(anyhow::anyhow!(
+                                    "{} operation(s) failed to sync",
+                                    report.failed.len()
+                                ))
+                            }
+                        })
+                    }
+                    WrapupStep::ReleaseLock => {
+                        quick_actions::run_auxin(&exe, dir, &["lock", "release"])
+                    }
+                };
+                match result {
+                    Ok(()) => progress::success(step.done()),
+                    Err(e) => {
+                        progress::error(&format!("Couldn't {}: {:#}", step.action(), e));
+                        failed = true;
+                    }
                 }
             }
 
-            // Execute push with progress tracking
-            progress::info("Starting push...");
+            let after = if took_steps {
+                println!();
+                WrapupCheck::run(project.as_deref())
+            } else {
+                before
+            };
+            let remaining = after.issues();
+            if remaining.is_empty() && !failed {
+                println!("{}", "✓ Safe to shut down".green().bold());
+                println!();
+                return Ok(());
+            }
 
-            match manager.upload_with_progress(
-                &current_dir,
-                &remote_name,
-                &branch_name,
-                |_progress| {
-                    // Progress callback - could be used for real-time display in future
-                },
-            ) {
-                Ok(result) => {
-                    println!();
+            println!("{}", "✗ Not safe to shut down yet".red().bold());
+            for issue in &remaining {
+                println!("  {} {}", "•".red(), issue.describe());
+            }
+            if remaining.is_empty() {
+                println!("  {} A step above failed", "•".red());
+            } else if check || !(yes || interactive) {
+                println!("\n  Run {} to finish up", "auxin wrapup".cyan());
+            }
+            println!();
+            std::process::exit(1);
+        }
 
-                    if is_resuming {
-                        progress::success("Upload resumed and completed successfully");
-                    } else {
-                        progress::success("Push completed successfully");
+        Commands::ReportIssue { output, all } => {
+            let reporter = CrashReporter::new();
+            let crashes = reporter.list_bundles()?.len();
+            let output = output.unwrap_or_else(|| {
+                PathBuf::from(format!(
+                    "auxin-report-{}.zip",
+                    chrono::Local::now().format("%Y%m%d-%H%M%S")
+                ))
+            });
+
+            let pb = progress::spinner("Collecting diagnostics...");
+            match reporter.package_report(&output, all) {
+                Ok(path) => {
+                    progress::finish_success(&pb, "Diagnostics packaged");
+                    println!("  {} {}", "Report:".bold(), path.display());
+                    match (crashes, all) {
+                        (0, _) => println!("  No crash reports found; included current diagnostics"),
+                        (n, true) => println!("  Included {} crash report(s)", n),
+                        (_, false) => println!("  Included the latest crash report"),
                     }
+                    println!();
+                    progress::info("Secrets are redacted, but please review the zip before sharing");
+                }
+                Err(e) => {
+                    progress::finish_error(&pb, "Failed to package diagnostics");
+                    println!("  Crash reports are in {}", reporter.crash_dir().display());
+                    return Err(e);
+                }
+            }
 
-                    // Show statistics
-                    if result.bytes_uploaded > 0 {
-                        let size_mb = result.bytes_uploaded as f64 / (1024.0 * 1024.0);
-                        let duration_secs = result.duration.as_secs_f64();
-                        let speed_mbps = if duration_secs > 0.0 {
-                            size_mb / duration_secs
-                        } else {
-                            0.0
-                        };
+            Ok(())
+        }
 
-                        println!();
-                        println!("  {} Uploaded: {:.2} MB", "•".dimmed(), size_mb);
-                        println!("  {} Duration: {:.1}s", "•".dimmed(), duration_secs);
-                        println!("  {} Speed: {:.2} MB/s", "•".dimmed(), speed_mbps);
+        Commands::Migrate {
+            to,
+            token,
+            repoint_origin,
+            no_source,
+            author_map,
+            dry_run,
+        } => {
+            let repo_root = std::env::current_dir()?;
+            if !repo_root.join(".oxen").exists() {
+                anyhow::bail!("Not an Oxen repository. Run this from the repository's root.");
+            }
 
-                        if result.files_uploaded > 0 {
-                            println!("  {} Files: {}", "•".dimmed(), result.files_uploaded);
-                        }
-                    }
+            let target = MigrationTarget::parse(&to)?;
+            let config = auxin_config::load_config().unwrap_or_default();
+            let configured_token = auxin::credential_store::server_token(&config);
 
-                    println!();
-                    progress::info(&format!(
-                        "Branch '{}' pushed to '{}'",
-                        branch_name, remote_name
-                    ));
+            let source = if no_source {
+                None
+            } else {
+                let pb = progress::spinner("Checking source server...");
+                let source = MigrationSource::from_config(&config, &repo_root)?;
+                match &source {
+                    Some(s) => progress::finish_success(
+                        &pb,
+                        &format!("Source: {}/{} on {}", s.namespace, s.repo_name, config.cli.url),
+                    ),
+                    None => progress::finish_info(
+                        &pb,
+                        "No source server; metadata will be read from commit messages",
+                    ),
+                }
+                source
+            };
+            if source.as_ref().is_some_and(|s| {
+                config.cli.url.trim_end_matches('/') == target.server_url
+                    && s.namespace == target.namespace
+                    && s.repo_name == target.repo_name
+            }) {
+                anyhow::bail!("The target is the repository's current server location");
+            }
 
-                    Ok(())
+            let timeout_secs = config.cli.timeout_secs as u64;
+            let mut migrator = RepoMigrator::new(
+                &repo_root,
+                target,
+                token.or(configured_token),
+                timeout_secs,
+                source,
+            )?;
+            if let Some(path) = author_map {
+                migrator = migrator.with_author_map(AuthorMap::from_file(&path)?);
+            }
+
+            let pb = progress::spinner("Collecting repository data...");
+            let plan = match migrator.plan() {
+                Ok(plan) => {
+                    pb.finish_and_clear();
+                    plan
                 }
                 Err(e) => {
-                    println!();
-                    progress::error(&format!("Push failed: {}", e));
-
-                    // Provide recovery hints
-                    println!();
-                    progress::info("To retry, run the same command again");
-                    progress::info("Your progress has been saved and will resume automatically");
+                    progress::finish_error(&pb, "Failed to collect repository data");
+                    return Err(e);
+                }
+            };
 
-                    // Check if it's a network error
-                    let error_str = e.to_string().to_lowercase();
-                    if error_str.contains("network")
-                        || error_str.contains("connection")
-                        || error_str.contains("timeout")
-                    {
-                        println!();
-                        progress::info("Network issue detected. Check your connection and retry.");
-                        progress::info("For recovery help: auxin recovery push");
-                    } else if error_str.contains("auth")
-                        || error_str.contains("permission")
-                        || error_str.contains("401")
-                        || error_str.contains("403")
-                    {
-                        println!();
-                        progress::info("Authentication issue. Try: auxin login");
-                    }
+            println!();
+            println!("{} {}", "Migrating to".bold(), migrator.target().remote_url());
+            println!("  Branches:     {}", plan.branches.len());
+            println!("  Commits:      {}", plan.local_commits);
+            println!("  Metadata:     {}", plan.metadata.len());
+            println!("  Bounces:      {}", plan.bounces.len());
+            println!("  Comments:     {}", plan.comment_count());
+            if migrator.has_source() {
+                println!("  Lock events:  {}", plan.lock_event_count());
+            }
+            if !plan.tags.is_empty() {
+                println!("  Tags:         {} (not copied)", plan.tags.len());
+            }
+            println!();
 
-                    Err(e)
+            if dry_run {
+                for branch in &plan.branches {
+                    println!("  {} {}", "→".cyan(), branch);
                 }
+                println!();
+                progress::info("Dry run: nothing migrated");
+                return Ok(());
             }
-        }
-
-        Commands::Doctor => {
-            println!("\n{}", "Auxin Doctor - Environment Check".cyan().bold());
-            println!("{}", "=".repeat(40).dimmed());
 
-            let mut all_good = true;
+            let pb = progress::spinner("Preparing target repository...");
+            match migrator.create_target() {
+                Ok(true) => progress::finish_success(&pb, "Created target repository"),
+                Ok(false) => progress::finish_info(&pb, "Target repository already exists"),
+                Err(e) => {
+                    progress::finish_error(&pb, "Failed to create target repository");
+                    return Err(e);
+                }
+            }
 
-            // 1. Check Oxen CLI
-            print!("\n{} ", "Checking Oxen CLI...".cyan());
-            let subprocess = auxin::OxenSubprocess::new();
-            match subprocess.version() {
-                Ok(version) => {
-                    println!("{} {}", "✓".green(), version.trim());
+            migrator.add_remote()?;
+            let pb = progress::progress_bar(plan.branches.len() as u64, "Pushing branches");
+            let mut push_failures = Vec::new();
+            for branch in &plan.branches {
+                pb.set_message(format!("Pushing {}", branch));
+                if let Err(e) = migrator.push_branch(branch) {
+                    push_failures.push((branch, e));
                 }
-                Err(_) => {
-                    println!("{} {}", "✗".red(), "Not found");
-                    println!("  {} Install with: pip install oxen-ai", "→".yellow());
-                    all_good = false;
+                pb.inc(1);
+            }
+            if let Err(e) = migrator.remove_remote() {
+                progress::warning(&format!(
+                    "Failed to remove the '{}' remote: {}",
+                    repo_migration::MIGRATION_REMOTE,
+                    e
+                ));
+            }
+            if !push_failures.is_empty() {
+                progress::finish_error(
+                    &pb,
+                    &format!("{} of {} branches failed", push_failures.len(), plan.branches.len()),
+                );
+                for (branch, e) in &push_failures {
+                    progress::warning(&format!("{}: {}", branch, e));
                 }
+                progress::info("Fix the problem and run the command again to resume");
+                anyhow::bail!("Migration incomplete");
             }
+            progress::finish_success(&pb, &format!("Pushed {} branches", plan.branches.len()));
 
-            // 2. Check if in a repository
-            let current_dir = std::env::current_dir()?;
-            let oxen_dir = current_dir.join(".oxen");
-
-            print!("{} ", "Checking repository...".cyan());
-            if oxen_dir.exists() {
-                println!("{} {}", "✓".green(), "Oxen repository found");
-
-                // 3. Check for remotes
-                print!("{} ", "Checking remotes...".cyan());
-                match subprocess.remote_list(&current_dir) {
-                    Ok(remotes) if !remotes.is_empty() => {
-                        println!("{}", "✓".green());
-                        for (name, url) in &remotes {
-                            println!("  {} {} → {}", "•".dimmed(), name.cyan(), url);
-                        }
-                    }
-                    Ok(_) => {
-                        println!("{} {}", "⚠".yellow(), "No remotes configured");
-                        println!("  {} Add with: auxin remote add origin <URL>", "→".yellow());
-                        all_good = false;
-                    }
+            if !plan.metadata.is_empty() {
+                let pb = progress::spinner("Uploading metadata...");
+                let report = match migrator.upload_metadata(&plan.metadata) {
+                    Ok(report) => report,
                     Err(e) => {
-                        println!("{} {}", "✗".red(), e);
-                        all_good = false;
+                        progress::finish_error(&pb, "Metadata upload failed");
+                        return Err(e);
+                    }
+                };
+                if report.is_complete() {
+                    progress::finish_success(
+                        &pb,
+                        &format!("Uploaded metadata for {} commits", report.stored.len()),
+                    );
+                } else {
+                    progress::finish_error(
+                        &pb,
+                        &format!("{} metadata uploads failed", report.failed.len()),
+                    );
+                    for failure in &report.failed {
+                        progress::warning(&format!("{}: {}", failure.commit_id, failure.error));
                     }
                 }
+            }
 
-                // 4. Check current branch
-                print!("{} ", "Checking branch...".cyan());
-                match subprocess.current_branch(&current_dir) {
-                    Ok(branch) => {
-                        println!("{} {}", "✓".green(), branch);
+            if !plan.bounces.is_empty() {
+                let pb = progress::progress_bar(plan.bounces.len() as u64, "Uploading bounces");
+                let mut failed = Vec::new();
+                for bounce in &plan.bounces {
+                    if let Err(e) = migrator.upload_bounce(bounce) {
+                        failed.push((&bounce.commit_id, e));
                     }
-                    Err(e) => {
-                        println!("{} {}", "✗".red(), e);
-                        all_good = false;
+                    pb.inc(1);
+                }
+                if failed.is_empty() {
+                    progress::finish_success(
+                        &pb,
+                        &format!("Uploaded {} bounces", plan.bounces.len()),
+                    );
+                } else {
+                    progress::finish_error(&pb, &format!("{} bounce uploads failed", failed.len()));
+                    for (commit_id, e) in &failed {
+                        progress::warning(&format!("{}: {}", commit_id, e));
                     }
                 }
-            } else {
-                println!("{} {}", "⚠".yellow(), "Not in an Oxen repository");
-                println!("  {} Initialize with: auxin init <path>", "→".yellow());
             }
 
-            // 5. Check authentication (try to see if we can list remotes without error)
-            print!("{} ", "Checking authentication...".cyan());
-            // We can't easily check auth status without trying to connect
-            // For now, just note that auth can be set up
-            println!(
-                "{} {}",
-                "?".yellow(),
-                "Run 'auxin auth login' to authenticate"
-            );
+            if !plan.activities.is_empty() {
+                let pb = progress::spinner("Importing comments and lock history...");
+                match migrator.import_activity(&plan.activities) {
+                    Ok(report) => progress::finish_success(
+                        &pb,
+                        &format!(
+                            "Imported {} activities ({} already present)",
+                            report.imported, report.skipped
+                        ),
+                    ),
+                    Err(e) => {
+                        progress::finish_error(&pb, "Activity import failed");
+                        return Err(e);
+                    }
+                }
+            }
 
-            // Summary
-            println!("\n{}", "─".repeat(40).dimmed());
-            if all_good {
+            let pb = progress::spinner("Verifying target...");
+            let verification = match migrator.verify(&plan) {
+                Ok(verification) => verification,
+                Err(e) => {
+                    progress::finish_error(&pb, "Verification failed");
+                    return Err(e);
+                }
+            };
+            if !verification.is_complete() {
+                progress::finish_error(&pb, "Target doesn't match the local repository");
                 println!(
-                    "{}",
-                    "All checks passed! You're ready to use Auxin."
-                        .green()
-                        .bold()
+                    "  Commits on {}: {} local, {} on target",
+                    verification.branch, verification.local_commits, verification.target_commits
                 );
+                for branch in &verification.missing_branches {
+                    println!("  Missing branch: {}", branch);
+                }
+                println!();
+                progress::info("Origin was left unchanged. Run the command again to retry.");
+                anyhow::bail!("Migration verification failed");
+            }
+            progress::finish_success(
+                &pb,
+                &format!("Verified {} commits on the target", verification.target_commits),
+            );
+
+            if !plan.tags.is_empty() {
+                progress::warning(&format!(
+                    "Tags were not copied, recreate them on the target: {}",
+                    plan.tags.join(", ")
+                ));
+            }
+
+            let remote_url = migrator.target().remote_url();
+            if repoint_origin {
+                match migrator.repoint_origin()? {
+                    Some(previous) => progress::success(&format!(
+                        "origin → {} (previous origin kept as '{}': {})",
+                        remote_url,
+                        repo_migration::PREVIOUS_ORIGIN_REMOTE,
+                        previous
+                    )),
+                    None => progress::success(&format!("origin → {}", remote_url)),
+                }
             } else {
-                println!("{}", "Some issues found. See suggestions above.".yellow());
+                println!();
+                progress::info(&format!(
+                    "To switch over, run 'auxin remote add origin {}'",
+                    remote_url
+                ));
             }
-            println!();
 
             Ok(())
         }
 
+        Commands::MigrateLegacy { path, dry_run } => {
+            let project = match path {
+                Some(path) => path,
+                None => std::env::current_dir()?,
+            };
+            let migration = legacy_migration::LegacyMigration::for_current_user(Some(project))?;
+            if !migration.is_needed() {
+                progress::info("No legacy .oxenvcs folders found");
+                return Ok(());
+            }
+
+            if dry_run {
+                for step in migration.plan()? {
+                    let verb = match step.action {
+                        legacy_migration::MigrationAction::RewriteConfig => "rewrite",
+                        legacy_migration::MigrationAction::ConvertComments => "convert",
+                        legacy_migration::MigrationAction::Copy => "move",
+                        legacy_migration::MigrationAction::Skip => "skip (exists)",
+                    };
+                    println!(
+                        "  {:<14} {} → {}",
+                        verb,
+                        step.from.display(),
+                        step.to.display()
+                    );
+                }
+                println!();
+                progress::info("Dry run: nothing was changed");
+                return Ok(());
+            }
+
+            print_legacy_migration_report(&migration.run()?);
+            Ok(())
+        }
+
+        Commands::MigrateState { path, dry_run } => {
+            let project = match path {
+                Some(path) => path,
+                None => std::env::current_dir()?,
+            };
+            let mut changed = false;
+            for state in state_dirs(&project) {
+                let plan = state.plan()?;
+                for (store, version) in &plan.newer {
+                    warn!(
+                        "{} is in format {}, newer than this auxin knows ({}); upgrade auxin",
+                        state.dir.join(store.relative_path()).display(),
+                        version,
+                        store.current_version()
+                    );
+                }
+                if plan.is_current() {
+                    continue;
+                }
+                changed = true;
+                println!("{}", state.dir.display().to_string().bold());
+                for step in &plan.steps {
+                    println!(
+                        "  {:<16} {} → {}  {}",
+                        step.store.name(),
+                        step.from,
+                        step.to,
+                        step.description.dimmed()
+                    );
+                }
+                if plan.steps.is_empty() {
+                    println!("  {}", "record format versions".dimmed());
+                }
+                if dry_run {
+                    continue;
+                }
+                match state.migrate(&plan)? {
+                    Some(backup) => success!("Migrated (backup in {})", backup.display()),
+                    None => success!("Migrated"),
+                }
+            }
+            if !changed {
+                progress::info("Everything is in the current format");
+            } else if dry_run {
+                println!();
+                progress::info("Dry run: nothing was changed");
+            }
+            Ok(())
+        }
+
         Commands::Remote(cmd) => {
             let current_dir = std::env::current_dir()?;
             let subprocess = auxin::OxenSubprocess::new();
@@ -5731,12 +14104,172 @@ async fn main() -> anyhow::Result<()> {
             Ok(())
         }
 
-        // TODO: Implement these command handlers
-        Commands::History(_)
-        | Commands::Workflow(_)
-        | Commands::Snapshot(_)
-        | Commands::Recovery(_) => {
-            anyhow::bail!("This command is not yet implemented")
+        Commands::Admin { command, token } => {
+            let config = auxin_config::load_config().unwrap_or_default();
+            let mut server_config = ServerConfig::from_config(&config);
+            server_config.token = token.or(server_config.token);
+            if server_config.token.is_none() {
+                anyhow::bail!(
+                    "auxin admin needs an Admin token: pass --token or set 'token' under [cli]"
+                );
+            }
+            let client = AuxinServerClient::new(server_config)?;
+            run_admin_command(&client, command)
+        }
+
+        Commands::History(command) => {
+            use auxin::OperationHistoryManager;
+
+            let manager = OperationHistoryManager::new();
+            match command {
+                HistoryCommands::View { limit, repo } => {
+                    let repo = repo.map(|repo| repo.canonicalize().unwrap_or(repo));
+                    manager.display_recent(limit, repo.as_deref())?;
+                }
+                HistoryCommands::Export { output } => {
+                    manager.export_csv(&output)?;
+                    success!(
+                        "Exported {} operations to {}",
+                        manager.load_history()?.len(),
+                        output.display()
+                    );
+                }
+                HistoryCommands::Stats => manager.display_stats()?,
+                HistoryCommands::Audit { flush } => {
+                    match manager.audit() {
+                        None => {
+                            progress::info(
+                                "No audit sinks configured (set [audit] sinks in config.toml)",
+                            )
+                        }
+                        Some(audit) if flush => {
+                            let mut failed = false;
+                            for delivery in audit.flush() {
+                                match &delivery.error {
+                                    None => println!(
+                                        "{} {:<8} {} delivered",
+                                        "✓".green(),
+                                        delivery.sink,
+                                        delivery.delivered
+                                    ),
+                                    Some(error) => {
+                                        failed = true;
+                                        println!(
+                                            "{} {:<8} {} delivered, {} waiting: {}",
+                                            "✗".red(),
+                                            delivery.sink,
+                                            delivery.delivered,
+                                            delivery.pending,
+                                            error
+                                        );
+                                    }
+                                }
+                            }
+                            if failed {
+                                anyhow::bail!("Some audit entries couldn't be delivered");
+                            }
+                        }
+                        Some(audit) => {
+                            for (sink, pending) in audit.pending() {
+                                println!("{:<8} {} waiting", sink, pending);
+                            }
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Workflow(command) => {
+            use auxin::WorkflowAutomation;
+
+            let workflow = WorkflowAutomation::new();
+            match command {
+                WorkflowCommands::Suggest { path } => {
+                    let path = match path {
+                        Some(path) => path,
+                        None => std::env::current_dir()?,
+                    };
+                    workflow.display_suggestions(&path)?;
+                }
+                WorkflowCommands::LockDaemon { path } => {
+                    if !workflow.config().auto_renew_locks {
+                        anyhow::bail!(
+                            "Auto-lock renewal is disabled in {}",
+                            auxin::WorkflowConfig::default_path().display()
+                        );
+                    }
+                    workflow.run_lock_renewal_daemon(&path)?;
+                }
+                WorkflowCommands::Config => workflow.display_config(),
+            }
+            Ok(())
+        }
+
+        Commands::Snapshot(command) => {
+            use auxin::{BackupRecoveryManager, Snapshot, SnapshotType};
+
+            let manager = BackupRecoveryManager::new();
+            match command {
+                SnapshotCommands::Create { path, description } => {
+                    let path = path
+                        .canonicalize()
+                        .with_context(|| format!("No repository at {}", path.display()))?;
+                    let mut snapshot = Snapshot::new(SnapshotType::Manual, &path)
+                        .with_description(description.unwrap_or_default());
+                    match OxenSubprocess::new().log(&path, Some(1)) {
+                        Ok(commits) => {
+                            if let Some(commit) = commits.first() {
+                                snapshot = snapshot.with_commit_id(&commit.id);
+                            }
+                        }
+                        Err(e) => vlog!("No commit recorded in snapshot: {}", e),
+                    }
+                    let snapshot = manager.create_snapshot(snapshot)?;
+                    manager.cleanup_old_snapshots()?;
+                    success!("Created snapshot {}", snapshot.id);
+                    if snapshot.commit_id.is_none() {
+                        progress::warning(
+                            "No commit found; the snapshot can't be restored automatically",
+                        );
+                    }
+                }
+                SnapshotCommands::List { all, repo } => {
+                    let repo = repo.map(|repo| repo.canonicalize().unwrap_or(repo));
+                    let limit = if all { None } else { Some(20) };
+                    manager.display_snapshots(limit, repo.as_deref())?;
+                }
+                SnapshotCommands::Restore { snapshot_id } => {
+                    println!();
+                    for line in manager.get_restore_instructions(&snapshot_id)? {
+                        println!("{}", line);
+                    }
+                    println!();
+                }
+                SnapshotCommands::Delete { snapshot_id } => {
+                    manager.delete_snapshot(&snapshot_id)?;
+                    success!("Deleted snapshot {}", snapshot_id);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Recovery(command) => {
+            let scenario = match command {
+                RecoveryCommands::Push => "push",
+                RecoveryCommands::Pull => "pull",
+                RecoveryCommands::Lock => "lock",
+            };
+            auxin::RecoveryHelper::display_recovery_guide(scenario);
+            Ok(())
+        }
+    };
+
+    if passive_upgrade_check {
+        let config = Config::load().unwrap_or_default();
+        if let Some(notice) = auxin::upgrade_check::passive_notice(&config) {
+            eprintln!("\n{} {}", "↑".cyan(), notice);
         }
     }
+    result
 }
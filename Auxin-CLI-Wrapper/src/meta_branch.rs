@@ -0,0 +1,318 @@
+//! Versioning auxin's own data on an `auxin-meta` branch
+//!
+//! Metadata edits, comments and bounce manifests normally live only in the
+//! local `.auxin/` and `.oxen/` folders. With `[meta_branch] enabled = true`
+//! they're also committed to a dedicated branch (`auxin-meta` by default) on
+//! the project's remote, so a fresh clone can bring them back.
+//!
+//! The branch is kept in a small repo of its own, `.auxin/meta-repo`, so
+//! syncing never checks anything out in the project itself. Each sync:
+//!
+//! 1. pulls the branch, bringing in what teammates synced
+//! 2. merges each file with the local copy: JSON objects key by key, lists
+//!    as a union, the local value winning where both set the same key
+//! 3. writes the merged files back into the project and the meta repo
+//! 4. commits and pushes
+//!
+//! Because lists are merged as a union, deleting a comment or bounce
+//! locally doesn't remove it from the branch.
+
+use anyhow::{Context, Result};
+use auxin_config::MetaBranch;
+use auxin_oxen::OxenSubprocess;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What's synced: a file or folder of JSON files in the project, and where
+/// it goes in the meta repo
+const ARTIFACTS: &[(&str, &str)] = &[
+    (".auxin/metadata_edits.json", "metadata_edits.json"),
+    (".oxen/comments", "comments"),
+    (".auxin/bounces", "bounces"),
+];
+
+/// Location of a project's meta repo
+pub fn meta_repo_path(project: &Path) -> PathBuf {
+    project.join(".auxin").join("meta-repo")
+}
+
+/// JSON files of every artifact under `root`, keyed by their path in the
+/// meta repo; `in_project` picks which side of `ARTIFACTS` to read
+fn artifact_files(root: &Path, in_project: bool) -> BTreeMap<String, PathBuf> {
+    let mut files = BTreeMap::new();
+    for (project_path, meta_path) in ARTIFACTS {
+        let path = root.join(if in_project { project_path } else { meta_path });
+        if path.is_file() {
+            files.insert(meta_path.to_string(), path);
+        } else if let Ok(entries) = fs::read_dir(&path) {
+            for entry in entries.flatten() {
+                let entry_path = entry.path();
+                if entry_path.extension().is_some_and(|ext| ext == "json") {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    files.insert(format!("{}/{}", meta_path, name), entry_path);
+                }
+            }
+        }
+    }
+    files
+}
+
+/// Where a meta repo path lives in the project
+fn project_path(project: &Path, meta_path: &str) -> Option<PathBuf> {
+    ARTIFACTS.iter().find_map(|(project_rel, meta_rel)| {
+        if meta_path == *meta_rel {
+            Some(project.join(project_rel))
+        } else {
+            meta_path
+                .strip_prefix(meta_rel)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .map(|name| project.join(project_rel).join(name))
+        }
+    })
+}
+
+/// Merge a teammate's version of a file with ours
+///
+/// Objects merge key by key, arrays keep every element of either side
+/// (theirs first), and anything else takes our value.
+pub fn merge_json(theirs: &Value, ours: &Value) -> Value {
+    match (theirs, ours) {
+        (Value::Object(theirs), Value::Object(ours)) => {
+            let mut merged = theirs.clone();
+            for (key, value) in ours {
+                let value = match theirs.get(key) {
+                    Some(their_value) => merge_json(their_value, value),
+                    None => value.clone(),
+                };
+                merged.insert(key.clone(), value);
+            }
+            Value::Object(merged)
+        },
+        (Value::Array(theirs), Value::Array(ours)) => {
+            let mut merged = theirs.clone();
+            for value in ours {
+                if !merged.contains(value) {
+                    merged.push(value.clone());
+                }
+            }
+            Value::Array(merged)
+        },
+        (_, ours) => ours.clone(),
+    }
+}
+
+/// What a sync did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Whether anything new was committed to the branch
+    pub committed: bool,
+    /// Project files updated with teammates' changes, relative to the project
+    pub updated_locally: Vec<String>,
+    /// Whether the branch reached the remote
+    pub pushed: bool,
+    /// Why pulling or pushing failed; the commit stays local until next time
+    pub remote_error: Option<String>,
+}
+
+/// Merge the artifacts of the project and its meta repo, writing the result
+/// to both; returns the project files that changed
+pub fn merge_artifacts(project: &Path, meta: &Path) -> Result<Vec<String>> {
+    let ours = artifact_files(project, true);
+    let theirs = artifact_files(meta, false);
+    let read = |path: &Path| -> Result<Value> {
+        let contents =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    };
+
+    let mut updated = Vec::new();
+    let paths: std::collections::BTreeSet<&String> = ours.keys().chain(theirs.keys()).collect();
+    for meta_path in paths {
+        let merged = match (theirs.get(meta_path), ours.get(meta_path)) {
+            (Some(their_file), Some(our_file)) => merge_json(&read(their_file)?, &read(our_file)?),
+            (Some(file), None) | (None, Some(file)) => read(file)?,
+            (None, None) => continue,
+        };
+        let contents = serde_json::to_string_pretty(&merged)?;
+
+        let in_meta = meta.join(meta_path);
+        if fs::read_to_string(&in_meta).ok().as_deref() != Some(contents.as_str()) {
+            if let Some(parent) = in_meta.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&in_meta, &contents)
+                .with_context(|| format!("Failed to write {}", in_meta.display()))?;
+        }
+
+        let Some(in_project) = project_path(project, meta_path) else {
+            continue;
+        };
+        let current = ours.get(meta_path).and_then(|path| read(path).ok());
+        if current.as_ref() != Some(&merged) {
+            if let Some(parent) = in_project.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&in_project, &contents)
+                .with_context(|| format!("Failed to write {}", in_project.display()))?;
+            let relative = in_project.strip_prefix(project).unwrap_or(&in_project);
+            updated.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(updated)
+}
+
+/// `.oxenignore` entry keeping the meta repo out of the project's commits
+const IGNORE_ENTRY: &str = ".auxin/meta-repo/";
+
+/// Create the meta repo on first use, pointing at the project's remote
+fn ensure_repo(
+    project: &Path,
+    meta: &Path,
+    config: &MetaBranch,
+    oxen: &OxenSubprocess,
+) -> Result<()> {
+    if meta.join(".oxen").is_dir() {
+        return Ok(());
+    }
+    fs::create_dir_all(meta)?;
+    oxen.init(meta).context("Failed to create the metadata repository")?;
+
+    let ignore = project.join(".oxenignore");
+    let mut rules = fs::read_to_string(&ignore).unwrap_or_default();
+    if !rules.lines().any(|line| line.trim() == IGNORE_ENTRY) {
+        if !rules.is_empty() && !rules.ends_with('\n') {
+            rules.push('\n');
+        }
+        rules.push_str(IGNORE_ENTRY);
+        rules.push('\n');
+        fs::write(&ignore, rules)
+            .with_context(|| format!("Failed to update {}", ignore.display()))?;
+    }
+
+    let url = oxen
+        .remote_list(project)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(name, _)| *name == config.remote)
+        .map(|(_, url)| url);
+    if let Some(url) = url {
+        oxen.remote_add(meta, &config.remote, &url)?;
+    }
+    Ok(())
+}
+
+/// Commit the project's metadata to the meta branch and push it
+pub fn sync(project: &Path, config: &MetaBranch, oxen: &OxenSubprocess) -> Result<SyncReport> {
+    let meta = meta_repo_path(project);
+    ensure_repo(project, &meta, config, oxen)?;
+    let has_remote = oxen
+        .remote_list(&meta)
+        .unwrap_or_default()
+        .iter()
+        .any(|(name, _)| *name == config.remote);
+
+    let mut report = SyncReport::default();
+    if has_remote {
+        // Fails harmlessly until someone has pushed the branch
+        if let Err(e) = oxen.pull_from(&meta, &config.remote, &config.branch) {
+            report.remote_error = Some(e.to_string());
+        }
+    }
+
+    report.updated_locally = merge_artifacts(project, &meta)?;
+
+    let status = oxen.status(&meta)?;
+    if !(status.modified.is_empty() && status.untracked.is_empty() && status.staged.is_empty()) {
+        oxen.add_all(&meta)?;
+        let user = crate::lock_integration::get_user_identifier();
+        oxen.commit(&meta, &format!("Sync auxin metadata from {}", user))?;
+        report.committed = true;
+    }
+    let on_branch = oxen.current_branch(&meta).ok().as_deref() == Some(config.branch.as_str());
+    if !on_branch {
+        let exists = oxen
+            .list_branches(&meta)
+            .unwrap_or_default()
+            .iter()
+            .any(|b| b.name == config.branch);
+        if exists {
+            oxen.checkout(&meta, &config.branch)?;
+        } else {
+            oxen.create_branch(&meta, &config.branch)?;
+        }
+    }
+
+    if has_remote {
+        match oxen.push(&meta, Some(&config.remote), Some(&config.branch)) {
+            Ok(()) => report.pushed = true,
+            Err(e) => report.remote_error = Some(e.to_string()),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_merge_json() {
+        let theirs = json!({
+            "edits": {"abc": {"bpm": 120}},
+            "audit": [{"field": "bpm", "user": "sam"}],
+            "version": 1
+        });
+        let ours = json!({
+            "edits": {"abc": {"key": "A Minor"}, "def": {"bpm": 90}},
+            "audit": [{"field": "key", "user": "alex"}, {"field": "bpm", "user": "sam"}],
+            "version": 2
+        });
+        assert_eq!(
+            merge_json(&theirs, &ours),
+            json!({
+                "edits": {"abc": {"bpm": 120, "key": "A Minor"}, "def": {"bpm": 90}},
+                "audit": [{"field": "bpm", "user": "sam"}, {"field": "key", "user": "alex"}],
+                "version": 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_artifacts() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Song.logicx");
+        let meta = meta_repo_path(&project);
+        fs::create_dir_all(project.join(".oxen/comments")).unwrap();
+        fs::create_dir_all(meta.join("comments")).unwrap();
+        fs::create_dir_all(meta.join("bounces")).unwrap();
+
+        // Ours: a comment on abc; theirs: another comment on abc and a bounce
+        fs::write(
+            project.join(".oxen/comments/abc.json"),
+            r#"[{"author": "alex", "text": "Louder vox"}]"#,
+        )
+        .unwrap();
+        fs::write(
+            meta.join("comments/abc.json"),
+            r#"[{"author": "sam", "text": "Love the bridge"}]"#,
+        )
+        .unwrap();
+        fs::write(meta.join("bounces/abc.json"), r#"{"commit_id": "abc"}"#).unwrap();
+
+        let updated = merge_artifacts(&project, &meta).unwrap();
+        assert_eq!(updated, [".auxin/bounces/abc.json", ".oxen/comments/abc.json"]);
+        let comments: Value =
+            serde_json::from_str(&fs::read_to_string(meta.join("comments/abc.json")).unwrap())
+                .unwrap();
+        assert_eq!(comments.as_array().unwrap().len(), 2);
+        assert!(project.join(".auxin/bounces/abc.json").exists());
+
+        // Nothing new the second time
+        assert!(merge_artifacts(&project, &meta).unwrap().is_empty());
+    }
+}
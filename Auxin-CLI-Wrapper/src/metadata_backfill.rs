@@ -0,0 +1,243 @@
+//! Retroactive upload of commit metadata to auxin-server
+//!
+//! Older commits only carry metadata embedded in their messages
+//! (`BPM: 120`, `Key: A Minor`, ...). The backfill walks local history,
+//! parses that legacy format, and uploads structured metadata for every
+//! commit the server doesn't know about yet.
+//!
+//! Progress is recorded in `.auxin/backfill_state.json` after each batch so
+//! an interrupted run resumes where it stopped.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commit_metadata::CommitMetadata;
+use crate::server_client::{LogicProMetadata, MetadataBatchEntry};
+use crate::CommitInfo;
+
+/// Persistent resume state for a backfill run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackfillState {
+    /// Server the state belongs to (a different server starts over)
+    pub server_url: String,
+
+    /// Repository namespace on the server
+    pub namespace: String,
+
+    /// Repository name on the server
+    pub repo_name: String,
+
+    /// Commits already uploaded by a previous run
+    pub uploaded: HashSet<String>,
+}
+
+impl BackfillState {
+    /// Location of the state file within a repository
+    pub fn file_path(repo_root: &Path) -> PathBuf {
+        repo_root.join(".auxin").join("backfill_state.json")
+    }
+
+    /// Load state for the given target, starting fresh if none matches
+    pub fn load(repo_root: &Path, server_url: &str, namespace: &str, repo_name: &str) -> Self {
+        let fresh = Self {
+            server_url: server_url.to_string(),
+            namespace: namespace.to_string(),
+            repo_name: repo_name.to_string(),
+            uploaded: HashSet::new(),
+        };
+
+        let path = Self::file_path(repo_root);
+        let Ok(content) = fs::read_to_string(&path) else {
+            return fresh;
+        };
+
+        match serde_json::from_str::<Self>(&content) {
+            Ok(state)
+                if state.server_url == server_url
+                    && state.namespace == namespace
+                    && state.repo_name == repo_name =>
+            {
+                state
+            }
+            _ => fresh,
+        }
+    }
+
+    /// Persist state to disk
+    pub fn save(&self, repo_root: &Path) -> Result<()> {
+        let path = Self::file_path(repo_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create .auxin directory")?;
+        }
+
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize state")?;
+        fs::write(&path, json).context("Failed to write backfill state")?;
+        Ok(())
+    }
+
+    /// Remove the state file (run completed or restart requested)
+    pub fn clear(repo_root: &Path) -> Result<()> {
+        let path = Self::file_path(repo_root);
+        if path.exists() {
+            fs::remove_file(&path).context("Failed to remove backfill state")?;
+        }
+        Ok(())
+    }
+}
+
+/// Extract structured server metadata from a legacy commit message
+///
+/// Returns `None` when the message carries no metadata worth uploading.
+pub fn metadata_from_message(message: &str) -> Option<LogicProMetadata> {
+    let parsed = CommitMetadata::parse_commit_message(message);
+
+    if parsed.bpm.is_none()
+        && parsed.sample_rate.is_none()
+        && parsed.key_signature.is_none()
+        && parsed.tags.is_empty()
+    {
+        return None;
+    }
+
+    Some(LogicProMetadata {
+        bpm: parsed.bpm.map(|b| b as f64),
+        sample_rate: parsed.sample_rate,
+        key_signature: parsed.key_signature,
+        // Always send a list; the server rejects null tags
+        tags: Some(parsed.tags),
+        custom: None,
+    })
+}
+
+/// Summary of what a backfill run has to do
+#[derive(Debug, Default)]
+pub struct BackfillPlan {
+    /// Entries to upload, oldest commit first
+    pub entries: Vec<MetadataBatchEntry>,
+
+    /// Commits the server already has metadata for
+    pub already_on_server: usize,
+
+    /// Commits uploaded by an earlier, interrupted run
+    pub already_uploaded: usize,
+
+    /// Commits without any embedded metadata
+    pub without_metadata: usize,
+}
+
+/// Work out which commits still need uploading
+///
+/// `commits` is in log order (newest first); the plan uploads oldest first
+/// so an interrupted run leaves a contiguous prefix of history done.
+pub fn plan_backfill(
+    commits: &[CommitInfo],
+    on_server: &HashSet<String>,
+    state: &BackfillState,
+) -> BackfillPlan {
+    let mut plan = BackfillPlan::default();
+
+    for commit in commits.iter().rev() {
+        if on_server.contains(&commit.id) {
+            plan.already_on_server += 1;
+        } else if state.uploaded.contains(&commit.id) {
+            plan.already_uploaded += 1;
+        } else if let Some(metadata) = metadata_from_message(&commit.message) {
+            plan.entries.push(MetadataBatchEntry {
+                commit_id: commit.id.clone(),
+                metadata,
+            });
+        } else {
+            plan.without_metadata += 1;
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_metadata_from_message() {
+        let md = metadata_from_message("Verse done\n\nBPM: 128\nKey: A Minor\nTags: vocals, mix")
+            .unwrap();
+        assert_eq!(md.bpm, Some(128.0));
+        assert_eq!(md.key_signature.as_deref(), Some("A Minor"));
+        assert_eq!(md.tags, Some(vec!["vocals".to_string(), "mix".to_string()]));
+    }
+
+    #[test]
+    fn test_metadata_from_plain_message() {
+        assert!(metadata_from_message("Just a plain commit").is_none());
+    }
+
+    #[test]
+    fn test_plan_skips_known_commits() {
+        // Log order: newest first
+        let commits = vec![
+            commit("c4", "Plain"),
+            commit("c3", "Mix\n\nBPM: 120"),
+            commit("c2", "Arrangement\n\nBPM: 110"),
+            commit("c1", "Start\n\nBPM: 100"),
+        ];
+        let on_server: HashSet<String> = ["c1".to_string()].into_iter().collect();
+        let mut state = BackfillState::default();
+        state.uploaded.insert("c2".to_string());
+
+        let plan = plan_backfill(&commits, &on_server, &state);
+
+        assert_eq!(plan.entries.len(), 1);
+        assert_eq!(plan.entries[0].commit_id, "c3");
+        assert_eq!(plan.already_on_server, 1);
+        assert_eq!(plan.already_uploaded, 1);
+        assert_eq!(plan.without_metadata, 1);
+    }
+
+    #[test]
+    fn test_plan_orders_oldest_first() {
+        let commits = vec![commit("new", "B\n\nBPM: 2"), commit("old", "A\n\nBPM: 1")];
+        let plan = plan_backfill(&commits, &HashSet::new(), &BackfillState::default());
+
+        let ids: Vec<_> = plan.entries.iter().map(|e| e.commit_id.as_str()).collect();
+        assert_eq!(ids, vec!["old", "new"]);
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let mut state = BackfillState::load(temp.path(), "http://srv", "ns", "repo");
+        state.uploaded.insert("abc".to_string());
+        state.save(temp.path()).unwrap();
+
+        let loaded = BackfillState::load(temp.path(), "http://srv", "ns", "repo");
+        assert!(loaded.uploaded.contains("abc"));
+
+        BackfillState::clear(temp.path()).unwrap();
+        assert!(!BackfillState::file_path(temp.path()).exists());
+    }
+
+    #[test]
+    fn test_state_for_other_target_starts_fresh() {
+        let temp = TempDir::new().unwrap();
+        let mut state = BackfillState::load(temp.path(), "http://srv", "ns", "repo");
+        state.uploaded.insert("abc".to_string());
+        state.save(temp.path()).unwrap();
+
+        let other = BackfillState::load(temp.path(), "http://other", "ns", "repo");
+        assert!(other.uploaded.is_empty());
+        assert_eq!(other.server_url, "http://other");
+    }
+}
@@ -0,0 +1,512 @@
+//! Metadata edits for existing commits
+//!
+//! Commit metadata (BPM, key, sample rate, tags) lives in the commit message,
+//! and rewriting oxen history to fix a typo isn't an option. Edits are stored
+//! in `.auxin/metadata_edits.json` instead and applied on top of the message
+//! wherever history is read (`auxin log`, server sync):
+//!
+//! ```json
+//! {
+//!   "overrides": {
+//!     "8f3e2a1b...": { "key_signature": "D Minor", "tags": ["live", "mixed"] }
+//!   },
+//!   "audit": [
+//!     {
+//!       "commit_id": "8f3e2a1b...",
+//!       "field": "key",
+//!       "old": "D Major",
+//!       "new": "D Minor",
+//!       "user": "me@studio-mac",
+//!       "edited_at": "2026-10-16T14:02:11Z"
+//!     }
+//!   ],
+//!   "unsynced": ["8f3e2a1b..."]
+//! }
+//! ```
+//!
+//! Every edit is appended to `audit`; commits whose edits haven't reached
+//! auxin-server yet are listed in `unsynced`.
+
+use crate::commit_metadata::CommitMetadata;
+use crate::music_theory;
+use crate::server_client::{LogicProMetadata, MetadataBatchEntry};
+use crate::CommitInfo;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Fields replaced for one commit; `None` keeps the value from the message
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// One change requested by `metadata edit` / `bulk-edit`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataChange {
+    SetBpm(f32),
+    SetSampleRate(u32),
+    SetKey(String),
+    SetTags(Vec<String>),
+    AddTag(String),
+    RemoveTag(String),
+}
+
+impl MetadataChange {
+    /// Parse a `--set` argument: `bpm=120`, `sample_rate=48000`,
+    /// `key="D Minor"` or `tags=live,mixed`
+    pub fn parse_set(assignment: &str) -> Result<Self> {
+        let (field, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected field=value, got '{}'", assignment))?;
+        let value = value.trim().trim_matches('"');
+        let invalid = || anyhow!("Invalid value '{}' for {}", value, field.trim());
+
+        match field.trim() {
+            "bpm" => music_theory::parse_bpm(value).map(Self::SetBpm),
+            "sample_rate" | "sample-rate" => value
+                .parse()
+                .map(Self::SetSampleRate)
+                .map_err(|_| invalid()),
+            "key" => music_theory::parse_key(value).map(Self::SetKey),
+            "tags" => Ok(Self::SetTags(split_tags(value))),
+            other => bail!(
+                "Unknown field '{}' (expected bpm, sample_rate, key or tags)",
+                other
+            ),
+        }
+    }
+
+    fn apply(&self, metadata: &mut CommitMetadata) {
+        match self {
+            Self::SetBpm(bpm) => metadata.bpm = Some(*bpm),
+            Self::SetSampleRate(rate) => metadata.sample_rate = Some(*rate),
+            Self::SetKey(key) => metadata.key_signature = Some(key.clone()),
+            Self::SetTags(tags) => metadata.tags = tags.clone(),
+            Self::AddTag(tag) => {
+                if !metadata.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    metadata.tags.push(tag.clone());
+                }
+            },
+            Self::RemoveTag(tag) => metadata.tags.retain(|t| !t.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+/// Commit selector for `metadata bulk-edit`: `tag:live`, `key:D Minor`,
+/// `bpm:120` or `message:vocal`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataFilter {
+    Tag(String),
+    Key(String),
+    Bpm(f32),
+    Message(String),
+}
+
+impl FromStr for MetadataFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (field, value) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected field:value, got '{}'", s))?;
+        let value = value.trim().trim_matches('"');
+        match field.trim() {
+            "tag" => Ok(Self::Tag(value.to_string())),
+            "key" => Ok(Self::Key(value.to_string())),
+            "bpm" => value
+                .parse()
+                .map(Self::Bpm)
+                .map_err(|_| anyhow!("Invalid BPM '{}' in filter", value)),
+            "message" => Ok(Self::Message(value.to_string())),
+            other => bail!(
+                "Unknown filter '{}' (expected tag, key, bpm or message)",
+                other
+            ),
+        }
+    }
+}
+
+impl MetadataFilter {
+    pub fn matches(&self, metadata: &CommitMetadata) -> bool {
+        match self {
+            Self::Tag(tag) => metadata.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            Self::Key(key) => metadata
+                .key_signature
+                .as_ref()
+                .is_some_and(|k| music_theory::same_key(k, key)),
+            Self::Bpm(bpm) => metadata.bpm.is_some_and(|b| (b - bpm).abs() < 0.01),
+            Self::Message(text) => metadata
+                .message
+                .to_lowercase()
+                .contains(&text.to_lowercase()),
+        }
+    }
+}
+
+/// Audit trail entry for one changed field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataEdit {
+    pub commit_id: String,
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub user: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+/// Contents of `.auxin/metadata_edits.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataEdits {
+    #[serde(default)]
+    pub overrides: BTreeMap<String, MetadataOverride>,
+    #[serde(default)]
+    pub audit: Vec<MetadataEdit>,
+    /// Commits with edits not yet stored on the server
+    #[serde(default)]
+    pub unsynced: BTreeSet<String>,
+}
+
+impl MetadataEdits {
+    /// Location of the edits for a repo
+    pub fn path(repo: &Path) -> PathBuf {
+        repo.join(".auxin").join("metadata_edits.json")
+    }
+
+    /// Read a repo's edits; a missing file means none
+    pub fn load(repo: &Path) -> Result<Self> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace the repo's edits atomically
+    pub fn save(&self, repo: &Path) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Metadata for a commit: its message with any edits applied
+    pub fn effective(&self, commit: &CommitInfo) -> CommitMetadata {
+        self.apply(
+            &commit.id,
+            CommitMetadata::parse_commit_message(&commit.message),
+        )
+    }
+
+    /// `metadata` of a commit with any edits to it applied
+    pub fn apply(&self, commit_id: &str, mut metadata: CommitMetadata) -> CommitMetadata {
+        if let Some(edit) = self.overrides.get(commit_id) {
+            if let Some(bpm) = edit.bpm {
+                metadata.bpm = Some(bpm);
+            }
+            if let Some(rate) = edit.sample_rate {
+                metadata.sample_rate = Some(rate);
+            }
+            if let Some(key) = &edit.key_signature {
+                metadata.key_signature = Some(key.clone());
+            }
+            if let Some(tags) = &edit.tags {
+                metadata.tags = tags.clone();
+            }
+        }
+        metadata
+    }
+
+    /// Apply `changes` to a commit, returning the audit entries recorded
+    ///
+    /// Changes that leave a field as it was aren't recorded.
+    pub fn edit(
+        &mut self,
+        commit: &CommitInfo,
+        changes: &[MetadataChange],
+        user: &str,
+    ) -> Vec<MetadataEdit> {
+        let before = self.effective(commit);
+        let mut after = before.clone();
+        for change in changes {
+            change.apply(&mut after);
+        }
+
+        let now = Utc::now();
+        let fields = [
+            (
+                "bpm",
+                before.bpm.map(|v| v.to_string()),
+                after.bpm.map(|v| v.to_string()),
+            ),
+            (
+                "sample_rate",
+                before.sample_rate.map(|v| v.to_string()),
+                after.sample_rate.map(|v| v.to_string()),
+            ),
+            (
+                "key",
+                before.key_signature.clone(),
+                after.key_signature.clone(),
+            ),
+            ("tags", join_tags(&before.tags), join_tags(&after.tags)),
+        ];
+        let edits: Vec<MetadataEdit> = fields
+            .into_iter()
+            .filter(|(_, old, new)| old != new)
+            .map(|(field, old, new)| MetadataEdit {
+                commit_id: commit.id.clone(),
+                field: field.to_string(),
+                old,
+                new,
+                user: user.to_string(),
+                edited_at: now,
+            })
+            .collect();
+        if edits.is_empty() {
+            return edits;
+        }
+
+        let entry = self.overrides.entry(commit.id.clone()).or_default();
+        for edit in &edits {
+            match edit.field.as_str() {
+                "bpm" => entry.bpm = after.bpm,
+                "sample_rate" => entry.sample_rate = after.sample_rate,
+                "key" => entry.key_signature = after.key_signature.clone(),
+                _ => entry.tags = Some(after.tags.clone()),
+            }
+        }
+        self.audit.extend(edits.iter().cloned());
+        self.unsynced.insert(commit.id.clone());
+        edits
+    }
+
+    /// Rewrite the metadata lines of each edited commit's message so log
+    /// output and filters see the edited values
+    pub fn apply_to_history(&self, commits: &mut [CommitInfo]) {
+        for commit in commits.iter_mut() {
+            if self.overrides.contains_key(&commit.id) {
+                let metadata = self.effective(commit);
+                commit.message = with_metadata_lines(&commit.message, &metadata);
+            }
+        }
+    }
+
+    /// Server entries for commits whose edits haven't been synced
+    pub fn sync_entries(&self, commits: &[CommitInfo]) -> Vec<MetadataBatchEntry> {
+        commits
+            .iter()
+            .filter(|c| self.unsynced.contains(&c.id))
+            .map(|c| MetadataBatchEntry {
+                commit_id: c.id.clone(),
+                metadata: server_metadata(&self.effective(c)),
+            })
+            .collect()
+    }
+
+    pub fn mark_synced<'a>(&mut self, commit_ids: impl IntoIterator<Item = &'a String>) {
+        for id in commit_ids {
+            self.unsynced.remove(id);
+        }
+    }
+}
+
+/// Structured metadata as stored on auxin-server
+pub fn server_metadata(metadata: &CommitMetadata) -> LogicProMetadata {
+    LogicProMetadata {
+        bpm: metadata.bpm.map(|b| b as f64),
+        sample_rate: metadata.sample_rate,
+        key_signature: metadata.key_signature.clone(),
+        // Always send a list; the server rejects null tags
+        tags: Some(metadata.tags.clone()),
+        custom: None,
+    }
+}
+
+/// `message` with its BPM / Sample Rate / Key / Tags lines replaced by
+/// those of `metadata`; other lines are kept as they are
+fn with_metadata_lines(message: &str, metadata: &CommitMetadata) -> String {
+    const FIELDS: [&str; 4] = ["BPM:", "Sample Rate:", "Key:", "Tags:"];
+    let kept: Vec<&str> = message
+        .lines()
+        .filter(|line| !FIELDS.iter().any(|f| line.starts_with(f)))
+        .collect();
+    let body = kept.join("\n").trim_end().to_string();
+
+    // Custom field lines are kept where they are
+    let formatted = CommitMetadata {
+        message: String::new(),
+        custom: BTreeMap::new(),
+        ..metadata.clone()
+    }
+    .format_commit_message();
+    let fields = formatted.trim_start();
+    if fields.is_empty() {
+        body
+    } else {
+        format!("{}\n\n{}", body, fields)
+    }
+}
+
+fn split_tags(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn join_tags(tags: &[String]) -> Option<String> {
+    (!tags.is_empty()).then(|| tags.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_changes_and_filters() {
+        assert_eq!(
+            MetadataChange::parse_set("key=\"D Minor\"").unwrap(),
+            MetadataChange::SetKey("D Minor".to_string())
+        );
+        assert_eq!(
+            MetadataChange::parse_set("bpm=128").unwrap(),
+            MetadataChange::SetBpm(128.0)
+        );
+        assert_eq!(
+            MetadataChange::parse_set("tags=live, mixed").unwrap(),
+            MetadataChange::SetTags(vec!["live".to_string(), "mixed".to_string()])
+        );
+        assert!(MetadataChange::parse_set("bpm=fast").is_err());
+        assert!(MetadataChange::parse_set("tempo=120").is_err());
+        assert!(MetadataChange::parse_set("key").is_err());
+
+        assert_eq!(
+            "tag:live".parse::<MetadataFilter>().unwrap(),
+            MetadataFilter::Tag("live".to_string())
+        );
+        assert!("colour:red".parse::<MetadataFilter>().is_err());
+
+        let metadata = CommitMetadata::new("Vocal comp")
+            .with_bpm(120.0)
+            .with_tag("Live");
+        assert!(MetadataFilter::Tag("live".to_string()).matches(&metadata));
+        assert!(MetadataFilter::Bpm(120.0).matches(&metadata));
+        assert!(MetadataFilter::Message("vocal".to_string()).matches(&metadata));
+        assert!(!MetadataFilter::Key("D Minor".to_string()).matches(&metadata));
+    }
+
+    #[test]
+    fn test_edit_records_audit_and_overrides() {
+        let mut edits = MetadataEdits::default();
+        let c1 = commit("c1", "Take 3\n\nBPM: 120\nKey: D Major\nTags: live");
+
+        let recorded = edits.edit(
+            &c1,
+            &[
+                MetadataChange::SetKey("D Minor".to_string()),
+                MetadataChange::AddTag("mixed".to_string()),
+                MetadataChange::SetBpm(120.0),
+            ],
+            "me@studio",
+        );
+        let fields: Vec<&str> = recorded.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["key", "tags"]);
+        assert_eq!(recorded[0].old.as_deref(), Some("D Major"));
+        assert_eq!(recorded[1].new.as_deref(), Some("live, mixed"));
+        assert!(edits.unsynced.contains("c1"));
+
+        let metadata = edits.effective(&c1);
+        assert_eq!(metadata.key_signature.as_deref(), Some("D Minor"));
+        assert_eq!(metadata.tags, vec!["live", "mixed"]);
+        assert_eq!(metadata.bpm, Some(120.0));
+
+        // Re-applying the same change records nothing
+        assert!(edits
+            .edit(
+                &c1,
+                &[MetadataChange::AddTag("mixed".to_string())],
+                "me@studio"
+            )
+            .is_empty());
+        assert_eq!(edits.audit.len(), 2);
+
+        let entries = edits.sync_entries(&[c1.clone(), commit("c2", "Other")]);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].metadata.key_signature.as_deref(),
+            Some("D Minor")
+        );
+        edits.mark_synced(&["c1".to_string()]);
+        assert!(edits.unsynced.is_empty());
+    }
+
+    #[test]
+    fn test_apply_to_history_keeps_other_lines() {
+        let mut edits = MetadataEdits::default();
+        let original = commit("c1", "Model update\n\nBPM: 120\nUnits: Meters\nTags: draft");
+        edits.edit(
+            &original,
+            &[MetadataChange::RemoveTag("draft".to_string())],
+            "me",
+        );
+        edits.edit(
+            &original,
+            &[MetadataChange::SetKey("A Minor".to_string())],
+            "me",
+        );
+
+        let mut history = vec![original, commit("c2", "Untouched\n\nBPM: 90")];
+        edits.apply_to_history(&mut history);
+
+        assert_eq!(
+            history[0].message,
+            "Model update\n\nUnits: Meters\n\nBPM: 120\nKey: A Minor"
+        );
+        assert_eq!(history[1].message, "Untouched\n\nBPM: 90");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            MetadataEdits::load(temp.path()).unwrap(),
+            MetadataEdits::default()
+        );
+
+        let mut edits = MetadataEdits::default();
+        edits.edit(&commit("c1", "Mix"), &[MetadataChange::SetBpm(98.5)], "me");
+        edits.save(temp.path()).unwrap();
+
+        assert_eq!(MetadataEdits::load(temp.path()).unwrap(), edits);
+        assert!(temp.path().join(".auxin/metadata_edits.json").exists());
+    }
+}
@@ -0,0 +1,267 @@
+//! Commit metadata stored next to the repository
+//!
+//! Metadata has always been written into the commit message as a block of
+//! `Label: value` lines, and reading it back meant string matching on every
+//! `log`, `search` and `compare`. Each commit now also gets a JSON sidecar
+//! in `.oxen/auxin/metadata/<commit_id>.json`:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "commit_id": "8f3e2a1b...",
+//!   "source": "commit",
+//!   "metadata": { "message": "Vocal comp", "bpm": 120.0, "key_signature": "A Minor", ... },
+//!   "details": { "Layers": "4", "Units": "Meters" }
+//! }
+//! ```
+//!
+//! `details` holds the block's lines that aren't metadata fields, such as a
+//! SketchUp model's units.
+//! Sidecars aren't pushed with the repository, so commits made elsewhere or
+//! before this existed fall back to their message until `auxin metadata
+//! migrate` writes one for them (`"source": "message"`). Edits from
+//! [`metadata_edits`](crate::metadata_edits) still apply on top.
+//!
+//! Sidecars follow the stability policy in `tests/compat.rs`: a newer
+//! format only adds fields, so its sidecars still load, and the fields this
+//! build doesn't know are written back unchanged.
+
+use crate::commit_metadata::CommitMetadata;
+use crate::vlog;
+use crate::CommitInfo;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sidecar format version written by this build; raised when fields are added
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Block labels read into [`CommitMetadata`] rather than `details`
+const METADATA_LABELS: [&str; 4] = ["BPM", "Sample Rate", "Key", "Tags"];
+
+/// Where a sidecar's contents came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataSource {
+    /// Recorded by `auxin commit`
+    Commit,
+    /// Read from the message of an existing commit
+    Message,
+}
+
+/// Contents of one sidecar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMetadata {
+    pub version: u32,
+    pub commit_id: String,
+    pub source: MetadataSource,
+    pub metadata: CommitMetadata,
+    /// Other `Label: value` lines, e.g. "Units"
+    #[serde(default)]
+    pub details: BTreeMap<String, String>,
+    /// Fields added by newer sidecar formats
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl StoredMetadata {
+    /// Read the metadata block at the end of a commit message
+    pub fn from_message(commit_id: &str, message: &str, source: MetadataSource) -> Self {
+        let mut metadata = CommitMetadata::parse_commit_message(message);
+        let mut details = BTreeMap::new();
+        if let Some((body, block)) = metadata_block(message) {
+            metadata.message = body.trim().to_string();
+            for (label, value) in block {
+                if !METADATA_LABELS.contains(&label) && !metadata.custom.contains_key(label) {
+                    details.insert(label.to_string(), value.to_string());
+                }
+            }
+        }
+        Self {
+            version: FORMAT_VERSION,
+            commit_id: commit_id.to_string(),
+            source,
+            metadata,
+            details,
+            other: BTreeMap::new(),
+        }
+    }
+
+    /// One of the other block lines, by label
+    pub fn detail(&self, label: &str) -> Option<&str> {
+        self.details.get(label).map(String::as_str)
+    }
+}
+
+/// A repository's sidecars
+#[derive(Debug, Clone)]
+pub struct MetadataStore {
+    dir: PathBuf,
+}
+
+impl MetadataStore {
+    /// The store of the repository at `repo`
+    pub fn for_repo(repo: &Path) -> Self {
+        Self {
+            dir: repo.join(".oxen").join("auxin").join("metadata"),
+        }
+    }
+
+    /// Location of a commit's sidecar
+    pub fn path(&self, commit_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", commit_id))
+    }
+
+    /// Read a commit's sidecar; `None` if it has none
+    pub fn load(&self, commit_id: &str) -> Result<Option<StoredMetadata>> {
+        let path = self.path(commit_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let stored = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(stored))
+    }
+
+    /// Write a sidecar atomically
+    pub fn save(&self, stored: &StoredMetadata) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(&stored.commit_id);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(stored)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Store the metadata of a commit just made with `message`
+    pub fn record(&self, commit_id: &str, message: &str) -> Result<StoredMetadata> {
+        let stored = StoredMetadata::from_message(commit_id, message, MetadataSource::Commit);
+        self.save(&stored)?;
+        Ok(stored)
+    }
+
+    /// A commit's metadata: its sidecar, or its message if it has none or
+    /// the sidecar can't be read
+    pub fn get(&self, commit: &CommitInfo) -> StoredMetadata {
+        match self.load(&commit.id) {
+            Ok(Some(stored)) => return stored,
+            Ok(None) => {}
+            Err(e) => vlog!("Ignoring metadata sidecar: {:#}", e),
+        }
+        StoredMetadata::from_message(&commit.id, &commit.message, MetadataSource::Message)
+    }
+
+    /// Write sidecars for `commits` that don't have one yet, from their
+    /// messages; returns how many were written
+    pub fn migrate(&self, commits: &[CommitInfo]) -> Result<usize> {
+        let mut written = 0;
+        for commit in commits {
+            if self.path(&commit.id).exists() {
+                continue;
+            }
+            let stored =
+                StoredMetadata::from_message(&commit.id, &commit.message, MetadataSource::Message);
+            self.save(&stored)?;
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+/// The text before the metadata block at the end of `message`, and the
+/// block's lines; the block is the last paragraph, if every line in it is
+/// `Label: value`
+fn metadata_block(message: &str) -> Option<(&str, Vec<(&str, &str)>)> {
+    let (body, block) = message.trim_end().rsplit_once("\n\n")?;
+    block
+        .lines()
+        .map(|line| {
+            let (label, value) = line.split_once(": ")?;
+            Some((label.trim(), value.trim()))
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|lines| (body, lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_message() {
+        let message =
+            "Massing study\nSecond line\n\nUnits: Meters\nLayers: 4\nTags: draft, site\nEngine: 5.3";
+        let stored = StoredMetadata::from_message("abc", message, MetadataSource::Message);
+        assert_eq!(stored.metadata.message, "Massing study\nSecond line");
+        assert_eq!(stored.metadata.tags, vec!["draft", "site"]);
+        assert_eq!(stored.detail("Units"), Some("Meters"));
+        // Lines after the standard fields are custom fields
+        assert_eq!(stored.metadata.custom["Engine"], "5.3");
+        assert_eq!(stored.detail("Engine"), None);
+        assert_eq!(stored.detail("Tags"), None);
+
+        // A colon in a one-paragraph message isn't a metadata block
+        let stored = StoredMetadata::from_message("def", "Fix: intro", MetadataSource::Message);
+        assert!(stored.details.is_empty());
+        assert_eq!(stored.metadata.message, "Fix: intro");
+    }
+
+    #[test]
+    fn test_record_get_and_migrate() {
+        let dir = TempDir::new().unwrap();
+        let store = MetadataStore::for_repo(dir.path());
+        let old = commit("c1", "Rough mix\n\nBPM: 120\nKey: A Minor");
+        let new = commit("c2", "Vocals\n\nBPM: 128");
+
+        // Without a sidecar the message is read
+        let stored = store.get(&old);
+        assert_eq!(stored.source, MetadataSource::Message);
+        assert_eq!(stored.metadata.bpm, Some(120.0));
+
+        store.record(&new.id, &new.message).unwrap();
+        assert!(dir.path().join(".oxen/auxin/metadata/c2.json").exists());
+        // The sidecar wins over the message
+        let stored = store.get(&commit("c2", "Vocals"));
+        assert_eq!(stored.source, MetadataSource::Commit);
+        assert_eq!(stored.metadata.bpm, Some(128.0));
+
+        assert_eq!(store.migrate(&[old.clone(), new.clone()]).unwrap(), 1);
+        assert_eq!(store.migrate(&[old.clone(), new]).unwrap(), 0);
+        let migrated = store.load("c1").unwrap().unwrap();
+        assert_eq!(migrated.metadata.key_signature.as_deref(), Some("A Minor"));
+
+        // Sidecars from a newer format load, and keep the fields they added
+        let mut future = serde_json::to_value(&migrated).unwrap();
+        future["version"] = (FORMAT_VERSION + 1).into();
+        future["mix_notes"] = "louder".into();
+        fs::write(store.path("c1"), future.to_string()).unwrap();
+        let stored = store.get(&old);
+        assert_eq!(stored.source, MetadataSource::Message);
+        assert_eq!(stored.metadata.key_signature.as_deref(), Some("A Minor"));
+        assert_eq!(stored.other["mix_notes"], "louder");
+        store.save(&stored).unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(store.path("c1")).unwrap()).unwrap();
+        assert_eq!(saved, future);
+
+        // One that doesn't parse falls back to the message
+        fs::write(store.path("c1"), "{").unwrap();
+        assert!(store.load("c1").is_err());
+        assert_eq!(store.get(&old).metadata.bpm, Some(120.0));
+    }
+}
@@ -0,0 +1,216 @@
+//! Key signature and tempo normalization
+//!
+//! "a minor", "Amin", "Am" and "A Minor" are the same key, and "Gb Major" is
+//! the same key as "F# Major". Keys are canonicalized when metadata is
+//! written (`auxin commit --key`, `auxin metadata edit --set key=...`) and
+//! compared by pitch class and mode when searching, so filters match however
+//! the key was typed. BPM values are checked against Logic Pro's tempo range.
+//!
+//! Canonical spellings use the conventional key signature with the fewest
+//! accidentals: "Db Major" rather than "C# Major", "G# Minor" rather than
+//! "Ab Minor".
+
+use anyhow::{anyhow, bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Lowest tempo Logic Pro accepts
+pub const MIN_BPM: f32 = 5.0;
+
+/// Highest tempo Logic Pro accepts
+pub const MAX_BPM: f32 = 990.0;
+
+const MAJOR_SPELLINGS: [&str; 12] = [
+    "C", "Db", "D", "Eb", "E", "F", "F#", "G", "Ab", "A", "Bb", "B",
+];
+const MINOR_SPELLINGS: [&str; 12] = [
+    "C", "C#", "D", "Eb", "E", "F", "F#", "G", "G#", "A", "Bb", "B",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Major,
+    Minor,
+}
+
+/// A key signature; enharmonic spellings compare equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    /// Pitch class of the tonic, 0 = C … 11 = B
+    pub tonic: u8,
+    pub mode: Mode,
+}
+
+impl Key {
+    /// Canonical spelling of `key`, or `key` unchanged if it isn't a key
+    /// auxin recognizes (e.g. a modal name like "D Dorian")
+    pub fn canonicalize(key: &str) -> String {
+        key.parse::<Key>()
+            .map(|k| k.to_string())
+            .unwrap_or_else(|_| key.trim().to_string())
+    }
+}
+
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    /// Accepts `A Minor`, `a minor`, `Amin`, `Am`, `A-`, `F#m`, `F♯ minor`,
+    /// `F sharp minor`, `Bb`, `B♭ major`, `Bbmaj`, `CM`; no mode means major
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || anyhow!("'{}' is not a key (expected e.g. 'A Minor' or 'F#m')", s);
+        let s = s.trim();
+        let mut chars = s.chars();
+        let letter = chars.next().ok_or_else(invalid)?;
+        let natural: i8 = match letter.to_ascii_uppercase() {
+            'C' => 0,
+            'D' => 2,
+            'E' => 4,
+            'F' => 5,
+            'G' => 7,
+            'A' => 9,
+            'B' => 11,
+            _ => return Err(invalid()),
+        };
+
+        let rest = chars.as_str();
+        let (accidental, rest) = if let Some(r) = rest.strip_prefix(['#', '♯']) {
+            (1, r)
+        } else if let Some(r) = rest.strip_prefix(['b', '♭']) {
+            (-1, r)
+        } else {
+            let word = rest.trim_start_matches([' ', '-']);
+            let lower = word.to_lowercase();
+            if lower.starts_with("sharp") {
+                (1, &word["sharp".len()..])
+            } else if lower.starts_with("flat") {
+                (-1, &word["flat".len()..])
+            } else {
+                (0, rest)
+            }
+        };
+
+        let mode = match rest.trim() {
+            "M" => Mode::Major,
+            "m" | "-" => Mode::Minor,
+            other => match other.to_lowercase().as_str() {
+                "" | "major" | "maj" | "ionian" => Mode::Major,
+                "minor" | "min" | "aeolian" => Mode::Minor,
+                _ => return Err(invalid()),
+            },
+        };
+
+        Ok(Self {
+            tonic: (natural + accidental).rem_euclid(12) as u8,
+            mode,
+        })
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            Mode::Major => write!(f, "{} Major", MAJOR_SPELLINGS[self.tonic as usize]),
+            Mode::Minor => write!(f, "{} Minor", MINOR_SPELLINGS[self.tonic as usize]),
+        }
+    }
+}
+
+/// Whether two key strings name the same key; keys auxin doesn't
+/// recognize compare case-insensitively
+pub fn same_key(a: &str, b: &str) -> bool {
+    match (a.parse::<Key>(), b.parse::<Key>()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.trim().eq_ignore_ascii_case(b.trim()),
+    }
+}
+
+/// Whether a stored key matches a key filter
+///
+/// Filters that name a key ("Am", "a minor") match enharmonic equivalents;
+/// anything else ("minor", "dorian") is a case-insensitive substring match.
+pub fn key_matches(stored: &str, filter: &str) -> bool {
+    match (stored.parse::<Key>(), filter.parse::<Key>()) {
+        (Ok(stored), Ok(filter)) => stored == filter,
+        (_, Ok(_)) => false,
+        _ => Key::canonicalize(stored)
+            .to_lowercase()
+            .contains(&filter.trim().to_lowercase()),
+    }
+}
+
+/// Check a tempo is one Logic Pro could have
+pub fn validate_bpm(bpm: f32) -> Result<f32> {
+    if !bpm.is_finite() || !(MIN_BPM..=MAX_BPM).contains(&bpm) {
+        bail!("BPM must be between {} and {}, got {}", MIN_BPM, MAX_BPM, bpm);
+    }
+    Ok(bpm)
+}
+
+/// Parse and validate a tempo such as `120`, `92.5` or `128 BPM`
+pub fn parse_bpm(s: &str) -> Result<f32> {
+    let s = s.trim();
+    let number = s
+        .strip_suffix("BPM")
+        .or_else(|| s.strip_suffix("bpm"))
+        .unwrap_or(s)
+        .trim();
+    let bpm = number
+        .parse::<f32>()
+        .map_err(|_| anyhow!("'{}' is not a BPM value", s))?;
+    validate_bpm(bpm)
+}
+
+/// Parse a key and return its canonical spelling, for `--key` arguments
+pub fn parse_key(s: &str) -> Result<String> {
+    Ok(s.parse::<Key>()?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_spellings() {
+        for input in ["A Minor", "a minor", "Amin", "Am", "A-", "A aeolian", " a MINOR "] {
+            assert_eq!(parse_key(input).unwrap(), "A Minor", "{}", input);
+        }
+        for input in ["F# Major", "F♯", "f sharp major", "Gb", "G♭ maj", "GbM"] {
+            assert_eq!(parse_key(input).unwrap(), "F# Major", "{}", input);
+        }
+        assert_eq!(parse_key("C#").unwrap(), "Db Major");
+        assert_eq!(parse_key("Abm").unwrap(), "G# Minor");
+        assert_eq!(parse_key("B-flat minor").unwrap(), "Bb Minor");
+        assert_eq!(parse_key("Cb").unwrap(), "B Major");
+
+        for input in ["", "H Major", "D Dorian", "minor", "C##", "Amajor7"] {
+            assert!(parse_key(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_key_matches() {
+        assert!(key_matches("a minor", "Am"));
+        assert!(key_matches("G# Minor", "ab minor"));
+        assert!(!key_matches("A Major", "Am"));
+        assert!(key_matches("Amin", "minor"));
+        assert!(key_matches("D Dorian", "dorian"));
+        assert!(!key_matches("D Dorian", "Dm"));
+        assert_eq!(Key::canonicalize("D Dorian"), "D Dorian");
+
+        assert!(same_key("Gb Major", "F#"));
+        assert!(same_key("d dorian", "D Dorian"));
+        assert!(!same_key("C Major", "C Minor"));
+    }
+
+    #[test]
+    fn test_bpm_validation() {
+        assert_eq!(parse_bpm("120").unwrap(), 120.0);
+        assert_eq!(parse_bpm("92.5 BPM").unwrap(), 92.5);
+        assert_eq!(parse_bpm("128bpm").unwrap(), 128.0);
+        assert!(parse_bpm("0").is_err());
+        assert!(parse_bpm("-120").is_err());
+        assert!(parse_bpm("1200").is_err());
+        assert!(parse_bpm("fast").is_err());
+        assert!(validate_bpm(f32::NAN).is_err());
+    }
+}
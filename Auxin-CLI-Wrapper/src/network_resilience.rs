@@ -1,9 +1,12 @@
+use crate::dual_stack::HappyEyeballsResolver;
 use anyhow::{anyhow, Context, Result};
+use auxin_config::{Config, NetworkClass, RetrySettings};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::fs;
+use std::net::Ipv6Addr;
 use std::path::PathBuf;
 use std::thread;
 use std::time::Duration as StdDuration;
@@ -21,16 +24,153 @@ const MAX_BACKOFF_MS: u64 = 16000;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectivityState {
     Online,
+    /// Connected to a network whose sign-in page (hotel or café Wi-Fi)
+    /// intercepts traffic; nothing gets through until the user signs in
+    Captive,
     Offline,
     Unknown,
 }
 
-/// Check current connectivity state
+impl ConnectivityState {
+    /// Short description for messages such as "... - operation queued"
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Online => "Network is online",
+            Self::Captive => "Network requires sign-in (captive portal)",
+            Self::Offline => "Network is offline",
+            Self::Unknown => "Network state unknown",
+        }
+    }
+}
+
+/// Check current connectivity state with the configured probe targets,
+/// using the `[network.connectivity]` retry settings
 pub fn check_connectivity() -> ConnectivityState {
-    if check_network_availability() {
-        ConnectivityState::Online
-    } else {
-        ConnectivityState::Offline
+    let config = Config::load().unwrap_or_default();
+    let settings = config.retry_settings(NetworkClass::Connectivity);
+    let probe = ConnectivityProbe::from_config(&config);
+
+    // Only a plain "offline" is worth retrying; a captive portal stays
+    // until someone signs in
+    RetryPolicy::from_settings(&settings)
+        .execute(|| match probe.check() {
+            ConnectivityState::Offline => Err(anyhow!("No probe target is reachable")),
+            state => Ok(state),
+        })
+        .unwrap_or(ConnectivityState::Offline)
+}
+
+/// Probes deciding whether the network is usable
+///
+/// Every target is probed concurrently over HTTP; any response, even an
+/// error status, means the host is reachable. Alongside, the captive portal
+/// URL, if configured, is fetched without following redirects: `204 No
+/// Content` means nothing intercepted it, a redirect means a portal may
+/// have. A portal can't answer for an HTTPS target, so one that answers
+/// rules a portal out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectivityProbe {
+    /// URLs to reach
+    pub targets: Vec<String>,
+    pub captive_portal_url: Option<String>,
+    pub timeout: StdDuration,
+}
+
+impl ConnectivityProbe {
+    /// Probe targets from `[network] probe_targets`; "server" is the health
+    /// endpoint of the `[cli]` server, "hub" is Oxen Hub, and bare hosts
+    /// (including IPv6 literals) are probed over HTTPS
+    pub fn from_config(config: &Config) -> Self {
+        let settings = config.retry_settings(NetworkClass::Connectivity);
+        let targets = config
+            .network
+            .probe_targets
+            .iter()
+            .map(|target| match target.trim() {
+                "server" => format!("{}/health", config.cli.url.trim_end_matches('/')),
+                "hub" => "https://hub.oxen.ai".to_string(),
+                url if url.contains("://") => url.to_string(),
+                host if host.parse::<Ipv6Addr>().is_ok() => format!("https://[{}]", host),
+                host => format!("https://{}", host),
+            })
+            .collect();
+        let portal = config.network.captive_portal_url.trim();
+
+        Self {
+            targets,
+            captive_portal_url: (!portal.is_empty()).then(|| portal.to_string()),
+            timeout: StdDuration::from_secs(settings.timeout_s),
+        }
+    }
+
+    /// Probe once
+    pub fn check(&self) -> ConnectivityState {
+        let agent = ureq::AgentBuilder::new()
+            .resolver(HappyEyeballsResolver::new(self.timeout))
+            .timeout(self.timeout)
+            .redirects(0)
+            .build();
+
+        thread::scope(|scope| {
+            let portal = self
+                .captive_portal_url
+                .as_deref()
+                .map(|url| scope.spawn(|| captive_portal_detected(&agent, url)));
+            let probes: Vec<_> = self
+                .targets
+                .iter()
+                .map(|url| scope.spawn(|| is_reachable(&agent, url)))
+                .collect();
+
+            // Join every probe so none outlives the scope
+            let reachable: Vec<&String> = probes
+                .into_iter()
+                .zip(&self.targets)
+                .filter_map(|(probe, url)| probe.join().unwrap_or(false).then_some(url))
+                .collect();
+            let captive = portal.and_then(|probe| probe.join().ok().flatten());
+
+            if self.targets.is_empty() {
+                return match captive {
+                    Some(true) => ConnectivityState::Captive,
+                    Some(false) => ConnectivityState::Online,
+                    None => ConnectivityState::Unknown,
+                };
+            }
+            let https_reachable = reachable.iter().any(|url| url.starts_with("https://"));
+            classify(!reachable.is_empty(), https_reachable, captive)
+        })
+    }
+}
+
+/// A portal also answers for plain-HTTP targets, so it wins over them, but
+/// not over an HTTPS target, which it can't impersonate
+fn classify(
+    any_reachable: bool,
+    https_reachable: bool,
+    captive: Option<bool>,
+) -> ConnectivityState {
+    match (captive, any_reachable) {
+        (Some(true), _) if !https_reachable => ConnectivityState::Captive,
+        (_, true) => ConnectivityState::Online,
+        _ => ConnectivityState::Offline,
+    }
+}
+
+fn is_reachable(agent: &ureq::Agent, url: &str) -> bool {
+    match agent.head(url).call() {
+        Ok(_) | Err(ureq::Error::Status(..)) => true,
+        Err(ureq::Error::Transport(_)) => false,
+    }
+}
+
+/// `Some(true)` for a redirect, `Some(false)` for `204 No Content`, and
+/// `None` for anything else, error statuses included, or no answer at all
+fn captive_portal_detected(agent: &ureq::Agent, url: &str) -> Option<bool> {
+    match agent.get(url).call() {
+        Ok(response) if response.status() == 204 => Some(false),
+        Ok(response) if (300..400).contains(&response.status()) => Some(true),
+        _ => None,
     }
 }
 
@@ -54,6 +194,20 @@ impl RetryPolicy {
         }
     }
 
+    /// Create a policy from resolved config settings
+    pub fn from_settings(settings: &RetrySettings) -> Self {
+        Self::new(
+            settings.max_retries,
+            settings.initial_backoff_ms,
+            settings.max_backoff_ms,
+        )
+    }
+
+    /// Policy configured for a class of operation (`[network.<class>]`)
+    pub fn for_class(class: NetworkClass) -> Self {
+        Self::from_settings(&Config::load().unwrap_or_default().retry_settings(class))
+    }
+
     /// Enable verbose logging
     pub fn set_verbose(mut self, verbose: bool) -> Self {
         self.verbose = verbose;
@@ -437,19 +591,13 @@ pub fn is_transient_error(error: &anyhow::Error) -> bool {
         .any(|pattern| error_str.contains(pattern))
 }
 
-/// Check if network is available by attempting to connect to Oxen Hub
+/// Quick check (one attempt, 2s timeout) that a probe target is reachable
 pub fn check_network_availability() -> bool {
-    use std::process::Command;
-
-    // Try to ping Oxen Hub
-    let output = Command::new("ping")
-        .args(["-c", "1", "-W", "2", "hub.oxen.ai"])
-        .output();
-
-    match output {
-        Ok(out) => out.status.success(),
-        Err(_) => false,
-    }
+    let probe = ConnectivityProbe {
+        timeout: StdDuration::from_secs(2),
+        ..ConnectivityProbe::from_config(&Config::load().unwrap_or_default())
+    };
+    probe.check() == ConnectivityState::Online
 }
 
 // ========== Circuit Breaker ==========
@@ -684,6 +832,11 @@ impl AdaptiveRetryPolicy {
         }
     }
 
+    /// Policy configured for a class of operation (`[network.<class>]`)
+    pub fn for_class(class: NetworkClass) -> Self {
+        Self::with_policy(RetryPolicy::for_class(class))
+    }
+
     /// Create with custom base policy
     pub fn with_policy(policy: RetryPolicy) -> Self {
         Self {
@@ -1472,6 +1625,83 @@ mod tests {
         let _ = check_connectivity();
     }
 
+    /// Serve `response` to every request on a local port
+    fn serve(response: &'static str) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_connectivity_probe() {
+        let no_content = serve("HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n");
+        let portal = serve(
+            "HTTP/1.1 302 Found\r\nLocation: http://login.hotel/\r\nContent-Length: 0\r\n\r\n",
+        );
+        let closed = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let probe = |targets: &[&String], captive: Option<&String>| ConnectivityProbe {
+            targets: targets.iter().map(|t| t.to_string()).collect(),
+            captive_portal_url: captive.cloned(),
+            timeout: StdDuration::from_secs(2),
+        };
+
+        let check = probe(&[&closed, &no_content], Some(&no_content)).check();
+        assert_eq!(check, ConnectivityState::Online);
+        let check = probe(&[&closed], Some(&no_content)).check();
+        assert_eq!(check, ConnectivityState::Offline);
+        let check = probe(&[&no_content], Some(&portal)).check();
+        assert_eq!(check, ConnectivityState::Captive);
+        assert_eq!(probe(&[], None).check(), ConnectivityState::Unknown);
+
+        // Anything but 204 or a redirect says nothing about a portal
+        let other = serve("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok");
+        let error = serve("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n");
+        for answer in [&other, &error] {
+            assert_eq!(probe(&[], Some(answer)).check(), ConnectivityState::Unknown);
+            let check = probe(&[&closed], Some(answer)).check();
+            assert_eq!(check, ConnectivityState::Offline);
+        }
+
+        // A portal can't answer for an HTTPS target
+        assert_eq!(classify(true, true, Some(true)), ConnectivityState::Online);
+        for any_reachable in [true, false] {
+            let check = classify(any_reachable, false, Some(true));
+            assert_eq!(check, ConnectivityState::Captive);
+        }
+
+        let mut config = Config::default();
+        config.cli.url = "http://studio:3000/".to_string();
+        config.network.probe_targets = vec![
+            "server".into(),
+            "hub".into(),
+            "example.com".into(),
+            "fd00::10".into(),
+        ];
+        let probe = ConnectivityProbe::from_config(&config);
+        assert_eq!(
+            probe.targets,
+            vec![
+                "http://studio:3000/health",
+                "https://hub.oxen.ai",
+                "https://example.com",
+                "https://[fd00::10]",
+            ]
+        );
+        assert_eq!(probe.captive_portal_url, None);
+    }
+
     #[test]
     fn test_retry_policy_builder() {
         let policy = RetryPolicy::new(5, 1000, 10000).set_verbose(true);
@@ -1480,4 +1710,34 @@ mod tests {
         assert_eq!(policy.max_backoff_ms, 10000);
         assert!(policy.verbose);
     }
+
+    #[test]
+    fn test_per_class_retry_settings() {
+        let mut config = Config::default();
+        config.network.push.max_retries = Some(8);
+        config.network.push.timeout_s = Some(600);
+        config.network.connectivity.initial_backoff_ms = Some(250);
+        config.network.pull.timeout_s = Some(900);
+
+        let push = config.retry_settings(NetworkClass::Push);
+        assert_eq!(push.max_retries, 8);
+        assert_eq!(push.timeout_s, 600);
+        assert_eq!(push.initial_backoff_ms, config.network.initial_backoff_ms as u64);
+
+        let pull = config.retry_settings(NetworkClass::Pull);
+        assert_eq!(pull.max_retries, config.network.max_retries as u32);
+        assert_eq!(pull.timeout_s, 900);
+
+        let server = config.retry_settings(NetworkClass::ServerApi);
+        assert_eq!(server.max_retries, 0);
+        assert_eq!(server.timeout_s, config.cli.timeout_secs as u64);
+
+        let connectivity = config.retry_settings(NetworkClass::Connectivity);
+        assert_eq!(connectivity.max_retries, 0);
+        assert_eq!(connectivity.initial_backoff_ms, 250);
+
+        let policy = RetryPolicy::from_settings(&push);
+        assert_eq!(policy.max_attempts(), 8);
+        assert_eq!(policy.max_delay_ms(), push.max_backoff_ms);
+    }
 }
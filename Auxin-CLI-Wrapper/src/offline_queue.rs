@@ -11,6 +11,11 @@
 //! - Conflict detection and resolution
 //! - Persistent storage across restarts
 //! - Operation ordering and dependencies
+//! - Editing a queued operation's parameters before it syncs
+//! - Dead-lettering of entries that keep failing, for review with
+//!   `auxin queue deadletter`
+//! - Quality-of-service classes: locks sync before metadata, comments and
+//!   pushes, with per-class concurrency limits
 //!
 //! # Example
 //!
@@ -35,14 +40,18 @@
 //! ```
 
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, PoisonError};
 use uuid::Uuid;
 
 use crate::network_resilience::{check_connectivity, ConnectivityState};
+use crate::server_client::{AuxinServerClient, LogicProMetadata, ServerConfig};
 use crate::OxenSubprocess;
 use crate::remote_lock::RemoteLockManager;
 
@@ -80,6 +89,53 @@ pub enum QueuedOperation {
 
     /// Sync comments
     SyncComments { repo_path: String },
+
+    /// Store commit metadata on the server
+    StoreMetadata {
+        repo_path: String,
+        namespace: String,
+        name: String,
+        commit_id: String,
+        metadata: LogicProMetadata,
+    },
+}
+
+/// Quality-of-service class of a queued operation, most urgent first
+///
+/// Small, time-sensitive operations sync first so a lock release isn't stuck
+/// behind a multi-gigabyte push.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QosClass {
+    Lock,
+    Metadata,
+    Comments,
+    /// Pushes and pulls
+    Transfer,
+}
+
+impl QosClass {
+    /// Every class, most urgent first
+    pub const ALL: [QosClass; 4] = [
+        QosClass::Lock,
+        QosClass::Metadata,
+        QosClass::Comments,
+        QosClass::Transfer,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QosClass::Lock => "lock",
+            QosClass::Metadata => "metadata",
+            QosClass::Comments => "comments",
+            QosClass::Transfer => "transfer",
+        }
+    }
+
+    /// The class `steps` above this one
+    fn promoted(self, steps: usize) -> Self {
+        Self::ALL[(self as usize).saturating_sub(steps)]
+    }
 }
 
 impl QueuedOperation {
@@ -110,9 +166,102 @@ impl QueuedOperation {
             QueuedOperation::SyncComments { repo_path } => {
                 format!("Sync comments for {}", repo_path)
             }
+            QueuedOperation::StoreMetadata { commit_id, .. } => {
+                format!("Store metadata for commit {}", &commit_id[..commit_id.len().min(8)])
+            }
         }
     }
 
+    /// Quality-of-service class the operation syncs in
+    pub fn qos_class(&self) -> QosClass {
+        match self {
+            QueuedOperation::AcquireLock { .. }
+            | QueuedOperation::ReleaseLock { .. }
+            | QueuedOperation::RenewLock { .. } => QosClass::Lock,
+            QueuedOperation::StoreMetadata { .. } => QosClass::Metadata,
+            QueuedOperation::SyncComments { .. } => QosClass::Comments,
+            QueuedOperation::PushCommits { .. } | QueuedOperation::PullCommits { .. } => {
+                QosClass::Transfer
+            }
+        }
+    }
+
+    /// What the operation acts on; operations on the same resource sync one
+    /// at a time, in the order they were queued
+    fn resource(&self) -> String {
+        match self {
+            QueuedOperation::AcquireLock { project_path, .. }
+            | QueuedOperation::ReleaseLock { project_path, .. }
+            | QueuedOperation::RenewLock { project_path, .. } => format!("lock:{}", project_path),
+            QueuedOperation::PushCommits { repo_path, .. }
+            | QueuedOperation::PullCommits { repo_path, .. } => format!("repo:{}", repo_path),
+            QueuedOperation::SyncComments { repo_path } => format!("comments:{}", repo_path),
+            QueuedOperation::StoreMetadata { commit_id, .. } => {
+                format!("metadata:{}", commit_id)
+            }
+        }
+    }
+
+    /// The operation's parameters, by field name
+    pub fn fields(&self) -> Vec<(String, serde_json::Value)> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(variant)) => variant
+                .into_iter()
+                .next()
+                .and_then(|(_, fields)| match fields {
+                    serde_json::Value::Object(fields) => Some(fields.into_iter().collect()),
+                    _ => None,
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// A copy with each `(field, value)` in `changes` applied
+    ///
+    /// Only the operation's own fields can be set, and each value must fit
+    /// the field: text can't be empty, numbers must be in range, structured
+    /// fields such as `metadata` take JSON, and paths must exist (they're
+    /// stored canonicalized).
+    pub fn with_changes(&self, changes: &[(String, String)]) -> Result<Self> {
+        let mut value = serde_json::to_value(self).context("Failed to serialize operation")?;
+        let (kind, fields) = value
+            .as_object_mut()
+            .and_then(|variant| variant.iter_mut().next())
+            .and_then(|(kind, fields)| Some((kind.clone(), fields.as_object_mut()?)))
+            .ok_or_else(|| anyhow!("Operation has no editable fields"))?;
+
+        for (field, raw) in changes {
+            let Some(current) = fields.get(field) else {
+                let names: Vec<&str> = fields.keys().map(String::as_str).collect();
+                return Err(anyhow!(
+                    "{} has no field '{}' (fields: {})",
+                    kind,
+                    field,
+                    names.join(", ")
+                ));
+            };
+            let new = if current.is_string() {
+                if raw.trim().is_empty() {
+                    return Err(anyhow!("{} can't be empty", field));
+                }
+                if field.ends_with("_path") {
+                    let path = fs::canonicalize(raw)
+                        .map_err(|_| anyhow!("{} doesn't exist: {}", field, raw))?;
+                    serde_json::Value::String(path.to_string_lossy().to_string())
+                } else {
+                    serde_json::Value::String(raw.clone())
+                }
+            } else {
+                serde_json::from_str(raw)
+                    .with_context(|| format!("Invalid value for {}: {}", field, raw))?
+            };
+            fields.insert(field.clone(), new);
+        }
+
+        serde_json::from_value(value).map_err(|e| anyhow!("Invalid {}: {}", kind, e))
+    }
+
     /// Check if this operation can be executed offline (none can)
     pub fn is_offline_capable(&self) -> bool {
         false // All queued operations require network
@@ -120,6 +269,9 @@ impl QueuedOperation {
 }
 
 /// Queue entry with metadata
+///
+/// Stored as JSON in the queue directory, which different auxin versions may
+/// share.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueueEntry {
     /// Unique entry ID
@@ -145,6 +297,23 @@ pub struct QueueEntry {
 
     /// Whether this entry has been processed
     pub completed: bool,
+
+    /// Set once the entry has failed too often to keep retrying
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter: Option<DeadLetter>,
+
+    /// Unknown fields of the entry file
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// Why a queue entry stopped being retried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// When it was set aside
+    pub at: DateTime<Utc>,
+    /// The last error, outermost first, then each cause
+    pub error_chain: Vec<String>,
 }
 
 impl QueueEntry {
@@ -159,6 +328,8 @@ impl QueueEntry {
             last_error: None,
             priority: 0,
             completed: false,
+            dead_letter: None,
+            other: BTreeMap::new(),
         }
     }
 
@@ -176,12 +347,190 @@ impl QueueEntry {
         self.last_error = Some(error);
     }
 
+    /// Record a failed attempt, dead-lettering the entry once it has
+    /// failed `max_attempts` times (never, if zero). Returns whether it was
+    /// dead-lettered.
+    pub fn record_failure(&mut self, error: &anyhow::Error, max_attempts: u32) -> bool {
+        self.mark_failed(error.to_string());
+        if max_attempts == 0 || self.attempts < max_attempts {
+            return false;
+        }
+        self.dead_letter = Some(DeadLetter {
+            at: Utc::now(),
+            error_chain: error.chain().map(|cause| cause.to_string()).collect(),
+        });
+        true
+    }
+
     /// Mark as completed
     pub fn mark_completed(&mut self) {
         self.completed = true;
         self.last_attempt = Some(Utc::now());
         self.last_error = None;
     }
+
+    /// Still waiting to be synced: neither completed nor dead-lettered
+    pub fn is_pending(&self) -> bool {
+        !self.completed && self.dead_letter.is_none()
+    }
+}
+
+/// How a sync schedules queued operations, from `[queue]` in the config
+#[derive(Debug, Clone)]
+pub struct SyncPolicy {
+    /// Operations synced at once, across all classes
+    pub max_concurrent: usize,
+    /// Most operations of each class synced at once, in [`QosClass::ALL`] order
+    pub class_limits: [usize; 4],
+    /// Waiting this long schedules an operation with the class above its
+    /// own, and twice as long two classes up; zero turns aging off
+    pub starvation: Duration,
+    /// Failed attempts before an entry is dead-lettered; zero retries forever
+    pub max_attempts: u32,
+}
+
+impl SyncPolicy {
+    pub fn from_config(queue: &auxin_config::Queue) -> Self {
+        let limit = |n: i64| n.max(1) as usize;
+        let concurrency = &queue.concurrency;
+        Self {
+            max_concurrent: limit(queue.max_concurrent),
+            class_limits: [
+                limit(concurrency.locks),
+                limit(concurrency.metadata),
+                limit(concurrency.comments),
+                limit(concurrency.pushes),
+            ],
+            starvation: Duration::minutes(queue.starvation_minutes.max(0)),
+            max_attempts: queue.max_attempts.clamp(0, u32::MAX as i64) as u32,
+        }
+    }
+
+    fn limit(&self, class: QosClass) -> usize {
+        self.class_limits[class as usize]
+    }
+
+    /// Class `entry` is scheduled with, `now`
+    pub fn effective_class(&self, entry: &QueueEntry, now: DateTime<Utc>) -> QosClass {
+        let class = entry.operation.qos_class();
+        let period = self.starvation.num_seconds();
+        if period <= 0 {
+            return class;
+        }
+        let waited = (now - entry.queued_at).num_seconds().max(0);
+        class.promoted((waited / period) as usize)
+    }
+}
+
+impl Default for SyncPolicy {
+    fn default() -> Self {
+        Self::from_config(&auxin_config::Queue::default())
+    }
+}
+
+/// An entry waiting in [`run_scheduled`]
+struct Scheduled {
+    entry: QueueEntry,
+    class: QosClass,
+    resource: String,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    /// In pick order
+    waiting: Vec<Scheduled>,
+    /// Entry ids per resource, oldest first
+    resource_order: HashMap<String, VecDeque<String>>,
+    running: [usize; 4],
+    busy: HashSet<String>,
+    finished: Vec<(QueueEntry, Result<()>)>,
+}
+
+impl SchedulerState {
+    fn next_runnable(&self, policy: &SyncPolicy) -> Option<usize> {
+        self.waiting.iter().position(|scheduled| {
+            self.running[scheduled.class as usize] < policy.limit(scheduled.class)
+                && !self.busy.contains(&scheduled.resource)
+                && self.resource_order[&scheduled.resource].front() == Some(&scheduled.entry.id)
+        })
+    }
+}
+
+/// Run `entries` through `execute` on up to `policy.max_concurrent` threads
+///
+/// Entries are picked by effective class, then priority, then age, as long
+/// as their class is under its limit. Operations on the same resource run
+/// one at a time in the order they were queued. Returns each entry with its
+/// outcome, in the order they finished.
+fn run_scheduled<F>(
+    entries: Vec<QueueEntry>,
+    policy: &SyncPolicy,
+    now: DateTime<Utc>,
+    execute: F,
+) -> Vec<(QueueEntry, Result<()>)>
+where
+    F: Fn(&QueueEntry) -> Result<()> + Sync,
+{
+    let mut state = SchedulerState::default();
+    let mut by_age: Vec<&QueueEntry> = entries.iter().collect();
+    by_age.sort_by_key(|entry| entry.queued_at);
+    for entry in by_age {
+        state
+            .resource_order
+            .entry(entry.operation.resource())
+            .or_default()
+            .push_back(entry.id.clone());
+    }
+
+    let workers = policy.max_concurrent.min(entries.len());
+    state.waiting = entries
+        .into_iter()
+        .map(|entry| Scheduled {
+            class: entry.operation.qos_class(),
+            resource: entry.operation.resource(),
+            entry,
+        })
+        .collect();
+    state.waiting.sort_by_key(|scheduled| {
+        let entry = &scheduled.entry;
+        (policy.effective_class(entry, now), Reverse(entry.priority), entry.queued_at)
+    });
+
+    let state = Mutex::new(state);
+    let wake = Condvar::new();
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let mut guard = state.lock().unwrap_or_else(PoisonError::into_inner);
+                let scheduled = loop {
+                    if guard.waiting.is_empty() {
+                        return;
+                    }
+                    if let Some(index) = guard.next_runnable(policy) {
+                        break guard.waiting.remove(index);
+                    }
+                    guard = wake.wait(guard).unwrap_or_else(PoisonError::into_inner);
+                };
+                guard.running[scheduled.class as usize] += 1;
+                guard.busy.insert(scheduled.resource.clone());
+                if let Some(order) = guard.resource_order.get_mut(&scheduled.resource) {
+                    order.pop_front();
+                }
+                drop(guard);
+
+                let result = execute(&scheduled.entry);
+
+                let mut guard = state.lock().unwrap_or_else(PoisonError::into_inner);
+                guard.running[scheduled.class as usize] -= 1;
+                guard.busy.remove(&scheduled.resource);
+                guard.finished.push((scheduled.entry, result));
+                drop(guard);
+                wake.notify_all();
+            });
+        }
+    });
+
+    state.into_inner().unwrap_or_else(PoisonError::into_inner).finished
 }
 
 /// Offline operation queue
@@ -191,6 +540,9 @@ pub struct OfflineQueue {
 
     /// In-memory cache of queue entries
     entries: Vec<QueueEntry>,
+
+    /// How syncs schedule entries
+    policy: SyncPolicy,
 }
 
 impl OfflineQueue {
@@ -199,7 +551,10 @@ impl OfflineQueue {
         let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
         let queue_dir = home.join(DEFAULT_QUEUE_DIR);
 
-        Self::with_dir(queue_dir)
+        let mut queue = Self::with_dir(queue_dir)?;
+        let config = auxin_config::Config::load().unwrap_or_default();
+        queue.policy = SyncPolicy::from_config(&config.queue);
+        Ok(queue)
     }
 
     /// Create a new offline queue with custom directory
@@ -212,6 +567,7 @@ impl OfflineQueue {
         let mut queue = Self {
             queue_dir,
             entries: Vec::new(),
+            policy: SyncPolicy::default(),
         };
 
         // Load existing entries
@@ -256,9 +612,14 @@ impl OfflineQueue {
         Ok(id)
     }
 
-    /// Get all pending (non-completed) entries
+    /// Get all pending (non-completed, non-dead-lettered) entries
     pub fn pending(&self) -> Vec<&QueueEntry> {
-        self.entries.iter().filter(|e| !e.completed).collect()
+        self.entries.iter().filter(|e| e.is_pending()).collect()
+    }
+
+    /// Get entries that failed too often and are no longer retried
+    pub fn dead_letters(&self) -> Vec<&QueueEntry> {
+        self.entries.iter().filter(|e| e.dead_letter.is_some()).collect()
     }
 
     /// Get all completed entries
@@ -285,6 +646,69 @@ impl OfflineQueue {
         Ok(())
     }
 
+    /// Full ID of the one entry whose ID starts with `prefix`
+    pub fn resolve_id(&self, prefix: &str) -> Result<String> {
+        let matches: Vec<&QueueEntry> =
+            self.entries.iter().filter(|e| e.id.starts_with(prefix)).collect();
+        match matches[..] {
+            [entry] => Ok(entry.id.clone()),
+            [] => Err(anyhow!("No queue entry with ID {}", prefix)),
+            _ => Err(anyhow!("Queue entry ID {} is ambiguous", prefix)),
+        }
+    }
+
+    /// Change the operation of an entry that hasn't synced yet; see
+    /// [`QueuedOperation::with_changes`]
+    pub fn edit(&mut self, id: &str, changes: &[(String, String)]) -> Result<&QueueEntry> {
+        let index = self.unfinished_index(id)?;
+        let operation = self.entries[index].operation.with_changes(changes)?;
+        self.entries[index].operation = operation;
+        self.save_entry(&self.entries[index])?;
+        Ok(&self.entries[index])
+    }
+
+    /// Put an entry back in line: out of the dead letters, with its attempts
+    /// reset and, if given, a new priority
+    pub fn requeue(&mut self, id: &str, priority: Option<i32>) -> Result<()> {
+        let index = self.unfinished_index(id)?;
+        let entry = &mut self.entries[index];
+        entry.dead_letter = None;
+        entry.attempts = 0;
+        entry.last_error = None;
+        if let Some(priority) = priority {
+            entry.priority = priority;
+        }
+        self.save_entry(&self.entries[index])
+    }
+
+    /// Put a dead-lettered entry back in the queue with its attempts reset
+    pub fn retry_dead_letter(&mut self, id: &str) -> Result<()> {
+        if !self.entries.iter().any(|e| e.id == id && e.dead_letter.is_some()) {
+            return Err(anyhow!("No dead-lettered queue entry with ID {}", id));
+        }
+        self.requeue(id, None)
+    }
+
+    fn unfinished_index(&self, id: &str) -> Result<usize> {
+        let index = self
+            .entries
+            .iter()
+            .position(|e| e.id == id)
+            .ok_or_else(|| anyhow!("No queue entry with ID {}", id))?;
+        if self.entries[index].completed {
+            return Err(anyhow!("Queue entry {} has already synced", id));
+        }
+        Ok(index)
+    }
+
+    /// Delete a dead-lettered entry for good
+    pub fn discard_dead_letter(&mut self, id: &str) -> Result<()> {
+        if !self.entries.iter().any(|e| e.id == id && e.dead_letter.is_some()) {
+            return Err(anyhow!("No dead-lettered queue entry with ID {}", id));
+        }
+        self.remove(id)
+    }
+
     /// Clear all completed entries
     pub fn clear_completed(&mut self) -> Result<()> {
         let completed_ids: Vec<String> = self.completed().iter().map(|e| e.id.clone()).collect();
@@ -296,8 +720,19 @@ impl OfflineQueue {
         Ok(())
     }
 
+    /// Replace the sync scheduling policy
+    pub fn set_policy(&mut self, policy: SyncPolicy) {
+        self.policy = policy;
+    }
+
     /// Sync all pending operations
     pub fn sync_all(&mut self) -> Result<SyncReport> {
+        self.sync_classes(&QosClass::ALL)
+    }
+
+    /// Sync the pending operations in `classes`, e.g. only locks before a
+    /// lock command so it doesn't wait on a queued push
+    pub fn sync_classes(&mut self, classes: &[QosClass]) -> Result<SyncReport> {
         crate::info!("Starting offline queue sync...");
 
         // Check connectivity
@@ -305,67 +740,73 @@ impl OfflineQueue {
             ConnectivityState::Offline => {
                 return Err(anyhow!("Cannot sync: network is offline"));
             }
+            ConnectivityState::Captive => {
+                return Err(anyhow!(
+                    "Cannot sync: network requires sign-in (captive portal)"
+                ));
+            }
             ConnectivityState::Unknown => {
                 crate::warn!("Network state unknown, attempting sync anyway...");
             }
             ConnectivityState::Online => {}
         }
 
-        let mut report = SyncReport::new();
-        let pending: Vec<QueueEntry> = self.pending().iter().cloned().cloned().collect();
-
-        // Sort by priority (highest first)
-        let mut sorted = pending;
-        sorted.sort_by(|a, b| b.priority.cmp(&a.priority));
-
-        for entry in sorted {
-            crate::info!("Syncing: {}", entry.operation.description());
-
-            match self.execute_entry(&entry) {
-                Ok(_) => {
-                    crate::info!("✓ Completed: {}", entry.operation.description());
-                    report.succeeded.push(entry.id.clone());
-
-                    // Mark as completed and clone for saving
-                    let entry_to_save = {
-                        if let Some(e) = self.entries.iter_mut().find(|e| e.id == entry.id) {
-                            e.mark_completed();
-                            Some(e.clone())
-                        } else {
-                            None
-                        }
-                    };
+        let pending: Vec<QueueEntry> = self
+            .pending()
+            .into_iter()
+            .filter(|entry| classes.contains(&entry.operation.qos_class()))
+            .cloned()
+            .collect();
+
+        // Each result is saved as soon as it's known, so an interrupted sync
+        // doesn't repeat finished operations
+        let queue_dir = self.queue_dir.clone();
+        let max_attempts = self.policy.max_attempts;
+        let finished = run_scheduled(pending, &self.policy, Utc::now(), |entry| {
+            let description = entry.operation.description();
+            crate::info!("Syncing: {}", description);
+
+            let result = Self::execute_entry(entry);
+            let mut updated = entry.clone();
+            match &result {
+                Ok(()) => {
+                    crate::info!("✓ Completed: {}", description);
+                    updated.mark_completed();
+                }
+                Err(e) => {
+                    crate::error!("✗ Failed: {} - {}", description, e);
+                    if updated.record_failure(e, max_attempts) {
+                        crate::warn!(
+                            "Giving up on {} after {} attempts; see 'auxin queue deadletter list'",
+                            description,
+                            updated.attempts
+                        );
+                    }
+                }
+            }
+            if let Err(e) = write_entry(&queue_dir, &updated) {
+                crate::warn!("Failed to save queue entry {}: {}", updated.id, e);
+            }
+            result
+        });
 
-                    // Save after releasing the mutable borrow
-                    if let Some(e) = entry_to_save {
-                        self.save_entry(&e)?;
+        let mut report = SyncReport::new();
+        for (entry, result) in finished {
+            let queued = self.entries.iter_mut().find(|e| e.id == entry.id);
+            match result {
+                Ok(()) => {
+                    if let Some(queued) = queued {
+                        queued.mark_completed();
                     }
+                    report.succeeded.push(entry.id);
                 }
                 Err(e) => {
-                    let error_msg = e.to_string();
-                    crate::error!(
-                        "✗ Failed: {} - {}",
-                        entry.operation.description(),
-                        error_msg
-                    );
-                    report.failed.push((entry.id.clone(), error_msg.clone()));
-
-                    // Mark as failed and clone for saving
-                    let entry_to_save = {
-                        if let Some(queue_entry) =
-                            self.entries.iter_mut().find(|e| e.id == entry.id)
-                        {
-                            queue_entry.mark_failed(error_msg);
-                            Some(queue_entry.clone())
-                        } else {
-                            None
+                    if let Some(queued) = queued {
+                        if queued.record_failure(&e, max_attempts) {
+                            report.dead_lettered.push(entry.id.clone());
                         }
-                    };
-
-                    // Save after releasing the mutable borrow
-                    if let Some(e) = entry_to_save {
-                        self.save_entry(&e)?;
                     }
+                    report.failed.push((entry.id, e.to_string()));
                 }
             }
         }
@@ -380,7 +821,7 @@ impl OfflineQueue {
     }
 
     /// Execute a single queue entry
-    fn execute_entry(&self, entry: &QueueEntry) -> Result<()> {
+    fn execute_entry(entry: &QueueEntry) -> Result<()> {
         crate::vlog!(
             "Executing queued operation: {}",
             entry.operation.description()
@@ -478,6 +919,24 @@ impl OfflineQueue {
                 crate::warn!("Comment sync not yet implemented for {}", repo_path);
                 Ok(())
             }
+
+            QueuedOperation::StoreMetadata {
+                repo_path: _,
+                namespace,
+                name,
+                commit_id,
+                metadata,
+            } => {
+                let config = auxin_config::Config::load().unwrap_or_default();
+                let client = AuxinServerClient::new(ServerConfig::from_config(&config))?;
+
+                client
+                    .store_metadata(namespace, name, commit_id, metadata)
+                    .with_context(|| format!("Failed to store metadata for {}", commit_id))?;
+
+                crate::vlog!("Stored metadata for commit {}", commit_id);
+                Ok(())
+            }
         }
     }
 
@@ -488,13 +947,7 @@ impl OfflineQueue {
 
     /// Save an entry to disk
     fn save_entry(&self, entry: &QueueEntry) -> Result<()> {
-        let file_path = self.entry_file_path(&entry.id);
-        let json =
-            serde_json::to_string_pretty(entry).context("Failed to serialize queue entry")?;
-
-        fs::write(&file_path, json).context("Failed to write queue entry to disk")?;
-
-        Ok(())
+        write_entry(&self.queue_dir, entry)
     }
 
     /// Load all entries from disk
@@ -546,7 +999,7 @@ impl OfflineQueue {
         let failed_count = self
             .entries
             .iter()
-            .filter(|e| e.last_error.is_some() && !e.completed)
+            .filter(|e| e.last_error.is_some() && e.is_pending())
             .count();
 
         QueueStats {
@@ -554,6 +1007,7 @@ impl OfflineQueue {
             pending: pending_count,
             completed: completed_count,
             failed: failed_count,
+            dead_letter: self.dead_letters().len(),
         }
     }
 }
@@ -567,6 +1021,7 @@ impl OfflineQueue {
         Self {
             queue_dir: path.join(".auxin_queue"),
             entries: Vec::new(),
+            policy: SyncPolicy::default(),
         }
     }
 
@@ -605,7 +1060,7 @@ impl OfflineQueue {
 
     /// Get the count of pending operations
     pub fn pending_count(&self) -> usize {
-        self.entries.iter().filter(|e| !e.completed).count()
+        self.entries.iter().filter(|e| e.is_pending()).count()
     }
 
     /// List all pending entries
@@ -613,7 +1068,7 @@ impl OfflineQueue {
         Ok(self
             .entries
             .iter()
-            .filter(|e| !e.completed)
+            .filter(|e| e.is_pending())
             .cloned()
             .collect())
     }
@@ -655,6 +1110,16 @@ impl OfflineQueue {
     }
 }
 
+/// Write an entry to its file in `queue_dir`
+fn write_entry(queue_dir: &Path, entry: &QueueEntry) -> Result<()> {
+    let file_path = queue_dir.join(format!("{}.json", entry.id));
+    let json = serde_json::to_string_pretty(entry).context("Failed to serialize queue entry")?;
+
+    fs::write(&file_path, json).context("Failed to write queue entry to disk")?;
+
+    Ok(())
+}
+
 impl Default for OfflineQueue {
     fn default() -> Self {
         Self::new().expect("Failed to create default offline queue")
@@ -669,6 +1134,9 @@ pub struct SyncReport {
 
     /// IDs and errors of failed entries
     pub failed: Vec<(String, String)>,
+
+    /// IDs of failed entries that were dead-lettered
+    pub dead_lettered: Vec<String>,
 }
 
 impl SyncReport {
@@ -676,6 +1144,7 @@ impl SyncReport {
         Self {
             succeeded: Vec::new(),
             failed: Vec::new(),
+            dead_lettered: Vec::new(),
         }
     }
 
@@ -699,12 +1168,14 @@ impl Default for SyncReport {
 }
 
 /// Queue statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct QueueStats {
     pub total: usize,
     pub pending: usize,
     pub completed: usize,
     pub failed: usize,
+    /// Entries that failed too often and are no longer retried
+    pub dead_letter: usize,
 }
 
 // =============================================================================
@@ -725,6 +1196,172 @@ mod tests {
         assert!(temp_dir.path().exists());
     }
 
+    #[test]
+    fn test_scheduler_orders_by_class_resource_and_age() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let now = Utc::now();
+        let queued = |operation: QueuedOperation, minutes_ago: i64, priority: i32| {
+            let mut entry = QueueEntry::new(operation);
+            entry.queued_at = now - Duration::minutes(minutes_ago);
+            entry.priority = priority;
+            entry
+        };
+        let push = |repo: &str| QueuedOperation::PushCommits {
+            repo_path: repo.to_string(),
+            branch: "main".to_string(),
+        };
+        let project_path = "song.logicx".to_string();
+        let entries = vec![
+            queued(push("song.logicx"), 10, 0),
+            queued(
+                QueuedOperation::StoreMetadata {
+                    repo_path: "song.logicx".to_string(),
+                    namespace: "studio".to_string(),
+                    name: "song".to_string(),
+                    commit_id: "abc123".to_string(),
+                    metadata: LogicProMetadata {
+                        bpm: Some(120.0),
+                        sample_rate: None,
+                        key_signature: None,
+                        tags: None,
+                        custom: None,
+                    },
+                },
+                1,
+                0,
+            ),
+            queued(
+                QueuedOperation::AcquireLock {
+                    project_path: project_path.clone(),
+                    user_id: "sam@studio".to_string(),
+                    timeout_hours: 4,
+                },
+                5,
+                0,
+            ),
+            // Higher priority, but queued after the acquire on the same lock
+            queued(
+                QueuedOperation::ReleaseLock {
+                    project_path,
+                    lock_id: "a".to_string(),
+                },
+                4,
+                100,
+            ),
+        ];
+        let description = |entry: &QueueEntry| entry.operation.description();
+        let order = |policy: &SyncPolicy| -> Vec<String> {
+            run_scheduled(entries.clone(), policy, now, |_| Ok(()))
+                .iter()
+                .map(|(entry, _)| description(entry))
+                .collect()
+        };
+        let descriptions = |indices: [usize; 4]| -> Vec<String> {
+            indices.iter().map(|&i| description(&entries[i])).collect()
+        };
+
+        let mut policy = SyncPolicy {
+            max_concurrent: 1,
+            starvation: Duration::zero(),
+            ..SyncPolicy::default()
+        };
+        assert_eq!(order(&policy), descriptions([2, 3, 1, 0]));
+
+        // Waiting three intervals lifts the push to the lock class
+        policy.starvation = Duration::minutes(3);
+        assert_eq!(policy.effective_class(&entries[0], now), QosClass::Lock);
+        assert_eq!(order(&policy), descriptions([0, 2, 3, 1]));
+
+        // Pushes on different repositories never run together past the limit
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let pushes: Vec<QueueEntry> =
+            ["a", "b", "c"].iter().map(|repo| queued(push(repo), 1, 0)).collect();
+        policy.max_concurrent = 4;
+        let finished = run_scheduled(pushes, &policy, now, |_| {
+            let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now_running, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            running.fetch_sub(1, Ordering::SeqCst);
+            Err(anyhow!("offline"))
+        });
+        assert_eq!(finished.len(), 3);
+        assert!(finished.iter().all(|(_, result)| result.is_err()));
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_dead_letter_after_max_attempts() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut queue = OfflineQueue::with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let id = queue
+            .enqueue(QueuedOperation::PushCommits {
+                repo_path: "song.logicx".to_string(),
+                branch: "main".to_string(),
+            })
+            .unwrap();
+        let error = anyhow!("connection refused").context("Failed to push commits");
+
+        let entry = queue.entries.iter_mut().find(|e| e.id == id).unwrap();
+        assert!(!entry.record_failure(&error, 2));
+        assert!(entry.record_failure(&error, 2));
+        let entry = entry.clone();
+        queue.save_entry(&entry).unwrap();
+        assert_eq!(
+            entry.dead_letter.unwrap().error_chain,
+            vec!["Failed to push commits", "connection refused"]
+        );
+
+        // Survives a reload, out of the pending list
+        let mut queue = OfflineQueue::with_dir(temp_dir.path().to_path_buf()).unwrap();
+        assert!(queue.pending().is_empty());
+        assert_eq!(queue.stats().dead_letter, 1);
+        assert_eq!(queue.resolve_id(&id[..8]).unwrap(), id);
+
+        queue.retry_dead_letter(&id).unwrap();
+        assert_eq!(queue.pending()[0].attempts, 0);
+        assert!(queue.discard_dead_letter(&id).is_err());
+    }
+
+    #[test]
+    fn test_edit_and_requeue() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut queue = OfflineQueue::with_dir(temp_dir.path().join("queue")).unwrap();
+        let id = queue
+            .enqueue(QueuedOperation::AcquireLock {
+                project_path: "wrong.logicx".to_string(),
+                user_id: "sam@studio".to_string(),
+                timeout_hours: 4,
+            })
+            .unwrap();
+        let set = |field: &str, value: &str| vec![(field.to_string(), value.to_string())];
+
+        let project_path = temp_dir.path().canonicalize().unwrap();
+        let project_path = project_path.to_string_lossy().to_string();
+        queue.edit(&id, &set("project_path", &project_path)).unwrap();
+        let entry = queue.edit(&id, &set("timeout_hours", "8")).unwrap();
+        assert!(matches!(
+            &entry.operation,
+            QueuedOperation::AcquireLock { project_path: p, timeout_hours: 8, .. }
+                if *p == project_path
+        ));
+
+        // Rejected: unknown fields, wrong types, missing paths
+        assert!(queue.edit(&id, &set("branch", "main")).is_err());
+        assert!(queue.edit(&id, &set("timeout_hours", "-1")).is_err());
+        assert!(queue.edit(&id, &set("project_path", "/no/such/project")).is_err());
+
+        let entry = queue.entries.iter_mut().find(|e| e.id == id).unwrap();
+        entry.record_failure(&anyhow!("offline"), 1);
+        queue.requeue(&id, Some(50)).unwrap();
+
+        let queue = OfflineQueue::with_dir(temp_dir.path().join("queue")).unwrap();
+        let entry = queue.get(&id).unwrap();
+        assert!(entry.is_pending());
+        assert_eq!((entry.priority, entry.attempts), (50, 0));
+    }
+
     #[test]
     fn test_enqueue_operation() {
         let temp_dir = TempDir::new().unwrap();
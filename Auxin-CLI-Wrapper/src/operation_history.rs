@@ -1,7 +1,9 @@
+use crate::audit_sink::AuditLog;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -63,11 +65,25 @@ pub enum HistoryOperation {
 
     // Conflict detection
     ConflictCheck,
+    ConflictResolve,
+
+    // Daemon
+    DaemonRestart,
 
     // Other
     Custom(String),
 }
 
+impl HistoryOperation {
+    /// Name shown in listings: the custom name, or the variant name
+    pub fn label(&self) -> String {
+        match self {
+            HistoryOperation::Custom(name) => name.clone(),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum OperationResult {
     Success,
@@ -116,19 +132,42 @@ impl OperationHistoryEntry {
 /// Manages operation history and audit trail
 pub struct OperationHistoryManager {
     history_file: PathBuf,
+    /// External systems each recorded entry is mirrored to
+    audit: Option<AuditLog>,
 }
 
 impl OperationHistoryManager {
-    /// Create new manager with default history location
+    /// Create new manager with default history location, mirroring to the
+    /// sinks in `[audit]`
     pub fn new() -> Self {
+        let config = auxin_config::Config::load().unwrap_or_default();
+        let audit = AuditLog::from_config(&config.audit).unwrap_or_else(|e| {
+            crate::warn!("Audit sinks not used: {}", e);
+            None
+        });
         Self {
             history_file: Self::default_history_path(),
+            audit,
         }
     }
 
     /// Create with custom history file path
     pub fn with_history_path(history_file: PathBuf) -> Self {
-        Self { history_file }
+        Self {
+            history_file,
+            audit: None,
+        }
+    }
+
+    /// Mirror recorded entries to `audit`
+    pub fn with_audit(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// The sinks entries are mirrored to, if any
+    pub fn audit(&self) -> Option<&AuditLog> {
+        self.audit.as_ref()
     }
 
     /// Get default history file path (~/.auxin/operation_history.json)
@@ -168,8 +207,16 @@ impl OperationHistoryManager {
         Ok(())
     }
 
-    /// Record a new operation
+    /// Record a new operation, and send it to the audit sinks
     pub fn record(&self, entry: OperationHistoryEntry) -> Result<()> {
+        if let Some(audit) = &self.audit {
+            for delivery in audit.record(&entry) {
+                if let Some(error) = delivery.error {
+                    crate::vlog!("Audit entry for {} spooled: {}", delivery.sink, error);
+                }
+            }
+        }
+
         let mut entries = self.load_history()?;
 
         // Add new entry
@@ -223,6 +270,16 @@ impl OperationHistoryManager {
             .collect())
     }
 
+    /// Get the most recent operations for a repository
+    pub fn get_recent_for_repo(
+        &self,
+        repo_path: &Path,
+        limit: usize,
+    ) -> Result<Vec<OperationHistoryEntry>> {
+        let entries = self.get_by_repo(repo_path)?;
+        Ok(entries.into_iter().rev().take(limit).collect())
+    }
+
     /// Get statistics about operations
     pub fn get_stats(&self) -> Result<OperationStats> {
         let entries = self.load_history()?;
@@ -254,12 +311,20 @@ impl OperationHistoryManager {
             })
             .count();
 
+        let mut failures_by_operation = BTreeMap::new();
+        for entry in entries.iter().filter(|e| e.is_failure()) {
+            *failures_by_operation
+                .entry(entry.operation.label())
+                .or_insert(0) += 1;
+        }
+
         Ok(OperationStats {
             total,
             successful,
             failed,
             lock_operations,
             network_operations,
+            failures_by_operation,
         })
     }
 
@@ -305,9 +370,12 @@ impl OperationHistoryManager {
         Ok(())
     }
 
-    /// Display recent history in a formatted way
-    pub fn display_recent(&self, limit: usize) -> Result<()> {
-        let entries = self.get_recent(limit)?;
+    /// Display recent history in a formatted way, only for `repo` if given
+    pub fn display_recent(&self, limit: usize, repo: Option<&Path>) -> Result<()> {
+        let entries = match repo {
+            Some(repo) => self.get_recent_for_repo(repo, limit)?,
+            None => self.get_recent(limit)?,
+        };
 
         if entries.is_empty() {
             println!("{}", "No operation history yet".bright_black());
@@ -329,6 +397,7 @@ impl OperationHistoryManager {
                 HistoryOperation::Pull => "⬇",
                 HistoryOperation::Commit => "●",
                 HistoryOperation::Login => "🔑",
+                HistoryOperation::DaemonRestart => "♻",
                 _ => "•",
             };
 
@@ -341,10 +410,10 @@ impl OperationHistoryManager {
             let time_ago = Self::format_time_ago(&entry.timestamp);
 
             println!(
-                "│ {} {} {:?} {} {}",
+                "│ {} {} {} {} {}",
                 icon,
                 result_icon,
-                entry.operation,
+                entry.operation.label(),
                 format!("by {}", entry.user).bright_black(),
                 time_ago.bright_black()
             );
@@ -362,6 +431,45 @@ impl OperationHistoryManager {
         Ok(())
     }
 
+    /// Display operation statistics
+    pub fn display_stats(&self) -> Result<()> {
+        let stats = self.get_stats()?;
+
+        if stats.total == 0 {
+            println!("{}", "No operation history yet".bright_black());
+            return Ok(());
+        }
+
+        println!(
+            "\n{}",
+            "┌─ Operation Statistics ──────────────────────────────────┐".bright_blue()
+        );
+        println!("│ Total operations:   {}", stats.total);
+        println!(
+            "│ Successful:         {} ({:.1}%)",
+            stats.successful.to_string().green(),
+            stats.success_rate()
+        );
+        println!("│ Failed:             {}", stats.failed.to_string().red());
+        println!("│ Lock operations:    {}", stats.lock_operations);
+        println!("│ Network operations: {}", stats.network_operations);
+
+        if !stats.failures_by_operation.is_empty() {
+            println!("│");
+            println!("│ {}", "Failures by operation:".bold());
+            for (operation, count) in &stats.failures_by_operation {
+                println!("│   {:<18} {}", operation, count);
+            }
+        }
+
+        println!(
+            "{}\n",
+            "└──────────────────────────────────────────────────────────┘".bright_blue()
+        );
+
+        Ok(())
+    }
+
     /// Format timestamp as "X minutes/hours/days ago"
     fn format_time_ago(timestamp: &DateTime<Utc>) -> String {
         let now = Utc::now();
@@ -392,6 +500,18 @@ pub struct OperationStats {
     pub failed: usize,
     pub lock_operations: usize,
     pub network_operations: usize,
+    /// Failed operations, counted by [`HistoryOperation::label`]
+    pub failures_by_operation: BTreeMap<String, usize>,
+}
+
+impl OperationStats {
+    /// Percentage of operations that succeeded
+    pub fn success_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.successful as f64 * 100.0 / self.total as f64
+    }
 }
 
 #[cfg(test)]
@@ -515,6 +635,36 @@ mod tests {
         assert_eq!(stats.failed, 1);
         assert_eq!(stats.lock_operations, 1);
         assert_eq!(stats.network_operations, 2);
+        assert_eq!(stats.failures_by_operation.get("Commit"), Some(&1));
+        assert_eq!(stats.success_rate(), 75.0);
+    }
+
+    #[test]
+    fn test_get_recent_for_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_file = temp_dir.path().join("history.json");
+        let manager = OperationHistoryManager::with_history_path(history_file);
+
+        for (repo, i) in [("/a", 0), ("/b", 1), ("/a", 2), ("/a", 3)] {
+            manager
+                .record(
+                    OperationHistoryEntry::new(HistoryOperation::Commit)
+                        .with_repo_path(repo)
+                        .with_metadata("index", i.to_string()),
+                )
+                .unwrap();
+        }
+
+        let recent = manager.get_recent_for_repo(Path::new("/a"), 2).unwrap();
+        let indexes: Vec<_> = recent
+            .iter()
+            .map(|e| e.metadata["index"].as_str())
+            .collect();
+        assert_eq!(indexes, ["3", "2"]);
+        assert!(manager
+            .get_recent_for_repo(Path::new("/c"), 10)
+            .unwrap()
+            .is_empty());
     }
 
     #[test]
@@ -0,0 +1,148 @@
+//! OS file lock held while restore or pull rewrites the working copy
+//!
+//! While oxen is rewriting a project, anything else writing into it (the
+//! daemon's auto-commit reacting to the very files being restored, a second
+//! `auxin pull` in another terminal) can leave a half-old, half-new tree.
+//! Restore and pull hold an exclusive lock on `.auxin/operation.lock` for
+//! the duration; `commit`, `add`, `quick-commit` and other restores and
+//! pulls refuse to start while it's held.
+//!
+//! The lock is an OS file lock (`flock` on macOS and Linux), so the OS
+//! releases it if auxin crashes or is killed, and the guard releases it when
+//! dropped, including on panic. Making the tree read-only instead isn't an
+//! option: oxen runs as the same user and would be locked out too.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Who holds the lock, as written into the lock file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub operation: String,
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Held for as long as the working copy is being rewritten
+#[derive(Debug)]
+pub struct OperationLock {
+    file: File,
+}
+
+impl OperationLock {
+    /// Location of a project's lock file
+    pub fn path(project: &Path) -> PathBuf {
+        project.join(".auxin").join("operation.lock")
+    }
+
+    fn open(project: &Path) -> Result<File> {
+        let path = Self::path(project);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))
+    }
+
+    /// Take the lock for `operation`, failing if another process holds it
+    pub fn acquire(project: &Path, operation: &str) -> Result<Self> {
+        let mut file = Self::open(project)?;
+        match file.try_lock() {
+            Ok(()) => {},
+            Err(TryLockError::WouldBlock) => {
+                bail!("{}", busy_message(read_holder(&mut file)))
+            },
+            Err(TryLockError::Error(e)) => return Err(e).context("Failed to lock the project"),
+        }
+
+        let holder = LockHolder {
+            operation: operation.to_string(),
+            pid: std::process::id(),
+            started_at: Utc::now(),
+        };
+        file.set_len(0)?;
+        file.write_all(serde_json::to_string(&holder)?.as_bytes())?;
+        file.flush()?;
+        Ok(Self { file })
+    }
+
+    /// Fail if a restore or pull is rewriting the project
+    pub fn ensure_free(project: &Path) -> Result<()> {
+        if !Self::path(project).exists() {
+            return Ok(());
+        }
+        let mut file = Self::open(project)?;
+        match file.try_lock_shared() {
+            Ok(()) => Ok(()),
+            Err(TryLockError::WouldBlock) => bail!("{}", busy_message(read_holder(&mut file))),
+            Err(TryLockError::Error(e)) => Err(e).context("Failed to check the project lock"),
+        }
+    }
+}
+
+impl Drop for OperationLock {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+fn read_holder(file: &mut File) -> Option<LockHolder> {
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn busy_message(holder: Option<LockHolder>) -> String {
+    match holder {
+        Some(holder) => format!(
+            "An auxin {} (pid {}) is rewriting this project; try again when it finishes",
+            holder.operation, holder.pid
+        ),
+        None => {
+            "Another auxin operation is rewriting this project; try again when it finishes"
+                .to_string()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_operation_lock() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        OperationLock::ensure_free(project).unwrap();
+
+        let lock = OperationLock::acquire(project, "restore").unwrap();
+        let err = OperationLock::acquire(project, "pull").unwrap_err();
+        assert!(err.to_string().contains(&format!(
+            "auxin restore (pid {})",
+            std::process::id()
+        )));
+        assert!(OperationLock::ensure_free(project).is_err());
+
+        drop(lock);
+        OperationLock::ensure_free(project).unwrap();
+
+        // Released when the holder unwinds
+        let result = std::panic::catch_unwind(|| {
+            let _lock = OperationLock::acquire(project, "pull").unwrap();
+            panic!("pull failed");
+        });
+        assert!(result.is_err());
+        OperationLock::ensure_free(project).unwrap();
+    }
+}
@@ -0,0 +1,552 @@
+//! OSC triggers for commit actions
+//!
+//! Producers live in their DAW. With `[integrations.osc]` enabled, `auxin osc
+//! listen` (started by the daemon, or run by hand) accepts OSC messages over
+//! UDP from a control surface, TouchOSC, or the DAW's own OSC output, and
+//! maps their addresses to actions:
+//!
+//! ```toml
+//! [integrations.osc]
+//! enabled = true
+//! bind = "127.0.0.1:9050"
+//!
+//! [integrations.osc.addresses]
+//! "/auxin/milestone" = "milestone"
+//! "/auxin/commit" = "commit"
+//! "/auxin/lock/toggle" = "toggle-lock"
+//! ```
+//!
+//! A message may carry string arguments: the first names the project (folder
+//! name or path) and the second overrides the commit message. Without a
+//! project argument the listener's only project is used. A first numeric
+//! argument of 0 is ignored, so buttons that send 1 on press and 0 on
+//! release trigger once.
+//!
+//! Actions run through the `auxin` CLI itself, so they behave exactly like
+//! the equivalent commands typed in the project directory.
+
+use crate::quick_actions::{self, current_metadata_args, LockState};
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::{SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Largest datagram accepted; OSC packets from control surfaces are tiny
+const MAX_PACKET_SIZE: usize = 8192;
+
+/// Something an OSC message can ask auxin to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OscAction {
+    /// Commit tagged `milestone` with the project's current metadata
+    Milestone,
+    /// Plain checkpoint commit
+    Commit,
+    /// Release the lock if this user holds it, otherwise acquire it
+    ToggleLock,
+}
+
+impl OscAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OscAction::Milestone => "milestone",
+            OscAction::Commit => "commit",
+            OscAction::ToggleLock => "toggle-lock",
+        }
+    }
+}
+
+impl fmt::Display for OscAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OscAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "milestone" => Ok(OscAction::Milestone),
+            "commit" => Ok(OscAction::Commit),
+            "toggle-lock" => Ok(OscAction::ToggleLock),
+            _ => bail!(
+                "Unknown OSC action '{}' (expected milestone, commit or toggle-lock)",
+                s
+            ),
+        }
+    }
+}
+
+/// A decoded OSC argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+    String(String),
+    Blob(Vec<u8>),
+    Long(i64),
+    Double(f64),
+    Bool(bool),
+    Nil,
+}
+
+impl OscArg {
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            OscArg::Int(v) => Some(*v as f64),
+            OscArg::Float(v) => Some(*v as f64),
+            OscArg::Long(v) => Some(*v as f64),
+            OscArg::Double(v) => Some(*v),
+            OscArg::Bool(v) => Some(if *v { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded OSC message
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    pub fn new(address: impl Into<String>, args: Vec<OscArg>) -> Self {
+        Self {
+            address: address.into(),
+            args,
+        }
+    }
+
+    /// Encode as an OSC 1.0 packet
+    pub fn encode(&self) -> Vec<u8> {
+        let mut packet = Vec::new();
+        write_padded_str(&mut packet, &self.address);
+
+        let mut tags = String::from(",");
+        let mut data = Vec::new();
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(v) => {
+                    tags.push('i');
+                    data.extend_from_slice(&v.to_be_bytes());
+                }
+                OscArg::Float(v) => {
+                    tags.push('f');
+                    data.extend_from_slice(&v.to_be_bytes());
+                }
+                OscArg::String(v) => {
+                    tags.push('s');
+                    write_padded_str(&mut data, v);
+                }
+                OscArg::Blob(v) => {
+                    tags.push('b');
+                    data.extend_from_slice(&(v.len() as i32).to_be_bytes());
+                    data.extend_from_slice(v);
+                    data.resize(data.len().next_multiple_of(4), 0);
+                }
+                OscArg::Long(v) => {
+                    tags.push('h');
+                    data.extend_from_slice(&v.to_be_bytes());
+                }
+                OscArg::Double(v) => {
+                    tags.push('d');
+                    data.extend_from_slice(&v.to_be_bytes());
+                }
+                OscArg::Bool(v) => tags.push(if *v { 'T' } else { 'F' }),
+                OscArg::Nil => tags.push('N'),
+            }
+        }
+
+        write_padded_str(&mut packet, &tags);
+        packet.extend_from_slice(&data);
+        packet
+    }
+}
+
+/// Decode an OSC packet (a message or a bundle) into its messages
+pub fn decode_packet(packet: &[u8]) -> Result<Vec<OscMessage>> {
+    let mut messages = Vec::new();
+    decode_into(packet, &mut messages)?;
+    Ok(messages)
+}
+
+fn decode_into(packet: &[u8], messages: &mut Vec<OscMessage>) -> Result<()> {
+    let mut reader = Reader::new(packet);
+
+    if packet.starts_with(b"#bundle\0") {
+        reader.take(16)?; // "#bundle\0" + time tag; actions run immediately
+        while !reader.is_empty() {
+            let size = reader.i32()?;
+            let size = usize::try_from(size).map_err(|_| anyhow!("Negative bundle element size"))?;
+            decode_into(reader.take(size)?, messages)?;
+        }
+        return Ok(());
+    }
+
+    let address = reader.padded_str()?;
+    if !address.starts_with('/') {
+        bail!("Invalid OSC address '{}'", address);
+    }
+
+    // Type tags are optional in very old senders
+    let tags = if reader.is_empty() {
+        String::new()
+    } else {
+        reader.padded_str()?
+    };
+    let tags = tags.strip_prefix(',').unwrap_or_default();
+
+    let mut args = Vec::with_capacity(tags.len());
+    for tag in tags.chars() {
+        let arg = match tag {
+            'i' => OscArg::Int(reader.i32()?),
+            'f' => OscArg::Float(f32::from_be_bytes(reader.array()?)),
+            's' | 'S' => OscArg::String(reader.padded_str()?),
+            'b' => {
+                let size = usize::try_from(reader.i32()?)
+                    .map_err(|_| anyhow!("Negative OSC blob size"))?;
+                let blob = reader.take(size)?.to_vec();
+                reader.take(size.next_multiple_of(4) - size)?;
+                OscArg::Blob(blob)
+            }
+            'h' => OscArg::Long(i64::from_be_bytes(reader.array()?)),
+            'd' => OscArg::Double(f64::from_be_bytes(reader.array()?)),
+            'T' => OscArg::Bool(true),
+            'F' => OscArg::Bool(false),
+            'N' | 'I' => OscArg::Nil,
+            other => bail!("Unsupported OSC type tag '{}'", other),
+        };
+        args.push(arg);
+    }
+
+    messages.push(OscMessage { address, args });
+    Ok(())
+}
+
+fn write_padded_str(buffer: &mut Vec<u8>, s: &str) {
+    buffer.extend_from_slice(s.as_bytes());
+    buffer.push(0);
+    buffer.resize(buffer.len().next_multiple_of(4), 0);
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow!("Truncated OSC packet"))?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("slice has N bytes"))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn padded_str(&mut self) -> Result<String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| anyhow!("Unterminated OSC string"))?;
+        let s = std::str::from_utf8(&rest[..len]).context("OSC string is not UTF-8")?;
+        self.take((len + 1).next_multiple_of(4))?;
+        Ok(s.to_string())
+    }
+}
+
+/// What a received message asks for
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscTrigger {
+    pub action: OscAction,
+    /// Project selector from the first string argument
+    pub project: Option<String>,
+    /// Commit message from the second string argument
+    pub message: Option<String>,
+}
+
+/// Address -> action table from `[integrations.osc.addresses]`
+#[derive(Debug, Clone, Default)]
+pub struct OscBindings {
+    addresses: BTreeMap<String, OscAction>,
+}
+
+impl OscBindings {
+    pub fn from_config(addresses: &BTreeMap<String, String>) -> Result<Self> {
+        let addresses = addresses
+            .iter()
+            .map(|(address, action)| {
+                let action = action
+                    .parse()
+                    .with_context(|| format!("[integrations.osc.addresses] \"{}\"", address))?;
+                Ok((address.clone(), action))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { addresses })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, OscAction)> {
+        self.addresses.iter().map(|(a, action)| (a.as_str(), *action))
+    }
+
+    /// The action a message triggers, if any
+    pub fn trigger(&self, message: &OscMessage) -> Option<OscTrigger> {
+        let action = *self.addresses.get(&message.address)?;
+
+        // Button release
+        if message.args.first().and_then(OscArg::as_number) == Some(0.0) {
+            return None;
+        }
+
+        let mut strings = message.args.iter().filter_map(|arg| match arg {
+            OscArg::String(s) if !s.is_empty() => Some(s.clone()),
+            _ => None,
+        });
+
+        Some(OscTrigger {
+            action,
+            project: strings.next(),
+            message: strings.next(),
+        })
+    }
+}
+
+/// Pick the project a trigger applies to
+pub fn resolve_project(projects: &[PathBuf], selector: Option<&str>) -> Result<PathBuf> {
+    let Some(selector) = selector else {
+        return match projects {
+            [project] => Ok(project.clone()),
+            [] => bail!("No projects to act on"),
+            _ => bail!("Several projects are watched; send the project name as an argument"),
+        };
+    };
+
+    let wanted = Path::new(selector);
+    projects
+        .iter()
+        .find(|project| {
+            project.as_path() == wanted
+                || project.file_name() == Some(wanted.as_os_str())
+                || project.file_stem() == Some(wanted.as_os_str())
+        })
+        .cloned()
+        .ok_or_else(|| anyhow!("No watched project matches '{}'", selector))
+}
+
+/// UDP listener that turns OSC messages into auxin commands
+pub struct OscListener {
+    socket: UdpSocket,
+    bindings: OscBindings,
+    projects: Vec<PathBuf>,
+    auxin_exe: PathBuf,
+}
+
+impl OscListener {
+    pub fn bind(addr: &str, bindings: OscBindings, projects: Vec<PathBuf>) -> Result<Self> {
+        let socket =
+            UdpSocket::bind(addr).with_context(|| format!("Failed to bind OSC listener to {}", addr))?;
+        let auxin_exe = std::env::current_exe().context("Failed to locate the auxin binary")?;
+        Ok(Self {
+            socket,
+            bindings,
+            projects,
+            auxin_exe,
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// Handle messages until the process is stopped
+    ///
+    /// Bad packets and failed actions are reported through `report` and
+    /// don't stop the listener.
+    pub fn run(&self, mut report: impl FnMut(Result<String>)) -> Result<()> {
+        let mut buffer = [0u8; MAX_PACKET_SIZE];
+        loop {
+            let (len, _) = self.socket.recv_from(&mut buffer)?;
+            let messages = match decode_packet(&buffer[..len]) {
+                Ok(messages) => messages,
+                Err(e) => {
+                    report(Err(e));
+                    continue;
+                }
+            };
+
+            for message in messages {
+                if let Some(trigger) = self.bindings.trigger(&message) {
+                    report(self.dispatch(&trigger));
+                }
+            }
+        }
+    }
+
+    /// Run a trigger; returns a one-line description of what was done
+    pub fn dispatch(&self, trigger: &OscTrigger) -> Result<String> {
+        let project = resolve_project(&self.projects, trigger.project.as_deref())?;
+        let name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let (args, summary) = match trigger.action {
+            OscAction::Milestone => {
+                let message = trigger.message.clone().unwrap_or_else(|| "Milestone".to_string());
+                let mut args = vec!["commit".to_string(), "-m".to_string(), message];
+                args.extend(current_metadata_args(&project));
+                args.extend(["--tags".to_string(), "milestone".to_string()]);
+                (args, format!("Milestone commit in {}", name))
+            }
+            OscAction::Commit => {
+                let message = trigger.message.clone().unwrap_or_else(|| "Checkpoint".to_string());
+                let args = vec!["commit".to_string(), "-m".to_string(), message];
+                (args, format!("Commit in {}", name))
+            }
+            OscAction::ToggleLock => {
+                if quick_actions::lock_state(&project) == LockState::Mine {
+                    (vec!["lock".into(), "release".into()], format!("Released lock on {}", name))
+                } else {
+                    (vec!["lock".into(), "acquire".into()], format!("Acquired lock on {}", name))
+                }
+            }
+        };
+
+        quick_actions::run_auxin(&self.auxin_exe, &project, &args)
+            .map_err(|e| anyhow!("{} ({})", e, name))?;
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings() -> OscBindings {
+        let config = auxin_config::Osc::default();
+        OscBindings::from_config(&config.addresses).unwrap()
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let message = OscMessage::new(
+            "/auxin/milestone",
+            vec![
+                OscArg::String("Song.logicx".to_string()),
+                OscArg::Int(-7),
+                OscArg::Float(1.5),
+                OscArg::Blob(vec![1, 2, 3]),
+                OscArg::Long(1 << 40),
+                OscArg::Double(0.25),
+                OscArg::Bool(true),
+                OscArg::Nil,
+            ],
+        );
+        let packet = message.encode();
+        assert_eq!(packet.len() % 4, 0);
+        assert_eq!(decode_packet(&packet).unwrap(), vec![message]);
+    }
+
+    #[test]
+    fn test_decode_known_packet_and_bundle() {
+        // "/lock" ",f" 1.0, as sent by TouchOSC
+        let packet = b"/lock\0\0\0,f\0\0\x3f\x80\x00\x00";
+        let messages = decode_packet(packet).unwrap();
+        assert_eq!(messages, vec![OscMessage::new("/lock", vec![OscArg::Float(1.0)])]);
+
+        let mut bundle = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+        for element in [&packet[..], &OscMessage::new("/commit", vec![]).encode()] {
+            bundle.extend_from_slice(&(element.len() as i32).to_be_bytes());
+            bundle.extend_from_slice(element);
+        }
+        let addresses: Vec<_> = decode_packet(&bundle)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.address)
+            .collect();
+        assert_eq!(addresses, ["/lock", "/commit"]);
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(decode_packet(b"").is_err());
+        assert!(decode_packet(b"nope\0\0\0\0").is_err());
+        assert!(decode_packet(b"/x\0\0,i\0\0\0\0").is_err());
+        assert!(decode_packet(b"/x\0\0,q\0\0").is_err());
+        assert!(decode_packet(b"#bundle\0\0\0\0\0\0\0\0\0\0\0\0\x40").is_err());
+    }
+
+    #[test]
+    fn test_trigger_mapping() {
+        let bindings = bindings();
+
+        let press = OscMessage::new("/auxin/lock/toggle", vec![OscArg::Float(1.0)]);
+        assert_eq!(
+            bindings.trigger(&press),
+            Some(OscTrigger {
+                action: OscAction::ToggleLock,
+                project: None,
+                message: None,
+            })
+        );
+
+        let release = OscMessage::new("/auxin/lock/toggle", vec![OscArg::Float(0.0)]);
+        assert_eq!(bindings.trigger(&release), None);
+        assert_eq!(bindings.trigger(&OscMessage::new("/other", vec![])), None);
+
+        let milestone = OscMessage::new(
+            "/auxin/milestone",
+            vec![
+                OscArg::String("Song".to_string()),
+                OscArg::String("Final vocal comp".to_string()),
+            ],
+        );
+        let trigger = bindings.trigger(&milestone).unwrap();
+        assert_eq!(trigger.action, OscAction::Milestone);
+        assert_eq!(trigger.project.as_deref(), Some("Song"));
+        assert_eq!(trigger.message.as_deref(), Some("Final vocal comp"));
+
+        let mut addresses = BTreeMap::new();
+        addresses.insert("/x".to_string(), "explode".to_string());
+        let error = OscBindings::from_config(&addresses).unwrap_err();
+        assert!(format!("{:#}", error).contains("Unknown OSC action 'explode'"));
+    }
+
+    #[test]
+    fn test_resolve_project() {
+        let song = PathBuf::from("/music/Song.logicx");
+        let demo = PathBuf::from("/music/Demo.logicx");
+
+        assert_eq!(resolve_project(std::slice::from_ref(&song), None).unwrap(), song);
+        assert!(resolve_project(&[], None).is_err());
+
+        let both = [song.clone(), demo.clone()];
+        assert!(resolve_project(&both, None).is_err());
+        assert_eq!(resolve_project(&both, Some("Demo")).unwrap(), demo);
+        assert_eq!(resolve_project(&both, Some("Song.logicx")).unwrap(), song);
+        assert_eq!(resolve_project(&both, Some("/music/Demo.logicx")).unwrap(), demo);
+        assert!(resolve_project(&both, Some("Other")).is_err());
+    }
+}
@@ -35,6 +35,7 @@
 /// ```
 use anyhow::Result;
 use std::path::Path;
+use std::sync::mpsc::Sender;
 
 // Feature-gated liboxen imports
 #[cfg(feature = "ffi")]
@@ -45,7 +46,7 @@ use liboxen::{
 };
 
 // Re-export common types from auxin-oxen crate
-pub use auxin_oxen::{BranchInfo, CommitInfo, StatusInfo};
+pub use auxin_oxen::{BranchInfo, CommitInfo, ProgressEvent, StatusInfo};
 
 /// Backend implementation type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -118,8 +119,36 @@ pub trait OxenBackend: Send + Sync {
     /// Push to remote
     fn push(&self, repo_path: &Path, remote: Option<&str>, branch: Option<&str>) -> Result<()>;
 
-    /// Pull from remote
-    fn pull(&self, repo_path: &Path) -> Result<()>;
+    /// Push to remote, sending progress updates down the channel as the
+    /// push runs
+    ///
+    /// Backends that can't report progress just push.
+    fn push_with_progress(
+        &self,
+        repo_path: &Path,
+        remote: Option<&str>,
+        branch: Option<&str>,
+        _progress: &Sender<ProgressEvent>,
+    ) -> Result<()> {
+        self.push(repo_path, remote, branch)
+    }
+
+    /// Pull from remote (default: the tracked remote and branch)
+    fn pull(&self, repo_path: &Path, remote: Option<&str>, branch: Option<&str>) -> Result<()>;
+
+    /// Pull from remote, sending progress updates down the channel as the
+    /// pull runs
+    ///
+    /// Backends that can't report progress just pull.
+    fn pull_with_progress(
+        &self,
+        repo_path: &Path,
+        remote: Option<&str>,
+        branch: Option<&str>,
+        _progress: &Sender<ProgressEvent>,
+    ) -> Result<()> {
+        self.pull(repo_path, remote, branch)
+    }
 
     /// Get backend type
     fn backend_type(&self) -> BackendType;
@@ -171,6 +200,25 @@ pub fn create_backend(backend_type: BackendType) -> Result<Box<dyn OxenBackend>>
     }
 }
 
+/// Create a backend whose network operations time out after
+/// `network_timeout_secs` (the FFI backend has no timeout)
+pub fn create_backend_with_network_timeout(
+    backend_type: BackendType,
+    network_timeout_secs: u64,
+) -> Result<Box<dyn OxenBackend>> {
+    match backend_type {
+        BackendType::Subprocess => {
+            let config = auxin_oxen::OxenConfig {
+                network_timeout: network_timeout_secs,
+                ..Default::default()
+            };
+            let subprocess = auxin_oxen::OxenSubprocess::with_config(config);
+            Ok(Box::new(SubprocessBackend::new(subprocess)))
+        }
+        BackendType::FFI => create_backend(backend_type),
+    }
+}
+
 /// Create the default backend (subprocess)
 pub fn create_default_backend() -> Result<Box<dyn OxenBackend>> {
     create_backend(BackendType::default())
@@ -256,8 +304,35 @@ impl OxenBackend for SubprocessBackend {
         self.inner.push(repo_path, remote, branch)
     }
 
-    fn pull(&self, repo_path: &Path) -> Result<()> {
-        self.inner.pull(repo_path)
+    fn push_with_progress(
+        &self,
+        repo_path: &Path,
+        remote: Option<&str>,
+        branch: Option<&str>,
+        progress: &Sender<ProgressEvent>,
+    ) -> Result<()> {
+        self.inner
+            .push_with_progress(repo_path, remote, branch, progress)
+    }
+
+    fn pull(&self, repo_path: &Path, remote: Option<&str>, branch: Option<&str>) -> Result<()> {
+        match (remote, branch) {
+            (Some(remote), Some(branch)) => self.inner.pull_from(repo_path, remote, branch),
+            _ => self.inner.pull(repo_path),
+        }
+    }
+
+    fn pull_with_progress(
+        &self,
+        repo_path: &Path,
+        remote: Option<&str>,
+        branch: Option<&str>,
+        progress: &Sender<ProgressEvent>,
+    ) -> Result<()> {
+        // As in `pull`, both are needed to leave the tracked branch
+        let (remote, branch) = remote.zip(branch).unzip();
+        self.inner
+            .pull_with_progress(repo_path, remote, branch, progress)
     }
 
     fn backend_type(&self) -> BackendType {
@@ -311,6 +386,8 @@ impl FFIBackend {
         CommitInfo {
             id: commit.id.clone(),
             message: commit.message.clone(),
+            author: Some(format!("{} <{}>", commit.author, commit.email)),
+            timestamp: chrono::DateTime::from_timestamp(commit.timestamp.unix_timestamp(), 0),
         }
     }
 
@@ -468,7 +545,7 @@ impl OxenBackend for FFIBackend {
             .map_err(Self::convert_error)
     }
 
-    fn pull(&self, repo_path: &Path) -> Result<()> {
+    fn pull(&self, repo_path: &Path, _remote: Option<&str>, _branch: Option<&str>) -> Result<()> {
         let repo = LocalRepository::from_dir(repo_path).map_err(Self::convert_error)?;
 
         self.runtime
@@ -2,10 +2,11 @@ use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
 use std::path::{Path, PathBuf};
 
+use auxin_config::RepoFeatures;
+
 use crate::commit_metadata::CommitMetadata;
 use crate::draft_manager::DraftManager;
-use crate::ignore_template::generate_oxenignore;
-use crate::logic_project::LogicProject;
+use crate::project_handler::{append_commit_metadata, LogicProHandler, ProjectHandler};
 use crate::OxenSubprocess;
 use crate::{info, vlog};
 
@@ -43,35 +44,48 @@ impl OxenRepository {
     /// 3. Create a .oxenignore file with Logic Pro-specific patterns
     /// 4. Initialize draft branch workflow
     pub async fn init_for_logic_project(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init_for_logic_project_with(path, &RepoFeatures::default()).await
+    }
+
+    /// Initializes a Logic Pro project repository with the given features,
+    /// recorded in `.auxin/features.toml` and committed with the project
+    pub async fn init_for_logic_project_with(
+        path: impl AsRef<Path>,
+        features: &RepoFeatures,
+    ) -> Result<Self> {
+        Self::init_for_project_with(path, &LogicProHandler, features).await
+    }
+
+    /// Initializes a repository for a project of the type `handler`
+    /// describes, with its `.oxenignore`, an initial commit and (unless
+    /// turned off in `features`) the draft branch
+    pub async fn init_for_project_with(
+        path: impl AsRef<Path>,
+        handler: &dyn ProjectHandler,
+        features: &RepoFeatures,
+    ) -> Result<Self> {
         let path = path.as_ref();
+        let project_name = handler.display_name();
 
-        vlog!("=== Initializing Logic Pro Project Repository ===");
+        vlog!("=== Initializing {} Project Repository ===", project_name);
         vlog!("Target path: {}", path.display());
 
-        // Detect Logic Pro project
-        vlog!("Step 1: Detecting Logic Pro project structure...");
-        let logic_project =
-            LogicProject::detect(path).context("Failed to detect Logic Pro project")?;
-
-        info!("Detected Logic Pro project: {}", logic_project.name());
-        vlog!("Project name: {}", logic_project.name());
-
-        // Check if this is part of a workspace folder structure
-        vlog!("Step 1a: Checking for Logic Pro workspace structure...");
-        let repo_path = if let Some(workspace) = LogicProject::detect_workspace_folder(path) {
-            info!("Detected Logic Pro workspace folder");
-            info!("  Workspace: {}", workspace.display());
-            info!("  Logic project: {}", path.display());
-            info!("Repository will be initialized at workspace level to capture external assets");
-            vlog!(
-                "Using workspace folder as repository root: {}",
-                workspace.display()
-            );
-            workspace
-        } else {
-            vlog!("No workspace detected, using .logicx as repository root");
-            path.to_path_buf()
-        };
+        // Detect the project
+        vlog!("Step 1: Detecting {} project structure...", project_name);
+        handler
+            .validate(path)
+            .with_context(|| format!("Failed to detect {} project", project_name))?;
+
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Unknown");
+        info!("Detected {} project: {}", project_name, name);
+        vlog!("Project name: {}", name);
+
+        vlog!("Step 1a: Choosing repository root...");
+        let repo_path = handler.repository_root(path);
+        vlog!("Repository root: {}", repo_path.display());
 
         // Initialize Oxen repository using subprocess
         vlog!(
@@ -104,7 +118,7 @@ impl OxenRepository {
         let ignore_path = repo_path.join(".oxenignore");
         vlog!("Ignore file path: {}", ignore_path.display());
 
-        let ignore_content = generate_oxenignore();
+        let ignore_content = handler.generate_oxenignore();
         vlog!("Generated ignore patterns ({} bytes)", ignore_content.len());
 
         tokio::fs::write(&ignore_path, ignore_content)
@@ -113,6 +127,10 @@ impl OxenRepository {
 
         info!("Created .oxenignore file");
 
+        features
+            .save(&repo_path)
+            .context("Failed to write .auxin/features.toml")?;
+
         // Create repository instance
         let repo_instance = Self {
             path: repo_path.clone(),
@@ -137,14 +155,30 @@ impl OxenRepository {
             .context("Failed to stage project files")?;
 
         vlog!("Creating initial commit...");
-        let initial_commit_msg = "Initial commit\n\nInitialized Oxen repository for Logic Pro project with .oxenignore template.";
+        let initial_commit_msg = format!(
+            "Initial commit\n\nInitialized Oxen repository for {} project with .oxenignore template.",
+            project_name
+        );
+        let initial_commit_msg = match handler.commit_metadata(path) {
+            Ok(details) => append_commit_metadata(&initial_commit_msg, &details),
+            Err(e) => {
+                vlog!("Could not read project details for the commit: {}", e);
+                initial_commit_msg
+            }
+        };
         repo_instance
             .oxen
-            .commit(&repo_path, initial_commit_msg)
+            .commit(&repo_path, &initial_commit_msg)
             .context("Failed to create initial commit")?;
 
         info!("Created initial commit");
 
+        if !features.features.draft_workflow {
+            vlog!("Draft workflow turned off for this repository");
+            vlog!("=== Initialization Complete ===");
+            return Ok(repo_instance);
+        }
+
         // Step 5: Initialize draft branch workflow (now that HEAD exists)
         vlog!("Step 5: Initializing draft branch workflow...");
         info!("Initializing draft branch workflow...");
@@ -165,6 +199,11 @@ impl OxenRepository {
 
     /// Initializes a new Oxen repository (generic)
     pub async fn init(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init_with(path, &RepoFeatures::default()).await
+    }
+
+    /// Initializes a new Oxen repository with the given features
+    pub async fn init_with(path: impl AsRef<Path>, features: &RepoFeatures) -> Result<Self> {
         let path = path.as_ref();
 
         let oxen = OxenSubprocess::new();
@@ -177,6 +216,9 @@ impl OxenRepository {
 
         oxen.init(path)
             .context("Failed to initialize Oxen repository")?;
+        features
+            .save(path)
+            .context("Failed to write .auxin/features.toml")?;
 
         Ok(Self {
             path: path.to_path_buf(),
@@ -303,10 +345,7 @@ impl OxenRepository {
     }
 
     /// Gets the commit history
-    pub async fn get_history(
-        &self,
-        limit: Option<usize>,
-    ) -> Result<Vec<crate::CommitInfo>> {
+    pub async fn get_history(&self, limit: Option<usize>) -> Result<Vec<crate::CommitInfo>> {
         let commits = self
             .oxen
             .log(&self.path, limit)
@@ -319,7 +358,8 @@ impl OxenRepository {
     ///
     /// Supports both full commit hashes (32+ chars) and short hashes (7+ chars).
     /// Short hashes are automatically expanded by searching the commit history.
-    pub async fn restore(&self, commit_id: &str) -> Result<()> {
+    /// Returns the full hash restored to.
+    pub async fn restore(&self, commit_id: &str) -> Result<String> {
         vlog!("Restore requested for: {}", commit_id);
 
         // If short hash (< 32 chars), expand to full hash
@@ -342,7 +382,7 @@ impl OxenRepository {
 
         println!("Successfully restored to commit: {}", full_hash);
 
-        Ok(())
+        Ok(full_hash)
     }
 
     /// Find a commit by prefix (short hash)
@@ -401,7 +441,13 @@ impl OxenRepository {
     }
 
     /// Ensure repository is on draft branch
+    ///
+    /// Does nothing when the repository's features turn the draft workflow off.
     pub async fn ensure_on_draft_branch(&self) -> Result<()> {
+        let features = RepoFeatures::load(&self.path)?.unwrap_or_default();
+        if !features.features.draft_workflow {
+            return Ok(());
+        }
         let draft = self.draft_manager()?;
 
         if !draft.is_on_draft_branch()? {
@@ -428,6 +474,8 @@ impl OxenRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ignore_template::generate_oxenignore;
+    use crate::logic_project::LogicProject;
     use std::fs;
 
     // Constructor tests
@@ -679,7 +727,7 @@ mod tests {
         assert_eq!(metadata.message, "Test message");
         assert_eq!(metadata.bpm, Some(120.0));
         assert_eq!(metadata.sample_rate, Some(48000));
-        assert_eq!(metadata.key_signature, Some("C major".to_string()));
+        assert_eq!(metadata.key_signature, Some("C Major".to_string()));
     }
 
     #[test]
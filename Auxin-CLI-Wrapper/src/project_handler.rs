@@ -0,0 +1,596 @@
+//! What auxin knows about each creative app's projects
+//!
+//! A [`ProjectHandler`] recognises one app's projects and supplies what
+//! `auxin init` and `auxin clone` need: validation, where the repository
+//! goes, the `.oxenignore` template, details read from the project and an
+//! example commit. [`ProjectRegistry`] holds the handlers; supporting
+//! another app means writing a handler and adding it to
+//! [`ProjectRegistry::builtin`].
+
+use crate::adobe_project::{AfterEffectsProject, PremiereProject};
+use crate::blender_parser::BlenderParser;
+use crate::blender_project::BlenderProject;
+use crate::ignore_template::{
+    generate_after_effects_oxenignore, generate_blender_oxenignore, generate_oxenignore,
+    generate_premiere_oxenignore, generate_sketchup_oxenignore, generate_unreal_oxenignore,
+};
+use crate::logic_parser::LogicParser;
+use crate::logic_project::LogicProject;
+use crate::sketchup_project::SketchUpProject;
+use crate::unreal_project::{is_unreal_project, UnrealProject};
+use crate::{info, vlog};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// One creative app's project format
+pub trait ProjectHandler: Send + Sync {
+    /// Name accepted by `auxin init --type`, e.g. "logicpro"
+    fn id(&self) -> &'static str;
+
+    /// Other names `--type` accepts, e.g. "logic"
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// The app's name as users know it, e.g. "Logic Pro"
+    fn display_name(&self) -> &'static str;
+
+    /// Extensions of the app's projects, without the dot
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Whether `path` looks like one of the app's projects; by default, by
+    /// its extension
+    fn detect(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.extensions()
+                    .iter()
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+            })
+    }
+
+    /// Check that `path` really is a project before it's versioned
+    fn validate(&self, path: &Path) -> Result<()>;
+
+    /// Where the repository for the project at `path` is created; by
+    /// default `path` if it's a folder, otherwise the folder it's in
+    fn repository_root(&self, path: &Path) -> PathBuf {
+        if path.is_dir() {
+            return path.to_path_buf();
+        }
+        path.parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf()
+    }
+
+    /// Contents of `.oxenignore` for a new repository
+    fn generate_oxenignore(&self) -> String;
+
+    /// Details read from the project itself, as label and value, e.g.
+    /// ("Tempo", "120 BPM")
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>>;
+
+    /// Details recorded in every commit as `Label: value` lines, e.g. the
+    /// engine version a game was saved with; none by default
+    fn commit_metadata(&self, _path: &Path) -> Result<Vec<(&'static str, String)>> {
+        Ok(Vec::new())
+    }
+
+    /// Example milestone commit, shown after init and clone
+    fn commit_example(&self) -> &'static str {
+        "auxin commit -m \"Your message\""
+    }
+}
+
+/// `.logicx` folder projects
+pub struct LogicProHandler;
+
+impl ProjectHandler for LogicProHandler {
+    fn id(&self) -> &'static str {
+        "logicpro"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["logic"]
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Logic Pro"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["logicx"]
+    }
+
+    fn validate(&self, path: &Path) -> Result<()> {
+        LogicProject::detect(path).map(|_| ())
+    }
+
+    /// The workspace folder around the project, if there is one, so
+    /// external assets are versioned too
+    fn repository_root(&self, path: &Path) -> PathBuf {
+        match LogicProject::detect_workspace_folder(path) {
+            Some(workspace) => {
+                info!("Detected Logic Pro workspace folder");
+                info!("  Workspace: {}", workspace.display());
+                info!("  Logic project: {}", path.display());
+                info!(
+                    "Repository will be initialized at workspace level to capture external assets"
+                );
+                workspace
+            }
+            None => {
+                vlog!("No workspace detected, using .logicx as repository root");
+                path.to_path_buf()
+            }
+        }
+    }
+
+    fn generate_oxenignore(&self) -> String {
+        generate_oxenignore()
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>> {
+        let data = LogicParser::parse(path)?;
+        Ok(vec![
+            ("Tempo", format!("{} BPM", data.tempo)),
+            ("Key", data.key_signature),
+            (
+                "Time signature",
+                format!("{}/{}", data.time_signature.0, data.time_signature.1),
+            ),
+            ("Sample rate", format!("{} Hz", data.sample_rate)),
+            ("Tracks", data.tracks.len().to_string()),
+        ])
+    }
+
+    fn commit_example(&self) -> &'static str {
+        "auxin commit -m \"Your message\" --bpm 120"
+    }
+}
+
+/// `.skp` model files
+pub struct SketchUpHandler;
+
+impl ProjectHandler for SketchUpHandler {
+    fn id(&self) -> &'static str {
+        "sketchup"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["skp"]
+    }
+
+    fn display_name(&self) -> &'static str {
+        "SketchUp"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["skp"]
+    }
+
+    fn validate(&self, path: &Path) -> Result<()> {
+        SketchUpProject::detect(path).map(|_| ())
+    }
+
+    fn generate_oxenignore(&self) -> String {
+        generate_sketchup_oxenignore()
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>> {
+        file_size(path)
+    }
+
+    fn commit_example(&self) -> &'static str {
+        "auxin commit -m \"Your message\" --units Inches --layers 10"
+    }
+}
+
+/// `.blend` files
+pub struct BlenderHandler;
+
+impl ProjectHandler for BlenderHandler {
+    fn id(&self) -> &'static str {
+        "blender"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["blend"]
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Blender"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["blend"]
+    }
+
+    fn validate(&self, path: &Path) -> Result<()> {
+        BlenderProject::detect(path).map(|_| ())
+    }
+
+    fn generate_oxenignore(&self) -> String {
+        generate_blender_oxenignore()
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>> {
+        let info = BlenderParser::parse(path)?;
+        let mut details = vec![
+            ("Blender version", info.version),
+            ("Scenes", info.scene_count.to_string()),
+            ("Objects", info.object_count.to_string()),
+        ];
+        details.extend(file_size(path)?);
+        Ok(details)
+    }
+
+    fn commit_example(&self) -> &'static str {
+        "auxin commit -m \"Your message\" --auto-metadata"
+    }
+}
+
+/// `.prproj` files
+pub struct PremiereHandler;
+
+impl ProjectHandler for PremiereHandler {
+    fn id(&self) -> &'static str {
+        "premiere"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["premierepro", "prproj"]
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Premiere Pro"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["prproj"]
+    }
+
+    fn validate(&self, path: &Path) -> Result<()> {
+        PremiereProject::detect(path).map(|_| ())
+    }
+
+    fn generate_oxenignore(&self) -> String {
+        generate_premiere_oxenignore()
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>> {
+        let info = PremiereProject::detect(path)?.read_info()?;
+        let mut details = vec![("Sequences", info.sequence_count.to_string())];
+        if let Some((width, height)) = info.resolution {
+            details.push(("Resolution", format!("{}x{}", width, height)));
+        }
+        if let Some(fps) = info.frame_rate {
+            details.push(("Frame rate", format_frame_rate(fps)));
+        }
+        Ok(details)
+    }
+}
+
+/// `.aep` files
+pub struct AfterEffectsHandler;
+
+impl ProjectHandler for AfterEffectsHandler {
+    fn id(&self) -> &'static str {
+        "aftereffects"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ae", "aep"]
+    }
+
+    fn display_name(&self) -> &'static str {
+        "After Effects"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["aep"]
+    }
+
+    fn validate(&self, path: &Path) -> Result<()> {
+        AfterEffectsProject::detect(path).map(|_| ())
+    }
+
+    fn generate_oxenignore(&self) -> String {
+        generate_after_effects_oxenignore()
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>> {
+        let compositions = AfterEffectsProject::detect(path)?.composition_count()?;
+        let mut details = vec![("Compositions", compositions.to_string())];
+        details.extend(file_size(path)?);
+        Ok(details)
+    }
+}
+
+/// `.uproject` folder projects
+pub struct UnrealHandler;
+
+impl ProjectHandler for UnrealHandler {
+    fn id(&self) -> &'static str {
+        "unreal"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ue", "unrealengine", "uproject"]
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Unreal Engine"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["uproject"]
+    }
+
+    /// A `.uproject` file, or a folder with one at its root
+    fn detect(&self, path: &Path) -> bool {
+        is_unreal_project(path)
+    }
+
+    fn validate(&self, path: &Path) -> Result<()> {
+        UnrealProject::detect(path)?.read_info().map(|_| ())
+    }
+
+    /// The project folder, where `Content/` and `Config/` are
+    fn repository_root(&self, path: &Path) -> PathBuf {
+        UnrealProject::detect(path)
+            .map(|project| project.project_dir)
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    fn generate_oxenignore(&self) -> String {
+        generate_unreal_oxenignore()
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>> {
+        let info = UnrealProject::detect(path)?.read_info()?;
+        let mut details = self.commit_metadata(path)?;
+        details.push(("Modules", info.modules.len().to_string()));
+        details.push(("Plugins", info.enabled_plugins.len().to_string()));
+        Ok(details)
+    }
+
+    fn commit_metadata(&self, path: &Path) -> Result<Vec<(&'static str, String)>> {
+        let info = UnrealProject::detect(path)?.read_info()?;
+        Ok(info
+            .engine_version
+            .map(|version| vec![("Engine", version)])
+            .unwrap_or_default())
+    }
+}
+
+/// Add `details` to the metadata block at the end of `message`, starting
+/// one if there isn't one
+pub fn append_commit_metadata(message: &str, details: &[(&str, String)]) -> String {
+    if details.is_empty() {
+        return message.to_string();
+    }
+    // The block is the last paragraph, if every line in it is `Label: value`
+    let has_block = message
+        .trim_end()
+        .rsplit_once("\n\n")
+        .is_some_and(|(_, block)| block.lines().all(|line| line.contains(": ")));
+    let mut message = message.trim_end().to_string();
+    message.push_str(if has_block { "\n" } else { "\n\n" });
+    let lines: Vec<String> = details
+        .iter()
+        .map(|(label, value)| format!("{}: {}", label, value))
+        .collect();
+    message.push_str(&lines.join("\n"));
+    message
+}
+
+/// The file in `dir` with `extension` that was saved last, for commands
+/// that act on "the" project in the current folder
+pub fn newest_file_with_extension(dir: &Path, extension: &str) -> Result<Option<PathBuf>> {
+    let mut files: Vec<(PathBuf, std::time::SystemTime)> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((entry.path(), modified))
+        })
+        .collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if files.len() > 1 {
+        vlog!(
+            "{} .{} files found, using the most recently saved",
+            files.len(),
+            extension
+        );
+    }
+    Ok(files.into_iter().next().map(|(path, _)| path))
+}
+
+/// "29.97 fps", "24 fps"
+fn format_frame_rate(fps: f64) -> String {
+    let rounded = format!("{:.3}", fps);
+    format!(
+        "{} fps",
+        rounded.trim_end_matches('0').trim_end_matches('.')
+    )
+}
+
+/// The size of a single-file project
+fn file_size(path: &Path) -> Result<Vec<(&'static str, String)>> {
+    let size = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .len();
+    Ok(vec![(
+        "File size",
+        format!("{:.2} MB", size as f64 / (1024.0 * 1024.0)),
+    )])
+}
+
+/// The project types auxin supports
+pub struct ProjectRegistry {
+    handlers: Vec<Box<dyn ProjectHandler>>,
+}
+
+impl ProjectRegistry {
+    /// Logic Pro, SketchUp, Blender, Premiere Pro, After Effects and Unreal
+    /// Engine
+    pub fn builtin() -> Self {
+        Self {
+            handlers: vec![
+                Box::new(LogicProHandler),
+                Box::new(SketchUpHandler),
+                Box::new(BlenderHandler),
+                Box::new(PremiereHandler),
+                Box::new(AfterEffectsHandler),
+                Box::new(UnrealHandler),
+            ],
+        }
+    }
+
+    /// Add a handler; it's consulted after those already registered
+    pub fn register(&mut self, handler: Box<dyn ProjectHandler>) {
+        self.handlers.push(handler);
+    }
+
+    /// The handler called `name` (its id or an alias, any case)
+    pub fn get(&self, name: &str) -> Option<&dyn ProjectHandler> {
+        let name = name.to_lowercase();
+        self.handlers
+            .iter()
+            .find(|handler| handler.id() == name || handler.aliases().contains(&name.as_str()))
+            .map(|handler| handler.as_ref())
+    }
+
+    /// The first handler that recognises `path`
+    pub fn detect(&self, path: &Path) -> Option<&dyn ProjectHandler> {
+        self.handlers
+            .iter()
+            .find(|handler| handler.detect(path))
+            .map(|handler| handler.as_ref())
+    }
+
+    /// Ids of every handler, for help and error messages
+    pub fn ids(&self) -> Vec<&'static str> {
+        self.handlers.iter().map(|handler| handler.id()).collect()
+    }
+}
+
+impl Default for ProjectRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_lookup() {
+        let registry = ProjectRegistry::builtin();
+        assert_eq!(registry.get("logic").unwrap().id(), "logicpro");
+        assert_eq!(registry.get("SketchUp").unwrap().display_name(), "SketchUp");
+        assert!(registry.get("ableton").is_none());
+        assert_eq!(
+            registry.ids(),
+            vec![
+                "logicpro",
+                "sketchup",
+                "blender",
+                "premiere",
+                "aftereffects",
+                "unreal"
+            ]
+        );
+        assert_eq!(registry.get("ae").unwrap().display_name(), "After Effects");
+
+        assert_eq!(
+            registry.detect(Path::new("Song.logicx")).unwrap().id(),
+            "logicpro"
+        );
+        assert_eq!(
+            registry.detect(Path::new("House.SKP")).unwrap().id(),
+            "sketchup"
+        );
+        assert!(registry.detect(Path::new("notes.txt")).is_none());
+    }
+
+    #[test]
+    fn test_unreal_commit_metadata() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("Shooter.uproject"),
+            r#"{"FileVersion": 3, "EngineAssociation": "5.3"}"#,
+        )
+        .unwrap();
+
+        let registry = ProjectRegistry::builtin();
+        let handler = registry.detect(dir.path()).unwrap();
+        assert_eq!(handler.id(), "unreal");
+        let details = handler.commit_metadata(dir.path()).unwrap();
+        assert_eq!(details, vec![("Engine", "5.3".to_string())]);
+
+        assert_eq!(
+            append_commit_metadata("Add level", &details),
+            "Add level\n\nEngine: 5.3"
+        );
+        assert_eq!(
+            append_commit_metadata("Add level\n\nTags: milestone", &details),
+            "Add level\n\nTags: milestone\nEngine: 5.3"
+        );
+        assert_eq!(append_commit_metadata("Add level", &[]), "Add level");
+    }
+
+    #[test]
+    fn test_format_frame_rate() {
+        assert_eq!(format_frame_rate(24.0), "24 fps");
+        assert_eq!(format_frame_rate(30000.0 / 1001.0), "29.97 fps");
+        assert_eq!(format_frame_rate(24000.0 / 1001.0), "23.976 fps");
+    }
+
+    #[test]
+    fn test_register_new_app() {
+        struct AbletonHandler;
+        impl ProjectHandler for AbletonHandler {
+            fn id(&self) -> &'static str {
+                "ableton"
+            }
+            fn display_name(&self) -> &'static str {
+                "Ableton Live"
+            }
+            fn extensions(&self) -> &'static [&'static str] {
+                &["als"]
+            }
+            fn validate(&self, _path: &Path) -> Result<()> {
+                Ok(())
+            }
+            fn generate_oxenignore(&self) -> String {
+                "Backup/\n".to_string()
+            }
+            fn extract_metadata(&self, _path: &Path) -> Result<Vec<(&'static str, String)>> {
+                Ok(vec![])
+            }
+        }
+
+        let mut registry = ProjectRegistry::builtin();
+        registry.register(Box::new(AbletonHandler));
+        let handler = registry.detect(Path::new("Set.als")).unwrap();
+        assert_eq!(handler.display_name(), "Ableton Live");
+        assert_eq!(handler.commit_example(), "auxin commit -m \"Your message\"");
+        assert_eq!(
+            handler.repository_root(Path::new("/projects/Set.als")),
+            PathBuf::from("/projects")
+        );
+    }
+}
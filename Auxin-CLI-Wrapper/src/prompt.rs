@@ -0,0 +1,209 @@
+//! Shell prompt segment: `auxin prompt`
+//!
+//! Prints a short status such as `main* 🔒 ⇡2` (branch, uncommitted changes,
+//! lock, operations waiting in the offline queue) for zsh, fish or starship
+//! prompts. Prompts run on every command, so nothing here spawns `oxen` or
+//! talks to a server:
+//!
+//! - the branch is read from `.oxen/HEAD`
+//! - the dirty marker and lock come from `.auxin/badges.json`, the status
+//!   snapshot kept for Finder badges (see [`crate::badge_state`])
+//! - the queue count comes from the pending entries in `~/.auxin/queue`
+//!
+//! When the snapshot is missing or older than [`REFRESH_AFTER_SECS`], the
+//! prompt starts `auxin badges` in the background and shows the cached
+//! values; the next prompt picks up the fresh ones.
+
+use crate::badge_state::{BadgeSnapshot, FileState, LockBadge};
+use crate::offline_queue::OfflineQueue;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Age after which the cached snapshot is refreshed in the background
+pub const REFRESH_AFTER_SECS: i64 = 30;
+
+/// Marker recording when a background refresh was last started
+const REFRESH_MARKER: &str = "prompt_refresh";
+
+/// Everything shown in the prompt segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptStatus {
+    pub branch: Option<String>,
+    pub dirty: bool,
+    pub lock: LockBadge,
+    pub queued: usize,
+    /// Whether the cached snapshot is missing or out of date
+    pub stale: bool,
+}
+
+impl PromptStatus {
+    /// Read the status of `project` from files only
+    pub fn read(project: &Path) -> Self {
+        let snapshot = BadgeSnapshot::load(project).ok().flatten();
+        let stale = snapshot.as_ref().is_none_or(|s| {
+            (Utc::now() - s.updated_at).num_seconds() > REFRESH_AFTER_SECS
+        });
+
+        Self {
+            branch: read_branch(project),
+            dirty: snapshot
+                .as_ref()
+                .is_some_and(|s| s.state != FileState::Clean),
+            lock: snapshot.map_or(LockBadge::Unlocked, |s| s.lock.state),
+            queued: OfflineQueue::new().map_or(0, |q| q.pending().len()),
+            stale,
+        }
+    }
+
+    /// The prompt segment; `ascii` avoids symbols some terminals can't draw
+    pub fn render(&self, ascii: bool) -> String {
+        let mut segment = self.branch.clone().unwrap_or_else(|| "?".to_string());
+        if self.dirty {
+            segment.push('*');
+        }
+        let lock = match (self.lock, ascii) {
+            (LockBadge::Unlocked, _) => None,
+            (LockBadge::Mine, false) => Some("🔒"),
+            (LockBadge::Other, false) => Some("⛔"),
+            (LockBadge::Mine, true) => Some("[locked]"),
+            (LockBadge::Other, true) => Some("[locked by other]"),
+        };
+        if let Some(lock) = lock {
+            segment.push(' ');
+            segment.push_str(lock);
+        }
+        if self.queued > 0 {
+            let arrow = if ascii { "^" } else { "⇡" };
+            segment.push_str(&format!(" {}{}", arrow, self.queued));
+        }
+        segment
+    }
+}
+
+/// The repository containing `dir`, if any
+pub fn find_repo_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|d| d.join(".oxen").is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Start `auxin badges` in the background, at most once per refresh interval
+pub fn refresh_in_background(auxin_exe: &Path, project: &Path) -> Result<()> {
+    let marker = project.join(".auxin").join(REFRESH_MARKER);
+    let recently_started = fs::metadata(&marker)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age.as_secs() < REFRESH_AFTER_SECS as u64);
+    if recently_started {
+        return Ok(());
+    }
+
+    fs::create_dir_all(project.join(".auxin"))?;
+    fs::write(&marker, "")?;
+    Command::new(auxin_exe)
+        .arg("badges")
+        .arg(project)
+        .current_dir(project)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start auxin badges")?;
+    Ok(())
+}
+
+/// A starship `custom` module running `auxin prompt`
+pub fn starship_module() -> String {
+    r#"# Add to ~/.config/starship.toml
+[custom.auxin]
+description = "Auxin branch, changes, lock and queued operations"
+command = "auxin prompt"
+detect_folders = [".oxen"]
+format = "[auxin $output]($style) "
+style = "bold purple"
+"#
+    .to_string()
+}
+
+/// Branch name from `.oxen/HEAD`; a detached HEAD shows as a short commit id
+fn read_branch(project: &Path) -> Option<String> {
+    let head = fs::read_to_string(project.join(".oxen").join("HEAD")).ok()?;
+    let head = head.trim();
+    let head = head.strip_prefix("ref: refs/heads/").unwrap_or(head);
+    if head.is_empty() {
+        return None;
+    }
+    let detached = head.len() >= 32 && head.chars().all(|c| c.is_ascii_hexdigit());
+    Some(if detached {
+        format!("@{}", &head[..8])
+    } else {
+        head.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::badge_state::LockSnapshot;
+    use auxin_oxen::StatusInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render() {
+        let mut status = PromptStatus {
+            branch: Some("main".to_string()),
+            dirty: false,
+            lock: LockBadge::Unlocked,
+            queued: 0,
+            stale: false,
+        };
+        assert_eq!(status.render(false), "main");
+
+        status.dirty = true;
+        status.lock = LockBadge::Mine;
+        status.queued = 2;
+        assert_eq!(status.render(false), "main* 🔒 ⇡2");
+        assert_eq!(status.render(true), "main* [locked] ^2");
+    }
+
+    #[test]
+    fn test_read_from_cache() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        fs::create_dir_all(project.join(".oxen")).unwrap();
+        fs::write(project.join(".oxen").join("HEAD"), "draft\n").unwrap();
+        assert_eq!(find_repo_root(&project.join("Media")).unwrap(), project);
+
+        let status = PromptStatus::read(project);
+        assert_eq!(status.branch.as_deref(), Some("draft"));
+        assert!(!status.dirty);
+        assert!(status.stale);
+
+        let changes = StatusInfo {
+            modified: vec![PathBuf::from("ProjectData")],
+            untracked: vec![],
+            staged: vec![],
+        };
+        let lock = LockSnapshot {
+            state: LockBadge::Other,
+            holder: Some("bob@studio".to_string()),
+        };
+        BadgeSnapshot::new(project, &changes, lock).write().unwrap();
+
+        let status = PromptStatus::read(project);
+        assert!(status.dirty);
+        assert_eq!(status.lock, LockBadge::Other);
+        assert!(!status.stale);
+
+        fs::write(
+            project.join(".oxen").join("HEAD"),
+            "0123456789abcdef0123456789abcdef",
+        )
+        .unwrap();
+        assert_eq!(read_branch(project).as_deref(), Some("@01234567"));
+    }
+}
@@ -0,0 +1,384 @@
+//! Quarantine for files arriving with `auxin pull`
+//!
+//! A repo shared with outside collaborators can deliver more than audio: a
+//! "helper" script, a plugin bundle, an app. With `[quarantine] enabled =
+//! true`, every executable or script that a pull adds or changes is held
+//! back until someone looks at it:
+//!
+//! - its execute permission is removed, so it can't be run by accident
+//! - on macOS it gets the `com.apple.quarantine` attribute, so Gatekeeper
+//!   checks it if it's opened anyway
+//! - it's listed in `.auxin/quarantine.json` for `auxin quarantine review`
+//!
+//! Approving a file restores its permissions; rejecting it deletes it.
+//! Extensions in `[quarantine] auto_approve_extensions` are let through.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Scripts, installers and executables that shouldn't run unreviewed
+const RISKY_EXTENSIONS: &[&str] = &[
+    "sh",
+    "bash",
+    "zsh",
+    "command",
+    "tool",
+    "py",
+    "rb",
+    "pl",
+    "php",
+    "js",
+    "applescript",
+    "scpt",
+    "workflow",
+    "pkg",
+    "mpkg",
+    "dmg",
+    "dylib",
+    "so",
+    "exe",
+    "dll",
+    "bat",
+    "cmd",
+    "ps1",
+    "vbs",
+    "jar",
+];
+
+/// Bundles whose contents are code: apps and audio plugins
+const RISKY_BUNDLES: &[&str] = &[
+    "app",
+    "bundle",
+    "plugin",
+    "component",
+    "vst",
+    "vst3",
+    "aaxplugin",
+    "kext",
+];
+
+/// Why a file was held back, if it should be
+pub fn risk(path: &str, executable: bool) -> Option<String> {
+    let extension = |name: &str| {
+        Path::new(name)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+    };
+
+    let mut components: Vec<&str> = path.split('/').collect();
+    let file_name = components.pop().unwrap_or(path);
+    if let Some(bundle) = components.iter().find(|dir| {
+        extension(dir).is_some_and(|ext| RISKY_BUNDLES.contains(&ext.as_str()))
+    }) {
+        return Some(format!("inside {}", bundle));
+    }
+    if let Some(ext) = extension(file_name) {
+        if RISKY_EXTENSIONS.contains(&ext.as_str()) {
+            return Some(format!(".{} file", ext));
+        }
+    }
+    executable.then(|| "executable".to_string())
+}
+
+/// Size and modification time of every file, to spot what a pull changed
+pub type FileStates = BTreeMap<String, (u64, Option<SystemTime>)>;
+
+/// Record the project's files, skipping `.oxen` and `.auxin`
+pub fn scan(project: &Path) -> Result<FileStates> {
+    fn walk(root: &Path, dir: &Path, files: &mut FileStates) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if dir == root && (name == ".oxen" || name == ".auxin") {
+                    continue;
+                }
+                walk(root, &path, files)?;
+            } else if file_type.is_file() {
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                files.insert(
+                    relative.to_string_lossy().replace('\\', "/"),
+                    (metadata.len(), metadata.modified().ok()),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = FileStates::new();
+    walk(project, project, &mut files)?;
+    Ok(files)
+}
+
+/// Files added or changed between two scans
+pub fn arrived(before: &FileStates, after: &FileStates) -> Vec<String> {
+    after
+        .iter()
+        .filter(|(path, state)| before.get(*path) != Some(state))
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+/// A file held back for review
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarantinedFile {
+    /// Path relative to the project
+    pub path: String,
+    pub reason: String,
+    /// Unix permissions before quarantine, restored on approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<u32>,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+/// What happened to a file under review
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewDecision {
+    Approve,
+    Reject,
+    Skip,
+}
+
+/// A project's quarantined files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Quarantine {
+    #[serde(default)]
+    pub files: Vec<QuarantinedFile>,
+}
+
+impl Quarantine {
+    /// Location of a project's quarantine list
+    pub fn path(project: &Path) -> PathBuf {
+        project.join(".auxin").join("quarantine.json")
+    }
+
+    /// Read a project's quarantine; a missing file means nothing is held
+    pub fn load(project: &Path) -> Result<Self> {
+        let path = Self::path(project);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace the project's quarantine atomically
+    pub fn save(&self, project: &Path) -> Result<()> {
+        let path = Self::path(project);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    pub fn get(&self, path: &str) -> Option<&QuarantinedFile> {
+        self.files.iter().find(|f| f.path == path)
+    }
+
+    /// Hold back the risky files among `paths`, except auto-approved
+    /// extensions; returns the files newly quarantined
+    pub fn hold(
+        &mut self,
+        project: &Path,
+        paths: &[String],
+        auto_approve: &[String],
+    ) -> Result<Vec<QuarantinedFile>> {
+        let mut held = Vec::new();
+        for path in paths {
+            let full = project.join(path);
+            let Ok(metadata) = fs::metadata(&full) else {
+                continue;
+            };
+            let mode = file_mode(&metadata);
+            let executable = mode.is_some_and(|mode| mode & 0o111 != 0);
+            let Some(reason) = risk(path, executable) else {
+                continue;
+            };
+            let approved = Path::new(path).extension().is_some_and(|ext| {
+                let ext = ext.to_string_lossy();
+                auto_approve
+                    .iter()
+                    .any(|a| a.trim_start_matches('.').eq_ignore_ascii_case(&ext))
+            });
+            if approved {
+                continue;
+            }
+
+            // A file quarantined again keeps the permissions it first had
+            let mode = self.get(path).and_then(|f| f.mode).or(mode);
+            lock_down(&full)?;
+            let file = QuarantinedFile {
+                path: path.clone(),
+                reason,
+                mode,
+                quarantined_at: Utc::now(),
+            };
+            self.files.retain(|f| f.path != *path);
+            self.files.push(file.clone());
+            held.push(file);
+        }
+        Ok(held)
+    }
+
+    /// Apply a review decision to a quarantined file
+    pub fn decide(&mut self, project: &Path, path: &str, decision: ReviewDecision) -> Result<()> {
+        let Some(file) = self.get(path).cloned() else {
+            anyhow::bail!("{} is not quarantined", path);
+        };
+        let full = project.join(&file.path);
+        match decision {
+            ReviewDecision::Approve => {
+                if full.exists() {
+                    release(&full, file.mode)?;
+                }
+            },
+            ReviewDecision::Reject => {
+                if full.exists() {
+                    fs::remove_file(&full)
+                        .with_context(|| format!("Failed to delete {}", full.display()))?;
+                }
+            },
+            ReviewDecision::Skip => return Ok(()),
+        }
+        self.files.retain(|f| f.path != file.path);
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// Remove execute permission and mark the file for Gatekeeper
+fn lock_down(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(path)?.permissions().mode();
+        fs::set_permissions(path, fs::Permissions::from_mode(mode & !0o111))
+            .with_context(|| format!("Failed to quarantine {}", path.display()))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let value = format!("0081;{:x};auxin;", Utc::now().timestamp());
+        let _ = std::process::Command::new("xattr")
+            .args(["-w", "com.apple.quarantine", &value])
+            .arg(path)
+            .output();
+    }
+
+    Ok(())
+}
+
+/// Undo `lock_down`, restoring the original permissions
+fn release(path: &Path, mode: Option<u32>) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .with_context(|| format!("Failed to restore permissions of {}", path.display()))?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = std::process::Command::new("xattr")
+            .args(["-d", "com.apple.quarantine"])
+            .arg(path)
+            .output();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_risk() {
+        assert_eq!(risk("tools/render.sh", false), Some(".sh file".to_string()));
+        assert_eq!(
+            risk("Plug-Ins/Crusher.component/Contents/MacOS/Crusher", false),
+            Some("inside Crusher.component".to_string())
+        );
+        assert_eq!(risk("bin/helper", true), Some("executable".to_string()));
+        assert_eq!(risk("Media/Audio Files/Kick.wav", false), None);
+        assert_eq!(risk("Alternatives/000/ProjectData", false), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hold_and_review() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = temp.path();
+        let before = scan(project).unwrap();
+        fs::write(project.join("Kick.wav"), b"RIFF").unwrap();
+        fs::write(project.join("bounce.sh"), b"#!/bin/sh\n").unwrap();
+        fs::set_permissions(project.join("bounce.sh"), fs::Permissions::from_mode(0o755))
+            .unwrap();
+        fs::write(project.join("notes.py"), b"print()\n").unwrap();
+        fs::write(project.join("payload.command"), b"curl evil\n").unwrap();
+
+        let paths = arrived(&before, &scan(project).unwrap());
+        assert_eq!(paths.len(), 4);
+
+        let mut quarantine = Quarantine::default();
+        let held = quarantine
+            .hold(project, &paths, &[".PY".to_string()])
+            .unwrap();
+        let held: Vec<_> = held.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(held, ["bounce.sh", "payload.command"]);
+
+        let mode = |name: &str| {
+            fs::metadata(project.join(name))
+                .unwrap()
+                .permissions()
+                .mode()
+                & 0o777
+        };
+        assert_eq!(mode("bounce.sh"), 0o644);
+
+        quarantine
+            .decide(project, "bounce.sh", ReviewDecision::Approve)
+            .unwrap();
+        assert_eq!(mode("bounce.sh"), 0o755);
+        quarantine
+            .decide(project, "payload.command", ReviewDecision::Reject)
+            .unwrap();
+        assert!(!project.join("payload.command").exists());
+        assert!(quarantine.files.is_empty());
+        assert!(quarantine
+            .decide(project, "bounce.sh", ReviewDecision::Approve)
+            .is_err());
+    }
+}
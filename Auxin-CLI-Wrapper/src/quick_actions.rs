@@ -0,0 +1,350 @@
+//! Single-shot actions for hardware buttons
+//!
+//! Stream Deck keys, keyboard macros and OSC controllers want one command per
+//! button that needs no input, prints nothing on success and exits quickly.
+//! The `auxin quick-*` commands and the OSC listener are built from the
+//! helpers here. They reuse the regular commands (run as a subprocess of
+//! the same `auxin` binary) so a button press does exactly what typing the
+//! command would.
+
+use crate::identity::Identity;
+use crate::{
+    server_client, AudioFormat, BounceManager, CommitMetadata, LogicParser, RemoteLockManager,
+};
+use anyhow::{bail, Context, Result};
+use auxin_config::Config;
+use auxin_oxen::OxenSubprocess;
+use chrono::{DateTime, Local, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Who holds a project's lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockState {
+    Unlocked,
+    /// Held by the current user
+    Mine,
+    /// Held by someone else
+    Other(String),
+}
+
+/// Outcome of [`quick_commit`]
+#[derive(Debug, Clone)]
+pub enum QuickCommit {
+    Committed {
+        commit_id: String,
+        message: String,
+        /// File name of the bounce attached to the commit
+        bounce: Option<String>,
+    },
+    NothingToCommit,
+}
+
+impl QuickCommit {
+    /// One-line description for a notification
+    pub fn summary(&self) -> String {
+        match self {
+            QuickCommit::Committed {
+                commit_id,
+                message,
+                bounce,
+            } => {
+                let short = &commit_id[..commit_id.len().min(8)];
+                match bounce {
+                    Some(bounce) => format!("{} {} + {}", short, message, bounce),
+                    None => format!("{} {}", short, message),
+                }
+            }
+            QuickCommit::NothingToCommit => "Nothing to commit".to_string(),
+        }
+    }
+}
+
+/// Stage everything and commit with an automatic message, the project's
+/// current metadata and the newest bounce
+pub fn quick_commit(project: &Path, auxin_exe: &Path) -> Result<QuickCommit> {
+    crate::operation_lock::OperationLock::ensure_free(project)?;
+    let oxen = OxenSubprocess::new();
+
+    let status = oxen.status(project)?;
+    if status.modified.is_empty() && status.untracked.is_empty() && status.staged.is_empty() {
+        return Ok(QuickCommit::NothingToCommit);
+    }
+    crate::stage_policy::stage_all(project, &Config::load().unwrap_or_default())?;
+
+    let message = format!("Quick save at {}", Local::now().format("%Y-%m-%d %H:%M"));
+    let mut args = vec!["commit".to_string(), "-m".to_string(), message.clone()];
+    args.extend(current_metadata_args(project));
+
+    let bounce = newest_unattached_bounce(project);
+    if let Some(bounce) = &bounce {
+        args.extend(["--bounce".to_string(), bounce.display().to_string()]);
+    }
+
+    run_auxin(auxin_exe, project, &args)?;
+
+    let commit_id = oxen
+        .log(project, Some(1))?
+        .into_iter()
+        .next()
+        .map(|commit| commit.id)
+        .unwrap_or_default();
+
+    Ok(QuickCommit::Committed {
+        commit_id,
+        message,
+        bounce: bounce
+            .and_then(|b| b.file_name().map(|n| n.to_string_lossy().to_string())),
+    })
+}
+
+/// Release the lock if the current user holds it, otherwise acquire it
+///
+/// Returns the new state.
+pub fn toggle_lock(project: &Path, auxin_exe: &Path) -> Result<LockState> {
+    match lock_state(project) {
+        LockState::Mine => {
+            run_auxin(auxin_exe, project, &["lock", "release"])?;
+            Ok(LockState::Unlocked)
+        }
+        LockState::Unlocked => {
+            run_auxin(auxin_exe, project, &["lock", "acquire"])?;
+            Ok(LockState::Mine)
+        }
+        LockState::Other(holder) => bail!("Locked by {}", holder),
+    }
+}
+
+/// Who holds the project's lock
+///
+/// Asks the server when server locks are enabled, falling back to the local
+/// lock file like `auxin lock acquire` does.
+pub fn lock_state(project: &Path) -> LockState {
+    let config = Config::load().unwrap_or_default();
+
+    if config.cli.use_server_locks {
+        let server_config = server_client::ServerConfig::from_config(&config);
+        let status = server_client::AuxinServerClient::new(server_config).and_then(|client| {
+            client.get_lock_status(&config.cli.default_namespace, &repo_name(project))
+        });
+        if let Ok(status) = status {
+            return match status.lock {
+                None => LockState::Unlocked,
+                Some(lock) if Identity::current().is_me(&lock.user) => LockState::Mine,
+                Some(lock) => LockState::Other(lock.user),
+            };
+        }
+    }
+
+    match RemoteLockManager::new().get_lock(project) {
+        Ok(Some(lock)) if Identity::current().is_me(&lock.locked_by) => LockState::Mine,
+        Ok(Some(lock)) => LockState::Other(lock.locked_by),
+        _ => LockState::Unlocked,
+    }
+}
+
+/// One-line project status for a button or notification
+pub fn status_line(project: &Path) -> Result<String> {
+    let oxen = OxenSubprocess::new();
+
+    let branch = oxen.current_branch(project)?;
+    let status = oxen.status(project)?;
+    let changes = status.modified.len() + status.untracked.len() + status.staged.len();
+
+    let mut parts = vec![repo_name(project), branch];
+    parts.push(match changes {
+        0 => "clean".to_string(),
+        1 => "1 change".to_string(),
+        n => format!("{} changes", n),
+    });
+    parts.push(match lock_state(project) {
+        LockState::Unlocked => "unlocked".to_string(),
+        LockState::Mine => "locked by you".to_string(),
+        LockState::Other(holder) => format!("locked by {}", holder),
+    });
+    if let Some(commit) = oxen.log(project, Some(1))?.into_iter().next() {
+        let subject = commit.message.lines().next().unwrap_or_default().to_string();
+        parts.push(format!("last: {}", subject));
+    }
+
+    Ok(parts.join(" · "))
+}
+
+/// `auxin commit` flags carrying the project's current BPM, sample rate and key
+///
+/// Logic Pro projects are read directly; otherwise the values from the
+/// latest commit that recorded them are carried forward.
+pub fn current_metadata_args(project: &Path) -> Vec<String> {
+    let (bpm, sample_rate, key) = if LogicParser::is_valid_project(project) {
+        match LogicParser::parse(project) {
+            Ok(data) => (
+                Some(data.tempo),
+                Some(data.sample_rate),
+                Some(data.key_signature).filter(|k| !k.is_empty()),
+            ),
+            Err(_) => (None, None, None),
+        }
+    } else {
+        OxenSubprocess::new()
+            .log(project, Some(50))
+            .unwrap_or_default()
+            .iter()
+            .map(|commit| CommitMetadata::parse_commit_message(&commit.message))
+            .find(|m| m.bpm.is_some() || m.sample_rate.is_some() || m.key_signature.is_some())
+            .map(|m| (m.bpm, m.sample_rate, m.key_signature))
+            .unwrap_or_default()
+    };
+
+    let mut args = Vec::new();
+    if let Some(bpm) = bpm {
+        args.extend(["--bpm".to_string(), bpm.to_string()]);
+    }
+    if let Some(sample_rate) = sample_rate {
+        args.extend(["--sample-rate".to_string(), sample_rate.to_string()]);
+    }
+    if let Some(key) = key {
+        args.extend(["--key".to_string(), key]);
+    }
+    args
+}
+
+/// Newest audio file in the project's Bounces folder that is newer than the
+/// last bounce attached to a commit
+///
+/// Looks in `Bounces/` inside the project and, for a `.logicx` package, next
+/// to it, where Logic Pro puts bounces by default.
+pub fn newest_unattached_bounce(project: &Path) -> Option<PathBuf> {
+    let mut folders = vec![project.join("Bounces")];
+    if project.extension().is_some_and(|ext| ext == "logicx") {
+        if let Some(parent) = project.parent() {
+            folders.push(parent.join("Bounces"));
+        }
+    }
+
+    let last_attached = BounceManager::new(project)
+        .list_bounces()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|b| b.added_at)
+        .max();
+
+    folders
+        .iter()
+        .filter_map(|folder| fs::read_dir(folder).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|ext| AudioFormat::from_extension(&ext.to_string_lossy()))
+                .is_some()
+        })
+        .filter_map(|entry| {
+            let modified: DateTime<Utc> = entry.metadata().ok()?.modified().ok()?.into();
+            Some((modified, entry.path()))
+        })
+        .filter(|(modified, _)| last_attached.is_none_or(|last| *modified > last))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Show a desktop notification; failures are ignored
+pub fn notify(title: &str, body: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let script = format!(
+            "display notification \"{}\" with title \"{}\"",
+            escape(body),
+            escape(title)
+        );
+        let _ = Command::new("osascript").args(["-e", &script]).output();
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = Command::new("notify-send").args([title, body]).output();
+    }
+}
+
+/// Run an `auxin` command in the project directory, capturing its output
+pub fn run_auxin<S: AsRef<str>>(auxin_exe: &Path, project: &Path, args: &[S]) -> Result<()> {
+    let args: Vec<&str> = args.iter().map(|a| a.as_ref()).collect();
+    let output = Command::new(auxin_exe)
+        .args(&args)
+        .current_dir(project)
+        .output()
+        .with_context(|| format!("Failed to run auxin {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let detail = if stderr.trim().is_empty() { stdout } else { stderr };
+        bail!("auxin {} failed: {}", args.first().unwrap_or(&""), detail.trim());
+    }
+    Ok(())
+}
+
+fn repo_name(project: &Path) -> String {
+    project
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn touch(path: &Path, age: Duration) {
+        fs::write(path, b"RIFF").unwrap();
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn test_newest_unattached_bounce() {
+        let temp = TempDir::new().unwrap();
+        let folder = temp.path().join("Song");
+        let project = folder.join("Song.logicx");
+        fs::create_dir_all(project.join("Bounces")).unwrap();
+        fs::create_dir_all(folder.join("Bounces")).unwrap();
+
+        assert_eq!(newest_unattached_bounce(&project), None);
+
+        touch(&project.join("Bounces/old.wav"), Duration::from_secs(3600));
+        touch(&folder.join("Bounces/mix v2.aif"), Duration::from_secs(60));
+        touch(&folder.join("Bounces/notes.txt"), Duration::ZERO);
+
+        assert_eq!(
+            newest_unattached_bounce(&project),
+            Some(folder.join("Bounces/mix v2.aif"))
+        );
+
+        // Once attached, older files are no longer offered
+        let manager = BounceManager::new(&project);
+        manager.init().unwrap();
+        manager
+            .add_bounce("abc123", &folder.join("Bounces/mix v2.aif"), None)
+            .unwrap();
+        assert_eq!(newest_unattached_bounce(&project), None);
+    }
+
+    #[test]
+    fn test_quick_commit_summary() {
+        let committed = QuickCommit::Committed {
+            commit_id: "0123456789abcdef".to_string(),
+            message: "Quick save at 2026-10-16 14:02".to_string(),
+            bounce: Some("mix.wav".to_string()),
+        };
+        assert_eq!(
+            committed.summary(),
+            "01234567 Quick save at 2026-10-16 14:02 + mix.wav"
+        );
+        assert_eq!(QuickCommit::NothingToCommit.summary(), "Nothing to commit");
+    }
+}
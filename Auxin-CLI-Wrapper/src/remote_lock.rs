@@ -198,9 +198,9 @@ impl RemoteLock {
 
     /// Check if lock belongs to current user/machine
     pub fn is_owned_by_current_user(&self) -> bool {
-        let current_user = get_user_identifier();
         let current_machine = get_machine_id();
-        self.locked_by == current_user && self.machine_id == current_machine
+        crate::identity::Identity::current().is_me(&self.locked_by)
+            && self.machine_id == current_machine
     }
 
     /// Renew lock (update heartbeat and expiration)
@@ -830,18 +830,9 @@ impl Default for RemoteLockManager {
 
 // ========== Helper Functions ==========
 
-/// Get current user identifier (username@hostname)
+/// Get current user identifier (name@machine, see `crate::identity`)
 fn get_user_identifier() -> String {
-    let username = std::env::var("USER")
-        .or_else(|_| std::env::var("USERNAME"))
-        .unwrap_or_else(|_| "unknown".to_string());
-
-    let hostname = hostname::get()
-        .ok()
-        .and_then(|h| h.into_string().ok())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    format!("{}@{}", username, hostname)
+    crate::identity::Identity::current().user()
 }
 
 /// Get machine identifier (unique per machine)
@@ -0,0 +1,343 @@
+//! Rename and move tracking
+//!
+//! Oxen sees a moved file as one file deleted and another added, so history
+//! stops at the move. `auxin mv` moves the file, stages both sides and
+//! records the move in `.auxin/renames.json`:
+//!
+//! ```json
+//! {
+//!   "renames": [
+//!     {
+//!       "from": "Alternatives/000",
+//!       "to": "Alternatives/001",
+//!       "base_commit": "8f3e2a1b...",
+//!       "moved_at": "2026-10-16T14:02:11Z"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `base_commit` is the commit the move was made on top of, so the old path
+//! applies to that commit and everything before it. `auxin describe` and the
+//! project parser use the log to follow files across moves.
+
+use crate::quick_actions::{self, LockState};
+use anyhow::{bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Repository data that `auxin mv` never touches
+const PRIVATE_DIRS: [&str; 2] = [".oxen", ".auxin"];
+
+/// One recorded move; paths are relative to the repo and use `/`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenameRecord {
+    pub from: String,
+    pub to: String,
+    /// HEAD when the move was made; `None` before the first commit
+    pub base_commit: Option<String>,
+    pub moved_at: DateTime<Utc>,
+}
+
+/// Contents of `.auxin/renames.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RenameLog {
+    pub renames: Vec<RenameRecord>,
+}
+
+impl RenameLog {
+    /// Location of the log for a repo
+    pub fn path(repo: &Path) -> PathBuf {
+        repo.join(".auxin").join("renames.json")
+    }
+
+    /// Read a repo's log; a missing file is an empty log
+    pub fn load(repo: &Path) -> Result<Self> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace the repo's log atomically
+    pub fn save(&self, repo: &Path) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Record a move made on top of `base_commit`
+    ///
+    /// Moving something again before committing updates the pending record
+    /// (A→B then B→C is stored as A→C), and moving it back drops the record.
+    pub fn record(&mut self, from: &str, to: &str, base_commit: Option<&str>) {
+        let pending = self
+            .renames
+            .iter()
+            .position(|r| r.to == from && r.base_commit.as_deref() == base_commit);
+        let from = match pending {
+            Some(index) => self.renames.remove(index).from,
+            None => from.to_string(),
+        };
+        if from == to {
+            return;
+        }
+        self.renames.push(RenameRecord {
+            from,
+            to: to.to_string(),
+            base_commit: base_commit.map(str::to_string),
+            moved_at: Utc::now(),
+        });
+    }
+
+    /// Where `path` was at `commit`, given where it was in the commit after it
+    ///
+    /// Walking history newest first, call this for each commit to follow a
+    /// file back across moves.
+    pub fn path_at(&self, path: &str, commit: &str) -> String {
+        self.renames
+            .iter()
+            .rev()
+            .filter(|r| r.base_commit.as_deref() == Some(commit))
+            .fold(path.to_string(), |path, r| {
+                replace_prefix(&path, &r.to, &r.from)
+            })
+    }
+
+    /// Where a path from before any recorded move lives now
+    pub fn current_path(&self, path: &str) -> String {
+        self.renames.iter().fold(path.to_string(), |path, r| {
+            replace_prefix(&path, &r.from, &r.to)
+        })
+    }
+}
+
+/// `path` with a leading `old` component prefix replaced by `new`
+fn replace_prefix(path: &str, old: &str, new: &str) -> String {
+    if path == old {
+        return new.to_string();
+    }
+    match path
+        .strip_prefix(old)
+        .and_then(|rest| rest.strip_prefix('/'))
+    {
+        Some(rest) => format!("{}/{}", new, rest),
+        None => path.to_string(),
+    }
+}
+
+/// Move `from` to `to` inside `repo`, stage both sides and record the move
+///
+/// Refuses to overwrite anything, to move paths outside the repo or into
+/// or out of `.oxen` and `.auxin`, and to move files while someone else
+/// holds the project lock.
+pub fn move_path(repo: &Path, from: &Path, to: &Path) -> Result<RenameRecord> {
+    let from_rel = repo_relative(repo, from)?;
+    let to_rel = repo_relative(repo, to)?;
+    let (source, target) = (repo.join(&from_rel), repo.join(&to_rel));
+
+    for (path, rel) in [(from, &from_rel), (to, &to_rel)] {
+        let top = rel.split('/').next().unwrap_or_default();
+        if PRIVATE_DIRS.contains(&top) {
+            bail!("Can't move into or out of {}: {}", top, path.display());
+        }
+    }
+
+    if !source.exists() {
+        bail!("{} does not exist", from.display());
+    }
+    if target.exists() {
+        bail!("{} already exists; move or remove it first", to.display());
+    }
+    if to_rel == from_rel || to_rel.starts_with(&format!("{}/", from_rel)) {
+        bail!("Cannot move {} into itself", from.display());
+    }
+    if let LockState::Other(holder) = quick_actions::lock_state(repo) {
+        bail!(
+            "Project is locked by {}; moving files now would conflict",
+            holder
+        );
+    }
+
+    let oxen = OxenSubprocess::new();
+    let base_commit = oxen
+        .log(repo, Some(1))
+        .ok()
+        .and_then(|log| log.into_iter().next());
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&source, &target)
+        .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+
+    // Staging the old path stages its removal. If that fails, put the file
+    // back so the working tree and the rename log stay in step.
+    if let Err(e) = oxen.add(repo, &[Path::new(&from_rel), Path::new(&to_rel)]) {
+        fs::rename(&target, &source).with_context(|| {
+            format!(
+                "Staging failed ({}) and {} could not be moved back",
+                e,
+                to.display()
+            )
+        })?;
+        return Err(e.context("Failed to stage the move; nothing was changed"));
+    }
+
+    let mut log = RenameLog::load(repo)?;
+    let base = base_commit.as_ref().map(|c| c.id.as_str());
+    log.record(&from_rel, &to_rel, base);
+    log.save(repo)?;
+
+    Ok(RenameRecord {
+        from: from_rel,
+        to: to_rel,
+        base_commit: base.map(str::to_string),
+        moved_at: Utc::now(),
+    })
+}
+
+/// `path` relative to `repo` with `/` separators; relative input is taken
+/// as relative to the current directory
+fn repo_relative(repo: &Path, path: &Path) -> Result<String> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    // Canonicalize the deepest existing ancestor so symlinked temp dirs and
+    // not-yet-created targets compare equal to the repo path
+    let existing = absolute
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(&absolute);
+    let rest = absolute.strip_prefix(existing).unwrap_or(Path::new(""));
+    let absolute = existing.canonicalize()?.join(rest);
+    let repo = repo.canonicalize()?;
+
+    let relative = absolute
+        .strip_prefix(&repo)
+        .map_err(|_| anyhow::anyhow!("{} is outside the repository", path.display()))?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if parts.is_empty() || parts.iter().any(|p| p == "..") {
+        bail!("{} is not a path inside the repository", path.display());
+    }
+    Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_collapses_pending_moves() {
+        let mut log = RenameLog::default();
+        log.record("Alternatives/000", "Alternatives/001", Some("c1"));
+        log.record("Alternatives/001", "Alternatives/002", Some("c1"));
+        assert_eq!(log.renames.len(), 1);
+        assert_eq!(log.renames[0].from, "Alternatives/000");
+        assert_eq!(log.renames[0].to, "Alternatives/002");
+
+        // Moving back before committing leaves nothing to record
+        log.record("Alternatives/002", "Alternatives/000", Some("c1"));
+        assert!(log.renames.is_empty());
+
+        // The same path moved again after a commit is a separate record
+        log.record("a.wav", "b.wav", Some("c1"));
+        log.record("b.wav", "c.wav", Some("c2"));
+        assert_eq!(log.renames.len(), 2);
+    }
+
+    #[test]
+    fn test_follow_paths_across_moves() {
+        let mut log = RenameLog::default();
+        log.record("Samples", "Audio Files/Samples", Some("c1"));
+        log.record(
+            "Audio Files/Samples/kick.wav",
+            "Audio Files/Samples/Kick 1.wav",
+            Some("c3"),
+        );
+
+        // Newest first: c4, c3, c2, c1
+        let mut path = "Audio Files/Samples/Kick 1.wav".to_string();
+        let mut seen = Vec::new();
+        for commit in ["c4", "c3", "c2", "c1"] {
+            path = log.path_at(&path, commit);
+            seen.push(path.clone());
+        }
+        assert_eq!(
+            seen,
+            vec![
+                "Audio Files/Samples/Kick 1.wav",
+                "Audio Files/Samples/kick.wav",
+                "Audio Files/Samples/kick.wav",
+                "Samples/kick.wav",
+            ]
+        );
+
+        assert_eq!(
+            log.current_path("Samples/kick.wav"),
+            "Audio Files/Samples/Kick 1.wav"
+        );
+        assert_eq!(log.current_path("Samples2/kick.wav"), "Samples2/kick.wav");
+    }
+
+    #[test]
+    fn test_save_load_and_relative_paths() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("Song");
+        fs::create_dir_all(repo.join("Audio Files")).unwrap();
+
+        let mut log = RenameLog::default();
+        log.record("a.wav", "b.wav", None);
+        log.save(&repo).unwrap();
+        assert_eq!(RenameLog::load(&repo).unwrap(), log);
+        assert!(RenameLog::load(temp.path()).unwrap().renames.is_empty());
+
+        assert_eq!(
+            repo_relative(&repo, &repo.join("Audio Files/new.wav")).unwrap(),
+            "Audio Files/new.wav"
+        );
+        assert!(repo_relative(&repo, temp.path()).is_err());
+        assert!(repo_relative(&repo, &repo).is_err());
+    }
+
+    #[test]
+    fn test_move_refuses_repository_data() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("Song");
+        fs::create_dir_all(repo.join(".oxen")).unwrap();
+        fs::create_dir_all(repo.join(".auxin")).unwrap();
+        fs::write(repo.join(".oxen/HEAD"), "main").unwrap();
+        fs::write(repo.join("mix.wav"), "audio").unwrap();
+
+        for (from, to) in [
+            (".oxen/HEAD", "HEAD"),
+            (".oxen", "oxen-backup"),
+            ("mix.wav", ".auxin/mix.wav"),
+            ("mix.wav", ".oxen/versions/mix.wav"),
+        ] {
+            let error = move_path(&repo, &repo.join(from), &repo.join(to)).unwrap_err();
+            let error = error.to_string();
+            assert!(error.contains("into or out of"), "{}", error);
+        }
+        assert!(repo.join(".oxen/HEAD").exists());
+        assert!(repo.join("mix.wav").exists());
+    }
+}
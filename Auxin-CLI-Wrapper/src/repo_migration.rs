@@ -0,0 +1,590 @@
+//! Repository migration between servers
+//!
+//! `auxin migrate --to <url>` moves a repository to another Auxin server,
+//! typically when a studio leaves hub.oxen.ai for a self-hosted server.
+//! Pushing only moves commits, so the migration also copies what Auxin keeps
+//! alongside them:
+//!
+//! - metadata sidecars (BPM, key, tags) from the source server, or parsed
+//!   from commit messages for commits the source has none for
+//! - bounces stored under `.auxin/bounces`
+//! - commit comments from `.oxen/comments`
+//! - lock history from the source server's activity feed
+//!
+//! Comment authors and lock holders are rewritten through the repository's
+//! [`AuthorMap`] (or one given with `--author-map`) on the way, so old
+//! machine identities arrive as team usernames.
+//!
+//! Every step can be repeated: existing repositories, metadata and imported
+//! activity are left as they are, so an interrupted migration is resumed by
+//! running it again.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::DateTime;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::author_map::AuthorMap;
+use crate::bounce::{BounceManager, BounceMetadata};
+use crate::collaboration::{Comment, CommentManager};
+use crate::metadata_backfill::metadata_from_message;
+use crate::server_client::{
+    ActivityImportReport, ActivityRecord, AuxinServerClient, LogicProMetadata,
+    MetadataBatchEntry, MetadataBatchReport, ServerConfig,
+};
+use auxin_oxen::{CommitInfo, OxenSubprocess};
+
+/// Temporary remote used to push to the target
+pub const MIGRATION_REMOTE: &str = "auxin-migrate";
+
+/// Name the old `origin` is kept under after `--repoint-origin`
+pub const PREVIOUS_ORIGIN_REMOTE: &str = "origin-previous";
+
+/// Activity fetched from the source server when collecting lock history
+const ACTIVITY_HISTORY_LIMIT: usize = 100_000;
+
+/// Server and repository a migration writes to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationTarget {
+    /// Server root, e.g. `https://vcs.studio.example`
+    pub server_url: String,
+    pub namespace: String,
+    pub repo_name: String,
+}
+
+impl MigrationTarget {
+    /// Parse a repository URL such as `https://server:3000/namespace/repo`
+    pub fn parse(url: &str) -> Result<Self> {
+        let url = url.trim().trim_end_matches('/');
+        let (scheme, rest) = url
+            .split_once("://")
+            .filter(|(scheme, _)| matches!(*scheme, "http" | "https"))
+            .ok_or_else(|| anyhow!("Expected an http(s) URL like https://server/namespace/repo"))?;
+
+        let mut segments = rest.split('/');
+        let host = segments.next().unwrap_or_default();
+        let path: Vec<&str> = segments.collect();
+
+        let [namespace, repo_name] = path[..] else {
+            bail!("Expected the URL to end in /namespace/repo: {}", url);
+        };
+        for part in [host, namespace, repo_name] {
+            if part.is_empty() || part == "." || part == ".." {
+                bail!("Invalid repository URL: {}", url);
+            }
+        }
+
+        Ok(Self {
+            server_url: format!("{}://{}", scheme, host),
+            namespace: namespace.to_string(),
+            repo_name: repo_name.to_string(),
+        })
+    }
+
+    /// URL used as the Oxen remote
+    pub fn remote_url(&self) -> String {
+        format!("{}/{}/{}", self.server_url, self.namespace, self.repo_name)
+    }
+}
+
+/// Auxin server the repository is currently registered on
+pub struct MigrationSource {
+    pub client: AuxinServerClient,
+    pub namespace: String,
+    pub repo_name: String,
+}
+
+impl MigrationSource {
+    /// The configured server, if it's reachable and has this repository
+    ///
+    /// Repositories are looked up as `<default_namespace>/<directory name>`,
+    /// the same as `auxin server backfill-metadata`.
+    pub fn from_config(config: &auxin_config::Config, repo_root: &Path) -> Result<Option<Self>> {
+        let repo_name = repo_root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .context("Failed to determine repository name")?;
+        let client = AuxinServerClient::new(ServerConfig::from_config(config))?;
+
+        let namespace = config.cli.default_namespace.clone();
+        if !client.health_check()? || client.get_repository(&namespace, &repo_name).is_err() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            client,
+            namespace,
+            repo_name,
+        }))
+    }
+}
+
+/// Everything a migration will copy, gathered before anything is written
+#[derive(Debug, Default)]
+pub struct MigrationPlan {
+    pub branches: Vec<String>,
+    /// Oxen can't push tags, so these are reported rather than copied
+    pub tags: Vec<String>,
+    pub metadata: Vec<MetadataBatchEntry>,
+    pub bounces: Vec<BounceMetadata>,
+    /// Comments and lock history, oldest first
+    pub activities: Vec<ActivityRecord>,
+    /// Branch checked out locally, whose commits are counted
+    pub current_branch: String,
+    /// Commits on the current branch
+    pub local_commits: usize,
+}
+
+impl MigrationPlan {
+    pub fn comment_count(&self) -> usize {
+        self.activities
+            .iter()
+            .filter(|a| a.activity_type == "comment")
+            .count()
+    }
+
+    pub fn lock_event_count(&self) -> usize {
+        self.activities.iter().filter(|a| is_lock_event(a)).count()
+    }
+}
+
+/// Result of comparing the target with the local repository
+#[derive(Debug)]
+pub struct Verification {
+    /// Branch whose commits were compared
+    pub branch: String,
+    pub local_commits: usize,
+    pub target_commits: usize,
+    /// Branches that didn't show up on the target
+    pub missing_branches: Vec<String>,
+}
+
+impl Verification {
+    pub fn is_complete(&self) -> bool {
+        self.local_commits == self.target_commits && self.missing_branches.is_empty()
+    }
+}
+
+/// Copies a local repository and its Auxin data to a target server
+pub struct RepoMigrator {
+    repo_root: PathBuf,
+    target: MigrationTarget,
+    client: AuxinServerClient,
+    source: Option<MigrationSource>,
+    /// Overrides the repository's `.auxin/mailmap`
+    author_map: Option<AuthorMap>,
+    oxen: OxenSubprocess,
+}
+
+impl RepoMigrator {
+    pub fn new(
+        repo_root: &Path,
+        target: MigrationTarget,
+        token: Option<String>,
+        timeout_secs: u64,
+        source: Option<MigrationSource>,
+    ) -> Result<Self> {
+        let client = AuxinServerClient::new(ServerConfig {
+            url: target.server_url.clone(),
+            token,
+            timeout_secs,
+            ..Default::default()
+        })?;
+
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            target,
+            client,
+            source,
+            author_map: None,
+            oxen: OxenSubprocess::new(),
+        })
+    }
+
+    /// Use this author map instead of the repository's own
+    pub fn with_author_map(mut self, author_map: AuthorMap) -> Self {
+        self.author_map = Some(author_map);
+        self
+    }
+
+    pub fn target(&self) -> &MigrationTarget {
+        &self.target
+    }
+
+    pub fn has_source(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Collect everything to migrate without touching the target
+    pub fn plan(&self) -> Result<MigrationPlan> {
+        let branch_list = self
+            .oxen
+            .list_branches(&self.repo_root)
+            .context("Failed to list branches")?;
+        let current_branch = match branch_list.iter().find(|b| b.is_current) {
+            Some(branch) => branch.name.clone(),
+            None => self.oxen.current_branch(&self.repo_root)?,
+        };
+        let branches = branch_list.into_iter().map(|b| b.name).collect();
+        let tags = self
+            .oxen
+            .list_tags(&self.repo_root)
+            .context("Failed to list tags")?;
+        let commits = self
+            .oxen
+            .log(&self.repo_root, None)
+            .context("Failed to read commit history")?;
+
+        let source_metadata = match &self.source {
+            Some(source) => fetch_source_metadata(source)?,
+            None => HashMap::new(),
+        };
+
+        let mut activities: Vec<ActivityRecord> = CommentManager::new()
+            .get_all_comments(&self.repo_root)?
+            .iter()
+            .map(comment_activity)
+            .collect();
+        if let Some(source) = &self.source {
+            let history = source
+                .client
+                .get_activity(&source.namespace, &source.repo_name, ACTIVITY_HISTORY_LIMIT)
+                .context("Failed to read lock history from the source server")?;
+            activities.extend(history.into_iter().filter(is_lock_event));
+        }
+        activities.sort_by_cached_key(|a| DateTime::parse_from_rfc3339(&a.timestamp).ok());
+
+        let authors = match &self.author_map {
+            Some(map) => map.clone(),
+            None => AuthorMap::load(&self.repo_root)?,
+        };
+        remap_authors(&mut activities, &authors);
+
+        Ok(MigrationPlan {
+            branches,
+            tags,
+            metadata: collect_metadata(&commits, source_metadata),
+            bounces: BounceManager::new(&self.repo_root).list_bounces()?,
+            activities,
+            current_branch,
+            local_commits: commits.len(),
+        })
+    }
+
+    /// Create the repository on the target, returning false if it already existed
+    pub fn create_target(&self) -> Result<bool> {
+        let MigrationTarget {
+            namespace,
+            repo_name,
+            ..
+        } = &self.target;
+
+        if self.client.get_repository(namespace, repo_name).is_ok() {
+            return Ok(false);
+        }
+        self.client
+            .create_repository(namespace, repo_name, Some("Migrated with auxin migrate"))?;
+        Ok(true)
+    }
+
+    /// Point the temporary migration remote at the target
+    pub fn add_remote(&self) -> Result<()> {
+        self.oxen
+            .remote_add(&self.repo_root, MIGRATION_REMOTE, &self.target.remote_url())
+    }
+
+    /// Remove the temporary migration remote
+    pub fn remove_remote(&self) -> Result<()> {
+        self.oxen.remote_remove(&self.repo_root, MIGRATION_REMOTE)
+    }
+
+    pub fn push_branch(&self, branch: &str) -> Result<()> {
+        self.oxen
+            .push(&self.repo_root, Some(MIGRATION_REMOTE), Some(branch))
+            .with_context(|| format!("Failed to push branch '{}'", branch))
+    }
+
+    pub fn upload_metadata(&self, entries: &[MetadataBatchEntry]) -> Result<MetadataBatchReport> {
+        self.client
+            .store_metadata_batch(&self.target.namespace, &self.target.repo_name, entries)
+    }
+
+    pub fn upload_bounce(&self, bounce: &BounceMetadata) -> Result<()> {
+        let path = BounceManager::new(&self.repo_root)
+            .get_bounce_path(&bounce.commit_id)?
+            .ok_or_else(|| anyhow!("Audio file for bounce {} is missing", bounce.commit_id))?;
+
+        self.client.upload_bounce(
+            &self.target.namespace,
+            &self.target.repo_name,
+            &bounce.commit_id,
+            &path,
+            bounce.description.as_deref(),
+        )
+    }
+
+    /// Import the activities the target doesn't have yet
+    pub fn import_activity(&self, activities: &[ActivityRecord]) -> Result<ActivityImportReport> {
+        let MigrationTarget {
+            namespace,
+            repo_name,
+            ..
+        } = &self.target;
+
+        let on_target: HashSet<String> = self
+            .client
+            .get_activity(namespace, repo_name, ACTIVITY_HISTORY_LIMIT)
+            .context("Failed to read activity on the target")?
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        let new: Vec<ActivityRecord> = activities
+            .iter()
+            .filter(|a| !on_target.contains(&a.id))
+            .cloned()
+            .collect();
+
+        let mut report = self.client.import_activity(namespace, repo_name, &new)?;
+        report.skipped = activities.len() - new.len();
+        Ok(report)
+    }
+
+    /// Compare the commit count of the current branch and the branches on
+    /// the target with the local repository
+    pub fn verify(&self, plan: &MigrationPlan) -> Result<Verification> {
+        let MigrationTarget {
+            namespace,
+            repo_name,
+            ..
+        } = &self.target;
+
+        let target_commits = self
+            .client
+            .get_branch_commits(namespace, repo_name, &plan.current_branch)?
+            .len();
+        let on_target: HashSet<String> = self
+            .client
+            .get_branches(namespace, repo_name)?
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+
+        Ok(Verification {
+            branch: plan.current_branch.clone(),
+            local_commits: plan.local_commits,
+            target_commits,
+            missing_branches: plan
+                .branches
+                .iter()
+                .filter(|b| !on_target.contains(*b))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// Make the target the repository's `origin`
+    ///
+    /// The old origin is kept as [`PREVIOUS_ORIGIN_REMOTE`]; its URL is returned.
+    pub fn repoint_origin(&self) -> Result<Option<String>> {
+        let previous = self
+            .oxen
+            .remote_list(&self.repo_root)?
+            .into_iter()
+            .find(|(name, _)| name == "origin")
+            .map(|(_, url)| url);
+
+        if let Some(url) = &previous {
+            self.oxen
+                .remote_add(&self.repo_root, PREVIOUS_ORIGIN_REMOTE, url)?;
+        }
+        self.oxen
+            .remote_add(&self.repo_root, "origin", &self.target.remote_url())?;
+
+        Ok(previous)
+    }
+}
+
+/// Metadata stored on the source server, by commit
+fn fetch_source_metadata(
+    source: &MigrationSource,
+) -> Result<HashMap<String, LogicProMetadata>> {
+    let ids = source
+        .client
+        .list_metadata(&source.namespace, &source.repo_name)
+        .context("Failed to list metadata on the source server")?;
+
+    ids.into_iter()
+        .map(|id| {
+            let metadata = source
+                .client
+                .get_metadata(&source.namespace, &source.repo_name, &id)?;
+            Ok((id, metadata))
+        })
+        .collect()
+}
+
+/// Metadata to upload: the source server's copy where it has one, otherwise
+/// whatever the commit message carries
+pub fn collect_metadata(
+    commits: &[CommitInfo],
+    mut from_source: HashMap<String, LogicProMetadata>,
+) -> Vec<MetadataBatchEntry> {
+    let mut entries: Vec<MetadataBatchEntry> = commits
+        .iter()
+        .filter_map(|commit| {
+            let metadata = from_source
+                .remove(&commit.id)
+                .or_else(|| metadata_from_message(&commit.message))?;
+            Some(MetadataBatchEntry {
+                commit_id: commit.id.clone(),
+                metadata,
+            })
+        })
+        .collect();
+
+    // Metadata for commits on other branches
+    let mut rest: Vec<_> = from_source.into_iter().collect();
+    rest.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.extend(
+        rest.into_iter()
+            .map(|(commit_id, metadata)| MetadataBatchEntry { commit_id, metadata }),
+    );
+
+    entries
+}
+
+/// A local commit comment as a server activity
+pub fn comment_activity(comment: &Comment) -> ActivityRecord {
+    ActivityRecord {
+        id: comment.id.clone(),
+        activity_type: "comment".to_string(),
+        user: comment.author.clone(),
+        message: comment.text.clone(),
+        timestamp: comment.created_at.to_rfc3339(),
+        metadata: Some(serde_json::json!({ "commit_id": comment.commit_id })),
+    }
+}
+
+/// Replace activity users with their canonical names
+pub fn remap_authors(activities: &mut [ActivityRecord], authors: &AuthorMap) {
+    for activity in activities {
+        activity.user = authors.resolve(&activity.user);
+    }
+}
+
+fn is_lock_event(activity: &ActivityRecord) -> bool {
+    matches!(
+        activity.activity_type.as_str(),
+        "lock_acquired" | "lock_released"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        let target = MigrationTarget::parse("https://vcs.studio.example:3000/band/album/").unwrap();
+        assert_eq!(target.server_url, "https://vcs.studio.example:3000");
+        assert_eq!(target.namespace, "band");
+        assert_eq!(target.repo_name, "album");
+        assert_eq!(target.remote_url(), "https://vcs.studio.example:3000/band/album");
+
+        for bad in [
+            "vcs.studio.example/band/album",
+            "ftp://vcs.studio.example/band/album",
+            "https://vcs.studio.example/album",
+            "https://vcs.studio.example/a/b/c",
+            "https:///band/album",
+            "https://vcs.studio.example/../album",
+        ] {
+            assert!(MigrationTarget::parse(bad).is_err(), "{} was accepted", bad);
+        }
+    }
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn metadata(bpm: f64) -> LogicProMetadata {
+        LogicProMetadata {
+            bpm: Some(bpm),
+            sample_rate: None,
+            key_signature: None,
+            tags: Some(vec![]),
+            custom: None,
+        }
+    }
+
+    #[test]
+    fn test_collect_metadata_prefers_source() {
+        let commits = [
+            commit("c1", "Verse\n\nBPM: 120"),
+            commit("c2", "Chorus\n\nBPM: 128"),
+            commit("c3", "Cleanup"),
+        ];
+        let from_source = HashMap::from([
+            ("c2".to_string(), metadata(130.0)),
+            ("other-branch".to_string(), metadata(90.0)),
+        ]);
+
+        let entries = collect_metadata(&commits, from_source);
+        let summary: Vec<(&str, Option<f64>)> = entries
+            .iter()
+            .map(|e| (e.commit_id.as_str(), e.metadata.bpm))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [("c1", Some(120.0)), ("c2", Some(130.0)), ("other-branch", Some(90.0))]
+        );
+    }
+
+    #[test]
+    fn test_comment_activity() {
+        let comment = Comment {
+            id: "comment-1".to_string(),
+            commit_id: "abc123".to_string(),
+            author: "alice".to_string(),
+            text: "Love the chorus".to_string(),
+            created_at: "2024-03-02T10:30:00Z".parse().unwrap(),
+        };
+
+        let activity = comment_activity(&comment);
+        assert_eq!(activity.id, "comment-1");
+        assert_eq!(activity.activity_type, "comment");
+        assert_eq!(activity.timestamp, "2024-03-02T10:30:00+00:00");
+        assert_eq!(activity.metadata.unwrap()["commit_id"], "abc123");
+    }
+
+    #[test]
+    fn test_remap_authors() {
+        let authors = AuthorMap::parse("alice <alice@old-laptop>").unwrap();
+        let mut activities = vec![
+            comment_activity(&Comment {
+                id: "comment-1".to_string(),
+                commit_id: "abc123".to_string(),
+                author: "alice@old-laptop".to_string(),
+                text: "Bounce is up".to_string(),
+                created_at: chrono::Utc::now(),
+            }),
+            ActivityRecord {
+                id: "lock-1".to_string(),
+                activity_type: "lock_acquired".to_string(),
+                user: "bob@studio-mac".to_string(),
+                message: "Lock acquired".to_string(),
+                timestamp: "2024-03-01T09:00:00Z".to_string(),
+                metadata: None,
+            },
+        ];
+
+        remap_authors(&mut activities, &authors);
+        assert_eq!(activities[0].user, "alice");
+        assert_eq!(activities[1].user, "bob@studio-mac");
+    }
+}
@@ -0,0 +1,218 @@
+//! Structured results of the read-only commands
+//!
+//! With `--json`, `status`, `show`, `lock status` and `queue status` print
+//! one of these instead of their tables, so scripts don't have to scrape
+//! the text. `log`, `team` and `activity` print their existing types
+//! (`CommitInfo`, `TeamMember`, `Activity`) and `doctor` a
+//! [`DoctorReport`](crate::doctor::DoctorReport).
+//!
+//! Fields are added over time but not renamed or removed.
+
+use crate::commit_metadata::CommitMetadata;
+use crate::delivery::Delivery;
+use crate::identity::Identity;
+use crate::offline_queue::{QueueEntry, QueueStats};
+use crate::remote_lock::RemoteLock;
+use crate::server_client::LockInfo;
+use crate::versioning::VersionEntry;
+use crate::StatusInfo;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Result of `auxin status`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusReport {
+    /// Nothing staged, modified or untracked
+    pub clean: bool,
+    pub staged: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub untracked: Vec<PathBuf>,
+    /// Operations in the offline queue waiting to sync
+    pub pending_sync: usize,
+}
+
+impl StatusReport {
+    pub fn new(status: StatusInfo, pending_sync: usize) -> Self {
+        Self {
+            clean: status.staged.is_empty()
+                && status.modified.is_empty()
+                && status.untracked.is_empty(),
+            staged: status.staged,
+            modified: status.modified,
+            untracked: status.untracked,
+            pending_sync,
+        }
+    }
+}
+
+/// Result of `auxin show`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitReport {
+    pub id: String,
+    /// The full commit message, metadata lines included
+    pub message: String,
+    /// BPM, key, tags and the rest, parsed from the message
+    pub metadata: CommitMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<VersionEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deliveries: Vec<Delivery>,
+}
+
+impl CommitReport {
+    pub fn new(id: &str, message: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            message: message.to_string(),
+            metadata: CommitMetadata::parse_commit_message(message),
+            version: None,
+            deliveries: Vec::new(),
+        }
+    }
+}
+
+/// Where a [`LockStatusReport`] came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockSource {
+    /// The auxin server (`use_server_locks`)
+    Server,
+    /// The lock file in the repository
+    Local,
+}
+
+/// Result of `auxin lock status`
+///
+/// Times are RFC 3339 strings, as the server sends them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockStatusReport {
+    pub source: LockSource,
+    /// Someone holds an unexpired lock
+    pub locked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquired_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_heartbeat: Option<String>,
+    /// Held by this user on this machine
+    #[serde(default)]
+    pub owned_by_you: bool,
+    /// No heartbeat for over an hour (local locks)
+    #[serde(default)]
+    pub stale: bool,
+    /// The holder's machine has gone offline (server locks)
+    #[serde(default)]
+    pub orphaned: bool,
+}
+
+impl LockStatusReport {
+    fn unlocked(source: LockSource) -> Self {
+        Self {
+            source,
+            locked: false,
+            lock_id: None,
+            holder: None,
+            machine_id: None,
+            acquired_at: None,
+            expires_at: None,
+            last_heartbeat: None,
+            owned_by_you: false,
+            stale: false,
+            orphaned: false,
+        }
+    }
+
+    /// Report for the lock file `lock`, if there is one
+    pub fn from_local(lock: Option<&RemoteLock>) -> Self {
+        let Some(lock) = lock else {
+            return Self::unlocked(LockSource::Local);
+        };
+        Self {
+            source: LockSource::Local,
+            locked: !lock.is_expired(),
+            lock_id: Some(lock.lock_id.clone()),
+            holder: Some(lock.locked_by.clone()),
+            machine_id: Some(lock.machine_id.clone()),
+            acquired_at: Some(lock.acquired_at.to_rfc3339()),
+            expires_at: Some(lock.expires_at.to_rfc3339()),
+            last_heartbeat: Some(lock.last_heartbeat.to_rfc3339()),
+            owned_by_you: lock.is_owned_by_current_user(),
+            stale: lock.is_stale(),
+            orphaned: false,
+        }
+    }
+
+    /// Report for the server's answer `info`
+    pub fn from_server(info: &LockInfo) -> Self {
+        let Some(lock) = info.lock.as_ref().filter(|_| info.locked) else {
+            return Self::unlocked(LockSource::Server);
+        };
+        Self {
+            source: LockSource::Server,
+            locked: true,
+            lock_id: Some(lock.lock_id.clone()),
+            holder: Some(lock.user.clone()),
+            machine_id: Some(lock.machine_id.clone()),
+            acquired_at: Some(lock.acquired_at.clone()),
+            expires_at: Some(lock.expires_at.clone()),
+            last_heartbeat: Some(lock.last_heartbeat.clone()),
+            owned_by_you: Identity::current().is_me(&lock.user)
+                && lock.machine_id == crate::server_client::get_machine_id(),
+            stale: false,
+            orphaned: info.orphaned,
+        }
+    }
+}
+
+/// Result of `auxin queue status`
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueStatusReport {
+    pub stats: QueueStats,
+    /// The entries waiting to sync
+    pub pending: Vec<QueueEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_report_clean() {
+        let status = StatusInfo {
+            modified: vec![],
+            untracked: vec![],
+            staged: vec![],
+        };
+        assert!(StatusReport::new(status.clone(), 0).clean);
+
+        let dirty = StatusInfo {
+            untracked: vec![PathBuf::from("Audio Files/take1.wav")],
+            ..status
+        };
+        let report = StatusReport::new(dirty, 2);
+        assert!(!report.clean);
+        assert_eq!(report.pending_sync, 2);
+    }
+
+    #[test]
+    fn test_lock_status_report_local() {
+        let report = LockStatusReport::from_local(None);
+        assert!(!report.locked);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["source"], "local");
+        assert!(json.get("holder").is_none());
+
+        let lock = RemoteLock::new("project.logicx", "someone-else@studio", 4);
+        let report = LockStatusReport::from_local(Some(&lock));
+        assert!(report.locked);
+        assert!(!report.owned_by_you);
+        assert_eq!(report.holder.as_deref(), Some("someone-else@studio"));
+    }
+}
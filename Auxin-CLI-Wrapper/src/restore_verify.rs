@@ -0,0 +1,252 @@
+//! Hash verification after `auxin restore`
+//!
+//! A checkout that's interrupted, or races a sync client, can leave files
+//! that look restored but aren't. After the checkout every file is compared
+//! with the hash the commit recorded for it (`oxen info`); a file that
+//! doesn't match, or is missing, is restored again on its own, up to
+//! `MAX_ATTEMPTS` times, before it's reported as failed.
+//!
+//! Besides what's on disk now, the paths in the pre-restore manifest
+//! (`tree_manifest`) are checked, so a committed file the checkout failed
+//! to bring back is caught too. Files the commit doesn't track are skipped.
+
+use crate::tree_manifest::TreeManifest;
+use anyhow::{Context, Result};
+use auxin_oxen::{FileInfo, OxenSubprocess};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Times a mismatched file is restored again before giving up
+pub const MAX_ATTEMPTS: usize = 2;
+
+/// Where expected and actual hashes come from, and how a file is restored
+pub trait RestoreSource {
+    /// The file as the commit recorded it; `None` if it isn't tracked there
+    fn committed(&self, path: &str) -> Option<FileInfo>;
+    /// The file as it is on disk; `None` if it's missing
+    fn on_disk(&self, path: &str) -> Option<FileInfo>;
+    /// Restore one file from the commit
+    fn restore(&self, path: &str) -> Result<()>;
+}
+
+/// `RestoreSource` backed by oxen, for a repo checked out at `commit`
+pub struct OxenRestoreSource<'a> {
+    oxen: &'a OxenSubprocess,
+    repo: &'a Path,
+    commit: &'a str,
+}
+
+impl<'a> OxenRestoreSource<'a> {
+    pub fn new(oxen: &'a OxenSubprocess, repo: &'a Path, commit: &'a str) -> Self {
+        Self { oxen, repo, commit }
+    }
+}
+
+impl RestoreSource for OxenRestoreSource<'_> {
+    fn committed(&self, path: &str) -> Option<FileInfo> {
+        self.oxen
+            .file_info(self.repo, Path::new(path), Some(self.commit))
+            .ok()
+    }
+
+    fn on_disk(&self, path: &str) -> Option<FileInfo> {
+        if !self.repo.join(path).is_file() {
+            return None;
+        }
+        self.oxen.file_info(self.repo, Path::new(path), None).ok()
+    }
+
+    fn restore(&self, path: &str) -> Result<()> {
+        self.oxen
+            .restore_files(self.repo, &[Path::new(path)])
+            .with_context(|| format!("Failed to restore {}", path))
+    }
+}
+
+/// Outcome of verifying a restore
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Files matching the commit, including repaired ones
+    pub verified: usize,
+    pub verified_bytes: u64,
+    /// Files that matched only after being restored again
+    pub repaired: Vec<String>,
+    /// Files that still don't match, and why
+    pub failed: Vec<(String, String)>,
+    /// Files the commit doesn't track
+    pub untracked: usize,
+}
+
+impl VerifyReport {
+    pub fn is_verified(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Paths to verify: the files on disk plus those there before the restore
+pub fn files_to_check(repo: &Path) -> Result<Vec<String>> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeSet<String>) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if dir == root && (name == ".oxen" || name == ".auxin") {
+                    continue;
+                }
+                walk(root, &path, files)?;
+            } else if file_type.is_file() {
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                files.insert(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeSet::new();
+    walk(repo, repo, &mut files)?;
+    if let Some(manifest) = TreeManifest::load(repo)? {
+        if manifest.operation == "restore" {
+            files.extend(manifest.files.into_keys());
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Verify `files` against the commit, restoring mismatches again
+///
+/// `progress` is called with each path before it's checked.
+pub fn verify(
+    files: &[String],
+    source: &dyn RestoreSource,
+    mut progress: impl FnMut(&str),
+) -> VerifyReport {
+    let mut report = VerifyReport::default();
+
+    for path in files {
+        progress(path);
+        let Some(expected) = source.committed(path) else {
+            report.untracked += 1;
+            continue;
+        };
+        let matches = || {
+            source
+                .on_disk(path)
+                .is_some_and(|actual| actual.hash == expected.hash)
+        };
+
+        let mut verified = matches();
+        let mut attempts = 0;
+        let mut last_error = None;
+        while !verified && attempts < MAX_ATTEMPTS {
+            attempts += 1;
+            match source.restore(path) {
+                Ok(()) => verified = matches(),
+                Err(e) => last_error = Some(e.to_string()),
+            }
+        }
+
+        if verified {
+            report.verified += 1;
+            report.verified_bytes += expected.size;
+            if attempts > 0 {
+                report.repaired.push(path.clone());
+            }
+        } else {
+            let reason = last_error.unwrap_or_else(|| match source.on_disk(path) {
+                Some(_) => "hash doesn't match the commit".to_string(),
+                None => "missing".to_string(),
+            });
+            report.failed.push((path.clone(), reason));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// Commit and disk hashes; `restore` fixes a file after `flaky` attempts
+    struct FakeSource {
+        committed: HashMap<&'static str, &'static str>,
+        disk: RefCell<HashMap<&'static str, &'static str>>,
+        flaky: HashMap<&'static str, usize>,
+        attempts: RefCell<HashMap<String, usize>>,
+    }
+
+    impl RestoreSource for FakeSource {
+        fn committed(&self, path: &str) -> Option<FileInfo> {
+            self.committed.get(path).map(|hash| FileInfo {
+                hash: hash.to_string(),
+                size: 100,
+            })
+        }
+
+        fn on_disk(&self, path: &str) -> Option<FileInfo> {
+            self.disk.borrow().get(path).map(|hash| FileInfo {
+                hash: hash.to_string(),
+                size: 100,
+            })
+        }
+
+        fn restore(&self, path: &str) -> Result<()> {
+            let mut attempts = self.attempts.borrow_mut();
+            let count = attempts.entry(path.to_string()).or_insert(0);
+            *count += 1;
+            if *count >= self.flaky.get(path).copied().unwrap_or(1) {
+                let (key, hash) = self.committed.get_key_value(path).unwrap();
+                self.disk.borrow_mut().insert(key, hash);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_verify_repairs_and_reports() {
+        let source = FakeSource {
+            committed: HashMap::from([
+                ("ProjectData", "aaa"),
+                ("Kick.wav", "bbb"),
+                ("Vox.wav", "ccc"),
+                ("Missing.wav", "ddd"),
+            ]),
+            disk: RefCell::new(HashMap::from([
+                ("ProjectData", "aaa"),
+                ("Kick.wav", "stale"),
+                ("Vox.wav", "stale"),
+                ("notes.txt", "eee"),
+            ])),
+            flaky: HashMap::from([("Vox.wav", 5)]),
+            attempts: RefCell::new(HashMap::new()),
+        };
+        let files: Vec<String> = ["Kick.wav", "Missing.wav", "ProjectData", "Vox.wav", "notes.txt"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let mut seen = 0;
+        let report = verify(&files, &source, |_| seen += 1);
+        assert_eq!(seen, 5);
+        assert_eq!(report.verified, 3);
+        assert_eq!(report.verified_bytes, 300);
+        assert_eq!(report.repaired, ["Kick.wav", "Missing.wav"]);
+        assert_eq!(
+            report.failed,
+            [(
+                "Vox.wav".to_string(),
+                "hash doesn't match the commit".to_string()
+            )]
+        );
+        assert_eq!(source.attempts.borrow()["Vox.wav"], MAX_ATTEMPTS);
+        assert_eq!(report.untracked, 1);
+        assert!(!report.is_verified());
+    }
+}
@@ -30,6 +30,8 @@
 /// let results = engine.search(&commits, &query);
 /// ```
 use crate::CommitInfo;
+use crate::metadata_store::MetadataStore;
+use crate::music_theory::{self, Key};
 use crate::CommitMetadata;
 use serde::{Deserialize, Serialize};
 
@@ -43,10 +45,11 @@ pub struct SearchQuery {
     /// Filter by exact sample rate
     pub sample_rate: Option<u32>,
 
-    /// Filter by key signature (case-insensitive, partial match)
+    /// Filter by key signature: a key ("Am") matches enharmonic spellings,
+    /// anything else ("minor") is a case-insensitive partial match
     pub key_contains: Option<String>,
 
-    /// Filter by exact key signature
+    /// Filter by exact key signature (enharmonic spellings match)
     pub key_exact: Option<String>,
 
     /// Filter by tags (ANY match - OR logic)
@@ -99,7 +102,7 @@ impl SearchQuery {
         self
     }
 
-    /// Filter by key signature (partial, case-insensitive match)
+    /// Filter by key signature (see [`music_theory::key_matches`])
     pub fn key_contains(mut self, key: impl Into<String>) -> Self {
         self.key_contains = Some(key.into());
         self
@@ -163,12 +166,28 @@ impl SearchQuery {
 }
 
 /// Search engine for querying commit history
-pub struct SearchEngine;
+pub struct SearchEngine {
+    /// Sidecar metadata, read instead of commit messages where present
+    store: Option<MetadataStore>,
+}
 
 impl SearchEngine {
     /// Create a new search engine
     pub fn new() -> Self {
-        Self
+        Self { store: None }
+    }
+
+    /// Create a search engine that reads a repository's metadata sidecars
+    pub fn with_store(store: MetadataStore) -> Self {
+        Self { store: Some(store) }
+    }
+
+    /// Metadata of a commit, from its sidecar if it has one
+    pub fn metadata(&self, commit: &CommitInfo) -> CommitMetadata {
+        match &self.store {
+            Some(store) => store.get(commit).metadata,
+            None => CommitMetadata::parse_commit_message(&commit.message),
+        }
     }
 
     /// Search commits with the given query
@@ -189,8 +208,7 @@ impl SearchEngine {
 
     /// Check if a commit matches the query criteria
     fn matches_query(&self, commit: &CommitInfo, query: &SearchQuery) -> bool {
-        // Parse commit metadata
-        let metadata = CommitMetadata::parse_commit_message(&commit.message);
+        let metadata = self.metadata(commit);
 
         // BPM filters
         if let Some(min_bpm) = query.bpm_min {
@@ -223,7 +241,7 @@ impl SearchEngine {
         // Key signature filters
         if let Some(ref key_contains) = query.key_contains {
             if let Some(ref key) = metadata.key_signature {
-                if !key.to_lowercase().contains(&key_contains.to_lowercase()) {
+                if !music_theory::key_matches(key, key_contains) {
                     return false;
                 }
             } else {
@@ -232,7 +250,11 @@ impl SearchEngine {
         }
 
         if let Some(ref key_exact) = query.key_exact {
-            if metadata.key_signature.as_deref() != Some(key_exact.as_str()) {
+            let same = metadata
+                .key_signature
+                .as_deref()
+                .is_some_and(|key| music_theory::same_key(key, key_exact));
+            if !same {
                 return false;
             }
         }
@@ -316,7 +338,7 @@ impl SearchEngine {
                         }
                     }
                     "key" => {
-                        query = query.key_contains(value);
+                        query = query.key_contains(Key::canonicalize(value));
                     }
                     "tag" | "tags" => {
                         let tags: Vec<String> =
@@ -341,7 +363,7 @@ impl SearchEngine {
 
     /// Calculate a relevance score for a commit (for ranking results)
     pub fn relevance_score(&self, commit: &CommitInfo, query: &SearchQuery) -> f32 {
-        let metadata = CommitMetadata::parse_commit_message(&commit.message);
+        let metadata = self.metadata(commit);
         let mut score = 0.0;
 
         // Higher score for exact BPM match
@@ -397,6 +419,7 @@ mod tests {
         CommitInfo {
             id: "abc123".to_string(),
             message: metadata.format_commit_message(),
+            ..Default::default()
         }
     }
 
@@ -443,6 +466,28 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn test_key_filter_matches_spellings() {
+        let engine = SearchEngine::new();
+        // Keys as older commits stored them, before normalization
+        let commits: Vec<CommitInfo> = ["a minor", "Amin", "A Major", "G# Minor"]
+            .iter()
+            .enumerate()
+            .map(|(i, key)| CommitInfo {
+                id: format!("commit{}", i),
+                message: format!("Take {}\n\nKey: {}", i, key),
+                ..Default::default()
+            })
+            .collect();
+
+        let query = SearchEngine::parse_query("key:Am");
+        assert_eq!(query.key_contains, Some("A Minor".to_string()));
+        assert_eq!(engine.search(&commits, &query).len(), 2);
+
+        let query = SearchEngine::parse_query("key:Abm");
+        assert_eq!(engine.search(&commits, &query).len(), 1);
+    }
+
     #[test]
     fn test_message_filter() {
         let engine = SearchEngine::new();
@@ -469,6 +514,7 @@ mod tests {
         let commit = CommitInfo {
             id: "abc123".to_string(),
             message: metadata.format_commit_message(),
+            ..Default::default()
         };
 
         let query = SearchQuery::new()
@@ -540,4 +586,21 @@ mod tests {
 
         assert_eq!(results.len(), 2);
     }
+
+    #[test]
+    fn test_search_reads_sidecars() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = MetadataStore::for_repo(dir.path());
+        let commit = create_test_commit("Demo", Some(100.0), None);
+        // Stored with a different tempo than the message says
+        let sidecar = create_test_commit("Demo", Some(130.0), None);
+        store.record(&sidecar.id, &sidecar.message).unwrap();
+
+        let query = SearchQuery::new().bpm_range(120.0, 140.0);
+        assert!(SearchEngine::new()
+            .search(&[commit.clone()], &query)
+            .is_empty());
+        let engine = SearchEngine::with_store(store);
+        assert_eq!(engine.search(&[commit], &query).len(), 1);
+    }
 }
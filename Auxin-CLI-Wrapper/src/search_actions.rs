@@ -0,0 +1,206 @@
+//! Actions on search results: `auxin search --interactive`
+//!
+//! Search results become a pick list; choosing a commit offers what to do
+//! with it. Most actions run the matching `auxin` command (`show`,
+//! `restore`, `compare`, `bounce play`) so they behave exactly as when
+//! typed. "Open sandbox" checks the commit out into a separate copy of the
+//! project under `~/.auxin/sandboxes/`, leaving the working copy alone.
+
+use crate::bounce::BounceManager;
+use crate::CommitInfo;
+use anyhow::{bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// What to do with a selected search result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchAction {
+    Show,
+    Restore,
+    CompareWithHead,
+    PlayBounce,
+    OpenSandbox,
+}
+
+impl SearchAction {
+    /// Actions offered for `commit`; playback only when it has a bounce
+    pub fn available(project: &Path, commit: &CommitInfo) -> Vec<Self> {
+        let has_bounce = BounceManager::new(project)
+            .get_bounce_path(&commit.id)
+            .ok()
+            .flatten()
+            .is_some();
+
+        [
+            Self::Show,
+            Self::CompareWithHead,
+            Self::PlayBounce,
+            Self::OpenSandbox,
+            Self::Restore,
+        ]
+        .into_iter()
+        .filter(|action| *action != Self::PlayBounce || has_bounce)
+        .collect()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Show => "Show details",
+            Self::Restore => "Restore working copy to this commit",
+            Self::CompareWithHead => "Compare with HEAD",
+            Self::PlayBounce => "Play bounce",
+            Self::OpenSandbox => "Open in sandbox (working copy untouched)",
+        }
+    }
+
+    /// Whether the action changes the working copy and needs confirming
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, Self::Restore)
+    }
+
+    /// `auxin` arguments that perform the action; `None` for the sandbox,
+    /// which has no command of its own
+    pub fn auxin_args(&self, commit_id: &str, head_id: &str) -> Option<Vec<String>> {
+        let args: Vec<&str> = match self {
+            Self::Show => vec!["show", commit_id],
+            Self::Restore => vec!["restore", commit_id],
+            Self::CompareWithHead => vec!["compare", commit_id, head_id],
+            Self::PlayBounce => vec!["bounce", "play", commit_id],
+            Self::OpenSandbox => return None,
+        };
+        Some(args.into_iter().map(str::to_string).collect())
+    }
+
+    /// Run the action, with the `auxin` command's output going to the terminal
+    pub fn run(
+        &self,
+        auxin_exe: &Path,
+        project: &Path,
+        commit_id: &str,
+        head_id: &str,
+    ) -> Result<()> {
+        let Some(args) = self.auxin_args(commit_id, head_id) else {
+            let sandbox = open_sandbox(project, commit_id)?;
+            println!("Sandbox: {}", sandbox.display());
+            return open_in_file_browser(&sandbox);
+        };
+
+        let status = Command::new(auxin_exe)
+            .args(&args)
+            .current_dir(project)
+            .status()
+            .with_context(|| format!("Failed to run auxin {}", args.join(" ")))?;
+        if !status.success() {
+            bail!("auxin {} failed", args[0]);
+        }
+        Ok(())
+    }
+}
+
+/// Where the sandbox for `commit_id` of `project` lives
+pub fn sandbox_dir(project: &Path, commit_id: &str) -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    let name = project
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+    let short = &commit_id[..8.min(commit_id.len())];
+    Ok(home
+        .join(".auxin")
+        .join("sandboxes")
+        .join(format!("{}-{}", short, name)))
+}
+
+/// Check `commit_id` out into a copy of the project, reusing an earlier one
+///
+/// The folder keeps the project's name (prefixed with the commit) so a
+/// `.logicx` package still opens in Logic Pro.
+pub fn open_sandbox(project: &Path, commit_id: &str) -> Result<PathBuf> {
+    let sandbox = sandbox_dir(project, commit_id)?;
+    if sandbox.join(".oxen").exists() {
+        return Ok(sandbox);
+    }
+    if !project.join(".oxen").exists() {
+        bail!("{} is not an auxin repository", project.display());
+    }
+
+    copy_dir(project, &sandbox)
+        .with_context(|| format!("Failed to copy the project to {}", sandbox.display()))?;
+    if let Err(e) = OxenSubprocess::new().checkout(&sandbox, commit_id) {
+        let _ = fs::remove_dir_all(&sandbox);
+        return Err(e.context(format!("Failed to check out {} in the sandbox", commit_id)));
+    }
+    Ok(sandbox)
+}
+
+fn open_in_file_browser(path: &Path) -> Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to run {}", opener))?;
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_available_actions_and_args() {
+        let temp = TempDir::new().unwrap();
+        let commit = CommitInfo {
+            id: "abc123def456".to_string(),
+            message: "Mix v2".to_string(),
+            ..Default::default()
+        };
+
+        let actions = SearchAction::available(temp.path(), &commit);
+        assert!(!actions.contains(&SearchAction::PlayBounce));
+        assert_eq!(actions.len(), 4);
+
+        let bounces = temp.path().join(".auxin").join("bounces");
+        fs::create_dir_all(&bounces).unwrap();
+        fs::write(bounces.join("abc123def456.wav"), b"RIFF").unwrap();
+        let actions = SearchAction::available(temp.path(), &commit);
+        assert!(actions.contains(&SearchAction::PlayBounce));
+
+        assert_eq!(
+            SearchAction::CompareWithHead
+                .auxin_args("abc123", "fff999")
+                .unwrap(),
+            vec!["compare", "abc123", "fff999"]
+        );
+        assert!(SearchAction::OpenSandbox
+            .auxin_args("abc123", "fff999")
+            .is_none());
+        assert!(SearchAction::Restore.is_destructive());
+    }
+
+    #[test]
+    fn test_sandbox_requires_repository() {
+        let temp = TempDir::new().unwrap();
+        assert!(open_sandbox(temp.path(), "abc123").is_err());
+    }
+}
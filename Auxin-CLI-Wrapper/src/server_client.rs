@@ -3,8 +3,14 @@
 //! This module provides a client for communicating with auxin-server
 //! for repository management, locks, and metadata operations.
 
+use crate::dual_stack::HappyEyeballsResolver;
+use crate::network_resilience::RetryPolicy;
 use anyhow::{anyhow, Context, Result};
+use auxin_config::{NetworkClass, RetrySettings};
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 /// Configuration for server connection
@@ -18,6 +24,9 @@ pub struct ServerConfig {
 
     /// Request timeout in seconds
     pub timeout_secs: u64,
+
+    /// Retries for failed requests (`[network.server_api]`)
+    pub retry: RetrySettings,
 }
 
 impl Default for ServerConfig {
@@ -26,6 +35,20 @@ impl Default for ServerConfig {
             url: "http://localhost:3000".to_string(),
             token: None,
             timeout_secs: 30,
+            retry: auxin_config::Config::default().retry_settings(NetworkClass::ServerApi),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// The server configured in `[cli]`, with `[network.server_api]` retries
+    pub fn from_config(config: &auxin_config::Config) -> Self {
+        let retry = config.retry_settings(NetworkClass::ServerApi);
+        Self {
+            url: config.cli.url.clone(),
+            token: crate::credential_store::server_token(config),
+            timeout_secs: retry.timeout_s,
+            retry,
         }
     }
 }
@@ -62,6 +85,12 @@ pub struct Branch {
 pub struct LockInfo {
     pub locked: bool,
     pub lock: Option<LockHolder>,
+    /// Last presence ping from the holder's daemon, if it runs one
+    #[serde(default)]
+    pub holder_last_seen: Option<String>,
+    /// The holder's machine has gone quiet; `reclaim_lock` can take over
+    #[serde(default)]
+    pub orphaned: bool,
 }
 
 /// Lock holder details
@@ -73,6 +102,9 @@ pub struct LockHolder {
     pub acquired_at: String,
     pub expires_at: String,
     pub last_heartbeat: String,
+    /// Token issued on acquisition; pass it back on release/heartbeat
+    #[serde(default)]
+    pub fencing_token: Option<u64>,
 }
 
 /// Lock acquire request
@@ -89,6 +121,42 @@ pub struct LockReleaseRequest {
     pub lock_id: String,
     pub user: String,
     pub machine_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fencing_token: Option<u64>,
+}
+
+/// Daemon presence ping
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PresenceRequest<'a> {
+    user: &'a str,
+    machine_id: &'a str,
+}
+
+/// Link a client identity to the authenticated account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdentityRequest<'a> {
+    user: &'a str,
+    machine_id: &'a str,
+    hub_user: Option<&'a str>,
+}
+
+/// A client identity the server linked to an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedIdentity {
+    pub identity: String,
+    pub account: String,
+    #[serde(default)]
+    pub hub_user: Option<String>,
+    pub machine_id: String,
+    pub linked_at: String,
+}
+
+/// Lock heartbeat request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockHeartbeatRequest {
+    pub lock_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fencing_token: Option<u64>,
 }
 
 /// Logic Pro metadata
@@ -101,29 +169,630 @@ pub struct LogicProMetadata {
     pub custom: Option<serde_json::Value>,
 }
 
+/// Single entry in a metadata batch upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataBatchEntry {
+    pub commit_id: String,
+    pub metadata: LogicProMetadata,
+}
+
+/// Metadata batch request body
+#[derive(Debug, Clone, Serialize)]
+struct MetadataBatchRequest<'a> {
+    entries: &'a [MetadataBatchEntry],
+}
+
+/// Per-commit failure reported by a metadata batch upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataBatchFailure {
+    pub commit_id: String,
+    pub error: String,
+}
+
+/// Outcome of a metadata batch upload
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataBatchReport {
+    /// Commits whose metadata was stored
+    pub stored: Vec<String>,
+
+    /// Commits that could not be stored, with the server's reason
+    pub failed: Vec<MetadataBatchFailure>,
+}
+
+impl MetadataBatchReport {
+    /// True if every entry was stored
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    fn merge(&mut self, other: MetadataBatchReport) {
+        self.stored.extend(other.stored);
+        self.failed.extend(other.failed);
+    }
+}
+
+/// Activity event recorded by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityRecord {
+    pub id: String,
+    /// e.g. "commit", "lock_acquired", "comment"
+    pub activity_type: String,
+    pub user: String,
+    pub message: String,
+    pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Activity import request body
+#[derive(Debug, Clone, Serialize)]
+struct ActivityImportRequest<'a> {
+    activities: &'a [ActivityRecord],
+}
+
+/// Outcome of an activity import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityImportReport {
+    /// Activities added to the log
+    pub imported: usize,
+    /// Activities the server already had, left out of the import
+    #[serde(default)]
+    pub skipped: usize,
+}
+
+/// Distribution of lock hold times, in minutes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HoldTimes {
+    pub count: usize,
+    pub mean_minutes: f64,
+    pub p50_minutes: f64,
+    pub p90_minutes: f64,
+    pub p99_minutes: f64,
+    pub max_minutes: f64,
+}
+
+/// One user's locking habits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserLockStats {
+    pub user: String,
+    pub holds: HoldTimes,
+    /// Locks that ran out instead of being released
+    pub expired: usize,
+    /// Times this user asked for a lock someone else held
+    pub blocked: usize,
+    /// Times someone asked for a lock this user held
+    pub blocking: usize,
+}
+
+/// Contention in one repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoContention {
+    pub namespace: String,
+    pub name: String,
+    pub holds: usize,
+    pub contention_events: usize,
+    pub mean_hold_minutes: f64,
+}
+
+/// Lock hold times and contention from `GET .../locks/stats`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockStats {
+    pub since: String,
+    pub holds: HoldTimes,
+    pub contention_events: usize,
+    pub expired: usize,
+    /// Busiest first
+    pub users: Vec<UserLockStats>,
+    /// Most contended first
+    pub repos: Vec<RepoContention>,
+}
+
+/// A held lock as listed by `GET /admin/locks`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminLock {
+    pub namespace: String,
+    pub name: String,
+    pub lock: LockHolder,
+    /// Minutes since the holder's last heartbeat
+    pub idle_minutes: i64,
+    pub stale: bool,
+}
+
+/// An activity from `GET /admin/audit`, with its repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub namespace: String,
+    pub name: String,
+    /// Server account the activity's user is linked to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    #[serde(flatten)]
+    pub activity: ActivityRecord,
+}
+
+/// Filters for `GET /admin/audit`
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub limit: usize,
+    pub namespace: Option<String>,
+    pub user: Option<String>,
+    /// e.g. "lock_released"
+    pub activity_type: Option<String>,
+}
+
+/// A user's live sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub username: String,
+    pub sessions: usize,
+    /// When the longest-lived session expires
+    pub expires_at: String,
+}
+
+/// Disk used by one namespace on the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamespaceUsage {
+    pub namespace: String,
+    pub repos: usize,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// What server-side gc removed (or would remove)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub expired_sessions: usize,
+    pub expired_locks: usize,
+    pub stale_guards: usize,
+    pub temp_files: usize,
+    pub bytes: u64,
+}
+
 /// Create repository request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateRepoRequest {
     pub description: Option<String>,
 }
 
+/// Maximum entries sent per metadata batch request (matches the server limit)
+pub const METADATA_BATCH_SIZE: usize = 100;
+
+/// Maximum activities sent per import request (the server accepts up to 1000)
+pub const ACTIVITY_IMPORT_BATCH_SIZE: usize = 500;
+
+/// Pause between consecutive metadata batch requests
+pub const METADATA_BATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Newest server API version this client speaks
+pub const API_VERSION: u32 = 1;
+
+/// Oldest server API version this client still speaks
+pub const MIN_API_VERSION: u32 = 1;
+
+/// Server response to the version handshake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVersionInfo {
+    pub api_version: u32,
+    pub min_api_version: u32,
+    pub server_version: String,
+}
+
+/// Outcome of API version negotiation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiCompatibility {
+    /// Server and client speak the same API version
+    Compatible { version: u32 },
+
+    /// Server predates API versioning; unversioned paths are used
+    Legacy,
+
+    /// Server supports a newer API but still serves ours
+    ServerNewer { version: u32, server_version: u32 },
+
+    /// Server only supports an older API than ours
+    ServerOlder { version: u32, server_version: u32 },
+
+    /// No API version is supported by both sides
+    Incompatible { server_min: u32, server_max: u32 },
+}
+
+impl ApiCompatibility {
+    /// Pick the best API version supported by both client and server
+    pub fn negotiate(info: &ServerVersionInfo) -> Self {
+        let version = info.api_version.min(API_VERSION);
+
+        if version < info.min_api_version || version < MIN_API_VERSION {
+            Self::Incompatible {
+                server_min: info.min_api_version,
+                server_max: info.api_version,
+            }
+        } else if info.api_version > API_VERSION {
+            Self::ServerNewer {
+                version,
+                server_version: info.api_version,
+            }
+        } else if info.api_version < API_VERSION {
+            Self::ServerOlder {
+                version,
+                server_version: info.api_version,
+            }
+        } else {
+            Self::Compatible { version }
+        }
+    }
+
+    /// Path prefix for API requests under this negotiation
+    pub fn api_prefix(&self) -> String {
+        match self {
+            Self::Legacy => "/api".to_string(),
+            Self::Compatible { version }
+            | Self::ServerNewer { version, .. }
+            | Self::ServerOlder { version, .. } => format!("/api/v{}", version),
+            Self::Incompatible { .. } => format!("/api/v{}", API_VERSION),
+        }
+    }
+
+    /// Warning to show the user, if the versions don't match exactly
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            Self::Compatible { .. } => None,
+            Self::Legacy => Some(
+                "Server predates API versioning; consider upgrading auxin-server".to_string(),
+            ),
+            Self::ServerNewer { server_version, .. } => Some(format!(
+                "Server supports API v{} but this client only v{}; upgrade auxin for new features",
+                server_version, API_VERSION
+            )),
+            Self::ServerOlder { server_version, .. } => Some(format!(
+                "Server only supports API v{} (client v{}); some features may be unavailable \
+                 until auxin-server is upgraded",
+                server_version, API_VERSION
+            )),
+            Self::Incompatible {
+                server_min,
+                server_max,
+            } => Some(format!(
+                "Server supports API v{}-v{}, this client v{}-v{}; requests will likely fail. {}",
+                server_min,
+                server_max,
+                MIN_API_VERSION,
+                API_VERSION,
+                if *server_max < MIN_API_VERSION {
+                    "Upgrade auxin-server"
+                } else {
+                    "Upgrade the auxin CLI"
+                }
+            )),
+        }
+    }
+}
+
+/// Header carrying the correlation ID shared with the server's logs
+pub const REQUEST_ID_HEADER: &str = "X-Auxin-Request-Id";
+
+/// Generate a short correlation ID for one request
+pub fn new_request_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..12].to_string()
+}
+
+/// Error body returned by the server (`application/problem+json`)
+///
+/// Servers predating problem+json only send `error`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type", default)]
+    pub problem_type: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Machine-readable error codes sent by the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCode {
+    LockHeld,
+    QuotaExceeded,
+    TokenExpired,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    /// Any code this client doesn't know about
+    Other(String),
+}
+
+impl ErrorCode {
+    pub fn parse(code: &str) -> Self {
+        match code {
+            "LOCK_HELD" => Self::LockHeld,
+            "QUOTA_EXCEEDED" => Self::QuotaExceeded,
+            "TOKEN_EXPIRED" => Self::TokenExpired,
+            "UNAUTHORIZED" => Self::Unauthorized,
+            "FORBIDDEN" => Self::Forbidden,
+            "NOT_FOUND" => Self::NotFound,
+            "CONFLICT" => Self::Conflict,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Process exit code for a command failing with this error
+    ///
+    /// See docs/user/error-codes.md.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::TokenExpired | Self::Unauthorized => 3,
+            Self::LockHeld => 4,
+            Self::QuotaExceeded => 7,
+            Self::Forbidden => 8,
+            _ => 1,
+        }
+    }
+
+    /// What the user can do about it
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::LockHeld => {
+                Some("Run 'auxin lock status' to see who holds the lock and when it expires")
+            }
+            Self::TokenExpired => Some(
+                "Your server session expired; log in again and update 'token' under [cli] \
+                 in ~/.auxin/config.toml",
+            ),
+            Self::Unauthorized => {
+                Some("Set a valid server token under [cli] in ~/.auxin/config.toml")
+            }
+            Self::Forbidden => Some("Ask the project owner to grant you access"),
+            Self::QuotaExceeded => Some(
+                "The request exceeds a server limit; try a smaller operation or ask the \
+                 server administrator",
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Exit code for failures to reach the server at all
+pub const NETWORK_ERROR_EXIT_CODE: i32 = 5;
+
+/// A failed server request, with the ID to look up in the server logs
+#[derive(Debug)]
+pub struct RequestError {
+    /// What the client was doing, e.g. "Failed to acquire lock"
+    pub action: Option<String>,
+    pub request_id: String,
+    /// HTTP status, if the server answered at all
+    pub status: Option<u16>,
+    /// Structured error body, if the server sent one
+    pub problem: Option<Box<ProblemDetails>>,
+    /// Transport error or status line
+    message: String,
+}
+
+impl RequestError {
+    fn from_ureq(request_id: String, error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(status, response) => {
+                let message = format!("{}: status code {}", response.get_url(), status);
+                Self {
+                    action: None,
+                    request_id,
+                    status: Some(status),
+                    problem: response.into_json().ok().map(Box::new),
+                    message,
+                }
+            }
+            ureq::Error::Transport(transport) => Self {
+                action: None,
+                request_id,
+                status: None,
+                problem: None,
+                message: transport.to_string(),
+            },
+        }
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// Machine-readable error code, if the server sent one
+    pub fn code(&self) -> Option<ErrorCode> {
+        self.problem
+            .as_ref()
+            .and_then(|p| p.code.as_deref())
+            .map(ErrorCode::parse)
+    }
+
+    /// Process exit code for a command failing with this error
+    pub fn exit_code(&self) -> i32 {
+        match (self.code(), self.status) {
+            (Some(code), _) => code.exit_code(),
+            (None, None) => NETWORK_ERROR_EXIT_CODE,
+            (None, Some(_)) => 1,
+        }
+    }
+
+    /// Describe what failed and convert to `anyhow::Error`
+    ///
+    /// The error stays downcastable so the CLI can pick an exit code from it.
+    pub fn context(mut self, action: &str) -> anyhow::Error {
+        self.action = Some(action.to_string());
+        anyhow::Error::new(self)
+    }
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ref action) = self.action {
+            write!(f, "{}: ", action)?;
+        }
+
+        match self.problem.as_deref() {
+            Some(ProblemDetails {
+                detail: Some(detail),
+                code: Some(code),
+                ..
+            }) => write!(f, "{} ({})", detail, code)?,
+            // Older servers: {"error": "..."}
+            Some(ProblemDetails {
+                error: Some(error),
+                ..
+            }) => write!(f, "{}", error)?,
+            _ => write!(f, "{}", self.message)?,
+        }
+
+        write!(f, ", request id {}", self.request_id)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+/// A request tagged with a correlation ID
+struct TaggedRequest {
+    request: ureq::Request,
+    request_id: String,
+    retry: RetryPolicy,
+}
+
+impl TaggedRequest {
+    fn new(request: ureq::Request, retry: RetryPolicy) -> Self {
+        let request_id = new_request_id();
+        Self {
+            request: request.set(REQUEST_ID_HEADER, &request_id),
+            request_id,
+            retry,
+        }
+    }
+
+    /// Add a query parameter, percent-encoded
+    fn query(mut self, key: &str, value: &str) -> Self {
+        self.request = self.request.query(key, value);
+        self
+    }
+
+    fn call(self) -> Result<ureq::Response, RequestError> {
+        self.log();
+        let result = self.with_retries(|request| request.call().map_err(Box::new));
+        Self::finish(self.request_id, result.map_err(|e| *e))
+    }
+
+    fn send_json(self, data: impl Serialize) -> Result<ureq::Response, RequestError> {
+        self.log();
+        let data = serde_json::to_value(data);
+        let result = match data {
+            Ok(data) => self.with_retries(|request| request.send_json(&data).map_err(Box::new)),
+            Err(e) => Err(Box::new(ureq::Error::from(std::io::Error::from(e)))),
+        };
+        Self::finish(self.request_id, result.map_err(|e| *e))
+    }
+
+    fn send_bytes(self, content_type: &str, data: &[u8]) -> Result<ureq::Response, RequestError> {
+        self.log();
+        let result = self.with_retries(|request| {
+            request
+                .set("Content-Type", content_type)
+                .send_bytes(data)
+                .map_err(Box::new)
+        });
+        Self::finish(self.request_id, result.map_err(|e| *e))
+    }
+
+    /// Send, retrying failures that are safe to repeat
+    ///
+    /// GET requests are retried on timeouts, dropped connections and
+    /// 429/502/503/504; other requests only when the server was never
+    /// reached, so nothing is applied twice. Errors are boxed as `ureq::Error`
+    /// is large.
+    fn with_retries(
+        &self,
+        send: impl Fn(ureq::Request) -> Result<ureq::Response, Box<ureq::Error>>,
+    ) -> Result<ureq::Response, Box<ureq::Error>> {
+        let idempotent = self.request.method() == "GET";
+        let mut attempt = 0;
+        loop {
+            let result = send(self.request.clone());
+            let retryable = match result.as_ref().map_err(|e| e.as_ref()) {
+                Ok(_) => false,
+                Err(ureq::Error::Status(code, _)) => {
+                    idempotent && matches!(code, 429 | 502 | 503 | 504)
+                }
+                Err(ureq::Error::Transport(transport)) => match transport.kind() {
+                    ureq::ErrorKind::Dns | ureq::ErrorKind::ConnectionFailed => true,
+                    ureq::ErrorKind::Io => idempotent,
+                    _ => false,
+                },
+            };
+            attempt += 1;
+            if !retryable || !self.retry.should_retry(attempt) {
+                return result;
+            }
+
+            let delay = self.retry.delay_for_attempt(attempt);
+            crate::vlog!(
+                "Retrying {} in {}ms (attempt {}/{})",
+                self.request.url(),
+                delay.as_millis(),
+                attempt,
+                self.retry.max_attempts()
+            );
+            std::thread::sleep(delay);
+        }
+    }
+
+    fn log(&self) {
+        crate::vlog!(
+            "{} {} (request id {})",
+            self.request.method(),
+            self.request.url(),
+            self.request_id
+        );
+    }
+
+    fn finish(
+        request_id: String,
+        result: Result<ureq::Response, ureq::Error>,
+    ) -> Result<ureq::Response, RequestError> {
+        result.map_err(|error| {
+            let error = RequestError::from_ureq(request_id, error);
+            crate::vlog!("Request failed: {}", error);
+            error
+        })
+    }
+}
+
 /// HTTP client for auxin-server
 pub struct AuxinServerClient {
     agent: ureq::Agent,
     config: ServerConfig,
+    /// API path prefix, negotiated with the server on first use
+    api_prefix: OnceLock<String>,
 }
 
 impl AuxinServerClient {
     /// Create a new client with the given configuration
     pub fn new(config: ServerConfig) -> Result<Self> {
+        let connect_timeout = Duration::from_secs(10);
         let agent = ureq::AgentBuilder::new()
-            .timeout_connect(Duration::from_secs(10))
+            .resolver(HappyEyeballsResolver::new(connect_timeout))
+            .timeout_connect(connect_timeout)
             .timeout_read(Duration::from_secs(config.timeout_secs))
             .timeout_write(Duration::from_secs(config.timeout_secs))
             .user_agent("auxin-cli/0.2.0")
             .build();
 
-        Ok(Self { agent, config })
+        Ok(Self {
+            agent,
+            config,
+            api_prefix: OnceLock::new(),
+        })
     }
 
     /// Create a client with default configuration
@@ -142,22 +811,46 @@ impl AuxinServerClient {
 
     /// Get the base URL for API requests
     fn api_url(&self, path: &str) -> String {
-        format!("{}/api{}", self.config.url.trim_end_matches('/'), path)
+        format!(
+            "{}{}{}",
+            self.config.url.trim_end_matches('/'),
+            self.api_prefix(),
+            path
+        )
     }
 
-    /// Make a GET request with optional auth
-    fn get(&self, url: &str) -> ureq::Request {
-        let req = self.agent.get(url);
-        if let Some(ref token) = self.config.token {
-            req.set("Authorization", &format!("Bearer {}", token))
-        } else {
-            req
-        }
+    /// API path prefix, negotiating with the server on first use
+    fn api_prefix(&self) -> &str {
+        self.api_prefix.get_or_init(|| match self.server_version() {
+            Ok(info) => {
+                let compat = info
+                    .as_ref()
+                    .map_or(ApiCompatibility::Legacy, ApiCompatibility::negotiate);
+                if let Some(warning) = compat.warning() {
+                    crate::warn!("{}", warning);
+                }
+                compat.api_prefix()
+            }
+            // Server unreachable: the request itself will report the error
+            Err(_) => format!("/api/v{}", API_VERSION),
+        })
+    }
+
+    /// Make a GET request with optional auth and a fresh request ID
+    fn get(&self, url: &str) -> TaggedRequest {
+        TaggedRequest::new(self.authorize(self.agent.get(url)), self.retry_policy())
+    }
+
+    /// Make a POST request with optional auth and a fresh request ID
+    fn post(&self, url: &str) -> TaggedRequest {
+        TaggedRequest::new(self.authorize(self.agent.post(url)), self.retry_policy())
     }
 
-    /// Make a POST request with optional auth
-    fn post(&self, url: &str) -> ureq::Request {
-        let req = self.agent.post(url);
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::from_settings(&self.config.retry)
+    }
+
+    fn authorize(&self, req: ureq::Request) -> ureq::Request {
         if let Some(ref token) = self.config.token {
             req.set("Authorization", &format!("Bearer {}", token))
         } else {
@@ -176,6 +869,31 @@ impl AuxinServerClient {
         }
     }
 
+    /// Query the API versions supported by the server
+    ///
+    /// Returns `None` for servers that predate API versioning.
+    pub fn server_version(&self) -> Result<Option<ServerVersionInfo>> {
+        let url = format!("{}/api/version", self.config.url.trim_end_matches('/'));
+        match self.agent.get(&url).call() {
+            // Old servers may answer with the web UI's index page instead of JSON
+            Ok(resp) => Ok(resp.into_json().ok()),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(anyhow!("Failed to query server version: {}", e)),
+        }
+    }
+
+    /// Negotiate the API version with the server
+    ///
+    /// The result is used for all further requests made by this client.
+    pub fn check_compatibility(&self) -> Result<ApiCompatibility> {
+        let compat = match self.server_version()? {
+            Some(info) => ApiCompatibility::negotiate(&info),
+            None => ApiCompatibility::Legacy,
+        };
+        let _ = self.api_prefix.set(compat.api_prefix());
+        Ok(compat)
+    }
+
     // ========== Repository Operations ==========
 
     /// List all repositories
@@ -184,7 +902,7 @@ impl AuxinServerClient {
         let response = self
             .get(&url)
             .call()
-            .map_err(|e| anyhow!("Failed to list repositories: {}", e))?;
+            .map_err(|e| e.context("Failed to list repositories"))?;
 
         response
             .into_json()
@@ -197,7 +915,7 @@ impl AuxinServerClient {
         let response = self
             .get(&url)
             .call()
-            .map_err(|e| anyhow!("Failed to get repository: {}", e))?;
+            .map_err(|e| e.context("Failed to get repository"))?;
 
         response.into_json().context("Failed to parse repository")
     }
@@ -217,7 +935,7 @@ impl AuxinServerClient {
         let response = self
             .post(&url)
             .send_json(&body)
-            .map_err(|e| anyhow!("Failed to create repository: {}", e))?;
+            .map_err(|e| e.context("Failed to create repository"))?;
 
         response
             .into_json()
@@ -231,11 +949,26 @@ impl AuxinServerClient {
         let url = self.api_url(&format!("/repos/{}/{}/commits", namespace, name));
         match self.get(&url).call() {
             Ok(response) => response.into_json().context("Failed to parse commits"),
-            Err(ureq::Error::Status(501, _)) => {
+            Err(e) if e.status() == Some(501) => {
                 // VCS not implemented in mock mode
                 Ok(vec![])
             }
-            Err(e) => Err(anyhow!("Failed to get commits: {}", e)),
+            Err(e) => Err(e.context("Failed to get commits")),
+        }
+    }
+
+    /// Get commits on a branch of a repository
+    pub fn get_branch_commits(
+        &self,
+        namespace: &str,
+        name: &str,
+        branch: &str,
+    ) -> Result<Vec<Commit>> {
+        let url = self.api_url(&format!("/repos/{}/{}/commits", namespace, name));
+        match self.get(&url).query("branch", branch).call() {
+            Ok(response) => response.into_json().context("Failed to parse commits"),
+            Err(e) if e.status() == Some(501) => Ok(vec![]),
+            Err(e) => Err(e.context("Failed to get commits")),
         }
     }
 
@@ -246,11 +979,11 @@ impl AuxinServerClient {
         let url = self.api_url(&format!("/repos/{}/{}/branches", namespace, name));
         match self.get(&url).call() {
             Ok(response) => response.into_json().context("Failed to parse branches"),
-            Err(ureq::Error::Status(501, _)) => {
+            Err(e) if e.status() == Some(501) => {
                 // VCS not implemented in mock mode
                 Ok(vec![])
             }
-            Err(e) => Err(anyhow!("Failed to get branches: {}", e)),
+            Err(e) => Err(e.context("Failed to get branches")),
         }
     }
 
@@ -262,7 +995,7 @@ impl AuxinServerClient {
         let response = self
             .get(&url)
             .call()
-            .map_err(|e| anyhow!("Failed to get lock status: {}", e))?;
+            .map_err(|e| e.context("Failed to get lock status"))?;
 
         response.into_json().context("Failed to parse lock status")
     }
@@ -286,7 +1019,7 @@ impl AuxinServerClient {
         let response = self
             .post(&url)
             .send_json(&body)
-            .map_err(|e| anyhow!("Failed to acquire lock: {}", e))?;
+            .map_err(|e| e.context("Failed to acquire lock"))?;
 
         response
             .into_json()
@@ -294,40 +1027,379 @@ impl AuxinServerClient {
     }
 
     /// Release a lock on a repository
+    ///
+    /// The server rejects the release if `fencing_token` belongs to an
+    /// earlier holder (e.g. our lock expired and someone else took it).
     pub fn release_lock(
         &self,
         namespace: &str,
         name: &str,
-        lock_id: &str,
+        lock: &LockHolder,
         user: &str,
         machine_id: &str,
     ) -> Result<()> {
         let url = self.api_url(&format!("/repos/{}/{}/locks/release", namespace, name));
         let body = LockReleaseRequest {
-            lock_id: lock_id.to_string(),
+            lock_id: lock.lock_id.clone(),
             user: user.to_string(),
             machine_id: machine_id.to_string(),
+            fencing_token: lock.fencing_token,
         };
 
         self.post(&url)
             .send_json(&body)
-            .map_err(|e| anyhow!("Failed to release lock: {}", e))?;
+            .map_err(|e| e.context("Failed to release lock"))?;
 
         Ok(())
     }
 
     /// Send heartbeat to extend lock
-    pub fn heartbeat_lock(&self, namespace: &str, name: &str) -> Result<()> {
+    pub fn heartbeat_lock(
+        &self,
+        namespace: &str,
+        name: &str,
+        lock: &LockHolder,
+    ) -> Result<LockHolder> {
         let url = self.api_url(&format!("/repos/{}/{}/locks/heartbeat", namespace, name));
+        let body = LockHeartbeatRequest {
+            lock_id: lock.lock_id.clone(),
+            fencing_token: lock.fencing_token,
+        };
+
+        let response = self
+            .post(&url)
+            .send_json(&body)
+            .map_err(|e| e.context("Failed to send heartbeat"))?;
+
+        response
+            .into_json()
+            .context("Failed to parse heartbeat response")
+    }
+
+    /// Take over a lock whose holder has gone offline
+    ///
+    /// The server refuses (409) unless the holder's daemon and the lock's
+    /// heartbeat have both gone quiet.
+    pub fn reclaim_lock(
+        &self,
+        namespace: &str,
+        name: &str,
+        user: &str,
+        machine_id: &str,
+        timeout_hours: u32,
+    ) -> Result<LockHolder> {
+        let url = self.api_url(&format!("/repos/{}/{}/locks/reclaim", namespace, name));
+        let body = LockAcquireRequest {
+            user: user.to_string(),
+            machine_id: machine_id.to_string(),
+            timeout_hours,
+        };
+
+        let response = self
+            .post(&url)
+            .send_json(&body)
+            .map_err(|e| e.context("Failed to reclaim lock"))?;
+
+        response
+            .into_json()
+            .context("Failed to parse lock response")
+    }
+
+    /// Tell the server this machine's daemon is running
+    pub fn send_presence(&self, user: &str, machine_id: &str) -> Result<()> {
+        let url = self.api_url("/presence");
         self.post(&url)
+            .send_json(&PresenceRequest { user, machine_id })
+            .map_err(|e| e.context("Failed to send presence"))?;
+
+        Ok(())
+    }
+
+    /// Link `identity` to the account the token belongs to, so the server's
+    /// audit log and lock stats show the account
+    pub fn link_identity(&self, identity: &crate::identity::Identity) -> Result<LinkedIdentity> {
+        let url = self.api_url("/identity");
+        let response = self
+            .post(&url)
+            .send_json(&IdentityRequest {
+                user: &identity.user(),
+                machine_id: &get_machine_id(),
+                hub_user: identity.hub_user.as_deref(),
+            })
+            .map_err(|e| e.context("Failed to link identity"))?;
+
+        response.into_json().context("Failed to parse linked identity")
+    }
+
+    /// Lock hold times and contention for a repository over the last `days`
+    pub fn repo_lock_stats(&self, namespace: &str, name: &str, days: i64) -> Result<LockStats> {
+        let url = self.api_url(&format!("/repos/{}/{}/locks/stats", namespace, name));
+        let response = self
+            .get(&url)
+            .query("days", &days.to_string())
+            .call()
+            .map_err(|e| e.context("Failed to get lock stats"))?;
+
+        response.into_json().context("Failed to parse lock stats")
+    }
+
+    /// Lock hold times and contention across the repositories we can read,
+    /// optionally only those in `namespace`
+    pub fn lock_stats(&self, days: i64, namespace: Option<&str>) -> Result<LockStats> {
+        let url = self.api_url("/locks/stats");
+        let mut request = self.get(&url).query("days", &days.to_string());
+        if let Some(namespace) = namespace {
+            request = request.query("namespace", namespace);
+        }
+        let response = request
+            .call()
+            .map_err(|e| e.context("Failed to get lock stats"))?;
+
+        response.into_json().context("Failed to parse lock stats")
+    }
+
+    // ========== Activity Operations ==========
+
+    /// Get the most recent activity for a repository (newest first)
+    pub fn get_activity(
+        &self,
+        namespace: &str,
+        name: &str,
+        limit: usize,
+    ) -> Result<Vec<ActivityRecord>> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/activity?limit={}",
+            namespace, name, limit
+        ));
+        let response = self
+            .get(&url)
+            .call()
+            .map_err(|e| e.context("Failed to get activity"))?;
+
+        response.into_json().context("Failed to parse activity")
+    }
+
+    /// Import activity recorded elsewhere, keeping IDs and timestamps
+    ///
+    /// Sent in chunks of [`ACTIVITY_IMPORT_BATCH_SIZE`]. The server refuses
+    /// a chunk holding an ID it already has, so leave those out first. Needs
+    /// the repository owner or an admin.
+    pub fn import_activity(
+        &self,
+        namespace: &str,
+        name: &str,
+        activities: &[ActivityRecord],
+    ) -> Result<ActivityImportReport> {
+        let url = self.api_url(&format!("/repos/{}/{}/activity/import", namespace, name));
+        let mut report = ActivityImportReport::default();
+
+        for chunk in activities.chunks(ACTIVITY_IMPORT_BATCH_SIZE) {
+            let body = ActivityImportRequest { activities: chunk };
+            let chunk_report: ActivityImportReport = self
+                .post(&url)
+                .send_json(&body)
+                .map_err(|e| e.context("Failed to import activity"))?
+                .into_json()
+                .context("Failed to parse activity import response")?;
+
+            report.imported += chunk_report.imported;
+        }
+
+        Ok(report)
+    }
+
+    // ========== Admin Operations ==========
+    //
+    // These need a token for an account with the Admin role.
+
+    /// Held locks across all repositories, longest idle first
+    pub fn admin_locks(&self, stale_minutes: i64, stale_only: bool) -> Result<Vec<AdminLock>> {
+        let url = self.api_url(&format!(
+            "/admin/locks?stale_minutes={}&stale_only={}",
+            stale_minutes, stale_only
+        ));
+        let response = self
+            .get(&url)
+            .call()
+            .map_err(|e| e.context("Failed to list locks"))?;
+
+        response.into_json().context("Failed to parse locks")
+    }
+
+    /// Break a repository's lock; returns the lock that was broken
+    ///
+    /// The server refuses unless the lock is stale or `force` is set.
+    pub fn admin_break_lock(
+        &self,
+        namespace: &str,
+        name: &str,
+        stale_minutes: i64,
+        force: bool,
+    ) -> Result<LockHolder> {
+        let url = self.api_url(&format!("/admin/locks/{}/{}/break", namespace, name));
+        let response = self
+            .post(&url)
+            .send_json(serde_json::json!({
+                "stale_minutes": stale_minutes,
+                "force": force,
+            }))
+            .map_err(|e| e.context("Failed to break lock"))?;
+
+        response.into_json().context("Failed to parse broken lock")
+    }
+
+    /// Activity across all repositories, newest first
+    pub fn admin_audit(&self, filter: &AuditFilter) -> Result<Vec<AuditEvent>> {
+        let url = self.api_url("/admin/audit");
+        let mut request = self.get(&url).query("limit", &filter.limit.to_string());
+        for (key, value) in [
+            ("namespace", &filter.namespace),
+            ("user", &filter.user),
+            ("activity_type", &filter.activity_type),
+        ] {
+            if let Some(value) = value {
+                request = request.query(key, value);
+            }
+        }
+        let response = request
+            .call()
+            .map_err(|e| e.context("Failed to get audit events"))?;
+
+        response.into_json().context("Failed to parse audit events")
+    }
+
+    /// Live sessions per user
+    pub fn admin_sessions(&self) -> Result<Vec<SessionSummary>> {
+        let url = self.api_url("/admin/sessions");
+        let response = self
+            .get(&url)
+            .call()
+            .map_err(|e| e.context("Failed to list sessions"))?;
+
+        response.into_json().context("Failed to parse sessions")
+    }
+
+    /// Revoke every token of `username`, or a single `token`; returns how
+    /// many were revoked
+    pub fn admin_revoke_tokens(
+        &self,
+        username: Option<&str>,
+        token: Option<&str>,
+    ) -> Result<usize> {
+        let url = self.api_url("/admin/tokens/revoke");
+        let response: serde_json::Value = self
+            .post(&url)
+            .send_json(serde_json::json!({ "username": username, "token": token }))
+            .map_err(|e| e.context("Failed to revoke tokens"))?
+            .into_json()
+            .context("Failed to parse revoke response")?;
+
+        Ok(response["revoked"].as_u64().unwrap_or(0) as usize)
+    }
+
+    /// Disk usage per namespace, largest first
+    pub fn admin_storage(&self) -> Result<Vec<NamespaceUsage>> {
+        let url = self.api_url("/admin/storage");
+        let response = self
+            .get(&url)
             .call()
-            .map_err(|e| anyhow!("Failed to send heartbeat: {}", e))?;
+            .map_err(|e| e.context("Failed to get storage usage"))?;
+
+        response.into_json().context("Failed to parse storage usage")
+    }
+
+    /// Run server-side housekeeping
+    pub fn admin_gc(&self, dry_run: bool) -> Result<GcReport> {
+        let url = self.api_url("/admin/gc");
+        let response = self
+            .post(&url)
+            .send_json(serde_json::json!({ "dry_run": dry_run }))
+            .map_err(|e| e.context("Failed to run gc"))?;
+
+        response.into_json().context("Failed to parse gc report")
+    }
+
+    // ========== Bounce Operations ==========
+
+    /// Upload a bounce audio file for a commit
+    ///
+    /// Requires the Producer or Admin role on the server.
+    pub fn upload_bounce(
+        &self,
+        namespace: &str,
+        name: &str,
+        commit_id: &str,
+        audio_path: &Path,
+        description: Option<&str>,
+    ) -> Result<()> {
+        let url = self.api_url(&format!("/repos/{}/{}/bounces/{}", namespace, name, commit_id));
+        let filename = audio_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .context("Bounce path has no file name")?;
+        let audio = std::fs::read(audio_path)
+            .with_context(|| format!("Failed to read {}", audio_path.display()))?;
+
+        let boundary = format!("auxin-{}", uuid::Uuid::new_v4().simple());
+        let body = multipart_body(&boundary, &filename, &audio, description);
+
+        self.post(&url)
+            .send_bytes(&format!("multipart/form-data; boundary={}", boundary), &body)
+            .map_err(|e| e.context("Failed to upload bounce"))?;
+
+        Ok(())
+    }
+
+    // ========== Stem Operations ==========
+
+    /// Upload a commit's stem set: its manifest and every file, read from
+    /// the stem set's directory `dir`
+    ///
+    /// Requires the Producer or Admin role on the server.
+    pub fn upload_stems(
+        &self,
+        namespace: &str,
+        name: &str,
+        set: &crate::stems::StemSet,
+        dir: &Path,
+    ) -> Result<()> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/stems/{}",
+            namespace, name, set.commit_id
+        ));
+        let manifest = serde_json::to_vec(set).context("Failed to serialize stem manifest")?;
+
+        let boundary = format!("auxin-{}", uuid::Uuid::new_v4().simple());
+        let mut body = Vec::with_capacity(set.total_size() as usize + 4096);
+        push_text_part(&mut body, &boundary, "manifest", &manifest);
+        for stem in &set.stems {
+            let path = dir.join(&stem.filename);
+            let audio =
+                std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            push_file_part(&mut body, &boundary, &stem.filename, &audio);
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+        self.post(&url)
+            .send_bytes(&format!("multipart/form-data; boundary={}", boundary), &body)
+            .map_err(|e| e.context("Failed to upload stems"))?;
 
         Ok(())
     }
 
     // ========== Metadata Operations ==========
 
+    /// List commit IDs that have metadata stored on the server
+    pub fn list_metadata(&self, namespace: &str, name: &str) -> Result<Vec<String>> {
+        let url = self.api_url(&format!("/repos/{}/{}/metadata", namespace, name));
+        let response = self
+            .get(&url)
+            .call()
+            .map_err(|e| e.context("Failed to list metadata"))?;
+
+        response.into_json().context("Failed to parse metadata list")
+    }
+
     /// Get metadata for a commit
     pub fn get_metadata(
         &self,
@@ -342,7 +1414,7 @@ impl AuxinServerClient {
         let response = self
             .get(&url)
             .call()
-            .map_err(|e| anyhow!("Failed to get metadata: {}", e))?;
+            .map_err(|e| e.context("Failed to get metadata"))?;
 
         response.into_json().context("Failed to parse metadata")
     }
@@ -361,24 +1433,137 @@ impl AuxinServerClient {
         ));
         self.post(&url)
             .send_json(metadata)
-            .map_err(|e| anyhow!("Failed to store metadata: {}", e))?;
+            .map_err(|e| e.context("Failed to store metadata"))?;
 
         Ok(())
     }
+
+    /// Store metadata for many commits
+    ///
+    /// Entries are sent in chunks of [`METADATA_BATCH_SIZE`], pausing
+    /// [`METADATA_BATCH_INTERVAL`] between requests so bulk imports don't
+    /// hammer the server. Per-commit failures are collected in the report
+    /// rather than aborting the upload. Servers without the batch endpoint
+    /// fall back to one request per commit.
+    pub fn store_metadata_batch(
+        &self,
+        namespace: &str,
+        name: &str,
+        entries: &[MetadataBatchEntry],
+    ) -> Result<MetadataBatchReport> {
+        self.store_metadata_batch_with_progress(namespace, name, entries, |_, _| Ok(()))
+    }
+
+    /// [`store_metadata_batch`](Self::store_metadata_batch), calling
+    /// `on_chunk` with each chunk and its report once the server has it
+    ///
+    /// An error from `on_chunk` stops the upload.
+    pub fn store_metadata_batch_with_progress(
+        &self,
+        namespace: &str,
+        name: &str,
+        entries: &[MetadataBatchEntry],
+        mut on_chunk: impl FnMut(&[MetadataBatchEntry], &MetadataBatchReport) -> Result<()>,
+    ) -> Result<MetadataBatchReport> {
+        let url = self.api_url(&format!("/repos/{}/{}/metadata/batch", namespace, name));
+        let mut report = MetadataBatchReport::default();
+
+        for (i, chunk) in entries.chunks(METADATA_BATCH_SIZE).enumerate() {
+            if i > 0 {
+                std::thread::sleep(METADATA_BATCH_INTERVAL);
+            }
+
+            let body = MetadataBatchRequest { entries: chunk };
+            let chunk_report = match self.post(&url).send_json(&body) {
+                Ok(response) => response
+                    .into_json()
+                    .context("Failed to parse metadata batch response")?,
+                // 207 Multi-Status is returned as success; ureq only errors on 4xx/5xx
+                Err(e) if matches!(e.status(), Some(404) | Some(405)) => {
+                    // Older server without the batch endpoint
+                    self.store_metadata_individually(namespace, name, chunk)
+                }
+                Err(e) => return Err(e.context("Failed to store metadata batch")),
+            };
+            on_chunk(chunk, &chunk_report)?;
+            report.merge(chunk_report);
+        }
+
+        Ok(report)
+    }
+
+    /// Fallback for servers that predate the batch endpoint
+    fn store_metadata_individually(
+        &self,
+        namespace: &str,
+        name: &str,
+        entries: &[MetadataBatchEntry],
+    ) -> MetadataBatchReport {
+        let mut report = MetadataBatchReport::default();
+
+        for entry in entries {
+            match self.store_metadata(namespace, name, &entry.commit_id, &entry.metadata) {
+                Ok(()) => report.stored.push(entry.commit_id.clone()),
+                Err(e) => report.failed.push(MetadataBatchFailure {
+                    commit_id: entry.commit_id.clone(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+
+        report
+    }
 }
 
-/// Get current user identifier
-pub fn get_user_identifier() -> String {
-    let username = std::env::var("USER")
-        .or_else(|_| std::env::var("USERNAME"))
-        .unwrap_or_else(|_| "unknown".to_string());
+/// Encode a bounce upload as `multipart/form-data` (fields `description` and `file`)
+fn multipart_body(
+    boundary: &str,
+    filename: &str,
+    audio: &[u8],
+    description: Option<&str>,
+) -> Vec<u8> {
+    let mut body = Vec::with_capacity(audio.len() + 512);
+
+    if let Some(description) = description {
+        push_text_part(&mut body, boundary, "description", description.as_bytes());
+    }
+    push_file_part(&mut body, boundary, filename, audio);
+    body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
 
-    let hostname = hostname::get()
-        .ok()
-        .and_then(|h| h.into_string().ok())
-        .unwrap_or_else(|| "unknown".to_string());
+    body
+}
+
+/// Append a plain form field to a `multipart/form-data` body
+fn push_text_part(body: &mut Vec<u8>, boundary: &str, name: &str, value: &[u8]) {
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+            boundary, name
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(value);
+    body.extend_from_slice(b"\r\n");
+}
 
-    format!("{}@{}", username, hostname)
+/// Append a `file` field to a `multipart/form-data` body
+fn push_file_part(body: &mut Vec<u8>, boundary: &str, filename: &str, data: &[u8]) {
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n",
+            boundary,
+            filename.replace('"', "")
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\r\n");
+}
+
+/// Get current user identifier
+pub fn get_user_identifier() -> String {
+    crate::identity::Identity::current().user()
 }
 
 /// Get machine identifier
@@ -404,12 +1589,145 @@ mod tests {
     #[test]
     fn test_api_url_generation() {
         let client = AuxinServerClient::with_url("http://localhost:3000").unwrap();
-        assert_eq!(client.api_url("/repos"), "http://localhost:3000/api/repos");
+        client.api_prefix.set("/api/v1".to_string()).unwrap();
+        assert_eq!(client.api_url("/repos"), "http://localhost:3000/api/v1/repos");
 
         let client = AuxinServerClient::with_url("http://localhost:3000/").unwrap();
+        client.api_prefix.set(ApiCompatibility::Legacy.api_prefix()).unwrap();
         assert_eq!(client.api_url("/repos"), "http://localhost:3000/api/repos");
     }
 
+    fn server_info(min: u32, max: u32) -> ServerVersionInfo {
+        ServerVersionInfo {
+            api_version: max,
+            min_api_version: min,
+            server_version: "0.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_negotiate_same_version() {
+        let compat = ApiCompatibility::negotiate(&server_info(1, API_VERSION));
+        assert_eq!(compat, ApiCompatibility::Compatible { version: API_VERSION });
+        assert_eq!(compat.api_prefix(), format!("/api/v{}", API_VERSION));
+        assert!(compat.warning().is_none());
+    }
+
+    #[test]
+    fn test_negotiate_newer_server() {
+        let compat = ApiCompatibility::negotiate(&server_info(1, API_VERSION + 1));
+        assert_eq!(
+            compat,
+            ApiCompatibility::ServerNewer {
+                version: API_VERSION,
+                server_version: API_VERSION + 1
+            }
+        );
+        assert!(compat.warning().is_some());
+    }
+
+    #[test]
+    fn test_negotiate_incompatible_server() {
+        let compat = ApiCompatibility::negotiate(&server_info(API_VERSION + 1, API_VERSION + 2));
+        assert!(matches!(compat, ApiCompatibility::Incompatible { .. }));
+        assert!(compat.warning().unwrap().contains("Upgrade the auxin CLI"));
+    }
+
+    #[test]
+    fn test_lock_holder_fencing_token_optional() {
+        let json = r#"{"lock_id":"l","user":"u","machine_id":"m","acquired_at":"a",
+            "expires_at":"e","last_heartbeat":"h"}"#;
+        let lock: LockHolder = serde_json::from_str(json).unwrap();
+        assert!(lock.fencing_token.is_none());
+
+        let body = LockHeartbeatRequest {
+            lock_id: lock.lock_id,
+            fencing_token: Some(3),
+        };
+        let value = serde_json::to_value(&body).unwrap();
+        assert_eq!(value["fencing_token"], 3);
+    }
+
+    #[test]
+    fn test_requests_carry_request_id() {
+        let client = AuxinServerClient::with_url("http://localhost:3000").unwrap();
+        let first = client.get("http://localhost:3000/api/v1/repos");
+        let second = client.post("http://localhost:3000/api/v1/repos");
+
+        assert_eq!(first.request_id.len(), 12);
+        assert_eq!(
+            first.request.header(REQUEST_ID_HEADER),
+            Some(first.request_id.as_str())
+        );
+        assert_ne!(first.request_id, second.request_id);
+    }
+
+    #[test]
+    fn test_request_error_mentions_request_id() {
+        // Nothing listens on port 9 (discard); the connection is refused
+        let client = AuxinServerClient::with_url("http://127.0.0.1:9").unwrap();
+        let request = client.get("http://127.0.0.1:9/api/v1/repos");
+        let request_id = request.request_id.clone();
+
+        let error = request.call().unwrap_err();
+        assert_eq!(error.request_id, request_id);
+        assert_eq!(error.status(), None);
+        assert_eq!(error.exit_code(), NETWORK_ERROR_EXIT_CODE);
+        assert!(error.to_string().ends_with(&format!("request id {}", request_id)));
+    }
+
+    fn status_error(status: u16, body: &str) -> RequestError {
+        RequestError {
+            action: None,
+            request_id: "abc123".to_string(),
+            status: Some(status),
+            problem: serde_json::from_str(body).ok().map(Box::new),
+            message: format!("http://srv/api/v1/x: status code {}", status),
+        }
+    }
+
+    #[test]
+    fn test_problem_json_error() {
+        let error = status_error(
+            409,
+            r#"{"type":"urn:auxin:problem:lock-held","title":"Conflict","status":409,
+                "detail":"Lock held by alice","code":"LOCK_HELD",
+                "error":"Conflict: Lock held by alice"}"#,
+        );
+
+        assert_eq!(error.code(), Some(ErrorCode::LockHeld));
+        assert_eq!(error.exit_code(), 4);
+        assert_eq!(
+            error.context("Failed to acquire lock").to_string(),
+            "Failed to acquire lock: Lock held by alice (LOCK_HELD), request id abc123"
+        );
+    }
+
+    #[test]
+    fn test_legacy_error_body() {
+        let error = status_error(401, r#"{"error":"Unauthorized: Token expired"}"#);
+
+        assert_eq!(error.code(), None);
+        assert_eq!(error.exit_code(), 1);
+        assert_eq!(
+            error.to_string(),
+            "Unauthorized: Token expired, request id abc123"
+        );
+    }
+
+    #[test]
+    fn test_error_code_exit_codes() {
+        assert_eq!(ErrorCode::parse("TOKEN_EXPIRED").exit_code(), 3);
+        assert_eq!(ErrorCode::parse("QUOTA_EXCEEDED").exit_code(), 7);
+        assert_eq!(ErrorCode::parse("FORBIDDEN").exit_code(), 8);
+        assert_eq!(
+            ErrorCode::parse("SOMETHING_NEW"),
+            ErrorCode::Other("SOMETHING_NEW".to_string())
+        );
+        assert_eq!(ErrorCode::parse("SOMETHING_NEW").exit_code(), 1);
+        assert!(ErrorCode::TokenExpired.hint().is_some());
+    }
+
     #[test]
     fn test_get_user_identifier() {
         let id = get_user_identifier();
@@ -434,4 +1752,97 @@ mod tests {
         assert!(json.contains("john@laptop"));
         assert!(json.contains("timeout_hours"));
     }
+
+    #[test]
+    fn test_metadata_batch_request_serialization() {
+        let entries = vec![MetadataBatchEntry {
+            commit_id: "abc123".to_string(),
+            metadata: LogicProMetadata {
+                bpm: Some(120.0),
+                sample_rate: Some(48000),
+                key_signature: None,
+                tags: Some(vec!["mixing".to_string()]),
+                custom: None,
+            },
+        }];
+
+        let json = serde_json::to_value(MetadataBatchRequest { entries: &entries }).unwrap();
+        assert_eq!(json["entries"][0]["commit_id"], "abc123");
+        assert_eq!(json["entries"][0]["metadata"]["bpm"], 120.0);
+    }
+
+    #[test]
+    fn test_metadata_batch_report_merge() {
+        let mut report = MetadataBatchReport::default();
+        assert!(report.is_complete());
+
+        report.merge(MetadataBatchReport {
+            stored: vec!["a".to_string()],
+            failed: vec![],
+        });
+        report.merge(MetadataBatchReport {
+            stored: vec!["b".to_string()],
+            failed: vec![MetadataBatchFailure {
+                commit_id: "c".to_string(),
+                error: "Bad request".to_string(),
+            }],
+        });
+
+        assert_eq!(report.stored, vec!["a", "b"]);
+        assert_eq!(report.failed.len(), 1);
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn test_metadata_batch_report_deserialization() {
+        let json = r#"{"stored":["a"],"failed":[{"commit_id":"b","error":"Invalid commit ID"}]}"#;
+        let report: MetadataBatchReport = serde_json::from_str(json).unwrap();
+        assert_eq!(report.stored, vec!["a"]);
+        assert_eq!(report.failed[0].commit_id, "b");
+    }
+
+    #[test]
+    fn test_admin_responses_deserialization() {
+        let json = r#"[{"namespace":"studio","name":"track-3","id":"a1",
+            "activity_type":"lock_released","user":"admin",
+            "message":"Lock broken by admin admin","timestamp":"2026-10-16T09:00:00Z",
+            "metadata":{"broken_by":"admin"},"request_id":"r1"}]"#;
+        let events: Vec<AuditEvent> = serde_json::from_str(json).unwrap();
+        assert_eq!(events[0].name, "track-3");
+        assert_eq!(events[0].activity.activity_type, "lock_released");
+
+        let json = r#"{"dry_run":true,"expired_sessions":2,"expired_locks":1,
+            "stale_guards":0,"temp_files":3,"bytes":4096}"#;
+        let report: GcReport = serde_json::from_str(json).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.temp_files, 3);
+    }
+
+    #[test]
+    fn test_lock_stats_deserialization() {
+        let json = r#"{"since":"2026-09-16T09:00:00Z",
+            "holds":{"count":2,"mean_minutes":90.0,"p50_minutes":60.0,"p90_minutes":120.0,
+                "p99_minutes":120.0,"max_minutes":120.0},
+            "contention_events":1,"expired":1,
+            "users":[{"user":"sam","holds":{"count":1,"mean_minutes":60.0,"p50_minutes":60.0,
+                "p90_minutes":60.0,"p99_minutes":60.0,"max_minutes":60.0},
+                "expired":0,"blocked":0,"blocking":1}],
+            "repos":[{"namespace":"studio","name":"track-3","holds":2,
+                "contention_events":1,"mean_hold_minutes":90.0}]}"#;
+        let stats: LockStats = serde_json::from_str(json).unwrap();
+        assert_eq!(stats.holds.p90_minutes, 120.0);
+        assert_eq!(stats.users[0].blocking, 1);
+        assert_eq!(stats.repos[0].name, "track-3");
+    }
+
+    #[test]
+    fn test_multipart_body() {
+        let body = multipart_body("b0undary", "mix.wav", b"RIFF", Some("Final mix"));
+        let text = String::from_utf8(body).unwrap();
+
+        assert!(text.starts_with("--b0undary\r\n"));
+        assert!(text.contains("name=\"description\"\r\n\r\nFinal mix\r\n"));
+        assert!(text.contains("name=\"file\"; filename=\"mix.wav\""));
+        assert!(text.ends_with("\r\n\r\nRIFF\r\n--b0undary--\r\n"));
+    }
 }
@@ -0,0 +1,307 @@
+//! Finding auxin-server on the LAN: `auxin server discover`
+//!
+//! auxin-server advertises itself as `_auxin._tcp` over multicast DNS (see
+//! `[server] advertise`). Discovery sends a one-shot mDNS query for
+//! `_auxin._tcp.local` from an ephemeral port (RFC 6762 §5.1), so
+//! responders reply to us directly, and collects the PTR, SRV, TXT and
+//! address records that arrive before the timeout.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+/// DNS-SD service type advertised by auxin-server
+pub const SERVICE: &str = "_auxin._tcp.local";
+
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+
+/// A server found on the LAN
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredServer {
+    /// Instance name, e.g. "Auxin Server on studio-mac"
+    pub name: String,
+    /// Host name, e.g. `studio-mac.local`
+    pub host: String,
+    pub port: u16,
+    pub addresses: Vec<IpAddr>,
+    /// TXT properties such as `version` and `api`
+    pub properties: BTreeMap<String, String>,
+}
+
+impl DiscoveredServer {
+    /// Server URL; an IPv4 address is preferred since `.local` names
+    /// don't resolve everywhere
+    pub fn url(&self) -> String {
+        let ipv4 = self.addresses.iter().find(|a| a.is_ipv4());
+        match ipv4.or(self.addresses.first()) {
+            Some(IpAddr::V6(ip)) => format!("http://[{}]:{}", ip, self.port),
+            Some(ip) => format!("http://{}:{}", ip, self.port),
+            None => format!("http://{}:{}", self.host, self.port),
+        }
+    }
+}
+
+/// A resource record from a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RecordData {
+    Ptr(String),
+    Srv { port: u16, target: String },
+    Txt(Vec<String>),
+    Address(IpAddr),
+    Other,
+}
+
+/// Browse for servers for `timeout`
+pub fn discover(timeout: Duration) -> Result<Vec<DiscoveredServer>> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .context("Failed to open a socket for discovery")?;
+    socket
+        .send_to(&query_packet(), SocketAddr::from((MDNS_GROUP, MDNS_PORT)))
+        .context("Failed to send the discovery query")?;
+
+    let deadline = Instant::now() + timeout;
+    let mut records = Vec::new();
+    let mut buffer = [0u8; 9000];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        socket.set_read_timeout(Some(remaining))?;
+        match socket.recv_from(&mut buffer) {
+            Ok((len, _)) => records.extend(parse_response(&buffer[..len]).unwrap_or_default()),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                break
+            },
+            Err(e) => return Err(e).context("Failed to receive discovery replies"),
+        }
+    }
+    Ok(assemble(&records))
+}
+
+/// A PTR query for [`SERVICE`] asking for unicast replies
+fn query_packet() -> Vec<u8> {
+    let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in SERVICE.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    packet.extend_from_slice(&TYPE_PTR.to_be_bytes());
+    packet.extend_from_slice(&0x8001u16.to_be_bytes()); // QU, class IN
+    packet
+}
+
+/// Every record in a response; `None` for queries and bad packets
+fn parse_response(packet: &[u8]) -> Option<Vec<(String, RecordData)>> {
+    let header = packet.get(..12)?;
+    if header[2] & 0x80 == 0 {
+        return None;
+    }
+    let count = |at: usize| u16::from_be_bytes([header[at], header[at + 1]]) as usize;
+    let (questions, records) = (count(4), count(6) + count(8) + count(10));
+
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = read_name(packet, pos)?.1 + 4;
+    }
+
+    let mut parsed = Vec::with_capacity(records);
+    for _ in 0..records {
+        let (name, next) = read_name(packet, pos)?;
+        let fields = packet.get(next..next + 10)?;
+        let record_type = u16::from_be_bytes([fields[0], fields[1]]);
+        let len = u16::from_be_bytes([fields[8], fields[9]]) as usize;
+        let start = next + 10;
+        let data = packet.get(start..start + len)?;
+
+        let record = match record_type {
+            TYPE_PTR => RecordData::Ptr(read_name(packet, start)?.0),
+            TYPE_SRV if len > 6 => RecordData::Srv {
+                port: u16::from_be_bytes([data[4], data[5]]),
+                target: read_name(packet, start + 6)?.0,
+            },
+            TYPE_TXT => {
+                let mut entries = Vec::new();
+                let mut at = 0;
+                while let Some(&entry_len) = data.get(at) {
+                    let entry = data.get(at + 1..at + 1 + entry_len as usize)?;
+                    entries.push(String::from_utf8_lossy(entry).to_string());
+                    at += 1 + entry_len as usize;
+                }
+                RecordData::Txt(entries)
+            },
+            TYPE_A if len == 4 => {
+                RecordData::Address(Ipv4Addr::new(data[0], data[1], data[2], data[3]).into())
+            },
+            TYPE_AAAA if len == 16 => {
+                let octets: [u8; 16] = data.try_into().ok()?;
+                RecordData::Address(Ipv6Addr::from(octets).into())
+            },
+            _ => RecordData::Other,
+        };
+        parsed.push((name, record));
+        pos = start + len;
+    }
+    Some(parsed)
+}
+
+/// Combine records from all replies into servers, sorted by name
+fn assemble(records: &[(String, RecordData)]) -> Vec<DiscoveredServer> {
+    let same = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+    let mut servers: Vec<DiscoveredServer> = Vec::new();
+
+    for (name, data) in records {
+        let RecordData::Ptr(instance) = data else {
+            continue;
+        };
+        let label = instance
+            .len()
+            .checked_sub(SERVICE.len() + 1)
+            .and_then(|end| instance.get(..end))
+            .unwrap_or(instance);
+        if !same(name, SERVICE) || servers.iter().any(|s| same(&s.name, label)) {
+            continue;
+        }
+        let Some((port, host)) = records.iter().find_map(|(name, data)| match data {
+            RecordData::Srv { port, target } if same(name, instance) => Some((*port, target)),
+            _ => None,
+        }) else {
+            continue;
+        };
+
+        let mut addresses: Vec<IpAddr> = Vec::new();
+        let mut properties = BTreeMap::new();
+        for (name, data) in records {
+            match data {
+                RecordData::Address(ip) if same(name, host) && !addresses.contains(ip) => {
+                    addresses.push(*ip)
+                },
+                RecordData::Txt(entries) if same(name, instance) => {
+                    for entry in entries {
+                        let (key, value) = entry.split_once('=').unwrap_or((entry, ""));
+                        properties.insert(key.to_string(), value.to_string());
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        servers.push(DiscoveredServer {
+            name: label.to_string(),
+            host: host.clone(),
+            port,
+            addresses,
+            properties,
+        });
+    }
+    servers.sort_by(|a, b| a.name.cmp(&b.name));
+    servers
+}
+
+/// A possibly compressed name at `pos`, and the position after it
+fn read_name(packet: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    for _ in 0..128 {
+        let len = *packet.get(pos)? as usize;
+        if len == 0 {
+            return Some((labels.join("."), end.unwrap_or(pos + 1)));
+        }
+        if len & 0xC0 == 0xC0 {
+            let pointer = ((len & 0x3F) << 8) | *packet.get(pos + 1)? as usize;
+            end.get_or_insert(pos + 2);
+            pos = pointer;
+            continue;
+        }
+        let label = packet.get(pos + 1..pos + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).to_string());
+        pos += 1 + len;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(out: &mut Vec<u8>, name: &str) {
+        for label in name.split('.') {
+            out.push(label.len() as u8);
+            out.extend_from_slice(label.as_bytes());
+        }
+        out.push(0);
+    }
+
+    fn record(out: &mut Vec<u8>, owner: &[u8], record_type: u16, data: &[u8]) {
+        out.extend_from_slice(owner);
+        out.extend_from_slice(&record_type.to_be_bytes());
+        out.extend_from_slice(&[0, 1, 0, 0, 0, 120]);
+        out.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        out.extend_from_slice(data);
+    }
+
+    #[test]
+    fn test_parse_compressed_response() {
+        let mut packet = vec![0, 0, 0x84, 0, 0, 0, 0, 4, 0, 0, 0, 1];
+        // PTR _auxin._tcp.local -> "Mix Room" + pointer to the service name
+        let mut owner = Vec::new();
+        name(&mut owner, SERVICE);
+        let mut ptr = vec![8];
+        ptr.extend_from_slice(b"Mix Room");
+        ptr.extend_from_slice(&[0xC0, 12]);
+        let instance = packet.len() + owner.len() + 10;
+        record(&mut packet, &owner, TYPE_PTR, &ptr);
+
+        let instance_ptr = [0xC0 | (instance >> 8) as u8, instance as u8];
+        let mut srv = vec![0, 0, 0, 0, 0x0B, 0xB8];
+        name(&mut srv, "studio.local");
+        record(&mut packet, &instance_ptr, TYPE_SRV, &srv);
+        record(
+            &mut packet,
+            &instance_ptr,
+            TYPE_TXT,
+            b"\x0dversion=0.2.0\x06api=v1",
+        );
+
+        let mut host = Vec::new();
+        name(&mut host, "studio.local");
+        record(
+            &mut packet,
+            &host,
+            TYPE_AAAA,
+            &"fd00::20".parse::<Ipv6Addr>().unwrap().octets(),
+        );
+        record(&mut packet, &host, TYPE_A, &[192, 168, 1, 20]);
+
+        let records = parse_response(&packet).unwrap();
+        let servers = assemble(&records);
+        assert_eq!(servers.len(), 1);
+        let server = &servers[0];
+        assert_eq!(server.name, "Mix Room");
+        assert_eq!(server.host, "studio.local");
+        assert_eq!(server.port, 3000);
+        assert_eq!(server.properties["version"], "0.2.0");
+        assert_eq!(server.url(), "http://192.168.1.20:3000");
+
+        let v6_only = DiscoveredServer {
+            addresses: vec!["fd00::20".parse().unwrap()],
+            ..server.clone()
+        };
+        assert_eq!(v6_only.url(), "http://[fd00::20]:3000");
+        assert!(parse_response(&query_packet()).is_none());
+    }
+}
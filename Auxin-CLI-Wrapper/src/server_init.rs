@@ -0,0 +1,364 @@
+//! Production config for auxin-server: `auxin server init-config`
+//!
+//! A server started with the defaults signs tokens with the placeholder
+//! secret `dev_secret_change_in_production`. The generator writes a
+//! `[server]` section with a random 64-byte secret, storage paths and
+//! Redis/Postgres URLs that suit the deployment, checks it with
+//! `Server::validate`, and produces the files to deploy it with:
+//!
+//! - `docker`: `docker-compose.yml`, mounting the config at
+//!   `/app/.auxin/config.toml`, with Redis and Postgres services when asked
+//! - `systemd`: `auxin-server.service`, reading the config from
+//!   `/opt/auxin/.auxin/config.toml`
+//! - `fly`: `fly.toml`, with the config delivered as the `AUXIN_CONFIG`
+//!   secret so it never lands in the image
+//!
+//! auxin-server reads `.auxin/config.toml` relative to its working
+//! directory, which is what each preset arranges.
+
+use anyhow::{anyhow, bail, Result};
+use auxin_config::Server;
+use base64::Engine;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Random bytes in a generated auth secret
+pub const SECRET_BYTES: usize = 64;
+
+/// How the server is deployed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    Docker,
+    Systemd,
+    Fly,
+}
+
+impl FromStr for Preset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "docker" => Ok(Self::Docker),
+            "systemd" => Ok(Self::Systemd),
+            "fly" | "fly.io" => Ok(Self::Fly),
+            _ => bail!("Unknown preset '{}' (expected docker, systemd or fly)", s),
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Docker => "docker",
+            Self::Systemd => "systemd",
+            Self::Fly => "fly",
+        })
+    }
+}
+
+/// Choices beyond the preset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitOptions {
+    /// Use Redis for repository locks
+    pub redis: bool,
+    /// Use Postgres for the web UI's projects
+    pub postgres: bool,
+    /// Fly app name, also used for Fly-internal hostnames
+    pub app_name: String,
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            redis: false,
+            postgres: false,
+            app_name: "auxin-server".to_string(),
+        }
+    }
+}
+
+/// A file to deploy with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentFile {
+    pub name: String,
+    pub contents: String,
+}
+
+/// A generated configuration and the files that deploy it
+#[derive(Debug, Clone)]
+pub struct ServerSetup {
+    pub preset: Preset,
+    pub server: Server,
+    /// Password in the Postgres URL, for the database container or role
+    pub database_password: Option<String>,
+    pub files: Vec<DeploymentFile>,
+    /// Commands to run after writing the files
+    pub next_steps: Vec<String>,
+}
+
+fn random_token(bytes: usize) -> Result<String> {
+    let mut buf = vec![0u8; bytes];
+    SystemRandom::new()
+        .fill(&mut buf)
+        .map_err(|_| anyhow!("No secure random number source"))?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(buf))
+}
+
+/// A random secret for signing auth tokens
+pub fn generate_secret() -> Result<String> {
+    random_token(SECRET_BYTES)
+}
+
+/// The `[server]` section for a preset
+fn server_for(preset: Preset, options: &InitOptions, db_password: &str) -> Result<Server> {
+    let (sync_dir, redis_host, postgres_host) = match preset {
+        Preset::Docker => ("/var/oxen/data", "redis".to_string(), "postgres".to_string()),
+        Preset::Systemd => (
+            "/var/lib/auxin/data",
+            "127.0.0.1".to_string(),
+            "127.0.0.1".to_string(),
+        ),
+        Preset::Fly => (
+            "/data",
+            format!("{}-redis.internal", options.app_name),
+            format!("{}-db.flycast", options.app_name),
+        ),
+    };
+
+    Ok(Server {
+        sync_dir: sync_dir.to_string(),
+        // Behind a reverse proxy on a plain host; containers need every interface
+        host: if preset == Preset::Systemd {
+            "127.0.0.1"
+        } else {
+            "0.0.0.0"
+        }
+        .to_string(),
+        auth_token_secret: generate_secret()?,
+        enable_redis_locks: options.redis,
+        enable_web_ui: options.postgres,
+        redis_url: format!("redis://{}:6379", redis_host),
+        database_url: format!(
+            "postgres://auxin:{}@{}:5432/auxin",
+            db_password, postgres_host
+        ),
+        // mDNS doesn't leave a container or a data center
+        advertise: false,
+        ..Server::default()
+    })
+}
+
+/// `config.toml` holding the server section
+pub fn render_config(preset: Preset, server: &Server) -> Result<String> {
+    #[derive(Serialize)]
+    struct ServerSection<'a> {
+        server: &'a Server,
+    }
+
+    Ok(format!(
+        "# auxin-server configuration ({} preset), generated by 'auxin server init-config'\n\
+         # Keep this file private: auth_token_secret signs every login token.\n\n{}",
+        preset,
+        toml::to_string_pretty(&ServerSection { server })?
+    ))
+}
+
+fn docker_compose(server: &Server, options: &InitOptions, db_password: &str) -> String {
+    let mut depends = Vec::new();
+    let mut services = String::new();
+    let mut volumes = String::from("  auxin-data:\n");
+    if options.redis {
+        depends.push("redis");
+        services.push_str(
+            "\n  redis:\n    image: redis:7-alpine\n    volumes:\n      - redis-data:/data\n    \
+             restart: unless-stopped\n",
+        );
+        volumes.push_str("  redis-data:\n");
+    }
+    if options.postgres {
+        depends.push("postgres");
+        services.push_str(&format!(
+            "\n  postgres:\n    image: postgres:16-alpine\n    environment:\n      \
+             - POSTGRES_USER=auxin\n      - POSTGRES_PASSWORD={}\n      - POSTGRES_DB=auxin\n    \
+             volumes:\n      - postgres-data:/var/lib/postgresql/data\n    \
+             restart: unless-stopped\n",
+            db_password
+        ));
+        volumes.push_str("  postgres-data:\n");
+    }
+    let depends_on = if depends.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "    depends_on:\n{}",
+            depends
+                .iter()
+                .map(|d| format!("      - {}\n", d))
+                .collect::<String>()
+        )
+    };
+
+    format!(
+        "services:\n  auxin-server:\n    build: .\n    ports:\n      - \"{port}:{port}\"\n    \
+         volumes:\n      - auxin-data:{sync_dir}\n      \
+         - ./config.toml:/app/.auxin/config.toml:ro\n    environment:\n      \
+         - RUST_LOG=info\n{depends_on}    restart: unless-stopped\n    healthcheck:\n      \
+         test: [\"CMD\", \"curl\", \"-f\", \"http://localhost:{port}/health\"]\n      \
+         interval: 30s\n      timeout: 10s\n      retries: 3\n{services}\nvolumes:\n{volumes}",
+        port = server.port,
+        sync_dir = server.sync_dir,
+    )
+}
+
+fn systemd_unit(server: &Server) -> String {
+    format!(
+        "[Unit]\nDescription=Auxin Server\nAfter=network.target\n\n[Service]\nType=simple\n\
+         User=auxin\n# auxin-server reads .auxin/config.toml from here\n\
+         WorkingDirectory=/opt/auxin\nEnvironment=\"RUST_LOG=info\"\n\
+         ExecStart=/opt/auxin/auxin-server\nRestart=always\nRestartSec=10\n\
+         ReadWritePaths={}\nNoNewPrivileges=true\nProtectSystem=strict\nProtectHome=true\n\
+         PrivateTmp=true\nStandardOutput=journal\nStandardError=journal\n\n\
+         [Install]\nWantedBy=multi-user.target\n",
+        server.sync_dir
+    )
+}
+
+fn fly_toml(server: &Server, options: &InitOptions) -> String {
+    format!(
+        "app = \"{app}\"\n\n[build]\n  dockerfile = \"Dockerfile\"\n\n\
+         # The config arrives as a secret: fly secrets set AUXIN_CONFIG=...\n\
+         [[files]]\n  guest_path = \"/app/.auxin/config.toml\"\n  \
+         secret_name = \"AUXIN_CONFIG\"\n\n\
+         [mounts]\n  source = \"auxin_data\"\n  destination = \"{sync_dir}\"\n\n\
+         [http_service]\n  internal_port = {port}\n  force_https = true\n  \
+         auto_stop_machines = false\n  min_machines_running = 1\n\n\
+         [[http_service.checks]]\n  method = \"GET\"\n  path = \"/health\"\n  \
+         interval = \"30s\"\n  timeout = \"10s\"\n",
+        app = options.app_name,
+        sync_dir = server.sync_dir,
+        port = server.port,
+    )
+}
+
+/// Generate a validated configuration and deployment files for `preset`
+pub fn generate(preset: Preset, options: &InitOptions) -> Result<ServerSetup> {
+    let db_password = random_token(24)?;
+    let server = server_for(preset, options, &db_password)?;
+    let problems = server.validate();
+    if !problems.is_empty() {
+        bail!("Generated configuration is invalid: {}", problems.join("; "));
+    }
+
+    let mut files = vec![DeploymentFile {
+        name: "config.toml".to_string(),
+        contents: render_config(preset, &server)?,
+    }];
+    let next_steps = match preset {
+        Preset::Docker => {
+            files.push(DeploymentFile {
+                name: "docker-compose.yml".to_string(),
+                contents: docker_compose(&server, options, &db_password),
+            });
+            vec!["docker compose up -d".to_string()]
+        },
+        Preset::Systemd => {
+            files.push(DeploymentFile {
+                name: "auxin-server.service".to_string(),
+                contents: systemd_unit(&server),
+            });
+            let mut steps = vec![
+                format!("sudo install -d -o auxin /opt/auxin/.auxin {}", server.sync_dir),
+                "sudo install -m 600 -o auxin config.toml /opt/auxin/.auxin/config.toml"
+                    .to_string(),
+                "sudo cp auxin-server.service /etc/systemd/system/".to_string(),
+                "sudo systemctl daemon-reload && sudo systemctl enable --now auxin-server"
+                    .to_string(),
+            ];
+            if options.postgres {
+                steps.insert(
+                    0,
+                    format!(
+                        "sudo -u postgres psql -c \"CREATE ROLE auxin LOGIN PASSWORD '{}'\" \
+                         -c \"CREATE DATABASE auxin OWNER auxin\"",
+                        db_password
+                    ),
+                );
+            }
+            steps
+        },
+        Preset::Fly => {
+            files.push(DeploymentFile {
+                name: "fly.toml".to_string(),
+                contents: fly_toml(&server, options),
+            });
+            let mut steps = vec![format!("fly volumes create auxin_data -a {}", options.app_name)];
+            if options.postgres {
+                steps.push(format!(
+                    "fly postgres create --name {}-db  # then create role auxin with the \
+                     password in config.toml",
+                    options.app_name
+                ));
+            }
+            if options.redis {
+                steps.push(format!(
+                    "fly apps create {0}-redis  # run redis:7-alpine as {0}-redis",
+                    options.app_name
+                ));
+            }
+            steps.push("fly secrets set AUXIN_CONFIG=\"$(base64 < config.toml)\"".to_string());
+            steps.push("fly deploy".to_string());
+            steps
+        },
+    };
+
+    Ok(ServerSetup {
+        preset,
+        server,
+        database_password: options.postgres.then_some(db_password),
+        files,
+        next_steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_presets() {
+        assert_eq!("Fly".parse::<Preset>().unwrap(), Preset::Fly);
+        assert!("k8s".parse::<Preset>().is_err());
+
+        let options = InitOptions {
+            redis: true,
+            postgres: true,
+            ..InitOptions::default()
+        };
+        let docker = generate(Preset::Docker, &options).unwrap();
+        assert!(docker.server.validate().is_empty());
+        assert!(docker.server.auth_token_secret.len() >= 80);
+        assert_eq!(docker.server.redis_url, "redis://redis:6379");
+        let names: Vec<_> = docker.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["config.toml", "docker-compose.yml"]);
+
+        // The rendered config reads back as the same server section
+        let config: auxin_config::Config = toml::from_str(&docker.files[0].contents).unwrap();
+        assert_eq!(config.server.auth_token_secret, docker.server.auth_token_secret);
+        assert!(docker.files[1].contents.contains("POSTGRES_PASSWORD="));
+
+        // Every generation gets its own secret
+        let again = generate(Preset::Docker, &options).unwrap();
+        assert_ne!(again.server.auth_token_secret, docker.server.auth_token_secret);
+
+        let systemd = generate(Preset::Systemd, &InitOptions::default()).unwrap();
+        assert_eq!(systemd.server.host, "127.0.0.1");
+        assert!(!systemd.server.enable_redis_locks);
+        assert!(systemd.database_password.is_none());
+
+        let fly = generate(Preset::Fly, &InitOptions::default()).unwrap();
+        assert!(fly.files[1].contents.contains("secret_name = \"AUXIN_CONFIG\""));
+        assert_eq!(fly.server.sync_dir, "/data");
+    }
+}
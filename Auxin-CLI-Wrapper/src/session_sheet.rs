@@ -0,0 +1,390 @@
+//! Session sheets: one-page HTML summaries of milestone commits
+//!
+//! A session sheet shows a commit's message, metadata, changed files,
+//! bounce waveform, project thumbnail and comments. It is a single
+//! self-contained HTML file (images are inlined), so Quick Look previews it
+//! in Finder and collaborators can review a version without running auxin.
+//!
+//! Sheets are written to `Session Sheets/` in the project folder, next to
+//! the `.logicx` package for Logic Pro projects because Finder doesn't
+//! browse inside packages. With `[integrations.session_sheets]` enabled,
+//! `auxin commit` renders one for every commit tagged `milestone`.
+
+use crate::collaboration::{Comment, CommentManager};
+use crate::versioning::VersionLog;
+use crate::{BounceManager, BounceMetadata, CommitMetadata, ThumbnailManager};
+use anyhow::{Context, Result};
+use auxin_oxen::CommitInfo;
+use base64::Engine;
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Folder session sheets are written to
+pub const SHEETS_DIR_NAME: &str = "Session Sheets";
+
+/// Number of bars in the waveform
+const WAVEFORM_BARS: usize = 200;
+
+/// Whether a commit's tags mark it as a milestone
+pub fn is_milestone(metadata: &CommitMetadata) -> bool {
+    metadata.tags.iter().any(|tag| tag.eq_ignore_ascii_case("milestone"))
+}
+
+/// Where sheets for a project are written
+pub fn sheets_dir(project: &Path) -> PathBuf {
+    let base = match project.parent() {
+        Some(parent) if project.extension().is_some_and(|ext| ext == "logicx") => parent,
+        _ => project,
+    };
+    base.join(SHEETS_DIR_NAME)
+}
+
+/// Everything shown on a session sheet
+#[derive(Debug, Clone)]
+pub struct SessionSheet {
+    pub project_name: String,
+    pub commit_id: String,
+    pub metadata: CommitMetadata,
+    /// Version label from `auxin version`, e.g. "v1.2"
+    pub version: Option<String>,
+    /// Files in the commit; empty when not known
+    pub changed_files: Vec<String>,
+    pub comments: Vec<Comment>,
+    pub bounce: Option<BounceMetadata>,
+    /// Peak level per waveform bar, 0.0 to 1.0
+    pub waveform: Option<Vec<f32>>,
+    /// Project screenshot as (MIME type, bytes)
+    pub thumbnail: Option<(String, Vec<u8>)>,
+    pub rendered_at: DateTime<Local>,
+}
+
+impl SessionSheet {
+    /// Gather a commit's comments, bounce and thumbnail from the project
+    ///
+    /// The waveform needs `ffmpeg`; without it the sheet is rendered without
+    /// one.
+    pub fn collect(project: &Path, commit: &CommitInfo, changed_files: Vec<String>) -> Self {
+        let bounces = BounceManager::new(project);
+        let bounce = bounces.get_bounce(&commit.id).ok().flatten();
+        let waveform = bounces
+            .get_bounce_path(&commit.id)
+            .ok()
+            .flatten()
+            .and_then(|path| waveform_peaks(&path, WAVEFORM_BARS));
+
+        let thumbnail = ThumbnailManager::new(project)
+            .get_thumbnail_path(&commit.id)
+            .ok()
+            .flatten()
+            .and_then(|path| {
+                let mime = match path.extension()?.to_str()? {
+                    "png" => "image/png",
+                    "jpg" | "jpeg" => "image/jpeg",
+                    _ => return None,
+                };
+                Some((mime.to_string(), fs::read(&path).ok()?))
+            });
+
+        Self {
+            project_name: project
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            commit_id: commit.id.clone(),
+            metadata: CommitMetadata::parse_commit_message(&commit.message),
+            version: VersionLog::load(project)
+                .ok()
+                .and_then(|log| log.for_commit(&commit.id).map(|v| v.label.clone())),
+            changed_files,
+            comments: CommentManager::new()
+                .get_comments(project, &commit.id)
+                .unwrap_or_default(),
+            bounce,
+            waveform,
+            thumbnail,
+            rendered_at: Local::now(),
+        }
+    }
+
+    /// File name for the sheet: date, short commit id and message
+    pub fn file_name(&self) -> String {
+        let short = &self.commit_id[..self.commit_id.len().min(8)];
+        let subject: String = self
+            .metadata
+            .message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+            .take(40)
+            .collect();
+        let subject = subject.trim();
+
+        let mut name = format!("{} {}", self.rendered_at.format("%Y-%m-%d"), short);
+        if !subject.is_empty() {
+            name.push(' ');
+            name.push_str(subject);
+        }
+        name + ".html"
+    }
+
+    /// Write the sheet to the project's `Session Sheets/` folder
+    pub fn write(&self, project: &Path) -> Result<PathBuf> {
+        let dir = sheets_dir(project);
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(self.file_name());
+        fs::write(&path, self.render_html())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn render_html(&self) -> String {
+        let metadata = &self.metadata;
+        let mut facts = Vec::new();
+        if let Some(version) = &self.version {
+            facts.push(("Version", version.clone()));
+        }
+        if let Some(bpm) = metadata.bpm {
+            facts.push(("BPM", bpm.to_string()));
+        }
+        if let Some(key) = &metadata.key_signature {
+            facts.push(("Key", key.clone()));
+        }
+        if let Some(sample_rate) = metadata.sample_rate {
+            facts.push(("Sample Rate", format!("{} Hz", sample_rate)));
+        }
+        if !metadata.tags.is_empty() {
+            facts.push(("Tags", metadata.tags.join(", ")));
+        }
+        facts.push(("Commit", self.commit_id.clone()));
+
+        let mut html = String::new();
+        html.push_str(&format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{} - {}</title>\n<style>{}</style>\n</head>\n<body>\n",
+            escape_html(&self.project_name),
+            escape_html(metadata.message.lines().next().unwrap_or_default()),
+            STYLE
+        ));
+
+        html.push_str(&format!(
+            "<header><p class=\"project\">{}</p><h1>{}</h1></header>\n",
+            escape_html(&self.project_name),
+            escape_html(metadata.message.trim()).replace('\n', "<br>")
+        ));
+
+        html.push_str("<table class=\"facts\">\n");
+        for (label, value) in facts {
+            html.push_str(&format!(
+                "<tr><th>{}</th><td>{}</td></tr>\n",
+                label,
+                escape_html(&value)
+            ));
+        }
+        html.push_str("</table>\n");
+
+        if let Some((mime, bytes)) = &self.thumbnail {
+            html.push_str(&format!(
+                "<section><h2>Project</h2><img class=\"thumbnail\" src=\"data:{};base64,{}\" \
+                 alt=\"Project screenshot\"></section>\n",
+                mime,
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            ));
+        }
+
+        if self.bounce.is_some() || self.waveform.is_some() {
+            html.push_str("<section><h2>Bounce</h2>\n");
+            if let Some(bounce) = &self.bounce {
+                html.push_str(&format!(
+                    "<p>{} &middot; {} &middot; {}</p>\n",
+                    escape_html(&bounce.original_filename),
+                    bounce.format_duration(),
+                    bounce.format_size()
+                ));
+            }
+            if let Some(peaks) = &self.waveform {
+                html.push_str(&waveform_svg(peaks));
+            }
+            html.push_str("</section>\n");
+        }
+
+        if !self.changed_files.is_empty() {
+            html.push_str(&format!(
+                "<section><h2>Changed Files ({})</h2><ul class=\"files\">\n",
+                self.changed_files.len()
+            ));
+            for file in &self.changed_files {
+                html.push_str(&format!("<li>{}</li>\n", escape_html(file)));
+            }
+            html.push_str("</ul></section>\n");
+        }
+
+        if !self.comments.is_empty() {
+            html.push_str("<section><h2>Comments</h2>\n");
+            for comment in &self.comments {
+                html.push_str(&format!(
+                    "<div class=\"comment\"><p class=\"meta\">{} &middot; {}</p><p>{}</p></div>\n",
+                    escape_html(&comment.author),
+                    comment.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                    escape_html(&comment.text)
+                ));
+            }
+            html.push_str("</section>\n");
+        }
+
+        html.push_str(&format!(
+            "<footer>Rendered by auxin on {}</footer>\n</body>\n</html>\n",
+            self.rendered_at.format("%Y-%m-%d %H:%M")
+        ));
+        html
+    }
+}
+
+/// Peak level per bar, decoded to mono with ffmpeg
+fn waveform_peaks(path: &Path, bars: usize) -> Option<Vec<f32>> {
+    let output = Command::new("ffmpeg")
+        .arg("-i")
+        .arg(path)
+        .args(["-ac", "1", "-ar", "8000", "-f", "f32le", "-"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let samples: Vec<f32> = output
+        .stdout
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]).abs())
+        .collect();
+    peaks(&samples, bars)
+}
+
+/// Largest absolute sample in each of `bars` equal slices, scaled to the loudest
+fn peaks(samples: &[f32], bars: usize) -> Option<Vec<f32>> {
+    if samples.is_empty() || bars == 0 {
+        return None;
+    }
+
+    let chunk = samples.len().div_ceil(bars);
+    let peaks: Vec<f32> = samples
+        .chunks(chunk)
+        .map(|c| c.iter().copied().fold(0.0, f32::max))
+        .collect();
+    let loudest = peaks.iter().copied().fold(0.0, f32::max);
+    if loudest <= 0.0 {
+        return Some(vec![0.0; peaks.len()]);
+    }
+    Some(peaks.into_iter().map(|p| p / loudest).collect())
+}
+
+fn waveform_svg(peaks: &[f32]) -> String {
+    let height = 80.0;
+    let mut svg = format!(
+        "<svg class=\"waveform\" viewBox=\"0 0 {} {}\" preserveAspectRatio=\"none\">",
+        peaks.len() * 2,
+        height
+    );
+    for (i, peak) in peaks.iter().enumerate() {
+        let bar = (peak * height).max(1.0);
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{:.1}\" width=\"1.4\" height=\"{:.1}\"/>",
+            i * 2,
+            (height - bar) / 2.0,
+            bar
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "\
+body{font:14px -apple-system,Helvetica,sans-serif;max-width:720px;margin:32px auto;\
+padding:0 24px;color:#222}\
+header .project{color:#888;margin:0}h1{font-size:22px;margin:4px 0 20px}\
+h2{font-size:13px;text-transform:uppercase;letter-spacing:.05em;color:#888;margin-top:28px}\
+.facts th{text-align:left;color:#888;font-weight:normal;padding:2px 16px 2px 0}\
+.thumbnail{max-width:100%;border-radius:6px;border:1px solid #ddd}\
+.waveform{width:100%;height:80px;fill:#4a7bd1}\
+.files{font-family:Menlo,monospace;font-size:12px;padding-left:18px}\
+.comment{border-left:3px solid #ddd;padding-left:12px;margin:12px 0}\
+.comment .meta{color:#888;font-size:12px;margin:0}\
+footer{margin-top:40px;color:#aaa;font-size:11px}";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn sheet() -> SessionSheet {
+        let message = CommitMetadata::new("Final <vocal> comp")
+            .with_bpm(140.0)
+            .with_key_signature("A Minor")
+            .with_tag("milestone")
+            .format_commit_message();
+        SessionSheet {
+            project_name: "MySong.logicx".to_string(),
+            commit_id: "0123456789abcdef".to_string(),
+            metadata: CommitMetadata::parse_commit_message(&message),
+            version: Some("v1.2".to_string()),
+            changed_files: vec!["Alternatives/000/ProjectData".to_string()],
+            comments: vec![Comment {
+                id: "1".to_string(),
+                commit_id: "0123456789abcdef".to_string(),
+                author: "alice".to_string(),
+                text: "Love the harmonies".to_string(),
+                created_at: Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap(),
+            }],
+            bounce: None,
+            waveform: Some(vec![0.0, 0.5, 1.0]),
+            thumbnail: Some(("image/png".to_string(), b"png".to_vec())),
+            rendered_at: Local.with_ymd_and_hms(2026, 10, 16, 14, 30, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_render_html() {
+        let sheet = sheet();
+        assert!(is_milestone(&sheet.metadata));
+
+        let html = sheet.render_html();
+        assert!(html.contains("<h1>Final &lt;vocal&gt; comp</h1>"));
+        assert!(html.contains("<tr><th>Version</th><td>v1.2</td></tr>"));
+        assert!(html.contains("<tr><th>BPM</th><td>140</td></tr>"));
+        assert!(html.contains("<tr><th>Key</th><td>A Minor</td></tr>"));
+        assert!(html.contains("<li>Alternatives/000/ProjectData</li>"));
+        assert!(html.contains("Love the harmonies"));
+        assert!(html.contains("src=\"data:image/png;base64,cG5n\""));
+        assert_eq!(html.matches("<rect ").count(), 3);
+
+        assert_eq!(sheet.file_name(), "2026-10-16 01234567 Final vocal comp.html");
+    }
+
+    #[test]
+    fn test_sheets_dir_and_peaks() {
+        assert_eq!(
+            sheets_dir(Path::new("/music/Song/Song.logicx")),
+            Path::new("/music/Song/Session Sheets")
+        );
+        assert_eq!(
+            sheets_dir(Path::new("/models/House")),
+            Path::new("/models/House/Session Sheets")
+        );
+
+        assert_eq!(peaks(&[0.1, 0.2, 0.4, 0.1], 2), Some(vec![0.5, 1.0]));
+        assert_eq!(peaks(&[0.0; 4], 2), Some(vec![0.0, 0.0]));
+        assert_eq!(peaks(&[], 2), None);
+    }
+}
@@ -1,4 +1,7 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Structured metadata for SketchUp project commits.
 ///
@@ -114,11 +117,13 @@ impl SketchUpMetadata {
 
     /// Sets the model units.
     ///
-    /// Builder pattern method that consumes and returns self.
+    /// Builder pattern method that consumes and returns self. Recognized
+    /// units are stored by their canonical [`ModelUnits`] name; anything else
+    /// is stored as given.
     ///
     /// # Arguments
     ///
-    /// * `units` - Model units (e.g., "Inches", "Feet", "Meters", "Millimeters")
+    /// * `units` - Model units (e.g., "Inches", "ft", "Meters", "mm")
     ///
     /// # Examples
     ///
@@ -126,14 +131,25 @@ impl SketchUpMetadata {
     /// use auxin::SketchUpMetadata;
     ///
     /// let commit = SketchUpMetadata::new("Architectural model")
-    ///     .with_units("Inches");
+    ///     .with_units("in");
     /// assert_eq!(commit.units, Some("Inches".to_string()));
     /// ```
     pub fn with_units(mut self, units: impl Into<String>) -> Self {
-        self.units = Some(units.into());
+        let units = units.into();
+        self.units = Some(
+            units
+                .parse::<ModelUnits>()
+                .map(|u| u.to_string())
+                .unwrap_or(units),
+        );
         self
     }
 
+    /// The model units as a [`ModelUnits`], if they are recognized
+    pub fn model_units(&self) -> Option<ModelUnits> {
+        self.units.as_deref().and_then(|u| u.parse().ok())
+    }
+
     /// Sets the layer count.
     ///
     /// Builder pattern method that consumes and returns self.
@@ -440,6 +456,188 @@ impl SketchUpMetadata {
     }
 }
 
+/// Length units a SketchUp model can use
+///
+/// Parses the names SketchUp and users write ("Inches", "in", "\"",
+/// "Metres", "mm") and converts lengths between units.
+///
+/// # Examples
+///
+/// ```
+/// use auxin::sketchup_metadata::{ModelUnits, UnitSystem};
+///
+/// let units: ModelUnits = "ft".parse().unwrap();
+/// assert_eq!(units, ModelUnits::Feet);
+/// assert_eq!(units.convert(10.0, ModelUnits::Inches), 120.0);
+/// assert_eq!(units.counterpart(UnitSystem::Metric), ModelUnits::Meters);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModelUnits {
+    Inches,
+    Feet,
+    Yards,
+    Millimeters,
+    Centimeters,
+    Meters,
+}
+
+/// Metric or imperial, for showing units the way the user thinks in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+impl ModelUnits {
+    pub const ALL: [Self; 6] = [
+        Self::Inches,
+        Self::Feet,
+        Self::Yards,
+        Self::Millimeters,
+        Self::Centimeters,
+        Self::Meters,
+    ];
+
+    /// Canonical name, as stored in commit messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Inches => "Inches",
+            Self::Feet => "Feet",
+            Self::Yards => "Yards",
+            Self::Millimeters => "Millimeters",
+            Self::Centimeters => "Centimeters",
+            Self::Meters => "Meters",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Inches => "in",
+            Self::Feet => "ft",
+            Self::Yards => "yd",
+            Self::Millimeters => "mm",
+            Self::Centimeters => "cm",
+            Self::Meters => "m",
+        }
+    }
+
+    /// Length of one unit in meters
+    pub fn meters(&self) -> f64 {
+        match self {
+            Self::Inches => 0.0254,
+            Self::Feet => 0.3048,
+            Self::Yards => 0.9144,
+            Self::Millimeters => 0.001,
+            Self::Centimeters => 0.01,
+            Self::Meters => 1.0,
+        }
+    }
+
+    pub fn system(&self) -> UnitSystem {
+        match self {
+            Self::Inches | Self::Feet | Self::Yards => UnitSystem::Imperial,
+            Self::Millimeters | Self::Centimeters | Self::Meters => UnitSystem::Metric,
+        }
+    }
+
+    /// Convert a length in these units to `to`
+    pub fn convert(&self, value: f64, to: ModelUnits) -> f64 {
+        value * self.meters() / to.meters()
+    }
+
+    /// The closest unit in `system`: itself if already in that system,
+    /// otherwise the unit used at a similar scale (inches ↔ millimeters,
+    /// feet ↔ meters)
+    pub fn counterpart(&self, system: UnitSystem) -> ModelUnits {
+        if self.system() == system {
+            return *self;
+        }
+        match self {
+            Self::Inches => Self::Millimeters,
+            Self::Feet | Self::Yards => Self::Meters,
+            Self::Millimeters | Self::Centimeters => Self::Inches,
+            Self::Meters => Self::Feet,
+        }
+    }
+
+    /// One unit expressed in `to`, e.g. `1 in = 25.4 mm`
+    pub fn conversion(&self, to: ModelUnits) -> String {
+        format!(
+            "1 {} = {} {}",
+            self.symbol(),
+            format_length(self.convert(1.0, to)),
+            to.symbol()
+        )
+    }
+
+    /// Name plus the size of one unit in `system`, e.g.
+    /// `Inches (1 in = 25.4 mm)`; just the name when no system is preferred
+    /// or the units already belong to it
+    pub fn display_in(&self, system: Option<UnitSystem>) -> String {
+        match system.map(|s| self.counterpart(s)) {
+            Some(other) if other != *self => {
+                format!("{} ({})", self.name(), self.conversion(other))
+            },
+            _ => self.name().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ModelUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ModelUnits {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let units = match s.trim().to_lowercase().replace("metre", "meter").as_str() {
+            "inches" | "inch" | "in" | "\"" => Self::Inches,
+            "feet" | "foot" | "ft" | "'" => Self::Feet,
+            "yards" | "yard" | "yd" => Self::Yards,
+            "millimeters" | "millimeter" | "mm" => Self::Millimeters,
+            "centimeters" | "centimeter" | "cm" => Self::Centimeters,
+            "meters" | "meter" | "m" => Self::Meters,
+            _ => {
+                return Err(anyhow!(
+                    "Unknown units '{}' (expected Inches, Feet, Yards, Millimeters, \
+                     Centimeters or Meters)",
+                    s.trim()
+                ))
+            },
+        };
+        Ok(units)
+    }
+}
+
+impl UnitSystem {
+    /// Read the `[ui] unit_system` setting; `"model"` (or anything
+    /// unrecognized) means no preference
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "metric" => Some(Self::Metric),
+            "imperial" => Some(Self::Imperial),
+            _ => None,
+        }
+    }
+}
+
+/// Parse units and return their canonical name, for `--units` arguments
+pub fn parse_units(s: &str) -> Result<String> {
+    Ok(s.parse::<ModelUnits>()?.to_string())
+}
+
+/// Up to four decimals, without trailing zeros
+fn format_length(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -560,4 +758,50 @@ mod tests {
         assert!(json.contains("\"message\":\"Test\""));
         assert!(json.contains("\"units\":\"Meters\""));
     }
+
+    #[test]
+    fn test_model_units_parsing() {
+        for (input, expected) in [
+            ("Inches", ModelUnits::Inches),
+            ("in", ModelUnits::Inches),
+            ("\"", ModelUnits::Inches),
+            ("FEET", ModelUnits::Feet),
+            ("Metres", ModelUnits::Meters),
+            ("mm", ModelUnits::Millimeters),
+            (" centimetre ", ModelUnits::Centimeters),
+            ("yd", ModelUnits::Yards),
+        ] {
+            assert_eq!(input.parse::<ModelUnits>().unwrap(), expected, "{}", input);
+        }
+        assert!("furlongs".parse::<ModelUnits>().is_err());
+        assert_eq!(parse_units("ft").unwrap(), "Feet");
+
+        let metadata = SketchUpMetadata::new("Model").with_units("metres");
+        assert_eq!(metadata.units, Some("Meters".to_string()));
+        assert_eq!(metadata.model_units(), Some(ModelUnits::Meters));
+        let custom = SketchUpMetadata::new("Model").with_units("Cubits");
+        assert_eq!(custom.units, Some("Cubits".to_string()));
+        assert_eq!(custom.model_units(), None);
+    }
+
+    #[test]
+    fn test_model_units_conversion() {
+        assert!((ModelUnits::Inches.convert(1.0, ModelUnits::Millimeters) - 25.4).abs() < 1e-9);
+        assert!((ModelUnits::Meters.convert(1.0, ModelUnits::Centimeters) - 100.0).abs() < 1e-9);
+        assert!((ModelUnits::Yards.convert(2.0, ModelUnits::Feet) - 6.0).abs() < 1e-9);
+
+        assert_eq!(
+            ModelUnits::Inches.display_in(Some(UnitSystem::Metric)),
+            "Inches (1 in = 25.4 mm)"
+        );
+        assert_eq!(
+            ModelUnits::Meters.display_in(Some(UnitSystem::Imperial)),
+            "Meters (1 m = 3.2808 ft)"
+        );
+        assert_eq!(ModelUnits::Feet.display_in(Some(UnitSystem::Imperial)), "Feet");
+        assert_eq!(ModelUnits::Feet.display_in(None), "Feet");
+
+        assert_eq!(UnitSystem::from_config("Metric"), Some(UnitSystem::Metric));
+        assert_eq!(UnitSystem::from_config("model"), None);
+    }
 }
@@ -0,0 +1,221 @@
+//! Reading model details from a `.skp` file
+//!
+//! The format is proprietary. A model starts with a UTF-16 "SketchUp Model"
+//! signature and the `{major.minor.build}` version that saved it, followed
+//! by an MFC archive in which every object is preceded by a tag naming its
+//! class: in full (`0xFFFF`, schema, name) the first time the class appears,
+//! and as `0x8000 | index` after that. Layers, components and groups are
+//! counted from those tags, so the counts are estimates; values given on
+//! `auxin commit` take precedence.
+
+use crate::project_handler::newest_file_with_extension;
+use crate::vlog;
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// UTF-16 signature at the start of every model
+const SIGNATURE: &str = "SketchUp Model";
+
+/// Archive tag introducing a class by name
+const NEW_CLASS_TAG: u16 = 0xFFFF;
+
+/// Archive tag bit marking a reference to a class seen before
+const CLASS_TAG: u16 = 0x8000;
+
+/// What's read from a `.skp`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SketchUpModelInfo {
+    /// Version of SketchUp that saved the model, e.g. "21.0.339"
+    pub version: Option<String>,
+    pub layer_count: u32,
+    pub component_count: u32,
+    pub group_count: u32,
+    pub file_size_bytes: u64,
+}
+
+/// Reads SketchUp models
+pub struct SketchUpParser;
+
+impl SketchUpParser {
+    /// Read the model at `path`
+    pub fn parse(path: &Path) -> Result<SketchUpModelInfo> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let info = Self::parse_bytes(&bytes)
+            .with_context(|| format!("Failed to read SketchUp model {}", path.display()))?;
+        vlog!(
+            "SketchUp model {}: version {:?}, {} layer(s), {} component(s), {} group(s)",
+            path.display(),
+            info.version,
+            info.layer_count,
+            info.component_count,
+            info.group_count
+        );
+        Ok(info)
+    }
+
+    /// Read a model already in memory
+    pub fn parse_bytes(bytes: &[u8]) -> Result<SketchUpModelInfo> {
+        let (signature, mut pos) =
+            read_cstring(bytes, 0).ok_or_else(|| anyhow!("Not a SketchUp model"))?;
+        if signature != SIGNATURE {
+            bail!("Not a SketchUp model");
+        }
+
+        let mut info = SketchUpModelInfo {
+            file_size_bytes: bytes.len() as u64,
+            ..Default::default()
+        };
+        if let Some((version, end)) = read_cstring(bytes, pos) {
+            if let Some(version) = version.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+                info.version = Some(version.to_string());
+                pos = end;
+            }
+        }
+
+        let counts = count_objects(&bytes[pos..]);
+        let count = |class: &str| counts.get(class).copied().unwrap_or(0);
+        info.layer_count = count("CLayer");
+        info.component_count = count("CComponentInstance");
+        info.group_count = count("CGroup");
+        Ok(info)
+    }
+
+    /// The model in `dir` a commit is about: the only `.skp` there, or the
+    /// most recently saved if there are several
+    pub fn find_model(dir: &Path) -> Result<PathBuf> {
+        newest_file_with_extension(dir, "skp")?
+            .ok_or_else(|| anyhow!("No SketchUp model (.skp) found in {}", dir.display()))
+    }
+}
+
+/// An MFC `CString` in its Unicode form: `FF FE FF`, a length byte and that
+/// many UTF-16 code units. Returns the string and the position after it.
+fn read_cstring(bytes: &[u8], pos: usize) -> Option<(String, usize)> {
+    let header = bytes.get(pos..pos + 4)?;
+    if header[..3] != [0xFF, 0xFE, 0xFF] {
+        return None;
+    }
+    let len = header[3] as usize;
+    let start = pos + 4;
+    let units: Vec<u16> = bytes
+        .get(start..start + len * 2)?
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    Some((String::from_utf16(&units).ok()?, start + len * 2))
+}
+
+/// Objects in the archive, by class name
+///
+/// Class and object entries share one index, starting at 1, so a class's
+/// index is the count of entries before it. Tags are looked for at every
+/// byte; a `0x8000` tag only counts if its index is a class already seen.
+fn count_objects(archive: &[u8]) -> HashMap<String, u32> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    let mut classes: HashMap<u16, String> = HashMap::new();
+    let mut next_index: u32 = 1;
+    let mut pos = 0;
+
+    while pos + 2 <= archive.len() {
+        let tag = u16::from_le_bytes([archive[pos], archive[pos + 1]]);
+        if tag == NEW_CLASS_TAG {
+            if let Some((name, end)) = read_class_name(archive, pos + 2) {
+                if let Ok(index) = u16::try_from(next_index) {
+                    classes.insert(index, name.clone());
+                }
+                *counts.entry(name).or_default() += 1;
+                // The class, then the object that follows it
+                next_index += 2;
+                pos = end;
+                continue;
+            }
+        } else if tag & CLASS_TAG != 0 {
+            if let Some(name) = classes.get(&(tag & !CLASS_TAG)) {
+                *counts.entry(name.clone()).or_default() += 1;
+                next_index += 1;
+                pos += 2;
+                continue;
+            }
+        }
+        pos += 1;
+    }
+    counts
+}
+
+/// The schema number, length and ASCII name after a new-class tag, if they
+/// look like one of SketchUp's `C...` classes
+fn read_class_name(archive: &[u8], pos: usize) -> Option<(String, usize)> {
+    let len = u16::from_le_bytes([*archive.get(pos + 2)?, *archive.get(pos + 3)?]) as usize;
+    let start = pos + 4;
+    let name = archive.get(start..start + len)?;
+    let valid = (2..=64).contains(&len)
+        && name[0] == b'C'
+        && name.iter().all(|b| b.is_ascii_alphanumeric() || *b == b'_');
+    if !valid {
+        return None;
+    }
+    Some((String::from_utf8_lossy(name).into_owned(), start + len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn cstring(s: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE, 0xFF, s.encode_utf16().count() as u8];
+        bytes.extend(s.encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+        bytes
+    }
+
+    fn new_class(name: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFF, 0x01, 0x00];
+        bytes.extend((name.len() as u16).to_le_bytes());
+        bytes.extend(name.as_bytes());
+        bytes
+    }
+
+    fn model() -> Vec<u8> {
+        let mut bytes = cstring("SketchUp Model");
+        bytes.extend(cstring("{21.0.339}"));
+        bytes.extend(new_class("CLayer")); // entries 1 and 2
+        bytes.extend(b"\x00\x00Layer0");
+        bytes.extend(0x8001u16.to_le_bytes()); // entry 3
+        bytes.extend(new_class("CComponentInstance")); // entries 4 and 5
+        bytes.extend(0x8004u16.to_le_bytes()); // entry 6
+        bytes.extend(new_class("CGroup")); // entries 7 and 8
+        bytes.extend(0x8004u16.to_le_bytes());
+        // Not a class: entry 2 is the first layer
+        bytes.extend(0x8002u16.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_parse_model() {
+        let bytes = model();
+        let info = SketchUpParser::parse_bytes(&bytes).unwrap();
+        assert_eq!(info.version.as_deref(), Some("21.0.339"));
+        assert_eq!(info.layer_count, 2);
+        assert_eq!(info.component_count, 3);
+        assert_eq!(info.group_count, 1);
+        assert_eq!(info.file_size_bytes, bytes.len() as u64);
+
+        assert!(SketchUpParser::parse_bytes(b"SketchUp test data").is_err());
+        assert!(SketchUpParser::parse_bytes(&cstring("Layout Document")).is_err());
+    }
+
+    #[test]
+    fn test_find_model() {
+        let dir = TempDir::new().unwrap();
+        assert!(SketchUpParser::find_model(dir.path()).is_err());
+
+        std::fs::write(dir.path().join("House.skb"), b"backup").unwrap();
+        std::fs::write(dir.path().join("House.skp"), model()).unwrap();
+        let found = SketchUpParser::find_model(dir.path()).unwrap();
+        assert_eq!(found.file_name().unwrap(), "House.skp");
+        assert_eq!(SketchUpParser::parse(&found).unwrap().layer_count, 2);
+    }
+}
@@ -0,0 +1,324 @@
+//! Spotlight sidecars for commit history
+//!
+//! When `[integrations.spotlight]` is enabled, every commit gets a small
+//! `.auxinmeta` text file holding its message and metadata. Auxin.app
+//! declares the extension as plain text, so Spotlight's built-in text
+//! importer indexes the files and a search for "A minor 140 BPM" finds the
+//! matching commits.
+//!
+//! Sidecars live outside the project (Spotlight skips hidden folders such as
+//! `.auxin/`), one folder per project:
+//!
+//! ```text
+//! ~/Library/Caches/Metadata/Auxin/
+//! └── MySong.logicx-1a2b3c4d/
+//!     ├── project.path           # absolute project path, used by gc
+//!     ├── 8f3e2a1b....auxinmeta
+//!     └── ...
+//! ```
+
+use crate::CommitMetadata;
+use anyhow::{Context, Result};
+use auxin_oxen::CommitInfo;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sidecar file extension
+pub const EXTENSION: &str = "auxinmeta";
+
+/// File in each project folder recording which project it belongs to
+const PROJECT_FILE: &str = "project.path";
+
+/// Files written and removed by a sync or gc
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SpotlightStats {
+    pub written: usize,
+    pub removed: usize,
+}
+
+/// Sidecar folder for one project
+pub struct SpotlightIndex {
+    project: PathBuf,
+    dir: PathBuf,
+}
+
+impl SpotlightIndex {
+    /// Where sidecars are written: `~/Library/Caches/Metadata/Auxin` on macOS,
+    /// where Spotlight looks for app metadata, and the user cache dir elsewhere
+    pub fn default_root() -> PathBuf {
+        if cfg!(target_os = "macos") {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("Library/Caches/Metadata/Auxin")
+        } else {
+            dirs::cache_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("auxin/metadata")
+        }
+    }
+
+    /// Index for a project under the default root
+    pub fn for_project(project: &Path) -> Self {
+        Self::new(&Self::default_root(), project)
+    }
+
+    /// Index for a project under `root`
+    ///
+    /// The folder name includes a hash of the project path so two projects
+    /// with the same name don't share sidecars.
+    pub fn new(root: &Path, project: &Path) -> Self {
+        let project = project.canonicalize().unwrap_or_else(|_| project.to_path_buf());
+        let name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string());
+        let hash = format!("{:x}", md5::compute(project.to_string_lossy().as_bytes()));
+        let dir = root.join(format!("{}-{}", name, &hash[..8]));
+        Self { project, dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write the sidecar for one commit
+    pub fn write(&self, commit: &CommitInfo) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let project_file = self.dir.join(PROJECT_FILE);
+        if !project_file.exists() {
+            fs::write(&project_file, self.project.to_string_lossy().as_bytes())?;
+        }
+
+        let path = self.sidecar_path(&commit.id);
+        fs::write(&path, self.render(commit))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Write sidecars for `commits` that don't have one yet and remove those
+    /// for commits no longer in the list
+    pub fn sync(&self, commits: &[CommitInfo]) -> Result<SpotlightStats> {
+        let mut stats = SpotlightStats::default();
+
+        for commit in commits {
+            if !self.sidecar_path(&commit.id).exists() {
+                self.write(commit)?;
+                stats.written += 1;
+            }
+        }
+
+        stats.removed = self.retain(commits)?;
+        Ok(stats)
+    }
+
+    /// Remove sidecars for commits not in `commits`
+    pub fn retain(&self, commits: &[CommitInfo]) -> Result<usize> {
+        let keep: HashSet<&str> = commits.iter().map(|c| c.id.as_str()).collect();
+        self.prune(|id| keep.contains(id))
+    }
+
+    /// Remove every sidecar for this project
+    pub fn clear(&self) -> Result<usize> {
+        let removed = self.prune(|_| false)?;
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir)?;
+        }
+        Ok(removed)
+    }
+
+    fn prune(&self, keep: impl Fn(&str) -> bool) -> Result<usize> {
+        let mut removed = 0;
+        for (id, path) in sidecars(&self.dir) {
+            if !keep(&id) {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn sidecar_path(&self, commit_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", commit_id, EXTENSION))
+    }
+
+    fn render(&self, commit: &CommitInfo) -> String {
+        let metadata = CommitMetadata::parse_commit_message(&commit.message);
+        let name = self
+            .project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut lines = vec![
+            metadata.message.trim().to_string(),
+            String::new(),
+            format!("Project: {}", name),
+            format!("Path: {}", self.project.display()),
+            format!("Commit: {}", commit.id),
+        ];
+        if let Some(bpm) = metadata.bpm {
+            lines.push(format!("BPM: {}", bpm));
+        }
+        if let Some(key) = &metadata.key_signature {
+            lines.push(format!("Key: {}", key));
+        }
+        if let Some(sample_rate) = metadata.sample_rate {
+            lines.push(format!("Sample Rate: {} Hz", sample_rate));
+        }
+        if !metadata.tags.is_empty() {
+            lines.push(format!("Tags: {}", metadata.tags.join(", ")));
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Remove sidecars for projects that no longer exist and for commits that
+/// are no longer in their project's history
+///
+/// `history` returns a project's commits; when it fails the project is
+/// treated as gone.
+pub fn gc<F>(root: &Path, history: F) -> Result<SpotlightStats>
+where
+    F: Fn(&Path) -> Result<Vec<CommitInfo>>,
+{
+    let mut stats = SpotlightStats::default();
+    let Ok(entries) = fs::read_dir(root) else {
+        return Ok(stats);
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let project = fs::read_to_string(dir.join(PROJECT_FILE))
+            .ok()
+            .map(|p| PathBuf::from(p.trim()));
+        let commits = project
+            .as_deref()
+            .filter(|p| p.exists())
+            .and_then(|p| history(p).ok());
+
+        match commits {
+            Some(commits) => {
+                let keep: HashSet<String> = commits.into_iter().map(|c| c.id).collect();
+                for (id, path) in sidecars(&dir) {
+                    if !keep.contains(&id) {
+                        fs::remove_file(&path)?;
+                        stats.removed += 1;
+                    }
+                }
+            }
+            None => {
+                stats.removed += sidecars(&dir).len();
+                fs::remove_dir_all(&dir)
+                    .with_context(|| format!("Failed to remove {}", dir.display()))?;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// (commit id, path) for every sidecar in `dir`
+fn sidecars(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == EXTENSION))
+        .filter_map(|p| Some((p.file_stem()?.to_string_lossy().to_string(), p)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+    use tempfile::TempDir;
+
+    fn commit(id: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_renders_metadata() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("MySong.logicx");
+        fs::create_dir(&project).unwrap();
+        let index = SpotlightIndex::new(&temp.path().join("index"), &project);
+
+        let message = CommitMetadata::new("Vocal comp")
+            .with_bpm(140.0)
+            .with_key_signature("A Minor")
+            .with_tag("vocals")
+            .format_commit_message();
+        let path = index.write(&commit("abc123", &message)).unwrap();
+
+        assert_eq!(path.file_name().unwrap(), "abc123.auxinmeta");
+        assert!(index.dir().file_name().unwrap().to_string_lossy().starts_with("MySong.logicx-"));
+        let text = fs::read_to_string(path).unwrap();
+        assert!(text.starts_with("Vocal comp\n"));
+        assert!(text.contains("BPM: 140\n"));
+        assert!(text.contains("Key: A Minor\n"));
+        assert!(text.contains("Tags: vocals\n"));
+    }
+
+    #[test]
+    fn test_sync_and_clear() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Song");
+        fs::create_dir(&project).unwrap();
+        let index = SpotlightIndex::new(&temp.path().join("index"), &project);
+
+        let stats = index.sync(&[commit("a", "First"), commit("b", "Second")]).unwrap();
+        assert_eq!(stats, SpotlightStats { written: 2, removed: 0 });
+
+        // "b" was reset away, "c" is new
+        let stats = index.sync(&[commit("a", "First"), commit("c", "Third")]).unwrap();
+        assert_eq!(stats, SpotlightStats { written: 1, removed: 1 });
+
+        assert_eq!(index.clear().unwrap(), 2);
+        assert!(!index.dir().exists());
+    }
+
+    #[test]
+    fn test_gc_removes_missing_projects_and_commits() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("index");
+        let kept = temp.path().join("Kept");
+        let deleted = temp.path().join("Deleted");
+        fs::create_dir(&kept).unwrap();
+        fs::create_dir(&deleted).unwrap();
+
+        let kept_index = SpotlightIndex::new(&root, &kept);
+        kept_index.write(&commit("a", "Keep me")).unwrap();
+        kept_index.write(&commit("b", "Gone from history")).unwrap();
+        let deleted_index = SpotlightIndex::new(&root, &deleted);
+        deleted_index.write(&commit("c", "Project deleted")).unwrap();
+        fs::remove_dir(&deleted).unwrap();
+
+        let kept_path = kept.canonicalize().unwrap();
+        let stats = gc(&root, |project| {
+            if project != kept_path {
+                bail!("unexpected project {}", project.display());
+            }
+            Ok(vec![commit("a", "Keep me")])
+        })
+        .unwrap();
+
+        assert_eq!(stats.removed, 2);
+        assert_eq!(sidecars(kept_index.dir()).len(), 1);
+        assert!(!deleted_index.dir().exists());
+    }
+}
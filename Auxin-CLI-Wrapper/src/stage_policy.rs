@@ -0,0 +1,278 @@
+//! Auto-stage policy per project type
+//!
+//! `.oxenignore` decides what can be versioned at all; the staging policy
+//! decides what `auxin add --all` picks up, per kind of project:
+//!
+//! ```toml
+//! [staging.logic]
+//! always = ["Alternatives/", "**/ProjectData"]
+//! never = ["Freeze Files/", "Bounces/"]
+//!
+//! [staging.default]
+//! never = ["*.bak"]
+//! ```
+//!
+//! Patterns use `.oxenignore` syntax. `never` holds changed files back from
+//! staging (they can still be added by path); `always` stages matching files
+//! even when `.oxenignore` excludes them. `[staging.default]` applies to
+//! every project, on top of the section for its type. The policy is applied
+//! by `add --all`, `quick-commit` and the daemon's auto-commits, which stage
+//! through `add --all`.
+
+use crate::describe::IgnoreRules;
+use crate::logic_parser::LogicParser;
+use crate::staging_preview::{self, StagingPreview};
+use anyhow::{Context, Result};
+use auxin_config::{Config, StagingRules};
+use auxin_oxen::OxenSubprocess;
+use std::fs;
+use std::path::Path;
+
+/// Section that applies to every project type
+pub const DEFAULT_SECTION: &str = "default";
+
+/// Project type, as named in `[staging.<type>]`
+pub fn project_type(project: &Path) -> &'static str {
+    let has_extension = |ext: &str| {
+        fs::read_dir(project).is_ok_and(|entries| {
+            entries
+                .flatten()
+                .any(|e| e.path().extension().is_some_and(|e| e.eq_ignore_ascii_case(ext)))
+        })
+    };
+
+    if project.extension().is_some_and(|ext| ext == "logicx")
+        || LogicParser::is_valid_project(project)
+    {
+        "logic"
+    } else if has_extension("skp") {
+        "sketchup"
+    } else if has_extension("blend") {
+        "blender"
+    } else {
+        "other"
+    }
+}
+
+/// A pattern and the config section it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyRule {
+    pub pattern: String,
+    pub section: String,
+}
+
+/// The rules in effect for one project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagePolicy {
+    pub project_type: &'static str,
+    pub always: Vec<PolicyRule>,
+    pub never: Vec<PolicyRule>,
+}
+
+impl StagePolicy {
+    /// Combine `[staging.default]` with the section for the project's type
+    pub fn for_project(project: &Path, config: &Config) -> Self {
+        Self::from_sections(project_type(project), &config.staging)
+    }
+
+    pub fn from_sections(
+        project_type: &'static str,
+        sections: &std::collections::BTreeMap<String, StagingRules>,
+    ) -> Self {
+        let mut policy = Self {
+            project_type,
+            always: Vec::new(),
+            never: Vec::new(),
+        };
+        for section in [DEFAULT_SECTION, project_type] {
+            let Some(rules) = sections.get(section) else {
+                continue;
+            };
+            let tag = |patterns: &[String]| -> Vec<PolicyRule> {
+                patterns
+                    .iter()
+                    .map(|pattern| PolicyRule {
+                        pattern: pattern.clone(),
+                        section: format!("staging.{}", section),
+                    })
+                    .collect()
+            };
+            policy.always.extend(tag(&rules.always));
+            policy.never.extend(tag(&rules.never));
+        }
+        policy
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.always.is_empty() && self.never.is_empty()
+    }
+
+    /// Rule matching `relative`, using `.oxenignore` semantics
+    fn matching<'a>(rules: &'a [PolicyRule], relative: &str) -> Option<&'a PolicyRule> {
+        let patterns: Vec<&str> = rules.iter().map(|r| r.pattern.as_str()).collect();
+        let rule = IgnoreRules::parse(&patterns.join("\n"))
+            .ignored_by(relative, false)
+            .map(|rule| rule.line)?;
+        rules.get(rule - 1)
+    }
+}
+
+/// A file the policy decided about, and the rule that decided
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDecision {
+    pub path: String,
+    pub rule: PolicyRule,
+}
+
+/// What `add --all` stages under a policy
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StagePlan {
+    pub stage: Vec<String>,
+    /// Changed files a `never` rule holds back
+    pub held_back: Vec<PolicyDecision>,
+    /// Files an `always` rule stages despite `.oxenignore`
+    pub forced: Vec<PolicyDecision>,
+}
+
+/// Apply a policy to a staging preview of the whole project
+pub fn plan(project: &Path, preview: &StagingPreview, policy: &StagePolicy) -> StagePlan {
+    let mut plan = StagePlan::default();
+
+    for path in &preview.to_stage {
+        match StagePolicy::matching(&policy.never, path) {
+            Some(rule) => plan.held_back.push(PolicyDecision {
+                path: path.clone(),
+                rule: rule.clone(),
+            }),
+            None => plan.stage.push(path.clone()),
+        }
+    }
+
+    if !policy.always.is_empty() {
+        for entry in &preview.ignored {
+            let files = if entry.is_dir {
+                files_under(project, &entry.path)
+            } else {
+                vec![entry.path.clone()]
+            };
+            for path in files {
+                if let Some(rule) = StagePolicy::matching(&policy.always, &path) {
+                    plan.forced.push(PolicyDecision {
+                        path: path.clone(),
+                        rule: rule.clone(),
+                    });
+                    plan.stage.push(path);
+                }
+            }
+        }
+    }
+
+    plan.stage.sort();
+    plan
+}
+
+/// Files inside a folder of the project, relative to the project
+fn files_under(project: &Path, folder: &str) -> Vec<String> {
+    fn walk(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => walk(&entry.path(), &name, files),
+                Ok(t) if t.is_file() => files.push(name),
+                _ => {},
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(&project.join(folder), folder, &mut files);
+    files.sort();
+    files
+}
+
+/// Stage the project's changes the way `add --all` does: everything when
+/// there's no policy, otherwise what the policy's plan says
+///
+/// Returns the plan, or `None` when there's no policy.
+pub fn stage_all(project: &Path, config: &Config) -> Result<Option<StagePlan>> {
+    let oxen = OxenSubprocess::new();
+    let policy = StagePolicy::for_project(project, config);
+    if policy.is_empty() {
+        oxen.add_all(project)?;
+        return Ok(None);
+    }
+
+    let status = oxen.status(project)?;
+    let preview =
+        staging_preview::preview(project, &[], &status, &IgnoreRules::load(project)?)?;
+    let plan = plan(project, &preview, &policy);
+    if !plan.stage.is_empty() {
+        let paths: Vec<&Path> = plan.stage.iter().map(Path::new).collect();
+        oxen.add(project, &paths).context("Failed to stage changes")?;
+    }
+    Ok(Some(plan))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staging_preview::IgnoredEntry;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_policy_plan() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = temp.path();
+        fs::create_dir_all(project.join("Alternatives/000")).unwrap();
+        fs::write(project.join("Alternatives/000/ProjectData"), b"x").unwrap();
+        fs::write(project.join("Alternatives/000/DisplayState.plist"), b"x").unwrap();
+
+        let sections: BTreeMap<String, StagingRules> = toml::from_str(
+            r#"
+            [default]
+            never = ["*.bak"]
+
+            [logic]
+            always = ["**/ProjectData"]
+            never = ["Freeze Files/"]
+
+            [sketchup]
+            never = ["*.skb"]
+            "#,
+        )
+        .unwrap();
+        let policy = StagePolicy::from_sections("logic", &sections);
+        assert_eq!(policy.never.len(), 2);
+        assert_eq!(policy.never[0].section, "staging.default");
+        assert_eq!(policy.always[0].section, "staging.logic");
+
+        let preview = StagingPreview {
+            to_stage: vec![
+                "Freeze Files/Track 1.aif".to_string(),
+                "Media/Kick.wav".to_string(),
+                "mix.bak".to_string(),
+            ],
+            ignored: vec![IgnoredEntry {
+                path: "Alternatives".to_string(),
+                is_dir: true,
+                file_count: 2,
+                pattern: "Alternatives/".to_string(),
+                line: 3,
+            }],
+        };
+        let plan = plan(project, &preview, &policy);
+        assert_eq!(plan.stage, ["Alternatives/000/ProjectData", "Media/Kick.wav"]);
+        assert_eq!(plan.held_back.len(), 2);
+        assert_eq!(plan.held_back[0].rule.pattern, "Freeze Files/");
+        assert_eq!(plan.held_back[1].rule.section, "staging.default");
+        assert_eq!(plan.forced[0].rule.pattern, "**/ProjectData");
+
+        assert!(StagePolicy::from_sections("blender", &sections)
+            .always
+            .is_empty());
+        assert_eq!(project_type(project), "logic");
+    }
+}
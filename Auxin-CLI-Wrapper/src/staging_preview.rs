@@ -0,0 +1,200 @@
+//! `auxin add --dry-run`: what would be staged, and what's ignored and why
+//!
+//! Samples or texture folders that silently never get versioned are almost
+//! always an `.oxenignore` rule reaching further than intended. The preview
+//! walks the same paths `auxin add` would, lists the changed files that
+//! would be staged, and pairs every excluded file or folder with the rule
+//! that excludes it (pattern and line number), without staging anything.
+
+use crate::describe::{relative_path, IgnoreRules};
+use anyhow::Result;
+use auxin_oxen::StatusInfo;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A file or folder left out by an ignore rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredEntry {
+    /// Path relative to the repo, `/`-separated
+    pub path: String,
+    pub is_dir: bool,
+    /// Files inside an ignored folder; 1 for a file
+    pub file_count: usize,
+    /// The rule, as written in `.oxenignore`
+    pub pattern: String,
+    /// 1-based line in `.oxenignore`
+    pub line: usize,
+}
+
+/// Result of a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StagingPreview {
+    /// Changed files that would be staged
+    pub to_stage: Vec<String>,
+    pub ignored: Vec<IgnoredEntry>,
+}
+
+impl StagingPreview {
+    /// Files left out across all ignored entries
+    pub fn ignored_file_count(&self) -> usize {
+        self.ignored.iter().map(|entry| entry.file_count).sum()
+    }
+}
+
+/// Preview staging `paths` (everything when empty) in `repo`
+///
+/// `status` says which files have changes; files it doesn't mention are
+/// already committed as they are and wouldn't be staged.
+pub fn preview(
+    repo: &Path,
+    paths: &[PathBuf],
+    status: &StatusInfo,
+    rules: &IgnoreRules,
+) -> Result<StagingPreview> {
+    let changed: BTreeSet<String> = status
+        .modified
+        .iter()
+        .chain(&status.untracked)
+        .chain(&status.staged)
+        .map(|path| relative_path(repo, &repo.join(path)))
+        .collect();
+
+    let roots: Vec<PathBuf> = if paths.is_empty() {
+        vec![repo.to_path_buf()]
+    } else {
+        paths.iter().map(|path| repo.join(path)).collect()
+    };
+
+    let mut preview = StagingPreview::default();
+    for root in roots {
+        visit(repo, &root, rules, &changed, &mut preview)?;
+    }
+    preview.to_stage.sort();
+    preview.to_stage.dedup();
+    preview.ignored.sort_by(|a, b| a.path.cmp(&b.path));
+    preview.ignored.dedup();
+    Ok(preview)
+}
+
+fn visit(
+    repo: &Path,
+    path: &Path,
+    rules: &IgnoreRules,
+    changed: &BTreeSet<String>,
+    preview: &mut StagingPreview,
+) -> Result<()> {
+    let relative = relative_path(repo, path);
+    if relative == ".oxen" || relative.starts_with(".oxen/") {
+        return Ok(());
+    }
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    let is_dir = metadata.is_dir();
+
+    if !relative.is_empty() {
+        if let Some(rule) = rules.ignored_by(&relative, is_dir) {
+            preview.ignored.push(IgnoredEntry {
+                path: relative,
+                is_dir,
+                file_count: if is_dir { count_files(path) } else { 1 },
+                pattern: rule.pattern.clone(),
+                line: rule.line,
+            });
+            return Ok(());
+        }
+    }
+
+    if is_dir {
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)?.flatten().map(|e| e.path()).collect();
+        entries.sort();
+        for entry in entries {
+            visit(repo, &entry, rules, changed, preview)?;
+        }
+    } else if is_changed(&relative, changed) {
+        preview.to_stage.push(relative);
+    }
+    Ok(())
+}
+
+/// Whether status lists the file, or a folder it's in (new folders are
+/// listed as a whole)
+fn is_changed(relative: &str, changed: &BTreeSet<String>) -> bool {
+    let mut prefix = relative;
+    loop {
+        if changed.contains(prefix) {
+            return true;
+        }
+        match prefix.rfind('/') {
+            Some(end) => prefix = &prefix[..end],
+            None => return false,
+        }
+    }
+}
+
+fn count_files(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| match entry.file_type() {
+                    Ok(t) if t.is_dir() => count_files(&entry.path()),
+                    Ok(t) if t.is_file() => 1,
+                    _ => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_preview() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        for file in [
+            ".oxen/config.toml",
+            "Alternatives/000/ProjectData",
+            "Freeze Files/Track 1.aif",
+            "Freeze Files/Track 2.aif",
+            "Samples/Kick.wav",
+            "Samples/Snare.wav",
+            "mix.tmp",
+        ] {
+            let path = repo.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, b"x").unwrap();
+        }
+        let rules = IgnoreRules::parse("# Generated\nFreeze Files/\n*.tmp\n");
+        let status = StatusInfo {
+            modified: vec![PathBuf::from("Alternatives/000/ProjectData")],
+            untracked: vec![PathBuf::from("Samples"), PathBuf::from("mix.tmp")],
+            staged: Vec::new(),
+        };
+
+        let all = preview(repo, &[], &status, &rules).unwrap();
+        assert_eq!(
+            all.to_stage,
+            [
+                "Alternatives/000/ProjectData",
+                "Samples/Kick.wav",
+                "Samples/Snare.wav"
+            ]
+        );
+        assert_eq!(all.ignored.len(), 2);
+        assert_eq!(all.ignored[0].path, "Freeze Files");
+        assert_eq!(all.ignored[0].file_count, 2);
+        assert_eq!(all.ignored[0].line, 2);
+        assert_eq!(all.ignored[1].pattern, "*.tmp");
+        assert_eq!(all.ignored_file_count(), 3);
+
+        let samples = preview(repo, &[PathBuf::from("Samples")], &status, &rules).unwrap();
+        assert_eq!(samples.to_stage.len(), 2);
+        assert!(samples.ignored.is_empty());
+    }
+}
@@ -0,0 +1,414 @@
+//! Versioned on-disk state and the migrations between versions
+//!
+//! auxin keeps state in a handful of stores: the offline queue, the
+//! write-ahead log and recovery snapshots under `~/.auxin`, and bounce
+//! manifests and metadata edits under a project's `.auxin`. Each store's
+//! format version is recorded in `state_versions.json` next to it; stores
+//! from before versions were recorded count as version 0.
+//!
+//! A format change adds a [`Migration`] to [`MIGRATIONS`] for the store,
+//! from its current version to the next. Migrations run in order, after the
+//! stores they rewrite have been copied to `state-backups/<time>/`, and
+//! the new versions are recorded once all of them succeed. auxin applies
+//! pending migrations when it starts; `auxin migrate-state --dry-run` lists
+//! them without changing anything.
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File recording each store's format version
+pub const VERSIONS_FILE: &str = "state_versions.json";
+
+/// Folder backups are kept in, next to the stores
+pub const BACKUPS_DIR: &str = "state-backups";
+
+/// A piece of on-disk state with a format of its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Store {
+    /// `~/.auxin/queue/`
+    Queue,
+    /// `~/.auxin/wal.json`
+    Wal,
+    /// `~/.auxin/snapshots/`
+    Snapshots,
+    /// `<project>/.auxin/bounces/`
+    Bounces,
+    /// `<project>/.auxin/metadata_edits.json`
+    MetadataEdits,
+}
+
+impl Store {
+    /// Stores under `~/.auxin`
+    pub const USER: [Store; 3] = [Store::Queue, Store::Wal, Store::Snapshots];
+
+    /// Stores under a project's `.auxin`
+    pub const PROJECT: [Store; 2] = [Store::Bounces, Store::MetadataEdits];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Store::Queue => "queue",
+            Store::Wal => "wal",
+            Store::Snapshots => "snapshots",
+            Store::Bounces => "bounces",
+            Store::MetadataEdits => "metadata_edits",
+        }
+    }
+
+    /// Where the store lives inside its `.auxin` folder
+    pub fn relative_path(&self) -> &'static str {
+        match self {
+            Store::Queue => "queue",
+            Store::Wal => "wal.json",
+            Store::Snapshots => "snapshots",
+            Store::Bounces => "bounces",
+            Store::MetadataEdits => "metadata_edits.json",
+        }
+    }
+
+    /// The version this build writes
+    pub fn current_version(&self) -> u32 {
+        current_version(*self, MIGRATIONS)
+    }
+}
+
+/// One step in a store's format history
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub store: Store,
+    /// Version the step starts from; it leaves the store at `from + 1`
+    pub from: u32,
+    pub description: &'static str,
+    /// Rewrites the store at the given path; `None` when the data is
+    /// already in the new format and only the version is recorded
+    pub apply: Option<fn(&Path) -> Result<()>>,
+}
+
+/// Every migration, oldest first within each store
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        store: Store::Queue,
+        from: 0,
+        description: "Record the queue format version",
+        apply: None,
+    },
+    Migration {
+        store: Store::Wal,
+        from: 0,
+        description: "Record the write-ahead log format version",
+        apply: None,
+    },
+    Migration {
+        store: Store::Snapshots,
+        from: 0,
+        description: "Record the snapshot format version",
+        apply: None,
+    },
+    Migration {
+        store: Store::Bounces,
+        from: 0,
+        description: "Record the bounce manifest format version",
+        apply: None,
+    },
+    Migration {
+        store: Store::MetadataEdits,
+        from: 0,
+        description: "Record the metadata edits format version",
+        apply: None,
+    },
+];
+
+fn current_version(store: Store, migrations: &[Migration]) -> u32 {
+    migrations
+        .iter()
+        .filter(|migration| migration.store == store)
+        .map(|migration| migration.from + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// A migration that will run
+#[derive(Debug, Clone)]
+pub struct PlannedMigration {
+    pub store: Store,
+    pub from: u32,
+    pub to: u32,
+    pub description: &'static str,
+    /// Whether the store's files are rewritten, and so backed up first
+    pub rewrites: bool,
+    apply: Option<fn(&Path) -> Result<()>>,
+}
+
+/// What `migrate` would do to one `.auxin` folder
+#[derive(Debug, Clone, Default)]
+pub struct MigrationPlan {
+    pub steps: Vec<PlannedMigration>,
+    /// Stores written by a newer auxin, with their versions
+    pub newer: Vec<(Store, u32)>,
+    /// Versions to record once the steps have run
+    versions: BTreeMap<String, u32>,
+    /// Whether the recorded versions change
+    record: bool,
+}
+
+impl MigrationPlan {
+    /// Whether there's nothing to do
+    pub fn is_current(&self) -> bool {
+        self.steps.is_empty() && !self.record
+    }
+
+    /// Whether any step rewrites data
+    pub fn rewrites(&self) -> bool {
+        self.steps.iter().any(|step| step.rewrites)
+    }
+}
+
+/// An `.auxin` folder holding versioned stores
+#[derive(Debug, Clone)]
+pub struct StateDir {
+    pub dir: PathBuf,
+    pub stores: &'static [Store],
+}
+
+impl StateDir {
+    /// `~/.auxin`
+    pub fn user() -> Result<Self> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        Ok(Self {
+            dir: home.join(".auxin"),
+            stores: &Store::USER,
+        })
+    }
+
+    /// The project's `.auxin`
+    pub fn project(repo_root: &Path) -> Self {
+        Self {
+            dir: repo_root.join(".auxin"),
+            stores: &Store::PROJECT,
+        }
+    }
+
+    /// Recorded version of each store, by name
+    pub fn versions(&self) -> Result<BTreeMap<String, u32>> {
+        let path = self.dir.join(VERSIONS_FILE);
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// What `migrate` would do
+    pub fn plan(&self) -> Result<MigrationPlan> {
+        self.plan_with(MIGRATIONS)
+    }
+
+    fn plan_with(&self, migrations: &[Migration]) -> Result<MigrationPlan> {
+        let recorded = self.versions()?;
+        let mut plan = MigrationPlan {
+            versions: recorded.clone(),
+            ..Default::default()
+        };
+
+        for &store in self.stores {
+            let current = current_version(store, migrations);
+            let version = recorded.get(store.name()).copied().unwrap_or(0);
+            if version > current {
+                plan.newer.push((store, version));
+                continue;
+            }
+            // A store that doesn't exist yet is created in the current format
+            if self.dir.join(store.relative_path()).exists() {
+                let mut steps: Vec<&Migration> = migrations
+                    .iter()
+                    .filter(|migration| migration.store == store && migration.from >= version)
+                    .collect();
+                steps.sort_by_key(|migration| migration.from);
+                plan.steps
+                    .extend(steps.into_iter().map(|migration| PlannedMigration {
+                        store,
+                        from: migration.from,
+                        to: migration.from + 1,
+                        description: migration.description,
+                        rewrites: migration.apply.is_some(),
+                        apply: migration.apply,
+                    }));
+            }
+            if recorded.get(store.name()) != Some(&current) {
+                plan.versions.insert(store.name().to_string(), current);
+                plan.record = true;
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Run `plan`, backing up the stores it rewrites first; returns the
+    /// backup folder, if one was made
+    ///
+    /// Versions are only recorded once every step has succeeded, so a
+    /// failed migration is retried from the start, on the backed-up data.
+    pub fn migrate(&self, plan: &MigrationPlan) -> Result<Option<PathBuf>> {
+        if let Some((store, version)) = plan.newer.first() {
+            bail!(
+                "{} was written by a newer auxin (format {}, this auxin knows up to {}); \
+                 upgrade auxin",
+                self.dir.join(store.relative_path()).display(),
+                version,
+                store.current_version()
+            );
+        }
+        if plan.is_current() {
+            return Ok(None);
+        }
+
+        let backup = if plan.rewrites() {
+            Some(self.backup(plan)?)
+        } else {
+            None
+        };
+
+        for step in &plan.steps {
+            if let Some(apply) = step.apply {
+                apply(&self.dir.join(step.store.relative_path())).with_context(|| {
+                    format!(
+                        "Migrating {} from format {} to {} failed",
+                        step.store.name(),
+                        step.from,
+                        step.to
+                    )
+                })?;
+            }
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(VERSIONS_FILE);
+        fs::write(&path, serde_json::to_string_pretty(&plan.versions)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(backup)
+    }
+
+    /// Copy every store the plan rewrites to `state-backups/<time>/`
+    fn backup(&self, plan: &MigrationPlan) -> Result<PathBuf> {
+        let backup = self
+            .dir
+            .join(BACKUPS_DIR)
+            .join(Utc::now().format("%Y%m%d-%H%M%S").to_string());
+        let mut stores: Vec<Store> = plan
+            .steps
+            .iter()
+            .filter(|step| step.rewrites)
+            .map(|step| step.store)
+            .collect();
+        stores.sort();
+        stores.dedup();
+
+        for store in stores {
+            let from = self.dir.join(store.relative_path());
+            let to = backup.join(store.relative_path());
+            copy_recursive(&from, &to)
+                .with_context(|| format!("Failed to back up {}", from.display()))?;
+        }
+        Ok(backup)
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn uppercase_wal(path: &Path) -> Result<()> {
+        let content = fs::read_to_string(path)?;
+        fs::write(path, content.to_uppercase())?;
+        Ok(())
+    }
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            store: Store::Wal,
+            from: 0,
+            description: "Record the version",
+            apply: None,
+        },
+        Migration {
+            store: Store::Wal,
+            from: 1,
+            description: "Uppercase everything",
+            apply: Some(uppercase_wal),
+        },
+        Migration {
+            store: Store::Queue,
+            from: 0,
+            description: "Record the version",
+            apply: None,
+        },
+    ];
+
+    #[test]
+    fn test_migrate_backs_up_and_records_versions() {
+        let temp = TempDir::new().unwrap();
+        let state = StateDir {
+            dir: temp.path().to_path_buf(),
+            stores: &Store::USER,
+        };
+        fs::write(temp.path().join("wal.json"), "[\"commit\"]").unwrap();
+
+        let plan = state.plan_with(TEST_MIGRATIONS).unwrap();
+        let steps: Vec<(Store, u32, u32)> = plan
+            .steps
+            .iter()
+            .map(|step| (step.store, step.from, step.to))
+            .collect();
+        // No queue folder yet, so only its version is recorded
+        assert_eq!(steps, vec![(Store::Wal, 0, 1), (Store::Wal, 1, 2)]);
+        assert!(plan.rewrites());
+
+        let backup = state.migrate(&plan).unwrap().unwrap();
+        assert_eq!(
+            fs::read_to_string(backup.join("wal.json")).unwrap(),
+            "[\"commit\"]"
+        );
+        assert_eq!(
+            fs::read_to_string(temp.path().join("wal.json")).unwrap(),
+            "[\"COMMIT\"]"
+        );
+
+        let versions = state.versions().unwrap();
+        assert_eq!(versions.get("wal"), Some(&2));
+        assert_eq!(versions.get("queue"), Some(&1));
+        assert_eq!(versions.get("snapshots"), Some(&0));
+        assert!(state.plan_with(TEST_MIGRATIONS).unwrap().is_current());
+    }
+
+    #[test]
+    fn test_newer_store_is_refused() {
+        let temp = TempDir::new().unwrap();
+        let state = StateDir::project(temp.path());
+        fs::create_dir_all(&state.dir).unwrap();
+        fs::write(state.dir.join(VERSIONS_FILE), r#"{"bounces": 7}"#).unwrap();
+
+        let plan = state.plan().unwrap();
+        assert_eq!(plan.newer, vec![(Store::Bounces, 7)]);
+        let error = state.migrate(&plan).unwrap_err().to_string();
+        assert!(error.contains("newer auxin"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Stem sets for commits: `auxin stems`
+//!
+//! Where a bounce is one stereo mix, a stem set is the named group of audio
+//! files a mix engineer expects at handoff (drums, bass, vox, ...). Each
+//! commit can have one set, stored in `.auxin/stems/<commit_id>/` next to
+//! a `manifest.json` recording each stem's name, size and SHA-256:
+//!
+//! ```json
+//! {
+//!   "commit_id": "8f3e2a1b...",
+//!   "stems": [
+//!     {
+//!       "name": "Drums",
+//!       "filename": "Drums.wav",
+//!       "format": "wav",
+//!       "size_bytes": 52428800,
+//!       "sha256": "9f86d081884c7d65..."
+//!     }
+//!   ],
+//!   "added_by": "me@studio-mac",
+//!   "updated_at": "2026-10-16T14:02:11Z"
+//! }
+//! ```
+//!
+//! Exports check every stem against its hash first, so a handoff never
+//! contains a truncated or altered file.
+
+use crate::bounce::AudioFormat;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest in each stem set directory and in exports
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// One audio file in a stem set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stem {
+    /// Stem name, e.g. "Drums"
+    pub name: String,
+    /// File name inside the stem set directory
+    pub filename: String,
+    pub format: AudioFormat,
+    pub size_bytes: u64,
+    /// Hex SHA-256 of the file
+    pub sha256: String,
+}
+
+/// The stems attached to a commit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StemSet {
+    pub commit_id: String,
+    /// Stems in the order they were added
+    #[serde(default)]
+    pub stems: Vec<Stem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub added_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StemSet {
+    pub fn total_size(&self) -> u64 {
+        self.stems.iter().map(|s| s.size_bytes).sum()
+    }
+
+    /// A stem by name, ignoring case
+    pub fn stem(&self, name: &str) -> Option<&Stem> {
+        self.stems
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A stem whose stored file no longer matches the manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StemProblem {
+    Missing(String),
+    Modified(String),
+}
+
+impl std::fmt::Display for StemProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StemProblem::Missing(name) => write!(f, "{}: file missing", name),
+            StemProblem::Modified(name) => write!(f, "{}: checksum mismatch", name),
+        }
+    }
+}
+
+/// Manages stem sets for a repository
+pub struct StemsManager {
+    /// Directory holding one subdirectory per commit
+    stems_dir: PathBuf,
+}
+
+impl StemsManager {
+    /// Create a stems manager for a repository
+    pub fn new(repo_root: &Path) -> Self {
+        Self {
+            stems_dir: repo_root.join(".auxin").join("stems"),
+        }
+    }
+
+    /// Directory of a commit's stem set
+    pub fn set_dir(&self, commit_id: &str) -> PathBuf {
+        self.stems_dir.join(commit_id)
+    }
+
+    /// Stored file of a stem
+    pub fn stem_path(&self, set: &StemSet, stem: &Stem) -> PathBuf {
+        self.set_dir(&set.commit_id).join(&stem.filename)
+    }
+
+    /// Add audio files to a commit's stem set, named after the files
+    ///
+    /// A file named like an existing stem replaces it. All files are
+    /// checked before anything is copied.
+    pub fn add_stems(
+        &self,
+        commit_id: &str,
+        files: &[PathBuf],
+        description: Option<&str>,
+    ) -> Result<StemSet> {
+        let mut incoming = Vec::with_capacity(files.len());
+        let mut names = HashSet::new();
+        for file in files {
+            if !file.is_file() {
+                bail!("Stem file not found: {}", file.display());
+            }
+            let ext = file
+                .extension()
+                .and_then(|e| e.to_str())
+                .ok_or_else(|| anyhow!("Cannot determine file format: {}", file.display()))?;
+            let format = AudioFormat::from_extension(ext)
+                .ok_or_else(|| anyhow!("Unsupported audio format: {}", ext))?;
+            let name = file
+                .file_stem()
+                .map(|s| s.to_string_lossy().trim().to_string())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| anyhow!("Cannot name stem: {}", file.display()))?;
+            if !names.insert(name.to_lowercase()) {
+                bail!("Two files would both be the stem '{}'", name);
+            }
+            incoming.push((file, name, format));
+        }
+
+        let dir = self.set_dir(commit_id);
+        fs::create_dir_all(&dir).context("Failed to create stems directory")?;
+        let mut set = self.get_stems(commit_id)?.unwrap_or_else(|| StemSet {
+            commit_id: commit_id.to_string(),
+            stems: Vec::new(),
+            description: None,
+            added_by: String::new(),
+            updated_at: Utc::now(),
+        });
+
+        for (file, name, format) in incoming {
+            let filename = format!("{}.{}", name, format.extension());
+            if let Some(old) = set.stem(&name) {
+                let old_path = dir.join(&old.filename);
+                if old.filename != filename && old_path.exists() {
+                    fs::remove_file(&old_path)
+                        .with_context(|| format!("Failed to replace stem {}", old.name))?;
+                }
+            }
+            let dest = dir.join(&filename);
+            let size_bytes = fs::copy(file, &dest)
+                .with_context(|| format!("Failed to copy {}", file.display()))?;
+            let stem = Stem {
+                sha256: sha256_file(&dest)?,
+                name,
+                filename,
+                format,
+                size_bytes,
+            };
+            match set
+                .stems
+                .iter_mut()
+                .find(|s| s.name.eq_ignore_ascii_case(&stem.name))
+            {
+                Some(existing) => *existing = stem,
+                None => set.stems.push(stem),
+            }
+        }
+
+        if let Some(description) = description {
+            set.description = Some(description.to_string());
+        }
+        set.added_by = crate::lock_integration::get_user_identifier();
+        set.updated_at = Utc::now();
+        self.save_manifest(&set)?;
+        Ok(set)
+    }
+
+    /// The stem set of a commit, if it has one
+    pub fn get_stems(&self, commit_id: &str) -> Result<Option<StemSet>> {
+        let path = self.set_dir(commit_id).join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let set = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(set))
+    }
+
+    /// All stem sets, most recently updated first
+    pub fn list_stem_sets(&self) -> Result<Vec<StemSet>> {
+        if !self.stems_dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut sets = Vec::new();
+        for entry in fs::read_dir(&self.stems_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let commit_id = entry.file_name().to_string_lossy().to_string();
+            if let Ok(Some(set)) = self.get_stems(&commit_id) {
+                sets.push(set);
+            }
+        }
+        sets.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+        Ok(sets)
+    }
+
+    /// Check every stored stem against the manifest
+    pub fn verify(&self, set: &StemSet) -> Result<Vec<StemProblem>> {
+        let mut problems = Vec::new();
+        for stem in &set.stems {
+            let path = self.stem_path(set, stem);
+            if !path.exists() {
+                problems.push(StemProblem::Missing(stem.name.clone()));
+            } else if sha256_file(&path)? != stem.sha256 {
+                problems.push(StemProblem::Modified(stem.name.clone()));
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Copy a verified stem set and its manifest into `dest`
+    pub fn export(&self, set: &StemSet, dest: &Path) -> Result<Vec<PathBuf>> {
+        let problems = self.verify(set)?;
+        if !problems.is_empty() {
+            let list: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+            bail!("Stems failed verification: {}", list.join(", "));
+        }
+
+        fs::create_dir_all(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+        let mut written = Vec::with_capacity(set.stems.len() + 1);
+        for stem in &set.stems {
+            let path = dest.join(&stem.filename);
+            fs::copy(self.stem_path(set, stem), &path)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            written.push(path);
+        }
+        let manifest = dest.join(MANIFEST_FILE);
+        fs::write(&manifest, serde_json::to_string_pretty(set)?)
+            .with_context(|| format!("Failed to write {}", manifest.display()))?;
+        written.push(manifest);
+        Ok(written)
+    }
+
+    /// Remove a commit's stem set
+    pub fn delete_stems(&self, commit_id: &str) -> Result<()> {
+        let dir = self.set_dir(commit_id);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).context("Failed to delete stems")?;
+        }
+        Ok(())
+    }
+
+    /// Replace a stem set's manifest atomically
+    fn save_manifest(&self, set: &StemSet) -> Result<()> {
+        let path = self.set_dir(&set.commit_id).join(MANIFEST_FILE);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(set)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+/// Hex SHA-256 of a file's contents
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file =
+        fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buffer[..read]);
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_add_verify_export() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        let drums = source.join("Drums.wav");
+        let vox = source.join("Vox.aiff");
+        fs::write(&drums, b"drums v1").unwrap();
+        fs::write(&vox, b"vox").unwrap();
+
+        let manager = StemsManager::new(temp.path());
+        let set = manager
+            .add_stems("abc123", &[drums.clone(), vox], Some("Mix stems"))
+            .unwrap();
+        assert_eq!(set.stems.len(), 2);
+        assert_eq!(set.total_size(), 11);
+        assert_eq!(set.stem("drums").unwrap().filename, "Drums.wav");
+
+        // Re-adding a stem replaces it
+        fs::write(&drums, b"drums v2").unwrap();
+        let set = manager.add_stems("abc123", &[drums], None).unwrap();
+        assert_eq!(set.stems.len(), 2);
+        assert_eq!(set.description.as_deref(), Some("Mix stems"));
+        assert_eq!(
+            set.stem("Drums").unwrap().sha256,
+            sha256_file(&manager.set_dir("abc123").join("Drums.wav")).unwrap()
+        );
+        assert_eq!(manager.list_stem_sets().unwrap(), vec![set.clone()]);
+
+        let out = temp.path().join("handoff");
+        assert_eq!(manager.export(&set, &out).unwrap().len(), 3);
+        assert_eq!(fs::read(out.join("Drums.wav")).unwrap(), b"drums v2");
+
+        fs::write(manager.set_dir("abc123").join("Vox.aiff"), b"changed").unwrap();
+        assert_eq!(
+            manager.verify(&set).unwrap(),
+            vec![StemProblem::Modified("Vox".to_string())]
+        );
+        assert!(manager.export(&set, &out).is_err());
+
+        let clash = vec![source.join("Drums.wav"), source.join("drums.mp3")];
+        fs::write(&clash[1], b"x").unwrap();
+        assert!(manager.add_stems("def456", &clash, None).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! `auxin sync-status`: is everything actually saved and synced?
+//!
+//! Work can be stuck in several places at once, each with its own command.
+//! This gathers them into one report:
+//! - operations waiting in the offline queue, and ones that gave up
+//! - write-ahead log entries for operations that never finished
+//! - interrupted uploads that `auxin push` would resume
+//! - whether the daemon is running, and whether auto-commits are paused for
+//!   the current project
+//! - whether the network and the auxin server can be reached
+//!
+//! [`SyncStatus::suggestions`] turns the report into next steps.
+
+use crate::anomaly::PauseState;
+use crate::chunked_upload::{self, UploadConfig, UploadSession};
+use crate::daemon_client::{DaemonClient, DaemonCompatibility, DaemonStatus};
+use crate::network_resilience::{check_connectivity, ConnectivityState};
+use crate::offline_queue::{OfflineQueue, QueueEntry};
+use crate::server_client::{AuxinServerClient, ServerConfig};
+use crate::write_ahead_log::{WalEntry, WriteAheadLog};
+use std::path::Path;
+
+/// Whether the auxin server answered its health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerReach {
+    /// No server in `[cli] url`
+    NotConfigured,
+    Reachable,
+    Unreachable,
+}
+
+/// Everything that may still be waiting to be saved or synced
+#[derive(Debug, Clone)]
+pub struct SyncStatus {
+    /// Offline queue entries still to sync
+    pub queued: Vec<QueueEntry>,
+    /// Offline queue entries that failed too often and were set aside
+    pub dead_letters: usize,
+    /// Write-ahead log entries that never completed
+    pub unfinished: Vec<WalEntry>,
+    /// Interrupted uploads
+    pub uploads: Vec<UploadSession>,
+    pub daemon: DaemonStatus,
+    /// Set when the daemon speaks a different protocol than this CLI
+    pub daemon_warning: Option<String>,
+    /// Auto-commits paused for the current project
+    pub paused: Option<PauseState>,
+    pub network: ConnectivityState,
+    pub server_url: String,
+    pub server: ServerReach,
+}
+
+/// A next step: why, and the command that takes it, if there is one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub reason: String,
+    pub command: Option<String>,
+}
+
+impl Suggestion {
+    fn new(reason: impl Into<String>, command: Option<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            command,
+        }
+    }
+}
+
+impl SyncStatus {
+    /// Gather the status; `project` is the repository the command runs in
+    pub fn gather(project: Option<&Path>) -> Self {
+        let config = auxin_config::Config::load().unwrap_or_default();
+
+        let (queued, dead_letters) = OfflineQueue::new().map_or((Vec::new(), 0), |queue| {
+            (queue.pending().into_iter().cloned().collect(), queue.dead_letters().len())
+        });
+        let unfinished = WriteAheadLog::new().get_incomplete_entries().unwrap_or_default();
+        let uploads =
+            chunked_upload::resumable_sessions(&UploadConfig::default().state_dir)
+                .unwrap_or_default();
+
+        let daemon = DaemonClient::new().status().unwrap_or_default();
+        let daemon_warning = DaemonCompatibility::check(&daemon).warning();
+        let paused = project.and_then(|p| PauseState::load(p).ok().flatten());
+
+        let network = check_connectivity();
+        let server_url = config.cli.url.clone();
+        let server = if server_url.trim().is_empty() {
+            ServerReach::NotConfigured
+        } else {
+            match AuxinServerClient::new(ServerConfig::from_config(&config))
+                .and_then(|client| client.health_check())
+            {
+                Ok(true) => ServerReach::Reachable,
+                _ => ServerReach::Unreachable,
+            }
+        };
+
+        Self {
+            queued,
+            dead_letters,
+            unfinished,
+            uploads,
+            daemon,
+            daemon_warning,
+            paused,
+            network,
+            server_url,
+            server,
+        }
+    }
+
+    /// Whether nothing is waiting to be saved or synced
+    pub fn is_synced(&self) -> bool {
+        self.queued.is_empty()
+            && self.dead_letters == 0
+            && self.unfinished.is_empty()
+            && self.uploads.is_empty()
+    }
+
+    /// What to do next, most pressing first
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let mut suggestions = Vec::new();
+        let command = |c: &str| Some(c.to_string());
+
+        let online = match self.network {
+            ConnectivityState::Offline => {
+                suggestions.push(Suggestion::new(
+                    "You're offline; queued work syncs once you reconnect",
+                    None,
+                ));
+                false
+            }
+            ConnectivityState::Captive => {
+                suggestions.push(Suggestion::new(
+                    "Sign in to the Wi-Fi network in a browser; nothing syncs until then",
+                    None,
+                ));
+                false
+            }
+            ConnectivityState::Online | ConnectivityState::Unknown => true,
+        };
+        if online && self.server == ServerReach::Unreachable {
+            suggestions.push(Suggestion::new(
+                format!("The server at {} isn't answering", self.server_url),
+                command("auxin doctor"),
+            ));
+        }
+
+        if !self.unfinished.is_empty() {
+            suggestions.push(Suggestion::new(
+                format!(
+                    "{} operation(s) were interrupted before finishing",
+                    self.unfinished.len()
+                ),
+                command("auxin sync-status --recover"),
+            ));
+        }
+        for upload in &self.uploads {
+            suggestions.push(Suggestion::new(
+                format!(
+                    "Upload of {} stopped at {:.0}%",
+                    upload.repo_path,
+                    upload.percentage()
+                ),
+                Some(format!("cd {} && auxin push", shell_quote(&upload.repo_path))),
+            ));
+        }
+        if !self.queued.is_empty() && online {
+            suggestions.push(Suggestion::new(
+                format!("{} operation(s) are waiting in the offline queue", self.queued.len()),
+                command("auxin queue sync"),
+            ));
+        }
+        if self.dead_letters > 0 {
+            suggestions.push(Suggestion::new(
+                format!(
+                    "{} queued operation(s) gave up after repeated failures",
+                    self.dead_letters
+                ),
+                command("auxin queue deadletter list"),
+            ));
+        }
+
+        if !self.daemon.is_running {
+            suggestions.push(Suggestion::new(
+                "The daemon isn't running, so changes aren't auto-committed",
+                command("auxin daemon start"),
+            ));
+        } else if let Some(warning) = &self.daemon_warning {
+            suggestions.push(Suggestion::new(warning.clone(), command("auxin daemon restart")));
+        }
+        if self.paused.is_some() {
+            suggestions.push(Suggestion::new(
+                "Auto-commits are paused for this project after a suspicious change",
+                command("auxin anomaly status"),
+            ));
+        }
+
+        suggestions
+    }
+}
+
+fn shell_quote(path: &str) -> String {
+    if path.chars().all(|c| c.is_ascii_alphanumeric() || "/._-~".contains(c)) {
+        path.to_string()
+    } else {
+        format!("'{}'", path.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::offline_queue::QueuedOperation;
+
+    #[test]
+    fn test_suggestions_follow_what_is_stuck() {
+        let mut status = SyncStatus {
+            queued: Vec::new(),
+            dead_letters: 0,
+            unfinished: Vec::new(),
+            uploads: Vec::new(),
+            daemon: DaemonStatus {
+                is_running: true,
+                ..Default::default()
+            },
+            daemon_warning: None,
+            paused: None,
+            network: ConnectivityState::Online,
+            server_url: "http://studio:3000".to_string(),
+            server: ServerReach::Reachable,
+        };
+        assert!(status.is_synced());
+        assert!(status.suggestions().is_empty());
+
+        status.queued.push(QueueEntry::new(QueuedOperation::SyncComments {
+            repo_path: "/music/song".to_string(),
+        }));
+        status.uploads.push(UploadSession::new(Path::new("/music/My Song"), "origin", "main"));
+        let commands: Vec<Option<String>> =
+            status.suggestions().into_iter().map(|s| s.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                Some("cd '/music/My Song' && auxin push".to_string()),
+                Some("auxin queue sync".to_string()),
+            ]
+        );
+        assert!(!status.is_synced());
+
+        // Offline: syncing the queue can't help yet
+        status.network = ConnectivityState::Offline;
+        status.server = ServerReach::Unreachable;
+        let suggestions = status.suggestions();
+        assert_eq!(suggestions[0].command, None);
+        assert!(suggestions.iter().all(|s| s.command.as_deref() != Some("auxin queue sync")));
+        assert!(suggestions.iter().all(|s| s.command.as_deref() != Some("auxin doctor")));
+    }
+}
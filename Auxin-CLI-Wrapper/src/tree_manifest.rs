@@ -0,0 +1,261 @@
+//! Working-tree manifests taken before restore and pull
+//!
+//! When a project "sounds different" after `auxin restore` or `auxin pull`,
+//! the question is what exactly the operation touched. Right before either
+//! one, the project's files are recorded in `.auxin/pre_operation_manifest.json`:
+//! path, size and modification time of every file, plus a SHA-256 of files
+//! up to `HASH_LIMIT`. `auxin what-changed --since-last-restore` compares that
+//! manifest with the tree as it is now.
+//!
+//! Large files (audio, mostly) aren't hashed to keep the manifest quick to
+//! take; one whose size is unchanged but whose timestamp moved is reported
+//! as possibly modified.
+
+use crate::stems::sha256_file;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files up to this size are hashed
+pub const HASH_LIMIT: u64 = 1024 * 1024;
+
+/// One file as it was recorded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
+    /// SHA-256, for files up to `HASH_LIMIT`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// The project's files right before an operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeManifest {
+    /// Operation about to run, e.g. "restore"
+    pub operation: String,
+    /// What it was run with: a commit, or remote/branch
+    pub target: String,
+    pub taken_at: DateTime<Utc>,
+    #[serde(default)]
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+impl TreeManifest {
+    /// Location of a project's manifest
+    pub fn path(project: &Path) -> PathBuf {
+        project.join(".auxin").join("pre_operation_manifest.json")
+    }
+
+    /// Record the project's files, skipping `.oxen` and `.auxin`
+    pub fn take(project: &Path, operation: &str, target: &str) -> Result<Self> {
+        Ok(Self {
+            operation: operation.to_string(),
+            target: target.to_string(),
+            taken_at: Utc::now(),
+            files: scan(project)?,
+        })
+    }
+
+    /// Take a manifest and save it, replacing the previous one
+    pub fn record(project: &Path, operation: &str, target: &str) -> Result<Self> {
+        let manifest = Self::take(project, operation, target)?;
+        manifest.save(project)?;
+        Ok(manifest)
+    }
+
+    /// The manifest from the last restore or pull, if any
+    pub fn load(project: &Path) -> Result<Option<Self>> {
+        let path = Self::path(project);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace the project's manifest atomically
+    pub fn save(&self, project: &Path) -> Result<()> {
+        let path = Self::path(project);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+fn scan(project: &Path) -> Result<BTreeMap<String, ManifestEntry>> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeMap<String, ManifestEntry>) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if dir == root && (name == ".oxen" || name == ".auxin") {
+                    continue;
+                }
+                walk(root, &path, files)?;
+            } else if file_type.is_file() {
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                let size = metadata.len();
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                files.insert(
+                    relative.to_string_lossy().replace('\\', "/"),
+                    ManifestEntry {
+                        size,
+                        modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                        sha256: if size <= HASH_LIMIT {
+                            sha256_file(&path).ok()
+                        } else {
+                            None
+                        },
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    walk(project, project, &mut files)?;
+    Ok(files)
+}
+
+/// How a file differs from the manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// Size or hash differs
+    Modified,
+    /// Too large to hash: same size, different timestamp
+    PossiblyModified,
+}
+
+/// A file the operation altered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    pub before: Option<ManifestEntry>,
+    pub after: Option<ManifestEntry>,
+}
+
+/// Files that differ between two manifests, by path
+///
+/// A file whose hash is unchanged isn't reported even if its timestamp
+/// moved; restoring a file to the same content often rewrites it.
+pub fn diff(
+    before: &BTreeMap<String, ManifestEntry>,
+    after: &BTreeMap<String, ManifestEntry>,
+) -> Vec<TreeChange> {
+    let mut changes = Vec::new();
+    for (path, old) in before {
+        let kind = match after.get(path) {
+            None => Some(ChangeKind::Removed),
+            Some(new) if old.size != new.size => Some(ChangeKind::Modified),
+            Some(new) => match (&old.sha256, &new.sha256) {
+                (Some(a), Some(b)) => (a != b).then_some(ChangeKind::Modified),
+                _ => (old.modified != new.modified).then_some(ChangeKind::PossiblyModified),
+            },
+        };
+        if let Some(kind) = kind {
+            changes.push(TreeChange {
+                path: path.clone(),
+                kind,
+                before: Some(old.clone()),
+                after: after.get(path).cloned(),
+            });
+        }
+    }
+    for (path, new) in after {
+        if !before.contains_key(path) {
+            changes.push(TreeChange {
+                path: path.clone(),
+                kind: ChangeKind::Added,
+                before: None,
+                after: Some(new.clone()),
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// What changed in the project since the last restore or pull
+pub fn since_last_operation(project: &Path) -> Result<Option<(TreeManifest, Vec<TreeChange>)>> {
+    let Some(manifest) = TreeManifest::load(project)? else {
+        return Ok(None);
+    };
+    let changes = diff(&manifest.files, &scan(project)?);
+    Ok(Some((manifest, changes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_manifest_diff() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        fs::create_dir_all(project.join("Alternatives/000")).unwrap();
+        fs::create_dir_all(project.join(".oxen")).unwrap();
+        fs::write(project.join(".oxen/HEAD"), b"abc").unwrap();
+        fs::write(project.join("Alternatives/000/ProjectData"), b"version 1").unwrap();
+        fs::write(project.join("Alternatives/000/DisplayState.plist"), b"state").unwrap();
+        fs::write(project.join("notes.txt"), b"todo").unwrap();
+
+        assert!(since_last_operation(project).unwrap().is_none());
+        let manifest = TreeManifest::record(project, "restore", "abc1234").unwrap();
+        assert_eq!(manifest.files.len(), 3);
+        assert!(manifest.files["notes.txt"].sha256.is_some());
+
+        // Same content rewritten isn't a change
+        fs::write(project.join("Alternatives/000/DisplayState.plist"), b"state").unwrap();
+        fs::write(project.join("Alternatives/000/ProjectData"), b"version 2").unwrap();
+        fs::remove_file(project.join("notes.txt")).unwrap();
+        fs::write(project.join("Kick.wav"), b"RIFF").unwrap();
+
+        let (loaded, changes) = since_last_operation(project).unwrap().unwrap();
+        assert_eq!(loaded.target, "abc1234");
+        let kinds: Vec<_> = changes.iter().map(|c| (c.path.as_str(), c.kind)).collect();
+        assert_eq!(
+            kinds,
+            [
+                ("Alternatives/000/ProjectData", ChangeKind::Modified),
+                ("Kick.wav", ChangeKind::Added),
+                ("notes.txt", ChangeKind::Removed),
+            ]
+        );
+
+        // Unhashed files fall back to the timestamp
+        let entry = |modified: i64| ManifestEntry {
+            size: 10,
+            modified: Utc.timestamp_opt(modified, 0).single(),
+            sha256: None,
+        };
+        let before = BTreeMap::from([("Vox.wav".to_string(), entry(1))]);
+        let after = BTreeMap::from([("Vox.wav".to_string(), entry(2))]);
+        assert_eq!(diff(&before, &after)[0].kind, ChangeKind::PossiblyModified);
+        assert!(diff(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! Unreal Engine projects
+//!
+//! An Unreal project is a folder with a `.uproject` descriptor (JSON) at
+//! its root, `Content/` for assets and `Config/` for settings. The engine
+//! regenerates `Intermediate/`, `Saved/`, `DerivedDataCache/` and
+//! `Binaries/`, which together are often larger than the project, so the
+//! ignore template in [`ignore_template`](crate::ignore_template) leaves
+//! them out.
+
+use crate::vlog;
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// An Unreal Engine project folder
+#[derive(Debug, Clone)]
+pub struct UnrealProject {
+    /// Canonical absolute path to the project folder
+    pub project_dir: PathBuf,
+    /// The `.uproject` descriptor in it
+    pub uproject_path: PathBuf,
+}
+
+/// What's read from a `.uproject`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnrealProjectInfo {
+    /// `EngineAssociation`: a version such as "5.3" for launcher installs,
+    /// or the id of a source build
+    pub engine_version: Option<String>,
+    /// Names of the project's C++ modules
+    pub modules: Vec<String>,
+    /// Names of the plugins the project turns on
+    pub enabled_plugins: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Descriptor {
+    #[serde(default)]
+    engine_association: String,
+    #[serde(default)]
+    modules: Vec<Module>,
+    #[serde(default)]
+    plugins: Vec<Plugin>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Module {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Plugin {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+}
+
+impl UnrealProject {
+    /// Find the project at `path`: a `.uproject` file, or a folder with
+    /// one at its root
+    pub fn detect(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        vlog!("Detecting Unreal Engine project at {}", path.display());
+        if !path.exists() {
+            bail!("Path does not exist: {}", path.display());
+        }
+        let path = std::fs::canonicalize(path).context("Failed to canonicalize path")?;
+
+        let uproject_path = if path.is_dir() {
+            find_uproject(&path)?.ok_or_else(|| {
+                anyhow!(
+                    "No .uproject file found in {}; is this an Unreal Engine project?",
+                    path.display()
+                )
+            })?
+        } else if is_uproject(&path) {
+            path
+        } else {
+            bail!(
+                "Path is not an Unreal Engine project (.uproject): {}",
+                path.display()
+            );
+        };
+        let project_dir = uproject_path
+            .parent()
+            .ok_or_else(|| anyhow!("Cannot determine parent directory"))?
+            .to_path_buf();
+        Ok(Self {
+            project_dir,
+            uproject_path,
+        })
+    }
+
+    /// The `.uproject` file name without the extension
+    pub fn name(&self) -> String {
+        self.uproject_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Unknown")
+            .to_string()
+    }
+
+    /// Engine version, modules and plugins, from the `.uproject`
+    pub fn read_info(&self) -> Result<UnrealProjectInfo> {
+        let json = std::fs::read_to_string(&self.uproject_path)
+            .with_context(|| format!("Failed to read {}", self.uproject_path.display()))?;
+        let descriptor: Descriptor = serde_json::from_str(&json).with_context(|| {
+            format!(
+                "Not a valid Unreal Engine project file: {}",
+                self.uproject_path.display()
+            )
+        })?;
+        let engine_version = descriptor.engine_association.trim();
+        Ok(UnrealProjectInfo {
+            engine_version: (!engine_version.is_empty()).then(|| engine_version.to_string()),
+            modules: descriptor.modules.into_iter().map(|m| m.name).collect(),
+            enabled_plugins: descriptor
+                .plugins
+                .into_iter()
+                .filter(|p| p.enabled)
+                .map(|p| p.name)
+                .collect(),
+        })
+    }
+
+    /// Patterns for files the engine and IDEs generate in the project
+    pub fn ignored_patterns() -> Vec<&'static str> {
+        vec![
+            "Binaries/",
+            "Intermediate/",
+            "Saved/",
+            "DerivedDataCache/",
+            ".vs/",
+            "*.sln",
+            "*.suo",
+            "*.VC.db",
+            "*.opensdf",
+            "*.sdf",
+            "*.xcworkspace",
+        ]
+    }
+}
+
+/// Whether `path` is a `.uproject` or a folder with one at its root
+pub fn is_unreal_project(path: &Path) -> bool {
+    if path.is_dir() {
+        matches!(find_uproject(path), Ok(Some(_)))
+    } else {
+        is_uproject(path)
+    }
+}
+
+fn is_uproject(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("uproject"))
+}
+
+/// The `.uproject` at the root of `dir`, the first by name if there are
+/// several
+fn find_uproject(dir: &Path) -> Result<Option<PathBuf>> {
+    let mut found: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && is_uproject(p))
+        .collect();
+    found.sort();
+    Ok(found.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const UPROJECT: &str = r#"{
+	"FileVersion": 3,
+	"EngineAssociation": "5.3",
+	"Category": "",
+	"Modules": [
+		{ "Name": "Shooter", "Type": "Runtime", "LoadingPhase": "Default" }
+	],
+	"Plugins": [
+		{ "Name": "ModelingToolsEditorMode", "Enabled": true },
+		{ "Name": "OculusVR", "Enabled": false }
+	]
+}"#;
+
+    #[test]
+    fn test_detect_and_read_info() {
+        let dir = TempDir::new().unwrap();
+        let uproject = dir.path().join("Shooter.uproject");
+        std::fs::write(&uproject, UPROJECT).unwrap();
+
+        let from_dir = UnrealProject::detect(dir.path()).unwrap();
+        let from_file = UnrealProject::detect(&uproject).unwrap();
+        assert_eq!(from_dir.uproject_path, from_file.uproject_path);
+        assert_eq!(from_dir.project_dir, from_file.project_dir);
+        assert_eq!(from_dir.name(), "Shooter");
+
+        let info = from_dir.read_info().unwrap();
+        assert_eq!(info.engine_version.as_deref(), Some("5.3"));
+        assert_eq!(info.modules, vec!["Shooter"]);
+        assert_eq!(info.enabled_plugins, vec!["ModelingToolsEditorMode"]);
+    }
+
+    #[test]
+    fn test_detect_rejects_other_folders() {
+        let dir = TempDir::new().unwrap();
+        assert!(!is_unreal_project(dir.path()));
+        assert!(UnrealProject::detect(dir.path()).is_err());
+
+        // Projects kept inside the engine's own folder have no association
+        let uproject = dir.path().join("Game.uproject");
+        std::fs::write(&uproject, r#"{"FileVersion": 3, "EngineAssociation": ""}"#).unwrap();
+        assert!(is_unreal_project(dir.path()));
+        let info = UnrealProject::detect(&uproject)
+            .unwrap()
+            .read_info()
+            .unwrap();
+        assert_eq!(info.engine_version, None);
+    }
+}
@@ -0,0 +1,469 @@
+//! Checking the Oxen CLI, auxin, the daemon and the server for upgrades
+//!
+//! Each auxin release publishes a manifest (`[updates] manifest_url`) with
+//! the latest version of every component. `auxin upgrade-check` compares it
+//! with what's installed and says how to upgrade each outdated component.
+//! Commands run in a terminal also check once a day, unless `[updates]
+//! check` is off; the last manifest fetched is kept in
+//! `~/.auxin/upgrade-check.json` so an offline check still has something to
+//! compare with.
+
+use crate::daemon_client::DaemonClient;
+use crate::server_client::{AuxinServerClient, ServerConfig};
+use crate::OxenSubprocess;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+/// How long a passive check waits for the manifest and the server
+const PASSIVE_TIMEOUT_SECS: u64 = 3;
+
+/// Time between passive checks
+fn check_interval() -> Duration {
+    Duration::hours(24)
+}
+
+/// Something `auxin upgrade-check` looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Component {
+    Oxen,
+    Auxin,
+    Daemon,
+    Server,
+}
+
+impl Component {
+    pub const ALL: [Component; 4] = [
+        Component::Oxen,
+        Component::Auxin,
+        Component::Daemon,
+        Component::Server,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Component::Oxen => "Oxen CLI",
+            Component::Auxin => "auxin",
+            Component::Daemon => "daemon",
+            Component::Server => "auxin-server",
+        }
+    }
+
+    /// How to upgrade this component; `server_url` names the server
+    pub fn instructions(&self, server_url: &str) -> String {
+        match self {
+            Component::Oxen => "pip install --upgrade oxen-ai".to_string(),
+            Component::Auxin => "brew upgrade auxin, or install the latest DMG from \
+                                 https://github.com/jbacus/auxin/releases"
+                .to_string(),
+            Component::Daemon => "install the latest Auxin.app (it updates the daemon), then run: \
+                 auxin daemon restart"
+                .to_string(),
+            Component::Server => format!(
+                "upgrade auxin-server at {} (with Docker: docker compose pull && \
+                 docker compose up -d), or ask its administrator to",
+                server_url
+            ),
+        }
+    }
+}
+
+/// Latest releases, as published in the release manifest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatestReleases {
+    #[serde(default)]
+    pub oxen: Option<String>,
+    #[serde(default)]
+    pub auxin: Option<String>,
+    #[serde(default)]
+    pub daemon: Option<String>,
+    #[serde(default)]
+    pub server: Option<String>,
+}
+
+impl LatestReleases {
+    /// Download the manifest at `url`
+    pub fn fetch(url: &str, timeout: std::time::Duration) -> Result<Self> {
+        let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        agent
+            .get(url)
+            .call()
+            .context("Failed to fetch the release manifest")?
+            .into_json()
+            .context("Release manifest isn't valid JSON")
+    }
+
+    pub fn get(&self, component: Component) -> Option<&str> {
+        match component {
+            Component::Oxen => self.oxen.as_deref(),
+            Component::Auxin => self.auxin.as_deref(),
+            Component::Daemon => self.daemon.as_deref(),
+            Component::Server => self.server.as_deref(),
+        }
+    }
+}
+
+/// The last manifest fetched, and when
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpgradeState {
+    pub checked_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub latest: LatestReleases,
+}
+
+impl UpgradeState {
+    pub fn default_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        Ok(home.join(".auxin").join("upgrade-check.json"))
+    }
+
+    /// Load the state at `path`; missing or unreadable state is empty
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Whether a passive check is due
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.checked_at
+            .is_none_or(|checked| now - checked >= check_interval())
+    }
+}
+
+/// Whether an installed component is behind the latest release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionState {
+    UpToDate,
+    Outdated,
+    /// The installed or latest version isn't known
+    Unknown,
+}
+
+/// One component's installed and latest versions
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentStatus {
+    pub component: Component,
+    pub installed: Option<String>,
+    pub latest: Option<String>,
+    pub state: VersionState,
+    /// Why the installed version isn't known, e.g. "not running"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// What to run, for outdated components
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upgrade: Option<String>,
+}
+
+/// What `auxin upgrade-check` reports
+#[derive(Debug, Clone, Serialize)]
+pub struct UpgradeReport {
+    /// When the manifest compared against was fetched
+    pub checked_at: Option<DateTime<Utc>>,
+    pub components: Vec<ComponentStatus>,
+    /// Why a fresh manifest couldn't be fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_error: Option<String>,
+}
+
+impl UpgradeReport {
+    pub fn outdated(&self) -> impl Iterator<Item = &ComponentStatus> {
+        self.components
+            .iter()
+            .filter(|status| status.state == VersionState::Outdated)
+    }
+
+    /// One line naming the outdated components, if any
+    pub fn notice(&self) -> Option<String> {
+        let outdated: Vec<String> = self
+            .outdated()
+            .map(|status| {
+                format!(
+                    "{} {} → {}",
+                    status.component.label(),
+                    status.installed.as_deref().unwrap_or("?"),
+                    status.latest.as_deref().unwrap_or("?")
+                )
+            })
+            .collect();
+        if outdated.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Upgrades available: {}. Run 'auxin upgrade-check' for instructions.",
+            outdated.join(", ")
+        ))
+    }
+}
+
+/// Installed version of each component, or why it isn't known
+#[derive(Debug, Clone)]
+pub struct Installed {
+    pub oxen: std::result::Result<String, String>,
+    pub auxin: String,
+    pub daemon: std::result::Result<String, String>,
+    pub server: std::result::Result<String, String>,
+}
+
+impl Installed {
+    /// Ask each component for its version, waiting at most `timeout` for
+    /// the server
+    pub fn detect(config: &auxin_config::Config, timeout: std::time::Duration) -> Self {
+        let oxen = OxenSubprocess::new()
+            .version()
+            .map(|version| version.trim_start_matches("oxen").trim().to_string())
+            .map_err(|_| "not installed".to_string());
+
+        let daemon_status = DaemonClient::new().status().unwrap_or_default();
+        let daemon = match (daemon_status.is_running, daemon_status.version) {
+            (true, Some(version)) => Ok(version),
+            (true, None) => Err("running, version not reported".to_string()),
+            (false, _) => Err("not running".to_string()),
+        };
+
+        let mut server_config = ServerConfig::from_config(config);
+        server_config.timeout_secs = timeout.as_secs().max(1);
+        let server = AuxinServerClient::new(server_config)
+            .and_then(|client| client.server_version())
+            .map_err(|_| "not reachable".to_string())
+            .and_then(|info| {
+                info.map(|info| info.server_version)
+                    .ok_or_else(|| "version not reported".to_string())
+            });
+
+        Self {
+            oxen,
+            auxin: env!("CARGO_PKG_VERSION").to_string(),
+            daemon,
+            server,
+        }
+    }
+
+    fn get(&self, component: Component) -> std::result::Result<&str, &str> {
+        let version = match component {
+            Component::Oxen => &self.oxen,
+            Component::Auxin => return Ok(&self.auxin),
+            Component::Daemon => &self.daemon,
+            Component::Server => &self.server,
+        };
+        version.as_deref().map_err(|note| note.as_str())
+    }
+}
+
+/// Compare `installed` with `latest` and say how to upgrade what's behind
+pub fn compare(
+    installed: &Installed,
+    latest: &LatestReleases,
+    checked_at: Option<DateTime<Utc>>,
+    server_url: &str,
+) -> UpgradeReport {
+    let components = Component::ALL
+        .iter()
+        .map(|&component| {
+            let (installed, note) = match installed.get(component) {
+                Ok(version) => (Some(version.to_string()), None),
+                Err(note) => (None, Some(note.to_string())),
+            };
+            let latest = latest.get(component).map(str::to_string);
+            let state = match (&installed, &latest) {
+                (Some(installed), Some(latest)) => match compare_versions(installed, latest) {
+                    Some(Ordering::Less) => VersionState::Outdated,
+                    Some(_) => VersionState::UpToDate,
+                    None => VersionState::Unknown,
+                },
+                _ => VersionState::Unknown,
+            };
+            ComponentStatus {
+                component,
+                upgrade: (state == VersionState::Outdated)
+                    .then(|| component.instructions(server_url)),
+                installed,
+                latest,
+                state,
+                note,
+            }
+        })
+        .collect();
+
+    UpgradeReport {
+        checked_at,
+        components,
+        manifest_error: None,
+    }
+}
+
+/// Compare the first version number in each string, e.g. `oxen 0.19.2`
+/// with `v0.20.0`; missing parts count as 0
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (a, b) = (version_numbers(a)?, version_numbers(b)?);
+    let len = a.len().max(b.len());
+    let part = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    Some(
+        (0..len)
+            .map(|i| part(&a, i).cmp(&part(&b, i)))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal),
+    )
+}
+
+fn version_numbers(version: &str) -> Option<Vec<u64>> {
+    let pattern = Regex::new(r"\d+(?:\.\d+)*").ok()?;
+    let found = pattern.find(version)?;
+    found.as_str().split('.').map(|n| n.parse().ok()).collect()
+}
+
+/// Check every component against a freshly fetched manifest, falling back
+/// to the last one fetched when the manifest can't be reached
+pub fn check(config: &auxin_config::Config, timeout: std::time::Duration) -> UpgradeReport {
+    let state_path = UpgradeState::default_path().ok();
+    let mut state = state_path
+        .as_deref()
+        .map(UpgradeState::load)
+        .unwrap_or_default();
+
+    let manifest_error = match LatestReleases::fetch(&config.updates.manifest_url, timeout) {
+        Ok(latest) => {
+            state = UpgradeState {
+                checked_at: Some(Utc::now()),
+                latest,
+            };
+            if let Some(path) = &state_path {
+                if let Err(e) = state.save(path) {
+                    crate::vlog!("Couldn't save upgrade check state: {}", e);
+                }
+            }
+            None
+        },
+        Err(e) => Some(format!("{:#}", e)),
+    };
+
+    let installed = Installed::detect(config, timeout);
+    let mut report = compare(&installed, &state.latest, state.checked_at, &config.cli.url);
+    report.manifest_error = manifest_error;
+    report
+}
+
+/// The once-a-day check: a notice naming outdated components, when a check
+/// is due and anything is behind
+///
+/// A failed fetch still counts as a check, so being offline doesn't slow
+/// down every command.
+pub fn passive_notice(config: &auxin_config::Config) -> Option<String> {
+    if !config.updates.check {
+        return None;
+    }
+    let path = UpgradeState::default_path().ok()?;
+    let state = UpgradeState::load(&path);
+    let now = Utc::now();
+    if !state.is_due(now) {
+        return None;
+    }
+
+    let report = check(config, std::time::Duration::from_secs(PASSIVE_TIMEOUT_SECS));
+    if report.manifest_error.is_some() {
+        let attempted = UpgradeState {
+            checked_at: Some(now),
+            latest: state.latest,
+        };
+        if let Err(e) = attempted.save(&path) {
+            crate::vlog!("Couldn't save upgrade check state: {}", e);
+        }
+    }
+    report.notice()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(
+            compare_versions("oxen 0.19.2", "0.20.0"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare_versions("v0.2.0", "0.2"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("2.1.0", "2.0.9"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("dev", "0.2.0"), None);
+    }
+
+    #[test]
+    fn test_compare_reports_outdated_components() {
+        let installed = Installed {
+            oxen: Ok("0.19.2".to_string()),
+            auxin: "0.2.0".to_string(),
+            daemon: Err("not running".to_string()),
+            server: Ok("0.3.0".to_string()),
+        };
+        let latest = LatestReleases {
+            oxen: Some("0.20.1".to_string()),
+            auxin: Some("0.2.0".to_string()),
+            daemon: Some("2.1.0".to_string()),
+            server: Some("0.3.1".to_string()),
+        };
+        let report = compare(&installed, &latest, None, "https://auxin.studio");
+
+        let states: Vec<(Component, VersionState)> = report
+            .components
+            .iter()
+            .map(|status| (status.component, status.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (Component::Oxen, VersionState::Outdated),
+                (Component::Auxin, VersionState::UpToDate),
+                (Component::Daemon, VersionState::Unknown),
+                (Component::Server, VersionState::Outdated),
+            ]
+        );
+        assert_eq!(report.components[2].note.as_deref(), Some("not running"));
+        assert!(report.components[3]
+            .upgrade
+            .as_deref()
+            .unwrap()
+            .contains("https://auxin.studio"));
+        assert_eq!(
+            report.notice().unwrap(),
+            "Upgrades available: Oxen CLI 0.19.2 → 0.20.1, auxin-server 0.3.0 → 0.3.1. \
+             Run 'auxin upgrade-check' for instructions."
+        );
+    }
+
+    #[test]
+    fn test_state_is_due_daily() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("upgrade-check.json");
+        let now = Utc::now();
+        assert!(UpgradeState::load(&path).is_due(now));
+
+        let state = UpgradeState {
+            checked_at: Some(now - Duration::hours(2)),
+            latest: LatestReleases {
+                auxin: Some("0.3.0".to_string()),
+                ..Default::default()
+            },
+        };
+        state.save(&path).unwrap();
+        let loaded = UpgradeState::load(&path);
+        assert!(!loaded.is_due(now));
+        assert!(loaded.is_due(now + Duration::hours(23)));
+        assert_eq!(loaded.latest.auxin.as_deref(), Some("0.3.0"));
+    }
+}
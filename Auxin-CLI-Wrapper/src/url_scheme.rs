@@ -0,0 +1,392 @@
+//! `auxin://` URL handling
+//!
+//! Apple Shortcuts, Raycast and Alfred can open URLs but can't easily run
+//! shell commands with arguments. Auxin.app registers the `auxin://` scheme
+//! and hands every URL to `auxin handle-url`, which maps it to an operation:
+//!
+//! ```text
+//! auxin://commit                            quick save (auto message, metadata, bounce)
+//! auxin://commit?message=Vocals&bpm=120     stage everything and commit
+//! auxin://milestone?message=Final%20mix     commit tagged 'milestone'
+//! auxin://lock/acquire?timeout=8
+//! auxin://lock/release
+//! auxin://lock/toggle
+//! auxin://status
+//! auxin://repo/<namespace>/<name>/commit/<id>   a link from `auxin link`
+//! ```
+//!
+//! Every URL accepts `project=<path>`; without it the current directory is
+//! used. Only these operations are reachable, since any web page can ask the
+//! browser to open an `auxin://` link. For the same reason only `status`
+//! runs straight away: anything that changes the project (a commit, the
+//! lock) is confirmed first, by Auxin.app or at the terminal.
+
+use crate::commit_link::CommitLink;
+use crate::quick_actions::{self, current_metadata_args, LockState, QuickCommit};
+use anyhow::{anyhow, bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use url::Url;
+
+/// URL scheme registered by Auxin.app
+pub const SCHEME: &str = "auxin";
+
+/// Operation requested by an `auxin://` URL
+#[derive(Debug, Clone, PartialEq)]
+pub enum UrlAction {
+    /// Stage everything and commit; with no message this is `quick-commit`
+    Commit {
+        message: Option<String>,
+        bpm: Option<f32>,
+        sample_rate: Option<u32>,
+        key: Option<String>,
+        tags: Option<String>,
+    },
+    Milestone { message: Option<String> },
+    LockAcquire { timeout_hours: Option<u64> },
+    LockRelease,
+    LockToggle,
+    Status,
+    /// A commit shared with `auxin link`, looked up in the project
+    ShowCommit(CommitLink),
+}
+
+impl UrlAction {
+    /// Whether running this changes the project, so it must be confirmed
+    pub fn changes_project(&self) -> bool {
+        !matches!(self, UrlAction::Status | UrlAction::ShowCommit(_))
+    }
+
+    /// What the action does, to complete "... wants to <description>"
+    fn description(&self) -> &'static str {
+        match self {
+            UrlAction::Commit { .. } => "stage and commit all changes",
+            UrlAction::Milestone { .. } => "stage and commit all changes as a milestone",
+            UrlAction::LockAcquire { .. } => "acquire the lock",
+            UrlAction::LockRelease => "release the lock",
+            UrlAction::LockToggle => "acquire or release the lock",
+            UrlAction::Status => "show the status",
+            UrlAction::ShowCommit(_) => "show a commit",
+        }
+    }
+}
+
+/// Parsed `auxin://` URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlRequest {
+    pub action: UrlAction,
+    pub project: Option<PathBuf>,
+}
+
+impl FromStr for UrlRequest {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let url = Url::parse(s).with_context(|| format!("Invalid URL '{}'", s))?;
+        if url.scheme() != SCHEME {
+            bail!("Not an {}:// URL: '{}'", SCHEME, s);
+        }
+
+        let mut query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        let mut take = |name: &str| query.remove(name).filter(|v| !v.is_empty());
+
+        let route = format!("{}{}", url.host_str().unwrap_or_default(), url.path());
+        let action = match route.trim_end_matches('/') {
+            "commit" => UrlAction::Commit {
+                message: take("message"),
+                bpm: parse_param(take("bpm"), "bpm")?,
+                sample_rate: parse_param(take("sample_rate"), "sample_rate")?,
+                key: take("key"),
+                tags: take("tags"),
+            },
+            "milestone" => UrlAction::Milestone {
+                message: take("message"),
+            },
+            "lock/acquire" => UrlAction::LockAcquire {
+                timeout_hours: parse_param(take("timeout"), "timeout")?,
+            },
+            "lock/release" => UrlAction::LockRelease,
+            "lock/toggle" => UrlAction::LockToggle,
+            "status" => UrlAction::Status,
+            link if link.starts_with("repo/") => UrlAction::ShowCommit(
+                CommitLink::parse(s).with_context(|| format!("Invalid commit link '{}'", s))?,
+            ),
+            other => bail!(
+                "Unknown action '{}' (expected commit, milestone, lock/acquire, lock/release, \
+                 lock/toggle, status or a commit link)",
+                other
+            ),
+        };
+        let project = take("project").map(|p| expand_home(&p));
+
+        if let Some(unknown) = query.keys().next() {
+            bail!("Unknown parameter '{}' for {}://{}", unknown, SCHEME, route);
+        }
+
+        Ok(UrlRequest { action, project })
+    }
+}
+
+impl UrlRequest {
+    /// Title for the notification reporting this request's result
+    pub fn title(&self) -> &'static str {
+        match self.action {
+            UrlAction::Commit { .. } => "Commit",
+            UrlAction::Milestone { .. } => "Milestone",
+            UrlAction::LockAcquire { .. } | UrlAction::LockRelease | UrlAction::LockToggle => {
+                "Lock"
+            }
+            UrlAction::Status => "Auxin status",
+            UrlAction::ShowCommit(_) => "Commit",
+        }
+    }
+
+    /// Question to ask before running a request that changes the project
+    pub fn confirmation_prompt(&self) -> Option<String> {
+        if !self.action.changes_project() {
+            return None;
+        }
+        let project = match &self.project {
+            Some(project) => project.display().to_string(),
+            None => "the current directory".to_string(),
+        };
+        Some(format!(
+            "An auxin:// link wants to {} in {}. Allow it?",
+            self.action.description(),
+            project
+        ))
+    }
+
+    /// Carry out the request; returns a one-line description of the result
+    pub fn run(&self, auxin_exe: &Path) -> Result<String> {
+        let project = match &self.project {
+            Some(project) => project.clone(),
+            None => std::env::current_dir()?,
+        };
+
+        match &self.action {
+            UrlAction::Commit { message: None, .. } => {
+                let commit = quick_actions::quick_commit(&project, auxin_exe)?;
+                Ok(commit.summary())
+            }
+            UrlAction::Commit {
+                message: Some(message),
+                bpm,
+                sample_rate,
+                key,
+                tags,
+            } => {
+                let mut args = vec!["commit".to_string(), "-m".to_string(), message.clone()];
+                if bpm.is_none() && sample_rate.is_none() && key.is_none() {
+                    args.extend(current_metadata_args(&project));
+                }
+                let flags = [
+                    ("--bpm", bpm.map(|v| v.to_string())),
+                    ("--sample-rate", sample_rate.map(|v| v.to_string())),
+                    ("--key", key.clone()),
+                    ("--tags", tags.clone()),
+                ];
+                for (flag, value) in flags {
+                    if let Some(value) = value {
+                        args.extend([flag.to_string(), value]);
+                    }
+                }
+                stage_and_commit(&project, auxin_exe, &args, message)
+            }
+            UrlAction::Milestone { message } => {
+                let message = message.clone().unwrap_or_else(|| "Milestone".to_string());
+                let mut args = vec!["commit".to_string(), "-m".to_string(), message.clone()];
+                args.extend(current_metadata_args(&project));
+                args.extend(["--tags".to_string(), "milestone".to_string()]);
+                stage_and_commit(&project, auxin_exe, &args, &message)
+            }
+            UrlAction::LockAcquire { timeout_hours } => {
+                let mut args = vec!["lock".to_string(), "acquire".to_string()];
+                if let Some(hours) = timeout_hours {
+                    args.extend(["--timeout".to_string(), hours.to_string()]);
+                }
+                quick_actions::run_auxin(auxin_exe, &project, &args)?;
+                Ok("Lock acquired".to_string())
+            }
+            UrlAction::LockRelease => {
+                quick_actions::run_auxin(auxin_exe, &project, &["lock", "release"])?;
+                Ok("Lock released".to_string())
+            }
+            UrlAction::LockToggle => match quick_actions::toggle_lock(&project, auxin_exe)? {
+                LockState::Mine => Ok("Lock acquired".to_string()),
+                _ => Ok("Lock released".to_string()),
+            },
+            UrlAction::Status => quick_actions::status_line(&project),
+            UrlAction::ShowCommit(link) => show_commit(&project, link),
+        }
+    }
+}
+
+fn stage_and_commit(
+    project: &Path,
+    auxin_exe: &Path,
+    args: &[String],
+    message: &str,
+) -> Result<String> {
+    let oxen = OxenSubprocess::new();
+    let status = oxen.status(project)?;
+    if status.modified.is_empty() && status.untracked.is_empty() && status.staged.is_empty() {
+        return Ok(QuickCommit::NothingToCommit.summary());
+    }
+    oxen.add_all(project)?;
+    quick_actions::run_auxin(auxin_exe, project, args)?;
+    Ok(format!("Committed: {}", message))
+}
+
+/// One line describing the linked commit, which must be in `project`
+fn show_commit(project: &Path, link: &CommitLink) -> Result<String> {
+    let config = auxin_config::Config::load().unwrap_or_default();
+    let here = CommitLink::for_repo(&config, project, &link.commit)?;
+    if (&here.namespace, &here.name) != (&link.namespace, &link.name) {
+        bail!(
+            "The link is for {}/{}, but {} is {}/{}",
+            link.namespace,
+            link.name,
+            project.display(),
+            here.namespace,
+            here.name
+        );
+    }
+    let commit = OxenSubprocess::new()
+        .log(project, None)?
+        .into_iter()
+        .find(|commit| commit.id.starts_with(&link.commit))
+        .with_context(|| format!("Commit {} isn't in {}", link.commit, link.name))?;
+    let short_id = &commit.id[..7.min(commit.id.len())];
+    Ok(format!("{}: {}", short_id, commit.message))
+}
+
+fn parse_param<T: FromStr>(value: Option<String>, name: &str) -> Result<Option<T>> {
+    value
+        .map(|v| {
+            v.parse()
+                .map_err(|_| anyhow!("Invalid value '{}' for parameter '{}'", v, name))
+        })
+        .transpose()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> UrlRequest {
+        url.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_commit_urls() {
+        assert_eq!(
+            parse("auxin://commit").action,
+            UrlAction::Commit {
+                message: None,
+                bpm: None,
+                sample_rate: None,
+                key: None,
+                tags: None,
+            }
+        );
+
+        let request =
+            parse("auxin://commit?message=Vocal%20comp&bpm=120&key=A+Minor&project=/tmp/Song");
+        assert_eq!(
+            request.action,
+            UrlAction::Commit {
+                message: Some("Vocal comp".to_string()),
+                bpm: Some(120.0),
+                sample_rate: None,
+                key: Some("A Minor".to_string()),
+                tags: None,
+            }
+        );
+        assert_eq!(request.project, Some(PathBuf::from("/tmp/Song")));
+        assert_eq!(request.title(), "Commit");
+
+        assert_eq!(
+            parse("auxin://milestone/").action,
+            UrlAction::Milestone { message: None }
+        );
+    }
+
+    #[test]
+    fn test_parse_lock_and_status_urls() {
+        assert_eq!(
+            parse("auxin://lock/acquire?timeout=8").action,
+            UrlAction::LockAcquire {
+                timeout_hours: Some(8)
+            }
+        );
+        assert_eq!(parse("auxin://lock/release").action, UrlAction::LockRelease);
+        assert_eq!(parse("auxin://lock/toggle").action, UrlAction::LockToggle);
+        assert_eq!(parse("auxin://status").action, UrlAction::Status);
+
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(
+            parse("auxin://status?project=~/Music/Song.logicx").project,
+            Some(home.join("Music/Song.logicx"))
+        );
+    }
+
+    #[test]
+    fn test_only_status_runs_without_confirmation() {
+        assert_eq!(parse("auxin://status").confirmation_prompt(), None);
+        for url in [
+            "auxin://commit",
+            "auxin://milestone",
+            "auxin://lock/acquire",
+            "auxin://lock/release",
+            "auxin://lock/toggle",
+        ] {
+            assert!(parse(url).action.changes_project(), "{} changes the project", url);
+        }
+        assert_eq!(
+            parse("auxin://commit?project=/tmp/Song").confirmation_prompt().unwrap(),
+            "An auxin:// link wants to stage and commit all changes in /tmp/Song. Allow it?"
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_link() {
+        let link = CommitLink {
+            namespace: "studio".to_string(),
+            name: "My Song.logicx".to_string(),
+            commit: "8f3e2a1b".to_string(),
+        };
+        let request = parse(&link.uri());
+        assert_eq!(request.action, UrlAction::ShowCommit(link.clone()));
+        assert_eq!(request.confirmation_prompt(), None);
+
+        let request = parse(&format!("{}?project=/tmp/Song", link.uri()));
+        assert_eq!(request.action, UrlAction::ShowCommit(link));
+        assert_eq!(request.project, Some(PathBuf::from("/tmp/Song")));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_urls() {
+        for url in [
+            "https://commit",
+            "auxin://push",
+            "auxin://lock",
+            "auxin://commit?bpm=fast",
+            "auxin://lock/release?force=1",
+            "auxin://repo/studio/Song/commit",
+            "auxin://repo/studio/Song/branch/main",
+            "not a url",
+        ] {
+            assert!(url.parse::<UrlRequest>().is_err(), "{} should be rejected", url);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Human version labels for milestone commits: `auxin version`
+//!
+//! Clients ask for "v1.2" or "Mix 3", not commit hashes. Milestones can be
+//! given auto-incremented labels, recorded in `.auxin/versions.json`:
+//!
+//! ```json
+//! {
+//!   "versions": [
+//!     {
+//!       "commit_id": "8f3e2a1b...",
+//!       "label": "v1.1",
+//!       "major": 1,
+//!       "minor": 1,
+//!       "number": 2,
+//!       "note": "Client revision",
+//!       "user": "me@studio-mac",
+//!       "created_at": "2026-10-16T14:02:11Z"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Labels come from `[versioning] format` (`v{major}.{minor}` by default;
+//! `{n}` counts versions, e.g. `Mix {n}`). The first version is 1.0. A
+//! versioned commit is also tagged `milestone` (see [`crate::metadata_edits`]
+//! for commits made without it) and gets an oxen tag with its label.
+
+use crate::metadata_edits::{MetadataChange, MetadataEdits};
+use crate::session_sheet::is_milestone;
+use crate::CommitInfo;
+use anyhow::{bail, Context, Result};
+use auxin_oxen::OxenSubprocess;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Which part of the version to increment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bump {
+    Major,
+    Minor,
+}
+
+/// A version given to a commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionEntry {
+    pub commit_id: String,
+    /// Label as shown, e.g. "v1.1" or "Mix 3"
+    pub label: String,
+    pub major: u32,
+    pub minor: u32,
+    /// 1 for the first version, 2 for the second, ...
+    pub number: u32,
+    /// What changed, e.g. "Client revision"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub user: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Contents of `.auxin/versions.json`, oldest version first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionLog {
+    #[serde(default)]
+    pub versions: Vec<VersionEntry>,
+}
+
+impl VersionLog {
+    /// Location of the version log for a repo
+    pub fn path(repo: &Path) -> PathBuf {
+        repo.join(".auxin").join("versions.json")
+    }
+
+    /// Read a repo's versions; a missing file means none
+    pub fn load(repo: &Path) -> Result<Self> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace the repo's version log atomically
+    pub fn save(&self, repo: &Path) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    pub fn latest(&self) -> Option<&VersionEntry> {
+        self.versions.last()
+    }
+
+    /// The version given to a commit, if any
+    pub fn for_commit(&self, commit_id: &str) -> Option<&VersionEntry> {
+        self.versions.iter().find(|v| v.commit_id == commit_id)
+    }
+
+    /// `(major, minor, number)` of the next version
+    pub fn next(&self, bump: Bump) -> (u32, u32, u32) {
+        match (self.latest(), bump) {
+            (None, _) => (1, 0, 1),
+            (Some(v), Bump::Major) => (v.major + 1, 0, v.number + 1),
+            (Some(v), Bump::Minor) => (v.major, v.minor + 1, v.number + 1),
+        }
+    }
+
+    /// Give `commit` the next version
+    pub fn bump(
+        &mut self,
+        commit: &CommitInfo,
+        bump: Bump,
+        format: &str,
+        note: Option<String>,
+        user: &str,
+    ) -> Result<&VersionEntry> {
+        if let Some(existing) = self.for_commit(&commit.id) {
+            bail!(
+                "Commit {} is already {}",
+                &commit.id[..commit.id.len().min(8)],
+                existing.label
+            );
+        }
+
+        let (major, minor, number) = self.next(bump);
+        let label = format_label(format, major, minor, number);
+        if self.versions.iter().any(|v| v.label == label) {
+            bail!(
+                "Version label '{}' is already used; check [versioning] format",
+                label
+            );
+        }
+
+        self.versions.push(VersionEntry {
+            commit_id: commit.id.clone(),
+            label,
+            major,
+            minor,
+            number,
+            note,
+            user: user.to_string(),
+            created_at: Utc::now(),
+        });
+        Ok(self.versions.last().expect("just pushed"))
+    }
+}
+
+/// Give `commit` the next version and save it, tagging the commit
+/// `milestone` if it isn't already
+///
+/// The oxen tag is only created for the latest commit, since `oxen tag`
+/// tags HEAD; failing to create it isn't an error.
+pub fn record(
+    repo: &Path,
+    commit: &CommitInfo,
+    bump: Bump,
+    format: &str,
+    note: Option<String>,
+) -> Result<VersionEntry> {
+    let user = crate::lock_integration::get_user_identifier();
+    let mut log = VersionLog::load(repo)?;
+    let entry = log.bump(commit, bump, format, note, &user)?.clone();
+
+    let mut edits = MetadataEdits::load(repo)?;
+    if !is_milestone(&edits.effective(commit)) {
+        edits.edit(
+            commit,
+            &[MetadataChange::AddTag("milestone".to_string())],
+            &user,
+        );
+        edits.save(repo)?;
+    }
+    log.save(repo)?;
+
+    let oxen = OxenSubprocess::new();
+    let is_head = oxen
+        .log(repo, Some(1))
+        .is_ok_and(|log| log.first().is_some_and(|head| head.id == commit.id));
+    if is_head {
+        if let Err(e) = oxen.tag(repo, &tag_name(&entry.label), entry.note.as_deref()) {
+            crate::vlog!("Could not create tag {}: {}", entry.label, e);
+        }
+    }
+    Ok(entry)
+}
+
+/// Fill in a `[versioning] format` template
+pub fn format_label(format: &str, major: u32, minor: u32, number: u32) -> String {
+    format
+        .replace("{major}", &major.to_string())
+        .replace("{minor}", &minor.to_string())
+        .replace("{n}", &number.to_string())
+}
+
+/// An oxen tag name for a label: spaces become dashes
+pub fn tag_name(label: &str) -> String {
+    label.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(id: &str) -> CommitInfo {
+        CommitInfo {
+            id: id.to_string(),
+            message: "Mix".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bump_sequence() {
+        let temp = TempDir::new().unwrap();
+        let mut log = VersionLog::load(temp.path()).unwrap();
+        let format = "v{major}.{minor}";
+
+        let first = log
+            .bump(&commit("a1"), Bump::Minor, format, None, "me")
+            .unwrap();
+        assert_eq!(first.label, "v1.0");
+        let note = Some("Client revision".to_string());
+        let second = log
+            .bump(&commit("b2"), Bump::Minor, format, note, "me")
+            .unwrap();
+        assert_eq!(second.label, "v1.1");
+        assert_eq!(
+            log.bump(&commit("c3"), Bump::Major, format, None, "me")
+                .unwrap()
+                .label,
+            "v2.0"
+        );
+        assert!(log
+            .bump(&commit("c3"), Bump::Minor, format, None, "me")
+            .is_err());
+
+        log.save(temp.path()).unwrap();
+        let log = VersionLog::load(temp.path()).unwrap();
+        assert_eq!(
+            log.for_commit("b2").unwrap().note.as_deref(),
+            Some("Client revision")
+        );
+        assert_eq!(log.next(Bump::Minor), (2, 1, 4));
+
+        assert_eq!(format_label("Mix {n}", 2, 1, 4), "Mix 4");
+        assert_eq!(tag_name("Mix 4"), "Mix-4");
+    }
+}
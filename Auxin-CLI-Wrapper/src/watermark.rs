@@ -0,0 +1,194 @@
+//! Watermarked preview copies of bounces: `auxin bounce export --watermark`
+//!
+//! A preview is the bounce with an audible watermark mixed in every
+//! `interval_secs`: a sine tone, or an audio file such as a voice tag
+//! ("Preview - Studio A"), from `[watermark]` in the config. The bounce
+//! itself is never modified. Rendering uses `ffmpeg`, like the bounce null
+//! test; the output format follows the output file's extension.
+
+use anyhow::{bail, Context, Result};
+use auxin_config::Watermark as WatermarkConfig;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Sample rate the watermark is generated at; ffmpeg resamples as needed
+const SAMPLE_RATE: u32 = 48_000;
+
+/// What is mixed in
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatermarkSound {
+    /// A sine tone lasting `secs`
+    Tone { frequency_hz: f64, secs: f64 },
+    /// An audio file, cut off at the interval if longer
+    File(PathBuf),
+}
+
+/// A watermark and how often it repeats
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watermark {
+    pub sound: WatermarkSound,
+    pub interval_secs: f64,
+    pub level_db: f64,
+}
+
+impl Watermark {
+    pub fn from_config(config: &WatermarkConfig) -> Self {
+        let sound = match config.sound.trim() {
+            "" | "tone" => WatermarkSound::Tone {
+                frequency_hz: config.tone_hz,
+                secs: config.tone_secs,
+            },
+            path => WatermarkSound::File(expand_home(path)),
+        };
+        Self {
+            sound,
+            interval_secs: config.interval_secs,
+            level_db: config.level_db,
+        }
+    }
+
+    /// The ffmpeg filter graph mixing the watermark (input 1 for a file)
+    /// into the bounce (input 0), with the result labelled `[out]`
+    ///
+    /// amix halves each input, so the mix is doubled to keep the bounce at
+    /// its original level.
+    pub fn filter_graph(&self) -> String {
+        let interval = self.interval_secs;
+        let watermark = match &self.sound {
+            WatermarkSound::Tone { frequency_hz, secs } => format!(
+                "aevalsrc=exprs='if(lt(mod(t,{interval}),{secs}),sin(2*PI*{frequency_hz}*t),0)'\
+                 :s={SAMPLE_RATE}"
+            ),
+            WatermarkSound::File(_) => format!(
+                "[1:a]aresample={SAMPLE_RATE},apad=whole_dur={interval},\
+                 aloop=loop=-1:size={}",
+                (interval * SAMPLE_RATE as f64).round() as u64
+            ),
+        };
+        format!(
+            "{watermark},volume={}dB[wm];\
+             [0:a][wm]amix=inputs=2:duration=first:dropout_transition=0,volume=2[out]",
+            self.level_db
+        )
+    }
+
+    /// Render a watermarked copy of `input` to `output`
+    pub fn render(&self, input: &Path, output: &Path) -> Result<()> {
+        if self.interval_secs <= 0.0 {
+            bail!("[watermark] interval_secs must be greater than 0");
+        }
+        if let WatermarkSound::File(path) = &self.sound {
+            if !path.exists() {
+                bail!("Watermark sound not found: {}", path.display());
+            }
+        }
+
+        let mut ffmpeg = Command::new("ffmpeg");
+        ffmpeg
+            .args(["-y", "-hide_banner", "-loglevel", "error", "-i"])
+            .arg(input);
+        if let WatermarkSound::File(path) = &self.sound {
+            ffmpeg.arg("-i").arg(path);
+        }
+        let result = ffmpeg
+            .args(["-filter_complex", &self.filter_graph(), "-map", "[out]"])
+            .arg(output)
+            .output()
+            .context("Failed to run ffmpeg. Install it with: brew install ffmpeg")?;
+
+        if !result.status.success() {
+            bail!(
+                "ffmpeg failed: {}",
+                String::from_utf8_lossy(&result.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Export `input` to `output`, watermarked or not; an unwatermarked
+/// export is a plain copy unless the output format differs
+pub fn export(input: &Path, output: &Path, watermark: Option<&Watermark>) -> Result<()> {
+    if let Some(watermark) = watermark {
+        return watermark.render(input, output);
+    }
+    let same_format = input.extension().map(|e| e.to_ascii_lowercase())
+        == output.extension().map(|e| e.to_ascii_lowercase());
+    if same_format {
+        std::fs::copy(input, output)
+            .with_context(|| format!("Failed to write {}", output.display()))?;
+        return Ok(());
+    }
+
+    let result = Command::new("ffmpeg")
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-i"])
+        .arg(input)
+        .arg(output)
+        .output()
+        .context("Failed to run ffmpeg. Install it with: brew install ffmpeg")?;
+    if !result.status.success() {
+        bail!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Where an export goes without `--output`: the current directory, named
+/// after the bounce, with "(preview)" and MP3 for watermarked copies
+pub fn default_output(original_filename: &str, watermarked: bool) -> PathBuf {
+    if !watermarked {
+        return PathBuf::from(original_filename);
+    }
+    let stem = Path::new(original_filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "bounce".to_string());
+    PathBuf::from(format!("{} (preview).mp3", stem))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_graph() {
+        let config = WatermarkConfig::default();
+        let tone = Watermark::from_config(&config);
+        assert_eq!(
+            tone.filter_graph(),
+            "aevalsrc=exprs='if(lt(mod(t,20),1),sin(2*PI*1000*t),0)':s=48000,volume=-12dB[wm];\
+             [0:a][wm]amix=inputs=2:duration=first:dropout_transition=0,volume=2[out]"
+        );
+
+        let voice = Watermark::from_config(&WatermarkConfig {
+            sound: "/tmp/preview-tag.wav".to_string(),
+            interval_secs: 15.5,
+            ..config
+        });
+        assert_eq!(
+            voice.sound,
+            WatermarkSound::File("/tmp/preview-tag.wav".into())
+        );
+        assert!(voice
+            .filter_graph()
+            .starts_with("[1:a]aresample=48000,apad=whole_dur=15.5,aloop=loop=-1:size=744000,"));
+
+        assert_eq!(
+            default_output("Mix v3.wav", true),
+            PathBuf::from("Mix v3 (preview).mp3")
+        );
+        assert_eq!(
+            default_output("Mix v3.wav", false),
+            PathBuf::from("Mix v3.wav")
+        );
+    }
+}
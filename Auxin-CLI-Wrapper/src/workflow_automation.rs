@@ -294,6 +294,60 @@ impl WorkflowAutomation {
         Ok(())
     }
 
+    /// Display the workflow configuration
+    pub fn display_config(&self) {
+        let on_off = |enabled: bool| {
+            if enabled {
+                "enabled".green()
+            } else {
+                "disabled".bright_black()
+            }
+        };
+        let config = &self.config;
+
+        println!(
+            "\n{}",
+            "┌─ Workflow Configuration ────────────────────────────────┐".bright_blue()
+        );
+        println!(
+            "│ Auto-lock renewal:      {}",
+            on_off(config.auto_renew_locks)
+        );
+        println!(
+            "│ Lock check interval:    {} minutes",
+            config.lock_check_interval_minutes
+        );
+        println!(
+            "│ Lock renew threshold:   {} minutes",
+            config.lock_renew_threshold_minutes
+        );
+        println!(
+            "│ Auto-pull on startup:   {}",
+            on_off(config.auto_pull_on_startup)
+        );
+        println!(
+            "│ Auto-push after commit: {}",
+            on_off(config.auto_push_after_commit)
+        );
+        println!(
+            "│ Confirmation prompts:   {}",
+            on_off(config.confirm_destructive_operations)
+        );
+        println!("│ Dry-run mode:           {}", on_off(config.dry_run_mode));
+        println!("│");
+        println!(
+            "│ {}",
+            WorkflowConfig::default_path()
+                .display()
+                .to_string()
+                .bright_black()
+        );
+        println!(
+            "{}\n",
+            "└──────────────────────────────────────────────────────────┘".bright_blue()
+        );
+    }
+
     /// Execute pre-commit checks
     pub fn pre_commit_checks(&self, repo_path: &Path) -> Result<bool> {
         crate::vlog!("Running pre-commit checks...");
@@ -0,0 +1,337 @@
+//! Registered repositories and bulk maintenance: `auxin workspace`
+//!
+//! Repositories created with `auxin init` or `auxin clone` are registered in
+//! `~/.auxin/workspace.json`; others can be added with `auxin workspace
+//! add`. `auxin workspace maintain` runs the selected tasks on every one of
+//! them, for a nightly cron job on a shared studio machine:
+//!
+//! - `gc`: removes Spotlight sidecars for commits no longer in the history
+//!   and Oxen temp files more than a day old
+//! - `prune-drafts`: removes the draft branch of projects that turned the
+//!   draft workflow off, and flags draft branches over their commit limit
+//! - `verify`: checks that the status and history can be read and that
+//!   this auxin understands the project's features
+//!
+//! Each task ends `ok`, `fixed` (it changed something), `skipped` or
+//! `attention` (someone needs to look). The command exits non-zero when
+//! any repository needs attention.
+
+use crate::draft_manager::DraftManager;
+use crate::spotlight::SpotlightIndex;
+use anyhow::{Context, Result};
+use auxin_oxen::OxenSubprocess;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Oxen temp files older than this are removed by `gc`
+const TMP_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The repositories `auxin workspace maintain` looks after
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    #[serde(default)]
+    pub repos: BTreeSet<PathBuf>,
+}
+
+impl Workspace {
+    /// `~/.auxin/workspace.json`
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home).join(".auxin").join("workspace.json")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Register `repo`; `false` if it already was
+    pub fn add(&mut self, repo: &Path) -> bool {
+        self.repos.insert(canonical(repo))
+    }
+
+    /// Forget `repo`; `false` if it wasn't registered
+    pub fn remove(&mut self, repo: &Path) -> bool {
+        self.repos.remove(&canonical(repo)) || self.repos.remove(repo)
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Add `repo` to the default workspace
+pub fn register(repo: &Path) -> Result<()> {
+    let path = Workspace::default_path();
+    let mut workspace = Workspace::load(&path)?;
+    if workspace.add(repo) {
+        workspace.save(&path)?;
+    }
+    Ok(())
+}
+
+/// A maintenance task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Task {
+    Gc,
+    PruneDrafts,
+    Verify,
+}
+
+impl Task {
+    pub fn name(self) -> &'static str {
+        match self {
+            Task::Gc => "gc",
+            Task::PruneDrafts => "prune-drafts",
+            Task::Verify => "verify",
+        }
+    }
+}
+
+/// How a task ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Ok,
+    /// The task changed something
+    Fixed,
+    Skipped,
+    /// Someone needs to look at the repository
+    Attention,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskResult {
+    pub task: Task,
+    pub outcome: Outcome,
+    pub detail: String,
+}
+
+impl TaskResult {
+    fn new(task: Task, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Self {
+            task,
+            outcome,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Every task's result for one repository
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RepoReport {
+    pub repo: PathBuf,
+    pub results: Vec<TaskResult>,
+}
+
+impl RepoReport {
+    pub fn needs_attention(&self) -> bool {
+        self.results.iter().any(|r| r.outcome == Outcome::Attention)
+    }
+}
+
+/// Run `tasks` on `repo`
+pub fn maintain(repo: &Path, tasks: &[Task], oxen: &OxenSubprocess) -> RepoReport {
+    let results = if !repo.join(".oxen").is_dir() {
+        vec![TaskResult::new(
+            Task::Verify,
+            Outcome::Attention,
+            "not an Oxen repository any more; 'auxin workspace remove' it if it moved",
+        )]
+    } else {
+        tasks
+            .iter()
+            .map(|&task| {
+                let result = match task {
+                    Task::Gc => gc(repo, oxen),
+                    Task::PruneDrafts => prune_drafts(repo, oxen),
+                    Task::Verify => verify(repo, oxen),
+                };
+                result.unwrap_or_else(|e| {
+                    TaskResult::new(task, Outcome::Attention, format!("{:#}", e))
+                })
+            })
+            .collect()
+    };
+    RepoReport {
+        repo: repo.to_path_buf(),
+        results,
+    }
+}
+
+fn gc(repo: &Path, oxen: &OxenSubprocess) -> Result<TaskResult> {
+    let mut removed = Vec::new();
+
+    let index = SpotlightIndex::for_project(repo);
+    if index.dir().exists() {
+        let sidecars = index.retain(&oxen.log(repo, None)?)?;
+        if sidecars > 0 {
+            removed.push(format!("{} stale Spotlight sidecars", sidecars));
+        }
+    }
+
+    let tmp = repo.join(".oxen").join("tmp");
+    let mut temp_files = 0;
+    if let Ok(entries) = fs::read_dir(&tmp) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let old = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > TMP_MAX_AGE);
+            if !old {
+                continue;
+            }
+            let path = entry.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            }
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+            temp_files += 1;
+        }
+    }
+    if temp_files > 0 {
+        removed.push(format!("{} old temp files", temp_files));
+    }
+
+    Ok(if removed.is_empty() {
+        TaskResult::new(Task::Gc, Outcome::Ok, "nothing to remove")
+    } else {
+        TaskResult::new(
+            Task::Gc,
+            Outcome::Fixed,
+            format!("removed {}", removed.join(", ")),
+        )
+    })
+}
+
+fn prune_drafts(repo: &Path, oxen: &OxenSubprocess) -> Result<TaskResult> {
+    let task = Task::PruneDrafts;
+    if crate::archive::ArchiveMark::load(repo)?.is_some() {
+        return Ok(TaskResult::new(task, Outcome::Skipped, "archived"));
+    }
+    let drafts = DraftManager::new(repo)?;
+    if !drafts.draft_branch_exists()? {
+        return Ok(TaskResult::new(task, Outcome::Ok, "no draft branch"));
+    }
+
+    let workflow =
+        auxin_config::RepoFeatures::load(repo)?.is_none_or(|f| f.features.draft_workflow);
+    let stats = drafts.get_stats()?;
+    if !workflow {
+        if stats.is_on_draft {
+            return Ok(TaskResult::new(
+                task,
+                Outcome::Attention,
+                "draft workflow is off but the draft branch is checked out; switch to main",
+            ));
+        }
+        oxen.delete_branch(repo, &stats.draft_branch_name)?;
+        return Ok(TaskResult::new(
+            task,
+            Outcome::Fixed,
+            "removed the draft branch left from before the draft workflow was turned off",
+        ));
+    }
+
+    if stats.is_on_draft && stats.commit_count > stats.max_commits {
+        return Ok(TaskResult::new(
+            task,
+            Outcome::Attention,
+            format!(
+                "{} commits on the draft branch (limit {}); merge it into main",
+                stats.commit_count, stats.max_commits
+            ),
+        ));
+    }
+    Ok(TaskResult::new(
+        task,
+        Outcome::Ok,
+        "within the commit limit",
+    ))
+}
+
+fn verify(repo: &Path, oxen: &OxenSubprocess) -> Result<TaskResult> {
+    let task = Task::Verify;
+    if let Some(problem) =
+        auxin_config::RepoFeatures::load(repo)?.and_then(|features| features.incompatibility())
+    {
+        return Ok(TaskResult::new(
+            task,
+            Outcome::Attention,
+            format!("this project {}; upgrade auxin", problem),
+        ));
+    }
+    oxen.status(repo).context("status can't be read")?;
+    if oxen
+        .log(repo, Some(1))
+        .context("history can't be read")?
+        .is_empty()
+    {
+        return Ok(TaskResult::new(task, Outcome::Attention, "no commits"));
+    }
+    Ok(TaskResult::new(
+        task,
+        Outcome::Ok,
+        "status and history readable",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_workspace_add_remove() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("workspace.json");
+        let repo = dir.path().join("Song.logicx");
+        fs::create_dir(&repo).unwrap();
+
+        let mut workspace = Workspace::load(&path).unwrap();
+        assert!(workspace.add(&repo));
+        assert!(!workspace.add(&repo.join("..").join("Song.logicx")));
+        workspace.save(&path).unwrap();
+
+        let mut loaded = Workspace::load(&path).unwrap();
+        assert_eq!(loaded.repos.len(), 1);
+        assert!(loaded.remove(&repo));
+        assert!(!loaded.remove(&repo));
+    }
+
+    #[test]
+    fn test_missing_repository_needs_attention() {
+        let dir = TempDir::new().unwrap();
+        let report = maintain(
+            &dir.path().join("Gone.logicx"),
+            &[Task::Gc],
+            &OxenSubprocess::new(),
+        );
+        assert!(report.needs_attention());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["results"][0]["outcome"], "attention");
+        assert_eq!(json["results"][0]["task"], "verify");
+    }
+}
@@ -0,0 +1,248 @@
+//! `auxin wrapup`: is it safe to shut down?
+//!
+//! The end-of-session check. It's safe to close the laptop when:
+//! - the project has no uncommitted changes
+//! - the offline queue is empty, with nothing dead-lettered
+//! - no operation was interrupted and no upload is half done
+//! - the project's lock isn't held by you, so collaborators can take it
+//!
+//! Each [`Issue`] knows the [`WrapupStep`] that fixes it, if there is one;
+//! `auxin wrapup` offers to run them, then checks again for its verdict.
+
+use crate::quick_actions::{self, current_metadata_args, LockState};
+use crate::sync_status::SyncStatus;
+use anyhow::Result;
+use auxin_config::Config;
+use auxin_oxen::OxenSubprocess;
+use std::path::{Path, PathBuf};
+
+/// Something that makes shutting down unsafe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// Files changed since the last commit
+    Uncommitted(usize),
+    /// The current user holds the project's lock
+    LockHeld,
+    /// Offline queue entries still to sync
+    Queued(usize),
+    /// Queue entries that gave up after repeated failures
+    DeadLetters(usize),
+    /// Operations interrupted before they finished
+    Interrupted(usize),
+    /// Half-done uploads, by repository path
+    Uploads(Vec<String>),
+}
+
+/// A step `auxin wrapup` can take to fix an [`Issue`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapupStep {
+    Commit,
+    /// Push the project, resuming an interrupted upload
+    Push,
+    SyncQueue,
+    ReleaseLock,
+}
+
+impl Issue {
+    pub fn describe(&self) -> String {
+        match self {
+            Issue::Uncommitted(n) => format!("{} file(s) changed since the last commit", n),
+            Issue::LockHeld => "You hold the project's lock".to_string(),
+            Issue::Queued(n) => format!("{} operation(s) waiting in the offline queue", n),
+            Issue::DeadLetters(n) => {
+                format!("{} queued operation(s) gave up (auxin queue deadletter list)", n)
+            }
+            Issue::Interrupted(n) => {
+                format!("{} operation(s) interrupted (auxin sync-status --recover)", n)
+            }
+            Issue::Uploads(repos) => format!("Upload interrupted: {}", repos.join(", ")),
+        }
+    }
+
+    /// The step that fixes this for `project`, if wrapup can take it
+    pub fn fix(&self, project: Option<&Path>) -> Option<WrapupStep> {
+        match self {
+            Issue::Uncommitted(_) => Some(WrapupStep::Commit),
+            Issue::LockHeld => Some(WrapupStep::ReleaseLock),
+            Issue::Queued(_) => Some(WrapupStep::SyncQueue),
+            Issue::Uploads(repos) => {
+                let project = project?.to_string_lossy();
+                repos.iter().any(|r| *r == project).then_some(WrapupStep::Push)
+            }
+            Issue::DeadLetters(_) | Issue::Interrupted(_) => None,
+        }
+    }
+}
+
+impl WrapupStep {
+    /// Question asked before taking the step
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            WrapupStep::Commit => "Commit all changes now?",
+            WrapupStep::Push => "Push the project now?",
+            WrapupStep::SyncQueue => "Sync the offline queue now?",
+            WrapupStep::ReleaseLock => "Release the lock so others can work on the project?",
+        }
+    }
+
+    /// What the step does, for "Couldn't ..." messages
+    pub fn action(&self) -> &'static str {
+        match self {
+            WrapupStep::Commit => "commit",
+            WrapupStep::Push => "push",
+            WrapupStep::SyncQueue => "sync the offline queue",
+            WrapupStep::ReleaseLock => "release the lock",
+        }
+    }
+
+    pub fn done(&self) -> &'static str {
+        match self {
+            WrapupStep::Commit => "Committed",
+            WrapupStep::Push => "Pushed",
+            WrapupStep::SyncQueue => "Offline queue synced",
+            WrapupStep::ReleaseLock => "Lock released",
+        }
+    }
+}
+
+/// The steps that fix `issues` in `project`, in order
+///
+/// A commit made while wrapping up has to be pushed too, and the lock is
+/// released last so nobody takes it before the work reaches the server.
+pub fn plan(issues: &[Issue], project: Option<&Path>) -> Vec<WrapupStep> {
+    let mut steps: Vec<WrapupStep> = issues.iter().filter_map(|i| i.fix(project)).collect();
+    if let Some(commit) = steps.iter().position(|s| *s == WrapupStep::Commit) {
+        if !steps.contains(&WrapupStep::Push) {
+            steps.insert(commit + 1, WrapupStep::Push);
+        }
+    }
+    steps
+}
+
+/// What stands between the user and shutting down
+#[derive(Debug, Clone)]
+pub struct WrapupCheck {
+    /// Repository the check ran in, if any
+    pub project: Option<PathBuf>,
+    pub uncommitted: usize,
+    pub lock: LockState,
+    pub sync: SyncStatus,
+}
+
+impl WrapupCheck {
+    pub fn run(project: Option<&Path>) -> Self {
+        let uncommitted = project
+            .and_then(|p| OxenSubprocess::new().status(p).ok())
+            .map_or(0, |s| s.modified.len() + s.untracked.len() + s.staged.len());
+        let lock = project.map_or(LockState::Unlocked, quick_actions::lock_state);
+
+        Self {
+            project: project.map(Path::to_path_buf),
+            uncommitted,
+            lock,
+            sync: SyncStatus::gather(project),
+        }
+    }
+
+    /// Everything that makes shutting down unsafe, in the order to fix it:
+    /// commit before pushing, push before letting go of the lock
+    pub fn issues(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        if self.uncommitted > 0 {
+            issues.push(Issue::Uncommitted(self.uncommitted));
+        }
+        if !self.sync.unfinished.is_empty() {
+            issues.push(Issue::Interrupted(self.sync.unfinished.len()));
+        }
+        if !self.sync.uploads.is_empty() {
+            let repos = self.sync.uploads.iter().map(|u| u.repo_path.clone()).collect();
+            issues.push(Issue::Uploads(repos));
+        }
+        if !self.sync.queued.is_empty() {
+            issues.push(Issue::Queued(self.sync.queued.len()));
+        }
+        if self.sync.dead_letters > 0 {
+            issues.push(Issue::DeadLetters(self.sync.dead_letters));
+        }
+        if self.lock == LockState::Mine {
+            issues.push(Issue::LockHeld);
+        }
+        issues
+    }
+
+    pub fn is_safe(&self) -> bool {
+        self.issues().is_empty()
+    }
+}
+
+/// Stage everything in `project` and commit it with `message` and the
+/// project's current metadata, through `auxin commit` like the quick actions
+///
+/// The lock stays held whatever `[lock] release_on` says; wrapup releases
+/// it itself once the commit is pushed.
+pub fn commit_all(project: &Path, auxin_exe: &Path, message: &str) -> Result<()> {
+    crate::operation_lock::OperationLock::ensure_free(project)?;
+    crate::stage_policy::stage_all(project, &Config::load().unwrap_or_default())?;
+
+    let mut args = vec!["commit".to_string(), "-m".to_string(), message.to_string()];
+    args.push("--keep-lock".to_string());
+    args.extend(current_metadata_args(project));
+    quick_actions::run_auxin(auxin_exe, project, &args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunked_upload::UploadSession;
+    use crate::daemon_client::DaemonStatus;
+    use crate::network_resilience::ConnectivityState;
+    use crate::sync_status::ServerReach;
+
+    #[test]
+    fn test_issues_in_fix_order() {
+        let project = PathBuf::from("/music/Song.logicx");
+        let mut check = WrapupCheck {
+            project: Some(project.clone()),
+            uncommitted: 0,
+            lock: LockState::Other("alex".to_string()),
+            sync: SyncStatus {
+                queued: Vec::new(),
+                dead_letters: 0,
+                unfinished: Vec::new(),
+                uploads: Vec::new(),
+                daemon: DaemonStatus::default(),
+                daemon_warning: None,
+                paused: None,
+                network: ConnectivityState::Offline,
+                server_url: String::new(),
+                server: ServerReach::NotConfigured,
+            },
+        };
+        // Someone else's lock, a stopped daemon and no network don't matter
+        assert!(check.is_safe());
+
+        check.uncommitted = 3;
+        check.lock = LockState::Mine;
+        check.sync.dead_letters = 1;
+        check.sync.uploads.push(UploadSession::new(&project, "origin", "main"));
+        check.sync.uploads.push(UploadSession::new(Path::new("/music/Other"), "origin", "main"));
+        let issues = check.issues();
+        assert_eq!(
+            issues.iter().map(|i| i.fix(Some(&project))).collect::<Vec<_>>(),
+            vec![
+                Some(WrapupStep::Commit),
+                Some(WrapupStep::Push),
+                None,
+                Some(WrapupStep::ReleaseLock),
+            ]
+        );
+        // Another project's upload can't be pushed from here
+        assert_eq!(issues[1].fix(Some(Path::new("/music/Third"))), None);
+
+        // A fresh commit gets pushed before the lock goes
+        assert_eq!(
+            plan(&issues, Some(Path::new("/music/Third"))),
+            vec![WrapupStep::Commit, WrapupStep::Push, WrapupStep::ReleaseLock]
+        );
+    }
+}
@@ -292,6 +292,14 @@ impl WriteAheadLog {
         Ok(entries.into_iter().find(|e| e.id == entry_id))
     }
 
+    /// Get the most recently created entries, newest first
+    pub fn recent_entries(&self, limit: usize) -> Result<Vec<WalEntry>> {
+        let mut entries = self.load_entries()?;
+        entries.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
     /// Check if there are any incomplete operations that need recovery
     pub fn needs_recovery(&self) -> Result<bool> {
         let incomplete = self.get_incomplete_entries()?;
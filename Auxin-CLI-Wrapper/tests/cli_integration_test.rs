@@ -14,6 +14,19 @@ fn run_cli(args: &[&str]) -> std::process::Output {
         .expect("Failed to execute command")
 }
 
+// Helper to run CLI commands with HOME (and so ~/.auxin) in `home`
+fn run_cli_in_home(home: &std::path::Path, args: &[&str]) -> std::process::Output {
+    Command::new("cargo")
+        .arg("run")
+        .arg("--bin")
+        .arg("auxin")
+        .arg("--")
+        .args(args)
+        .env("HOME", home)
+        .output()
+        .expect("Failed to execute command")
+}
+
 // Helper to check if command succeeded
 fn command_succeeded(output: &std::process::Output) -> bool {
     output.status.success()
@@ -124,6 +137,19 @@ mod cli_tests {
         );
     }
 
+    #[test]
+    fn test_init_command_lists_supported_types() {
+        let output = run_cli(&["init", "--type", "ableton", "Set.als"]);
+        let stderr = get_stderr(&output);
+
+        assert!(!command_succeeded(&output), "Unknown type should fail");
+        assert!(
+            stderr.contains("auto, logicpro, sketchup, blender"),
+            "Should list the registered types: {}",
+            stderr
+        );
+    }
+
     // MARK: - Add Command Tests
 
     #[test]
@@ -221,6 +247,29 @@ mod cli_tests {
         assert!(stdout.contains("--tags"), "Should document tags flag");
     }
 
+    #[test]
+    fn test_commit_command_recognizes_auto_metadata_flag() {
+        let output = run_cli(&["commit", "--help"]);
+        let stdout = get_stdout(&output);
+
+        assert!(
+            stdout.contains("--auto-metadata"),
+            "Should document auto-metadata flag"
+        );
+    }
+
+    #[test]
+    fn test_commit_command_recognizes_field_flag() {
+        let output = run_cli(&["commit", "--help"]);
+        let stdout = get_stdout(&output);
+
+        assert!(stdout.contains("--field"), "Should document custom field flag");
+        assert!(
+            stdout.contains("metadata_fields"),
+            "Should explain where fields are defined"
+        );
+    }
+
     // MARK: - Log Command Tests
 
     #[test]
@@ -246,6 +295,15 @@ mod cli_tests {
         );
     }
 
+    #[test]
+    fn test_log_command_recognizes_date_flags() {
+        let output = run_cli(&["log", "--help"]);
+        let stdout = get_stdout(&output);
+
+        assert!(stdout.contains("--since"), "Should document since flag");
+        assert!(stdout.contains("--until"), "Should document until flag");
+    }
+
     #[test]
     fn test_log_command_works_without_limit() {
         // This tests that log command is recognized even without --limit
@@ -309,6 +367,40 @@ mod cli_tests {
         );
     }
 
+    // MARK: - Diff Command Tests
+
+    #[test]
+    fn test_diff_command_accepts_two_commits() {
+        let output = run_cli(&["diff", "--help"]);
+        let stdout = get_stdout(&output);
+
+        assert!(command_succeeded(&output), "Diff help should succeed");
+        assert!(stdout.contains("COMMIT_B"), "Should document second commit");
+        assert!(
+            !stdout.contains("future enhancement"),
+            "Two-commit diff should be available"
+        );
+    }
+
+    // MARK: - Resolve Command Tests
+
+    #[test]
+    fn test_resolve_command_strategies() {
+        let output = run_cli(&["resolve", "--help"]);
+        let stdout = get_stdout(&output);
+
+        assert!(command_succeeded(&output), "Resolve help should succeed");
+        for flag in ["--ours", "--theirs", "--keep-both"] {
+            assert!(stdout.contains(flag), "Should document {}", flag);
+        }
+
+        let output = run_cli(&["resolve", "--ours", "--theirs"]);
+        assert!(
+            !command_succeeded(&output),
+            "Only one strategy should be accepted"
+        );
+    }
+
     // MARK: - Invalid Command Tests
 
     #[test]
@@ -420,4 +512,104 @@ mod cli_tests {
             );
         }
     }
+
+    // MARK: - History, Workflow, Snapshot and Recovery Tests
+
+    #[test]
+    fn test_recovery_guides() {
+        for (scenario, expected) in [
+            ("push", "Failed push recovery steps"),
+            ("pull", "Failed pull recovery steps"),
+            ("lock", "Lock conflict recovery steps"),
+        ] {
+            let output = run_cli(&["recovery", scenario]);
+            assert!(
+                command_succeeded(&output),
+                "recovery {} should succeed: {}",
+                scenario,
+                get_stderr(&output)
+            );
+            assert!(get_stdout(&output).contains(expected));
+        }
+    }
+
+    #[test]
+    fn test_snapshot_create_list_delete() {
+        let home = tempfile::TempDir::new().unwrap();
+        let project = tempfile::TempDir::new().unwrap();
+        let project_path = project.path().to_str().unwrap();
+
+        let output = run_cli_in_home(
+            home.path(),
+            &["snapshot", "create", project_path, "Before mix"],
+        );
+        assert!(command_succeeded(&output), "{}", get_stderr(&output));
+
+        let snapshots = home.path().join(".auxin").join("snapshots");
+        let ids: Vec<String> = std::fs::read_dir(&snapshots)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(ids.len(), 1);
+
+        let output = run_cli_in_home(home.path(), &["snapshot", "list", "--repo", project_path]);
+        assert!(command_succeeded(&output));
+        let stdout = get_stdout(&output);
+        assert!(stdout.contains("Before mix"));
+        assert!(stdout.contains(&ids[0][..8]));
+
+        let output = run_cli_in_home(home.path(), &["snapshot", "restore", &ids[0]]);
+        assert!(command_succeeded(&output));
+        assert!(get_stdout(&output).contains("To restore from snapshot"));
+
+        let output = run_cli_in_home(home.path(), &["snapshot", "delete", &ids[0]]);
+        assert!(command_succeeded(&output));
+        assert_eq!(std::fs::read_dir(&snapshots).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_history_and_workflow_without_state() {
+        let home = tempfile::TempDir::new().unwrap();
+
+        for args in [
+            &["history", "view"][..],
+            &["history", "stats"],
+            &["workflow", "config"],
+        ] {
+            let output = run_cli_in_home(home.path(), args);
+            assert!(
+                command_succeeded(&output),
+                "{:?} should succeed: {}",
+                args,
+                get_stderr(&output)
+            );
+            assert!(!get_stderr(&output).contains("not yet implemented"));
+        }
+
+        let csv = home.path().join("history.csv");
+        let output = run_cli_in_home(home.path(), &["history", "export", csv.to_str().unwrap()]);
+        assert!(command_succeeded(&output), "{}", get_stderr(&output));
+        assert!(std::fs::read_to_string(&csv)
+            .unwrap()
+            .starts_with("Timestamp,Operation"));
+    }
+
+    // MARK: - JSON Output Tests
+
+    #[test]
+    fn test_global_json_flag_before_and_after_command() {
+        let home = tempfile::TempDir::new().unwrap();
+
+        for args in [
+            &["--json", "queue", "status"][..],
+            &["queue", "status", "--json"],
+        ] {
+            let output = run_cli_in_home(home.path(), args);
+            assert!(command_succeeded(&output), "{}", get_stderr(&output));
+            let report: serde_json::Value = serde_json::from_str(&get_stdout(&output))
+                .unwrap_or_else(|e| panic!("{:?} didn't print JSON: {}", args, e));
+            assert_eq!(report["stats"]["pending"], 0);
+            assert_eq!(report["pending"], serde_json::json!([]));
+        }
+    }
 }
@@ -0,0 +1,541 @@
+//! Serialization compatibility of auxin's stored and exchanged types
+//!
+//! `CommitMetadata`, `BounceMetadata`, `QueueEntry`, `Snapshot`,
+//! `UploadSession` and `DownloadSession` are written to disk and sent
+//! between the CLI, daemon, app and server, often by different auxin
+//! versions. Their JSON is part of the public API, with this stability
+//! policy:
+//!
+//! - Round trips are lossless: deserializing what was serialized and
+//!   serializing it again gives the same JSON
+//! - Field names and enum spellings don't change; a rename is a new field
+//! - New fields are optional, so documents from older versions still load
+//! - Fields a version doesn't know are kept in `other` and written back, so
+//!   an older CLI rewriting a queue or snapshot doesn't drop a newer one's
+//!   data
+//! - Removing a field, or changing its type, is a breaking change and needs
+//!   a `state_migration` step for the stores that hold it
+//!
+//! Each type's `other` map holds those unknown fields. Commit metadata
+//! sidecars (`StoredMetadata`) follow the same policy: their `version` only
+//! records which format wrote them, and one from a newer format still loads.
+//!
+//! The golden documents below are the current format. If one stops
+//! loading, the change breaks existing files; if only its re-serialized
+//! form changes, update it along with a note in the changelog.
+
+use auxin::chunked_upload::FileUploadState;
+use auxin::offline_queue::DeadLetter;
+use auxin::{
+    AudioFormat, BounceMetadata, CommitMetadata, DownloadSession, DownloadStatus,
+    OfflineQueuedOperation, QueueEntry, Snapshot, SnapshotType, StoredMetadata, UploadSession,
+    UploadStatus,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use proptest::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// Serialize, deserialize and serialize again; both JSON forms must match
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Result<(), TestCaseError> {
+    let json = serde_json::to_string(value).unwrap();
+    let back: T =
+        serde_json::from_str(&json).map_err(|e| TestCaseError::fail(format!("{}: {}", e, json)))?;
+    prop_assert_eq!(
+        serde_json::to_value(&back).unwrap(),
+        serde_json::to_value(value).unwrap()
+    );
+    Ok(())
+}
+
+/// Add `unknown` fields to the JSON of `value`; they must survive a rewrite
+fn preserves_unknown<T: Serialize + DeserializeOwned>(
+    value: &T,
+    unknown: &BTreeMap<String, Value>,
+) -> Result<(), TestCaseError> {
+    let mut document = serde_json::to_value(value).unwrap();
+    let fields = document.as_object_mut().unwrap();
+    for (key, field) in unknown {
+        fields.insert(key.clone(), field.clone());
+    }
+    let back: T = serde_json::from_value(document.clone())
+        .map_err(|e| TestCaseError::fail(format!("{}: {}", e, document)))?;
+    prop_assert_eq!(serde_json::to_value(&back).unwrap(), document);
+    Ok(())
+}
+
+/// A document from the current version must load and re-serialize unchanged
+fn golden<T: Serialize + DeserializeOwned>(document: Value) -> T {
+    let value: T = serde_json::from_value(document.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&value).unwrap(), document);
+    value
+}
+
+fn time() -> impl Strategy<Value = DateTime<Utc>> {
+    (0i64..4_102_444_800, 0u32..1_000_000_000)
+        .prop_map(|(secs, nanos)| Utc.timestamp_opt(secs, nanos).unwrap())
+}
+
+fn text() -> impl Strategy<Value = String> {
+    "\\PC{0,24}"
+}
+
+/// Fields a future version might add; prefixed so they can't collide
+fn unknown_fields() -> impl Strategy<Value = BTreeMap<String, Value>> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        text().prop_map(Value::from),
+    ];
+    let value = leaf.prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::from),
+            prop::collection::btree_map("[a-z]{1,8}", inner, 0..4)
+                .prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    });
+    prop::collection::btree_map("future_[a-z_]{1,12}", value, 1..4)
+}
+
+prop_compose! {
+    fn commit_metadata()(
+        message in text(),
+        bpm in prop::option::of((2000u32..40000).prop_map(|n| n as f32 / 100.0)),
+        sample_rate in prop::option::of(prop_oneof![Just(44100u32), Just(48000), Just(96000), any::<u32>()]),
+        key_signature in prop::option::of(text()),
+        tags in prop::collection::vec(text(), 0..5),
+        timestamp in prop::option::of(any::<i64>()),
+        thumbnail_path in prop::option::of(text()),
+        bounce_path in prop::option::of(text()),
+        custom in prop::collection::btree_map(text(), text(), 0..3),
+    ) -> CommitMetadata {
+        let mut metadata = CommitMetadata::new(message);
+        metadata.bpm = bpm;
+        metadata.sample_rate = sample_rate;
+        metadata.key_signature = key_signature;
+        metadata.tags = tags;
+        metadata.timestamp = timestamp;
+        metadata.thumbnail_path = thumbnail_path;
+        metadata.bounce_path = bounce_path;
+        metadata.custom = custom;
+        metadata
+    }
+}
+
+prop_compose! {
+    fn bounce_metadata()(
+        commit_id in "[0-9a-f]{7,40}",
+        original_filename in text(),
+        format in prop_oneof![
+            Just(AudioFormat::Wav),
+            Just(AudioFormat::Aiff),
+            Just(AudioFormat::Mp3),
+            Just(AudioFormat::Flac),
+            Just(AudioFormat::M4a),
+        ],
+        size_bytes in any::<u64>(),
+        duration_secs in prop::option::of((0u64..100_000_000).prop_map(|ms| ms as f64 / 1000.0)),
+        sample_rate in prop::option::of(any::<u32>()),
+        bit_depth in prop::option::of(any::<u16>()),
+        channels in prop::option::of(any::<u8>()),
+        added_at in time(),
+        added_by in text(),
+        description in prop::option::of(text()),
+        fingerprint in prop::option::of("[0-9a-f]{64}"),
+    ) -> BounceMetadata {
+        BounceMetadata {
+            commit_id,
+            original_filename,
+            format,
+            size_bytes,
+            duration_secs,
+            sample_rate,
+            bit_depth,
+            channels,
+            added_at,
+            added_by,
+            description,
+            fingerprint,
+            other: BTreeMap::new(),
+        }
+    }
+}
+
+fn queued_operation() -> impl Strategy<Value = OfflineQueuedOperation> {
+    prop_oneof![
+        (text(), text(), any::<u32>()).prop_map(|(project_path, user_id, timeout_hours)| {
+            OfflineQueuedOperation::AcquireLock {
+                project_path,
+                user_id,
+                timeout_hours,
+            }
+        }),
+        (text(), text()).prop_map(|(project_path, lock_id)| {
+            OfflineQueuedOperation::ReleaseLock {
+                project_path,
+                lock_id,
+            }
+        }),
+        (text(), text(), any::<u32>()).prop_map(|(project_path, lock_id, additional_hours)| {
+            OfflineQueuedOperation::RenewLock {
+                project_path,
+                lock_id,
+                additional_hours,
+            }
+        }),
+        (text(), text()).prop_map(|(repo_path, branch)| {
+            OfflineQueuedOperation::PushCommits { repo_path, branch }
+        }),
+        (text(), text()).prop_map(|(repo_path, branch)| {
+            OfflineQueuedOperation::PullCommits { repo_path, branch }
+        }),
+        text().prop_map(|repo_path| OfflineQueuedOperation::SyncComments { repo_path }),
+    ]
+}
+
+prop_compose! {
+    fn queue_entry()(
+        operation in queued_operation(),
+        id in text(),
+        queued_at in time(),
+        attempts in any::<u32>(),
+        last_attempt in prop::option::of(time()),
+        last_error in prop::option::of(text()),
+        priority in any::<i32>(),
+        completed in any::<bool>(),
+        dead_letter in prop::option::of((time(), prop::collection::vec(text(), 0..4))),
+    ) -> QueueEntry {
+        let mut entry = QueueEntry::new(operation);
+        entry.id = id;
+        entry.queued_at = queued_at;
+        entry.attempts = attempts;
+        entry.last_attempt = last_attempt;
+        entry.last_error = last_error;
+        entry.priority = priority;
+        entry.completed = completed;
+        entry.dead_letter = dead_letter.map(|(at, error_chain)| DeadLetter { at, error_chain });
+        entry
+    }
+}
+
+prop_compose! {
+    fn snapshot()(
+        id in text(),
+        created_at in time(),
+        snapshot_type in prop_oneof![
+            Just(SnapshotType::Manual),
+            Just(SnapshotType::AutoBeforePush),
+            Just(SnapshotType::AutoBeforePull),
+            Just(SnapshotType::AutoBeforeLockBreak),
+            Just(SnapshotType::AutoBeforeRollback),
+            Just(SnapshotType::AnomalyDetected),
+            Just(SnapshotType::Scheduled),
+        ],
+        repo_path in text(),
+        commit_id in prop::option::of("[0-9a-f]{7,40}"),
+        description in text(),
+        metadata in prop::collection::hash_map(text(), text(), 0..4),
+    ) -> Snapshot {
+        let mut snapshot = Snapshot::new(snapshot_type, repo_path);
+        snapshot.id = id;
+        snapshot.created_at = created_at;
+        snapshot.commit_id = commit_id;
+        snapshot.description = description;
+        snapshot.metadata = metadata;
+        snapshot
+    }
+}
+
+fn upload_status() -> impl Strategy<Value = UploadStatus> {
+    prop_oneof![
+        Just(UploadStatus::Pending),
+        Just(UploadStatus::InProgress),
+        Just(UploadStatus::Completed),
+        Just(UploadStatus::Failed),
+        Just(UploadStatus::Aborted),
+    ]
+}
+
+prop_compose! {
+    fn file_upload_state()(
+        path in text(),
+        size in any::<u64>(),
+        bytes_uploaded in any::<u64>(),
+        status in upload_status(),
+        last_error in prop::option::of(text()),
+        started_at in time(),
+        last_activity in time(),
+    ) -> FileUploadState {
+        FileUploadState {
+            path,
+            size,
+            bytes_uploaded,
+            status,
+            last_error,
+            started_at,
+            last_activity,
+        }
+    }
+}
+
+prop_compose! {
+    fn upload_session()(
+        id in text(),
+        repo_path in text(),
+        remote in text(),
+        branch in text(),
+        files in prop::collection::vec(file_upload_state(), 0..4),
+        total_bytes in any::<u64>(),
+        bytes_uploaded in any::<u64>(),
+        started_at in time(),
+        last_activity in time(),
+        status in upload_status(),
+        bandwidth_samples in prop::collection::vec((0u64..1 << 40).prop_map(|n| n as f64 / 8.0), 0..8),
+    ) -> UploadSession {
+        let mut session = UploadSession::new(&PathBuf::from(repo_path), &remote, &branch);
+        session.id = id;
+        session.files = files;
+        session.total_bytes = total_bytes;
+        session.bytes_uploaded = bytes_uploaded;
+        session.started_at = started_at;
+        session.last_activity = last_activity;
+        session.status = status;
+        session.bandwidth_samples = bandwidth_samples;
+        session
+    }
+}
+
+prop_compose! {
+    fn download_session()(
+        id in text(),
+        repo_path in text(),
+        remote in text(),
+        branch in text(),
+        bytes_downloaded in any::<u64>(),
+        attempts in any::<u32>(),
+        started_at in time(),
+        last_activity in time(),
+        status in prop_oneof![
+            Just(DownloadStatus::Pending),
+            Just(DownloadStatus::InProgress),
+            Just(DownloadStatus::Completed),
+            Just(DownloadStatus::Failed),
+        ],
+        last_error in prop::option::of(text()),
+        bandwidth_samples in prop::collection::vec((0u64..1 << 40).prop_map(|n| n as f64 / 8.0), 0..8),
+    ) -> DownloadSession {
+        let mut session = DownloadSession::new(&PathBuf::from(repo_path), &remote, &branch);
+        session.id = id;
+        session.bytes_downloaded = bytes_downloaded;
+        session.attempts = attempts;
+        session.started_at = started_at;
+        session.last_activity = last_activity;
+        session.status = status;
+        session.last_error = last_error;
+        session.bandwidth_samples = bandwidth_samples;
+        session
+    }
+}
+
+proptest! {
+    #[test]
+    fn commit_metadata_round_trips(value in commit_metadata(), unknown in unknown_fields()) {
+        round_trip(&value)?;
+        preserves_unknown(&value, &unknown)?;
+    }
+
+    #[test]
+    fn bounce_metadata_round_trips(value in bounce_metadata(), unknown in unknown_fields()) {
+        round_trip(&value)?;
+        preserves_unknown(&value, &unknown)?;
+    }
+
+    #[test]
+    fn queue_entry_round_trips(value in queue_entry(), unknown in unknown_fields()) {
+        round_trip(&value)?;
+        preserves_unknown(&value, &unknown)?;
+    }
+
+    #[test]
+    fn snapshot_round_trips(value in snapshot(), unknown in unknown_fields()) {
+        round_trip(&value)?;
+        preserves_unknown(&value, &unknown)?;
+    }
+
+    #[test]
+    fn upload_session_round_trips(value in upload_session(), unknown in unknown_fields()) {
+        round_trip(&value)?;
+        preserves_unknown(&value, &unknown)?;
+    }
+
+    #[test]
+    fn download_session_round_trips(value in download_session(), unknown in unknown_fields()) {
+        round_trip(&value)?;
+        preserves_unknown(&value, &unknown)?;
+    }
+}
+
+#[test]
+fn golden_commit_metadata() {
+    let metadata: CommitMetadata = golden(json!({
+        "message": "Final mix",
+        "bpm": 128.0,
+        "sample_rate": 48000,
+        "key_signature": "A Minor",
+        "tags": ["mix", "milestone"],
+        "timestamp": 1760693400,
+        "bounce_path": "abc1234.wav"
+    }));
+    assert_eq!(metadata.bpm, Some(128.0));
+
+    // Written before the optional fields existed
+    let older: CommitMetadata = serde_json::from_value(json!({
+        "message": "Rough mix",
+        "bpm": null,
+        "sample_rate": null,
+        "key_signature": null,
+        "tags": []
+    }))
+    .unwrap();
+    assert!(older.timestamp.is_none() && older.other.is_empty());
+}
+
+#[test]
+fn golden_bounce_metadata() {
+    let bounce: BounceMetadata = golden(json!({
+        "commit_id": "abc1234",
+        "original_filename": "Mix v3.wav",
+        "format": "wav",
+        "size_bytes": 52428800,
+        "duration_secs": 212.5,
+        "sample_rate": 48000,
+        "bit_depth": 24,
+        "channels": 2,
+        "added_at": "2026-10-17T09:30:00Z",
+        "added_by": "me@studio-mac",
+        "description": "Client review",
+        "fingerprint": null
+    }));
+    assert_eq!(bounce.format, AudioFormat::Wav);
+}
+
+#[test]
+fn golden_queue_entry() {
+    let entry: QueueEntry = golden(json!({
+        "id": "3f2b8c1e-0000-4000-8000-000000000000",
+        "operation": {
+            "ReleaseLock": { "project_path": "/Music/Song.logicx", "lock_id": "lock-1" }
+        },
+        "queued_at": "2026-10-17T09:30:00Z",
+        "attempts": 2,
+        "last_attempt": "2026-10-17T09:35:00Z",
+        "last_error": "connection refused",
+        "priority": 10,
+        "completed": false
+    }));
+    assert!(matches!(
+        entry.operation,
+        OfflineQueuedOperation::ReleaseLock { .. }
+    ));
+}
+
+#[test]
+fn golden_snapshot() {
+    let snapshot: Snapshot = golden(json!({
+        "id": "snap-1",
+        "created_at": "2026-10-17T09:30:00Z",
+        "snapshot_type": "AutoBeforePush",
+        "repo_path": "/Music/Song.logicx",
+        "commit_id": "abc1234",
+        "description": "Before push",
+        "metadata": { "branch": "main" }
+    }));
+    assert_eq!(snapshot.snapshot_type, SnapshotType::AutoBeforePush);
+    assert_eq!(
+        snapshot.metadata,
+        HashMap::from([("branch".to_string(), "main".to_string())])
+    );
+}
+
+#[test]
+fn golden_upload_session() {
+    let session: UploadSession = golden(json!({
+        "id": "session-1",
+        "repo_path": "/Music/Song.logicx",
+        "remote": "origin",
+        "branch": "main",
+        "files": [{
+            "path": "Audio Files/Vox.wav",
+            "size": 1000,
+            "bytes_uploaded": 500,
+            "status": "InProgress",
+            "last_error": null,
+            "started_at": "2026-10-17T09:30:00Z",
+            "last_activity": "2026-10-17T09:31:00Z"
+        }],
+        "total_bytes": 1000,
+        "bytes_uploaded": 500,
+        "started_at": "2026-10-17T09:30:00Z",
+        "last_activity": "2026-10-17T09:31:00Z",
+        "status": "InProgress",
+        "bandwidth_samples": [1048576.0]
+    }));
+    assert_eq!(session.files[0].status, UploadStatus::InProgress);
+}
+
+#[test]
+fn golden_download_session() {
+    let session: DownloadSession = golden(json!({
+        "id": "session-2",
+        "repo_path": "/Music/Song.logicx",
+        "remote": "origin",
+        "branch": "main",
+        "bytes_downloaded": 734003200,
+        "attempts": 2,
+        "started_at": "2026-10-17T09:30:00Z",
+        "last_activity": "2026-10-17T09:41:00Z",
+        "status": "Failed",
+        "last_error": "connection reset",
+        "bandwidth_samples": [2097152.0]
+    }));
+    assert_eq!(session.status, DownloadStatus::Failed);
+}
+
+#[test]
+fn golden_metadata_sidecar() {
+    let stored: StoredMetadata = golden(json!({
+        "version": 1,
+        "commit_id": "abc1234",
+        "source": "commit",
+        "metadata": {
+            "message": "Massing study",
+            "bpm": null,
+            "sample_rate": null,
+            "key_signature": null,
+            "tags": ["draft"]
+        },
+        "details": { "Units": "Meters" }
+    }));
+    assert_eq!(stored.detail("Units"), Some("Meters"));
+
+    // A newer format only adds fields
+    let newer: StoredMetadata = golden(json!({
+        "version": 2,
+        "commit_id": "abc1234",
+        "source": "message",
+        "metadata": {
+            "message": "Massing study",
+            "bpm": null,
+            "sample_rate": null,
+            "key_signature": null,
+            "tags": []
+        },
+        "details": {},
+        "reviewed_by": "sam"
+    }));
+    assert_eq!(newer.other["reviewed_by"], "sam");
+}
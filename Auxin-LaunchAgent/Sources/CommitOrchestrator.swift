@@ -7,6 +7,7 @@ public class CommitOrchestrator {
     // MARK: - Properties
 
     private let cliPath: String
+    private let eventHooks: DaemonEventHooks
     private var monitoredProjects: Set<String> = []
     private var isCommitting = false
     private let commitQueue = DispatchQueue(label: "com.oxen.logic.commit", qos: .userInitiated)
@@ -30,6 +31,7 @@ public class CommitOrchestrator {
     /// - Parameter cliPath: Path to auxin-cli binary
     public init(cliPath: String = "/usr/local/bin/auxin") {
         self.cliPath = cliPath
+        self.eventHooks = DaemonEventHooks(cliPath: cliPath)
         verifyCliExists()
     }
 
@@ -125,11 +127,23 @@ public class CommitOrchestrator {
             )
         }
 
+        // The tree is mid-change while auxin restore or pull runs
+        guard !isBeingRewritten(normalizedPath) else {
+            print("⚠️  Restore or pull in progress, skipping commit")
+            return CommitResult(
+                success: false,
+                commitId: nil,
+                message: "Restore or pull in progress",
+                duration: 0
+            )
+        }
+
         isCommitting = true
         defer { isCommitting = false }
 
         // Check if project has changes
         let hasChanges = await checkForChanges(at: normalizedPath)
+        await refreshBadges(at: normalizedPath)
 
         guard hasChanges else {
             print("No changes detected in \(projectPath)")
@@ -141,8 +155,28 @@ public class CommitOrchestrator {
             )
         }
 
+        // Don't version what looks like data loss over good history
+        if type != .manual, let reason = await checkForAnomalies(at: normalizedPath) {
+            print("⚠️  Auto-commits paused: \(reason)")
+            eventHooks.fire(
+                .autoCommitPaused,
+                projectPath: normalizedPath,
+                details: ["reason": reason]
+            )
+            return CommitResult(
+                success: false,
+                commitId: nil,
+                message: "Auto-commits paused: \(reason). Run 'auxin anomaly status' for details.",
+                duration: Date().timeIntervalSince(startTime)
+            )
+        }
+
         // Generate commit message based on type (use provided message or auto-generate)
-        let commitMessage = message ?? generateCommitMessage(for: type)
+        var commitMessage = message ?? generateCommitMessage(for: type)
+        if message == nil, type == .autoSave,
+           let title = await generateAutoTitle(projectPath: normalizedPath) {
+            commitMessage = title
+        }
 
         print("\(commitTypeIcon(type)) Creating \(commitTypeName(type))...")
         print("   Project: \(projectPath)")
@@ -159,8 +193,16 @@ public class CommitOrchestrator {
 
         if result.success {
             print("✓ Commit successful (\(String(format: "%.2f", duration))s)")
+            await refreshBadges(at: normalizedPath)
             if let commitId = result.commitId {
                 print("  Commit ID: \(commitId)")
+                if type == .autoSave {
+                    eventHooks.fire(
+                        .autoCommitCreated,
+                        projectPath: normalizedPath,
+                        details: ["commit-id": commitId, "message": commitMessage]
+                    )
+                }
             }
         } else {
             print("✗ Commit failed: \(result.message)")
@@ -207,6 +249,7 @@ public class CommitOrchestrator {
         let process = Process()
         process.executableURL = URL(fileURLWithPath: cliPath)
         process.currentDirectoryURL = URL(fileURLWithPath: projectPath)
+        process.environment = DaemonLog.environment(project: projectPath)
         process.arguments = ["status", "--porcelain"]
 
         let pipe = Pipe()
@@ -229,6 +272,67 @@ public class CommitOrchestrator {
         }
     }
 
+    /// Run `auxin anomaly check`, which pauses auto-commits after changes
+    /// that look like data loss
+    ///
+    /// Returns why auto-commits are paused, or nil to go ahead. Only the
+    /// check's paused exit code (3) stops a commit, so an older CLI without
+    /// the command never blocks one.
+    private func checkForAnomalies(at projectPath: String) async -> String? {
+        let process = Process()
+        process.executableURL = URL(fileURLWithPath: cliPath)
+        process.currentDirectoryURL = URL(fileURLWithPath: projectPath)
+        process.environment = DaemonLog.environment(project: projectPath)
+        process.arguments = ["anomaly", "check"]
+
+        let pipe = Pipe()
+        process.standardOutput = pipe
+        process.standardError = pipe
+
+        do {
+            try process.run()
+            let data = pipe.fileHandleForReading.readDataToEndOfFile()
+            process.waitUntilExit()
+
+            guard process.terminationStatus == 3 else { return nil }
+
+            // The reasons are listed as "  • <reason>"
+            let output = String(data: data, encoding: .utf8) ?? ""
+            let reasons = output
+                .split(separator: "\n")
+                .map { $0.trimmingCharacters(in: .whitespaces) }
+                .filter { $0.hasPrefix("•") }
+                .map { $0.dropFirst().trimmingCharacters(in: .whitespaces) }
+            return reasons.isEmpty ? "suspicious change detected" : reasons.joined(separator: "; ")
+
+        } catch {
+            print("⚠️  Failed to check for anomalies: \(error)")
+            return nil
+        }
+    }
+
+    /// Whether `auxin restore` or `auxin pull` holds `.auxin/operation.lock`
+    private func isBeingRewritten(_ projectPath: String) -> Bool {
+        let lockPath = (projectPath as NSString).appendingPathComponent(".auxin/operation.lock")
+        let fd = open(lockPath, O_RDONLY)
+        guard fd >= 0 else { return false }
+        defer { close(fd) }
+
+        if flock(fd, LOCK_SH | LOCK_NB) == 0 {
+            flock(fd, LOCK_UN)
+            return false
+        }
+        return errno == EWOULDBLOCK
+    }
+
+    /// Rewrite `.auxin/badges.json`, read by Finder badge extensions
+    private func refreshBadges(at projectPath: String) async {
+        let result = await runCliCommand(projectPath: projectPath, arguments: ["badges"])
+        if !result.success {
+            print("⚠️  Failed to refresh badges: \(result.output.trimmingCharacters(in: .whitespacesAndNewlines))")
+        }
+    }
+
     private func executeCommit(
         projectPath: String,
         message: String,
@@ -324,6 +428,7 @@ public class CommitOrchestrator {
         let process = Process()
         process.executableURL = URL(fileURLWithPath: cliPath)
         process.currentDirectoryURL = URL(fileURLWithPath: projectPath)
+        process.environment = DaemonLog.environment(project: projectPath)
         process.arguments = arguments
 
         let pipe = Pipe()
@@ -382,6 +487,20 @@ public class CommitOrchestrator {
         }
     }
 
+    /// Describe the detected changes via `auxin auto-title`
+    ///
+    /// Returns nil when titles are disabled, nothing could be described, or
+    /// the CLI is too old to know the command.
+    private func generateAutoTitle(projectPath: String) async -> String? {
+        let result = await runCliCommand(projectPath: projectPath, arguments: ["auto-title"])
+        guard result.success else { return nil }
+
+        return result.output
+            .split(separator: "\n")
+            .map { $0.trimmingCharacters(in: .whitespaces) }
+            .last { !$0.isEmpty }
+    }
+
     private func formatTimestamp(_ date: Date) -> String {
         let formatter = DateFormatter()
         formatter.dateStyle = .short
@@ -407,6 +526,18 @@ public class CommitOrchestrator {
 
     // MARK: - Draft Branch Management
 
+    /// Whether the project's `.auxin/features.toml` keeps the draft workflow on
+    /// - Parameter projectPath: Path to Logic Pro project
+    /// - Returns: false only if `auxin features get draft_workflow` says "off"
+    public func usesDraftWorkflow(at projectPath: String) async -> Bool {
+        let result = await runCliCommand(
+            projectPath: (projectPath as NSString).standardizingPath,
+            arguments: ["features", "get", "draft_workflow"]
+        )
+        let value = result.output.trimmingCharacters(in: .whitespacesAndNewlines)
+        return !(result.success && value == "off")
+    }
+
     /// Ensure project is on the draft branch
     /// - Parameter projectPath: Path to Logic Pro project
     /// - Returns: true if on draft branch or successfully switched
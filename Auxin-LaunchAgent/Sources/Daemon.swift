@@ -10,15 +10,26 @@ public class OxenDaemon {
     private let orchestrator: CommitOrchestrator
     private let powerManager: PowerManagement
     private let networkMonitor: NetworkMonitor
+    private let eventHooks: DaemonEventHooks
+    private let oscIntegration: OSCIntegration
     private var xpcService: OxenDaemonXPCService?
+    private var healthSocket: HealthSocket?
     private var monitors: [String: FSEventsMonitor] = [:]
     private var projectTypes: [String: ProjectType] = [:]  // Track project types
     private var isRunning = false
+    private var isMonitoring = false  // initial project scan finished
+    private var lockExpiryTimer: DispatchSourceTimer?
+    private var reportedExpiringLocks: Set<String> = []  // lock IDs already reported
+    private var presenceTimer: DispatchSourceTimer?
+    private var presencePingFailing = false  // only log the first failure
+    private let startedAt = Date()
 
     // MARK: - Configuration
 
     private let cliPath: String
     private let debounceThreshold: TimeInterval
+    private let lockExpiryWarning: TimeInterval = 30 * 60
+    private let presenceInterval: TimeInterval = 5 * 60
 
     // MARK: - Initialization
 
@@ -31,6 +42,8 @@ public class OxenDaemon {
         self.orchestrator = CommitOrchestrator(cliPath: cliPath)
         self.powerManager = PowerManagement()
         self.networkMonitor = NetworkMonitor(cliPath: cliPath)
+        self.eventHooks = DaemonEventHooks(cliPath: cliPath)
+        self.oscIntegration = OSCIntegration(cliPath: cliPath)
 
         printBanner()
     }
@@ -62,6 +75,8 @@ public class OxenDaemon {
         let xpc = OxenDaemonXPCService(orchestrator: orchestrator)
         xpc.start()
         self.xpcService = xpc
+        DaemonVersion.writeInfoFile()
+        startHealthSocket()
 
         // 4. Scan for existing projects (Logic Pro, SketchUp, Blender)
         print("[4/5] Scanning for creative projects...")
@@ -70,6 +85,8 @@ public class OxenDaemon {
         // 5. Start monitoring registered projects
         print("[5/5] Starting file system monitors...")
         await startMonitoring()
+        startLockExpiryChecks()
+        startPresencePings()
 
         print("\n✓ Daemon started successfully")
         printStatus()
@@ -119,8 +136,18 @@ public class OxenDaemon {
         }
         monitors.removeAll()
 
+        lockExpiryTimer?.cancel()
+        lockExpiryTimer = nil
+        presenceTimer?.cancel()
+        presenceTimer = nil
+        oscIntegration.stop()
+        isMonitoring = false
+
         // Stop XPC service
         xpcService?.stop()
+        DaemonVersion.removeInfoFile()
+        healthSocket?.stop()
+        healthSocket = nil
 
         // Stop network monitoring
         networkMonitor.stopMonitoring()
@@ -149,8 +176,15 @@ public class OxenDaemon {
         print("\n📁 Registering project: \(projectPath)")
         print("  Type: \(projectType.displayName)")
 
-        // Ensure on draft branch
-        if await orchestrator.ensureOnDraftBranch(at: normalizedPath) {
+        if isArchived(normalizedPath) {
+            print("  Archived (read-only); not watching")
+            return
+        }
+
+        // Ensure on draft branch, unless the project turned the workflow off
+        if !(await orchestrator.usesDraftWorkflow(at: normalizedPath)) {
+            print("  Draft workflow off for this project")
+        } else if await orchestrator.ensureOnDraftBranch(at: normalizedPath) {
             print("  ✓ On draft branch")
         }
 
@@ -174,6 +208,9 @@ public class OxenDaemon {
         monitors[normalizedPath] = monitor
         projectTypes[normalizedPath] = projectType
         orchestrator.registerProject(normalizedPath)
+        if isMonitoring {
+            oscIntegration.update(projects: orchestrator.getRegisteredProjects())
+        }
 
         print("  ✓ Monitoring started")
     }
@@ -189,6 +226,9 @@ public class OxenDaemon {
 
         projectTypes.removeValue(forKey: normalizedPath)
         orchestrator.unregisterProject(normalizedPath)
+        if isMonitoring {
+            oscIntegration.update(projects: orchestrator.getRegisteredProjects())
+        }
         print("Unregistered project: \(projectPath)")
     }
 
@@ -196,6 +236,13 @@ public class OxenDaemon {
 
     /// Handle auto-commit triggered by FSEvents
     private func handleAutoCommit(for projectPath: String) async {
+        // Stop watching projects archived since they were registered
+        if isArchived(projectPath) {
+            print("📦 Project archived, no longer watching: \(projectPath)")
+            unregisterProject(projectPath)
+            return
+        }
+
         // Check if project is paused in XPC service
         if xpcService?.isPaused(projectPath) ?? false {
             print("⏸️  Auto-commit paused for: \(projectPath)")
@@ -217,6 +264,12 @@ public class OxenDaemon {
         await orchestrator.performEmergencyCommits()
     }
 
+    /// Whether `auxin archive mark` made the project read-only
+    private func isArchived(_ projectPath: String) -> Bool {
+        let markerPath = (projectPath as NSString).appendingPathComponent(".auxin/archive.json")
+        return FileManager.default.fileExists(atPath: markerPath)
+    }
+
     // MARK: - Project Discovery
 
     /// Scan common locations for creative projects (Logic Pro, SketchUp, Blender)
@@ -283,6 +336,7 @@ public class OxenDaemon {
     /// Start monitoring for all registered projects
     private func startMonitoring() async {
         let projects = orchestrator.getRegisteredProjects()
+        isMonitoring = true
 
         if projects.isEmpty {
             print("  No projects to monitor")
@@ -293,6 +347,124 @@ public class OxenDaemon {
         }
 
         print("  Starting \(projects.count) monitor(s)")
+        oscIntegration.update(projects: projects)
+    }
+
+    // MARK: - Lock Expiry
+
+    /// Periodically fire `lock-expiring` for locks this user holds
+    private func startLockExpiryChecks() {
+        let timer = DispatchSource.makeTimerSource(
+            queue: DispatchQueue(label: "com.auxin.lock-expiry", qos: .utility)
+        )
+        timer.schedule(deadline: .now() + 60, repeating: 300)
+        timer.setEventHandler { [weak self] in
+            self?.checkExpiringLocks()
+        }
+        timer.resume()
+        lockExpiryTimer = timer
+    }
+
+    private func checkExpiringLocks() {
+        for projectPath in orchestrator.getRegisteredProjects() {
+            guard let lock = LockManager.shared.ownLockExpiring(
+                projectPath: projectPath,
+                within: lockExpiryWarning
+            ), !reportedExpiringLocks.contains(lock.lockId) else {
+                continue
+            }
+
+            reportedExpiringLocks.insert(lock.lockId)
+            print("⏳ Lock on \(projectPath) expires in \(Int(lock.remainingTime / 60)) minutes")
+            eventHooks.fire(
+                .lockExpiring,
+                projectPath: projectPath,
+                details: [
+                    "locked-by": lock.lockedBy,
+                    "expires-at": ISO8601DateFormatter().string(from: lock.expiresAt)
+                ]
+            )
+        }
+    }
+
+    // MARK: - Server Presence
+
+    /// Ping the server every few minutes so it can tell this machine's locks
+    /// from ones left behind by a crash (`auxin daemon ping`, a no-op
+    /// unless server locks are enabled)
+    private func startPresencePings() {
+        let timer = DispatchSource.makeTimerSource(
+            queue: DispatchQueue(label: "com.auxin.presence", qos: .utility)
+        )
+        timer.schedule(deadline: .now() + 5, repeating: presenceInterval)
+        timer.setEventHandler { [weak self] in
+            self?.sendPresencePing()
+        }
+        timer.resume()
+        presenceTimer = timer
+    }
+
+    private func sendPresencePing() {
+        guard networkMonitor.isNetworkAvailable else { return }
+
+        let task = Process()
+        task.executableURL = URL(fileURLWithPath: cliPath)
+        task.arguments = ["daemon", "ping"]
+        task.environment = DaemonLog.environment()
+        task.standardInput = FileHandle.nullDevice
+        task.standardOutput = FileHandle.nullDevice
+        task.standardError = FileHandle.nullDevice
+
+        do {
+            try task.run()
+            task.waitUntilExit()
+            let failed = task.terminationStatus != 0
+            if failed && !presencePingFailing {
+                print("⚠️  Presence ping to the server failed; will keep retrying")
+            }
+            presencePingFailing = failed
+        } catch {
+            print("⚠️  Failed to run presence ping: \(error.localizedDescription)")
+        }
+    }
+
+    // MARK: - Health Check
+
+    /// Answer `auxin daemon health` and the watchdog on `~/.auxin/daemon.sock`
+    private func startHealthSocket() {
+        let socket = HealthSocket { [weak self] in
+            self?.healthSnapshot() ?? [:]
+        }
+        do {
+            try socket.start()
+            healthSocket = socket
+        } catch {
+            print("⚠️  Health check unavailable: \(error.localizedDescription)")
+        }
+    }
+
+    /// Version, start time, and each project's watcher state
+    private func healthSnapshot() -> [String: Any] {
+        let formatter = ISO8601DateFormatter()
+        let projects: [[String: Any]] = monitors.keys.sorted().map { path in
+            var project: [String: Any] = [
+                "path": path,
+                "watching": monitors[path]?.isActive() ?? false
+            ]
+            if let lastEvent = monitors[path]?.getLastEventTime() {
+                project["last_event_at"] = formatter.string(from: lastEvent)
+            }
+            return project
+        }
+
+        return [
+            "pid": Int(ProcessInfo.processInfo.processIdentifier),
+            "version": DaemonVersion.version,
+            "protocol_version": DaemonVersion.protocolVersion,
+            "started_at": formatter.string(from: startedAt),
+            "generated_at": formatter.string(from: Date()),
+            "projects": projects
+        ]
     }
 
     // MARK: - Status & Diagnostics
@@ -302,7 +474,7 @@ public class OxenDaemon {
         ╔═══════════════════════════════════════════════════════════╗
         ║                                                           ║
         ║              Auxin Version Control                        ║
-        ║              Production Daemon v2.0.0                     ║
+        ║              Production Daemon v\(DaemonVersion.version)                     ║
         ║                                                           ║
         ║     Logic Pro • SketchUp • Blender                        ║
         ╚═══════════════════════════════════════════════════════════╝
@@ -373,7 +545,7 @@ extension OxenDaemon {
                 return
 
             case "--version", "version":
-                print("Oxen VCS Daemon v2.0.0")
+                print("Oxen VCS Daemon v\(DaemonVersion.version) (protocol \(DaemonVersion.protocolVersion))")
                 return
 
             default:
@@ -415,6 +587,8 @@ extension OxenDaemon {
             • Power management integration
             • Network monitoring with auto-sync on reconnect
             • XPC communication for UI integration
+            • User scripts on daemon events (auxin daemon hooks)
+            • OSC triggers for commits and locks ([integrations.osc])
             • Draft branch workflow
 
         INSTALLATION:
@@ -0,0 +1,76 @@
+import Foundation
+
+/// Events that user scripts can react to
+///
+/// Raw values match `DaemonEvent` in the CLI's `daemon_hooks` module.
+public enum DaemonEvent: String {
+    case autoCommitCreated = "auto-commit-created"
+    case lockExpiring = "lock-expiring"
+    case pushFailed = "push-failed"
+    case autoCommitPaused = "auto-commit-paused"
+}
+
+/// Runs user-registered daemon hooks
+///
+/// Hooks live in `~/.auxin/daemon_hooks.json` and are managed with
+/// `auxin daemon hooks`. The daemon hands each event to
+/// `auxin daemon hooks fire`, which owns matching, timeouts and the script
+/// environment, so the daemon never waits on a user script.
+public class DaemonEventHooks {
+
+    private let cliPath: String
+    private let queue = DispatchQueue(label: "com.auxin.event-hooks", qos: .utility)
+
+    public init(cliPath: String = "/usr/local/bin/auxin") {
+        self.cliPath = cliPath
+    }
+
+    /// Fire an event in the background
+    /// - Parameters:
+    ///   - event: What happened
+    ///   - projectPath: Project the event concerns, if any
+    ///   - details: Values exported to scripts as `AUXIN_<KEY>`
+    public func fire(_ event: DaemonEvent, projectPath: String? = nil, details: [String: String] = [:]) {
+        // Nothing registered - skip spawning the CLI on every auto-commit
+        guard FileManager.default.fileExists(atPath: DaemonEventHooks.registryPath) else {
+            return
+        }
+
+        var arguments = ["daemon", "hooks", "fire", event.rawValue]
+        if let projectPath = projectPath {
+            arguments += ["--project", projectPath]
+        }
+        for (key, value) in details.sorted(by: { $0.key < $1.key }) {
+            arguments += ["--set", "\(key)=\(value)"]
+        }
+
+        queue.async { [cliPath] in
+            let task = Process()
+            task.executableURL = URL(fileURLWithPath: cliPath)
+            task.arguments = arguments
+            task.standardInput = FileHandle.nullDevice
+            task.standardOutput = FileHandle.nullDevice
+            let errorPipe = Pipe()
+            task.standardError = errorPipe
+
+            do {
+                try task.run()
+                let errorData = errorPipe.fileHandleForReading.readDataToEndOfFile()
+                task.waitUntilExit()
+
+                if task.terminationStatus != 0 {
+                    let error = String(data: errorData, encoding: .utf8) ?? ""
+                    print("⚠️  Daemon hooks for \(event.rawValue) failed: \(error.trimmingCharacters(in: .whitespacesAndNewlines))")
+                }
+            } catch {
+                print("⚠️  Failed to run daemon hooks for \(event.rawValue): \(error.localizedDescription)")
+            }
+        }
+    }
+
+    /// Location of the hook registry written by the CLI
+    public static var registryPath: String {
+        FileManager.default.homeDirectoryForCurrentUser
+            .appendingPathComponent(".auxin/daemon_hooks.json").path
+    }
+}
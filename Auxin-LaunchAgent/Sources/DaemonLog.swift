@@ -0,0 +1,25 @@
+import Foundation
+
+/// Structured log shared with the `auxin` CLI
+///
+/// CLI commands run by the daemon append their messages to the daemon log as
+/// JSON lines when `AUXIN_LOG_FILE` is set, tagged with the project in
+/// `AUXIN_LOG_PROJECT`. `auxin daemon logs` reads them back and filters by
+/// level, time, project and text.
+public enum DaemonLog {
+    /// The log file `auxin daemon logs` reads
+    public static var filePath: String {
+        FileManager.default.homeDirectoryForCurrentUser
+            .appendingPathComponent("Library/Logs/Auxin/daemon.log").path
+    }
+
+    /// Environment for a CLI command run for `projectPath`
+    public static func environment(project projectPath: String? = nil) -> [String: String] {
+        var environment = ProcessInfo.processInfo.environment
+        environment["AUXIN_LOG_FILE"] = filePath
+        if let projectPath = projectPath {
+            environment["AUXIN_LOG_PROJECT"] = projectPath
+        }
+        return environment
+    }
+}
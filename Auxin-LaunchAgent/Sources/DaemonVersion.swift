@@ -0,0 +1,48 @@
+import Foundation
+
+/// Version information shared with the `auxin` CLI
+///
+/// The CLI can't reach the XPC service, so a running daemon advertises its
+/// version in `~/.auxin/daemon.json`. The CLI compares `protocolVersion` with
+/// the one it was built for and warns about mismatches.
+public enum DaemonVersion {
+    /// Daemon release version
+    public static let version = "2.0.0"
+
+    /// Version of the CLI <-> daemon contract (commands, flags and files the
+    /// daemon relies on). Bump when either side changes incompatibly.
+    public static let protocolVersion = 6
+
+    /// Location of the advertised version file
+    public static var infoFilePath: String {
+        FileManager.default.homeDirectoryForCurrentUser
+            .appendingPathComponent(".auxin/daemon.json").path
+    }
+
+    /// Advertise this daemon's version (called on start)
+    public static func writeInfoFile(path: String = infoFilePath) {
+        let info: [String: Any] = [
+            "version": version,
+            "protocol_version": protocolVersion,
+            "pid": Int(ProcessInfo.processInfo.processIdentifier),
+            "started_at": ISO8601DateFormatter().string(from: Date())
+        ]
+
+        do {
+            let directory = (path as NSString).deletingLastPathComponent
+            try FileManager.default.createDirectory(
+                atPath: directory,
+                withIntermediateDirectories: true
+            )
+            let data = try JSONSerialization.data(withJSONObject: info, options: [.prettyPrinted])
+            try data.write(to: URL(fileURLWithPath: path), options: .atomic)
+        } catch {
+            print("⚠️  Failed to write daemon version file: \(error)")
+        }
+    }
+
+    /// Stop advertising (called on stop)
+    public static func removeInfoFile(path: String = infoFilePath) {
+        try? FileManager.default.removeItem(atPath: path)
+    }
+}
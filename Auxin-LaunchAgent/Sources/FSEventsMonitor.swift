@@ -149,6 +149,11 @@ public class FSEventsMonitor {
         return watchedPath
     }
 
+    /// When the last relevant change was seen, if any
+    public func getLastEventTime() -> Date? {
+        return lastEventTime
+    }
+
     // MARK: - Private Methods
 
     /// Handles incoming file system events
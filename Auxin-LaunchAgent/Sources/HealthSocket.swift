@@ -0,0 +1,122 @@
+import Foundation
+
+/// Health check for `auxin daemon health` and `auxin daemon watchdog`
+///
+/// The CLI can't reach the XPC service, so the daemon also listens on a Unix
+/// socket at `~/.auxin/daemon.sock`. Each connection gets one JSON document
+/// and is closed: the daemon's version, when it started, and for every
+/// project whether its file system watcher is running and when it last saw
+/// a change. The reply is built on the main queue, so a daemon whose main
+/// queue is stuck doesn't answer, and the watchdog restarts it.
+public final class HealthSocket {
+
+    /// Location of the socket
+    public static var socketPath: String {
+        FileManager.default.homeDirectoryForCurrentUser
+            .appendingPathComponent(".auxin/daemon.sock").path
+    }
+
+    private let path: String
+    private let snapshot: () -> [String: Any]
+    private let queue = DispatchQueue(label: "com.auxin.health", qos: .utility)
+    private var listenFD: Int32 = -1
+    private var source: DispatchSourceRead?
+
+    /// - Parameter snapshot: Builds the reply; called on the main queue
+    public init(path: String = HealthSocket.socketPath, snapshot: @escaping () -> [String: Any]) {
+        self.path = path
+        self.snapshot = snapshot
+    }
+
+    /// Start answering health checks
+    public func start() throws {
+        let directory = (path as NSString).deletingLastPathComponent
+        try FileManager.default.createDirectory(atPath: directory, withIntermediateDirectories: true)
+        unlink(path)  // left behind by a daemon that didn't stop cleanly
+
+        let fd = socket(AF_UNIX, SOCK_STREAM, 0)
+        guard fd >= 0 else { throw HealthSocketError.socketFailed(errno) }
+
+        var address = sockaddr_un()
+        address.sun_family = sa_family_t(AF_UNIX)
+        let capacity = MemoryLayout.size(ofValue: address.sun_path)
+        guard path.utf8.count < capacity else {
+            close(fd)
+            throw HealthSocketError.pathTooLong(path)
+        }
+        withUnsafeMutablePointer(to: &address.sun_path) { pointer in
+            pointer.withMemoryRebound(to: CChar.self, capacity: capacity) { buffer in
+                _ = strncpy(buffer, path, capacity)
+            }
+        }
+
+        let bound = withUnsafePointer(to: &address) { pointer in
+            pointer.withMemoryRebound(to: sockaddr.self, capacity: 1) { address in
+                bind(fd, address, socklen_t(MemoryLayout<sockaddr_un>.size))
+            }
+        }
+        guard bound == 0, listen(fd, 8) == 0 else {
+            let code = errno
+            close(fd)
+            throw HealthSocketError.bindFailed(code)
+        }
+        chmod(path, 0o600)
+
+        let source = DispatchSource.makeReadSource(fileDescriptor: fd, queue: queue)
+        source.setEventHandler { [weak self] in
+            self?.acceptConnection()
+        }
+        source.setCancelHandler {
+            close(fd)
+        }
+        source.resume()
+
+        listenFD = fd
+        self.source = source
+    }
+
+    /// Stop answering and remove the socket
+    public func stop() {
+        source?.cancel()
+        source = nil
+        listenFD = -1
+        unlink(path)
+    }
+
+    private func acceptConnection() {
+        let client = accept(listenFD, nil, nil)
+        guard client >= 0 else { return }
+
+        DispatchQueue.main.async { [weak self] in
+            guard let self = self else {
+                close(client)
+                return
+            }
+            let reply = (try? JSONSerialization.data(withJSONObject: self.snapshot()))
+                ?? Data("{}".utf8)
+            self.queue.async {
+                reply.withUnsafeBytes { buffer in
+                    _ = write(client, buffer.baseAddress, buffer.count)
+                }
+                close(client)
+            }
+        }
+    }
+}
+
+enum HealthSocketError: Error, LocalizedError {
+    case socketFailed(Int32)
+    case bindFailed(Int32)
+    case pathTooLong(String)
+
+    var errorDescription: String? {
+        switch self {
+        case .socketFailed(let code):
+            return "Failed to create socket: \(String(cString: strerror(code)))"
+        case .bindFailed(let code):
+            return "Failed to listen on socket: \(String(cString: strerror(code)))"
+        case .pathTooLong(let path):
+            return "Socket path too long: \(path)"
+        }
+    }
+}
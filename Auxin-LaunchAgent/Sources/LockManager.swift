@@ -97,6 +97,17 @@ class LockManager {
         return readLock(projectPath: projectPath)
     }
 
+    /// Gets this user's lock on a project if it expires within `interval`
+    func ownLockExpiring(projectPath: String, within interval: TimeInterval) -> ProjectLock? {
+        guard let lock = readLock(projectPath: projectPath),
+              lock.lockedBy == getCurrentUserIdentifier(),
+              !lock.isExpired,
+              lock.remainingTime <= interval else {
+            return nil
+        }
+        return lock
+    }
+
     /// Cleans up expired locks across all projects
     func cleanupExpiredLocks(baseDir: String) {
         // Find all .oxen directories
@@ -16,11 +16,13 @@ public class NetworkMonitor {
     private var lastPath: NWPath?
 
     private let cliPath: String
+    private let eventHooks: DaemonEventHooks
 
     // MARK: - Initialization
 
     public init(cliPath: String = "/usr/local/bin/auxin") {
         self.cliPath = cliPath
+        self.eventHooks = DaemonEventHooks(cliPath: cliPath)
         self.monitor = NWPathMonitor()
         self.queue = DispatchQueue(label: "com.auxin.network-monitor", qos: .utility)
     }
@@ -154,6 +156,11 @@ public class NetworkMonitor {
                 if !error.isEmpty {
                     print("  Error: \(error)")
                 }
+                let reason = error.isEmpty ? "exit code \(task.terminationStatus)" : error
+                eventHooks.fire(
+                    .pushFailed,
+                    details: ["error": reason.trimmingCharacters(in: .whitespacesAndNewlines)]
+                )
                 // Don't retry immediately - will try again on next reconnect
             }
         } catch {
@@ -0,0 +1,55 @@
+import Foundation
+
+/// Keeps `auxin osc listen` running for the monitored projects
+///
+/// The listener reads `[integrations.osc]` from the user's config itself and
+/// exits straight away when the integration is disabled, so the daemon can
+/// always start it.
+public class OSCIntegration {
+
+    private let cliPath: String
+    private var process: Process?
+    private var projects: [String] = []
+
+    public init(cliPath: String = "/usr/local/bin/auxin") {
+        self.cliPath = cliPath
+    }
+
+    /// (Re)start the listener when the set of projects changes
+    public func update(projects: Set<String>) {
+        let sorted = projects.sorted()
+        guard sorted != self.projects || process?.isRunning != true else {
+            return
+        }
+
+        stop()
+        self.projects = sorted
+        guard !sorted.isEmpty else { return }
+
+        let task = Process()
+        task.executableURL = URL(fileURLWithPath: cliPath)
+        task.arguments = ["osc", "listen"] + sorted.flatMap { ["--project", $0] }
+        task.standardInput = FileHandle.nullDevice
+        task.terminationHandler = { task in
+            if task.terminationStatus != 0 && task.terminationReason == .exit {
+                print("⚠️  OSC listener exited with code \(task.terminationStatus)")
+            }
+        }
+
+        do {
+            try task.run()
+            process = task
+        } catch {
+            print("⚠️  Failed to start OSC listener: \(error.localizedDescription)")
+        }
+    }
+
+    /// Stop the listener
+    public func stop() {
+        if let process = process, process.isRunning {
+            process.terminate()
+            process.waitUntilExit()  // release the UDP port before rebinding
+        }
+        process = nil
+    }
+}
@@ -388,7 +388,8 @@ public class OxenDaemonXPCService: NSObject, OxenDaemonXPCProtocol {
             "isRunning": true,
             "projectCount": projects.count,
             "pausedCount": pausedProjects.count,
-            "version": "2.0.0",
+            "version": DaemonVersion.version,
+            "protocolVersion": DaemonVersion.protocolVersion,
             "uptime": ProcessInfo.processInfo.systemUptime
         ]
 
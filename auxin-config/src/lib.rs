@@ -1,6 +1,7 @@
 use figment::{Figment, providers::{Format, Toml, Env}};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Config {
@@ -20,6 +21,50 @@ pub struct Config {
     pub cli: Cli,
     #[serde(default)]
     pub server: Server,
+    #[serde(default)]
+    pub integrations: Integrations,
+    #[serde(default)]
+    pub daemon: Daemon,
+    #[serde(default)]
+    pub versioning: Versioning,
+    #[serde(default)]
+    pub watermark: Watermark,
+    #[serde(default)]
+    pub quarantine: Quarantine,
+    #[serde(default)]
+    pub meta_branch: MetaBranch,
+    #[serde(default)]
+    pub identity: Identity,
+    #[serde(default)]
+    pub updates: Updates,
+    #[serde(default)]
+    pub audit: Audit,
+    /// Staging policy by project type (`logic`, `sketchup`, `blender`,
+    /// `other`); `default` applies to every project
+    #[serde(default)]
+    pub staging: BTreeMap<String, StagingRules>,
+    /// Custom commit metadata fields by project type, as with `staging`
+    #[serde(default)]
+    pub metadata_fields: BTreeMap<String, Vec<MetadataField>>,
+    /// Servers by name, for projects that choose one with `[cli] server`
+    #[serde(default)]
+    pub servers: BTreeMap<String, NamedServer>,
+    /// User-defined commands, e.g. `save = "commit -m 'WIP' --tags wip"`
+    #[serde(default)]
+    pub aliases: BTreeMap<String, Alias>,
+    /// The project's `.auxin/features.toml`, already applied to the
+    /// settings above
+    #[serde(skip)]
+    pub features: Features,
+    /// Values replaced by `resolve_secrets`: dotted key -> (as written, resolved)
+    #[serde(skip)]
+    resolved: BTreeMap<String, (String, String)>,
+    /// References `resolve_secrets` couldn't fill in, and why
+    #[serde(skip)]
+    unresolved: Vec<String>,
+    /// Settings `apply_features` turned off: dotted key -> value as written
+    #[serde(skip)]
+    feature_overrides: BTreeMap<String, bool>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -38,6 +83,22 @@ pub struct Lock {
     pub auto_renew: bool,
     #[serde(default = "default_renew_before")]
     pub renew_before_minutes: i64,
+    /// When the holder's lock is released without `auxin lock release`
+    #[serde(default)]
+    pub release_on: LockRelease,
+}
+
+/// `[lock] release_on`: what gives up the lock on its own
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LockRelease {
+    /// A successful `auxin push`
+    Push,
+    /// A successful `auxin commit`
+    Commit,
+    /// Only `auxin lock release`
+    #[default]
+    Never,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -52,6 +113,54 @@ pub struct Network {
     pub connectivity_check_interval_s: i64,
     #[serde(default = "default_connectivity_timeout")]
     pub connectivity_check_timeout_s: i64,
+    /// What connectivity checks try to reach: "server" (the `[cli]` server),
+    /// "hub" (hub.oxen.ai) or a URL
+    #[serde(default = "default_probe_targets")]
+    pub probe_targets: Vec<String>,
+    /// URL answering 204 when no captive portal intercepts traffic, e.g.
+    /// `http://connectivitycheck.gstatic.com/generate_204`; empty (the
+    /// default) turns captive portal detection off
+    #[serde(default)]
+    pub captive_portal_url: String,
+    /// `[network.push]`: pushes and uploads
+    #[serde(default)]
+    pub push: RetryOverrides,
+    /// `[network.pull]`: pulls and downloads
+    #[serde(default)]
+    pub pull: RetryOverrides,
+    /// `[network.server_api]`: requests to auxin-server
+    #[serde(default)]
+    pub server_api: RetryOverrides,
+    /// `[network.connectivity]`: online/offline checks
+    #[serde(default)]
+    pub connectivity: RetryOverrides,
+}
+
+/// Per-operation-class overrides of the `[network]` retry settings
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct RetryOverrides {
+    pub max_retries: Option<i64>,
+    pub initial_backoff_ms: Option<i64>,
+    pub max_backoff_ms: Option<i64>,
+    pub timeout_s: Option<i64>,
+}
+
+/// Kinds of network operation with their own retry settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkClass {
+    Push,
+    Pull,
+    ServerApi,
+    Connectivity,
+}
+
+/// Retry and timeout settings for one [`NetworkClass`], overrides applied
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetrySettings {
+    pub max_retries: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub timeout_s: u64,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -64,6 +173,31 @@ pub struct Queue {
     pub max_entries: i64,
     #[serde(default = "default_cleanup_days")]
     pub cleanup_after_days: i64,
+    /// Queued operations synced at once, across all QoS classes
+    #[serde(default = "default_queue_workers")]
+    pub max_concurrent: i64,
+    /// `[queue.concurrency]`: most operations of each QoS class synced at once
+    #[serde(default)]
+    pub concurrency: QueueConcurrency,
+    /// Minutes an operation waits before it's scheduled with the class above
+    /// its own, so a backlog of lock and metadata work can't starve pushes
+    #[serde(default = "default_starvation_minutes")]
+    pub starvation_minutes: i64,
+    /// Failed sync attempts before an operation is dead-lettered (0 = retry
+    /// forever)
+    #[serde(default = "default_queue_max_attempts")]
+    pub max_attempts: i64,
+}
+
+/// Per-QoS-class limits on queued operations synced at once
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct QueueConcurrency {
+    pub locks: i64,
+    pub metadata: i64,
+    pub comments: i64,
+    /// Pushes and pulls
+    pub pushes: i64,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -74,6 +208,35 @@ pub struct Ui {
     pub emoji: bool,
     #[serde(default = "default_terminal_width")]
     pub terminal_width: i64,
+    /// "model", "metric" or "imperial": how SketchUp units are shown
+    #[serde(default = "default_unit_system")]
+    pub unit_system: String,
+    /// `[ui.console]`: how `auxin console` arranges its panes
+    #[serde(default)]
+    pub console: ConsoleLayout,
+}
+
+/// `[ui.console]`: pane layout of `auxin console`, saved as it's changed
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ConsoleLayout {
+    pub preset: ConsolePreset,
+    /// Percent of the screen the status and project panes take, beside or
+    /// above the activity log
+    pub side_percent: u16,
+}
+
+/// Arrangement of the console's panes
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConsolePreset {
+    /// Status and project panes left of the activity log
+    #[default]
+    SideBySide,
+    /// Status and project panes above the activity log
+    Stacked,
+    /// Only the activity log
+    LogOnly,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -96,6 +259,10 @@ pub struct Cli {
     pub use_server_metadata: bool,
     #[serde(default = "default_namespace")]
     pub default_namespace: String,
+    /// `[servers.<name>]` entry this project uses in place of `url` and
+    /// `token`; empty uses them as written
+    #[serde(default)]
+    pub server: String,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -108,6 +275,10 @@ pub struct Server {
     pub port: i64,
     #[serde(default = "default_auth_secret")]
     pub auth_token_secret: String,
+    /// File holding the auth secret, e.g. a Docker or systemd credential;
+    /// takes precedence over `auth_token_secret`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub auth_token_secret_file: String,
     #[serde(default = "default_token_expiry")]
     pub auth_token_expiry_hours: i64,
     #[serde(default = "default_false")]
@@ -118,6 +289,366 @@ pub struct Server {
     pub redis_url: String,
     #[serde(default)]
     pub database_url: String,
+    #[serde(default = "default_shutdown_timeout")]
+    pub shutdown_timeout_secs: i64,
+    /// Seconds between sweeps that warn holders of expiring locks and clear
+    /// expired ones; 0 turns the sweeper off
+    #[serde(default = "default_lock_sweep_interval")]
+    pub lock_sweep_interval_secs: i64,
+    /// How long before a lock expires its holder is warned
+    #[serde(default = "default_lock_expiry_warning")]
+    pub lock_expiry_warning_minutes: i64,
+    /// Minutes without a lock heartbeat or a daemon presence ping before a
+    /// lock counts as probably orphaned and can be reclaimed; 0 turns
+    /// orphan detection off
+    #[serde(default = "default_lock_orphan")]
+    pub lock_orphan_minutes: i64,
+    /// Advertise the server on the LAN over mDNS as `_auxin._tcp`
+    #[serde(default = "default_true")]
+    pub advertise: bool,
+    /// Name shown by `auxin server discover`; empty uses the host name
+    #[serde(default)]
+    pub advertise_name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Integrations {
+    #[serde(default)]
+    pub osc: Osc,
+    #[serde(default)]
+    pub spotlight: Spotlight,
+    #[serde(default)]
+    pub session_sheets: SessionSheets,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Osc {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    #[serde(default = "default_osc_bind")]
+    pub bind: String,
+    /// OSC address -> action name
+    #[serde(default = "default_osc_addresses")]
+    pub addresses: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Spotlight {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct SessionSheets {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Daemon {
+    /// Describe detected changes in auto-commit messages
+    #[serde(default = "default_true")]
+    pub auto_titles: bool,
+    /// Pause auto-commits when a change looks like data loss
+    #[serde(default = "default_true")]
+    pub anomaly_detection: bool,
+    /// Shrinking by at least this percentage since the last commit is suspicious
+    #[serde(default = "default_anomaly_size_drop")]
+    pub anomaly_size_drop_percent: f64,
+    /// Deleting at least this many files since the last commit is suspicious
+    #[serde(default = "default_anomaly_deleted_files")]
+    pub anomaly_deleted_files: usize,
+    /// How long to wait for the daemon to answer a health check
+    #[serde(default = "default_health_timeout")]
+    pub health_timeout_secs: u64,
+    /// How often `auxin daemon watchdog` checks the daemon
+    #[serde(default = "default_watchdog_interval")]
+    pub watchdog_interval_secs: u64,
+}
+
+/// Human version labels for milestone commits (`auxin version`)
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Versioning {
+    /// Give every new milestone commit the next minor version
+    #[serde(default = "default_false")]
+    pub auto: bool,
+    /// Label template: `{major}`, `{minor}`, and `{n}` (versions so far)
+    #[serde(default = "default_version_format")]
+    pub format: String,
+}
+
+/// Audible watermark for `auxin bounce export --watermark`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Watermark {
+    /// `"tone"`, or the path of an audio file such as a voice tag
+    #[serde(default = "default_watermark_sound")]
+    pub sound: String,
+    /// Pitch of the tone
+    #[serde(default = "default_watermark_tone_hz")]
+    pub tone_hz: f64,
+    /// Length of each tone
+    #[serde(default = "default_watermark_tone_secs")]
+    pub tone_secs: f64,
+    /// Seconds from the start of one watermark to the next
+    #[serde(default = "default_watermark_interval")]
+    pub interval_secs: f64,
+    /// Watermark level; 0 is full scale
+    #[serde(default = "default_watermark_level")]
+    pub level_db: f64,
+}
+
+/// Hold back executables and scripts that arrive with `auxin pull`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Quarantine {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    /// Extensions let through without review, e.g. `["py"]`
+    #[serde(default)]
+    pub auto_approve_extensions: Vec<String>,
+}
+
+/// Version auxin's own data (metadata edits, comments, bounce manifests)
+/// on a dedicated branch of the project's remote
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MetaBranch {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    #[serde(default = "default_meta_branch")]
+    pub branch: String,
+    #[serde(default = "default_meta_remote")]
+    pub remote: String,
+}
+
+/// Who you are to your team, in locks, comments and activity
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Identity {
+    /// Name recorded as `name@machine`; empty uses the Oxen hub username
+    /// from `auxin auth login`, then the login name
+    #[serde(default)]
+    pub name: String,
+    /// Other identities that are also you, e.g. `sam@old-laptop`
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// Checking for newer releases of auxin, its daemon and server, and Oxen
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Updates {
+    /// Check once a day while running commands
+    #[serde(default = "default_true")]
+    pub check: bool,
+    /// Release manifest listing the latest version of each component
+    #[serde(default = "default_manifest_url")]
+    pub manifest_url: String,
+}
+
+/// Mirroring the operation history to an external audit system
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Audit {
+    /// Where every recorded operation is sent as it happens
+    #[serde(default)]
+    pub sinks: Vec<AuditSinkKind>,
+    /// Local syslog socket, or `host:port` for a remote collector over UDP;
+    /// empty uses the system logger
+    #[serde(default)]
+    pub syslog_address: String,
+    /// Endpoint each entry is POSTed to as JSON
+    #[serde(default)]
+    pub http_url: String,
+    /// Sent as a bearer token; write `${ENV_VAR}` to keep it out of the file
+    #[serde(default)]
+    pub http_token: String,
+    /// File entries are appended to, one JSON object per line
+    #[serde(default)]
+    pub file: String,
+    /// Entries kept per sink while it can't be reached; the oldest are
+    /// dropped beyond this
+    #[serde(default = "default_audit_buffer_limit")]
+    pub buffer_limit: usize,
+}
+
+/// `[audit] sinks`: an external system entries are delivered to
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditSinkKind {
+    Syslog,
+    Http,
+    File,
+}
+
+/// Newest `.auxin/features.toml` format this build understands
+pub const FEATURES_FORMAT: u32 = 1;
+
+/// Behaviors chosen per repository, kept in `.auxin/features.toml`
+///
+/// Written by `auxin init` and committed with the project, so every clone
+/// behaves the same. Features the file doesn't mention are on.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RepoFeatures {
+    #[serde(default = "default_features_format")]
+    pub format: u32,
+    #[serde(default)]
+    pub features: Features,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Features {
+    /// Spotlight sidecars and server-side metadata for commits
+    #[serde(default = "default_true")]
+    pub metadata_sidecars: bool,
+    /// Auto-commits go to the `draft` branch
+    #[serde(default = "default_true")]
+    pub draft_workflow: bool,
+    /// Server locks, metadata uploads and meta branch sync
+    #[serde(default = "default_true")]
+    pub server_sync: bool,
+    /// Features from newer versions of auxin
+    #[serde(flatten)]
+    pub other: BTreeMap<String, toml::Value>,
+}
+
+impl Features {
+    /// Names and descriptions of the features this build knows
+    pub const KNOWN: [(&'static str, &'static str); 3] = [
+        ("metadata_sidecars", "Spotlight sidecars and server-side metadata for commits"),
+        ("draft_workflow", "Auto-commits go to the draft branch"),
+        ("server_sync", "Server locks, metadata uploads and meta branch sync"),
+    ];
+
+    /// Whether a known feature is on; `None` for unknown names
+    pub fn get(&self, name: &str) -> Option<bool> {
+        match name {
+            "metadata_sidecars" => Some(self.metadata_sidecars),
+            "draft_workflow" => Some(self.draft_workflow),
+            "server_sync" => Some(self.server_sync),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, name: &str, enabled: bool) -> anyhow::Result<()> {
+        match name {
+            "metadata_sidecars" => self.metadata_sidecars = enabled,
+            "draft_workflow" => self.draft_workflow = enabled,
+            "server_sync" => self.server_sync = enabled,
+            _ => anyhow::bail!(
+                "Unknown feature '{}' (known: {})",
+                name,
+                Self::KNOWN.map(|(name, _)| name).join(", ")
+            ),
+        }
+        Ok(())
+    }
+
+    /// Turned-on features this build doesn't know
+    pub fn unknown_enabled(&self) -> Vec<&str> {
+        self.other
+            .iter()
+            .filter(|(_, value)| value.as_bool() != Some(false))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+impl RepoFeatures {
+    pub fn path(repo_root: &Path) -> PathBuf {
+        repo_root.join(".auxin").join("features.toml")
+    }
+
+    /// The repository's features; `None` for repositories created before
+    /// features were recorded
+    pub fn load(repo_root: &Path) -> anyhow::Result<Option<RepoFeatures>> {
+        let path = Self::path(repo_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, repo_root: &Path) -> anyhow::Result<()> {
+        let path = Self::path(repo_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Why this build can't safely work with the repository, if it can't
+    pub fn incompatibility(&self) -> Option<String> {
+        if self.format > FEATURES_FORMAT {
+            return Some(format!(
+                "features format {} is newer than this auxin understands ({})",
+                self.format, FEATURES_FORMAT
+            ));
+        }
+        let unknown = self.features.unknown_enabled();
+        if !unknown.is_empty() {
+            return Some(format!("uses features this auxin doesn't know: {}", unknown.join(", ")));
+        }
+        None
+    }
+}
+
+/// A `[servers.<name>]` section: one of several auxin servers, e.g. one
+/// per studio, chosen per project with `[cli] server`
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct NamedServer {
+    pub url: String,
+    /// Token for this server; `"keychain"` reads the one stored for `url`.
+    /// Empty sends none, so another server's token never leaks here
+    #[serde(default)]
+    pub token: String,
+    /// Replaces `[cli] default_namespace`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+/// A `[staging.<type>]` section: gitignore-style patterns that
+/// `add --all` always stages (even when `.oxenignore` excludes them) or
+/// never stages
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct StagingRules {
+    #[serde(default)]
+    pub always: Vec<String>,
+    #[serde(default)]
+    pub never: Vec<String>,
+}
+
+/// A `[[metadata_fields.<type>]]` entry: a commit metadata field a team
+/// tracks alongside BPM and key, e.g. "client" or "revision"
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct MetadataField {
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub field_type: MetadataFieldType,
+    /// `auxin commit` refuses to commit without it
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// What a custom metadata field's value must look like
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataFieldType {
+    #[default]
+    Text,
+    /// Any number, e.g. `2.5`
+    Number,
+    /// A whole number, e.g. `3`
+    Integer,
+    /// `true`/`false` or `yes`/`no`
+    Boolean,
+}
+
+/// An `[aliases]` entry: one command line, or a list run in order
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Alias {
+    Command(String),
+    Macro(Vec<String>),
 }
 
 // Default value functions for serde
@@ -131,10 +662,15 @@ fn default_initial_backoff() -> i64 { 1000 }
 fn default_max_backoff() -> i64 { 15000 }
 fn default_connectivity_interval() -> i64 { 30 }
 fn default_connectivity_timeout() -> i64 { 5 }
+fn default_probe_targets() -> Vec<String> { vec!["server".to_string(), "hub".to_string()] }
 fn default_queue_dir() -> String { "~/.auxin/queue".to_string() }
 fn default_max_entries() -> i64 { 1000 }
 fn default_cleanup_days() -> i64 { 7 }
+fn default_queue_workers() -> i64 { 4 }
+fn default_starvation_minutes() -> i64 { 30 }
+fn default_queue_max_attempts() -> i64 { 5 }
 fn default_terminal_width() -> i64 { 0 }
+fn default_unit_system() -> String { "model".to_string() }
 fn default_project_type() -> String { "auto".to_string() }
 fn default_server_url() -> String { "http://localhost:3000".to_string() }
 fn default_timeout_secs() -> i64 { 30 }
@@ -142,8 +678,40 @@ fn default_namespace() -> String { "community".to_string() }
 fn default_sync_dir() -> String { "/var/oxen/data".to_string() }
 fn default_host() -> String { "0.0.0.0".to_string() }
 fn default_port() -> i64 { 3000 }
-fn default_auth_secret() -> String { "dev_secret_change_in_production".to_string() }
+fn default_auth_secret() -> String { DEV_AUTH_SECRET.to_string() }
 fn default_token_expiry() -> i64 { 24 }
+fn default_shutdown_timeout() -> i64 { 30 }
+fn default_lock_sweep_interval() -> i64 { 60 }
+fn default_lock_expiry_warning() -> i64 { 15 }
+fn default_lock_orphan() -> i64 { 15 }
+fn default_version_format() -> String { "v{major}.{minor}".to_string() }
+fn default_anomaly_size_drop() -> f64 { 90.0 }
+fn default_anomaly_deleted_files() -> usize { 100 }
+fn default_health_timeout() -> u64 { 5 }
+fn default_watchdog_interval() -> u64 { 60 }
+fn default_watermark_sound() -> String { "tone".to_string() }
+fn default_meta_branch() -> String { "auxin-meta".to_string() }
+fn default_meta_remote() -> String { "origin".to_string() }
+fn default_watermark_tone_hz() -> f64 { 1000.0 }
+fn default_watermark_tone_secs() -> f64 { 1.0 }
+fn default_watermark_interval() -> f64 { 20.0 }
+fn default_watermark_level() -> f64 { -12.0 }
+fn default_features_format() -> u32 { FEATURES_FORMAT }
+fn default_manifest_url() -> String {
+    "https://github.com/jbacus/auxin/releases/latest/download/versions.json".to_string()
+}
+fn default_audit_buffer_limit() -> usize { 100_000 }
+fn default_osc_bind() -> String { "127.0.0.1:9050".to_string() }
+fn default_osc_addresses() -> BTreeMap<String, String> {
+    [
+        ("/auxin/milestone", "milestone"),
+        ("/auxin/commit", "commit"),
+        ("/auxin/lock/toggle", "toggle-lock"),
+    ]
+    .into_iter()
+    .map(|(address, action)| (address.to_string(), action.to_string()))
+    .collect()
+}
 
 // Default trait implementations
 impl Default for Defaults {
@@ -161,6 +729,7 @@ impl Default for Lock {
             timeout_hours: default_lock_timeout(),
             auto_renew: default_false(),
             renew_before_minutes: default_renew_before(),
+            release_on: LockRelease::default(),
         }
     }
 }
@@ -173,6 +742,12 @@ impl Default for Network {
             max_backoff_ms: default_max_backoff(),
             connectivity_check_interval_s: default_connectivity_interval(),
             connectivity_check_timeout_s: default_connectivity_timeout(),
+            probe_targets: default_probe_targets(),
+            captive_portal_url: String::new(),
+            push: RetryOverrides::default(),
+            pull: RetryOverrides::default(),
+            server_api: RetryOverrides::default(),
+            connectivity: RetryOverrides::default(),
         }
     }
 }
@@ -184,6 +759,21 @@ impl Default for Queue {
             queue_dir: default_queue_dir(),
             max_entries: default_max_entries(),
             cleanup_after_days: default_cleanup_days(),
+            max_concurrent: default_queue_workers(),
+            concurrency: QueueConcurrency::default(),
+            starvation_minutes: default_starvation_minutes(),
+            max_attempts: default_queue_max_attempts(),
+        }
+    }
+}
+
+impl Default for QueueConcurrency {
+    fn default() -> Self {
+        Self {
+            locks: 4,
+            metadata: 2,
+            comments: 1,
+            pushes: 1,
         }
     }
 }
@@ -194,6 +784,37 @@ impl Default for Ui {
             progress: default_true(),
             emoji: default_true(),
             terminal_width: default_terminal_width(),
+            unit_system: default_unit_system(),
+            console: ConsoleLayout::default(),
+        }
+    }
+}
+
+impl ConsolePreset {
+    /// Name as written in the config
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsolePreset::SideBySide => "side-by-side",
+            ConsolePreset::Stacked => "stacked",
+            ConsolePreset::LogOnly => "log-only",
+        }
+    }
+
+    /// The preset after this one, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            ConsolePreset::SideBySide => ConsolePreset::Stacked,
+            ConsolePreset::Stacked => ConsolePreset::LogOnly,
+            ConsolePreset::LogOnly => ConsolePreset::SideBySide,
+        }
+    }
+}
+
+impl Default for ConsoleLayout {
+    fn default() -> Self {
+        Self {
+            preset: ConsolePreset::default(),
+            side_percent: 30,
         }
     }
 }
@@ -215,6 +836,7 @@ impl Default for Cli {
             use_server_locks: default_true(),
             use_server_metadata: default_true(),
             default_namespace: default_namespace(),
+            server: String::new(),
         }
     }
 }
@@ -226,11 +848,28 @@ impl Default for Server {
             host: default_host(),
             port: default_port(),
             auth_token_secret: default_auth_secret(),
+            auth_token_secret_file: String::new(),
             auth_token_expiry_hours: default_token_expiry(),
             enable_redis_locks: default_false(),
             enable_web_ui: default_false(),
             redis_url: String::new(),
             database_url: String::new(),
+            shutdown_timeout_secs: default_shutdown_timeout(),
+            lock_sweep_interval_secs: default_lock_sweep_interval(),
+            lock_expiry_warning_minutes: default_lock_expiry_warning(),
+            lock_orphan_minutes: default_lock_orphan(),
+            advertise: default_true(),
+            advertise_name: String::new(),
+        }
+    }
+}
+
+impl Default for Osc {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            bind: default_osc_bind(),
+            addresses: default_osc_addresses(),
         }
     }
 }
@@ -246,13 +885,276 @@ impl Default for Config {
             project: Project::default(),
             cli: Cli::default(),
             server: Server::default(),
+            integrations: Integrations::default(),
+            daemon: Daemon::default(),
+            versioning: Versioning::default(),
+            watermark: Watermark::default(),
+            quarantine: Quarantine::default(),
+            meta_branch: MetaBranch::default(),
+            identity: Identity::default(),
+            updates: Updates::default(),
+            audit: Audit::default(),
+            staging: BTreeMap::new(),
+            metadata_fields: BTreeMap::new(),
+            servers: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            features: Features::default(),
+            resolved: BTreeMap::new(),
+            unresolved: Vec::new(),
+            feature_overrides: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for Versioning {
+    fn default() -> Self {
+        Self {
+            auto: default_false(),
+            format: default_version_format(),
+        }
+    }
+}
+
+impl Default for Watermark {
+    fn default() -> Self {
+        Self {
+            sound: default_watermark_sound(),
+            tone_hz: default_watermark_tone_hz(),
+            tone_secs: default_watermark_tone_secs(),
+            interval_secs: default_watermark_interval(),
+            level_db: default_watermark_level(),
+        }
+    }
+}
+
+impl Default for Quarantine {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            auto_approve_extensions: Vec::new(),
+        }
+    }
+}
+
+impl Default for MetaBranch {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            branch: default_meta_branch(),
+            remote: default_meta_remote(),
+        }
+    }
+}
+
+impl Default for RepoFeatures {
+    fn default() -> Self {
+        Self {
+            format: default_features_format(),
+            features: Features::default(),
+        }
+    }
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            metadata_sidecars: default_true(),
+            draft_workflow: default_true(),
+            server_sync: default_true(),
+            other: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for Updates {
+    fn default() -> Self {
+        Self {
+            check: default_true(),
+            manifest_url: default_manifest_url(),
+        }
+    }
+}
+
+impl Default for Audit {
+    fn default() -> Self {
+        Self {
+            sinks: Vec::new(),
+            syslog_address: String::new(),
+            http_url: String::new(),
+            http_token: String::new(),
+            file: String::new(),
+            buffer_limit: default_audit_buffer_limit(),
+        }
+    }
+}
+
+impl Default for Daemon {
+    fn default() -> Self {
+        Self {
+            auto_titles: default_true(),
+            anomaly_detection: default_true(),
+            anomaly_size_drop_percent: default_anomaly_size_drop(),
+            anomaly_deleted_files: default_anomaly_deleted_files(),
+            health_timeout_secs: default_health_timeout(),
+            watchdog_interval_secs: default_watchdog_interval(),
+        }
+    }
+}
+
+/// Replace each `${NAME}` in `value` with the environment variable `NAME`
+///
+/// `$${` is a literal `${`. Fails if a variable isn't set.
+pub fn expand_env(value: &str) -> anyhow::Result<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start..];
+        if let Some(literal) = after.strip_prefix("$${") {
+            expanded.push_str("${");
+            rest = literal;
+        } else if let Some(reference) = after.strip_prefix("${") {
+            let end = reference
+                .find('}')
+                .ok_or_else(|| anyhow::anyhow!("Unclosed '${{' in '{}'", value))?;
+            let name = &reference[..end];
+            let resolved = std::env::var(name)
+                .map_err(|_| anyhow::anyhow!("Environment variable {} is not set", name))?;
+            expanded.push_str(&resolved);
+            rest = &reference[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = &after[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Parts of a key name that mark its value as a secret, or a URL that
+/// may carry one
+const SECRET_KEY_PARTS: &[&str] = &[
+    "auth", "token", "secret", "password", "redis", "database", "webhook",
+];
+
+/// Whether `${NAME}` is expanded in the value of `key`
+///
+/// Only secrets are, so an alias or a label template can contain `${`
+/// as written.
+fn holds_secret(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SECRET_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// Expand `${NAME}` in the strings under `value` kept in secret-bearing
+/// keys, recording what changed and leaving values that can't be expanded
+/// as written
+fn expand_strings(
+    value: &mut toml::Value,
+    path: &str,
+    secret: bool,
+    resolved: &mut BTreeMap<String, (String, String)>,
+    unresolved: &mut Vec<String>,
+) {
+    match value {
+        toml::Value::String(s) if secret && s.contains('$') => match expand_env(s) {
+            Ok(expanded) if expanded != *s => {
+                resolved.insert(path.to_string(), (s.clone(), expanded.clone()));
+                *s = expanded;
+            }
+            Ok(_) => {}
+            Err(e) => unresolved.push(format!("{}: {}", path, e)),
+        },
+        // Alias names are the user's, and never mark a secret
+        toml::Value::Table(table) if path != "aliases" => {
+            for (key, value) in table.iter_mut() {
+                let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                let secret = secret || holds_secret(key);
+                expand_strings(value, &path, secret, resolved, unresolved);
+            }
+        }
+        toml::Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                let path = format!("{}[{}]", path, index);
+                expand_strings(item, &path, secret, resolved, unresolved);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `[cli] server` names a server with no `[servers.<name>]` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownServer {
+    pub name: String,
+    /// Names of the servers that are configured
+    pub configured: Vec<String>,
+}
+
+impl std::fmt::Display for UnknownServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[cli] server is '{}', but there is no [servers.{}]",
+            self.name, self.name
+        )?;
+        if self.configured.is_empty() {
+            write!(f, "; no servers are configured")?;
+        } else {
+            write!(f, "; configured servers: {}", self.configured.join(", "))?;
         }
+        write!(f, " (run 'auxin server use --clear' to use [cli] url and token)")
+    }
+}
+
+impl std::error::Error for UnknownServer {}
+
+/// Placeholder `auth_token_secret` that must not reach production
+pub const DEV_AUTH_SECRET: &str = "dev_secret_change_in_production";
+
+/// Shortest `auth_token_secret` accepted as production-ready
+pub const MIN_AUTH_SECRET_LEN: usize = 32;
+
+impl Server {
+    /// Problems that make this unfit for a production deployment
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.auth_token_secret == DEV_AUTH_SECRET {
+            problems.push("auth_token_secret is the development placeholder".to_string());
+        } else if self.auth_token_secret.len() < MIN_AUTH_SECRET_LEN {
+            problems.push(format!(
+                "auth_token_secret is shorter than {} characters",
+                MIN_AUTH_SECRET_LEN
+            ));
+        }
+        if !(1..=65535).contains(&self.port) {
+            problems.push(format!("port {} is out of range", self.port));
+        }
+        if !self.sync_dir.starts_with('/') {
+            problems.push(format!("sync_dir '{}' isn't an absolute path", self.sync_dir));
+        }
+        if self.auth_token_expiry_hours <= 0 {
+            problems.push("auth_token_expiry_hours must be positive".to_string());
+        }
+        if self.enable_redis_locks && !self.redis_url.starts_with("redis") {
+            problems.push("enable_redis_locks is set without a redis:// URL".to_string());
+        }
+        if self.enable_web_ui && !self.database_url.starts_with("postgres") {
+            problems.push("enable_web_ui is set without a postgres:// database URL".to_string());
+        }
+        problems
     }
 }
 
 // Main configuration loading
 impl Config {
     pub fn load() -> anyhow::Result<Config> {
+        Self::load_unselected()?.select_server()
+    }
+
+    /// `load` without `select_server`: `[cli]` as written, for commands that
+    /// report or change which server is selected
+    pub fn load_unselected() -> anyhow::Result<Config> {
         let home_dir = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
         let user_config_path = home_dir.join(".auxin/config.toml");
         let project_config_path = PathBuf::from(".auxin/config.toml");
@@ -263,9 +1165,198 @@ impl Config {
             .merge(Env::prefixed("AUXIN_"))
             .extract()?;
 
+        // An unreadable features file is reported by the CLI, not here
+        let features = RepoFeatures::load(Path::new(".")).ok().flatten().unwrap_or_default();
+        Ok(config.resolve_secrets()?.apply_features(features.features))
+    }
+
+    /// Turn off what the repository's features turn off
+    ///
+    /// `save_to_file` writes the settings back as they were written.
+    pub fn apply_features(mut self, features: Features) -> Config {
+        let mut off = Vec::new();
+        if !features.server_sync {
+            off.extend(["cli.use_server_locks", "cli.use_server_metadata", "meta_branch.enabled"]);
+        }
+        if !features.metadata_sidecars {
+            off.extend(["cli.use_server_metadata", "integrations.spotlight.enabled"]);
+        }
+        for key in off {
+            let setting = match key {
+                "cli.use_server_locks" => &mut self.cli.use_server_locks,
+                "cli.use_server_metadata" => &mut self.cli.use_server_metadata,
+                "meta_branch.enabled" => &mut self.meta_branch.enabled,
+                _ => &mut self.integrations.spotlight.enabled,
+            };
+            self.feature_overrides.entry(key.to_string()).or_insert(*setting);
+            *setting = false;
+        }
+        self.features = features;
+        self
+    }
+
+    /// Custom metadata fields for a project type: those in
+    /// `[metadata_fields.default]`, then the type's own, which replace
+    /// default fields of the same name
+    pub fn metadata_fields_for(&self, project_type: &str) -> Vec<MetadataField> {
+        let mut fields: Vec<MetadataField> = Vec::new();
+        for section in ["default", project_type] {
+            for field in self.metadata_fields.get(section).into_iter().flatten() {
+                fields.retain(|f| !f.name.eq_ignore_ascii_case(&field.name));
+                fields.push(field.clone());
+            }
+        }
+        fields
+    }
+
+    /// The `[servers.<name>]` entry `[cli] server` chooses, if configured
+    pub fn selected_server(&self) -> Option<&NamedServer> {
+        self.servers.get(&self.cli.server)
+    }
+
+    /// Point `[cli]` at the server `[cli] server` chooses, so everything
+    /// that talks to "the server" talks to that one
+    ///
+    /// Its `url` and `token` replace those in `[cli]`, references included,
+    /// so saving the config writes them back unresolved. An unknown name is
+    /// an [`UnknownServer`] error rather than a quiet fallback to `[cli]`.
+    pub fn select_server(mut self) -> anyhow::Result<Config> {
+        if self.cli.server.is_empty() {
+            return Ok(self);
+        }
+        let Some(server) = self.selected_server().cloned() else {
+            return Err(UnknownServer {
+                name: self.cli.server.clone(),
+                configured: self.servers.keys().cloned().collect(),
+            }
+            .into());
+        };
+        let name = self.cli.server.clone();
+        for key in ["url", "token"] {
+            let cli_key = format!("cli.{}", key);
+            self.resolved.remove(&cli_key);
+            if let Some(raw) = self.resolved.get(&format!("servers.{}.{}", name, key)).cloned() {
+                self.resolved.insert(cli_key, raw);
+            }
+        }
+        self.cli.url = server.url;
+        self.cli.token = server.token;
+        if let Some(namespace) = server.namespace {
+            self.cli.default_namespace = namespace;
+        }
+        Ok(self)
+    }
+
+    /// Fill in secrets written as `${ENV_VAR}` or kept in
+    /// `auth_token_secret_file`
+    ///
+    /// Values of secret-bearing keys (auth secrets, tokens, passwords, Redis
+    /// and database URLs, webhook URLs) may reference environment variables,
+    /// so they can stay out of the file; other values are taken as written.
+    /// `save_to_file` writes the references back rather than the secrets.
+    ///
+    /// A reference to an unset variable is left as written and listed by
+    /// `unresolved`, so a CLI that doesn't need a server secret still gets
+    /// the rest of its configuration; the server refuses to start while one
+    /// remains under `[server]`.
+    pub fn resolve_secrets(self) -> anyhow::Result<Config> {
+        let mut resolved = BTreeMap::new();
+        let mut unresolved = Vec::new();
+        let mut value = toml::Value::try_from(&self)?;
+        expand_strings(&mut value, "", false, &mut resolved, &mut unresolved);
+        let mut config: Config = value.try_into()?;
+
+        let secret_file = &config.server.auth_token_secret_file;
+        if !secret_file.is_empty() {
+            let secret = std::fs::read_to_string(secret_file).map_err(|e| {
+                anyhow::anyhow!("Failed to read auth_token_secret_file {}: {}", secret_file, e)
+            })?;
+            let secret = secret.trim_end_matches(['\n', '\r']).to_string();
+            resolved.insert(
+                "server.auth_token_secret".to_string(),
+                (config.server.auth_token_secret.clone(), secret.clone()),
+            );
+            config.server.auth_token_secret = secret;
+        }
+
+        config.resolved = resolved;
+        config.unresolved = unresolved;
         Ok(config)
     }
 
+    /// `${...}` references left unresolved, as `key: reason`
+    pub fn unresolved(&self) -> &[String] {
+        &self.unresolved
+    }
+
+    /// Dotted keys whose values came from the environment or a secret file
+    pub fn resolved_keys(&self) -> impl Iterator<Item = &str> {
+        self.resolved.keys().map(String::as_str)
+    }
+
+    /// The configuration as it should be written: values that were resolved
+    /// and haven't changed since go back to their `${...}` form
+    pub fn to_unresolved_toml(&self) -> anyhow::Result<toml::Value> {
+        let mut value = toml::Value::try_from(self)?;
+        for (path, (raw, resolved)) in &self.resolved {
+            let mut slot = Some(&mut value);
+            for key in path.split('.') {
+                slot = slot.and_then(|v| match key.split_once('[') {
+                    Some((key, index)) => {
+                        let index: usize = index.trim_end_matches(']').parse().ok()?;
+                        v.get_mut(key)?.get_mut(index)
+                    }
+                    None => v.get_mut(key),
+                });
+            }
+            if let Some(slot) = slot {
+                if slot.as_str() == Some(resolved.as_str()) {
+                    *slot = toml::Value::String(raw.clone());
+                }
+            }
+        }
+        for (path, written) in &self.feature_overrides {
+            let slot = path.split('.').try_fold(&mut value, |v, key| v.get_mut(key));
+            if let Some(slot) = slot {
+                if slot.as_bool() == Some(false) {
+                    *slot = toml::Value::Boolean(*written);
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    /// Retry settings for a class of network operation
+    ///
+    /// Values missing from `[network.<class>]` come from `[network]`, except
+    /// that server requests and connectivity checks only retry when their
+    /// section sets `max_retries`. Timeouts default to 120s for pushes and pulls,
+    /// `[cli] timeout_secs` for server requests and
+    /// `connectivity_check_timeout_s` for connectivity checks.
+    pub fn retry_settings(&self, class: NetworkClass) -> RetrySettings {
+        let network = &self.network;
+        let (overrides, default_retries, default_timeout) = match class {
+            NetworkClass::Push => (&network.push, network.max_retries, 120),
+            NetworkClass::Pull => (&network.pull, network.max_retries, 120),
+            NetworkClass::ServerApi => (&network.server_api, 0, self.cli.timeout_secs),
+            NetworkClass::Connectivity => {
+                (&network.connectivity, 0, network.connectivity_check_timeout_s)
+            }
+        };
+        let non_negative = |value: i64| value.max(0) as u64;
+
+        RetrySettings {
+            max_retries: non_negative(overrides.max_retries.unwrap_or(default_retries)) as u32,
+            initial_backoff_ms: non_negative(
+                overrides.initial_backoff_ms.unwrap_or(network.initial_backoff_ms),
+            ),
+            max_backoff_ms: non_negative(
+                overrides.max_backoff_ms.unwrap_or(network.max_backoff_ms),
+            ),
+            timeout_s: non_negative(overrides.timeout_s.unwrap_or(default_timeout)).max(1),
+        }
+    }
+
     pub fn project_config_path() -> Option<PathBuf> {
         let path = PathBuf::from(".auxin/config.toml");
         if path.exists() || path.parent().map(|p| p.exists()).unwrap_or(false) {
@@ -283,8 +1374,8 @@ impl Config {
             std::fs::create_dir_all(parent)?;
         }
 
-        // Serialize to TOML
-        let toml_str = toml::to_string_pretty(self)?;
+        // Serialize to TOML, keeping secrets as references
+        let toml_str = toml::to_string_pretty(&self.to_unresolved_toml()?)?;
 
         // Write to file
         let mut file = std::fs::File::create(path)?;
@@ -296,4 +1387,181 @@ impl Config {
 
 pub fn load_config() -> anyhow::Result<Config> {
     Config::load()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_env() {
+        std::env::set_var("AUXIN_TEST_EXPAND_HOST", "studio.local");
+        assert_eq!(
+            expand_env("https://${AUXIN_TEST_EXPAND_HOST}:3000").unwrap(),
+            "https://studio.local:3000"
+        );
+        assert_eq!(expand_env("$${AUXIN_TEST_EXPAND_HOST}").unwrap(), "${AUXIN_TEST_EXPAND_HOST}");
+        assert_eq!(expand_env("cost $5").unwrap(), "cost $5");
+
+        let unset = expand_env("${AUXIN_TEST_EXPAND_UNSET}").unwrap_err();
+        assert!(unset.to_string().contains("AUXIN_TEST_EXPAND_UNSET is not set"));
+        let unclosed = expand_env("redis://${AUXIN_TEST_EXPAND_HOST").unwrap_err();
+        assert!(unclosed.to_string().contains("Unclosed"));
+    }
+
+    #[test]
+    fn test_expand_strings_nested() {
+        std::env::set_var("AUXIN_TEST_NESTED_TOKEN", "s3cret");
+        let mut value: toml::Value = toml::from_str(
+            r#"
+            plain = "no references"
+            [servers.studio]
+            token = "${AUXIN_TEST_NESTED_TOKEN}"
+            escaped_token = "$${AUXIN_TEST_NESTED_TOKEN}"
+            [hooks]
+            webhooks = ["https://${AUXIN_TEST_NESTED_TOKEN}@hooks", "${AUXIN_TEST_NESTED_UNSET}"]
+            label = "v${AUXIN_TEST_NESTED_TOKEN}"
+            [aliases]
+            token = "commit -m '${AUXIN_TEST_NESTED_TOKEN}'"
+            "#,
+        )
+        .unwrap();
+        let mut resolved = BTreeMap::new();
+        let mut unresolved = Vec::new();
+        expand_strings(&mut value, "", false, &mut resolved, &mut unresolved);
+
+        assert_eq!(value["servers"]["studio"]["token"].as_str(), Some("s3cret"));
+        assert_eq!(value["hooks"]["webhooks"][0].as_str(), Some("https://s3cret@hooks"));
+        assert_eq!(
+            value["servers"]["studio"]["escaped_token"].as_str(),
+            Some("${AUXIN_TEST_NESTED_TOKEN}")
+        );
+        assert_eq!(
+            resolved.get("servers.studio.token"),
+            Some(&("${AUXIN_TEST_NESTED_TOKEN}".to_string(), "s3cret".to_string()))
+        );
+        assert!(resolved.contains_key("hooks.webhooks[0]"));
+        assert!(resolved.contains_key("servers.studio.escaped_token"));
+        assert!(!resolved.contains_key("plain"));
+
+        // Only secret-bearing keys are expanded; aliases never are
+        assert_eq!(value["hooks"]["label"].as_str(), Some("v${AUXIN_TEST_NESTED_TOKEN}"));
+        assert_eq!(
+            value["aliases"]["token"].as_str(),
+            Some("commit -m '${AUXIN_TEST_NESTED_TOKEN}'")
+        );
+
+        // Left as written, and reported
+        assert_eq!(value["hooks"]["webhooks"][1].as_str(), Some("${AUXIN_TEST_NESTED_UNSET}"));
+        assert_eq!(unresolved.len(), 1);
+        assert!(unresolved[0].starts_with("hooks.webhooks[1]: "));
+    }
+
+    fn config_with_servers(server: &str) -> Config {
+        toml::from_str(&format!(
+            r#"
+            [cli]
+            url = "http://localhost:3000"
+            token = "local-token"
+            server = "{}"
+            [servers.studio]
+            url = "https://studio.example.com"
+            token = "${{AUXIN_TEST_STUDIO_TOKEN}}"
+            namespace = "studio-a"
+            [servers.home]
+            url = "http://home.local:3000"
+            "#,
+            server
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_named_server() {
+        std::env::set_var("AUXIN_TEST_STUDIO_TOKEN", "studio-secret");
+        let config = config_with_servers("studio").resolve_secrets().unwrap();
+        assert_eq!(config.selected_server().unwrap().url, "https://studio.example.com");
+
+        let config = config.select_server().unwrap();
+        assert_eq!(config.cli.url, "https://studio.example.com");
+        assert_eq!(config.cli.token, "studio-secret");
+        assert_eq!(config.cli.default_namespace, "studio-a");
+        // Saved back as the reference, not the secret
+        let written = config.to_unresolved_toml().unwrap();
+        for token in [&written["cli"]["token"], &written["servers"]["studio"]["token"]] {
+            assert_eq!(token.as_str(), Some("${AUXIN_TEST_STUDIO_TOKEN}"));
+        }
+
+        // A server without a token sends none
+        let config = config_with_servers("home").resolve_secrets().unwrap();
+        let config = config.select_server().unwrap();
+        assert_eq!(config.cli.url, "http://home.local:3000");
+        assert_eq!(config.cli.token, "");
+        assert_eq!(config.cli.default_namespace, default_namespace());
+    }
+
+    #[test]
+    fn test_select_unknown_server() {
+        let config = config_with_servers("nowhere");
+        assert!(config.selected_server().is_none());
+
+        let error = config.select_server().unwrap_err();
+        let unknown = error.downcast_ref::<UnknownServer>().unwrap();
+        assert_eq!(unknown.name, "nowhere");
+        assert_eq!(unknown.configured, ["home", "studio"]);
+        assert!(error.to_string().contains("configured servers: home, studio"));
+
+        // No server chosen: [cli] as written
+        let config = config_with_servers("").select_server().unwrap();
+        assert_eq!(config.cli.url, "http://localhost:3000");
+        assert_eq!(config.cli.token, "local-token");
+    }
+
+    #[test]
+    fn test_features_incompatibility() {
+        assert_eq!(RepoFeatures::default().incompatibility(), None);
+
+        let newer = RepoFeatures { format: FEATURES_FORMAT + 1, ..Default::default() };
+        assert!(newer.incompatibility().unwrap().contains("newer than this auxin"));
+
+        let unknown: RepoFeatures = toml::from_str(
+            "format = 1\n[features]\nstem_tracking = true\nold_idea = false\n",
+        )
+        .unwrap();
+        let reason = unknown.incompatibility().unwrap();
+        assert!(reason.contains("stem_tracking"));
+        assert!(!reason.contains("old_idea"));
+
+        // An unknown feature that is off changes nothing
+        let off: RepoFeatures = toml::from_str("[features]\nold_idea = false\n").unwrap();
+        assert_eq!(off.incompatibility(), None);
+    }
+
+    #[test]
+    fn test_server_validate() {
+        let production = Server {
+            auth_token_secret: "x".repeat(MIN_AUTH_SECRET_LEN),
+            sync_dir: "/var/auxin".to_string(),
+            ..Default::default()
+        };
+        assert!(production.validate().is_empty());
+
+        let placeholder =
+            Server { auth_token_secret: DEV_AUTH_SECRET.to_string(), ..production.clone() };
+        assert_eq!(
+            placeholder.validate(),
+            vec!["auth_token_secret is the development placeholder".to_string()]
+        );
+        let defaults = Server::default().validate();
+        assert!(defaults.iter().any(|p| p.contains("development placeholder")));
+
+        let short = Server { auth_token_secret: "short".to_string(), ..production.clone() };
+        assert!(short.validate()[0].contains("shorter than"));
+
+        let redis = Server { enable_redis_locks: true, ..production };
+        assert_eq!(
+            redis.validate(),
+            vec!["enable_redis_locks is set without a redis:// URL".to_string()]
+        );
+    }
+}
@@ -41,3 +41,7 @@ pub mod logger;
 // Re-export the main subprocess module
 mod oxen_subprocess;
 pub use oxen_subprocess::*;
+
+// Progress parsed from oxen's output while a command runs
+pub mod progress_event;
+pub use progress_event::ProgressEvent;
@@ -1,9 +1,64 @@
+//! CLI logging macros
+//!
+//! Messages go to stderr. When a log file is set (`AUXIN_LOG_FILE`, which
+//! the daemon sets for the commands it runs), each message is also appended
+//! there as one JSON [`Record`] per line, tagged with the project in
+//! `AUXIN_LOG_PROJECT`, so `auxin daemon logs` can filter them.
+
+use chrono::{DateTime, Utc};
 #[allow(unused_imports)] // Required for macro expansion
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 static VERBOSE: AtomicBool = AtomicBool::new(false);
 
+static LOG_FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// Where structured records go, and the project they're tagged with
+struct LogFile {
+    path: PathBuf,
+    project: Option<String>,
+}
+
+/// Severity of a log message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "debug" => Ok(Level::Debug),
+            "info" => Ok(Level::Info),
+            "warn" | "warning" => Ok(Level::Warn),
+            "error" => Ok(Level::Error),
+            _ => anyhow::bail!("Unknown log level '{}' (use debug, info, warn or error)", s),
+        }
+    }
+}
+
+/// One line of a structured log file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub ts: DateTime<Utc>,
+    pub level: Level,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub message: String,
+}
+
 /// Enable verbose logging
 pub fn set_verbose(enabled: bool) {
     VERBOSE.store(enabled, Ordering::Relaxed);
@@ -14,12 +69,64 @@ pub fn is_verbose() -> bool {
     VERBOSE.load(Ordering::Relaxed)
 }
 
+/// Also append messages to `path` as JSON records tagged with `project`;
+/// `None` stops
+pub fn set_log_file(path: Option<PathBuf>, project: Option<String>) {
+    if let Ok(mut log_file) = LOG_FILE.lock() {
+        *log_file = path.map(|path| LogFile { path, project });
+    }
+}
+
+/// Set the log file from `AUXIN_LOG_FILE` and `AUXIN_LOG_PROJECT`
+pub fn init_from_env() {
+    if let Some(path) = std::env::var_os("AUXIN_LOG_FILE").filter(|p| !p.is_empty()) {
+        let project = std::env::var("AUXIN_LOG_PROJECT")
+            .ok()
+            .filter(|p| !p.is_empty());
+        set_log_file(Some(PathBuf::from(path)), project);
+    }
+}
+
+/// Append a message to the log file, if one is set
+///
+/// Logging never fails the command, so write errors are ignored.
+pub fn record(level: Level, message: &str) {
+    let Ok(log_file) = LOG_FILE.lock() else {
+        return;
+    };
+    let Some(log_file) = log_file.as_ref() else {
+        return;
+    };
+
+    let record = Record {
+        ts: Utc::now(),
+        level,
+        project: log_file.project.clone(),
+        message: message.to_string(),
+    };
+    let Ok(line) = serde_json::to_string(&record) else {
+        return;
+    };
+    if let Some(parent) = log_file.path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_file.path)
+    {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
 /// Log a verbose debug message (only shown when --verbose is enabled)
 #[macro_export]
 macro_rules! vlog {
     ($($arg:tt)*) => {
         if $crate::logger::is_verbose() {
-            eprintln!("{} {}", "[DEBUG]".bright_blue().bold(), format!($($arg)*));
+            let message = format!($($arg)*);
+            $crate::logger::record($crate::logger::Level::Debug, &message);
+            eprintln!("{} {}", "[DEBUG]".bright_blue().bold(), message);
         }
     };
 }
@@ -27,33 +134,41 @@ macro_rules! vlog {
 /// Log an info message
 #[macro_export]
 macro_rules! info {
-    ($($arg:tt)*) => {
-        eprintln!("{} {}", "[INFO]".bright_green().bold(), format!($($arg)*));
-    };
+    ($($arg:tt)*) => {{
+        let message = format!($($arg)*);
+        $crate::logger::record($crate::logger::Level::Info, &message);
+        eprintln!("{} {}", "[INFO]".bright_green().bold(), message);
+    }};
 }
 
 /// Log a warning message
 #[macro_export]
 macro_rules! warn {
-    ($($arg:tt)*) => {
-        eprintln!("{} {}", "[WARN]".yellow().bold(), format!($($arg)*));
-    };
+    ($($arg:tt)*) => {{
+        let message = format!($($arg)*);
+        $crate::logger::record($crate::logger::Level::Warn, &message);
+        eprintln!("{} {}", "[WARN]".yellow().bold(), message);
+    }};
 }
 
 /// Log an error message
 #[macro_export]
 macro_rules! error {
-    ($($arg:tt)*) => {
-        eprintln!("{} {}", "[ERROR]".red().bold(), format!($($arg)*));
-    };
+    ($($arg:tt)*) => {{
+        let message = format!($($arg)*);
+        $crate::logger::record($crate::logger::Level::Error, &message);
+        eprintln!("{} {}", "[ERROR]".red().bold(), message);
+    }};
 }
 
 /// Log a success message
 #[macro_export]
 macro_rules! success {
-    ($($arg:tt)*) => {
-        println!("{} {}", "✓".bright_green().bold(), format!($($arg)*));
-    };
+    ($($arg:tt)*) => {{
+        let message = format!($($arg)*);
+        $crate::logger::record($crate::logger::Level::Info, &message);
+        println!("{} {}", "✓".bright_green().bold(), message);
+    }};
 }
 
 #[cfg(test)]
@@ -219,6 +334,29 @@ mod tests {
         error!("{}", "");
     }
 
+    #[test]
+    fn test_log_file_records() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("logs").join("daemon.log");
+        set_log_file(Some(path.clone()), Some("/music/Song.logicx".to_string()));
+        warn!("Disk nearly full: {}%", 97);
+        set_log_file(None, None);
+        info!("Not recorded");
+
+        // Other tests may log while the file is set; keep only ours
+        let records: Vec<Record> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .filter(|r: &Record| r.message.starts_with("Disk") || r.message == "Not recorded")
+            .collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].level, Level::Warn);
+        assert_eq!(records[0].project.as_deref(), Some("/music/Song.logicx"));
+        assert_eq!(records[0].message, "Disk nearly full: 97%");
+        assert_eq!("warning".parse::<Level>().unwrap(), Level::Warn);
+    }
+
     #[test]
     fn test_logger_module_organization() {
         // Verify the module exports what we expect
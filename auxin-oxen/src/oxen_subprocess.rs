@@ -1,3 +1,4 @@
+use crate::progress_event::ProgressEvent;
 use crate::{error, info, vlog};
 /// Oxen subprocess wrapper for executing actual oxen CLI commands
 ///
@@ -20,11 +21,14 @@ use crate::{error, info, vlog};
 /// let result = oxen.init(Path::new("my_project.logicx"));
 /// ```
 use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::{ErrorKind, Read};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Output, Stdio};
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use wait_timeout::ChildExt;
@@ -547,6 +551,54 @@ fn sanitize_message(message: &str) -> Result<String> {
     Ok(message.to_string())
 }
 
+/// Arguments for `oxen push|pull [remote] [branch]`
+fn transfer_args<'a>(
+    command: &'a str,
+    remote: Option<&'a str>,
+    branch: Option<&'a str>,
+) -> Vec<&'a str> {
+    let mut args = vec![command];
+    args.extend(remote);
+    args.extend(branch);
+    args
+}
+
+/// Read `reader` to the end, sending a [`ProgressEvent`] for each line or
+/// `\r` redraw that has one, and return everything read
+fn read_progress(mut reader: impl Read, progress: &Sender<ProgressEvent>) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut line_start = 0;
+    let mut buf = [0u8; 4096];
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+        output.extend_from_slice(&buf[..n]);
+
+        while let Some(len) = output[line_start..]
+            .iter()
+            .position(|&b| b == b'\r' || b == b'\n')
+        {
+            let line = String::from_utf8_lossy(&output[line_start..line_start + len]);
+            if let Some(event) = ProgressEvent::parse(&line) {
+                // The receiver going away just means nobody is watching
+                let _ = progress.send(event);
+            }
+            line_start += len + 1;
+        }
+    }
+
+    let rest = String::from_utf8_lossy(&output[line_start..]);
+    if let Some(event) = ProgressEvent::parse(&rest) {
+        let _ = progress.send(event);
+    }
+    output
+}
+
 /// Wrapper for executing Oxen CLI commands via subprocess.
 ///
 /// This struct provides a Rust interface to the `oxen` command-line tool by executing
@@ -903,6 +955,8 @@ impl OxenSubprocess {
         Ok(CommitInfo {
             id: commit_id,
             message: message.to_string(),
+            author: None,
+            timestamp: Some(Utc::now()),
         })
     }
 
@@ -938,6 +992,25 @@ impl OxenSubprocess {
         Ok(commits)
     }
 
+    /// Get the commit log of a branch other than the current one
+    pub fn log_branch(
+        &self,
+        repo_path: &Path,
+        branch: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<CommitInfo>> {
+        vlog!("Fetching commit log of branch: {}", branch);
+
+        let mut args = vec!["log".to_string(), branch.to_string()];
+        if let Some(n) = limit {
+            args.push(format!("-n={}", n));
+        }
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        let output = self.run_command(&args, Some(repo_path), None)?;
+        self.parse_log_output(&output)
+    }
+
     /// Get repository status (with caching)
     pub fn status(&self, repo_path: &Path) -> Result<StatusInfo> {
         vlog!("Getting repository status");
@@ -1039,17 +1112,32 @@ impl OxenSubprocess {
     pub fn push(&self, repo_path: &Path, remote: Option<&str>, branch: Option<&str>) -> Result<()> {
         vlog!("Pushing to remote");
 
-        let mut args = vec!["push"];
-        if let Some(r) = remote {
-            args.push(r);
-        }
-        if let Some(b) = branch {
-            args.push(b);
-        }
-
         // Use network timeout for push operations
         let timeout = Some(Duration::from_secs(self.config.network_timeout));
-        self.run_command(&args, Some(repo_path), timeout)?;
+        self.run_command(&transfer_args("push", remote, branch), Some(repo_path), timeout)?;
+
+        info!("Pushed to remote");
+        Ok(())
+    }
+
+    /// Push to remote, sending each progress update oxen prints to `progress`
+    /// while the push runs
+    pub fn push_with_progress(
+        &self,
+        repo_path: &Path,
+        remote: Option<&str>,
+        branch: Option<&str>,
+        progress: &Sender<ProgressEvent>,
+    ) -> Result<()> {
+        vlog!("Pushing to remote");
+
+        let timeout = Some(Duration::from_secs(self.config.network_timeout));
+        self.run_command_with_progress(
+            &transfer_args("push", remote, branch),
+            Some(repo_path),
+            timeout,
+            progress,
+        )?;
 
         info!("Pushed to remote");
         Ok(())
@@ -1067,6 +1155,42 @@ impl OxenSubprocess {
         Ok(())
     }
 
+    /// Pull a specific remote branch (with network timeout)
+    pub fn pull_from(&self, repo_path: &Path, remote: &str, branch: &str) -> Result<()> {
+        vlog!("Pulling {}/{}", remote, branch);
+
+        let timeout = Some(Duration::from_secs(self.config.network_timeout));
+        self.run_command(&["pull", remote, branch], Some(repo_path), timeout)?;
+        self.invalidate_cache(repo_path);
+
+        info!("Pulled {}/{}", remote, branch);
+        Ok(())
+    }
+
+    /// Pull from remote, sending each progress update oxen prints to
+    /// `progress` while the pull runs
+    pub fn pull_with_progress(
+        &self,
+        repo_path: &Path,
+        remote: Option<&str>,
+        branch: Option<&str>,
+        progress: &Sender<ProgressEvent>,
+    ) -> Result<()> {
+        vlog!("Pulling from remote");
+
+        let timeout = Some(Duration::from_secs(self.config.network_timeout));
+        self.run_command_with_progress(
+            &transfer_args("pull", remote, branch),
+            Some(repo_path),
+            timeout,
+            progress,
+        )?;
+        self.invalidate_cache(repo_path);
+
+        info!("Pulled from remote");
+        Ok(())
+    }
+
     // ========== New Operations ==========
 
     /// Fetch from remote without merging
@@ -1171,6 +1295,43 @@ impl OxenSubprocess {
         Ok(output)
     }
 
+    /// Files that differ between two commits, with their sizes at each
+    ///
+    /// The file list comes from `oxen diff <from> <to>`; sizes are looked
+    /// up with `oxen info` and are `None` when that fails. Files listed as
+    /// modified whose content hash is the same at both commits are left out.
+    pub fn diff_commits(&self, repo_path: &Path, from: &str, to: &str) -> Result<Vec<FileChange>> {
+        vlog!("Diffing commits {} and {}", from, to);
+
+        let output = self.run_command(&["diff", from, to], Some(repo_path), None)?;
+        let info =
+            |path: &Path, revision: &str| self.file_info(repo_path, path, Some(revision)).ok();
+
+        let mut changes = Vec::new();
+        for (path, kind) in self.parse_diff_commits_output(&output) {
+            let old = (kind != FileChangeKind::Added)
+                .then(|| info(&path, from))
+                .flatten();
+            let new = (kind != FileChangeKind::Removed)
+                .then(|| info(&path, to))
+                .flatten();
+            if let (Some(old), Some(new)) = (&old, &new) {
+                if old.hash == new.hash {
+                    continue;
+                }
+            }
+            changes.push(FileChange {
+                path,
+                kind,
+                old_size: old.map(|i| i.size),
+                new_size: new.map(|i| i.size),
+            });
+        }
+
+        vlog!("{} file(s) differ", changes.len());
+        Ok(changes)
+    }
+
     /// Reset/unstage files
     pub fn reset(&self, repo_path: &Path, files: Option<&[&Path]>) -> Result<()> {
         vlog!("Resetting files");
@@ -1196,6 +1357,49 @@ impl OxenSubprocess {
         Ok(())
     }
 
+    /// Discard working-copy changes to files, restoring their committed version
+    pub fn restore_files(&self, repo_path: &Path, files: &[&Path]) -> Result<()> {
+        vlog!("Restoring {} file(s)", files.len());
+
+        let file_args: Vec<String> = files
+            .iter()
+            .map(|f| f.to_string_lossy().to_string())
+            .collect();
+        let mut args = vec!["restore"];
+        args.extend(file_args.iter().map(String::as_str));
+
+        self.run_command(&args, Some(repo_path), None)?;
+        self.invalidate_cache(repo_path);
+
+        info!("Restored {} file(s)", files.len());
+        Ok(())
+    }
+
+    /// Files left with merge conflicts by a pull or merge
+    pub fn merge_conflicts(&self, repo_path: &Path) -> Result<Vec<PathBuf>> {
+        vlog!("Listing merge conflicts");
+
+        let output = self.run_command(&["status"], Some(repo_path), None)?;
+        let conflicts = self.parse_merge_conflicts(&output);
+
+        vlog!("{} conflicting file(s)", conflicts.len());
+        Ok(conflicts)
+    }
+
+    /// Replace a conflicting file with one side of the merge
+    pub fn checkout_conflict(&self, repo_path: &Path, file: &Path, side: MergeSide) -> Result<()> {
+        vlog!("Taking {:?} version of {}", side, file.display());
+
+        let file = sanitize_path(file, Some(repo_path))?;
+        let flag = match side {
+            MergeSide::Ours => "--ours",
+            MergeSide::Theirs => "--theirs",
+        };
+        self.run_command(&["checkout", flag, &file], Some(repo_path), None)?;
+        self.invalidate_cache(repo_path);
+        Ok(())
+    }
+
     /// Create a tag
     pub fn tag(&self, repo_path: &Path, tag_name: &str, message: Option<&str>) -> Result<()> {
         vlog!("Creating tag: {}", tag_name);
@@ -1315,6 +1519,28 @@ impl OxenSubprocess {
         Ok(())
     }
 
+    /// Get a tracked file's content hash and size, at `revision` or HEAD
+    ///
+    /// Uses `oxen info --json`; fails when the file isn't tracked at that
+    /// revision.
+    pub fn file_info(
+        &self,
+        repo_path: &Path,
+        file: &Path,
+        revision: Option<&str>,
+    ) -> Result<FileInfo> {
+        vlog!("Getting file info: {}", file.display());
+
+        let file = file.to_string_lossy();
+        let mut args = vec!["info", "--json", file.as_ref()];
+        if let Some(revision) = revision {
+            args.push(revision);
+        }
+
+        let output = self.run_command(&args, Some(repo_path), None)?;
+        self.parse_file_info_output(&output)
+    }
+
     // ========== Private Helper Methods ==========
 
     /// Run an oxen command with timeout
@@ -1357,20 +1583,91 @@ impl OxenSubprocess {
                 let _ = child.kill();
                 let _ = child.wait();
 
-                let cmd_str = args.join(" ");
-                error!(
-                    "Command timed out after {:?}: oxen {}",
-                    timeout_duration, cmd_str
-                );
+                Err(self.timeout_error(timeout_duration, args))
+            }
+        }
+    }
+
+    /// Run an oxen command with timeout, parsing its output as it arrives
+    ///
+    /// Both streams are read on their own threads, split on `\r` as well as
+    /// `\n` so each redraw of a progress bar is seen, and every line that
+    /// parses as a [`ProgressEvent`] is sent to `progress`. The complete
+    /// output is then handled as in `run_command`.
+    fn run_command_with_progress(
+        &self,
+        args: &[&str],
+        cwd: Option<&Path>,
+        timeout: Option<Duration>,
+        progress: &Sender<ProgressEvent>,
+    ) -> Result<String> {
+        if self.verbose {
+            vlog!("Running: {} {}", self.config.oxen_path, args.join(" "));
+        }
 
-                Err(anyhow!(OxenError::Timeout(format!(
-                    "Command timed out after {:?}: oxen {}",
-                    timeout_duration, cmd_str
-                ))))
+        let mut cmd = Command::new(&self.config.oxen_path);
+        cmd.args(args).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        if let Some(dir) = cwd {
+            cmd.current_dir(dir);
+        }
+
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("Failed to spawn oxen command: {}", args.join(" ")))?;
+        let child_stdout = child.stdout.take().context("oxen stdout was not piped")?;
+        let child_stderr = child.stderr.take().context("oxen stderr was not piped")?;
+
+        let timeout_duration = timeout.unwrap_or(Duration::from_secs(self.config.default_timeout));
+
+        let (status, stdout, stderr) = std::thread::scope(|scope| {
+            let stdout_sender = progress.clone();
+            let stderr_sender = progress.clone();
+            let stdout = scope.spawn(move || read_progress(child_stdout, &stdout_sender));
+            let stderr = scope.spawn(move || read_progress(child_stderr, &stderr_sender));
+
+            let status = child.wait_timeout(timeout_duration);
+            if !matches!(status, Ok(Some(_))) {
+                // Timed out or couldn't wait - kill the process so the
+                // readers see the pipes close
+                let _ = child.kill();
+                let _ = child.wait();
             }
+
+            (
+                status,
+                stdout.join().unwrap_or_default(),
+                stderr.join().unwrap_or_default(),
+            )
+        });
+
+        let status = status
+            .with_context(|| format!("Error waiting for oxen command: {}", args.join(" ")))?;
+
+        match status {
+            Some(status) => self.handle_output(
+                Output {
+                    status,
+                    stdout,
+                    stderr,
+                },
+                args,
+            ),
+            None => Err(self.timeout_error(timeout_duration, args)),
         }
     }
 
+    /// Log and build the error for a command killed after `timeout`
+    fn timeout_error(&self, timeout: Duration, args: &[&str]) -> anyhow::Error {
+        let cmd_str = args.join(" ");
+        error!("Command timed out after {:?}: oxen {}", timeout, cmd_str);
+
+        anyhow!(OxenError::Timeout(format!(
+            "Command timed out after {:?}: oxen {}",
+            timeout, cmd_str
+        )))
+    }
+
     /// Collect output from completed child process
     fn collect_output(&self, child: Child) -> Result<Output> {
         let output = child
@@ -1446,8 +1743,7 @@ impl OxenSubprocess {
     /// Parse log output into CommitInfo structs
     fn parse_log_output(&self, output: &str) -> Result<Vec<CommitInfo>> {
         let mut commits = Vec::new();
-        let mut current_id = None;
-        let mut current_message = String::new();
+        let mut current: Option<CommitInfo> = None;
 
         for line in output.lines() {
             let trimmed = line.trim();
@@ -1455,39 +1751,178 @@ impl OxenSubprocess {
             // Look for commit hash line
             if let Some(hash) = trimmed.strip_prefix("commit ") {
                 // Save previous commit if exists
-                if let Some(id) = current_id.take() {
-                    commits.push(CommitInfo {
-                        id,
-                        message: current_message.trim().to_string(),
-                    });
-                    current_message.clear();
-                }
+                commits.extend(current.take());
+                current = Some(CommitInfo {
+                    id: hash.trim().to_string(),
+                    ..Default::default()
+                });
+                continue;
+            }
+            let Some(commit) = current.as_mut() else {
+                continue;
+            };
 
-                // Extract new commit hash
-                current_id = Some(hash.trim().to_string());
-            } else if !trimmed.is_empty()
-                && !trimmed.starts_with("Author:")
-                && !trimmed.starts_with("Date:")
-            {
-                // This is part of the commit message
-                if !current_message.is_empty() {
-                    current_message.push('\n');
+            // Author and date come before the message, which is indented
+            if commit.message.is_empty() {
+                if let Some(author) = line.strip_prefix("Author:") {
+                    commit.author = Some(author.trim().to_string());
+                    continue;
+                }
+                if let Some(date) = line.strip_prefix("Date:") {
+                    commit.timestamp = parse_log_date(date.trim());
+                    continue;
+                }
+                if trimmed.is_empty() {
+                    continue;
                 }
-                current_message.push_str(trimmed);
             }
+
+            // This is part of the commit message; blank lines within it
+            // are kept so metadata blocks stay separate from the text
+            if !commit.message.is_empty() {
+                commit.message.push('\n');
+            }
+            commit.message.push_str(trimmed);
         }
 
         // Don't forget the last commit
-        if let Some(id) = current_id {
-            commits.push(CommitInfo {
-                id,
-                message: current_message.trim().to_string(),
-            });
+        commits.extend(current);
+        for commit in &mut commits {
+            commit.message = commit.message.trim().to_string();
         }
 
         Ok(commits)
     }
 
+    /// Parse `oxen info --json` output
+    fn parse_file_info_output(&self, output: &str) -> Result<FileInfo> {
+        let value: serde_json::Value = serde_json::from_str(output.trim())
+            .map_err(|e| anyhow::anyhow!("Unexpected oxen info output: {}", e))?;
+
+        let hash = value
+            .get("hash")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("oxen info output has no hash"))?;
+        let size = value
+            .get("size")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("oxen info output has no size"))?;
+
+        Ok(FileInfo {
+            hash: hash.to_string(),
+            size,
+        })
+    }
+
+    /// Parse the file list of `oxen diff <from> <to>`
+    ///
+    /// Accepts section headers ("Added Files", "Removed Files:"), labelled
+    /// lines ("modified: path") and status codes ("M  path"). Once a content
+    /// diff starts ("---", "+++", "@@"), its lines are skipped up to the
+    /// next line that isn't part of a hunk.
+    fn parse_diff_commits_output(&self, output: &str) -> Vec<(PathBuf, FileChangeKind)> {
+        let mut changes = Vec::new();
+        let mut section = None;
+        let mut in_hunk = false;
+
+        for line in output.lines() {
+            if line.starts_with("---") || line.starts_with("+++") || line.starts_with("@@") {
+                in_hunk = true;
+                section = None;
+                continue;
+            }
+            if in_hunk {
+                if line.is_empty() || line.starts_with([' ', '+', '-', '\\']) {
+                    continue;
+                }
+                in_hunk = false;
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with(['+', '-', '@', '(', '=']) {
+                continue;
+            }
+
+            // Section headers
+            let mut words = trimmed.trim_end_matches(':').split_whitespace();
+            if let (Some(kind), Some(noun), None) = (
+                words.next().and_then(FileChangeKind::from_label),
+                words.next(),
+                words.next(),
+            ) {
+                let noun = noun.to_ascii_lowercase();
+                if noun.starts_with("file") || noun.starts_with("dir") {
+                    section = Some(kind);
+                    continue;
+                }
+            }
+
+            // "modified: path"
+            if let Some((label, path)) = trimmed.split_once(':') {
+                if let Some(kind) = FileChangeKind::from_label(label.trim()) {
+                    if !path.trim().is_empty() {
+                        changes.push((PathBuf::from(path.trim()), kind));
+                    }
+                    continue;
+                }
+            }
+
+            // "M  path"
+            if let Some(path) = trimmed.get(1..).and_then(|rest| rest.strip_prefix("  ")) {
+                let code = &trimmed[..1];
+                if code.chars().all(|c| c.is_ascii_uppercase())
+                    && !path.starts_with(char::is_whitespace)
+                {
+                    if let Some(kind) = FileChangeKind::from_label(code) {
+                        changes.push((PathBuf::from(path), kind));
+                        continue;
+                    }
+                }
+            }
+
+            // Files listed under a section header
+            if let Some(kind) = section {
+                changes.push((PathBuf::from(strip_size_suffix(trimmed)), kind));
+            }
+        }
+
+        changes
+    }
+
+    /// Conflicting files in status output
+    ///
+    /// They're listed under a "Merge conflicts" or "Unmerged paths" header,
+    /// until the next line that isn't indented, or anywhere as
+    /// "both modified: path".
+    fn parse_merge_conflicts(&self, output: &str) -> Vec<PathBuf> {
+        let mut conflicts = Vec::new();
+        let mut in_section = false;
+
+        for line in output.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with("(use") {
+                continue;
+            }
+
+            if let Some((label, path)) = trimmed.split_once(':') {
+                if matches!(label, "both modified" | "both added" | "conflict") {
+                    conflicts.push(PathBuf::from(path.trim()));
+                    continue;
+                }
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                let lower = trimmed.to_ascii_lowercase();
+                in_section = lower.starts_with("merge conflict") || lower.starts_with("unmerged");
+            } else if in_section {
+                conflicts.push(PathBuf::from(trimmed));
+            }
+        }
+
+        conflicts.dedup();
+        conflicts
+    }
+
     /// Parse status output
     fn parse_status_output(&self, output: &str) -> Result<StatusInfo> {
         let mut modified = Vec::new();
@@ -1612,12 +2047,63 @@ impl Default for OxenSubprocess {
 // ========== Data Structures ==========
 
 /// Information about a commit
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct CommitInfo {
     /// Commit hash/ID
     pub id: String,
     /// Commit message
     pub message: String,
+    /// Author as printed by `oxen log`, e.g. "Jane <jane@example.com>"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// When the commit was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Drop a trailing " (2 MB)" size from a listed path
+///
+/// Only a number and a unit at the very end count, so names like
+/// "Vox (local).wav" or "Mix (old)" are kept whole.
+fn strip_size_suffix(line: &str) -> &str {
+    let Some(open) = line.rfind(" (") else {
+        return line;
+    };
+    let Some(size) = line[open + 2..].strip_suffix(')') else {
+        return line;
+    };
+    let is_size = match size.split_once(' ') {
+        Some((number, unit)) => {
+            !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit() || c == '.')
+                && !unit.is_empty()
+                && unit.chars().all(|c| c.is_ascii_alphabetic())
+        }
+        None => false,
+    };
+    if is_size {
+        &line[..open]
+    } else {
+        line
+    }
+}
+
+/// Read the `Date:` of an `oxen log` entry, e.g. "Thursday, 20 November
+/// 2025 18:02:01 +00"; RFC 3339, RFC 2822 and plain dates are accepted too
+fn parse_log_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_str(value, "%A, %d %B %Y %H:%M:%S %#z") {
+        return Some(date.with_timezone(&Utc));
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
 }
 
 /// Repository status information
@@ -1631,6 +2117,71 @@ pub struct StatusInfo {
     pub staged: Vec<PathBuf>,
 }
 
+/// One side of a merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide {
+    /// The branch merged into
+    Ours,
+    /// The branch being merged in
+    Theirs,
+}
+
+/// How a file differs between two commits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl FileChangeKind {
+    /// Read a word or status code from `oxen diff` output
+    fn from_label(label: &str) -> Option<Self> {
+        match label.to_ascii_lowercase().as_str() {
+            "a" | "added" | "new" | "new file" => Some(Self::Added),
+            "d" | "removed" | "deleted" => Some(Self::Removed),
+            "m" | "modified" => Some(Self::Modified),
+            _ => None,
+        }
+    }
+}
+
+/// A file that differs between two commits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub kind: FileChangeKind,
+    /// Size in bytes at the first commit, if known
+    pub old_size: Option<u64>,
+    /// Size in bytes at the second commit, if known
+    pub new_size: Option<u64>,
+}
+
+impl FileChange {
+    /// Bytes gained (or lost, if negative); `None` when a size is unknown
+    pub fn size_delta(&self) -> Option<i64> {
+        let old = match self.kind {
+            FileChangeKind::Added => 0,
+            _ => self.old_size?,
+        };
+        let new = match self.kind {
+            FileChangeKind::Removed => 0,
+            _ => self.new_size?,
+        };
+        Some(new as i64 - old as i64)
+    }
+}
+
+/// Content hash and size of a tracked file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileInfo {
+    /// Content hash
+    pub hash: String,
+    /// Size in bytes
+    pub size: u64,
+}
+
 /// Branch information
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BranchInfo {
@@ -1881,6 +2432,19 @@ modified: another.rs
         assert_eq!(status.staged.len(), 1);
     }
 
+    #[test]
+    fn test_parse_file_info_output() {
+        let oxen = OxenSubprocess::new();
+        let output = r#"{"hash":"9a8b7c","size":52428800,"data_type":"binary","extension":""}"#;
+
+        let info = oxen.parse_file_info_output(output).unwrap();
+        assert_eq!(info.hash, "9a8b7c");
+        assert_eq!(info.size, 52428800);
+
+        assert!(oxen.parse_file_info_output("not json").is_err());
+        assert!(oxen.parse_file_info_output(r#"{"size": 1}"#).is_err());
+    }
+
     #[test]
     fn test_parse_branches_output() {
         let oxen = OxenSubprocess::new();
@@ -1924,6 +2488,142 @@ Date: 2025-01-02
         assert!(commits[0].message.contains("First commit"));
         assert_eq!(commits[1].id, "789xyz012");
         assert!(commits[1].message.contains("Second commit"));
+        assert_eq!(
+            commits[0].author.as_deref(),
+            Some("User <user@example.com>")
+        );
+        assert_eq!(
+            commits[1].timestamp.unwrap().to_rfc3339(),
+            "2025-01-02T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_log_output_oxen_dates() {
+        let oxen = OxenSubprocess::new();
+        let output = "commit 0f1e2d\n\
+                      Author: Ox <ox@example.com>\n\
+                      Date:   Thursday, 20 November 2025 18:02:01 +00\n\
+                      \n\
+                      \x20   Vocal comp\n\
+                      \n\
+                      \x20   BPM: 120\n\
+                      \n\
+                      commit 3c4b5a\n\
+                      Date:   not a date\n\
+                      \n\
+                      \x20   Author: notes\n";
+
+        let commits = oxen.parse_log_output(output).unwrap();
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(
+            commits[0].timestamp.unwrap().to_rfc3339(),
+            "2025-11-20T18:02:01+00:00"
+        );
+        // The blank line before the metadata block is kept
+        assert_eq!(commits[0].message, "Vocal comp\n\nBPM: 120");
+        assert_eq!(commits[1].timestamp, None);
+        assert_eq!(commits[1].author, None);
+        assert_eq!(commits[1].message, "Author: notes");
+    }
+
+    #[test]
+    fn test_parse_diff_commits_output() {
+        let oxen = OxenSubprocess::new();
+        let output = "Added Files\n\
+                      \x20 Audio Files/Vox.wav (2 MB)\n\
+                      \x20 Audio Files/Vox (local).wav\n\
+                      \x20 Bounces/Mix (old) (1.5 KB)\n\
+                      Removed Files:\n\
+                      \x20 Audio Files/Old.wav\n\
+                      modified: Project.logicx/Alternatives/000/ProjectData\n\
+                      M  notes.txt\n\
+                      --- a/notes.txt\n\
+                      +++ b/notes.txt\n\
+                      @@ -1,3 +1,3 @@\n\
+                      \x20A new take\n\
+                      -m first draft\n\
+                      +modified: second draft\n\
+                      \x20Lyrics (v2)\n\
+                      a  lowercase code\n\
+                      D  old.txt\n";
+
+        let changes = oxen.parse_diff_commits_output(output);
+
+        assert_eq!(
+            changes,
+            vec![
+                (PathBuf::from("Audio Files/Vox.wav"), FileChangeKind::Added),
+                (
+                    PathBuf::from("Audio Files/Vox (local).wav"),
+                    FileChangeKind::Added
+                ),
+                (PathBuf::from("Bounces/Mix (old)"), FileChangeKind::Added),
+                (
+                    PathBuf::from("Audio Files/Old.wav"),
+                    FileChangeKind::Removed
+                ),
+                (
+                    PathBuf::from("Project.logicx/Alternatives/000/ProjectData"),
+                    FileChangeKind::Modified
+                ),
+                (PathBuf::from("notes.txt"), FileChangeKind::Modified),
+                (PathBuf::from("old.txt"), FileChangeKind::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_merge_conflicts() {
+        let oxen = OxenSubprocess::new();
+        let output = "On branch main -> 1a2b3c\n\
+                      \n\
+                      Merge conflicts:\n\
+                      \x20 (use \"oxen checkout --theirs\" to take their changes)\n\
+                      \x20 Project.logicx/Alternatives/000/ProjectData\n\
+                      \x20 Audio Files/Vox.wav\n\
+                      Modified Files:\n\
+                      \x20 notes.txt\n\
+                      \x20 both modified: mix.wav\n";
+
+        assert_eq!(
+            oxen.parse_merge_conflicts(output),
+            vec![
+                PathBuf::from("Project.logicx/Alternatives/000/ProjectData"),
+                PathBuf::from("Audio Files/Vox.wav"),
+                PathBuf::from("mix.wav"),
+            ]
+        );
+        assert!(oxen
+            .parse_merge_conflicts("Modified Files:\n  a.wav\n")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_file_change_size_delta() {
+        let change = |kind, old_size, new_size| FileChange {
+            path: PathBuf::from("a.wav"),
+            kind,
+            old_size,
+            new_size,
+        };
+        assert_eq!(
+            change(FileChangeKind::Added, None, Some(10)).size_delta(),
+            Some(10)
+        );
+        assert_eq!(
+            change(FileChangeKind::Removed, Some(10), None).size_delta(),
+            Some(-10)
+        );
+        assert_eq!(
+            change(FileChangeKind::Modified, Some(10), Some(4)).size_delta(),
+            Some(-6)
+        );
+        assert_eq!(
+            change(FileChangeKind::Modified, None, Some(4)).size_delta(),
+            None
+        );
     }
 
     #[test]
@@ -1931,6 +2631,7 @@ Date: 2025-01-02
         let commit = CommitInfo {
             id: "abc123".to_string(),
             message: "Test commit".to_string(),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&commit).unwrap();
@@ -2105,10 +2806,12 @@ Date: 2025-01-01
         let commit1 = CommitInfo {
             id: "abc123".to_string(),
             message: "Test".to_string(),
+            ..Default::default()
         };
         let commit2 = CommitInfo {
             id: "abc123".to_string(),
             message: "Test".to_string(),
+            ..Default::default()
         };
         assert_eq!(commit1, commit2);
     }
@@ -2345,4 +3048,25 @@ Date: 2025-01-01
             assert!(!url.contains('\n'));
         }
     }
+
+    #[test]
+    fn test_transfer_args() {
+        assert_eq!(transfer_args("push", None, None), vec!["push"]);
+        assert_eq!(
+            transfer_args("pull", Some("origin"), Some("main")),
+            vec!["pull", "origin", "main"]
+        );
+    }
+
+    #[test]
+    fn test_read_progress_splits_redraws() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let output: &[u8] = b"Pushing\r1 MB/4 MB\r2 MB/4 MB\r4 MB/4 MB\nPushed to origin/main\n";
+        let collected = read_progress(output, &tx);
+        drop(tx);
+
+        assert_eq!(collected, output);
+        let bytes: Vec<_> = rx.iter().filter_map(|event| event.bytes).collect();
+        assert_eq!(bytes, vec![1_000_000, 2_000_000, 4_000_000]);
+    }
 }
@@ -0,0 +1,211 @@
+//! Progress reported by long-running oxen commands
+//!
+//! `oxen push` and `oxen pull` draw a progress bar on stderr, redrawing it
+//! with `\r`.
+//! [`ProgressEvent::parse`] picks what it can out of one redraw: bytes
+//! transferred and total, files, transfer rate, ETA and percentage. Oxen
+//! doesn't promise a format, so every field is optional and lines with
+//! nothing recognisable are ignored.
+
+use regex::Regex;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// One progress update parsed from oxen's output
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProgressEvent {
+    /// Bytes transferred so far
+    pub bytes: Option<u64>,
+    /// Bytes to transfer in total
+    pub total_bytes: Option<u64>,
+    /// Files transferred so far
+    pub files: Option<u64>,
+    /// Files to transfer in total
+    pub total_files: Option<u64>,
+    pub bytes_per_second: Option<f64>,
+    pub eta: Option<Duration>,
+    /// 0.0 to 100.0
+    pub percent: Option<f64>,
+}
+
+struct Patterns {
+    ansi: Regex,
+    sizes: Regex,
+    rate: Regex,
+    files: Regex,
+    percent: Regex,
+    eta: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        const SIZE: &str = r"(\d+(?:\.\d+)?)\s*([KMGT]i?B|B|bytes)";
+        Patterns {
+            ansi: Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap(),
+            sizes: Regex::new(&format!(r"(?i){}\s*/\s*{}(/s)?", SIZE, SIZE)).unwrap(),
+            rate: Regex::new(&format!(r"(?i){}\s*/\s*s\b", SIZE)).unwrap(),
+            files: Regex::new(r"(?i)(\d+)\s*/\s*(\d+)\s*files?\b").unwrap(),
+            percent: Regex::new(r"(\d+(?:\.\d+)?)\s*%").unwrap(),
+            eta: Regex::new(r"(?i)\beta:?\s*((?:\d+\s*[hms]\s*)+|\d+(?::\d+){1,2})").unwrap(),
+        }
+    })
+}
+
+impl ProgressEvent {
+    /// Parse one line (or `\r` redraw) of oxen output
+    ///
+    /// Returns `None` if the line carries no progress.
+    pub fn parse(line: &str) -> Option<Self> {
+        let p = patterns();
+        let line = p.ansi.replace_all(line, "");
+        let mut event = Self::default();
+
+        if let Some(caps) = p.rate.captures(&line) {
+            event.bytes_per_second = size_to_bytes(&caps[1], &caps[2]);
+        }
+
+        // The first "x / y" pair that isn't itself a rate ("5 MB/s")
+        if let Some(caps) = p.sizes.captures_iter(&line).find(|c| c.get(5).is_none()) {
+            event.bytes = size_to_bytes(&caps[1], &caps[2]).map(|b| b as u64);
+            event.total_bytes = size_to_bytes(&caps[3], &caps[4]).map(|b| b as u64);
+        }
+
+        if let Some(caps) = p.files.captures(&line) {
+            event.files = caps[1].parse().ok();
+            event.total_files = caps[2].parse().ok();
+        }
+
+        if let Some(caps) = p.percent.captures(&line) {
+            event.percent = caps[1].parse::<f64>().ok().map(|pc| pc.min(100.0));
+        }
+
+        if let Some(caps) = p.eta.captures(&line) {
+            event.eta = parse_eta(&caps[1]);
+        }
+
+        (event != Self::default()).then_some(event)
+    }
+
+    /// Take every field `newer` has, keeping ours for the rest
+    ///
+    /// Oxen prints file counts and byte counts on separate lines, so the
+    /// latest full picture is built up from several events.
+    pub fn merge(&mut self, newer: &ProgressEvent) {
+        fn take<T: Copy>(ours: &mut Option<T>, theirs: Option<T>) {
+            if theirs.is_some() {
+                *ours = theirs;
+            }
+        }
+        take(&mut self.bytes, newer.bytes);
+        take(&mut self.total_bytes, newer.total_bytes);
+        take(&mut self.files, newer.files);
+        take(&mut self.total_files, newer.total_files);
+        take(&mut self.bytes_per_second, newer.bytes_per_second);
+        take(&mut self.eta, newer.eta);
+        take(&mut self.percent, newer.percent);
+    }
+
+    /// Percentage done, from `percent` or else the byte counts
+    pub fn percent_done(&self) -> Option<f64> {
+        self.percent
+            .or_else(|| match (self.bytes, self.total_bytes) {
+                (Some(bytes), Some(total)) if total > 0 => {
+                    Some(bytes as f64 / total as f64 * 100.0)
+                }
+                _ => None,
+            })
+    }
+}
+
+/// "12.5" and "MiB" to a byte count
+fn size_to_bytes(number: &str, unit: &str) -> Option<f64> {
+    let number: f64 = number.parse().ok()?;
+    let unit = unit.to_ascii_lowercase();
+    let multiplier = match unit.as_str() {
+        "b" | "bytes" => 1.0,
+        "kb" => 1e3,
+        "mb" => 1e6,
+        "gb" => 1e9,
+        "tb" => 1e12,
+        "kib" => 1024.0,
+        "mib" => 1024.0_f64.powi(2),
+        "gib" => 1024.0_f64.powi(3),
+        "tib" => 1024.0_f64.powi(4),
+        _ => return None,
+    };
+    Some(number * multiplier)
+}
+
+/// "1m 20s", "45s" or "00:01:23"
+fn parse_eta(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    if text.contains(':') {
+        let seconds = text.split(':').try_fold(0u64, |acc, part| {
+            part.parse::<u64>().ok().map(|n| acc * 60 + n)
+        })?;
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let mut seconds = 0;
+    let mut digits = String::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            '0'..='9' => digits.push(c),
+            'h' | 'H' | 'm' | 'M' | 's' | 'S' => {
+                let n: u64 = digits.parse().ok()?;
+                digits.clear();
+                seconds += n * match c.to_ascii_lowercase() {
+                    'h' => 3600,
+                    'm' => 60,
+                    _ => 1,
+                };
+            }
+            _ => return None,
+        }
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress_bar() {
+        let line = "\x1b[32m🐂 push\x1b[0m [####>-----] 12.5 MB/120 MB (2.5 MB/s, eta 43s) 10%";
+        let event = ProgressEvent::parse(line).unwrap();
+        assert_eq!(event.bytes, Some(12_500_000));
+        assert_eq!(event.total_bytes, Some(120_000_000));
+        assert_eq!(event.bytes_per_second, Some(2_500_000.0));
+        assert_eq!(event.eta, Some(Duration::from_secs(43)));
+        assert_eq!(event.percent, Some(10.0));
+
+        let event =
+            ProgressEvent::parse("Pushing 3/12 files 1.5 GiB / 2 GiB ETA 00:01:23").unwrap();
+        assert_eq!(event.files, Some(3));
+        assert_eq!(event.total_files, Some(12));
+        assert_eq!(event.bytes, Some(1_610_612_736));
+        assert_eq!(event.eta, Some(Duration::from_secs(83)));
+    }
+
+    #[test]
+    fn test_parse_ignores_plain_lines() {
+        assert_eq!(ProgressEvent::parse("Pushed to origin/main"), None);
+        assert_eq!(ProgressEvent::parse(""), None);
+        // A rate alone isn't mistaken for transferred/total
+        let event = ProgressEvent::parse("uploading at 800 KiB/s").unwrap();
+        assert_eq!(event.bytes, None);
+        assert_eq!(event.bytes_per_second, Some(819_200.0));
+    }
+
+    #[test]
+    fn test_merge_keeps_earlier_fields() {
+        let mut latest = ProgressEvent::parse("4/10 files").unwrap();
+        latest.merge(&ProgressEvent::parse("5 MB/20 MB eta 1m 5s").unwrap());
+        assert_eq!(latest.files, Some(4));
+        assert_eq!(latest.bytes, Some(5_000_000));
+        assert_eq!(latest.eta, Some(Duration::from_secs(65)));
+        assert_eq!(latest.percent_done(), Some(25.0));
+    }
+}
@@ -4,6 +4,7 @@
 #
 # This script backs up:
 # - Repository data (Docker volume)
+# - Server state: locks, metadata, comments, activity, accounts
 # - Configuration files
 # - Optional: Encrypt and upload to S3
 #
@@ -65,6 +66,13 @@ docker run --rm \
 REPO_SIZE=$(du -h "$BACKUP_PATH/repositories.tar.gz" | cut -f1)
 log "Repository data backed up ($REPO_SIZE)"
 
+# Backup server state (locks, metadata, comments, activity, accounts)
+log "Backing up server state..."
+docker exec auxin-server /app/auxin-server backup --online --output /tmp/state.tar.zst \
+    && docker cp auxin-server:/tmp/state.tar.zst "$BACKUP_PATH/state.tar.zst" \
+    && docker exec auxin-server rm -f /tmp/state.tar.zst \
+    || warn "Failed to backup server state"
+
 # Backup configuration files
 log "Backing up configuration files..."
 if [ -f "$CONFIG_DIR/config.toml" ]; then
@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { useParams, Link, useNavigate } from 'react-router-dom';
+import { useParams, useSearchParams, Link, useNavigate } from 'react-router-dom';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { ArrowLeft, GitBranch, Terminal, Download, Trash2, AlertTriangle, FolderTree, Lock, Music } from 'lucide-react';
 import { CommitList } from '@/components/commits/CommitList';
@@ -28,8 +28,11 @@ type TabType = 'commits' | 'locks' | 'metadata' | 'clone' | 'files' | 'branches'
 export function RepoPage() {
   const { namespace, name } = useParams<{ namespace: string; name: string }>();
   const navigate = useNavigate();
-  const [activeTab, setActiveTab] = useState<TabType>('clone');
-  const [selectedCommit, setSelectedCommit] = useState<string | null>(null);
+  // `?commit=<id>` comes from links made with `auxin link`
+  const [searchParams] = useSearchParams();
+  const linkedCommit = searchParams.get('commit');
+  const [activeTab, setActiveTab] = useState<TabType>(linkedCommit ? 'metadata' : 'clone');
+  const [selectedCommit, setSelectedCommit] = useState<string | null>(linkedCommit);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
   const queryClient = useQueryClient();
 
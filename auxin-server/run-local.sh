@@ -48,5 +48,5 @@ echo ""
 echo "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━"
 echo ""
 
-# Run the server
-exec "$BINARY"
+# Run the server; --dev allows the development auth secret
+exec "$BINARY" --dev
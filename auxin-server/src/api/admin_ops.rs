@@ -0,0 +1,483 @@
+//! Server administration API operations (`auxin admin`)
+//!
+//! Every endpoint requires the Admin role.
+
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::auth::{require_role, AuthService, SessionSummary, UserRole};
+use crate::backup::list_repos;
+use crate::clock;
+use crate::error::{AppError, AppResult};
+use crate::extensions::{
+    log_activity, Activity, ActivityLog, ActivityType, FileLock, Identities, LockGarbage,
+    LockStore, Waitlist,
+};
+use crate::repo::RepositoryOps;
+use crate::request_id::RequestId;
+use crate::websocket::WsHub;
+use auxin_config::Config;
+
+/// Minutes without a heartbeat before a lock counts as stale (clients
+/// heartbeat every 10 minutes)
+pub const DEFAULT_STALE_MINUTES: i64 = 30;
+
+/// A held lock, as listed for admins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminLock {
+    pub namespace: String,
+    pub name: String,
+    pub lock: FileLock,
+    /// Minutes since the holder's last heartbeat
+    pub idle_minutes: i64,
+    pub stale: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LocksQuery {
+    pub stale_minutes: Option<i64>,
+    #[serde(default)]
+    pub stale_only: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BreakLockRequest {
+    pub stale_minutes: Option<i64>,
+    /// Break the lock even if its holder is still heartbeating
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    pub limit: Option<usize>,
+    pub namespace: Option<String>,
+    pub user: Option<String>,
+    pub activity_type: Option<ActivityType>,
+}
+
+/// An activity with the repository it happened in
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    pub namespace: String,
+    pub name: String,
+    /// Account the activity's user is linked to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    #[serde(flatten)]
+    pub activity: Activity,
+}
+
+/// Revoke one token, or every token of a user
+#[derive(Debug, Deserialize)]
+pub struct RevokeTokensRequest {
+    pub username: Option<String>,
+    pub token: Option<String>,
+}
+
+/// Disk used by one namespace
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceUsage {
+    pub namespace: String,
+    pub repos: usize,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GcRequest {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// What server-side gc removed (or, for a dry run, would remove)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub expired_sessions: usize,
+    #[serde(flatten)]
+    pub locks: LockGarbage,
+}
+
+fn sync_dir(config: &Config) -> PathBuf {
+    PathBuf::from(&config.server.sync_dir)
+}
+
+fn repos(config: &Config) -> AppResult<Vec<(String, String)>> {
+    list_repos(&sync_dir(config))
+        .map_err(|e| AppError::Internal(format!("Failed to list repositories: {}", e)))
+}
+
+fn idle_minutes(lock: &FileLock) -> i64 {
+    (clock::now() - lock.last_heartbeat).num_minutes().max(0)
+}
+
+/// Held locks across all repositories, longest idle first
+pub fn held_locks(config: &Config, stale_minutes: i64) -> AppResult<Vec<AdminLock>> {
+    let mut locks = Vec::new();
+    for (namespace, name) in repos(config)? {
+        let repo = RepositoryOps::open(sync_dir(config).join(&namespace).join(&name))?;
+        let Some(lock) = LockStore::new(config, &repo, &namespace, &name)?.status()? else {
+            continue;
+        };
+        let idle_minutes = idle_minutes(&lock);
+        locks.push(AdminLock {
+            namespace,
+            name,
+            lock,
+            idle_minutes,
+            stale: idle_minutes >= stale_minutes,
+        });
+    }
+    locks.sort_by_key(|lock| std::cmp::Reverse(lock.idle_minutes));
+    Ok(locks)
+}
+
+/// Activity across repositories, newest first; `query.user` matches the
+/// recorded user or the account it's linked to
+pub fn audit_events(config: &Config, query: &AuditQuery) -> AppResult<Vec<AuditEvent>> {
+    let identities = Identities::load(&sync_dir(config))
+        .map_err(|e| AppError::Internal(format!("Failed to read identities: {}", e)))?;
+    let mut events = Vec::new();
+    for (namespace, name) in repos(config)? {
+        if query.namespace.as_ref().is_some_and(|ns| *ns != namespace) {
+            continue;
+        }
+        let log = ActivityLog::load(&sync_dir(config).join(&namespace).join(&name))?;
+        events.extend(
+            log.activities
+                .into_iter()
+                .map(|activity| AuditEvent {
+                    namespace: namespace.clone(),
+                    name: name.clone(),
+                    account: identities.account(&activity.user).map(str::to_string),
+                    activity,
+                })
+                .filter(|e| {
+                    query.user.as_ref().is_none_or(|user| {
+                        e.activity.user == *user || e.account.as_ref() == Some(user)
+                    })
+                })
+                .filter(|e| {
+                    query.activity_type.as_ref().is_none_or(|t| e.activity.activity_type == *t)
+                }),
+        );
+    }
+    events.sort_by_key(|event| std::cmp::Reverse(event.activity.timestamp));
+    events.truncate(query.limit.unwrap_or(100));
+    Ok(events)
+}
+
+fn add_usage(path: &Path, usage: &mut NamespaceUsage) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            add_usage(&entry.path(), usage);
+        } else if file_type.is_file() {
+            usage.files += 1;
+            usage.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+}
+
+/// Disk usage per namespace, largest first
+pub fn storage_usage(config: &Config) -> AppResult<Vec<NamespaceUsage>> {
+    let mut usage: BTreeMap<String, NamespaceUsage> = BTreeMap::new();
+    for (namespace, name) in repos(config)? {
+        let entry = usage.entry(namespace.clone()).or_insert_with(|| NamespaceUsage {
+            namespace: namespace.clone(),
+            ..Default::default()
+        });
+        entry.repos += 1;
+        add_usage(&sync_dir(config).join(&namespace).join(&name), entry);
+    }
+    let mut usage: Vec<NamespaceUsage> = usage.into_values().collect();
+    usage.sort_by_key(|usage| std::cmp::Reverse(usage.bytes));
+    Ok(usage)
+}
+
+/// Clear expired locks, stale guards and abandoned temporary files in
+/// every repository
+pub fn collect_lock_garbage(config: &Config, dry_run: bool) -> AppResult<LockGarbage> {
+    let mut total = LockGarbage::default();
+    for (namespace, name) in repos(config)? {
+        let repo_path = sync_dir(config).join(&namespace).join(&name);
+        let garbage = FileLock::collect_garbage(&repo_path, dry_run).map_err(|e| {
+            AppError::Internal(format!("Failed to clean {}/{}: {}", namespace, name, e))
+        })?;
+        total.expired_locks += garbage.expired_locks;
+        total.stale_guards += garbage.stale_guards;
+        total.temp_files += garbage.temp_files;
+        total.bytes += garbage.bytes;
+    }
+    Ok(total)
+}
+
+/// Run blocking filesystem work off the async workers
+pub(super) async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> AppResult<T> + Send + 'static,
+) -> AppResult<T> {
+    web::block(work)
+        .await
+        .map_err(|e| AppError::Internal(format!("Background task failed: {}", e)))?
+}
+
+/// List held locks across all repositories
+/// GET /api/admin/locks?stale_minutes=30&stale_only=true
+pub async fn list_locks(
+    config: web::Data<Config>,
+    query: web::Query<LocksQuery>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    require_role(&req, &auth_service, UserRole::Admin)?;
+
+    let stale_minutes = query.stale_minutes.unwrap_or(DEFAULT_STALE_MINUTES);
+    let stale_only = query.stale_only;
+    let config = config.into_inner();
+    let mut locks = blocking(move || held_locks(&config, stale_minutes)).await?;
+    if stale_only {
+        locks.retain(|lock| lock.stale);
+    }
+
+    Ok(HttpResponse::Ok().json(locks))
+}
+
+/// Break a repository's lock
+/// POST /api/admin/locks/{namespace}/{name}/break
+///
+/// Only stale locks are broken unless `force` is set. The holder is told
+/// over WebSocket and the break is recorded in the repository's activity.
+pub async fn break_lock(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    body: Option<web::Json<BreakLockRequest>>,
+    ws_hub: web::Data<WsHub>,
+    auth_service: web::Data<AuthService>,
+    request_id: RequestId,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let admin = require_role(&req, &auth_service, UserRole::Admin)?;
+    let (namespace, repo_name) = path.into_inner();
+    let body = body.map(|b| b.into_inner()).unwrap_or_default();
+
+    let repo_path = sync_dir(&config).join(&namespace).join(&repo_name);
+    let repo = RepositoryOps::open(&repo_path)?;
+    let locks = LockStore::new(&config, &repo, &namespace, &repo_name)?;
+    let lock = locks
+        .status()?
+        .ok_or_else(|| AppError::NotFound("No lock is held".to_string()))?;
+
+    let idle = idle_minutes(&lock);
+    let stale_minutes = body.stale_minutes.unwrap_or(DEFAULT_STALE_MINUTES);
+    if !body.force && idle < stale_minutes {
+        return Err(AppError::Conflict(format!(
+            "{}'s lock had a heartbeat {} minutes ago; it isn't stale (pass force to break it)",
+            lock.user, idle
+        )));
+    }
+
+    locks.release(&lock.lock_id, Some(lock.fencing_token))?;
+    info!(
+        "Admin {} broke {}'s lock on {}/{}",
+        admin.username, lock.user, namespace, repo_name
+    );
+
+    log_activity(
+        &repo_path,
+        ActivityType::LockReleased,
+        &lock.user,
+        &format!("Lock broken by admin {}", admin.username),
+        Some(serde_json::json!({
+            "lock_id": lock.lock_id,
+            "broken_by": admin.username,
+            "idle_minutes": idle,
+        })),
+        Some(request_id.as_str()),
+    )?;
+
+    let _ = ws_hub
+        .broadcast_lock_released(&namespace, &repo_name, &lock.lock_id)
+        .await;
+    let waiting = Waitlist::take(&repo_path).unwrap_or_default();
+    let _ = ws_hub
+        .broadcast_lock_available(&namespace, &repo_name, &waiting)
+        .await;
+
+    Ok(HttpResponse::Ok().json(lock))
+}
+
+/// Activity across all repositories, newest first
+/// GET /api/admin/audit?limit=100&namespace=&user=&activity_type=
+pub async fn audit_log(
+    config: web::Data<Config>,
+    query: web::Query<AuditQuery>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    require_role(&req, &auth_service, UserRole::Admin)?;
+
+    let config = config.into_inner();
+    let query = query.into_inner();
+    let events = blocking(move || audit_events(&config, &query)).await?;
+
+    Ok(HttpResponse::Ok().json(events))
+}
+
+/// Live sessions per user
+/// GET /api/admin/sessions
+pub async fn list_sessions(
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    require_role(&req, &auth_service, UserRole::Admin)?;
+
+    let sessions: Vec<SessionSummary> = auth_service.sessions()?;
+    Ok(HttpResponse::Ok().json(sessions))
+}
+
+/// Revoke a token, or all of a user's tokens
+/// POST /api/admin/tokens/revoke
+pub async fn revoke_tokens(
+    body: web::Json<RevokeTokensRequest>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let admin = require_role(&req, &auth_service, UserRole::Admin)?;
+
+    let revoked = match (&body.username, &body.token) {
+        (Some(username), None) => auth_service.revoke_user_tokens(username)?,
+        (None, Some(token)) => {
+            let known = auth_service.validate_token(token).is_ok();
+            auth_service.revoke_token(token)?;
+            usize::from(known)
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "Give either a username or a token".to_string(),
+            ))
+        }
+    };
+    info!("Admin {} revoked {} tokens", admin.username, revoked);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "revoked": revoked })))
+}
+
+/// Disk usage per namespace
+/// GET /api/admin/storage
+pub async fn storage(
+    config: web::Data<Config>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    require_role(&req, &auth_service, UserRole::Admin)?;
+
+    let config = config.into_inner();
+    let usage = blocking(move || storage_usage(&config)).await?;
+    Ok(HttpResponse::Ok().json(usage))
+}
+
+/// Server-side housekeeping: expired sessions, expired locks, stale lock
+/// guards and temporary files abandoned by interrupted writes
+/// POST /api/admin/gc
+pub async fn gc(
+    config: web::Data<Config>,
+    body: Option<web::Json<GcRequest>>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let admin = require_role(&req, &auth_service, UserRole::Admin)?;
+    let dry_run = body.is_some_and(|b| b.dry_run);
+
+    let expired_sessions = if dry_run {
+        auth_service.expired_count()?
+    } else {
+        auth_service.cleanup_expired()?
+    };
+    let config = config.into_inner();
+    let locks = blocking(move || collect_lock_garbage(&config, dry_run)).await?;
+
+    let report = GcReport {
+        dry_run,
+        expired_sessions,
+        locks,
+    };
+    if !dry_run {
+        info!("Admin {} ran gc: {:?}", admin.username, report);
+    }
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_locks_audit_and_storage_across_repos() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.server.sync_dir = temp.path().to_string_lossy().to_string();
+
+        let album = temp.path().join("studio/album");
+        let demos = temp.path().join("studio/demos");
+        let remix = temp.path().join("guest/remix");
+        for repo in [&album, &demos, &remix] {
+            fs::create_dir_all(repo.join(".oxen")).unwrap();
+        }
+        fs::write(remix.join("mix.wav"), vec![0u8; 1000]).unwrap();
+
+        // An idle lock on the album, a fresh one on the demos
+        FileLock::acquire(&album, "sam", "studio-mac", 8).unwrap();
+        let mut idle = FileLock::snapshot(&album).unwrap();
+        let lock = idle.lock.as_mut().unwrap();
+        lock.last_heartbeat = clock::now() - Duration::minutes(45);
+        FileLock::restore_snapshot(&album, &idle).unwrap();
+        FileLock::acquire(&demos, "alex", "laptop", 8).unwrap();
+
+        let locks = held_locks(&config, DEFAULT_STALE_MINUTES).unwrap();
+        assert_eq!(locks.len(), 2);
+        assert_eq!((locks[0].name.as_str(), locks[0].stale), ("album", true));
+        assert_eq!((locks[1].name.as_str(), locks[1].stale), ("demos", false));
+
+        log_activity(&album, ActivityType::Commit, "sam", "Vocals", None, None).unwrap();
+        log_activity(&remix, ActivityType::Commit, "kai", "Drop", None, None).unwrap();
+        let query = AuditQuery {
+            limit: None,
+            namespace: None,
+            user: None,
+            activity_type: Some(ActivityType::Commit),
+        };
+        let events = audit_events(&config, &query).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].activity.user, "kai");
+        assert_eq!(events[0].namespace, "guest");
+
+        // Filtering by account finds the names linked to it
+        Identities::link(temp.path(), "kai", "kai.w", None, "laptop", false).unwrap();
+        let query = AuditQuery {
+            user: Some("kai.w".to_string()),
+            ..query
+        };
+        let events = audit_events(&config, &query).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].account.as_deref(), Some("kai.w"));
+
+        let usage = storage_usage(&config).unwrap();
+        assert_eq!(usage[0].namespace, "guest");
+        assert!(usage[0].bytes >= 1000);
+        assert_eq!(usage[1].repos, 2);
+
+        let garbage = collect_lock_garbage(&config, true).unwrap();
+        assert_eq!(garbage, LockGarbage::default());
+    }
+}
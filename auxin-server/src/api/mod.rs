@@ -1,7 +1,10 @@
 mod access_ops;
+mod admin_ops;
 mod bounce_ops;
 mod project_ops;
 mod repo_ops;
+mod stats_ops;
+mod stems_ops;
 
 use actix_web::{web, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
@@ -18,14 +21,28 @@ use crate::repo::RepositoryOps;
 // Re-export API handlers
 pub use repo_ops::{
     acquire_lock, clone_repository, create_branch, delete_branch, fetch_repository, get_activity,
-    get_commits, get_metadata, get_status, heartbeat_lock, list_branches, lock_status,
-    pull_repository, push_repository, release_lock, restore_commit, store_metadata,
+    get_commits, get_metadata, get_status, heartbeat_lock, import_activity, link_identity,
+    list_branches, list_metadata, lock_status, pull_repository, push_repository, reclaim_lock,
+    record_presence, release_lock, restore_commit, search_metadata, store_metadata,
+    store_metadata_batch, IdentityRequest, ImportActivityRequest, ImportActivityResponse,
+    MetadataBatchEntry, MetadataBatchFailure,
+    MetadataBatchRequest, MetadataBatchResponse, PresenceRequest, SearchParams,
+    MAX_ACTIVITY_IMPORT_SIZE, MAX_METADATA_BATCH_SIZE,
 };
 
 pub use bounce_ops::{delete_bounce, get_bounce, get_bounce_audio, list_bounces, upload_bounce};
 
+pub use stems_ops::{get_stem_audio, get_stems, list_stems, upload_stems};
+
+pub use stats_ops::{lock_stats, repo_lock_stats, LockStatsQuery, DEFAULT_STATS_DAYS};
+
 pub use access_ops::{grant_access, list_access, revoke_access};
 
+pub use admin_ops::{
+    audit_log, break_lock, gc, list_locks, list_sessions, revoke_tokens, storage, AdminLock,
+    AuditEvent, GcReport, NamespaceUsage, DEFAULT_STALE_MINUTES,
+};
+
 // File-based collaborator management (default)
 #[cfg(not(feature = "web-ui"))]
 pub use project_ops::{
@@ -39,6 +56,51 @@ pub use project_ops::{
     update_project,
 };
 
+/// Current HTTP API version, served under `/api/v{API_VERSION}`
+pub const API_VERSION: u32 = 1;
+
+/// Oldest API version still served (unversioned `/api/...` paths map to it)
+pub const MIN_API_VERSION: u32 = 1;
+
+/// Version handshake response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiVersionInfo {
+    pub api_version: u32,
+    pub min_api_version: u32,
+    pub server_version: String,
+}
+
+impl ApiVersionInfo {
+    pub fn current() -> Self {
+        Self {
+            api_version: API_VERSION,
+            min_api_version: MIN_API_VERSION,
+            server_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Report supported API versions so clients can negotiate
+pub async fn get_version() -> HttpResponse {
+    HttpResponse::Ok().json(ApiVersionInfo::current())
+}
+
+/// Health check response; carries versions so clients can detect skew
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+    #[serde(flatten)]
+    pub version: ApiVersionInfo,
+}
+
+/// Liveness probe (unversioned, at `/health`)
+pub async fn health_check() -> HttpResponse {
+    HttpResponse::Ok().json(HealthResponse {
+        status: "ok".to_string(),
+        version: ApiVersionInfo::current(),
+    })
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateRepoRequest {
     pub description: Option<String>,
@@ -1,13 +1,21 @@
 use actix_web::{web, HttpResponse};
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::auth::{get_optional_user_id_from_request, get_user_id_from_request, AuthService};
+use crate::auth::{
+    get_optional_user_id_from_request, get_user_from_request, get_user_id_from_request,
+    AuthService,
+};
 use auxin_config::Config;
+use crate::clock;
 use crate::error::{AppError, AppResult};
-use crate::extensions::{get_activities, log_activity, ActivityType, LogicProMetadata};
+use crate::extensions::{
+    get_activities, get_activities_for_request, log_activity, Activity, ActivityLog, ActivityType,
+    Identities, LockStore, LogicProMetadata, MetadataIndex, Presence, SearchQuery, Waitlist,
+};
 use crate::project::ProjectAuth;
 use crate::repo::RepositoryOps;
+use crate::request_id::RequestId;
 use crate::websocket::WsHub;
 use std::path::PathBuf;
 
@@ -38,11 +46,34 @@ pub struct LockRequest {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReleaseLockRequest {
     pub lock_id: String,
+    /// Token issued on acquisition; validated when present
+    #[serde(default)]
+    pub fencing_token: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HeartbeatRequest {
     pub lock_id: String,
+    /// Token issued on acquisition; validated when present
+    #[serde(default)]
+    pub fencing_token: Option<u64>,
+}
+
+/// A presence ping from a machine's background daemon
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PresenceRequest {
+    pub user: String,
+    pub machine_id: String,
+}
+
+/// A client's name for the authenticated account
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IdentityRequest {
+    /// The `name@machine` the client records in locks and activity
+    pub user: String,
+    pub machine_id: String,
+    #[serde(default)]
+    pub hub_user: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,6 +86,32 @@ pub struct DeleteBranchRequest {
     pub branch_name: String,
 }
 
+/// Maximum number of entries accepted in a single metadata batch
+pub const MAX_METADATA_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetadataBatchEntry {
+    pub commit_id: String,
+    pub metadata: LogicProMetadata,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetadataBatchRequest {
+    pub entries: Vec<MetadataBatchEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetadataBatchFailure {
+    pub commit_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MetadataBatchResponse {
+    pub stored: Vec<String>,
+    pub failed: Vec<MetadataBatchFailure>,
+}
+
 /// Get commit history for a repository
 pub async fn get_commits(
     config: web::Data<Config>,
@@ -75,7 +132,7 @@ pub async fn get_commits(
     ProjectAuth::require_read(&repo_path, user_id.as_deref())?;
 
     let repo = RepositoryOps::open(&repo_path)?;
-    let commits = repo.log(query.limit)?;
+    let commits = repo.log_branch(query.branch.as_deref(), query.limit)?;
 
     Ok(HttpResponse::Ok().json(commits))
 }
@@ -83,11 +140,29 @@ pub async fn get_commits(
 #[derive(Debug, Deserialize)]
 pub struct CommitQuery {
     pub limit: Option<usize>,
+    /// Branch to list instead of HEAD
+    pub branch: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ActivityQuery {
     pub limit: Option<usize>,
+    /// Only activity recorded by this request (`X-Auxin-Request-Id`)
+    pub request_id: Option<String>,
+}
+
+/// Largest number of activities accepted by one import request
+pub const MAX_ACTIVITY_IMPORT_SIZE: usize = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportActivityRequest {
+    pub activities: Vec<Activity>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportActivityResponse {
+    /// Activities added to the log
+    pub imported: usize,
 }
 
 /// Push to remote repository
@@ -97,6 +172,7 @@ pub async fn push_repository(
     body: web::Json<PushRequest>,
     ws_hub: web::Data<WsHub>,
     auth_service: web::Data<AuthService>,
+    request_id: RequestId,
     http_req: actix_web::HttpRequest,
 ) -> AppResult<HttpResponse> {
     let (namespace, repo_name) = path.into_inner();
@@ -125,6 +201,7 @@ pub async fn push_repository(
             "remote": body.remote,
             "branch": branch
         })),
+        Some(request_id.as_str()),
     )?;
 
     // Broadcast to WebSocket subscribers
@@ -150,6 +227,7 @@ pub async fn pull_repository(
     path: web::Path<(String, String)>,
     body: web::Json<PullRequest>,
     auth_service: web::Data<AuthService>,
+    request_id: RequestId,
     req: actix_web::HttpRequest,
 ) -> AppResult<HttpResponse> {
     let (namespace, repo_name) = path.into_inner();
@@ -178,6 +256,7 @@ pub async fn pull_repository(
             "remote": body.remote,
             "branch": branch
         })),
+        Some(request_id.as_str()),
     )?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
@@ -247,6 +326,7 @@ pub async fn restore_commit(
     path: web::Path<(String, String, String)>,
     ws_hub: web::Data<WsHub>,
     auth_service: web::Data<AuthService>,
+    request_id: RequestId,
     req: actix_web::HttpRequest,
 ) -> AppResult<HttpResponse> {
     let (namespace, repo_name, commit_id) = path.into_inner();
@@ -277,6 +357,7 @@ pub async fn restore_commit(
         Some(serde_json::json!({
             "commit_id": commit_id
         })),
+        Some(request_id.as_str()),
     )?;
 
     // Broadcast via WebSocket
@@ -332,6 +413,79 @@ pub async fn get_metadata(
     }
 }
 
+/// List commits that have stored metadata
+pub async fn list_metadata(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    info!("Listing metadata for: {}/{}", namespace, repo_name);
+
+    let repo_path = PathBuf::from(&config.server.sync_dir)
+        .join(&namespace)
+        .join(&repo_name);
+
+    // Check read access
+    let user_id = get_optional_user_id_from_request(&req, &auth_service);
+    ProjectAuth::require_read(&repo_path, user_id.as_deref())?;
+
+    let repo = RepositoryOps::open(&repo_path)?;
+    let commit_ids = repo.list_metadata()?;
+
+    Ok(HttpResponse::Ok().json(commit_ids))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    /// Query string, e.g. `bpm:120-140 tag:mixing`
+    pub q: Option<String>,
+}
+
+/// Search commit metadata
+///
+/// Accepts the CLI search language in `q` and returns matching commits with
+/// their metadata. Messages are included (and searchable via `msg:`) when
+/// the commit history is available.
+pub async fn search_metadata(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    params: web::Query<SearchParams>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    let query_str = params.q.as_deref().unwrap_or("");
+    info!(
+        "Searching metadata in {}/{}: {}",
+        namespace, repo_name, query_str
+    );
+
+    let repo_path = PathBuf::from(&config.server.sync_dir)
+        .join(&namespace)
+        .join(&repo_name);
+
+    // Check read access
+    let user_id = get_optional_user_id_from_request(&req, &auth_service);
+    ProjectAuth::require_read(&repo_path, user_id.as_deref())?;
+
+    let repo = RepositoryOps::open(&repo_path)?;
+    let index = MetadataIndex::build(&repo)?;
+    let query = SearchQuery::parse(query_str);
+
+    // History is optional: metadata-only search still works without it
+    let messages: Vec<(String, String)> = repo
+        .log(None)
+        .map(|commits| commits.into_iter().map(|c| (c.id, c.message)).collect())
+        .unwrap_or_default();
+
+    let hits = index.search(&query, &messages);
+    info!("Search matched {} of {} commits", hits.len(), index.len());
+
+    Ok(HttpResponse::Ok().json(hits))
+}
+
 /// Store Logic Pro metadata for a commit
 pub async fn store_metadata(
     config: web::Data<Config>,
@@ -363,6 +517,78 @@ pub async fn store_metadata(
     })))
 }
 
+/// Store Logic Pro metadata for many commits in one request
+///
+/// Entries are written independently; failures are reported per commit
+/// (207 Multi-Status) instead of aborting the whole batch.
+pub async fn store_metadata_batch(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    body: web::Json<MetadataBatchRequest>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    info!(
+        "Storing metadata batch ({} entries) in: {}/{}",
+        body.entries.len(),
+        namespace,
+        repo_name
+    );
+
+    if body.entries.len() > MAX_METADATA_BATCH_SIZE {
+        return Err(AppError::QuotaExceeded(format!(
+            "Batch too large: {} entries (max {})",
+            body.entries.len(),
+            MAX_METADATA_BATCH_SIZE
+        )));
+    }
+
+    let repo_path = PathBuf::from(&config.server.sync_dir)
+        .join(&namespace)
+        .join(&repo_name);
+
+    // Check write access
+    let user_id = get_user_id_from_request(&req, &auth_service)?;
+    ProjectAuth::require_write(&repo_path, &user_id)?;
+
+    let repo = RepositoryOps::open(&repo_path)?;
+    let mut response = MetadataBatchResponse::default();
+
+    for entry in &body.entries {
+        // Commit IDs become file names, so reject anything path-like
+        let result = if entry.commit_id.is_empty()
+            || entry.commit_id.contains("..")
+            || entry.commit_id.contains('/')
+            || entry.commit_id.contains('\\')
+        {
+            Err(AppError::BadRequest("Invalid commit ID".to_string()))
+        } else {
+            repo.store_metadata(&entry.commit_id, &entry.metadata)
+        };
+
+        match result {
+            Ok(()) => response.stored.push(entry.commit_id.clone()),
+            Err(e) => response.failed.push(MetadataBatchFailure {
+                commit_id: entry.commit_id.clone(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    info!(
+        "Metadata batch complete: {} stored, {} failed",
+        response.stored.len(),
+        response.failed.len()
+    );
+
+    if response.failed.is_empty() {
+        Ok(HttpResponse::Created().json(response))
+    } else {
+        Ok(HttpResponse::build(actix_web::http::StatusCode::MULTI_STATUS).json(response))
+    }
+}
+
 /// Acquire lock for repository
 pub async fn acquire_lock(
     config: web::Data<Config>,
@@ -370,6 +596,7 @@ pub async fn acquire_lock(
     body: web::Json<LockRequest>,
     ws_hub: web::Data<WsHub>,
     auth_service: web::Data<AuthService>,
+    request_id: RequestId,
     http_req: actix_web::HttpRequest,
 ) -> AppResult<HttpResponse> {
     let (namespace, repo_name) = path.into_inner();
@@ -384,8 +611,37 @@ pub async fn acquire_lock(
     ProjectAuth::require_write(&repo_path, &user_id)?;
 
     let repo = RepositoryOps::open(&repo_path)?;
+    let locks = LockStore::new(&config, &repo, &namespace, &repo_name)?;
     let timeout = body.timeout_hours.unwrap_or(24);
-    let lock = repo.acquire_lock(&body.user, &body.machine_id, timeout)?;
+    let lock = match locks.acquire(&body.user, &body.machine_id, timeout) {
+        Ok(lock) => lock,
+        Err(e @ AppError::LockHeld(_)) => {
+            // Recorded for `auxin lock stats`; told when the lock is free
+            let holder = locks.status().ok().flatten().map(|lock| lock.user);
+            log_activity(
+                &repo_path,
+                ActivityType::LockContended,
+                &body.user,
+                &format!(
+                    "Asked for the lock held by {}",
+                    holder.as_deref().unwrap_or("another user")
+                ),
+                Some(serde_json::json!({
+                    "holder": holder,
+                    "machine_id": body.machine_id,
+                })),
+                Some(request_id.as_str()),
+            )?;
+            if let Err(err) = Waitlist::add(&repo_path, &body.user, &body.machine_id) {
+                warn!("Failed to add {} to the lock waitlist: {}", body.user, err);
+            }
+            return Err(e);
+        },
+        Err(e) => return Err(e),
+    };
+    if let Err(e) = Waitlist::remove(&repo_path, &body.user) {
+        warn!("Failed to update the lock waitlist: {}", e);
+    }
 
     // Log activity
     log_activity(
@@ -395,9 +651,11 @@ pub async fn acquire_lock(
         &format!("Acquired lock for {} hours", timeout),
         Some(serde_json::json!({
             "lock_id": lock.lock_id,
+            "fencing_token": lock.fencing_token,
             "machine_id": body.machine_id,
             "timeout_hours": timeout
         })),
+        Some(request_id.as_str()),
     )?;
 
     // Broadcast to WebSocket subscribers
@@ -415,6 +673,7 @@ pub async fn release_lock(
     body: web::Json<ReleaseLockRequest>,
     ws_hub: web::Data<WsHub>,
     auth_service: web::Data<AuthService>,
+    request_id: RequestId,
     http_req: actix_web::HttpRequest,
 ) -> AppResult<HttpResponse> {
     let (namespace, repo_name) = path.into_inner();
@@ -429,15 +688,16 @@ pub async fn release_lock(
     ProjectAuth::require_write(&repo_path, &user_id)?;
 
     let repo = RepositoryOps::open(&repo_path)?;
+    let locks = LockStore::new(&config, &repo, &namespace, &repo_name)?;
 
     // Get lock info before releasing (for activity log)
-    let lock_info = repo.lock_status()?;
+    let lock_info = locks.status()?;
     let user = lock_info
         .as_ref()
         .map(|l| l.user.clone())
         .unwrap_or_else(|| "unknown".to_string());
 
-    repo.release_lock(&body.lock_id)?;
+    locks.release(&body.lock_id, body.fencing_token)?;
 
     // Log activity
     log_activity(
@@ -448,12 +708,17 @@ pub async fn release_lock(
         Some(serde_json::json!({
             "lock_id": body.lock_id
         })),
+        Some(request_id.as_str()),
     )?;
 
     // Broadcast to WebSocket subscribers
     let _ = ws_hub
         .broadcast_lock_released(&namespace, &repo_name, &body.lock_id)
         .await;
+    let waiting = Waitlist::take(&repo_path).unwrap_or_default();
+    let _ = ws_hub
+        .broadcast_lock_available(&namespace, &repo_name, &waiting)
+        .await;
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "status": "success",
@@ -481,11 +746,151 @@ pub async fn heartbeat_lock(
     ProjectAuth::require_write(&repo_path, &user_id)?;
 
     let repo = RepositoryOps::open(&repo_path)?;
-    let lock = repo.heartbeat_lock(&body.lock_id)?;
+    let locks = LockStore::new(&config, &repo, &namespace, &repo_name)?;
+    let lock = locks.heartbeat(&body.lock_id, body.fencing_token)?;
+
+    Ok(HttpResponse::Ok().json(lock))
+}
+
+/// Take over a lock whose holder has gone offline
+///
+/// Lighter than an admin break: anyone with write access can reclaim, but
+/// only a lock [`Presence::of_holder`] calls orphaned, i.e. the holder's
+/// daemon and the lock's heartbeat have both gone quiet.
+pub async fn reclaim_lock(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    body: web::Json<LockRequest>,
+    ws_hub: web::Data<WsHub>,
+    auth_service: web::Data<AuthService>,
+    request_id: RequestId,
+    http_req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    info!("Reclaiming lock for: {}/{}", namespace, repo_name);
+
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let repo_path = sync_dir.join(&namespace).join(&repo_name);
+
+    // Check write access
+    let user_id = get_user_id_from_request(&http_req, &auth_service)?;
+    ProjectAuth::require_write(&repo_path, &user_id)?;
+
+    let repo = RepositoryOps::open(&repo_path)?;
+    let locks = LockStore::new(&config, &repo, &namespace, &repo_name)?;
+    let held = locks
+        .status()?
+        .ok_or_else(|| AppError::NotFound("No lock is held; acquire it instead".to_string()))?;
+
+    let holder = Presence::of_holder(&sync_dir, &held, config.server.lock_orphan_minutes)
+        .map_err(|e| AppError::Internal(format!("Failed to read daemon presence: {}", e)))?;
+    let offline_minutes = holder.last_seen.map(|seen| (clock::now() - seen).num_minutes());
+    if !holder.orphaned {
+        let seen = match offline_minutes {
+            Some(minutes) => format!("its daemon was seen {} minutes ago", minutes),
+            None => "no daemon reports from its machine".to_string(),
+        };
+        return Err(AppError::Conflict(format!(
+            "{}'s lock doesn't look orphaned ({}); ask them to release it or have an admin \
+             break it",
+            held.user, seen
+        )));
+    }
+
+    locks.release(&held.lock_id, Some(held.fencing_token))?;
+    log_activity(
+        &repo_path,
+        ActivityType::LockReleased,
+        &held.user,
+        &format!("Lock reclaimed by {} while its holder was offline", body.user),
+        Some(serde_json::json!({
+            "lock_id": held.lock_id,
+            "reclaimed_by": body.user,
+            "offline_minutes": offline_minutes,
+        })),
+        Some(request_id.as_str()),
+    )?;
+    let _ = ws_hub
+        .broadcast_lock_released(&namespace, &repo_name, &held.lock_id)
+        .await;
+
+    let timeout = body.timeout_hours.unwrap_or(24);
+    let lock = locks.acquire(&body.user, &body.machine_id, timeout)?;
+    if let Err(e) = Waitlist::remove(&repo_path, &body.user) {
+        warn!("Failed to update the lock waitlist: {}", e);
+    }
+    log_activity(
+        &repo_path,
+        ActivityType::LockAcquired,
+        &body.user,
+        &format!("Reclaimed {}'s orphaned lock for {} hours", held.user, timeout),
+        Some(serde_json::json!({
+            "lock_id": lock.lock_id,
+            "fencing_token": lock.fencing_token,
+            "machine_id": body.machine_id,
+            "timeout_hours": timeout,
+            "reclaimed_from": held.user,
+        })),
+        Some(request_id.as_str()),
+    )?;
+    let _ = ws_hub
+        .broadcast_lock_acquired(&namespace, &repo_name, &body.user, &lock.lock_id)
+        .await;
 
     Ok(HttpResponse::Ok().json(lock))
 }
 
+/// Record that a machine's background daemon is running, so its locks
+/// can be told apart from a crashed machine's
+/// POST /api/presence
+pub async fn record_presence(
+    config: web::Data<Config>,
+    body: web::Json<PresenceRequest>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let account = get_user_from_request(&req, &auth_service)?;
+
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let presence = Presence::record(&sync_dir, &body.machine_id, &body.user)
+        .map_err(|e| AppError::Internal(format!("Failed to record presence: {}", e)))?;
+    if let Err(e) =
+        Identities::link(&sync_dir, &body.user, &account.username, None, &body.machine_id, false)
+    {
+        warn!("Failed to link {} to {}: {}", body.user, account.username, e);
+    }
+
+    Ok(HttpResponse::Ok().json(presence))
+}
+
+/// Link the name a client records in locks and activity to the
+/// authenticated account, for the audit log and lock stats; a name linked
+/// to another account can only be moved by an admin
+/// POST /api/identity
+pub async fn link_identity(
+    config: web::Data<Config>,
+    body: web::Json<IdentityRequest>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let account = get_user_from_request(&req, &auth_service)?;
+    if body.user.trim().is_empty() {
+        return Err(AppError::BadRequest("user must not be empty".to_string()));
+    }
+
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let link = Identities::link(
+        &sync_dir,
+        body.user.trim(),
+        &account.username,
+        body.hub_user.as_deref().filter(|hub| !hub.trim().is_empty()),
+        &body.machine_id,
+        account.role.can_manage_users(),
+    )?;
+
+    Ok(HttpResponse::Ok().json(link))
+}
+
 /// Get lock status
 pub async fn lock_status(
     config: web::Data<Config>,
@@ -505,13 +910,20 @@ pub async fn lock_status(
     ProjectAuth::require_read(&repo_path, user_id.as_deref())?;
 
     let repo = RepositoryOps::open(&repo_path)?;
-    let status = repo.lock_status()?;
+    let status = LockStore::new(&config, &repo, &namespace, &repo_name)?.status()?;
 
     match status {
-        Some(lock) => Ok(HttpResponse::Ok().json(serde_json::json!({
-            "locked": true,
-            "lock": lock
-        }))),
+        Some(lock) => {
+            let sync_dir = PathBuf::from(&config.server.sync_dir);
+            let holder = Presence::of_holder(&sync_dir, &lock, config.server.lock_orphan_minutes)
+                .unwrap_or_default();
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "locked": true,
+                "lock": lock,
+                "holder_last_seen": holder.last_seen,
+                "orphaned": holder.orphaned
+            })))
+        },
         None => Ok(HttpResponse::Ok().json(serde_json::json!({
             "locked": false
         }))),
@@ -538,11 +950,61 @@ pub async fn get_activity(
     ProjectAuth::require_read(&repo_path, user_id.as_deref())?;
 
     let limit = query.limit.unwrap_or(50);
-    let activities = get_activities(&repo_path, limit)?;
+    let activities = match query.request_id {
+        Some(ref request_id) => get_activities_for_request(&repo_path, request_id)?,
+        None => get_activities(&repo_path, limit)?,
+    };
 
     Ok(HttpResponse::Ok().json(activities))
 }
 
+/// Import activity recorded elsewhere, e.g. when migrating from another server
+///
+/// IDs, users and timestamps are kept as sent, so this needs the repository
+/// owner or an admin. An import repeating an ID, or using one already in the
+/// log, is rejected as a whole.
+pub async fn import_activity(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    body: web::Json<ImportActivityRequest>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    let activities = body.into_inner().activities;
+    info!(
+        "Importing {} activities into: {}/{}",
+        activities.len(),
+        namespace,
+        repo_name
+    );
+
+    if activities.len() > MAX_ACTIVITY_IMPORT_SIZE {
+        return Err(AppError::BadRequest(format!(
+            "Import too large: {} activities (max {})",
+            activities.len(),
+            MAX_ACTIVITY_IMPORT_SIZE
+        )));
+    }
+
+    let repo_path = PathBuf::from(&config.server.sync_dir)
+        .join(&namespace)
+        .join(&repo_name);
+
+    // Imported entries carry any user and time, so only the owner or an
+    // admin may add them
+    let user = get_user_from_request(&req, &auth_service)?;
+    if !user.role.can_manage_users() {
+        ProjectAuth::require_owner(&repo_path, &user.id)?;
+    }
+
+    let mut log = ActivityLog::load(&repo_path)?;
+    let imported = log.import(activities)?;
+    log.save(&repo_path)?;
+
+    Ok(HttpResponse::Ok().json(ImportActivityResponse { imported }))
+}
+
 /// Clone a repository from remote
 pub async fn clone_repository(
     config: web::Data<Config>,
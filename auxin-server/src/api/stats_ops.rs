@@ -0,0 +1,146 @@
+//! Lock analytics API operations (`auxin lock stats`)
+
+use actix_web::{web, HttpResponse};
+use chrono::Duration;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use super::admin_ops::blocking;
+use crate::auth::{get_optional_user_id_from_request, AuthService};
+use crate::backup::list_repos;
+use crate::clock;
+use crate::error::{AppError, AppResult};
+use crate::extensions::{ActivityLog, Identities, LockStats, LockStatsBuilder};
+use crate::project::ProjectAuth;
+use auxin_config::Config;
+
+/// Days of history reported when the query doesn't say
+pub const DEFAULT_STATS_DAYS: i64 = 30;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct LockStatsQuery {
+    pub days: Option<i64>,
+    /// Only repositories in this namespace (server-wide stats)
+    pub namespace: Option<String>,
+}
+
+impl LockStatsQuery {
+    /// A builder counting each linked name as its account
+    fn builder(&self, sync_dir: &Path) -> AppResult<LockStatsBuilder> {
+        let days = self.days.unwrap_or(DEFAULT_STATS_DAYS).max(1);
+        let identities = Identities::load(sync_dir)
+            .map_err(|e| AppError::Internal(format!("Failed to read identities: {}", e)))?;
+        Ok(LockStatsBuilder::new(clock::now() - Duration::days(days)).with_identities(identities))
+    }
+}
+
+/// Lock stats for every repository `user_id` can read
+pub fn server_lock_stats(
+    config: &Config,
+    query: &LockStatsQuery,
+    user_id: Option<&str>,
+) -> AppResult<LockStats> {
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let repos = list_repos(&sync_dir)
+        .map_err(|e| AppError::Internal(format!("Failed to list repositories: {}", e)))?;
+
+    let mut builder = query.builder(&sync_dir)?;
+    for (namespace, name) in repos {
+        if query.namespace.as_ref().is_some_and(|ns| *ns != namespace) {
+            continue;
+        }
+        let repo_path = sync_dir.join(&namespace).join(&name);
+        if !ProjectAuth::can_read(&repo_path, user_id).unwrap_or(false) {
+            continue;
+        }
+        let log = ActivityLog::load(&repo_path)?;
+        builder.add_repo(&namespace, &name, &log.activities);
+    }
+    Ok(builder.finish())
+}
+
+/// Lock hold times and contention for a repository
+/// GET /api/repos/{namespace}/{name}/locks/stats?days=30
+pub async fn repo_lock_stats(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    query: web::Query<LockStatsQuery>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let repo_path = sync_dir.join(&namespace).join(&repo_name);
+
+    let user_id = get_optional_user_id_from_request(&req, &auth_service);
+    ProjectAuth::require_read(&repo_path, user_id.as_deref())?;
+
+    let stats = blocking(move || {
+        let log = ActivityLog::load(&repo_path)?;
+        let mut builder = query.builder(&sync_dir)?;
+        builder.add_repo(&namespace, &repo_name, &log.activities);
+        Ok(builder.finish())
+    })
+    .await?;
+
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+/// Lock hold times and contention across the repositories the caller can
+/// read, with the most contended first
+/// GET /api/locks/stats?days=30&namespace=
+pub async fn lock_stats(
+    config: web::Data<Config>,
+    query: web::Query<LockStatsQuery>,
+    auth_service: web::Data<AuthService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let user_id = get_optional_user_id_from_request(&req, &auth_service);
+
+    let config = config.into_inner();
+    let query = query.into_inner();
+    let stats =
+        blocking(move || server_lock_stats(&config, &query, user_id.as_deref())).await?;
+
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::{log_activity, ActivityType};
+    use crate::project::{ProjectMetadata, Visibility};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_server_lock_stats_skips_unreadable_repos() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.server.sync_dir = temp_dir.path().to_string_lossy().to_string();
+
+        for (name, visibility) in [("open", Visibility::Public), ("secret", Visibility::Private)] {
+            let repo_path = temp_dir.path().join("studio").join(name);
+            std::fs::create_dir_all(repo_path.join(".oxen")).unwrap();
+            ProjectMetadata::new("owner".to_string(), "owner".to_string(), visibility)
+                .save(&repo_path)
+                .unwrap();
+            log_activity(
+                &repo_path,
+                ActivityType::LockContended,
+                "alex",
+                "Asked for the lock held by sam",
+                Some(serde_json::json!({ "holder": "sam" })),
+                None,
+            )
+            .unwrap();
+        }
+
+        let query = LockStatsQuery::default();
+        let stats = server_lock_stats(&config, &query, Some("someone")).unwrap();
+        assert_eq!(stats.repos.len(), 1);
+        assert_eq!(stats.repos[0].name, "open");
+
+        let stats = server_lock_stats(&config, &query, Some("owner")).unwrap();
+        assert_eq!(stats.contention_events, 2);
+    }
+}
@@ -0,0 +1,343 @@
+//! Stem set API operations
+//!
+//! Stores the multi-file stem sets pushed by `auxin stems push`, laid out
+//! like the CLI's: `.auxin/stems/<commit>/` with the stem files and their
+//! `manifest.json`.
+
+use actix_multipart::Multipart;
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use super::bounce_ops::AudioFormat;
+use crate::auth::{require_role, AuthService, UserRole};
+use crate::error::{AppError, AppResult};
+use crate::project::ProjectAuth;
+use crate::repo_access::RepoAccessService;
+use auxin_config::Config;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Largest stem set accepted by one upload, all files together; the upload
+/// is held in memory until every stem has arrived
+pub const MAX_STEM_UPLOAD_BYTES: usize = 1024 * 1024 * 1024;
+
+/// One audio file in a stem set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stem {
+    pub name: String,
+    pub filename: String,
+    pub format: AudioFormat,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+/// The stems attached to a commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StemSet {
+    pub commit_id: String,
+    #[serde(default)]
+    pub stems: Vec<Stem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub added_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Get stems directory for a repository
+fn get_stems_dir(config: &Config, namespace: &str, repo_name: &str) -> PathBuf {
+    PathBuf::from(&config.server.sync_dir)
+        .join(namespace)
+        .join(repo_name)
+        .join(".auxin")
+        .join("stems")
+}
+
+/// Commit IDs and stem filenames become paths, so only plain names are allowed
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', '\0'])
+}
+
+fn read_manifest(dir: &Path) -> AppResult<StemSet> {
+    let contents = fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|e| AppError::Internal(format!("Failed to read stem manifest: {}", e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| AppError::Internal(format!("Failed to parse stem manifest: {}", e)))
+}
+
+/// Require read access to a repository
+fn require_read_access(
+    namespace: &str,
+    repo_name: &str,
+    auth_service: &AuthService,
+    repo_access: &RepoAccessService,
+    req: &actix_web::HttpRequest,
+) -> AppResult<String> {
+    let user = require_role(req, auth_service, UserRole::Client)?;
+    if !repo_access.has_access(namespace, repo_name, &user.id, user.role)? {
+        return Err(AppError::Forbidden(
+            "You do not have access to this repository".to_string(),
+        ));
+    }
+    Ok(user.username)
+}
+
+/// List all stem sets for a repository, most recently updated first
+/// Requires authentication and read access to repository
+pub async fn list_stems(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+    auth_service: web::Data<AuthService>,
+    repo_access: web::Data<RepoAccessService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    let username = require_read_access(&namespace, &repo_name, &auth_service, &repo_access, &req)?;
+
+    info!(
+        "User {} listing stems for {}/{}",
+        username, namespace, repo_name
+    );
+
+    let stems_dir = get_stems_dir(&config, &namespace, &repo_name);
+    let mut sets = Vec::new();
+    if let Ok(entries) = fs::read_dir(&stems_dir) {
+        for entry in entries.flatten() {
+            if entry.path().join(MANIFEST_FILE).exists() {
+                if let Ok(set) = read_manifest(&entry.path()) {
+                    sets.push(set);
+                }
+            }
+        }
+    }
+    sets.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+
+    Ok(HttpResponse::Ok().json(sets))
+}
+
+/// Get the stem set of a commit
+/// Requires authentication and read access to repository
+pub async fn get_stems(
+    config: web::Data<Config>,
+    path: web::Path<(String, String, String)>,
+    auth_service: web::Data<AuthService>,
+    repo_access: web::Data<RepoAccessService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name, commit_id) = path.into_inner();
+    require_read_access(&namespace, &repo_name, &auth_service, &repo_access, &req)?;
+
+    let dir = get_stems_dir(&config, &namespace, &repo_name).join(&commit_id);
+    if !is_plain_name(&commit_id) || !dir.join(MANIFEST_FILE).exists() {
+        return Err(AppError::NotFound(format!(
+            "No stems found for commit {}",
+            commit_id
+        )));
+    }
+
+    Ok(HttpResponse::Ok().json(read_manifest(&dir)?))
+}
+
+/// Get one stem's audio, by stem name or filename
+/// Requires authentication and read access to repository
+pub async fn get_stem_audio(
+    config: web::Data<Config>,
+    path: web::Path<(String, String, String, String)>,
+    auth_service: web::Data<AuthService>,
+    repo_access: web::Data<RepoAccessService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name, commit_id, stem_name) = path.into_inner();
+    require_read_access(&namespace, &repo_name, &auth_service, &repo_access, &req)?;
+
+    let dir = get_stems_dir(&config, &namespace, &repo_name).join(&commit_id);
+    let set = if is_plain_name(&commit_id) && dir.join(MANIFEST_FILE).exists() {
+        read_manifest(&dir)?
+    } else {
+        return Err(AppError::NotFound(format!(
+            "No stems found for commit {}",
+            commit_id
+        )));
+    };
+    let stem = set
+        .stems
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(&stem_name) || s.filename == stem_name)
+        .ok_or_else(|| AppError::NotFound(format!("No stem named {}", stem_name)))?;
+
+    let data = fs::read(dir.join(&stem.filename))
+        .map_err(|e| AppError::Internal(format!("Failed to read stem audio: {}", e)))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(stem.format.mime_type())
+        .body(data))
+}
+
+/// Upload a commit's stem set: a `manifest` field with the set's JSON and
+/// one `file` field per stem. Replaces any existing set for the commit.
+/// Requires Producer or Admin role and write access to the repository
+pub async fn upload_stems(
+    config: web::Data<Config>,
+    path: web::Path<(String, String, String)>,
+    mut payload: Multipart,
+    auth_service: web::Data<AuthService>,
+    repo_access: web::Data<RepoAccessService>,
+    req: actix_web::HttpRequest,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name, commit_id) = path.into_inner();
+
+    // Require Producer or Admin role
+    let user = require_role(&req, &auth_service, UserRole::Producer)?;
+    if !repo_access.has_access(&namespace, &repo_name, &user.id, user.role)? {
+        return Err(AppError::Forbidden(
+            "You do not have access to this repository".to_string(),
+        ));
+    }
+
+    // Stems are only stored for repositories that exist, so the path
+    // can't create new directories under the sync dir
+    let repo_path = PathBuf::from(&config.server.sync_dir)
+        .join(&namespace)
+        .join(&repo_name);
+    if !is_plain_name(&namespace) || !is_plain_name(&repo_name) || !repo_path.join(".oxen").is_dir()
+    {
+        return Err(AppError::NotFound(format!(
+            "Repository {}/{} not found",
+            namespace, repo_name
+        )));
+    }
+    ProjectAuth::require_write(&repo_path, &user.id)?;
+
+    if !is_plain_name(&commit_id) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid commit ID: {}",
+            commit_id
+        )));
+    }
+
+    info!(
+        "User {} uploading stems for {}/{} commit {}",
+        user.username, namespace, repo_name, commit_id
+    );
+
+    let mut manifest: Option<Vec<u8>> = None;
+    let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut received = 0;
+
+    while let Ok(Some(mut field)) = payload.try_next().await {
+        let content_disposition = match field.content_disposition() {
+            Some(cd) => cd,
+            None => continue,
+        };
+        let field_name = content_disposition.get_name().unwrap_or("").to_string();
+        let filename = content_disposition.get_filename().map(|s| s.to_string());
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk =
+                chunk.map_err(|e| AppError::Internal(format!("Failed to read upload: {}", e)))?;
+            received += chunk.len();
+            if received > MAX_STEM_UPLOAD_BYTES {
+                return Err(AppError::QuotaExceeded(format!(
+                    "Stem upload larger than {} MB",
+                    MAX_STEM_UPLOAD_BYTES / (1024 * 1024)
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        match (field_name.as_str(), filename) {
+            ("manifest", _) => manifest = Some(data),
+            ("file", Some(filename)) => {
+                files.insert(filename, data);
+            },
+            _ => {},
+        }
+    }
+
+    let manifest =
+        manifest.ok_or_else(|| AppError::BadRequest("No stem manifest provided".to_string()))?;
+    let mut set: StemSet = serde_json::from_slice(&manifest)
+        .map_err(|e| AppError::BadRequest(format!("Invalid stem manifest: {}", e)))?;
+    if set.commit_id != commit_id {
+        return Err(AppError::BadRequest(format!(
+            "Manifest is for commit {}, not {}",
+            set.commit_id, commit_id
+        )));
+    }
+
+    // Every stem in the manifest must be uploaded, complete, and nothing else
+    for stem in &set.stems {
+        if !is_plain_name(&stem.filename) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid stem filename: {}",
+                stem.filename
+            )));
+        }
+        match files.get(&stem.filename) {
+            Some(data) if data.len() as u64 == stem.size_bytes => {},
+            Some(_) => {
+                return Err(AppError::BadRequest(format!(
+                    "Stem {} is incomplete",
+                    stem.filename
+                )))
+            },
+            None => {
+                return Err(AppError::BadRequest(format!(
+                    "Stem {} was not uploaded",
+                    stem.filename
+                )))
+            },
+        }
+    }
+    if files.len() != set.stems.len() {
+        return Err(AppError::BadRequest(
+            "Upload contains files not in the manifest".to_string(),
+        ));
+    }
+    set.added_by = user.username.clone();
+
+    // Write the new set beside the old one, then swap it in
+    let stems_dir = get_stems_dir(&config, &namespace, &repo_name);
+    let dir = stems_dir.join(&commit_id);
+    let staging = stems_dir.join(format!(".{}.upload", commit_id));
+    let write = || -> std::io::Result<()> {
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+        for (filename, data) in &files {
+            fs::write(staging.join(filename), data)?;
+        }
+        let json = serde_json::to_string_pretty(&set).map_err(std::io::Error::other)?;
+        fs::write(staging.join(MANIFEST_FILE), json)?;
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        fs::rename(&staging, &dir)
+    };
+    write().map_err(|e| AppError::Internal(format!("Failed to store stems: {}", e)))?;
+
+    info!("Stored {} stems for commit {}", set.stems.len(), commit_id);
+    Ok(HttpResponse::Created().json(set))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_plain_name() {
+        assert!(is_plain_name("8f3e2a1b"));
+        assert!(is_plain_name("Lead Vox.wav"));
+        assert!(!is_plain_name(".."));
+        assert!(!is_plain_name("../secrets"));
+        assert!(!is_plain_name("a\\b.wav"));
+        assert!(!is_plain_name(""));
+    }
+}
@@ -11,6 +11,7 @@ use std::sync::{Arc, RwLock};
 use tracing::info;
 
 use auxin_config::Config;
+use crate::clock;
 use crate::error::{AppError, AppResult};
 
 /// User role for access control
@@ -91,6 +92,15 @@ struct TokenData {
     expires_at: chrono::DateTime<Utc>,
 }
 
+/// A user's live sessions, as shown to admins (never the tokens themselves)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub username: String,
+    pub sessions: usize,
+    /// When the longest-lived session expires
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
 /// Simple token-based authentication with user persistence
 #[derive(Debug, Clone)]
 pub struct AuthService {
@@ -136,6 +146,11 @@ impl AuthService {
             info!("No existing users file or error loading: {}", e);
         }
 
+        // Restore sessions saved by a graceful shutdown
+        if let Err(e) = service.load_sessions() {
+            info!("Could not restore saved sessions: {}", e);
+        }
+
         service
     }
 
@@ -197,6 +212,78 @@ impl AuthService {
         Ok(())
     }
 
+    /// Get sessions file path (written on graceful shutdown and on revocation)
+    fn sessions_file_path(&self) -> PathBuf {
+        PathBuf::from(&self.config.server.sync_dir)
+            .join(".auxin")
+            .join("sessions.json")
+    }
+
+    /// Restore tokens saved by `save_sessions`, dropping expired ones
+    ///
+    /// The file is removed once loaded so tokens revoked after this
+    /// restart can't come back on the next one.
+    fn load_sessions(&self) -> AppResult<()> {
+        let path = self.sessions_file_path();
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| AppError::Internal(format!("Failed to read sessions file: {}", e)))?;
+        let _ = std::fs::remove_file(&path);
+
+        let saved: HashMap<String, TokenData> = serde_json::from_str(&content)
+            .map_err(|e| AppError::Internal(format!("Failed to parse sessions file: {}", e)))?;
+
+        let mut tokens = self
+            .tokens
+            .write()
+            .map_err(|_| AppError::Internal("Lock poisoned".to_string()))?;
+
+        let now = clock::now();
+        tokens.extend(saved.into_iter().filter(|(_, data)| data.expires_at > now));
+
+        info!("Restored {} sessions from disk", tokens.len());
+        Ok(())
+    }
+
+    /// Save unexpired tokens so clients stay logged in across a restart
+    pub fn save_sessions(&self) -> AppResult<usize> {
+        let path = self.sessions_file_path();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| AppError::Internal(format!("Failed to create directory: {}", e)))?;
+        }
+
+        let tokens = self
+            .tokens
+            .read()
+            .map_err(|_| AppError::Internal("Lock poisoned".to_string()))?;
+
+        let now = clock::now();
+        let live: HashMap<&String, &TokenData> = tokens
+            .iter()
+            .filter(|(_, data)| data.expires_at > now)
+            .collect();
+
+        let content = serde_json::to_string(&live)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize sessions: {}", e)))?;
+
+        std::fs::write(&path, content)
+            .map_err(|e| AppError::Internal(format!("Failed to write sessions file: {}", e)))?;
+
+        // Tokens are bearer credentials: user-only read/write (Unix only)
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+        }
+
+        Ok(live.len())
+    }
+
     /// Register a new user
     pub fn register(&self, username: &str, email: &str, password: &str, role: Option<UserRole>) -> AppResult<User> {
         // Validate input
@@ -242,7 +329,7 @@ impl AuthService {
             email: email.to_string(),
             password_hash: Some(password_hash),
             role: role.unwrap_or_default(),
-            created_at: Utc::now(),
+            created_at: clock::now(),
         };
 
         // Save user
@@ -299,7 +386,7 @@ impl AuthService {
     /// Generate a new token for a user
     pub fn generate_token(&self, user_id: &str, username: &str) -> AppResult<String> {
         let token = format!("auxin_{}", uuid::Uuid::new_v4());
-        let expires_at = Utc::now() + Duration::hours(self.config.server.auth_token_expiry_hours as i64);
+        let expires_at = clock::now() + Duration::hours(self.config.server.auth_token_expiry_hours as i64);
 
         let token_data = TokenData {
             user_id: user_id.to_string(),
@@ -326,8 +413,8 @@ impl AuthService {
             .get(token)
             .ok_or_else(|| AppError::Unauthorized("Invalid token".to_string()))?;
 
-        if token_data.expires_at < Utc::now() {
-            return Err(AppError::Unauthorized("Token expired".to_string()));
+        if token_data.expires_at < clock::now() {
+            return Err(AppError::TokenExpired("Token expired".to_string()));
         }
 
         Ok(token_data.username.clone())
@@ -344,8 +431,8 @@ impl AuthService {
             .get(token)
             .ok_or_else(|| AppError::Unauthorized("Invalid token".to_string()))?;
 
-        if token_data.expires_at < Utc::now() {
-            return Err(AppError::Unauthorized("Token expired".to_string()));
+        if token_data.expires_at < clock::now() {
+            return Err(AppError::TokenExpired("Token expired".to_string()));
         }
 
         let users = self
@@ -361,14 +448,77 @@ impl AuthService {
 
     /// Revoke a token
     pub fn revoke_token(&self, token: &str) -> AppResult<()> {
-        self.tokens
+        let removed = self
+            .tokens
             .write()
             .map_err(|_| AppError::Internal("Lock poisoned".to_string()))?
             .remove(token);
 
+        if removed.is_some() {
+            self.save_sessions()?;
+        }
         Ok(())
     }
 
+    /// Revoke every token of a user; returns how many were revoked
+    ///
+    /// Sessions are saved straight away, not only at shutdown, so a crash
+    /// can't bring the revoked tokens back.
+    pub fn revoke_user_tokens(&self, username: &str) -> AppResult<usize> {
+        let revoked = {
+            let mut tokens = self
+                .tokens
+                .write()
+                .map_err(|_| AppError::Internal("Lock poisoned".to_string()))?;
+
+            let before = tokens.len();
+            tokens.retain(|_, data| data.username != username);
+            before - tokens.len()
+        };
+
+        if revoked > 0 {
+            self.save_sessions()?;
+        }
+        Ok(revoked)
+    }
+
+    /// Live sessions per user, busiest first
+    pub fn sessions(&self) -> AppResult<Vec<SessionSummary>> {
+        let tokens = self
+            .tokens
+            .read()
+            .map_err(|_| AppError::Internal("Lock poisoned".to_string()))?;
+
+        let now = clock::now();
+        let mut by_user: HashMap<&str, SessionSummary> = HashMap::new();
+        for data in tokens.values().filter(|data| data.expires_at > now) {
+            let summary = by_user
+                .entry(&data.username)
+                .or_insert_with(|| SessionSummary {
+                    username: data.username.clone(),
+                    sessions: 0,
+                    expires_at: data.expires_at,
+                });
+            summary.sessions += 1;
+            summary.expires_at = summary.expires_at.max(data.expires_at);
+        }
+
+        let mut sessions: Vec<SessionSummary> = by_user.into_values().collect();
+        sessions.sort_by(|a, b| b.sessions.cmp(&a.sessions).then(a.username.cmp(&b.username)));
+        Ok(sessions)
+    }
+
+    /// Number of expired tokens `cleanup_expired` would remove
+    pub fn expired_count(&self) -> AppResult<usize> {
+        let tokens = self
+            .tokens
+            .read()
+            .map_err(|_| AppError::Internal("Lock poisoned".to_string()))?;
+
+        let now = clock::now();
+        Ok(tokens.values().filter(|data| data.expires_at <= now).count())
+    }
+
     /// Clean up expired tokens
     pub fn cleanup_expired(&self) -> AppResult<usize> {
         let mut tokens = self
@@ -376,7 +526,7 @@ impl AuthService {
             .write()
             .map_err(|_| AppError::Internal("Lock poisoned".to_string()))?;
 
-        let now = Utc::now();
+        let now = clock::now();
         let initial_count = tokens.len();
 
         tokens.retain(|_, data| data.expires_at > now);
@@ -485,6 +635,14 @@ pub fn get_user_id_from_request(
     req: &actix_web::HttpRequest,
     auth_service: &AuthService,
 ) -> AppResult<String> {
+    get_user_from_request(req, auth_service).map(|user| user.id)
+}
+
+/// Extract the authenticated user's account from HTTP request
+pub fn get_user_from_request(
+    req: &actix_web::HttpRequest,
+    auth_service: &AuthService,
+) -> AppResult<User> {
     let token = req
         .headers()
         .get("Authorization")
@@ -492,8 +650,7 @@ pub fn get_user_id_from_request(
         .and_then(|s| s.strip_prefix("Bearer "))
         .ok_or_else(|| AppError::Unauthorized("No authorization token".to_string()))?;
 
-    let user = auth_service.get_user_by_token(token)?;
-    Ok(user.id)
+    auth_service.get_user_by_token(token)
 }
 
 /// Extract optional authenticated user ID from HTTP request (for public endpoints)
@@ -682,6 +839,45 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_revoke_user_tokens() {
+        let temp_dir = TempDir::new().unwrap();
+        let auth = AuthService::new(test_config_with_dir(&temp_dir));
+        let laptop = auth.generate_token("id1", "sam").unwrap();
+        auth.generate_token("id1", "sam").unwrap();
+        let other = auth.generate_token("id2", "alex").unwrap();
+
+        let sessions = auth.sessions().unwrap();
+        assert_eq!(sessions[0].username, "sam");
+        assert_eq!(sessions[0].sessions, 2);
+        assert_eq!(sessions.len(), 2);
+
+        assert_eq!(auth.revoke_user_tokens("sam").unwrap(), 2);
+        assert!(auth.validate_token(&laptop).is_err());
+        assert_eq!(auth.validate_token(&other).unwrap(), "alex");
+
+        // Saved on revocation: a restart without a graceful shutdown
+        // keeps the revocation and the other sessions
+        let restarted = AuthService::new(test_config_with_dir(&temp_dir));
+        assert!(restarted.validate_token(&laptop).is_err());
+        assert_eq!(restarted.validate_token(&other).unwrap(), "alex");
+    }
+
+    #[test]
+    fn test_sessions_survive_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let auth = AuthService::new(test_config_with_dir(&temp_dir));
+        let token = auth.generate_token("user-id", "testuser").unwrap();
+
+        assert_eq!(auth.save_sessions().unwrap(), 1);
+
+        let restarted = AuthService::new(test_config_with_dir(&temp_dir));
+        assert_eq!(restarted.validate_token(&token).unwrap(), "testuser");
+
+        // Loaded once; a later restart without a save starts clean
+        assert!(!restarted.sessions_file_path().exists());
+    }
+
     #[test]
     fn test_cleanup_expired() {
         let temp_dir = TempDir::new().unwrap();
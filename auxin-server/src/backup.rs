@@ -0,0 +1,431 @@
+//! Backup and restore of server-side state: `auxin-server backup` / `restore`
+//!
+//! Repository content is backed up with the data volume (see
+//! `docs/deployment/PRODUCTION.md`); this covers what the server keeps
+//! beside it, in one `.tar.zst` archive:
+//!
+//! ```text
+//! manifest.json
+//! server/users.json, server/repo_access.json, ...   (sync_dir/.auxin)
+//! repos/<namespace>/<name>/project.json             (owner, collaborators)
+//! repos/<namespace>/<name>/activity.json            (audit log)
+//! repos/<namespace>/<name>/metadata/...             (Logic Pro metadata)
+//! repos/<namespace>/<name>/comments/...
+//! repos/<namespace>/<name>/locks.json               (lock + fencing counter)
+//! ```
+//!
+//! Locks are read from and restored to whichever backend is configured
+//! (lock files or Redis), so an archive taken from one restores into the
+//! other.
+//!
+//! A plain backup expects the server to be stopped. `--online` takes a
+//! consistent snapshot of a running server instead: state is read
+//! repeatedly until two passes agree and every JSON file parses, so no file
+//! is caught mid-write. Sessions only live in memory while the server runs,
+//! so after restoring an online backup users sign in again.
+
+use anyhow::{anyhow, bail, Context, Result};
+use auxin_config::Config;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use crate::extensions::{LockSnapshot, LockStore};
+use crate::repo::RepositoryOps;
+
+/// Archive layout version; restore refuses newer archives
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Per-repository state, relative to `.oxen`
+const REPO_STATE: &[&str] = &["project.json", "activity.json", "metadata", "comments"];
+
+/// Passes an online backup makes before giving up on a busy server
+const MAX_PASSES: usize = 5;
+
+const MANIFEST: &str = "manifest.json";
+const LOCKS: &str = "locks.json";
+
+/// Describes an archive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    pub server_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Taken from a running server with `--online`
+    pub online: bool,
+    /// Lock backend the locks were read from: `file` or `redis`
+    pub lock_backend: String,
+    /// Repositories as `namespace/name`
+    pub repos: Vec<String>,
+}
+
+/// Server state held in memory, keyed by archive path
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub manifest: Manifest,
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+/// Whether something is listening on the configured address
+pub fn server_is_running(config: &Config) -> bool {
+    let host = match config.server.host.as_str() {
+        "0.0.0.0" | "::" | "" => "127.0.0.1",
+        host => host,
+    };
+    let addrs: Vec<SocketAddr> = (host, config.server.port as u16)
+        .to_socket_addrs()
+        .map(|addrs| addrs.collect())
+        .unwrap_or_default();
+    addrs
+        .iter()
+        .any(|addr| TcpStream::connect_timeout(addr, Duration::from_millis(500)).is_ok())
+}
+
+fn lock_backend(config: &Config) -> &'static str {
+    if cfg!(feature = "redis-locks") && config.server.enable_redis_locks {
+        "redis"
+    } else {
+        "file"
+    }
+}
+
+/// Repositories under `sync_dir` as `(namespace, name)`
+pub(crate) fn list_repos(sync_dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut repos = Vec::new();
+    let Ok(namespaces) = fs::read_dir(sync_dir) else {
+        return Ok(repos);
+    };
+    for namespace in namespaces.flatten() {
+        let namespace_name = namespace.file_name().to_string_lossy().to_string();
+        if namespace_name.starts_with('.') || !namespace.path().is_dir() {
+            continue;
+        }
+        for repo in fs::read_dir(namespace.path())?.flatten() {
+            if repo.path().join(".oxen").is_dir() {
+                let name = repo.file_name().to_string_lossy().to_string();
+                repos.push((namespace_name.clone(), name));
+            }
+        }
+    }
+    repos.sort();
+    Ok(repos)
+}
+
+/// Add every file under `dir` to `files`, keyed under `prefix`
+fn read_tree(dir: &Path, prefix: &str, files: &mut BTreeMap<String, Vec<u8>>) -> Result<()> {
+    if dir.is_file() {
+        let data = fs::read(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        files.insert(prefix.to_string(), data);
+        return Ok(());
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        read_tree(&entry.path(), &format!("{}/{}", prefix, name), files)?;
+    }
+    Ok(())
+}
+
+/// One pass over the server's state
+fn read_state(config: &Config, repos: &[(String, String)]) -> Result<BTreeMap<String, Vec<u8>>> {
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let mut files = BTreeMap::new();
+    read_tree(&sync_dir.join(".auxin"), "server", &mut files)?;
+
+    for (namespace, name) in repos {
+        let repo_path = sync_dir.join(namespace).join(name);
+        let prefix = format!("repos/{}/{}", namespace, name);
+        for item in REPO_STATE {
+            read_tree(
+                &repo_path.join(".oxen").join(item),
+                &format!("{}/{}", prefix, item),
+                &mut files,
+            )?;
+        }
+
+        let repo = RepositoryOps::open(&repo_path).map_err(|e| anyhow!("{}: {}", prefix, e))?;
+        let locks = LockStore::new(config, &repo, namespace, name)
+            .and_then(|store| store.snapshot())
+            .map_err(|e| anyhow!("{}: {}", prefix, e))?;
+        files.insert(format!("{}/{}", prefix, LOCKS), serde_json::to_vec_pretty(&locks)?);
+    }
+    Ok(files)
+}
+
+/// JSON files in `files` that don't parse
+fn unparseable(files: &BTreeMap<String, Vec<u8>>) -> Vec<String> {
+    files
+        .iter()
+        .filter(|(path, data)| {
+            path.ends_with(".json") && serde_json::from_slice::<serde_json::Value>(data).is_err()
+        })
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+/// Read the server's state; `online` keeps reading until it's consistent
+pub fn take_snapshot(config: &Config, online: bool) -> Result<Snapshot> {
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let repos = list_repos(&sync_dir)?;
+
+    let mut files = read_state(config, &repos)?;
+    if online {
+        let mut settled = false;
+        for _ in 1..MAX_PASSES {
+            let again = read_state(config, &repos)?;
+            settled = again == files && unparseable(&again).is_empty();
+            files = again;
+            if settled {
+                break;
+            }
+        }
+        if !settled {
+            bail!(
+                "Server state kept changing over {} passes; try again when it's quieter",
+                MAX_PASSES
+            );
+        }
+    }
+
+    Ok(Snapshot {
+        manifest: Manifest {
+            format_version: FORMAT_VERSION,
+            server_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            online,
+            lock_backend: lock_backend(config).to_string(),
+            repos: repos
+                .iter()
+                .map(|(namespace, name)| format!("{}/{}", namespace, name))
+                .collect(),
+        },
+        files,
+    })
+}
+
+/// Write a snapshot as a zstd-compressed tar archive, readable by the owner only
+pub fn write_archive(snapshot: &Snapshot, path: &Path) -> Result<()> {
+    let tmp_path = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    let result = (|| -> Result<()> {
+        let file = fs::File::create(&tmp_path)
+            .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+
+        let encoder = zstd::Encoder::new(file, 0)?;
+        let mut builder = tar::Builder::new(encoder);
+        let mtime = snapshot.manifest.created_at.timestamp().max(0) as u64;
+        let manifest = serde_json::to_vec_pretty(&snapshot.manifest)?;
+        let files = snapshot
+            .files
+            .iter()
+            .map(|(path, data)| (path.as_str(), data.as_slice()));
+        let entries = std::iter::once((MANIFEST, manifest.as_slice())).chain(files);
+        for (entry_path, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o600);
+            header.set_mtime(mtime);
+            header.set_cksum();
+            builder.append_data(&mut header, entry_path, data)?;
+        }
+        builder.into_inner()?.finish()?.sync_all()?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Archive paths must stay inside the archive's layout
+fn is_safe(path: &str) -> bool {
+    (path == MANIFEST || path.starts_with("server/") || path.starts_with("repos/"))
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Read and check an archive: its manifest, paths and JSON
+pub fn read_archive(path: &Path) -> Result<Snapshot> {
+    let file = fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let decoder = zstd::Decoder::new(file)?;
+    let mut archive = tar::Archive::new(decoder);
+
+    let mut files = BTreeMap::new();
+    for entry in archive.entries().context("Not a backup archive")? {
+        let mut entry = entry.context("Corrupt backup archive")?;
+        let entry_path = entry.path()?.to_string_lossy().replace('\\', "/");
+        if !is_safe(&entry_path) {
+            bail!("Unexpected path in backup: {}", entry_path);
+        }
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        files.insert(entry_path, data);
+    }
+
+    let manifest: Manifest = serde_json::from_slice(
+        &files.remove(MANIFEST).context("Backup has no manifest.json")?,
+    )
+    .context("Backup manifest is corrupt")?;
+    if manifest.format_version > FORMAT_VERSION {
+        bail!(
+            "Backup format {} is newer than this server understands ({}); upgrade first",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+    if let Some(bad) = unparseable(&files).first() {
+        bail!("Backup file {} is corrupt", bad);
+    }
+    Ok(Snapshot { manifest, files })
+}
+
+/// What a restore did (or, for a dry run, would do)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreReport {
+    /// Files written to `sync_dir/.auxin`
+    pub server_files: usize,
+    /// Repositories whose state was restored
+    pub repos: usize,
+    /// Files written into those repositories
+    pub repo_files: usize,
+    /// Locks put back (expired ones are dropped)
+    pub locks: usize,
+    /// Repositories in the backup that aren't in `sync_dir`; restore their
+    /// data first, then run the restore again
+    pub missing_repos: Vec<String>,
+}
+
+/// Write a snapshot into the configured `sync_dir` and lock backend
+///
+/// Files in the backup replace their current versions; nothing else is
+/// deleted. Each repository's lock is set to the backed-up one.
+pub fn restore(snapshot: &Snapshot, config: &Config, dry_run: bool) -> Result<RestoreReport> {
+    let sync_dir = PathBuf::from(&config.server.sync_dir);
+    let mut report = RestoreReport::default();
+
+    let write = |path: &Path, data: &[u8]| -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, data).with_context(|| format!("Failed to write {}", path.display()))
+    };
+
+    for (archive_path, data) in &snapshot.files {
+        if let Some(rest) = archive_path.strip_prefix("server/") {
+            report.server_files += 1;
+            if !dry_run {
+                write(&sync_dir.join(".auxin").join(rest), data)?;
+            }
+        }
+    }
+
+    for repo_key in &snapshot.manifest.repos {
+        let Some((namespace, name)) = repo_key.split_once('/') else {
+            continue;
+        };
+        let prefix = format!("repos/{}/", repo_key);
+        if !is_safe(&prefix) {
+            bail!("Unexpected repository in backup: {}", repo_key);
+        }
+        let repo_path = sync_dir.join(namespace).join(name);
+        let Ok(repo) = RepositoryOps::open(&repo_path) else {
+            report.missing_repos.push(repo_key.clone());
+            continue;
+        };
+        report.repos += 1;
+
+        for (archive_path, data) in snapshot.files.range(prefix.clone()..) {
+            let Some(rest) = archive_path.strip_prefix(&prefix) else {
+                break;
+            };
+            if rest == LOCKS {
+                let locks: LockSnapshot = serde_json::from_slice(data)?;
+                if locks.lock.as_ref().is_some_and(|lock| lock.expires_at > Utc::now()) {
+                    report.locks += 1;
+                }
+                if !dry_run {
+                    LockStore::new(config, &repo, namespace, name)
+                        .and_then(|store| store.restore(&locks))
+                        .map_err(|e| anyhow!("{}: {}", repo_key, e))?;
+                }
+            } else {
+                report.repo_files += 1;
+                if !dry_run {
+                    write(&repo_path.join(".oxen").join(rest), data)?;
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::FileLock;
+    use tempfile::TempDir;
+
+    fn config_for(sync_dir: &Path) -> Config {
+        let mut config = Config::default();
+        config.server.sync_dir = sync_dir.to_string_lossy().to_string();
+        config
+    }
+
+    #[test]
+    fn test_backup_and_restore_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        let repo = source.join("studio/album");
+        fs::create_dir_all(source.join(".auxin")).unwrap();
+        fs::create_dir_all(repo.join(".oxen/metadata")).unwrap();
+        fs::create_dir_all(source.join("studio/demos/.oxen")).unwrap();
+        fs::write(source.join(".auxin/users.json"), "[]").unwrap();
+        fs::write(repo.join(".oxen/activity.json"), r#"{"activities": []}"#).unwrap();
+        fs::write(repo.join(".oxen/metadata/abc.json"), r#"{"bpm": 120}"#).unwrap();
+        let lock = FileLock::acquire(&repo, "alex", "studio-mac", 4).unwrap();
+
+        let snapshot = take_snapshot(&config_for(&source), true).unwrap();
+        assert_eq!(snapshot.manifest.repos, ["studio/album", "studio/demos"]);
+        assert_eq!(snapshot.manifest.lock_backend, "file");
+        let archive = temp.path().join("state.tar.zst");
+        write_archive(&snapshot, &archive).unwrap();
+
+        // Restore into a server that has only the album's data
+        let target = temp.path().join("target");
+        fs::create_dir_all(target.join("studio/album/.oxen")).unwrap();
+        let restored = read_archive(&archive).unwrap();
+        assert_eq!(restored.manifest, snapshot.manifest);
+
+        let config = config_for(&target);
+        let dry_run = restore(&restored, &config, true).unwrap();
+        assert!(!target.join(".auxin").exists());
+
+        let report = restore(&restored, &config, false).unwrap();
+        assert_eq!(report, dry_run);
+        assert_eq!(report.server_files, 1);
+        assert_eq!(report.repos, 1);
+        assert_eq!(report.repo_files, 2);
+        assert_eq!(report.locks, 1);
+        assert_eq!(report.missing_repos, ["studio/demos"]);
+        assert_eq!(
+            fs::read_to_string(target.join("studio/album/.oxen/metadata/abc.json")).unwrap(),
+            r#"{"bpm": 120}"#
+        );
+        let held = FileLock::status(&target.join("studio/album")).unwrap().unwrap();
+        assert_eq!(held.lock_id, lock.lock_id);
+        assert_eq!(held.fencing_token, lock.fencing_token);
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Mutex, OnceLock};
+
+/// Clock installed by `auxin-server --mock`; unset in normal operation
+static MOCK_CLOCK: OnceLock<MockClock> = OnceLock::new();
+
+/// Current time as seen by locks, tokens and the activity log
+///
+/// This is the wall clock unless the server runs in mock mode, where tests
+/// can freeze or advance it to make lock and token expiry deterministic.
+pub fn now() -> DateTime<Utc> {
+    match MOCK_CLOCK.get() {
+        Some(clock) => clock.now(),
+        None => Utc::now(),
+    }
+}
+
+/// Install the controllable clock used by [`now`] (mock mode only)
+pub fn install_mock() -> &'static MockClock {
+    MOCK_CLOCK.get_or_init(MockClock::default)
+}
+
+/// The installed mock clock, if any
+pub fn mock() -> Option<&'static MockClock> {
+    MOCK_CLOCK.get()
+}
+
+/// Clock that can be frozen at a fixed instant or shifted from wall time
+#[derive(Debug, Default)]
+pub struct MockClock {
+    state: Mutex<ClockState>,
+}
+
+#[derive(Debug, Default)]
+struct ClockState {
+    frozen_at: Option<DateTime<Utc>>,
+    offset: Duration,
+}
+
+impl MockClock {
+    pub fn now(&self) -> DateTime<Utc> {
+        let state = self.state.lock().unwrap();
+        state.frozen_at.unwrap_or_else(|| Utc::now() + state.offset)
+    }
+
+    /// Stop the clock at `at` until it is advanced or reset
+    pub fn freeze(&self, at: DateTime<Utc>) {
+        let mut state = self.state.lock().unwrap();
+        state.frozen_at = Some(at);
+        state.offset = Duration::zero();
+    }
+
+    /// Move the clock forward (or back, for negative durations)
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.state.lock().unwrap();
+        match state.frozen_at.as_mut() {
+            Some(frozen_at) => *frozen_at += by,
+            None => state.offset += by,
+        }
+    }
+
+    /// Go back to following wall time
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = ClockState::default();
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.state.lock().unwrap().frozen_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frozen_clock_only_moves_when_advanced() {
+        let clock = MockClock::default();
+        let start = Utc::now() - Duration::days(30);
+
+        clock.freeze(start);
+        assert_eq!(clock.now(), start);
+        assert!(clock.is_frozen());
+
+        clock.advance(Duration::hours(2));
+        assert_eq!(clock.now(), start + Duration::hours(2));
+
+        clock.reset();
+        assert!(!clock.is_frozen());
+        assert!(clock.now() > start + Duration::days(29));
+    }
+
+    #[test]
+    fn test_advance_offsets_wall_time() {
+        let clock = MockClock::default();
+        clock.advance(Duration::hours(5));
+
+        let drift = clock.now() - Utc::now();
+        assert!(drift > Duration::hours(4) && drift <= Duration::hours(5));
+    }
+}
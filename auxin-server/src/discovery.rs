@@ -0,0 +1,355 @@
+//! LAN discovery: advertises the server as `_auxin._tcp` over multicast DNS
+//!
+//! `auxin server discover` sends a DNS-SD query for `_auxin._tcp.local` to
+//! the mDNS group; this responder answers with PTR, SRV, TXT and A records
+//! so small studios can find the server without typing a URL. The socket
+//! shares port 5353 with the system responder (mDNSResponder, Avahi).
+
+use auxin_config::Config;
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// DNS-SD service type
+pub const SERVICE: &str = "_auxin._tcp.local";
+
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+
+/// Record lifetime; short so a stopped server disappears quickly
+const TTL: u32 = 120;
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_SRV: u16 = 33;
+const TYPE_ANY: u16 = 255;
+const CLASS_IN: u16 = 1;
+/// Top bit of a question's class: the querier wants a unicast reply
+const UNICAST_RESPONSE: u16 = 0x8000;
+
+/// What the server advertises
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advertisement {
+    /// Instance name, e.g. "Studio Server"
+    pub name: String,
+    /// Host name without `.local`
+    pub host: String,
+    pub port: u16,
+    /// Address to advertise; `None` uses the interface facing each querier
+    pub address: Option<Ipv4Addr>,
+}
+
+/// A question from an mDNS query
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Question {
+    name: String,
+    qtype: u16,
+    unicast: bool,
+}
+
+impl Advertisement {
+    /// The advertisement for `[server]`, or `None` when the server only
+    /// listens on loopback and can't be reached from the LAN
+    pub fn from_config(config: &Config, port: u16) -> Option<Self> {
+        let address = match config.server.host.parse::<Ipv4Addr>() {
+            Ok(ip) if ip.is_loopback() => return None,
+            Ok(ip) if !ip.is_unspecified() => Some(ip),
+            Ok(_) => None,
+            Err(_) if config.server.host == "localhost" => return None,
+            Err(_) => None,
+        };
+        let host = local_host_name();
+        let name = match config.server.advertise_name.trim() {
+            "" => format!("Auxin Server on {}", host),
+            name => name.to_string(),
+        };
+
+        Some(Self {
+            // Dots would split the instance label
+            name: name.replace('.', "-"),
+            host,
+            port,
+            address,
+        })
+    }
+
+    fn instance(&self) -> String {
+        format!("{}.{}", self.name, SERVICE)
+    }
+
+    fn target(&self) -> String {
+        format!("{}.local", self.host)
+    }
+
+    fn txt(&self) -> Vec<String> {
+        vec![
+            format!("version={}", env!("CARGO_PKG_VERSION")),
+            format!("api=v{}", crate::api::API_VERSION),
+            "path=/".to_string(),
+        ]
+    }
+
+    /// Whether a question asks about this service
+    fn answers(&self, question: &Question) -> bool {
+        let name = question.name.to_ascii_lowercase();
+        let asks = |record_type: u16| question.qtype == record_type || question.qtype == TYPE_ANY;
+        (name == SERVICE && asks(TYPE_PTR))
+            || (name == self.instance().to_ascii_lowercase() && (asks(TYPE_SRV) || asks(TYPE_TXT)))
+            || (name == self.target().to_ascii_lowercase() && asks(TYPE_A))
+    }
+
+    /// A response carrying every record, so one round trip is enough
+    fn response(&self, id: u16, address: Ipv4Addr) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(512);
+        packet.extend_from_slice(&id.to_be_bytes());
+        packet.extend_from_slice(&0x8400u16.to_be_bytes()); // response, authoritative
+        packet.extend_from_slice(&[0, 0, 0, 4, 0, 0, 0, 0]); // 4 answers
+
+        let mut ptr = Vec::new();
+        write_name(&mut ptr, &self.instance());
+        write_record(&mut packet, SERVICE, TYPE_PTR, &ptr);
+
+        let mut srv = vec![0, 0, 0, 0]; // priority, weight
+        srv.extend_from_slice(&self.port.to_be_bytes());
+        write_name(&mut srv, &self.target());
+        write_record(&mut packet, &self.instance(), TYPE_SRV, &srv);
+
+        let mut txt = Vec::new();
+        for entry in self.txt() {
+            txt.push(entry.len() as u8);
+            txt.extend_from_slice(entry.as_bytes());
+        }
+        write_record(&mut packet, &self.instance(), TYPE_TXT, &txt);
+
+        write_record(&mut packet, &self.target(), TYPE_A, &address.octets());
+        packet
+    }
+}
+
+/// Start answering mDNS queries on a background thread
+pub fn spawn(advertisement: Advertisement) -> io::Result<()> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    let bind = SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, MDNS_PORT);
+    socket.bind(&SockAddr::from(bind))?;
+    socket.join_multicast_v4(&MDNS_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+    socket.set_multicast_loop_v4(true)?;
+    let socket = UdpSocket::from(socket);
+
+    thread::Builder::new()
+        .name("mdns".to_string())
+        .spawn(move || {
+            announce(&socket, &advertisement);
+            serve(&socket, &advertisement);
+        })?;
+    Ok(())
+}
+
+/// Unsolicited announcements so browsers already listening see the server
+fn announce(socket: &UdpSocket, advertisement: &Advertisement) {
+    let group = SocketAddr::from((MDNS_GROUP, MDNS_PORT));
+    let Some(address) = advertisement
+        .address
+        .or_else(|| local_address_towards(group))
+    else {
+        return;
+    };
+    for _ in 0..2 {
+        if let Err(e) = socket.send_to(&advertisement.response(0, address), group) {
+            warn!("mDNS announcement failed: {}", e);
+            return;
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+fn serve(socket: &UdpSocket, advertisement: &Advertisement) {
+    let mut buffer = [0u8; 9000];
+    loop {
+        let (len, from) = match socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            Err(e) => {
+                warn!("mDNS responder stopped: {}", e);
+                return;
+            },
+        };
+        let Some((id, questions)) = parse_query(&buffer[..len]) else {
+            continue;
+        };
+        if !questions.iter().any(|q| advertisement.answers(q)) {
+            continue;
+        }
+        let Some(address) = advertisement
+            .address
+            .or_else(|| local_address_towards(from))
+        else {
+            continue;
+        };
+
+        // Queries from other ports are one-shot queries expecting a direct
+        // reply with their ID (RFC 6762 §6.7)
+        let unicast = from.port() != MDNS_PORT || questions.iter().any(|q| q.unicast);
+        let (destination, id) = if unicast {
+            (from, id)
+        } else {
+            (SocketAddr::from((MDNS_GROUP, MDNS_PORT)), 0)
+        };
+        debug!("Answering mDNS query from {}", from);
+        if let Err(e) = socket.send_to(&advertisement.response(id, address), destination) {
+            debug!("mDNS reply to {} failed: {}", destination, e);
+        }
+    }
+}
+
+/// The local IPv4 address used to reach `peer`
+fn local_address_towards(peer: SocketAddr) -> Option<Ipv4Addr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect(peer).ok()?;
+    match socket.local_addr().ok()? {
+        SocketAddr::V4(addr) if !addr.ip().is_unspecified() => Some(*addr.ip()),
+        _ => None,
+    }
+}
+
+/// This machine's name without any domain, e.g. `studio-mac`
+fn local_host_name() -> String {
+    Command::new("hostname")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .and_then(|name| name.trim().split('.').next().map(str::to_string))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "auxin-server".to_string())
+}
+
+/// The ID and questions of a query; `None` for responses and bad packets
+fn parse_query(packet: &[u8]) -> Option<(u16, Vec<Question>)> {
+    let header = packet.get(..12)?;
+    let id = u16::from_be_bytes([header[0], header[1]]);
+    if header[2] & 0x80 != 0 {
+        return None;
+    }
+    let count = u16::from_be_bytes([header[4], header[5]]);
+
+    let mut pos = 12;
+    let mut questions = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (name, next) = read_name(packet, pos)?;
+        let fields = packet.get(next..next + 4)?;
+        let class = u16::from_be_bytes([fields[2], fields[3]]);
+        questions.push(Question {
+            name,
+            qtype: u16::from_be_bytes([fields[0], fields[1]]),
+            unicast: class & UNICAST_RESPONSE != 0,
+        });
+        pos = next + 4;
+    }
+    Some((id, questions))
+}
+
+/// A possibly compressed name at `pos`, and the position after it
+fn read_name(packet: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    for _ in 0..128 {
+        let len = *packet.get(pos)? as usize;
+        if len == 0 {
+            return Some((labels.join("."), end.unwrap_or(pos + 1)));
+        }
+        if len & 0xC0 == 0xC0 {
+            let pointer = ((len & 0x3F) << 8) | *packet.get(pos + 1)? as usize;
+            end.get_or_insert(pos + 2);
+            pos = pointer;
+            continue;
+        }
+        let label = packet.get(pos + 1..pos + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).to_string());
+        pos += 1 + len;
+    }
+    None
+}
+
+fn write_name(out: &mut Vec<u8>, name: &str) {
+    for label in name.split('.').filter(|l| !l.is_empty()) {
+        let label = &label.as_bytes()[..label.len().min(63)];
+        out.push(label.len() as u8);
+        out.extend_from_slice(label);
+    }
+    out.push(0);
+}
+
+fn write_record(out: &mut Vec<u8>, name: &str, record_type: u16, data: &[u8]) {
+    write_name(out, name);
+    out.extend_from_slice(&record_type.to_be_bytes());
+    out.extend_from_slice(&CLASS_IN.to_be_bytes());
+    out.extend_from_slice(&TTL.to_be_bytes());
+    out.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advertisement() -> Advertisement {
+        Advertisement {
+            name: "Studio Server".to_string(),
+            host: "studio-mac".to_string(),
+            port: 3000,
+            address: Some(Ipv4Addr::new(192, 168, 1, 20)),
+        }
+    }
+
+    fn query(name: &str, qtype: u16, class: u16) -> Vec<u8> {
+        let mut packet = vec![0x12, 0x34, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        write_name(&mut packet, name);
+        packet.extend_from_slice(&qtype.to_be_bytes());
+        packet.extend_from_slice(&class.to_be_bytes());
+        packet
+    }
+
+    #[test]
+    fn test_answers_service_queries() {
+        let ad = advertisement();
+        let (id, questions) = parse_query(&query(SERVICE, TYPE_PTR, 0x8001)).unwrap();
+        assert_eq!(id, 0x1234);
+        assert!(questions[0].unicast);
+        assert!(ad.answers(&questions[0]));
+
+        let (_, questions) = parse_query(&query("studio-mac.local", TYPE_A, 1)).unwrap();
+        assert!(ad.answers(&questions[0]));
+        let (_, questions) = parse_query(&query("_http._tcp.local", TYPE_PTR, 1)).unwrap();
+        assert!(!ad.answers(&questions[0]));
+
+        let response = ad.response(0x1234, Ipv4Addr::new(192, 168, 1, 20));
+        assert!(
+            parse_query(&response).is_none(),
+            "responses are not queries"
+        );
+        assert_eq!(&response[..2], &[0x12, 0x34]);
+        assert_eq!(u16::from_be_bytes([response[6], response[7]]), 4);
+        let (name, _) = read_name(&response, 12).unwrap();
+        assert_eq!(name, SERVICE);
+        assert!(response.windows(4).any(|w| w == [192, 168, 1, 20]));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut config = Config::default();
+        config.server.host = "127.0.0.1".to_string();
+        assert!(Advertisement::from_config(&config, 3000).is_none());
+
+        config.server.host = "10.0.0.5".to_string();
+        config.server.advertise_name = "Mix Room v2.1".to_string();
+        let ad = Advertisement::from_config(&config, 3000).unwrap();
+        assert_eq!(ad.name, "Mix Room v2-1");
+        assert_eq!(ad.address, Some(Ipv4Addr::new(10, 0, 0, 5)));
+    }
+}
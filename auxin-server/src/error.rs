@@ -1,6 +1,10 @@
 use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// Media type for RFC 9457 problem details
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
 #[derive(Debug)]
 pub enum AppError {
     NotFound(String),
@@ -11,6 +15,27 @@ pub enum AppError {
     Internal(String),
     NotImplemented(String),
     Database(String),
+    /// Repository lock is held by someone else
+    LockHeld(String),
+    /// Auth token was valid but has expired
+    TokenExpired(String),
+    /// Request exceeds a server-side limit
+    QuotaExceeded(String),
+}
+
+/// Error response body (`application/problem+json`, RFC 9457)
+///
+/// `code` is a stable machine-readable identifier clients branch on;
+/// `error` repeats the display message for clients predating this format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: String,
+    pub error: String,
 }
 
 impl fmt::Display for AppError {
@@ -24,6 +49,60 @@ impl fmt::Display for AppError {
             AppError::Internal(msg) => write!(f, "Internal error: {}", msg),
             AppError::NotImplemented(msg) => write!(f, "Not implemented: {}", msg),
             AppError::Database(msg) => write!(f, "Database error: {}", msg),
+            AppError::LockHeld(msg) => write!(f, "Conflict: {}", msg),
+            AppError::TokenExpired(msg) => write!(f, "Unauthorized: {}", msg),
+            AppError::QuotaExceeded(msg) => write!(f, "Quota exceeded: {}", msg),
+        }
+    }
+}
+
+impl AppError {
+    /// Machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::BadRequest(_) => "BAD_REQUEST",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+            AppError::NotImplemented(_) => "NOT_IMPLEMENTED",
+            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::LockHeld(_) => "LOCK_HELD",
+            AppError::TokenExpired(_) => "TOKEN_EXPIRED",
+            AppError::QuotaExceeded(_) => "QUOTA_EXCEEDED",
+        }
+    }
+
+    /// The message without the category prefix
+    pub fn detail(&self) -> &str {
+        match self {
+            AppError::NotFound(msg)
+            | AppError::BadRequest(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::Conflict(msg)
+            | AppError::Internal(msg)
+            | AppError::NotImplemented(msg)
+            | AppError::Database(msg)
+            | AppError::LockHeld(msg)
+            | AppError::TokenExpired(msg)
+            | AppError::QuotaExceeded(msg) => msg,
+        }
+    }
+
+    /// Build the problem+json body for this error
+    pub fn problem(&self) -> ProblemDetails {
+        let status = self.status_code();
+        let code = self.code();
+
+        ProblemDetails {
+            problem_type: format!("urn:auxin:problem:{}", code.to_lowercase().replace('_', "-")),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail: self.detail().to_string(),
+            code: code.to_string(),
+            error: self.to_string(),
         }
     }
 }
@@ -39,16 +118,16 @@ impl ResponseError for AppError {
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
             AppError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::LockHeld(_) => StatusCode::CONFLICT,
+            AppError::TokenExpired(_) => StatusCode::UNAUTHORIZED,
+            AppError::QuotaExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
-        let status = self.status_code();
-        let message = self.to_string();
-
-        HttpResponse::build(status).json(serde_json::json!({
-            "error": message,
-        }))
+        HttpResponse::build(self.status_code())
+            .content_type(PROBLEM_JSON)
+            .json(self.problem())
     }
 }
 
@@ -138,6 +217,30 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_problem_details() {
+        let error = AppError::LockHeld("Lock held by alice".to_string());
+        let problem = error.problem();
+
+        assert_eq!(problem.code, "LOCK_HELD");
+        assert_eq!(problem.status, 409);
+        assert_eq!(problem.title, "Conflict");
+        assert_eq!(problem.detail, "Lock held by alice");
+        assert_eq!(problem.problem_type, "urn:auxin:problem:lock-held");
+        assert_eq!(problem.error, "Conflict: Lock held by alice");
+    }
+
+    #[test]
+    fn test_error_response_is_problem_json() {
+        let response = AppError::TokenExpired("Token expired".to_string()).error_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            PROBLEM_JSON
+        );
+    }
+
     #[test]
     fn test_app_result_ok() {
         let result: AppResult<i32> = Ok(42);
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::Path;
 use tracing::info;
 
+use crate::clock;
 use crate::error::{AppError, AppResult};
 
 /// Activity event types
@@ -12,11 +14,16 @@ pub enum ActivityType {
     Commit,
     LockAcquired,
     LockReleased,
+    /// A lock ran out without being released and was cleared by the server
+    LockExpired,
+    /// Someone asked for a lock while another user held it
+    LockContended,
     BranchCreated,
     UserJoined,
     Push,
     Pull,
     Restore,
+    Comment,
 }
 
 /// Single activity event
@@ -29,6 +36,10 @@ pub struct Activity {
     pub timestamp: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    /// ID of the request that caused this activity, for correlating with
+    /// client and server logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 /// Activity storage for a repository
@@ -82,6 +93,27 @@ impl ActivityLog {
         self.activities.push(activity);
     }
 
+    /// Add activities recorded elsewhere (e.g. on another server), keeping
+    /// their IDs and timestamps
+    ///
+    /// Nothing is added if an ID repeats within `activities` or is already
+    /// in the log. Returns the number added.
+    pub fn import(&mut self, activities: Vec<Activity>) -> AppResult<usize> {
+        let mut known: HashSet<&str> = self.activities.iter().map(|a| a.id.as_str()).collect();
+        for activity in &activities {
+            if !known.insert(&activity.id) {
+                return Err(AppError::Conflict(format!(
+                    "Activity '{}' is already in the log or repeated in the import",
+                    activity.id
+                )));
+            }
+        }
+
+        let imported = activities.len();
+        self.activities.extend(activities);
+        Ok(imported)
+    }
+
     /// Get recent activities (most recent first)
     pub fn recent(&self, limit: usize) -> Vec<&Activity> {
         let mut activities: Vec<&Activity> = self.activities.iter().collect();
@@ -101,6 +133,14 @@ impl ActivityLog {
     pub fn by_user(&self, user: &str) -> Vec<&Activity> {
         self.activities.iter().filter(|a| a.user == user).collect()
     }
+
+    /// Get activities recorded by a specific request
+    pub fn by_request(&self, request_id: &str) -> Vec<&Activity> {
+        self.activities
+            .iter()
+            .filter(|a| a.request_id.as_deref() == Some(request_id))
+            .collect()
+    }
 }
 
 /// Log a new activity for a repository
@@ -110,6 +150,7 @@ pub fn log_activity(
     user: &str,
     message: &str,
     metadata: Option<serde_json::Value>,
+    request_id: Option<&str>,
 ) -> AppResult<Activity> {
     let mut log = ActivityLog::load(repo_path)?;
 
@@ -118,8 +159,9 @@ pub fn log_activity(
         activity_type: activity_type.clone(),
         user: user.to_string(),
         message: message.to_string(),
-        timestamp: Utc::now(),
+        timestamp: clock::now(),
         metadata,
+        request_id: request_id.map(|id| id.to_string()),
     };
 
     log.add(activity.clone());
@@ -139,6 +181,12 @@ pub fn get_activities(repo_path: &Path, limit: usize) -> AppResult<Vec<Activity>
     Ok(log.recent(limit).into_iter().cloned().collect())
 }
 
+/// Get the activities recorded by a request
+pub fn get_activities_for_request(repo_path: &Path, request_id: &str) -> AppResult<Vec<Activity>> {
+    let log = ActivityLog::load(repo_path)?;
+    Ok(log.by_request(request_id).into_iter().cloned().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,6 +207,7 @@ mod tests {
             "testuser",
             "Initial commit",
             None,
+            None,
         )
         .unwrap();
 
@@ -185,6 +234,7 @@ mod tests {
             "user1",
             "First commit",
             None,
+            None,
         )
         .unwrap();
 
@@ -196,6 +246,7 @@ mod tests {
             "user2",
             "Acquired lock",
             None,
+            None,
         )
         .unwrap();
 
@@ -207,6 +258,7 @@ mod tests {
             "user1",
             "Second commit",
             None,
+            None,
         )
         .unwrap();
 
@@ -235,12 +287,29 @@ mod tests {
             "testuser",
             "Added features",
             Some(metadata.clone()),
+            None,
         )
         .unwrap();
 
         assert_eq!(activity.metadata.unwrap(), metadata);
     }
 
+    #[test]
+    fn test_activities_by_request() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        std::fs::create_dir_all(repo_path.join(".oxen")).unwrap();
+
+        log_activity(repo_path, ActivityType::Push, "user", "Push", None, Some("req-1")).unwrap();
+        log_activity(repo_path, ActivityType::Pull, "user", "Pull", None, Some("req-2")).unwrap();
+        log_activity(repo_path, ActivityType::Commit, "user", "Commit", None, None).unwrap();
+
+        let found = get_activities_for_request(repo_path, "req-2").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].message, "Pull");
+        assert_eq!(found[0].request_id.as_deref(), Some("req-2"));
+    }
+
     #[test]
     fn test_limit_activities() {
         let temp_dir = TempDir::new().unwrap();
@@ -255,6 +324,7 @@ mod tests {
                 "user",
                 &format!("Commit {}", i),
                 None,
+                None,
             )
             .unwrap();
         }
@@ -280,9 +350,9 @@ mod tests {
         let repo_path = temp_dir.path();
         std::fs::create_dir_all(repo_path.join(".oxen")).unwrap();
 
-        log_activity(repo_path, ActivityType::Commit, "user", "Commit", None).unwrap();
-        log_activity(repo_path, ActivityType::LockAcquired, "user", "Lock", None).unwrap();
-        log_activity(repo_path, ActivityType::Commit, "user", "Commit 2", None).unwrap();
+        log_activity(repo_path, ActivityType::Commit, "user", "Commit", None, None).unwrap();
+        log_activity(repo_path, ActivityType::LockAcquired, "user", "Lock", None, None).unwrap();
+        log_activity(repo_path, ActivityType::Commit, "user", "Commit 2", None, None).unwrap();
 
         let log = ActivityLog::load(repo_path).unwrap();
         let commits = log.by_type(ActivityType::Commit);
@@ -295,12 +365,42 @@ mod tests {
         let repo_path = temp_dir.path();
         std::fs::create_dir_all(repo_path.join(".oxen")).unwrap();
 
-        log_activity(repo_path, ActivityType::Commit, "user1", "Commit 1", None).unwrap();
-        log_activity(repo_path, ActivityType::Commit, "user2", "Commit 2", None).unwrap();
-        log_activity(repo_path, ActivityType::Commit, "user1", "Commit 3", None).unwrap();
+        log_activity(repo_path, ActivityType::Commit, "user1", "Commit 1", None, None).unwrap();
+        log_activity(repo_path, ActivityType::Commit, "user2", "Commit 2", None, None).unwrap();
+        log_activity(repo_path, ActivityType::Commit, "user1", "Commit 3", None, None).unwrap();
 
         let log = ActivityLog::load(repo_path).unwrap();
         let user1_activities = log.by_user("user1");
         assert_eq!(user1_activities.len(), 2);
     }
+
+    #[test]
+    fn test_import_rejects_duplicate_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        std::fs::create_dir_all(repo_path.join(".oxen")).unwrap();
+
+        let existing =
+            log_activity(repo_path, ActivityType::Commit, "user1", "Commit", None, None).unwrap();
+        let comment = Activity {
+            id: "comment-1".to_string(),
+            activity_type: ActivityType::Comment,
+            user: "user2".to_string(),
+            message: "Love the chorus".to_string(),
+            timestamp: "2024-01-01T12:00:00Z".parse().unwrap(),
+            metadata: None,
+            request_id: None,
+        };
+
+        let mut log = ActivityLog::load(repo_path).unwrap();
+        assert!(log.import(vec![existing, comment.clone()]).is_err());
+        assert!(log.import(vec![comment.clone(), comment.clone()]).is_err());
+        assert_eq!(log.activities.len(), 1);
+
+        assert_eq!(log.import(vec![comment.clone()]).unwrap(), 1);
+        assert!(log.import(vec![comment]).is_err());
+        assert_eq!(log.activities.len(), 2);
+        let imported = log.by_type(ActivityType::Comment)[0];
+        assert_eq!(imported.timestamp.to_rfc3339(), "2024-01-01T12:00:00+00:00");
+    }
 }
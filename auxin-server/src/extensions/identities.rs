@@ -0,0 +1,209 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::clock;
+use crate::error::{AppError, AppResult};
+
+/// Held while identities.json is read, changed and written back, so
+/// concurrent presence pings don't drop each other's links
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A client identity, as locks and activity record it (`name@machine`),
+/// linked to the server account that used it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkedIdentity {
+    pub identity: String,
+    pub account: String,
+    /// The Oxen hub username the client is logged in with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hub_user: Option<String>,
+    pub machine_id: String,
+    pub linked_at: DateTime<Utc>,
+}
+
+/// Client identities by the accounts they belong to, in
+/// `.auxin/identities.json`
+///
+/// Locks, comments and activity record whatever name the client sends, so
+/// one person shows up as `sam@studio-mac`, `sam@laptop` and their hub
+/// username. Authenticated clients link each name to their account, which
+/// the audit log and lock stats show instead. A name stays with the account
+/// that linked it first; only an admin can move it to another.
+pub struct Identities;
+
+/// Loaded links, resolving recorded names to accounts
+#[derive(Debug, Clone, Default)]
+pub struct IdentityMap {
+    links: HashMap<String, LinkedIdentity>,
+}
+
+impl Identities {
+    /// Link `identity` to `account`, keeping a hub username recorded before
+    /// when none is given
+    ///
+    /// A name already linked to another account is only moved when
+    /// `relink` is set.
+    pub fn link(
+        sync_dir: &Path,
+        identity: &str,
+        account: &str,
+        hub_user: Option<&str>,
+        machine_id: &str,
+        relink: bool,
+    ) -> AppResult<LinkedIdentity> {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let io_error = |e: Error| AppError::Internal(format!("Failed to link identity: {}", e));
+
+        let mut links = Self::load(sync_dir).map_err(io_error)?.links;
+        if let Some(owner) = links.get(identity).map(|link| &link.account) {
+            if owner != account && !relink {
+                return Err(AppError::Forbidden(format!(
+                    "'{}' is linked to another account",
+                    identity
+                )));
+            }
+        }
+        let hub_user = hub_user.map(str::to_string).or_else(|| {
+            links
+                .get(identity)
+                .filter(|link| link.account == account)
+                .and_then(|link| link.hub_user.clone())
+        });
+        let link = LinkedIdentity {
+            identity: identity.to_string(),
+            account: account.to_string(),
+            hub_user,
+            machine_id: machine_id.to_string(),
+            linked_at: clock::now(),
+        };
+        // Presence pings link on every call; only changes are written
+        if let Some(old) = links
+            .get(identity)
+            .filter(|old| old.account == account && old.hub_user == link.hub_user)
+        {
+            return Ok(old.clone());
+        }
+        links.insert(identity.to_string(), link.clone());
+        Self::save(sync_dir, &links).map_err(io_error)?;
+        Ok(link)
+    }
+
+    /// Read the links; a file that doesn't parse is an error rather than
+    /// empty, so the next link doesn't overwrite it
+    pub fn load(sync_dir: &Path) -> Result<IdentityMap, Error> {
+        let path = Self::path(sync_dir);
+        let links = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{} is corrupt: {}", path.display(), e),
+                )
+            })?,
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(IdentityMap { links })
+    }
+
+    fn save(sync_dir: &Path, links: &HashMap<String, LinkedIdentity>) -> Result<(), Error> {
+        let path = Self::path(sync_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Written whole and renamed so a concurrent reader never sees half
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(links)?)?;
+        fs::rename(tmp, path)
+    }
+
+    fn path(sync_dir: &Path) -> PathBuf {
+        sync_dir.join(".auxin").join("identities.json")
+    }
+}
+
+impl IdentityMap {
+    /// The account a recorded name or hub username belongs to
+    pub fn account(&self, user: &str) -> Option<&str> {
+        self.links
+            .get(user)
+            .or_else(|| {
+                self.links
+                    .values()
+                    .find(|link| link.hub_user.as_deref() == Some(user))
+            })
+            .map(|link| link.account.as_str())
+    }
+
+    /// The account `user` belongs to, or `user` unchanged if unlinked
+    pub fn resolve(&self, user: &str) -> String {
+        self.account(user).unwrap_or(user).to_string()
+    }
+
+    /// Every name linked to `account`
+    pub fn identities_of(&self, account: &str) -> Vec<&LinkedIdentity> {
+        let mut links: Vec<&LinkedIdentity> =
+            self.links.values().filter(|link| link.account == account).collect();
+        links.sort_by(|a, b| a.identity.cmp(&b.identity));
+        links
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_linked_names_resolve_to_account() {
+        let temp_dir = TempDir::new().unwrap();
+        let sync_dir = temp_dir.path();
+
+        Identities::link(sync_dir, "sam@studio-mac", "sam", Some("samantha"), "studio-mac", false)
+            .unwrap();
+        // A presence ping without the hub username keeps it
+        let link = Identities::link(sync_dir, "sam@laptop", "sam", None, "laptop", false).unwrap();
+        assert_eq!(link.hub_user, None);
+        let link =
+            Identities::link(sync_dir, "sam@studio-mac", "sam", None, "studio-mac", false).unwrap();
+        assert_eq!(link.hub_user.as_deref(), Some("samantha"));
+
+        let map = Identities::load(sync_dir).unwrap();
+        assert_eq!(map.resolve("sam@laptop"), "sam");
+        assert_eq!(map.resolve("samantha"), "sam");
+        assert_eq!(map.resolve("alex@mixroom"), "alex@mixroom");
+        assert_eq!(map.identities_of("sam").len(), 2);
+    }
+
+    #[test]
+    fn test_name_of_another_account_needs_relink() {
+        let temp_dir = TempDir::new().unwrap();
+        let sync_dir = temp_dir.path();
+
+        Identities::link(sync_dir, "sam@studio-mac", "sam", None, "studio-mac", false).unwrap();
+        let err = Identities::link(sync_dir, "sam@studio-mac", "alex", None, "studio-mac", false)
+            .unwrap_err();
+        assert!(matches!(err, AppError::Forbidden(_)));
+        assert_eq!(Identities::load(sync_dir).unwrap().resolve("sam@studio-mac"), "sam");
+
+        Identities::link(sync_dir, "sam@studio-mac", "alex", None, "studio-mac", true).unwrap();
+        assert_eq!(Identities::load(sync_dir).unwrap().resolve("sam@studio-mac"), "alex");
+    }
+
+    #[test]
+    fn test_corrupt_file_is_not_overwritten() {
+        let temp_dir = TempDir::new().unwrap();
+        let sync_dir = temp_dir.path();
+        let path = sync_dir.join(".auxin").join("identities.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+
+        assert!(Identities::load(sync_dir).is_err());
+        assert!(Identities::link(sync_dir, "sam@laptop", "sam", None, "laptop", false).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+    }
+}
@@ -0,0 +1,297 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::extensions::{Activity, ActivityType, IdentityMap};
+
+/// Distribution of how long locks were held, in minutes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HoldTimes {
+    pub count: usize,
+    pub mean_minutes: f64,
+    pub p50_minutes: f64,
+    pub p90_minutes: f64,
+    pub p99_minutes: f64,
+    pub max_minutes: f64,
+}
+
+impl HoldTimes {
+    fn from_minutes(mut minutes: Vec<f64>) -> Self {
+        if minutes.is_empty() {
+            return Self::default();
+        }
+        minutes.sort_by(f64::total_cmp);
+        // Nearest-rank percentile
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * minutes.len() as f64).ceil() as usize;
+            minutes[rank.clamp(1, minutes.len()) - 1]
+        };
+        Self {
+            count: minutes.len(),
+            mean_minutes: minutes.iter().sum::<f64>() / minutes.len() as f64,
+            p50_minutes: percentile(50.0),
+            p90_minutes: percentile(90.0),
+            p99_minutes: percentile(99.0),
+            max_minutes: minutes[minutes.len() - 1],
+        }
+    }
+}
+
+/// One user's locking habits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserLockStats {
+    pub user: String,
+    pub holds: HoldTimes,
+    /// Locks that ran out instead of being released
+    pub expired: usize,
+    /// Times this user asked for a lock someone else held
+    pub blocked: usize,
+    /// Times someone asked for a lock this user held
+    pub blocking: usize,
+}
+
+/// Contention in one repository
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepoContention {
+    pub namespace: String,
+    pub name: String,
+    pub holds: usize,
+    /// Acquire attempts refused because the lock was held
+    pub contention_events: usize,
+    pub mean_hold_minutes: f64,
+}
+
+/// Lock hold times and contention over a period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockStats {
+    pub since: DateTime<Utc>,
+    pub holds: HoldTimes,
+    pub contention_events: usize,
+    pub expired: usize,
+    /// Busiest first
+    pub users: Vec<UserLockStats>,
+    /// Most contended first
+    pub repos: Vec<RepoContention>,
+}
+
+#[derive(Default)]
+struct UserTally {
+    minutes: Vec<f64>,
+    expired: usize,
+    blocked: usize,
+    blocking: usize,
+}
+
+/// Builds [`LockStats`] from repositories' activity logs
+///
+/// A hold runs from `lock_acquired` to the `lock_released` or `lock_expired`
+/// with the same `lock_id` (an expired lock ends at its expiry time, not when
+/// it was cleared). Holds still open are left out. A contention event is a
+/// `lock_contended` activity, logged when an acquire is refused.
+pub struct LockStatsBuilder {
+    since: DateTime<Utc>,
+    minutes: Vec<f64>,
+    contention_events: usize,
+    expired: usize,
+    users: HashMap<String, UserTally>,
+    repos: Vec<RepoContention>,
+    identities: IdentityMap,
+}
+
+impl LockStatsBuilder {
+    /// Count holds that ended, and contention that happened, after `since`
+    pub fn new(since: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            minutes: Vec::new(),
+            contention_events: 0,
+            expired: 0,
+            users: HashMap::new(),
+            repos: Vec::new(),
+            identities: IdentityMap::default(),
+        }
+    }
+
+    /// Count names linked to an account as that account
+    pub fn with_identities(mut self, identities: IdentityMap) -> Self {
+        self.identities = identities;
+        self
+    }
+
+    /// Add one repository's activity
+    pub fn add_repo(&mut self, namespace: &str, name: &str, activities: &[Activity]) {
+        let mut sorted: Vec<&Activity> = activities.iter().collect();
+        sorted.sort_by_key(|activity| activity.timestamp);
+
+        let mut open: HashMap<&str, &Activity> = HashMap::new();
+        let mut repo_minutes = Vec::new();
+        let mut contention_events = 0;
+        for activity in sorted {
+            let lock_id = metadata_str(activity, "lock_id");
+            match activity.activity_type {
+                ActivityType::LockAcquired => {
+                    if let Some(lock_id) = lock_id {
+                        open.insert(lock_id, activity);
+                    }
+                },
+                ActivityType::LockReleased | ActivityType::LockExpired => {
+                    let Some(acquired) = lock_id.and_then(|id| open.remove(id)) else {
+                        continue;
+                    };
+                    let expired = activity.activity_type == ActivityType::LockExpired;
+                    let ended = expired
+                        .then(|| metadata_str(activity, "expires_at"))
+                        .flatten()
+                        .and_then(|t| t.parse::<DateTime<Utc>>().ok())
+                        .unwrap_or(activity.timestamp)
+                        .max(acquired.timestamp);
+                    if ended < self.since {
+                        continue;
+                    }
+                    let minutes = (ended - acquired.timestamp).num_seconds() as f64 / 60.0;
+                    repo_minutes.push(minutes);
+                    let user = self.identities.resolve(&acquired.user);
+                    let tally = self.users.entry(user).or_default();
+                    tally.minutes.push(minutes);
+                    if expired {
+                        tally.expired += 1;
+                        self.expired += 1;
+                    }
+                },
+                ActivityType::LockContended if activity.timestamp >= self.since => {
+                    contention_events += 1;
+                    let user = self.identities.resolve(&activity.user);
+                    self.users.entry(user).or_default().blocked += 1;
+                    if let Some(holder) = metadata_str(activity, "holder") {
+                        let holder = self.identities.resolve(holder);
+                        self.users.entry(holder).or_default().blocking += 1;
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        if repo_minutes.is_empty() && contention_events == 0 {
+            return;
+        }
+        self.contention_events += contention_events;
+        self.repos.push(RepoContention {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            holds: repo_minutes.len(),
+            contention_events,
+            mean_hold_minutes: HoldTimes::from_minutes(repo_minutes.clone()).mean_minutes,
+        });
+        self.minutes.extend(repo_minutes);
+    }
+
+    pub fn finish(mut self) -> LockStats {
+        let mut users: Vec<UserLockStats> = self
+            .users
+            .into_iter()
+            .map(|(user, tally)| UserLockStats {
+                user,
+                holds: HoldTimes::from_minutes(tally.minutes),
+                expired: tally.expired,
+                blocked: tally.blocked,
+                blocking: tally.blocking,
+            })
+            .collect();
+        users.sort_by(|a, b| {
+            (b.holds.count, b.blocking)
+                .cmp(&(a.holds.count, a.blocking))
+                .then_with(|| a.user.cmp(&b.user))
+        });
+        self.repos.sort_by(|a, b| {
+            (b.contention_events, b.holds)
+                .cmp(&(a.contention_events, a.holds))
+                .then_with(|| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)))
+        });
+
+        LockStats {
+            since: self.since,
+            holds: HoldTimes::from_minutes(self.minutes),
+            contention_events: self.contention_events,
+            expired: self.expired,
+            users,
+            repos: self.repos,
+        }
+    }
+}
+
+fn metadata_str<'a>(activity: &'a Activity, key: &str) -> Option<&'a str> {
+    activity.metadata.as_ref()?.get(key)?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn activity(
+        activity_type: ActivityType,
+        user: &str,
+        at: DateTime<Utc>,
+        metadata: serde_json::Value,
+    ) -> Activity {
+        Activity {
+            id: uuid::Uuid::new_v4().to_string(),
+            activity_type,
+            user: user.to_string(),
+            message: String::new(),
+            timestamp: at,
+            metadata: Some(metadata),
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn test_hold_times_and_contention() {
+        let start = Utc::now() - Duration::days(1);
+        let at = |minutes: i64| start + Duration::minutes(minutes);
+        let id = |lock_id: &str| serde_json::json!({ "lock_id": lock_id });
+        let activities = vec![
+            activity(ActivityType::LockAcquired, "sam", at(0), id("a")),
+            activity(
+                ActivityType::LockContended,
+                "alex",
+                at(30),
+                serde_json::json!({ "holder": "sam" }),
+            ),
+            activity(ActivityType::LockReleased, "sam", at(60), id("a")),
+            activity(ActivityType::LockAcquired, "alex", at(61), id("b")),
+            // Cleared late by the sweeper; the hold ended at expiry
+            activity(
+                ActivityType::LockExpired,
+                "alex",
+                at(200),
+                serde_json::json!({ "lock_id": "b", "expires_at": at(181).to_rfc3339() }),
+            ),
+            activity(ActivityType::LockAcquired, "sam", at(300), id("c")),
+        ];
+
+        let mut builder = LockStatsBuilder::new(start - Duration::days(30));
+        builder.add_repo("studio", "track-3", &activities);
+        builder.add_repo("studio", "quiet", &[]);
+        let stats = builder.finish();
+
+        assert_eq!(stats.holds.count, 2);
+        assert_eq!(stats.holds.p50_minutes, 60.0);
+        assert_eq!(stats.holds.max_minutes, 120.0);
+        assert_eq!(stats.holds.mean_minutes, 90.0);
+        assert_eq!((stats.contention_events, stats.expired), (1, 1));
+        assert_eq!(stats.repos.len(), 1);
+        assert_eq!(stats.repos[0].contention_events, 1);
+
+        let sam = stats.users.iter().find(|u| u.user == "sam").unwrap();
+        assert_eq!((sam.holds.count, sam.blocking, sam.blocked), (1, 1, 0));
+        let alex = stats.users.iter().find(|u| u.user == "alex").unwrap();
+        assert_eq!((alex.expired, alex.blocked), (1, 1));
+
+        // Nothing before the window counts
+        let mut builder = LockStatsBuilder::new(at(100));
+        builder.add_repo("studio", "track-3", &activities);
+        let stats = builder.finish();
+        assert_eq!((stats.holds.count, stats.contention_events), (1, 0));
+    }
+}
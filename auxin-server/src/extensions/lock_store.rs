@@ -0,0 +1,107 @@
+use auxin_config::Config;
+
+use crate::error::AppResult;
+use crate::extensions::{FileLock, LockSnapshot};
+use crate::repo::RepositoryOps;
+
+#[cfg(feature = "redis-locks")]
+use crate::extensions::RedisLockManager;
+
+/// Lock backend for a repository, chosen from server configuration
+///
+/// File locks work for a single server (or several sharing one `sync_dir`);
+/// with the `redis-locks` feature and `enable_redis_locks`, locks live in
+/// Redis so server instances behind a load balancer agree on the holder.
+pub enum LockStore<'a> {
+    File(&'a RepositoryOps),
+    #[cfg(feature = "redis-locks")]
+    Redis {
+        manager: RedisLockManager,
+        repo_key: String,
+    },
+}
+
+impl<'a> LockStore<'a> {
+    #[cfg_attr(not(feature = "redis-locks"), allow(unused_variables))]
+    pub fn new(
+        config: &Config,
+        repo: &'a RepositoryOps,
+        namespace: &str,
+        repo_name: &str,
+    ) -> AppResult<Self> {
+        #[cfg(feature = "redis-locks")]
+        if config.server.enable_redis_locks {
+            return Ok(Self::Redis {
+                manager: RedisLockManager::new(&config.server.redis_url)?,
+                repo_key: format!("{}/{}", namespace, repo_name),
+            });
+        }
+
+        Ok(Self::File(repo))
+    }
+
+    pub fn acquire(&self, user: &str, machine_id: &str, timeout_hours: u64) -> AppResult<FileLock> {
+        match self {
+            Self::File(repo) => repo.acquire_lock(user, machine_id, timeout_hours),
+            #[cfg(feature = "redis-locks")]
+            Self::Redis { manager, repo_key } => {
+                manager.acquire(repo_key, user, machine_id, timeout_hours)
+            }
+        }
+    }
+
+    pub fn release(&self, lock_id: &str, fencing_token: Option<u64>) -> AppResult<()> {
+        match self {
+            Self::File(repo) => repo.release_lock(lock_id, fencing_token),
+            #[cfg(feature = "redis-locks")]
+            Self::Redis { manager, repo_key } => manager.release(repo_key, lock_id, fencing_token),
+        }
+    }
+
+    pub fn heartbeat(&self, lock_id: &str, fencing_token: Option<u64>) -> AppResult<FileLock> {
+        match self {
+            Self::File(repo) => repo.heartbeat_lock(lock_id, fencing_token),
+            #[cfg(feature = "redis-locks")]
+            Self::Redis { manager, repo_key } => {
+                manager.heartbeat(repo_key, lock_id, fencing_token)
+            }
+        }
+    }
+
+    pub fn status(&self) -> AppResult<Option<FileLock>> {
+        match self {
+            Self::File(repo) => repo.lock_status(),
+            #[cfg(feature = "redis-locks")]
+            Self::Redis { manager, repo_key } => manager.status(repo_key),
+        }
+    }
+
+    /// Remove the lock if it has expired, returning it
+    ///
+    /// Redis drops expired locks by itself, so there is never one to take.
+    pub fn take_expired(&self) -> AppResult<Option<FileLock>> {
+        match self {
+            Self::File(repo) => repo.take_expired_lock(),
+            #[cfg(feature = "redis-locks")]
+            Self::Redis { .. } => Ok(None),
+        }
+    }
+
+    /// The lock and fencing counter, for a backup
+    pub fn snapshot(&self) -> AppResult<LockSnapshot> {
+        match self {
+            Self::File(repo) => repo.lock_snapshot(),
+            #[cfg(feature = "redis-locks")]
+            Self::Redis { manager, repo_key } => manager.snapshot(repo_key),
+        }
+    }
+
+    /// Put back the lock and fencing counter from a backup
+    pub fn restore(&self, snapshot: &LockSnapshot) -> AppResult<()> {
+        match self {
+            Self::File(repo) => repo.restore_lock_snapshot(snapshot),
+            #[cfg(feature = "redis-locks")]
+            Self::Redis { manager, repo_key } => manager.restore(repo_key, snapshot),
+        }
+    }
+}
@@ -1,9 +1,33 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, ErrorKind};
 use std::path::{Path, PathBuf};
+use std::time::{Duration as StdDuration, SystemTime};
+
+use crate::clock;
+
+/// Attempts to take the acquisition guard before giving up
+const GUARD_RETRIES: u32 = 50;
+
+/// Delay between guard attempts
+const GUARD_RETRY_DELAY: StdDuration = StdDuration::from_millis(20);
+
+/// Guards older than this were left behind by a crashed server instance
+const GUARD_STALE_AFTER: StdDuration = StdDuration::from_secs(10);
+
+/// Temporary files older than this were left behind by an interrupted write
+const TEMP_STALE_AFTER: StdDuration = StdDuration::from_secs(3600);
 
 /// File-based distributed lock
+///
+/// Safe across several server instances sharing the same `sync_dir`: every
+/// read-modify-write of the lock file happens under an exclusive guard file
+/// created with `O_EXCL`, and the lock file itself is replaced atomically.
+///
+/// Each acquisition is issued a fencing token that strictly increases per
+/// repository. Clients pass it back on release/heartbeat so that a client
+/// whose lock expired and was taken over can't act on the new holder's lock.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileLock {
     pub lock_id: String,
@@ -12,6 +36,9 @@ pub struct FileLock {
     pub acquired_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
     pub last_heartbeat: DateTime<Utc>,
+    /// Monotonic per-repository token issued on acquisition
+    #[serde(default)]
+    pub fencing_token: u64,
 }
 
 impl FileLock {
@@ -21,8 +48,9 @@ impl FileLock {
         user: impl Into<String>,
         machine_id: impl Into<String>,
         timeout_hours: u64,
-    ) -> Result<Self, std::io::Error> {
-        let lock_path = repo_path.join(".oxen/locks/project.lock");
+    ) -> Result<Self, Error> {
+        let _guard = AcquireGuard::take(repo_path)?;
+        let lock_path = Self::lock_path(repo_path);
 
         // Check if lock already exists
         if lock_path.exists() {
@@ -33,8 +61,8 @@ impl FileLock {
                 // Lock expired, can acquire
                 fs::remove_file(&lock_path)?;
             } else {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::AlreadyExists,
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
                     format!(
                         "Lock held by {} until {}",
                         existing.user, existing.expires_at
@@ -43,7 +71,7 @@ impl FileLock {
             }
         }
 
-        let now = Utc::now();
+        let now = clock::now();
         let lock = Self {
             lock_id: uuid::Uuid::new_v4().to_string(),
             user: user.into(),
@@ -51,6 +79,7 @@ impl FileLock {
             acquired_at: now,
             expires_at: now + Duration::hours(timeout_hours as i64),
             last_heartbeat: now,
+            fencing_token: Self::next_fencing_token(repo_path)?,
         };
 
         lock.write_to_file(&lock_path)?;
@@ -58,8 +87,15 @@ impl FileLock {
     }
 
     /// Release a lock
-    pub fn release(repo_path: &Path, lock_id: &str) -> Result<(), std::io::Error> {
-        let lock_path = repo_path.join(".oxen/locks/project.lock");
+    ///
+    /// When `fencing_token` is given it must match the current holder's token.
+    pub fn release(
+        repo_path: &Path,
+        lock_id: &str,
+        fencing_token: Option<u64>,
+    ) -> Result<(), Error> {
+        let _guard = AcquireGuard::take(repo_path)?;
+        let lock_path = Self::lock_path(repo_path);
 
         if !lock_path.exists() {
             return Ok(()); // Already released
@@ -68,45 +104,55 @@ impl FileLock {
         let existing = Self::read_from_file(&lock_path)?;
 
         if existing.lock_id != lock_id {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::PermissionDenied,
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
                 "Cannot release lock owned by different user",
             ));
         }
+        existing.check_fencing_token(fencing_token)?;
 
         fs::remove_file(&lock_path)?;
         Ok(())
     }
 
     /// Update heartbeat for a lock
-    pub fn heartbeat(repo_path: &Path, lock_id: &str) -> Result<Self, std::io::Error> {
-        let lock_path = repo_path.join(".oxen/locks/project.lock");
+    ///
+    /// When `fencing_token` is given it must match the current holder's token.
+    pub fn heartbeat(
+        repo_path: &Path,
+        lock_id: &str,
+        fencing_token: Option<u64>,
+    ) -> Result<Self, Error> {
+        let _guard = AcquireGuard::take(repo_path)?;
+        let lock_path = Self::lock_path(repo_path);
 
         if !lock_path.exists() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "Lock not found",
-            ));
+            return Err(Error::new(ErrorKind::NotFound, "Lock not found"));
         }
 
         let mut lock = Self::read_from_file(&lock_path)?;
 
         if lock.lock_id != lock_id {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::PermissionDenied,
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
                 "Cannot update heartbeat for lock owned by different user",
             ));
         }
+        lock.check_fencing_token(fencing_token)?;
+        // An expired lock is up for grabs; a heartbeat can't revive it
+        if lock.is_expired() {
+            return Err(Error::new(ErrorKind::NotFound, "Lock not found"));
+        }
 
-        lock.last_heartbeat = Utc::now();
+        lock.renew(clock::now());
         lock.write_to_file(&lock_path)?;
 
         Ok(lock)
     }
 
     /// Get current lock status
-    pub fn status(repo_path: &Path) -> Result<Option<Self>, std::io::Error> {
-        let lock_path = repo_path.join(".oxen/locks/project.lock");
+    pub fn status(repo_path: &Path) -> Result<Option<Self>, Error> {
+        let lock_path = Self::lock_path(repo_path);
 
         if !lock_path.exists() {
             return Ok(None);
@@ -115,34 +161,301 @@ impl FileLock {
         let lock = Self::read_from_file(&lock_path)?;
 
         if lock.is_expired() {
-            fs::remove_file(&lock_path)?;
+            // Re-check under the guard: another instance may have replaced it
+            let _guard = AcquireGuard::take(repo_path)?;
+            if Self::read_from_file(&lock_path).is_ok_and(|current| current.is_expired()) {
+                fs::remove_file(&lock_path)?;
+            }
+            return Ok(None);
+        }
+
+        Ok(Some(lock))
+    }
+
+    /// Remove the lock if it has expired, returning it so its holder can be
+    /// told and the expiry recorded
+    pub fn take_expired(repo_path: &Path) -> Result<Option<Self>, Error> {
+        let lock_path = Self::lock_path(repo_path);
+        if !lock_path.exists() {
+            return Ok(None);
+        }
+
+        let _guard = AcquireGuard::take(repo_path)?;
+        match Self::read_from_file(&lock_path) {
+            Ok(lock) if lock.is_expired() => {
+                fs::remove_file(&lock_path)?;
+                Ok(Some(lock))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// The lock and the last fencing token issued, read together under the
+    /// guard (for backups); an expired lock is left out
+    pub fn snapshot(repo_path: &Path) -> Result<LockSnapshot, Error> {
+        let _guard = AcquireGuard::take(repo_path)?;
+        let lock_path = Self::lock_path(repo_path);
+
+        let lock = if lock_path.exists() {
+            Some(Self::read_from_file(&lock_path)?).filter(|lock| !lock.is_expired())
+        } else {
+            None
+        };
+
+        Ok(LockSnapshot {
+            lock,
+            fencing_token: Self::last_fencing_token(repo_path)?,
+        })
+    }
+
+    /// Replace the lock with a snapshot's
+    ///
+    /// The fencing counter only moves forward, so tokens issued since the
+    /// snapshot was taken are never handed out again.
+    pub fn restore_snapshot(repo_path: &Path, snapshot: &LockSnapshot) -> Result<(), Error> {
+        let _guard = AcquireGuard::take(repo_path)?;
+        let lock_path = Self::lock_path(repo_path);
+
+        let counter = Self::last_fencing_token(repo_path)?.max(snapshot.fencing_token);
+        write_atomically(&Self::counter_path(repo_path), counter.to_string().as_bytes())?;
+
+        match snapshot.lock.as_ref().filter(|lock| !lock.is_expired()) {
+            Some(lock) => lock.write_to_file(&lock_path)?,
+            None if lock_path.exists() => fs::remove_file(&lock_path)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Clear what crashed writers and expired holders left in the locks
+    /// folder: expired locks, stale guards and abandoned temporary files
+    pub fn collect_garbage(repo_path: &Path, dry_run: bool) -> Result<LockGarbage, Error> {
+        let mut garbage = LockGarbage::default();
+        let Ok(entries) = fs::read_dir(Self::locks_dir(repo_path)) else {
+            return Ok(garbage);
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == "acquire.guard" {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                if AcquireGuard::reclaim_if_stale(&path, dry_run)? {
+                    garbage.stale_guards += 1;
+                    garbage.bytes += size;
+                }
+                continue;
+            } else if name.contains(".tmp-") && older_than(&path, TEMP_STALE_AFTER) {
+                garbage.temp_files += 1;
+            } else {
+                continue;
+            }
+            garbage.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if !dry_run {
+                let _ = fs::remove_file(&path);
+            }
+        }
+
+        let lock_path = Self::lock_path(repo_path);
+        if Self::read_from_file(&lock_path).is_ok_and(|lock| lock.is_expired()) {
+            garbage.expired_locks += 1;
+            if !dry_run {
+                // Removes the expired lock under the guard
+                Self::status(repo_path)?;
+            }
+        }
+        Ok(garbage)
+    }
+
+    /// Mark the current lock as already expired, keeping its holder and token
+    ///
+    /// Used by mock mode to exercise takeover paths without waiting out the
+    /// timeout. Returns the expired lock, or `None` if nothing was held.
+    pub fn force_expire(repo_path: &Path) -> Result<Option<Self>, Error> {
+        let _guard = AcquireGuard::take(repo_path)?;
+        let lock_path = Self::lock_path(repo_path);
+
+        if !lock_path.exists() {
             return Ok(None);
         }
 
+        let mut lock = Self::read_from_file(&lock_path)?;
+        lock.expires_at = clock::now() - Duration::seconds(1);
+        lock.write_to_file(&lock_path)?;
+
         Ok(Some(lock))
     }
 
-    fn is_expired(&self) -> bool {
-        Utc::now() > self.expires_at
+    pub(crate) fn is_expired(&self) -> bool {
+        clock::now() > self.expires_at
+    }
+
+    /// Record a heartbeat at `now` and push the expiry out by the lock's
+    /// timeout from there
+    pub(crate) fn renew(&mut self, now: DateTime<Utc>) {
+        let timeout = self.expires_at - self.last_heartbeat;
+        self.last_heartbeat = now;
+        self.expires_at = now + timeout;
+    }
+
+    /// Reject tokens from a previous holder of the lock
+    pub(crate) fn check_fencing_token(&self, fencing_token: Option<u64>) -> Result<(), Error> {
+        match fencing_token {
+            Some(token) if token != self.fencing_token => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Stale fencing token {} (current holder has {})",
+                    token, self.fencing_token
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn locks_dir(repo_path: &Path) -> PathBuf {
+        repo_path.join(".oxen/locks")
+    }
+
+    fn lock_path(repo_path: &Path) -> PathBuf {
+        Self::locks_dir(repo_path).join("project.lock")
+    }
+
+    fn counter_path(repo_path: &Path) -> PathBuf {
+        Self::locks_dir(repo_path).join("fencing_token")
+    }
+
+    /// The last fencing token issued, 0 if none (caller must hold the guard)
+    fn last_fencing_token(repo_path: &Path) -> Result<u64, Error> {
+        match fs::read_to_string(Self::counter_path(repo_path)) {
+            Ok(content) => content
+                .trim()
+                .parse::<u64>()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Issue the next fencing token (caller must hold the guard)
+    fn next_fencing_token(repo_path: &Path) -> Result<u64, Error> {
+        let next = Self::last_fencing_token(repo_path)? + 1;
+        write_atomically(&Self::counter_path(repo_path), next.to_string().as_bytes())?;
+        Ok(next)
     }
 
-    fn read_from_file(path: &PathBuf) -> Result<Self, std::io::Error> {
+    fn read_from_file(path: &Path) -> Result<Self, Error> {
         let content = fs::read_to_string(path)?;
-        serde_json::from_str(&content)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+        serde_json::from_str(&content).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
+    }
+
+    fn write_to_file(&self, path: &Path) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| Error::other(e.to_string()))?;
+        write_atomically(path, content.as_bytes())
+    }
+}
+
+/// A repository's lock state at one moment, as kept in a backup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LockSnapshot {
+    /// The current holder, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<FileLock>,
+    /// The last fencing token issued
+    #[serde(default)]
+    pub fencing_token: u64,
+}
+
+/// What lock housekeeping removed (or, for a dry run, would remove)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockGarbage {
+    pub expired_locks: usize,
+    pub stale_guards: usize,
+    pub temp_files: usize,
+    pub bytes: u64,
+}
+
+/// Write via a temporary file and rename so readers never see partial content
+fn write_atomically(path: &Path, content: &[u8]) -> Result<(), Error> {
+    // Ensure directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
     }
 
-    fn write_to_file(&self, path: &PathBuf) -> Result<(), std::io::Error> {
-        let content = serde_json::to_string_pretty(self)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+    let tmp_path = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Exclusive guard serializing lock changes across server instances
+///
+/// Removed on drop; guards left behind by a crashed instance are reclaimed
+/// once they're older than [`GUARD_STALE_AFTER`]. Reclaiming happens under
+/// an OS lock on `acquire.reclaim`, so two instances can't both judge the
+/// same guard stale and have one delete the guard the other just created.
+struct AcquireGuard {
+    path: PathBuf,
+}
 
-        // Ensure directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
+impl AcquireGuard {
+    fn take(repo_path: &Path) -> Result<Self, Error> {
+        let locks_dir = FileLock::locks_dir(repo_path);
+        fs::create_dir_all(&locks_dir)?;
+        let path = locks_dir.join("acquire.guard");
+
+        for _ in 0..GUARD_RETRIES {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Self { path }),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if !Self::reclaim_if_stale(&path, false)? {
+                        std::thread::sleep(GUARD_RETRY_DELAY);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
         }
 
-        fs::write(path, content)?;
-        Ok(())
+        Err(Error::new(
+            ErrorKind::WouldBlock,
+            "Lock is busy, try again shortly",
+        ))
+    }
+
+    fn is_stale(path: &Path) -> bool {
+        older_than(path, GUARD_STALE_AFTER)
+    }
+
+    /// Whether the guard at `path` is stale, removing it if so (unless
+    /// `dry_run`); checked and removed while holding the reclaim lock
+    fn reclaim_if_stale(path: &Path, dry_run: bool) -> Result<bool, Error> {
+        let reclaim = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path.with_file_name("acquire.reclaim"))?;
+        reclaim.lock()?;
+        let stale = Self::is_stale(path);
+        if stale && !dry_run {
+            match fs::remove_file(path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        reclaim.unlock()?;
+        Ok(stale)
+    }
+}
+
+fn older_than(path: &Path, age: StdDuration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|elapsed| elapsed > age)
+}
+
+impl Drop for AcquireGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
     }
 }
 
@@ -185,7 +498,7 @@ mod tests {
         let repo_path = temp_dir.path();
 
         let lock = FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
-        FileLock::release(repo_path, &lock.lock_id).unwrap();
+        FileLock::release(repo_path, &lock.lock_id, None).unwrap();
 
         // Lock should be released, can acquire again
         let lock2 = FileLock::acquire(repo_path, "user2", "machine2", 1).unwrap();
@@ -200,7 +513,7 @@ mod tests {
         FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
 
         // Try to release with wrong lock ID
-        let result = FileLock::release(repo_path, "wrong_id");
+        let result = FileLock::release(repo_path, "wrong_id", None);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().kind(),
@@ -214,7 +527,7 @@ mod tests {
         let repo_path = temp_dir.path();
 
         // Releasing non-existent lock should succeed
-        let result = FileLock::release(repo_path, "any_id");
+        let result = FileLock::release(repo_path, "any_id", None);
         assert!(result.is_ok());
     }
 
@@ -229,8 +542,30 @@ mod tests {
         // Sleep briefly to ensure timestamp changes
         std::thread::sleep(std::time::Duration::from_millis(10));
 
-        let updated = FileLock::heartbeat(repo_path, &lock.lock_id).unwrap();
+        let updated = FileLock::heartbeat(repo_path, &lock.lock_id, None).unwrap();
         assert!(updated.last_heartbeat > old_heartbeat);
+        assert!(updated.expires_at > lock.expires_at);
+    }
+
+    #[test]
+    fn test_renew_outlives_original_timeout() {
+        let start = Utc::now();
+        let mut lock = FileLock {
+            lock_id: "id".to_string(),
+            user: "u".to_string(),
+            machine_id: "m".to_string(),
+            acquired_at: start,
+            expires_at: start + Duration::hours(1),
+            last_heartbeat: start,
+            fencing_token: 1,
+        };
+
+        lock.renew(start + Duration::minutes(50));
+        assert_eq!(lock.expires_at, start + Duration::minutes(110));
+        // Past the original expiry, still held
+        lock.renew(start + Duration::minutes(100));
+        assert_eq!(lock.expires_at, start + Duration::minutes(160));
+        assert_eq!(lock.acquired_at, start);
     }
 
     #[test]
@@ -240,7 +575,7 @@ mod tests {
 
         FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
 
-        let result = FileLock::heartbeat(repo_path, "wrong_id");
+        let result = FileLock::heartbeat(repo_path, "wrong_id", None);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().kind(),
@@ -253,7 +588,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let repo_path = temp_dir.path();
 
-        let result = FileLock::heartbeat(repo_path, "any_id");
+        let result = FileLock::heartbeat(repo_path, "any_id", None);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::NotFound);
     }
@@ -276,7 +611,7 @@ mod tests {
         assert_eq!(status.unwrap().lock_id, lock.lock_id);
 
         // Release lock
-        FileLock::release(repo_path, &lock.lock_id).unwrap();
+        FileLock::release(repo_path, &lock.lock_id, None).unwrap();
 
         // Status should be None again
         let status = FileLock::status(repo_path).unwrap();
@@ -290,7 +625,7 @@ mod tests {
         let lock_path = repo_path.join(".oxen/locks/project.lock");
 
         // Create an expired lock manually
-        let now = Utc::now();
+        let now = clock::now();
         let expired_lock = FileLock {
             lock_id: uuid::Uuid::new_v4().to_string(),
             user: "user1".to_string(),
@@ -298,6 +633,7 @@ mod tests {
             acquired_at: now - Duration::hours(2),
             expires_at: now - Duration::hours(1), // Expired 1 hour ago
             last_heartbeat: now - Duration::hours(1),
+            fencing_token: 1,
         };
 
         expired_lock.write_to_file(&lock_path).unwrap();
@@ -311,9 +647,24 @@ mod tests {
         assert_eq!(lock.user, "user2");
     }
 
+    #[test]
+    fn test_take_expired_returns_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        let lock = FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
+        assert!(FileLock::take_expired(repo_path).unwrap().is_none());
+        assert!(FileLock::status(repo_path).unwrap().is_some());
+
+        FileLock::force_expire(repo_path).unwrap();
+        let expired = FileLock::take_expired(repo_path).unwrap().unwrap();
+        assert_eq!(expired.lock_id, lock.lock_id);
+        assert!(FileLock::take_expired(repo_path).unwrap().is_none());
+    }
+
     #[test]
     fn test_lock_serialization() {
-        let now = Utc::now();
+        let now = clock::now();
         let lock = FileLock {
             lock_id: "test-id".to_string(),
             user: "testuser".to_string(),
@@ -321,6 +672,7 @@ mod tests {
             acquired_at: now,
             expires_at: now + Duration::hours(1),
             last_heartbeat: now,
+            fencing_token: 7,
         };
 
         let json = serde_json::to_string(&lock).unwrap();
@@ -330,4 +682,141 @@ mod tests {
         assert_eq!(lock.user, deserialized.user);
         assert_eq!(lock.machine_id, deserialized.machine_id);
     }
+
+    #[test]
+    fn test_fencing_tokens_increase() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        let first = FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
+        FileLock::release(repo_path, &first.lock_id, Some(first.fencing_token)).unwrap();
+        let second = FileLock::acquire(repo_path, "user2", "machine2", 1).unwrap();
+
+        assert!(first.fencing_token > 0);
+        assert!(second.fencing_token > first.fencing_token);
+    }
+
+    #[test]
+    fn test_stale_fencing_token_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        let lock = FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
+        let stale = Some(lock.fencing_token - 1);
+
+        let result = FileLock::heartbeat(repo_path, &lock.lock_id, stale);
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+        let result = FileLock::release(repo_path, &lock.lock_id, stale);
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+        // Still held by the original owner
+        assert!(FileLock::status(repo_path).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_stale_guard_is_reclaimed() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        let guard_path = repo_path.join(".oxen/locks/acquire.guard");
+
+        // Guard left behind by a crashed instance
+        fs::create_dir_all(guard_path.parent().unwrap()).unwrap();
+        let file = fs::File::create(&guard_path).unwrap();
+        file.set_modified(SystemTime::now() - StdDuration::from_secs(60))
+            .unwrap();
+
+        let lock = FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
+        assert_eq!(lock.user, "user1");
+        assert!(!guard_path.exists());
+    }
+
+    #[test]
+    fn test_stale_guard_reclaimed_by_one_instance_at_a_time() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+        let guard_path = repo_path.join(".oxen/locks/acquire.guard");
+        fs::create_dir_all(guard_path.parent().unwrap()).unwrap();
+        let file = fs::File::create(&guard_path).unwrap();
+        file.set_modified(SystemTime::now() - StdDuration::from_secs(60))
+            .unwrap();
+
+        // Every instance sees the same stale guard; none may delete the
+        // fresh guard another one created after reclaiming it
+        let inside = Arc::new(AtomicUsize::new(0));
+        let overlaps = Arc::new(AtomicUsize::new(0));
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let (repo_path, inside, overlaps) =
+                    (repo_path.clone(), inside.clone(), overlaps.clone());
+                std::thread::spawn(move || {
+                    let _guard = AcquireGuard::take(&repo_path).unwrap();
+                    if inside.fetch_add(1, Ordering::SeqCst) > 0 {
+                        overlaps.fetch_add(1, Ordering::SeqCst);
+                    }
+                    std::thread::sleep(StdDuration::from_millis(5));
+                    inside.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(overlaps.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_force_expire_allows_takeover() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        assert!(FileLock::force_expire(repo_path).unwrap().is_none());
+
+        let lock = FileLock::acquire(repo_path, "user1", "machine1", 8).unwrap();
+        let expired = FileLock::force_expire(repo_path).unwrap().unwrap();
+        assert_eq!(expired.lock_id, lock.lock_id);
+        assert!(expired.is_expired());
+
+        let lock2 = FileLock::acquire(repo_path, "user2", "machine2", 1).unwrap();
+        assert!(lock2.fencing_token > lock.fencing_token);
+    }
+
+    #[test]
+    fn test_restore_snapshot_keeps_tokens_increasing() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        let lock = FileLock::acquire(repo_path, "user1", "machine1", 1).unwrap();
+        let snapshot = FileLock::snapshot(repo_path).unwrap();
+        assert_eq!(snapshot.fencing_token, lock.fencing_token);
+
+        // Tokens issued after the snapshot must not be reissued
+        FileLock::release(repo_path, &lock.lock_id, None).unwrap();
+        let later = FileLock::acquire(repo_path, "user2", "machine2", 1).unwrap();
+        FileLock::restore_snapshot(repo_path, &snapshot).unwrap();
+
+        let restored = FileLock::status(repo_path).unwrap().unwrap();
+        assert_eq!(restored.lock_id, lock.lock_id);
+        FileLock::release(repo_path, &lock.lock_id, Some(lock.fencing_token)).unwrap();
+        let next = FileLock::acquire(repo_path, "user3", "machine3", 1).unwrap();
+        assert!(next.fencing_token > later.fencing_token);
+    }
+
+    #[test]
+    fn test_lock_without_fencing_token_deserializes() {
+        let json = r#"{
+            "lock_id": "old",
+            "user": "u",
+            "machine_id": "m",
+            "acquired_at": "2025-01-01T00:00:00Z",
+            "expires_at": "2025-01-02T00:00:00Z",
+            "last_heartbeat": "2025-01-01T00:00:00Z"
+        }"#;
+
+        let lock: FileLock = serde_json::from_str(json).unwrap();
+        assert_eq!(lock.fencing_token, 0);
+    }
 }
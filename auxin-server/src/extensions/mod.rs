@@ -2,9 +2,27 @@
 // This module contains Logic Pro metadata support, distributed locking, activity logging, etc.
 
 pub mod activity;
+pub mod identities;
+pub mod lock_stats;
+pub mod lock_store;
 pub mod locks;
 pub mod metadata;
+pub mod presence;
+#[cfg(feature = "redis-locks")]
+pub mod redis_locks;
+pub mod search;
+pub mod waitlist;
 
-pub use activity::{get_activities, log_activity, Activity, ActivityLog, ActivityType};
-pub use locks::FileLock;
+pub use activity::{
+    get_activities, get_activities_for_request, log_activity, Activity, ActivityLog, ActivityType,
+};
+pub use identities::{Identities, IdentityMap, LinkedIdentity};
+pub use lock_stats::{HoldTimes, LockStats, LockStatsBuilder, RepoContention, UserLockStats};
+pub use lock_store::LockStore;
+pub use locks::{FileLock, LockGarbage, LockSnapshot};
 pub use metadata::LogicProMetadata;
+pub use presence::{HolderPresence, MachinePresence, Presence};
+#[cfg(feature = "redis-locks")]
+pub use redis_locks::RedisLockManager;
+pub use search::{MetadataIndex, SearchHit, SearchQuery};
+pub use waitlist::{WaitingUser, Waitlist};
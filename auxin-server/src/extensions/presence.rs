@@ -0,0 +1,148 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+
+use crate::clock;
+use crate::extensions::FileLock;
+
+/// A machine whose daemon last reported in at `last_seen`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MachinePresence {
+    pub machine_id: String,
+    pub user: String,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Whether a lock's holder still looks alive
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HolderPresence {
+    /// Last presence ping from the holder's daemon, if it has one
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Neither the lock nor the holder's daemon has been heard from
+    pub orphaned: bool,
+}
+
+/// Daemon presence pings, by machine, in `.auxin/presence.json`
+///
+/// The background daemon pings every few minutes while it runs, so a machine
+/// that crashed or lost power goes quiet even though its locks, which only
+/// expire after hours, stay held.
+pub struct Presence;
+
+impl Presence {
+    /// Record a ping from `machine_id`'s daemon
+    pub fn record(sync_dir: &Path, machine_id: &str, user: &str) -> Result<MachinePresence, Error> {
+        let mut machines = Self::load(sync_dir)?;
+        let presence = MachinePresence {
+            machine_id: machine_id.to_string(),
+            user: user.to_string(),
+            last_seen: clock::now(),
+        };
+        machines.insert(machine_id.to_string(), presence.clone());
+        Self::save(sync_dir, &machines)?;
+        Ok(presence)
+    }
+
+    /// The last ping from `machine_id`'s daemon
+    pub fn get(sync_dir: &Path, machine_id: &str) -> Result<Option<MachinePresence>, Error> {
+        Ok(Self::load(sync_dir)?.remove(machine_id))
+    }
+
+    /// Whether `lock` is probably orphaned: the holder's daemon was running
+    /// when the lock was taken, and neither it nor the lock's heartbeat has
+    /// been heard from for `orphan_minutes`
+    ///
+    /// A holder without a daemon is never called orphaned; there's nothing
+    /// to tell a crashed machine from one that never pinged.
+    pub fn of_holder(
+        sync_dir: &Path,
+        lock: &FileLock,
+        orphan_minutes: i64,
+    ) -> Result<HolderPresence, Error> {
+        let last_seen = Self::get(sync_dir, &lock.machine_id)?.map(|p| p.last_seen);
+        let quiet = Duration::minutes(orphan_minutes);
+        let now = clock::now();
+        let orphaned = orphan_minutes > 0
+            && last_seen.is_some_and(|seen| {
+                seen >= lock.acquired_at - quiet && now - seen >= quiet
+            })
+            && now - lock.last_heartbeat >= quiet;
+        Ok(HolderPresence {
+            last_seen,
+            orphaned,
+        })
+    }
+
+    fn load(sync_dir: &Path) -> Result<HashMap<String, MachinePresence>, Error> {
+        match fs::read_to_string(Self::path(sync_dir)) {
+            Ok(content) => Ok(serde_json::from_str(&content).unwrap_or_default()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(sync_dir: &Path, machines: &HashMap<String, MachinePresence>) -> Result<(), Error> {
+        let path = Self::path(sync_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Written whole and renamed so a concurrent reader never sees half
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(machines)?)?;
+        fs::rename(tmp, path)
+    }
+
+    fn path(sync_dir: &Path) -> PathBuf {
+        sync_dir.join(".auxin").join("presence.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_holder_orphaned_once_daemon_and_heartbeat_go_quiet() {
+        let temp_dir = TempDir::new().unwrap();
+        let sync_dir = temp_dir.path();
+        let now = clock::now();
+        let mut lock = FileLock {
+            lock_id: "a".to_string(),
+            user: "sam@studio".to_string(),
+            machine_id: "studio".to_string(),
+            acquired_at: now - Duration::hours(2),
+            expires_at: now + Duration::hours(2),
+            last_heartbeat: now - Duration::hours(1),
+            fencing_token: 1,
+        };
+
+        // No daemon on the holder's machine: can't tell
+        let holder = Presence::of_holder(sync_dir, &lock, 15).unwrap();
+        assert_eq!(holder, HolderPresence::default());
+
+        // The daemon pinged just now: online
+        Presence::record(sync_dir, "studio", "sam").unwrap();
+        assert!(!Presence::of_holder(sync_dir, &lock, 15).unwrap().orphaned);
+
+        // The daemon went quiet an hour ago
+        let mut machines = Presence::load(sync_dir).unwrap();
+        machines.get_mut("studio").unwrap().last_seen = now - Duration::hours(1);
+        Presence::save(sync_dir, &machines).unwrap();
+        let holder = Presence::of_holder(sync_dir, &lock, 15).unwrap();
+        assert!(holder.orphaned);
+        assert!(!Presence::of_holder(sync_dir, &lock, 0).unwrap().orphaned);
+
+        // ...but the lock is still heartbeating
+        lock.last_heartbeat = now;
+        assert!(!Presence::of_holder(sync_dir, &lock, 15).unwrap().orphaned);
+
+        // A daemon that stopped long before the lock was taken says nothing
+        lock.last_heartbeat = now - Duration::hours(1);
+        lock.acquired_at = now - Duration::minutes(30);
+        assert!(!Presence::of_holder(sync_dir, &lock, 15).unwrap().orphaned);
+    }
+}
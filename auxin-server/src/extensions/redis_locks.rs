@@ -0,0 +1,377 @@
+use chrono::{Duration, Utc};
+use redis::{Client, Connection, Script};
+
+use crate::error::{AppError, AppResult};
+use crate::extensions::{FileLock, LockSnapshot};
+
+/// Atomically create the lock if absent and issue the next fencing token
+///
+/// KEYS: lock hash, fencing counter. ARGV: lock_id, lock JSON, TTL in ms.
+const ACQUIRE_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[1]) == 1 then
+    return false
+end
+local token = redis.call('INCR', KEYS[2])
+redis.call('HSET', KEYS[1], 'lock_id', ARGV[1], 'fencing_token', token, 'data', ARGV[2])
+redis.call('PEXPIRE', KEYS[1], ARGV[3])
+return token
+";
+
+/// Delete the lock only if lock_id (and fencing token, if given) match
+///
+/// Returns 1 released, 0 not held, -1 wrong lock_id, -2 stale token.
+const RELEASE_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return 0
+end
+if redis.call('HGET', KEYS[1], 'lock_id') ~= ARGV[1] then
+    return -1
+end
+if ARGV[2] ~= '' and redis.call('HGET', KEYS[1], 'fencing_token') ~= ARGV[2] then
+    return -2
+end
+redis.call('DEL', KEYS[1])
+return 1
+";
+
+/// Record a heartbeat and extend the lock if lock_id (and fencing token, if
+/// given) match
+///
+/// KEYS: lock hash. ARGV: lock_id, fencing token, heartbeat time, new
+/// expiry, new TTL in ms. Returns {status, data, token} with status as in
+/// RELEASE_SCRIPT.
+const HEARTBEAT_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return {0, '', 0}
+end
+if redis.call('HGET', KEYS[1], 'lock_id') ~= ARGV[1] then
+    return {-1, '', 0}
+end
+local token = redis.call('HGET', KEYS[1], 'fencing_token')
+if ARGV[2] ~= '' and token ~= ARGV[2] then
+    return {-2, '', tonumber(token)}
+end
+local lock = cjson.decode(redis.call('HGET', KEYS[1], 'data'))
+lock['last_heartbeat'] = ARGV[3]
+lock['expires_at'] = ARGV[4]
+local data = cjson.encode(lock)
+redis.call('HSET', KEYS[1], 'data', data)
+redis.call('PEXPIRE', KEYS[1], ARGV[5])
+return {1, data, tonumber(token)}
+";
+
+/// Replace the lock with a backed-up one, never moving the counter back
+///
+/// KEYS: lock hash, fencing counter. ARGV: fencing counter, lock JSON (empty
+/// for no lock), lock_id, lock's fencing token, TTL in ms.
+const RESTORE_SCRIPT: &str = r"
+local current = tonumber(redis.call('GET', KEYS[2]) or '0')
+if tonumber(ARGV[1]) > current then
+    redis.call('SET', KEYS[2], ARGV[1])
+end
+redis.call('DEL', KEYS[1])
+if ARGV[2] ~= '' then
+    redis.call('HSET', KEYS[1], 'lock_id', ARGV[3], 'fencing_token', ARGV[4], 'data', ARGV[2])
+    redis.call('PEXPIRE', KEYS[1], ARGV[5])
+end
+return 1
+";
+
+/// Redis-backed lock for running several server instances behind a load
+/// balancer
+///
+/// Follows the single-instance Redlock algorithm (atomic `SET`-if-absent
+/// with expiry, compare-and-delete on release) and adds fencing tokens from
+/// a per-repository `INCR` counter so a holder whose lock expired can't
+/// release or renew its successor's lock.
+#[derive(Clone)]
+pub struct RedisLockManager {
+    client: Client,
+}
+
+impl RedisLockManager {
+    pub fn new(redis_url: &str) -> AppResult<Self> {
+        let client = Client::open(redis_url)
+            .map_err(|e| AppError::Internal(format!("Invalid Redis URL: {}", e)))?;
+        Ok(Self { client })
+    }
+
+    /// Acquire the lock for a repository (`repo_key` is `namespace/name`)
+    pub fn acquire(
+        &self,
+        repo_key: &str,
+        user: &str,
+        machine_id: &str,
+        timeout_hours: u64,
+    ) -> AppResult<FileLock> {
+        let now = Utc::now();
+        let mut lock = FileLock {
+            lock_id: uuid::Uuid::new_v4().to_string(),
+            user: user.to_string(),
+            machine_id: machine_id.to_string(),
+            acquired_at: now,
+            expires_at: now + Duration::hours(timeout_hours as i64),
+            last_heartbeat: now,
+            fencing_token: 0,
+        };
+        let data = serde_json::to_string(&lock)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize lock: {}", e)))?;
+        let ttl_ms = timeout_hours.saturating_mul(3_600_000);
+
+        let mut conn = self.connection()?;
+        let token: Option<u64> = Script::new(ACQUIRE_SCRIPT)
+            .key(lock_key(repo_key))
+            .key(fence_key(repo_key))
+            .arg(&lock.lock_id)
+            .arg(data)
+            .arg(ttl_ms)
+            .invoke(&mut conn)
+            .map_err(redis_error)?;
+
+        match token {
+            Some(token) => {
+                lock.fencing_token = token;
+                Ok(lock)
+            }
+            None => {
+                let message = match self.status(repo_key)? {
+                    Some(existing) => {
+                        format!("Lock held by {} until {}", existing.user, existing.expires_at)
+                    }
+                    None => "Lock is held by another user".to_string(),
+                };
+                Err(AppError::LockHeld(message))
+            }
+        }
+    }
+
+    /// Release the lock if `lock_id` (and `fencing_token`, if given) match
+    pub fn release(
+        &self,
+        repo_key: &str,
+        lock_id: &str,
+        fencing_token: Option<u64>,
+    ) -> AppResult<()> {
+        let mut conn = self.connection()?;
+        let status: i64 = Script::new(RELEASE_SCRIPT)
+            .key(lock_key(repo_key))
+            .arg(lock_id)
+            .arg(token_arg(fencing_token))
+            .invoke(&mut conn)
+            .map_err(redis_error)?;
+
+        match status {
+            -1 => Err(AppError::Unauthorized(
+                "Cannot release lock owned by different user".to_string(),
+            )),
+            -2 => Err(stale_token_error(fencing_token)),
+            _ => Ok(()), // Released, or already released
+        }
+    }
+
+    /// Record a heartbeat if `lock_id` (and `fencing_token`, if given)
+    /// match, extending the lock by its timeout
+    pub fn heartbeat(
+        &self,
+        repo_key: &str,
+        lock_id: &str,
+        fencing_token: Option<u64>,
+    ) -> AppResult<FileLock> {
+        // The script checks the lock is still this one before applying it
+        let mut renewed = self
+            .status(repo_key)?
+            .ok_or_else(|| AppError::NotFound("Lock not found".to_string()))?;
+        renewed.renew(Utc::now());
+        let ttl_ms = (renewed.expires_at - renewed.last_heartbeat)
+            .num_milliseconds()
+            .max(1);
+
+        let mut conn = self.connection()?;
+        let (status, data, token): (i64, String, u64) = Script::new(HEARTBEAT_SCRIPT)
+            .key(lock_key(repo_key))
+            .arg(lock_id)
+            .arg(token_arg(fencing_token))
+            .arg(renewed.last_heartbeat.to_rfc3339())
+            .arg(renewed.expires_at.to_rfc3339())
+            .arg(ttl_ms)
+            .invoke(&mut conn)
+            .map_err(redis_error)?;
+
+        match status {
+            0 => Err(AppError::NotFound("Lock not found".to_string())),
+            -1 => Err(AppError::Unauthorized(
+                "Cannot update heartbeat for lock owned by different user".to_string(),
+            )),
+            -2 => Err(stale_token_error(fencing_token)),
+            _ => parse_lock(&data, token),
+        }
+    }
+
+    /// Get the current lock, if any (expired locks are dropped by Redis)
+    pub fn status(&self, repo_key: &str) -> AppResult<Option<FileLock>> {
+        let mut conn = self.connection()?;
+        let (data, token): (Option<String>, Option<u64>) = redis::cmd("HMGET")
+            .arg(lock_key(repo_key))
+            .arg("data")
+            .arg("fencing_token")
+            .query(&mut conn)
+            .map_err(redis_error)?;
+
+        match (data, token) {
+            (Some(data), Some(token)) => parse_lock(&data, token).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// The lock and the last fencing token issued, for a backup
+    pub fn snapshot(&self, repo_key: &str) -> AppResult<LockSnapshot> {
+        let lock = self.status(repo_key)?;
+        let mut conn = self.connection()?;
+        let fencing_token: Option<u64> = redis::cmd("GET")
+            .arg(fence_key(repo_key))
+            .query(&mut conn)
+            .map_err(redis_error)?;
+
+        Ok(LockSnapshot {
+            lock,
+            fencing_token: fencing_token.unwrap_or_default(),
+        })
+    }
+
+    /// Replace the lock with a snapshot's; an expired lock is dropped
+    pub fn restore(&self, repo_key: &str, snapshot: &LockSnapshot) -> AppResult<()> {
+        let now = Utc::now();
+        let lock = snapshot.lock.as_ref().filter(|lock| lock.expires_at > now);
+        let data = match lock {
+            Some(lock) => serde_json::to_string(lock)
+                .map_err(|e| AppError::Internal(format!("Failed to serialize lock: {}", e)))?,
+            None => String::new(),
+        };
+        let ttl_ms = lock
+            .map(|lock| (lock.expires_at - now).num_milliseconds().max(1))
+            .unwrap_or(1);
+
+        let mut conn = self.connection()?;
+        let _: i64 = Script::new(RESTORE_SCRIPT)
+            .key(lock_key(repo_key))
+            .key(fence_key(repo_key))
+            .arg(snapshot.fencing_token)
+            .arg(data)
+            .arg(lock.map(|lock| lock.lock_id.as_str()).unwrap_or_default())
+            .arg(lock.map(|lock| lock.fencing_token).unwrap_or_default())
+            .arg(ttl_ms)
+            .invoke(&mut conn)
+            .map_err(redis_error)?;
+        Ok(())
+    }
+
+    fn connection(&self) -> AppResult<Connection> {
+        self.client.get_connection().map_err(redis_error)
+    }
+}
+
+fn lock_key(repo_key: &str) -> String {
+    format!("auxin:lock:{}", repo_key)
+}
+
+fn fence_key(repo_key: &str) -> String {
+    format!("auxin:fence:{}", repo_key)
+}
+
+/// Scripts take an empty string for "no token supplied" (older clients)
+fn token_arg(fencing_token: Option<u64>) -> String {
+    fencing_token.map(|t| t.to_string()).unwrap_or_default()
+}
+
+fn parse_lock(data: &str, fencing_token: u64) -> AppResult<FileLock> {
+    let mut lock: FileLock = serde_json::from_str(data)
+        .map_err(|e| AppError::Internal(format!("Corrupt lock data in Redis: {}", e)))?;
+    lock.fencing_token = fencing_token;
+    Ok(lock)
+}
+
+fn stale_token_error(fencing_token: Option<u64>) -> AppError {
+    AppError::Conflict(format!(
+        "Stale fencing token {}",
+        fencing_token.unwrap_or_default()
+    ))
+}
+
+fn redis_error(e: redis::RedisError) -> AppError {
+    AppError::Internal(format!("Redis lock error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_namespaced_per_repo() {
+        assert_eq!(lock_key("studio/album"), "auxin:lock:studio/album");
+        assert_eq!(fence_key("studio/album"), "auxin:fence:studio/album");
+    }
+
+    #[test]
+    fn test_token_arg() {
+        assert_eq!(token_arg(Some(42)), "42");
+        assert_eq!(token_arg(None), "");
+    }
+
+    #[test]
+    fn test_parse_lock_applies_token() {
+        let now = Utc::now();
+        let lock = FileLock {
+            lock_id: "id".to_string(),
+            user: "u".to_string(),
+            machine_id: "m".to_string(),
+            acquired_at: now,
+            expires_at: now + Duration::hours(1),
+            last_heartbeat: now,
+            fencing_token: 0,
+        };
+        let data = serde_json::to_string(&lock).unwrap();
+
+        let parsed = parse_lock(&data, 9).unwrap();
+        assert_eq!(parsed.lock_id, "id");
+        assert_eq!(parsed.fencing_token, 9);
+    }
+
+    #[test]
+    fn test_invalid_url_rejected() {
+        assert!(RedisLockManager::new("not a url").is_err());
+    }
+
+    #[test]
+    #[ignore = "needs a Redis server (AUXIN_TEST_REDIS_URL, default localhost)"]
+    fn test_heartbeat_extends_past_original_ttl() {
+        let url = std::env::var("AUXIN_TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        let manager = RedisLockManager::new(&url).unwrap();
+        let repo_key = format!("test/{}", uuid::Uuid::new_v4());
+        let lock = manager.acquire(&repo_key, "u", "m", 1).unwrap();
+
+        // Bring the key to the end of its original TTL
+        let mut conn = manager.connection().unwrap();
+        let _: i64 = redis::cmd("PEXPIRE")
+            .arg(lock_key(&repo_key))
+            .arg(200)
+            .query(&mut conn)
+            .unwrap();
+
+        let renewed = manager
+            .heartbeat(&repo_key, &lock.lock_id, Some(lock.fencing_token))
+            .unwrap();
+        assert!(renewed.expires_at > lock.expires_at);
+        std::thread::sleep(std::time::Duration::from_millis(400));
+
+        let held = manager.status(&repo_key).unwrap().unwrap();
+        assert_eq!(held.lock_id, lock.lock_id);
+        assert_eq!(held.expires_at, renewed.expires_at);
+        let ttl: i64 = redis::cmd("PTTL")
+            .arg(lock_key(&repo_key))
+            .query(&mut conn)
+            .unwrap();
+        assert!(ttl > 3_000_000);
+        manager.release(&repo_key, &lock.lock_id, None).unwrap();
+    }
+}
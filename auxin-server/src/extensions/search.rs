@@ -0,0 +1,290 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::AppResult;
+use crate::extensions::LogicProMetadata;
+use crate::repo::RepositoryOps;
+
+/// Metadata search query
+///
+/// Uses the same query language as the CLI's `auxin search`, e.g.
+/// `bpm:120-140 key:minor tag:mixing,vocals sr:48000 msg:final limit:10`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchQuery {
+    pub bpm_min: Option<f64>,
+    pub bpm_max: Option<f64>,
+    pub sample_rate: Option<i32>,
+    /// Case-insensitive partial match
+    pub key_contains: Option<String>,
+    /// Any of these tags (OR logic)
+    pub tags_any: Vec<String>,
+    /// Case-insensitive partial match against the commit message
+    pub message_contains: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl SearchQuery {
+    /// Parse a query string; unknown or malformed terms are ignored
+    pub fn parse(query_str: &str) -> Self {
+        let mut query = Self::default();
+
+        for part in query_str.split_whitespace() {
+            let Some((key, value)) = part.split_once(':') else {
+                continue;
+            };
+
+            match key.to_lowercase().as_str() {
+                "bpm" => {
+                    if let Some((min, max)) = value.split_once('-') {
+                        // Range: "120-140"
+                        if let (Ok(min), Ok(max)) = (min.parse(), max.parse()) {
+                            query.bpm_min = Some(min);
+                            query.bpm_max = Some(max);
+                        }
+                    } else if let Some(min) = value.strip_prefix('>') {
+                        query.bpm_min = min.parse().ok();
+                    } else if let Some(max) = value.strip_prefix('<') {
+                        query.bpm_max = max.parse().ok();
+                    } else if let Ok(bpm) = value.parse() {
+                        query.bpm_min = Some(bpm);
+                        query.bpm_max = Some(bpm);
+                    }
+                }
+                "sr" | "samplerate" | "sample-rate" => {
+                    query.sample_rate = value.parse().ok();
+                }
+                "key" => {
+                    query.key_contains = Some(value.to_string());
+                }
+                "tag" | "tags" => {
+                    query.tags_any = value.split(',').map(|s| s.trim().to_string()).collect();
+                }
+                "msg" | "message" => {
+                    query.message_contains = Some(value.to_string());
+                }
+                "limit" => {
+                    query.limit = value.parse().ok();
+                }
+                _ => {}
+            }
+        }
+
+        query
+    }
+
+    /// Check whether a commit's metadata (and message, if known) matches
+    pub fn matches(&self, metadata: &LogicProMetadata, message: Option<&str>) -> bool {
+        if let Some(min) = self.bpm_min {
+            if metadata.bpm.is_none_or(|bpm| bpm < min) {
+                return false;
+            }
+        }
+
+        if let Some(max) = self.bpm_max {
+            if metadata.bpm.is_none_or(|bpm| bpm > max) {
+                return false;
+            }
+        }
+
+        if let Some(sr) = self.sample_rate {
+            if metadata.sample_rate != Some(sr) {
+                return false;
+            }
+        }
+
+        if let Some(ref key_contains) = self.key_contains {
+            let needle = key_contains.to_lowercase();
+            match metadata.key_signature {
+                Some(ref key) if key.to_lowercase().contains(&needle) => {}
+                _ => return false,
+            }
+        }
+
+        if !self.tags_any.is_empty() && !self.tags_any.iter().any(|t| metadata.tags.contains(t)) {
+            return false;
+        }
+
+        if let Some(ref msg_contains) = self.message_contains {
+            let needle = msg_contains.to_lowercase();
+            match message {
+                Some(msg) if msg.to_lowercase().contains(&needle) => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// A single search result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub commit_id: String,
+    pub message: Option<String>,
+    pub metadata: LogicProMetadata,
+}
+
+/// In-memory index of the structured metadata stored for a repository
+#[derive(Debug, Default)]
+pub struct MetadataIndex {
+    entries: Vec<(String, LogicProMetadata)>,
+}
+
+impl MetadataIndex {
+    /// Build the index from metadata stored in the repository
+    pub fn build(repo: &RepositoryOps) -> AppResult<Self> {
+        let mut entries = Vec::new();
+
+        for commit_id in repo.list_metadata()? {
+            if let Some(metadata) = repo.get_metadata(&commit_id)? {
+                entries.push((commit_id, metadata));
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Run a query against the index
+    ///
+    /// `messages` maps commit IDs to commit messages and, when non-empty,
+    /// also defines result order (history order). Without it, results are
+    /// ordered by commit ID and message filters match nothing.
+    pub fn search(&self, query: &SearchQuery, messages: &[(String, String)]) -> Vec<SearchHit> {
+        let message_of: HashMap<&str, &str> = messages
+            .iter()
+            .map(|(id, msg)| (id.as_str(), msg.as_str()))
+            .collect();
+
+        let mut hits: Vec<SearchHit> = self
+            .entries
+            .iter()
+            .filter(|(id, metadata)| {
+                query.matches(metadata, message_of.get(id.as_str()).copied())
+            })
+            .map(|(id, metadata)| SearchHit {
+                commit_id: id.clone(),
+                message: message_of.get(id.as_str()).map(|m| m.to_string()),
+                metadata: metadata.clone(),
+            })
+            .collect();
+
+        if !messages.is_empty() {
+            let position: HashMap<&str, usize> = messages
+                .iter()
+                .enumerate()
+                .map(|(i, (id, _))| (id.as_str(), i))
+                .collect();
+            hits.sort_by_key(|hit| {
+                position
+                    .get(hit.commit_id.as_str())
+                    .copied()
+                    .unwrap_or(usize::MAX)
+            });
+        }
+
+        if let Some(limit) = query.limit {
+            hits.truncate(limit);
+        }
+
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> MetadataIndex {
+        MetadataIndex {
+            entries: vec![
+                (
+                    "a".to_string(),
+                    LogicProMetadata::new()
+                        .with_bpm(100.0)
+                        .with_key_signature("C Major"),
+                ),
+                (
+                    "b".to_string(),
+                    LogicProMetadata::new()
+                        .with_bpm(128.0)
+                        .with_key_signature("A Minor")
+                        .with_tags(vec!["mixing".to_string()]),
+                ),
+                (
+                    "c".to_string(),
+                    LogicProMetadata::new()
+                        .with_bpm(135.0)
+                        .with_sample_rate(48000)
+                        .with_tags(vec!["vocals".to_string()]),
+                ),
+            ],
+        }
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.commit_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = SearchQuery::parse("bpm:120-140 key:minor tag:mixing,vocals sr:48000 limit:5");
+        assert_eq!(query.bpm_min, Some(120.0));
+        assert_eq!(query.bpm_max, Some(140.0));
+        assert_eq!(query.key_contains.as_deref(), Some("minor"));
+        assert_eq!(query.tags_any, vec!["mixing", "vocals"]);
+        assert_eq!(query.sample_rate, Some(48000));
+        assert_eq!(query.limit, Some(5));
+    }
+
+    #[test]
+    fn test_parse_query_bounds() {
+        assert_eq!(SearchQuery::parse("bpm:>120").bpm_min, Some(120.0));
+        assert_eq!(SearchQuery::parse("bpm:<90").bpm_max, Some(90.0));
+        assert_eq!(SearchQuery::parse("nonsense words"), SearchQuery::default());
+    }
+
+    #[test]
+    fn test_search_bpm_and_tags() {
+        let index = index();
+
+        let hits = index.search(&SearchQuery::parse("bpm:120-140"), &[]);
+        assert_eq!(ids(&hits), vec!["b", "c"]);
+
+        let hits = index.search(&SearchQuery::parse("bpm:120-140 tag:mixing"), &[]);
+        assert_eq!(ids(&hits), vec!["b"]);
+
+        let hits = index.search(&SearchQuery::parse("key:MINOR"), &[]);
+        assert_eq!(ids(&hits), vec!["b"]);
+    }
+
+    #[test]
+    fn test_search_uses_history_order_and_messages() {
+        let index = index();
+        let messages = vec![
+            ("c".to_string(), "Final vocals".to_string()),
+            ("b".to_string(), "Final mix".to_string()),
+            ("a".to_string(), "Sketch".to_string()),
+        ];
+
+        let hits = index.search(&SearchQuery::parse("msg:final"), &messages);
+        assert_eq!(ids(&hits), vec!["c", "b"]);
+        assert_eq!(hits[0].message.as_deref(), Some("Final vocals"));
+
+        // Message filters can't match without history
+        assert!(index.search(&SearchQuery::parse("msg:final"), &[]).is_empty());
+    }
+
+    #[test]
+    fn test_search_limit() {
+        let hits = index().search(&SearchQuery::parse("limit:2"), &[]);
+        assert_eq!(hits.len(), 2);
+    }
+}
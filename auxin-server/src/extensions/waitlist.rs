@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+
+use crate::clock;
+
+/// A user who asked for a lock while someone else held it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaitingUser {
+    pub user: String,
+    pub machine_id: String,
+    pub since: DateTime<Utc>,
+}
+
+/// Users waiting for a repository's lock, in `.oxen/locks/waitlist.json`
+///
+/// A user joins by being refused the lock and leaves by acquiring it; the
+/// whole list is told when the lock is released, expires or is broken.
+pub struct Waitlist;
+
+impl Waitlist {
+    /// Add a user, keeping their original place if they're already waiting
+    pub fn add(repo_path: &Path, user: &str, machine_id: &str) -> Result<(), Error> {
+        let mut waiting = Self::list(repo_path)?;
+        if waiting.iter().any(|w| w.user == user) {
+            return Ok(());
+        }
+        waiting.push(WaitingUser {
+            user: user.to_string(),
+            machine_id: machine_id.to_string(),
+            since: clock::now(),
+        });
+        Self::save(repo_path, &waiting)
+    }
+
+    /// Remove a user, e.g. once they hold the lock
+    pub fn remove(repo_path: &Path, user: &str) -> Result<(), Error> {
+        let mut waiting = Self::list(repo_path)?;
+        let before = waiting.len();
+        waiting.retain(|w| w.user != user);
+        if waiting.len() == before {
+            return Ok(());
+        }
+        Self::save(repo_path, &waiting)
+    }
+
+    /// Users waiting, longest first
+    pub fn list(repo_path: &Path) -> Result<Vec<WaitingUser>, Error> {
+        match fs::read_to_string(Self::path(repo_path)) {
+            Ok(content) => Ok(serde_json::from_str(&content).unwrap_or_default()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Empty the list, returning who was waiting
+    pub fn take(repo_path: &Path) -> Result<Vec<WaitingUser>, Error> {
+        let waiting = Self::list(repo_path)?;
+        if !waiting.is_empty() {
+            fs::remove_file(Self::path(repo_path))?;
+        }
+        Ok(waiting)
+    }
+
+    fn save(repo_path: &Path, waiting: &[WaitingUser]) -> Result<(), Error> {
+        let path = Self::path(repo_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(waiting)?)
+    }
+
+    fn path(repo_path: &Path) -> PathBuf {
+        repo_path.join(".oxen").join("locks").join("waitlist.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_waitlist_keeps_order_and_empties() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        Waitlist::add(repo_path, "sam", "m1").unwrap();
+        Waitlist::add(repo_path, "alex", "m2").unwrap();
+        Waitlist::add(repo_path, "sam", "m3").unwrap();
+        let waiting = Waitlist::list(repo_path).unwrap();
+        assert_eq!(waiting.len(), 2);
+        assert_eq!(waiting[0].machine_id, "m1");
+
+        Waitlist::remove(repo_path, "sam").unwrap();
+        let taken = Waitlist::take(repo_path).unwrap();
+        assert_eq!(taken[0].user, "alex");
+        assert!(Waitlist::list(repo_path).unwrap().is_empty());
+    }
+}
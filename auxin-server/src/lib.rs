@@ -3,10 +3,16 @@
 
 pub mod api;
 pub mod auth;
+pub mod backup;
+pub mod clock;
+pub mod discovery;
 pub mod error;
 pub mod extensions;
+pub mod lock_sweeper;
+pub mod mock_mode;
 pub mod project;
 pub mod repo_access;
+pub mod request_id;
 pub mod websocket;
 
 #[cfg(feature = "web-ui")]
@@ -0,0 +1,310 @@
+//! Background sweep of repository locks
+//!
+//! A lock that runs out without being released used to linger until someone
+//! tried to take it. Every `lock_sweep_interval_secs` the sweeper visits each
+//! repository and:
+//!
+//! - warns the holder over WebSocket (`LockExpiring`) once the lock is within
+//!   `lock_expiry_warning_minutes` of expiring, once per lock
+//! - clears an expired lock, records a `lock_expired` activity and broadcasts
+//!   `LockExpired` and `LockReleased`
+//! - tells the users who were refused the lock (the [`Waitlist`]) that it's
+//!   free with `LockAvailable`
+//!
+//! Redis drops expired locks by itself, and a status check clears an expired
+//! file lock in passing, so the sweeper also remembers the locks it saw on the
+//! previous pass: one that has since disappeared after its expiry time, with
+//! no release recorded, expired.
+
+use auxin_config::Config;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn};
+
+use crate::backup::list_repos;
+use crate::clock;
+use crate::error::AppResult;
+use crate::extensions::{
+    get_activities, log_activity, ActivityType, FileLock, LockStore, WaitingUser, Waitlist,
+};
+use crate::repo::RepositoryOps;
+use crate::websocket::WsHub;
+
+/// Recent activities searched for a release before calling a vanished lock
+/// expired
+const RELEASE_LOOKBACK: usize = 50;
+
+/// Something a sweep found that clients should hear about
+#[derive(Debug, Clone)]
+pub enum SweepEvent {
+    /// The lock expires within the warning window
+    Expiring {
+        namespace: String,
+        name: String,
+        lock: FileLock,
+    },
+    /// The lock expired and was cleared; `waiting` were refused it
+    Expired {
+        namespace: String,
+        name: String,
+        lock: FileLock,
+        waiting: Vec<WaitingUser>,
+    },
+}
+
+/// Finds expiring and expired locks across every repository
+pub struct LockSweeper {
+    config: Config,
+    /// Locks seen on the last pass, by `namespace/name`
+    held: HashMap<String, FileLock>,
+    /// Locks whose holder has been warned
+    warned: HashSet<String>,
+}
+
+impl LockSweeper {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            held: HashMap::new(),
+            warned: HashSet::new(),
+        }
+    }
+
+    /// Visit every repository once, clearing and recording expired locks
+    pub fn sweep(&mut self) -> Vec<SweepEvent> {
+        let sync_dir = PathBuf::from(&self.config.server.sync_dir);
+        let repos = match list_repos(&sync_dir) {
+            Ok(repos) => repos,
+            Err(e) => {
+                warn!("Lock sweep couldn't list repositories: {}", e);
+                return Vec::new();
+            },
+        };
+
+        let now = clock::now();
+        let mut events = Vec::new();
+        let mut held = HashMap::new();
+        for (namespace, name) in repos {
+            let repo_path = sync_dir.join(&namespace).join(&name);
+            if let Err(e) =
+                self.sweep_repo(&repo_path, &namespace, &name, now, &mut held, &mut events)
+            {
+                warn!("Lock sweep failed for {}/{}: {}", namespace, name, e);
+            }
+        }
+
+        self.warned.retain(|lock_id| held.values().any(|lock| &lock.lock_id == lock_id));
+        self.held = held;
+        events
+    }
+
+    fn sweep_repo(
+        &mut self,
+        repo_path: &Path,
+        namespace: &str,
+        name: &str,
+        now: DateTime<Utc>,
+        held: &mut HashMap<String, FileLock>,
+        events: &mut Vec<SweepEvent>,
+    ) -> AppResult<()> {
+        let key = format!("{}/{}", namespace, name);
+        let repo = RepositoryOps::open(repo_path)?;
+        let locks = LockStore::new(&self.config, &repo, namespace, name)?;
+
+        // Taken before the status check, which would clear it silently
+        let taken = locks.take_expired()?;
+        let current = locks.status()?;
+        let expired = match taken {
+            Some(lock) => Some(lock),
+            None => self.held.get(&key).cloned().filter(|previous| {
+                previous.expires_at <= now
+                    && current.as_ref().map(|lock| &lock.lock_id) != Some(&previous.lock_id)
+                    && !release_recorded(repo_path, &previous.lock_id)
+            }),
+        };
+
+        if let Some(lock) = expired {
+            info!("Lock on {} held by {} expired", key, lock.user);
+            log_activity(
+                repo_path,
+                ActivityType::LockExpired,
+                &lock.user,
+                "Lock expired without being released",
+                Some(serde_json::json!({
+                    "lock_id": lock.lock_id,
+                    "fencing_token": lock.fencing_token,
+                    "machine_id": lock.machine_id,
+                    "expires_at": lock.expires_at,
+                    "last_heartbeat": lock.last_heartbeat,
+                })),
+                None,
+            )?;
+            // Someone who already took it over isn't waiting any more
+            let waiting = if current.is_none() {
+                Waitlist::take(repo_path).unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            events.push(SweepEvent::Expired {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+                lock,
+                waiting,
+            });
+        }
+
+        if let Some(lock) = current {
+            let warning = Duration::minutes(self.config.server.lock_expiry_warning_minutes.max(0));
+            if lock.expires_at - now <= warning && self.warned.insert(lock.lock_id.clone()) {
+                events.push(SweepEvent::Expiring {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                    lock: lock.clone(),
+                });
+            }
+            held.insert(key, lock);
+        }
+        Ok(())
+    }
+}
+
+/// Whether the lock's release (or break) is in the activity log
+fn release_recorded(repo_path: &Path, lock_id: &str) -> bool {
+    get_activities(repo_path, RELEASE_LOOKBACK)
+        .unwrap_or_default()
+        .iter()
+        .any(|activity| {
+            activity.activity_type == ActivityType::LockReleased
+                && activity
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.get("lock_id"))
+                    .and_then(|id| id.as_str())
+                    == Some(lock_id)
+        })
+}
+
+/// Tell WebSocket clients what a sweep found
+pub async fn notify(ws_hub: &WsHub, events: &[SweepEvent]) {
+    for event in events {
+        match event {
+            SweepEvent::Expiring {
+                namespace,
+                name,
+                lock,
+            } => {
+                let (user, lock_id) = (&lock.user, &lock.lock_id);
+                let expires_at = lock.expires_at.to_rfc3339();
+                let _ = ws_hub
+                    .broadcast_lock_expiring(namespace, name, user, lock_id, &expires_at)
+                    .await;
+            },
+            SweepEvent::Expired {
+                namespace,
+                name,
+                lock,
+                waiting,
+            } => {
+                let _ = ws_hub
+                    .broadcast_lock_expired(namespace, name, &lock.user, &lock.lock_id)
+                    .await;
+                let _ = ws_hub
+                    .broadcast_lock_released(namespace, name, &lock.lock_id)
+                    .await;
+                let _ = ws_hub.broadcast_lock_available(namespace, name, waiting).await;
+            },
+        }
+    }
+}
+
+/// Run the sweeper in the background, unless `lock_sweep_interval_secs` is 0
+pub fn spawn(config: Config, ws_hub: WsHub) {
+    let interval = config.server.lock_sweep_interval_secs;
+    if interval <= 0 {
+        info!("Lock sweeper disabled");
+        return;
+    }
+
+    actix_rt::spawn(async move {
+        let mut sweeper = LockSweeper::new(config);
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval as u64));
+        loop {
+            ticker.tick().await;
+            let swept = tokio::task::spawn_blocking(move || {
+                let events = sweeper.sweep();
+                (sweeper, events)
+            })
+            .await;
+            let Ok((returned, events)) = swept else {
+                error!("Lock sweep panicked; sweeper stopped");
+                return;
+            };
+            sweeper = returned;
+            notify(&ws_hub, &events).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::ActivityLog;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sweep_warns_then_expires() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().join("studio").join("track-3");
+        fs::create_dir_all(repo_path.join(".oxen")).unwrap();
+
+        let mut config = Config::default();
+        config.server.sync_dir = temp_dir.path().to_string_lossy().to_string();
+        config.server.lock_expiry_warning_minutes = 90;
+        let mut sweeper = LockSweeper::new(config);
+
+        // Within the warning window: warned once
+        let lock = FileLock::acquire(&repo_path, "sam", "m1", 1).unwrap();
+        let events = sweeper.sweep();
+        assert!(matches!(&events[..], [SweepEvent::Expiring { lock: l, .. }] if l.user == "sam"));
+        assert!(sweeper.sweep().is_empty());
+
+        // Expired: cleared, recorded and the waitlist told
+        Waitlist::add(&repo_path, "alex", "m2").unwrap();
+        FileLock::force_expire(&repo_path).unwrap();
+        let events = sweeper.sweep();
+        let [SweepEvent::Expired { lock: expired, waiting, .. }] = &events[..] else {
+            panic!("expected an expiry, got {:?}", events);
+        };
+        assert_eq!(expired.lock_id, lock.lock_id);
+        assert_eq!(waiting[0].user, "alex");
+        assert!(FileLock::status(&repo_path).unwrap().is_none());
+        let log = ActivityLog::load(&repo_path).unwrap();
+        assert_eq!(log.by_type(ActivityType::LockExpired).len(), 1);
+
+        // A lock cleared in passing by a status check is still caught
+        FileLock::acquire(&repo_path, "alex", "m2", 1).unwrap();
+        sweeper.sweep();
+        let lock = FileLock::force_expire(&repo_path).unwrap().unwrap();
+        sweeper.held.insert("studio/track-3".to_string(), lock);
+        assert!(FileLock::status(&repo_path).unwrap().is_none());
+        let events = sweeper.sweep();
+        assert!(matches!(&events[..], [SweepEvent::Expired { lock: l, .. }] if l.user == "alex"));
+
+        // A released lock isn't reported once its expiry time passes
+        let lock = FileLock::acquire(&repo_path, "sam", "m1", 0).unwrap();
+        sweeper.held.insert("studio/track-3".to_string(), lock.clone());
+        FileLock::release(&repo_path, &lock.lock_id, None).unwrap();
+        log_activity(
+            &repo_path,
+            ActivityType::LockReleased,
+            "sam",
+            "Released lock",
+            Some(serde_json::json!({ "lock_id": lock.lock_id })),
+            None,
+        )
+        .unwrap();
+        assert!(sweeper.sweep().is_empty());
+    }
+}
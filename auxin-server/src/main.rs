@@ -1,19 +1,180 @@
 use actix_files::{Files, NamedFile};
-use actix_web::{middleware, web, App, HttpResponse, HttpServer, Result};
+use actix_web::{dev::ServerHandle, middleware, web, App, HttpServer, Result};
 use std::path::PathBuf;
-use tracing::info;
+use tracing::{error, info, warn};
 
-use auxin_config::Config;
+use auxin_config::{Config, DEV_AUTH_SECRET};
 use auxin_server::api;
 use auxin_server::auth::{self, AuthService};
+use auxin_server::backup;
+use auxin_server::clock;
+use auxin_server::discovery::{self, Advertisement};
+use auxin_server::lock_sweeper;
+use auxin_server::mock_mode::{self, fault_injection_middleware, FaultState};
 use auxin_server::repo_access::RepoAccessService;
+use auxin_server::request_id::request_id_middleware;
 use auxin_server::websocket::{ws_handler, WsHub};
 
 #[cfg(feature = "web-ui")]
 use auxin_server::db;
 
+/// Delay suggested to WebSocket clients before reconnecting after shutdown
+const RECONNECT_AFTER_SECS: u64 = 5;
+
+const USAGE: &str = "Usage: auxin-server [--dev] [--mock] [--port <PORT>]
+       auxin-server backup --output <FILE> [--online]
+       auxin-server restore --input <FILE> [--dry-run]
+
+Commands:
+  backup         Save locks, metadata, comments, activity and accounts to a
+                 .tar.zst archive (the server must be stopped unless --online)
+  restore        Restore an archive into the configured data directory and
+                 lock backend (the server must be stopped)
+
+Options:
+  --dev          Allow the development auth secret (local testing only)
+  --mock         Run against a throwaway data directory with fault injection
+                 and clock control under /mock (for CI and demos)
+  --port <PORT>  Listen on PORT instead of the configured port
+  --online       Back up a running server, retrying until the snapshot is
+                 consistent
+  --dry-run      Check an archive and show what restore would write
+  -h, --help     Print this help";
+
+/// Maintenance commands run instead of the server
+#[derive(Debug)]
+enum Command {
+    Backup { output: PathBuf, online: bool },
+    Restore { input: PathBuf, dry_run: bool },
+}
+
+/// Command-line options; everything else comes from configuration
+#[derive(Debug, Default)]
+struct Args {
+    dev: bool,
+    mock: bool,
+    port: Option<u16>,
+    command: Option<Command>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1).peekable();
+
+    let command = iter.next_if(|arg| arg == "backup" || arg == "restore");
+    let mut file: Option<PathBuf> = None;
+    let mut online = false;
+    let mut dry_run = false;
+
+    while let Some(arg) = iter.next() {
+        match (command.as_deref(), arg.as_str()) {
+            (Some("backup"), "--output") | (Some("restore"), "--input") => {
+                file = Some(iter.next().ok_or(format!("{} requires a file", arg))?.into());
+            }
+            (Some("backup"), "--online") => online = true,
+            (Some("restore"), "--dry-run") => dry_run = true,
+            (_, "--dev") => args.dev = true,
+            (None, "--mock") => args.mock = true,
+            (None, "--port") => {
+                let value = iter.next().ok_or("--port requires a value")?;
+                args.port = Some(value.parse().map_err(|_| format!("Invalid port: {}", value))?);
+            }
+            (_, "-h" | "--help") => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            (_, other) => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    args.command = match command.as_deref() {
+        Some("backup") => Some(Command::Backup {
+            output: file.ok_or("backup requires --output <FILE>")?,
+            online,
+        }),
+        Some(_) => Some(Command::Restore {
+            input: file.ok_or("restore requires --input <FILE>")?,
+            dry_run,
+        }),
+        None => None,
+    };
+
+    Ok(args)
+}
+
+/// Run a maintenance command; returns the process exit code
+fn run_command(command: Command, config: &Config) -> i32 {
+    let result = match command {
+        Command::Backup { output, online } => {
+            if !online && backup::server_is_running(config) {
+                eprintln!(
+                    "The server is running on port {}. Stop it first, or pass --online for a \
+                     consistent snapshot of the running server",
+                    config.server.port
+                );
+                return 1;
+            }
+            backup::take_snapshot(config, online).and_then(|snapshot| {
+                backup::write_archive(&snapshot, &output)?;
+                println!(
+                    "Backed up {} repositories ({} files, {} locks) to {}",
+                    snapshot.manifest.repos.len(),
+                    snapshot.files.len(),
+                    snapshot.manifest.lock_backend,
+                    output.display()
+                );
+                Ok(())
+            })
+        }
+        Command::Restore { input, dry_run } => {
+            if !dry_run && backup::server_is_running(config) {
+                eprintln!(
+                    "The server is running on port {}; stop it before restoring",
+                    config.server.port
+                );
+                return 1;
+            }
+            backup::read_archive(&input).and_then(|snapshot| {
+                let report = backup::restore(&snapshot, config, dry_run)?;
+                let verb = if dry_run { "Would restore" } else { "Restored" };
+                println!(
+                    "{} backup from {} into {}",
+                    verb,
+                    snapshot.manifest.created_at.format("%Y-%m-%d %H:%M UTC"),
+                    config.server.sync_dir
+                );
+                println!("  server files: {}", report.server_files);
+                println!(
+                    "  repositories: {} ({} files, {} active locks)",
+                    report.repos, report.repo_files, report.locks
+                );
+                for repo in &report.missing_repos {
+                    println!("  skipped {}: restore its data first, then run restore again", repo);
+                }
+                Ok(())
+            })
+        }
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            1
+        }
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
     // Initialize tracing
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -25,11 +186,58 @@ async fn main() -> std::io::Result<()> {
     info!("Starting Auxin Server (Oxen-aligned architecture)...");
 
     // Load configuration
-    let config = Config::load().expect("Failed to load configuration");
+    // [cli] server picks the server the CLI talks to; it's nothing to us
+    let mut config = Config::load_unselected().expect("Failed to load configuration");
     info!("Configuration loaded");
+
+    if let Some(port) = args.port {
+        config.server.port = port.into();
+    }
+
+    if let Some(command) = args.command {
+        std::process::exit(run_command(command, &config));
+    }
+
+    if args.mock {
+        // Nothing from a real deployment is read or written in mock mode
+        let mock_dir = std::env::temp_dir().join(format!("auxin-mock-{}", uuid::Uuid::new_v4()));
+        config.server.sync_dir = mock_dir.to_string_lossy().to_string();
+        config.server.enable_redis_locks = false;
+        clock::install_mock();
+        warn!("MOCK MODE: data is discarded on exit; fault injection enabled under /mock");
+    }
+    // References outside [server] are the CLI's business
+    let unresolved: Vec<_> =
+        config.unresolved().iter().filter(|problem| problem.starts_with("server.")).collect();
+    if !unresolved.is_empty() {
+        for problem in unresolved {
+            error!("Configuration: {}", problem);
+        }
+        std::process::exit(1);
+    }
+    if !args.mock {
+        if !args.dev && config.server.auth_token_secret == DEV_AUTH_SECRET {
+            error!(
+                "Refusing to start with the development auth_token_secret. Set a secret \
+                 (auth_token_secret, auth_token_secret_file or \"${{VAR}}\"), generate a \
+                 config with 'auxin server init-config', or pass --dev for local testing"
+            );
+            std::process::exit(1);
+        }
+        for problem in config.server.validate() {
+            warn!("Configuration: {} (generate one with 'auxin server init-config')", problem);
+        }
+    }
     info!("SYNC_DIR: {}", config.server.sync_dir);
     info!("Server will listen on {}:{}", config.server.host, config.server.port);
 
+    if config.server.enable_redis_locks {
+        #[cfg(feature = "redis-locks")]
+        info!("Using Redis for repository locks");
+        #[cfg(not(feature = "redis-locks"))]
+        warn!("enable_redis_locks is set but redis-locks feature is not built; using file locks");
+    }
+
     // Ensure SYNC_DIR exists
     std::fs::create_dir_all(&config.server.sync_dir).expect("Failed to create SYNC_DIR");
 
@@ -80,9 +288,34 @@ async fn main() -> std::io::Result<()> {
         info!("Frontend not built. Run 'cd frontend && npm install && npm run build' to enable web UI");
     }
 
+    let mock = args.mock;
+    let fault_state = FaultState::default();
+    let mock_dir = PathBuf::from(&config.server.sync_dir);
+
+    // Let `auxin server discover` find this server on the LAN
+    if config.server.advertise && !mock {
+        match Advertisement::from_config(&config, port) {
+            Some(advertisement) => {
+                let name = advertisement.name.clone();
+                match discovery::spawn(advertisement) {
+                    Ok(()) => info!("Advertising as '{}' ({})", name, discovery::SERVICE),
+                    Err(e) => warn!("LAN discovery unavailable: {}", e),
+                }
+            }
+            None => info!("Listening on loopback only; not advertising on the LAN"),
+        }
+    }
+
+    // Warn holders before their locks expire and clear expired ones
+    lock_sweeper::spawn(config.clone(), ws_hub.clone());
+
+    let shutdown_timeout = config.server.shutdown_timeout_secs.max(0) as u64;
+    let shutdown_auth = auth_service.clone();
+    let shutdown_hub = ws_hub.clone();
+
     // Start HTTP server
     info!("Starting Actix Web server...");
-    HttpServer::new(move || {
+    let server = HttpServer::new(move || {
         let mut app = App::new()
             .app_data(web::Data::new(config.clone()))
             .app_data(web::Data::new(auth_service.clone()))
@@ -95,154 +328,46 @@ async fn main() -> std::io::Result<()> {
             app = app.app_data(web::Data::new(pool.clone()));
         }
 
+        let projects_enabled = db_pool.is_some();
+
         let mut app = app
-            .wrap(middleware::Logger::default())
+            // Injected faults still get a request ID and a log line
+            .wrap(middleware::Condition::new(
+                mock,
+                middleware::from_fn(fault_injection_middleware),
+            ))
+            // Request IDs are assigned inside the logger so it can print them
+            .wrap(middleware::from_fn(request_id_middleware))
+            .wrap(middleware::Logger::new(
+                r#"%a "%r" %s %b %T request_id=%{x-auxin-request-id}o"#,
+            ))
             .wrap(
                 actix_cors::Cors::default()
                     .allow_any_origin()
                     .allow_any_method()
                     .allow_any_header(),
             )
-            .route("/health", web::get().to(health_check))
-            // Auth endpoints
-            .route("/api/auth/register", web::post().to(auth::register))
-            .route("/api/auth/login", web::post().to(auth::login))
-            .route("/api/auth/logout", web::post().to(auth::logout))
-            .route("/api/auth/me", web::get().to(auth::me));
-
-        // Project CRUD endpoints (requires web-ui feature and database)
-        #[cfg(feature = "web-ui")]
-        if db_pool.is_some() {
-            app = app
-                .route("/api/projects", web::post().to(api::create_project))
-                .route("/api/projects", web::get().to(api::list_projects))
-                .route("/api/projects/{id}", web::get().to(api::get_project))
-                .route(
-                    "/api/projects/{namespace}/{name}",
-                    web::get().to(api::get_project_by_namespace),
-                )
-                .route("/api/projects/{id}", web::put().to(api::update_project))
-                .route("/api/projects/{id}", web::delete().to(api::delete_project));
-        }
-
-        let mut app = app
-            // Public endpoints
-            .route("/api/repos", web::get().to(api::list_repositories))
-            .route(
-                "/api/repos/{namespace}/{name}",
-                web::get().to(api::get_repository),
-            )
-            // Repository operations
-            .route(
-                "/api/repos/{namespace}/{name}",
-                web::post().to(api::create_repository),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/clone",
-                web::post().to(api::clone_repository),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/status",
-                web::get().to(api::get_status),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/commits",
-                web::get().to(api::get_commits),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/commits/{commit}/restore",
-                web::post().to(api::restore_commit),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/push",
-                web::post().to(api::push_repository),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/pull",
-                web::post().to(api::pull_repository),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/fetch",
-                web::post().to(api::fetch_repository),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/branches",
-                web::get().to(api::list_branches),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/branches",
-                web::post().to(api::create_branch),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/branches/{branch}",
-                web::delete().to(api::delete_branch),
-            )
-            // Auxin extensions
-            .route(
-                "/api/repos/{namespace}/{name}/metadata/{commit}",
-                web::get().to(api::get_metadata),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/metadata/{commit}",
-                web::post().to(api::store_metadata),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/locks/acquire",
-                web::post().to(api::acquire_lock),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/locks/release",
-                web::post().to(api::release_lock),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/locks/heartbeat",
-                web::post().to(api::heartbeat_lock),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/locks/status",
-                web::get().to(api::lock_status),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/activity",
-                web::get().to(api::get_activity),
-            )
+            .route("/health", web::get().to(api::health_check))
             // WebSocket for real-time notifications
             .route("/ws/repos/{namespace}/{name}", web::get().to(ws_handler))
-            // Bounce audio endpoints
-            .route(
-                "/api/repos/{namespace}/{name}/bounces",
-                web::get().to(api::list_bounces),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/bounces/{commit}",
-                web::get().to(api::get_bounce),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/bounces/{commit}/audio",
-                web::get().to(api::get_bounce_audio),
+            // Versioned API (must be registered before the legacy /api scope)
+            .service(
+                web::scope(&format!("/api/v{}", api::API_VERSION))
+                    .configure(|cfg| configure_api(cfg, projects_enabled)),
             )
-            .route(
-                "/api/repos/{namespace}/{name}/bounces/{commit}",
-                web::post().to(api::upload_bounce),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/bounces/{commit}",
-                web::delete().to(api::delete_bounce),
-            )
-            // Repository access control endpoints
-            .route(
-                "/api/repos/{namespace}/{name}/access/grant",
-                web::post().to(api::grant_access),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/access/revoke",
-                web::post().to(api::revoke_access),
-            )
-            .route(
-                "/api/repos/{namespace}/{name}/access",
-                web::get().to(api::list_access),
+            // Compatibility shim: unversioned paths used by older clients
+            .service(
+                web::scope("/api")
+                    .wrap(middleware::DefaultHeaders::new().add(("Deprecation", "true")))
+                    .configure(|cfg| configure_api(cfg, projects_enabled)),
             );
 
+        if mock {
+            app = app
+                .app_data(web::Data::new(fault_state.clone()))
+                .configure(mock_mode::configure);
+        }
+
         // Serve frontend static files if available
         if serve_frontend {
             app = app
@@ -253,12 +378,267 @@ async fn main() -> std::io::Result<()> {
         app
     })
     .bind((host.as_str(), port))?
-    .run()
-    .await
+    // In-flight requests get this long to finish once shutdown starts
+    .shutdown_timeout(shutdown_timeout)
+    // Signals are handled below so WebSocket clients can be told first
+    .disable_signals()
+    .run();
+
+    actix_rt::spawn(stop_on_signal(server.handle(), shutdown_hub, shutdown_timeout));
+
+    server.await?;
+
+    if mock {
+        if let Err(e) = std::fs::remove_dir_all(&mock_dir) {
+            warn!("Failed to remove mock data directory: {}", e);
+        }
+        info!("Shutdown complete");
+        return Ok(());
+    }
+
+    // Requests have drained (or the deadline passed); keep clients logged in
+    match shutdown_auth.save_sessions() {
+        Ok(count) => info!("Saved {} sessions", count),
+        Err(e) => warn!("Failed to save sessions: {}", e),
+    }
+
+    info!("Shutdown complete");
+    Ok(())
+}
+
+/// Wait for SIGTERM or Ctrl-C, then shut down gracefully
+///
+/// New connections are refused, WebSocket clients get a reconnect hint,
+/// and in-flight requests (lock and metadata writes) run to completion
+/// within `shutdown_timeout` seconds.
+async fn stop_on_signal(handle: ServerHandle, ws_hub: WsHub, shutdown_timeout: u64) {
+    wait_for_signal().await;
+    info!("Shutdown signal received; draining connections (up to {}s)", shutdown_timeout);
+
+    ws_hub.begin_shutdown(RECONNECT_AFTER_SECS);
+    handle.stop(true).await;
 }
 
-async fn health_check() -> Result<HttpResponse> {
-    Ok(HttpResponse::Ok().body("OK"))
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            tokio::select! {
+                _ = sigterm.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        Err(e) => {
+            warn!("Failed to install SIGTERM handler: {}", e);
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Register API routes relative to a scope (`/api/v1` or the legacy `/api`)
+fn configure_api(cfg: &mut web::ServiceConfig, projects_enabled: bool) {
+    cfg.route("/version", web::get().to(api::get_version))
+        // Auth endpoints
+        .route("/auth/register", web::post().to(auth::register))
+        .route("/auth/login", web::post().to(auth::login))
+        .route("/auth/logout", web::post().to(auth::logout))
+        .route("/auth/me", web::get().to(auth::me));
+
+    // Server administration (Admin role)
+    cfg.route("/admin/locks", web::get().to(api::list_locks))
+        .route(
+            "/admin/locks/{namespace}/{name}/break",
+            web::post().to(api::break_lock),
+        )
+        .route("/admin/audit", web::get().to(api::audit_log))
+        .route("/admin/sessions", web::get().to(api::list_sessions))
+        .route("/admin/tokens/revoke", web::post().to(api::revoke_tokens))
+        .route("/admin/storage", web::get().to(api::storage))
+        .route("/admin/gc", web::post().to(api::gc));
+
+    // Project CRUD endpoints (requires web-ui feature and database)
+    #[cfg(feature = "web-ui")]
+    if projects_enabled {
+        cfg.route("/projects", web::post().to(api::create_project))
+            .route("/projects", web::get().to(api::list_projects))
+            .route("/projects/{id}", web::get().to(api::get_project))
+            .route(
+                "/projects/{namespace}/{name}",
+                web::get().to(api::get_project_by_namespace),
+            )
+            .route("/projects/{id}", web::put().to(api::update_project))
+            .route("/projects/{id}", web::delete().to(api::delete_project));
+    }
+
+    // Public endpoints
+    cfg.route("/repos", web::get().to(api::list_repositories))
+        .route("/locks/stats", web::get().to(api::lock_stats))
+        .route("/presence", web::post().to(api::record_presence))
+        .route("/identity", web::post().to(api::link_identity))
+        .route(
+            "/repos/{namespace}/{name}",
+            web::get().to(api::get_repository),
+        )
+        // Repository operations
+        .route(
+            "/repos/{namespace}/{name}",
+            web::post().to(api::create_repository),
+        )
+        .route(
+            "/repos/{namespace}/{name}/clone",
+            web::post().to(api::clone_repository),
+        )
+        .route(
+            "/repos/{namespace}/{name}/status",
+            web::get().to(api::get_status),
+        )
+        .route(
+            "/repos/{namespace}/{name}/commits",
+            web::get().to(api::get_commits),
+        )
+        .route(
+            "/repos/{namespace}/{name}/commits/{commit}/restore",
+            web::post().to(api::restore_commit),
+        )
+        .route(
+            "/repos/{namespace}/{name}/push",
+            web::post().to(api::push_repository),
+        )
+        .route(
+            "/repos/{namespace}/{name}/pull",
+            web::post().to(api::pull_repository),
+        )
+        .route(
+            "/repos/{namespace}/{name}/fetch",
+            web::post().to(api::fetch_repository),
+        )
+        .route(
+            "/repos/{namespace}/{name}/branches",
+            web::get().to(api::list_branches),
+        )
+        .route(
+            "/repos/{namespace}/{name}/branches",
+            web::post().to(api::create_branch),
+        )
+        .route(
+            "/repos/{namespace}/{name}/branches/{branch}",
+            web::delete().to(api::delete_branch),
+        )
+        // Auxin extensions
+        .route(
+            "/repos/{namespace}/{name}/metadata",
+            web::get().to(api::list_metadata),
+        )
+        // (batch route must precede /metadata/{commit} so "batch" isn't taken as a commit)
+        .route(
+            "/repos/{namespace}/{name}/metadata/batch",
+            web::post().to(api::store_metadata_batch),
+        )
+        .route(
+            "/repos/{namespace}/{name}/metadata/{commit}",
+            web::get().to(api::get_metadata),
+        )
+        .route(
+            "/repos/{namespace}/{name}/metadata/{commit}",
+            web::post().to(api::store_metadata),
+        )
+        .route(
+            "/repos/{namespace}/{name}/search",
+            web::get().to(api::search_metadata),
+        )
+        .route(
+            "/repos/{namespace}/{name}/locks/acquire",
+            web::post().to(api::acquire_lock),
+        )
+        .route(
+            "/repos/{namespace}/{name}/locks/release",
+            web::post().to(api::release_lock),
+        )
+        .route(
+            "/repos/{namespace}/{name}/locks/heartbeat",
+            web::post().to(api::heartbeat_lock),
+        )
+        .route(
+            "/repos/{namespace}/{name}/locks/reclaim",
+            web::post().to(api::reclaim_lock),
+        )
+        .route(
+            "/repos/{namespace}/{name}/locks/status",
+            web::get().to(api::lock_status),
+        )
+        .route(
+            "/repos/{namespace}/{name}/locks/stats",
+            web::get().to(api::repo_lock_stats),
+        )
+        .route(
+            "/repos/{namespace}/{name}/activity",
+            web::get().to(api::get_activity),
+        )
+        .route(
+            "/repos/{namespace}/{name}/activity/import",
+            web::post().to(api::import_activity),
+        )
+        // Bounce audio endpoints
+        .route(
+            "/repos/{namespace}/{name}/bounces",
+            web::get().to(api::list_bounces),
+        )
+        .route(
+            "/repos/{namespace}/{name}/bounces/{commit}",
+            web::get().to(api::get_bounce),
+        )
+        .route(
+            "/repos/{namespace}/{name}/bounces/{commit}/audio",
+            web::get().to(api::get_bounce_audio),
+        )
+        .route(
+            "/repos/{namespace}/{name}/bounces/{commit}",
+            web::post().to(api::upload_bounce),
+        )
+        .route(
+            "/repos/{namespace}/{name}/bounces/{commit}",
+            web::delete().to(api::delete_bounce),
+        )
+        // Stem set endpoints
+        .route(
+            "/repos/{namespace}/{name}/stems",
+            web::get().to(api::list_stems),
+        )
+        .route(
+            "/repos/{namespace}/{name}/stems/{commit}",
+            web::get().to(api::get_stems),
+        )
+        .route(
+            "/repos/{namespace}/{name}/stems/{commit}",
+            web::post().to(api::upload_stems),
+        )
+        .route(
+            "/repos/{namespace}/{name}/stems/{commit}/{stem}",
+            web::get().to(api::get_stem_audio),
+        )
+        // Repository access control endpoints
+        .route(
+            "/repos/{namespace}/{name}/access/grant",
+            web::post().to(api::grant_access),
+        )
+        .route(
+            "/repos/{namespace}/{name}/access/revoke",
+            web::post().to(api::revoke_access),
+        )
+        .route(
+            "/repos/{namespace}/{name}/access",
+            web::get().to(api::list_access),
+        );
+
+    #[cfg(not(feature = "web-ui"))]
+    let _ = projects_enabled;
 }
 
 // Serve SPA for all non-API routes
@@ -0,0 +1,282 @@
+//! Control plane for `auxin-server --mock`
+//!
+//! Mock mode runs the normal API against a throwaway `sync_dir` and adds a
+//! `/mock` scope that tests use to inject faults, drive the clock, and expire
+//! locks on demand. None of this is registered outside mock mode.
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+use auxin_config::Config;
+
+use crate::clock;
+use crate::error::{AppError, AppResult, ProblemDetails, PROBLEM_JSON};
+use crate::extensions::FileLock;
+
+/// Faults applied to API requests while mock mode is active
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Faults {
+    /// Delay added before every matching request
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Status returned instead of running the handler (e.g. 500 or 503)
+    #[serde(default)]
+    pub fail_status: Option<u16>,
+    /// Number of requests to fail before the fault clears itself;
+    /// unset means fail until the faults are reset
+    #[serde(default)]
+    pub fail_count: Option<u32>,
+    /// Only requests whose path contains this string are affected
+    #[serde(default)]
+    pub path_contains: Option<String>,
+}
+
+impl Faults {
+    fn matches(&self, path: &str) -> bool {
+        self.path_contains
+            .as_deref()
+            .is_none_or(|fragment| path.contains(fragment))
+    }
+}
+
+/// Shared fault configuration, cloned into every worker
+#[derive(Debug, Clone, Default)]
+pub struct FaultState(Arc<Mutex<Faults>>);
+
+impl FaultState {
+    pub fn get(&self) -> Faults {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, faults: Faults) {
+        *self.0.lock().unwrap() = faults;
+    }
+
+    /// Decide what to do with a request: (delay, status to fail with)
+    fn apply(&self, path: &str) -> (u64, Option<StatusCode>) {
+        let mut faults = self.0.lock().unwrap();
+        if !faults.matches(path) {
+            return (0, None);
+        }
+
+        let status = faults
+            .fail_status
+            .and_then(|code| StatusCode::from_u16(code).ok());
+        if status.is_some() {
+            if let Some(remaining) = faults.fail_count.as_mut() {
+                *remaining = remaining.saturating_sub(1);
+                if *remaining == 0 {
+                    faults.fail_status = None;
+                    faults.fail_count = None;
+                }
+            }
+        }
+
+        (faults.latency_ms, status)
+    }
+}
+
+/// Middleware applying the configured [`Faults`] to everything outside `/mock`
+pub async fn fault_injection_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let state = req.app_data::<web::Data<FaultState>>().cloned();
+    let (latency_ms, fail_status) = match state {
+        Some(state) if !req.path().starts_with("/mock") => state.apply(req.path()),
+        _ => (0, None),
+    };
+
+    if latency_ms > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(latency_ms)).await;
+    }
+
+    if let Some(status) = fail_status {
+        info!("Injecting {} for {}", status.as_u16(), req.path());
+        let response = HttpResponse::build(status)
+            .content_type(PROBLEM_JSON)
+            .json(injected_problem(status));
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    Ok(next.call(req).await?.map_into_left_body())
+}
+
+fn injected_problem(status: StatusCode) -> ProblemDetails {
+    let title = status.canonical_reason().unwrap_or("Error").to_string();
+    ProblemDetails {
+        problem_type: "urn:auxin:problem:injected-fault".to_string(),
+        title: title.clone(),
+        status: status.as_u16(),
+        detail: "Injected by mock mode".to_string(),
+        code: "INJECTED_FAULT".to_string(),
+        error: format!("{}: Injected by mock mode", title),
+    }
+}
+
+/// Register the `/mock` control endpoints
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/mock")
+            .route("/faults", web::get().to(get_faults))
+            .route("/faults", web::put().to(set_faults))
+            .route("/faults", web::delete().to(reset_faults))
+            .route("/clock", web::get().to(get_clock))
+            .route("/clock", web::post().to(set_clock))
+            .route("/clock", web::delete().to(reset_clock))
+            .route(
+                "/repos/{namespace}/{name}/locks/expire",
+                web::post().to(expire_lock),
+            ),
+    );
+}
+
+async fn get_faults(state: web::Data<FaultState>) -> HttpResponse {
+    HttpResponse::Ok().json(state.get())
+}
+
+async fn set_faults(state: web::Data<FaultState>, body: web::Json<Faults>) -> AppResult<HttpResponse> {
+    let faults = body.into_inner();
+    if let Some(code) = faults.fail_status {
+        if !(400..=599).contains(&code) {
+            return Err(AppError::BadRequest(format!(
+                "fail_status must be an error status (400-599), got {}",
+                code
+            )));
+        }
+    }
+
+    info!("Mock faults set: {:?}", faults);
+    state.set(faults.clone());
+    Ok(HttpResponse::Ok().json(faults))
+}
+
+async fn reset_faults(state: web::Data<FaultState>) -> HttpResponse {
+    state.set(Faults::default());
+    HttpResponse::Ok().json(Faults::default())
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClockResponse {
+    pub now: DateTime<Utc>,
+    pub frozen: bool,
+}
+
+/// Clock adjustment; `freeze_at` is applied before `advance_secs`
+#[derive(Debug, Deserialize)]
+pub struct ClockRequest {
+    pub freeze_at: Option<DateTime<Utc>>,
+    pub advance_secs: Option<i64>,
+}
+
+fn mock_clock() -> AppResult<&'static clock::MockClock> {
+    clock::mock().ok_or_else(|| AppError::Internal("Mock clock is not installed".to_string()))
+}
+
+fn clock_response(clock: &clock::MockClock) -> HttpResponse {
+    HttpResponse::Ok().json(ClockResponse {
+        now: clock.now(),
+        frozen: clock.is_frozen(),
+    })
+}
+
+async fn get_clock() -> AppResult<HttpResponse> {
+    Ok(clock_response(mock_clock()?))
+}
+
+async fn set_clock(body: web::Json<ClockRequest>) -> AppResult<HttpResponse> {
+    let clock = mock_clock()?;
+
+    if let Some(at) = body.freeze_at {
+        clock.freeze(at);
+    }
+    if let Some(secs) = body.advance_secs {
+        clock.advance(Duration::seconds(secs));
+    }
+
+    info!("Mock clock now {}", clock.now());
+    Ok(clock_response(clock))
+}
+
+async fn reset_clock() -> AppResult<HttpResponse> {
+    let clock = mock_clock()?;
+    clock.reset();
+    Ok(clock_response(clock))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExpireLockResponse {
+    pub expired: bool,
+    pub lock: Option<FileLock>,
+}
+
+/// Expire the repository's lock immediately so another client can take it
+async fn expire_lock(
+    config: web::Data<Config>,
+    path: web::Path<(String, String)>,
+) -> AppResult<HttpResponse> {
+    let (namespace, repo_name) = path.into_inner();
+    let repo_path = PathBuf::from(&config.server.sync_dir)
+        .join(&namespace)
+        .join(&repo_name);
+
+    if !repo_path.exists() {
+        return Err(AppError::NotFound(format!(
+            "Repository {}/{} not found",
+            namespace, repo_name
+        )));
+    }
+
+    let lock = FileLock::force_expire(&repo_path)
+        .map_err(|e| AppError::Internal(format!("Failed to expire lock: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(ExpireLockResponse {
+        expired: lock.is_some(),
+        lock,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fail_count_clears_fault() {
+        let state = FaultState::default();
+        state.set(Faults {
+            fail_status: Some(500),
+            fail_count: Some(2),
+            ..Default::default()
+        });
+
+        assert_eq!(state.apply("/api/v1/repos").1, Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(state.apply("/api/v1/repos").1, Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(state.apply("/api/v1/repos").1, None);
+        assert_eq!(state.get(), Faults::default());
+    }
+
+    #[test]
+    fn test_path_filter() {
+        let state = FaultState::default();
+        state.set(Faults {
+            latency_ms: 50,
+            fail_status: Some(503),
+            path_contains: Some("/locks/".to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(state.apply("/api/v1/repos/a/b/status"), (0, None));
+        assert_eq!(
+            state.apply("/api/v1/repos/a/b/locks/acquire"),
+            (50, Some(StatusCode::SERVICE_UNAVAILABLE))
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
 use crate::error::{AppError, AppResult};
-use crate::extensions::{FileLock, LogicProMetadata};
+use crate::extensions::{FileLock, LockSnapshot, LogicProMetadata};
 
 // Import auxin-oxen subprocess module
 use auxin_oxen::{OxenSubprocess, CommitInfo as OxenCommitInfo};
@@ -96,18 +96,28 @@ impl RepositoryOps {
 
     /// Get commit history
     pub fn log(&self, limit: Option<usize>) -> AppResult<Vec<CommitInfo>> {
-        let commits = self
-            .oxen
-            .log(&self.repo_path, limit)
-            .map_err(|e| AppError::Internal(format!("Failed to get commit history: {}", e)))?;
+        self.log_branch(None, limit)
+    }
+
+    /// Commit history of `branch`, or of HEAD if none is given
+    pub fn log_branch(
+        &self,
+        branch: Option<&str>,
+        limit: Option<usize>,
+    ) -> AppResult<Vec<CommitInfo>> {
+        let commits = match branch {
+            Some(branch) => self.oxen.log_branch(&self.repo_path, branch, limit),
+            None => self.oxen.log(&self.repo_path, limit),
+        }
+        .map_err(|e| AppError::Internal(format!("Failed to get commit history: {}", e)))?;
 
         let result: Vec<CommitInfo> = commits
             .into_iter()
             .map(|c| CommitInfo {
                 id: c.id,
                 message: c.message,
-                author: "unknown".to_string(), // Oxen subprocess doesn't provide author yet
-                timestamp: chrono::Utc::now().to_rfc3339(), // Placeholder timestamp
+                author: c.author.unwrap_or_else(|| "unknown".to_string()),
+                timestamp: c.timestamp.map(|t| t.to_rfc3339()).unwrap_or_default(),
             })
             .collect();
 
@@ -276,6 +286,33 @@ impl RepositoryOps {
         Ok(Some(metadata))
     }
 
+    /// List commit IDs that have stored metadata
+    pub fn list_metadata(&self) -> AppResult<Vec<String>> {
+        let metadata_dir = self.repo_path.join(".oxen").join("metadata");
+
+        if !metadata_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = std::fs::read_dir(&metadata_dir)
+            .map_err(|e| AppError::Internal(format!("Failed to read metadata directory: {}", e)))?;
+
+        let mut commit_ids: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                    path.file_stem().map(|s| s.to_string_lossy().to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        commit_ids.sort();
+        Ok(commit_ids)
+    }
+
     /// Acquire lock for this repository
     pub fn acquire_lock(
         &self,
@@ -284,33 +321,37 @@ impl RepositoryOps {
         timeout_hours: u64,
     ) -> AppResult<FileLock> {
         FileLock::acquire(&self.repo_path, user, machine_id, timeout_hours).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::AlreadyExists {
-                AppError::Conflict(e.to_string())
-            } else {
-                AppError::Internal(format!("Failed to acquire lock: {}", e))
+            match e.kind() {
+                std::io::ErrorKind::AlreadyExists => AppError::LockHeld(e.to_string()),
+                std::io::ErrorKind::WouldBlock => AppError::Conflict(e.to_string()),
+                _ => AppError::Internal(format!("Failed to acquire lock: {}", e)),
             }
         })
     }
 
     /// Release lock for this repository
-    pub fn release_lock(&self, lock_id: &str) -> AppResult<()> {
-        FileLock::release(&self.repo_path, lock_id).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::PermissionDenied {
-                AppError::Unauthorized(e.to_string())
-            } else {
-                AppError::Internal(format!("Failed to release lock: {}", e))
+    pub fn release_lock(&self, lock_id: &str, fencing_token: Option<u64>) -> AppResult<()> {
+        FileLock::release(&self.repo_path, lock_id, fencing_token).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => AppError::Unauthorized(e.to_string()),
+            std::io::ErrorKind::InvalidInput | std::io::ErrorKind::WouldBlock => {
+                AppError::Conflict(e.to_string())
             }
+            _ => AppError::Internal(format!("Failed to release lock: {}", e)),
         })
     }
 
     /// Update lock heartbeat
-    pub fn heartbeat_lock(&self, lock_id: &str) -> AppResult<FileLock> {
-        FileLock::heartbeat(&self.repo_path, lock_id).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::PermissionDenied {
-                AppError::Unauthorized(e.to_string())
-            } else {
-                AppError::Internal(format!("Failed to update heartbeat: {}", e))
+    pub fn heartbeat_lock(
+        &self,
+        lock_id: &str,
+        fencing_token: Option<u64>,
+    ) -> AppResult<FileLock> {
+        FileLock::heartbeat(&self.repo_path, lock_id, fencing_token).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => AppError::Unauthorized(e.to_string()),
+            std::io::ErrorKind::InvalidInput | std::io::ErrorKind::WouldBlock => {
+                AppError::Conflict(e.to_string())
             }
+            _ => AppError::Internal(format!("Failed to update heartbeat: {}", e)),
         })
     }
 
@@ -319,6 +360,24 @@ impl RepositoryOps {
         FileLock::status(&self.repo_path)
             .map_err(|e| AppError::Internal(format!("Failed to get lock status: {}", e)))
     }
+
+    /// Remove the lock if it has expired, returning it
+    pub fn take_expired_lock(&self) -> AppResult<Option<FileLock>> {
+        FileLock::take_expired(&self.repo_path)
+            .map_err(|e| AppError::Internal(format!("Failed to clear expired lock: {}", e)))
+    }
+
+    /// Read the lock and fencing counter for a backup
+    pub fn lock_snapshot(&self) -> AppResult<LockSnapshot> {
+        FileLock::snapshot(&self.repo_path)
+            .map_err(|e| AppError::Internal(format!("Failed to read lock: {}", e)))
+    }
+
+    /// Restore the lock and fencing counter from a backup
+    pub fn restore_lock_snapshot(&self, snapshot: &LockSnapshot) -> AppResult<()> {
+        FileLock::restore_snapshot(&self.repo_path, snapshot)
+            .map_err(|e| AppError::Internal(format!("Failed to restore lock: {}", e)))
+    }
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -0,0 +1,115 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest};
+use std::future::{ready, Ready};
+use tracing::Instrument;
+
+/// Header carrying the correlation ID between CLI and server
+pub const REQUEST_ID_HEADER: &str = "x-auxin-request-id";
+
+/// Longest client-supplied ID accepted; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// Correlation ID for the current request
+///
+/// Taken from the `X-Auxin-Request-Id` header when the client sent a valid
+/// one, otherwise generated here. Handlers can extract it to record it in
+/// the activity log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Use the client's ID if it is safe to log, otherwise make a new one
+    pub fn from_header(value: Option<&str>) -> Self {
+        match value {
+            Some(id) if is_valid(id) => Self(id.to_string()),
+            _ => Self::generate(),
+        }
+    }
+
+    pub fn generate() -> Self {
+        let uuid = uuid::Uuid::new_v4().simple().to_string();
+        Self(uuid[..12].to_string())
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl FromRequest for RequestId {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        // Always set by the middleware; fall back for handlers tested without it
+        let id = req
+            .extensions()
+            .get::<RequestId>()
+            .cloned()
+            .unwrap_or_else(RequestId::generate);
+        ready(Ok(id))
+    }
+}
+
+/// Middleware that assigns a request ID, adds it to the tracing span, and
+/// echoes it in the response headers
+pub async fn request_id_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = RequestId::from_header(
+        req.headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok()),
+    );
+    req.extensions_mut().insert(request_id.clone());
+
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let mut res = next.call(req).instrument(span).await?;
+
+    if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+        res.headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_id_is_kept() {
+        let id = RequestId::from_header(Some("cli-3f2a9c"));
+        assert_eq!(id.as_str(), "cli-3f2a9c");
+    }
+
+    #[test]
+    fn test_invalid_client_id_is_replaced() {
+        for bad in ["", "has space", "new\nline", &"x".repeat(65)] {
+            let id = RequestId::from_header(Some(bad));
+            assert_ne!(id.as_str(), bad);
+            assert_eq!(id.as_str().len(), 12);
+        }
+
+        assert_eq!(RequestId::from_header(None).as_str().len(), 12);
+    }
+}
@@ -1,14 +1,14 @@
 use actix_web::{web, HttpRequest, HttpResponse};
-use actix_ws::Message;
+use actix_ws::{CloseCode, CloseReason, Message};
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, watch, RwLock};
 use tracing::{error, info};
 
 use crate::error::AppResult;
-use crate::extensions::ActivityType;
+use crate::extensions::{ActivityType, WaitingUser};
 
 /// WebSocket message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +30,22 @@ pub enum WsMessage {
     LockReleased {
         lock_id: String,
     },
+    /// The lock runs out soon; its holder should save and release or
+    /// re-acquire it
+    LockExpiring {
+        user: String,
+        lock_id: String,
+        expires_at: String,
+    },
+    /// The lock ran out and was cleared by the server
+    LockExpired {
+        user: String,
+        lock_id: String,
+    },
+    /// The lock is free; `waiting` are the users who were refused it
+    LockAvailable {
+        waiting: Vec<String>,
+    },
     /// New commit
     Commit {
         commit_id: String,
@@ -41,6 +57,10 @@ pub enum WsMessage {
         branch_name: String,
         user: String,
     },
+    /// Server is shutting down; reconnect after the given delay
+    ServerShutdown {
+        reconnect_after_secs: u64,
+    },
     /// Ping/Pong for keepalive
     Ping,
     Pong,
@@ -54,6 +74,8 @@ type RepoChannel = broadcast::Sender<String>;
 pub struct WsHub {
     /// Map of repository key (namespace/name) to broadcast channel
     channels: Arc<RwLock<HashMap<String, RepoChannel>>>,
+    /// Set to the reconnect delay once shutdown begins
+    shutdown: Arc<watch::Sender<Option<u64>>>,
 }
 
 impl Default for WsHub {
@@ -66,9 +88,22 @@ impl WsHub {
     pub fn new() -> Self {
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
+            shutdown: Arc::new(watch::Sender::new(None)),
         }
     }
 
+    /// Tell every connected client to disconnect and reconnect later
+    ///
+    /// Each session sends a `ServerShutdown` message and closes with
+    /// "service restart" (1012); new connections are refused from now on.
+    pub fn begin_shutdown(&self, reconnect_after_secs: u64) {
+        self.shutdown.send_replace(Some(reconnect_after_secs));
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.borrow().is_some()
+    }
+
     /// Get or create a broadcast channel for a repository
     async fn get_or_create_channel(&self, repo_key: &str) -> RepoChannel {
         let mut channels = self.channels.write().await;
@@ -160,6 +195,64 @@ impl WsHub {
         self.broadcast(&repo_key, ws_message).await
     }
 
+    /// Warn the holder that their lock is about to expire
+    pub async fn broadcast_lock_expiring(
+        &self,
+        namespace: &str,
+        repo_name: &str,
+        user: &str,
+        lock_id: &str,
+        expires_at: &str,
+    ) -> AppResult<()> {
+        let repo_key = format!("{}/{}", namespace, repo_name);
+
+        let ws_message = WsMessage::LockExpiring {
+            user: user.to_string(),
+            lock_id: lock_id.to_string(),
+            expires_at: expires_at.to_string(),
+        };
+
+        self.broadcast(&repo_key, ws_message).await
+    }
+
+    /// Broadcast that a lock expired and was cleared
+    pub async fn broadcast_lock_expired(
+        &self,
+        namespace: &str,
+        repo_name: &str,
+        user: &str,
+        lock_id: &str,
+    ) -> AppResult<()> {
+        let repo_key = format!("{}/{}", namespace, repo_name);
+
+        let ws_message = WsMessage::LockExpired {
+            user: user.to_string(),
+            lock_id: lock_id.to_string(),
+        };
+
+        self.broadcast(&repo_key, ws_message).await
+    }
+
+    /// Tell the users waiting for a lock that it's free; nothing is sent if
+    /// no one was waiting
+    pub async fn broadcast_lock_available(
+        &self,
+        namespace: &str,
+        repo_name: &str,
+        waiting: &[WaitingUser],
+    ) -> AppResult<()> {
+        if waiting.is_empty() {
+            return Ok(());
+        }
+        let repo_key = format!("{}/{}", namespace, repo_name);
+
+        let ws_message = WsMessage::LockAvailable {
+            waiting: waiting.iter().map(|w| w.user.clone()).collect(),
+        };
+
+        self.broadcast(&repo_key, ws_message).await
+    }
+
     /// Broadcast commit event
     pub async fn broadcast_commit(
         &self,
@@ -193,15 +286,21 @@ pub async fn ws_handler(
 
     info!("WebSocket connection request for: {}", repo_key);
 
+    if hub.is_shutting_down() {
+        return Ok(HttpResponse::ServiceUnavailable().body("Server is shutting down"));
+    }
+
     // Upgrade to WebSocket
     let (response, mut session, mut msg_stream) = actix_ws::handle(&req, stream)?;
 
     // Subscribe to repository channel
     let mut receiver = hub.subscribe(&repo_key).await;
+    let mut shutdown = hub.shutdown.subscribe();
 
     // Spawn task to handle the WebSocket connection
     actix_rt::spawn(async move {
         info!("WebSocket connected for: {}", repo_key);
+        let mut close_reason = None;
 
         loop {
             tokio::select! {
@@ -236,11 +335,26 @@ pub async fn ws_handler(
                         break;
                     }
                 }
+                // Server shutdown: send a reconnect hint, then close
+                Ok(()) = shutdown.changed() => {
+                    let Some(reconnect_after_secs) = *shutdown.borrow_and_update() else {
+                        continue;
+                    };
+                    let notice = WsMessage::ServerShutdown { reconnect_after_secs };
+                    if let Ok(json) = serde_json::to_string(&notice) {
+                        let _ = session.text(json).await;
+                    }
+                    close_reason = Some(CloseReason {
+                        code: CloseCode::Restart,
+                        description: Some("Server restarting".to_string()),
+                    });
+                    break;
+                }
             }
         }
 
         info!("WebSocket disconnected for: {}", repo_key);
-        let _ = session.close(None).await;
+        let _ = session.close(close_reason).await;
     });
 
     Ok(response)
@@ -311,6 +425,26 @@ mod tests {
         assert!(received.contains("user"));
     }
 
+    #[tokio::test]
+    async fn test_begin_shutdown_notifies_sessions() {
+        let hub = WsHub::new();
+        let mut shutdown = hub.shutdown.subscribe();
+        assert!(!hub.is_shutting_down());
+
+        hub.begin_shutdown(5);
+
+        shutdown.changed().await.unwrap();
+        assert_eq!(*shutdown.borrow(), Some(5));
+        assert!(hub.is_shutting_down());
+
+        let json = serde_json::to_string(&WsMessage::ServerShutdown {
+            reconnect_after_secs: 5,
+        })
+        .unwrap();
+        assert!(json.contains("ServerShutdown"));
+        assert!(json.contains("\"reconnect_after_secs\":5"));
+    }
+
     #[tokio::test]
     async fn test_multiple_subscribers() {
         let hub = WsHub::new();
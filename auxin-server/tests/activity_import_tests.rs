@@ -0,0 +1,143 @@
+// Integration tests for importing activity (used by `auxin migrate`)
+
+use actix_web::{test, web, App};
+use auxin_server::api;
+use auxin_server::project::ProjectMetadata;
+use auxin_server::websocket::WsHub;
+use serde_json::json;
+use tempfile::TempDir;
+
+mod common;
+use common::setup_repo;
+
+#[actix_web::test]
+async fn test_import_keeps_history_and_rejects_duplicates() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(WsHub::new()))
+            .route(
+                "/api/repos/{namespace}/{name}/activity",
+                web::get().to(api::get_activity),
+            )
+            .route(
+                "/api/repos/{namespace}/{name}/activity/import",
+                web::post().to(api::import_activity),
+            ),
+    )
+    .await;
+
+    let body = json!({
+        "activities": [
+            {
+                "id": "lock-1",
+                "activity_type": "lock_acquired",
+                "user": "alice",
+                "message": "Lock acquired",
+                "timestamp": "2024-03-01T09:00:00Z"
+            },
+            {
+                "id": "comment-1",
+                "activity_type": "comment",
+                "user": "bob",
+                "message": "Love the chorus",
+                "timestamp": "2024-03-02T10:30:00Z",
+                "metadata": {"commit_id": "abc123"}
+            }
+        ]
+    });
+    let import = || {
+        test::TestRequest::post()
+            .uri("/api/repos/studio/album/activity/import")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(&body)
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, import()).await;
+    assert_eq!(resp.status(), 200);
+    let result: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(result, json!({"imported": 2}));
+
+    // Re-running the import is refused rather than duplicating entries
+    assert_eq!(test::call_service(&app, import()).await.status(), 409);
+
+    let req = test::TestRequest::get()
+        .uri("/api/repos/studio/album/activity")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let activities: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(activities.len(), 2);
+    assert_eq!(activities[0]["id"], "comment-1");
+    assert_eq!(activities[0]["timestamp"], "2024-03-02T10:30:00Z");
+    assert_eq!(activities[1]["user"], "alice");
+}
+
+#[actix_web::test]
+async fn test_import_requires_authentication() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, _) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/activity/import",
+                web::post().to(api::import_activity),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/repos/studio/album/activity/import")
+        .set_json(json!({"activities": []}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+}
+
+#[actix_web::test]
+async fn test_import_requires_owner() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, _) = setup_repo(&temp_dir);
+
+    // A collaborator can write but not import entries for other users
+    let writer = auth_service
+        .register("writer", "writer@example.com", "password123", None)
+        .unwrap();
+    let token = auth_service
+        .generate_token(&writer.id, &writer.username)
+        .unwrap();
+    let repo_path = temp_dir.path().join("studio").join("album");
+    let mut metadata = ProjectMetadata::load(&repo_path).unwrap();
+    metadata.add_collaborator(writer.id).unwrap();
+    metadata.save(&repo_path).unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/activity/import",
+                web::post().to(api::import_activity),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/repos/studio/album/activity/import")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(json!({"activities": [{
+            "id": "lock-1",
+            "activity_type": "lock_acquired",
+            "user": "alice",
+            "message": "Lock acquired",
+            "timestamp": "2024-03-01T09:00:00Z"
+        }]}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+}
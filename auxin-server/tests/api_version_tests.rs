@@ -0,0 +1,37 @@
+// Integration tests for the API version handshake
+
+use actix_web::{test, web, App};
+use auxin_server::api::{self, ApiVersionInfo, HealthResponse};
+
+#[actix_web::test]
+async fn test_version_reports_supported_range() {
+    let app = test::init_service(
+        App::new().route("/api/version", web::get().to(api::get_version)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/version").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let info: ApiVersionInfo = test::read_body_json(resp).await;
+    assert_eq!(info.api_version, api::API_VERSION);
+    assert_eq!(info.min_api_version, api::MIN_API_VERSION);
+    assert!(info.min_api_version <= info.api_version);
+    assert!(!info.server_version.is_empty());
+}
+
+#[actix_web::test]
+async fn test_health_reports_versions() {
+    let app =
+        test::init_service(App::new().route("/health", web::get().to(api::health_check))).await;
+
+    let req = test::TestRequest::get().uri("/health").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let health: HealthResponse = test::read_body_json(resp).await;
+    assert_eq!(health.status, "ok");
+    assert_eq!(health.version.api_version, api::API_VERSION);
+    assert_eq!(health.version.server_version, env!("CARGO_PKG_VERSION"));
+}
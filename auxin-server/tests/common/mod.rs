@@ -0,0 +1,35 @@
+// Fixtures shared by the integration tests
+
+use auxin_config::Config;
+use auxin_server::auth::AuthService;
+use auxin_server::project::{ProjectMetadata, Visibility};
+use auxin_server::repo::RepositoryOps;
+use tempfile::TempDir;
+
+pub fn test_config(temp_dir: &TempDir) -> Config {
+    let mut config = Config::default();
+    config.server.sync_dir = temp_dir.path().to_string_lossy().to_string();
+    config.server.auth_token_secret = "test_secret".to_string();
+    config
+}
+
+/// Create a repository owned by a fresh user, returning (config, auth, token)
+pub fn setup_repo(temp_dir: &TempDir) -> (Config, AuthService, String) {
+    let config = test_config(temp_dir);
+    let auth_service = AuthService::new(config.clone());
+
+    let user = auth_service
+        .register("producer", "producer@example.com", "password123", None)
+        .unwrap();
+    let token = auth_service
+        .generate_token(&user.id, &user.username)
+        .unwrap();
+
+    let repo_path = temp_dir.path().join("studio").join("album");
+    RepositoryOps::init(&repo_path).unwrap();
+    ProjectMetadata::new(user.id, user.username, Visibility::Private)
+        .save(&repo_path)
+        .unwrap();
+
+    (config, auth_service, token)
+}
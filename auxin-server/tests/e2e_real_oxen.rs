@@ -131,7 +131,7 @@ fn test_lock_contention_real_oxen() {
     );
 
     // User A releases lock
-    repo.release_lock(&lock_a.lock_id).unwrap();
+    repo.release_lock(&lock_a.lock_id, Some(lock_a.fencing_token)).unwrap();
 
     // Verify lock is released
     let status_after = repo.lock_status().unwrap();
@@ -0,0 +1,247 @@
+// Integration tests for the bulk metadata and search endpoints
+
+use actix_web::{test, web, App};
+use auxin_server::api;
+use auxin_server::extensions::LogicProMetadata;
+use auxin_server::repo::RepositoryOps;
+use serde_json::json;
+use tempfile::TempDir;
+
+mod common;
+use common::setup_repo;
+
+#[actix_web::test]
+async fn test_metadata_batch_stores_all_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/metadata/batch",
+                web::post().to(api::store_metadata_batch),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/repos/studio/album/metadata/batch")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(json!({
+            "entries": [
+                {"commit_id": "aaa111", "metadata": {"bpm": 120.0, "sample_rate": 48000, "key_signature": null, "tags": []}},
+                {"commit_id": "bbb222", "metadata": {"bpm": 128.0, "sample_rate": null, "key_signature": "A Minor", "tags": ["mixing"]}}
+            ]
+        }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["stored"].as_array().unwrap().len(), 2);
+    assert!(body["failed"].as_array().unwrap().is_empty());
+
+    let repo = RepositoryOps::open(temp_dir.path().join("studio/album")).unwrap();
+    let stored = repo.get_metadata("bbb222").unwrap().unwrap();
+    assert_eq!(stored.key_signature.as_deref(), Some("A Minor"));
+    assert_eq!(repo.list_metadata().unwrap(), vec!["aaa111", "bbb222"]);
+}
+
+#[actix_web::test]
+async fn test_list_metadata_returns_commit_ids() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+
+    let repo = RepositoryOps::open(temp_dir.path().join("studio/album")).unwrap();
+    repo.store_metadata("c2", &LogicProMetadata::new()).unwrap();
+    repo.store_metadata("c1", &LogicProMetadata::new()).unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/metadata",
+                web::get().to(api::list_metadata),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/repos/studio/album/metadata")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let body: Vec<String> = test::read_body_json(resp).await;
+    assert_eq!(body, vec!["c1", "c2"]);
+}
+
+#[actix_web::test]
+async fn test_metadata_batch_reports_partial_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/metadata/batch",
+                web::post().to(api::store_metadata_batch),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/repos/studio/album/metadata/batch")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(json!({
+            "entries": [
+                {"commit_id": "good", "metadata": {"bpm": 90.0, "sample_rate": null, "key_signature": null, "tags": []}},
+                {"commit_id": "../escape", "metadata": {"bpm": 90.0, "sample_rate": null, "key_signature": null, "tags": []}}
+            ]
+        }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 207);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["stored"], json!(["good"]));
+    assert_eq!(body["failed"][0]["commit_id"], "../escape");
+}
+
+#[actix_web::test]
+async fn test_metadata_batch_rejects_oversized_batch() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/metadata/batch",
+                web::post().to(api::store_metadata_batch),
+            ),
+    )
+    .await;
+
+    let entries: Vec<_> = (0..=api::MAX_METADATA_BATCH_SIZE)
+        .map(|i| {
+            json!({
+                "commit_id": format!("commit{}", i),
+                "metadata": {"bpm": null, "sample_rate": null, "key_signature": null, "tags": []}
+            })
+        })
+        .collect();
+
+    let req = test::TestRequest::post()
+        .uri("/api/repos/studio/album/metadata/batch")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(json!({ "entries": entries }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 413);
+
+    let problem: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(problem["code"], "QUOTA_EXCEEDED");
+}
+
+#[actix_web::test]
+async fn test_metadata_batch_requires_auth() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, _token) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/metadata/batch",
+                web::post().to(api::store_metadata_batch),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/repos/studio/album/metadata/batch")
+        .set_json(json!({ "entries": [] }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+}
+
+#[actix_web::test]
+async fn test_search_metadata_filters_by_query() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+
+    let repo = RepositoryOps::open(temp_dir.path().join("studio/album")).unwrap();
+    repo.store_metadata("slow", &LogicProMetadata::new().with_bpm(90.0)).unwrap();
+    repo.store_metadata(
+        "mix",
+        &LogicProMetadata::new()
+            .with_bpm(128.0)
+            .with_tags(vec!["mixing".to_string()]),
+    )
+    .unwrap();
+    repo.store_metadata("fast", &LogicProMetadata::new().with_bpm(132.0)).unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/search",
+                web::get().to(api::search_metadata),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/repos/studio/album/search?q=bpm:120-140+tag:mixing")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    let hits = body.as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["commit_id"], "mix");
+    assert_eq!(hits[0]["metadata"]["bpm"], 128.0);
+}
+
+#[actix_web::test]
+async fn test_search_metadata_requires_read_access() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, _token) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/search",
+                web::get().to(api::search_metadata),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/repos/studio/album/search?q=bpm:120")
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+}
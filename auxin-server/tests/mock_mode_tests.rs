@@ -0,0 +1,109 @@
+// Integration tests for `auxin-server --mock` fault injection
+
+use actix_web::{middleware, test, web, App};
+use auxin_server::api;
+use auxin_server::mock_mode::{self, fault_injection_middleware, FaultState};
+use auxin_server::websocket::WsHub;
+use serde_json::json;
+use tempfile::TempDir;
+
+mod common;
+use common::setup_repo;
+
+macro_rules! mock_app {
+    ($config:expr, $auth:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new($config))
+                .app_data(web::Data::new($auth))
+                .app_data(web::Data::new(WsHub::new()))
+                .app_data(web::Data::new(FaultState::default()))
+                .wrap(middleware::from_fn(fault_injection_middleware))
+                .configure(mock_mode::configure)
+                .route(
+                    "/api/repos/{namespace}/{name}/locks/acquire",
+                    web::post().to(api::acquire_lock),
+                )
+                .route(
+                    "/api/repos/{namespace}/{name}/locks/status",
+                    web::get().to(api::lock_status),
+                ),
+        )
+        .await
+    };
+}
+
+#[actix_web::test]
+async fn test_injected_failures_clear_after_count() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+    let app = mock_app!(config, auth_service);
+
+    let req = test::TestRequest::put()
+        .uri("/mock/faults")
+        .set_json(json!({"fail_status": 500, "fail_count": 1, "path_contains": "/locks/"}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let status_request = || {
+        test::TestRequest::get()
+            .uri("/api/repos/studio/album/locks/status")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, status_request()).await;
+    assert_eq!(resp.status(), 500);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "INJECTED_FAULT");
+
+    // The single injected failure is used up
+    let resp = test::call_service(&app, status_request()).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_rejects_non_error_fail_status() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, _) = setup_repo(&temp_dir);
+    let app = mock_app!(config, auth_service);
+
+    let req = test::TestRequest::put()
+        .uri("/mock/faults")
+        .set_json(json!({"fail_status": 200}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+}
+
+#[actix_web::test]
+async fn test_expire_lock_lets_another_client_take_it() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+    let app = mock_app!(config, auth_service);
+
+    let acquire = |machine: &str| {
+        test::TestRequest::post()
+            .uri("/api/repos/studio/album/locks/acquire")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(json!({"user": "producer", "machine_id": machine, "timeout_hours": 8}))
+            .to_request()
+    };
+
+    assert_eq!(test::call_service(&app, acquire("mac-a")).await.status(), 200);
+    assert_eq!(test::call_service(&app, acquire("mac-b")).await.status(), 409);
+
+    let req = test::TestRequest::post()
+        .uri("/mock/repos/studio/album/locks/expire")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["expired"], true);
+    assert_eq!(body["lock"]["machine_id"], "mac-a");
+
+    assert_eq!(test::call_service(&app, acquire("mac-b")).await.status(), 200);
+}
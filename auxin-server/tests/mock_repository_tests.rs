@@ -188,7 +188,7 @@ fn test_auxin_locks_work_in_mock() {
     assert!(status.unwrap().is_some());
 
     // Release lock
-    let release_result = repo.release_lock(&lock.lock_id);
+    let release_result = repo.release_lock(&lock.lock_id, Some(lock.fencing_token));
     assert!(release_result.is_ok());
 
     // Verify lock is released
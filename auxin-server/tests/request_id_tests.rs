@@ -0,0 +1,61 @@
+// Integration tests for request correlation IDs
+
+use actix_web::{middleware, test, web, App};
+use auxin_server::api;
+use auxin_server::request_id::{request_id_middleware, REQUEST_ID_HEADER};
+use auxin_server::websocket::WsHub;
+use serde_json::json;
+use tempfile::TempDir;
+
+mod common;
+use common::setup_repo;
+
+#[actix_web::test]
+async fn test_request_id_echoed_and_recorded_in_activity() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, token) = setup_repo(&temp_dir);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(WsHub::new()))
+            .wrap(middleware::from_fn(request_id_middleware))
+            .route(
+                "/api/repos/{namespace}/{name}/locks/acquire",
+                web::post().to(api::acquire_lock),
+            )
+            .route(
+                "/api/repos/{namespace}/{name}/activity",
+                web::get().to(api::get_activity),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/repos/studio/album/locks/acquire")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header((REQUEST_ID_HEADER, "cli-abc123"))
+        .set_json(json!({"user": "producer", "machine_id": "mac", "timeout_hours": 1}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get(REQUEST_ID_HEADER).unwrap(), "cli-abc123");
+
+    let req = test::TestRequest::get()
+        .uri("/api/repos/studio/album/activity?request_id=cli-abc123")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    // The activity query got its own, server-generated ID
+    let generated = resp.headers().get(REQUEST_ID_HEADER).unwrap();
+    assert_ne!(generated, "cli-abc123");
+
+    let activities: serde_json::Value = test::read_body_json(resp).await;
+    let activities = activities.as_array().unwrap();
+    assert_eq!(activities.len(), 1);
+    assert_eq!(activities[0]["activity_type"], "lock_acquired");
+    assert_eq!(activities[0]["request_id"], "cli-abc123");
+}
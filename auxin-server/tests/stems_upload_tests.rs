@@ -0,0 +1,71 @@
+// Integration tests for the stem upload checks
+
+use actix_web::{test, web, App};
+use auxin_server::api;
+use auxin_server::repo_access::RepoAccessService;
+use tempfile::TempDir;
+
+mod common;
+use common::setup_repo;
+
+/// A multipart request with only a manifest field
+fn upload(uri: &str, token: &str) -> test::TestRequest {
+    let body = "--stems\r\n\
+        Content-Disposition: form-data; name=\"manifest\"\r\n\r\n\
+        {\"commit_id\":\"abc123\",\"stems\":[],\"added_by\":\"\",\
+        \"updated_at\":\"2024-03-01T09:00:00Z\"}\r\n\
+        --stems--\r\n";
+    test::TestRequest::post()
+        .uri(uri)
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header(("Content-Type", "multipart/form-data; boundary=stems"))
+        .set_payload(body)
+}
+
+#[actix_web::test]
+async fn test_upload_needs_write_access_and_an_existing_repo() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, auth_service, owner_token) = setup_repo(&temp_dir);
+
+    // Another producer, not a collaborator on studio/album
+    let other = auth_service
+        .register("other", "other@example.com", "password123", None)
+        .unwrap();
+    let other_token = auth_service
+        .generate_token(&other.id, &other.username)
+        .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(RepoAccessService::new(config.clone())))
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(auth_service))
+            .route(
+                "/api/repos/{namespace}/{name}/stems/{commit_id}",
+                web::post().to(api::upload_stems),
+            ),
+    )
+    .await;
+
+    let resp = test::call_service(
+        &app,
+        upload("/api/repos/studio/album/stems/abc123", &other_token).to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 403);
+
+    let resp = test::call_service(
+        &app,
+        upload("/api/repos/studio/missing/stems/abc123", &owner_token).to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 404);
+    assert!(!temp_dir.path().join("studio").join("missing").exists());
+
+    let resp = test::call_service(
+        &app,
+        upload("/api/repos/studio/album/stems/abc123", &owner_token).to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 201);
+}